@@ -1,13 +1,10 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use axiom_applications_observability::complete_development_loop::*;
 use axiom_applications_observability::types::*;
 use axiom_applications_observability::mcp::*;
 use axiom_applications_observability::tools::*;
-use axiom_applications_observability::code_generation::*;
-use axiom_applications_observability::screenshot_matrix_engine::*;
+use axiom_applications_observability::visual_regression::*;
 
 use std::sync::Arc;
-use tokio::runtime::Runtime;
 
 // Performance targets from the plan:
 // - Code Generation: < 2 seconds for complete Context+Presentation+Client trio
@@ -52,8 +49,6 @@ fn benchmark_complete_development_cycle(c: &mut Criterion) {
 }
 
 fn benchmark_code_generation_performance(c: &mut Criterion) {
-    let rt = Runtime::new().unwrap();
-    
     let mut group = c.benchmark_group("code_generation");
     group.sample_size(20);
     
@@ -147,7 +142,7 @@ fn benchmark_screenshot_matrix_performance(c: &mut Criterion) {
             || tokio::runtime::Runtime::new().unwrap(),
             |rt| {
                 rt.block_on(async {
-                    let engine = setup_benchmark_screenshot_engine().await.unwrap();
+                    let _engine = setup_benchmark_screenshot_engine().await.unwrap();
                     
                     // Mock screenshot matrix result for benchmarking
                     let result = ScreenshotMatrix {
@@ -161,25 +156,35 @@ fn benchmark_screenshot_matrix_performance(c: &mut Criterion) {
                                 scale: 3.0,
                                 color_scheme: "light".to_string(),
                                 capture_mode: "standard".to_string(),
+                                status_bar: None,
+                                runtime: None,
+                                window: Default::default(),
                             },
                             image_data: vec![0u8; 1024], // Mock image data
+                            image: None,
                             metadata: ScreenshotMetadata {
                                 timestamp: chrono::Utc::now(),
                                 device_info: DeviceInfo {
                                     model: "iPhone 15".to_string(),
                                     screen_size: ScreenSize { width: 393.0, height: 852.0 },
                                     orientation: "portrait".to_string(),
+                                    udid: None,
                                 },
                                 app_state: AppState {
                                     view_hierarchy: "NavigationView".to_string(),
                                     active_context: "MainContext".to_string(),
+                                    view_tree: None,
                                 },
+                                overrides: Default::default(),
                             },
                         }).collect(),
                         analysis: ScreenshotAnalysis {
                             total_screenshots: 8,
                             consistency_score: 0.95,
                             detected_issues: vec![],
+                            layout_issues: Vec::new(),
+                            consistency_pairs: Vec::new(),
+                            warnings: Vec::new(),
                         },
                     };
                     
@@ -212,25 +217,35 @@ fn benchmark_screenshot_matrix_performance(c: &mut Criterion) {
                                 scale: 3.0,
                                 color_scheme: "light".to_string(),
                                 capture_mode: "standard".to_string(),
+                                status_bar: None,
+                                runtime: None,
+                                window: Default::default(),
                             },
                             image_data: vec![0u8; 1024],
+                            image: None,
                             metadata: ScreenshotMetadata {
                                 timestamp: chrono::Utc::now(),
                                 device_info: DeviceInfo {
                                     model: "iPhone 15".to_string(),
                                     screen_size: ScreenSize { width: 393.0, height: 852.0 },
                                     orientation: if i % 2 == 0 { "portrait" } else { "landscape" }.to_string(),
+                                    udid: None,
                                 },
                                 app_state: AppState {
                                     view_hierarchy: "NavigationView".to_string(),
                                     active_context: "MainContext".to_string(),
+                                    view_tree: None,
                                 },
+                                overrides: Default::default(),
                             },
                         }).collect(),
                         analysis: ScreenshotAnalysis {
                             total_screenshots: 4,
                             consistency_score: 0.98,
                             detected_issues: vec![],
+                            layout_issues: Vec::new(),
+                            consistency_pairs: Vec::new(),
+                            warnings: Vec::new(),
                         },
                     };
                     black_box(result)
@@ -274,16 +289,21 @@ fn benchmark_metadata_streaming_latency(c: &mut Criterion) {
             || tokio::runtime::Runtime::new().unwrap(),
             |rt| {
                 rt.block_on(async {
-                    let mcp = setup_benchmark_mcp().await.unwrap();
+                    let (notifications, _received) = tokio::sync::mpsc::channel(1);
+                    let mcp = setup_benchmark_mcp().await.unwrap().with_notification_sink(Arc::new(notifications));
                     
                     let start = std::time::Instant::now();
-                    let result = mcp.execute_tool(AxiomMCPTool::StreamPerformanceMetrics).await.unwrap();
+                    let started = mcp.execute_tool(AxiomMCPTool::StartMetricsStream(Default::default())).await.unwrap();
+                    let ToolResult::MetricsStreamStarted(handle) = &started else {
+                        panic!("Expected a started stream, got {:?}", started);
+                    };
+                    let stopped = mcp.execute_tool(AxiomMCPTool::StopMetricsStream(handle.stream_id.clone())).await.unwrap();
                     let duration = start.elapsed();
                     
                     // Should be very fast for streaming operations
                     assert!(duration.as_millis() < 20, "Performance streaming exceeded latency target");
                     
-                    black_box(result)
+                    black_box(stopped)
                 })
             },
             criterion::BatchSize::SmallInput
@@ -365,9 +385,19 @@ fn benchmark_visual_analysis_performance(c: &mut Criterion) {
             |rt| {
                 rt.block_on(async {
                     let mcp = setup_benchmark_mcp().await.unwrap();
+                    let screenshots = vec![benchmark_png_screenshot()];
+                    mcp.execute_tool(AxiomMCPTool::DetectUIRegressions(RegressionCheckRequest {
+                        mode: RegressionMode::Record,
+                        screen: "TaskList".to_string(),
+                        screenshots: screenshots.clone(),
+                    })).await.unwrap();
                     
                     let start = std::time::Instant::now();
-                    let result = mcp.execute_tool(AxiomMCPTool::DetectUIRegressions).await.unwrap();
+                    let result = mcp.execute_tool(AxiomMCPTool::DetectUIRegressions(RegressionCheckRequest {
+                        mode: RegressionMode::Check,
+                        screen: "TaskList".to_string(),
+                        screenshots,
+                    })).await.unwrap();
                     let duration = start.elapsed();
                     
                     // Visual analysis should complete in reasonable time
@@ -405,14 +435,9 @@ async fn setup_benchmark_loop() -> Result<MockLoop, Box<dyn std::error::Error>>
 }
 
 async fn setup_benchmark_mcp() -> Result<AxiomApplicationsObservabilityMCP, Box<dyn std::error::Error>> {
-    let config = MCPConfiguration {
-        hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
-        intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
-        simulator_management_enabled: true,
-        code_generation_enabled: true,
-        visual_analysis_enabled: true,
-        performance_monitoring_enabled: true,
-    };
+    // Servers and thresholds come from AXIOM_OBS_CONFIG or AXIOM_OBS_ variables
+    let mut config = MCPConfiguration::load()?;
+    config.visual_regression.baseline_dir = std::env::temp_dir().join("axiom-benchmark-baselines");
     
     let capabilities = MCPCapabilities {
         code_generation: true,
@@ -423,7 +448,44 @@ async fn setup_benchmark_mcp() -> Result<AxiomApplicationsObservabilityMCP, Box<
         performance_monitoring: true,
     };
     
-    AxiomApplicationsObservabilityMCP::new(config, capabilities).await
+    Ok(AxiomApplicationsObservabilityMCP::new(config, capabilities).await?)
+}
+
+/// A full-size iPhone screenshot as a real PNG, for the regression diff
+fn benchmark_png_screenshot() -> Screenshot {
+    let image = image::RgbaImage::from_fn(1179, 2556, |x, y| image::Rgba([(x % 256) as u8, (y % 256) as u8, 128, 255]));
+    Screenshot {
+        id: "regression_baseline".to_string(),
+        client_id: "test_client".to_string(),
+        configuration: ScreenshotConfiguration {
+            device_type: "iPhone 15".to_string(),
+            screen_size: ScreenSize { width: 393.0, height: 852.0 },
+            orientation: "portrait".to_string(),
+            scale: 3.0,
+            color_scheme: "light".to_string(),
+            capture_mode: "standard".to_string(),
+            status_bar: None,
+            runtime: None,
+            window: Default::default(),
+        },
+        image_data: encode_png(&image).unwrap(),
+        image: None,
+        metadata: ScreenshotMetadata {
+            timestamp: chrono::Utc::now(),
+            device_info: DeviceInfo {
+                model: "iPhone 15".to_string(),
+                screen_size: ScreenSize { width: 393.0, height: 852.0 },
+                orientation: "portrait".to_string(),
+                udid: None,
+            },
+            app_state: AppState {
+                view_hierarchy: "NavigationView".to_string(),
+                active_context: "MainContext".to_string(),
+                view_tree: None,
+            },
+            overrides: Default::default(),
+        },
+    }
 }
 
 async fn setup_benchmark_screenshot_engine() -> Result<MockEngine, Box<dyn std::error::Error>> {
@@ -443,22 +505,26 @@ fn create_benchmark_context_spec() -> ContextSpec {
                 property_type: "[BenchmarkItem]".to_string(),
                 is_published: true,
                 default_value: Some("[]".to_string()),
+                requirement_ids: vec![],
             },
             StateProperty {
                 name: "isLoading".to_string(),
                 property_type: "Bool".to_string(),
                 is_published: true,
                 default_value: Some("false".to_string()),
+                requirement_ids: vec![],
             },
             StateProperty {
                 name: "selectedItem".to_string(),
                 property_type: "BenchmarkItem?".to_string(),
                 is_published: true,
                 default_value: Some("nil".to_string()),
+                requirement_ids: vec![],
             },
         ],
         client_binding: "BenchmarkClient".to_string(),
         lifecycle_management: true,
+        template_overrides: Default::default(),
     }
 }
 
@@ -481,6 +547,8 @@ fn create_benchmark_presentation_spec() -> PresentationSpec {
             max_render_time_ms: 16.0,
             max_memory_mb: 15.0,
         },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
     }
 }
 
@@ -494,28 +562,34 @@ fn create_benchmark_client_spec() -> ClientSpec {
                 parameters: vec!["completion: @escaping ([BenchmarkItem]) -> Void".to_string()],
                 return_type: "Void".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             },
             ActionDefinition {
                 name: "createItem".to_string(),
                 parameters: vec!["item: BenchmarkItem".to_string()],
                 return_type: "Result<BenchmarkItem, Error>".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             },
             ActionDefinition {
                 name: "updateItem".to_string(),
                 parameters: vec!["item: BenchmarkItem".to_string()],
                 return_type: "Result<BenchmarkItem, Error>".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             },
             ActionDefinition {
                 name: "deleteItem".to_string(),
                 parameters: vec!["id: String".to_string()],
                 return_type: "Result<Void, Error>".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             },
         ],
         state_streaming: true,
         mock_implementation: true,
+        template_overrides: Default::default(),
+        persistence: None,
     }
 }
 
@@ -530,6 +604,7 @@ fn create_benchmark_implementation() -> Implementation {
                 name: "BenchmarkContext".to_string(),
                 responsibilities: vec!["Data management".to_string(), "State coordination".to_string()],
                 state_properties: vec!["items".to_string(), "isLoading".to_string()],
+                actions: vec![],
                 client_binding: "BenchmarkClient".to_string(),
                 generated_code: "// Benchmark context implementation".to_string(),
             }],
@@ -574,6 +649,8 @@ fn create_benchmark_implementation() -> Implementation {
             test_cases: vec![],
             estimated_coverage: 85.0,
             test_categories: vec!["Unit".to_string()],
+            test_files: vec![],
+            measured: false,
         },
         documentation: ImplementationDocumentation {
             overview: "Benchmark implementation".to_string(),
@@ -587,6 +664,11 @@ fn create_benchmark_implementation() -> Implementation {
             estimated_cpu_usage_percent: 12.0,
             performance_grade: "A".to_string(),
         },
+        persistence_layer: None,
+        navigation_layer: None,
+        review_feedback: vec![],
+        expected_screen_text: vec![],
+        traceability: vec![],
     }
 }
 
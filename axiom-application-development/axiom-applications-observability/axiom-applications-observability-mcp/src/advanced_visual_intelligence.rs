@@ -1,27 +1,965 @@
-use crate::error::Result;
+use crate::error::{AxiomMCPError, Result};
 use crate::types::*;
+use crate::visual_regression::{decode_png, encode_png, slug, PixelRect};
+use async_trait::async_trait;
+use image::RgbaImage;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Smallest comfortable touch target, per the Human Interface Guidelines
+pub const MIN_TOUCH_TARGET_PT: f64 = 44.0;
+
+/// WCAG AA contrast for body text
+pub const WCAG_AA_NORMAL_TEXT: f64 = 4.5;
+
+/// WCAG AA contrast for large text: 18pt, or 14pt bold
+pub const WCAG_AA_LARGE_TEXT: f64 = 3.0;
+
+/// Share of a text box a color must cover to count as the text color,
+/// so stray anti-aliased pixels are not mistaken for it
+const MIN_FOREGROUND_SHARE: f64 = 0.01;
+
+/// How far text may exceed its frame before it counts as truncated,
+/// which absorbs rounding in reported sizes
+pub const TRUNCATION_TOLERANCE_PT: f64 = 0.5;
+
+/// Share of the smaller of two sibling views they may overlap by
+pub const OVERLAP_TOLERANCE: f64 = 0.05;
+
+/// Context kept around an issue in its evidence crop
+const CROP_PADDING_PT: f64 = 8.0;
+
+/// Where screen text checks write their evidence crops, under the temp dir
+pub const DEFAULT_SCREEN_TEXT_DIR_NAME: &str = "axiom-screen-text";
+
+/// Where the development loop writes layout issue crops, under the temp dir
+pub const DEFAULT_LAYOUT_ISSUE_DIR_NAME: &str = "axiom-layout-issues";
+
+/// Containers whose children are meant to be drawn over each other
+const LAYERED_CONTAINERS: &[&str] = &["ZStack", "overlay", "background"];
+
+/// Containers whose content is meant to extend past their own frame
+const SCROLLING_CONTAINERS: &[&str] = &["ScrollView", "List", "UIScrollView", "UICollectionView", "UITableView"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UIPatternAnalysis {
     pub identified_patterns: Vec<String>,
     pub pattern_confidence: f64,
     pub consistency_score: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AccessibilityReport {
     pub compliance_score: f64,
     pub overall_score: f64,
     pub issues_found: Vec<String>,
     pub recommendations: Vec<String>,
+    /// Per-element findings of an accessibility audit
+    #[serde(default)]
+    pub findings: Vec<AccessibilityFinding>,
 }
 
-pub struct VisualIntelligenceEngine;
+/// Position and size in points, from the top left of the screen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ElementFrame {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ElementFrame {
+    /// The frame in pixels of an image at `scale`, clipped to it
+    fn to_pixels(self, scale: f64, image: &RgbaImage) -> Option<PixelRect> {
+        let left = (self.x * scale).round().max(0.0) as u32;
+        let top = (self.y * scale).round().max(0.0) as u32;
+        let right = (((self.x + self.width) * scale).round().max(0.0) as u32).min(image.width());
+        let bottom = (((self.y + self.height) * scale).round().max(0.0) as u32).min(image.height());
+        (right > left && bottom > top).then(|| PixelRect {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        })
+    }
+
+    fn scaled(self, factor: f64) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+            width: self.width * factor,
+            height: self.height * factor,
+        }
+    }
+
+    fn contains_center_of(&self, other: &ElementFrame) -> bool {
+        let (x, y) = (other.x + other.width / 2.0, other.y + other.height / 2.0);
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ElementType {
+    Button,
+    Link,
+    Toggle,
+    Slider,
+    TextField,
+    /// A text field that hides what is typed, such as a password field
+    SecureTextField,
+    Cell,
+    Image,
+    StaticText,
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+/// A node of the accessibility element tree of the running app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilityElement {
+    pub identifier: String,
+    #[serde(default)]
+    pub element_type: ElementType,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub traits: Vec<String>,
+    pub frame: ElementFrame,
+    /// Hidden from assistive technologies, along with everything inside it
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub font_size: Option<f64>,
+    #[serde(default)]
+    pub is_bold: bool,
+    #[serde(default)]
+    pub children: Vec<AccessibilityElement>,
+}
+
+impl AccessibilityElement {
+    pub fn is_tappable(&self) -> bool {
+        matches!(
+            self.element_type,
+            ElementType::Button | ElementType::Link | ElementType::Toggle | ElementType::Slider | ElementType::TextField | ElementType::SecureTextField | ElementType::Cell
+        ) || self.traits.iter().any(|t| t == "button" || t == "link")
+    }
+
+    fn has_label(&self) -> bool {
+        self.label.as_deref().is_some_and(|label| !label.trim().is_empty())
+    }
+
+    /// Whether the element draws text whose contrast can be measured
+    fn shows_text(&self) -> bool {
+        match self.element_type {
+            ElementType::StaticText => true,
+            ElementType::Button | ElementType::Link => self.has_label(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityRule {
+    MissingLabel,
+    SmallTouchTarget,
+    LowContrast,
+    UnlabeledImage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessibilityFinding {
+    pub element_id: String,
+    pub rule: AccessibilityRule,
+    pub severity: FindingSeverity,
+    pub message: String,
+    pub suggested_fix: String,
+    pub frame: ElementFrame,
+    /// Measured text contrast, for `LowContrast`
+    #[serde(default)]
+    pub contrast_ratio: Option<f64>,
+}
+
+/// A screenshot and the element tree of the screen it shows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessibilityAuditRequest {
+    pub screenshot: Screenshot,
+    /// Requested from the intelligence server when not given
+    #[serde(default)]
+    pub element_tree: Option<AccessibilityElement>,
+}
+
+/// Text and background colors found under a text element
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContrastSample {
+    pub foreground: [u8; 3],
+    pub background: [u8; 3],
+    pub ratio: f64,
+}
+
+/// WCAG relative luminance of an sRGB color
+pub fn relative_luminance(color: [u8; 3]) -> f64 {
+    let linear = |channel: u8| {
+        let c = f64::from(channel) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color[0]) + 0.7152 * linear(color[1]) + 0.0722 * linear(color[2])
+}
+
+/// WCAG contrast ratio, from 1 (identical) to 21 (black on white)
+pub fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Contrast WCAG AA asks of text at this size; unknown sizes are held to
+/// the body text ratio
+pub fn required_contrast(font_size: Option<f64>, is_bold: bool) -> f64 {
+    match font_size {
+        Some(size) if size >= 18.0 || (is_bold && size >= 14.0) => WCAG_AA_LARGE_TEXT,
+        _ => WCAG_AA_NORMAL_TEXT,
+    }
+}
+
+/// Contrast of the text in `rect`, taking the most common color as the
+/// background and the color that stands out most from it as the text
+///
+/// None when the area holds a single color, so there is no text to measure.
+pub fn measure_contrast(image: &RgbaImage, rect: PixelRect) -> Option<ContrastSample> {
+    // Colors bucketed to 4 bits per channel, averaged within each bucket
+    let mut buckets: HashMap<[u8; 3], (u64, [u64; 3])> = HashMap::new();
+    let mut total = 0u64;
+    for y in rect.y..rect.y + rect.height {
+        for x in rect.x..rect.x + rect.width {
+            let [r, g, b, _] = image.get_pixel(x, y).0;
+            let (count, sums) = buckets.entry([r >> 4, g >> 4, b >> 4]).or_insert((0, [0; 3]));
+            *count += 1;
+            sums[0] += u64::from(r);
+            sums[1] += u64::from(g);
+            sums[2] += u64::from(b);
+            total += 1;
+        }
+    }
+
+    let average = |(count, sums): &(u64, [u64; 3])| sums.map(|sum| (sum / count) as u8);
+    let (background_bucket, background) = buckets
+        .iter()
+        .max_by_key(|(bucket, (count, _))| (*count, **bucket))
+        .map(|(bucket, stats)| (*bucket, average(stats)))?;
+    let min_count = ((total as f64) * MIN_FOREGROUND_SHARE).ceil().max(1.0) as u64;
+
+    buckets
+        .iter()
+        .filter(|(bucket, (count, _))| **bucket != background_bucket && *count >= min_count)
+        .map(|(_, stats)| {
+            let foreground = average(stats);
+            ContrastSample {
+                foreground,
+                background,
+                ratio: contrast_ratio(foreground, background),
+            }
+        })
+        .max_by(|a, b| a.ratio.total_cmp(&b.ratio))
+}
+
+/// Check every element of `tree` against the screenshot it was captured with
+///
+/// Elements hidden from assistive technologies are skipped with their
+/// children. Contrast is only measured where the screenshot is available.
+pub fn audit_accessibility(tree: &AccessibilityElement, image: Option<&RgbaImage>, scale: f64) -> (usize, Vec<AccessibilityFinding>) {
+    let mut audited = 0;
+    let mut findings = Vec::new();
+    let mut stack = vec![tree];
+
+    while let Some(element) = stack.pop() {
+        if element.hidden {
+            continue;
+        }
+        audited += 1;
+        audit_element(element, image, scale, &mut findings);
+        stack.extend(element.children.iter().rev());
+    }
+
+    (audited, findings)
+}
+
+fn audit_element(element: &AccessibilityElement, image: Option<&RgbaImage>, scale: f64, findings: &mut Vec<AccessibilityFinding>) {
+    let finding = |rule, severity, message: String, suggested_fix: String| AccessibilityFinding {
+        element_id: element.identifier.clone(),
+        rule,
+        severity,
+        message,
+        suggested_fix,
+        frame: element.frame,
+        contrast_ratio: None,
+    };
+    let id = &element.identifier;
+
+    if element.is_tappable() && !element.has_label() {
+        findings.push(finding(
+            AccessibilityRule::MissingLabel,
+            FindingSeverity::Error,
+            format!("{} is tappable but has no accessibility label", id),
+            format!("Add .accessibilityLabel(\"…\") to {} describing what it does", id),
+        ));
+    }
+
+    let ElementFrame { width, height, .. } = element.frame;
+    if element.is_tappable() && (width < MIN_TOUCH_TARGET_PT || height < MIN_TOUCH_TARGET_PT) {
+        findings.push(finding(
+            AccessibilityRule::SmallTouchTarget,
+            FindingSeverity::Warning,
+            format!("{} is {:.0}x{:.0}pt, under the {:.0}x{:.0}pt minimum touch target", id, width, height, MIN_TOUCH_TARGET_PT, MIN_TOUCH_TARGET_PT),
+            format!("Give {} .frame(minWidth: 44, minHeight: 44) or extend its hit area with .contentShape", id),
+        ));
+    }
+
+    if element.element_type == ElementType::Image && !element.has_label() {
+        findings.push(finding(
+            AccessibilityRule::UnlabeledImage,
+            FindingSeverity::Warning,
+            format!("Image {} has no accessibility label", id),
+            format!("Label {} with .accessibilityLabel, or mark it .accessibilityHidden(true) if it is decorative", id),
+        ));
+    }
+
+    let sample = image
+        .filter(|_| element.shows_text())
+        .and_then(|image| element.frame.to_pixels(scale, image).and_then(|rect| measure_contrast(image, rect)));
+    if let Some(sample) = sample {
+        let required = required_contrast(element.font_size, element.is_bold);
+        if sample.ratio < required {
+            let mut low_contrast = finding(
+                AccessibilityRule::LowContrast,
+                FindingSeverity::Error,
+                format!(
+                    "Text of {} has a contrast ratio of {:.2}:1, below the WCAG AA {:.1}:1",
+                    id, sample.ratio, required
+                ),
+                format!(
+                    "Change the text color {} or the background {} of {} until they reach {:.1}:1",
+                    hex(sample.foreground), hex(sample.background), id, required
+                ),
+            );
+            low_contrast.contrast_ratio = Some(sample.ratio);
+            findings.push(low_contrast);
+        }
+    }
+}
+
+fn hex(color: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", color[0], color[1], color[2])
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ElementSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// A view in the hierarchy captured with a screenshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewNode {
+    pub identifier: String,
+    /// SwiftUI or UIKit type, such as `Text` or `ZStack`
+    #[serde(default)]
+    pub kind: String,
+    pub frame: ElementFrame,
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Size the text needs on a single line at the current text size
+    #[serde(default)]
+    pub intrinsic_size: Option<ElementSize>,
+    /// Most lines the text may wrap to; absent or 0 means no limit
+    #[serde(default)]
+    pub line_limit: Option<u32>,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub children: Vec<ViewNode>,
+}
+
+impl ViewNode {
+    fn visible_children(&self) -> impl Iterator<Item = &ViewNode> {
+        self.children.iter().filter(|child| !child.hidden)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutIssueKind {
+    Truncation,
+    Overlap,
+    /// A view reaching past the window, typically a fixed-width layout in
+    /// a compact split view
+    Offscreen,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutIssue {
+    pub kind: LayoutIssueKind,
+    /// Empty until the issue is attributed to a screenshot
+    #[serde(default)]
+    pub screenshot_id: String,
+    pub element_ids: Vec<String>,
+    /// The truncated text's frame, or where two views overlap, in points
+    pub region: ElementFrame,
+    pub message: String,
+    /// The region cut out of the screenshot, padded for context
+    #[serde(default)]
+    pub crop_path: Option<PathBuf>,
+}
+
+/// Text views whose content needs more lines than their frame holds
+///
+/// A text needs as many lines as its single-line width divides into its
+/// frame's width, and gets as many as its frame's height fits, capped by
+/// its line limit. Hidden views are skipped with their children.
+pub fn detect_truncation(root: &ViewNode, tolerance: f64) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if node.hidden {
+            continue;
+        }
+        if let Some(message) = truncation(node, tolerance) {
+            issues.push(LayoutIssue {
+                kind: LayoutIssueKind::Truncation,
+                screenshot_id: String::new(),
+                element_ids: vec![node.identifier.clone()],
+                region: node.frame,
+                message,
+                crop_path: None,
+            });
+        }
+        stack.extend(node.children.iter().rev());
+    }
+
+    issues
+}
+
+fn truncation(node: &ViewNode, tolerance: f64) -> Option<String> {
+    let text = node.text.as_deref()?;
+    let intrinsic = node.intrinsic_size?;
+    let ElementFrame { width, height, .. } = node.frame;
+    if intrinsic.width <= width + tolerance {
+        return None;
+    }
+
+    let lines_needed = if width > 0.0 {
+        ((intrinsic.width - tolerance) / width).ceil() as u32
+    } else {
+        u32::MAX
+    };
+    let lines_fitting = if intrinsic.height > 0.0 {
+        ((height + tolerance) / intrinsic.height).floor() as u32
+    } else {
+        1
+    };
+    let lines_available = match node.line_limit {
+        Some(limit) if limit > 0 => lines_fitting.min(limit),
+        _ => lines_fitting,
+    };
+    if lines_needed <= lines_available {
+        return None;
+    }
+
+    Some(format!(
+        "\"{}\" in {} is truncated: it is {:.0}pt wide on one line, and its {:.0}x{:.0}pt frame shows {} line(s)",
+        text, node.identifier, intrinsic.width, width, height, lines_available
+    ))
+}
+
+/// Sibling views whose frames overlap by more than `tolerance` of the
+/// smaller one
+///
+/// Children of stacking containers such as `ZStack` are expected to
+/// overlap and are not compared.
+pub fn detect_overlaps(root: &ViewNode, tolerance: f64) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if node.hidden {
+            continue;
+        }
+        if !LAYERED_CONTAINERS.contains(&node.kind.as_str()) {
+            let children: Vec<&ViewNode> = node.visible_children().collect();
+            for (i, a) in children.iter().enumerate() {
+                for b in &children[i + 1..] {
+                    if let Some(issue) = overlap(a, b, tolerance) {
+                        issues.push(issue);
+                    }
+                }
+            }
+        }
+        stack.extend(node.children.iter().rev());
+    }
+
+    issues
+}
+
+fn overlap(a: &ViewNode, b: &ViewNode, tolerance: f64) -> Option<LayoutIssue> {
+    let left = a.frame.x.max(b.frame.x);
+    let top = a.frame.y.max(b.frame.y);
+    let right = (a.frame.x + a.frame.width).min(b.frame.x + b.frame.width);
+    let bottom = (a.frame.y + a.frame.height).min(b.frame.y + b.frame.height);
+    if right <= left || bottom <= top {
+        return None;
+    }
+
+    let area = |frame: &ElementFrame| frame.width * frame.height;
+    let smaller = area(&a.frame).min(area(&b.frame));
+    let shared = (right - left) * (bottom - top);
+    if smaller <= 0.0 || shared / smaller <= tolerance {
+        return None;
+    }
+
+    Some(LayoutIssue {
+        kind: LayoutIssueKind::Overlap,
+        screenshot_id: String::new(),
+        element_ids: vec![a.identifier.clone(), b.identifier.clone()],
+        region: ElementFrame { x: left, y: top, width: right - left, height: bottom - top },
+        message: format!(
+            "{} and {} overlap by {:.0}x{:.0}pt ({:.0}% of the smaller view)",
+            a.identifier, b.identifier, right - left, bottom - top, 100.0 * shared / smaller
+        ),
+        crop_path: None,
+    })
+}
+
+/// The outermost views reaching past a `window`-sized window by more than
+/// `tolerance` points sideways
+///
+/// Views inside scrolling containers are expected to extend past the
+/// window and are not checked, and neither are hidden views.
+pub fn detect_offscreen(root: &ViewNode, window: &ScreenSize, tolerance: f64) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if node.hidden {
+            continue;
+        }
+        let ElementFrame { x, width, .. } = node.frame;
+        let (overflow_leading, overflow_trailing) = (-x, x + width - window.width);
+        if overflow_leading > tolerance || overflow_trailing > tolerance {
+            let left = x.max(0.0);
+            let right = (x + width).min(window.width);
+            issues.push(LayoutIssue {
+                kind: LayoutIssueKind::Offscreen,
+                screenshot_id: String::new(),
+                element_ids: vec![node.identifier.clone()],
+                region: ElementFrame { x: left, width: (right - left).max(0.0), ..node.frame },
+                message: format!(
+                    "{} is {:.0}pt wide and reaches {:.0}pt past the {:.0}pt wide window",
+                    node.identifier, width, overflow_leading.max(overflow_trailing), window.width
+                ),
+                crop_path: None,
+            });
+            // Its children are cut off with it
+            continue;
+        }
+        if !SCROLLING_CONTAINERS.contains(&node.kind.as_str()) {
+            stack.extend(node.children.iter().rev());
+        }
+    }
+
+    issues
+}
+
+/// `region` grown by the crop padding, in pixels of `image`
+fn crop_rect(region: ElementFrame, scale: f64, image: &RgbaImage) -> Option<PixelRect> {
+    ElementFrame {
+        x: region.x - CROP_PADDING_PT,
+        y: region.y - CROP_PADDING_PT,
+        width: region.width + 2.0 * CROP_PADDING_PT,
+        height: region.height + 2.0 * CROP_PADDING_PT,
+    }
+    .to_pixels(scale, image)
+}
+
+/// Lines of text Vision finds in the image named by the first argument,
+/// printed as JSON with frames in pixels from the top left
+const VISION_OCR_SCRIPT: &str = r#"import Foundation
+import ImageIO
+import Vision
+
+let url = URL(fileURLWithPath: CommandLine.arguments[1])
+guard let source = CGImageSourceCreateWithURL(url as CFURL, nil),
+      let image = CGImageSourceCreateImageAtIndex(source, 0, nil) else {
+    FileHandle.standardError.write("Cannot read \(url.path)\n".data(using: .utf8)!)
+    exit(1)
+}
+
+let request = VNRecognizeTextRequest()
+request.recognitionLevel = .accurate
+request.usesLanguageCorrection = false
+do {
+    try VNImageRequestHandler(cgImage: image).perform([request])
+} catch {
+    FileHandle.standardError.write("\(error)\n".data(using: .utf8)!)
+    exit(1)
+}
+
+let width = Double(image.width)
+let height = Double(image.height)
+let lines: [[String: Any]] = (request.results ?? []).compactMap { observation in
+    guard let candidate = observation.topCandidates(1).first else { return nil }
+    let box = observation.boundingBox
+    return [
+        "text": candidate.string,
+        "confidence": Double(candidate.confidence),
+        "frame": [
+            "x": Double(box.minX) * width,
+            "y": (1 - Double(box.maxY)) * height,
+            "width": Double(box.width) * width,
+            "height": Double(box.height) * height,
+        ],
+    ]
+}
+FileHandle.standardOutput.write(try! JSONSerialization.data(withJSONObject: lines))
+"#;
+
+/// Text a screen is expected to show
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextExpectation {
+    pub text: String,
+    /// `text` is a regular expression rather than literal text
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl TextExpectation {
+    pub fn literal(text: impl Into<String>) -> Self {
+        Self { text: text.into(), regex: false }
+    }
+
+    pub fn pattern(pattern: impl Into<String>) -> Self {
+        Self { text: pattern.into(), regex: true }
+    }
+}
+
+/// A line of text found on a screenshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecognizedText {
+    pub text: String,
+    /// From 0 to 1
+    pub confidence: f64,
+    /// In points in reports; recognizers give it in pixels
+    pub frame: ElementFrame,
+}
+
+/// An expected text and where it was found, if it was
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextMatch {
+    pub expected: TextExpectation,
+    /// The recognized lines holding the text, joined by spaces
+    pub found: Option<String>,
+    /// Lowest confidence of those lines
+    pub confidence: Option<f64>,
+    pub frame: Option<ElementFrame>,
+    /// The text cut out of the screenshot, or the region searched for it
+    /// when it was not found
+    #[serde(default)]
+    pub crop_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenTextReport {
+    pub screenshot_id: String,
+    /// Lines recognized in the searched region, in reading order
+    pub recognized: Vec<RecognizedText>,
+    pub matched: Vec<TextMatch>,
+    pub missing: Vec<TextMatch>,
+}
+
+impl ScreenTextReport {
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Texts a screenshot should show
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenTextAssertionRequest {
+    pub screenshot: Screenshot,
+    pub expected: Vec<TextExpectation>,
+    /// Only text centered inside this area counts, in points
+    #[serde(default)]
+    pub region: Option<ElementFrame>,
+}
+
+/// Finds the lines of text in a PNG, with frames in its pixels
+#[async_trait]
+pub trait TextRecognizer: Send + Sync {
+    async fn recognize(&self, png: &[u8]) -> Result<Vec<RecognizedText>>;
+}
+
+/// Recognizes text with the Vision framework, through a Swift script run
+/// by `xcrun swift`
+#[derive(Debug, Clone)]
+pub struct VisionTextRecognizer {
+    /// Where the script and the image it reads are written
+    pub work_dir: PathBuf,
+}
+
+impl Default for VisionTextRecognizer {
+    fn default() -> Self {
+        Self { work_dir: std::env::temp_dir() }
+    }
+}
+
+#[async_trait]
+impl TextRecognizer for VisionTextRecognizer {
+    async fn recognize(&self, png: &[u8]) -> Result<Vec<RecognizedText>> {
+        let id = uuid::Uuid::new_v4();
+        let script = self.work_dir.join(format!("axiom-ocr-{}.swift", id));
+        let image = self.work_dir.join(format!("axiom-ocr-{}.png", id));
+        tokio::fs::create_dir_all(&self.work_dir).await?;
+        tokio::fs::write(&script, VISION_OCR_SCRIPT).await?;
+        tokio::fs::write(&image, png).await?;
+
+        let output = tokio::process::Command::new("xcrun").arg("swift").arg(&script).arg(&image).output().await;
+        let _ = tokio::fs::remove_file(&script).await;
+        let _ = tokio::fs::remove_file(&image).await;
+        let output = output.map_err(|e| {
+            AxiomMCPError::toolchain(
+                "visual_intelligence", "recognize text", format!("Cannot run xcrun swift: {}", e),
+            ).with_cause(e)
+        })?;
+        if !output.status.success() {
+            return Err(AxiomMCPError::toolchain("visual_intelligence", "recognize text", format!(
+                "Vision text recognition failed: {}", String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+}
+
+/// Recognizes text with Tesseract, for hosts without the Vision framework
+#[cfg(feature = "tesseract-ocr")]
+#[derive(Debug, Clone)]
+pub struct TesseractTextRecognizer {
+    /// Tesseract language code, such as `eng`
+    pub language: String,
+}
+
+#[cfg(feature = "tesseract-ocr")]
+impl Default for TesseractTextRecognizer {
+    fn default() -> Self {
+        Self { language: "eng".to_string() }
+    }
+}
+
+#[cfg(feature = "tesseract-ocr")]
+#[async_trait]
+impl TextRecognizer for TesseractTextRecognizer {
+    async fn recognize(&self, png: &[u8]) -> Result<Vec<RecognizedText>> {
+        let (language, png) = (self.language.clone(), png.to_vec());
+        let tsv = tokio::task::spawn_blocking(move || {
+            let failed = |e: &dyn std::fmt::Display| {
+                AxiomMCPError::toolchain(
+                    "visual_intelligence", "recognize text", format!("Tesseract text recognition failed: {}", e),
+                )
+            };
+            let mut tesseract = tesseract::Tesseract::new(None, Some(&language))
+                .map_err(|e| failed(&e))?
+                .set_image_from_mem(&png)
+                .map_err(|e| failed(&e))?
+                .recognize()
+                .map_err(|e| failed(&e))?;
+            tesseract.get_tsv_text(0).map_err(|e| failed(&e))
+        })
+        .await??;
+        Ok(parse_tesseract_tsv(&tsv))
+    }
+}
+
+/// Vision on macOS; elsewhere Tesseract when built with `tesseract-ocr`
+fn default_text_recognizer() -> Option<Arc<dyn TextRecognizer>> {
+    if cfg!(target_os = "macos") {
+        Some(Arc::new(VisionTextRecognizer::default()))
+    } else {
+        fallback_text_recognizer()
+    }
+}
+
+#[cfg(feature = "tesseract-ocr")]
+fn fallback_text_recognizer() -> Option<Arc<dyn TextRecognizer>> {
+    Some(Arc::new(TesseractTextRecognizer::default()))
+}
+
+#[cfg(not(feature = "tesseract-ocr"))]
+fn fallback_text_recognizer() -> Option<Arc<dyn TextRecognizer>> {
+    None
+}
+
+/// Lines of the words in Tesseract's TSV output, in its reading order,
+/// each as confident as its words are on average
+pub fn parse_tesseract_tsv(tsv: &str) -> Vec<RecognizedText> {
+    type Word<'a> = (&'a str, f64, ElementFrame);
+    let mut lines: Vec<([&str; 4], Vec<Word>)> = Vec::new();
+
+    for row in tsv.lines().skip(1) {
+        let columns: Vec<&str> = row.split('\t').collect();
+        let [level, page, block, paragraph, line, _word, left, top, width, height, confidence, text] = columns[..] else {
+            continue;
+        };
+        // Level 5 rows are words; the others outline blocks, paragraphs and lines
+        if level != "5" || text.trim().is_empty() {
+            continue;
+        }
+        let number = |column: &str| column.trim().parse::<f64>().ok();
+        let (Some(x), Some(y), Some(width), Some(height), Some(confidence)) =
+            (number(left), number(top), number(width), number(height), number(confidence))
+        else {
+            continue;
+        };
+        if confidence < 0.0 {
+            continue;
+        }
+
+        let word = (text.trim(), confidence / 100.0, ElementFrame { x, y, width, height });
+        let key = [page, block, paragraph, line];
+        match lines.last_mut() {
+            Some((last, words)) if *last == key => words.push(word),
+            _ => lines.push((key, vec![word])),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(_, words)| RecognizedText {
+            text: words.iter().map(|(text, ..)| *text).collect::<Vec<_>>().join(" "),
+            confidence: words.iter().map(|(_, confidence, _)| confidence).sum::<f64>() / words.len() as f64,
+            frame: words.iter().map(|(.., frame)| *frame).reduce(union).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// `text` with typographic quotes, dashes and ellipses in their ASCII
+/// forms and each run of whitespace as one space, so text typeset by
+/// the app compares equal to text typed in an expectation
+pub fn fold_text(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => folded.push('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}' | '\u{00BB}' => folded.push('"'),
+            '\u{2010}'..='\u{2015}' | '\u{2212}' => folded.push('-'),
+            '\u{2026}' => folded.push_str("..."),
+            c if c.is_whitespace() => {
+                if !folded.is_empty() && !folded.ends_with(' ') {
+                    folded.push(' ');
+                }
+            }
+            c => folded.push(c),
+        }
+    }
+    folded.truncate(folded.trim_end().len());
+    folded
+}
+
+/// Folded, lowercased and without whitespace, which OCR both drops and
+/// invents between words
+fn literal_key(text: &str) -> String {
+    fold_text(text).to_lowercase().chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+type TextMatcher = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+fn text_matcher(expected: &TextExpectation) -> Result<TextMatcher> {
+    if expected.text.trim().is_empty() {
+        return Err(AxiomMCPError::validation("visual_intelligence", "match screen text", "Expected text cannot be empty"));
+    }
+    if expected.regex {
+        let pattern = regex::RegexBuilder::new(&expected.text)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| AxiomMCPError::validation(
+                "visual_intelligence", "match screen text", format!("Invalid text pattern {}: {}", expected.text, e),
+            ))?;
+        Ok(Box::new(move |text| pattern.is_match(&fold_text(text))))
+    } else {
+        let key = literal_key(&expected.text);
+        Ok(Box::new(move |text| literal_key(text).contains(&key)))
+    }
+}
+
+/// Where `expected` appears in `recognized`, on one line or across
+/// consecutive lines in reading order
+///
+/// Literal text matches regardless of case, whitespace and typographic
+/// punctuation; patterns match the folded text, ignoring case. The
+/// fewest lines holding the text win, then the most confident.
+pub fn find_text(recognized: &[RecognizedText], expected: &TextExpectation) -> Result<Option<RecognizedText>> {
+    Ok(find_with(recognized, &text_matcher(expected)?))
+}
+
+fn find_with(recognized: &[RecognizedText], matches: &TextMatcher) -> Option<RecognizedText> {
+    let confidence = |lines: &[RecognizedText]| lines.iter().map(|line| line.confidence).fold(f64::INFINITY, f64::min);
+    (1..=recognized.len()).find_map(|length| {
+        recognized
+            .windows(length)
+            .filter(|lines| matches(&lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join(" ")))
+            .max_by(|a, b| confidence(a).total_cmp(&confidence(b)))
+            .map(|lines| RecognizedText {
+                text: lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join(" "),
+                confidence: confidence(lines),
+                frame: lines.iter().map(|line| line.frame).reduce(union).unwrap_or_default(),
+            })
+    })
+}
+
+fn union(a: ElementFrame, b: ElementFrame) -> ElementFrame {
+    let left = a.x.min(b.x);
+    let top = a.y.min(b.y);
+    let right = (a.x + a.width).max(b.x + b.width);
+    let bottom = (a.y + a.height).max(b.y + b.height);
+    ElementFrame { x: left, y: top, width: right - left, height: bottom - top }
+}
+
+pub struct VisualIntelligenceEngine {
+    /// Reads screen text; `None` where no recognizer is available
+    text_recognizer: Option<Arc<dyn TextRecognizer>>,
+}
+
+impl std::fmt::Debug for VisualIntelligenceEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VisualIntelligenceEngine")
+            .field("text_recognizer", &self.text_recognizer.is_some())
+            .finish()
+    }
+}
 
 impl VisualIntelligenceEngine {
     pub async fn new() -> Result<Self> {
-        Ok(Self)
+        Ok(Self { text_recognizer: default_text_recognizer() })
+    }
+
+    /// Read screen text with `recognizer` instead of the platform's
+    pub fn with_text_recognizer(mut self, recognizer: Arc<dyn TextRecognizer>) -> Self {
+        self.text_recognizer = Some(recognizer);
+        self
     }
     
     pub async fn analyze_ui_patterns(&self, _screenshots: Vec<Screenshot>) -> Result<UIPatternAnalysis> {
@@ -38,15 +976,225 @@ impl VisualIntelligenceEngine {
             overall_score: 85.0,
             issues_found: vec!["Missing alt text on image".to_string()],
             recommendations: vec!["Add VoiceOver support".to_string()],
+            findings: Vec::new(),
+        })
+    }
+
+    /// Audit the screen in `screenshot` using its accessibility element tree
+    ///
+    /// `compliance_score` is the share of audited elements without errors,
+    /// `overall_score` the share without any finding.
+    pub async fn audit_accessibility(&self, screenshot: &Screenshot, tree: &AccessibilityElement) -> Result<AccessibilityReport> {
+        if screenshot.configuration.scale <= 0.0 {
+            return Err(AxiomMCPError::validation(
+                "visual_intelligence", "audit accessibility", "Screenshot scale must be positive",
+            ));
+        }
+        let image = match screenshot.png_data().and_then(|png| decode_png(&png)) {
+            Ok(image) => Some(image),
+            Err(e) => {
+                tracing::warn!("Auditing without contrast checks: {}", e);
+                None
+            }
+        };
+
+        let (audited, mut findings) = audit_accessibility(tree, image.as_ref(), screenshot.configuration.scale);
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+
+        let elements_with = |severity: Option<FindingSeverity>| {
+            let mut ids: Vec<&str> = findings
+                .iter()
+                .filter(|f| severity.is_none() || severity == Some(f.severity))
+                .map(|f| f.element_id.as_str())
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids.len()
+        };
+        let score = |failing: usize| if audited == 0 { 100.0 } else { 100.0 * (audited - failing) as f64 / audited as f64 };
+
+        let mut recommendations: Vec<String> = Vec::new();
+        for finding in &findings {
+            if !recommendations.contains(&finding.suggested_fix) {
+                recommendations.push(finding.suggested_fix.clone());
+            }
+        }
+
+        Ok(AccessibilityReport {
+            compliance_score: score(elements_with(Some(FindingSeverity::Error))),
+            overall_score: score(elements_with(None)),
+            issues_found: findings.iter().map(|f| f.message.clone()).collect(),
+            recommendations,
+            findings,
         })
     }
     
+    /// Find truncated text and overlapping views in every screenshot that
+    /// carries a view tree, and views reaching past the window in those of
+    /// an iPad window sharing the screen, adding them to the matrix
+    /// analysis and scoring its consistency again with them
+    ///
+    /// An evidence crop of each issue is written under
+    /// `crop_dir/<screenshot id>/`.
+    pub async fn detect_layout_issues(&self, matrix: &mut ScreenshotMatrix, crop_dir: &Path) -> Result<Vec<LayoutIssue>> {
+        let mut found = Vec::new();
+
+        for screenshot in &matrix.screenshots {
+            let Some(tree) = &screenshot.metadata.app_state.view_tree else {
+                continue;
+            };
+            let mut issues = detect_truncation(tree, TRUNCATION_TOLERANCE_PT);
+            issues.extend(detect_overlaps(tree, OVERLAP_TOLERANCE));
+            if screenshot.configuration.window != crate::device_matrix::WindowConfiguration::Fullscreen {
+                issues.extend(detect_offscreen(tree, &screenshot.configuration.screen_size, TRUNCATION_TOLERANCE_PT));
+            }
+            if issues.is_empty() {
+                continue;
+            }
+
+            let image = match screenshot.png_data().and_then(|png| decode_png(&png)) {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    tracing::warn!("No evidence crops for screenshot {}: {}", screenshot.id, e);
+                    None
+                }
+            };
+            let dir = crop_dir.join(slug(&screenshot.id));
+            for (index, issue) in issues.iter_mut().enumerate() {
+                issue.screenshot_id = screenshot.id.clone();
+                let Some(image) = &image else {
+                    continue;
+                };
+                let Some(rect) = crop_rect(issue.region, screenshot.configuration.scale, image) else {
+                    continue;
+                };
+                let crop = image::imageops::crop_imm(image, rect.x, rect.y, rect.width, rect.height).to_image();
+                let path = dir.join(format!("{:02}-{}.png", index + 1, slug(&issue.element_ids.join("-"))));
+                tokio::fs::create_dir_all(&dir).await?;
+                tokio::fs::write(&path, encode_png(&crop)?).await?;
+                issue.crop_path = Some(path);
+            }
+
+            let label = screenshot.configuration.label();
+            for issue in &issues {
+                matrix.analysis.detected_issues.push(format!("{}: {}", label, issue.message));
+            }
+            found.extend(issues);
+        }
+
+        matrix.analysis.layout_issues.extend(found.iter().cloned());
+        matrix.score_consistency();
+        Ok(found)
+    }
+    
+    /// Check that the screenshot shows each expected text
+    pub async fn assert_screen_contains(&self, request: &ScreenTextAssertionRequest, crop_dir: &Path) -> Result<ScreenTextReport> {
+        let png = request.screenshot.png_data()?;
+        self.check_screen_text(
+            &request.screenshot.id,
+            &png,
+            request.screenshot.configuration.scale,
+            &request.expected,
+            request.region,
+            crop_dir,
+        )
+        .await
+    }
+
+    /// Recognize the text of a screenshot at `scale` and look for each
+    /// expected text in it, within `region` when given
+    ///
+    /// Frames are reported in points. A crop of where each text was found,
+    /// or of the region it was missing from, is written under
+    /// `crop_dir/<screenshot id>/`.
+    pub async fn check_screen_text(
+        &self,
+        screenshot_id: &str,
+        png: &[u8],
+        scale: f64,
+        expected: &[TextExpectation],
+        region: Option<ElementFrame>,
+        crop_dir: &Path,
+    ) -> Result<ScreenTextReport> {
+        if expected.is_empty() {
+            return Err(AxiomMCPError::validation("visual_intelligence", "assert screen text", "No expected text given"));
+        }
+        if scale <= 0.0 {
+            return Err(AxiomMCPError::validation(
+                "visual_intelligence", "assert screen text", "Screenshot scale must be positive",
+            ));
+        }
+        let matchers = expected.iter().map(text_matcher).collect::<Result<Vec<_>>>()?;
+        let recognizer = self.text_recognizer.as_ref().ok_or_else(|| {
+            AxiomMCPError::toolchain(
+                "visual_intelligence",
+                "assert screen text",
+                "No text recognizer is available; screen text is read with Vision on macOS, or with the tesseract-ocr feature",
+            )
+        })?;
+
+        let mut recognized = recognizer.recognize(png).await?;
+        for line in &mut recognized {
+            line.frame = line.frame.scaled(1.0 / scale);
+        }
+        if let Some(region) = region {
+            recognized.retain(|line| region.contains_center_of(&line.frame));
+        }
+        recognized.sort_by(|a, b| a.frame.y.total_cmp(&b.frame.y).then(a.frame.x.total_cmp(&b.frame.x)));
+
+        let image = match decode_png(png) {
+            Ok(image) => Some(image),
+            Err(e) => {
+                tracing::warn!("No evidence crops for screenshot {}: {}", screenshot_id, e);
+                None
+            }
+        };
+        let dir = crop_dir.join(slug(screenshot_id));
+        let mut matched = Vec::new();
+        let mut missing = Vec::new();
+        for (index, (expectation, matches)) in expected.iter().zip(&matchers).enumerate() {
+            let found = find_with(&recognized, matches);
+            let evidence = found.as_ref().map(|line| line.frame).or(region);
+            let crop = image
+                .as_ref()
+                .zip(evidence)
+                .and_then(|(image, frame)| crop_rect(frame, scale, image).map(|rect| (image, rect)));
+            let crop_path = match crop {
+                Some((image, rect)) => {
+                    let crop = image::imageops::crop_imm(image, rect.x, rect.y, rect.width, rect.height).to_image();
+                    let path = dir.join(format!("{:02}-{}.png", index + 1, slug(&expectation.text)));
+                    tokio::fs::create_dir_all(&dir).await?;
+                    tokio::fs::write(&path, encode_png(&crop)?).await?;
+                    Some(path)
+                }
+                None => None,
+            };
+
+            let text_match = TextMatch {
+                expected: expectation.clone(),
+                confidence: found.as_ref().map(|line| line.confidence),
+                frame: found.as_ref().map(|line| line.frame),
+                found: found.map(|line| line.text),
+                crop_path,
+            };
+            if text_match.found.is_some() {
+                matched.push(text_match);
+            } else {
+                missing.push(text_match);
+            }
+        }
+
+        Ok(ScreenTextReport { screenshot_id: screenshot_id.to_string(), recognized, matched, missing })
+    }
+    
     pub async fn detect_regressions(&self, _baseline: Vec<Screenshot>, _updated: Vec<Screenshot>) -> Result<RegressionReport> {
         Ok(RegressionReport {
             total_comparisons: 10,
             regressions_detected: 1,
             false_positive_rate: 0.05,
             confidence_score: 0.95,
+            mode: None,
+            configurations: Vec::new(),
         })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,196 @@
+use crate::complete_development_loop::CycleStage;
+use crate::error::{AxiomMCPError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{broadcast, oneshot};
+
+/// How long a cycle waits at a gate unless configured otherwise
+pub const DEFAULT_APPROVAL_TIMEOUT_MS: u64 = 30 * 60 * 1000;
+
+/// Rejections of one stage before the cycle gives up on it
+pub const DEFAULT_MAX_REJECTIONS: u32 = 3;
+
+/// Approval requests buffered per subscriber before it starts lagging
+const APPROVAL_EVENT_CAPACITY: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalMode {
+    /// Wait for `approve_stage` or `reject_stage`
+    Interactive,
+    /// Approve every gate at once, for CI
+    AutoApprove,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApprovalPolicy {
+    pub mode: ApprovalMode,
+    /// Stages a person reviews before the cycle moves on
+    pub gates: Vec<CycleStage>,
+    /// After this long without a decision the cycle stops; its checkpoint
+    /// can be resumed to ask again
+    pub timeout_ms: u64,
+    pub max_rejections: u32,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        Self {
+            mode: ApprovalMode::Interactive,
+            gates: vec![CycleStage::Analysis, CycleStage::Implementation, CycleStage::VisualValidation],
+            timeout_ms: DEFAULT_APPROVAL_TIMEOUT_MS,
+            max_rejections: DEFAULT_MAX_REJECTIONS,
+        }
+    }
+}
+
+/// A generated file to look at before approving
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewArtifact {
+    pub name: String,
+    pub content: String,
+}
+
+/// A cycle waiting at a gate, sent to subscribers when it starts waiting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub cycle_id: String,
+    pub stage: CycleStage,
+    /// 1 for the first review of the stage, one more after each rejection
+    pub attempt: u32,
+    pub summary: String,
+    pub artifacts: Vec<ReviewArtifact>,
+    /// The stage's full output
+    pub output: serde_json::Value,
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Approve,
+    /// Run the stage again with `feedback`
+    Reject { feedback: String },
+}
+
+/// Send a cycle's stage back with feedback for the next attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectStageRequest {
+    pub cycle_id: String,
+    pub feedback: String,
+}
+
+/// The decision taken on a request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageReview {
+    pub request: ApprovalRequest,
+    pub decision: ApprovalDecision,
+}
+
+struct PendingApproval {
+    request: ApprovalRequest,
+    reply: oneshot::Sender<ApprovalDecision>,
+}
+
+/// Where development cycles wait for a person between stages
+pub struct ApprovalGates {
+    policy: ApprovalPolicy,
+    pending: Mutex<BTreeMap<String, PendingApproval>>,
+    requests: broadcast::Sender<ApprovalRequest>,
+}
+
+impl std::fmt::Debug for ApprovalGates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApprovalGates")
+            .field("policy", &self.policy)
+            .field("pending", &self.pending.lock().unwrap().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ApprovalGates {
+    pub fn new(policy: ApprovalPolicy) -> Self {
+        Self {
+            policy,
+            pending: Mutex::new(BTreeMap::new()),
+            requests: broadcast::channel(APPROVAL_EVENT_CAPACITY).0,
+        }
+    }
+
+    pub fn policy(&self) -> &ApprovalPolicy {
+        &self.policy
+    }
+
+    pub fn is_gated(&self, stage: CycleStage) -> bool {
+        self.policy.gates.contains(&stage)
+    }
+
+    /// Requests as cycles start waiting, for hosts that forward them to
+    /// clients as notifications
+    pub fn subscribe(&self) -> broadcast::Receiver<ApprovalRequest> {
+        self.requests.subscribe()
+    }
+
+    /// Requests still waiting for a decision, by cycle id
+    pub fn pending(&self) -> Vec<ApprovalRequest> {
+        self.pending.lock().unwrap().values().map(|pending| pending.request.clone()).collect()
+    }
+
+    /// Wait for a decision on `request`
+    pub async fn review(&self, request: ApprovalRequest) -> Result<ApprovalDecision> {
+        if self.policy.mode == ApprovalMode::AutoApprove {
+            tracing::info!("Auto-approved {} of cycle {}", request.stage, request.cycle_id);
+            return Ok(ApprovalDecision::Approve);
+        }
+
+        let (reply, decision) = oneshot::channel();
+        let cycle_id = request.cycle_id.clone();
+        let stage = request.stage;
+        self.pending.lock().unwrap().insert(cycle_id.clone(), PendingApproval { request: request.clone(), reply });
+        tracing::info!("Cycle {} is waiting for approval of its {}: {}", cycle_id, stage, request.summary);
+        // Nobody may be subscribed; the request is still listed as pending
+        let _ = self.requests.send(request);
+
+        let timeout = Duration::from_millis(self.policy.timeout_ms);
+        let decision = tokio::time::timeout(timeout, decision).await;
+        self.pending.lock().unwrap().remove(&cycle_id);
+        match decision {
+            Ok(Ok(decision)) => Ok(decision),
+            Ok(Err(_)) => Err(AxiomMCPError::internal("approval_gates", "await approval", format!(
+                "Approval of the {} of cycle {} was abandoned", stage, cycle_id
+            ))),
+            Err(_) => Err(AxiomMCPError::validation("approval_gates", "await approval", format!(
+                "The {} of cycle {} was not approved within {:?}; resume the cycle to review it again",
+                stage, cycle_id, timeout
+            ))),
+        }
+    }
+
+    pub fn approve(&self, cycle_id: &str) -> Result<StageReview> {
+        self.decide(cycle_id, ApprovalDecision::Approve)
+    }
+
+    pub fn reject(&self, cycle_id: &str, feedback: String) -> Result<StageReview> {
+        if feedback.trim().is_empty() {
+            return Err(AxiomMCPError::validation("approval_gates", "reject stage", "Rejection feedback cannot be empty"));
+        }
+        self.decide(cycle_id, ApprovalDecision::Reject { feedback })
+    }
+
+    fn decide(&self, cycle_id: &str, decision: ApprovalDecision) -> Result<StageReview> {
+        let pending = self.pending.lock().unwrap().remove(cycle_id).ok_or_else(|| {
+            AxiomMCPError::validation("approval_gates", "decide stage", format!("Cycle {} is not waiting for approval", cycle_id))
+        })?;
+        let request = pending.request;
+        // The cycle timed out between the lookup and now
+        if pending.reply.send(decision.clone()).is_err() {
+            return Err(AxiomMCPError::validation("approval_gates", "decide stage", format!(
+                "Cycle {} stopped waiting for approval of its {}", cycle_id, request.stage
+            )));
+        }
+        Ok(StageReview { request, decision })
+    }
+}
@@ -0,0 +1,533 @@
+use crate::error::{AxiomMCPError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Where artifacts are kept, relative to the project
+pub const DEFAULT_ARTIFACT_DIR: &str = ".axiom/artifacts";
+
+const OBJECTS_DIR: &str = "objects";
+
+/// What each stored file is, and which cycles and stages stored it
+const INDEX_FILE: &str = "index.json";
+
+pub const DEFAULT_MAX_TOTAL_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+pub const DEFAULT_MAX_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// How long build logs and test results are kept unless configured otherwise
+pub const DEFAULT_LOG_MAX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// What a stored artifact is; each kind has a retention policy of its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Screenshot,
+    Recording,
+    /// The whole log of a project build, of which build results keep the errors
+    BuildLog,
+    /// `xcresulttool`'s reading of a test run's result bundle
+    TestResults,
+    /// Metrics measured for a cycle, with the verdict on them
+    PerformanceMeasurement,
+    /// An exported cycle report
+    Report,
+}
+
+impl ArtifactKind {
+    pub const ALL: [ArtifactKind; 6] = [
+        ArtifactKind::Screenshot,
+        ArtifactKind::Recording,
+        ArtifactKind::BuildLog,
+        ArtifactKind::TestResults,
+        ArtifactKind::PerformanceMeasurement,
+        ArtifactKind::Report,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ArtifactKind::Screenshot => "screenshot",
+            ArtifactKind::Recording => "recording",
+            ArtifactKind::BuildLog => "build_log",
+            ArtifactKind::TestResults => "test_results",
+            ArtifactKind::PerformanceMeasurement => "performance_measurement",
+            ArtifactKind::Report => "report",
+        }
+    }
+}
+
+impl std::fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How much the store keeps; `None` puts no limit on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub max_total_bytes: Option<u64>,
+    pub max_age_secs: Option<u64>,
+}
+
+impl RetentionPolicy {
+    pub fn unlimited() -> Self {
+        Self { max_total_bytes: None, max_age_secs: None }
+    }
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_total_bytes: Some(DEFAULT_MAX_TOTAL_BYTES),
+            max_age_secs: Some(DEFAULT_MAX_AGE_SECS),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtifactStoreConfig {
+    pub dir: PathBuf,
+    /// Kinds left out are kept under `RetentionPolicy::default()`
+    pub retention: BTreeMap<ArtifactKind, RetentionPolicy>,
+}
+
+impl ArtifactStoreConfig {
+    pub fn retention_for(&self, kind: ArtifactKind) -> RetentionPolicy {
+        self.retention.get(&kind).cloned().unwrap_or_default()
+    }
+}
+
+impl Default for ArtifactStoreConfig {
+    /// Reports are kept until removed by hand, and logs for a week
+    fn default() -> Self {
+        let logs = RetentionPolicy { max_age_secs: Some(DEFAULT_LOG_MAX_AGE_SECS), ..RetentionPolicy::default() };
+        Self {
+            dir: PathBuf::from(DEFAULT_ARTIFACT_DIR),
+            retention: BTreeMap::from([
+                (ArtifactKind::BuildLog, logs.clone()),
+                (ArtifactKind::TestResults, logs),
+                (ArtifactKind::Report, RetentionPolicy::unlimited()),
+            ]),
+        }
+    }
+}
+
+/// The cycle and stage an artifact was stored for
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactSource {
+    #[serde(default)]
+    pub cycle_id: Option<String>,
+    #[serde(default)]
+    pub stage: Option<String>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ArtifactSource {
+    pub fn cycle(cycle_id: impl Into<String>, stage: impl ToString) -> Self {
+        Self { cycle_id: Some(cycle_id.into()), stage: Some(stage.to_string()), metadata: BTreeMap::new() }
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cycle_id.is_none() && self.stage.is_none() && self.metadata.is_empty()
+    }
+}
+
+/// A stored file, named by the SHA-256 of its content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    pub id: String,
+    pub kind: ArtifactKind,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// When the content was first stored
+    pub stored_at: chrono::DateTime<chrono::Utc>,
+    /// Every cycle and stage that stored the content, oldest first
+    #[serde(default)]
+    pub sources: Vec<ArtifactSource>,
+}
+
+impl ArtifactRecord {
+    pub fn extension(&self) -> Option<&str> {
+        self.path.extension().and_then(|extension| extension.to_str())
+    }
+}
+
+/// Arguments of `list_artifacts`; unset fields match every artifact
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtifactQuery {
+    pub kind: Option<ArtifactKind>,
+    pub cycle_id: Option<String>,
+    pub stage: Option<String>,
+}
+
+impl ArtifactQuery {
+    pub fn matches(&self, record: &ArtifactRecord) -> bool {
+        if self.kind.is_some_and(|kind| kind != record.kind) {
+            return false;
+        }
+        if self.cycle_id.is_none() && self.stage.is_none() {
+            return true;
+        }
+        record.sources.iter().any(|source| {
+            self.cycle_id.as_ref().is_none_or(|cycle_id| source.cycle_id.as_ref() == Some(cycle_id))
+                && self.stage.as_ref().is_none_or(|stage| source.stage.as_ref() == Some(stage))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruneReason {
+    /// Older than `max_age_secs`
+    Expired,
+    /// Among the oldest while the store was over `max_total_bytes`
+    OverSize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrunedArtifact {
+    pub id: String,
+    pub kind: ArtifactKind,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub reason: PruneReason,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtifactPruneReport {
+    /// Oldest first within each kind
+    pub removed: Vec<PrunedArtifact>,
+    pub freed_bytes: u64,
+    pub kept: usize,
+    pub kept_bytes: u64,
+    /// Kept because the caller still needs them, such as regression baselines
+    pub pinned: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArtifactIndex {
+    #[serde(default)]
+    artifacts: BTreeMap<String, ArtifactRecord>,
+}
+
+/// Content-addressed files shared by the screenshot store, the build and
+/// test stages, performance validation and the report exporter
+///
+/// Files are named by the hash of their content, so storing the same bytes
+/// again writes nothing and only renews them for retention. An index next to
+/// them says what each one is and which cycles and stages stored it, which
+/// is what `list_artifacts` queries and what a resumed cycle or an exported
+/// report finds its files by.
+pub struct ArtifactStore {
+    config: ArtifactStoreConfig,
+    /// Serializes updates of the index file
+    index_lock: Mutex<()>,
+}
+
+impl std::fmt::Debug for ArtifactStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArtifactStore").field("config", &self.config).finish()
+    }
+}
+
+impl ArtifactStore {
+    pub fn new(config: ArtifactStoreConfig) -> Self {
+        Self { config, index_lock: Mutex::new(()) }
+    }
+
+    pub fn config(&self) -> &ArtifactStoreConfig {
+        &self.config
+    }
+
+    /// Store `bytes` as a `kind` artifact with the file extension `extension`,
+    /// or renew the copy stored earlier and add `source` to it
+    pub async fn put(&self, kind: ArtifactKind, bytes: &[u8], extension: &str, source: ArtifactSource) -> Result<ArtifactRecord> {
+        let id = content_hash(bytes);
+        if let Some(record) = self.renew(&id, source.clone())? {
+            return Ok(record);
+        }
+        let temp = self.temp_path(extension).await?;
+        tokio::fs::write(&temp, bytes).await?;
+        self.insert_file(&id, kind, &temp, extension, source).await
+    }
+
+    /// `put` for callers that go on without the artifact when it cannot be
+    /// stored, which is only logged
+    pub async fn keep(&self, kind: ArtifactKind, bytes: &[u8], extension: &str, source: ArtifactSource) -> Option<ArtifactRecord> {
+        match self.put(kind, bytes, extension, source).await {
+            Ok(record) => Some(record),
+            Err(e) => {
+                tracing::warn!("Could not keep a {} artifact in {}: {}", kind, self.config.dir.display(), e);
+                None
+            }
+        }
+    }
+
+    /// `put` for a file already on disk, which is copied rather than read
+    /// into memory
+    pub async fn put_file(&self, kind: ArtifactKind, file: &Path, extension: &str, source: ArtifactSource) -> Result<ArtifactRecord> {
+        let id = file_hash(file).await?;
+        if let Some(record) = self.renew(&id, source.clone())? {
+            return Ok(record);
+        }
+        let temp = self.temp_path(extension).await?;
+        tokio::fs::copy(file, &temp).await?;
+        self.insert_file(&id, kind, &temp, extension, source).await
+    }
+
+    /// Move `file` into the store as artifact `id`, for content named by the
+    /// hash of something else, such as a screenshot encoded as HEIC that is
+    /// named by its PNG. `file` should come from `temp_path`, so it is moved
+    /// within one file system and a reader never sees half of it.
+    pub async fn insert_file(&self, id: &str, kind: ArtifactKind, file: &Path, extension: &str, source: ArtifactSource) -> Result<ArtifactRecord> {
+        let path = self.object_path(id, extension);
+        if let Err(e) = tokio::fs::rename(file, &path).await {
+            let _ = tokio::fs::remove_file(file).await;
+            return Err(e.into());
+        }
+        let size_bytes = tokio::fs::metadata(&path).await?.len();
+        let mut record = ArtifactRecord {
+            id: id.to_string(),
+            kind,
+            path,
+            size_bytes,
+            stored_at: chrono::Utc::now(),
+            sources: Vec::new(),
+        };
+        self.update_index(|index| {
+            // A concurrent put of the same content may have inserted it since
+            // `renew` looked, so its sources are kept
+            let stored = index.artifacts.entry(record.id.clone()).or_insert_with(|| record.clone());
+            if !source.is_empty() && !stored.sources.contains(&source) {
+                stored.sources.push(source);
+            }
+            record = stored.clone();
+        })?;
+        Ok(record)
+    }
+
+    /// Artifact `id`, renewed for retention with `source` added to it;
+    /// `None` when the store does not have it
+    pub fn renew(&self, id: &str, source: ArtifactSource) -> Result<Option<ArtifactRecord>> {
+        let Some(record) = self.get(id)? else {
+            return Ok(None);
+        };
+        std::fs::File::options().write(true).open(&record.path)?.set_modified(SystemTime::now())?;
+        if source.is_empty() || record.sources.contains(&source) {
+            return Ok(Some(record));
+        }
+        let mut renewed = None;
+        self.update_index(|index| {
+            if let Some(record) = index.artifacts.get_mut(id) {
+                record.sources.push(source);
+                renewed = Some(record.clone());
+            }
+        })?;
+        Ok(renewed)
+    }
+
+    /// A path in the store to write a file at before `insert_file` moves it in
+    pub async fn temp_path(&self, extension: &str) -> Result<PathBuf> {
+        let objects = self.config.dir.join(OBJECTS_DIR);
+        tokio::fs::create_dir_all(&objects).await?;
+        Ok(objects.join(format!(".{}.{}", uuid::Uuid::new_v4(), extension)))
+    }
+
+    /// The artifact stored as `id`; one whose file was removed behind the
+    /// store's back is not there
+    pub fn get(&self, id: &str) -> Result<Option<ArtifactRecord>> {
+        let record = self.load_index()?.artifacts.remove(id);
+        Ok(record.filter(|record| record.path.exists()))
+    }
+
+    /// `get`, failing for an artifact the store does not have
+    pub fn require(&self, id: &str) -> Result<ArtifactRecord> {
+        self.get(id)?.ok_or_else(|| {
+            AxiomMCPError::validation("artifact_store", "get artifact", format!("No artifact {} is stored", id))
+        })
+    }
+
+    pub async fn read(&self, id: &str) -> Result<Vec<u8>> {
+        let record = self.require(id)?;
+        tokio::fs::read(&record.path).await.map_err(|e| AxiomMCPError::internal("artifact_store", "read artifact", format!(
+            "Artifact {} is gone from {}: {}", id, record.path.display(), e
+        )).with_cause(e))
+    }
+
+    /// Artifacts `query` matches, oldest first
+    pub fn list(&self, query: &ArtifactQuery) -> Result<Vec<ArtifactRecord>> {
+        let mut records: Vec<ArtifactRecord> = self
+            .load_index()?
+            .artifacts
+            .into_values()
+            .filter(|record| query.matches(record) && record.path.exists())
+            .collect();
+        records.sort_by(|a, b| (a.stored_at, &a.id).cmp(&(b.stored_at, &b.id)));
+        Ok(records)
+    }
+
+    /// Remove every kind's artifacts past its retention policy as of `now`,
+    /// except those in `pinned`
+    pub fn prune(&self, now: SystemTime, pinned: &BTreeSet<String>) -> Result<ArtifactPruneReport> {
+        let mut report = ArtifactPruneReport::default();
+        for kind in ArtifactKind::ALL {
+            let pruned = self.prune_kind(kind, &self.config.retention_for(kind), now, pinned)?;
+            report.removed.extend(pruned.removed);
+            report.freed_bytes += pruned.freed_bytes;
+            report.kept += pruned.kept;
+            report.kept_bytes += pruned.kept_bytes;
+            report.pinned += pruned.pinned;
+        }
+        Ok(report)
+    }
+
+    /// Remove `kind` artifacts past `retention` as of `now`: expired ones,
+    /// then the oldest until the kind fits its size limit. Files the index
+    /// does not name are left alone.
+    pub fn prune_kind(&self, kind: ArtifactKind, retention: &RetentionPolicy, now: SystemTime, pinned: &BTreeSet<String>) -> Result<ArtifactPruneReport> {
+        let _lock = self.lock_index()?;
+        let mut index = self.read_index()?;
+        let mut artifacts = Vec::new();
+        let mut missing = Vec::new();
+        for record in index.artifacts.values().filter(|record| record.kind == kind) {
+            match std::fs::metadata(&record.path) {
+                Ok(metadata) => artifacts.push((metadata.modified()?, record.clone())),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => missing.push(record.id.clone()),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        artifacts.sort_by(|(a_modified, a), (b_modified, b)| (a_modified, &a.path).cmp(&(b_modified, &b.path)));
+
+        let max_age = retention.max_age_secs.map(Duration::from_secs);
+        let mut total: u64 = artifacts.iter().map(|(_, record)| record.size_bytes).sum();
+        let mut report = ArtifactPruneReport::default();
+        for (modified, record) in artifacts {
+            if pinned.contains(&record.id) {
+                report.pinned += 1;
+                report.kept += 1;
+                report.kept_bytes += record.size_bytes;
+                continue;
+            }
+            let age = now.duration_since(modified).unwrap_or_default();
+            let reason = if max_age.is_some_and(|max_age| age > max_age) {
+                Some(PruneReason::Expired)
+            } else if retention.max_total_bytes.is_some_and(|max| total > max) {
+                Some(PruneReason::OverSize)
+            } else {
+                None
+            };
+            match reason {
+                Some(reason) => {
+                    std::fs::remove_file(&record.path)?;
+                    index.artifacts.remove(&record.id);
+                    total -= record.size_bytes;
+                    report.freed_bytes += record.size_bytes;
+                    report.removed.push(PrunedArtifact {
+                        id: record.id,
+                        kind,
+                        path: record.path,
+                        size_bytes: record.size_bytes,
+                        reason,
+                    });
+                }
+                None => {
+                    report.kept += 1;
+                    report.kept_bytes += record.size_bytes;
+                }
+            }
+        }
+
+        if !report.removed.is_empty() || !missing.is_empty() {
+            for id in &missing {
+                index.artifacts.remove(id);
+            }
+            self.write_index(&index)?;
+        }
+        if !report.removed.is_empty() {
+            tracing::info!(
+                "Pruned {} {} artifacts ({} bytes) from {}",
+                report.removed.len(), kind, report.freed_bytes, self.config.dir.display()
+            );
+        }
+        Ok(report)
+    }
+
+    fn object_path(&self, id: &str, extension: &str) -> PathBuf {
+        self.config.dir.join(OBJECTS_DIR).join(format!("{}.{}", id, extension))
+    }
+
+    fn load_index(&self) -> Result<ArtifactIndex> {
+        let _lock = self.lock_index()?;
+        self.read_index()
+    }
+
+    fn update_index(&self, update: impl FnOnce(&mut ArtifactIndex)) -> Result<()> {
+        let _lock = self.lock_index()?;
+        let mut index = self.read_index()?;
+        update(&mut index);
+        self.write_index(&index)
+    }
+
+    fn lock_index(&self) -> Result<std::sync::MutexGuard<'_, ()>> {
+        self.index_lock.lock().map_err(|_| {
+            AxiomMCPError::internal("artifact_store", "lock index", "A thread panicked while updating the artifact index")
+        })
+    }
+
+    fn read_index(&self) -> Result<ArtifactIndex> {
+        let path = self.config.dir.join(INDEX_FILE);
+        match std::fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents).map_err(|e| AxiomMCPError::internal("artifact_store", "read index", format!(
+                "The artifact index {} is unreadable: {}", path.display(), e
+            )).with_cause(e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ArtifactIndex::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Written aside and renamed, so an interrupted write leaves the old index
+    fn write_index(&self, index: &ArtifactIndex) -> Result<()> {
+        std::fs::create_dir_all(&self.config.dir)?;
+        let temp = self.config.dir.join(format!(".{}-{}", INDEX_FILE, uuid::Uuid::new_v4()));
+        std::fs::write(&temp, serde_json::to_vec_pretty(index)?)?;
+        std::fs::rename(&temp, self.config.dir.join(INDEX_FILE))?;
+        Ok(())
+    }
+}
+
+/// Lowercase hex SHA-256
+pub fn content_hash(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+/// `content_hash` of a file's content, read a block at a time
+async fn file_hash(path: &Path) -> Result<String> {
+    use tokio::io::AsyncReadExt;
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
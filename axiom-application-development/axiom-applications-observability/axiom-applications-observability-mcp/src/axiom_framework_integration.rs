@@ -1,4 +1,5 @@
-use crate::error::Result;
+use crate::error::{AxiomMCPError, Result};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
@@ -79,4 +80,419 @@ impl AxiomFrameworkIntegration {
             certification_level: "Axiom Certified".to_string(),
         })
     }
-}
\ No newline at end of file
+}
+/// File in which SwiftPM records the version each dependency resolved to
+pub const PACKAGE_RESOLVED_FILE: &str = "Package.resolved";
+
+/// Base class of generated contexts on frameworks with client observation
+pub const CONTEXT_BASE_CLASS: &str = "AxiomClientObservingContext";
+
+/// Base class of generated contexts below the `ClientObservingContext` floor
+pub const LEGACY_CONTEXT_BASE_CLASS: &str = "AxiomContext";
+
+/// Axiom Apple framework packages generated code builds against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameworkPackage {
+    AxiomCore,
+    AxiomArchitecture,
+}
+
+impl FrameworkPackage {
+    pub const ALL: [FrameworkPackage; 2] = [FrameworkPackage::AxiomCore, FrameworkPackage::AxiomArchitecture];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FrameworkPackage::AxiomCore => "AxiomCore",
+            FrameworkPackage::AxiomArchitecture => "AxiomArchitecture",
+        }
+    }
+
+    /// Whether the SwiftPM package `identity` provides this framework; the
+    /// `axiom` package provides both
+    fn provided_by(self, identity: &str) -> bool {
+        identity == "axiom" || identity == self.name().to_lowercase()
+    }
+}
+
+/// A semantic version of a framework package; pre-release and build
+/// suffixes are ignored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FrameworkVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl FrameworkVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// `1.2.3`, `v1.2` or `2.0.0-beta.1`
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim().trim_start_matches('v');
+        let release = version.split(['-', '+']).next().unwrap_or_default();
+        let mut parts = release.split('.').map(|part| part.parse::<u64>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl std::fmt::Display for FrameworkVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl TryFrom<String> for FrameworkVersion {
+    type Error = String;
+
+    fn try_from(version: String) -> std::result::Result<Self, Self::Error> {
+        Self::parse(&version).ok_or_else(|| format!("'{}' is not a version like 1.2.3", version))
+    }
+}
+
+impl From<FrameworkVersion> for String {
+    fn from(version: FrameworkVersion) -> Self {
+        version.to_string()
+    }
+}
+
+/// Framework versions to generate for, taking precedence over the
+/// project's `Package.resolved`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AxiomFrameworkConfig {
+    #[serde(default)]
+    pub core_version: Option<FrameworkVersion>,
+    #[serde(default)]
+    pub architecture_version: Option<FrameworkVersion>,
+}
+
+impl AxiomFrameworkConfig {
+    pub fn version(&self, package: FrameworkPackage) -> Option<FrameworkVersion> {
+        match package {
+            FrameworkPackage::AxiomCore => self.core_version,
+            FrameworkPackage::AxiomArchitecture => self.architecture_version,
+        }
+    }
+}
+
+/// A dependency as `Package.resolved` pins it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedPin {
+    /// SwiftPM identity, the lowercased last component of the location
+    pub identity: String,
+    pub location: String,
+    /// None for a pin to a branch or revision, or an unparsable version
+    pub version: Option<FrameworkVersion>,
+    pub branch: Option<String>,
+    pub revision: Option<String>,
+}
+
+/// `Package.resolved` as written by Swift 5.5 and earlier, with the pins
+/// under `object`, or as written since, with them at the top level
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PackageResolved {
+    V1 { object: PinsV1 },
+    V2 { pins: Vec<PinV2> },
+}
+
+#[derive(Deserialize)]
+struct PinsV1 {
+    pins: Vec<PinV1>,
+}
+
+#[derive(Deserialize)]
+struct PinV1 {
+    package: String,
+    #[serde(rename = "repositoryURL")]
+    repository_url: String,
+    state: PinState,
+}
+
+#[derive(Deserialize)]
+struct PinV2 {
+    identity: String,
+    location: String,
+    state: PinState,
+}
+
+#[derive(Deserialize)]
+struct PinState {
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    branch: Option<String>,
+    #[serde(default)]
+    revision: Option<String>,
+}
+
+impl ResolvedPin {
+    fn new(identity: String, location: String, state: PinState) -> Self {
+        Self {
+            identity,
+            location,
+            version: state.version.as_deref().and_then(FrameworkVersion::parse),
+            branch: state.branch,
+            revision: state.revision,
+        }
+    }
+}
+
+/// The pins of a `Package.resolved`, in either of its formats
+pub fn parse_package_resolved(contents: &str) -> Result<Vec<ResolvedPin>> {
+    let resolved: PackageResolved = serde_json::from_str(contents).map_err(|e| {
+        AxiomMCPError::validation("axiom_framework_integration", "parse Package.resolved", e.to_string()).with_cause(e)
+    })?;
+    Ok(match resolved {
+        PackageResolved::V1 { object } => object.pins
+            .into_iter()
+            .map(|pin| {
+                let identity = identity_of(&pin.repository_url).unwrap_or_else(|| pin.package.to_lowercase());
+                ResolvedPin::new(identity, pin.repository_url, pin.state)
+            })
+            .collect(),
+        PackageResolved::V2 { pins } => pins
+            .into_iter()
+            .map(|pin| ResolvedPin::new(pin.identity.to_lowercase(), pin.location, pin.state))
+            .collect(),
+    })
+}
+
+/// `https://github.com/tojkuv/AxiomCore.git` as a SwiftPM identity, `axiomcore`
+fn identity_of(location: &str) -> Option<String> {
+    let name = location.trim_end_matches('/').rsplit(['/', ':']).next()?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    (!name.is_empty()).then(|| name.to_lowercase())
+}
+
+/// Where a framework's version came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VersionSource {
+    Configured,
+    PackageResolved { path: PathBuf, identity: String },
+    /// Neither configured nor pinned; generated code targets the latest APIs
+    Undetected,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectedFramework {
+    pub package: FrameworkPackage,
+    /// None when undetected or pinned to a branch or revision
+    pub version: Option<FrameworkVersion>,
+    pub source: VersionSource,
+}
+
+/// The project's `Package.resolved`: at its root for a package, or in the
+/// shared data of its Xcode workspace or project
+pub async fn find_package_resolved(project_root: &Path) -> Option<PathBuf> {
+    let at_root = project_root.join(PACKAGE_RESOLVED_FILE);
+    if tokio::fs::metadata(&at_root).await.is_ok() {
+        return Some(at_root);
+    }
+
+    let mut entries = tokio::fs::read_dir(project_root).await.ok()?;
+    // A workspace's resolution covers the projects in it, so it comes first
+    let mut candidates = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("xcworkspace") => candidates.push((0, path.join("xcshareddata/swiftpm").join(PACKAGE_RESOLVED_FILE))),
+            Some("xcodeproj") => candidates.push((1, path.join("project.xcworkspace/xcshareddata/swiftpm").join(PACKAGE_RESOLVED_FILE))),
+            _ => {}
+        }
+    }
+    candidates.sort();
+    for (_, candidate) in candidates {
+        if tokio::fs::metadata(&candidate).await.is_ok() {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+/// The version of each framework, from `config` or else the project's
+/// `Package.resolved`
+pub async fn detect_framework_versions(project_root: &Path, config: &AxiomFrameworkConfig) -> Result<Vec<DetectedFramework>> {
+    let needs_resolution = FrameworkPackage::ALL.iter().any(|&package| config.version(package).is_none());
+    let path = if needs_resolution { find_package_resolved(project_root).await } else { None };
+    let resolved = match path {
+        Some(path) => {
+            let contents = tokio::fs::read_to_string(&path).await.map_err(|e| {
+                AxiomMCPError::validation("axiom_framework_integration", "read Package.resolved", format!(
+                    "Cannot read {}: {}", path.display(), e
+                )).with_cause(e)
+            })?;
+            let pins = parse_package_resolved(&contents).map_err(|e| match e {
+                AxiomMCPError::Validation(mut context) => {
+                    context.message = format!("{}: {}", path.display(), context.message);
+                    AxiomMCPError::Validation(context)
+                }
+                e => e,
+            })?;
+            Some((path, pins))
+        }
+        None => None,
+    };
+
+    Ok(FrameworkPackage::ALL
+        .into_iter()
+        .map(|package| {
+            if let Some(version) = config.version(package) {
+                return DetectedFramework { package, version: Some(version), source: VersionSource::Configured };
+            }
+            let pinned = resolved.as_ref().and_then(|(path, pins)| {
+                pins.iter().find(|pin| package.provided_by(&pin.identity)).map(|pin| (path, pin))
+            });
+            match pinned {
+                Some((path, pin)) => DetectedFramework {
+                    package,
+                    version: pin.version,
+                    source: VersionSource::PackageResolved { path: path.clone(), identity: pin.identity.clone() },
+                },
+                None => DetectedFramework { package, version: None, source: VersionSource::Undetected },
+            }
+        })
+        .collect())
+}
+
+/// Code generation features that need a minimum framework version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenerationFeature {
+    /// Contexts inheriting `AxiomClientObservingContext`, which observes
+    /// the client's state stream
+    ClientObservingContext,
+    /// Clients loading and saving through a generated SwiftData repository
+    SwiftDataPersistence,
+}
+
+impl GenerationFeature {
+    pub fn name(self) -> &'static str {
+        match self {
+            GenerationFeature::ClientObservingContext => "client_observing_context",
+            GenerationFeature::SwiftDataPersistence => "swift_data_persistence",
+        }
+    }
+}
+
+/// A row of the compatibility matrix
+#[derive(Debug, Clone, Copy)]
+pub struct FeatureFloor {
+    pub feature: GenerationFeature,
+    pub package: FrameworkPackage,
+    pub minimum: FrameworkVersion,
+    /// The older pattern generated below the floor; without one the feature
+    /// is generated as is, with a warning
+    pub fallback: Option<&'static str>,
+}
+
+/// Minimum framework version of each generation feature
+pub const COMPATIBILITY_MATRIX: &[FeatureFloor] = &[
+    FeatureFloor {
+        feature: GenerationFeature::ClientObservingContext,
+        package: FrameworkPackage::AxiomArchitecture,
+        minimum: FrameworkVersion::new(1, 1, 0),
+        fallback: Some("contexts inherit AxiomContext and observe their client themselves"),
+    },
+    FeatureFloor {
+        feature: GenerationFeature::SwiftDataPersistence,
+        package: FrameworkPackage::AxiomCore,
+        minimum: FrameworkVersion::new(1, 2, 0),
+        fallback: None,
+    },
+];
+
+/// A feature generated differently, or with a warning, for a framework
+/// below its floor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureDowngrade {
+    pub feature: GenerationFeature,
+    pub package: FrameworkPackage,
+    pub detected: FrameworkVersion,
+    pub minimum: FrameworkVersion,
+    pub fallback: Option<String>,
+}
+
+impl FeatureDowngrade {
+    pub fn warning(&self) -> String {
+        let feature = self.feature.name();
+        match &self.fallback {
+            Some(fallback) => format!(
+                "{} {} is below {} for {}; {}", self.package.name(), self.detected, self.minimum, feature, fallback
+            ),
+            None => format!(
+                "{} {} is below {} for {}, which has no older pattern; the generated code may not compile",
+                self.package.name(), self.detected, self.minimum, feature
+            ),
+        }
+    }
+}
+
+/// The framework versions code is generated for, and the features
+/// downgraded for them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameworkCompatibility {
+    pub frameworks: Vec<DetectedFramework>,
+    pub downgrades: Vec<FeatureDowngrade>,
+    pub warnings: Vec<String>,
+}
+
+impl FrameworkCompatibility {
+    /// Apply the compatibility matrix; a framework without a version is
+    /// assumed to be the latest
+    pub fn for_frameworks(frameworks: Vec<DetectedFramework>) -> Self {
+        let downgrades: Vec<FeatureDowngrade> = COMPATIBILITY_MATRIX
+            .iter()
+            .filter_map(|floor| {
+                let detected = frameworks.iter().find(|framework| framework.package == floor.package)?.version?;
+                (detected < floor.minimum).then(|| FeatureDowngrade {
+                    feature: floor.feature,
+                    package: floor.package,
+                    detected,
+                    minimum: floor.minimum,
+                    fallback: floor.fallback.map(str::to_string),
+                })
+            })
+            .collect();
+        let warnings = frameworks
+            .iter()
+            .filter(|framework| framework.version.is_none())
+            .map(|framework| match &framework.source {
+                VersionSource::PackageResolved { path, identity } => format!(
+                    "{} is pinned to a branch or revision in {} ({}); generating for the latest APIs",
+                    framework.package.name(), path.display(), identity
+                ),
+                _ => format!("{} version not detected; generating for the latest APIs", framework.package.name()),
+            })
+            .chain(downgrades.iter().map(FeatureDowngrade::warning))
+            .collect();
+        Self { frameworks, downgrades, warnings }
+    }
+
+    pub fn downgrade(&self, feature: GenerationFeature) -> Option<&FeatureDowngrade> {
+        self.downgrades.iter().find(|downgrade| downgrade.feature == feature)
+    }
+
+    pub fn version(&self, package: FrameworkPackage) -> Option<FrameworkVersion> {
+        self.frameworks.iter().find(|framework| framework.package == package)?.version
+    }
+
+    /// The class generated contexts inherit
+    pub fn context_base_class(&self) -> &'static str {
+        if self.downgrade(GenerationFeature::ClientObservingContext).is_some() {
+            LEGACY_CONTEXT_BASE_CLASS
+        } else {
+            CONTEXT_BASE_CLASS
+        }
+    }
+}
@@ -1,41 +1,453 @@
 use crate::types::*;
-use crate::error::Result;
+use crate::error::{AxiomMCPError, Result};
+use crate::swift_validation::{SwiftSyntaxChecker, SwiftValidator};
+use crate::axiom_framework_integration::{FrameworkCompatibility, GenerationFeature};
+use crate::generation_cache::{spec_hash, GenerationCache, GenerationCacheStats};
+use crate::tool_metrics::record_stage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Templates in this directory replace the built-in ones of the same file name
+pub const PROJECT_TEMPLATES_DIR: &str = ".axiom/templates";
+
+/// The code the generator renders from a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateKind {
+    Context,
+    Presentation,
+    Client,
+}
+
+impl TemplateKind {
+    pub const ALL: [TemplateKind; 3] = [TemplateKind::Context, TemplateKind::Presentation, TemplateKind::Client];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TemplateKind::Context => "context",
+            TemplateKind::Presentation => "presentation",
+            TemplateKind::Client => "client",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// File name of an override in the project templates directory
+    pub fn file_name(self) -> String {
+        format!("{}.swift.template", self.name())
+    }
+
+    /// The template shipped with the crate, a starting point for overrides
+    pub fn built_in(self) -> &'static str {
+        match self {
+            TemplateKind::Context => include_str!("../templates/context.swift.template"),
+            TemplateKind::Presentation => include_str!("../templates/presentation.swift.template"),
+            TemplateKind::Client => include_str!("../templates/client.swift.template"),
+        }
+    }
+
+    /// Variables a template of this kind may use as `{name}`, with what
+    /// the generator fills in
+    pub fn variables(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            TemplateKind::Context => &[
+                ("name", "Context class name"),
+                ("imports", "Import statements, `import SwiftUI` by default"),
+                ("base_class", "Class the context inherits from, `AxiomClientObservingContext` by default and `AxiomContext` for frameworks without client observation"),
+                ("properties", "One indented `@Published var` line per state property"),
+                ("client_binding", "Type of the client the context observes"),
+            ],
+            TemplateKind::Presentation => &[
+                ("name", "View struct name"),
+                ("imports", "Import statements, `import SwiftUI` by default"),
+                ("context_binding", "Context type injected as an environment object"),
+                ("ui_components", "Components of the body, one per line"),
+                ("title", "Navigation title, the name without its `View` suffix"),
+            ],
+            TemplateKind::Client => &[
+                ("name", "Client actor name"),
+                ("imports", "Import statements, `import Foundation` by default, and `import SwiftData` with persistence"),
+                ("protocols", "Protocols the actor conforms to, `AxiomClient` first"),
+                ("actions", "One indented mock method per action, after the repository and its methods with persistence"),
+            ],
+        }
+    }
+}
+
+/// Where an active template was read from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TemplateSource {
+    BuiltIn,
+    Project { path: PathBuf },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub description: String,
+}
+
+/// An active template as reported by `list_code_generation_templates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateInfo {
+    pub kind: TemplateKind,
+    pub file_name: String,
+    pub source: TemplateSource,
+    pub variables: Vec<TemplateVariable>,
+}
+
+/// A SwiftData entity generated as a `@Model` class, with a container
+/// helper and a repository the mock client loads and saves through
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistenceSpec {
+    pub entity_name: String,
+    pub fields: Vec<PersistedField>,
+    #[serde(default)]
+    pub relationships: Vec<EntityRelationship>,
+    #[serde(default)]
+    pub migration_strategy: MigrationStrategy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedField {
+    pub name: String,
+    /// Swift type, without the `?` of an optional field
+    pub field_type: String,
+    #[serde(default)]
+    pub optional: bool,
+    /// Swift expression the initializer defaults the field to; optional
+    /// fields default to `nil` without one
+    #[serde(default)]
+    pub default_value: Option<String>,
+    /// Marked `@Attribute(.unique)`
+    #[serde(default)]
+    pub unique: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityRelationship {
+    pub name: String,
+    pub target_entity: String,
+    #[serde(default)]
+    pub kind: RelationshipKind,
+    /// Property of the target entity pointing back at this one
+    #[serde(default)]
+    pub inverse: Option<String>,
+    #[serde(default)]
+    pub delete_rule: DeleteRule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationshipKind {
+    /// An optional reference to one target
+    #[default]
+    ToOne,
+    /// An array of targets, empty by default
+    ToMany,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeleteRule {
+    #[default]
+    Nullify,
+    Cascade,
+    Deny,
+}
+
+impl DeleteRule {
+    fn swift_name(self) -> &'static str {
+        match self {
+            DeleteRule::Nullify => "nullify",
+            DeleteRule::Cascade => "cascade",
+            DeleteRule::Deny => "deny",
+        }
+    }
+}
+
+/// How the store is migrated when the models change
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MigrationStrategy {
+    /// SwiftData's automatic lightweight migration
+    #[default]
+    Automatic,
+    /// A `VersionedSchema` at `version` and an empty `SchemaMigrationPlan`
+    /// to add stages to
+    Versioned { version: u32 },
+}
+
+impl PersistenceSpec {
+    fn validate(&self) -> Result<()> {
+        if !is_swift_identifier(&self.entity_name) {
+            return Err(AxiomMCPError::validation("code_generation", "generate persistence", format!(
+                "Entity name '{}' is not a Swift type name", self.entity_name
+            )));
+        }
+        if self.fields.is_empty() {
+            return Err(AxiomMCPError::validation("code_generation", "generate persistence", format!(
+                "Entity {} has no fields", self.entity_name
+            )));
+        }
+        
+        let names = self.fields.iter().map(|field| &field.name)
+            .chain(self.relationships.iter().map(|relationship| &relationship.name));
+        let mut seen = std::collections::HashSet::new();
+        for name in names {
+            if !is_swift_identifier(name) {
+                return Err(AxiomMCPError::validation("code_generation", "generate persistence", format!(
+                    "Property name '{}' of {} is not a Swift identifier", name, self.entity_name
+                )));
+            }
+            if !seen.insert(name) {
+                return Err(AxiomMCPError::validation("code_generation", "generate persistence", format!(
+                    "Entity {} declares '{}' more than once", self.entity_name, name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Screens connected by a `NavigationStack`, generated as a typed `Route`
+/// enum, an `AppCoordinator` owning the navigation state and hooks through
+/// which each presentation triggers its routes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NavigationSpec {
+    /// Presentation at the bottom of the stack
+    pub root: String,
+    pub routes: Vec<RouteSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteSpec {
+    /// Case of the `Route` enum, `taskDetail`
+    pub name: String,
+    /// Presentation the route shows, initialized with the parameters
+    pub presentation: String,
+    /// Values the presenting screen passes along, as associated values
+    #[serde(default)]
+    pub parameters: Vec<RouteParameter>,
+    #[serde(default)]
+    pub style: PresentationStyle,
+    /// Presentations that navigate to the route; the root when empty
+    #[serde(default)]
+    pub from: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteParameter {
+    pub name: String,
+    /// Swift type, which must be `Hashable`
+    pub parameter_type: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresentationStyle {
+    /// Pushed onto the navigation path
+    #[default]
+    Push,
+    Sheet,
+    FullScreen,
+}
+
+impl NavigationSpec {
+    /// Presentations the navigation shows or navigates from, the root first
+    pub fn presentations(&self) -> Vec<&str> {
+        let mut presentations = vec![self.root.as_str()];
+        for route in &self.routes {
+            for presentation in std::iter::once(&route.presentation).chain(&route.from) {
+                if !presentations.contains(&presentation.as_str()) {
+                    presentations.push(presentation);
+                }
+            }
+        }
+        presentations
+    }
+    
+    /// Every presentation the navigation refers to must have been generated
+    /// alongside it
+    pub fn check_presentations(&self, layer: &PresentationLayer) -> Result<()> {
+        let missing: Vec<&str> = self.presentations()
+            .into_iter()
+            .filter(|name| !layer.presentations.iter().any(|presentation| presentation.name == *name))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        
+        Err(AxiomMCPError::generation("code_generation", "generate navigation", format!(
+            "Navigation refers to presentations missing from the implementation: {}",
+            missing.join(", ")
+        )))
+    }
+    
+    fn validate(&self) -> Result<()> {
+        if self.routes.is_empty() {
+            return Err(AxiomMCPError::validation("code_generation", "generate navigation", format!(
+                "Navigation from {} has no routes", self.root
+            )));
+        }
+        
+        let mut seen = std::collections::HashSet::new();
+        for route in &self.routes {
+            let names = std::iter::once(&route.name).chain(route.parameters.iter().map(|parameter| &parameter.name));
+            let types = std::iter::once(&route.presentation).chain(&route.from);
+            if let Some(name) = names.chain(types).find(|name| !is_swift_identifier(name)) {
+                return Err(AxiomMCPError::validation("code_generation", "generate navigation", format!(
+                    "Route {} uses '{}', which is not a Swift identifier", route.name, name
+                )));
+            }
+            if !seen.insert(&route.name) {
+                return Err(AxiomMCPError::validation("code_generation", "generate navigation", format!(
+                    "Route {} is declared more than once", route.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ActiveTemplate {
+    source: TemplateSource,
+    contents: String,
+}
 
 /// Axiom-compliant code generator
+#[derive(Debug)]
 pub struct AxiomCodeGenerator {
-    templates: std::collections::HashMap<String, String>,
+    templates: BTreeMap<TemplateKind, ActiveTemplate>,
+    validator: SwiftValidator,
+    syntax_checked: bool,
+    framework: FrameworkCompatibility,
+    cache: Arc<GenerationCache>,
 }
 
 impl AxiomCodeGenerator {
+    /// A generator using the built-in templates
     pub async fn new() -> Result<Self> {
-        let mut templates = std::collections::HashMap::new();
-        
-        templates.insert("context".to_string(), include_str!("../templates/context.swift.template").to_string());
-        templates.insert("presentation".to_string(), include_str!("../templates/presentation.swift.template").to_string());
-        templates.insert("client".to_string(), include_str!("../templates/client.swift.template").to_string());
+        let templates = TemplateKind::ALL
+            .into_iter()
+            .map(|kind| (kind, ActiveTemplate { source: TemplateSource::BuiltIn, contents: kind.built_in().to_string() }))
+            .collect();
         
-        Ok(Self { templates })
+        Ok(Self {
+            templates,
+            validator: SwiftValidator::new(),
+            syntax_checked: false,
+            framework: FrameworkCompatibility::default(),
+            cache: Arc::new(GenerationCache::default()),
+        })
+    }
+    
+    /// A generator using the templates under `project_root`'s
+    /// `PROJECT_TEMPLATES_DIR`, and the built-in ones for the rest
+    pub async fn for_project(project_root: &Path) -> Result<Self> {
+        Self::new().await?.with_template_dir(&project_root.join(PROJECT_TEMPLATES_DIR)).await
+    }
+    
+    /// Replace built-in templates with those in `dir`; each one found is
+    /// validated, and a missing file or directory keeps the built-in
+    pub async fn with_template_dir(mut self, dir: &Path) -> Result<Self> {
+        for kind in TemplateKind::ALL {
+            let path = dir.join(kind.file_name());
+            let contents = match tokio::fs::read_to_string(&path).await {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(AxiomMCPError::generation(
+                        "code_generation", "load templates", format!("Cannot read template {}: {}", path.display(), e),
+                    ).with_cause(e));
+                }
+            };
+            validate_template(kind, &contents).map_err(|e| match e {
+                AxiomMCPError::Generation(mut context) => {
+                    context.message = format!("Template {}: {}", path.display(), context.message);
+                    AxiomMCPError::Generation(context)
+                }
+                e => e,
+            })?;
+            
+            tracing::info!("Using {} template from {}", kind.name(), path.display());
+            self.templates.insert(kind, ActiveTemplate { source: TemplateSource::Project { path }, contents });
+        }
+        Ok(self)
+    }
+    
+    /// Also parse generated code with `checker`, typically `swiftc` found by
+    /// `SwiftcSyntaxChecker::detect`; without one only the structural and
+    /// pattern checks run
+    pub fn with_syntax_checker(mut self, checker: Arc<dyn SwiftSyntaxChecker>) -> Self {
+        self.validator = self.validator.with_syntax_checker(checker);
+        self.syntax_checked = true;
+        self
+    }
+    
+    /// Generate for the detected framework versions, selecting older
+    /// patterns for features they are below the floor of
+    pub fn with_framework_compatibility(mut self, framework: FrameworkCompatibility) -> Self {
+        self.framework = framework;
+        self
+    }
+    
+    pub fn framework_compatibility(&self) -> &FrameworkCompatibility {
+        &self.framework
+    }
+    
+    /// Keep generated code and validations in `cache`, which generators
+    /// with other templates can share since entries are keyed by them
+    pub fn with_cache(mut self, cache: Arc<GenerationCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+    
+    pub fn cache_stats(&self) -> GenerationCacheStats {
+        self.cache.stats()
+    }
+    
+    /// Forget cached generations, returning the cache's stats from just before
+    pub fn clear_cache(&self) -> GenerationCacheStats {
+        self.cache.clear()
+    }
+    
+    /// The active template of each kind and where it came from
+    pub fn templates(&self) -> Vec<TemplateInfo> {
+        self.templates
+            .iter()
+            .map(|(&kind, template)| TemplateInfo {
+                kind,
+                file_name: kind.file_name(),
+                source: template.source.clone(),
+                variables: kind
+                    .variables()
+                    .iter()
+                    .map(|&(name, description)| TemplateVariable { name: name.to_string(), description: description.to_string() })
+                    .collect(),
+            })
+            .collect()
     }
     
     pub async fn generate_presentation(&self, spec: PresentationSpec) -> Result<GeneratedCode> {
-        let code = format!(
-            r#"import SwiftUI
-
-struct {} : View {{
-    @EnvironmentObject var context: {}
+        self.cached("presentation", &spec, || self.render_presentation(&spec))
+    }
     
-    var body: some View {{
-        VStack {{
-            {}
-        }}
-        .navigationTitle("{}")
-    }}
-}}"#,
-            spec.name,
-            spec.context_binding,
-            spec.ui_components.join("\n            "),
-            spec.name.replace("View", "")
-        );
+    fn render_presentation(&self, spec: &PresentationSpec) -> Result<GeneratedCode> {
+        let variables = HashMap::from([
+            ("name", spec.name.clone()),
+            ("imports", "import SwiftUI".to_string()),
+            ("context_binding", spec.context_binding.clone()),
+            ("ui_components", spec.ui_components.join("\n            ")),
+            ("title", navigation_title(&spec.name)),
+        ]);
+        let code = self.render(TemplateKind::Presentation, variables, &spec.template_overrides)?;
         
         Ok(GeneratedCode {
             generated_code: code,
@@ -46,6 +458,10 @@ struct {} : View {{
     }
     
     pub async fn generate_context(&self, spec: ContextSpec) -> Result<GeneratedCode> {
+        self.cached("context", &spec, || self.render_context(&spec))
+    }
+    
+    fn render_context(&self, spec: &ContextSpec) -> Result<GeneratedCode> {
         let properties = spec.state_properties.iter()
             .map(|prop| format!("    @Published var {}: {} = {}", 
                 prop.name, 
@@ -54,25 +470,14 @@ struct {} : View {{
             .collect::<Vec<_>>()
             .join("\n");
         
-        let code = format!(
-            r#"import SwiftUI
-
-@MainActor
-class {}: AxiomClientObservingContext {{
-{}
-    
-    private let client: {}
-    
-    init(client: {}) {{
-        self.client = client
-        super.init()
-    }}
-}}"#,
-            spec.name,
-            properties,
-            spec.client_binding,
-            spec.client_binding
-        );
+        let variables = HashMap::from([
+            ("name", spec.name.clone()),
+            ("imports", "import SwiftUI".to_string()),
+            ("base_class", self.framework.context_base_class().to_string()),
+            ("properties", properties),
+            ("client_binding", spec.client_binding.clone()),
+        ]);
+        let code = self.render(TemplateKind::Context, variables, &spec.template_overrides)?;
         
         Ok(GeneratedCode {
             generated_code: code,
@@ -82,8 +487,21 @@ class {}: AxiomClientObservingContext {{
         })
     }
     
+    /// With `spec.persistence`, the client also holds the entity's
+    /// repository, and actions loading, saving and deleting through it
+    /// replace mock actions of the same name
     pub async fn generate_mock_client(&self, spec: ClientSpec) -> Result<GeneratedCode> {
-        let actions = spec.actions.iter()
+        self.cached("mock_client", &spec, || self.render_mock_client(&spec))
+    }
+    
+    fn render_mock_client(&self, spec: &ClientSpec) -> Result<GeneratedCode> {
+        if let Some(persistence) = &spec.persistence {
+            persistence.validate()?;
+            self.warn_if_downgraded(GenerationFeature::SwiftDataPersistence);
+        }
+        let persistent_actions = spec.persistence.as_ref().map(repository_actions).unwrap_or_default();
+        let mock_actions = spec.actions.iter()
+            .filter(|action| !persistent_actions.iter().any(|(name, _)| *name == action.name))
             .map(|action| format!("    {} func {}({}) -> {} {{
         // Mock implementation
         return {}
@@ -93,18 +511,24 @@ class {}: AxiomClientObservingContext {{
                 action.parameters.join(", "),
                 action.return_type,
                 if action.return_type == "Void" { "()" } else { "/* mock value */" }))
+            .collect::<Vec<_>>();
+        let actions = spec.persistence.as_ref().map(repository_storage).into_iter()
+            .chain(persistent_actions.into_iter().map(|(_, code)| code))
+            .chain(mock_actions)
             .collect::<Vec<_>>()
             .join("\n\n");
+        let imports = if spec.persistence.is_some() { "import Foundation\nimport SwiftData" } else { "import Foundation" };
         
-        let code = format!(
-            r#"import Foundation
-
-actor {}: AxiomClient {{
-{}
-}}"#,
-            spec.name,
-            actions
-        );
+        let mut protocols = vec!["AxiomClient"];
+        protocols.extend(spec.protocol_conformance.iter().map(String::as_str).filter(|p| *p != "AxiomClient"));
+        
+        let variables = HashMap::from([
+            ("name", spec.name.clone()),
+            ("imports", imports.to_string()),
+            ("protocols", protocols.join(", ")),
+            ("actions", actions),
+        ]);
+        let code = self.render(TemplateKind::Client, variables, &spec.template_overrides)?;
         
         Ok(GeneratedCode {
             generated_code: code,
@@ -114,36 +538,550 @@ actor {}: AxiomClient {{
         })
     }
     
-    pub async fn validate_generated_code(&self, code: &str) -> Result<ValidationResult> {
-        // Basic validation checks
-        let mut issues = Vec::new();
-        let mut score = 100.0;
+    /// The `Route` enum, the `AppCoordinator` presenting routes by their
+    /// style, the `AppNavigationView` hosting the stack and, for each
+    /// presentation that navigates, a `{Presentation}Routes` hook
+    pub async fn generate_navigation(&self, spec: NavigationSpec) -> Result<GeneratedCode> {
+        self.cached("navigation", &spec, || Self::render_navigation(&spec))
+    }
+    
+    fn render_navigation(spec: &NavigationSpec) -> Result<GeneratedCode> {
+        spec.validate()?;
         
-        if !code.contains("import") {
-            issues.push("Missing import statement".to_string());
-            score -= 10.0;
+        let mut sections = vec![route_enum(spec), coordinator_class(spec), navigation_view(spec)];
+        sections.extend(spec.presentations().into_iter().filter_map(|presentation| route_hooks(spec, presentation)));
+        
+        Ok(GeneratedCode {
+            generated_code: format!("import SwiftUI\n\n{}\n", sections.join("\n\n")),
+            validation_passed: true,
+            performance_score: 90.0,
+            compliance_score: 96.0,
+        })
+    }
+    
+    /// The entity's `@Model` class, a helper creating its `ModelContainer`,
+    /// and the repository actor generated clients use
+    pub async fn generate_persistence(&self, spec: PersistenceSpec) -> Result<GeneratedCode> {
+        self.cached("persistence", &spec, || self.render_persistence(&spec))
+    }
+    
+    fn render_persistence(&self, spec: &PersistenceSpec) -> Result<GeneratedCode> {
+        spec.validate()?;
+        self.warn_if_downgraded(GenerationFeature::SwiftDataPersistence);
+        
+        let mut sections = vec![model_class(spec)];
+        if let MigrationStrategy::Versioned { version } = spec.migration_strategy {
+            sections.push(versioned_schema(spec, version));
         }
+        sections.push(container_helper(spec));
+        sections.push(repository_actor(spec));
         
-        Ok(ValidationResult {
-            passed: issues.is_empty(),
-            overall_score: score,
-            architecture_compliance: 95.0,
-            type_safety_score: 98.0,
-            performance_score: 85.0,
-            issues,
-            recommendations: vec![],
+        Ok(GeneratedCode {
+            generated_code: format!("import Foundation\nimport SwiftData\n\n{}\n", sections.join("\n\n")),
+            validation_passed: true,
+            performance_score: 90.0,
+            compliance_score: 95.0,
         })
     }
     
-    pub async fn process_template(&self, template_name: &str, data: &std::collections::HashMap<String, String>) -> Result<String> {
-        let template = self.templates.get(template_name)
-            .ok_or_else(|| crate::error::AxiomMCPError::ValidationError(format!("Template not found: {}", template_name)))?;
+    /// The cached code for `spec`, or what `generate` renders; failures are
+    /// not cached
+    fn cached<S: Serialize>(&self, operation: &str, spec: &S, generate: impl FnOnce() -> Result<GeneratedCode>) -> Result<GeneratedCode> {
+        let key = spec_hash(operation, spec, &self.generation_version()?)?;
+        if let Some(code) = self.cache.code(&key) {
+            tracing::debug!("Reusing cached {} generation {}", operation, &key[..12]);
+            return Ok(code);
+        }
+        let started = std::time::Instant::now();
+        let code = generate()?;
+        record_stage(format!("generate {}", operation), started.elapsed());
+        self.cache.insert_code(key, code.clone());
+        Ok(code)
+    }
+    
+    /// Hash of everything besides the spec that shapes generated code: the
+    /// active templates and the framework versions generated for
+    fn generation_version(&self) -> Result<String> {
+        let templates: BTreeMap<&str, &str> = self.templates
+            .iter()
+            .map(|(kind, template)| (kind.name(), template.contents.as_str()))
+            .collect();
+        spec_hash("templates", &(templates, &self.framework), env!("CARGO_PKG_VERSION"))
+    }
+    
+    fn warn_if_downgraded(&self, feature: GenerationFeature) {
+        if let Some(downgrade) = self.framework.downgrade(feature) {
+            tracing::warn!("{}", downgrade.warning());
+        }
+    }
+    
+    /// Render the active template of `kind`, with `overrides` taking
+    /// precedence over the derived `variables`
+    fn render(&self, kind: TemplateKind, mut variables: HashMap<&str, String>, overrides: &HashMap<String, String>) -> Result<String> {
+        for (name, value) in overrides {
+            let Some(&(known, _)) = kind.variables().iter().find(|(known, _)| known == name) else {
+                return Err(AxiomMCPError::validation("code_generation", "render template", format!(
+                    "Unknown {} template variable '{}'; expected one of {}",
+                    kind.name(), name, variable_names(kind)
+                )));
+            };
+            variables.insert(known, value.clone());
+        }
+        
+        Ok(render_template(&self.templates[&kind].contents, |name| variables.get(name).map(String::as_str)))
+    }
+    
+    /// Syntax errors fail validation; Axiom pattern warnings only lower
+    /// the scores
+    pub async fn validate_generated_code(&self, code: &str) -> Result<ValidationResult> {
+        let checks = if self.syntax_checked { "syntax" } else { "structural" };
+        let key = spec_hash("validation", &code, checks)?;
+        if let Some(validation) = self.cache.validation(&key) {
+            return Ok(validation);
+        }
+        let validation = self.validate_uncached(code).await;
+        self.cache.insert_validation(key, validation.clone());
+        Ok(validation)
+    }
+    
+    async fn validate_uncached(&self, code: &str) -> ValidationResult {
+        let validation = self.validator.validate(code).await;
+        let errors = validation.syntax_errors.len() as f64;
+        let warnings = validation.pattern_warnings.len() as f64;
         
-        let mut result = template.clone();
-        for (key, value) in data {
-            result = result.replace(&format!("{{{}}}", key), value);
+        ValidationResult {
+            passed: validation.syntax_errors.is_empty(),
+            overall_score: (100.0 - 25.0 * errors - 5.0 * warnings).max(0.0),
+            architecture_compliance: (100.0 - 10.0 * warnings).max(0.0),
+            type_safety_score: 98.0,
+            performance_score: 85.0,
+            issues: validation.syntax_errors.iter().map(ToString::to_string).collect(),
+            recommendations: validation.pattern_warnings.iter().map(ToString::to_string).collect(),
+            syntax_errors: validation.syntax_errors,
+            pattern_warnings: validation.pattern_warnings,
+            syntax_checked: validation.syntax_checked,
         }
+    }
+    
+    pub async fn process_template(&self, template_name: &str, data: &HashMap<String, String>) -> Result<String> {
+        let template = TemplateKind::from_name(template_name)
+            .and_then(|kind| self.templates.get(&kind))
+            .ok_or_else(|| AxiomMCPError::validation("code_generation", "process template", format!("Template not found: {}", template_name)))?;
         
-        Ok(result)
+        Ok(render_template(&template.contents, |name| data.get(name).map(String::as_str)))
     }
-}
\ No newline at end of file
+}
+
+fn variable_names(kind: TemplateKind) -> String {
+    kind.variables().iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+}
+
+pub(crate) fn is_swift_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// `TaskItem` as a variable name, `taskItem`
+pub(crate) fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_lowercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// English plural of an entity name, good enough for method names
+pub(crate) fn plural(name: &str) -> String {
+    let consonant_y = name.strip_suffix('y')
+        .filter(|stem| stem.ends_with(|c: char| !"aeiouAEIOU".contains(c)));
+    if let Some(stem) = consonant_y {
+        format!("{}ies", stem)
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| name.ends_with(suffix)) {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+/// Types in the entity's schema: the entity and the targets of its relationships
+fn schema_models(spec: &PersistenceSpec) -> String {
+    let mut models = vec![spec.entity_name.as_str()];
+    for relationship in &spec.relationships {
+        if !models.contains(&relationship.target_entity.as_str()) {
+            models.push(&relationship.target_entity);
+        }
+    }
+    models.iter().map(|model| format!("{}.self", model)).collect::<Vec<_>>().join(", ")
+}
+
+fn model_class(spec: &PersistenceSpec) -> String {
+    let mut properties = Vec::new();
+    let mut parameters = Vec::new();
+    let mut assignments = Vec::new();
+
+    for field in &spec.fields {
+        let field_type = if field.optional { format!("{}?", field.field_type) } else { field.field_type.clone() };
+        let attribute = if field.unique { "@Attribute(.unique) " } else { "" };
+        properties.push(format!("    {}var {}: {}", attribute, field.name, field_type));
+        parameters.push(match field.default_value.as_deref().or(field.optional.then_some("nil")) {
+            Some(default) => format!("{}: {} = {}", field.name, field_type, default),
+            None => format!("{}: {}", field.name, field_type),
+        });
+        assignments.push(format!("        self.{0} = {0}", field.name));
+    }
+
+    for relationship in &spec.relationships {
+        let mut arguments = Vec::new();
+        if relationship.delete_rule != DeleteRule::Nullify {
+            arguments.push(format!("deleteRule: .{}", relationship.delete_rule.swift_name()));
+        }
+        if let Some(inverse) = &relationship.inverse {
+            arguments.push(format!("inverse: \\{}.{}", relationship.target_entity, inverse));
+        }
+        let attribute = if arguments.is_empty() { String::new() } else { format!("@Relationship({}) ", arguments.join(", ")) };
+        let declaration = match relationship.kind {
+            RelationshipKind::ToOne => format!("{}: {}?", relationship.name, relationship.target_entity),
+            RelationshipKind::ToMany => format!("{}: [{}] = []", relationship.name, relationship.target_entity),
+        };
+        properties.push(format!("    {}var {}", attribute, declaration));
+    }
+
+    format!(
+        "@Model\nfinal class {} {{\n{}\n\n    init({}) {{\n{}\n    }}\n}}",
+        spec.entity_name,
+        properties.join("\n"),
+        parameters.join(", "),
+        assignments.join("\n")
+    )
+}
+
+fn versioned_schema(spec: &PersistenceSpec, version: u32) -> String {
+    let entity = &spec.entity_name;
+    format!(
+        r#"enum {entity}SchemaV{version}: VersionedSchema {{
+    static let versionIdentifier = Schema.Version({version}, 0, 0)
+    static var models: [any PersistentModel.Type] {{ [{models}] }}
+}}
+
+enum {entity}MigrationPlan: SchemaMigrationPlan {{
+    static var schemas: [any VersionedSchema.Type] {{ [{entity}SchemaV{version}.self] }}
+    static var stages: [MigrationStage] {{ [] }}
+}}"#,
+        entity = entity,
+        version = version,
+        models = schema_models(spec)
+    )
+}
+
+fn container_helper(spec: &PersistenceSpec) -> String {
+    let (schema, container) = match spec.migration_strategy {
+        MigrationStrategy::Automatic => (
+            format!("Schema([{}])", schema_models(spec)),
+            "ModelContainer(for: schema, configurations: [configuration])".to_string(),
+        ),
+        MigrationStrategy::Versioned { version } => (
+            format!("Schema(versionedSchema: {}SchemaV{}.self)", spec.entity_name, version),
+            format!("ModelContainer(for: schema, migrationPlan: {}MigrationPlan.self, configurations: [configuration])", spec.entity_name),
+        ),
+    };
+    format!(
+        r#"enum {entity}Persistence {{
+    static let schema = {schema}
+
+    /// Keep the store in memory for previews and tests
+    static func makeContainer(inMemory: Bool = false) throws -> ModelContainer {{
+        let configuration = ModelConfiguration(schema: schema, isStoredInMemoryOnly: inMemory)
+        return try {container}
+    }}
+}}"#,
+        entity = spec.entity_name,
+        schema = schema,
+        container = container
+    )
+}
+
+fn repository_actor(spec: &PersistenceSpec) -> String {
+    format!(
+        r#"@ModelActor
+actor {entity}Repository {{
+    func loadAll() throws -> [{entity}] {{
+        try modelContext.fetch(FetchDescriptor<{entity}>())
+    }}
+
+    func save(_ {variable}: {entity}) throws {{
+        modelContext.insert({variable})
+        try modelContext.save()
+    }}
+
+    func delete(_ {variable}: {entity}) throws {{
+        modelContext.delete({variable})
+        try modelContext.save()
+    }}
+}}"#,
+        entity = spec.entity_name,
+        variable = lower_first(&spec.entity_name)
+    )
+}
+
+/// The repository a persistent client holds, created from the app's container
+fn repository_storage(spec: &PersistenceSpec) -> String {
+    format!(
+        r#"    private let repository: {entity}Repository
+
+    init(modelContainer: ModelContainer) {{
+        repository = {entity}Repository(modelContainer: modelContainer)
+    }}"#,
+        entity = spec.entity_name
+    )
+}
+
+/// Client actions backed by the repository, with their names
+fn repository_actions(spec: &PersistenceSpec) -> Vec<(String, String)> {
+    let entity = &spec.entity_name;
+    let variable = lower_first(entity);
+    let load = format!("load{}", plural(entity));
+    let save = format!("save{}", entity);
+    let delete = format!("delete{}", entity);
+    vec![
+        (load.clone(), format!(
+            "    func {}() async throws -> [{}] {{\n        try await repository.loadAll()\n    }}",
+            load, entity
+        )),
+        (save.clone(), format!(
+            "    func {0}(_ {1}: {2}) async throws {{\n        try await repository.save({1})\n    }}",
+            save, variable, entity
+        )),
+        (delete.clone(), format!(
+            "    func {0}(_ {1}: {2}) async throws {{\n        try await repository.delete({1})\n    }}",
+            delete, variable, entity
+        )),
+    ]
+}
+
+/// `taskDetail` as part of a type or method name, `TaskDetail`
+pub(crate) fn upper_first(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// `name: Type, other: Type`, as declared by a route case or hook
+fn route_parameters(route: &RouteSpec) -> String {
+    route.parameters.iter()
+        .map(|parameter| format!("{}: {}", parameter.name, parameter.parameter_type))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `.taskDetail(id: id)`, the route built from parameters of the same names
+fn route_value(route: &RouteSpec) -> String {
+    if route.parameters.is_empty() {
+        return format!(".{}", route.name);
+    }
+    let arguments = route.parameters.iter()
+        .map(|parameter| format!("{0}: {0}", parameter.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(".{}({})", route.name, arguments)
+}
+
+fn route_enum(spec: &NavigationSpec) -> String {
+    let cases = spec.routes.iter()
+        .map(|route| if route.parameters.is_empty() {
+            format!("    case {}", route.name)
+        } else {
+            format!("    case {}({})", route.name, route_parameters(route))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("enum Route: Hashable, Identifiable {{\n{}\n\n    var id: Self {{ self }}\n}}", cases)
+}
+
+fn coordinator_class(spec: &NavigationSpec) -> String {
+    let show_cases = spec.routes.iter()
+        .map(|route| {
+            let presentation = match route.style {
+                PresentationStyle::Push => "path.append(route)",
+                PresentationStyle::Sheet => "sheet = route",
+                PresentationStyle::FullScreen => "fullScreenCover = route",
+            };
+            format!("        case .{}:\n            {}", route.name, presentation)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let destination_cases = spec.routes.iter()
+        .map(|route| {
+            let bindings = route.parameters.iter()
+                .map(|parameter| format!("let {}", parameter.name))
+                .collect::<Vec<_>>();
+            let arguments = route.parameters.iter()
+                .map(|parameter| format!("{0}: {0}", parameter.name))
+                .collect::<Vec<_>>();
+            if bindings.is_empty() {
+                format!("        case .{}:\n            {}()", route.name, route.presentation)
+            } else {
+                format!(
+                    "        case .{}({}):\n            {}({})",
+                    route.name, bindings.join(", "), route.presentation, arguments.join(", ")
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"@MainActor
+final class AppCoordinator: ObservableObject {{
+    @Published var path = NavigationPath()
+    @Published var sheet: Route?
+    @Published var fullScreenCover: Route?
+
+    func show(_ route: Route) {{
+        switch route {{
+{show_cases}
+        }}
+    }}
+
+    /// Close the topmost modal, or pop the topmost pushed screen
+    func dismiss() {{
+        if fullScreenCover != nil {{
+            fullScreenCover = nil
+        }} else if sheet != nil {{
+            sheet = nil
+        }} else if !path.isEmpty {{
+            path.removeLast()
+        }}
+    }}
+
+    func popToRoot() {{
+        path = NavigationPath()
+    }}
+
+    @ViewBuilder
+    func destination(for route: Route) -> some View {{
+        switch route {{
+{destination_cases}
+        }}
+    }}
+}}"#,
+        show_cases = show_cases,
+        destination_cases = destination_cases
+    )
+}
+
+fn navigation_view(spec: &NavigationSpec) -> String {
+    format!(
+        r#"struct AppNavigationView: View {{
+    @StateObject private var coordinator = AppCoordinator()
+
+    var body: some View {{
+        NavigationStack(path: $coordinator.path) {{
+            {root}()
+                .navigationDestination(for: Route.self) {{ route in
+                    coordinator.destination(for: route)
+                }}
+        }}
+        .sheet(item: $coordinator.sheet) {{ route in
+            coordinator.destination(for: route)
+        }}
+        .fullScreenCover(item: $coordinator.fullScreenCover) {{ route in
+            coordinator.destination(for: route)
+        }}
+        .environmentObject(coordinator)
+    }}
+}}"#,
+        root = spec.root
+    )
+}
+
+/// The routes `presentation` navigates to, reached from the coordinator
+/// the presentation reads from its environment; `None` when it has none
+fn route_hooks(spec: &NavigationSpec, presentation: &str) -> Option<String> {
+    let routes: Vec<&RouteSpec> = spec.routes.iter()
+        .filter(|route| if route.from.is_empty() {
+            presentation == spec.root
+        } else {
+            route.from.iter().any(|from| from == presentation)
+        })
+        .collect();
+    if routes.is_empty() {
+        return None;
+    }
+
+    let methods = routes.iter()
+        .map(|route| format!(
+            "    func show{}({}) {{\n        coordinator.show({})\n    }}",
+            upper_first(&route.name), route_parameters(route), route_value(route)
+        ))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some(format!(
+        r#"@MainActor
+struct {presentation}Routes {{
+    let coordinator: AppCoordinator
+
+{methods}
+}}
+
+extension AppCoordinator {{
+    var {property}Routes: {presentation}Routes {{ {presentation}Routes(coordinator: self) }}
+}}"#,
+        presentation = presentation,
+        methods = methods,
+        property = lower_first(presentation)
+    ))
+}
+
+/// Replace each `{variable}` with its value in one pass, so values are
+/// never themselves expanded; variables without a value are left as is
+fn render_template<'a>(template: &str, value: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        rendered.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let length = after
+            .find(|c: char| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(after.len());
+        match value(&after[..length]).filter(|_| after[length..].starts_with('}')) {
+            Some(value) => {
+                rendered.push_str(value);
+                rest = &after[length + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Title the presentation template shows in the navigation bar, the
+/// presentation's name without `View`
+pub fn navigation_title(presentation: &str) -> String {
+    presentation.replace("View", "")
+}
+
+/// A template must name the type it declares and render to balanced Swift
+/// using only the variables of its kind
+pub fn validate_template(kind: TemplateKind, contents: &str) -> Result<()> {
+    if !contents.contains("{name}") {
+        return Err(AxiomMCPError::generation("code_generation", "validate template", format!(
+            "{} template does not use {{name}}", kind.name()
+        )));
+    }
+
+    // With every known variable empty, a marker left over is an unknown
+    // variable
+    let rendered = render_template(contents, |name| {
+        kind.variables().iter().any(|(known, _)| *known == name).then_some("")
+    });
+    let problems = crate::swift_validation::check_structure(&rendered);
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    let problems = problems.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ");
+    Err(AxiomMCPError::generation("code_generation", "validate template", format!(
+        "{}; {} templates may use {}", problems, kind.name(), variable_names(kind)
+    )))
+}
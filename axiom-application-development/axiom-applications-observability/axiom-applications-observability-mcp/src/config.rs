@@ -0,0 +1,249 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::hot_reload::HotReloadFilter;
+use crate::mcp::MCPConfiguration;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the configuration file when `--config` is
+/// not given
+pub const CONFIG_PATH_ENV: &str = "AXIOM_OBS_CONFIG";
+
+/// Prefix of the environment variables that override configuration fields.
+///
+/// The rest of the name is the field in upper case, with `__` between a
+/// field and the one nested in it: `AXIOM_OBS_HOT_RELOAD_SERVER_URL`,
+/// `AXIOM_OBS_VISUAL_ANALYSIS_ENABLED=false`,
+/// `AXIOM_OBS_SCREENSHOT_STORAGE__DIR`. Lists and maps are given as JSON,
+/// as in `AXIOM_OBS_HOT_RELOAD_FILTERS__INCLUDE='["**/*.swift"]'`.
+pub const ENV_PREFIX: &str = "AXIOM_OBS_";
+
+/// Field of configuration errors about the file itself rather than one of
+/// its fields
+const CONFIG_ARG: &str = "--config";
+
+/// Where `init_mcp` gets its configuration
+#[derive(Debug, Clone)]
+pub enum ConfigSource {
+    Explicit(Box<MCPConfiguration>),
+    /// `MCPConfiguration::load()`, from the process's arguments and environment
+    Load,
+}
+
+impl From<MCPConfiguration> for ConfigSource {
+    fn from(config: MCPConfiguration) -> Self {
+        ConfigSource::Explicit(Box::new(config))
+    }
+}
+
+impl ConfigSource {
+    pub fn resolve(self) -> Result<MCPConfiguration> {
+        match self {
+            ConfigSource::Explicit(config) => Ok(*config),
+            ConfigSource::Load => MCPConfiguration::load(),
+        }
+    }
+}
+
+impl MCPConfiguration {
+    /// The defaults, overridden by the configuration file from `--config` or
+    /// `AXIOM_OBS_CONFIG`, overridden in turn by `AXIOM_OBS_` environment
+    /// variables, then validated
+    pub fn load() -> Result<Self> {
+        Self::load_from(std::env::args().skip(1), std::env::vars())
+    }
+
+    /// `load` with the given command line arguments and environment
+    pub fn load_from(
+        args: impl IntoIterator<Item = String>,
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self> {
+        let mut env: Vec<(String, String)> = env.into_iter().collect();
+        // A whole section is set before the fields inside it
+        env.sort();
+
+        let defaults = serde_json::to_value(Self::default())?;
+        let mut merged = defaults.clone();
+
+        let path = match config_path_arg(args)? {
+            Some(path) => Some(path),
+            None => env.iter().find(|(name, _)| name == CONFIG_PATH_ENV).map(|(_, path)| PathBuf::from(path)),
+        };
+        if let Some(path) = path {
+            merge_file(&mut merged, read_config_file(&path)?, &path)?;
+        }
+
+        for (name, value) in &env {
+            if name == CONFIG_PATH_ENV {
+                continue;
+            }
+            if let Some(field) = name.strip_prefix(ENV_PREFIX) {
+                override_field(&mut merged, name, field, value)?;
+            }
+        }
+
+        let config: Self = match serde_json::from_value(merged.clone()) {
+            Ok(config) => config,
+            Err(error) => {
+                let field = failing_field(&defaults, &merged).unwrap_or_default();
+                return Err(AxiomMCPError::configuration(field, error.to_string()));
+            }
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check the server URLs, that enabled features have what they depend
+    /// on, and the thresholds and hot reload globs
+    pub fn validate(&self) -> Result<()> {
+        validate_url("hot_reload_server_url", &self.hot_reload_server_url)?;
+        validate_url("intelligence_server_url", &self.intelligence_server_url)?;
+
+        if self.visual_analysis_enabled && !self.simulator_management_enabled {
+            return Err(AxiomMCPError::configuration(
+                "visual_analysis_enabled",
+                "needs simulator_management_enabled, which captures the screenshots it analyzes",
+            ));
+        }
+
+        self.performance_thresholds
+            .validate()
+            .map_err(|error| AxiomMCPError::configuration("performance_thresholds", error.to_string()))?;
+        HotReloadFilter::new(self.hot_reload_filters.clone())
+            .map_err(|error| AxiomMCPError::configuration("hot_reload_filters", error.to_string()))?;
+        self.reload_memory
+            .validate()
+            .map_err(|error| AxiomMCPError::configuration("reload_memory", error.to_string()))?;
+        Ok(())
+    }
+}
+
+fn validate_url(field: &str, url: &str) -> Result<()> {
+    if url.trim().is_empty() {
+        return Err(AxiomMCPError::configuration(field, "cannot be empty"));
+    }
+    let host = match url.split_once("://") {
+        Some(("ws" | "wss", rest)) => rest.split(['/', '?']).next().unwrap_or_default(),
+        _ => return Err(AxiomMCPError::configuration(field, format!("expected a ws:// or wss:// URL, got `{}`", url))),
+    };
+    if host.is_empty() {
+        return Err(AxiomMCPError::configuration(field, format!("`{}` has no host", url)));
+    }
+    Ok(())
+}
+
+/// The path after `--config`, or in `--config=<path>`
+fn config_path_arg(args: impl IntoIterator<Item = String>) -> Result<Option<PathBuf>> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return match args.next() {
+                Some(path) => Ok(Some(PathBuf::from(path))),
+                None => Err(AxiomMCPError::configuration(CONFIG_ARG, "--config needs a path")),
+            };
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Ok(Some(PathBuf::from(path)));
+        }
+    }
+    Ok(None)
+}
+
+/// A TOML or JSON configuration file, by extension
+fn read_config_file(path: &Path) -> Result<Value> {
+    let text = std::fs::read_to_string(path).map_err(|error| {
+        AxiomMCPError::configuration(CONFIG_ARG, format!("Cannot read configuration file {}: {}", path.display(), error))
+    })?;
+    let parsed = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::from_str::<Value>(&text).map_err(|error| error.to_string()),
+        Some("json") => serde_json::from_str::<Value>(&text).map_err(|error| error.to_string()),
+        _ => Err("expected a .toml or .json file".to_string()),
+    };
+    parsed.map_err(|error| {
+        AxiomMCPError::configuration(CONFIG_ARG, format!("Invalid configuration file {}: {}", path.display(), error))
+    })
+}
+
+/// Lay the file's fields over the defaults. Fields the configuration does
+/// not have are rejected rather than ignored, so a misspelled one is not
+/// silently left at its default.
+fn merge_file(merged: &mut Value, file: Value, path: &Path) -> Result<()> {
+    let Value::Object(fields) = file else {
+        return Err(AxiomMCPError::configuration(
+            CONFIG_ARG, format!("Invalid configuration file {}: expected a table of fields", path.display()),
+        ));
+    };
+    let known = merged.as_object_mut().expect("configuration serializes to an object");
+    for (field, value) in fields {
+        match known.get_mut(&field) {
+            Some(current) => merge(current, value),
+            None => return Err(AxiomMCPError::configuration(field, format!("is not a configuration field (in {})", path.display()))),
+        }
+    }
+    Ok(())
+}
+
+/// Tables merge field by field; anything else replaces what was there
+fn merge(current: &mut Value, overlay: Value) {
+    match (current, overlay) {
+        (Value::Object(current), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match current.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        current.insert(key, value);
+                    }
+                }
+            }
+        }
+        (current, overlay) => *current = overlay,
+    }
+}
+
+/// Set the field an `AXIOM_OBS_` variable names, parsing its value as the
+/// type the field already has
+fn override_field(merged: &mut Value, variable: &str, name: &str, raw: &str) -> Result<()> {
+    let path: Vec<String> = name.split("__").map(str::to_lowercase).collect();
+    let field = path.join(".");
+    if path.iter().any(String::is_empty) || merged.get(&path[0]).is_none() {
+        return Err(AxiomMCPError::configuration(field, format!("{} does not name a configuration field", variable)));
+    }
+
+    let mut target = merged;
+    for key in &path {
+        if !target.is_object() {
+            *target = Value::Object(Map::new());
+        }
+        target = target.as_object_mut().expect("just made an object").entry(key.clone()).or_insert(Value::Null);
+    }
+
+    let value = match &*target {
+        Value::String(_) => Value::String(raw.to_string()),
+        Value::Bool(_) => match raw.to_lowercase().as_str() {
+            "true" | "1" => Value::Bool(true),
+            "false" | "0" => Value::Bool(false),
+            _ => return Err(AxiomMCPError::configuration(field, format!("{} must be true or false, got `{}`", variable, raw))),
+        },
+        Value::Number(_) => match serde_json::from_str::<Value>(raw) {
+            Ok(number @ Value::Number(_)) => number,
+            _ => return Err(AxiomMCPError::configuration(field, format!("{} must be a number, got `{}`", variable, raw))),
+        },
+        Value::Array(_) | Value::Object(_) => serde_json::from_str(raw).map_err(|error| {
+            AxiomMCPError::configuration(field.clone(), format!("{} must be JSON: {}", variable, error))
+        })?,
+        // Unset optional fields and new map entries: JSON if it parses,
+        // otherwise the text
+        Value::Null => serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string())),
+    };
+    *target = value;
+    Ok(())
+}
+
+/// The first top-level field that fails to deserialize when it alone is
+/// taken from `merged`
+fn failing_field(defaults: &Value, merged: &Value) -> Option<String> {
+    merged.as_object()?.iter().find_map(|(field, value)| {
+        let mut candidate = defaults.clone();
+        candidate[field] = value.clone();
+        serde_json::from_value::<MCPConfiguration>(candidate).is_err().then(|| field.clone())
+    })
+}
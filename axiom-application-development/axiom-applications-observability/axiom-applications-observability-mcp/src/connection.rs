@@ -0,0 +1,364 @@
+use chrono::{DateTime, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::VecDeque;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, Notify};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// Connection state changes buffered per subscriber before it starts lagging
+pub(crate) const EVENT_CHANNEL_CAPACITY: usize = 32;
+
+/// Incoming messages buffered per subscriber before it starts lagging
+const INCOMING_CHANNEL_CAPACITY: usize = 256;
+
+/// How a dropped WebSocket connection is retried, and how much is kept for it meanwhile
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// Growth of the delay per failed attempt
+    pub multiplier: f64,
+    /// Fraction of the delay randomly added or removed, so clients that lost
+    /// the same server do not all retry at once
+    pub jitter: f64,
+    /// Outbound messages kept while disconnected; the oldest are dropped first
+    pub buffer_capacity: usize,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+            buffer_capacity: 256,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before retry number `attempt`, counting from 1
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.delay_with(attempt, random_unit())
+    }
+
+    /// `delay` with the jitter drawn from `random`, which is in `[0, 1)`
+    pub fn delay_with(&self, attempt: u32, random: f64) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(64) as i32;
+        let base = (self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_delay.as_secs_f64());
+        let factor = 1.0 - self.jitter + 2.0 * self.jitter * random;
+        Duration::from_secs_f64((base * factor).max(0.0))
+    }
+}
+
+/// Uniform in `[0, 1)`; every `RandomState` is seeded differently
+fn random_unit() -> f64 {
+    (RandomState::new().build_hasher().finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    /// Lost or never established; retrying with backoff
+    Reconnecting,
+}
+
+/// Snapshot of one connection, as reported by `get_connection_status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionStatus {
+    pub name: String,
+    pub url: String,
+    pub state: ConnectionState,
+    pub connected_since: Option<DateTime<Utc>>,
+    /// Failed attempts since the connection was last up
+    pub reconnect_attempt: u32,
+    /// Times the connection was re-established after being lost
+    pub reconnects: u64,
+    pub buffered_messages: usize,
+    /// Outbound messages dropped because the buffer was full during an outage
+    pub dropped_messages: u64,
+    /// Streams resubscribed after every reconnect
+    pub active_subscriptions: usize,
+    pub last_error: Option<String>,
+}
+
+/// A connection changed state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionEvent {
+    pub name: String,
+    pub state: ConnectionState,
+    pub error: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+struct Shared {
+    name: String,
+    url: String,
+    policy: ReconnectPolicy,
+    inner: Mutex<Inner>,
+    outbound_ready: Notify,
+    events: broadcast::Sender<ConnectionEvent>,
+    incoming: broadcast::Sender<String>,
+}
+
+struct Inner {
+    status: ConnectionStatus,
+    connected_once: bool,
+    outbound: VecDeque<String>,
+    /// Keyed by stream so subscribing twice resubscribes once
+    subscriptions: Vec<(String, String)>,
+    /// Sent first on every connection
+    greeting: Option<String>,
+}
+
+/// A WebSocket client connection that survives server restarts
+///
+/// Outbound messages go through a bounded buffer, so callers never fail
+/// while the server is away; they are sent once the connection is back.
+/// Stream subscriptions are replayed after every reconnect, before the
+/// buffered messages and after the greeting, if there is one.
+pub struct ReconnectingWebSocket {
+    shared: Arc<Shared>,
+    task: JoinHandle<()>,
+}
+
+impl ReconnectingWebSocket {
+    /// Start connecting to `url` in the background
+    pub fn connect(name: &str, url: &str, policy: ReconnectPolicy) -> Self {
+        Self::connect_with_events(name, url, policy, broadcast::channel(EVENT_CHANNEL_CAPACITY).0)
+    }
+
+    /// Like `connect`, publishing state changes on a shared `events` channel
+    pub fn connect_with_events(
+        name: &str,
+        url: &str,
+        policy: ReconnectPolicy,
+        events: broadcast::Sender<ConnectionEvent>,
+    ) -> Self {
+        Self::connect_with_greeting(name, url, policy, events, None)
+    }
+
+    /// Like `connect_with_events`, opening every connection with `greeting`,
+    /// such as a protocol handshake
+    pub fn connect_with_greeting(
+        name: &str,
+        url: &str,
+        policy: ReconnectPolicy,
+        events: broadcast::Sender<ConnectionEvent>,
+        greeting: Option<String>,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            name: name.to_string(),
+            url: url.to_string(),
+            policy,
+            inner: Mutex::new(Inner {
+                status: ConnectionStatus {
+                    name: name.to_string(),
+                    url: url.to_string(),
+                    state: ConnectionState::Connecting,
+                    connected_since: None,
+                    reconnect_attempt: 0,
+                    reconnects: 0,
+                    buffered_messages: 0,
+                    dropped_messages: 0,
+                    active_subscriptions: 0,
+                    last_error: None,
+                },
+                connected_once: false,
+                outbound: VecDeque::new(),
+                subscriptions: Vec::new(),
+                greeting,
+            }),
+            outbound_ready: Notify::new(),
+            events,
+            incoming: broadcast::channel(INCOMING_CHANNEL_CAPACITY).0,
+        });
+
+        let task = tokio::spawn(run_connection(Arc::clone(&shared)));
+        Self { shared, task }
+    }
+
+    /// Queue a text message, dropping the oldest queued one when the buffer is full
+    pub fn send(&self, message: String) {
+        {
+            let mut inner = self.shared.inner.lock().unwrap();
+            if inner.outbound.len() >= self.shared.policy.buffer_capacity {
+                inner.outbound.pop_front();
+                inner.status.dropped_messages += 1;
+                tracing::debug!("{} outbound buffer is full, dropping the oldest message", self.shared.name);
+            }
+            inner.outbound.push_back(message);
+        }
+        self.shared.outbound_ready.notify_one();
+    }
+
+    /// Subscribe to `stream` with `message`, now and after every reconnect
+    pub fn subscribe(&self, stream: &str, message: String) {
+        {
+            let mut inner = self.shared.inner.lock().unwrap();
+            inner.subscriptions.retain(|(existing, _)| existing != stream);
+            inner.subscriptions.push((stream.to_string(), message.clone()));
+        }
+        self.send(message);
+    }
+
+    /// Stop resubscribing to `stream` after reconnects
+    pub fn unsubscribe(&self, stream: &str) {
+        let mut inner = self.shared.inner.lock().unwrap();
+        inner.subscriptions.retain(|(existing, _)| existing != stream);
+    }
+
+    /// Streams currently subscribed to
+    pub fn subscriptions(&self) -> Vec<String> {
+        let inner = self.shared.inner.lock().unwrap();
+        inner.subscriptions.iter().map(|(stream, _)| stream.clone()).collect()
+    }
+
+    pub fn status(&self) -> ConnectionStatus {
+        let inner = self.shared.inner.lock().unwrap();
+        let mut status = inner.status.clone();
+        status.buffered_messages = inner.outbound.len();
+        status.active_subscriptions = inner.subscriptions.len();
+        status
+    }
+
+    pub fn state_changes(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.shared.events.subscribe()
+    }
+
+    /// Text messages received from the server
+    pub fn incoming(&self) -> broadcast::Receiver<String> {
+        self.shared.incoming.subscribe()
+    }
+}
+
+impl Drop for ReconnectingWebSocket {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for ReconnectingWebSocket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReconnectingWebSocket")
+            .field("name", &self.shared.name)
+            .field("url", &self.shared.url)
+            .finish()
+    }
+}
+
+impl Shared {
+    fn set_state(&self, state: ConnectionState, error: Option<String>) {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.status.state == state && error.is_none() {
+                return;
+            }
+            match state {
+                ConnectionState::Connected => {
+                    if inner.connected_once {
+                        inner.status.reconnects += 1;
+                    }
+                    inner.connected_once = true;
+                    inner.status.connected_since = Some(Utc::now());
+                    inner.status.reconnect_attempt = 0;
+                }
+                ConnectionState::Reconnecting => {
+                    inner.status.connected_since = None;
+                    inner.status.reconnect_attempt += 1;
+                }
+                ConnectionState::Connecting => {}
+            }
+            inner.status.state = state;
+            if error.is_some() {
+                inner.status.last_error = error.clone();
+            }
+        }
+
+        match (&state, &error) {
+            (ConnectionState::Connected, _) => tracing::info!("{} connected to {}", self.name, self.url),
+            (_, Some(error)) => tracing::warn!("{} connection to {} is {:?}: {}", self.name, self.url, state, error),
+            _ => tracing::debug!("{} connection to {} is {:?}", self.name, self.url, state),
+        }
+
+        // Nobody listening is fine
+        let _ = self.events.send(ConnectionEvent {
+            name: self.name.clone(),
+            state,
+            error,
+            timestamp: Utc::now(),
+        });
+    }
+}
+
+async fn run_connection(shared: Arc<Shared>) {
+    loop {
+        let error = match tokio_tungstenite::connect_async(shared.url.as_str()).await {
+            Ok((socket, _)) => {
+                shared.set_state(ConnectionState::Connected, None);
+                serve(&shared, socket).await
+            }
+            Err(e) => e.to_string(),
+        };
+
+        shared.set_state(ConnectionState::Reconnecting, Some(error));
+        let attempt = shared.inner.lock().unwrap().status.reconnect_attempt;
+        tokio::time::sleep(shared.policy.delay(attempt)).await;
+    }
+}
+
+/// Exchange messages until the connection is lost, returning why
+async fn serve(shared: &Shared, socket: WebSocketStream<MaybeTlsStream<TcpStream>>) -> String {
+    let (mut sink, mut stream) = socket.split();
+
+    let opening: Vec<String> = {
+        let mut inner = shared.inner.lock().unwrap();
+        let subscriptions: Vec<String> = inner.subscriptions.iter().map(|(_, message)| message.clone()).collect();
+        // Queued copies of these would be sent twice
+        inner.outbound.retain(|message| !subscriptions.contains(message));
+        inner.greeting.iter().cloned().chain(subscriptions).collect()
+    };
+    for message in opening {
+        if let Err(e) = sink.send(Message::Text(message)).await {
+            return e.to_string();
+        }
+    }
+
+    loop {
+        // Drain before waiting; messages may have been queued while disconnected
+        loop {
+            let Some(message) = shared.inner.lock().unwrap().outbound.pop_front() else {
+                break;
+            };
+            if let Err(e) = sink.send(Message::Text(message.clone())).await {
+                // Kept for the next connection
+                shared.inner.lock().unwrap().outbound.push_front(message);
+                return e.to_string();
+            }
+        }
+
+        tokio::select! {
+            _ = shared.outbound_ready.notified() => {}
+            incoming = stream.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let _ = shared.incoming.send(text);
+                }
+                Some(Ok(Message::Close(_))) | None => return "Connection closed by the server".to_string(),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return e.to_string(),
+            },
+        }
+    }
+}
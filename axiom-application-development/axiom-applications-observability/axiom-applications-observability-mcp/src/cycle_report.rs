@@ -0,0 +1,834 @@
+use crate::artifact_store::{ArtifactKind, ArtifactSource, ArtifactStore};
+use crate::complete_development_loop::{
+    BuildStage, ConsistencyCheck, CycleCheckpoint, CycleStage, IterationRecord, LoopTermination, PerformanceValidationStage,
+    SkippedValidation, StageFailure, StageOutput, TestExecutionStage, VisualValidationStage,
+};
+use crate::error::{AxiomMCPError, Result};
+use crate::performance_analysis_integration::{PerformanceThresholds, PerformanceVerdict, ReloadMemoryTrend};
+use crate::tool_metrics::ToolMetricsReport;
+use crate::traceability::TraceabilityMatrix;
+use crate::types::{Implementation, RequirementAnalysis, ValidationResult};
+use crate::visual_regression::{decode_png, encode_png};
+use base64::Engine;
+use image::imageops::{self, FilterType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Where cycle reports are written unless configured otherwise
+pub const DEFAULT_REPORTS_DIR: &str = ".axiom/reports";
+
+/// Width in pixels screenshots are scaled down to in HTML reports
+pub const DEFAULT_THUMBNAIL_WIDTH: u32 = 240;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+const HTML_STYLE: &str = "body { font-family: -apple-system, Helvetica, sans-serif; margin: 2em auto; max-width: 72em; color: #1d1d1f; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #d2d2d7; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f5f5f7; }
+img { display: block; max-width: 100%; }
+.missing { color: #86868b; }";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CycleReportConfig {
+    /// Reports are written here as `{cycle_id}.md` and `{cycle_id}.html`
+    pub dir: PathBuf,
+    /// Width screenshots are embedded at in HTML reports
+    pub thumbnail_width: u32,
+}
+
+impl Default for CycleReportConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(DEFAULT_REPORTS_DIR),
+            thumbnail_width: DEFAULT_THUMBNAIL_WIDTH,
+        }
+    }
+}
+
+/// Arguments of `export_cycle_report`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleReportRequest {
+    pub cycle_id: String,
+    /// Also write a self-contained HTML report with the screenshots embedded
+    #[serde(default)]
+    pub html: bool,
+}
+
+/// Where a cycle's report was written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedCycleReport {
+    pub cycle_id: String,
+    pub markdown_path: PathBuf,
+    pub html_path: Option<PathBuf>,
+    /// `None` while the cycle has not stopped iterating
+    pub termination: Option<LoopTermination>,
+    /// Ids of the written reports in the artifact store, when the exporter has one
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+}
+
+/// Everything a development cycle has produced so far, read from its checkpoint
+#[derive(Debug, Clone)]
+pub struct CycleReport {
+    pub cycle_id: String,
+    pub requirement: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub analysis: Option<RequirementAnalysis>,
+    pub implementation: Option<Implementation>,
+    pub build: Option<BuildStage>,
+    pub test_execution: Option<TestExecutionStage>,
+    pub visual_validation: Option<VisualValidationStage>,
+    pub performance: Option<PerformanceValidationStage>,
+    pub iterations: Vec<IterationRecord>,
+    pub termination: Option<LoopTermination>,
+    pub failure: Option<StageFailure>,
+    pub next_stage: Option<CycleStage>,
+    /// Checks the cycle left out for disabled capabilities
+    pub skipped: Vec<SkippedValidation>,
+    /// Memory of the app across hot reloads, which the checkpoint does not keep
+    pub reload_memory: Option<ReloadMemoryTrend>,
+    /// How long each tool took while the MCP ran, with its stages
+    pub tool_metrics: Option<ToolMetricsReport>,
+}
+
+impl CycleReport {
+    pub fn from_checkpoint(checkpoint: &CycleCheckpoint) -> Result<Self> {
+        Ok(Self {
+            cycle_id: checkpoint.cycle_id.clone(),
+            requirement: checkpoint.requirement.clone(),
+            started_at: checkpoint.started_at,
+            updated_at: checkpoint.updated_at,
+            analysis: stage_output(checkpoint, CycleStage::Analysis)?,
+            implementation: stage_output(checkpoint, CycleStage::Implementation)?,
+            build: stage_output(checkpoint, CycleStage::Build)?,
+            test_execution: stage_output(checkpoint, CycleStage::TestExecution)?,
+            visual_validation: stage_output(checkpoint, CycleStage::VisualValidation)?,
+            performance: stage_output(checkpoint, CycleStage::PerformanceValidation)?,
+            iterations: checkpoint.iterations.clone(),
+            termination: checkpoint.termination.clone(),
+            failure: checkpoint.failure.clone(),
+            next_stage: checkpoint.next_stage(),
+            skipped: checkpoint.skipped.clone(),
+            reload_memory: None,
+            tool_metrics: None,
+        })
+    }
+
+    /// The report as Markdown, with screenshots linked to where they were written
+    pub fn to_markdown(&self, thresholds: &PerformanceThresholds) -> String {
+        let mut markdown = String::new();
+        for block in self.blocks(thresholds) {
+            block.write_markdown(&mut markdown);
+        }
+        markdown
+    }
+
+    /// The report as a standalone HTML page, with the screenshots in
+    /// `thumbnails`, keyed by path, embedded as data URIs
+    pub fn to_html(&self, thresholds: &PerformanceThresholds, thumbnails: &BTreeMap<PathBuf, String>) -> String {
+        let mut body = String::new();
+        for block in self.blocks(thresholds) {
+            block.write_html(&mut body, thumbnails);
+        }
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Development cycle {}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(&self.cycle_id),
+            HTML_STYLE,
+            body
+        )
+    }
+
+    /// Scaled-down PNG data URIs of the screenshots and crops the report
+    /// shows; images that are gone or not PNGs are left out
+    async fn thumbnails(&self, width: u32) -> BTreeMap<PathBuf, String> {
+        let mut thumbnails = BTreeMap::new();
+        for path in self.image_paths() {
+            match thumbnail(&path, width).await {
+                Ok(uri) => {
+                    thumbnails.insert(path, uri);
+                }
+                Err(e) => tracing::debug!("Reporting {} without its image: {}", path.display(), e),
+            }
+        }
+        thumbnails
+    }
+
+    fn image_paths(&self) -> Vec<PathBuf> {
+        let Some(check) = self.consistency() else {
+            return Vec::new();
+        };
+        check.screenshots.iter()
+            .filter_map(|screenshot| screenshot.image.as_ref().map(|image| image.path.clone()))
+            .chain(check.layout_issues.iter().filter_map(|issue| issue.crop_path.clone()))
+            .collect()
+    }
+
+    /// Which generated elements address which parts of the requirement,
+    /// once the cycle has analyzed it and generated its implementation
+    pub fn traceability(&self) -> Option<TraceabilityMatrix> {
+        Some(TraceabilityMatrix::build(self.analysis.as_ref()?, self.implementation.as_ref()?))
+    }
+
+    fn consistency(&self) -> Option<&ConsistencyCheck> {
+        self.visual_validation.as_ref()?.consistency.as_ref()
+    }
+
+    fn blocks(&self, thresholds: &PerformanceThresholds) -> Vec<Block> {
+        let mut blocks = vec![
+            Block::Heading(1, format!("Development cycle {}", self.cycle_id)),
+            Block::Paragraph(format!("Requirement: {}", self.requirement)),
+            Block::table(&["Started", "Updated", "Iterations", "Outcome"], vec![vec![
+                self.started_at.format(TIME_FORMAT).to_string(),
+                self.updated_at.format(TIME_FORMAT).to_string(),
+                self.iterations.len().to_string(),
+                self.outcome(),
+            ]]),
+        ];
+        self.analysis_blocks(&mut blocks);
+        self.file_blocks(&mut blocks);
+        self.traceability_blocks(&mut blocks);
+        self.validation_blocks(&mut blocks);
+        self.test_blocks(&mut blocks);
+        self.screenshot_blocks(&mut blocks);
+        self.performance_blocks(&mut blocks, thresholds);
+        self.reload_memory_blocks(&mut blocks);
+        self.timing_blocks(&mut blocks);
+        self.iteration_blocks(&mut blocks);
+        self.termination_blocks(&mut blocks);
+        blocks
+    }
+
+    /// A note for each check of `stage` the cycle skipped, which is then
+    /// all there is to say about it
+    fn skipped_blocks(&self, blocks: &mut Vec<Block>, stage: CycleStage) -> bool {
+        let notes: Vec<Block> = self.skipped.iter()
+            .filter(|skipped| skipped.stage == stage)
+            .map(|skipped| Block::Paragraph(format!("Skipped: {}.", skipped)))
+            .collect();
+        let skipped = !notes.is_empty();
+        blocks.extend(notes);
+        skipped
+    }
+
+    fn outcome(&self) -> String {
+        match (&self.termination, &self.failure, self.next_stage) {
+            (Some(termination), _, _) => termination.reason.to_string(),
+            (None, Some(failure), _) => format!("stopped at the {}", failure.stage),
+            (None, None, Some(stage)) => format!("waiting for the {}", stage),
+            (None, None, None) => "completed".to_string(),
+        }
+    }
+
+    fn analysis_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Requirement analysis".to_string()));
+        let Some(analysis) = &self.analysis else {
+            blocks.push(not_reached());
+            return;
+        };
+        blocks.push(Block::List(vec![
+            format!("Confidence: {:.1}%", analysis.confidence_score),
+            format!("Analyzed by: {}", analysis.analyzer.label()),
+            format!("Complexity: {}", analysis.complexity_estimate),
+            format!("Approach: {}", analysis.recommended_approach),
+            format!("Components: {}", listed(&analysis.estimated_components)),
+            format!("Entities: {}", listed(&analysis.entities)),
+            format!("Screens: {}", listed(&analysis.screens)),
+            format!("Actions: {}", listed(&analysis.actions)),
+        ]));
+        if !analysis.clarifying_questions.is_empty() {
+            blocks.push(Block::Heading(3, "Clarifications".to_string()));
+            blocks.push(Block::List(analysis.clarifying_questions.iter()
+                .map(|question| format!("{} {}", question.question, question.answer_or_default()))
+                .collect()));
+        }
+    }
+
+    fn file_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Generated files".to_string()));
+        let Some(implementation) = &self.implementation else {
+            blocks.push(not_reached());
+            return;
+        };
+        let files: Vec<(String, usize)> = implementation.review_artifacts()
+            .into_iter()
+            .map(|artifact| (artifact.name, artifact.content.lines().count()))
+            .collect();
+        let total: usize = files.iter().map(|(_, lines)| lines).sum();
+        blocks.push(Block::Paragraph(format!(
+            "Implementation {}: {} files, {} lines.", implementation.implementation_id, files.len(), total
+        )));
+        blocks.push(Block::table(&["File", "Lines"], files.into_iter().map(|(name, lines)| vec![name, lines.to_string()]).collect()));
+    }
+
+    fn traceability_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Traceability".to_string()));
+        let (Some(implementation), Some(matrix)) = (&self.implementation, self.traceability()) else {
+            blocks.push(not_reached());
+            return;
+        };
+        if implementation.traceability.is_empty() {
+            blocks.push(Block::Paragraph(format!(
+                "Implementation {} does not record which requirement fragments it addresses.", implementation.implementation_id
+            )));
+            return;
+        }
+        blocks.push(Block::Paragraph(format!(
+            "{} of {} requirement fragments are implemented ({:.1}%).",
+            matrix.rows.len() - matrix.gaps.len(), matrix.rows.len(), matrix.coverage
+        )));
+        blocks.push(Block::table(&["Fragment", "Kind", "Requirement", "Implemented by"], matrix.rows.iter().map(|row| vec![
+            row.fragment.id.clone(),
+            row.fragment.kind.to_string(),
+            row.fragment.text.clone(),
+            if row.elements.is_empty() {
+                "nothing (gap)".to_string()
+            } else {
+                row.elements.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+            },
+        ]).collect()));
+        if !matrix.untraced.is_empty() {
+            blocks.push(Block::Paragraph("Generated without a requirement fragment, possibly more than was asked for:".to_string()));
+            blocks.push(Block::List(matrix.untraced.iter().map(ToString::to_string).collect()));
+        }
+    }
+
+    fn validation_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Validation".to_string()));
+        let Some(build) = &self.build else {
+            blocks.push(not_reached());
+            return;
+        };
+        blocks.push(Block::Paragraph(validation_summary(&build.validation)));
+        if build.validation.issues.is_empty() {
+            blocks.push(Block::Paragraph("No issues.".to_string()));
+        } else {
+            blocks.push(Block::List(build.validation.issues.clone()));
+        }
+        if let Some(compilation) = &build.compilation {
+            blocks.push(Block::Paragraph(if compilation.succeeded {
+                format!("The project built in {} ms.", compilation.duration_ms)
+            } else {
+                format!("The project build failed with {} errors.", compilation.errors().count())
+            }));
+        }
+        if let Some(app_run) = &build.app_run {
+            blocks.push(Block::Paragraph(match (&app_run.error, &app_run.launch) {
+                (Some(error), _) => format!("The app did not run: {}", error),
+                (None, Some(launch)) => format!("The app launched on {} with pid {}.", launch.device.name, launch.pid),
+                (None, None) => "The app was not launched.".to_string(),
+            }));
+        }
+        self.skipped_blocks(blocks, CycleStage::Build);
+    }
+
+    fn test_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Tests".to_string()));
+        if self.skipped_blocks(blocks, CycleStage::TestExecution) {
+            return;
+        }
+        let Some(stage) = &self.test_execution else {
+            blocks.push(not_reached());
+            return;
+        };
+        let Some(run) = &stage.run else {
+            blocks.push(Block::Paragraph("The generated tests were not run.".to_string()));
+            return;
+        };
+        if !run.build_errors.is_empty() {
+            blocks.push(Block::Paragraph(format!("The tests did not build for {}:", run.device.name)));
+            blocks.push(Block::List(run.build_errors.iter().map(|error| error.to_string()).collect()));
+            return;
+        }
+        let coverage = match run.coverage {
+            Some(coverage) => format!(" with {:.1}% line coverage", coverage),
+            None => String::new(),
+        };
+        blocks.push(Block::Paragraph(format!(
+            "{} of {} tests passed on {} in {} ms{}.", run.passed().count(), run.tests.len(), run.device.name, run.duration_ms, coverage
+        )));
+        blocks.push(Block::table(&["Test", "Result", "Duration (ms)", "Failures"], run.tests.iter().map(|test| vec![
+            test.identifier.clone(),
+            test.outcome.to_string(),
+            test.duration_ms.to_string(),
+            test.failures.join("; "),
+        ]).collect()));
+    }
+
+    fn screenshot_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Screenshots".to_string()));
+        if self.skipped_blocks(blocks, CycleStage::VisualValidation) {
+            return;
+        }
+        let Some(visual) = &self.visual_validation else {
+            blocks.push(not_reached());
+            return;
+        };
+        let mut checks = vec![
+            format!("UI patterns: {}", listed(&visual.ui_patterns.identified_patterns)),
+            format!(
+                "Accessibility compliance {:.1}% with {} issues",
+                visual.accessibility.compliance_score,
+                visual.accessibility.issues_found.len()
+            ),
+        ];
+        if let Some(screen_text) = &visual.screen_text {
+            checks.push(format!(
+                "{} of {} expected texts on screen",
+                screen_text.matched.len(),
+                screen_text.matched.len() + screen_text.missing.len()
+            ));
+        }
+        blocks.push(Block::List(checks));
+
+        let Some(check) = &visual.consistency else {
+            blocks.push(Block::Paragraph("No screenshot matrix was captured.".to_string()));
+            return;
+        };
+        blocks.push(Block::Paragraph(format!(
+            "Consistency across devices {:.2} against a minimum of {:.2}: {}.",
+            check.report.score,
+            check.min_score,
+            if check.passed() { "passed" } else { "failed" }
+        )));
+        blocks.push(screenshot_grid(check));
+
+        blocks.push(Block::Heading(3, "Differences".to_string()));
+        if check.report.pairs.is_empty() {
+            blocks.push(Block::Paragraph("No variants could be compared.".to_string()));
+        } else {
+            let mut pairs: Vec<_> = check.report.pairs.iter().collect();
+            pairs.sort_by(|a, b| a.score.total_cmp(&b.score));
+            let similarity = |value: Option<f64>| value.map(|value| format!("{:.2}", value)).unwrap_or_else(|| "n/a".to_string());
+            blocks.push(Block::table(
+                &["Screen", "First", "Second", "Layout", "Perceptual", "Penalty", "Score"],
+                pairs.into_iter().map(|pair| vec![
+                    pair.screen.clone(),
+                    pair.first.clone(),
+                    pair.second.clone(),
+                    similarity(pair.layout_similarity),
+                    similarity(pair.perceptual_similarity),
+                    format!("{:.2}", pair.issue_penalty),
+                    format!("{:.2}", pair.score),
+                ]).collect(),
+            ));
+        }
+
+        if !check.layout_issues.is_empty() {
+            blocks.push(Block::Heading(3, "Layout issues".to_string()));
+            let rows = check.layout_issues.iter().map(|issue| vec![
+                Cell::Text(issue.screenshot_id.clone()),
+                Cell::Text(lowercase(&issue.kind)),
+                Cell::Text(issue.element_ids.join(", ")),
+                Cell::Text(issue.message.clone()),
+                match &issue.crop_path {
+                    Some(path) => Cell::Image { alt: format!("{} crop", issue.screenshot_id), path: path.clone() },
+                    None => Cell::Text(String::new()),
+                },
+            ]).collect();
+            blocks.push(Block::Table { header: strings(&["Screenshot", "Kind", "Views", "Message", "Crop"]), rows });
+        }
+    }
+
+    fn performance_blocks(&self, blocks: &mut Vec<Block>, thresholds: &PerformanceThresholds) {
+        blocks.push(Block::Heading(2, "Performance".to_string()));
+        if self.skipped_blocks(blocks, CycleStage::PerformanceValidation) {
+            return;
+        }
+        let Some(performance) = &self.performance else {
+            blocks.push(not_reached());
+            return;
+        };
+        blocks.push(Block::Paragraph(format!("Verdict: {}.", lowercase(&performance.verdict.status))));
+        blocks.push(performance_table(&performance.verdict, thresholds));
+    }
+
+    fn reload_memory_blocks(&self, blocks: &mut Vec<Block>) {
+        let Some(trend) = &self.reload_memory else {
+            return;
+        };
+        blocks.push(Block::Heading(3, "Memory across hot reloads".to_string()));
+        blocks.push(Block::Paragraph(format!("{}.", trend.summary())));
+        if trend.samples.is_empty() {
+            return;
+        }
+        let megabytes = |value: f64| format!("{:.1} MB", value);
+        blocks.push(Block::table(
+            &["Reload", "At", "Files", "Before", "After"],
+            trend.samples.iter().map(|sample| vec![
+                sample.batch_id.to_string(),
+                sample.at.format(TIME_FORMAT).to_string(),
+                sample.files.len().to_string(),
+                sample.before_mb.map(megabytes).unwrap_or_default(),
+                megabytes(sample.after_mb),
+            ]).collect(),
+        ));
+        if let Some(path) = trend.finding.as_ref().and_then(|finding| finding.heap_snapshot.as_ref()?.path.as_ref()) {
+            blocks.push(Block::Paragraph(format!("Heap snapshot: {}", path.display())));
+        }
+    }
+
+    fn timing_blocks(&self, blocks: &mut Vec<Block>) {
+        let Some(metrics) = &self.tool_metrics else {
+            return;
+        };
+        blocks.push(Block::Heading(2, "Timing".to_string()));
+        if metrics.tools.is_empty() {
+            blocks.push(Block::Paragraph("No tool has been called.".to_string()));
+            return;
+        }
+        blocks.push(Block::table(
+            &["Tool", "Calls", "Failed", "Mean", "p95", "Slowest", "Output"],
+            metrics.tools.iter().map(|stats| vec![
+                stats.tool.clone(),
+                stats.calls.to_string(),
+                format!("{} ({:.0}%)", stats.failures, stats.failure_rate() * 100.0),
+                format!("{:.0} ms", stats.durations.mean_ms()),
+                format!("≤ {} ms", stats.durations.quantile_ms(0.95)),
+                format!("{} ms", stats.durations.max_ms),
+                format!("{} B", stats.output_bytes),
+            ]).collect(),
+        ));
+
+        let stages: Vec<Vec<String>> = metrics.tools.iter()
+            .flat_map(|stats| stats.stages.iter().map(|(stage, timing)| vec![
+                stats.tool.clone(),
+                stage.clone(),
+                timing.count.to_string(),
+                format!("{:.0} ms", timing.mean_ms()),
+                format!("{} ms", timing.max_ms),
+            ]))
+            .collect();
+        if !stages.is_empty() {
+            blocks.push(Block::Heading(3, "Stages".to_string()));
+            blocks.push(Block::table(&["Tool", "Stage", "Count", "Mean", "Slowest"], stages));
+        }
+    }
+
+    fn iteration_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Iterations".to_string()));
+        if self.iterations.is_empty() {
+            blocks.push(Block::Paragraph("No iteration has completed.".to_string()));
+            return;
+        }
+        blocks.push(Block::table(
+            &["Iteration", "Implementation", "Validation errors", "Tests passed", "Performance", "Files changed"],
+            self.iterations.iter().map(|record| vec![
+                record.iteration.to_string(),
+                record.implementation_id.clone(),
+                record.validation_errors.to_string(),
+                record.tests_passed.to_string(),
+                lowercase(&record.verdict),
+                match &record.diff {
+                    Some(diff) => diff.files_changed.len().to_string(),
+                    None => "first".to_string(),
+                },
+            ]).collect(),
+        ));
+    }
+
+    fn termination_blocks(&self, blocks: &mut Vec<Block>) {
+        blocks.push(Block::Heading(2, "Termination".to_string()));
+        blocks.push(Block::Paragraph(match (&self.termination, &self.failure, self.next_stage) {
+            (Some(termination), _, _) => format!(
+                "Stopped iterating ({}) after {} iterations: {}", termination.reason, termination.iterations, termination.detail
+            ),
+            (None, Some(failure), _) => format!(
+                "Stopped at the {} on {}: {}", failure.stage, failure.failed_at.format(TIME_FORMAT), failure.error
+            ),
+            (None, None, Some(stage)) => format!("Still running; the {} comes next.", stage),
+            (None, None, None) => "Every stage completed.".to_string(),
+        }));
+    }
+}
+
+/// Writes cycle reports to the configured directory
+#[derive(Debug, Clone)]
+pub struct CycleReportExporter {
+    config: CycleReportConfig,
+    thresholds: PerformanceThresholds,
+    reload_memory: Option<ReloadMemoryTrend>,
+    tool_metrics: Option<ToolMetricsReport>,
+    artifacts: Option<Arc<ArtifactStore>>,
+}
+
+impl CycleReportExporter {
+    pub fn new(config: CycleReportConfig) -> Self {
+        Self { config, thresholds: PerformanceThresholds::default(), reload_memory: None, tool_metrics: None, artifacts: None }
+    }
+
+    /// Show `thresholds` next to each performance metric's value
+    pub fn with_thresholds(mut self, thresholds: PerformanceThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Add the app's memory across hot reloads under the performance section
+    pub fn with_reload_memory(mut self, trend: ReloadMemoryTrend) -> Self {
+        self.reload_memory = Some(trend);
+        self
+    }
+
+    /// Add each tool's call durations and stage timings as the timing section
+    pub fn with_tool_metrics(mut self, metrics: ToolMetricsReport) -> Self {
+        self.tool_metrics = Some(metrics);
+        self
+    }
+
+    /// Also keep each written report in `artifacts` under the cycle's id
+    pub fn with_artifact_store(mut self, artifacts: Arc<ArtifactStore>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Write the checkpoint's report as Markdown, and as HTML when `html` is set
+    pub async fn export(&self, checkpoint: &CycleCheckpoint, html: bool) -> Result<ExportedCycleReport> {
+        let mut report = CycleReport::from_checkpoint(checkpoint)?;
+        report.reload_memory = self.reload_memory.clone();
+        report.tool_metrics = self.tool_metrics.clone();
+        tokio::fs::create_dir_all(&self.config.dir).await?;
+
+        let mut artifacts = Vec::new();
+        let markdown_path = self.config.dir.join(format!("{}.md", report.cycle_id));
+        let markdown = report.to_markdown(&self.thresholds);
+        tokio::fs::write(&markdown_path, &markdown).await?;
+        artifacts.extend(self.keep(&report.cycle_id, markdown.as_bytes(), "md").await);
+        let html_path = if html {
+            let thumbnails = report.thumbnails(self.config.thumbnail_width).await;
+            let path = self.config.dir.join(format!("{}.html", report.cycle_id));
+            let document = report.to_html(&self.thresholds, &thumbnails);
+            tokio::fs::write(&path, &document).await?;
+            artifacts.extend(self.keep(&report.cycle_id, document.as_bytes(), "html").await);
+            Some(tokio::fs::canonicalize(&path).await?)
+        } else {
+            None
+        };
+
+        Ok(ExportedCycleReport {
+            cycle_id: report.cycle_id,
+            markdown_path: tokio::fs::canonicalize(&markdown_path).await?,
+            html_path,
+            termination: report.termination,
+            artifacts,
+        })
+    }
+
+    async fn keep(&self, cycle_id: &str, bytes: &[u8], extension: &str) -> Option<String> {
+        let artifacts = self.artifacts.as_ref()?;
+        let source = ArtifactSource::cycle(cycle_id, "report").with_metadata("format", extension);
+        artifacts.keep(ArtifactKind::Report, bytes, extension, source).await.map(|record| record.id)
+    }
+}
+
+fn stage_output<T: serde::de::DeserializeOwned>(checkpoint: &CycleCheckpoint, stage: CycleStage) -> Result<Option<T>> {
+    let Some(record) = checkpoint.stages.get(&stage) else {
+        return Ok(None);
+    };
+    serde_json::from_value(record.output.clone()).map(Some).map_err(|e| {
+        AxiomMCPError::validation("cycle_report", "read checkpoint", format!(
+            "The {} of cycle {} cannot be read: {}", stage, checkpoint.cycle_id, e
+        ))
+    })
+}
+
+fn not_reached() -> Block {
+    Block::Paragraph("Not reached.".to_string())
+}
+
+fn listed(items: &[String]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(ToString::to_string).collect()
+}
+
+/// A unit enum's variant name in lower case, `pass` for `VerdictStatus::Pass`
+fn lowercase(value: &impl std::fmt::Debug) -> String {
+    format!("{:?}", value).to_lowercase()
+}
+
+fn validation_summary(validation: &ValidationResult) -> String {
+    format!(
+        "{} with a score of {:.1}% (architecture {:.1}%, type safety {:.1}%, performance {:.1}%).",
+        if validation.passed { "Passed" } else { "Failed" },
+        validation.overall_score,
+        validation.architecture_compliance,
+        validation.type_safety_score,
+        validation.performance_score
+    )
+}
+
+/// One row per device and one column per orientation and color scheme, in
+/// the order the matrix was captured
+fn screenshot_grid(check: &ConsistencyCheck) -> Block {
+    let mut devices: Vec<&str> = Vec::new();
+    let mut variants: Vec<(&str, &str)> = Vec::new();
+    for screenshot in &check.screenshots {
+        if !devices.contains(&screenshot.device_type.as_str()) {
+            devices.push(&screenshot.device_type);
+        }
+        let variant = (screenshot.orientation.as_str(), screenshot.color_scheme.as_str());
+        if !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+
+    let header = std::iter::once("Device".to_string())
+        .chain(variants.iter().map(|(orientation, color_scheme)| format!("{} {}", orientation, color_scheme)))
+        .collect();
+    let rows = devices.iter().map(|&device| {
+        std::iter::once(Cell::Text(device.to_string()))
+            .chain(variants.iter().map(|&(orientation, color_scheme)| {
+                let screenshot = check.screenshots.iter().find(|screenshot| {
+                    screenshot.device_type == device && screenshot.orientation == orientation && screenshot.color_scheme == color_scheme
+                });
+                match screenshot {
+                    Some(screenshot) => match &screenshot.image {
+                        Some(image) => Cell::Image { alt: screenshot.id.clone(), path: image.path.clone() },
+                        None => Cell::Text(screenshot.id.clone()),
+                    },
+                    None => Cell::Text(String::new()),
+                }
+            }))
+            .collect()
+    }).collect();
+    Block::Table { header, rows }
+}
+
+fn performance_table(verdict: &PerformanceVerdict, thresholds: &PerformanceThresholds) -> Block {
+    let rows = verdict.metrics.iter().map(|metric| {
+        let threshold = thresholds.metrics.get(&metric.metric);
+        let limit = |value: Option<f64>| value.map(|value| format!("{:.1}", value)).unwrap_or_default();
+        vec![
+            serde_json::to_value(metric.metric).ok().and_then(|name| name.as_str().map(str::to_string)).unwrap_or_default(),
+            metric.value.map(|value| format!("{:.1}", value)).unwrap_or_else(|| "not captured".to_string()),
+            format!("{:.1}", metric.target),
+            limit(threshold.map(|threshold| threshold.warn)),
+            limit(threshold.map(|threshold| threshold.fail)),
+            match (metric.delta, metric.delta_percent) {
+                (Some(delta), Some(percent)) => format!("{:+.1} ({:+.1}%)", delta, percent),
+                (Some(delta), None) => format!("{:+.1}", delta),
+                _ => String::new(),
+            },
+            lowercase(&metric.status),
+        ]
+    }).collect();
+    Block::table(&["Metric", "Value", "Target", "Warn", "Fail", "Delta", "Status"], rows)
+}
+
+async fn thumbnail(path: &Path, width: u32) -> Result<String> {
+    let image = decode_png(&tokio::fs::read(path).await?)?;
+    let width = width.clamp(1, image.width().max(1));
+    let height = ((f64::from(image.height()) * f64::from(width) / f64::from(image.width().max(1))).round() as u32).max(1);
+    let png = encode_png(&imageops::resize(&image, width, height, FilterType::Triangle))?;
+    Ok(format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png)))
+}
+
+/// The report's structure, rendered as either Markdown or HTML
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    List(Vec<String>),
+    Table { header: Vec<String>, rows: Vec<Vec<Cell>> },
+}
+
+enum Cell {
+    Text(String),
+    Image { alt: String, path: PathBuf },
+}
+
+impl Block {
+    fn table(header: &[&str], rows: Vec<Vec<String>>) -> Self {
+        Block::Table {
+            header: strings(header),
+            rows: rows.into_iter().map(|row| row.into_iter().map(Cell::Text).collect()).collect(),
+        }
+    }
+
+    fn write_markdown(&self, out: &mut String) {
+        match self {
+            Block::Heading(level, text) => out.push_str(&format!("{} {}\n\n", "#".repeat(*level), text)),
+            Block::Paragraph(text) => out.push_str(&format!("{}\n\n", text)),
+            Block::List(items) => {
+                for item in items {
+                    out.push_str(&format!("- {}\n", item));
+                }
+                out.push('\n');
+            }
+            Block::Table { header, rows } => {
+                let row = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+                out.push_str(&row(header.iter().map(|cell| escape_markdown_cell(cell)).collect()));
+                out.push_str(&row(header.iter().map(|_| "---".to_string()).collect()));
+                for cells in rows {
+                    out.push_str(&row(cells.iter().map(Cell::markdown).collect()));
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    fn write_html(&self, out: &mut String, thumbnails: &BTreeMap<PathBuf, String>) {
+        match self {
+            Block::Heading(level, text) => out.push_str(&format!("<h{0}>{1}</h{0}>\n", level, escape_html(text))),
+            Block::Paragraph(text) => out.push_str(&format!("<p>{}</p>\n", escape_html(text))),
+            Block::List(items) => {
+                out.push_str("<ul>\n");
+                for item in items {
+                    out.push_str(&format!("<li>{}</li>\n", escape_html(item)));
+                }
+                out.push_str("</ul>\n");
+            }
+            Block::Table { header, rows } => {
+                out.push_str("<table>\n<tr>");
+                for cell in header {
+                    out.push_str(&format!("<th>{}</th>", escape_html(cell)));
+                }
+                out.push_str("</tr>\n");
+                for cells in rows {
+                    out.push_str("<tr>");
+                    for cell in cells {
+                        out.push_str(&format!("<td>{}</td>", cell.html(thumbnails)));
+                    }
+                    out.push_str("</tr>\n");
+                }
+                out.push_str("</table>\n");
+            }
+        }
+    }
+}
+
+impl Cell {
+    fn markdown(&self) -> String {
+        match self {
+            Cell::Text(text) => escape_markdown_cell(text),
+            Cell::Image { alt, path } => format!("![{}](<{}>)", escape_markdown_cell(alt), path.display()),
+        }
+    }
+
+    fn html(&self, thumbnails: &BTreeMap<PathBuf, String>) -> String {
+        match self {
+            Cell::Text(text) => escape_html(text),
+            Cell::Image { alt, path } => match thumbnails.get(path) {
+                Some(uri) => format!("<img src=\"{}\" alt=\"{}\">", uri, escape_html(alt)),
+                None => format!("<span class=\"missing\">{}</span>", escape_html(alt)),
+            },
+        }
+    }
+}
+
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -1,7 +1,21 @@
-use crate::error::Result;
+use crate::approval_gates::ReviewArtifact;
+use crate::complete_development_loop::CycleStage;
+use crate::error::{AxiomMCPError, Result};
+use git2::build::CheckoutBuilder;
+use git2::{BranchType, DiffFormat, ErrorCode, Oid, Repository, Signature, StatusOptions};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
+/// Every branch a development cycle works on is named this plus its id
+pub const CYCLE_BRANCH_PREFIX: &str = "axiom/cycle-";
+
+/// Where approved implementation files are committed, relative to the repository
+pub const DEFAULT_GENERATED_DIR: &str = "Generated";
+
+/// Identity for cycle commits in repositories without `user.name`/`user.email`
+const FALLBACK_COMMITTER: (&str, &str) = ("Axiom", "axiom@localhost");
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DevelopmentSessionSpec {
     pub session_name: String,
@@ -47,7 +61,350 @@ pub struct ArchitectureReport {
     pub overall_score: f64,
 }
 
-pub struct AxiomObservabilityWorkflow;
+/// A branch a development cycle created, and where it started
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CycleBranch {
+    pub cycle_id: String,
+    pub branch: String,
+    pub base_commit: String,
+    /// The branch checked out when the cycle started; `None` for a detached HEAD
+    pub origin_branch: Option<String>,
+}
+
+/// An approved stage to record on its cycle's branch
+#[derive(Debug, Clone)]
+pub struct StageCommit {
+    pub cycle_id: String,
+    pub stage: CycleStage,
+    pub attempt: u32,
+    pub summary: String,
+    /// Written under the generated directory before committing
+    pub files: Vec<ReviewArtifact>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleCommit {
+    pub cycle_id: String,
+    pub stage: CycleStage,
+    pub branch: String,
+    pub commit: String,
+    pub message: String,
+    pub files: Vec<PathBuf>,
+}
+
+/// Everything a cycle changed since it branched off, for review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleDiff {
+    pub cycle_id: String,
+    pub branch: String,
+    pub base_commit: String,
+    pub head_commit: String,
+    pub commits: usize,
+    pub files_changed: Vec<String>,
+    pub patch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbandonedCycle {
+    pub cycle_id: String,
+    pub branch: String,
+    pub discarded_commits: usize,
+    /// What is checked out now; `None` when the cycle started from a detached HEAD
+    pub checked_out: Option<String>,
+}
+
+/// Keeps each development cycle's output on its own branch of the project
+/// repository, apart from manual edits
+///
+/// The branches it creates are marked in the repository's config, and it
+/// refuses to commit to, diff or delete any branch without that mark.
+#[derive(Debug, Clone)]
+pub struct CycleRepository {
+    path: PathBuf,
+    generated_dir: PathBuf,
+}
+
+impl CycleRepository {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), generated_dir: PathBuf::from(DEFAULT_GENERATED_DIR) }
+    }
+
+    pub fn with_generated_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.generated_dir = dir.into();
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn branch_name(cycle_id: &str) -> String {
+        format!("{}{}", CYCLE_BRANCH_PREFIX, cycle_id)
+    }
+
+    /// Branch off HEAD for `cycle_id` and check the branch out, or check out
+    /// the branch it already has when the cycle is resumed
+    pub fn start_cycle(&self, cycle_id: &str) -> Result<CycleBranch> {
+        validate_cycle_id(cycle_id)?;
+        let repo = self.open()?;
+        self.ensure_clean(&repo)?;
+        let name = Self::branch_name(cycle_id);
+        match repo.find_branch(&name, BranchType::Local) {
+            Ok(_) => {
+                let branch = owned_branch(&repo, cycle_id)?;
+                checkout_branch(&repo, &name)?;
+                return Ok(branch);
+            }
+            Err(e) if e.code() == ErrorCode::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let head = repo.head()?;
+        let origin_branch = if head.is_branch() { head.shorthand().map(str::to_string) } else { None };
+        let base = head.peel_to_commit()?;
+        repo.branch(&name, &base, false)?;
+        let mut config = repo.config()?;
+        config.set_str(&branch_key(&name, "axiomCycle"), cycle_id)?;
+        config.set_str(&branch_key(&name, "axiomBase"), &base.id().to_string())?;
+        if let Some(origin) = &origin_branch {
+            config.set_str(&branch_key(&name, "axiomOrigin"), origin)?;
+        }
+        checkout_branch(&repo, &name)?;
+        tracing::info!("Cycle {} works on {} from {}", cycle_id, name, base.id());
+
+        Ok(CycleBranch { cycle_id: cycle_id.to_string(), branch: name, base_commit: base.id().to_string(), origin_branch })
+    }
+
+    /// Write the stage's files and commit them to its cycle's branch, which
+    /// must be checked out
+    pub fn commit_stage(&self, commit: StageCommit) -> Result<CycleCommit> {
+        validate_cycle_id(&commit.cycle_id)?;
+        let repo = self.open()?;
+        let branch = owned_branch(&repo, &commit.cycle_id)?;
+        let head = repo.head()?;
+        if !head.is_branch() || head.shorthand() != Some(branch.branch.as_str()) {
+            return Err(AxiomMCPError::validation("development_workflow", "commit stage", format!(
+                "{} is not checked out, so the {} of cycle {} was not committed",
+                branch.branch, commit.stage, commit.cycle_id
+            )));
+        }
+        let workdir = repo.workdir().ok_or_else(|| {
+            AxiomMCPError::configuration("cycle_repository", format!("{} is a bare repository", self.path.display()))
+        })?;
+
+        let mut index = repo.index()?;
+        let mut files = Vec::new();
+        for artifact in &commit.files {
+            let relative = self.generated_dir.join(&artifact.name);
+            if !is_contained(&relative) {
+                return Err(AxiomMCPError::validation(
+                    "development_workflow", "commit stage", format!("'{}' is outside the repository", relative.display()),
+                ));
+            }
+            let path = workdir.join(&relative);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, &artifact.content)?;
+            index.add_path(&relative)?;
+            files.push(relative);
+        }
+        index.write()?;
+        let tree = repo.find_tree(index.write_tree()?)?;
+        let parent = head.peel_to_commit()?;
+        let signature = match repo.signature() {
+            Ok(signature) => signature,
+            Err(_) => Signature::now(FALLBACK_COMMITTER.0, FALLBACK_COMMITTER.1)?,
+        };
+        let message = stage_commit_message(&commit);
+        let id = repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &[&parent])?;
+
+        Ok(CycleCommit {
+            cycle_id: commit.cycle_id,
+            stage: commit.stage,
+            branch: branch.branch,
+            commit: id.to_string(),
+            message,
+            files,
+        })
+    }
+
+    /// The patch from where the cycle branched off to the tip of its branch
+    pub fn cycle_diff(&self, cycle_id: &str) -> Result<CycleDiff> {
+        validate_cycle_id(cycle_id)?;
+        let repo = self.open()?;
+        let branch = owned_branch(&repo, cycle_id)?;
+        let base = repo.find_commit(Oid::from_str(&branch.base_commit)?)?;
+        let head = repo.find_branch(&branch.branch, BranchType::Local)?.get().peel_to_commit()?;
+        let diff = repo.diff_tree_to_tree(Some(&base.tree()?), Some(&head.tree()?), None)?;
+
+        let files_changed = diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(|path| path.display().to_string())
+            .collect();
+        let mut patch = String::new();
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                patch.push(line.origin());
+            }
+            patch.push_str(&String::from_utf8_lossy(line.content()));
+            true
+        })?;
+
+        Ok(CycleDiff {
+            cycle_id: cycle_id.to_string(),
+            branch: branch.branch,
+            base_commit: branch.base_commit,
+            head_commit: head.id().to_string(),
+            commits: commits_since(&repo, head.id(), base.id())?,
+            files_changed,
+            patch,
+        })
+    }
+
+    /// Go back to where the cycle started and delete its branch, discarding
+    /// everything committed to it
+    pub fn abandon_cycle(&self, cycle_id: &str) -> Result<AbandonedCycle> {
+        validate_cycle_id(cycle_id)?;
+        let repo = self.open()?;
+        let branch = owned_branch(&repo, cycle_id)?;
+        let base = repo.find_commit(Oid::from_str(&branch.base_commit)?)?;
+        let mut cycle_branch = repo.find_branch(&branch.branch, BranchType::Local)?;
+        let discarded_commits = commits_since(&repo, cycle_branch.get().peel_to_commit()?.id(), base.id())?;
+
+        let head = repo.head()?;
+        let checked_out = if head.is_branch() && head.shorthand() == Some(branch.branch.as_str()) {
+            self.ensure_clean(&repo)?;
+            match &branch.origin_branch {
+                Some(origin) => checkout_branch(&repo, origin)?,
+                None => {
+                    repo.checkout_tree(base.as_object(), Some(CheckoutBuilder::new().safe()))?;
+                    repo.set_head_detached(base.id())?;
+                }
+            }
+            branch.origin_branch.clone()
+        } else if head.is_branch() {
+            head.shorthand().map(str::to_string)
+        } else {
+            None
+        };
+        // Deleting the branch also drops its config section, marks included
+        cycle_branch.delete()?;
+        tracing::info!("Abandoned cycle {}, discarding {} commits", cycle_id, discarded_commits);
+
+        Ok(AbandonedCycle { cycle_id: cycle_id.to_string(), branch: branch.branch, discarded_commits, checked_out })
+    }
+
+    fn open(&self) -> Result<Repository> {
+        Ok(Repository::open(&self.path)?)
+    }
+
+    /// Changes to tracked files would be carried onto, or lost from, a cycle
+    /// branch; untracked files are left where they are
+    fn ensure_clean(&self, repo: &Repository) -> Result<()> {
+        let mut options = StatusOptions::new();
+        options.include_untracked(false).include_ignored(false);
+        let dirty: Vec<String> = repo
+            .statuses(Some(&mut options))?
+            .iter()
+            .filter(|entry| !entry.status().is_empty())
+            .filter_map(|entry| entry.path().map(str::to_string))
+            .collect();
+        if dirty.is_empty() {
+            return Ok(());
+        }
+        Err(AxiomMCPError::validation("development_workflow", "check working tree", format!(
+            "The working tree of {} has uncommitted changes to {}; commit or stash them first",
+            self.path.display(),
+            dirty.join(", ")
+        )))
+    }
+}
+
+fn validate_cycle_id(cycle_id: &str) -> Result<()> {
+    if cycle_id.is_empty() || !cycle_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(AxiomMCPError::validation(
+            "development_workflow", "check cycle id", format!("'{}' is not a development cycle id", cycle_id),
+        ));
+    }
+    Ok(())
+}
+
+fn branch_key(branch: &str, key: &str) -> String {
+    format!("branch.{}.{}", branch, key)
+}
+
+/// The cycle's branch, provided it carries the mark of having been created
+/// for that cycle
+fn owned_branch(repo: &Repository, cycle_id: &str) -> Result<CycleBranch> {
+    let name = CycleRepository::branch_name(cycle_id);
+    match repo.find_branch(&name, BranchType::Local) {
+        Ok(_) => {}
+        Err(e) if e.code() == ErrorCode::NotFound => {
+            return Err(AxiomMCPError::validation(
+                "development_workflow", "find cycle branch", format!("Cycle {} has no branch", cycle_id),
+            ));
+        }
+        Err(e) => return Err(e.into()),
+    }
+    let config = repo.config()?;
+    let (owner, base) = match (config.get_string(&branch_key(&name, "axiomCycle")), config.get_string(&branch_key(&name, "axiomBase"))) {
+        (Ok(owner), Ok(base)) => (owner, base),
+        _ => return Err(AxiomMCPError::validation(
+            "development_workflow", "find cycle branch", format!("{} was not created by Axiom; leaving it alone", name),
+        )),
+    };
+    if owner != cycle_id {
+        return Err(AxiomMCPError::validation(
+            "development_workflow", "find cycle branch", format!("{} was not created by Axiom; leaving it alone", name),
+        ));
+    }
+    Ok(CycleBranch {
+        cycle_id: owner,
+        origin_branch: config.get_string(&branch_key(&name, "axiomOrigin")).ok(),
+        branch: name,
+        base_commit: base,
+    })
+}
+
+fn checkout_branch(repo: &Repository, branch: &str) -> Result<()> {
+    let reference = format!("refs/heads/{}", branch);
+    let target = repo.revparse_single(&reference)?;
+    repo.checkout_tree(&target, Some(CheckoutBuilder::new().safe()))?;
+    repo.set_head(&reference)?;
+    Ok(())
+}
+
+fn commits_since(repo: &Repository, head: Oid, base: Oid) -> Result<usize> {
+    let mut walk = repo.revwalk()?;
+    walk.push(head)?;
+    walk.hide(base)?;
+    Ok(walk.count())
+}
+
+fn is_contained(path: &Path) -> bool {
+    path.components().all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// A subject for people, and trailers with the cycle and stage for tools
+pub fn stage_commit_message(commit: &StageCommit) -> String {
+    let stage = serde_json::to_value(commit.stage)
+        .ok()
+        .and_then(|stage| stage.as_str().map(str::to_string))
+        .unwrap_or_else(|| commit.stage.to_string());
+    let mut message = format!("Axiom: approve the {} of cycle {}\n\n", commit.stage, commit.cycle_id);
+    if !commit.summary.is_empty() {
+        message.push_str(&commit.summary);
+        message.push_str("\n\n");
+    }
+    message.push_str(&format!("Axiom-Cycle: {}\nAxiom-Stage: {}\nAxiom-Attempt: {}\n", commit.cycle_id, stage, commit.attempt));
+    message
+}
+
+pub struct AxiomObservabilityWorkflow {
+    cycle_repository: Option<CycleRepository>,
+}
 
 impl AxiomObservabilityWorkflow {
     pub async fn new(
@@ -57,7 +414,54 @@ impl AxiomObservabilityWorkflow {
         _screenshot_engine: Arc<crate::screenshot_matrix_engine::ScreenshotMatrixEngine>,
         _visual_intelligence: Arc<crate::advanced_visual_intelligence::VisualIntelligenceEngine>
     ) -> Result<Self> {
-        Ok(Self)
+        Ok(Self { cycle_repository: None })
+    }
+    
+    /// Keep development cycles on their own branches of `repository`
+    pub fn with_cycle_repository(mut self, repository: CycleRepository) -> Self {
+        self.cycle_repository = Some(repository);
+        self
+    }
+    
+    pub fn cycle_repository(&self) -> Option<&CycleRepository> {
+        self.cycle_repository.as_ref()
+    }
+    
+    /// Check out the branch of `cycle_id`; `None` without a cycle repository
+    pub async fn start_cycle_branch(&self, cycle_id: &str) -> Result<Option<CycleBranch>> {
+        let repository = match self.cycle_repository.clone() {
+            Some(repository) => repository,
+            None => return Ok(None),
+        };
+        let cycle_id = cycle_id.to_string();
+        tokio::task::spawn_blocking(move || repository.start_cycle(&cycle_id)).await?.map(Some)
+    }
+    
+    /// Commit an approved stage; `None` without a cycle repository
+    pub async fn commit_stage(&self, commit: StageCommit) -> Result<Option<CycleCommit>> {
+        let repository = match self.cycle_repository.clone() {
+            Some(repository) => repository,
+            None => return Ok(None),
+        };
+        tokio::task::spawn_blocking(move || repository.commit_stage(commit)).await?.map(Some)
+    }
+    
+    pub async fn get_cycle_diff(&self, cycle_id: &str) -> Result<CycleDiff> {
+        let repository = self.required_cycle_repository()?;
+        let cycle_id = cycle_id.to_string();
+        tokio::task::spawn_blocking(move || repository.cycle_diff(&cycle_id)).await?
+    }
+    
+    pub async fn abandon_cycle(&self, cycle_id: &str) -> Result<AbandonedCycle> {
+        let repository = self.required_cycle_repository()?;
+        let cycle_id = cycle_id.to_string();
+        tokio::task::spawn_blocking(move || repository.abandon_cycle(&cycle_id)).await?
+    }
+    
+    fn required_cycle_repository(&self) -> Result<CycleRepository> {
+        self.cycle_repository.clone().ok_or_else(|| {
+            AxiomMCPError::configuration("cycle_repository", "Development cycles are not kept on git branches")
+        })
     }
     
     pub async fn start_development_session(&self, _spec: DevelopmentSessionSpec) -> Result<SessionHandle> {
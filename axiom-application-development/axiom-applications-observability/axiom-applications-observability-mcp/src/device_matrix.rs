@@ -0,0 +1,503 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::simulator::SimulatorDevice;
+use crate::types::{ScreenSize, ScreenshotConfiguration};
+use serde::{Deserialize, Serialize};
+
+/// Size in points and scale of the models presets capture on; custom
+/// devices of other models give their own
+const KNOWN_MODELS: &[(&str, f64, f64, f64)] = &[
+    ("iPhone 15 Pro", 393.0, 852.0, 3.0),
+    ("iPhone 15", 393.0, 852.0, 3.0),
+    ("iPhone 15 Pro Max", 430.0, 932.0, 3.0),
+    ("iPhone 16 Pro Max", 440.0, 956.0, 3.0),
+    ("iPhone 11 Pro Max", 414.0, 896.0, 3.0),
+    ("iPhone SE (3rd generation)", 375.0, 667.0, 2.0),
+    ("iPad Air 11-inch (M2)", 820.0, 1180.0, 2.0),
+    ("iPad Pro 13-inch (M4)", 1032.0, 1376.0, 2.0),
+];
+
+const ORIENTATIONS: &[&str] = &["portrait", "landscape"];
+
+/// Width of the divider between the apps of a split view, in points
+const SPLIT_DIVIDER_PT: f64 = 10.0;
+
+/// Narrowest iPad window with a regular horizontal size class, the half
+/// of a 13-inch iPad in landscape
+const IPAD_REGULAR_WIDTH_PT: f64 = 678.0;
+
+/// Narrowest iPhone screen with a regular horizontal size class, the Max
+/// and Plus models in landscape
+const IPHONE_REGULAR_WIDTH_PT: f64 = 896.0;
+
+/// How much of the screen the captured app's window takes on an iPad
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowConfiguration {
+    #[default]
+    Fullscreen,
+    /// Half of a split view
+    SplitHalf,
+    /// The narrow side of a split view
+    SplitThird,
+    /// A narrow window floating over another app
+    SlideOver,
+}
+
+impl WindowConfiguration {
+    pub const ALL: [WindowConfiguration; 4] = [
+        WindowConfiguration::Fullscreen,
+        WindowConfiguration::SplitHalf,
+        WindowConfiguration::SplitThird,
+        WindowConfiguration::SlideOver,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WindowConfiguration::Fullscreen => "fullscreen",
+            WindowConfiguration::SplitHalf => "split_half",
+            WindowConfiguration::SplitThird => "split_third",
+            WindowConfiguration::SlideOver => "slide_over",
+        }
+    }
+
+    /// The window's size in points on a screen of `screen` points, as
+    /// oriented for the capture
+    pub fn window_size(self, screen: &ScreenSize) -> ScreenSize {
+        // The narrow side of a split and slide-over are 375pt wide on the
+        // larger iPads and 320pt on the others
+        let narrow = if screen.width >= 1024.0 { 375.0 } else { 320.0 };
+        let width = match self {
+            WindowConfiguration::Fullscreen => screen.width,
+            WindowConfiguration::SplitHalf => (screen.width - SPLIT_DIVIDER_PT) / 2.0,
+            WindowConfiguration::SplitThird | WindowConfiguration::SlideOver => narrow,
+        };
+        ScreenSize { width, height: screen.height }
+    }
+}
+
+impl std::fmt::Display for WindowConfiguration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Horizontal size class a layout adapts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeClass {
+    Compact,
+    Regular,
+}
+
+impl SizeClass {
+    /// The size class of a `width`pt wide window on `model`
+    pub fn of(model: &str, width: f64) -> Self {
+        let regular_from = if is_ipad(model) { IPAD_REGULAR_WIDTH_PT } else { IPHONE_REGULAR_WIDTH_PT };
+        if width >= regular_from {
+            SizeClass::Regular
+        } else {
+            SizeClass::Compact
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SizeClass::Compact => "compact",
+            SizeClass::Regular => "regular",
+        }
+    }
+}
+
+/// Whether `model` runs iPadOS, where windows can share the screen
+pub fn is_ipad(model: &str) -> bool {
+    model.starts_with("iPad")
+}
+
+/// Device lists for common screenshot matrices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceMatrixPreset {
+    /// Two current iPhones
+    MobileOnly,
+    /// One large iPad
+    TabletOnly,
+    /// The iPhones and the iPad
+    #[serde(alias = "all_devices")]
+    IpadInclusive,
+    /// The display sizes App Store screenshots are asked for, in portrait
+    /// and light mode only
+    Marketing,
+    /// Two iPads in landscape, fullscreen, split and in slide-over
+    #[serde(alias = "ipad-multitasking")]
+    IpadMultitasking,
+}
+
+impl DeviceMatrixPreset {
+    pub const ALL: [DeviceMatrixPreset; 5] = [
+        DeviceMatrixPreset::MobileOnly,
+        DeviceMatrixPreset::TabletOnly,
+        DeviceMatrixPreset::IpadInclusive,
+        DeviceMatrixPreset::Marketing,
+        DeviceMatrixPreset::IpadMultitasking,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DeviceMatrixPreset::MobileOnly => "mobile_only",
+            DeviceMatrixPreset::TabletOnly => "tablet_only",
+            DeviceMatrixPreset::IpadInclusive => "ipad_inclusive",
+            DeviceMatrixPreset::Marketing => "marketing",
+            DeviceMatrixPreset::IpadMultitasking => "ipad_multitasking",
+        }
+    }
+
+    /// The preset named `name`, also taking `all_devices` for
+    /// `ipad_inclusive` and dashes for underscores
+    pub fn parse(name: &str) -> Option<Self> {
+        if name == "all_devices" {
+            return Some(DeviceMatrixPreset::IpadInclusive);
+        }
+        let name = name.replace('-', "_");
+        Self::ALL.into_iter().find(|preset| preset.name() == name)
+    }
+
+    pub fn matrix(self) -> DeviceMatrixConfig {
+        let (models, orientations, color_schemes): (&[&str], &[&str], &[&str]) = match self {
+            DeviceMatrixPreset::MobileOnly => (&["iPhone 15 Pro", "iPhone 15"], ORIENTATIONS, &["light", "dark"]),
+            DeviceMatrixPreset::TabletOnly => (&["iPad Pro 13-inch (M4)"], ORIENTATIONS, &["light", "dark"]),
+            DeviceMatrixPreset::IpadInclusive => {
+                (&["iPhone 15 Pro", "iPhone 15", "iPad Pro 13-inch (M4)"], ORIENTATIONS, &["light", "dark"])
+            }
+            DeviceMatrixPreset::Marketing => {
+                (&["iPhone 16 Pro Max", "iPhone 11 Pro Max", "iPad Pro 13-inch (M4)"], &["portrait"], &["light"])
+            }
+            DeviceMatrixPreset::IpadMultitasking => {
+                (&["iPad Pro 13-inch (M4)", "iPad Air 11-inch (M2)"], &["landscape"], &["light"])
+            }
+        };
+        let window_configurations = match self {
+            DeviceMatrixPreset::IpadMultitasking => WindowConfiguration::ALL.to_vec(),
+            _ => default_window_configurations(),
+        };
+        DeviceMatrixConfig {
+            devices: models
+                .iter()
+                .map(|model| MatrixDevice {
+                    model: model.to_string(),
+                    os_version: None,
+                    orientations: orientations.iter().map(ToString::to_string).collect(),
+                    screen_size: None,
+                    scale: None,
+                })
+                .collect(),
+            color_schemes: color_schemes.iter().map(ToString::to_string).collect(),
+            window_configurations,
+            substitute_nearest_runtime: false,
+        }
+    }
+}
+
+/// A device of a custom screenshot matrix
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatrixDevice {
+    /// Simulator device type name, `iPhone 15 Pro`
+    pub model: String,
+    /// `17.2`, or `17` for the newest 17.x; the newest installed runtime
+    /// when unset
+    #[serde(default)]
+    pub os_version: Option<String>,
+    #[serde(default = "default_orientations")]
+    pub orientations: Vec<String>,
+    /// Screen size in points; known models have theirs filled in
+    #[serde(default)]
+    pub screen_size: Option<ScreenSize>,
+    #[serde(default)]
+    pub scale: Option<f64>,
+}
+
+fn default_orientations() -> Vec<String> {
+    ORIENTATIONS.iter().map(ToString::to_string).collect()
+}
+
+fn default_color_schemes() -> Vec<String> {
+    vec!["light".to_string(), "dark".to_string()]
+}
+
+fn default_window_configurations() -> Vec<WindowConfiguration> {
+    vec![WindowConfiguration::Fullscreen]
+}
+
+/// The devices, orientations, color schemes and iPad windows a
+/// screenshot matrix covers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceMatrixConfig {
+    pub devices: Vec<MatrixDevice>,
+    #[serde(default = "default_color_schemes")]
+    pub color_schemes: Vec<String>,
+    /// Windows iPads capture the app in; other devices capture fullscreen
+    /// only, with a warning when this asks for more
+    #[serde(default = "default_window_configurations")]
+    pub window_configurations: Vec<WindowConfiguration>,
+    /// Capture on the installed runtime nearest a missing one, with a
+    /// warning, instead of failing
+    #[serde(default)]
+    pub substitute_nearest_runtime: bool,
+}
+
+impl DeviceMatrixConfig {
+    /// Every device, orientation, color scheme and window combination,
+    /// grouped by device so a simulator captures its configurations in a
+    /// row, on whichever runtime the simulator of each model has
+    pub fn configurations(&self) -> Result<Vec<ScreenshotConfiguration>> {
+        self.check()?;
+        let mut configurations = Vec::new();
+        for device in &self.devices {
+            configurations.extend(self.device_configurations(device, None)?);
+        }
+        Ok(configurations)
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.devices.is_empty() {
+            return Err(invalid("A screenshot matrix needs at least one device"));
+        }
+        if self.color_schemes.is_empty() {
+            return Err(invalid("A screenshot matrix needs at least one color scheme"));
+        }
+        if let Some(scheme) = self.color_schemes.iter().find(|scheme| !matches!(scheme.as_str(), "light" | "dark")) {
+            return Err(invalid(format!("Unknown color scheme {}; expected light or dark", scheme)));
+        }
+        if self.window_configurations.is_empty() {
+            return Err(invalid("A screenshot matrix needs at least one window configuration"));
+        }
+        Ok(())
+    }
+
+    /// The devices that capture fewer windows than asked for, since only
+    /// iPads share the screen
+    pub fn warnings(&self) -> Vec<String> {
+        let split: Vec<&str> = self.window_configurations
+            .iter()
+            .filter(|window| **window != WindowConfiguration::Fullscreen)
+            .map(|window| window.name())
+            .collect();
+        if split.is_empty() {
+            return Vec::new();
+        }
+        self.devices
+            .iter()
+            .filter(|device| !is_ipad(&device.model))
+            .map(|device| format!(
+                "{} is not an iPad, so it captures fullscreen only and skips {}", device.model, split.join(", ")
+            ))
+            .collect()
+    }
+
+    /// The windows `model` captures in
+    fn windows_of(&self, model: &str) -> Vec<WindowConfiguration> {
+        if is_ipad(model) {
+            return self.window_configurations.clone();
+        }
+        vec![WindowConfiguration::Fullscreen]
+    }
+
+    fn device_configurations(&self, device: &MatrixDevice, runtime: Option<&str>) -> Result<Vec<ScreenshotConfiguration>> {
+        let known = KNOWN_MODELS.iter().find(|(model, ..)| *model == device.model);
+        let (screen_size, scale) = match (&device.screen_size, device.scale, known) {
+            (Some(size), Some(scale), _) => (size.clone(), scale),
+            (size, scale, Some(&(_, width, height, known_scale))) => {
+                (size.clone().unwrap_or(ScreenSize { width, height }), scale.unwrap_or(known_scale))
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "{} is not a model the engine knows the size of; give its screen_size and scale", device.model
+                )));
+            }
+        };
+        if device.orientations.is_empty() {
+            return Err(invalid(format!("{} needs at least one orientation", device.model)));
+        }
+
+        let mut configurations = Vec::new();
+        for orientation in &device.orientations {
+            let (width, height) = match orientation.as_str() {
+                "portrait" => (screen_size.width, screen_size.height),
+                "landscape" => (screen_size.height, screen_size.width),
+                other => {
+                    return Err(invalid(format!(
+                        "Unknown orientation {} for {}; expected portrait or landscape", other, device.model
+                    )));
+                }
+            };
+            for window in self.windows_of(&device.model) {
+                for color_scheme in &self.color_schemes {
+                    configurations.push(ScreenshotConfiguration {
+                        device_type: device.model.clone(),
+                        screen_size: window.window_size(&ScreenSize { width, height }),
+                        orientation: orientation.clone(),
+                        scale,
+                        color_scheme: color_scheme.clone(),
+                        capture_mode: "standard".to_string(),
+                        status_bar: None,
+                        runtime: runtime.map(str::to_string),
+                        window,
+                    });
+                }
+            }
+        }
+        Ok(configurations)
+    }
+}
+
+/// Arguments of `capture_screenshot_matrix`: a preset or a custom device
+/// list, `ipad_inclusive` when neither is given
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotMatrixRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<DeviceMatrixPreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom: Option<DeviceMatrixConfig>,
+    /// UI script bringing the app to the screen to capture
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_capture_script: Option<String>,
+}
+
+impl ScreenshotMatrixRequest {
+    pub fn matrix(&self) -> Result<DeviceMatrixConfig> {
+        match (self.preset, &self.custom) {
+            (Some(_), Some(_)) => Err(invalid("Give either a preset or a custom device list, not both")),
+            (Some(preset), None) => Ok(preset.matrix()),
+            (None, Some(custom)) => Ok(custom.clone()),
+            (None, None) => Ok(DeviceMatrixPreset::IpadInclusive.matrix()),
+        }
+    }
+}
+
+fn invalid(message: impl Into<String>) -> AxiomMCPError {
+    AxiomMCPError::validation("device_matrix", "configure device matrix", message)
+}
+
+/// Which runtime a matrix device is captured on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedDevice {
+    pub model: String,
+    /// `iOS 17.2`
+    pub runtime: String,
+    #[serde(default)]
+    pub requested_os_version: Option<String>,
+    /// The requested runtime is not installed and the nearest one stands in
+    #[serde(default)]
+    pub substituted: bool,
+}
+
+/// A matrix with a runtime picked for every device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedDeviceMatrix {
+    pub devices: Vec<ResolvedDevice>,
+    pub configurations: Vec<ScreenshotConfiguration>,
+    /// Substituted runtimes, and devices that skip windows only iPads have
+    pub warnings: Vec<String>,
+}
+
+/// Pick the runtime of each device of `matrix` from the simulators of
+/// `simctl list devices`
+///
+/// A device captures on the newest available simulator of its model that
+/// runs the requested OS version, or any version when none is requested.
+/// A missing model or runtime fails resolution, naming every device that
+/// cannot be captured; with `substitute_nearest_runtime` a missing runtime
+/// is replaced by the closest installed one instead, newer on a tie.
+pub fn resolve_device_matrix(matrix: &DeviceMatrixConfig, installed: &[SimulatorDevice]) -> Result<ResolvedDeviceMatrix> {
+    matrix.check()?;
+    let mut resolved = ResolvedDeviceMatrix { devices: Vec::new(), configurations: Vec::new(), warnings: matrix.warnings() };
+    let mut problems = Vec::new();
+
+    for device in &matrix.devices {
+        let mut runtimes: Vec<(Vec<u32>, &str)> = installed
+            .iter()
+            .filter(|simulator| simulator.is_available && simulator.name == device.model)
+            .filter_map(|simulator| Some((os_version(&simulator.runtime)?, simulator.runtime.as_str())))
+            .collect();
+        runtimes.sort();
+        runtimes.dedup();
+        // Newest first
+        runtimes.reverse();
+
+        if runtimes.is_empty() {
+            problems.push(format!("no {} simulator is installed; create one with simctl create", device.model));
+            continue;
+        }
+        let installed_runtimes = || runtimes.iter().rev().map(|(_, runtime)| *runtime).collect::<Vec<_>>().join(", ");
+
+        let (runtime, substituted) = match &device.os_version {
+            None => (runtimes[0].1, false),
+            Some(requested) => {
+                let Some(wanted) = os_version(requested) else {
+                    problems.push(format!("{} asks for OS version '{}', which is not a version number", device.model, requested));
+                    continue;
+                };
+                match runtimes.iter().find(|(version, _)| matches_version(version, &wanted)) {
+                    Some((_, runtime)) => (*runtime, false),
+                    None if matrix.substitute_nearest_runtime => {
+                        let (_, nearest) = runtimes
+                            .iter()
+                            .min_by(|(a, _), (b, _)| {
+                                distance(a, &wanted).cmp(&distance(b, &wanted)).then_with(|| b.cmp(a))
+                            })
+                            .expect("runtimes is not empty");
+                        resolved.warnings.push(format!(
+                            "{}: OS {} is not installed; capturing on {} instead", device.model, requested, nearest
+                        ));
+                        (*nearest, true)
+                    }
+                    None => {
+                        problems.push(format!(
+                            "{} needs OS {}, which is not installed (installed: {}); install the runtime or allow substituting the nearest one",
+                            device.model, requested, installed_runtimes()
+                        ));
+                        continue;
+                    }
+                }
+            }
+        };
+
+        resolved.configurations.extend(matrix.device_configurations(device, Some(runtime))?);
+        resolved.devices.push(ResolvedDevice {
+            model: device.model.clone(),
+            runtime: runtime.to_string(),
+            requested_os_version: device.os_version.clone(),
+            substituted,
+        });
+    }
+
+    if !problems.is_empty() {
+        return Err(AxiomMCPError::simulator("device_matrix", "resolve device matrix", format!(
+            "The screenshot matrix cannot be captured: {}", problems.join("; ")
+        )));
+    }
+    Ok(resolved)
+}
+
+/// `17.2` out of `iOS 17.2`, `17.2` or `17`
+fn os_version(text: &str) -> Option<Vec<u32>> {
+    let number = text.split_whitespace().last()?;
+    let components: Vec<u32> = number.split('.').map(|part| part.parse().ok()).collect::<Option<_>>()?;
+    (1..=3).contains(&components.len()).then_some(components)
+}
+
+/// Whether `version` is `wanted` or, when `wanted` leaves out its minor
+/// or patch version, one of its releases
+fn matches_version(version: &[u32], wanted: &[u32]) -> bool {
+    wanted.iter().enumerate().all(|(index, component)| version.get(index).copied().unwrap_or(0) == *component)
+}
+
+/// How far apart two versions are, major versions first
+fn distance(version: &[u32], wanted: &[u32]) -> Vec<u32> {
+    (0..3)
+        .map(|index| {
+            let (a, b) = (version.get(index).copied().unwrap_or(0), wanted.get(index).copied().unwrap_or(0));
+            a.abs_diff(b)
+        })
+        .collect()
+}
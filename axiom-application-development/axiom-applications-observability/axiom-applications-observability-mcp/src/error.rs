@@ -1,49 +1,330 @@
+use crate::resource_locks::LockHolder;
+use crate::tool_registry::SchemaViolation;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
 /// Result type for Axiom MCP operations
 pub type Result<T> = std::result::Result<T, AxiomMCPError>;
 
-/// Comprehensive error types for the Axiom Applications Observability MCP
+type Cause = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// The kind of failure, which decides whether trying again can help
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The configuration or the enabled capabilities rule the operation out
+    Configuration,
+    /// A server connection is down, or a request over it went unanswered
+    Connectivity,
+    /// An external tool such as simctl, swiftc, xcodebuild or git failed
+    Toolchain,
+    /// Templates, or the code generated from them, are unusable
+    Generation,
+    /// The request is malformed or names something that does not exist
+    Validation,
+    /// A simulator is missing, in the wrong state or misbehaving
+    Simulator,
+    /// The MCP itself failed: storage, serialization, background tasks
+    Internal,
+    /// Another tool call holds a resource the operation needs
+    Busy,
+}
+
+/// Where an error happened and what caused it
+#[derive(Debug)]
+pub struct ErrorContext {
+    /// The part of the MCP that failed, e.g. `simulator` or `development_loop`
+    pub subsystem: &'static str,
+    /// What it was doing, e.g. `boot` or `load checkpoint`
+    pub operation: String,
+    pub message: String,
+    /// Whether the same operation may succeed when it is tried again
+    pub transient: bool,
+    cause: Option<Cause>,
+}
+
+impl ErrorContext {
+    fn new(subsystem: &'static str, operation: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { subsystem, operation: operation.into(), message: message.into(), transient: false, cause: None }
+    }
+
+    /// The underlying error, such as the I/O or git error
+    pub fn cause(&self) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        self.cause.as_deref()
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ErrorContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause.as_deref().map(|cause| cause as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Errors of the Axiom Applications Observability MCP, by category
 #[derive(Error, Debug)]
 pub enum AxiomMCPError {
-    #[error("MCP initialization failed: {0}")]
-    InitializationError(String),
-    
-    #[error("Tool execution failed: {0}")]
-    ToolExecutionError(String),
-    
-    #[error("Validation error: {0}")]
-    ValidationError(String),
-    
-    #[error("Code generation error: {0}")]
-    CodeGenerationError(String),
-    
-    #[error("Network error: {0}")]
-    NetworkError(String),
-    
-    #[error("Serialization error: {0}")]
-    SerializationError(#[from] serde_json::Error),
-    
-    #[error("IO error: {0}")]
-    IoError(#[from] std::io::Error),
-    
-    #[error("HTTP request error: {0}")]
-    HttpError(#[from] reqwest::Error),
-    
-    #[error("Task join error: {0}")]
-    TaskError(#[from] tokio::task::JoinError),
-    
-    #[error("Generic error: {0}")]
-    Other(#[from] anyhow::Error),
+    /// A configuration field that is missing, malformed or contradicts
+    /// another
+    #[error("Invalid configuration for {field}: {message}")]
+    Configuration { field: String, message: String },
+
+    /// A capability the operation needs that `MCPCapabilities` leaves off,
+    /// with the tool that needed it when a tool call did
+    #[error("The {capability} capability is not enabled{}", .tool.as_ref().map(|tool| format!("; {} needs it", tool)).unwrap_or_default())]
+    CapabilityDisabled { capability: String, tool: Option<String> },
+
+    #[error(transparent)]
+    Connectivity(ErrorContext),
+
+    #[error(transparent)]
+    Toolchain(ErrorContext),
+
+    #[error(transparent)]
+    Generation(ErrorContext),
+
+    #[error(transparent)]
+    Validation(ErrorContext),
+
+    #[error("Unknown tool: {0}")]
+    UnknownTool(String),
+
+    /// Arguments of a tool call that do not match its input schema
+    #[error("Invalid arguments for {tool}: {}", describe_violations(.violations))]
+    InvalidToolArguments { tool: String, violations: Vec<SchemaViolation> },
+
+    /// A message on a server connection that does not match the protocol
+    /// schema, with the fields that stray from it
+    #[error("The {message_type} message on the {connection} connection does not match the protocol: {}", describe_violations(.violations))]
+    ProtocolViolation { connection: String, message_type: String, violations: Vec<SchemaViolation> },
+
+    #[error(transparent)]
+    Simulator(ErrorContext),
+
+    #[error(transparent)]
+    Internal(ErrorContext),
+
+    /// A tool call that waited out its timeout for a resource another call holds
+    #[error("Resource busy: {} has been held by {} since {}; gave up after waiting {waited_ms} ms",
+        .holder.resource, .holder.tool, .holder.since.format("%H:%M:%S UTC"))]
+    ResourceBusy { holder: LockHolder, waited_ms: u64 },
 }
 
 impl AxiomMCPError {
-    pub fn is_recoverable(&self) -> bool {
+    pub fn configuration(field: impl Into<String>, message: impl Into<String>) -> Self {
+        AxiomMCPError::Configuration { field: field.into(), message: message.into() }
+    }
+
+    /// An operation needing `capability`, which `MCPCapabilities` leaves off
+    pub fn capability_disabled(capability: &str) -> Self {
+        AxiomMCPError::CapabilityDisabled { capability: capability.to_string(), tool: None }
+    }
+
+    /// The tool `tool` needing `capability`, refused before it runs
+    pub fn tool_capability_disabled(tool: &str, capability: &str) -> Self {
+        AxiomMCPError::CapabilityDisabled { capability: capability.to_string(), tool: Some(tool.to_string()) }
+    }
+
+    /// A `message_type` message on `connection` breaking the schema as `violations` say
+    pub fn protocol_violation(connection: &str, message_type: &str, violations: Vec<SchemaViolation>) -> Self {
+        AxiomMCPError::ProtocolViolation {
+            connection: connection.to_string(),
+            message_type: message_type.to_string(),
+            violations,
+        }
+    }
+
+    pub fn connectivity(subsystem: &'static str, operation: impl Into<String>, message: impl Into<String>) -> Self {
+        AxiomMCPError::Connectivity(ErrorContext::new(subsystem, operation, message))
+    }
+
+    pub fn toolchain(subsystem: &'static str, operation: impl Into<String>, message: impl Into<String>) -> Self {
+        AxiomMCPError::Toolchain(ErrorContext::new(subsystem, operation, message))
+    }
+
+    pub fn generation(subsystem: &'static str, operation: impl Into<String>, message: impl Into<String>) -> Self {
+        AxiomMCPError::Generation(ErrorContext::new(subsystem, operation, message))
+    }
+
+    pub fn validation(subsystem: &'static str, operation: impl Into<String>, message: impl Into<String>) -> Self {
+        AxiomMCPError::Validation(ErrorContext::new(subsystem, operation, message))
+    }
+
+    pub fn simulator(subsystem: &'static str, operation: impl Into<String>, message: impl Into<String>) -> Self {
+        AxiomMCPError::Simulator(ErrorContext::new(subsystem, operation, message))
+    }
+
+    pub fn internal(subsystem: &'static str, operation: impl Into<String>, message: impl Into<String>) -> Self {
+        AxiomMCPError::Internal(ErrorContext::new(subsystem, operation, message))
+    }
+
+    /// Keep `cause` as the error's source
+    pub fn with_cause(mut self, cause: impl Into<Cause>) -> Self {
+        if let Some(context) = self.context_mut() {
+            context.cause = Some(cause.into());
+        }
+        self
+    }
+
+    /// Mark the failure as one that may not happen again, such as a timeout
+    pub fn transient(mut self) -> Self {
+        if let Some(context) = self.context_mut() {
+            context.transient = true;
+        }
+        self
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            AxiomMCPError::Configuration { .. } |
+            AxiomMCPError::CapabilityDisabled { .. } |
+            AxiomMCPError::ProtocolViolation { .. } => ErrorCategory::Configuration,
+            AxiomMCPError::Connectivity(_) => ErrorCategory::Connectivity,
+            AxiomMCPError::Toolchain(_) => ErrorCategory::Toolchain,
+            AxiomMCPError::Generation(_) => ErrorCategory::Generation,
+            AxiomMCPError::Validation(_) |
+            AxiomMCPError::UnknownTool(_) |
+            AxiomMCPError::InvalidToolArguments { .. } => ErrorCategory::Validation,
+            AxiomMCPError::Simulator(_) => ErrorCategory::Simulator,
+            AxiomMCPError::Internal(_) => ErrorCategory::Internal,
+            AxiomMCPError::ResourceBusy { .. } => ErrorCategory::Busy,
+        }
+    }
+
+    pub fn context(&self) -> Option<&ErrorContext> {
         match self {
-            AxiomMCPError::NetworkError(_) => true,
-            AxiomMCPError::HttpError(_) => true,
-            AxiomMCPError::TaskError(_) => true,
-            _ => false,
+            AxiomMCPError::Connectivity(context) |
+            AxiomMCPError::Toolchain(context) |
+            AxiomMCPError::Generation(context) |
+            AxiomMCPError::Validation(context) |
+            AxiomMCPError::Simulator(context) |
+            AxiomMCPError::Internal(context) => Some(context),
+            AxiomMCPError::Configuration { .. } |
+            AxiomMCPError::CapabilityDisabled { .. } |
+            AxiomMCPError::ProtocolViolation { .. } |
+            AxiomMCPError::UnknownTool(_) |
+            AxiomMCPError::InvalidToolArguments { .. } |
+            AxiomMCPError::ResourceBusy { .. } => None,
         }
     }
-}
\ No newline at end of file
+
+    fn context_mut(&mut self) -> Option<&mut ErrorContext> {
+        match self {
+            AxiomMCPError::Connectivity(context) |
+            AxiomMCPError::Toolchain(context) |
+            AxiomMCPError::Generation(context) |
+            AxiomMCPError::Validation(context) |
+            AxiomMCPError::Simulator(context) |
+            AxiomMCPError::Internal(context) => Some(context),
+            AxiomMCPError::Configuration { .. } |
+            AxiomMCPError::CapabilityDisabled { .. } |
+            AxiomMCPError::ProtocolViolation { .. } |
+            AxiomMCPError::UnknownTool(_) |
+            AxiomMCPError::InvalidToolArguments { .. } |
+            AxiomMCPError::ResourceBusy { .. } => None,
+        }
+    }
+
+    /// Whether running the same operation again may succeed. Connectivity
+    /// and busy failures always may; toolchain, simulator and internal
+    /// failures only when transient; configuration, validation and
+    /// generation failures need something to change first.
+    pub fn is_retryable(&self) -> bool {
+        match self.category() {
+            ErrorCategory::Connectivity | ErrorCategory::Busy => true,
+            ErrorCategory::Toolchain | ErrorCategory::Simulator | ErrorCategory::Internal => {
+                self.context().is_some_and(|context| context.transient)
+            }
+            ErrorCategory::Configuration | ErrorCategory::Generation | ErrorCategory::Validation => false,
+        }
+    }
+
+    /// The error as MCP clients receive it
+    pub fn report(&self) -> ErrorReport {
+        let context = self.context();
+        ErrorReport {
+            category: self.category(),
+            subsystem: context.map(|context| context.subsystem.to_string()),
+            operation: context.map(|context| context.operation.clone()),
+            field: match self {
+                AxiomMCPError::Configuration { field, .. } => Some(field.clone()),
+                AxiomMCPError::CapabilityDisabled { capability, .. } => Some(capability.clone()),
+                AxiomMCPError::ProtocolViolation { violations, .. } => violations.first().map(|violation| violation.path.clone()),
+                _ => None,
+            },
+            message: self.to_string(),
+            cause: context.and_then(ErrorContext::cause).map(ToString::to_string),
+            retryable: self.is_retryable(),
+        }
+    }
+}
+
+/// Serializable form of an `AxiomMCPError`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorReport {
+    pub category: ErrorCategory,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsystem: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// The configuration field or capability of a configuration error, or
+    /// the JSON pointer to the first stray field of a protocol violation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cause: Option<String>,
+    pub retryable: bool,
+}
+
+impl From<serde_json::Error> for AxiomMCPError {
+    fn from(error: serde_json::Error) -> Self {
+        AxiomMCPError::internal("serialization", "encode or decode JSON", error.to_string()).with_cause(error)
+    }
+}
+
+impl From<std::io::Error> for AxiomMCPError {
+    fn from(error: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        let transient = matches!(error.kind(), ErrorKind::TimedOut | ErrorKind::Interrupted | ErrorKind::WouldBlock);
+        let error = AxiomMCPError::internal("storage", "file I/O", error.to_string()).with_cause(error);
+        if transient { error.transient() } else { error }
+    }
+}
+
+impl From<reqwest::Error> for AxiomMCPError {
+    fn from(error: reqwest::Error) -> Self {
+        AxiomMCPError::connectivity("http", "request", error.to_string()).with_cause(error)
+    }
+}
+
+impl From<git2::Error> for AxiomMCPError {
+    fn from(error: git2::Error) -> Self {
+        // Another git process holding the index or a ref lock lets go
+        let transient = error.code() == git2::ErrorCode::Locked;
+        let error = AxiomMCPError::toolchain("git", "git operation", error.message().to_string()).with_cause(error);
+        if transient { error.transient() } else { error }
+    }
+}
+
+impl From<tokio::task::JoinError> for AxiomMCPError {
+    fn from(error: tokio::task::JoinError) -> Self {
+        AxiomMCPError::internal("tasks", "join task", error.to_string()).with_cause(error)
+    }
+}
+
+impl From<anyhow::Error> for AxiomMCPError {
+    fn from(error: anyhow::Error) -> Self {
+        AxiomMCPError::internal("tasks", "run", error.to_string()).with_cause(error)
+    }
+}
+
+fn describe_violations(violations: &[SchemaViolation]) -> String {
+    violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+}
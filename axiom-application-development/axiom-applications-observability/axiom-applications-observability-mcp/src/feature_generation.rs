@@ -0,0 +1,481 @@
+use crate::code_generation::{
+    is_swift_identifier, lower_first, plural, upper_first, AxiomCodeGenerator, NavigationSpec, PersistedField,
+    PersistenceSpec, PresentationStyle, RouteSpec, TemplateKind,
+};
+use crate::error::{AxiomMCPError, Result};
+use crate::types::*;
+use futures_util::future::try_join_all;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// A whole feature, from which the generator derives a Context, its
+/// Client, a Presentation per screen and, when asked for, persistence for
+/// the entities and navigation between the screens, all named consistently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureSpec {
+    /// `TaskManager` gives `TaskManagerContext` and `TaskManagerClient`
+    pub name: String,
+    #[serde(default)]
+    pub entities: Vec<FeatureEntity>,
+    /// The first screen is the root of the navigation
+    pub screens: Vec<FeatureScreen>,
+    /// Generate a SwiftData model for each entity, the client loading and
+    /// saving the first
+    #[serde(default)]
+    pub persistence: bool,
+    /// Connect the screens with a `NavigationStack`, pushing every other
+    /// screen from the first
+    #[serde(default)]
+    pub navigation: bool,
+    /// Template variable values for every artifact of a kind, as in
+    /// `PresentationSpec::template_overrides`
+    #[serde(default)]
+    pub template_overrides: BTreeMap<TemplateKind, HashMap<String, String>>,
+}
+
+/// Something the feature keeps track of, held by the context as an array
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureEntity {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<PersistedField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureScreen {
+    /// `TaskList` gives the presentation `TaskListView`
+    pub name: String,
+    /// Client actions the screen triggers through the context, a button each
+    #[serde(default)]
+    pub actions: Vec<String>,
+}
+
+/// The specs a feature generates from
+#[derive(Debug, Clone)]
+pub struct FeatureSpecs {
+    pub context: ContextSpec,
+    pub client: ClientSpec,
+    pub presentations: Vec<PresentationSpec>,
+    pub persistence: Vec<PersistenceSpec>,
+    pub navigation: Option<NavigationSpec>,
+}
+
+impl FeatureSpec {
+    pub fn context_name(&self) -> String {
+        format!("{}Context", self.name)
+    }
+
+    pub fn client_name(&self) -> String {
+        format!("{}Client", self.name)
+    }
+
+    /// Actions of every screen, each once, in the order screens use them
+    pub fn actions(&self) -> Vec<&str> {
+        let mut actions = Vec::new();
+        for action in self.screens.iter().flat_map(|screen| &screen.actions) {
+            if !actions.contains(&action.as_str()) {
+                actions.push(action.as_str());
+            }
+        }
+        actions
+    }
+
+    /// Derive the specs of every artifact, so the context binds to the
+    /// client, each presentation to the context, and the screens call
+    /// actions the client declares
+    pub fn derive_specs(&self) -> Result<FeatureSpecs> {
+        self.validate()?;
+        let overrides = |kind: TemplateKind| self.template_overrides.get(&kind).cloned().unwrap_or_default();
+
+        let state_properties = self.entities.iter()
+            .map(|entity| StateProperty {
+                name: lower_first(&plural(&entity.name)),
+                property_type: format!("[{}]", entity.name),
+                is_published: true,
+                default_value: Some("[]".to_string()),
+                requirement_ids: vec![],
+            })
+            .chain(std::iter::once(StateProperty {
+                name: "isLoading".to_string(),
+                property_type: "Bool".to_string(),
+                is_published: true,
+                default_value: Some("false".to_string()),
+                requirement_ids: vec![],
+            }))
+            .collect();
+        let persistence: Vec<PersistenceSpec> = if self.persistence {
+            self.entities.iter()
+                .map(|entity| PersistenceSpec {
+                    entity_name: entity.name.clone(),
+                    fields: entity.fields.clone(),
+                    relationships: vec![],
+                    migration_strategy: Default::default(),
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let context = ContextSpec {
+            name: self.context_name(),
+            state_properties,
+            client_binding: self.client_name(),
+            lifecycle_management: true,
+            template_overrides: overrides(TemplateKind::Context),
+        };
+        let client = ClientSpec {
+            name: self.client_name(),
+            protocol_conformance: vec![],
+            actions: self.actions().into_iter()
+                .map(|action| ActionDefinition {
+                    name: action.to_string(),
+                    parameters: vec![],
+                    return_type: "Void".to_string(),
+                    is_async: true,
+                    requirement_ids: vec![],
+                })
+                .collect(),
+            state_streaming: true,
+            mock_implementation: true,
+            template_overrides: overrides(TemplateKind::Client),
+            persistence: persistence.first().cloned(),
+        };
+        let presentations = self.screens.iter()
+            .map(|screen| PresentationSpec {
+                name: presentation_name(&screen.name),
+                context_binding: self.context_name(),
+                ui_components: screen.actions.iter()
+                    .map(|action| format!("Button(\"{}\") {{ Task {{ await context.{}() }} }}", action_label(action), action))
+                    .collect(),
+                accessibility_requirements: vec![],
+                performance_requirements: PerformanceRequirements::default(),
+                template_overrides: overrides(TemplateKind::Presentation),
+                requirement_ids: vec![],
+            })
+            .collect();
+        let navigation = self.navigation.then(|| NavigationSpec {
+            root: presentation_name(&self.screens[0].name),
+            routes: self.screens[1..].iter()
+                .map(|screen| RouteSpec {
+                    name: lower_first(&screen.name),
+                    presentation: presentation_name(&screen.name),
+                    parameters: vec![],
+                    style: PresentationStyle::Push,
+                    from: vec![],
+                })
+                .collect(),
+        });
+
+        Ok(FeatureSpecs { context, client, presentations, persistence, navigation })
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |message: String| -> Result<()> {
+            Err(AxiomMCPError::validation("code_generation", "generate feature", message))
+        };
+        if !is_swift_identifier(&self.name) {
+            return invalid(format!("Feature name '{}' is not a Swift identifier", self.name));
+        }
+        if self.screens.is_empty() {
+            return invalid(format!("Feature {} has no screens", self.name));
+        }
+
+        let mut screens = HashSet::new();
+        for screen in &self.screens {
+            if let Some(name) = std::iter::once(&screen.name).chain(&screen.actions).find(|name| !is_swift_identifier(name)) {
+                return invalid(format!("Screen {} uses '{}', which is not a Swift identifier", screen.name, name));
+            }
+            if !screens.insert(presentation_name(&screen.name)) {
+                return invalid(format!("Feature {} declares screen {} more than once", self.name, screen.name));
+            }
+        }
+        let mut entities = HashSet::new();
+        for entity in &self.entities {
+            if !is_swift_identifier(&entity.name) {
+                return invalid(format!("Entity name '{}' is not a Swift identifier", entity.name));
+            }
+            if !entities.insert(&entity.name) {
+                return invalid(format!("Feature {} declares entity {} more than once", self.name, entity.name));
+            }
+        }
+
+        if self.persistence {
+            let Some(persisted) = self.entities.first() else {
+                return invalid(format!("Feature {} asks for persistence but has no entities", self.name));
+            };
+            // Repository methods replace mock actions of the same name, and
+            // take the entity the screen's button has no way to pass
+            let repository = [
+                format!("load{}", plural(&persisted.name)),
+                format!("save{}", persisted.name),
+                format!("delete{}", persisted.name),
+            ];
+            if let Some(action) = self.actions().into_iter().find(|action| repository.iter().any(|method| method.as_str() == *action)) {
+                return invalid(format!(
+                    "Screen action {} clashes with the {} repository method of the same name", action, persisted.name
+                ));
+            }
+        }
+        if self.navigation && self.screens.len() < 2 {
+            return invalid(format!("Feature {} asks for navigation but has a single screen", self.name));
+        }
+        Ok(())
+    }
+}
+
+/// Generated code of one artifact, under the type it declares
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureArtifact {
+    pub name: String,
+    pub code: GeneratedCode,
+}
+
+/// A reference from one artifact that no other artifact declares
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceIssue {
+    pub kind: ReferenceKind,
+    /// The artifact making the reference
+    pub artifact: String,
+    pub reference: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// A presentation's environment object is not the context
+    ContextBinding,
+    /// The context holds a client other than the generated one
+    ClientBinding,
+    /// A presentation calls a method the context does not declare
+    ContextAction,
+    /// The context forwards to a method the client does not declare
+    ClientAction,
+}
+
+/// How the artifacts of a feature refer to one another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationSummary {
+    pub context: String,
+    pub client: String,
+    /// Each action, with the presentations calling it
+    pub actions: BTreeMap<String, Vec<String>>,
+    pub routes: Vec<String>,
+    pub issues: Vec<ReferenceIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedFeature {
+    pub name: String,
+    pub context: FeatureArtifact,
+    pub client: FeatureArtifact,
+    pub presentations: Vec<FeatureArtifact>,
+    /// One per entity, with persistence
+    pub persistence: Vec<FeatureArtifact>,
+    pub navigation: Option<FeatureArtifact>,
+    pub integration: IntegrationSummary,
+}
+
+impl GeneratedFeature {
+    pub fn artifacts(&self) -> impl Iterator<Item = &FeatureArtifact> {
+        [&self.context, &self.client].into_iter()
+            .chain(&self.presentations)
+            .chain(&self.persistence)
+            .chain(&self.navigation)
+    }
+
+    /// Every artifact passed validation and every reference between them
+    /// resolves
+    pub fn passed(&self) -> bool {
+        self.integration.issues.is_empty() && self.artifacts().all(|artifact| artifact.code.validation_passed)
+    }
+}
+
+impl AxiomCodeGenerator {
+    /// Generate every artifact of the feature at once, then check the
+    /// references between them. Unresolved references are reported in the
+    /// integration summary rather than failing the call, so the code can
+    /// still be inspected
+    pub async fn generate_feature(&self, spec: FeatureSpec) -> Result<GeneratedFeature> {
+        let specs = spec.derive_specs()?;
+        let navigation_spec = specs.navigation.clone();
+
+        let (context, client, presentations, persistence, navigation) = tokio::join!(
+            self.generate_context(specs.context.clone()),
+            self.generate_mock_client(specs.client.clone()),
+            try_join_all(specs.presentations.iter().map(|presentation| self.generate_presentation(presentation.clone()))),
+            try_join_all(specs.persistence.iter().map(|persistence| self.generate_persistence(persistence.clone()))),
+            async {
+                match navigation_spec {
+                    Some(navigation) => self.generate_navigation(navigation).await.map(Some),
+                    None => Ok(None),
+                }
+            },
+        );
+
+        let mut context = FeatureArtifact { name: specs.context.name.clone(), code: context? };
+        context.code.generated_code.push_str(&forwarding_extension(&specs.context.name, &spec.actions()));
+        let client = FeatureArtifact { name: specs.client.name.clone(), code: client? };
+        let presentations: Vec<FeatureArtifact> = specs.presentations.iter()
+            .zip(presentations?)
+            .map(|(presentation, code)| FeatureArtifact { name: presentation.name.clone(), code })
+            .collect();
+        let persistence = specs.persistence.iter()
+            .zip(persistence?)
+            .map(|(persistence, code)| FeatureArtifact { name: persistence.entity_name.clone(), code })
+            .collect();
+        let navigation = navigation?.map(|code| FeatureArtifact { name: "AppNavigationView".to_string(), code });
+
+        let integration = IntegrationSummary {
+            context: context.name.clone(),
+            client: client.name.clone(),
+            actions: action_callers(&presentations),
+            routes: specs.navigation.iter().flat_map(|navigation| &navigation.routes).map(|route| route.name.clone()).collect(),
+            issues: check_references(&context, &client, &presentations),
+        };
+        Ok(GeneratedFeature { name: spec.name, context, client, presentations, persistence, navigation, integration })
+    }
+}
+
+/// Check the references the generated code makes between artifacts: each
+/// presentation's context binding and the methods it calls, and the
+/// context's client binding and the client methods it forwards to
+pub fn check_references(context: &FeatureArtifact, client: &FeatureArtifact, presentations: &[FeatureArtifact]) -> Vec<ReferenceIssue> {
+    let patterns = ReferencePatterns::get();
+    let context_code = &context.code.generated_code;
+    let client_code = &client.code.generated_code;
+    let context_types = captured(&patterns.declared_type, context_code);
+    let context_methods = captured(&patterns.method, context_code);
+    let client_types = captured(&patterns.declared_type, client_code);
+    let client_methods = captured(&patterns.method, client_code);
+    let mut issues = Vec::new();
+
+    for presentation in presentations {
+        let code = &presentation.code.generated_code;
+        let bindings = captured(&patterns.environment_object, code);
+        if bindings.is_empty() {
+            issues.push(ReferenceIssue {
+                kind: ReferenceKind::ContextBinding,
+                artifact: presentation.name.clone(),
+                reference: context.name.clone(),
+                message: format!("{} has no environment object to reach {} through", presentation.name, context.name),
+            });
+        }
+        for binding in bindings.into_iter().filter(|binding| !context_types.contains(binding)) {
+            issues.push(ReferenceIssue {
+                kind: ReferenceKind::ContextBinding,
+                artifact: presentation.name.clone(),
+                reference: binding.to_string(),
+                message: format!("{} binds to {}, but the context is declared as {}", presentation.name, binding, declared(&context_types)),
+            });
+        }
+        for action in unique(captured(&patterns.context_call, code)).filter(|action| !context_methods.contains(action)) {
+            issues.push(ReferenceIssue {
+                kind: ReferenceKind::ContextAction,
+                artifact: presentation.name.clone(),
+                reference: action.to_string(),
+                message: format!("{} calls context.{}(), which {} does not declare", presentation.name, action, context.name),
+            });
+        }
+    }
+
+    for binding in captured(&patterns.client_binding, context_code).into_iter().filter(|binding| !client_types.contains(binding)) {
+        issues.push(ReferenceIssue {
+            kind: ReferenceKind::ClientBinding,
+            artifact: context.name.clone(),
+            reference: binding.to_string(),
+            message: format!("{} holds a {}, but the client is declared as {}", context.name, binding, declared(&client_types)),
+        });
+    }
+    for action in unique(captured(&patterns.client_call, context_code)).filter(|action| !client_methods.contains(action)) {
+        issues.push(ReferenceIssue {
+            kind: ReferenceKind::ClientAction,
+            artifact: context.name.clone(),
+            reference: action.to_string(),
+            message: format!("{} forwards to client.{}(), which {} does not declare", context.name, action, client.name),
+        });
+    }
+    issues
+}
+
+struct ReferencePatterns {
+    declared_type: Regex,
+    method: Regex,
+    environment_object: Regex,
+    client_binding: Regex,
+    context_call: Regex,
+    client_call: Regex,
+}
+
+impl ReferencePatterns {
+    fn get() -> &'static Self {
+        static PATTERNS: OnceLock<ReferencePatterns> = OnceLock::new();
+        PATTERNS.get_or_init(|| {
+            let pattern = |source| Regex::new(source).expect("reference patterns are valid");
+            ReferencePatterns {
+                declared_type: pattern(r"\b(?:class|actor|struct)\s+(\w+)"),
+                method: pattern(r"\bfunc\s+(\w+)\s*\("),
+                environment_object: pattern(r"@EnvironmentObject\s+var\s+\w+\s*:\s*(\w+)"),
+                client_binding: pattern(r"\blet\s+client\s*:\s*(\w+)"),
+                context_call: pattern(r"\bcontext\.(\w+)\("),
+                client_call: pattern(r"\bclient\.(\w+)\("),
+            }
+        })
+    }
+}
+
+fn captured<'a>(pattern: &Regex, code: &'a str) -> Vec<&'a str> {
+    pattern.captures_iter(code).filter_map(|captures| captures.get(1)).map(|name| name.as_str()).collect()
+}
+
+fn unique(names: Vec<&str>) -> impl Iterator<Item = &str> {
+    let mut seen = HashSet::new();
+    names.into_iter().filter(move |name| seen.insert(*name))
+}
+
+fn declared(types: &[&str]) -> String {
+    if types.is_empty() { "nothing".to_string() } else { types.join(", ") }
+}
+
+/// Actions the presentations call on the context, with who calls them
+fn action_callers(presentations: &[FeatureArtifact]) -> BTreeMap<String, Vec<String>> {
+    let mut actions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for presentation in presentations {
+        for action in unique(captured(&ReferencePatterns::get().context_call, &presentation.code.generated_code)) {
+            actions.entry(action.to_string()).or_default().push(presentation.name.clone());
+        }
+    }
+    actions
+}
+
+/// Context methods the screens call, forwarding to the client the context
+/// holds privately; an extension in the same file can still reach it
+fn forwarding_extension(context: &str, actions: &[&str]) -> String {
+    if actions.is_empty() {
+        return String::new();
+    }
+    let methods = actions.iter()
+        .map(|action| format!("    func {0}() async {{\n        await client.{0}()\n    }}", action))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    format!("\n\nextension {} {{\n{}\n}}", context, methods)
+}
+
+/// `TaskList` as a presentation, `TaskListView`
+fn presentation_name(screen: &str) -> String {
+    if screen.ends_with("View") { screen.to_string() } else { format!("{}View", screen) }
+}
+
+/// `addTask` as a button title, `Add Task`
+fn action_label(action: &str) -> String {
+    let mut label = String::new();
+    for (index, c) in upper_first(action).char_indices() {
+        if index > 0 && c.is_uppercase() {
+            label.push(' ');
+        }
+        label.push(c);
+    }
+    label
+}
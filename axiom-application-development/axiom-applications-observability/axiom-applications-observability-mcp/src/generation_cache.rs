@@ -0,0 +1,200 @@
+use crate::error::Result;
+use crate::types::{GeneratedCode, ValidationResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// Generations and validations kept before the least recently used is evicted
+pub const DEFAULT_GENERATION_CACHE_CAPACITY: usize = 256;
+
+/// Spec fields whose order carries no meaning and does not reach the
+/// generated code, so specs listing them in another order share an entry
+const UNORDERED_FIELDS: &[&str] = &["accessibility_requirements"];
+
+/// Spec fields the generated code does not depend on, left out of the hash
+/// so the same artifact generated for another requirement is a hit
+const IGNORED_FIELDS: &[&str] = &["requirement_ids"];
+
+/// Hits and misses of the generation cache since it was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl GenerationCacheStats {
+    /// Share of lookups answered from the cache, 0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CachedOutput {
+    Code(GeneratedCode),
+    Validation(ValidationResult),
+}
+
+#[derive(Debug)]
+struct Entry {
+    output: CachedOutput,
+    used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, Entry>,
+    /// Keys by when they were last used, least recent first
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+    stats: GenerationCacheStats,
+}
+
+impl CacheState {
+    fn get(&mut self, key: &str) -> Option<CachedOutput> {
+        self.clock += 1;
+        let Some(entry) = self.entries.get_mut(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.recency.remove(&entry.used);
+        entry.used = self.clock;
+        self.recency.insert(self.clock, key.to_string());
+        self.stats.hits += 1;
+        Some(entry.output.clone())
+    }
+
+    fn insert(&mut self, key: String, output: CachedOutput, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        if let Some(previous) = self.entries.insert(key.clone(), Entry { output, used: self.clock }) {
+            self.recency.remove(&previous.used);
+        }
+        self.recency.insert(self.clock, key);
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+/// Generated code and its validation, keyed by a hash of what produced
+/// them: the spec, the active templates and the framework versions
+#[derive(Debug)]
+pub struct GenerationCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl Default for GenerationCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_GENERATION_CACHE_CAPACITY)
+    }
+}
+
+impl GenerationCache {
+    /// A capacity of 0 disables caching while still counting misses
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, state: Mutex::new(CacheState::default()) }
+    }
+
+    pub fn stats(&self) -> GenerationCacheStats {
+        let state = self.state.lock().unwrap();
+        GenerationCacheStats { entries: state.entries.len(), capacity: self.capacity, ..state.stats }
+    }
+
+    /// Drop every entry; the hit and miss counts are kept. Returns the
+    /// stats from just before.
+    pub fn clear(&self) -> GenerationCacheStats {
+        let stats = self.stats();
+        let mut state = self.state.lock().unwrap();
+        state.entries.clear();
+        state.recency.clear();
+        stats
+    }
+
+    pub fn code(&self, key: &str) -> Option<GeneratedCode> {
+        match self.state.lock().unwrap().get(key)? {
+            CachedOutput::Code(code) => Some(code),
+            CachedOutput::Validation(_) => None,
+        }
+    }
+
+    pub fn insert_code(&self, key: String, code: GeneratedCode) {
+        self.state.lock().unwrap().insert(key, CachedOutput::Code(code), self.capacity);
+    }
+
+    pub fn validation(&self, key: &str) -> Option<ValidationResult> {
+        match self.state.lock().unwrap().get(key)? {
+            CachedOutput::Validation(validation) => Some(validation),
+            CachedOutput::Code(_) => None,
+        }
+    }
+
+    pub fn insert_validation(&self, key: String, validation: ValidationResult) {
+        self.state.lock().unwrap().insert(key, CachedOutput::Validation(validation), self.capacity);
+    }
+}
+
+/// Hex SHA-256 of `operation` and `spec` under `version`, the same for
+/// specs differing only in map order or the order of unordered lists
+pub fn spec_hash(operation: &str, spec: &impl Serialize, version: &str) -> Result<String> {
+    let mut canonical = String::new();
+    write_canonical(&serde_json::to_value(spec)?, false, &mut canonical);
+    let mut hasher = Sha256::new();
+    for part in [operation, version, canonical.as_str()] {
+        hasher.update(part.len().to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// JSON with object keys sorted, and the elements of unordered lists too
+fn write_canonical(value: &Value, unordered: bool, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<(&String, &Value)> = map.iter().filter(|(key, _)| !IGNORED_FIELDS.contains(&key.as_str())).collect();
+            fields.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, UNORDERED_FIELDS.contains(&key.as_str()), out);
+            }
+            out.push('}');
+        }
+        Value::Array(values) => {
+            let mut elements: Vec<String> = values
+                .iter()
+                .map(|value| {
+                    let mut element = String::new();
+                    write_canonical(value, false, &mut element);
+                    element
+                })
+                .collect();
+            if unordered {
+                elements.sort();
+            }
+            out.push('[');
+            out.push_str(&elements.join(","));
+            out.push(']');
+        }
+        value => out.push_str(&value.to_string()),
+    }
+}
@@ -1,9 +1,699 @@
-use crate::error::Result;
+use crate::connection::{ConnectionEvent, ConnectionStatus, ReconnectPolicy, ReconnectingWebSocket};
+use crate::error::{AxiomMCPError, Result};
+use crate::hot_reload_protocol::{decode_server_message, ping, MessageType, ServerMessage};
+pub use crate::hot_reload_protocol::{ChangedFile, ReloadMessage, ReloadPayload};
+use crate::reload_scope::{ComponentIndex, ReloadScope, ScopeDecision};
+use crate::tool_registry::SchemaViolation;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 
-pub struct HotReloadClient;
+/// Quiet period after the last file change before a reload is sent
+pub const DEFAULT_DEBOUNCE_WINDOW: Duration = Duration::from_millis(150);
+
+/// Name of the hot reload server connection in connection status and events
+pub const CONNECTION_NAME: &str = "hot_reload";
+
+/// How long `validate_hot_reload_connection` waits for the server's pong
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Filtered events kept for `get_hot_reload_stats`
+const RECENT_FILTERED_EVENTS: usize = 20;
+
+/// Reason recorded for a path that matched no include pattern
+const NOT_INCLUDED: &str = "not_included";
+
+/// Configuration for the reload pipeline
+#[derive(Debug, Clone)]
+pub struct HotReloadConfig {
+    /// Changes arriving within this window of each other are sent as one reload
+    pub debounce_window: Duration,
+}
+
+impl Default for HotReloadConfig {
+    fn default() -> Self {
+        Self {
+            debounce_window: DEFAULT_DEBOUNCE_WINDOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeType {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// A single file system event from the watcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub change_type: ChangeType,
+}
+
+impl FileChange {
+    pub fn new(path: impl Into<PathBuf>, change_type: ChangeType) -> Self {
+        Self {
+            path: path.into(),
+            change_type,
+        }
+    }
+}
+
+/// Which watched files trigger a reload
+///
+/// Patterns are globs matched against the path the watcher reports, which
+/// is usually absolute, so start them with `**/`. `*` does not cross a
+/// `/`. A path must match an include pattern, or the include list must be
+/// empty, and must match no exclude pattern; exclude wins when both match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotReloadFilters {
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl Default for HotReloadFilters {
+    /// Swift sources of an Xcode or SwiftPM project, without VCS, build
+    /// and generated output
+    fn default() -> Self {
+        Self {
+            include: vec!["**/*.swift".to_string()],
+            exclude: vec![
+                "**/.git/**".to_string(),
+                "**/.build/**".to_string(),
+                "**/.swiftpm/**".to_string(),
+                "**/DerivedData/**".to_string(),
+                "**/Pods/**".to_string(),
+                "**/Generated/**".to_string(),
+                "**/*.generated.swift".to_string(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum FilterDecision {
+    Included,
+    /// Matched this exclude pattern
+    Excluded { pattern: String },
+    /// Matched no include pattern
+    NotIncluded,
+}
+
+impl FilterDecision {
+    pub fn is_included(&self) -> bool {
+        matches!(self, FilterDecision::Included)
+    }
+
+    fn reason(&self) -> Option<String> {
+        match self {
+            FilterDecision::Included => None,
+            FilterDecision::Excluded { pattern } => Some(pattern.clone()),
+            FilterDecision::NotIncluded => Some(NOT_INCLUDED.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredEvent {
+    pub path: String,
+    /// The exclude pattern that matched, or `not_included`
+    pub reason: String,
+    pub at: DateTime<Utc>,
+}
+
+/// What the filter did with watcher events, for finding out why a change
+/// did not reload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotReloadStats {
+    pub filters: HotReloadFilters,
+    pub events_received: u64,
+    pub events_forwarded: u64,
+    pub events_filtered: u64,
+    /// Filtered events per exclude pattern, and under `not_included`
+    pub filtered_by: BTreeMap<String, u64>,
+    /// Latest filtered events, newest last
+    pub recent_filtered: Vec<FilteredEvent>,
+    /// The most recent reload and the scope it was sent with
+    #[serde(default)]
+    pub last_reload: Option<ReloadReport>,
+}
+
+/// A reload the pipeline sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadReport {
+    pub batch_id: u64,
+    pub files: Vec<String>,
+    pub scope: ReloadScope,
+    pub component_ids: Vec<String>,
+    /// Why this scope was chosen
+    pub reason: String,
+    pub at: DateTime<Utc>,
+}
+
+struct CompiledFilters {
+    filters: HotReloadFilters,
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl CompiledFilters {
+    fn new(filters: HotReloadFilters) -> Result<Self> {
+        Ok(Self {
+            include: compile(&filters.include)?,
+            exclude: compile(&filters.exclude)?,
+            filters,
+        })
+    }
+
+    fn decide(&self, path: &Path) -> FilterDecision {
+        // The first listed pattern is reported when several match
+        if let Some(index) = self.exclude.matches(path).into_iter().min() {
+            return FilterDecision::Excluded { pattern: self.filters.exclude[index].clone() };
+        }
+        if !self.filters.include.is_empty() && !self.include.is_match(path) {
+            return FilterDecision::NotIncluded;
+        }
+        FilterDecision::Included
+    }
+}
+
+fn compile(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(glob(pattern)?);
+    }
+    builder.build().map_err(|e| AxiomMCPError::validation(
+        "hot_reload", "compile filters", format!("Invalid hot reload filters: {}", e),
+    ))
+}
+
+fn glob(pattern: &str) -> Result<Glob> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| AxiomMCPError::validation(
+            "hot_reload", "compile filters", format!("Invalid hot reload filter '{}': {}", pattern, e),
+        ))
+}
+
+#[derive(Default)]
+struct FilterCounters {
+    received: u64,
+    forwarded: u64,
+    filtered: u64,
+    filtered_by: BTreeMap<String, u64>,
+    recent: VecDeque<FilteredEvent>,
+}
+
+/// Include/exclude filtering of watcher events, adjustable while running
+///
+/// Shared between the reload pipeline, which checks every event, and the
+/// tools that change the filters and report what was filtered.
+pub struct HotReloadFilter {
+    compiled: Mutex<CompiledFilters>,
+    counters: Mutex<FilterCounters>,
+}
+
+impl HotReloadFilter {
+    pub fn new(filters: HotReloadFilters) -> Result<Self> {
+        Ok(Self {
+            compiled: Mutex::new(CompiledFilters::new(filters)?),
+            counters: Mutex::new(FilterCounters::default()),
+        })
+    }
+
+    /// Replace the filters; on an invalid pattern the current ones stay
+    pub fn set_filters(&self, filters: HotReloadFilters) -> Result<()> {
+        let compiled = CompiledFilters::new(filters)?;
+        tracing::info!(
+            "Hot reload filters set to include {:?}, exclude {:?}",
+            compiled.filters.include, compiled.filters.exclude
+        );
+        *self.compiled.lock().unwrap() = compiled;
+        Ok(())
+    }
+
+    pub fn filters(&self) -> HotReloadFilters {
+        self.compiled.lock().unwrap().filters.clone()
+    }
+
+    /// What the current filters would do with `path`, without counting it
+    pub fn decide(&self, path: &Path) -> FilterDecision {
+        self.compiled.lock().unwrap().decide(path)
+    }
+
+    /// Decide on a watcher event and count the outcome
+    pub fn check(&self, path: &Path) -> FilterDecision {
+        let decision = self.decide(path);
+        let mut counters = self.counters.lock().unwrap();
+        counters.received += 1;
+        match decision.reason() {
+            None => counters.forwarded += 1,
+            Some(reason) => {
+                tracing::trace!("Hot reload ignored {} ({})", path.display(), reason);
+                counters.filtered += 1;
+                *counters.filtered_by.entry(reason.clone()).or_default() += 1;
+                if counters.recent.len() == RECENT_FILTERED_EVENTS {
+                    counters.recent.pop_front();
+                }
+                counters.recent.push_back(FilteredEvent {
+                    path: path.display().to_string(),
+                    reason,
+                    at: Utc::now(),
+                });
+            }
+        }
+        decision
+    }
+
+    pub fn stats(&self) -> HotReloadStats {
+        let filters = self.filters();
+        let counters = self.counters.lock().unwrap();
+        HotReloadStats {
+            filters,
+            events_received: counters.received,
+            events_forwarded: counters.forwarded,
+            events_filtered: counters.filtered,
+            filtered_by: counters.filtered_by.clone(),
+            recent_filtered: counters.recent.iter().cloned().collect(),
+            last_reload: None,
+        }
+    }
+}
+
+impl Default for HotReloadFilter {
+    fn default() -> Self {
+        Self::new(HotReloadFilters::default()).expect("default hot reload filters are valid globs")
+    }
+}
+
+impl std::fmt::Debug for HotReloadFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HotReloadFilter")
+            .field("filters", &self.filters())
+            .finish()
+    }
+}
+
+/// Files changed within one debounce window, one entry per file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadBatch {
+    changes: Vec<FileChange>,
+}
+
+impl ReloadBatch {
+    /// Add a change, folding it into an earlier change of the same file
+    ///
+    /// The latest change wins, except that a file added and then written to
+    /// within the batch is still reported as added.
+    pub fn push(&mut self, change: FileChange) {
+        match self.changes.iter_mut().find(|existing| existing.path == change.path) {
+            Some(existing) => {
+                existing.change_type = match (existing.change_type, change.change_type) {
+                    (ChangeType::Added, ChangeType::Modified) => ChangeType::Added,
+                    (_, latest) => latest,
+                };
+            }
+            None => self.changes.push(change),
+        }
+    }
+
+    pub fn merge(&mut self, other: ReloadBatch) {
+        for change in other.changes {
+            self.push(change);
+        }
+    }
+
+    pub fn changes(&self) -> &[FileChange] {
+        &self.changes
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl ReloadMessage {
+    pub fn from_batch(batch_id: u64, batch: &ReloadBatch, decision: ScopeDecision) -> Self {
+        let files = batch.changes()
+            .iter()
+            .map(|change| ChangedFile {
+                file_path: change.path.display().to_string(),
+                file_name: file_name(&change.path),
+                change_type: change.change_type,
+            })
+            .collect();
+
+        Self::new(MessageType::FilesChanged, ReloadPayload {
+            batch_id,
+            files,
+            scope: decision.scope,
+            component_ids: decision.component_ids,
+            scope_reason: decision.reason,
+        })
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Connection the pipeline sends reload messages over
+///
+/// Returns once the server has finished the reload, so the pipeline knows
+/// when the next batch can go out.
+#[async_trait]
+pub trait ReloadTransport: Send + Sync {
+    async fn send_reload(&self, message: &ReloadMessage) -> Result<()>;
+}
+
+/// Told about every reload the pipeline sends, such as to sample the
+/// app before and after it
+///
+/// The pipeline waits for both calls, so the next reload waits for them too.
+#[async_trait]
+pub trait ReloadObserver: Send + Sync {
+    async fn before_reload(&self, batch_id: u64);
+
+    /// Only called for reloads the transport accepted
+    async fn after_reload(&self, reload: &ReloadReport);
+}
+
+/// Client of the hot reload server's WebSocket
+#[derive(Debug)]
+pub struct HotReloadClient {
+    socket: ReconnectingWebSocket,
+}
 
 impl HotReloadClient {
-    pub async fn new(_url: &str) -> Result<Self> {
-        Ok(Self)
+    pub async fn new(url: &str) -> Result<Self> {
+        Ok(Self {
+            socket: ReconnectingWebSocket::connect(CONNECTION_NAME, url, ReconnectPolicy::default()),
+        })
     }
-}
\ No newline at end of file
+
+    /// Connect with `policy`, publishing connection state changes on `events`
+    pub async fn connect(url: &str, policy: ReconnectPolicy, events: broadcast::Sender<ConnectionEvent>) -> Result<Self> {
+        Ok(Self {
+            socket: ReconnectingWebSocket::connect_with_events(CONNECTION_NAME, url, policy, events),
+        })
+    }
+
+    pub fn connection_status(&self) -> ConnectionStatus {
+        self.socket.status()
+    }
+
+    /// Ping the server and wait for its pong, checking every message it
+    /// sends meanwhile against the protocol schema
+    ///
+    /// Fails with a protocol violation naming the message type and field
+    /// the first time the server strays from the schema, and with a
+    /// connectivity error if no pong arrives within `timeout`.
+    pub async fn validate_connection(&self, timeout: Duration) -> Result<HotReloadHandshake> {
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let echo = format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let started = Instant::now();
+        // Listen before pinging so a fast pong is not missed
+        let mut incoming = self.socket.incoming();
+        self.socket.send(serde_json::to_string(&ping(&nonce, &echo))?);
+
+        let mut messages_checked = 0;
+        let pong = tokio::time::timeout(timeout, async {
+            loop {
+                let text = match incoming.recv().await {
+                    Ok(text) => text,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(AxiomMCPError::connectivity("hot_reload", "validate connection", "Hot reload connection closed"));
+                    }
+                };
+                let message = decode_server_message(&text)?;
+                messages_checked += 1;
+                match message {
+                    ServerMessage::Pong(pong) if pong.payload.nonce == nonce => return Ok(pong),
+                    // Only the nonce tells pongs apart; another client's ping may be answered too
+                    ServerMessage::Pong(_) | ServerMessage::StateSync(_) => {}
+                    ServerMessage::Error(error) => {
+                        tracing::debug!("Hot reload server reported {}: {}", error.payload.code, error.payload.message);
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|_| {
+            let status = self.connection_status();
+            AxiomMCPError::connectivity("hot_reload", "validate connection", format!(
+                "The hot reload server at {} did not answer the ping within {:?} (connection {:?})",
+                status.url, timeout, status.state
+            ))
+        })??;
+
+        if pong.payload.echo != echo {
+            return Err(AxiomMCPError::protocol_violation(CONNECTION_NAME, MessageType::Pong.name(), vec![
+                SchemaViolation::new("/payload/echo", format!("expected the ping's \"{}\" back, found \"{}\"", echo, pong.payload.echo)),
+            ]));
+        }
+
+        Ok(HotReloadHandshake {
+            url: self.connection_status().url,
+            server_version: pong.version,
+            round_trip_ms: started.elapsed().as_millis() as u64,
+            messages_checked,
+        })
+    }
+}
+
+/// A hot reload server that answered the ping in the expected schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotReloadHandshake {
+    pub url: String,
+    /// Protocol version the server wrote its pong with
+    pub server_version: String,
+    pub round_trip_ms: u64,
+    /// Server messages read up to and including the pong, all of which matched the schema
+    pub messages_checked: usize,
+}
+
+#[async_trait]
+impl ReloadTransport for HotReloadClient {
+    /// Returns once the reload is queued; during an outage it is sent after reconnecting
+    async fn send_reload(&self, message: &ReloadMessage) -> Result<()> {
+        self.socket.send(serde_json::to_string(message)?);
+        Ok(())
+    }
+}
+
+/// Debounces file changes into batches and sends one reload per batch
+///
+/// Editors such as Xcode write a file several times on save (swap file,
+/// rename, attribute change). Changes are collected until the debounce
+/// window passes without a new one, then sent as a single reload. While a
+/// reload is in flight, further batches are merged into one pending batch
+/// that is sent as soon as the reload finishes.
+///
+/// Changes to files the filter rejects are counted and dropped before
+/// they reach a batch. Each batch is re-indexed before it is sent, and
+/// reloads only the components it touches when the index can tell which
+/// those are.
+#[derive(Debug)]
+pub struct ReloadPipeline {
+    changes: mpsc::UnboundedSender<FileChange>,
+    filter: Arc<HotReloadFilter>,
+    reloader: Arc<Reloader>,
+    task: JoinHandle<()>,
+}
+
+/// Resolves the scope of a batch and sends it
+struct Reloader {
+    transport: Arc<dyn ReloadTransport>,
+    index: Arc<ComponentIndex>,
+    last_reload: Mutex<Option<ReloadReport>>,
+    observer: Mutex<Option<Arc<dyn ReloadObserver>>>,
+}
+
+impl std::fmt::Debug for Reloader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reloader")
+            .field("last_reload", &self.last_reload)
+            .field("observed", &self.observer.lock().unwrap().is_some())
+            .finish()
+    }
+}
+
+impl ReloadPipeline {
+    /// Spawn with the default filters
+    pub fn spawn(transport: Arc<dyn ReloadTransport>, config: HotReloadConfig) -> Self {
+        Self::spawn_filtered(transport, config, Arc::new(HotReloadFilter::default()))
+    }
+
+    pub fn spawn_filtered(transport: Arc<dyn ReloadTransport>, config: HotReloadConfig, filter: Arc<HotReloadFilter>) -> Self {
+        let (changes, receiver) = mpsc::unbounded_channel();
+        let reloader = Arc::new(Reloader {
+            transport,
+            index: Arc::new(ComponentIndex::new()),
+            last_reload: Mutex::new(None),
+            observer: Mutex::new(None),
+        });
+        let task = tokio::spawn(run_pipeline(Arc::clone(&reloader), config, receiver));
+        Self { changes, filter, reloader, task }
+    }
+
+    pub fn filter(&self) -> &Arc<HotReloadFilter> {
+        &self.filter
+    }
+
+    /// Components of the watched files, for indexing generated or
+    /// existing files before their first change
+    pub fn components(&self) -> &Arc<ComponentIndex> {
+        &self.reloader.index
+    }
+
+    /// Tell `observer` about every reload from now on, replacing any
+    /// earlier observer
+    pub fn observe(&self, observer: Arc<dyn ReloadObserver>) {
+        *self.reloader.observer.lock().unwrap() = Some(observer);
+    }
+
+    /// Filter counts together with the most recent reload
+    pub fn stats(&self) -> HotReloadStats {
+        let mut stats = self.filter.stats();
+        stats.last_reload = self.reloader.last_reload.lock().unwrap().clone();
+        stats
+    }
+
+    /// Report a change from the file watcher
+    pub fn file_changed(&self, change: FileChange) -> Result<()> {
+        if !self.filter.check(&change.path).is_included() {
+            return Ok(());
+        }
+        self.changes.send(change).map_err(|_| {
+            AxiomMCPError::internal("hot_reload", "report file change", "Hot reload pipeline has stopped")
+        })
+    }
+
+    /// Send whatever is still collected or pending, then stop
+    pub async fn shutdown(self) -> Result<()> {
+        drop(self.changes);
+        self.task.await?;
+        Ok(())
+    }
+}
+
+async fn run_pipeline(
+    reloader: Arc<Reloader>,
+    config: HotReloadConfig,
+    mut receiver: mpsc::UnboundedReceiver<FileChange>,
+) {
+    let mut collecting = ReloadBatch::default();
+    let mut deadline = Instant::now();
+    let mut pending: Option<ReloadBatch> = None;
+    let mut in_flight: Option<JoinHandle<Result<()>>> = None;
+    let mut next_batch_id = 1;
+    let mut open = true;
+
+    loop {
+        if !open && collecting.is_empty() && pending.is_none() && in_flight.is_none() {
+            break;
+        }
+
+        tokio::select! {
+            change = receiver.recv(), if open => match change {
+                Some(change) => {
+                    collecting.push(change);
+                    deadline = Instant::now() + config.debounce_window;
+                }
+                // Nothing more is coming, so there is no point waiting out the window
+                None => {
+                    open = false;
+                    deadline = Instant::now();
+                }
+            },
+            _ = tokio::time::sleep_until(deadline), if !collecting.is_empty() => {
+                let batch = std::mem::take(&mut collecting);
+                if in_flight.is_some() {
+                    tracing::debug!("Reload in flight, queueing {} changed files", batch.len());
+                    pending.get_or_insert_with(ReloadBatch::default).merge(batch);
+                } else {
+                    in_flight = Some(send(&reloader, next_batch_id, batch));
+                    next_batch_id += 1;
+                }
+            },
+            result = async { in_flight.as_mut().unwrap().await }, if in_flight.is_some() => {
+                in_flight = None;
+                match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => tracing::warn!("Hot reload failed: {}", e),
+                    Err(e) => tracing::warn!("Hot reload task failed: {}", e),
+                }
+                if let Some(batch) = pending.take() {
+                    in_flight = Some(send(&reloader, next_batch_id, batch));
+                    next_batch_id += 1;
+                }
+            },
+        }
+    }
+}
+
+fn send(reloader: &Arc<Reloader>, batch_id: u64, batch: ReloadBatch) -> JoinHandle<Result<()>> {
+    let reloader = Arc::clone(reloader);
+    tokio::spawn(async move {
+        let mut sources = HashMap::new();
+        for change in batch.changes() {
+            if change.change_type == ChangeType::Deleted {
+                continue;
+            }
+            match tokio::fs::read_to_string(&change.path).await {
+                Ok(source) => {
+                    sources.insert(change.path.clone(), source);
+                }
+                Err(e) => tracing::debug!("Could not read {} to scope the reload: {}", change.path.display(), e),
+            }
+        }
+
+        let decision = reloader.index.apply(&batch, &sources);
+        tracing::info!(
+            "Reloading {} changed files (batch {}, {:?}: {})",
+            batch.len(), batch_id, decision.scope, decision.reason
+        );
+        let message = ReloadMessage::from_batch(batch_id, &batch, decision);
+
+        let report = ReloadReport {
+            batch_id,
+            files: message.payload.files.iter().map(|file| file.file_path.clone()).collect(),
+            scope: message.payload.scope,
+            component_ids: message.payload.component_ids.clone(),
+            reason: message.payload.scope_reason.clone(),
+            at: Utc::now(),
+        };
+        *reloader.last_reload.lock().unwrap() = Some(report.clone());
+        let observer = reloader.observer.lock().unwrap().clone();
+        if let Some(observer) = &observer {
+            observer.before_reload(batch_id).await;
+        }
+        reloader.transport.send_reload(&message).await?;
+        if let Some(observer) = &observer {
+            observer.after_reload(&report).await;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,280 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::hot_reload::{ChangeType, CONNECTION_NAME};
+use crate::reload_scope::ReloadScope;
+use crate::tool_registry::{validate_arguments, SchemaViolation};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Hot reload protocol version sent with every message
+pub const PROTOCOL_VERSION: &str = "1.0.0";
+
+/// Major version a server's messages must carry to be read
+const PROTOCOL_MAJOR_VERSION: &str = "1";
+
+/// Platform sent with every message
+pub const PLATFORM: &str = "ios";
+
+/// Which way a message travels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    ToServer,
+    FromServer,
+}
+
+/// Every message type of the hot reload protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageType {
+    /// A batch of changed files to reload
+    FilesChanged,
+    /// Asks the server to echo a nonce back, proving it reads this schema
+    Ping,
+    Pong,
+    /// App state the server holds on to across reloads
+    StateSync,
+    Error,
+}
+
+impl MessageType {
+    pub const ALL: [MessageType; 5] = [
+        MessageType::FilesChanged,
+        MessageType::Ping,
+        MessageType::Pong,
+        MessageType::StateSync,
+        MessageType::Error,
+    ];
+
+    /// Value of the message's `type` field
+    pub fn name(self) -> &'static str {
+        match self {
+            MessageType::FilesChanged => "files_changed",
+            MessageType::Ping => "ping",
+            MessageType::Pong => "pong",
+            MessageType::StateSync => "state_sync",
+            MessageType::Error => "error",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|message_type| message_type.name() == name)
+    }
+
+    pub fn direction(self) -> Direction {
+        match self {
+            MessageType::FilesChanged | MessageType::Ping => Direction::ToServer,
+            MessageType::Pong | MessageType::StateSync | MessageType::Error => Direction::FromServer,
+        }
+    }
+
+    /// JSON Schema of the whole message; it rejects fields the types do not have
+    pub fn schema(self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "type": { "type": "string", "enum": [self.name()] },
+                "platform": { "type": "string", "enum": ["ios", "android"] },
+                "payload": self.payload_schema(),
+                "version": { "type": "string" },
+            },
+            "required": ["type", "platform", "payload", "version"],
+            "additionalProperties": false,
+        })
+    }
+
+    fn payload_schema(self) -> Value {
+        match self {
+            MessageType::FilesChanged => strict_object(json!({
+                "batchId": { "type": "integer", "minimum": 0 },
+                "files": {
+                    "type": "array",
+                    "items": strict_object(json!({
+                        "filePath": { "type": "string" },
+                        "fileName": { "type": "string" },
+                        "changeType": { "type": "string", "enum": ["added", "modified", "deleted"] },
+                    })),
+                },
+                "scope": { "type": "string", "enum": ["targeted", "full"] },
+                "componentIds": { "type": "array", "items": { "type": "string" } },
+                "scopeReason": { "type": "string" },
+            })),
+            MessageType::Ping | MessageType::Pong => strict_object(json!({
+                "nonce": { "type": "string" },
+                "echo": { "type": "string" },
+            })),
+            MessageType::StateSync => strict_object(json!({
+                "state": { "type": "object", "additionalProperties": { "type": "string" } },
+            })),
+            MessageType::Error => strict_object(json!({
+                "message": { "type": "string" },
+                "code": { "type": "string" },
+            })),
+        }
+    }
+}
+
+/// An object schema requiring every one of `properties` and nothing else
+fn strict_object(properties: Value) -> Value {
+    let required: Vec<&String> = properties.as_object().map(|properties| properties.keys().collect()).unwrap_or_default();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// The envelope every hot reload message travels in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HotReloadMessage<P> {
+    #[serde(rename = "type")]
+    pub message_type: String,
+    pub platform: String,
+    pub payload: P,
+    pub version: String,
+}
+
+impl<P> HotReloadMessage<P> {
+    /// A message of `message_type` in the current protocol version
+    pub fn new(message_type: MessageType, payload: P) -> Self {
+        Self {
+            message_type: message_type.name().to_string(),
+            platform: PLATFORM.to_string(),
+            payload,
+            version: PROTOCOL_VERSION.to_string(),
+        }
+    }
+}
+
+/// WebSocket message asking the hot reload server to reload a batch of files
+pub type ReloadMessage = HotReloadMessage<ReloadPayload>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ReloadPayload {
+    /// Increases by one for every reload the pipeline sends
+    pub batch_id: u64,
+    pub files: Vec<ChangedFile>,
+    /// `targeted` reloads only `component_ids`, keeping navigation state elsewhere
+    pub scope: ReloadScope,
+    pub component_ids: Vec<String>,
+    pub scope_reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChangedFile {
+    pub file_path: String,
+    pub file_name: String,
+    pub change_type: ChangeType,
+}
+
+/// Payload of a ping and of the pong answering it, which repeats both fields
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EchoPayload {
+    /// Tells the pong to this ping apart from others
+    pub nonce: String,
+    pub echo: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateSyncPayload {
+    pub state: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorPayload {
+    pub message: String,
+    pub code: String,
+}
+
+/// A message from the hot reload server that matched the schema
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerMessage {
+    Pong(HotReloadMessage<EchoPayload>),
+    StateSync(HotReloadMessage<StateSyncPayload>),
+    Error(HotReloadMessage<ErrorPayload>),
+}
+
+impl ServerMessage {
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            ServerMessage::Pong(_) => MessageType::Pong,
+            ServerMessage::StateSync(_) => MessageType::StateSync,
+            ServerMessage::Error(_) => MessageType::Error,
+        }
+    }
+
+    /// Protocol version the server wrote the message with
+    pub fn version(&self) -> &str {
+        match self {
+            ServerMessage::Pong(message) => &message.version,
+            ServerMessage::StateSync(message) => &message.version,
+            ServerMessage::Error(message) => &message.version,
+        }
+    }
+}
+
+/// A ping asking the server to send `nonce` and `echo` back in a pong
+pub fn ping(nonce: &str, echo: &str) -> HotReloadMessage<EchoPayload> {
+    HotReloadMessage::new(MessageType::Ping, EchoPayload { nonce: nonce.to_string(), echo: echo.to_string() })
+}
+
+/// Check `text` against the schema of the message type it names, in
+/// either direction
+pub fn check_message(text: &str) -> Result<(MessageType, Value)> {
+    let message: Value = serde_json::from_str(text)
+        .map_err(|e| violation("unknown", SchemaViolation::new("", format!("not a JSON message: {}", e))))?;
+    let name = message.get("type").and_then(Value::as_str).unwrap_or_default();
+    let Some(message_type) = MessageType::from_name(name) else {
+        let known: Vec<&str> = MessageType::ALL.iter().map(|message_type| message_type.name()).collect();
+        let found = message.get("type").map(Value::to_string).unwrap_or_else(|| "nothing".to_string());
+        return Err(violation(if name.is_empty() { "unknown" } else { name }, SchemaViolation::new(
+            "/type",
+            format!("expected one of {}, found {}", known.join(", "), found),
+        )));
+    };
+
+    let mut violations = validate_arguments(&message_type.schema(), &message);
+    if let Some(version) = message.get("version").and_then(Value::as_str) {
+        if version.split('.').next() != Some(PROTOCOL_MAJOR_VERSION) {
+            violations.push(SchemaViolation::new("/version", format!(
+                "expected a {}.x version, found \"{}\"", PROTOCOL_MAJOR_VERSION, version
+            )));
+        }
+    }
+    if !violations.is_empty() {
+        return Err(AxiomMCPError::protocol_violation(CONNECTION_NAME, name, violations));
+    }
+    Ok((message_type, message))
+}
+
+/// Read a message from the hot reload server, rejecting any that strays
+/// from the schema, including message types only the client sends
+pub fn decode_server_message(text: &str) -> Result<ServerMessage> {
+    let (message_type, message) = check_message(text)?;
+    match message_type {
+        MessageType::Pong => decode(message_type, message).map(ServerMessage::Pong),
+        MessageType::StateSync => decode(message_type, message).map(ServerMessage::StateSync),
+        MessageType::Error => decode(message_type, message).map(ServerMessage::Error),
+        MessageType::FilesChanged | MessageType::Ping => Err(violation(message_type.name(), SchemaViolation::new(
+            "/type",
+            "the client sends this message type; the server must not",
+        ))),
+    }
+}
+
+/// The schema and the types should agree, so this only fails when they do not
+fn decode<P: DeserializeOwned>(message_type: MessageType, message: Value) -> Result<HotReloadMessage<P>> {
+    serde_json::from_value(message).map_err(|e| violation(message_type.name(), SchemaViolation::new("", e.to_string())))
+}
+
+fn violation(message_type: &str, violation: SchemaViolation) -> AxiomMCPError {
+    AxiomMCPError::protocol_violation(CONNECTION_NAME, message_type, vec![violation])
+}
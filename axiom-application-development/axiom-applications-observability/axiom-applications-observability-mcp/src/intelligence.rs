@@ -1,9 +1,268 @@
-use crate::error::Result;
+use crate::advanced_visual_intelligence::AccessibilityElement;
+use crate::connection::{ConnectionEvent, ConnectionState, ConnectionStatus, ReconnectPolicy, ReconnectingWebSocket, EVENT_CHANNEL_CAPACITY};
+use crate::error::{AxiomMCPError, Result};
+use crate::intelligence_protocol::{
+    client_hello, decode_message, IntelligenceMessage, IntelligenceRequest, RequirementInsights, ServerCapability,
+    ServerProtocol,
+};
+use crate::performance_analysis_integration::{CapturedMetrics, PerformanceMetric};
+use crate::ui_script::UiAction;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
 
-pub struct IntelligenceClient;
+/// Name of the intelligence server connection in connection status and events
+pub const CONNECTION_NAME: &str = "intelligence";
+
+/// How long a server has to answer the handshake before it is taken to
+/// predate it
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long past its counting window the app has to report a frame rate
+const FRAME_RATE_REPLY_GRACE: Duration = Duration::from_secs(5);
+
+/// Client of the intelligence server's WebSocket
+///
+/// Every connection opens with a handshake settling which message schema
+/// the server speaks and what it offers. Requests wait for it, then fail
+/// with a configuration error if the server lacks what they need.
+#[derive(Debug)]
+pub struct IntelligenceClient {
+    socket: Arc<ReconnectingWebSocket>,
+    protocol: watch::Receiver<ServerProtocol>,
+    handshake: JoinHandle<()>,
+}
 
 impl IntelligenceClient {
-    pub async fn new(_url: &str) -> Result<Self> {
-        Ok(Self)
+    pub async fn new(url: &str) -> Result<Self> {
+        Self::connect(url, ReconnectPolicy::default(), broadcast::channel(EVENT_CHANNEL_CAPACITY).0).await
+    }
+
+    /// Connect with `policy`, publishing connection state changes on `events`
+    pub async fn connect(url: &str, policy: ReconnectPolicy, events: broadcast::Sender<ConnectionEvent>) -> Result<Self> {
+        // Subscribed before connecting so the first connection is not missed
+        let state_changes = events.subscribe();
+        let socket = Arc::new(ReconnectingWebSocket::connect_with_greeting(
+            CONNECTION_NAME,
+            url,
+            policy,
+            events,
+            Some(client_hello()),
+        ));
+        let (protocol_sender, protocol) = watch::channel(ServerProtocol::pending());
+        let handshake = tokio::spawn(follow_handshakes(
+            Arc::downgrade(&socket),
+            state_changes,
+            socket.incoming(),
+            protocol_sender,
+        ));
+        Ok(Self { socket, protocol, handshake })
+    }
+
+    /// What the server on the current connection speaks and offers
+    pub fn server_protocol(&self) -> ServerProtocol {
+        self.protocol.borrow().clone()
+    }
+
+    /// Subscribe to an intelligence stream; it is resubscribed after reconnects.
+    /// A stream the server turns out not to offer is dropped once the
+    /// handshake says so.
+    pub fn subscribe_stream(&self, stream: &str) -> Result<()> {
+        if let Some(capability) = ServerCapability::for_stream(stream) {
+            let protocol = self.server_protocol();
+            if protocol.is_settled() {
+                protocol.require(capability)?;
+            }
+        }
+        let message = serde_json::json!({ "type": "subscribe", "stream": stream });
+        self.socket.subscribe(stream, serde_json::to_string(&message)?);
+        Ok(())
+    }
+
+    pub fn unsubscribe_stream(&self, stream: &str) -> Result<()> {
+        self.socket.unsubscribe(stream);
+        let message = serde_json::json!({ "type": "unsubscribe", "stream": stream });
+        self.socket.send(serde_json::to_string(&message)?);
+        Ok(())
+    }
+
+    /// Messages pushed by the intelligence server
+    pub fn messages(&self) -> broadcast::Receiver<String> {
+        self.socket.incoming()
+    }
+
+    /// Ask the app for its current accessibility element tree and wait for the reply
+    pub async fn accessibility_tree(&self, timeout: Duration) -> Result<AccessibilityElement> {
+        match self.request(IntelligenceRequest::AccessibilityTree, timeout).await? {
+            IntelligenceMessage::AccessibilityTree { root, .. } => Ok(root),
+            reply => Err(unexpected_reply(&reply)),
+        }
+    }
+
+    /// Ask the app to count frames for `window` and report its average frame rate
+    pub async fn frame_rate(&self, window: Duration) -> Result<f64> {
+        match self.request(IntelligenceRequest::FrameRate { window }, window + FRAME_RATE_REPLY_GRACE).await? {
+            IntelligenceMessage::FrameRate { fps, .. } => Ok(fps),
+            reply => Err(unexpected_reply(&reply)),
+        }
+    }
+
+    /// Ask the app for the current values of `metrics`; those it cannot
+    /// measure are left out of the reply
+    pub async fn performance_metrics(&self, metrics: &[PerformanceMetric], timeout: Duration) -> Result<CapturedMetrics> {
+        match self.request(IntelligenceRequest::PerformanceMetrics { metrics }, timeout).await? {
+            IntelligenceMessage::PerformanceMetrics { metrics, .. } => Ok(metrics),
+            reply => Err(unexpected_reply(&reply)),
+        }
+    }
+
+    /// Ask the app to tap, type or swipe, and wait until it has
+    pub async fn perform_ui_action(&self, action: &UiAction, timeout: Duration) -> Result<()> {
+        match self.request(IntelligenceRequest::UiAction { action }, timeout).await? {
+            IntelligenceMessage::UiActionResult { error: None, .. } => Ok(()),
+            IntelligenceMessage::UiActionResult { error: Some(error), .. } => {
+                Err(AxiomMCPError::validation("intelligence", "request_ui_action", error))
+            }
+            reply => Err(unexpected_reply(&reply)),
+        }
+    }
+
+    /// Ask the server what `requirement` describes
+    pub async fn analyze_requirement(&self, requirement: &str, timeout: Duration) -> Result<RequirementInsights> {
+        match self.request(IntelligenceRequest::RequirementAnalysis { requirement }, timeout).await? {
+            IntelligenceMessage::RequirementAnalysis { insights, .. } => Ok(insights),
+            reply => Err(unexpected_reply(&reply)),
+        }
+    }
+
+    /// Send `request` in the schema the server speaks once the handshake
+    /// settles, and wait for the reply carrying the same request id
+    async fn request(&self, request: IntelligenceRequest<'_>, timeout: Duration) -> Result<IntelligenceMessage> {
+        let request_type = request.message_type();
+        let reply = tokio::time::timeout(timeout, async {
+            let protocol = self.settled_protocol(request_type).await?;
+            protocol.require(request.capability())?;
+
+            let request_id = uuid::Uuid::new_v4().to_string();
+            // Listen before asking so a fast reply is not missed
+            let mut messages = self.messages();
+            self.socket.send(request.encode(protocol.version, &request_id));
+            loop {
+                let message = match messages.recv().await {
+                    Ok(message) => message,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(AxiomMCPError::connectivity("intelligence", request_type, "Intelligence connection closed"));
+                    }
+                };
+                match decode_message(protocol.version, &message) {
+                    Ok(Some(reply)) if request.is_answered_by(&reply) && reply.request_id() == Some(request_id.as_str()) => {
+                        return Ok(reply);
+                    }
+                    // A reply to this request that cannot be read will not be followed by one that can
+                    Err(e) if message.contains(request_id.as_str()) => return Err(e),
+                    _ => continue,
+                }
+            }
+        })
+        .await;
+
+        reply.map_err(|_| AxiomMCPError::connectivity("intelligence", request_type, format!(
+            "The app did not send {} within {:?}", request.describe_reply(), timeout
+        )))?
+    }
+
+    async fn settled_protocol(&self, request_type: &str) -> Result<ServerProtocol> {
+        let mut protocol = self.protocol.clone();
+        let settled = protocol.wait_for(ServerProtocol::is_settled).await.map_err(|_| {
+            AxiomMCPError::internal("intelligence", request_type, "The handshake task stopped")
+        })?;
+        Ok(settled.clone())
+    }
+
+    pub fn connection_status(&self) -> ConnectionStatus {
+        self.socket.status()
+    }
+}
+
+impl Drop for IntelligenceClient {
+    fn drop(&mut self) {
+        self.handshake.abort();
+    }
+}
+
+fn unexpected_reply(reply: &IntelligenceMessage) -> AxiomMCPError {
+    AxiomMCPError::internal("intelligence", "match reply", format!("Unexpected reply {:?}", reply))
+}
+
+/// Settle the protocol of each connection: the server's hello decides it,
+/// and a server silent for `HANDSHAKE_TIMEOUT` predates the handshake.
+/// Streams the server turns out not to offer are unsubscribed, and
+/// messages of unknown types are logged and skipped.
+async fn follow_handshakes(
+    socket: Weak<ReconnectingWebSocket>,
+    mut state_changes: broadcast::Receiver<ConnectionEvent>,
+    mut messages: broadcast::Receiver<String>,
+    protocol: watch::Sender<ServerProtocol>,
+) {
+    let mut deadline: Option<tokio::time::Instant> = None;
+    loop {
+        let settled = tokio::select! {
+            // The hello of a connection always follows its connected event
+            biased;
+            event = state_changes.recv() => match event {
+                Ok(event) if event.name == CONNECTION_NAME => {
+                    protocol.send_replace(ServerProtocol::pending());
+                    deadline = (event.state == ConnectionState::Connected)
+                        .then(|| tokio::time::Instant::now() + HANDSHAKE_TIMEOUT);
+                    None
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => None,
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            message = messages.recv() => match message {
+                Ok(message) => match decode_message(protocol.borrow().version, &message) {
+                    Ok(Some(IntelligenceMessage::Hello(hello))) => Some(ServerProtocol::from_hello(&hello)),
+                    Ok(Some(_)) => None,
+                    Ok(None) => {
+                        tracing::debug!("Skipping intelligence message of unknown type: {}", message);
+                        None
+                    }
+                    Err(e) => {
+                        tracing::debug!("Skipping unreadable intelligence message: {}", e);
+                        None
+                    }
+                },
+                Err(broadcast::error::RecvError::Lagged(_)) => None,
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            _ = tokio::time::sleep_until(deadline.unwrap_or_else(tokio::time::Instant::now)), if deadline.is_some() => {
+                tracing::warn!("The intelligence server did not answer the handshake, reading it as protocol version 1");
+                Some(ServerProtocol::legacy())
+            }
+        };
+
+        let Some(settled) = settled else {
+            continue;
+        };
+        deadline = None;
+        let degraded = settled.degraded();
+        if !degraded.is_empty() {
+            tracing::warn!(
+                "The intelligence server (protocol version {}) does not offer {:?}",
+                settled.version,
+                degraded.iter().map(|capability| capability.name()).collect::<Vec<_>>()
+            );
+        }
+        if let Some(socket) = socket.upgrade() {
+            for stream in socket.subscriptions() {
+                if ServerCapability::for_stream(&stream).is_some_and(|capability| !settled.supports(capability)) {
+                    tracing::info!("Unsubscribing from {}, which the intelligence server does not offer", stream);
+                    socket.unsubscribe(&stream);
+                }
+            }
+        }
+        protocol.send_replace(settled);
     }
-}
\ No newline at end of file
+}
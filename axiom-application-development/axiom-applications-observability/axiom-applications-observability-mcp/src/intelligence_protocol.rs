@@ -0,0 +1,477 @@
+use crate::advanced_visual_intelligence::{AccessibilityElement, ElementFrame, ElementType};
+use crate::error::{AxiomMCPError, Result};
+use crate::performance_analysis_integration::{CapturedMetrics, PerformanceMetric};
+use crate::ui_script::UiAction;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// Schema this client writes and prefers to read
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Schema of servers from before the handshake, which never answer it
+pub const LEGACY_PROTOCOL_VERSION: u32 = 1;
+
+/// Schemas this client can read, oldest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: [u32; 2] = [LEGACY_PROTOCOL_VERSION, PROTOCOL_VERSION];
+
+/// Stream of the app's visual state changes
+pub const VISUAL_STATE_STREAM: &str = "visual_state";
+
+/// Message type of the handshake, in both directions
+const HELLO: &str = "hello";
+
+/// Something the intelligence server may or may not offer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerCapability {
+    AccessibilityTree,
+    FrameRate,
+    PerformanceMetrics,
+    VisualStateStream,
+    /// Taps, text entry and swipes sent to the app
+    UiInteraction,
+    /// Entities, screens and actions read from a natural language requirement
+    RequirementAnalysis,
+}
+
+impl ServerCapability {
+    pub const ALL: [ServerCapability; 6] = [
+        ServerCapability::AccessibilityTree,
+        ServerCapability::FrameRate,
+        ServerCapability::PerformanceMetrics,
+        ServerCapability::VisualStateStream,
+        ServerCapability::UiInteraction,
+        ServerCapability::RequirementAnalysis,
+    ];
+
+    /// What a server from before the handshake offers
+    pub const LEGACY: [ServerCapability; 2] = [ServerCapability::AccessibilityTree, ServerCapability::FrameRate];
+
+    /// Name in the server's capability list
+    pub fn name(self) -> &'static str {
+        match self {
+            ServerCapability::AccessibilityTree => "accessibility_tree",
+            ServerCapability::FrameRate => "frame_rate",
+            ServerCapability::PerformanceMetrics => "performance_metrics",
+            ServerCapability::VisualStateStream => "visual_state_stream",
+            ServerCapability::UiInteraction => "ui_interaction",
+            ServerCapability::RequirementAnalysis => "requirement_analysis",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|capability| capability.name() == name)
+    }
+
+    /// The capability subscribing to `stream` needs, if any
+    pub fn for_stream(stream: &str) -> Option<Self> {
+        match stream {
+            VISUAL_STATE_STREAM => Some(ServerCapability::VisualStateStream),
+            _ => None,
+        }
+    }
+}
+
+/// The client's half of the handshake, sent first on every connection
+pub fn client_hello() -> String {
+    json!({
+        "type": HELLO,
+        "protocolVersions": SUPPORTED_PROTOCOL_VERSIONS,
+        "capabilities": ServerCapability::ALL.map(ServerCapability::name),
+        "client": env!("CARGO_PKG_NAME"),
+        "clientVersion": env!("CARGO_PKG_VERSION"),
+    })
+    .to_string()
+}
+
+/// The server's half of the handshake
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerHello {
+    /// The schema the server chose from those the client offered
+    pub protocol_version: u32,
+    /// Capability names; names this client does not know are ignored
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub server_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandshakeState {
+    /// Connecting, or waiting for the server to answer
+    Pending,
+    Negotiated,
+    /// The server did not answer, so it predates the handshake
+    Legacy,
+    /// The server chose a schema this client cannot read
+    Unsupported,
+}
+
+/// What was agreed with the intelligence server on its current connection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerProtocol {
+    pub handshake: HandshakeState,
+    /// Schema the server's messages are read with
+    pub version: u32,
+    pub server_version: Option<String>,
+    pub capabilities: BTreeSet<ServerCapability>,
+}
+
+impl ServerProtocol {
+    pub fn pending() -> Self {
+        Self {
+            handshake: HandshakeState::Pending,
+            version: PROTOCOL_VERSION,
+            server_version: None,
+            capabilities: ServerCapability::ALL.into_iter().collect(),
+        }
+    }
+
+    pub fn legacy() -> Self {
+        Self {
+            handshake: HandshakeState::Legacy,
+            version: LEGACY_PROTOCOL_VERSION,
+            server_version: None,
+            capabilities: ServerCapability::LEGACY.into_iter().collect(),
+        }
+    }
+
+    pub fn from_hello(hello: &ServerHello) -> Self {
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&hello.protocol_version) {
+            return Self {
+                handshake: HandshakeState::Unsupported,
+                version: hello.protocol_version,
+                server_version: hello.server_version.clone(),
+                capabilities: BTreeSet::new(),
+            };
+        }
+        Self {
+            handshake: HandshakeState::Negotiated,
+            version: hello.protocol_version,
+            server_version: hello.server_version.clone(),
+            capabilities: hello.capabilities.iter().filter_map(|name| ServerCapability::from_name(name)).collect(),
+        }
+    }
+
+    /// Whether the handshake has finished, one way or the other
+    pub fn is_settled(&self) -> bool {
+        self.handshake != HandshakeState::Pending
+    }
+
+    pub fn supports(&self, capability: ServerCapability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+
+    /// Capabilities this client uses that the server does not offer
+    pub fn degraded(&self) -> Vec<ServerCapability> {
+        ServerCapability::ALL.into_iter().filter(|capability| !self.supports(*capability)).collect()
+    }
+
+    /// An error for using `capability` when the server does not offer it
+    pub fn require(&self, capability: ServerCapability) -> Result<()> {
+        if self.handshake == HandshakeState::Unsupported {
+            return Err(AxiomMCPError::configuration("intelligence_server", format!(
+                "The intelligence server speaks protocol version {}, this client reads versions {:?}",
+                self.version, SUPPORTED_PROTOCOL_VERSIONS
+            )));
+        }
+        if !self.supports(capability) {
+            return Err(AxiomMCPError::configuration("intelligence_server", format!(
+                "The intelligence server (protocol version {}) does not offer {}; upgrade it to version {}",
+                self.version, capability.name(), PROTOCOL_VERSION
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A request to the app through the intelligence server
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntelligenceRequest<'a> {
+    AccessibilityTree,
+    /// Count frames for `window` and report the average
+    FrameRate { window: Duration },
+    PerformanceMetrics { metrics: &'a [PerformanceMetric] },
+    UiAction { action: &'a UiAction },
+    RequirementAnalysis { requirement: &'a str },
+}
+
+impl IntelligenceRequest<'_> {
+    pub fn message_type(&self) -> &'static str {
+        match self {
+            IntelligenceRequest::AccessibilityTree => "request_accessibility_tree",
+            IntelligenceRequest::FrameRate { .. } => "request_frame_rate",
+            IntelligenceRequest::PerformanceMetrics { .. } => "request_performance_metrics",
+            IntelligenceRequest::UiAction { .. } => "request_ui_action",
+            IntelligenceRequest::RequirementAnalysis { .. } => "request_requirement_analysis",
+        }
+    }
+
+    pub fn capability(&self) -> ServerCapability {
+        match self {
+            IntelligenceRequest::AccessibilityTree => ServerCapability::AccessibilityTree,
+            IntelligenceRequest::FrameRate { .. } => ServerCapability::FrameRate,
+            IntelligenceRequest::PerformanceMetrics { .. } => ServerCapability::PerformanceMetrics,
+            IntelligenceRequest::UiAction { .. } => ServerCapability::UiInteraction,
+            IntelligenceRequest::RequirementAnalysis { .. } => ServerCapability::RequirementAnalysis,
+        }
+    }
+
+    /// What the reply carries, for timeout messages
+    pub fn describe_reply(&self) -> &'static str {
+        match self {
+            IntelligenceRequest::AccessibilityTree => "its accessibility tree",
+            IntelligenceRequest::FrameRate { .. } => "its frame rate",
+            IntelligenceRequest::PerformanceMetrics { .. } => "its performance metrics",
+            IntelligenceRequest::UiAction { .. } => "the outcome of the UI action",
+            IntelligenceRequest::RequirementAnalysis { .. } => "its analysis of the requirement",
+        }
+    }
+
+    /// Whether `message` answers this request
+    pub fn is_answered_by(&self, message: &IntelligenceMessage) -> bool {
+        matches!(
+            (self, message),
+            (IntelligenceRequest::AccessibilityTree, IntelligenceMessage::AccessibilityTree { .. }) |
+            (IntelligenceRequest::FrameRate { .. }, IntelligenceMessage::FrameRate { .. }) |
+            (IntelligenceRequest::PerformanceMetrics { .. }, IntelligenceMessage::PerformanceMetrics { .. }) |
+            (IntelligenceRequest::UiAction { .. }, IntelligenceMessage::UiActionResult { .. }) |
+            (IntelligenceRequest::RequirementAnalysis { .. }, IntelligenceMessage::RequirementAnalysis { .. })
+        )
+    }
+
+    /// The request as schema `version` writes it
+    pub fn encode(&self, version: u32, request_id: &str) -> String {
+        let mut message = match (self, version) {
+            (IntelligenceRequest::AccessibilityTree, _) => json!({}),
+            (IntelligenceRequest::FrameRate { window }, LEGACY_PROTOCOL_VERSION) => json!({ "window_ms": window.as_millis() as u64 }),
+            (IntelligenceRequest::FrameRate { window }, _) => json!({ "windowMs": window.as_millis() as u64 }),
+            (IntelligenceRequest::PerformanceMetrics { metrics }, _) => json!({ "metrics": metrics }),
+            (IntelligenceRequest::UiAction { action }, _) => json!({ "action": action }),
+            (IntelligenceRequest::RequirementAnalysis { requirement }, _) => json!({ "requirement": requirement }),
+        };
+        message["type"] = self.message_type().into();
+        let request_id_key = if version == LEGACY_PROTOCOL_VERSION { "request_id" } else { "requestId" };
+        message[request_id_key] = request_id.into();
+        message.to_string()
+    }
+}
+
+/// A message from the intelligence server, in the current types whichever
+/// schema it was written with
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntelligenceMessage {
+    Hello(ServerHello),
+    AccessibilityTree { request_id: String, root: AccessibilityElement },
+    FrameRate { request_id: String, fps: f64 },
+    PerformanceMetrics { request_id: String, metrics: CapturedMetrics },
+    /// `error` says why the app could not perform the action
+    UiActionResult { request_id: String, error: Option<String> },
+    RequirementAnalysis { request_id: String, insights: RequirementInsights },
+}
+
+impl IntelligenceMessage {
+    /// The request this message answers
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            IntelligenceMessage::Hello(_) => None,
+            IntelligenceMessage::AccessibilityTree { request_id, .. } |
+            IntelligenceMessage::FrameRate { request_id, .. } |
+            IntelligenceMessage::PerformanceMetrics { request_id, .. } |
+            IntelligenceMessage::UiActionResult { request_id, .. } |
+            IntelligenceMessage::RequirementAnalysis { request_id, .. } => Some(request_id),
+        }
+    }
+}
+
+/// What the server read from a requirement; the client derives the
+/// persistence and navigation specs from it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequirementInsights {
+    /// 0 to 100, as the server judges how completely the requirement is understood
+    pub confidence_score: f64,
+    /// `Simple`, `Medium` or `Complex`
+    pub complexity: String,
+    #[serde(default)]
+    pub entities: Vec<String>,
+    #[serde(default)]
+    pub screens: Vec<String>,
+    #[serde(default)]
+    pub actions: Vec<String>,
+    /// The entity the requirement asks to save, if it asks to save anything
+    #[serde(default)]
+    pub persisted_entity: Option<String>,
+}
+
+/// Read a message the server wrote with schema `version`, upgrading older
+/// schemas to the current types. `None` for a message type this client
+/// does not know, which the caller should skip.
+pub fn decode_message(version: u32, text: &str) -> Result<Option<IntelligenceMessage>> {
+    let message: Value = serde_json::from_str(text).map_err(|e| malformed("message", version, e))?;
+    let message_type = message["type"].as_str().unwrap_or_default().to_string();
+    let decoded = match (message_type.as_str(), version) {
+        (HELLO, _) => serde_json::from_value(message).map(IntelligenceMessage::Hello),
+        (_, LEGACY_PROTOCOL_VERSION) => match v1::decode(&message_type, message) {
+            Some(decoded) => decoded,
+            None => return Ok(None),
+        },
+        (_, _) => match v2::decode(&message_type, message) {
+            Some(decoded) => decoded,
+            None => return Ok(None),
+        },
+    };
+    decoded.map(Some).map_err(|e| malformed(&message_type, version, e))
+}
+
+fn malformed(message_type: &str, version: u32, error: serde_json::Error) -> AxiomMCPError {
+    AxiomMCPError::internal("intelligence", "decode message", format!(
+        "Malformed {} from the intelligence server (protocol version {}): {}", message_type, version, error
+    ))
+    .with_cause(error)
+}
+
+/// The current schema
+mod v2 {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct AccessibilityTreeReply {
+        request_id: String,
+        root: AccessibilityElement,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct FrameRateReply {
+        request_id: String,
+        fps: f64,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PerformanceMetricsReply {
+        request_id: String,
+        metrics: CapturedMetrics,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct UiActionReply {
+        request_id: String,
+        #[serde(default)]
+        error: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct RequirementAnalysisReply {
+        request_id: String,
+        #[serde(flatten)]
+        insights: RequirementInsights,
+    }
+
+    pub(super) fn decode(message_type: &str, message: Value) -> Option<serde_json::Result<IntelligenceMessage>> {
+        let decoded = match message_type {
+            "accessibility_tree" => serde_json::from_value(message)
+                .map(|reply: AccessibilityTreeReply| IntelligenceMessage::AccessibilityTree { request_id: reply.request_id, root: reply.root }),
+            "frame_rate" => serde_json::from_value(message)
+                .map(|reply: FrameRateReply| IntelligenceMessage::FrameRate { request_id: reply.request_id, fps: reply.fps }),
+            "performance_metrics" => serde_json::from_value(message).map(|reply: PerformanceMetricsReply| {
+                IntelligenceMessage::PerformanceMetrics { request_id: reply.request_id, metrics: reply.metrics }
+            }),
+            "ui_action_result" => serde_json::from_value(message)
+                .map(|reply: UiActionReply| IntelligenceMessage::UiActionResult { request_id: reply.request_id, error: reply.error }),
+            "requirement_analysis" => serde_json::from_value(message).map(|reply: RequirementAnalysisReply| {
+                IntelligenceMessage::RequirementAnalysis { request_id: reply.request_id, insights: reply.insights }
+            }),
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
+
+/// The schema from before the handshake: snake_case keys, frames as
+/// `[x, y, width, height]` and no performance metrics
+mod v1 {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct AccessibilityTreeReply {
+        request_id: String,
+        tree: Element,
+    }
+
+    #[derive(Deserialize)]
+    struct Element {
+        identifier: String,
+        #[serde(default)]
+        element_type: Option<String>,
+        #[serde(default)]
+        label: Option<String>,
+        #[serde(default)]
+        traits: Vec<String>,
+        frame: [f64; 4],
+        #[serde(default)]
+        hidden: bool,
+        #[serde(default)]
+        font_size: Option<f64>,
+        #[serde(default)]
+        is_bold: bool,
+        #[serde(default)]
+        children: Vec<Element>,
+    }
+
+    impl Element {
+        fn upgrade(self) -> AccessibilityElement {
+            let [x, y, width, height] = self.frame;
+            AccessibilityElement {
+                identifier: self.identifier,
+                element_type: self.element_type.map(|name| element_type(&name)).unwrap_or_default(),
+                label: self.label,
+                traits: self.traits,
+                frame: ElementFrame { x, y, width, height },
+                hidden: self.hidden,
+                font_size: self.font_size,
+                is_bold: self.is_bold,
+                children: self.children.into_iter().map(Element::upgrade).collect(),
+            }
+        }
+    }
+
+    /// Version 1 named element types in snake_case, e.g. `text_field`
+    fn element_type(name: &str) -> ElementType {
+        let camel_case: String = name
+            .split('_')
+            .enumerate()
+            .map(|(i, word)| match (i, word.chars().next()) {
+                (0, _) | (_, None) => word.to_string(),
+                (_, Some(first)) => first.to_uppercase().chain(word.chars().skip(1)).collect(),
+            })
+            .collect();
+        serde_json::from_value(Value::String(camel_case)).unwrap_or_default()
+    }
+
+    #[derive(Deserialize)]
+    struct FrameRateReply {
+        request_id: String,
+        frame_rate: f64,
+    }
+
+    pub(super) fn decode(message_type: &str, message: Value) -> Option<serde_json::Result<IntelligenceMessage>> {
+        let decoded = match message_type {
+            "accessibility_tree" => serde_json::from_value(message).map(|reply: AccessibilityTreeReply| {
+                IntelligenceMessage::AccessibilityTree { request_id: reply.request_id, root: reply.tree.upgrade() }
+            }),
+            "frame_rate" => serde_json::from_value(message)
+                .map(|reply: FrameRateReply| IntelligenceMessage::FrameRate { request_id: reply.request_id, fps: reply.frame_rate }),
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
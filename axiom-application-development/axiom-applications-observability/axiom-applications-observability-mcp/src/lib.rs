@@ -4,31 +4,65 @@
 //! Provides code generation, performance monitoring, visual analysis, and complete development loop automation.
 
 pub mod mcp;
+pub mod config;
 pub mod tools;
+pub mod tool_registry;
+pub mod resource_locks;
+pub mod tool_metrics;
+pub mod protocol;
 pub mod types;
 pub mod error;
 pub mod code_generation;
+pub mod generation_cache;
+pub mod feature_generation;
+pub mod swift_validation;
+pub mod project_build;
+pub mod test_execution;
 pub mod complete_development_loop;
+pub mod cycle_report;
+pub mod approval_gates;
+pub mod requirement_clarification;
+pub mod requirement_analysis;
+pub mod traceability;
+pub mod connection;
 pub mod hot_reload;
+pub mod hot_reload_protocol;
+pub mod reload_scope;
 pub mod intelligence;
+pub mod intelligence_protocol;
 pub mod simulator;
+pub mod simulator_pool;
+pub mod simulator_capture;
+pub mod ui_script;
+pub mod device_matrix;
 pub mod screenshot_matrix_engine;
+pub mod artifact_store;
+pub mod screenshot_store;
+pub mod screenshot_consistency;
+pub mod system_status;
 pub mod advanced_visual_intelligence;
+pub mod visual_regression;
 pub mod performance_analysis_integration;
+pub mod metrics_stream;
 pub mod development_workflow;
 pub mod axiom_framework_integration;
 
 // Re-export main types for convenience
 pub use mcp::{AxiomApplicationsObservabilityMCP, MCPConfiguration, MCPCapabilities};
+pub use config::ConfigSource;
 pub use tools::{AxiomMCPTool, ToolResult};
 pub use error::{AxiomMCPError, Result};
 pub use types::*;
 
-/// Initialize the Axiom Applications Observability MCP system
-pub async fn init_mcp(config: MCPConfiguration, capabilities: MCPCapabilities) -> Result<AxiomApplicationsObservabilityMCP> {
+/// Initialize the Axiom Applications Observability MCP system, with an
+/// explicit configuration or `ConfigSource::Load` to read it from the
+/// configuration file and environment
+pub async fn init_mcp(config: impl Into<ConfigSource>, capabilities: MCPCapabilities) -> Result<AxiomApplicationsObservabilityMCP> {
     tracing::info!("Initializing Axiom Applications Observability MCP");
     
+    let config = config.into().resolve()?;
     let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await?;
+    mcp.system_status().await.log();
     
     tracing::info!("✅ Axiom Applications Observability MCP initialized successfully");
     Ok(mcp)
@@ -51,6 +85,7 @@ mod tests {
             code_generation_enabled: true,
             visual_analysis_enabled: true,
             performance_monitoring_enabled: true,
+            ..Default::default()
         };
         
         let capabilities = MCPCapabilities {
@@ -1,9 +1,45 @@
 use serde::{Deserialize, Serialize};
-use crate::{error::Result, tools::{AxiomMCPTool, ToolResult}};
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use crate::{error::Result, tools::{ArtifactArguments, AxiomMCPTool, CycleArguments, DeviceArguments, MetricsStreamArguments, NoArguments, RecordingArguments, RequirementArguments, ToolResult}};
+use crate::protocol::{CallToolParams, CallToolResult, JsonRpcError, JsonRpcRequest, JsonRpcResponse, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR};
+use crate::tool_registry::{ToolListing, ToolRegistry};
+use crate::tools::tool_spec;
+use crate::approval_gates::{ApprovalGates, ApprovalRequest, RejectStageRequest};
+use crate::requirement_clarification::{ClarificationRequest, RequirementAnswers, RequirementClarifier};
+use crate::code_generation::AxiomCodeGenerator;
+use crate::axiom_framework_integration::{detect_framework_versions, AxiomFrameworkConfig, FrameworkCompatibility};
+use crate::complete_development_loop::{AxiomObservabilityLoop, CycleStore};
+use crate::cycle_report::{CycleReport, CycleReportConfig, CycleReportExporter, CycleReportRequest};
+use crate::device_matrix::ScreenshotMatrixRequest;
+use crate::development_workflow::AxiomObservabilityWorkflow;
+use crate::advanced_visual_intelligence::{AccessibilityAuditRequest, ScreenTextAssertionRequest, VisualIntelligenceEngine, DEFAULT_SCREEN_TEXT_DIR_NAME};
+use crate::connection::{ConnectionEvent, ReconnectPolicy};
+use crate::hot_reload::{FileChange, HotReloadClient, HANDSHAKE_TIMEOUT, HotReloadConfig, HotReloadFilter, HotReloadFilters, ReloadPipeline, ReloadTransport};
+use crate::intelligence::IntelligenceClient;
+use crate::metrics_stream::{MetricsSource, MetricsStreamRequest, MetricsStreams, NotificationSink};
+use crate::performance_analysis_integration::{AppPerformanceMeter, CapturedMetrics, FrameRateSource, MeasureAppPerformanceRequest, PerformanceHistory, PerformanceThresholds, PerformanceTrends, ReloadMemoryConfig, ReloadMemoryTracker, TrendOptions};
+use crate::reload_scope::ComponentIndex;
+use crate::requirement_analysis::{requirement_analyzer, RequirementAnalyzer, RequirementAnalyzerMode};
+use crate::resource_locks::{Resource, ResourceLocks, ResourceNeed};
+use crate::tool_metrics::{time_stage, ToolMetrics, ToolMetricsConfig};
+use crate::simulator::{InstallAppRequest, LaunchAppRequest, ResetSimulatorStateRequest, SimulatorController, SimulatorLocale};
+use crate::simulator_capture::{SimulatorCapture, StartRecordingRequest, DEFAULT_CAPTURE_DIR_NAME};
+use crate::ui_script::{IntelligenceUiDriver, RunUiScriptRequest, UiScript, UiScriptRunner};
+use crate::artifact_store::{ArtifactKind, ArtifactQuery, ArtifactStore, ArtifactStoreConfig};
+use crate::screenshot_store::{ScreenshotStorageConfig, ScreenshotStore};
+use crate::system_status::{ConnectionProbe, SimulatorProbe, StorageProbe, SystemStatus, SystemStatusChecker, TemplateProbe};
+use crate::visual_regression::{RegressionCheckRequest, VisualRegressionChecker, VisualRegressionConfig};
+use serde_json::Value;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{broadcast, RwLock};
 
-/// Configuration for the Axiom Applications Observability MCP
+/// Connection state changes buffered per subscriber before it starts lagging
+const CONNECTION_EVENT_CAPACITY: usize = 32;
+
+/// How long `audit_accessibility` waits for the app to send its element tree
+const ACCESSIBILITY_TREE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Configuration for the Axiom Applications Observability MCP; see
+/// `MCPConfiguration::load` to read it from a file and the environment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MCPConfiguration {
     pub hot_reload_server_url: String,
@@ -12,6 +48,67 @@ pub struct MCPConfiguration {
     pub code_generation_enabled: bool,
     pub visual_analysis_enabled: bool,
     pub performance_monitoring_enabled: bool,
+    /// Which watched files trigger a hot reload
+    #[serde(default)]
+    pub hot_reload_filters: HotReloadFilters,
+    /// Baseline location, thresholds and ignore regions for `detect_ui_regressions`
+    #[serde(default)]
+    pub visual_regression: VisualRegressionConfig,
+    /// Where captured screenshots are written, and how long they are kept
+    #[serde(default)]
+    pub screenshot_storage: ScreenshotStorageConfig,
+    /// Launch time, memory and frame rate limits, unless the project has
+    /// its own thresholds file
+    #[serde(default)]
+    pub performance_thresholds: PerformanceThresholds,
+    /// Axiom framework versions to generate for, when the project's
+    /// `Package.resolved` does not say or should not be followed
+    #[serde(default)]
+    pub axiom_framework: AxiomFrameworkConfig,
+    /// Where `export_cycle_report` writes reports and how large their
+    /// screenshots are
+    #[serde(default)]
+    pub cycle_reports: CycleReportConfig,
+    /// How much the launched app may grow across hot reloads before it is
+    /// flagged as leaking
+    #[serde(default)]
+    pub reload_memory: ReloadMemoryConfig,
+    /// Whether requirements are analyzed by the intelligence server, by
+    /// offline rules, or by the server with the rules to fall back on
+    #[serde(default)]
+    pub requirement_analyzer: RequirementAnalyzerMode,
+    /// Where per-tool call metrics are persisted as JSONL, besides in memory
+    #[serde(default)]
+    pub tool_metrics: ToolMetricsConfig,
+    /// Where screenshots, build logs, test results, performance
+    /// measurements and reports are kept, and how long each kind is; the
+    /// screenshots' retention is `screenshot_storage.retention`
+    #[serde(default)]
+    pub artifacts: ArtifactStoreConfig,
+}
+
+impl Default for MCPConfiguration {
+    /// Local hot reload and intelligence servers with every feature enabled
+    fn default() -> Self {
+        Self {
+            hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
+            intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
+            simulator_management_enabled: true,
+            code_generation_enabled: true,
+            visual_analysis_enabled: true,
+            performance_monitoring_enabled: true,
+            hot_reload_filters: HotReloadFilters::default(),
+            visual_regression: VisualRegressionConfig::default(),
+            screenshot_storage: ScreenshotStorageConfig::default(),
+            performance_thresholds: PerformanceThresholds::default(),
+            axiom_framework: AxiomFrameworkConfig::default(),
+            cycle_reports: CycleReportConfig::default(),
+            reload_memory: ReloadMemoryConfig::default(),
+            requirement_analyzer: RequirementAnalyzerMode::default(),
+            tool_metrics: ToolMetricsConfig::default(),
+            artifacts: ArtifactStoreConfig::default(),
+        }
+    }
 }
 
 /// Capabilities of the MCP system
@@ -25,12 +122,50 @@ pub struct MCPCapabilities {
     pub performance_monitoring: bool,
 }
 
+impl MCPCapabilities {
+    /// Whether the capability a tool spec names, such as `visual_analysis`,
+    /// is on; names this struct has no flag for are never enabled
+    pub fn is_enabled(&self, capability: &str) -> bool {
+        match capability {
+            "code_generation" => self.code_generation,
+            "intelligence_analysis" => self.intelligence_analysis,
+            "hot_reload_integration" => self.hot_reload_integration,
+            "visual_analysis" => self.visual_analysis,
+            "simulator_management" => self.simulator_management,
+            "performance_monitoring" => self.performance_monitoring,
+            _ => false,
+        }
+    }
+    
+    /// The first of `required` that is off
+    pub fn first_disabled<'a>(&self, required: &[&'a str]) -> Option<&'a str> {
+        required.iter().copied().find(|capability| !self.is_enabled(capability))
+    }
+}
+
 /// Main MCP implementation for Axiom Applications Observability
 #[derive(Debug)]
 pub struct AxiomApplicationsObservabilityMCP {
     config: MCPConfiguration,
     capabilities: MCPCapabilities,
     state: Arc<RwLock<MCPState>>,
+    hot_reload_client: Option<Arc<HotReloadClient>>,
+    hot_reload_filter: Arc<HotReloadFilter>,
+    reload_pipeline: Option<Arc<ReloadPipeline>>,
+    reload_memory: Arc<ReloadMemoryTracker>,
+    intelligence_client: Option<Arc<IntelligenceClient>>,
+    connection_events: broadcast::Sender<ConnectionEvent>,
+    simulator: Option<Arc<SimulatorController>>,
+    simulator_capture: Arc<SimulatorCapture>,
+    artifact_store: Arc<ArtifactStore>,
+    screenshot_store: Arc<ScreenshotStore>,
+    code_generator: Arc<AxiomCodeGenerator>,
+    development_loop: Option<Arc<AxiomObservabilityLoop>>,
+    metrics_streams: Option<Arc<MetricsStreams>>,
+    notification_sink: Option<Arc<dyn NotificationSink>>,
+    locks: Arc<ResourceLocks>,
+    requirement_analyzer: Arc<dyn RequirementAnalyzer>,
+    tool_metrics: Arc<ToolMetrics>,
 }
 
 #[derive(Debug)]
@@ -46,6 +181,23 @@ impl Clone for AxiomApplicationsObservabilityMCP {
             config: self.config.clone(),
             capabilities: self.capabilities.clone(),
             state: Arc::clone(&self.state),
+            hot_reload_client: self.hot_reload_client.clone(),
+            hot_reload_filter: Arc::clone(&self.hot_reload_filter),
+            reload_pipeline: self.reload_pipeline.clone(),
+            reload_memory: Arc::clone(&self.reload_memory),
+            intelligence_client: self.intelligence_client.clone(),
+            connection_events: self.connection_events.clone(),
+            simulator: self.simulator.clone(),
+            simulator_capture: Arc::clone(&self.simulator_capture),
+            artifact_store: Arc::clone(&self.artifact_store),
+            screenshot_store: Arc::clone(&self.screenshot_store),
+            code_generator: Arc::clone(&self.code_generator),
+            development_loop: self.development_loop.clone(),
+            metrics_streams: self.metrics_streams.clone(),
+            notification_sink: self.notification_sink.clone(),
+            locks: Arc::clone(&self.locks),
+            requirement_analyzer: Arc::clone(&self.requirement_analyzer),
+            tool_metrics: Arc::clone(&self.tool_metrics),
         }
     }
 }
@@ -55,18 +207,9 @@ impl AxiomApplicationsObservabilityMCP {
     pub async fn new(config: MCPConfiguration, capabilities: MCPCapabilities) -> Result<Self> {
         tracing::info!("Creating Axiom Applications Observability MCP");
         
-        // Validate configuration
-        if config.hot_reload_server_url.is_empty() {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Hot reload server URL cannot be empty".to_string()
-            ));
-        }
+        config.validate()?;
         
-        if config.intelligence_server_url.is_empty() {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Intelligence server URL cannot be empty".to_string()
-            ));
-        }
+        let hot_reload_filter = Arc::new(HotReloadFilter::new(config.hot_reload_filters.clone())?);
         
         let state = Arc::new(RwLock::new(MCPState {
             active_sessions: 0,
@@ -74,16 +217,161 @@ impl AxiomApplicationsObservabilityMCP {
             last_activity: None,
         }));
         
+        // Both connections retry in the background, so a server that is not
+        // up yet does not fail initialization
+        let connection_events = broadcast::channel(CONNECTION_EVENT_CAPACITY).0;
+        let hot_reload_client = if capabilities.hot_reload_integration {
+            Some(Arc::new(HotReloadClient::connect(
+                &config.hot_reload_server_url,
+                ReconnectPolicy::default(),
+                connection_events.clone(),
+            ).await?))
+        } else {
+            None
+        };
+        let reload_pipeline = hot_reload_client.as_ref().map(|client| {
+            let transport: Arc<dyn ReloadTransport> = client.clone();
+            Arc::new(ReloadPipeline::spawn_filtered(
+                transport,
+                HotReloadConfig::default(),
+                Arc::clone(&hot_reload_filter),
+            ))
+        });
+        let reload_memory = Arc::new(ReloadMemoryTracker::new(config.reload_memory.clone()));
+        if let (Some(pipeline), true) = (&reload_pipeline, capabilities.performance_monitoring) {
+            pipeline.observe(reload_memory.clone());
+        }
+        let intelligence_client = if capabilities.intelligence_analysis {
+            Some(Arc::new(IntelligenceClient::connect(
+                &config.intelligence_server_url,
+                ReconnectPolicy::default(),
+                connection_events.clone(),
+            ).await?))
+        } else {
+            None
+        };
+        
+        let requirement_analyzer = requirement_analyzer(config.requirement_analyzer, intelligence_client.clone())?;
+        
+        let simulator = if capabilities.simulator_management {
+            Some(Arc::new(SimulatorController::new().await?))
+        } else {
+            None
+        };
+        
+        // Project templates are validated here, so a broken override is
+        // reported once rather than on every generation
+        let code_generator = if capabilities.code_generation {
+            let project_root = std::path::Path::new(".");
+            let framework = framework_compatibility(project_root, &config.axiom_framework).await;
+            AxiomCodeGenerator::for_project(project_root).await?.with_framework_compatibility(framework)
+        } else {
+            AxiomCodeGenerator::new().await?
+        };
+        let mut artifacts = config.artifacts.clone();
+        artifacts.retention.insert(ArtifactKind::Screenshot, config.screenshot_storage.retention.clone());
+        let artifact_store = Arc::new(ArtifactStore::new(artifacts));
+        let screenshot_store = Arc::new(
+            ScreenshotStore::new(config.screenshot_storage.clone()).with_artifact_store(Arc::clone(&artifact_store)),
+        );
+        // The app reports its metrics over the intelligence connection
+        let metrics_streams = intelligence_client.as_ref().map(|client| {
+            let source: Arc<dyn MetricsSource> = client.clone();
+            Arc::new(MetricsStreams::new(source))
+        });
+        
+        let tool_metrics = Arc::new(ToolMetrics::new(config.tool_metrics.clone()));
+        
         Ok(Self {
             config,
             capabilities,
             state,
+            hot_reload_client,
+            hot_reload_filter,
+            reload_pipeline,
+            reload_memory,
+            intelligence_client,
+            connection_events,
+            simulator,
+            simulator_capture: Arc::new(SimulatorCapture::new(std::env::temp_dir().join(DEFAULT_CAPTURE_DIR_NAME))),
+            artifact_store,
+            screenshot_store,
+            code_generator: Arc::new(code_generator),
+            development_loop: None,
+            metrics_streams,
+            notification_sink: None,
+            locks: Arc::new(ResourceLocks::default()),
+            requirement_analyzer,
+            tool_metrics,
         })
     }
     
+    /// Manage simulators through `controller` instead of `xcrun simctl`
+    pub fn with_simulator_controller(mut self, controller: SimulatorController) -> Self {
+        self.simulator = Some(Arc::new(controller));
+        self
+    }
+    
+    /// Keep recordings in `capture` instead of the temp directory
+    pub fn with_simulator_capture(mut self, capture: SimulatorCapture) -> Self {
+        self.simulator_capture = Arc::new(capture);
+        self
+    }
+    
+    /// Resume development cycles with `development_loop`, which should keep
+    /// its checkpoints in the project's cycle store. Its cycles skip the
+    /// stages this MCP's capabilities leave off, and keep their artifacts in
+    /// this MCP's artifact store.
+    pub fn with_development_loop(mut self, development_loop: AxiomObservabilityLoop) -> Self {
+        let development_loop = development_loop
+            .with_capabilities(self.capabilities.clone())
+            .with_artifact_store(Arc::clone(&self.artifact_store));
+        self.development_loop = Some(Arc::new(development_loop));
+        self
+    }
+    
+    /// Sample streamed metrics from `source` instead of the intelligence server
+    pub fn with_metrics_source(mut self, source: Arc<dyn MetricsSource>) -> Self {
+        self.metrics_streams = Some(Arc::new(MetricsStreams::new(source)));
+        self
+    }
+    
+    /// Send this session's notifications, such as metrics updates, to
+    /// `sink`. Clones share their metrics streams, so a host serving several
+    /// sessions gives each clone its own sink.
+    pub fn with_notification_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.notification_sink = Some(sink);
+        self
+    }
+    
+    /// Give up on a tool call after waiting `timeout` for another call to
+    /// release a simulator, the project or the hot reload channel
+    pub fn with_lock_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.locks = Arc::new(ResourceLocks::new(timeout));
+        self
+    }
+    
+    /// The analyzer `config.requirement_analyzer` selected, to share with
+    /// a development loop
+    pub fn requirement_analyzer(&self) -> Arc<dyn RequirementAnalyzer> {
+        Arc::clone(&self.requirement_analyzer)
+    }
+    
     /// Execute a tool with the MCP
     pub async fn execute_tool(&self, tool: AxiomMCPTool) -> Result<ToolResult> {
-        tracing::debug!("Executing tool: {:?}", std::mem::discriminant(&tool));
+        let arguments = tool.arguments()?;
+        self.call_tool(tool.name(), arguments).await
+    }
+    
+    /// Every tool the enabled capabilities allow, with its description and
+    /// input schema, as the `tools/list` response
+    pub fn list_tools(&self) -> ToolListing {
+        tool_registry().list_where(|tool| self.capabilities.first_disabled(tool.required_capabilities()).is_none())
+    }
+    
+    /// Run a tool by name once its arguments pass the tool's input schema
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<ToolResult> {
+        tracing::debug!("Executing tool: {}", name);
         
         // Update state
         {
@@ -92,44 +380,98 @@ impl AxiomApplicationsObservabilityMCP {
             state.last_activity = Some(chrono::Utc::now());
         }
         
-        // Execute the tool based on type
-        match tool {
-            AxiomMCPTool::GeneratePresentation(spec) => {
-                self.generate_presentation(spec).await
-            },
-            AxiomMCPTool::GenerateContext(spec) => {
-                self.generate_context(spec).await
-            },
-            AxiomMCPTool::GenerateMockClient(spec) => {
-                self.generate_mock_client(spec).await
-            },
-            AxiomMCPTool::ValidateArchitecture => {
-                self.validate_architecture().await
-            },
-            AxiomMCPTool::AnalyzeAppStructure => {
-                self.analyze_app_structure().await
-            },
-            AxiomMCPTool::StreamPerformanceMetrics => {
-                self.stream_performance_metrics().await
-            },
-            AxiomMCPTool::CaptureScreenshotMatrix => {
-                self.capture_screenshot_matrix().await
-            },
-            AxiomMCPTool::CompareVisualStates => {
-                self.compare_visual_states().await
-            },
-            AxiomMCPTool::DetectUIRegressions => {
-                self.detect_ui_regressions().await
-            },
-            AxiomMCPTool::StartDevelopmentSession => {
-                self.start_development_session().await
-            },
-            AxiomMCPTool::ProcessNaturalLanguageRequirement(requirement) => {
-                self.process_natural_language_requirement(requirement).await
-            },
-            AxiomMCPTool::OptimizePerformanceBottlenecks => {
-                self.optimize_performance_bottlenecks().await
+        // Unknown tools are left out so misspelled names cannot grow the metrics
+        if tool_spec(name).is_none() {
+            return tool_registry().call(self, name, arguments).await;
+        }
+        self.tool_metrics.instrument(name, async {
+            let required = tool_spec(name).map(|spec| spec.required_capabilities).unwrap_or_default();
+            if let Some(capability) = self.capabilities.first_disabled(required) {
+                return Err(crate::error::AxiomMCPError::tool_capability_disabled(name, capability));
+            }
+            // Held until the tool returns, so calls touching the same simulator
+            // or project run one after the other
+            let resources = self.resources_for(name, &arguments).await;
+            let _guard = time_stage("acquire resources", self.locks.acquire(name, resources)).await?;
+            tool_registry().call(self, name, arguments).await
+        }).await
+    }
+    
+    /// Calls, durations and failures of each tool since the MCP started
+    pub fn tool_metrics(&self) -> Arc<ToolMetrics> {
+        Arc::clone(&self.tool_metrics)
+    }
+    
+    /// The store screenshots, build logs, test results, measurements and
+    /// reports are kept in, to share with a development loop
+    pub fn artifact_store(&self) -> Arc<ArtifactStore> {
+        Arc::clone(&self.artifact_store)
+    }
+    
+    /// The resources a call of `name` with `arguments` would use. A device
+    /// is resolved to its UDID, so naming it by name or by UDID locks the
+    /// same simulator; arguments the schema will reject lock nothing.
+    async fn resources_for(&self, name: &str, arguments: &Value) -> Vec<Resource> {
+        let Some(spec) = tool_spec(name) else {
+            return Vec::new();
+        };
+        let mut resources = Vec::new();
+        for need in spec.resources {
+            match need {
+                ResourceNeed::Simulator(argument) => {
+                    let Some(selector) = arguments.get(*argument).and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let udid = match self.simulator() {
+                        Ok(simulator) => simulator.resolve(selector).await.map(|device| device.udid).ok(),
+                        Err(_) => None,
+                    };
+                    resources.push(Resource::Simulator(udid.unwrap_or_else(|| selector.to_string())));
+                }
+                ResourceNeed::AllSimulators => resources.push(Resource::AllSimulators),
+                ResourceNeed::Project => {
+                    let root = std::env::current_dir().unwrap_or_else(|_| ".".into());
+                    resources.push(Resource::Project(root));
+                }
+                ResourceNeed::HotReloadChannel => resources.push(Resource::HotReloadChannel),
+            }
+        }
+        resources
+    }
+    
+    /// Answer one JSON-RPC message. Unknown tools and arguments failing
+    /// their schema are protocol errors; a tool that runs and fails is a
+    /// result with `isError` set.
+    pub async fn handle_message(&self, message: &str) -> JsonRpcResponse {
+        let message: Value = match serde_json::from_str(message) {
+            Ok(message) => message,
+            Err(error) => return JsonRpcResponse::failure(Value::Null, JsonRpcError::new(PARSE_ERROR, error.to_string())),
+        };
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let request: JsonRpcRequest = match serde_json::from_value(message) {
+            Ok(request) => request,
+            Err(error) => return JsonRpcResponse::failure(id, JsonRpcError::new(INVALID_REQUEST, error.to_string())),
+        };
+        
+        match request.method.as_str() {
+            "tools/list" => JsonRpcResponse::success(request.id, &self.list_tools()),
+            "tools/call" => {
+                let params: CallToolParams = match serde_json::from_value(request.params) {
+                    Ok(params) => params,
+                    Err(error) => return JsonRpcResponse::failure(
+                        request.id,
+                        JsonRpcError::new(INVALID_PARAMS, format!("Invalid tools/call params: {}", error)),
+                    ),
+                };
+                match self.call_tool(&params.name, params.arguments).await {
+                    Ok(result) => JsonRpcResponse::success(request.id, &CallToolResult::from_result(&result)),
+                    Err(error) => match JsonRpcError::for_tool_call(&error) {
+                        Some(protocol_error) => JsonRpcResponse::failure(request.id, protocol_error),
+                        None => JsonRpcResponse::success(request.id, &CallToolResult::from_error(&error)),
+                    },
+                }
             },
+            method => JsonRpcResponse::failure(request.id, JsonRpcError::new(METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
         }
     }
     
@@ -144,147 +486,159 @@ impl AxiomApplicationsObservabilityMCP {
         }
     }
     
+    /// Connection state changes of the hot reload and intelligence servers,
+    /// for hosts that forward them to clients as notifications
+    pub fn subscribe_connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.connection_events.subscribe()
+    }
+    
+    /// Development cycles starting to wait at an approval gate, for hosts
+    /// that forward them to clients as notifications; `None` without a loop
+    /// that has gates
+    pub fn subscribe_approval_requests(&self) -> Option<broadcast::Receiver<ApprovalRequest>> {
+        self.approval_gates().map(|gates| gates.subscribe())
+    }
+    
+    fn approval_gates(&self) -> Option<Arc<ApprovalGates>> {
+        self.development_loop.as_ref().and_then(|development_loop| development_loop.approval_gates())
+    }
+    
+    /// Development cycles starting to wait for answers about their
+    /// requirement; `None` without a loop that asks
+    pub fn subscribe_clarification_requests(&self) -> Option<broadcast::Receiver<ClarificationRequest>> {
+        self.requirement_clarifier().map(|clarifier| clarifier.subscribe())
+    }
+    
+    fn requirement_clarifier(&self) -> Option<Arc<RequirementClarifier>> {
+        self.development_loop.as_ref().and_then(|development_loop| development_loop.requirement_clarifier())
+    }
+    
+    /// Probe each subsystem its capability enables
+    pub async fn system_status(&self) -> SystemStatus {
+        let mut checker = SystemStatusChecker::new()
+            .with_probe(Arc::new(TemplateProbe::new(self.code_generator.templates())))
+            .with_probe(Arc::new(StorageProbe::new(self.screenshot_store.config().dir.clone())));
+        if let Some(client) = &self.hot_reload_client {
+            checker = checker.with_probe(Arc::new(ConnectionProbe::hot_reload(client.connection_status())));
+        }
+        if let Some(client) = &self.intelligence_client {
+            checker = checker.with_probe(Arc::new(
+                ConnectionProbe::intelligence(client.connection_status()).with_protocol(client.server_protocol()),
+            ));
+        }
+        if let Some(simulator) = &self.simulator {
+            checker = checker.with_probe(Arc::new(SimulatorProbe::new(Arc::clone(simulator))));
+        }
+        checker.check(&self.capabilities).await.with_locks(self.locks.holders())
+    }
+    
+    pub fn hot_reload_client(&self) -> Option<Arc<HotReloadClient>> {
+        self.hot_reload_client.clone()
+    }
+    
+    /// Report a change from the file watcher; changes the hot reload
+    /// filters reject are counted and dropped
+    pub fn file_changed(&self, change: FileChange) -> Result<()> {
+        match &self.reload_pipeline {
+            Some(pipeline) => pipeline.file_changed(change),
+            None => Err(crate::error::AxiomMCPError::capability_disabled("hot_reload_integration")),
+        }
+    }
+    
+    pub fn hot_reload_filter(&self) -> Arc<HotReloadFilter> {
+        Arc::clone(&self.hot_reload_filter)
+    }
+    
+    /// Components of the watched Swift files, which decide how far a reload reaches
+    pub fn component_index(&self) -> Option<Arc<ComponentIndex>> {
+        self.reload_pipeline.as_ref().map(|pipeline| Arc::clone(pipeline.components()))
+    }
+    
+    pub fn intelligence_client(&self) -> Option<Arc<IntelligenceClient>> {
+        self.intelligence_client.clone()
+    }
+    
     // Tool implementations
     
     async fn generate_presentation(&self, spec: crate::types::PresentationSpec) -> Result<ToolResult> {
         if !self.capabilities.code_generation {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Code generation capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
         }
         
         // Validate input specification
         if spec.name.is_empty() {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Presentation name cannot be empty".to_string()
+            return Err(crate::error::AxiomMCPError::validation(
+                "code_generation", "generate presentation", "Presentation name cannot be empty"
             ));
         }
         
         if spec.context_binding.is_empty() {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Context binding cannot be empty".to_string()
+            return Err(crate::error::AxiomMCPError::validation(
+                "code_generation", "generate presentation", "Context binding cannot be empty"
             ));
         }
         
         if spec.performance_requirements.max_render_time_ms < 0.0 {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Max render time cannot be negative".to_string()
+            return Err(crate::error::AxiomMCPError::validation(
+                "code_generation", "generate presentation", "Max render time cannot be negative"
             ));
         }
         
         if spec.performance_requirements.max_memory_mb < 0.0 {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Max memory cannot be negative".to_string()
+            return Err(crate::error::AxiomMCPError::validation(
+                "code_generation", "generate presentation", "Max memory cannot be negative"
             ));
         }
         
-        // Simulate presentation generation
-        let generated_code = format!(
-            r#"import SwiftUI
-
-struct {} : View {{
-    @EnvironmentObject var context: {}
-    
-    var body: some View {{
-        VStack {{
-            {}
-        }}
-        .navigationTitle("{}")
-    }}
-}}"#,
-            spec.name,
-            spec.context_binding,
-            spec.ui_components.join("\n            "),
-            spec.name.replace("View", "")
-        );
-        
-        Ok(ToolResult::GeneratedCode(crate::types::GeneratedCode {
-            generated_code,
-            validation_passed: true,
-            performance_score: 85.0,
-            compliance_score: 95.0,
-        }))
+        Ok(ToolResult::GeneratedCode(self.code_generator.generate_presentation(spec).await?))
     }
     
     async fn generate_context(&self, spec: crate::types::ContextSpec) -> Result<ToolResult> {
         if !self.capabilities.code_generation {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Code generation capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
         }
         
-        let properties = spec.state_properties.iter()
-            .map(|prop| format!("    @Published var {}: {} = {}", 
-                prop.name, 
-                prop.property_type, 
-                prop.default_value.as_deref().unwrap_or("nil")))
-            .collect::<Vec<_>>()
-            .join("\n");
+        Ok(ToolResult::GeneratedCode(self.code_generator.generate_context(spec).await?))
+    }
+    
+    async fn generate_mock_client(&self, spec: crate::types::ClientSpec) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
         
-        let generated_code = format!(
-            r#"import SwiftUI
-
-@MainActor
-class {}: AxiomClientObservingContext {{
-{}
-    
-    private let client: {}
-    
-    init(client: {}) {{
-        self.client = client
-        super.init()
-    }}
-}}"#,
-            spec.name,
-            properties,
-            spec.client_binding,
-            spec.client_binding
-        );
+        Ok(ToolResult::GeneratedCode(self.code_generator.generate_mock_client(spec).await?))
+    }
+    
+    async fn generate_persistence(&self, spec: crate::code_generation::PersistenceSpec) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
         
-        Ok(ToolResult::GeneratedCode(crate::types::GeneratedCode {
-            generated_code,
-            validation_passed: true,
-            performance_score: 90.0,
-            compliance_score: 98.0,
-        }))
+        Ok(ToolResult::GeneratedCode(self.code_generator.generate_persistence(spec).await?))
     }
     
-    async fn generate_mock_client(&self, spec: crate::types::ClientSpec) -> Result<ToolResult> {
+    async fn generate_feature(&self, spec: crate::feature_generation::FeatureSpec) -> Result<ToolResult> {
         if !self.capabilities.code_generation {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Code generation capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
         }
         
-        let actions = spec.actions.iter()
-            .map(|action| format!("    {} func {}({}) -> {} {{
-        // Mock implementation
-        return {}
-    }}", 
-                if action.is_async { "async" } else { "" },
-                action.name,
-                action.parameters.join(", "),
-                action.return_type,
-                if action.return_type == "Void" { "()" } else { "/* mock value */" }))
-            .collect::<Vec<_>>()
-            .join("\n\n");
-        
-        let generated_code = format!(
-            r#"import Foundation
-
-actor {}: AxiomClient {{
-{}
-}}"#,
-            spec.name,
-            actions
-        );
+        Ok(ToolResult::GeneratedFeature(self.code_generator.generate_feature(spec).await?))
+    }
+    
+    async fn list_code_generation_templates(&self) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
         
-        Ok(ToolResult::GeneratedCode(crate::types::GeneratedCode {
-            generated_code,
-            validation_passed: true,
-            performance_score: 88.0,
-            compliance_score: 96.0,
-        }))
+        Ok(ToolResult::CodeGenerationTemplates(self.code_generator.templates()))
+    }
+    
+    async fn check_framework_compatibility(&self) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
+        
+        Ok(ToolResult::FrameworkCompatibility(self.code_generator.framework_compatibility().clone()))
     }
     
     async fn validate_architecture(&self) -> Result<ToolResult> {
@@ -299,14 +653,15 @@ actor {}: AxiomClient {{
             performance_score: 89.0,
             issues: vec![],
             recommendations: vec!["Consider adding more comprehensive error handling".to_string()],
+            syntax_errors: vec![],
+            pattern_warnings: vec![],
+            syntax_checked: false,
         }))
     }
     
     async fn analyze_app_structure(&self) -> Result<ToolResult> {
         if !self.capabilities.intelligence_analysis {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Intelligence analysis capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("intelligence_analysis"));
         }
         
         // Simulate app structure analysis
@@ -324,90 +679,123 @@ actor {}: AxiomClient {{
         }))
     }
     
-    async fn stream_performance_metrics(&self) -> Result<ToolResult> {
+    fn metrics_streams(&self) -> Result<&Arc<MetricsStreams>> {
         if !self.capabilities.performance_monitoring {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Performance monitoring capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("performance_monitoring"));
+        }
+        self.metrics_streams.as_ref().ok_or_else(|| {
+            crate::error::AxiomMCPError::configuration(
+                "intelligence_analysis",
+                "Metrics are sampled over the intelligence connection, which is not enabled",
+            )
+        })
+    }
+    
+    async fn start_metrics_stream(&self, request: MetricsStreamRequest) -> Result<ToolResult> {
+        let streams = self.metrics_streams()?;
+        let sink = self.notification_sink.clone().ok_or_else(|| {
+            crate::error::AxiomMCPError::configuration("notification_sink", "This session cannot receive notifications")
+        })?;
+        Ok(ToolResult::MetricsStreamStarted(streams.start(&request, sink)?))
+    }
+    
+    async fn stop_metrics_stream(&self, stream_id: String) -> Result<ToolResult> {
+        Ok(ToolResult::MetricsStreamStopped(self.metrics_streams()?.stop(&stream_id)?))
+    }
+    
+    async fn evaluate_performance(&self, metrics: CapturedMetrics) -> Result<ToolResult> {
+        if !self.capabilities.performance_monitoring {
+            return Err(crate::error::AxiomMCPError::capability_disabled("performance_monitoring"));
         }
         
-        // Simulate performance metrics streaming
-        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        let thresholds = PerformanceThresholds::for_project(std::path::Path::new("."), &self.config.performance_thresholds).await?;
+        Ok(ToolResult::PerformanceVerdict(thresholds.evaluate(&metrics)))
+    }
+    
+    async fn measure_app_performance(&self, request: MeasureAppPerformanceRequest) -> Result<ToolResult> {
+        if !self.capabilities.performance_monitoring {
+            return Err(crate::error::AxiomMCPError::capability_disabled("performance_monitoring"));
+        }
+        // Frame rate needs the app's intelligence connection; without it
+        // only launch times and memory are measured
+        let mut meter = AppPerformanceMeter::new(Arc::clone(self.simulator()?));
+        if let Some(client) = &self.intelligence_client {
+            let source: Arc<dyn FrameRateSource> = client.clone();
+            meter = meter.with_frame_rate_source(source);
+        }
+        
+        let thresholds = PerformanceThresholds::for_project(std::path::Path::new("."), &self.config.performance_thresholds).await?;
+        let measurement = meter.measure(&request, &thresholds).await?;
+        Ok(ToolResult::AppPerformance(measurement))
+    }
+    
+    async fn get_performance_trends(&self, options: TrendOptions) -> Result<ToolResult> {
+        if !self.capabilities.performance_monitoring {
+            return Err(crate::error::AxiomMCPError::capability_disabled("performance_monitoring"));
+        }
         
-        Ok(ToolResult::PerformanceStream("performance_stream_123".to_string()))
+        let snapshots = PerformanceHistory::for_project(std::path::Path::new(".")).snapshots().await?;
+        Ok(ToolResult::PerformanceTrends(PerformanceTrends::from_snapshots(&snapshots, options)))
     }
     
-    async fn capture_screenshot_matrix(&self) -> Result<ToolResult> {
+    async fn capture_screenshot_matrix(&self, request: ScreenshotMatrixRequest) -> Result<ToolResult> {
         if !self.capabilities.visual_analysis {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Visual analysis capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("visual_analysis"));
+        }
+        let matrix = request.matrix()?;
+        let configurations = matrix.configurations()?;
+        if let Some(script) = &request.pre_capture_script {
+            UiScript::parse(script)?;
         }
         
         // Simulate screenshot capture
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
         
-        // Generate multiple device configurations as expected by tests
-        let devices = vec![
-            ("iPhone 15 Pro", 393.0, 852.0, 3.0),
-            ("iPad Pro", 1024.0, 1366.0, 2.0),
-            ("iPhone 15", 393.0, 852.0, 3.0),
-            ("iPad Air", 820.0, 1180.0, 2.0),
-        ];
-        
-        let orientations = vec!["portrait", "landscape"];
-        let color_schemes = vec!["light", "dark"];
-        
-        let mut screenshots = Vec::new();
-        for (device_name, width, height, scale) in devices {
-            for orientation in &orientations {
-                for color_scheme in &color_schemes {
-                    screenshots.push(crate::types::Screenshot {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        client_id: "test_client".to_string(),
-                        configuration: crate::types::ScreenshotConfiguration {
-                            device_type: device_name.to_string(),
-                            screen_size: crate::types::ScreenSize { width, height },
-                            orientation: orientation.to_string(),
-                            scale,
-                            color_scheme: color_scheme.to_string(),
-                            capture_mode: "full_screen".to_string(),
-                        },
-                        image_data: vec![1, 2, 3, 4], // Mock image data
-                        metadata: crate::types::ScreenshotMetadata {
-                            timestamp: chrono::Utc::now(),
-                            device_info: crate::types::DeviceInfo {
-                                model: device_name.to_string(),
-                                screen_size: crate::types::ScreenSize { width, height },
-                                orientation: orientation.to_string(),
-                            },
-                            app_state: crate::types::AppState {
-                                view_hierarchy: "ContentView".to_string(),
-                                active_context: "MainContext".to_string(),
-                            },
-                        },
-                    });
-                }
-            }
-        }
+        let screenshots: Vec<crate::types::Screenshot> = configurations
+            .into_iter()
+            .map(|configuration| crate::types::Screenshot {
+                id: uuid::Uuid::new_v4().to_string(),
+                client_id: "test_client".to_string(),
+                image_data: vec![1, 2, 3, 4], // Mock image data
+                image: None,
+                metadata: crate::types::ScreenshotMetadata {
+                    timestamp: chrono::Utc::now(),
+                    device_info: crate::types::DeviceInfo {
+                        model: configuration.device_type.clone(),
+                        screen_size: configuration.screen_size.clone(),
+                        orientation: configuration.orientation.clone(),
+                        udid: None,
+                    },
+                    app_state: crate::types::AppState {
+                        view_hierarchy: "ContentView".to_string(),
+                        active_context: "MainContext".to_string(),
+                        view_tree: None,
+                    },
+                    overrides: configuration.overrides(),
+                },
+                configuration,
+            })
+            .collect();
         
         let screenshot_count = screenshots.len();
+        let consistency = crate::screenshot_consistency::consistency_report(&screenshots, &[]);
         
         Ok(ToolResult::ScreenshotMatrix(crate::types::ScreenshotMatrix {
             screenshots,
             analysis: crate::types::ScreenshotAnalysis {
                 total_screenshots: screenshot_count,
-                consistency_score: 95.0,
+                consistency_score: consistency.score,
                 detected_issues: vec![],
+                layout_issues: Vec::new(),
+                consistency_pairs: consistency.pairs,
+                warnings: matrix.warnings(),
             },
         }))
     }
     
     async fn compare_visual_states(&self) -> Result<ToolResult> {
         if !self.capabilities.visual_analysis {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Visual analysis capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("visual_analysis"));
         }
         
         // Simulate visual comparison
@@ -420,22 +808,73 @@ actor {}: AxiomClient {{
         }))
     }
     
-    async fn detect_ui_regressions(&self) -> Result<ToolResult> {
+    async fn detect_ui_regressions(&self, request: RegressionCheckRequest) -> Result<ToolResult> {
         if !self.capabilities.visual_analysis {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Visual analysis capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("visual_analysis"));
+        }
+        
+        let checker = VisualRegressionChecker::new(self.config.visual_regression.clone())
+            .with_screenshot_store(Arc::clone(&self.screenshot_store));
+        let report = checker.run(&request).await?;
+        Ok(ToolResult::RegressionReport(report))
+    }
+    
+    async fn prune_screenshots(&self) -> Result<ToolResult> {
+        if !self.capabilities.visual_analysis {
+            return Err(crate::error::AxiomMCPError::capability_disabled("visual_analysis"));
         }
         
-        // Simulate regression detection
-        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        let store = Arc::clone(&self.screenshot_store);
+        let report = tokio::task::spawn_blocking(move || store.prune(std::time::SystemTime::now())).await??;
+        Ok(ToolResult::ScreenshotsPruned(report))
+    }
+    
+    async fn list_artifacts(&self, query: ArtifactQuery) -> Result<ToolResult> {
+        Ok(ToolResult::Artifacts(self.artifact_store.list(&query)?))
+    }
+    
+    async fn get_artifact(&self, artifact_id: String) -> Result<ToolResult> {
+        Ok(ToolResult::Artifact(self.artifact_store.require(&artifact_id)?))
+    }
+    
+    /// Every kind past its retention policy, keeping the baselines' screenshots
+    async fn prune_artifacts(&self) -> Result<ToolResult> {
+        let store = Arc::clone(&self.artifact_store);
+        let baselines = self.screenshot_store.baselines()?.into_values().collect();
+        let report = tokio::task::spawn_blocking(move || store.prune(std::time::SystemTime::now(), &baselines)).await??;
+        Ok(ToolResult::ArtifactsPruned(report))
+    }
+    
+    async fn audit_accessibility(&self, request: AccessibilityAuditRequest) -> Result<ToolResult> {
+        if !self.capabilities.visual_analysis {
+            return Err(crate::error::AxiomMCPError::capability_disabled("visual_analysis"));
+        }
         
-        Ok(ToolResult::RegressionReport(crate::types::RegressionReport {
-            total_comparisons: 10,
-            regressions_detected: 0,
-            false_positive_rate: 0.2,
-            confidence_score: 96.0,
-        }))
+        let tree = match (request.element_tree, &self.intelligence_client) {
+            (Some(tree), _) => tree,
+            (None, Some(client)) => client.accessibility_tree(ACCESSIBILITY_TREE_TIMEOUT).await?,
+            (None, None) => {
+                return Err(crate::error::AxiomMCPError::configuration(
+                    "intelligence_analysis",
+                    "No element tree given and intelligence analysis is not enabled to request one",
+                ));
+            }
+        };
+        
+        let engine = VisualIntelligenceEngine::new().await?;
+        let report = engine.audit_accessibility(&request.screenshot, &tree).await?;
+        Ok(ToolResult::AccessibilityReport(report))
+    }
+    
+    async fn assert_screen_contains(&self, request: ScreenTextAssertionRequest) -> Result<ToolResult> {
+        if !self.capabilities.visual_analysis {
+            return Err(crate::error::AxiomMCPError::capability_disabled("visual_analysis"));
+        }
+        
+        let engine = VisualIntelligenceEngine::new().await?;
+        let crop_dir = std::env::temp_dir().join(DEFAULT_SCREEN_TEXT_DIR_NAME);
+        let report = engine.assert_screen_contains(&request, &crop_dir).await?;
+        Ok(ToolResult::ScreenText(report))
     }
     
     async fn start_development_session(&self) -> Result<ToolResult> {
@@ -457,41 +896,115 @@ actor {}: AxiomClient {{
     
     async fn process_natural_language_requirement(&self, requirement: String) -> Result<ToolResult> {
         if !self.capabilities.intelligence_analysis {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Intelligence analysis capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("intelligence_analysis"));
         }
         
-        // Simulate requirement analysis
-        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        Ok(ToolResult::RequirementAnalysis(self.requirement_analyzer.analyze(&requirement).await?))
+    }
+    
+    async fn resume_development_cycle(&self, cycle_id: String) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
         
-        let complexity = if requirement.len() < 50 {
-            "Simple"
-        } else if requirement.len() < 150 {
-            "Medium"
-        } else {
-            "Complex"
-        };
+        let development_loop = self.development_loop.as_ref().ok_or_else(|| {
+            crate::error::AxiomMCPError::configuration("development_loop", "No development loop is configured to resume cycles with")
+        })?;
+        Ok(ToolResult::CompleteLoopResult(Box::new(development_loop.resume_development_cycle(&cycle_id).await?)))
+    }
+    
+    async fn list_development_cycles(&self) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
         
-        Ok(ToolResult::RequirementAnalysis(crate::types::RequirementAnalysis {
-            requirement_id: uuid::Uuid::new_v4().to_string(),
-            analysis_timestamp: chrono::Utc::now(),
-            confidence_score: 87.5,
-            complexity_estimate: complexity.to_string(),
-            recommended_approach: "Axiom Context-Presentation-Client pattern".to_string(),
-            estimated_components: vec![
-                "Context for state management".to_string(),
-                "Presentation for UI".to_string(),
-                "Client for data operations".to_string(),
-            ],
-        }))
+        let cycles = self.cycle_store().list().await?;
+        Ok(ToolResult::DevelopmentCycles(cycles))
+    }
+    
+    async fn export_cycle_report(&self, request: CycleReportRequest) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
+        
+        let checkpoint = self.cycle_store().load(&request.cycle_id).await?;
+        let thresholds = PerformanceThresholds::for_project(std::path::Path::new("."), &self.config.performance_thresholds).await?;
+        let mut exporter = CycleReportExporter::new(self.config.cycle_reports.clone())
+            .with_thresholds(thresholds)
+            .with_artifact_store(Arc::clone(&self.artifact_store));
+        if self.capabilities.performance_monitoring {
+            exporter = exporter.with_reload_memory(self.reload_memory.trend());
+        }
+        exporter = exporter.with_tool_metrics(self.tool_metrics.report());
+        Ok(ToolResult::CycleReport(exporter.export(&checkpoint, request.html).await?))
+    }
+    
+    async fn get_traceability_matrix(&self, cycle_id: String) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
+        
+        let checkpoint = self.cycle_store().load(&cycle_id).await?;
+        let matrix = CycleReport::from_checkpoint(&checkpoint)?.traceability().ok_or_else(|| {
+            crate::error::AxiomMCPError::validation("traceability", "get traceability matrix", format!(
+                "Cycle {} has not generated its implementation yet", cycle_id
+            ))
+        })?;
+        Ok(ToolResult::TraceabilityMatrix(matrix))
+    }
+    
+    /// The development loop's checkpoints, or the project's when there is no loop
+    fn cycle_store(&self) -> CycleStore {
+        match self.development_loop.as_ref().and_then(|development_loop| development_loop.cycle_store()) {
+            Some(store) => store.clone(),
+            None => CycleStore::for_project(std::path::Path::new(".")),
+        }
+    }
+    
+    async fn approve_stage(&self, cycle_id: String) -> Result<ToolResult> {
+        let gates = self.approval_gates().ok_or_else(|| {
+            crate::error::AxiomMCPError::configuration("development_loop", "The development loop has no approval gates")
+        })?;
+        Ok(ToolResult::StageReview(gates.approve(&cycle_id)?))
+    }
+    
+    async fn reject_stage(&self, request: RejectStageRequest) -> Result<ToolResult> {
+        let gates = self.approval_gates().ok_or_else(|| {
+            crate::error::AxiomMCPError::configuration("development_loop", "The development loop has no approval gates")
+        })?;
+        Ok(ToolResult::StageReview(gates.reject(&request.cycle_id, request.feedback)?))
+    }
+    
+    async fn provide_requirement_answers(&self, answers: RequirementAnswers) -> Result<ToolResult> {
+        let clarifier = self.requirement_clarifier().ok_or_else(|| {
+            crate::error::AxiomMCPError::configuration("development_loop", "The development loop does not ask clarifying questions")
+        })?;
+        Ok(ToolResult::RequirementAnswers(clarifier.provide_answers(answers)?))
+    }
+    
+    async fn get_cycle_diff(&self, cycle_id: String) -> Result<ToolResult> {
+        let workflow = self.cycle_workflow()?;
+        Ok(ToolResult::CycleDiff(workflow.get_cycle_diff(&cycle_id).await?))
+    }
+    
+    async fn abandon_cycle(&self, cycle_id: String) -> Result<ToolResult> {
+        let workflow = self.cycle_workflow()?;
+        Ok(ToolResult::AbandonedCycle(workflow.abandon_cycle(&cycle_id).await?))
+    }
+    
+    fn cycle_workflow(&self) -> Result<Arc<AxiomObservabilityWorkflow>> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
+        
+        self.development_loop.as_ref().map(|development_loop| development_loop.workflow()).ok_or_else(|| {
+            crate::error::AxiomMCPError::configuration("development_loop", "No development loop is configured with cycle branches")
+        })
     }
     
     async fn optimize_performance_bottlenecks(&self) -> Result<ToolResult> {
         if !self.capabilities.performance_monitoring {
-            return Err(crate::error::AxiomMCPError::ValidationError(
-                "Performance monitoring capability not enabled".to_string()
-            ));
+            return Err(crate::error::AxiomMCPError::capability_disabled("performance_monitoring"));
         }
         
         // Simulate bottleneck analysis
@@ -510,6 +1023,227 @@ actor {}: AxiomClient {{
             },
         ]))
     }
+    
+    async fn get_connection_status(&self) -> Result<ToolResult> {
+        let mut connections = Vec::new();
+        if let Some(client) = &self.hot_reload_client {
+            connections.push(client.connection_status());
+        }
+        if let Some(client) = &self.intelligence_client {
+            connections.push(client.connection_status());
+        }
+        
+        Ok(ToolResult::ConnectionStatus(connections))
+    }
+    
+    async fn get_system_status(&self) -> Result<ToolResult> {
+        Ok(ToolResult::SystemStatus(self.system_status().await))
+    }
+    
+    async fn set_hot_reload_filters(&self, filters: HotReloadFilters) -> Result<ToolResult> {
+        if !self.capabilities.hot_reload_integration {
+            return Err(crate::error::AxiomMCPError::capability_disabled("hot_reload_integration"));
+        }
+        
+        self.hot_reload_filter.set_filters(filters)?;
+        Ok(ToolResult::HotReloadStats(self.hot_reload_stats()))
+    }
+    
+    async fn get_hot_reload_stats(&self) -> Result<ToolResult> {
+        if !self.capabilities.hot_reload_integration {
+            return Err(crate::error::AxiomMCPError::capability_disabled("hot_reload_integration"));
+        }
+        
+        Ok(ToolResult::HotReloadStats(self.hot_reload_stats()))
+    }
+    
+    async fn validate_hot_reload_connection(&self) -> Result<ToolResult> {
+        let client = self.hot_reload_client.as_ref()
+            .ok_or_else(|| crate::error::AxiomMCPError::capability_disabled("hot_reload_integration"))?;
+        
+        Ok(ToolResult::HotReloadHandshake(client.validate_connection(HANDSHAKE_TIMEOUT).await?))
+    }
+    
+    async fn clear_generation_cache(&self) -> Result<ToolResult> {
+        if !self.capabilities.code_generation {
+            return Err(crate::error::AxiomMCPError::capability_disabled("code_generation"));
+        }
+        
+        Ok(ToolResult::GenerationCacheCleared(self.code_generator.clear_cache()))
+    }
+    
+    async fn get_tool_metrics(&self) -> Result<ToolResult> {
+        Ok(ToolResult::ToolMetrics(self.tool_metrics.report()))
+    }
+    
+    async fn get_reload_memory_trend(&self) -> Result<ToolResult> {
+        if !self.capabilities.performance_monitoring {
+            return Err(crate::error::AxiomMCPError::capability_disabled("performance_monitoring"));
+        }
+        
+        Ok(ToolResult::ReloadMemoryTrend(self.reload_memory.trend()))
+    }
+    
+    fn simulator(&self) -> Result<&Arc<SimulatorController>> {
+        match &self.simulator {
+            Some(simulator) if self.capabilities.simulator_management => Ok(simulator),
+            _ => Err(crate::error::AxiomMCPError::capability_disabled("simulator_management")),
+        }
+    }
+    
+    async fn list_simulators(&self) -> Result<ToolResult> {
+        let devices = self.simulator()?.list_devices().await?;
+        Ok(ToolResult::Simulators(devices))
+    }
+    
+    async fn boot_simulator(&self, device: String) -> Result<ToolResult> {
+        let result = self.simulator()?.boot(&device).await?;
+        Ok(ToolResult::SimulatorAction(result))
+    }
+    
+    async fn shutdown_simulator(&self, device: String) -> Result<ToolResult> {
+        let result = self.simulator()?.shutdown(&device).await?;
+        Ok(ToolResult::SimulatorAction(result))
+    }
+    
+    async fn erase_simulator(&self, device: String) -> Result<ToolResult> {
+        let result = self.simulator()?.erase(&device).await?;
+        Ok(ToolResult::SimulatorAction(result))
+    }
+    
+    async fn install_app(&self, request: InstallAppRequest) -> Result<ToolResult> {
+        let result = self.simulator()?.install_app(&request).await?;
+        Ok(ToolResult::AppInstalled(result))
+    }
+    
+    async fn launch_app(&self, request: LaunchAppRequest) -> Result<ToolResult> {
+        let result = self.simulator()?.launch_app(&request).await?;
+        // Later hot reloads are measured against the app just launched
+        if self.capabilities.performance_monitoring {
+            self.reload_memory.track(result.pid);
+        }
+        Ok(ToolResult::AppLaunched(result))
+    }
+    
+    async fn reset_simulator_state(&self, request: ResetSimulatorStateRequest) -> Result<ToolResult> {
+        let result = self.simulator()?.reset_state(&request).await?;
+        Ok(ToolResult::SimulatorStateReset(result))
+    }
+    
+    async fn start_recording(&self, request: StartRecordingRequest) -> Result<ToolResult> {
+        let handle = self.simulator_capture.start_recording(self.simulator()?, &request).await?;
+        Ok(ToolResult::RecordingStarted(handle))
+    }
+    
+    async fn stop_recording(&self, recording_id: String) -> Result<ToolResult> {
+        let recording = self.simulator_capture.stop_recording(&recording_id).await?;
+        Ok(ToolResult::Recording(recording))
+    }
+    
+    async fn run_ui_script(&self, request: RunUiScriptRequest) -> Result<ToolResult> {
+        let script = UiScript::parse(&request.script)?;
+        let locale = request.locale.as_deref().map(SimulatorLocale::parse).transpose()?;
+        let simulator = self.simulator()?;
+        let Some(intelligence) = &self.intelligence_client else {
+            return Err(crate::error::AxiomMCPError::capability_disabled("intelligence_analysis"));
+        };
+        let device = simulator.resolve(&request.device).await?;
+        let driver = IntelligenceUiDriver::new(Arc::clone(intelligence), Arc::clone(simulator));
+        let mut runner = UiScriptRunner::new(Arc::new(driver)).with_store(Arc::clone(&self.screenshot_store));
+        if let Some(locale) = locale {
+            simulator.set_locale(&device.udid, &locale).await?;
+            runner = runner.with_locale(locale);
+        }
+        Ok(ToolResult::UiScript(runner.run(&device.udid, &script).await))
+    }
+    
+    fn hot_reload_stats(&self) -> crate::hot_reload::HotReloadStats {
+        match &self.reload_pipeline {
+            Some(pipeline) => pipeline.stats(),
+            None => self.hot_reload_filter.stats(),
+        }
+    }
+}
+
+/// The framework versions under `project_root` the generator targets. An
+/// unreadable `Package.resolved` is a warning rather than a startup failure,
+/// and leaves generation targeting the latest APIs
+async fn framework_compatibility(project_root: &std::path::Path, config: &AxiomFrameworkConfig) -> FrameworkCompatibility {
+    let compatibility = match detect_framework_versions(project_root, config).await {
+        Ok(frameworks) => FrameworkCompatibility::for_frameworks(frameworks),
+        Err(error) => {
+            let mut compatibility = FrameworkCompatibility::for_frameworks(Vec::new());
+            compatibility.warnings.push(format!("Cannot detect the Axiom framework versions: {}", error));
+            compatibility
+        }
+    };
+    for downgrade in &compatibility.downgrades {
+        tracing::warn!("{}", downgrade.warning());
+    }
+    compatibility
+}
+
+/// Every tool's handler, registered against its spec in `TOOL_SPECS`
+fn tool_registry() -> &'static ToolRegistry<AxiomApplicationsObservabilityMCP> {
+    static TOOLS: OnceLock<ToolRegistry<AxiomApplicationsObservabilityMCP>> = OnceLock::new();
+    TOOLS.get_or_init(|| {
+        ToolRegistry::<AxiomApplicationsObservabilityMCP>::new()
+            .with_tool("generate_presentation", |mcp, spec| Box::pin(mcp.generate_presentation(spec)))
+            .with_tool("generate_context", |mcp, spec| Box::pin(mcp.generate_context(spec)))
+            .with_tool("generate_mock_client", |mcp, spec| Box::pin(mcp.generate_mock_client(spec)))
+            .with_tool("generate_persistence", |mcp, spec| Box::pin(mcp.generate_persistence(spec)))
+            .with_tool("generate_feature", |mcp, spec| Box::pin(mcp.generate_feature(spec)))
+            .with_tool("list_code_generation_templates", |mcp, _: NoArguments| Box::pin(mcp.list_code_generation_templates()))
+            .with_tool("check_framework_compatibility", |mcp, _: NoArguments| Box::pin(mcp.check_framework_compatibility()))
+            .with_tool("validate_architecture", |mcp, _: NoArguments| Box::pin(mcp.validate_architecture()))
+            .with_tool("analyze_app_structure", |mcp, _: NoArguments| Box::pin(mcp.analyze_app_structure()))
+            .with_tool("start_metrics_stream", |mcp, request| Box::pin(mcp.start_metrics_stream(request)))
+            .with_tool("stop_metrics_stream", |mcp, arguments: MetricsStreamArguments| Box::pin(mcp.stop_metrics_stream(arguments.stream_id)))
+            .with_tool("evaluate_performance", |mcp, metrics| Box::pin(mcp.evaluate_performance(metrics)))
+            .with_tool("measure_app_performance", |mcp, request| Box::pin(mcp.measure_app_performance(request)))
+            .with_tool("get_performance_trends", |mcp, options| Box::pin(mcp.get_performance_trends(options)))
+            .with_tool("capture_screenshot_matrix", |mcp, request| Box::pin(mcp.capture_screenshot_matrix(request)))
+            .with_tool("compare_visual_states", |mcp, _: NoArguments| Box::pin(mcp.compare_visual_states()))
+            .with_tool("detect_ui_regressions", |mcp, request| Box::pin(mcp.detect_ui_regressions(request)))
+            .with_tool("prune_screenshots", |mcp, _: NoArguments| Box::pin(mcp.prune_screenshots()))
+            .with_tool("list_artifacts", |mcp, query| Box::pin(mcp.list_artifacts(query)))
+            .with_tool("get_artifact", |mcp, arguments: ArtifactArguments| Box::pin(mcp.get_artifact(arguments.artifact_id)))
+            .with_tool("prune_artifacts", |mcp, _: NoArguments| Box::pin(mcp.prune_artifacts()))
+            .with_tool("audit_accessibility", |mcp, request| Box::pin(mcp.audit_accessibility(request)))
+            .with_tool("assert_screen_contains", |mcp, request| Box::pin(mcp.assert_screen_contains(request)))
+            .with_tool("start_development_session", |mcp, _: NoArguments| Box::pin(mcp.start_development_session()))
+            .with_tool("process_natural_language_requirement", |mcp, arguments: RequirementArguments| {
+                Box::pin(mcp.process_natural_language_requirement(arguments.requirement))
+            })
+            .with_tool("resume_development_cycle", |mcp, arguments: CycleArguments| Box::pin(mcp.resume_development_cycle(arguments.cycle_id)))
+            .with_tool("list_development_cycles", |mcp, _: NoArguments| Box::pin(mcp.list_development_cycles()))
+            .with_tool("export_cycle_report", |mcp, request| Box::pin(mcp.export_cycle_report(request)))
+            .with_tool("get_traceability_matrix", |mcp, arguments: CycleArguments| Box::pin(mcp.get_traceability_matrix(arguments.cycle_id)))
+            .with_tool("approve_stage", |mcp, arguments: CycleArguments| Box::pin(mcp.approve_stage(arguments.cycle_id)))
+            .with_tool("reject_stage", |mcp, request| Box::pin(mcp.reject_stage(request)))
+            .with_tool("provide_requirement_answers", |mcp, answers| Box::pin(mcp.provide_requirement_answers(answers)))
+            .with_tool("get_cycle_diff", |mcp, arguments: CycleArguments| Box::pin(mcp.get_cycle_diff(arguments.cycle_id)))
+            .with_tool("abandon_cycle", |mcp, arguments: CycleArguments| Box::pin(mcp.abandon_cycle(arguments.cycle_id)))
+            .with_tool("optimize_performance_bottlenecks", |mcp, _: NoArguments| Box::pin(mcp.optimize_performance_bottlenecks()))
+            .with_tool("get_connection_status", |mcp, _: NoArguments| Box::pin(mcp.get_connection_status()))
+            .with_tool("get_system_status", |mcp, _: NoArguments| Box::pin(mcp.get_system_status()))
+            .with_tool("set_hot_reload_filters", |mcp, filters| Box::pin(mcp.set_hot_reload_filters(filters)))
+            .with_tool("get_hot_reload_stats", |mcp, _: NoArguments| Box::pin(mcp.get_hot_reload_stats()))
+            .with_tool("validate_hot_reload_connection", |mcp, _: NoArguments| Box::pin(mcp.validate_hot_reload_connection()))
+            .with_tool("clear_generation_cache", |mcp, _: NoArguments| Box::pin(mcp.clear_generation_cache()))
+            .with_tool("get_reload_memory_trend", |mcp, _: NoArguments| Box::pin(mcp.get_reload_memory_trend()))
+            .with_tool("get_tool_metrics", |mcp, _: NoArguments| Box::pin(mcp.get_tool_metrics()))
+            .with_tool("list_simulators", |mcp, _: NoArguments| Box::pin(mcp.list_simulators()))
+            .with_tool("boot_simulator", |mcp, arguments: DeviceArguments| Box::pin(mcp.boot_simulator(arguments.device)))
+            .with_tool("shutdown_simulator", |mcp, arguments: DeviceArguments| Box::pin(mcp.shutdown_simulator(arguments.device)))
+            .with_tool("erase_simulator", |mcp, arguments: DeviceArguments| Box::pin(mcp.erase_simulator(arguments.device)))
+            .with_tool("install_app", |mcp, request| Box::pin(mcp.install_app(request)))
+            .with_tool("launch_app", |mcp, request| Box::pin(mcp.launch_app(request)))
+            .with_tool("reset_simulator_state", |mcp, request| Box::pin(mcp.reset_simulator_state(request)))
+            .with_tool("start_recording", |mcp, request| Box::pin(mcp.start_recording(request)))
+            .with_tool("stop_recording", |mcp, arguments: RecordingArguments| Box::pin(mcp.stop_recording(arguments.recording_id)))
+            .with_tool("run_ui_script", |mcp, request| Box::pin(mcp.run_ui_script(request)))
+    })
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,313 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::intelligence::IntelligenceClient;
+use crate::performance_analysis_integration::{CapturedMetrics, PerformanceMetric};
+use crate::protocol::JsonRpcNotification;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Method of the notifications carrying a stream's updates
+pub const METRICS_NOTIFICATION_METHOD: &str = "axiom/metrics";
+
+/// Shortest time between samples a stream may ask for
+pub const MIN_METRICS_INTERVAL_MS: u64 = 100;
+
+pub const DEFAULT_METRICS_INTERVAL_MS: u64 = 1000;
+
+/// What a stream carries when the request names no metrics; launch times
+/// only change when the app is launched, so they are left to
+/// `measure_app_performance`
+pub const DEFAULT_STREAMED_METRICS: [PerformanceMetric; 4] = [
+    PerformanceMetric::MemoryMb,
+    PerformanceMetric::CpuPercent,
+    PerformanceMetric::FrameRateFps,
+    PerformanceMetric::HitchTimeRatio,
+];
+
+/// How long the app has to answer one sample request
+const SAMPLE_REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Current values of the running app's metrics
+#[async_trait]
+pub trait MetricsSource: Send + Sync {
+    /// Values of those of `metrics` the app can report right now
+    async fn sample(&self, metrics: &[PerformanceMetric]) -> Result<CapturedMetrics>;
+}
+
+#[async_trait]
+impl MetricsSource for IntelligenceClient {
+    async fn sample(&self, metrics: &[PerformanceMetric]) -> Result<CapturedMetrics> {
+        self.performance_metrics(metrics, SAMPLE_REPLY_TIMEOUT).await
+    }
+}
+
+/// Delivers notifications to one client session. A slow transport is
+/// slow to return, and a stream holds back its next update until it does.
+#[async_trait]
+pub trait NotificationSink: Send + Sync + std::fmt::Debug {
+    async fn notify(&self, notification: JsonRpcNotification) -> Result<()>;
+}
+
+/// A bounded channel to the task writing the session's transport
+#[async_trait]
+impl NotificationSink for tokio::sync::mpsc::Sender<JsonRpcNotification> {
+    async fn notify(&self, notification: JsonRpcNotification) -> Result<()> {
+        self.send(notification)
+            .await
+            .map_err(|_| AxiomMCPError::connectivity("metrics_stream", "notify", "The session's notification channel is closed"))
+    }
+}
+
+/// Arguments of `start_metrics_stream`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsStreamRequest {
+    /// Time between samples
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+    /// Metrics to stream; `DEFAULT_STREAMED_METRICS` when empty
+    #[serde(default)]
+    pub metrics: Vec<PerformanceMetric>,
+}
+
+fn default_interval_ms() -> u64 {
+    DEFAULT_METRICS_INTERVAL_MS
+}
+
+impl Default for MetricsStreamRequest {
+    fn default() -> Self {
+        Self { interval_ms: default_interval_ms(), metrics: Vec::new() }
+    }
+}
+
+impl MetricsStreamRequest {
+    fn validate(&self) -> Result<()> {
+        if self.interval_ms < MIN_METRICS_INTERVAL_MS {
+            return Err(AxiomMCPError::validation("metrics_stream", "start stream", format!(
+                "Interval must be at least {}ms, got {}ms", MIN_METRICS_INTERVAL_MS, self.interval_ms
+            )));
+        }
+        Ok(())
+    }
+
+    fn streamed_metrics(&self) -> Vec<PerformanceMetric> {
+        if self.metrics.is_empty() {
+            return DEFAULT_STREAMED_METRICS.to_vec();
+        }
+        let mut metrics = self.metrics.clone();
+        metrics.sort();
+        metrics.dedup();
+        metrics
+    }
+}
+
+/// A started stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsStreamHandle {
+    pub stream_id: String,
+    pub interval_ms: u64,
+    pub metrics: Vec<PerformanceMetric>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Params of an `axiom/metrics` notification. Only metrics whose value
+/// changed since the previous update are included, and a sample that
+/// changed nothing sends no update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsUpdate {
+    pub stream_id: String,
+    /// Counts the stream's updates from 1
+    pub sequence: u64,
+    /// Latest value of each changed metric
+    pub values: CapturedMetrics,
+    /// Change of each since the previous update; a metric reported for the
+    /// first time has none
+    pub deltas: CapturedMetrics,
+    /// Samples this update covers, more than one when the session was still
+    /// taking the previous update as they came in
+    pub samples: u32,
+    pub sampled_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// What a stream sent before it was stopped
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsStreamSummary {
+    pub stream_id: String,
+    pub updates_sent: u64,
+    /// Samples folded into a later update instead of sent on their own
+    pub samples_coalesced: u64,
+    /// Samples the source could not take
+    pub failed_samples: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct StreamCounters {
+    updates_sent: AtomicU64,
+    samples_coalesced: AtomicU64,
+    failed_samples: AtomicU64,
+}
+
+struct RunningStream {
+    handle: MetricsStreamHandle,
+    started: Instant,
+    counters: Arc<StreamCounters>,
+    task: JoinHandle<()>,
+}
+
+/// Metrics subscriptions. Each samples the source on its own interval and
+/// notifies its own sink, so sessions streaming at once do not share
+/// updates or slow each other down.
+pub struct MetricsStreams {
+    source: Arc<dyn MetricsSource>,
+    streams: Mutex<BTreeMap<String, RunningStream>>,
+}
+
+impl std::fmt::Debug for MetricsStreams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsStreams")
+            .field("streams", &self.streams.lock().unwrap().keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MetricsStreams {
+    pub fn new(source: Arc<dyn MetricsSource>) -> Self {
+        Self { source, streams: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Start sampling as `request` asks, notifying `sink` of each change
+    pub fn start(&self, request: &MetricsStreamRequest, sink: Arc<dyn NotificationSink>) -> Result<MetricsStreamHandle> {
+        request.validate()?;
+        let handle = MetricsStreamHandle {
+            stream_id: uuid::Uuid::new_v4().to_string(),
+            interval_ms: request.interval_ms,
+            metrics: request.streamed_metrics(),
+            started_at: chrono::Utc::now(),
+        };
+        let counters = Arc::new(StreamCounters::default());
+        let task = tokio::spawn(stream_metrics(handle.clone(), self.source.clone(), sink, counters.clone()));
+        tracing::info!("Streaming {:?} every {}ms as {}", handle.metrics, handle.interval_ms, handle.stream_id);
+
+        self.streams.lock().unwrap().insert(handle.stream_id.clone(), RunningStream {
+            handle: handle.clone(),
+            started: Instant::now(),
+            counters,
+            task,
+        });
+        Ok(handle)
+    }
+
+    /// Stop a stream; an update still being delivered is dropped
+    pub fn stop(&self, stream_id: &str) -> Result<MetricsStreamSummary> {
+        let stream = self.streams.lock().unwrap().remove(stream_id).ok_or_else(|| {
+            AxiomMCPError::validation("metrics_stream", "stop stream", format!("No metrics stream {}", stream_id))
+        })?;
+        stream.task.abort();
+        Ok(MetricsStreamSummary {
+            stream_id: stream.handle.stream_id,
+            updates_sent: stream.counters.updates_sent.load(Ordering::Relaxed),
+            samples_coalesced: stream.counters.samples_coalesced.load(Ordering::Relaxed),
+            failed_samples: stream.counters.failed_samples.load(Ordering::Relaxed),
+            duration_ms: stream.started.elapsed().as_millis() as u64,
+        })
+    }
+
+    pub fn active(&self) -> Vec<MetricsStreamHandle> {
+        self.streams.lock().unwrap().values().map(|stream| stream.handle.clone()).collect()
+    }
+}
+
+impl Drop for MetricsStreams {
+    fn drop(&mut self) {
+        for stream in self.streams.lock().unwrap().values() {
+            stream.task.abort();
+        }
+    }
+}
+
+type Delivery<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// Sample on every tick and notify `sink` of what changed. While an update
+/// is being delivered, samples are merged into the next one rather than
+/// queued, so a slow session gets fewer updates instead of a growing backlog.
+async fn stream_metrics(
+    handle: MetricsStreamHandle,
+    source: Arc<dyn MetricsSource>,
+    sink: Arc<dyn NotificationSink>,
+    counters: Arc<StreamCounters>,
+) {
+    let mut ticker = tokio::time::interval(Duration::from_millis(handle.interval_ms));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut sent = CapturedMetrics::new();
+    let mut pending: Option<(CapturedMetrics, chrono::DateTime<chrono::Utc>)> = None;
+    let mut samples = 0;
+    let mut sequence = 0;
+    let mut delivery: Option<Delivery<'_>> = None;
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => match source.sample(&handle.metrics).await {
+                Ok(sample) => {
+                    let (latest, sampled_at) = pending.get_or_insert_with(|| (CapturedMetrics::new(), chrono::Utc::now()));
+                    for (metric, value) in sample.iter() {
+                        latest.insert(metric, value);
+                    }
+                    *sampled_at = chrono::Utc::now();
+                    samples += 1;
+                }
+                Err(e) => {
+                    counters.failed_samples.fetch_add(1, Ordering::Relaxed);
+                    tracing::debug!("Metrics stream {} could not sample: {}", handle.stream_id, e);
+                }
+            },
+            delivered = async { delivery.as_mut().expect("only polled while delivering").await }, if delivery.is_some() => {
+                delivery = None;
+                match delivered {
+                    Ok(()) => {
+                        counters.updates_sent.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(e) => tracing::warn!("Metrics stream {} could not deliver an update: {}", handle.stream_id, e),
+                }
+            }
+        }
+
+        if delivery.is_some() {
+            continue;
+        }
+        let Some((latest, sampled_at)) = pending.take() else {
+            continue;
+        };
+        let values: CapturedMetrics = latest.iter().filter(|&(metric, value)| sent.get(metric) != Some(value)).fold(
+            CapturedMetrics::new(),
+            |values, (metric, value)| values.with(metric, value),
+        );
+        let covered = std::mem::take(&mut samples);
+        if values.is_empty() {
+            continue;
+        }
+
+        sequence += 1;
+        counters.samples_coalesced.fetch_add(u64::from(covered - 1), Ordering::Relaxed);
+        let update = MetricsUpdate {
+            stream_id: handle.stream_id.clone(),
+            sequence,
+            deltas: values.difference(&sent),
+            values: values.clone(),
+            samples: covered,
+            sampled_at,
+        };
+        for (metric, value) in values.iter() {
+            sent.insert(metric, value);
+        }
+        match JsonRpcNotification::new(METRICS_NOTIFICATION_METHOD, &update) {
+            Ok(notification) => delivery = Some(sink.notify(notification)),
+            Err(e) => tracing::warn!("Metrics stream {} could not encode an update: {}", handle.stream_id, e),
+        }
+    }
+}
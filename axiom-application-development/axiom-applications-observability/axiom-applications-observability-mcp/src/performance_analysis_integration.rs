@@ -1,13 +1,1111 @@
-use crate::error::Result;
-use std::sync::Arc;
+use crate::error::{AxiomMCPError, Result};
+use crate::hot_reload::{ReloadObserver, ReloadReport};
+use crate::intelligence::IntelligenceClient;
+use crate::simulator::{LaunchAppRequest, SimulatorController, SimulatorDevice};
+use crate::types::PerformanceEstimate;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
+/// Thresholds a project keeps next to its sources, replacing the configured ones
+pub const PROJECT_THRESHOLDS_FILE: &str = ".axiom/performance-thresholds.json";
+
+/// Most terminate-and-launch runs one measurement may ask for
+pub const MAX_MEASUREMENT_RUNS: u32 = 20;
+
+/// Per-cycle metric snapshots, one JSON object per line
+pub const PROJECT_HISTORY_FILE: &str = ".axiom/performance-history.jsonl";
+
+/// Where heap snapshots of a leaking app are written unless configured otherwise
+pub const DEFAULT_HEAP_SNAPSHOT_DIR: &str = ".axiom/heap-snapshots";
+
+/// Replaced by the app's pid in a heap snapshot command
+const PID_PLACEHOLDER: &str = "{pid}";
+
+/// App runtime measurements that can be held to a threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PerformanceMetric {
+    /// Launch of a terminated app until its first frame
+    ColdLaunchMs,
+    /// Launch of an app still in memory until its first frame
+    WarmLaunchMs,
+    /// Resident memory once the app settles
+    MemoryMb,
+    CpuPercent,
+    FrameRateFps,
+    /// Milliseconds spent hitching per second of scrolling or animation
+    HitchTimeRatio,
+}
+
+impl PerformanceMetric {
+    pub fn higher_is_better(self) -> bool {
+        matches!(self, PerformanceMetric::FrameRateFps)
+    }
+}
+
+/// Values up to `warn` pass, up to `fail` warn, and beyond `fail` fail;
+/// for metrics where higher is better the comparisons flip. Deltas are
+/// reported against `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricThreshold {
+    pub target: f64,
+    pub warn: f64,
+    pub fail: f64,
+}
+
+impl MetricThreshold {
+    pub fn new(target: f64, warn: f64, fail: f64) -> Self {
+        Self { target, warn, fail }
+    }
+
+    fn validate(&self, metric: PerformanceMetric) -> Result<()> {
+        let ordered = if metric.higher_is_better() {
+            self.target >= self.warn && self.warn >= self.fail
+        } else {
+            self.target <= self.warn && self.warn <= self.fail
+        };
+        if ordered {
+            Ok(())
+        } else {
+            Err(AxiomMCPError::validation("performance", "check thresholds", format!(
+                "Threshold for {:?} must order target, warn and fail from best to worst, got {} / {} / {}",
+                metric, self.target, self.warn, self.fail
+            )))
+        }
+    }
+
+    fn status(&self, metric: PerformanceMetric, value: f64) -> MetricStatus {
+        let within = |limit: f64| if metric.higher_is_better() { value >= limit } else { value <= limit };
+        if within(self.warn) {
+            MetricStatus::Pass
+        } else if within(self.fail) {
+            MetricStatus::Warn
+        } else {
+            MetricStatus::Fail
+        }
+    }
+}
+
+/// Per-metric thresholds app runtime measurements are evaluated against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerformanceThresholds {
+    pub metrics: BTreeMap<PerformanceMetric, MetricThreshold>,
+    /// Fail the verdict when a metric with a threshold was not captured,
+    /// instead of only reporting it as missing
+    pub fail_on_missing: bool,
+}
+
+impl Default for PerformanceThresholds {
+    fn default() -> Self {
+        Self {
+            metrics: BTreeMap::from([
+                (PerformanceMetric::ColdLaunchMs, MetricThreshold::new(400.0, 1000.0, 2000.0)),
+                (PerformanceMetric::WarmLaunchMs, MetricThreshold::new(200.0, 500.0, 1000.0)),
+                (PerformanceMetric::MemoryMb, MetricThreshold::new(100.0, 250.0, 500.0)),
+                (PerformanceMetric::CpuPercent, MetricThreshold::new(20.0, 50.0, 80.0)),
+                (PerformanceMetric::FrameRateFps, MetricThreshold::new(60.0, 55.0, 45.0)),
+                (PerformanceMetric::HitchTimeRatio, MetricThreshold::new(5.0, 5.0, 10.0)),
+            ]),
+            fail_on_missing: false,
+        }
+    }
+}
+
+impl PerformanceThresholds {
+    /// Read thresholds from a JSON file
+    pub async fn load(path: &Path) -> Result<Self> {
+        let thresholds: Self = serde_json::from_str(&tokio::fs::read_to_string(path).await?)?;
+        thresholds.validate()?;
+        Ok(thresholds)
+    }
+
+    /// The project's thresholds file under `project_root` if it has one,
+    /// otherwise `configured`
+    pub async fn for_project(project_root: &Path, configured: &PerformanceThresholds) -> Result<Self> {
+        let thresholds = match tokio::fs::read_to_string(project_root.join(PROJECT_THRESHOLDS_FILE)).await {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => configured.clone(),
+            Err(e) => return Err(e.into()),
+        };
+        thresholds.validate()?;
+        Ok(thresholds)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.metrics.iter().try_for_each(|(metric, threshold)| threshold.validate(*metric))
+    }
+
+    /// Grade each metric that has a threshold
+    ///
+    /// Captured metrics without a threshold are not graded.
+    pub fn evaluate(&self, captured: &CapturedMetrics) -> PerformanceVerdict {
+        let metrics: Vec<MetricVerdict> = self
+            .metrics
+            .iter()
+            .map(|(&metric, threshold)| match captured.get(metric) {
+                Some(value) => MetricVerdict {
+                    metric,
+                    value: Some(value),
+                    target: threshold.target,
+                    status: threshold.status(metric, value),
+                    delta: Some(value - threshold.target),
+                    delta_percent: (threshold.target != 0.0).then(|| 100.0 * (value - threshold.target) / threshold.target),
+                },
+                None => MetricVerdict {
+                    metric,
+                    value: None,
+                    target: threshold.target,
+                    status: MetricStatus::Missing,
+                    delta: None,
+                    delta_percent: None,
+                },
+            })
+            .collect();
+
+        let status = metrics
+            .iter()
+            .map(|verdict| match verdict.status {
+                MetricStatus::Pass => VerdictStatus::Pass,
+                MetricStatus::Warn => VerdictStatus::Warn,
+                MetricStatus::Fail => VerdictStatus::Fail,
+                MetricStatus::Missing if self.fail_on_missing => VerdictStatus::Fail,
+                MetricStatus::Missing => VerdictStatus::Pass,
+            })
+            .max()
+            .unwrap_or(VerdictStatus::Pass);
+
+        PerformanceVerdict { status, metrics }
+    }
+}
+
+/// Measured values, keyed by metric
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CapturedMetrics {
+    values: BTreeMap<PerformanceMetric, f64>,
+}
+
+impl CapturedMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, metric: PerformanceMetric, value: f64) -> Self {
+        self.insert(metric, value);
+        self
+    }
+
+    pub fn insert(&mut self, metric: PerformanceMetric, value: f64) {
+        self.values.insert(metric, value);
+    }
+
+    pub fn get(&self, metric: PerformanceMetric) -> Option<f64> {
+        self.values.get(&metric).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (PerformanceMetric, f64)> + '_ {
+        self.values.iter().map(|(&metric, &value)| (metric, value))
+    }
+
+    /// `self - baseline` for each metric both have, such as actuals
+    /// against an estimate
+    pub fn difference(&self, baseline: &CapturedMetrics) -> CapturedMetrics {
+        let values = self
+            .iter()
+            .filter_map(|(metric, value)| Some((metric, value - baseline.get(metric)?)))
+            .collect();
+        CapturedMetrics { values }
+    }
+
+    /// The metrics a code generation estimate predicts
+    pub fn from_estimate(estimate: &PerformanceEstimate) -> Self {
+        Self::new()
+            .with(PerformanceMetric::ColdLaunchMs, estimate.estimated_startup_time_ms)
+            .with(PerformanceMetric::MemoryMb, estimate.estimated_memory_usage_kb as f64 / 1024.0)
+            .with(PerformanceMetric::CpuPercent, estimate.estimated_cpu_usage_percent)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricStatus {
+    Pass,
+    Warn,
+    Fail,
+    /// The metric has a threshold but was not captured
+    Missing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerdictStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricVerdict {
+    pub metric: PerformanceMetric,
+    pub value: Option<f64>,
+    pub target: f64,
+    pub status: MetricStatus,
+    /// `value - target`; negative is better unless higher is better
+    pub delta: Option<f64>,
+    /// `delta` as a percentage of a non-zero target
+    pub delta_percent: Option<f64>,
+}
+
+/// The worst grade across metrics, and each metric's grade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceVerdict {
+    pub status: VerdictStatus,
+    pub metrics: Vec<MetricVerdict>,
+}
+
+impl PerformanceVerdict {
+    /// Warnings still meet requirements; only failures do not
+    pub fn meets_requirements(&self) -> bool {
+        self.status != VerdictStatus::Fail
+    }
+
+    pub fn metric(&self, metric: PerformanceMetric) -> Option<&MetricVerdict> {
+        self.metrics.iter().find(|verdict| verdict.metric == metric)
+    }
+}
+
+/// Resident memory of a running process
+#[async_trait]
+pub trait ProcessMemory: Send + Sync {
+    async fn resident_mb(&self, pid: u32) -> Result<f64>;
+}
+
+/// Reads resident memory with the host's `ps`, since simulator apps run
+/// as host processes
+#[derive(Debug, Default)]
+pub struct PsProcessMemory;
+
+#[async_trait]
+impl ProcessMemory for PsProcessMemory {
+    async fn resident_mb(&self, pid: u32) -> Result<f64> {
+        let output = tokio::process::Command::new("ps")
+            .args(["-o", "rss=", "-p", &pid.to_string()])
+            .output()
+            .await
+            .map_err(|e| AxiomMCPError::toolchain("performance", "run ps", format!("Cannot run ps: {}", e)).with_cause(e))?;
+        if !output.status.success() {
+            return Err(AxiomMCPError::simulator("performance", "sample memory", format!("Process {} is not running", pid)));
+        }
+        parse_rss_mb(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Megabytes from `ps -o rss=` output, which is in kilobytes
+pub fn parse_rss_mb(output: &str) -> Result<f64> {
+    output
+        .trim()
+        .parse::<f64>()
+        .map(|kb| kb / 1024.0)
+        .map_err(|_| AxiomMCPError::toolchain("performance", "sample memory", format!("Unexpected ps output: {:?}", output.trim())))
+}
+
+/// Frame rate counted by the running app
+#[async_trait]
+pub trait FrameRateSource: Send + Sync {
+    async fn frame_rate(&self, window: Duration) -> Result<f64>;
+}
+
+#[async_trait]
+impl FrameRateSource for IntelligenceClient {
+    async fn frame_rate(&self, window: Duration) -> Result<f64> {
+        IntelligenceClient::frame_rate(self, window).await
+    }
+}
+
+/// Measure an installed app's launch times, memory and frame rate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasureAppPerformanceRequest {
+    /// UDID, or name and runtime, of a booted simulator
+    pub device: String,
+    pub bundle_id: String,
+    #[serde(default = "default_runs")]
+    pub runs: u32,
+    /// How long to sample memory and count frames in each run
+    #[serde(default = "default_window_ms")]
+    pub window_ms: u64,
+    #[serde(default = "default_memory_interval_ms")]
+    pub memory_interval_ms: u64,
+}
+
+fn default_runs() -> u32 {
+    3
+}
+
+fn default_window_ms() -> u64 {
+    3000
+}
+
+fn default_memory_interval_ms() -> u64 {
+    500
+}
+
+impl MeasureAppPerformanceRequest {
+    pub fn new(device: impl Into<String>, bundle_id: impl Into<String>) -> Self {
+        Self {
+            device: device.into(),
+            bundle_id: bundle_id.into(),
+            runs: default_runs(),
+            window_ms: default_window_ms(),
+            memory_interval_ms: default_memory_interval_ms(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.bundle_id.trim().is_empty() {
+            return Err(AxiomMCPError::validation("performance", "measure app", "Bundle identifier cannot be empty"));
+        }
+        if self.runs == 0 || self.runs > MAX_MEASUREMENT_RUNS {
+            return Err(AxiomMCPError::validation("performance", "measure app", format!(
+                "Runs must be between 1 and {}, got {}", MAX_MEASUREMENT_RUNS, self.runs
+            )));
+        }
+        if self.memory_interval_ms == 0 {
+            return Err(AxiomMCPError::validation("performance", "measure app", "Memory sample interval cannot be zero"));
+        }
+        Ok(())
+    }
+}
+
+/// One terminate, cold launch, memory window, warm launch and frame count
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasurementRun {
+    /// Process of the cold launch
+    pub pid: u32,
+    /// Memory is the median of the run's samples
+    pub metrics: CapturedMetrics,
+    pub memory_samples_mb: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricSummary {
+    pub median: f64,
+    /// Nearest-rank 95th percentile
+    pub p95: f64,
+    pub min: f64,
+    pub max: f64,
+    pub samples: usize,
+}
+
+impl MetricSummary {
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        };
+        let p95 = sorted[((0.95 * n as f64).ceil() as usize).max(1) - 1];
+        Some(Self { median, p95, min, max, samples: n })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppPerformanceMeasurement {
+    pub device: SimulatorDevice,
+    pub bundle_id: String,
+    pub runs: Vec<MeasurementRun>,
+    pub summaries: BTreeMap<PerformanceMetric, MetricSummary>,
+    /// Medians across runs, in the same terms as `CapturedMetrics::from_estimate`
+    pub metrics: CapturedMetrics,
+    pub verdict: PerformanceVerdict,
+}
+
+/// Measures an installed app on a simulator
+///
+/// Each run terminates the app, times a cold launch, samples its memory,
+/// times a warm launch of the still running app and, when a frame rate
+/// source is attached, has the app count frames.
+pub struct AppPerformanceMeter {
+    simulator: Arc<SimulatorController>,
+    memory: Arc<dyn ProcessMemory>,
+    frame_rate: Option<Arc<dyn FrameRateSource>>,
+}
+
+impl AppPerformanceMeter {
+    pub fn new(simulator: Arc<SimulatorController>) -> Self {
+        Self {
+            simulator,
+            memory: Arc::new(PsProcessMemory),
+            frame_rate: None,
+        }
+    }
+
+    pub fn with_process_memory(mut self, memory: Arc<dyn ProcessMemory>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub fn with_frame_rate_source(mut self, source: Arc<dyn FrameRateSource>) -> Self {
+        self.frame_rate = Some(source);
+        self
+    }
+
+    pub async fn measure(
+        &self,
+        request: &MeasureAppPerformanceRequest,
+        thresholds: &PerformanceThresholds,
+    ) -> Result<AppPerformanceMeasurement> {
+        request.validate()?;
+        let device = self.simulator.resolve(&request.device).await?;
+        let cold_launch = LaunchAppRequest {
+            device: device.udid.clone(),
+            bundle_id: request.bundle_id.clone(),
+            ..Default::default()
+        };
+        let warm_launch = LaunchAppRequest { keep_running: true, ..cold_launch.clone() };
+        let window = Duration::from_millis(request.window_ms);
+
+        let mut runs = Vec::new();
+        for _ in 0..request.runs {
+            self.simulator.terminate_app(&device, &request.bundle_id).await?;
+            let started = Instant::now();
+            let cold = self.simulator.launch_on(device.clone(), &cold_launch).await?;
+            let cold_ms = elapsed_ms(started);
+
+            let memory_samples_mb = self.sample_memory(cold.pid, request).await?;
+
+            let started = Instant::now();
+            self.simulator.launch_on(device.clone(), &warm_launch).await?;
+            let warm_ms = elapsed_ms(started);
+
+            let mut metrics = CapturedMetrics::new()
+                .with(PerformanceMetric::ColdLaunchMs, cold_ms)
+                .with(PerformanceMetric::WarmLaunchMs, warm_ms);
+            if let Some(memory) = MetricSummary::from_values(&memory_samples_mb) {
+                metrics.insert(PerformanceMetric::MemoryMb, memory.median);
+            }
+            if let Some(source) = &self.frame_rate {
+                match source.frame_rate(window).await {
+                    Ok(fps) => metrics.insert(PerformanceMetric::FrameRateFps, fps),
+                    Err(e) => tracing::warn!("Measuring without frame rate: {}", e),
+                }
+            }
+            runs.push(MeasurementRun { pid: cold.pid, metrics, memory_samples_mb });
+        }
+
+        let mut values: BTreeMap<PerformanceMetric, Vec<f64>> = BTreeMap::new();
+        for (metric, value) in runs.iter().flat_map(|run| run.metrics.iter()) {
+            values.entry(metric).or_default().push(value);
+        }
+        let summaries: BTreeMap<_, _> = values
+            .iter()
+            .filter_map(|(&metric, values)| Some((metric, MetricSummary::from_values(values)?)))
+            .collect();
+        let metrics = CapturedMetrics {
+            values: summaries.iter().map(|(&metric, summary)| (metric, summary.median)).collect(),
+        };
+
+        tracing::info!("Measured {} over {} runs on {}", request.bundle_id, runs.len(), device.name);
+        Ok(AppPerformanceMeasurement {
+            device,
+            bundle_id: request.bundle_id.clone(),
+            runs,
+            summaries,
+            verdict: thresholds.evaluate(&metrics),
+            metrics,
+        })
+    }
+
+    /// One sample every interval across the window, starting right away
+    async fn sample_memory(&self, pid: u32, request: &MeasureAppPerformanceRequest) -> Result<Vec<f64>> {
+        let count = request.window_ms / request.memory_interval_ms + 1;
+        let mut samples = Vec::new();
+        for sample in 0..count {
+            if sample > 0 {
+                tokio::time::sleep(Duration::from_millis(request.memory_interval_ms)).await;
+            }
+            samples.push(self.memory.resident_mb(pid).await?);
+        }
+        Ok(samples)
+    }
+}
+
+impl std::fmt::Debug for AppPerformanceMeter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppPerformanceMeter")
+            .field("simulator", &self.simulator)
+            .field("frame_rate", &self.frame_rate.is_some())
+            .finish()
+    }
+}
+
+fn elapsed_ms(started: Instant) -> f64 {
+    started.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Metrics of one development cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceSnapshot {
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    pub analysis_id: String,
+    pub implementation_id: String,
+    pub metrics: CapturedMetrics,
+}
+
+/// Append-only JSONL store of performance snapshots
+#[derive(Debug, Clone)]
+pub struct PerformanceHistory {
+    path: std::path::PathBuf,
+}
+
+impl PerformanceHistory {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The history file under `project_root`
+    pub fn for_project(project_root: &Path) -> Self {
+        Self::new(project_root.join(PROJECT_HISTORY_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn record(&self, snapshot: &PerformanceSnapshot) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut line = serde_json::to_string(snapshot)?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+
+    /// Every snapshot in the order recorded, empty when nothing has been
+    /// recorded yet; unreadable lines are skipped
+    pub async fn snapshots(&self) -> Result<Vec<PerformanceSnapshot>> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(index, line)| match serde_json::from_str(line) {
+                Ok(snapshot) => Some(snapshot),
+                Err(e) => {
+                    tracing::warn!("Skipping line {} of {}: {}", index + 1, self.path.display(), e);
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+/// How the latest value of a metric is compared with its history
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrendOptions {
+    /// Earlier cycles the rolling median is taken over
+    pub window: usize,
+    /// How much worse than the rolling median the latest value may be
+    pub regression_threshold_percent: f64,
+}
+
+impl Default for TrendOptions {
+    fn default() -> Self {
+        Self {
+            window: 5,
+            regression_threshold_percent: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    pub implementation_id: String,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricTrend {
+    pub metric: PerformanceMetric,
+    /// Oldest first
+    pub points: Vec<TrendPoint>,
+    pub latest: f64,
+    /// Median of up to `window` values before the latest
+    pub rolling_median: Option<f64>,
+    /// Change of the latest value from the rolling median; positive is worse
+    pub worse_by_percent: Option<f64>,
+    pub regressed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceTrends {
+    pub snapshots: usize,
+    pub options: TrendOptions,
+    pub metrics: Vec<MetricTrend>,
+}
+
+impl PerformanceTrends {
+    /// Time series per metric, flagging metrics whose latest value is worse
+    /// than the rolling median of the cycles before it by more than the
+    /// threshold
+    pub fn from_snapshots(snapshots: &[PerformanceSnapshot], options: TrendOptions) -> Self {
+        let mut ordered: Vec<&PerformanceSnapshot> = snapshots.iter().collect();
+        ordered.sort_by_key(|snapshot| snapshot.recorded_at);
+
+        let mut series: BTreeMap<PerformanceMetric, Vec<TrendPoint>> = BTreeMap::new();
+        for snapshot in ordered {
+            for (metric, value) in snapshot.metrics.iter() {
+                series.entry(metric).or_default().push(TrendPoint {
+                    recorded_at: snapshot.recorded_at,
+                    implementation_id: snapshot.implementation_id.clone(),
+                    value,
+                });
+            }
+        }
+
+        let metrics = series
+            .into_iter()
+            .filter_map(|(metric, points)| {
+                let (latest, earlier) = points.split_last()?;
+                let earlier: Vec<f64> = earlier.iter().rev().take(options.window).map(|point| point.value).collect();
+                let rolling_median = MetricSummary::from_values(&earlier).map(|summary| summary.median);
+                let worse_by_percent = rolling_median.filter(|median| *median != 0.0).map(|median| {
+                    let change = 100.0 * (latest.value - median) / median.abs();
+                    if metric.higher_is_better() { -change } else { change }
+                });
+                Some(MetricTrend {
+                    metric,
+                    latest: latest.value,
+                    rolling_median,
+                    regressed: worse_by_percent.is_some_and(|worse| worse > options.regression_threshold_percent),
+                    worse_by_percent,
+                    points,
+                })
+            })
+            .collect();
+
+        Self { snapshots: snapshots.len(), options, metrics }
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &MetricTrend> {
+        self.metrics.iter().filter(|trend| trend.regressed)
+    }
+
+    pub fn summary(&self) -> String {
+        let regressions: Vec<String> = self
+            .regressions()
+            .map(|trend| format!("{:?} {:.1}% worse", trend.metric, trend.worse_by_percent.unwrap_or_default()))
+            .collect();
+        if regressions.is_empty() {
+            format!("No regressions across {} cycles", self.snapshots)
+        } else {
+            format!("Regressed across {} cycles: {}", self.snapshots, regressions.join(", "))
+        }
+    }
+}
+
+/// When memory growth across hot reloads counts as a leak
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReloadMemoryConfig {
+    /// Latest reloads the trend is fitted over
+    pub window: usize,
+    /// Reloads sampled before growth is judged; fewer cannot tell a leak from noise
+    pub min_reloads: usize,
+    /// Fitted growth per reload above which the app is taken to leak
+    pub max_growth_mb_per_reload: f64,
+    /// How long the app settles after a reload before it is sampled
+    pub settle_ms: u64,
+    /// Run once growth crosses the limit, with `{pid}` replaced by the app's
+    /// pid, such as `["heap", "{pid}"]`; its output is kept in `heap_snapshot_dir`
+    pub heap_snapshot_command: Option<Vec<String>>,
+    pub heap_snapshot_dir: PathBuf,
+}
+
+impl Default for ReloadMemoryConfig {
+    fn default() -> Self {
+        Self {
+            window: 10,
+            min_reloads: 5,
+            max_growth_mb_per_reload: 1.0,
+            settle_ms: 500,
+            heap_snapshot_command: None,
+            heap_snapshot_dir: PathBuf::from(DEFAULT_HEAP_SNAPSHOT_DIR),
+        }
+    }
+}
+
+impl ReloadMemoryConfig {
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(AxiomMCPError::validation("performance", "check reload memory limits", message));
+        if self.min_reloads < 2 || self.min_reloads > self.window {
+            return invalid(format!(
+                "min_reloads must be between 2 and the window of {} reloads, got {}", self.window, self.min_reloads
+            ));
+        }
+        if !self.max_growth_mb_per_reload.is_finite() || self.max_growth_mb_per_reload < 0.0 {
+            return invalid(format!("max_growth_mb_per_reload must not be negative, got {}", self.max_growth_mb_per_reload));
+        }
+        if self.heap_snapshot_command.as_ref().is_some_and(Vec::is_empty) {
+            return invalid("heap_snapshot_command names no program".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Resident memory of the app around one hot reload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReloadMemorySample {
+    pub batch_id: u64,
+    pub at: chrono::DateTime<chrono::Utc>,
+    /// Files the reload carried
+    pub files: Vec<String>,
+    /// `None` when the app could not be sampled before the reload
+    pub before_mb: Option<f64>,
+    /// Once the app has settled
+    pub after_mb: f64,
+}
+
+/// Straight line through values taken at positions 0, 1, 2, ...
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrendFit {
+    pub slope: f64,
+    pub intercept: f64,
+}
+
+impl TrendFit {
+    /// Theil-Sen fit: the slope is the median of the slopes between every
+    /// pair of values, so a few spikes, such as a sample caught in the
+    /// middle of an allocation burst, do not tilt it the way they tilt a
+    /// least squares fit. `None` for fewer than two values.
+    pub fn theil_sen(values: &[f64]) -> Option<Self> {
+        let mut slopes = Vec::new();
+        for (i, first) in values.iter().enumerate() {
+            for (j, second) in values.iter().enumerate().skip(i + 1) {
+                slopes.push((second - first) / (j - i) as f64);
+            }
+        }
+        let slope = MetricSummary::from_values(&slopes)?.median;
+        let offsets: Vec<f64> = values.iter().enumerate().map(|(i, value)| value - slope * i as f64).collect();
+        let intercept = MetricSummary::from_values(&offsets)?.median;
+        Some(Self { slope, intercept })
+    }
+
+    pub fn at(&self, position: usize) -> f64 {
+        self.intercept + self.slope * position as f64
+    }
+}
+
+/// Memory growing across hot reloads faster than the configured limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryLeakFinding {
+    pub pid: u32,
+    pub growth_mb_per_reload: f64,
+    pub max_growth_mb_per_reload: f64,
+    /// Fitted growth from the first reload of the timeline to the last
+    pub total_growth_mb: f64,
+    /// The reloads the trend was fitted over, oldest first
+    pub timeline: Vec<ReloadMemorySample>,
+    /// Taken when growth crossed the limit, if a command is configured
+    pub heap_snapshot: Option<HeapSnapshot>,
+}
+
+impl MemoryLeakFinding {
+    pub fn message(&self) -> String {
+        format!(
+            "Memory of process {} grew {:.1} MB per reload over the last {} reloads ({:+.1} MB), above the {:.1} MB limit",
+            self.pid, self.growth_mb_per_reload, self.timeline.len(), self.total_growth_mb, self.max_growth_mb_per_reload
+        )
+    }
+}
+
+/// Output of the heap snapshot command, run against a leaking app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeapSnapshot {
+    /// With the pid filled in
+    pub command: Vec<String>,
+    pub taken_at: chrono::DateTime<chrono::Utc>,
+    /// Where the command's output was written; `None` when it failed
+    pub path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+/// Memory of the followed app across its latest hot reloads
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReloadMemoryTrend {
+    /// `None` until an app is launched to follow
+    pub pid: Option<u32>,
+    /// Oldest first, at most the configured window of them
+    pub samples: Vec<ReloadMemorySample>,
+    /// Of the memory after each reload; `None` for fewer than two samples
+    pub fit: Option<TrendFit>,
+    pub min_reloads: usize,
+    pub max_growth_mb_per_reload: f64,
+    pub finding: Option<MemoryLeakFinding>,
+}
+
+impl ReloadMemoryTrend {
+    /// Fit the memory after each of the latest reloads; faster growth than
+    /// the limit across at least `min_reloads` of them is a finding
+    pub fn from_samples(pid: Option<u32>, samples: &[ReloadMemorySample], config: &ReloadMemoryConfig) -> Self {
+        let samples = samples[samples.len().saturating_sub(config.window)..].to_vec();
+        let after: Vec<f64> = samples.iter().map(|sample| sample.after_mb).collect();
+        let fit = TrendFit::theil_sen(&after);
+        let finding = match (pid, fit) {
+            (Some(pid), Some(fit)) if samples.len() >= config.min_reloads && fit.slope > config.max_growth_mb_per_reload => {
+                Some(MemoryLeakFinding {
+                    pid,
+                    growth_mb_per_reload: fit.slope,
+                    max_growth_mb_per_reload: config.max_growth_mb_per_reload,
+                    total_growth_mb: fit.at(samples.len() - 1) - fit.at(0),
+                    timeline: samples.clone(),
+                    heap_snapshot: None,
+                })
+            }
+            _ => None,
+        };
+        Self {
+            pid,
+            samples,
+            fit,
+            min_reloads: config.min_reloads,
+            max_growth_mb_per_reload: config.max_growth_mb_per_reload,
+            finding,
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match (&self.finding, self.fit, self.pid) {
+            (Some(finding), _, _) => finding.message(),
+            (None, Some(fit), Some(pid)) => format!(
+                "Memory of process {} changed {:+.1} MB per reload over {} reloads",
+                pid, fit.slope, self.samples.len()
+            ),
+            (None, None, Some(pid)) => format!("{} reloads of process {} sampled, too few for a trend", self.samples.len(), pid),
+            (None, _, None) => "No launched app to follow across reloads".to_string(),
+        }
+    }
+}
+
+/// Samples the launched app's memory before and after every hot reload,
+/// and watches the trend for leaks
+pub struct ReloadMemoryTracker {
+    config: ReloadMemoryConfig,
+    memory: Arc<dyn ProcessMemory>,
+    state: Mutex<ReloadMemoryState>,
+}
+
+#[derive(Default)]
+struct ReloadMemoryState {
+    pid: Option<u32>,
+    samples: VecDeque<ReloadMemorySample>,
+    /// Sampled before the reload in flight
+    before: Option<(u64, f64)>,
+    /// Whether the trend was over the limit at the last sample
+    leaking: bool,
+    /// Taken when the trend last crossed the limit
+    heap_snapshot: Option<HeapSnapshot>,
+}
+
+impl ReloadMemoryTracker {
+    pub fn new(config: ReloadMemoryConfig) -> Self {
+        Self {
+            config,
+            memory: Arc::new(PsProcessMemory),
+            state: Mutex::new(ReloadMemoryState::default()),
+        }
+    }
+
+    pub fn with_process_memory(mut self, memory: Arc<dyn ProcessMemory>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub fn config(&self) -> &ReloadMemoryConfig {
+        &self.config
+    }
+
+    /// Follow the process `pid`, such as an app just launched; the samples
+    /// of the process followed before are dropped
+    pub fn track(&self, pid: u32) {
+        let mut state = self.state.lock().unwrap();
+        if state.pid != Some(pid) {
+            *state = ReloadMemoryState { pid: Some(pid), ..Default::default() };
+        }
+    }
+
+    pub fn trend(&self) -> ReloadMemoryTrend {
+        self.trend_of(&self.state.lock().unwrap())
+    }
+
+    fn trend_of(&self, state: &ReloadMemoryState) -> ReloadMemoryTrend {
+        let samples: Vec<ReloadMemorySample> = state.samples.iter().cloned().collect();
+        let mut trend = ReloadMemoryTrend::from_samples(state.pid, &samples, &self.config);
+        if let Some(finding) = &mut trend.finding {
+            finding.heap_snapshot = state.heap_snapshot.clone();
+        }
+        trend
+    }
+
+    /// Add a sample of the process `pid` and judge the trend again, taking
+    /// a heap snapshot when growth crosses the limit. A sample of a process
+    /// no longer followed is dropped.
+    pub async fn record(&self, pid: u32, sample: ReloadMemorySample) -> ReloadMemoryTrend {
+        let batch_id = sample.batch_id;
+        let crossed = {
+            let mut state = self.state.lock().unwrap();
+            if state.pid != Some(pid) {
+                return self.trend_of(&state);
+            }
+            state.samples.push_back(sample);
+            while state.samples.len() > self.config.window {
+                state.samples.pop_front();
+            }
+            let leaking = self.trend_of(&state).finding.is_some();
+            let crossed = leaking && !state.leaking;
+            state.leaking = leaking;
+            if !leaking {
+                state.heap_snapshot = None;
+            }
+            crossed
+        };
+
+        if crossed {
+            if let Some(finding) = self.trend().finding {
+                tracing::warn!("{}", finding.message());
+            }
+            if let Some(command) = &self.config.heap_snapshot_command {
+                let snapshot = take_heap_snapshot(command, pid, batch_id, &self.config.heap_snapshot_dir).await;
+                let mut state = self.state.lock().unwrap();
+                if state.pid == Some(pid) && state.leaking {
+                    state.heap_snapshot = Some(snapshot);
+                }
+            }
+        }
+        self.trend()
+    }
+
+    async fn sample(&self, pid: u32) -> Option<f64> {
+        match self.memory.resident_mb(pid).await {
+            Ok(mb) => Some(mb),
+            Err(e) => {
+                tracing::debug!("Could not sample the memory of process {} around a reload: {}", pid, e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ReloadObserver for ReloadMemoryTracker {
+    async fn before_reload(&self, batch_id: u64) {
+        let Some(pid) = self.state.lock().unwrap().pid else {
+            return;
+        };
+        let before = self.sample(pid).await;
+        self.state.lock().unwrap().before = before.map(|mb| (batch_id, mb));
+    }
+
+    async fn after_reload(&self, reload: &ReloadReport) {
+        let Some(pid) = self.state.lock().unwrap().pid else {
+            return;
+        };
+        tokio::time::sleep(Duration::from_millis(self.config.settle_ms)).await;
+        let Some(after_mb) = self.sample(pid).await else {
+            return;
+        };
+        let before_mb = self.state.lock().unwrap().before.take()
+            .filter(|(batch_id, _)| *batch_id == reload.batch_id)
+            .map(|(_, mb)| mb);
+        self.record(pid, ReloadMemorySample {
+            batch_id: reload.batch_id,
+            at: reload.at,
+            files: reload.files.clone(),
+            before_mb,
+            after_mb,
+        }).await;
+    }
+}
+
+impl std::fmt::Debug for ReloadMemoryTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("ReloadMemoryTracker")
+            .field("config", &self.config)
+            .field("pid", &state.pid)
+            .field("samples", &state.samples.len())
+            .finish()
+    }
+}
+
+/// Run the heap snapshot command against `pid`, keeping its output under `dir`
+async fn take_heap_snapshot(command: &[String], pid: u32, batch_id: u64, dir: &Path) -> HeapSnapshot {
+    let command: Vec<String> = command.iter().map(|arg| arg.replace(PID_PLACEHOLDER, &pid.to_string())).collect();
+    let path = dir.join(format!("heap-{}-reload-{}.txt", pid, batch_id));
+    let written = async {
+        let (program, args) = command.split_first().ok_or_else(|| {
+            AxiomMCPError::configuration("reload_memory.heap_snapshot_command", "names no program")
+        })?;
+        let output = tokio::process::Command::new(program)
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| AxiomMCPError::toolchain("performance", "take heap snapshot", format!("Cannot run {}: {}", program, e)).with_cause(e))?;
+        if !output.status.success() {
+            return Err(AxiomMCPError::toolchain("performance", "take heap snapshot", format!(
+                "{} failed ({}): {}", program, output.status, String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&path, &output.stdout).await?;
+        Ok::<_, AxiomMCPError>(())
+    }
+    .await;
+
+    let taken_at = chrono::Utc::now();
+    match written {
+        Ok(()) => {
+            tracing::info!("Heap snapshot of process {} written to {}", pid, path.display());
+            HeapSnapshot { command, taken_at, path: Some(path), error: None }
+        }
+        Err(e) => {
+            tracing::warn!("Heap snapshot of process {} failed: {}", pid, e);
+            HeapSnapshot { command, taken_at, path: None, error: Some(e.to_string()) }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceAnalysisSpec {
     pub analysis_name: String,
     pub duration_seconds: u64,
     pub profilers_to_include: Vec<String>,
     pub detailed_analysis: bool,
+    /// Measurements to grade against the thresholds
+    #[serde(default)]
+    pub metrics: CapturedMetrics,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +1114,9 @@ pub struct PerformanceReport {
     pub bottlenecks: Vec<String>,
     pub optimizations: Vec<String>,
     pub executive_summary: String,
+    /// Grades of the spec's metrics, when it had any
+    #[serde(default)]
+    pub verdict: Option<PerformanceVerdict>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,22 +1124,41 @@ pub struct RealtimePerformanceStream {
     pub stream_id: String,
 }
 
-pub struct PerformanceAnalysisIntegration;
+#[derive(Debug, Default)]
+pub struct PerformanceAnalysisIntegration {
+    thresholds: PerformanceThresholds,
+}
 
 impl PerformanceAnalysisIntegration {
     pub async fn new(
         _intelligence_client: Arc<crate::intelligence::IntelligenceClient>,
         _hot_reload_client: Arc<crate::hot_reload::HotReloadClient>
     ) -> Result<Self> {
-        Ok(Self)
+        Ok(Self::default())
+    }
+    
+    /// Grade measurements against `thresholds` instead of the defaults
+    pub fn with_thresholds(mut self, thresholds: PerformanceThresholds) -> Result<Self> {
+        thresholds.validate()?;
+        self.thresholds = thresholds;
+        Ok(self)
+    }
+    
+    pub fn thresholds(&self) -> &PerformanceThresholds {
+        &self.thresholds
+    }
+    
+    pub fn evaluate(&self, captured: &CapturedMetrics) -> PerformanceVerdict {
+        self.thresholds.evaluate(captured)
     }
     
-    pub async fn start_comprehensive_analysis(&self, _spec: PerformanceAnalysisSpec) -> Result<PerformanceReport> {
+    pub async fn start_comprehensive_analysis(&self, spec: PerformanceAnalysisSpec) -> Result<PerformanceReport> {
         Ok(PerformanceReport {
             performance_score: 85.0,
             bottlenecks: vec!["Memory allocation in main thread".to_string()],
             optimizations: vec!["Use lazy loading for images".to_string()],
             executive_summary: "Application performance is good with minor optimization opportunities".to_string(),
+            verdict: (!spec.metrics.is_empty()).then(|| self.evaluate(&spec.metrics)),
         })
     }
     
@@ -0,0 +1,420 @@
+use crate::artifact_store::{ArtifactKind, ArtifactSource, ArtifactStore};
+use crate::complete_development_loop::StageOutput;
+use crate::error::{AxiomMCPError, Result};
+use crate::types::{Implementation, InfrastructureSpecification};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Lines kept around each error when a build log is truncated
+pub const DEFAULT_LOG_CONTEXT_LINES: usize = 3;
+
+/// The simulator destination `xcodebuild` builds for unless configured otherwise
+pub const DEFAULT_SIMULATOR_DESTINATION: &str = "generic/platform=iOS Simulator";
+
+/// The target generated sources go into when the spec names none
+const DEFAULT_TARGET: &str = "App";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A compiler or build system diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildDiagnostic {
+    pub severity: DiagnosticSeverity,
+    /// Relative to the project root when the file is inside it
+    pub file: Option<String>,
+    /// 1-based
+    pub line: Option<usize>,
+    /// 1-based
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for BuildDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.file, self.line, self.column) {
+            (Some(file), Some(line), Some(column)) => write!(f, "{}:{}:{}: {}", file, line, column, self.message),
+            (Some(file), Some(line), None) => write!(f, "{}:{}: {}", file, line, self.message),
+            (Some(file), None, _) => write!(f, "{}: {}", file, self.message),
+            (None, _, _) => f.write_str(&self.message),
+        }
+    }
+}
+
+/// What a build command printed and whether it succeeded
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandOutput {
+    pub success: bool,
+    /// stdout followed by stderr
+    pub log: String,
+}
+
+/// Runs build tools, behind a trait so tests can replay captured output
+#[async_trait]
+pub trait BuildCommand: Send + Sync {
+    /// Run `program` with `args` in `dir`; a build that fails is not an error,
+    /// only a command that could not be run
+    async fn run(&self, dir: &Path, program: &str, args: &[String]) -> Result<CommandOutput>;
+}
+
+/// Runs build tools as subprocesses
+#[derive(Debug, Default)]
+pub struct ProcessBuildCommand;
+
+#[async_trait]
+impl BuildCommand for ProcessBuildCommand {
+    async fn run(&self, dir: &Path, program: &str, args: &[String]) -> Result<CommandOutput> {
+        let output = tokio::process::Command::new(program).args(args).current_dir(dir).output().await.map_err(|e| {
+            AxiomMCPError::toolchain("project_build", "build", format!("Cannot run {}: {}", program, e)).with_cause(e)
+        })?;
+        let mut log = String::from_utf8_lossy(&output.stdout).into_owned();
+        log.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(CommandOutput { success: output.status.success(), log })
+    }
+}
+
+/// The tool that builds a materialized project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "tool", rename_all = "snake_case")]
+pub enum BuildToolchain {
+    /// `xcodebuild` for the first scheme naming a target of the spec, or
+    /// its first target
+    Xcodebuild { destination: String },
+    /// `swift build`, for packages that build on the host
+    SwiftBuild,
+}
+
+impl Default for BuildToolchain {
+    fn default() -> Self {
+        BuildToolchain::Xcodebuild { destination: DEFAULT_SIMULATOR_DESTINATION.to_string() }
+    }
+}
+
+impl BuildToolchain {
+    /// The program and arguments that build `infrastructure`
+    pub fn invocation(&self, infrastructure: &InfrastructureSpecification) -> (String, Vec<String>) {
+        match self {
+            BuildToolchain::Xcodebuild { destination } => {
+                let args = ["build", "-scheme", scheme_name(infrastructure), "-destination", destination.as_str(), "-derivedDataPath", ".build/DerivedData"];
+                ("xcodebuild".to_string(), args.iter().map(|arg| arg.to_string()).collect())
+            }
+            BuildToolchain::SwiftBuild => ("swift".to_string(), vec!["build".to_string()]),
+        }
+    }
+}
+
+/// The outcome of building one implementation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectBuildResult {
+    pub succeeded: bool,
+    /// The command line that was run
+    pub command: String,
+    pub project_dir: PathBuf,
+    pub diagnostics: Vec<BuildDiagnostic>,
+    /// The errors with the lines around them, or the end of the log when
+    /// there are none
+    pub log: String,
+    /// Id of the whole log in the artifact store, when the builder has one
+    #[serde(default)]
+    pub log_artifact: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl ProjectBuildResult {
+    pub fn errors(&self) -> impl Iterator<Item = &BuildDiagnostic> {
+        self.diagnostics.iter().filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+    }
+}
+
+/// Writes implementations to disk as Swift packages and builds them
+#[derive(Clone)]
+pub struct ProjectBuilder {
+    command: Arc<dyn BuildCommand>,
+    toolchain: BuildToolchain,
+    /// Each implementation is materialized in a directory of its own here
+    workspace: PathBuf,
+    context_lines: usize,
+    artifacts: Option<Arc<ArtifactStore>>,
+}
+
+impl std::fmt::Debug for ProjectBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectBuilder")
+            .field("toolchain", &self.toolchain)
+            .field("workspace", &self.workspace)
+            .field("context_lines", &self.context_lines)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProjectBuilder {
+    pub fn new(workspace: impl Into<PathBuf>) -> Self {
+        Self {
+            command: Arc::new(ProcessBuildCommand),
+            toolchain: BuildToolchain::default(),
+            workspace: workspace.into(),
+            context_lines: DEFAULT_LOG_CONTEXT_LINES,
+            artifacts: None,
+        }
+    }
+
+    pub fn with_command(mut self, command: Arc<dyn BuildCommand>) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// Keep each build's whole log in `artifacts`
+    pub fn with_artifact_store(mut self, artifacts: Arc<ArtifactStore>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    pub fn with_toolchain(mut self, toolchain: BuildToolchain) -> Self {
+        self.toolchain = toolchain;
+        self
+    }
+
+    pub fn with_context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    pub fn toolchain(&self) -> &BuildToolchain {
+        &self.toolchain
+    }
+
+    /// Materialize `implementation` and build it
+    pub async fn build(&self, implementation: &Implementation) -> Result<ProjectBuildResult> {
+        self.build_for(implementation, ArtifactSource::default()).await
+    }
+
+    /// `build`, keeping the log as an artifact of `source`'s cycle and stage
+    pub async fn build_for(&self, implementation: &Implementation, source: ArtifactSource) -> Result<ProjectBuildResult> {
+        let project_dir = self.workspace.join(&implementation.implementation_id);
+        materialize_project(implementation, &project_dir).await?;
+
+        let (program, args) = self.toolchain.invocation(&implementation.infrastructure);
+        let command = std::iter::once(program.as_str()).chain(args.iter().map(String::as_str)).collect::<Vec<_>>().join(" ");
+        tracing::info!("Building {} with {}", project_dir.display(), command);
+        let started = std::time::Instant::now();
+        let output = self.command.run(&project_dir, &program, &args).await?;
+
+        let mut diagnostics = parse_build_diagnostics(&output.log, &project_dir);
+        // A failed build that printed no diagnostics still has to say why
+        if !output.success && !diagnostics.iter().any(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error) {
+            diagnostics.push(BuildDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                file: None,
+                line: None,
+                column: None,
+                message: format!("{} failed without reporting an error", program),
+            });
+        }
+        let log_artifact = match &self.artifacts {
+            Some(artifacts) => {
+                let source = source.with_metadata("implementation_id", &implementation.implementation_id);
+                artifacts.keep(ArtifactKind::BuildLog, output.log.as_bytes(), "log", source).await.map(|record| record.id)
+            }
+            None => None,
+        };
+        Ok(ProjectBuildResult {
+            succeeded: output.success,
+            command,
+            project_dir,
+            diagnostics,
+            log: truncate_build_log(&output.log, self.context_lines),
+            log_artifact,
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+fn target_name(infrastructure: &InfrastructureSpecification) -> &str {
+    infrastructure.build_configuration.targets.first().map(String::as_str).unwrap_or(DEFAULT_TARGET)
+}
+
+/// The scheme `xcodebuild` builds and tests: the first naming a target of
+/// the spec, or its first target
+pub fn scheme_name(infrastructure: &InfrastructureSpecification) -> &str {
+    let configuration = &infrastructure.build_configuration;
+    // Package schemes are named after targets; `Debug` is a configuration
+    configuration
+        .schemes
+        .iter()
+        .find(|scheme| configuration.targets.contains(scheme))
+        .map(String::as_str)
+        .unwrap_or_else(|| target_name(infrastructure))
+}
+
+/// Write the package `implementation` describes under `dir`: its
+/// directories, a `Package.swift`, its generated files in the first
+/// target and its generated tests in a test target for it; returns the
+/// files written, relative to `dir`
+pub async fn materialize_project(implementation: &Implementation, dir: &Path) -> Result<Vec<PathBuf>> {
+    let infrastructure = &implementation.infrastructure;
+    let target = target_name(infrastructure);
+    for directory in &infrastructure.project_structure.directories {
+        tokio::fs::create_dir_all(dir.join(relative(directory)?)).await?;
+    }
+    let sources = Path::new("Sources").join(target);
+    tokio::fs::create_dir_all(dir.join(&sources)).await?;
+
+    let mut written = Vec::new();
+    let manifest = PathBuf::from("Package.swift");
+    tokio::fs::write(dir.join(&manifest), package_manifest(infrastructure, !implementation.test_suite.test_files.is_empty())).await?;
+    written.push(manifest);
+    for artifact in implementation.review_artifacts() {
+        let path = sources.join(relative(&artifact.name)?);
+        tokio::fs::write(dir.join(&path), artifact.content).await?;
+        written.push(path);
+    }
+    if !implementation.test_suite.test_files.is_empty() {
+        let tests = Path::new("Tests").join(format!("{}Tests", target));
+        tokio::fs::create_dir_all(dir.join(&tests)).await?;
+        for file in &implementation.test_suite.test_files {
+            let path = tests.join(relative(&file.name)?);
+            tokio::fs::write(dir.join(&path), &file.generated_code).await?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+/// `path` when it stays inside the project
+fn relative(path: &str) -> Result<&Path> {
+    let path = Path::new(path);
+    if path.components().all(|component| matches!(component, std::path::Component::Normal(_))) {
+        Ok(path)
+    } else {
+        Err(AxiomMCPError::generation(
+            "project_build", "materialize project", format!("'{}' is outside the project", path.display()),
+        ))
+    }
+}
+
+/// A `Package.swift` with one target per spec target, the first holding
+/// the generated sources, and a test target for the first when `tests`
+pub fn package_manifest(infrastructure: &InfrastructureSpecification, tests: bool) -> String {
+    let configuration = &infrastructure.build_configuration;
+    let target = target_name(infrastructure);
+    let dependencies: Vec<String> = infrastructure
+        .dependencies
+        .iter()
+        .map(|dependency| match dependency.contains("://") {
+            true => format!("        .package(url: \"{}\", branch: \"main\"),", dependency),
+            false => format!("        .package(path: \"{}\"),", dependency),
+        })
+        .collect();
+    let mut targets: Vec<String> = if configuration.targets.is_empty() {
+        vec![format!("        .target(name: \"{}\", path: \"Sources/{}\"),", target, target)]
+    } else {
+        configuration.targets.iter().map(|name| format!("        .target(name: \"{}\", path: \"Sources/{}\"),", name, name)).collect()
+    };
+    if tests {
+        targets.push(format!(
+            "        .testTarget(name: \"{}Tests\", dependencies: [\"{}\"], path: \"Tests/{}Tests\"),", target, target, target
+        ));
+    }
+
+    format!(
+        "// swift-tools-version:{}\nimport PackageDescription\n\nlet package = Package(\n    name: \"{}\",\n    platforms: [.iOS(.v17)],\n    products: [.library(name: \"{}\", targets: [\"{}\"])],\n    dependencies: [\n{}\n    ],\n    targets: [\n{}\n    ]\n)\n",
+        configuration.swift_version,
+        target,
+        target,
+        target,
+        dependencies.join("\n"),
+        targets.join("\n"),
+    )
+}
+
+/// Errors and warnings in `xcodebuild` or `swift build` output, once each
+/// even though both repeat them; paths under `project_dir` become relative
+pub fn parse_build_diagnostics(log: &str, project_dir: &Path) -> Vec<BuildDiagnostic> {
+    let mut diagnostics: Vec<BuildDiagnostic> = Vec::new();
+    for diagnostic in log.lines().filter_map(|line| parse_diagnostic(line.trim_end(), project_dir)) {
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+fn parse_diagnostic(line: &str, project_dir: &Path) -> Option<BuildDiagnostic> {
+    // Whichever marker comes first, so a warning quoting an error stays a warning
+    let (severity, at, marker) = [(DiagnosticSeverity::Error, "error: "), (DiagnosticSeverity::Warning, "warning: ")]
+        .into_iter()
+        .filter_map(|(severity, marker)| line.find(marker).map(|at| (severity, at, marker)))
+        .min_by_key(|(_, at, _)| *at)?;
+    let location = line[..at].trim_end();
+    let location = location.strip_suffix(':').unwrap_or(location).trim();
+    let message = line[at + marker.len()..].trim();
+    if message.is_empty() {
+        return None;
+    }
+
+    // `path:line:column`, `path:line`, a bare tool name, or nothing
+    let mut parts = location.rsplitn(3, ':');
+    let (file, line_number, column) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(column), Some(line), Some(file)) if column.parse::<usize>().is_ok() && line.parse::<usize>().is_ok() => {
+            (Some(file), line.parse().ok(), column.parse().ok())
+        }
+        (Some(line), Some(file), _) if line.parse::<usize>().is_ok() => (Some(file), line.parse().ok(), None),
+        _ if location.starts_with('/') => (Some(location), None, None),
+        _ => (None, None, None),
+    };
+    let file = file.map(|file| {
+        Path::new(file).strip_prefix(project_dir).map(|path| path.display().to_string()).unwrap_or_else(|_| file.to_string())
+    });
+    Some(BuildDiagnostic { severity, file, line: line_number, column, message: message.to_string() })
+}
+
+/// The error lines of `log` with `context_lines` on either side, gaps
+/// marked; the last `context_lines` lines when nothing failed
+pub fn truncate_build_log(log: &str, context_lines: usize) -> String {
+    let lines: Vec<&str> = log.lines().collect();
+    let errors: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.contains("error: ") || line.starts_with("** BUILD FAILED"))
+        .map(|(index, _)| index)
+        .collect();
+    if errors.is_empty() {
+        let start = lines.len().saturating_sub(context_lines);
+        let mut kept = Vec::new();
+        if start > 0 {
+            kept.push(format!("... {} lines omitted", start));
+        }
+        kept.extend(lines[start..].iter().map(|line| line.to_string()));
+        return kept.join("\n");
+    }
+
+    let mut keep = vec![false; lines.len()];
+    for error in errors {
+        let from = error.saturating_sub(context_lines);
+        let to = (error + context_lines).min(lines.len() - 1);
+        keep[from..=to].iter_mut().for_each(|kept| *kept = true);
+    }
+    let mut kept = Vec::new();
+    let mut omitted = 0;
+    for (line, keep) in lines.iter().zip(keep) {
+        if keep {
+            if omitted > 0 {
+                kept.push(format!("... {} lines omitted", omitted));
+                omitted = 0;
+            }
+            kept.push(line.to_string());
+        } else {
+            omitted += 1;
+        }
+    }
+    if omitted > 0 {
+        kept.push(format!("... {} lines omitted", omitted));
+    }
+    kept.join("\n")
+}
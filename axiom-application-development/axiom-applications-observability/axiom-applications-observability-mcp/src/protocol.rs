@@ -0,0 +1,163 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::tools::ToolResult;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+    pub jsonrpc: String,
+    /// Absent for notifications
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), data: None }
+    }
+
+    /// The protocol error for a tool call that never reached its tool:
+    /// an unknown name or arguments failing the input schema. Errors from
+    /// running the tool are reported in its result instead.
+    pub fn for_tool_call(error: &AxiomMCPError) -> Option<Self> {
+        let data = match error {
+            AxiomMCPError::UnknownTool(tool) => json!({ "tool": tool }),
+            AxiomMCPError::InvalidToolArguments { tool, violations } => json!({ "tool": tool, "violations": violations }),
+            _ => return None,
+        };
+        Some(Self { code: INVALID_PARAMS, message: error.to_string(), data: Some(data) })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    /// A response carrying `result`, or an internal error when it does not
+    /// serialize
+    pub fn success(id: Value, result: &impl Serialize) -> Self {
+        match serde_json::to_value(result) {
+            Ok(result) => Self { jsonrpc: JSONRPC_VERSION.to_string(), id, result: Some(result), error: None },
+            Err(error) => Self::failure(id, JsonRpcError::new(INTERNAL_ERROR, error.to_string())),
+        }
+    }
+
+    pub fn failure(id: Value, error: JsonRpcError) -> Self {
+        Self { jsonrpc: JSONRPC_VERSION.to_string(), id, result: None, error: Some(error) }
+    }
+}
+
+/// A message to the client that expects no reply, such as a metrics update
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: impl Into<String>, params: &impl Serialize) -> Result<Self> {
+        Ok(Self { jsonrpc: JSONRPC_VERSION.to_string(), method: method.into(), params: serde_json::to_value(params)? })
+    }
+}
+
+/// Params of `tools/call`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallToolParams {
+    pub name: String,
+    #[serde(default = "no_arguments")]
+    pub arguments: Value,
+}
+
+fn no_arguments() -> Value {
+    json!({})
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolContent {
+    Text { text: String },
+    /// A file the tool wrote, for the client to open or fetch
+    ResourceLink {
+        uri: String,
+        name: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+}
+
+impl ToolContent {
+    fn file_link(path: &Path, mime_type: &str) -> Self {
+        ToolContent::ResourceLink {
+            uri: format!("file://{}", path.display()),
+            name: path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            mime_type: mime_type.to_string(),
+        }
+    }
+}
+
+/// Result of `tools/call`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToolResult {
+    pub content: Vec<ToolContent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<Value>,
+    pub is_error: bool,
+}
+
+impl CallToolResult {
+    /// The result's summary, with the result itself as structured content
+    pub fn from_result(result: &ToolResult) -> Self {
+        let mut content = vec![ToolContent::Text { text: result.summary() }];
+        if let ToolResult::CycleReport(report) = result {
+            content.push(ToolContent::file_link(&report.markdown_path, "text/markdown"));
+            if let Some(path) = &report.html_path {
+                content.push(ToolContent::file_link(path, "text/html"));
+            }
+        }
+        Self {
+            content,
+            structured_content: serde_json::to_value(result).ok(),
+            is_error: !result.is_success(),
+        }
+    }
+
+    /// A tool that ran and failed, with the error's category and whether
+    /// calling the tool again may help as structured content
+    pub fn from_error(error: &AxiomMCPError) -> Self {
+        Self {
+            content: vec![ToolContent::Text { text: error.to_string() }],
+            structured_content: serde_json::to_value(error.report()).ok(),
+            is_error: true,
+        }
+    }
+}
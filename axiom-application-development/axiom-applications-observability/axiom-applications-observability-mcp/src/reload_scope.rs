@@ -0,0 +1,384 @@
+use crate::hot_reload::{ChangeType, ReloadBatch};
+use crate::types::GeneratedCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeclarationKind {
+    Struct,
+    Class,
+    Enum,
+    Actor,
+    Protocol,
+    Extension,
+}
+
+impl DeclarationKind {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "struct" => Some(DeclarationKind::Struct),
+            "class" => Some(DeclarationKind::Class),
+            "enum" => Some(DeclarationKind::Enum),
+            "actor" => Some(DeclarationKind::Actor),
+            "protocol" => Some(DeclarationKind::Protocol),
+            "extension" => Some(DeclarationKind::Extension),
+            _ => None,
+        }
+    }
+}
+
+/// Axiom layer a type belongs to, judged by what it conforms to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentKind {
+    Presentation,
+    Context,
+    Client,
+    Other,
+}
+
+/// A top-level type declaration or extension in a Swift file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwiftDeclaration {
+    /// The declared type, or the extended one for an extension
+    pub name: String,
+    pub kind: DeclarationKind,
+    pub conformances: Vec<String>,
+}
+
+impl SwiftDeclaration {
+    pub fn component_kind(&self) -> ComponentKind {
+        let conforms = |names: &[&str]| self.conformances.iter().any(|c| names.contains(&c.as_str()));
+        if conforms(&["View"]) {
+            ComponentKind::Presentation
+        } else if conforms(&["AxiomContext", "ObservableObject"]) || self.name.ends_with("Context") {
+            ComponentKind::Context
+        } else if self.kind == DeclarationKind::Actor || conforms(&["AxiomClient"]) {
+            ComponentKind::Client
+        } else {
+            ComponentKind::Other
+        }
+    }
+}
+
+/// Top-level declarations of a Swift file, in source order
+///
+/// A lightweight scan rather than a parse: comments and string literals
+/// are skipped, and only declarations outside any braces are reported, so
+/// nested types belong to the type that encloses them.
+pub fn top_level_declarations(source: &str) -> Vec<SwiftDeclaration> {
+    let tokens = top_level_tokens(source);
+    let mut declarations = Vec::new();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let Some(kind) = DeclarationKind::from_keyword(&tokens[index]) else {
+            index += 1;
+            continue;
+        };
+        let Some(name) = tokens.get(index + 1).filter(|token| is_identifier(token)) else {
+            index += 1;
+            continue;
+        };
+
+        // Header up to the body: generic parameters, conformances, then constraints
+        let mut conformances = Vec::new();
+        let mut generic_depth = 0;
+        let mut in_conformances = false;
+        let mut in_where_clause = false;
+        index += 2;
+        while index < tokens.len() && tokens[index] != "{" {
+            match tokens[index].as_str() {
+                "<" => generic_depth += 1,
+                ">" => generic_depth -= 1,
+                ":" if generic_depth == 0 && !in_where_clause => in_conformances = true,
+                "where" => {
+                    in_conformances = false;
+                    in_where_clause = true;
+                }
+                token if in_conformances && generic_depth == 0 && is_identifier(token) => {
+                    conformances.push(token.to_string());
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+
+        declarations.push(SwiftDeclaration {
+            name: name.clone(),
+            kind,
+            conformances,
+        });
+    }
+
+    declarations
+}
+
+/// Identifiers and punctuation outside braces, with `{` marking where a body starts
+fn top_level_tokens(source: &str) -> Vec<String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && next == Some('*') {
+            // Swift block comments nest
+            let mut nesting = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                    nesting += 1;
+                    i += 2;
+                } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                    nesting -= 1;
+                    i += 2;
+                    if nesting == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            continue;
+        }
+        if c == '"' {
+            i = skip_string(&chars, i);
+            continue;
+        }
+
+        match c {
+            '{' => {
+                if depth == 0 {
+                    tokens.push("{".to_string());
+                }
+                depth += 1;
+                i += 1;
+            }
+            '}' => {
+                depth = depth.saturating_sub(1);
+                i += 1;
+            }
+            _ if depth > 0 => i += 1,
+            _ if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                    i += 1;
+                }
+                tokens.push(chars[start..i].iter().collect());
+            }
+            ':' | '<' | '>' | ',' => {
+                tokens.push(c.to_string());
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    tokens
+}
+
+/// Index just past the string literal starting at `start`
+fn skip_string(chars: &[char], start: usize) -> usize {
+    let multiline = chars.get(start + 1) == Some(&'"') && chars.get(start + 2) == Some(&'"');
+    let mut i = if multiline { start + 3 } else { start + 1 };
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '"' if multiline => {
+                if chars.get(i + 1) == Some(&'"') && chars.get(i + 2) == Some(&'"') {
+                    return i + 3;
+                }
+                i += 1;
+            }
+            '"' => return i + 1,
+            '\n' if !multiline => return i + 1,
+            _ => i += 1,
+        }
+    }
+    i
+}
+
+fn is_identifier(token: &str) -> bool {
+    token.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReloadScope {
+    /// Only the listed components are reloaded; navigation state elsewhere survives
+    Targeted,
+    Full,
+}
+
+/// The scope of one reload and why it was chosen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScopeDecision {
+    pub scope: ReloadScope,
+    /// Type names of the components to reload; empty for a full reload
+    pub component_ids: Vec<String>,
+    pub reason: String,
+}
+
+impl ScopeDecision {
+    pub fn full(reason: impl Into<String>) -> Self {
+        Self {
+            scope: ReloadScope::Full,
+            component_ids: Vec::new(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Which components each watched Swift file defines
+///
+/// Kept up to date as files are generated or change. A batch is reloaded
+/// in place only when every file in it is known, still declares the same
+/// types as before, and maps unambiguously to components; anything else
+/// falls back to a full reload.
+#[derive(Debug, Default)]
+pub struct ComponentIndex {
+    files: Mutex<HashMap<PathBuf, Vec<SwiftDeclaration>>>,
+}
+
+impl ComponentIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index `source` as the current content of `path`
+    pub fn index_file(&self, path: impl Into<PathBuf>, source: &str) {
+        self.files.lock().unwrap().insert(path.into(), top_level_declarations(source));
+    }
+
+    /// Index code the generator wrote to `path`
+    pub fn record_generated(&self, path: impl Into<PathBuf>, generated: &GeneratedCode) {
+        self.index_file(path, &generated.generated_code);
+    }
+
+    pub fn forget(&self, path: &Path) {
+        self.files.lock().unwrap().remove(path);
+    }
+
+    pub fn declarations(&self, path: &Path) -> Option<Vec<SwiftDeclaration>> {
+        self.files.lock().unwrap().get(path).cloned()
+    }
+
+    /// Component kinds of every indexed type, by name
+    pub fn components(&self) -> BTreeMap<String, ComponentKind> {
+        let files = self.files.lock().unwrap();
+        files.values()
+            .flatten()
+            .filter(|declaration| declaration.kind != DeclarationKind::Extension)
+            .map(|declaration| (declaration.name.clone(), declaration.component_kind()))
+            .collect()
+    }
+
+    /// Update the index with the new content of each changed file and
+    /// decide how far the reload must reach
+    ///
+    /// `sources` holds the content of every added or modified file that
+    /// could be read.
+    pub fn apply(&self, batch: &ReloadBatch, sources: &HashMap<PathBuf, String>) -> ScopeDecision {
+        let mut files = self.files.lock().unwrap();
+        let mut full_reason = None;
+        let mut changed = Vec::new();
+
+        for change in batch.changes() {
+            let name = file_name(&change.path);
+            let declarations = sources.get(&change.path).map(|source| top_level_declarations(source));
+            let previous = match declarations {
+                Some(declarations) => files.insert(change.path.clone(), declarations),
+                None => files.remove(&change.path),
+            };
+
+            let reason = match (change.change_type, &previous) {
+                (ChangeType::Added, _) => Some(format!("{} was added", name)),
+                (ChangeType::Deleted, _) => Some(format!("{} was deleted", name)),
+                (ChangeType::Modified, _) if !sources.contains_key(&change.path) => {
+                    Some(format!("{} could not be read", name))
+                }
+                (ChangeType::Modified, None) => Some(format!("{} was not indexed before this change", name)),
+                (ChangeType::Modified, Some(previous)) if names(previous) != names(&files[&change.path]) => {
+                    Some(format!("the types declared in {} changed", name))
+                }
+                (ChangeType::Modified, Some(_)) => None,
+            };
+            match reason {
+                Some(reason) => {
+                    full_reason.get_or_insert(reason);
+                }
+                None => changed.push(change.path.clone()),
+            }
+        }
+
+        // Every file is indexed either way, so the next change is judged on current content
+        if let Some(reason) = full_reason {
+            return ScopeDecision::full(reason);
+        }
+
+        let mut defined_in: HashMap<&str, Vec<&Path>> = HashMap::new();
+        for (path, declarations) in files.iter() {
+            for declaration in declarations.iter().filter(|d| d.kind != DeclarationKind::Extension) {
+                defined_in.entry(declaration.name.as_str()).or_default().push(path.as_path());
+            }
+        }
+
+        let mut component_ids = BTreeSet::new();
+        for path in &changed {
+            let name = file_name(path);
+            let declarations = &files[path];
+            if declarations.is_empty() {
+                return ScopeDecision::full(format!("{} declares no types", name));
+            }
+            for declaration in declarations {
+                match defined_in.get(declaration.name.as_str()).map(Vec::as_slice) {
+                    Some([_]) => {}
+                    Some(paths) => {
+                        let mut paths: Vec<String> = paths.iter().map(|path| path.display().to_string()).collect();
+                        paths.sort();
+                        return ScopeDecision::full(format!(
+                            "{} is declared in more than one file ({})", declaration.name, paths.join(", ")
+                        ));
+                    }
+                    None => {
+                        return ScopeDecision::full(format!(
+                            "{} extends {}, which no indexed file declares", name, declaration.name
+                        ));
+                    }
+                }
+                component_ids.insert(declaration.name.clone());
+            }
+        }
+
+        let component_ids: Vec<String> = component_ids.into_iter().collect();
+        let changed: Vec<String> = changed.iter().map(|path| file_name(path)).collect();
+        ScopeDecision {
+            scope: ReloadScope::Targeted,
+            reason: format!("changes in {} are confined to {}", changed.join(", "), component_ids.join(", ")),
+            component_ids,
+        }
+    }
+}
+
+fn names(declarations: &[SwiftDeclaration]) -> BTreeSet<(&str, DeclarationKind)> {
+    declarations.iter().map(|declaration| (declaration.name.as_str(), declaration.kind)).collect()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -0,0 +1,229 @@
+use crate::code_generation::{NavigationSpec, PersistenceSpec};
+use crate::complete_development_loop::{
+    clarifying_questions, infer_actions, infer_entities, infer_navigation, infer_persistence, infer_screens,
+    navigation_for, persistence_for, requirement_confidence,
+};
+use crate::connection::ConnectionState;
+use crate::error::{AxiomMCPError, ErrorCategory, Result};
+use crate::intelligence::IntelligenceClient;
+use crate::intelligence_protocol::RequirementInsights;
+use crate::traceability::requirement_fragments;
+use crate::types::RequirementAnalysis;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long the intelligence server has to analyze a requirement
+pub const REQUIREMENT_ANALYSIS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Taken off the confidence of a rule-based analysis, which matches words
+/// rather than understanding them
+pub const HEURISTIC_CONFIDENCE_PENALTY: f64 = 15.0;
+
+const RECOMMENDED_APPROACH: &str = "Axiom Context-Presentation-Client";
+
+/// What produced a requirement analysis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyzerKind {
+    Intelligence,
+    /// Keyword and pattern rules run offline; analyses checkpointed
+    /// before the analyzer was recorded all came from these
+    #[default]
+    Heuristic,
+}
+
+impl AnalyzerKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            AnalyzerKind::Intelligence => "intelligence server",
+            AnalyzerKind::Heuristic => "offline rules (heuristic)",
+        }
+    }
+}
+
+/// Which analyzer requirements go to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementAnalyzerMode {
+    /// The intelligence server while it is reachable and offers analysis,
+    /// the offline rules otherwise
+    #[default]
+    Auto,
+    /// Only the intelligence server; analysis fails while it is down
+    Intelligence,
+    /// Only the offline rules, as in CI without an intelligence server
+    Offline,
+}
+
+/// Reads the entities, screens and actions a natural language requirement
+/// describes. The analysis says which analyzer produced it.
+#[async_trait]
+pub trait RequirementAnalyzer: Send + Sync + std::fmt::Debug {
+    async fn analyze(&self, requirement: &str) -> Result<RequirementAnalysis>;
+
+    /// Whether it is worth asking right now
+    fn available(&self) -> bool {
+        true
+    }
+}
+
+/// Rule-based analysis: keyword tables for actions and what is saved,
+/// `... screen`, `... page` and `... list` patterns for screens and what
+/// they show, and plurals folded to
+/// singular entity names. Needs nothing running, and is less sure of
+/// itself than the intelligence server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineRequirementAnalyzer;
+
+#[async_trait]
+impl RequirementAnalyzer for OfflineRequirementAnalyzer {
+    async fn analyze(&self, requirement: &str) -> Result<RequirementAnalysis> {
+        let persistence = infer_persistence(requirement);
+        let navigation = infer_navigation(requirement);
+        let (entities, screens, actions) = (infer_entities(requirement), infer_screens(requirement), infer_actions(requirement));
+        Ok(RequirementAnalysis {
+            requirement_id: uuid::Uuid::new_v4().to_string(),
+            analysis_timestamp: chrono::Utc::now(),
+            confidence_score: (requirement_confidence(requirement) - HEURISTIC_CONFIDENCE_PENALTY).max(0.0),
+            complexity_estimate: complexity(requirement).to_string(),
+            recommended_approach: RECOMMENDED_APPROACH.to_string(),
+            estimated_components: estimated_components(&persistence, &navigation),
+            persistence,
+            navigation,
+            review_feedback: vec![],
+            fragments: requirement_fragments(&entities, &screens, &actions),
+            entities,
+            screens,
+            actions,
+            clarifying_questions: clarifying_questions(requirement),
+            analyzer: AnalyzerKind::Heuristic,
+        })
+    }
+}
+
+/// Asks the intelligence server, deriving the persistence and navigation
+/// specs from what it reports
+#[derive(Debug)]
+pub struct IntelligenceRequirementAnalyzer {
+    client: Arc<IntelligenceClient>,
+    timeout: Duration,
+}
+
+impl IntelligenceRequirementAnalyzer {
+    pub fn new(client: Arc<IntelligenceClient>) -> Self {
+        Self { client, timeout: REQUIREMENT_ANALYSIS_TIMEOUT }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl RequirementAnalyzer for IntelligenceRequirementAnalyzer {
+    async fn analyze(&self, requirement: &str) -> Result<RequirementAnalysis> {
+        let insights = self.client.analyze_requirement(requirement, self.timeout).await?;
+        Ok(analysis_from_insights(insights))
+    }
+
+    fn available(&self) -> bool {
+        self.client.connection_status().state == ConnectionState::Connected
+    }
+}
+
+/// The server's reading of a requirement as an analysis
+pub fn analysis_from_insights(insights: RequirementInsights) -> RequirementAnalysis {
+    let persistence = insights.persisted_entity.map(persistence_for);
+    let navigation = navigation_for(&insights.screens);
+    RequirementAnalysis {
+        requirement_id: uuid::Uuid::new_v4().to_string(),
+        analysis_timestamp: chrono::Utc::now(),
+        confidence_score: insights.confidence_score,
+        complexity_estimate: insights.complexity,
+        recommended_approach: RECOMMENDED_APPROACH.to_string(),
+        estimated_components: estimated_components(&persistence, &navigation),
+        persistence,
+        navigation,
+        review_feedback: vec![],
+        fragments: requirement_fragments(&insights.entities, &insights.screens, &insights.actions),
+        entities: insights.entities,
+        screens: insights.screens,
+        actions: insights.actions,
+        clarifying_questions: vec![],
+        analyzer: AnalyzerKind::Intelligence,
+    }
+}
+
+/// `primary` while it is available, the offline rules when it is not or
+/// when it cannot be reached or does not offer analysis
+#[derive(Debug)]
+pub struct AutoRequirementAnalyzer {
+    primary: Arc<dyn RequirementAnalyzer>,
+    offline: OfflineRequirementAnalyzer,
+}
+
+impl AutoRequirementAnalyzer {
+    pub fn new(primary: Arc<dyn RequirementAnalyzer>) -> Self {
+        Self { primary, offline: OfflineRequirementAnalyzer }
+    }
+}
+
+#[async_trait]
+impl RequirementAnalyzer for AutoRequirementAnalyzer {
+    async fn analyze(&self, requirement: &str) -> Result<RequirementAnalysis> {
+        if !self.primary.available() {
+            tracing::info!("Intelligence server unavailable; analyzing the requirement with offline rules");
+            return self.offline.analyze(requirement).await;
+        }
+        match self.primary.analyze(requirement).await {
+            Ok(analysis) => Ok(analysis),
+            Err(e) if matches!(e.category(), ErrorCategory::Connectivity | ErrorCategory::Configuration) => {
+                tracing::warn!("Requirement analysis fell back to offline rules: {}", e);
+                self.offline.analyze(requirement).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// The analyzer `mode` selects, given the intelligence client when the
+/// intelligence capability is on
+pub fn requirement_analyzer(
+    mode: RequirementAnalyzerMode,
+    intelligence: Option<Arc<IntelligenceClient>>,
+) -> Result<Arc<dyn RequirementAnalyzer>> {
+    let intelligence = intelligence.map(|client| Arc::new(IntelligenceRequirementAnalyzer::new(client)));
+    Ok(match (mode, intelligence) {
+        (RequirementAnalyzerMode::Offline, _) | (RequirementAnalyzerMode::Auto, None) => Arc::new(OfflineRequirementAnalyzer),
+        (RequirementAnalyzerMode::Auto, Some(intelligence)) => Arc::new(AutoRequirementAnalyzer::new(intelligence)),
+        (RequirementAnalyzerMode::Intelligence, Some(intelligence)) => intelligence,
+        (RequirementAnalyzerMode::Intelligence, None) => {
+            return Err(AxiomMCPError::configuration(
+                "requirement_analyzer",
+                "The intelligence analyzer needs the intelligence_analysis capability",
+            ));
+        }
+    })
+}
+
+fn complexity(requirement: &str) -> &'static str {
+    match requirement.len() {
+        0..=49 => "Simple",
+        50..=149 => "Medium",
+        _ => "Complex",
+    }
+}
+
+fn estimated_components(persistence: &Option<PersistenceSpec>, navigation: &Option<NavigationSpec>) -> Vec<String> {
+    let mut components = vec!["Context".to_string(), "Presentation".to_string(), "Client".to_string()];
+    if persistence.is_some() {
+        components.push("Persistence".to_string());
+    }
+    if navigation.is_some() {
+        components.push("Navigation".to_string());
+    }
+    components
+}
@@ -0,0 +1,194 @@
+use crate::error::{AxiomMCPError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{broadcast, oneshot};
+
+/// How long a cycle waits for answers unless configured otherwise
+pub const DEFAULT_CLARIFICATION_TIMEOUT_MS: u64 = 30 * 60 * 1000;
+
+/// Clarification requests buffered per subscriber before it starts lagging
+const CLARIFICATION_EVENT_CAPACITY: usize = 16;
+
+/// Something the requirement leaves open, and what is assumed if nobody says
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClarifyingQuestion {
+    /// `screen_count`, `entities` or `actions`
+    pub id: String,
+    pub question: String,
+    /// Phrased as requirement text, since answers are read with the requirement
+    pub suggested_default: String,
+    #[serde(default)]
+    pub answer: Option<String>,
+}
+
+impl ClarifyingQuestion {
+    pub fn answer_or_default(&self) -> &str {
+        self.answer.as_deref().unwrap_or(&self.suggested_default)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClarificationMode {
+    /// Wait for `provide_requirement_answers`
+    Interactive,
+    /// Go ahead with the suggested defaults, for CI
+    UseDefaults,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClarificationPolicy {
+    pub mode: ClarificationMode,
+    /// After this long without answers the cycle stops; its checkpoint can
+    /// be resumed to ask again
+    pub timeout_ms: u64,
+}
+
+impl Default for ClarificationPolicy {
+    fn default() -> Self {
+        Self { mode: ClarificationMode::Interactive, timeout_ms: DEFAULT_CLARIFICATION_TIMEOUT_MS }
+    }
+}
+
+/// A cycle waiting for answers before it analyzes its requirement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClarificationRequest {
+    pub cycle_id: String,
+    pub requirement: String,
+    pub confidence_score: f64,
+    pub questions: Vec<ClarifyingQuestion>,
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Answers by question id; a question left out takes its suggested default
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementAnswers {
+    pub cycle_id: String,
+    #[serde(default)]
+    pub answers: BTreeMap<String, String>,
+}
+
+struct PendingClarification {
+    request: ClarificationRequest,
+    reply: oneshot::Sender<Vec<ClarifyingQuestion>>,
+}
+
+/// Where development cycles wait for answers to what their requirement
+/// leaves open
+pub struct RequirementClarifier {
+    policy: ClarificationPolicy,
+    pending: Mutex<BTreeMap<String, PendingClarification>>,
+    requests: broadcast::Sender<ClarificationRequest>,
+}
+
+impl std::fmt::Debug for RequirementClarifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequirementClarifier")
+            .field("policy", &self.policy)
+            .field("pending", &self.pending.lock().unwrap().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl RequirementClarifier {
+    pub fn new(policy: ClarificationPolicy) -> Self {
+        Self {
+            policy,
+            pending: Mutex::new(BTreeMap::new()),
+            requests: broadcast::channel(CLARIFICATION_EVENT_CAPACITY).0,
+        }
+    }
+
+    pub fn policy(&self) -> &ClarificationPolicy {
+        &self.policy
+    }
+
+    /// Requests as cycles start waiting, for hosts that forward them to
+    /// clients as notifications
+    pub fn subscribe(&self) -> broadcast::Receiver<ClarificationRequest> {
+        self.requests.subscribe()
+    }
+
+    /// Requests still waiting for answers, by cycle id
+    pub fn pending(&self) -> Vec<ClarificationRequest> {
+        self.pending.lock().unwrap().values().map(|pending| pending.request.clone()).collect()
+    }
+
+    /// Wait for answers to the questions of `request`
+    pub async fn clarify(&self, request: ClarificationRequest) -> Result<Vec<ClarifyingQuestion>> {
+        if self.policy.mode == ClarificationMode::UseDefaults {
+            tracing::info!("Cycle {} goes ahead with the defaults for {} questions", request.cycle_id, request.questions.len());
+            return Ok(answered(request.questions, &BTreeMap::new()));
+        }
+
+        let (reply, answers) = oneshot::channel();
+        let cycle_id = request.cycle_id.clone();
+        self.pending.lock().unwrap().insert(cycle_id.clone(), PendingClarification { request: request.clone(), reply });
+        tracing::info!("Cycle {} is waiting for answers to {} questions", cycle_id, request.questions.len());
+        // Nobody may be subscribed; the request is still listed as pending
+        let _ = self.requests.send(request);
+
+        let timeout = Duration::from_millis(self.policy.timeout_ms);
+        let answers = tokio::time::timeout(timeout, answers).await;
+        self.pending.lock().unwrap().remove(&cycle_id);
+        match answers {
+            Ok(Ok(questions)) => Ok(questions),
+            Ok(Err(_)) => Err(AxiomMCPError::internal("requirement_clarification", "await answers", format!(
+                "Clarification of cycle {} was abandoned", cycle_id
+            ))),
+            Err(_) => Err(AxiomMCPError::validation("requirement_clarification", "await answers", format!(
+                "Cycle {} got no answers within {:?}; resume the cycle to be asked again",
+                cycle_id, timeout
+            ))),
+        }
+    }
+
+    /// Answer the questions cycle `answers.cycle_id` is waiting on, which
+    /// it then goes ahead with
+    pub fn provide_answers(&self, answers: RequirementAnswers) -> Result<ClarificationRequest> {
+        let mut pending = self.pending.lock().unwrap();
+        let waiting = pending.get(&answers.cycle_id).ok_or_else(|| {
+            AxiomMCPError::validation(
+                "requirement_clarification", "provide answers", format!("Cycle {} is not waiting for answers", answers.cycle_id),
+            )
+        })?;
+        for (id, answer) in &answers.answers {
+            if !waiting.request.questions.iter().any(|question| &question.id == id) {
+                return Err(AxiomMCPError::validation(
+                    "requirement_clarification", "provide answers", format!("Cycle {} did not ask '{}'", answers.cycle_id, id),
+                ));
+            }
+            if answer.trim().is_empty() {
+                return Err(AxiomMCPError::validation(
+                    "requirement_clarification", "provide answers", format!("The answer to '{}' cannot be empty", id),
+                ));
+            }
+        }
+
+        let waiting = pending.remove(&answers.cycle_id).expect("checked above");
+        drop(pending);
+        let mut request = waiting.request;
+        request.questions = answered(request.questions, &answers.answers);
+        // The cycle timed out between the lookup and now
+        if waiting.reply.send(request.questions.clone()).is_err() {
+            return Err(AxiomMCPError::validation("requirement_clarification", "provide answers", format!(
+                "Cycle {} stopped waiting for answers", answers.cycle_id
+            )));
+        }
+        Ok(request)
+    }
+}
+
+/// `questions` with the given answers, and the suggested default for the rest
+pub fn answered(questions: Vec<ClarifyingQuestion>, answers: &BTreeMap<String, String>) -> Vec<ClarifyingQuestion> {
+    questions
+        .into_iter()
+        .map(|question| {
+            let answer = answers.get(&question.id).cloned().unwrap_or_else(|| question.answer_or_default().to_string());
+            ClarifyingQuestion { answer: Some(answer), ..question }
+        })
+        .collect()
+}
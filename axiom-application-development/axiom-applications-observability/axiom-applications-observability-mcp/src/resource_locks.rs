@@ -0,0 +1,163 @@
+use crate::error::{AxiomMCPError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// How long a tool call waits for the resources it needs unless configured otherwise
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Something two tool calls must not use at the same time
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum Resource {
+    /// One simulator, by UDID
+    Simulator(String),
+    /// Every simulator, for tools that pick or create their devices as they go
+    AllSimulators,
+    /// The project directory cycles generate into and branch in
+    Project(PathBuf),
+    /// The connection reloads are sent to the app over
+    HotReloadChannel,
+}
+
+impl Resource {
+    /// Whether holding one rules out holding the other
+    pub fn conflicts_with(&self, other: &Resource) -> bool {
+        match (self, other) {
+            (Resource::AllSimulators, Resource::Simulator(_)) | (Resource::Simulator(_), Resource::AllSimulators) => true,
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Resource::Simulator(udid) => write!(f, "simulator {}", udid),
+            Resource::AllSimulators => f.write_str("all simulators"),
+            Resource::Project(path) => write!(f, "project {}", path.display()),
+            Resource::HotReloadChannel => f.write_str("hot reload channel"),
+        }
+    }
+}
+
+/// A resource a tool declares it needs, made concrete by the arguments of
+/// each call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceNeed {
+    /// The simulator the named string argument selects
+    Simulator(&'static str),
+    AllSimulators,
+    Project,
+    HotReloadChannel,
+}
+
+/// A tool call holding a resource
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub resource: Resource,
+    pub tool: String,
+    /// Tells apart concurrent calls of the same tool
+    pub call_id: u64,
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct LockState {
+    held: Vec<LockHolder>,
+    next_call_id: u64,
+}
+
+/// Exclusive locks on resources, taken all at once for a tool call, so
+/// calls needing a common resource queue while the rest run in parallel
+#[derive(Debug)]
+pub struct ResourceLocks {
+    timeout: Duration,
+    state: Arc<Mutex<LockState>>,
+    released: Arc<Notify>,
+}
+
+impl Default for ResourceLocks {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCK_TIMEOUT)
+    }
+}
+
+impl ResourceLocks {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, state: Arc::default(), released: Arc::new(Notify::new()) }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Resources held right now, oldest first
+    pub fn holders(&self) -> Vec<LockHolder> {
+        self.state.lock().unwrap().held.clone()
+    }
+
+    /// Take every one of `resources` for a call of `tool`, waiting for the
+    /// calls holding any of them. Nothing is held while waiting, so two
+    /// calls each needing what the other holds cannot deadlock. Gives up
+    /// after the timeout with a busy error naming the holder.
+    pub async fn acquire(&self, tool: &str, mut resources: Vec<Resource>) -> Result<ResourceGuard> {
+        resources.sort();
+        resources.dedup();
+        let started = tokio::time::Instant::now();
+        let deadline = started + self.timeout;
+        loop {
+            // Created before checking, so a release in between still wakes it
+            let released = self.released.notified();
+            {
+                let mut state = self.state.lock().unwrap();
+                let blocking = state.held.iter().find(|holder| {
+                    resources.iter().any(|resource| resource.conflicts_with(&holder.resource))
+                });
+                match blocking {
+                    None => return Ok(self.take(&mut state, tool, resources)),
+                    Some(holder) if tokio::time::Instant::now() >= deadline => {
+                        return Err(AxiomMCPError::ResourceBusy {
+                            holder: holder.clone(),
+                            waited_ms: started.elapsed().as_millis() as u64,
+                        });
+                    }
+                    Some(holder) => tracing::debug!("{} waits for {} held by {}", tool, holder.resource, holder.tool),
+                }
+            }
+            let _ = tokio::time::timeout_at(deadline, released).await;
+        }
+    }
+
+    fn take(&self, state: &mut LockState, tool: &str, resources: Vec<Resource>) -> ResourceGuard {
+        state.next_call_id += 1;
+        let call_id = state.next_call_id;
+        let since = Utc::now();
+        state.held.extend(resources.into_iter().map(|resource| LockHolder {
+            resource,
+            tool: tool.to_string(),
+            call_id,
+            since,
+        }));
+        ResourceGuard { call_id, state: Arc::clone(&self.state), released: Arc::clone(&self.released) }
+    }
+}
+
+/// Releases a call's resources when dropped
+#[derive(Debug)]
+pub struct ResourceGuard {
+    call_id: u64,
+    state: Arc<Mutex<LockState>>,
+    released: Arc<Notify>,
+}
+
+impl Drop for ResourceGuard {
+    fn drop(&mut self) {
+        self.state.lock().unwrap().held.retain(|holder| holder.call_id != self.call_id);
+        self.released.notify_waiters();
+    }
+}
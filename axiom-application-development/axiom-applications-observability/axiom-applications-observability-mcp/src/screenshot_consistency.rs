@@ -0,0 +1,270 @@
+use crate::advanced_visual_intelligence::{ElementFrame, LayoutIssue, ViewNode};
+use crate::device_matrix::WindowConfiguration;
+use crate::types::{Screenshot, ScreenshotMatrix};
+use crate::visual_regression::decode_png;
+use image::imageops::{self, FilterType};
+use image::{GrayImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Score below which the development loop fails visual validation
+pub const DEFAULT_MIN_CONSISTENCY_SCORE: f64 = 0.8;
+
+/// Share of a pair's similarity taken from its view hierarchies when both
+/// the hierarchies and the images can be compared; the rest is the images'
+const LAYOUT_WEIGHT: f64 = 0.6;
+
+/// Taken off a pair's score for each layout issue of either variant
+const ISSUE_PENALTY: f64 = 0.1;
+
+/// Width captures are resized to at the reference aspect ratio before hashing
+const NORMALIZED_WIDTH: u32 = 64;
+
+/// A difference hash compares each of 8 rows of 9 samples to its neighbor
+const HASH_WIDTH: u32 = 9;
+const HASH_HEIGHT: u32 = 8;
+const HASH_BITS: f64 = ((HASH_WIDTH - 1) * HASH_HEIGHT) as f64;
+
+/// How alike two device variants of the same screen are
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairConsistency {
+    /// The screen both variants show, with their orientation and color scheme
+    pub screen: String,
+    /// Screenshot ids of the two variants
+    pub first: String,
+    pub second: String,
+    /// Overlap of the views both hierarchies have, 0 to 1; `None` when
+    /// either capture came without a hierarchy
+    pub layout_similarity: Option<f64>,
+    /// Share of matching perceptual hash bits, 0 to 1; `None` when either
+    /// image could not be decoded
+    pub perceptual_similarity: Option<f64>,
+    /// Taken off for the layout issues detected on either variant
+    pub issue_penalty: f64,
+    pub score: f64,
+}
+
+/// Consistency of every screen of a matrix across the devices it was captured on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    /// Mean score of the pairs, 0 to 1; 1 when nothing could be compared
+    pub score: f64,
+    pub pairs: Vec<PairConsistency>,
+}
+
+impl ConsistencyReport {
+    /// The pair that differs the most
+    pub fn least_consistent(&self) -> Option<&PairConsistency> {
+        self.pairs.iter().min_by(|a, b| a.score.total_cmp(&b.score))
+    }
+}
+
+impl ScreenshotMatrix {
+    /// Score the matrix's consistency from its screenshots and the layout
+    /// issues found so far
+    pub fn score_consistency(&mut self) {
+        let report = consistency_report(&self.screenshots, &self.analysis.layout_issues);
+        self.analysis.consistency_score = report.score;
+        self.analysis.consistency_pairs = report.pairs;
+    }
+}
+
+/// Compare every two device variants of each screen
+///
+/// Variants are screenshots of the same view, or of the same client when
+/// no view was recorded, in the same orientation, color scheme and iPad
+/// window. Each is stretched to the aspect ratio of
+/// the screen's first variant, so a view at the same relative place on a
+/// phone and a tablet lines up. Pairs with neither a hierarchy nor a
+/// decodable image on both sides are left out.
+pub fn consistency_report(screenshots: &[Screenshot], issues: &[LayoutIssue]) -> ConsistencyReport {
+    let mut screens: BTreeMap<String, Vec<&Screenshot>> = BTreeMap::new();
+    for screenshot in screenshots {
+        screens.entry(screen_label(screenshot)).or_default().push(screenshot);
+    }
+    let mut issue_counts: HashMap<&str, usize> = HashMap::new();
+    for issue in issues {
+        *issue_counts.entry(issue.screenshot_id.as_str()).or_default() += 1;
+    }
+
+    let mut pairs = Vec::new();
+    for (screen, variants) in screens {
+        let Some(reference) = variants.first().and_then(|screenshot| aspect_ratio(screenshot)) else {
+            continue;
+        };
+        let normalized: Vec<Variant> = variants.iter().map(|screenshot| Variant::new(screenshot, reference)).collect();
+        for (index, first) in normalized.iter().enumerate() {
+            for second in &normalized[index + 1..] {
+                let issue_count = issue_counts.get(first.id).copied().unwrap_or(0) + issue_counts.get(second.id).copied().unwrap_or(0);
+                if let Some(pair) = compare(&screen, first, second, issue_count) {
+                    pairs.push(pair);
+                }
+            }
+        }
+    }
+
+    let score = if pairs.is_empty() {
+        1.0
+    } else {
+        pairs.iter().map(|pair| pair.score).sum::<f64>() / pairs.len() as f64
+    };
+    ConsistencyReport { score, pairs }
+}
+
+/// A capture normalized for comparison with the other variants of its screen
+struct Variant<'a> {
+    id: &'a str,
+    /// Frames of the identified views, in units of the screen's size
+    frames: Option<HashMap<&'a str, ElementFrame>>,
+    hash: Option<u64>,
+}
+
+impl<'a> Variant<'a> {
+    fn new(screenshot: &'a Screenshot, reference_aspect: f64) -> Self {
+        let hash = match screenshot.png_data().and_then(|png| decode_png(&png)) {
+            Ok(image) => Some(difference_hash(&normalize_image(&image, reference_aspect))),
+            Err(e) => {
+                tracing::debug!("Comparing screenshot {} without its image: {}", screenshot.id, e);
+                None
+            }
+        };
+        Self {
+            id: &screenshot.id,
+            frames: normalized_frames(screenshot),
+            hash,
+        }
+    }
+}
+
+fn compare(screen: &str, first: &Variant, second: &Variant, issue_count: usize) -> Option<PairConsistency> {
+    let layout_similarity = match (&first.frames, &second.frames) {
+        (Some(a), Some(b)) => layout_similarity(a, b),
+        _ => None,
+    };
+    let perceptual_similarity = match (first.hash, second.hash) {
+        (Some(a), Some(b)) => Some(1.0 - f64::from((a ^ b).count_ones()) / HASH_BITS),
+        _ => None,
+    };
+    let similarity = match (layout_similarity, perceptual_similarity) {
+        (Some(layout), Some(perceptual)) => LAYOUT_WEIGHT * layout + (1.0 - LAYOUT_WEIGHT) * perceptual,
+        (Some(similarity), None) | (None, Some(similarity)) => similarity,
+        (None, None) => return None,
+    };
+    let issue_penalty = ISSUE_PENALTY * issue_count as f64;
+
+    Some(PairConsistency {
+        screen: screen.to_string(),
+        first: first.id.to_string(),
+        second: second.id.to_string(),
+        layout_similarity,
+        perceptual_similarity,
+        issue_penalty,
+        score: (similarity - issue_penalty).clamp(0.0, 1.0),
+    })
+}
+
+fn screen_label(screenshot: &Screenshot) -> String {
+    let view = match screenshot.metadata.app_state.view_hierarchy.as_str() {
+        "" => screenshot.client_id.as_str(),
+        view => view,
+    };
+    let mut label = format!(
+        "{} {} {}",
+        view, screenshot.configuration.orientation, screenshot.configuration.color_scheme
+    );
+    // A split window lays out like a phone, not like its fullscreen iPad
+    if screenshot.configuration.window != WindowConfiguration::Fullscreen {
+        label.push_str(&format!(" {}", screenshot.configuration.window));
+    }
+    label
+}
+
+/// Width over height of the screen in points
+fn aspect_ratio(screenshot: &Screenshot) -> Option<f64> {
+    let size = &screenshot.configuration.screen_size;
+    (size.width > 0.0 && size.height > 0.0).then_some(size.width / size.height)
+}
+
+/// Resize `image` to `NORMALIZED_WIDTH` at `aspect`, stretching it when its
+/// own aspect ratio differs
+pub fn normalize_image(image: &RgbaImage, aspect: f64) -> RgbaImage {
+    let height = (f64::from(NORMALIZED_WIDTH) / aspect).round().max(1.0) as u32;
+    imageops::resize(image, NORMALIZED_WIDTH, height, FilterType::Triangle)
+}
+
+/// 64-bit difference hash: whether each sample of a downscaled grayscale
+/// image is brighter than the one to its right
+pub fn difference_hash(image: &RgbaImage) -> u64 {
+    let gray: GrayImage = imageops::grayscale(image);
+    let small = imageops::resize(&gray, HASH_WIDTH, HASH_HEIGHT, FilterType::Triangle);
+    let mut hash = 0u64;
+    for y in 0..HASH_HEIGHT {
+        for x in 0..HASH_WIDTH - 1 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// Frames of the visible, identified views of the screenshot's hierarchy,
+/// divided by the screen's size
+fn normalized_frames(screenshot: &Screenshot) -> Option<HashMap<&str, ElementFrame>> {
+    let tree = screenshot.metadata.app_state.view_tree.as_ref()?;
+    let size = &screenshot.configuration.screen_size;
+    if size.width <= 0.0 || size.height <= 0.0 {
+        return None;
+    }
+
+    let mut frames = HashMap::new();
+    collect_frames(tree, size.width, size.height, &mut frames);
+    Some(frames)
+}
+
+fn collect_frames<'a>(node: &'a ViewNode, width: f64, height: f64, frames: &mut HashMap<&'a str, ElementFrame>) {
+    if node.hidden {
+        return;
+    }
+    if !node.identifier.is_empty() {
+        frames.entry(node.identifier.as_str()).or_insert(ElementFrame {
+            x: node.frame.x / width,
+            y: node.frame.y / height,
+            width: node.frame.width / width,
+            height: node.frame.height / height,
+        });
+    }
+    for child in &node.children {
+        collect_frames(child, width, height, frames);
+    }
+}
+
+/// Mean intersection over union of the views of either hierarchy; a view
+/// only one of them has counts as not overlapping at all
+fn layout_similarity(a: &HashMap<&str, ElementFrame>, b: &HashMap<&str, ElementFrame>) -> Option<f64> {
+    let identifiers: BTreeSet<&str> = a.keys().chain(b.keys()).copied().collect();
+    if identifiers.is_empty() {
+        return None;
+    }
+    let total: f64 = identifiers
+        .iter()
+        .map(|identifier| match (a.get(identifier), b.get(identifier)) {
+            (Some(first), Some(second)) => intersection_over_union(first, second),
+            _ => 0.0,
+        })
+        .sum();
+    Some(total / identifiers.len() as f64)
+}
+
+fn intersection_over_union(a: &ElementFrame, b: &ElementFrame) -> f64 {
+    let width = ((a.x + a.width).min(b.x + b.width) - a.x.max(b.x)).max(0.0);
+    let height = ((a.y + a.height).min(b.y + b.height) - a.y.max(b.y)).max(0.0);
+    let intersection = width * height;
+    let union = a.width * a.height + b.width * b.height - intersection;
+    if union <= 0.0 {
+        // Two empty frames line up when they sit at the same point
+        return if a.x == b.x && a.y == b.y { 1.0 } else { 0.0 };
+    }
+    intersection / union
+}
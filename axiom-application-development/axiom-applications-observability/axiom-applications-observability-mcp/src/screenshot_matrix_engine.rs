@@ -1,74 +1,214 @@
-use crate::error::Result;
+use crate::device_matrix::{resolve_device_matrix, DeviceMatrixConfig, DeviceMatrixPreset};
+use crate::error::{AxiomMCPError, Result};
+use crate::screenshot_consistency::consistency_report;
+use crate::simulator::{StateResetOptions, StatusBarOverride};
+use crate::simulator_pool::{PoolRunStats, SimulatorPool};
 use crate::types::*;
-use std::sync::Arc;
+use crate::ui_script::{UiScript, UiScriptRunner};
+use std::sync::{Arc, Mutex};
 
-pub struct ScreenshotMatrixEngine;
+#[derive(Debug, Default)]
+pub struct ScreenshotMatrixEngine {
+    /// Captures on real simulators when set
+    pool: Option<Arc<SimulatorPool>>,
+    /// Reset before runs asking for a clean state; `StateResetOptions::clean_slate` when unset
+    state_reset: Option<StateResetOptions>,
+    /// Runs the pre-capture scripts of pooled runs
+    ui_script_runner: Option<Arc<UiScriptRunner>>,
+    last_run: Mutex<Option<PoolRunStats>>,
+}
 
 impl ScreenshotMatrixEngine {
     pub async fn new(_controller: Arc<crate::simulator::SimulatorController>) -> Result<Self> {
-        Ok(Self)
+        Ok(Self::default())
+    }
+
+    /// Capture matrices on `pool`, several simulators at a time
+    pub fn with_pool(mut self, pool: Arc<SimulatorPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Reset `options` on each simulator of runs asking for a clean state
+    pub fn with_state_reset(mut self, options: StateResetOptions) -> Self {
+        self.state_reset = Some(options);
+        self
+    }
+
+    /// Run the pre-capture scripts of matrix specs with `runner`
+    pub fn with_ui_script_runner(mut self, runner: Arc<UiScriptRunner>) -> Self {
+        self.ui_script_runner = Some(runner);
+        self
+    }
+
+    /// Scheduling stats of the last pooled matrix run
+    pub fn last_run(&self) -> Option<PoolRunStats> {
+        self.last_run.lock().unwrap().clone()
     }
-    
+
     pub async fn generate_screenshot_matrix(&self, spec: ScreenshotMatrixSpec) -> Result<ScreenshotMatrix> {
-        // Generate appropriate number of screenshots based on configuration
-        let screenshot_count = match &spec.configuration_type {
-            ConfigurationType::Preset(preset_name) => match preset_name.as_str() {
-                "mobile_only" => 6,   // 1 device × 2 orientations × 3 states
-                "tablet_only" => 4,   // 1 device × 2 orientations × 2 color schemes  
-                "all_devices" => 12,  // 3 devices × 2 orientations × 2 color schemes
-                _ => 1,
-            }
-        };
-        
-        let mut screenshots = Vec::new();
-        for i in 0..screenshot_count {
-            screenshots.push(Screenshot {
+        if let Some(pool) = &self.pool {
+            return self.capture_on_pool(pool, &spec).await;
+        }
+
+        // Without a pool there are no simulators, so stand in for each capture
+        if let Some(script) = &spec.pre_capture_script {
+            tracing::debug!("Skipping the {}-step pre-capture script without simulators", script.steps.len());
+        }
+        let matrix = device_matrix(&spec.configuration_type)?;
+        let screenshots: Vec<Screenshot> = matrix.configurations()?
+            .into_iter()
+            .enumerate()
+            .map(|(i, configuration)| Screenshot {
                 id: format!("screenshot-{}", i),
                 client_id: "test-client".to_string(),
-                configuration: ScreenshotConfiguration {
-                    device_type: format!("device-{}", i % 3),
-                    screen_size: ScreenSize::default(),
-                    orientation: if i % 2 == 0 { "portrait".to_string() } else { "landscape".to_string() },
-                    scale: 2.0,
-                    color_scheme: if i % 4 < 2 { "light".to_string() } else { "dark".to_string() },
-                    capture_mode: "standard".to_string(),
-                },
                 image_data: vec![1, 2, 3, 4], // Mock image data
+                image: None,
                 metadata: ScreenshotMetadata {
                     timestamp: chrono::Utc::now(),
                     device_info: DeviceInfo {
-                        model: format!("TestDevice{}", i % 3),
-                        screen_size: ScreenSize::default(),
-                        orientation: if i % 2 == 0 { "portrait".to_string() } else { "landscape".to_string() },
+                        model: configuration.device_type.clone(),
+                        screen_size: configuration.screen_size.clone(),
+                        orientation: configuration.orientation.clone(),
+                        udid: None,
                     },
                     app_state: AppState {
                         view_hierarchy: "TestView".to_string(),
                         active_context: "TestContext".to_string(),
+                        view_tree: None,
                     },
+                    overrides: configuration.overrides(),
                 },
-            });
-        }
+                configuration,
+            })
+            .collect();
         
+        let consistency = consistency_report(&screenshots, &[]);
         Ok(ScreenshotMatrix {
+            analysis: ScreenshotAnalysis {
+                total_screenshots: screenshots.len(),
+                consistency_score: consistency.score,
+                detected_issues: vec![],
+                layout_issues: Vec::new(),
+                consistency_pairs: consistency.pairs,
+                warnings: matrix.warnings(),
+            },
             screenshots,
+        })
+    }
+
+    async fn capture_on_pool(&self, pool: &SimulatorPool, spec: &ScreenshotMatrixSpec) -> Result<ScreenshotMatrix> {
+        let matrix = device_matrix(&spec.configuration_type)?;
+        let (mut configurations, warnings) = match pool.installed_devices().await? {
+            Some(installed) => {
+                let resolved = resolve_device_matrix(&matrix, &installed)?;
+                (resolved.configurations, resolved.warnings)
+            }
+            None => (matrix.configurations()?, matrix.warnings()),
+        };
+        for warning in &warnings {
+            tracing::warn!("{}", warning);
+        }
+        for configuration in &mut configurations {
+            configuration.status_bar = spec.status_bar.clone();
+        }
+        let reset = spec.clean_state.then(|| self.state_reset.clone().unwrap_or_else(StateResetOptions::clean_slate));
+        let run = match (&spec.pre_capture_script, reset) {
+            (Some(script), reset) => {
+                let runner = self.ui_script_runner.as_ref().ok_or_else(|| AxiomMCPError::configuration(
+                    "pre_capture_script",
+                    "Running a pre-capture script needs the intelligence server to drive the app",
+                ))?;
+                pool.capture_scripted_matrix(configurations, reset.as_ref(), runner, script).await?
+            }
+            (None, Some(options)) => pool.capture_clean_matrix(configurations, &options).await?,
+            (None, None) => pool.capture_matrix(configurations).await?,
+        };
+
+        let screenshots: Vec<Screenshot> = run.captures
+            .into_iter()
+            .map(|capture| Screenshot {
+                id: uuid::Uuid::new_v4().to_string(),
+                client_id: spec.matrix_name.clone(),
+                metadata: ScreenshotMetadata {
+                    timestamp: chrono::Utc::now(),
+                    device_info: DeviceInfo {
+                        model: capture.configuration.device_type.clone(),
+                        screen_size: capture.configuration.screen_size.clone(),
+                        orientation: capture.configuration.orientation.clone(),
+                        udid: Some(capture.udid),
+                    },
+                    app_state: AppState {
+                        view_hierarchy: String::new(),
+                        active_context: String::new(),
+                        view_tree: None,
+                    },
+                    overrides: capture.configuration.overrides(),
+                },
+                configuration: capture.configuration,
+                image_data: capture.image_data,
+                image: capture.image,
+            })
+            .collect();
+
+        *self.last_run.lock().unwrap() = Some(run.stats);
+        let consistency = consistency_report(&screenshots, &[]);
+        Ok(ScreenshotMatrix {
             analysis: ScreenshotAnalysis {
-                total_screenshots: screenshot_count,
-                consistency_score: 95.0,
+                total_screenshots: screenshots.len(),
+                consistency_score: consistency.score,
                 detected_issues: vec![],
+                layout_issues: Vec::new(),
+                consistency_pairs: consistency.pairs,
+                warnings,
             },
+            screenshots,
         })
     }
 }
 
+/// The devices a matrix is captured on
+pub fn device_matrix(configuration_type: &ConfigurationType) -> Result<DeviceMatrixConfig> {
+    match configuration_type {
+        ConfigurationType::Preset(preset_name) => match DeviceMatrixPreset::parse(preset_name) {
+            Some(preset) => Ok(preset.matrix()),
+            None => {
+                let names: Vec<&str> = DeviceMatrixPreset::ALL.iter().map(|preset| preset.name()).collect();
+                Err(AxiomMCPError::validation("screenshot_matrix", "capture matrix", format!(
+                    "Unknown screenshot matrix preset {}; expected {}", preset_name, names.join(", ")
+                )))
+            }
+        },
+        ConfigurationType::Custom(matrix) => Ok(matrix.clone()),
+    }
+}
+
+/// Every device, orientation, color scheme and window combination of a
+/// matrix, grouped by device so a simulator captures its configurations in a row
+pub fn matrix_configurations(configuration_type: &ConfigurationType) -> Result<Vec<ScreenshotConfiguration>> {
+    device_matrix(configuration_type)?.configurations()
+}
+
 pub struct ScreenshotMatrixSpec {
     pub matrix_name: String,
     pub configuration_type: ConfigurationType,
     pub app_states: Option<Vec<String>>,
     pub capture_options: CaptureOptions,
+    /// Reset each simulator's permissions, keychain and other leftover
+    /// state before it captures; only pooled runs use real simulators
+    pub clean_state: bool,
+    /// Shown on every simulator instead of its own clock, battery and
+    /// signal; only pooled runs use real simulators
+    pub status_bar: Option<StatusBarOverride>,
+    /// Run on each simulator before it captures, after any reset, to bring
+    /// the app to the screen to capture; only pooled runs use real simulators
+    pub pre_capture_script: Option<UiScript>,
 }
 
 pub enum ConfigurationType {
+    /// A `DeviceMatrixPreset` by name
     Preset(String),
+    Custom(DeviceMatrixConfig),
 }
 
 pub struct CaptureOptions {
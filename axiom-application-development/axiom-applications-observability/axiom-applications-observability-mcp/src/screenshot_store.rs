@@ -0,0 +1,268 @@
+use crate::artifact_store::{ArtifactKind, ArtifactRecord, ArtifactSource, ArtifactStore, ArtifactStoreConfig};
+use crate::error::{AxiomMCPError, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+pub use crate::artifact_store::{content_hash, PruneReason, RetentionPolicy, DEFAULT_MAX_AGE_SECS, DEFAULT_MAX_TOTAL_BYTES};
+
+/// Where screenshots are kept, relative to the project
+pub const DEFAULT_SCREENSHOT_DIR: &str = ".axiom/screenshots";
+
+/// Which stored images back a visual regression baseline
+const BASELINES_FILE: &str = "baselines.json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageEncoding {
+    #[default]
+    Png,
+    /// Several times smaller, but only PNGs can be diffed or audited
+    Heic,
+}
+
+impl ImageEncoding {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageEncoding::Png => "png",
+            ImageEncoding::Heic => "heic",
+        }
+    }
+
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "png" => Some(ImageEncoding::Png),
+            "heic" => Some(ImageEncoding::Heic),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotStorageConfig {
+    /// Holds the baselines, and the images too unless they go to a shared
+    /// artifact store
+    pub dir: PathBuf,
+    pub encoding: ImageEncoding,
+    pub retention: RetentionPolicy,
+}
+
+impl Default for ScreenshotStorageConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(DEFAULT_SCREENSHOT_DIR),
+            encoding: ImageEncoding::default(),
+            retention: RetentionPolicy::default(),
+        }
+    }
+}
+
+/// A captured image in the store, named by the SHA-256 of its PNG, which
+/// is also its artifact id
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredImage {
+    pub hash: String,
+    pub path: PathBuf,
+    pub encoding: ImageEncoding,
+    pub size_bytes: u64,
+}
+
+impl StoredImage {
+    fn from_record(record: ArtifactRecord) -> Result<Self> {
+        let encoding = record.extension().and_then(ImageEncoding::from_extension).ok_or_else(|| {
+            AxiomMCPError::internal("screenshot_store", "store screenshot", format!(
+                "Artifact {} at {} is not a screenshot", record.id, record.path.display()
+            ))
+        })?;
+        Ok(Self { hash: record.id, path: record.path, encoding, size_bytes: record.size_bytes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrunedScreenshot {
+    pub hash: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub reason: PruneReason,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PruneReport {
+    /// Oldest first
+    pub removed: Vec<PrunedScreenshot>,
+    pub freed_bytes: u64,
+    pub kept: usize,
+    pub kept_bytes: u64,
+    /// Kept because a baseline was recorded from them
+    pub baselines: usize,
+}
+
+/// Converts a PNG to HEIC, behind a trait so tests need no macOS tools
+#[async_trait]
+pub trait HeicEncoder: Send + Sync {
+    async fn encode(&self, png: &Path, heic: &Path) -> Result<()>;
+}
+
+/// Encodes with `sips`, which ships with macOS
+#[derive(Debug, Default)]
+pub struct SipsEncoder;
+
+#[async_trait]
+impl HeicEncoder for SipsEncoder {
+    async fn encode(&self, png: &Path, heic: &Path) -> Result<()> {
+        let output = tokio::process::Command::new("sips")
+            .args(["-s", "format", "heic"])
+            .arg(png)
+            .arg("--out")
+            .arg(heic)
+            .output()
+            .await
+            .map_err(|e| AxiomMCPError::toolchain("screenshot_store", "encode HEIC", format!("Cannot run sips: {}", e)).with_cause(e))?;
+        if !output.status.success() {
+            return Err(AxiomMCPError::toolchain("screenshot_store", "encode HEIC", format!(
+                "sips could not encode {} as HEIC: {}", png.display(), String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Screenshots kept as artifacts, with the baselines recorded from them
+///
+/// Images are named by the hash of their PNG, so capturing an unchanged
+/// screen again stores nothing new. Screenshots hold a `StoredImage`
+/// instead of their bytes, and `prune` enforces the retention policy,
+/// oldest first, skipping images a regression baseline was recorded from.
+pub struct ScreenshotStore {
+    config: ScreenshotStorageConfig,
+    artifacts: Arc<ArtifactStore>,
+    heic_encoder: Arc<dyn HeicEncoder>,
+    /// Serializes updates of the baselines file
+    baselines_lock: tokio::sync::Mutex<()>,
+}
+
+impl std::fmt::Debug for ScreenshotStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScreenshotStore").field("config", &self.config).field("artifacts", &self.artifacts).finish()
+    }
+}
+
+impl ScreenshotStore {
+    /// A store keeping its images in an artifact store of its own, under `config.dir`
+    pub fn new(config: ScreenshotStorageConfig) -> Self {
+        let artifacts = ArtifactStore::new(ArtifactStoreConfig {
+            dir: config.dir.clone(),
+            retention: BTreeMap::from([(ArtifactKind::Screenshot, config.retention.clone())]),
+        });
+        Self {
+            config,
+            artifacts: Arc::new(artifacts),
+            heic_encoder: Arc::new(SipsEncoder),
+            baselines_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Keep the images in `artifacts` alongside the other subsystems'
+    /// artifacts; the baselines stay under `config.dir`
+    pub fn with_artifact_store(mut self, artifacts: Arc<ArtifactStore>) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
+
+    pub fn with_heic_encoder(mut self, encoder: Arc<dyn HeicEncoder>) -> Self {
+        self.heic_encoder = encoder;
+        self
+    }
+
+    pub fn config(&self) -> &ScreenshotStorageConfig {
+        &self.config
+    }
+
+    pub fn artifacts(&self) -> &Arc<ArtifactStore> {
+        &self.artifacts
+    }
+
+    /// Store `png` in the configured encoding, or reuse the copy stored
+    /// earlier, which then counts as captured now for retention
+    pub async fn put(&self, png: &[u8]) -> Result<StoredImage> {
+        self.put_for(png, ArtifactSource::default()).await
+    }
+
+    /// `put`, recording the cycle and stage that captured the screenshot
+    pub async fn put_for(&self, png: &[u8], source: ArtifactSource) -> Result<StoredImage> {
+        let hash = content_hash(png);
+        if let Some(record) = self.artifacts.renew(&hash, source.clone())? {
+            return StoredImage::from_record(record);
+        }
+
+        let record = match self.config.encoding {
+            ImageEncoding::Png => self.artifacts.put(ArtifactKind::Screenshot, png, ImageEncoding::Png.extension(), source).await?,
+            ImageEncoding::Heic => {
+                let temp = self.artifacts.temp_path(ImageEncoding::Png.extension()).await?;
+                tokio::fs::write(&temp, png).await?;
+                let heic = temp.with_extension(ImageEncoding::Heic.extension());
+                let encoded = self.heic_encoder.encode(&temp, &heic).await;
+                let _ = tokio::fs::remove_file(&temp).await;
+                if let Err(e) = encoded {
+                    let _ = tokio::fs::remove_file(&heic).await;
+                    return Err(e);
+                }
+                self.artifacts.insert_file(&hash, ArtifactKind::Screenshot, &heic, ImageEncoding::Heic.extension(), source).await?
+            }
+        };
+        StoredImage::from_record(record)
+    }
+
+    pub async fn read(&self, image: &StoredImage) -> Result<Vec<u8>> {
+        tokio::fs::read(&image.path).await.map_err(|e| AxiomMCPError::internal("screenshot_store", "read screenshot", format!(
+            "Screenshot {} is gone from {}: {}", image.hash, image.path.display(), e
+        )).with_cause(e))
+    }
+
+    /// Keep `image` as long as it backs the baseline `baseline_id`, releasing
+    /// the image the baseline was recorded from before
+    pub async fn pin_baseline(&self, baseline_id: &str, image: &StoredImage) -> Result<()> {
+        let _lock = self.baselines_lock.lock().await;
+        let mut baselines = self.baselines()?;
+        baselines.insert(baseline_id.to_string(), image.hash.clone());
+        tokio::fs::create_dir_all(&self.config.dir).await?;
+        tokio::fs::write(self.config.dir.join(BASELINES_FILE), serde_json::to_vec_pretty(&baselines)?).await?;
+        Ok(())
+    }
+
+    /// Image hashes by the id of the baseline recorded from them
+    pub fn baselines(&self) -> Result<BTreeMap<String, String>> {
+        match std::fs::read(self.config.dir.join(BASELINES_FILE)) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Remove images past the retention policy as of `now`: expired ones,
+    /// then the oldest until the store fits its size limit
+    pub fn prune(&self, now: SystemTime) -> Result<PruneReport> {
+        let pinned = self.baselines()?.into_values().collect();
+        let pruned = self.artifacts.prune_kind(ArtifactKind::Screenshot, &self.config.retention, now, &pinned)?;
+        Ok(PruneReport {
+            removed: pruned
+                .removed
+                .into_iter()
+                .map(|artifact| PrunedScreenshot {
+                    hash: artifact.id,
+                    path: artifact.path,
+                    size_bytes: artifact.size_bytes,
+                    reason: artifact.reason,
+                })
+                .collect(),
+            freed_bytes: pruned.freed_bytes,
+            kept: pruned.kept,
+            kept_bytes: pruned.kept_bytes,
+            baselines: pruned.pinned,
+        })
+    }
+}
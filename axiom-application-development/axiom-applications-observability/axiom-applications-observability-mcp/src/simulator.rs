@@ -1,9 +1,1188 @@
-use crate::error::Result;
+use crate::error::{AxiomMCPError, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-pub struct SimulatorController;
+/// How long `boot` waits for a device to reach `Booted`
+pub const DEFAULT_BOOT_TIMEOUT: Duration = Duration::from_secs(120);
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const RUNTIME_PREFIX: &str = "com.apple.CoreSimulator.SimRuntime.";
+
+/// `simctl launch` passes variables with this prefix on to the app, without it
+const CHILD_ENV_PREFIX: &str = "SIMCTL_CHILD_";
+
+/// Longest `--console` capture a launch may ask for
+pub const MAX_CONSOLE_CAPTURE: Duration = Duration::from_secs(60);
+
+/// simctl failures of a device in the middle of booting or shutting down,
+/// or of CoreSimulator being slow to answer
+/// Environment the Axiom debug harness reads the iPad window to size the
+/// app's scene to from; set in the simulator's launchd for a capture
+pub const WINDOW_CONFIGURATION_ENV: &str = "AXIOM_WINDOW_CONFIGURATION";
+
+/// Locale the pasteboard commands run in; `simctl pbcopy` and `pbpaste`
+/// mangle anything outside ASCII in a locale without UTF-8
+const PASTEBOARD_LOCALE: &str = "en_US.UTF-8";
+
+const TRANSIENT_SIMCTL_FAILURES: &[&str] = &["current state: booting", "current state: shutting down", "timed out"];
+
+/// The `xcrun simctl` subprocess, behind a trait so tests can script it
+#[async_trait]
+pub trait Simctl: Send + Sync {
+    /// Run `simctl` with `args`, returning stdout; a non-zero exit is an error
+    async fn run(&self, args: &[&str]) -> Result<String>;
+
+    /// Run `simctl` with `env` set for the launched app, and when `capture`
+    /// is set, collect stdout for that long and then stop the command
+    async fn run_with_env(&self, args: &[&str], env: &[(String, String)], capture: Option<Duration>) -> Result<String>;
+
+    /// Run `simctl` with `input` on stdin, in a UTF-8 locale so text passes
+    /// through it unchanged
+    async fn run_with_input(&self, args: &[&str], input: &[u8]) -> Result<String>;
+}
+
+/// Runs the real `xcrun simctl`
+#[derive(Debug, Default)]
+pub struct XcrunSimctl;
+
+#[async_trait]
+impl Simctl for XcrunSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        let output = tokio::process::Command::new("xcrun")
+            .arg("simctl")
+            .args(args)
+            .output()
+            .await
+            .map_err(simctl_unavailable)?;
+
+        if !output.status.success() {
+            return Err(simctl_failed(args, &String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    async fn run_with_env(&self, args: &[&str], env: &[(String, String)], capture: Option<Duration>) -> Result<String> {
+        let mut command = tokio::process::Command::new("xcrun");
+        command.arg("simctl").args(args);
+        for (key, value) in env {
+            command.env(format!("{}{}", CHILD_ENV_PREFIX, key), value);
+        }
+
+        let Some(capture) = capture else {
+            let output = command.output().await.map_err(simctl_unavailable)?;
+            if !output.status.success() {
+                return Err(simctl_failed(args, &String::from_utf8_lossy(&output.stderr)));
+            }
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+        };
+
+        // The console stays attached until the app exits, so read for the
+        // capture window and then detach
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(simctl_unavailable)?;
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let mut output = Vec::new();
+        let exited = tokio::time::timeout(capture, async {
+            stdout.read_to_end(&mut output).await?;
+            child.wait().await
+        })
+        .await;
+
+        match exited {
+            Ok(status) => {
+                if status?.success() {
+                    return Ok(String::from_utf8_lossy(&output).into_owned());
+                }
+                let mut error = String::new();
+                stderr.read_to_string(&mut error).await?;
+                Err(simctl_failed(args, &error))
+            }
+            // Still running after the capture window
+            Err(_) => {
+                child.start_kill()?;
+                Ok(String::from_utf8_lossy(&output).into_owned())
+            }
+        }
+    }
+
+    async fn run_with_input(&self, args: &[&str], input: &[u8]) -> Result<String> {
+        let mut child = tokio::process::Command::new("xcrun")
+            .arg("simctl")
+            .args(args)
+            .env("LANG", PASTEBOARD_LOCALE)
+            .env("LC_ALL", PASTEBOARD_LOCALE)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(simctl_unavailable)?;
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        stdin.write_all(input).await?;
+        // Closing stdin tells pbcopy the text is complete
+        drop(stdin);
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            return Err(simctl_failed(args, &String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceState {
+    Shutdown,
+    Booting,
+    Booted,
+    ShuttingDown,
+    Creating,
+    Other(String),
+}
+
+impl DeviceState {
+    fn parse(state: &str) -> Self {
+        match state {
+            "Shutdown" => DeviceState::Shutdown,
+            "Booting" => DeviceState::Booting,
+            "Booted" => DeviceState::Booted,
+            "Shutting Down" => DeviceState::ShuttingDown,
+            "Creating" => DeviceState::Creating,
+            other => DeviceState::Other(other.to_string()),
+        }
+    }
+}
+
+/// A simulator device from `simctl list devices`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatorDevice {
+    pub udid: String,
+    pub name: String,
+    pub state: DeviceState,
+    /// e.g. `com.apple.CoreSimulator.SimRuntime.iOS-17-2`
+    pub runtime_identifier: String,
+    /// e.g. `iOS 17.2`
+    pub runtime: String,
+    pub is_available: bool,
+    /// Why the device is unavailable, typically a missing runtime
+    pub availability_error: Option<String>,
+    pub device_type_identifier: Option<String>,
+}
+
+impl SimulatorDevice {
+    pub fn label(&self) -> String {
+        format!("{} ({}, {})", self.name, self.runtime, self.udid)
+    }
+}
+
+#[derive(Deserialize)]
+struct DeviceList {
+    devices: BTreeMap<String, Vec<RawDevice>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawDevice {
+    udid: String,
+    name: String,
+    state: String,
+    #[serde(default)]
+    is_available: bool,
+    availability_error: Option<String>,
+    device_type_identifier: Option<String>,
+}
+
+/// Parse the output of `simctl list devices --json`
+pub fn parse_device_list(json: &str) -> Result<Vec<SimulatorDevice>> {
+    let list: DeviceList = serde_json::from_str(json)?;
+    let devices = list.devices
+        .into_iter()
+        .flat_map(|(runtime_identifier, devices)| {
+            let runtime = runtime_name(&runtime_identifier);
+            devices.into_iter().map(move |device| SimulatorDevice {
+                udid: device.udid,
+                name: device.name,
+                state: DeviceState::parse(&device.state),
+                runtime_identifier: runtime_identifier.clone(),
+                runtime: runtime.clone(),
+                is_available: device.is_available,
+                availability_error: device.availability_error,
+                device_type_identifier: device.device_type_identifier,
+            })
+        })
+        .collect();
+    Ok(devices)
+}
+
+/// `com.apple.CoreSimulator.SimRuntime.iOS-17-2` as `iOS 17.2`
+fn runtime_name(identifier: &str) -> String {
+    let runtime = identifier.strip_prefix(RUNTIME_PREFIX).unwrap_or(identifier);
+    match runtime.split_once('-') {
+        Some((platform, version)) => format!("{} {}", platform, version.replace('-', ".")),
+        None => runtime.to_string(),
+    }
+}
+
+/// Result of booting, shutting down or erasing a device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatorActionResult {
+    pub action: String,
+    pub device: SimulatorDevice,
+    pub previous_state: DeviceState,
+    /// False when the device was already in the requested state
+    pub changed: bool,
+    pub duration_ms: u64,
+}
+
+/// Install the `.app` bundle at `app_path` on `device`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallAppRequest {
+    /// UDID, or name and runtime
+    pub device: String,
+    pub app_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallAppResult {
+    pub device: SimulatorDevice,
+    pub app_path: PathBuf,
+    pub duration_ms: u64,
+}
+
+/// Launch an installed app on `device`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchAppRequest {
+    /// UDID, or name and runtime
+    pub device: String,
+    pub bundle_id: String,
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub environment: BTreeMap<String, String>,
+    /// Attach to the app's console and capture its stdout for this many seconds
+    #[serde(default)]
+    pub console_seconds: Option<u64>,
+    /// Bring an already running instance to the foreground instead of
+    /// restarting it
+    #[serde(default)]
+    pub keep_running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchAppResult {
+    pub device: SimulatorDevice,
+    pub bundle_id: String,
+    pub pid: u32,
+    /// App stdout captured through `--console`
+    pub output: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Privacy services `simctl privacy` can reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyService {
+    All,
+    Calendar,
+    Contacts,
+    Location,
+    LocationAlways,
+    Photos,
+    PhotosAdd,
+    MediaLibrary,
+    Microphone,
+    Motion,
+    Reminders,
+    Siri,
+}
+
+impl PrivacyService {
+    /// The name simctl knows the service by
+    pub fn as_simctl(self) -> &'static str {
+        match self {
+            PrivacyService::All => "all",
+            PrivacyService::Calendar => "calendar",
+            PrivacyService::Contacts => "contacts",
+            PrivacyService::Location => "location",
+            PrivacyService::LocationAlways => "location-always",
+            PrivacyService::Photos => "photos",
+            PrivacyService::PhotosAdd => "photos-add",
+            PrivacyService::MediaLibrary => "media-library",
+            PrivacyService::Microphone => "microphone",
+            PrivacyService::Motion => "motion",
+            PrivacyService::Reminders => "reminders",
+            PrivacyService::Siri => "siri",
+        }
+    }
+}
+
+/// Remove an app with everything it stored, and optionally install it again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppDataReset {
+    pub bundle_id: String,
+    /// Installed again after uninstalling when set
+    #[serde(default)]
+    pub app_path: Option<PathBuf>,
+}
+
+/// Permissions granted on a device to forget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyReset {
+    /// Every service when empty
+    #[serde(default)]
+    pub services: Vec<PrivacyService>,
+    /// Only this app's permissions when set
+    #[serde(default)]
+    pub bundle_id: Option<String>,
+}
+
+/// What to put back to a clean state; anything left unset is left alone
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StateResetOptions {
+    pub app_data: Option<AppDataReset>,
+    pub privacy: Option<PrivacyReset>,
+    /// Clear `simctl status_bar` overrides
+    pub status_bar: bool,
+    pub keychain: bool,
+    /// `light` or `dark`
+    pub appearance: Option<String>,
+}
+
+impl StateResetOptions {
+    /// Everything a screenshot depends on that does not belong to one app
+    pub fn clean_slate() -> Self {
+        Self {
+            app_data: None,
+            privacy: Some(PrivacyReset { services: Vec::new(), bundle_id: None }),
+            status_bar: true,
+            keychain: true,
+            appearance: Some("light".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatteryState {
+    Charging,
+    Charged,
+    Discharging,
+}
+
+impl BatteryState {
+    pub fn as_simctl(self) -> &'static str {
+        match self {
+            BatteryState::Charging => "charging",
+            BatteryState::Charged => "charged",
+            BatteryState::Discharging => "discharging",
+        }
+    }
+}
+
+/// What `simctl status_bar override` shows instead of the device's own
+/// status; anything unset keeps its real value
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusBarOverride {
+    /// Shown as is, such as `9:41`
+    pub time: Option<String>,
+    /// 0 to 100
+    pub battery_level: Option<u8>,
+    pub battery_state: Option<BatteryState>,
+    /// 0 to 4
+    pub cellular_bars: Option<u8>,
+    /// 0 to 3
+    pub wifi_bars: Option<u8>,
+}
+
+impl StatusBarOverride {
+    /// The status bar of Apple's own product shots
+    pub fn marketing() -> Self {
+        Self {
+            time: Some("9:41".to_string()),
+            battery_level: Some(100),
+            battery_state: Some(BatteryState::Charged),
+            cellular_bars: Some(4),
+            wifi_bars: Some(3),
+        }
+    }
+}
+
+/// Overrides applied to a device for one capture and cleared after it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureOverrides {
+    /// `light` or `dark`; the device's own appearance is restored afterwards
+    pub appearance: Option<String>,
+    pub status_bar: Option<StatusBarOverride>,
+    /// iPad window to lay the app out in instead of the full screen
+    pub window: Option<crate::device_matrix::WindowConfiguration>,
+}
+
+/// The language, region formats and keyboard a device is set to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatorLocale {
+    /// `de_DE`, `ja_JP` or `zh_Hans_CN`
+    pub identifier: String,
+    /// Keyboard identifier such as `ja_JP-Kana@sw=Kana;hw=Automatic`; the
+    /// locale's own keyboard when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyboard: Option<String>,
+}
+
+impl SimulatorLocale {
+    pub fn parse(identifier: &str) -> Result<Self> {
+        let parts: Vec<&str> = identifier.split('_').collect();
+        let language = parts[0];
+        let valid = (2..=3).contains(&language.len())
+            && language.chars().all(|c| c.is_ascii_lowercase())
+            && parts.len() <= 3
+            && parts[1..].iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+        if !valid {
+            return Err(AxiomMCPError::validation("simulator", "set locale", format!(
+                "Invalid locale {}; write it like de_DE or ja_JP", identifier
+            )));
+        }
+        Ok(Self { identifier: identifier.to_string(), keyboard: None })
+    }
+
+    pub fn with_keyboard(mut self, keyboard: impl Into<String>) -> Self {
+        self.keyboard = Some(keyboard.into());
+        self
+    }
+
+    /// `de`, or `zh-Hans` for a locale naming its script
+    pub fn language(&self) -> String {
+        let mut parts = self.identifier.split('_');
+        let language = parts.next().unwrap_or_default();
+        match parts.next() {
+            Some(script) if script.len() == 4 && script.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                format!("{}-{}", language, script)
+            }
+            _ => language.to_string(),
+        }
+    }
+
+    pub fn keyboard(&self) -> &str {
+        self.keyboard.as_deref().unwrap_or(&self.identifier)
+    }
+
+    /// Whether keys simulated as US ones reach the app as the same characters
+    pub fn has_us_keyboard(&self) -> bool {
+        let keyboard = self.keyboard();
+        keyboard == "en_US" || keyboard.starts_with("en_US@")
+    }
+}
+
+/// How text reaches a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputStrategy {
+    /// Typed key by key
+    Direct,
+    /// Put on the device's pasteboard and pasted into the field
+    Pasteboard,
+}
+
+impl InputStrategy {
+    /// Type `text` directly only when it is plain ASCII and the device has a
+    /// US keyboard: simulated keys are US key codes, so another layout turns
+    /// them into other characters, and accented letters or emoji have no key
+    /// to press at all
+    pub fn select(text: &str, locale: Option<&SimulatorLocale>) -> Self {
+        let us_keyboard = locale.is_none_or(SimulatorLocale::has_us_keyboard);
+        let typeable = text.chars().all(|c| c == ' ' || c == '\n' || c.is_ascii_graphic());
+        if us_keyboard && typeable {
+            InputStrategy::Direct
+        } else {
+            InputStrategy::Pasteboard
+        }
+    }
+}
+
+/// What became of text a script entered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum TextEntry {
+    Typed,
+    /// `after_typing_failed` when typing it was tried first
+    Pasted { after_typing_failed: bool },
+    /// Not entered: `element` is a secure text field, which takes neither
+    /// simulated keys nor pasted text reliably, so the text is reported as
+    /// left out rather than typed into nothing
+    SecureField { element: String },
+}
+
+/// `simctl spawn` commands writing `locale` into the device's global defaults
+pub fn locale_args(udid: &str, locale: &SimulatorLocale) -> Vec<Vec<String>> {
+    let write = |key: &str, kind: &str, value: &str| {
+        ["spawn", udid, "defaults", "write", "Apple Global Domain", key, kind, value]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+    };
+    vec![
+        write("AppleLanguages", "-array", &locale.language()),
+        write("AppleLocale", "-string", &locale.identifier),
+        write("AppleKeyboards", "-array", locale.keyboard()),
+    ]
+}
+
+/// Reset the state of the booted device `device`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResetSimulatorStateRequest {
+    /// UDID, or name and runtime
+    pub device: String,
+    #[serde(flatten)]
+    pub options: StateResetOptions,
+}
+
+/// One thing a reset put back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reset", rename_all = "snake_case")]
+pub enum StateReset {
+    AppUninstalled { bundle_id: String },
+    AppReinstalled { bundle_id: String, app_path: PathBuf },
+    Privacy { service: PrivacyService, bundle_id: Option<String> },
+    StatusBar,
+    Keychain,
+    Appearance { appearance: String },
+}
+
+impl std::fmt::Display for StateReset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateReset::AppUninstalled { bundle_id } => write!(f, "uninstalled {}", bundle_id),
+            StateReset::AppReinstalled { bundle_id, .. } => write!(f, "reinstalled {}", bundle_id),
+            StateReset::Privacy { service, bundle_id: Some(bundle_id) } => {
+                write!(f, "{} permissions of {}", service.as_simctl(), bundle_id)
+            }
+            StateReset::Privacy { service, bundle_id: None } => write!(f, "{} permissions", service.as_simctl()),
+            StateReset::StatusBar => f.write_str("status bar"),
+            StateReset::Keychain => f.write_str("keychain"),
+            StateReset::Appearance { appearance } => write!(f, "{} appearance", appearance),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatorStateReset {
+    pub device: SimulatorDevice,
+    /// In the order they were reset
+    pub reset: Vec<StateReset>,
+    pub duration_ms: u64,
+}
+
+/// Lists, boots, shuts down and erases simulator devices through `simctl`
+///
+/// Devices are selected by UDID, or by a loose match on name and runtime
+/// such as `iphone 15 pro 17.2`; a selector matching several devices is
+/// rejected with the candidates listed rather than guessed.
+pub struct SimulatorController {
+    simctl: Arc<dyn Simctl>,
+    boot_timeout: Duration,
+    poll_interval: Duration,
+}
 
 impl SimulatorController {
     pub async fn new() -> Result<Self> {
-        Ok(Self)
+        Ok(Self::with_simctl(Arc::new(XcrunSimctl)))
+    }
+
+    pub fn with_simctl(simctl: Arc<dyn Simctl>) -> Self {
+        Self {
+            simctl,
+            boot_timeout: DEFAULT_BOOT_TIMEOUT,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    pub fn with_boot_timeout(mut self, timeout: Duration) -> Self {
+        self.boot_timeout = timeout;
+        self
+    }
+
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub async fn list_devices(&self) -> Result<Vec<SimulatorDevice>> {
+        let output = self.simctl.run(&["list", "devices", "--json"]).await?;
+        parse_device_list(&output)
+    }
+
+    /// The one available device `selector` names
+    pub async fn resolve(&self, selector: &str) -> Result<SimulatorDevice> {
+        select_device(&self.list_devices().await?, selector)
+    }
+
+    /// Boot the device and wait until it reports `Booted`
+    pub async fn boot(&self, selector: &str) -> Result<SimulatorActionResult> {
+        let started = Instant::now();
+        let device = self.resolve(selector).await?;
+        if device.state == DeviceState::Booted {
+            return Ok(action_result("boot", device.clone(), device.state, false, started));
+        }
+
+        if device.state != DeviceState::Booting {
+            self.simctl.run(&["boot", &device.udid]).await?;
+        }
+
+        let deadline = started + self.boot_timeout;
+        loop {
+            let current = self.device(&device.udid).await?;
+            if current.state == DeviceState::Booted {
+                tracing::info!("Booted {} in {:?}", current.label(), started.elapsed());
+                return Ok(action_result("boot", current, device.state, true, started));
+            }
+            if Instant::now() >= deadline {
+                return Err(AxiomMCPError::simulator("simulator", "boot", format!(
+                    "{} did not finish booting within {:?} (state: {:?})",
+                    current.label(), self.boot_timeout, current.state
+                )).transient());
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    pub async fn shutdown(&self, selector: &str) -> Result<SimulatorActionResult> {
+        let started = Instant::now();
+        let device = self.resolve(selector).await?;
+        if device.state == DeviceState::Shutdown {
+            return Ok(action_result("shutdown", device.clone(), device.state, false, started));
+        }
+
+        self.simctl.run(&["shutdown", &device.udid]).await?;
+        let current = self.device(&device.udid).await?;
+        Ok(action_result("shutdown", current, device.state, true, started))
+    }
+
+    /// Erase all content and settings; the device must be shut down first
+    pub async fn erase(&self, selector: &str) -> Result<SimulatorActionResult> {
+        let started = Instant::now();
+        let device = self.resolve(selector).await?;
+        if device.state != DeviceState::Shutdown {
+            return Err(AxiomMCPError::simulator("simulator", "erase", format!(
+                "{} is {:?}; shut it down before erasing",
+                device.label(), device.state
+            )));
+        }
+
+        self.simctl.run(&["erase", &device.udid]).await?;
+        let current = self.device(&device.udid).await?;
+        Ok(action_result("erase", current, device.state, true, started))
+    }
+
+    /// Install an app bundle built for the simulator
+    pub async fn install_app(&self, request: &InstallAppRequest) -> Result<InstallAppResult> {
+        let started = Instant::now();
+        check_app_bundle(&request.app_path)?;
+        let device = self.resolve(&request.device).await?;
+
+        let app_path = request.app_path.to_string_lossy();
+        self.simctl
+            .run(&["install", &device.udid, &app_path])
+            .await
+            .map_err(|e| actionable_error(e, &device, &app_path))?;
+
+        tracing::info!("Installed {} on {}", app_path, device.label());
+        Ok(InstallAppResult {
+            device,
+            app_path: request.app_path.clone(),
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Launch an installed app, optionally capturing its console output
+    pub async fn launch_app(&self, request: &LaunchAppRequest) -> Result<LaunchAppResult> {
+        if request.bundle_id.trim().is_empty() {
+            return Err(AxiomMCPError::validation("simulator", "launch app", "Bundle identifier cannot be empty"));
+        }
+        let device = self.resolve(&request.device).await?;
+        self.launch_on(device, request).await
+    }
+
+    /// Launch on an already resolved device, timing only the launch itself;
+    /// `request.device` is ignored
+    pub async fn launch_on(&self, device: SimulatorDevice, request: &LaunchAppRequest) -> Result<LaunchAppResult> {
+        let started = Instant::now();
+        let capture = request.console_seconds.map(|seconds| Duration::from_secs(seconds).min(MAX_CONSOLE_CAPTURE));
+
+        let args = launch_args(&device.udid, request, capture.is_some());
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let env: Vec<(String, String)> = request.environment.clone().into_iter().collect();
+        let stdout = self.simctl
+            .run_with_env(&args, &env, capture)
+            .await
+            .map_err(|e| actionable_error(e, &device, &request.bundle_id))?;
+
+        let (pid, output) = parse_launch_output(&stdout, &request.bundle_id)?;
+        tracing::info!("Launched {} on {} with pid {}", request.bundle_id, device.label(), pid);
+        Ok(LaunchAppResult {
+            device,
+            bundle_id: request.bundle_id.clone(),
+            pid,
+            output: capture.map(|_| output),
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Stop the app if it is running, returning whether it was
+    pub async fn terminate_app(&self, device: &SimulatorDevice, bundle_id: &str) -> Result<bool> {
+        match self.simctl.run(&["terminate", &device.udid, bundle_id]).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.to_string().to_lowercase().contains("found nothing to terminate") => Ok(false),
+            Err(e) => Err(actionable_error(e, device, bundle_id)),
+        }
+    }
+
+    /// Switch a booted device to the `light` or `dark` appearance
+    pub async fn set_appearance(&self, udid: &str, color_scheme: &str) -> Result<()> {
+        let appearance = appearance(color_scheme)?;
+        self.simctl.run(&["ui", udid, "appearance", appearance]).await?;
+        Ok(())
+    }
+
+    /// The appearance of a booted device, `None` when it has none to switch
+    pub async fn appearance(&self, udid: &str) -> Result<Option<String>> {
+        let output = self.simctl.run(&["ui", udid, "appearance"]).await?;
+        Ok(appearance(output.trim()).ok().map(str::to_string))
+    }
+    
+    /// Lay the app out in `window` on a booted iPad, or back in the full
+    /// screen when `None`, through the launchd environment the Axiom debug
+    /// harness resizes its scene from
+    pub async fn set_window_configuration(&self, udid: &str, window: Option<crate::device_matrix::WindowConfiguration>) -> Result<()> {
+        match window {
+            Some(window) => self.simctl.run(&["spawn", udid, "launchctl", "setenv", WINDOW_CONFIGURATION_ENV, window.name()]).await?,
+            None => self.simctl.run(&["spawn", udid, "launchctl", "unsetenv", WINDOW_CONFIGURATION_ENV]).await?,
+        };
+        Ok(())
+    }
+    
+    /// Set a booted device's language, region formats and keyboard; the
+    /// keyboard applies to text entered from then on, the language to apps
+    /// launched afterwards
+    pub async fn set_locale(&self, udid: &str, locale: &SimulatorLocale) -> Result<()> {
+        for args in locale_args(udid, locale) {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            self.simctl.run(&args).await?;
+        }
+        tracing::info!("Set {} to the {} locale with the {} keyboard", udid, locale.identifier, locale.keyboard());
+        Ok(())
+    }
+
+    /// Put `text` on a booted device's pasteboard, reading it back so text
+    /// the pasteboard mangled is an error rather than what gets pasted
+    pub async fn set_pasteboard(&self, udid: &str, text: &str) -> Result<()> {
+        self.simctl.run_with_input(&["pbcopy", udid], text.as_bytes()).await?;
+        let pasteboard = self.pasteboard(udid).await?;
+        if pasteboard != text {
+            return Err(AxiomMCPError::simulator("simulator", "set pasteboard", format!(
+                "The pasteboard of {} holds {:?} instead of {:?}", udid, pasteboard, text
+            )));
+        }
+        Ok(())
+    }
+
+    /// The text on a booted device's pasteboard
+    pub async fn pasteboard(&self, udid: &str) -> Result<String> {
+        self.simctl.run_with_input(&["pbpaste", udid], &[]).await
+    }
+    
+    /// Capture `udid` with `overrides` applied, putting the device back the
+    /// way it was whether or not the capture succeeds
+    pub async fn screenshot_with_overrides(&self, udid: &str, overrides: &CaptureOverrides) -> Result<Vec<u8>> {
+        let status_bar = overrides.status_bar.as_ref().map(|status_bar| status_bar_override_args(udid, status_bar)).transpose()?;
+        let restore_appearance = match &overrides.appearance {
+            Some(color_scheme) => {
+                let previous = self.appearance(udid).await?;
+                self.set_appearance(udid, color_scheme).await?;
+                previous.filter(|previous| previous != color_scheme)
+            }
+            None => None,
+        };
+
+        let captured = async {
+            if let Some(window) = overrides.window {
+                self.set_window_configuration(udid, Some(window)).await?;
+            }
+            if let Some(args) = &status_bar {
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                self.simctl.run(&args).await?;
+            }
+            self.screenshot(udid).await
+        }
+        .await;
+
+        // A failed override may still have changed part of the status bar
+        let mut cleared = Ok(());
+        if status_bar.is_some() {
+            cleared = self.simctl.run(&["status_bar", udid, "clear"]).await.map(|_| ());
+        }
+        if let Some(previous) = restore_appearance {
+            let restored = self.set_appearance(udid, &previous).await;
+            cleared = cleared.and(restored);
+        }
+        if overrides.window.is_some() {
+            let restored = self.set_window_configuration(udid, None).await;
+            cleared = cleared.and(restored);
+        }
+        match captured {
+            Ok(image) => cleared.map(|_| image),
+            Err(e) => {
+                if let Err(cleanup) = cleared {
+                    tracing::warn!("Could not clear the capture overrides of {}: {}", udid, cleanup);
+                }
+                Err(e)
+            }
+        }
+    }
+    
+    /// Reset what `request.options` asks for on a booted device, so runs
+    /// on it do not depend on what earlier runs left behind
+    pub async fn reset_state(&self, request: &ResetSimulatorStateRequest) -> Result<SimulatorStateReset> {
+        let device = self.resolve(&request.device).await?;
+        self.reset_state_on(device, &request.options).await
+    }
+
+    /// Reset on an already resolved device
+    pub async fn reset_state_on(&self, device: SimulatorDevice, options: &StateResetOptions) -> Result<SimulatorStateReset> {
+        let started = Instant::now();
+        if device.state != DeviceState::Booted {
+            return Err(AxiomMCPError::simulator("simulator", "reset state", format!(
+                "{} is {:?}; boot it before resetting its state",
+                device.label(), device.state
+            )));
+        }
+        if let Some(AppDataReset { app_path: Some(app_path), .. }) = &options.app_data {
+            check_app_bundle(app_path)?;
+        }
+
+        let mut reset = Vec::new();
+        for (step, args) in reset_state_args(&device.udid, options)? {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            let subject = match &step {
+                StateReset::AppUninstalled { bundle_id } => bundle_id.clone(),
+                StateReset::AppReinstalled { app_path, .. } => app_path.to_string_lossy().into_owned(),
+                other => other.to_string(),
+            };
+            self.simctl.run(&args).await.map_err(|e| actionable_error(e, &device, &subject))?;
+            reset.push(step);
+        }
+
+        tracing::info!("Reset {} things on {}", reset.len(), device.label());
+        Ok(SimulatorStateReset {
+            device,
+            reset,
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// PNG of what a booted device is showing
+    pub async fn screenshot(&self, udid: &str) -> Result<Vec<u8>> {
+        let path = std::env::temp_dir().join(format!("axiom-{}-{}.png", udid, uuid::Uuid::new_v4()));
+        let path_arg = path.to_string_lossy();
+        self.simctl.run(&["io", udid, "screenshot", &path_arg]).await?;
+        let image = tokio::fs::read(&path).await?;
+        let _ = tokio::fs::remove_file(&path).await;
+        Ok(image)
+    }
+
+    async fn device(&self, udid: &str) -> Result<SimulatorDevice> {
+        self.list_devices()
+            .await?
+            .into_iter()
+            .find(|device| device.udid == udid)
+            .ok_or_else(|| AxiomMCPError::simulator("simulator", "refresh device", format!("Simulator {} disappeared", udid)))
+    }
+}
+
+impl std::fmt::Debug for SimulatorController {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimulatorController")
+            .field("boot_timeout", &self.boot_timeout)
+            .field("poll_interval", &self.poll_interval)
+            .finish()
     }
-}
\ No newline at end of file
+}
+
+fn action_result(action: &str, device: SimulatorDevice, previous_state: DeviceState, changed: bool, started: Instant) -> SimulatorActionResult {
+    SimulatorActionResult {
+        action: action.to_string(),
+        device,
+        previous_state,
+        changed,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// The `simctl` commands resetting `options` on `udid`, each with what it resets
+///
+/// The app is uninstalled before permissions are reset, since that also
+/// drops its own, and reinstalled last so it starts from a reset device.
+pub fn reset_state_args(udid: &str, options: &StateResetOptions) -> Result<Vec<(StateReset, Vec<String>)>> {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    let mut steps = Vec::new();
+
+    if let Some(app) = &options.app_data {
+        if app.bundle_id.trim().is_empty() {
+            return Err(AxiomMCPError::validation("simulator", "reset state", "Bundle identifier cannot be empty"));
+        }
+        steps.push((
+            StateReset::AppUninstalled { bundle_id: app.bundle_id.clone() },
+            args(&["uninstall", udid, app.bundle_id.as_str()]),
+        ));
+    }
+    if let Some(privacy) = &options.privacy {
+        let services = if privacy.services.is_empty() { vec![PrivacyService::All] } else { privacy.services.clone() };
+        for service in services {
+            let mut command = args(&["privacy", udid, "reset", service.as_simctl()]);
+            command.extend(privacy.bundle_id.clone());
+            steps.push((StateReset::Privacy { service, bundle_id: privacy.bundle_id.clone() }, command));
+        }
+    }
+    if options.status_bar {
+        steps.push((StateReset::StatusBar, args(&["status_bar", udid, "clear"])));
+    }
+    if options.keychain {
+        steps.push((StateReset::Keychain, args(&["keychain", udid, "reset"])));
+    }
+    if let Some(color_scheme) = &options.appearance {
+        let appearance = appearance(color_scheme)?;
+        steps.push((
+            StateReset::Appearance { appearance: appearance.to_string() },
+            args(&["ui", udid, "appearance", appearance]),
+        ));
+    }
+    if let Some(AppDataReset { bundle_id, app_path: Some(app_path) }) = &options.app_data {
+        steps.push((
+            StateReset::AppReinstalled { bundle_id: bundle_id.clone(), app_path: app_path.clone() },
+            args(&["install", udid, app_path.to_string_lossy().as_ref()]),
+        ));
+    }
+    Ok(steps)
+}
+
+/// `simctl status_bar override` arguments, with the network modes the
+/// bar counts need to be shown
+pub fn status_bar_override_args(udid: &str, status_bar: &StatusBarOverride) -> Result<Vec<String>> {
+    let in_range = |name: &str, value: Option<u8>, max: u8| match value {
+        Some(value) if value > max => Err(AxiomMCPError::validation("simulator", "override status bar", format!(
+            "Status bar {} must be between 0 and {}, not {}", name, max, value
+        ))),
+        _ => Ok(()),
+    };
+    in_range("battery level", status_bar.battery_level, 100)?;
+    in_range("cellular bars", status_bar.cellular_bars, 4)?;
+    in_range("wifi bars", status_bar.wifi_bars, 3)?;
+    if status_bar == &StatusBarOverride::default() {
+        return Err(AxiomMCPError::validation("simulator", "override status bar", "Status bar override sets nothing"));
+    }
+
+    let mut args: Vec<String> = ["status_bar", udid, "override"].iter().map(|arg| arg.to_string()).collect();
+    let mut flag = |name: &str, value: String| {
+        args.push(format!("--{}", name));
+        args.push(value);
+    };
+    if let Some(time) = &status_bar.time {
+        flag("time", time.clone());
+    }
+    if let Some(level) = status_bar.battery_level {
+        flag("batteryLevel", level.to_string());
+    }
+    if let Some(state) = status_bar.battery_state {
+        flag("batteryState", state.as_simctl().to_string());
+    }
+    if let Some(bars) = status_bar.cellular_bars {
+        flag("cellularMode", "active".to_string());
+        flag("cellularBars", bars.to_string());
+    }
+    if let Some(bars) = status_bar.wifi_bars {
+        flag("wifiMode", "active".to_string());
+        flag("wifiBars", bars.to_string());
+    }
+    Ok(args)
+}
+
+fn appearance(color_scheme: &str) -> Result<&str> {
+    match color_scheme {
+        "light" | "dark" => Ok(color_scheme),
+        other => Err(AxiomMCPError::validation("simulator", "set appearance", format!(
+            "Unknown color scheme {}; expected light or dark", other
+        ))),
+    }
+}
+
+/// `simctl launch` arguments; `--terminate-running-process` so a relaunch
+/// after a rebuild picks up the new binary
+pub fn launch_args(udid: &str, request: &LaunchAppRequest, console: bool) -> Vec<String> {
+    let mut args = vec!["launch".to_string()];
+    if !request.keep_running {
+        args.push("--terminate-running-process".to_string());
+    }
+    if console {
+        args.push("--console-pty".to_string());
+    }
+    args.push(udid.to_string());
+    args.push(request.bundle_id.clone());
+    args.extend(request.arguments.iter().cloned());
+    args
+}
+
+/// The pid from the `<bundle id>: <pid>` line `simctl launch` starts with,
+/// and everything the app printed after it
+fn parse_launch_output(stdout: &str, bundle_id: &str) -> Result<(u32, String)> {
+    let mut lines = stdout.lines();
+    let pid = lines
+        .by_ref()
+        .find_map(|line| line.trim().strip_prefix(bundle_id)?.trim_start().strip_prefix(':')?.trim().parse().ok())
+        .ok_or_else(|| AxiomMCPError::simulator("simulator", "simctl launch", format!(
+            "simctl launch did not report a pid for {}: {}", bundle_id, stdout.trim()
+        )))?;
+    Ok((pid, lines.collect::<Vec<_>>().join("\n")))
+}
+
+/// Reject paths that cannot be a simulator app before asking simctl
+fn check_app_bundle(path: &Path) -> Result<()> {
+    if path.extension().and_then(|extension| extension.to_str()) != Some("app") {
+        return Err(AxiomMCPError::validation("simulator", "install app", format!(
+            "{} is not an .app bundle", path.display()
+        )));
+    }
+    if !path.is_dir() {
+        return Err(AxiomMCPError::validation("simulator", "install app", format!(
+            "{} does not exist; build the app first", path.display()
+        )));
+    }
+    if path.components().any(|component| component.as_os_str().to_string_lossy().ends_with("-iphoneos")) {
+        return Err(AxiomMCPError::validation("simulator", "install app", format!(
+            "{} was built for a device; build it for the iphonesimulator SDK instead", path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// `xcrun simctl` could not be started at all, typically without Xcode
+fn simctl_unavailable(error: std::io::Error) -> AxiomMCPError {
+    AxiomMCPError::toolchain("simulator", "run simctl", format!("Cannot run xcrun simctl: {}", error)).with_cause(error)
+}
+
+/// A simctl command that ran and failed; a device caught between states
+/// may accept the same command a moment later
+fn simctl_failed(args: &[&str], stderr: &str) -> AxiomMCPError {
+    let operation = format!("simctl {}", args.first().copied().unwrap_or_default());
+    let error = AxiomMCPError::simulator("simulator", operation, format!("simctl {} failed: {}", args.join(" "), stderr.trim()));
+    let lower = stderr.to_lowercase();
+    if TRANSIENT_SIMCTL_FAILURES.iter().any(|failure| lower.contains(failure)) {
+        error.transient()
+    } else {
+        error
+    }
+}
+
+/// Turn a simctl failure into an error saying what to do about it
+///
+/// `subject` is the app path or bundle id the command was about.
+fn actionable_error(error: AxiomMCPError, device: &SimulatorDevice, subject: &str) -> AxiomMCPError {
+    let message = error.to_string();
+    let lower = message.to_lowercase();
+
+    let hint = if lower.contains("current state: shutdown") || lower.contains("device is not booted") {
+        format!("{} is shut down; boot it before installing or launching", device.label())
+    } else if lower.contains("arch") && (lower.contains("mismatch") || lower.contains("incompatible") || lower.contains("matching")) {
+        format!(
+            "{} is not built for the simulator's architecture; build for the iphonesimulator SDK with an architecture the {} runtime supports",
+            subject, device.runtime
+        )
+    } else if lower.contains("unknown to frontboard") || lower.contains("not installed") || lower.contains("found nothing to launch") {
+        format!(
+            "No app with bundle id {} is installed on {}; install it first or check CFBundleIdentifier in its Info.plist",
+            subject, device.label()
+        )
+    } else if lower.contains("missing bundle id") || lower.contains("cfbundleidentifier") {
+        format!("{} has no valid CFBundleIdentifier in its Info.plist", subject)
+    } else {
+        return error;
+    };
+
+    let operation = error.context().map(|context| context.operation.clone()).unwrap_or_default();
+    AxiomMCPError::simulator("simulator", operation, format!("{} ({})", hint, message)).with_cause(error)
+}
+
+/// Pick the device `selector` names
+///
+/// A UDID selects that device. Otherwise every word of the selector must
+/// appear in the device's name or runtime, ignoring case, and only
+/// available devices are considered. When several match, one whose whole
+/// name is spelled out wins, so `iPhone 15` picks the iPhone 15 over the
+/// iPhone 15 Pro; a tie is an error listing the candidates.
+pub fn select_device(devices: &[SimulatorDevice], selector: &str) -> Result<SimulatorDevice> {
+    if let Some(device) = devices.iter().find(|device| device.udid.eq_ignore_ascii_case(selector.trim())) {
+        return Ok(device.clone());
+    }
+
+    let wanted = words(selector);
+    if wanted.is_empty() {
+        return Err(AxiomMCPError::validation("simulator", "select device", "Simulator selector cannot be empty"));
+    }
+
+    let matches: Vec<&SimulatorDevice> = devices
+        .iter()
+        .filter(|device| device.is_available)
+        .filter(|device| {
+            let mut offered = words(&device.name);
+            for word in words(&device.runtime) {
+                // `17` as well as `17.2`
+                if let Some((major, _)) = word.split_once('.') {
+                    offered.push(major.to_string());
+                }
+                offered.push(word);
+            }
+            wanted.iter().all(|word| offered.contains(word))
+        })
+        .collect();
+
+    let exact: Vec<&SimulatorDevice> = matches
+        .iter()
+        .copied()
+        .filter(|device| words(&device.name).iter().all(|word| wanted.contains(word)))
+        .collect();
+
+    let candidates = if exact.is_empty() { matches } else { exact };
+    match candidates.as_slice() {
+        [device] => Ok((*device).clone()),
+        [] => Err(AxiomMCPError::validation("simulator", "select device", format!(
+            "No available simulator matches '{}'", selector
+        ))),
+        several => {
+            let labels: Vec<String> = several.iter().map(|device| device.label()).collect();
+            Err(AxiomMCPError::validation("simulator", "select device", format!(
+                "'{}' matches {} simulators: {}; pass a UDID or add the runtime",
+                selector, several.len(), labels.join("; ")
+            )))
+        }
+    }
+}
+
+/// Lowercase words, splitting on anything but letters, digits and `.`
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '.'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
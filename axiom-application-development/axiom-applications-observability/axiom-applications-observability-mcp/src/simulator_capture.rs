@@ -0,0 +1,597 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::simulator::{DeviceState, SimulatorController, SimulatorDevice};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::AsyncReadExt;
+use tokio::task::JoinHandle;
+
+/// Where recordings and logs go unless configured otherwise, under the temp dir
+pub const DEFAULT_CAPTURE_DIR_NAME: &str = "axiom-captures";
+
+/// Recordings stop by themselves after this long
+pub const DEFAULT_MAX_RECORDING_SECS: u64 = 120;
+
+pub const DEFAULT_MAX_RECORDING_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Log output kept per capture; the rest is dropped
+pub const DEFAULT_MAX_LOG_BYTES: usize = 2 * 1024 * 1024;
+
+/// Artifacts older than this are deleted when the next capture starts
+pub const DEFAULT_RETENTION_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// How long an interrupted command gets to finish writing before it is killed
+const STOP_GRACE: Duration = Duration::from_secs(10);
+
+/// Output a capture command is allowed
+#[derive(Debug, Clone, Copy)]
+pub struct OutputLimits {
+    /// Interrupt the command after this long
+    pub stop_after: Option<Duration>,
+    /// Stdout kept; the command keeps running past it
+    pub max_output_bytes: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CapturedOutput {
+    pub stdout: String,
+    /// Stdout went past `max_output_bytes`
+    pub truncated: bool,
+}
+
+/// A `simctl` command running in the background until it is stopped
+#[async_trait]
+pub trait CaptureProcess: Send {
+    /// Interrupt the command like Ctrl-C would, so it finishes the file it
+    /// writes, and collect its stdout
+    async fn stop(self: Box<Self>) -> Result<CapturedOutput>;
+}
+
+/// Starts background `xcrun simctl` commands, behind a trait so tests can script them
+#[async_trait]
+pub trait CaptureCommand: Send + Sync {
+    async fn start(&self, args: &[String], limits: OutputLimits) -> Result<Box<dyn CaptureProcess>>;
+}
+
+/// Runs the real `xcrun simctl`
+#[derive(Debug, Default)]
+pub struct XcrunCapture;
+
+struct XcrunCaptureProcess {
+    child: tokio::process::Child,
+    stdout: JoinHandle<std::io::Result<(Vec<u8>, bool)>>,
+    watchdog: Option<JoinHandle<()>>,
+}
+
+#[async_trait]
+impl CaptureCommand for XcrunCapture {
+    async fn start(&self, args: &[String], limits: OutputLimits) -> Result<Box<dyn CaptureProcess>> {
+        let mut child = tokio::process::Command::new("xcrun")
+            .arg("simctl")
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| AxiomMCPError::toolchain("simulator_capture", "start capture", format!("Cannot run xcrun simctl: {}", e)).with_cause(e))?;
+
+        // Keep reading past the cap, or the command blocks on a full pipe
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let max_output_bytes = limits.max_output_bytes;
+        let stdout = tokio::spawn(async move {
+            let mut kept = Vec::new();
+            let mut truncated = false;
+            let mut buffer = [0u8; 8192];
+            loop {
+                let read = stdout.read(&mut buffer).await?;
+                if read == 0 {
+                    return Ok::<_, std::io::Error>((kept, truncated));
+                }
+                let room = max_output_bytes.saturating_sub(kept.len());
+                kept.extend_from_slice(&buffer[..read.min(room)]);
+                truncated |= read > room;
+            }
+        });
+
+        let watchdog = match (limits.stop_after, child.id()) {
+            (Some(after), Some(pid)) => Some(tokio::spawn(async move {
+                tokio::time::sleep(after).await;
+                if let Err(e) = interrupt(pid).await {
+                    tracing::warn!("Could not stop simctl ({}) after {:?}: {}", pid, after, e);
+                }
+            })),
+            _ => None,
+        };
+
+        Ok(Box::new(XcrunCaptureProcess { child, stdout, watchdog }))
+    }
+}
+
+#[async_trait]
+impl CaptureProcess for XcrunCaptureProcess {
+    async fn stop(mut self: Box<Self>) -> Result<CapturedOutput> {
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.abort();
+        }
+        // No pid once it exited, when the watchdog already stopped it
+        if let Some(pid) = self.child.id() {
+            interrupt(pid).await?;
+        }
+        if tokio::time::timeout(STOP_GRACE, self.child.wait()).await.is_err() {
+            tracing::warn!("simctl did not finish within {:?} of being interrupted; killing it", STOP_GRACE);
+            self.child.kill().await?;
+        }
+
+        let (stdout, truncated) = (&mut self.stdout).await??;
+        Ok(CapturedOutput { stdout: String::from_utf8_lossy(&stdout).into_owned(), truncated })
+    }
+}
+
+/// SIGINT, which `recordVideo` and `log stream` both finish cleanly on
+async fn interrupt(pid: u32) -> Result<()> {
+    let status = tokio::process::Command::new("kill").args(["-INT", &pid.to_string()]).status().await?;
+    if !status.success() {
+        return Err(AxiomMCPError::toolchain(
+            "simulator_capture", "interrupt process", format!("Could not interrupt process {}", pid),
+        ));
+    }
+    Ok(())
+}
+
+/// How large captures may get and how long they are kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureLimits {
+    pub max_recording_secs: u64,
+    /// A longer recording is deleted rather than kept
+    pub max_recording_bytes: u64,
+    pub max_log_bytes: usize,
+    pub retention_secs: u64,
+}
+
+impl Default for CaptureLimits {
+    fn default() -> Self {
+        Self {
+            max_recording_secs: DEFAULT_MAX_RECORDING_SECS,
+            max_recording_bytes: DEFAULT_MAX_RECORDING_BYTES,
+            max_log_bytes: DEFAULT_MAX_LOG_BYTES,
+            retention_secs: DEFAULT_RETENTION_SECS,
+        }
+    }
+}
+
+/// Record the screen of booted simulator `device`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartRecordingRequest {
+    /// UDID, or name and runtime
+    pub device: String,
+    /// Stop sooner than the configured cap
+    #[serde(default)]
+    pub max_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHandle {
+    pub recording_id: String,
+    pub device: SimulatorDevice,
+    pub path: PathBuf,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    pub recording_id: String,
+    pub device: SimulatorDevice,
+    pub path: PathBuf,
+    pub duration_ms: u64,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    #[default]
+    Info,
+    Default,
+    Error,
+    Fault,
+}
+
+impl LogLevel {
+    /// From the `messageType` of a `log stream` entry
+    pub fn parse(message_type: &str) -> Option<Self> {
+        match message_type.to_ascii_lowercase().as_str() {
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "default" => Some(LogLevel::Default),
+            "error" => Some(LogLevel::Error),
+            "fault" => Some(LogLevel::Fault),
+            _ => None,
+        }
+    }
+
+    /// `log stream --level` only knows the three lowest levels
+    fn stream_level(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            _ => "default",
+        }
+    }
+}
+
+/// Stream the logs an app writes under `subsystem` on booted simulator `device`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogCaptureRequest {
+    /// UDID, or name and runtime
+    pub device: String,
+    pub subsystem: String,
+    /// Every category of the subsystem when empty
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub min_level: LogLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogCaptureHandle {
+    pub capture_id: String,
+    pub device: SimulatorDevice,
+    pub predicate: String,
+    pub min_level: LogLevel,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub subsystem: String,
+    pub category: String,
+    pub process: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogCapture {
+    pub capture_id: String,
+    pub device: SimulatorDevice,
+    pub predicate: String,
+    pub entries: Vec<LogEntry>,
+    /// Output went past the size cap, so later entries are missing
+    pub truncated: bool,
+    /// The entries as JSON, kept with the recordings
+    pub path: PathBuf,
+    pub duration_ms: u64,
+}
+
+/// What a failed validation left behind to see how it got there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureEvidence {
+    pub failed_checks: Vec<String>,
+    pub recording: Option<Recording>,
+    pub logs: Option<LogCapture>,
+    /// Why the recording or logs are missing
+    pub capture_errors: Vec<String>,
+}
+
+/// A recording and a log capture running together while checks run
+#[derive(Debug)]
+pub struct CaptureSession {
+    recording: Option<RecordingHandle>,
+    logs: Option<LogCaptureHandle>,
+    errors: Vec<String>,
+}
+
+struct Running<H> {
+    handle: H,
+    process: Box<dyn CaptureProcess>,
+    started: Instant,
+}
+
+/// Records simulator screens and streams app logs into an artifacts
+/// directory, deleting what is older than the retention window whenever a
+/// capture starts
+pub struct SimulatorCapture {
+    command: std::sync::Arc<dyn CaptureCommand>,
+    artifacts_dir: PathBuf,
+    limits: CaptureLimits,
+    recordings: Mutex<BTreeMap<String, Running<RecordingHandle>>>,
+    log_captures: Mutex<BTreeMap<String, Running<LogCaptureHandle>>>,
+}
+
+impl std::fmt::Debug for SimulatorCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimulatorCapture")
+            .field("artifacts_dir", &self.artifacts_dir)
+            .field("limits", &self.limits)
+            .field("recordings", &self.recordings.lock().unwrap().keys().collect::<Vec<_>>())
+            .field("log_captures", &self.log_captures.lock().unwrap().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SimulatorCapture {
+    pub fn new(artifacts_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            command: std::sync::Arc::new(XcrunCapture),
+            artifacts_dir: artifacts_dir.into(),
+            limits: CaptureLimits::default(),
+            recordings: Mutex::new(BTreeMap::new()),
+            log_captures: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn with_command(mut self, command: std::sync::Arc<dyn CaptureCommand>) -> Self {
+        self.command = command;
+        self
+    }
+
+    pub fn with_limits(mut self, limits: CaptureLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn artifacts_dir(&self) -> &Path {
+        &self.artifacts_dir
+    }
+
+    pub async fn start_recording(&self, simulator: &SimulatorController, request: &StartRecordingRequest) -> Result<RecordingHandle> {
+        let device = booted(simulator, &request.device, "recording").await?;
+        self.prepare().await?;
+
+        let recording_id = uuid::Uuid::new_v4().to_string();
+        let path = self.artifacts_dir.join(format!("recording-{}.mp4", recording_id));
+        let max_secs = request.max_seconds.unwrap_or(self.limits.max_recording_secs).min(self.limits.max_recording_secs);
+        let limits = OutputLimits { stop_after: Some(Duration::from_secs(max_secs)), max_output_bytes: 64 * 1024 };
+        let process = self.command.start(&record_video_args(&device.udid, &path), limits).await?;
+
+        let handle = RecordingHandle { recording_id: recording_id.clone(), device, path, started_at: chrono::Utc::now() };
+        tracing::info!("Recording {} to {}", handle.device.label(), handle.path.display());
+        self.recordings.lock().unwrap().insert(recording_id, Running { handle: handle.clone(), process, started: Instant::now() });
+        Ok(handle)
+    }
+
+    pub async fn stop_recording(&self, recording_id: &str) -> Result<Recording> {
+        let running = self.recordings.lock().unwrap().remove(recording_id).ok_or_else(|| {
+            AxiomMCPError::validation("simulator_capture", "stop recording", format!("No recording {} is running", recording_id))
+        })?;
+        running.process.stop().await?;
+        let duration_ms = running.started.elapsed().as_millis() as u64;
+        let RecordingHandle { recording_id, device, path, .. } = running.handle;
+
+        let size_bytes = tokio::fs::metadata(&path).await.map_err(|e| {
+            AxiomMCPError::toolchain(
+                "simulator_capture", "stop recording", format!("simctl did not write the recording {}: {}", path.display(), e),
+            ).with_cause(e)
+        })?.len();
+        if size_bytes > self.limits.max_recording_bytes {
+            tokio::fs::remove_file(&path).await?;
+            return Err(AxiomMCPError::validation("simulator_capture", "stop recording", format!(
+                "Recording {} was {} bytes, over the {} byte cap, and was deleted; record a shorter span",
+                recording_id, size_bytes, self.limits.max_recording_bytes
+            )));
+        }
+        Ok(Recording { recording_id, device, path, duration_ms, size_bytes })
+    }
+
+    pub async fn start_log_capture(&self, simulator: &SimulatorController, request: &LogCaptureRequest) -> Result<LogCaptureHandle> {
+        let predicate = log_predicate(&request.subsystem, &request.categories)?;
+        let device = booted(simulator, &request.device, "capturing its logs").await?;
+        self.prepare().await?;
+
+        let capture_id = uuid::Uuid::new_v4().to_string();
+        let limits = OutputLimits { stop_after: None, max_output_bytes: self.limits.max_log_bytes };
+        let process = self.command.start(&log_stream_args(&device.udid, &predicate, request.min_level), limits).await?;
+
+        let handle = LogCaptureHandle { capture_id: capture_id.clone(), device, predicate, min_level: request.min_level };
+        self.log_captures.lock().unwrap().insert(capture_id, Running { handle: handle.clone(), process, started: Instant::now() });
+        Ok(handle)
+    }
+
+    pub async fn stop_log_capture(&self, capture_id: &str) -> Result<LogCapture> {
+        let running = self.log_captures.lock().unwrap().remove(capture_id).ok_or_else(|| {
+            AxiomMCPError::validation("simulator_capture", "stop log capture", format!("No log capture {} is running", capture_id))
+        })?;
+        let output = running.process.stop().await?;
+        let LogCaptureHandle { capture_id, device, predicate, min_level } = running.handle;
+
+        let entries = parse_log_stream(&output.stdout, min_level);
+        let path = self.artifacts_dir.join(format!("logs-{}.json", capture_id));
+        tokio::fs::write(&path, serde_json::to_vec_pretty(&entries)?).await?;
+        Ok(LogCapture {
+            capture_id,
+            device,
+            predicate,
+            entries,
+            truncated: output.truncated,
+            path,
+            duration_ms: running.started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Record `device` and stream `subsystem`'s logs until `finish_session`;
+    /// whichever cannot start is noted rather than failing the session
+    pub async fn start_session(&self, simulator: &SimulatorController, device: &str, subsystem: &str) -> CaptureSession {
+        let mut errors = Vec::new();
+        let recording = match self.start_recording(simulator, &StartRecordingRequest { device: device.to_string(), max_seconds: None }).await {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                errors.push(format!("Recording: {}", e));
+                None
+            }
+        };
+        let logs = LogCaptureRequest {
+            device: device.to_string(),
+            subsystem: subsystem.to_string(),
+            categories: Vec::new(),
+            min_level: LogLevel::default(),
+        };
+        let logs = match self.start_log_capture(simulator, &logs).await {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                errors.push(format!("Logs: {}", e));
+                None
+            }
+        };
+        CaptureSession { recording, logs, errors }
+    }
+
+    /// Stop the session; its artifacts are evidence when a check failed and
+    /// are deleted otherwise
+    pub async fn finish_session(&self, session: CaptureSession, failed_checks: Vec<String>) -> Option<FailureEvidence> {
+        let mut capture_errors = session.errors;
+        let recording = match session.recording {
+            Some(handle) => match self.stop_recording(&handle.recording_id).await {
+                Ok(recording) => Some(recording),
+                Err(e) => {
+                    capture_errors.push(format!("Recording: {}", e));
+                    None
+                }
+            },
+            None => None,
+        };
+        let logs = match session.logs {
+            Some(handle) => match self.stop_log_capture(&handle.capture_id).await {
+                Ok(logs) => Some(logs),
+                Err(e) => {
+                    capture_errors.push(format!("Logs: {}", e));
+                    None
+                }
+            },
+            None => None,
+        };
+
+        if failed_checks.is_empty() {
+            let paths = recording.iter().map(|recording| &recording.path).chain(logs.iter().map(|logs| &logs.path));
+            for path in paths {
+                if let Err(e) = tokio::fs::remove_file(path).await {
+                    tracing::warn!("Could not delete {}: {}", path.display(), e);
+                }
+            }
+            return None;
+        }
+        Some(FailureEvidence { failed_checks, recording, logs, capture_errors })
+    }
+
+    /// Create the artifacts directory and delete what outlived the retention window
+    async fn prepare(&self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.artifacts_dir).await?;
+        let retention = Duration::from_secs(self.limits.retention_secs);
+        match prune_artifacts(&self.artifacts_dir, retention, SystemTime::now()) {
+            Ok(pruned) if !pruned.is_empty() => {
+                tracing::info!("Deleted {} captures older than {:?} from {}", pruned.len(), retention, self.artifacts_dir.display());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Could not prune {}: {}", self.artifacts_dir.display(), e),
+        }
+        Ok(())
+    }
+}
+
+async fn booted(simulator: &SimulatorController, selector: &str, purpose: &str) -> Result<SimulatorDevice> {
+    let device = simulator.resolve(selector).await?;
+    if device.state != DeviceState::Booted {
+        return Err(AxiomMCPError::simulator("simulator_capture", "check booted", format!(
+            "{} is {:?}; boot it before {}", device.label(), device.state, purpose
+        )));
+    }
+    Ok(device)
+}
+
+/// `simctl io recordVideo` arguments; `--force` since a path is never reused
+pub fn record_video_args(udid: &str, path: &Path) -> Vec<String> {
+    vec![
+        "io".to_string(),
+        udid.to_string(),
+        "recordVideo".to_string(),
+        "--codec=h264".to_string(),
+        "--force".to_string(),
+        path.to_string_lossy().into_owned(),
+    ]
+}
+
+/// `log stream` inside the simulator, one JSON entry per line
+pub fn log_stream_args(udid: &str, predicate: &str, min_level: LogLevel) -> Vec<String> {
+    ["spawn", udid, "log", "stream", "--style", "ndjson", "--level", min_level.stream_level(), "--predicate", predicate]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect()
+}
+
+/// A `log` predicate matching `subsystem`, narrowed to `categories` when
+/// any are given
+pub fn log_predicate(subsystem: &str, categories: &[String]) -> Result<String> {
+    if subsystem.trim().is_empty() {
+        return Err(AxiomMCPError::validation("simulator_capture", "build log predicate", "Log subsystem cannot be empty"));
+    }
+    let mut predicate = format!("subsystem == {}", quoted(subsystem));
+    if !categories.is_empty() {
+        let categories: Vec<String> = categories.iter().map(|category| format!("category == {}", quoted(category))).collect();
+        predicate.push_str(&format!(" AND ({})", categories.join(" OR ")));
+    }
+    Ok(predicate)
+}
+
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Entries of `log stream --style ndjson` output at `min_level` or above
+///
+/// The banner `log` prints first and a line cut off by the size cap are
+/// not JSON and are skipped.
+pub fn parse_log_stream(output: &str, min_level: LogLevel) -> Vec<LogEntry> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|entry| {
+            let text = |key: &str| entry.get(key).and_then(|value| value.as_str()).unwrap_or_default().to_string();
+            let level = LogLevel::parse(entry.get("messageType")?.as_str()?)?;
+            let process = Path::new(&text("processImagePath"))
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Some(LogEntry {
+                timestamp: text("timestamp"),
+                level,
+                subsystem: text("subsystem"),
+                category: text("category"),
+                process,
+                message: text("eventMessage"),
+            })
+        })
+        .filter(|entry| entry.level >= min_level)
+        .collect()
+}
+
+/// Delete the files in `dir` last modified more than `retention` before
+/// `now`, returning them sorted; a missing directory has nothing to delete
+pub fn prune_artifacts(dir: &Path, retention: Duration, now: SystemTime) -> Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut pruned = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        // A clock set back makes everything look new, which only keeps it longer
+        let age = now.duration_since(metadata.modified()?).unwrap_or_default();
+        if age > retention {
+            std::fs::remove_file(entry.path())?;
+            pruned.push(entry.path());
+        }
+    }
+    pruned.sort();
+    Ok(pruned)
+}
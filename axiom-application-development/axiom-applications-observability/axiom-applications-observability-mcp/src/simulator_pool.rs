@@ -0,0 +1,603 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::screenshot_store::{ScreenshotStore, StoredImage};
+use crate::tool_metrics::time_stage;
+use crate::simulator::{DeviceState, SimulatorController, SimulatorDevice, StateReset, StateResetOptions};
+use crate::types::ScreenshotConfiguration;
+use crate::ui_script::{UiScript, UiScriptRunner};
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Simulators booted at once unless configured otherwise
+pub const DEFAULT_MAX_DEVICES: usize = 3;
+
+/// Below this much free memory the matrix runs on a single simulator
+pub const DEFAULT_MIN_FREE_MEMORY_MB: u64 = 4096;
+
+/// Rough footprint of one booted simulator
+pub const DEFAULT_MEMORY_PER_DEVICE_MB: u64 = 1536;
+
+/// What happens to booted simulators once a matrix run finishes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolPolicy {
+    /// Keep them booted for the next run
+    KeepWarm,
+    /// Shut them all down
+    TearDown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatorPoolConfig {
+    pub max_devices: usize,
+    pub policy: PoolPolicy,
+    /// Free memory needed before more than one simulator is booted
+    pub min_free_memory_mb: u64,
+    /// Memory budgeted per simulator when deciding how many to boot
+    pub memory_per_device_mb: u64,
+}
+
+impl Default for SimulatorPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_devices: DEFAULT_MAX_DEVICES,
+            policy: PoolPolicy::KeepWarm,
+            min_free_memory_mb: DEFAULT_MIN_FREE_MEMORY_MB,
+            memory_per_device_mb: DEFAULT_MEMORY_PER_DEVICE_MB,
+        }
+    }
+}
+
+/// A booted simulator owned by the pool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PooledDevice {
+    pub udid: String,
+    pub device_type: String,
+    /// Runtime the configuration asked for; `None` for whichever the
+    /// simulator of the device type has
+    #[serde(default)]
+    pub runtime: Option<String>,
+}
+
+impl PooledDevice {
+    /// The device type and runtime it captures configurations of
+    pub fn simulator(&self) -> (&str, Option<&str>) {
+        (&self.device_type, self.runtime.as_deref())
+    }
+}
+
+/// Boots, captures on and shuts down simulators for the pool
+#[async_trait]
+pub trait SimulatorBackend: Send + Sync {
+    /// Boot a simulator of the configuration's device type and runtime
+    /// that the pool does not hold yet
+    async fn boot(&self, configuration: &ScreenshotConfiguration, exclude: &[String]) -> Result<PooledDevice>;
+
+    /// Capture with `configuration.overrides()` applied, clearing them
+    /// afterwards even when the capture fails
+    async fn capture(&self, device: &PooledDevice, configuration: &ScreenshotConfiguration) -> Result<Vec<u8>>;
+
+    async fn shutdown(&self, device: &PooledDevice) -> Result<()>;
+
+    /// Reset what `options` asks for on a booted simulator
+    async fn reset_state(&self, device: &PooledDevice, options: &StateResetOptions) -> Result<Vec<StateReset>>;
+
+    async fn free_memory_mb(&self) -> Result<u64>;
+
+    /// The simulators a matrix can be resolved against; `None` when the
+    /// backend cannot tell, leaving each configuration's runtime unset
+    async fn installed_devices(&self) -> Result<Option<Vec<SimulatorDevice>>> {
+        Ok(None)
+    }
+}
+
+/// One configuration of the matrix, captured on `udid`
+#[derive(Debug, Clone)]
+pub struct PooledCapture {
+    pub configuration: ScreenshotConfiguration,
+    pub udid: String,
+    /// Empty when the pool has a screenshot store
+    pub image_data: Vec<u8>,
+    pub image: Option<StoredImage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolRunStats {
+    /// Simulators capturing at the same time
+    pub parallelism: usize,
+    pub booted_devices: usize,
+    /// Simulators kept warm from an earlier run and used again
+    pub reused_devices: usize,
+    pub shut_down_devices: usize,
+    /// Simulators whose state was reset before they captured
+    #[serde(default)]
+    pub reset_devices: usize,
+    /// Simulators that ran the pre-capture script before they captured
+    #[serde(default)]
+    pub scripted_devices: usize,
+    /// Why parallelism was reduced, if it was
+    pub guardrail: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolRun {
+    /// In the order the configurations were given
+    pub captures: Vec<PooledCapture>,
+    pub stats: PoolRunStats,
+}
+
+#[derive(Default)]
+struct PoolState {
+    /// Booted and not in use, least recently used first
+    idle: VecDeque<PooledDevice>,
+    /// Booted and held by a worker
+    busy: Vec<PooledDevice>,
+    /// Booted before the current run
+    warm: Vec<String>,
+    /// Reset during the current run
+    reset: Vec<String>,
+    /// Ran the pre-capture script during the current run
+    scripted: Vec<String>,
+    booted: usize,
+    reused: usize,
+    shut_down: usize,
+}
+
+/// What to do on each simulator before its first capture of a run
+#[derive(Clone, Copy, Default)]
+struct Preparation<'a> {
+    reset: Option<&'a StateResetOptions>,
+    script: Option<(&'a UiScriptRunner, &'a UiScript)>,
+}
+
+impl PoolState {
+    fn total(&self) -> usize {
+        self.idle.len() + self.busy.len()
+    }
+
+    fn udids(&self) -> Vec<String> {
+        self.idle.iter().chain(&self.busy).map(|device| device.udid.clone()).collect()
+    }
+}
+
+/// Configurations waiting to be captured, and the device types and
+/// runtimes workers hold
+struct WorkQueue {
+    pending: VecDeque<(usize, ScreenshotConfiguration)>,
+    claimed: Vec<(String, Option<String>)>,
+}
+
+impl WorkQueue {
+    /// The next configuration for a worker holding `current`: another for
+    /// the same simulator, otherwise the oldest for a simulator no other
+    /// worker holds
+    fn next(&mut self, current: Option<&PooledDevice>) -> Option<(usize, ScreenshotConfiguration)> {
+        if let Some(current) = current.map(PooledDevice::simulator) {
+            if let Some(position) = self.pending.iter().position(|(_, c)| c.simulator() == current) {
+                return self.pending.remove(position);
+            }
+            if let Some(claim) = self.claimed.iter().position(|(device_type, runtime)| (device_type.as_str(), runtime.as_deref()) == current) {
+                self.claimed.remove(claim);
+            }
+        }
+
+        let position = self.pending.iter().position(|(_, c)| {
+            !self.claimed.iter().any(|(device_type, runtime)| (device_type.as_str(), runtime.as_deref()) == c.simulator())
+        })?;
+        let next = self.pending.remove(position)?;
+        self.claimed.push((next.1.device_type.clone(), next.1.runtime.clone()));
+        Some(next)
+    }
+}
+
+/// Captures a screenshot matrix on several simulators at once
+///
+/// Each device type and runtime in the matrix gets one simulator, and up
+/// to `max_devices` of them capture at the same time; a worker moves on to
+/// another simulator once its own is done, evicting an idle simulator
+/// when the pool is full. Booted simulators are kept between runs under
+/// `PoolPolicy::KeepWarm`, so consecutive runs skip booting.
+pub struct SimulatorPool {
+    backend: Arc<dyn SimulatorBackend>,
+    config: SimulatorPoolConfig,
+    state: Mutex<PoolState>,
+    /// One run at a time; runs share the booted simulators
+    run_lock: tokio::sync::Mutex<()>,
+    /// Takes each capture as it is made, so a run does not hold its images
+    store: Option<Arc<ScreenshotStore>>,
+}
+
+impl SimulatorPool {
+    pub fn new(backend: Arc<dyn SimulatorBackend>, config: SimulatorPoolConfig) -> Self {
+        Self {
+            backend,
+            config,
+            state: Mutex::new(PoolState::default()),
+            run_lock: tokio::sync::Mutex::new(()),
+            store: None,
+        }
+    }
+
+    /// Write captures to `store`, leaving only references in the run
+    pub fn with_store(mut self, store: Arc<ScreenshotStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn config(&self) -> &SimulatorPoolConfig {
+        &self.config
+    }
+
+    /// Simulators kept booted between runs
+    pub fn warm_devices(&self) -> Vec<PooledDevice> {
+        self.state.lock().unwrap().idle.iter().cloned().collect()
+    }
+
+    /// The simulators installed on the backend, to resolve a device matrix against
+    pub async fn installed_devices(&self) -> Result<Option<Vec<SimulatorDevice>>> {
+        self.backend.installed_devices().await
+    }
+
+    /// Capture every configuration, spreading them over the pool's simulators
+    pub async fn capture_matrix(&self, configurations: Vec<ScreenshotConfiguration>) -> Result<PoolRun> {
+        self.run(configurations, Preparation::default()).await
+    }
+
+    /// Capture like `capture_matrix`, resetting `options` on each simulator
+    /// before its first capture of the run
+    pub async fn capture_clean_matrix(&self, configurations: Vec<ScreenshotConfiguration>, options: &StateResetOptions) -> Result<PoolRun> {
+        self.run(configurations, Preparation { reset: Some(options), script: None }).await
+    }
+
+    /// Capture like `capture_clean_matrix`, then drive each simulator's app
+    /// into the state to capture with `script` once it is reset. A script
+    /// that fails stops the run.
+    pub async fn capture_scripted_matrix(
+        &self,
+        configurations: Vec<ScreenshotConfiguration>,
+        reset: Option<&StateResetOptions>,
+        runner: &UiScriptRunner,
+        script: &UiScript,
+    ) -> Result<PoolRun> {
+        self.run(configurations, Preparation { reset, script: Some((runner, script)) }).await
+    }
+
+    async fn run(&self, configurations: Vec<ScreenshotConfiguration>, preparation: Preparation<'_>) -> Result<PoolRun> {
+        let _run = self.run_lock.lock().await;
+        let started = Instant::now();
+
+        {
+            let mut state = self.state.lock().unwrap();
+            state.warm = state.idle.iter().map(|device| device.udid.clone()).collect();
+            state.reset.clear();
+            state.scripted.clear();
+            state.booted = 0;
+            state.reused = 0;
+            state.shut_down = 0;
+        }
+
+        let mut simulators: Vec<(&str, Option<&str>)> = configurations.iter().map(ScreenshotConfiguration::simulator).collect();
+        simulators.sort_unstable();
+        simulators.dedup();
+        let (parallelism, guardrail) = self.parallelism(simulators.len()).await;
+        if let Some(reason) = &guardrail {
+            tracing::warn!("Capturing the screenshot matrix on {} simulator(s): {}", parallelism, reason);
+        }
+
+        let queue = Mutex::new(WorkQueue {
+            pending: configurations.into_iter().enumerate().collect(),
+            claimed: Vec::new(),
+        });
+        let results = join_all((0..parallelism).map(|_| self.worker(&queue, preparation))).await;
+
+        let mut captures = Vec::new();
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(worker_captures) => captures.extend(worker_captures),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        self.finish_run().await;
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+
+        captures.sort_by_key(|(index, _)| *index);
+        let state = self.state.lock().unwrap();
+        Ok(PoolRun {
+            captures: captures.into_iter().map(|(_, capture)| capture).collect(),
+            stats: PoolRunStats {
+                parallelism,
+                booted_devices: state.booted,
+                reused_devices: state.reused,
+                shut_down_devices: state.shut_down,
+                reset_devices: state.reset.len(),
+                scripted_devices: state.scripted.len(),
+                guardrail,
+                duration_ms: started.elapsed().as_millis() as u64,
+            },
+        })
+    }
+
+    /// Simulators to run at once, and why fewer than configured when so
+    async fn parallelism(&self, device_types: usize) -> (usize, Option<String>) {
+        let wanted = self.config.max_devices.max(1).min(device_types.max(1));
+        if wanted == 1 {
+            return (1, None);
+        }
+
+        let free = match self.backend.free_memory_mb().await {
+            Ok(free) => free,
+            Err(e) => return (1, Some(format!("free memory is unknown ({})", e))),
+        };
+        if free < self.config.min_free_memory_mb {
+            return (1, Some(format!(
+                "{} MB free is below the {} MB needed to run simulators in parallel",
+                free, self.config.min_free_memory_mb
+            )));
+        }
+
+        let affordable = (free / self.config.memory_per_device_mb.max(1)).max(1) as usize;
+        if affordable < wanted {
+            return (affordable, Some(format!(
+                "{} MB free only fits {} simulators at {} MB each",
+                free, affordable, self.config.memory_per_device_mb
+            )));
+        }
+        (wanted, None)
+    }
+
+    async fn worker(&self, queue: &Mutex<WorkQueue>, preparation: Preparation<'_>) -> Result<Vec<(usize, PooledCapture)>> {
+        let mut captures = Vec::new();
+        let mut device: Option<PooledDevice> = None;
+
+        let result = async {
+            loop {
+                let Some((index, configuration)) = queue.lock().unwrap().next(device.as_ref()) else {
+                    break;
+                };
+                if device.as_ref().is_some_and(|device| device.simulator() != configuration.simulator()) {
+                    self.release(device.take());
+                }
+                if device.is_none() {
+                    let acquired = self.acquire(&configuration).await?;
+                    device = Some(acquired.clone());
+                    if let Some(options) = preparation.reset {
+                        self.reset_once(&acquired, options).await?;
+                    }
+                    if let Some((runner, script)) = preparation.script {
+                        self.script_once(&acquired, runner, script).await?;
+                    }
+                }
+
+                let current = device.as_ref().expect("acquired above");
+                let stage = format!("capture {}", configuration.device_type);
+                let mut image_data = time_stage(stage, self.backend.capture(current, &configuration)).await?;
+                let image = match &self.store {
+                    Some(store) => Some(store.put(&std::mem::take(&mut image_data)).await?),
+                    None => None,
+                };
+                captures.push((index, PooledCapture {
+                    configuration,
+                    udid: current.udid.clone(),
+                    image_data,
+                    image,
+                }));
+            }
+            Ok::<_, AxiomMCPError>(())
+        }
+        .await;
+
+        self.release(device);
+        result.map(|_| captures)
+    }
+
+    /// A booted simulator for the configuration's device type and runtime,
+    /// booting one when none is idle and evicting the least recently used
+    /// idle one when the pool is full
+    async fn acquire(&self, configuration: &ScreenshotConfiguration) -> Result<PooledDevice> {
+        let (evicted, exclude) = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(position) = state.idle.iter().position(|device| device.simulator() == configuration.simulator()) {
+                let device = state.idle.remove(position).expect("position is in range");
+                if state.warm.contains(&device.udid) {
+                    state.warm.retain(|udid| udid != &device.udid);
+                    state.reused += 1;
+                }
+                state.busy.push(device.clone());
+                return Ok(device);
+            }
+
+            let evicted = if state.total() >= self.config.max_devices.max(1) {
+                state.idle.pop_front()
+            } else {
+                None
+            };
+            (evicted, state.udids())
+        };
+
+        if let Some(evicted) = evicted {
+            self.shut_down(&evicted).await;
+        }
+
+        let device = self.backend.boot(configuration, &exclude).await?;
+        tracing::info!("Booted {} ({}) for the screenshot matrix", device.device_type, device.udid);
+        let mut state = self.state.lock().unwrap();
+        state.booted += 1;
+        state.busy.push(device.clone());
+        Ok(device)
+    }
+
+    /// Reset a simulator unless it was already reset this run, when
+    /// another worker held it before
+    async fn reset_once(&self, device: &PooledDevice, options: &StateResetOptions) -> Result<()> {
+        if self.state.lock().unwrap().reset.contains(&device.udid) {
+            return Ok(());
+        }
+        let reset = self.backend.reset_state(device, options).await?;
+        tracing::info!("Reset {} things on {} before capturing", reset.len(), device.udid);
+        self.state.lock().unwrap().reset.push(device.udid.clone());
+        Ok(())
+    }
+
+    /// Run the pre-capture script on a simulator unless it already ran
+    /// there this run
+    async fn script_once(&self, device: &PooledDevice, runner: &UiScriptRunner, script: &UiScript) -> Result<()> {
+        if self.state.lock().unwrap().scripted.contains(&device.udid) {
+            return Ok(());
+        }
+        let run = runner.run(&device.udid, script).await;
+        if let Some(failure) = &run.failure {
+            return Err(AxiomMCPError::validation("ui_script", "run pre-capture script", format!(
+                "The pre-capture script on {} ({}) {}", device.device_type, device.udid, failure
+            )));
+        }
+        tracing::info!("Ran {} pre-capture steps on {}", run.completed.len(), device.udid);
+        self.state.lock().unwrap().scripted.push(device.udid.clone());
+        Ok(())
+    }
+
+    fn release(&self, device: Option<PooledDevice>) {
+        let Some(device) = device else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        state.busy.retain(|busy| busy.udid != device.udid);
+        state.idle.push_back(device);
+    }
+
+    async fn shut_down(&self, device: &PooledDevice) {
+        match self.backend.shutdown(device).await {
+            Ok(()) => self.state.lock().unwrap().shut_down += 1,
+            Err(e) => tracing::warn!("Shutting down {} failed: {}", device.udid, e),
+        }
+    }
+
+    /// Apply the pool policy to the simulators left idle
+    async fn finish_run(&self) {
+        let surplus: Vec<PooledDevice> = {
+            let mut state = self.state.lock().unwrap();
+            let keep = match self.config.policy {
+                PoolPolicy::KeepWarm => self.config.max_devices,
+                PoolPolicy::TearDown => 0,
+            };
+            let surplus = state.idle.len().saturating_sub(keep);
+            state.idle.drain(..surplus).collect()
+        };
+        for device in surplus {
+            self.shut_down(&device).await;
+        }
+    }
+}
+
+impl std::fmt::Debug for SimulatorPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimulatorPool")
+            .field("config", &self.config)
+            .field("warm_devices", &self.warm_devices())
+            .finish()
+    }
+}
+
+/// `SimulatorBackend` over `simctl`
+///
+/// Boots an existing device whose name matches the device type, on the
+/// configuration's runtime when it names one, captures
+/// with `simctl io screenshot` in the configuration's color scheme and
+/// status bar, and reads free memory from the host.
+pub struct SimctlBackend {
+    controller: Arc<SimulatorController>,
+}
+
+impl SimctlBackend {
+    pub fn new(controller: Arc<SimulatorController>) -> Self {
+        Self { controller }
+    }
+}
+
+#[async_trait]
+impl SimulatorBackend for SimctlBackend {
+    async fn boot(&self, configuration: &ScreenshotConfiguration, exclude: &[String]) -> Result<PooledDevice> {
+        let (device_type, runtime) = configuration.simulator();
+        let device = self.controller
+            .list_devices()
+            .await?
+            .into_iter()
+            .filter(|device| device.is_available && device.name == device_type && !exclude.contains(&device.udid))
+            .filter(|device| runtime.is_none() || runtime == Some(device.runtime.as_str()))
+            // Prefer a device that is already up
+            .min_by_key(|device| device.state != DeviceState::Booted)
+            .ok_or_else(|| AxiomMCPError::simulator("simulator_pool", "boot", format!(
+                "No free simulator named {} is available{}; create one with simctl create",
+                device_type,
+                runtime.map(|runtime| format!(" on {}", runtime)).unwrap_or_default()
+            )))?;
+
+        let booted = self.controller.boot(&device.udid).await?;
+        Ok(PooledDevice {
+            udid: booted.device.udid,
+            device_type: device_type.to_string(),
+            runtime: configuration.runtime.clone(),
+        })
+    }
+
+    async fn capture(&self, device: &PooledDevice, configuration: &ScreenshotConfiguration) -> Result<Vec<u8>> {
+        self.controller.screenshot_with_overrides(&device.udid, &configuration.overrides()).await
+    }
+
+    async fn shutdown(&self, device: &PooledDevice) -> Result<()> {
+        self.controller.shutdown(&device.udid).await.map(|_| ())
+    }
+
+    async fn reset_state(&self, device: &PooledDevice, options: &StateResetOptions) -> Result<Vec<StateReset>> {
+        let resolved = self.controller.resolve(&device.udid).await?;
+        Ok(self.controller.reset_state_on(resolved, options).await?.reset)
+    }
+
+    async fn free_memory_mb(&self) -> Result<u64> {
+        free_memory_mb().await
+    }
+
+    async fn installed_devices(&self) -> Result<Option<Vec<SimulatorDevice>>> {
+        self.controller.list_devices().await.map(Some)
+    }
+}
+
+/// Memory available to new processes on this host
+pub async fn free_memory_mb() -> Result<u64> {
+    if let Ok(meminfo) = tokio::fs::read_to_string("/proc/meminfo").await {
+        return meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix("MemAvailable:"))
+            .and_then(|value| value.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+            .map(|kb| kb / 1024)
+            .ok_or_else(|| AxiomMCPError::internal("simulator_pool", "read free memory", "MemAvailable missing from /proc/meminfo"));
+    }
+
+    // macOS: free, inactive and purgeable pages can all be handed out
+    let output = tokio::process::Command::new("vm_stat").output().await.map_err(|e| {
+        AxiomMCPError::toolchain("simulator_pool", "read free memory", format!("Cannot run vm_stat: {}", e)).with_cause(e)
+    })?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let page_size = text
+        .lines()
+        .next()
+        .and_then(|line| line.split("page size of ").nth(1))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|size| size.parse::<u64>().ok())
+        .unwrap_or(4096);
+    let pages: u64 = text
+        .lines()
+        .filter(|line| ["Pages free:", "Pages inactive:", "Pages purgeable:"].iter().any(|prefix| line.starts_with(prefix)))
+        .filter_map(|line| line.rsplit(':').next()?.trim().trim_end_matches('.').parse::<u64>().ok())
+        .sum();
+    Ok(pages * page_size / (1024 * 1024))
+}
@@ -0,0 +1,507 @@
+use crate::error::{AxiomMCPError, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Declaration modifiers that may precede `class`, `struct` or `actor`
+const DECLARATION_MODIFIERS: &[&str] = &["public", "internal", "private", "fileprivate", "open", "final"];
+
+/// Names the temp file of each unit handed to the syntax checker
+static NEXT_UNIT: AtomicU64 = AtomicU64::new(0);
+
+/// Which pass of the validator produced a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationTier {
+    /// Delimiter balance, unterminated literals and leftover template markers
+    Structure,
+    /// Errors reported by the Swift compiler's parser
+    Syntax,
+    /// Departures from Axiom conventions in otherwise valid code
+    Pattern,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeFinding {
+    pub tier: ValidationTier,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, when the finding points at a character
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl CodeFinding {
+    fn new(tier: ValidationTier, line: usize, column: Option<usize>, message: impl Into<String>) -> Self {
+        Self { tier, line, column, message: message.into() }
+    }
+}
+
+impl std::fmt::Display for CodeFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}:{}: {}", self.line, column, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/// Findings of every tier that ran over one unit of Swift
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SwiftValidation {
+    /// Structure and syntax findings; any of these make the code unusable
+    pub syntax_errors: Vec<CodeFinding>,
+    pub pattern_warnings: Vec<CodeFinding>,
+    /// Whether the compiler parsed the code, as opposed to only the
+    /// structural checks
+    pub syntax_checked: bool,
+}
+
+/// Parses a Swift source file, behind a trait so tests need no toolchain
+#[async_trait]
+pub trait SwiftSyntaxChecker: Send + Sync {
+    /// Syntax errors in the file at `source`; an error means the check
+    /// itself could not run
+    async fn check(&self, source: &Path) -> Result<Vec<CodeFinding>>;
+}
+
+/// Runs `swiftc -parse`, which stops after parsing and so needs neither
+/// the SDK nor the code's dependencies
+#[derive(Debug, Clone)]
+pub struct SwiftcSyntaxChecker {
+    swiftc: PathBuf,
+}
+
+impl SwiftcSyntaxChecker {
+    pub fn new(swiftc: impl Into<PathBuf>) -> Self {
+        Self { swiftc: swiftc.into() }
+    }
+
+    /// `swiftc` from `PATH`, or from Xcode through `xcrun`, when a
+    /// toolchain is installed
+    pub async fn detect() -> Option<Self> {
+        let on_path = tokio::process::Command::new("swiftc").arg("--version").output().await;
+        if on_path.is_ok_and(|output| output.status.success()) {
+            return Some(Self::new("swiftc"));
+        }
+
+        let output = tokio::process::Command::new("xcrun").args(["--find", "swiftc"]).output().await.ok()?;
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !path.is_empty()).then(|| Self::new(path))
+    }
+}
+
+#[async_trait]
+impl SwiftSyntaxChecker for SwiftcSyntaxChecker {
+    async fn check(&self, source: &Path) -> Result<Vec<CodeFinding>> {
+        let output = tokio::process::Command::new(&self.swiftc)
+            .arg("-parse")
+            .arg(source)
+            .output()
+            .await
+            .map_err(|e| {
+                AxiomMCPError::toolchain("swift_validation", "parse", format!("Cannot run {}: {}", self.swiftc.display(), e)).with_cause(e)
+            })?;
+        if output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let errors = parse_swiftc_diagnostics(&stderr);
+        if errors.is_empty() {
+            return Err(AxiomMCPError::toolchain("swift_validation", "parse", format!("swiftc -parse failed: {}", stderr.trim())));
+        }
+        Ok(errors)
+    }
+}
+
+/// Errors from `swiftc` diagnostics of the form
+/// `path:line:column: error: message`; warnings and notes are dropped
+pub fn parse_swiftc_diagnostics(output: &str) -> Vec<CodeFinding> {
+    output
+        .lines()
+        .filter_map(|diagnostic| {
+            let (location, message) = diagnostic.split_once(": error: ")?;
+            let mut parts = location.rsplitn(3, ':');
+            let column = parts.next()?.parse().ok()?;
+            let line = parts.next()?.parse().ok()?;
+            parts.next()?;
+            Some(CodeFinding::new(ValidationTier::Syntax, line, Some(column), message.trim()))
+        })
+        .collect()
+}
+
+/// Validates generated Swift in tiers: structural checks that need
+/// nothing but the text, then the compiler's parser when a checker is
+/// attached, then Axiom pattern checks
+#[derive(Default)]
+pub struct SwiftValidator {
+    syntax_checker: Option<Arc<dyn SwiftSyntaxChecker>>,
+    /// Compiler findings by source hash, since the same unit is often
+    /// validated again unchanged
+    syntax_cache: Mutex<HashMap<u64, Vec<CodeFinding>>>,
+}
+
+impl std::fmt::Debug for SwiftValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwiftValidator")
+            .field("syntax_checker", &self.syntax_checker.is_some())
+            .finish()
+    }
+}
+
+impl SwiftValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_syntax_checker(mut self, checker: Arc<dyn SwiftSyntaxChecker>) -> Self {
+        self.syntax_checker = Some(checker);
+        self
+    }
+
+    pub async fn validate(&self, code: &str) -> SwiftValidation {
+        let mut validation = SwiftValidation {
+            syntax_errors: check_structure(code),
+            pattern_warnings: check_patterns(code),
+            syntax_checked: false,
+        };
+
+        // The compiler would only repeat what the structural checks found
+        let checker = self.syntax_checker.as_ref().filter(|_| validation.syntax_errors.is_empty());
+        if let Some(checker) = checker {
+            match self.check_syntax(checker.as_ref(), code).await {
+                Ok(errors) => {
+                    validation.syntax_errors = errors;
+                    validation.syntax_checked = true;
+                }
+                Err(e) => tracing::warn!("Swift syntax check skipped: {}", e),
+            }
+        }
+
+        validation
+    }
+
+    async fn check_syntax(&self, checker: &dyn SwiftSyntaxChecker, code: &str) -> Result<Vec<CodeFinding>> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        code.hash(&mut hasher);
+        let key = hasher.finish();
+        let cached = self.syntax_cache.lock().unwrap().get(&key).cloned();
+        if let Some(errors) = cached {
+            return Ok(errors);
+        }
+
+        let source = std::env::temp_dir().join(format!(
+            "axiom-validate-{}-{}.swift",
+            std::process::id(),
+            NEXT_UNIT.fetch_add(1, Ordering::Relaxed)
+        ));
+        tokio::fs::write(&source, code).await?;
+        let checked = checker.check(&source).await;
+        if let Err(e) = tokio::fs::remove_file(&source).await {
+            tracing::debug!("Could not remove {}: {}", source.display(), e);
+        }
+
+        let errors = checked?;
+        self.syntax_cache.lock().unwrap().insert(key, errors.clone());
+        Ok(errors)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Frame {
+    Delimiter { open: char, line: usize, column: usize },
+    /// `\(` inside a string literal, which returns to the string when closed
+    Interpolation { multiline: bool, line: usize, column: usize },
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Code,
+    LineComment,
+    BlockComment { depth: usize, line: usize, column: usize },
+    Text { multiline: bool, line: usize, column: usize },
+}
+
+fn closing(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+/// Unbalanced delimiters, unterminated literals and comments, and
+/// unresolved template markers
+pub fn check_structure(code: &str) -> Vec<CodeFinding> {
+    let mut findings = check_delimiters(code);
+    findings.extend(find_template_markers(code));
+    findings.sort_by_key(|finding| (finding.line, finding.column));
+    findings
+}
+
+fn check_delimiters(code: &str) -> Vec<CodeFinding> {
+    let error = |line, column, message: String| CodeFinding::new(ValidationTier::Structure, line, Some(column), message);
+    let chars: Vec<char> = code.chars().collect();
+    let mut findings = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut mode = Mode::Code;
+    let (mut line, mut column) = (1, 1);
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let starts_triple_quote = chars[i..].starts_with(&['"', '"', '"']);
+        // Characters consumed at this position
+        let mut width = 1;
+
+        match mode {
+            Mode::Code => match c {
+                '/' if next == Some('/') => mode = Mode::LineComment,
+                '/' if next == Some('*') => {
+                    mode = Mode::BlockComment { depth: 1, line, column };
+                    width = 2;
+                }
+                '"' if starts_triple_quote => {
+                    mode = Mode::Text { multiline: true, line, column };
+                    width = 3;
+                }
+                '"' => mode = Mode::Text { multiline: false, line, column },
+                '(' | '[' | '{' => stack.push(Frame::Delimiter { open: c, line, column }),
+                ')' | ']' | '}' => {
+                    let matches = |frame: &Frame| match *frame {
+                        Frame::Delimiter { open, .. } => closing(open) == c,
+                        Frame::Interpolation { .. } => c == ')',
+                    };
+                    match stack.iter().rposition(matches) {
+                        Some(position) => {
+                            // Everything opened after the match was left open
+                            for frame in stack.drain(position + 1..).rev() {
+                                if let Frame::Delimiter { open, line: open_line, column: open_column } = frame {
+                                    findings.push(error(open_line, open_column, format!(
+                                        "'{}' is not closed before '{}' on line {}", open, c, line
+                                    )));
+                                }
+                            }
+                            if let Some(Frame::Interpolation { multiline, line: open_line, column: open_column }) = stack.pop() {
+                                mode = Mode::Text { multiline, line: open_line, column: open_column };
+                            }
+                        }
+                        None => findings.push(error(line, column, format!("Unexpected '{}' with nothing open", c))),
+                    }
+                }
+                _ => {}
+            },
+            Mode::LineComment => {
+                if c == '\n' {
+                    mode = Mode::Code;
+                }
+            }
+            Mode::BlockComment { depth, line: open_line, column: open_column } => {
+                if c == '/' && next == Some('*') {
+                    mode = Mode::BlockComment { depth: depth + 1, line: open_line, column: open_column };
+                    width = 2;
+                } else if c == '*' && next == Some('/') {
+                    mode = if depth == 1 {
+                        Mode::Code
+                    } else {
+                        Mode::BlockComment { depth: depth - 1, line: open_line, column: open_column }
+                    };
+                    width = 2;
+                }
+            }
+            Mode::Text { multiline, line: open_line, column: open_column } => match c {
+                '\\' if next == Some('(') => {
+                    stack.push(Frame::Interpolation { multiline, line: open_line, column: open_column });
+                    mode = Mode::Code;
+                    width = 2;
+                }
+                '\\' => width = 2,
+                '"' if multiline && starts_triple_quote => {
+                    mode = Mode::Code;
+                    width = 3;
+                }
+                '"' if !multiline => mode = Mode::Code,
+                '\n' if !multiline => {
+                    findings.push(error(open_line, open_column, "Unterminated string literal".to_string()));
+                    mode = Mode::Code;
+                }
+                _ => {}
+            },
+        }
+
+        for &consumed in chars.iter().skip(i).take(width) {
+            if consumed == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        i += width;
+    }
+
+    match mode {
+        Mode::BlockComment { line, column, .. } => findings.push(error(line, column, "Unterminated block comment".to_string())),
+        Mode::Text { line, column, .. } => findings.push(error(line, column, "Unterminated string literal".to_string())),
+        Mode::Code | Mode::LineComment => {}
+    }
+    for frame in stack {
+        match frame {
+            Frame::Delimiter { open, line, column } => {
+                findings.push(error(line, column, format!("'{}' is never closed", open)));
+            }
+            Frame::Interpolation { line, column, .. } => {
+                findings.push(error(line, column, "Unterminated string interpolation".to_string()));
+            }
+        }
+    }
+    findings
+}
+
+/// `{name}` markers as `process_template` fills them, along with
+/// `{{name}}` and Xcode `<#name#>` placeholders. A closure written as
+/// `{x}` without spaces reads the same and is reported too.
+fn find_template_markers(code: &str) -> Vec<CodeFinding> {
+    let mut findings = Vec::new();
+    for (index, text) in code.lines().enumerate() {
+        let mut search = 0;
+        while let Some(offset) = text[search..].find(['{', '<']) {
+            let start = search + offset;
+            let rest = &text[start..];
+            let marker = if let Some(inner) = rest.strip_prefix("<#") {
+                inner.find("#>").map(|end| &rest[..end + 4])
+            } else if let Some(inner) = rest.strip_prefix("{{") {
+                inner.find("}}").map(|end| &rest[..end + 4])
+            } else if let Some(inner) = rest.strip_prefix('{') {
+                let name_length = inner
+                    .find(|c: char| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'))
+                    .unwrap_or(inner.len());
+                let name = &inner[..name_length];
+                let named = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_');
+                (named && rest[1 + name_length..].starts_with('}')).then(|| &rest[..name_length + 2])
+            } else {
+                None
+            };
+
+            match marker {
+                Some(marker) => {
+                    findings.push(CodeFinding::new(
+                        ValidationTier::Structure,
+                        index + 1,
+                        Some(text[..start].chars().count() + 1),
+                        format!("Unresolved template marker {}", marker),
+                    ));
+                    search = start + marker.len();
+                }
+                None => search = start + 1,
+            }
+        }
+    }
+    findings
+}
+
+/// A `class`, `struct`, `actor` or `enum` declaration on one line
+struct Declaration<'a> {
+    kind: &'a str,
+    name: &'a str,
+    conformances: Vec<&'a str>,
+    attributes: Vec<&'a str>,
+}
+
+fn parse_declaration(text: &str) -> Option<Declaration<'_>> {
+    let code = text.split("//").next().unwrap_or_default();
+    let mut attributes = Vec::new();
+    let mut rest = code.trim_start();
+    loop {
+        let (word, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        if word.starts_with('@') {
+            attributes.push(word);
+        } else if !DECLARATION_MODIFIERS.contains(&word) {
+            break;
+        }
+        rest = remainder.trim_start();
+    }
+
+    let (kind, remainder) = rest.split_once(char::is_whitespace)?;
+    if !matches!(kind, "class" | "struct" | "actor" | "enum") {
+        return None;
+    }
+    let remainder = remainder.trim_start();
+    let name_length = remainder
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(remainder.len());
+    let name = &remainder[..name_length];
+    // `class func` and `class var` declare members, not types
+    if name.is_empty() || matches!(name, "func" | "var" | "let" | "subscript") {
+        return None;
+    }
+
+    let mut after = remainder[name_length..].trim_start();
+    if after.starts_with('<') {
+        after = after.find('>').map_or("", |end| after[end + 1..].trim_start());
+    }
+    let conformances = match after.strip_prefix(':') {
+        Some(list) => {
+            let list = list.split('{').next().unwrap_or_default();
+            let list = list.split(" where ").next().unwrap_or_default();
+            list.split(',').map(str::trim).filter(|c| !c.is_empty()).collect()
+        }
+        None => Vec::new(),
+    };
+
+    Some(Declaration { kind, name, conformances, attributes })
+}
+
+/// Axiom conventions: contexts are `@MainActor` classes and clients are
+/// actors, so state reaches views on the main actor and client state is
+/// isolated
+pub fn check_patterns(code: &str) -> Vec<CodeFinding> {
+    let warning = |line, message: String| CodeFinding::new(ValidationTier::Pattern, line, None, message);
+    let lines: Vec<&str> = code.lines().collect();
+    let mut findings = Vec::new();
+    let mut declares_types = false;
+
+    for (index, text) in lines.iter().enumerate() {
+        let Some(declaration) = parse_declaration(text) else {
+            continue;
+        };
+        declares_types = true;
+
+        // Attributes may also sit on the lines above the declaration
+        let mut attributes = declaration.attributes.clone();
+        for above in lines[..index].iter().rev().map(|above| above.trim()) {
+            if !above.starts_with('@') {
+                break;
+            }
+            attributes.extend(above.split_whitespace().filter(|word| word.starts_with('@')));
+        }
+
+        let is_context = declaration.name.ends_with("Context")
+            || declaration.conformances.iter().any(|conformance| conformance.ends_with("Context"));
+        if declaration.kind == "class" && is_context && !attributes.contains(&"@MainActor") {
+            findings.push(warning(index + 1, format!(
+                "Context {} is not @MainActor; contexts publish state to views on the main actor",
+                declaration.name
+            )));
+        }
+
+        if declaration.kind != "actor" && declaration.conformances.contains(&"AxiomClient") {
+            findings.push(warning(index + 1, format!(
+                "Client {} conforms to AxiomClient as a {}; clients must be actors",
+                declaration.name, declaration.kind
+            )));
+        }
+    }
+
+    let imports = lines.iter().any(|text| text.trim_start().starts_with("import "));
+    if declares_types && !imports {
+        findings.insert(0, warning(1, "No import statement".to_string()));
+    }
+    findings
+}
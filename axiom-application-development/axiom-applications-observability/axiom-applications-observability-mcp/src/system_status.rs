@@ -0,0 +1,462 @@
+use crate::code_generation::{TemplateInfo, TemplateKind, TemplateSource};
+use crate::connection::{ConnectionState, ConnectionStatus};
+use crate::intelligence_protocol::{HandshakeState, ServerProtocol, PROTOCOL_VERSION};
+use crate::mcp::MCPCapabilities;
+use crate::resource_locks::LockHolder;
+use crate::simulator::SimulatorController;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Failed reconnect attempts after which a connection counts as down
+/// rather than recovering
+pub const FAILED_AFTER_RECONNECT_ATTEMPTS: u32 = 3;
+
+/// File the storage probe writes and removes again
+const WRITE_CHECK_FILE_NAME: &str = ".axiom-write-check";
+
+/// A part of the MCP that can be ready or not, one per capability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    HotReload,
+    Intelligence,
+    Simulator,
+    CodeGeneration,
+    ScreenshotStorage,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 5] = [
+        Subsystem::HotReload,
+        Subsystem::Intelligence,
+        Subsystem::Simulator,
+        Subsystem::CodeGeneration,
+        Subsystem::ScreenshotStorage,
+    ];
+
+    /// The `MCPCapabilities` flag that enables it
+    pub fn capability(self) -> &'static str {
+        match self {
+            Subsystem::HotReload => "hot_reload_integration",
+            Subsystem::Intelligence => "intelligence_analysis",
+            Subsystem::Simulator => "simulator_management",
+            Subsystem::CodeGeneration => "code_generation",
+            Subsystem::ScreenshotStorage => "visual_analysis",
+        }
+    }
+
+    pub fn is_enabled(self, capabilities: &MCPCapabilities) -> bool {
+        match self {
+            Subsystem::HotReload => capabilities.hot_reload_integration,
+            Subsystem::Intelligence => capabilities.intelligence_analysis,
+            Subsystem::Simulator => capabilities.simulator_management,
+            Subsystem::CodeGeneration => capabilities.code_generation,
+            Subsystem::ScreenshotStorage => capabilities.visual_analysis,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Subsystem::HotReload => "hot reload",
+            Subsystem::Intelligence => "intelligence",
+            Subsystem::Simulator => "simulators",
+            Subsystem::CodeGeneration => "code generation",
+            Subsystem::ScreenshotStorage => "screenshot storage",
+        }
+    }
+}
+
+/// Ordered from best to worst, so the overall status is the maximum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessStatus {
+    /// Its capability is off, so it was not probed
+    Disabled,
+    Ready,
+    /// Usable, but recovering or partly unavailable
+    Degraded,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubsystemReport {
+    pub subsystem: Subsystem,
+    pub capability: String,
+    pub status: ReadinessStatus,
+    pub detail: String,
+    pub last_error: Option<String>,
+    /// What to do about a degraded or failed subsystem
+    pub remediation: Option<String>,
+    /// Features turned off because the server does not offer them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded_capabilities: Vec<String>,
+}
+
+impl SubsystemReport {
+    fn new(subsystem: Subsystem, status: ReadinessStatus, detail: impl Into<String>) -> Self {
+        Self {
+            subsystem,
+            capability: subsystem.capability().to_string(),
+            status,
+            detail: detail.into(),
+            last_error: None,
+            remediation: None,
+            degraded_capabilities: Vec::new(),
+        }
+    }
+
+    pub fn ready(subsystem: Subsystem, detail: impl Into<String>) -> Self {
+        Self::new(subsystem, ReadinessStatus::Ready, detail)
+    }
+
+    pub fn degraded(subsystem: Subsystem, detail: impl Into<String>) -> Self {
+        Self::new(subsystem, ReadinessStatus::Degraded, detail)
+    }
+
+    pub fn failed(subsystem: Subsystem, detail: impl Into<String>) -> Self {
+        Self::new(subsystem, ReadinessStatus::Failed, detail)
+    }
+
+    pub fn disabled(subsystem: Subsystem) -> Self {
+        Self::new(subsystem, ReadinessStatus::Disabled, format!("{} is not enabled", subsystem.capability()))
+    }
+
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.last_error = Some(error.into());
+        self
+    }
+
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+
+    pub fn with_degraded_capabilities(mut self, capabilities: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.degraded_capabilities = capabilities.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Readiness of every subsystem, as `get_system_status` reports it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemStatus {
+    /// The worst status of an enabled subsystem; ready when none is enabled
+    pub overall: ReadinessStatus,
+    pub subsystems: Vec<SubsystemReport>,
+    pub checked_at: DateTime<Utc>,
+    /// Resources tool calls in flight hold, oldest first
+    #[serde(default)]
+    pub locks: Vec<LockHolder>,
+}
+
+impl SystemStatus {
+    pub fn from_reports(subsystems: Vec<SubsystemReport>) -> Self {
+        let overall = subsystems
+            .iter()
+            .map(|report| report.status)
+            .max()
+            .unwrap_or(ReadinessStatus::Ready)
+            .max(ReadinessStatus::Ready);
+        Self { overall, subsystems, checked_at: Utc::now(), locks: Vec::new() }
+    }
+
+    pub fn with_locks(mut self, locks: Vec<LockHolder>) -> Self {
+        self.locks = locks;
+        self
+    }
+
+    pub fn subsystem(&self, subsystem: Subsystem) -> Option<&SubsystemReport> {
+        self.subsystems.iter().find(|report| report.subsystem == subsystem)
+    }
+
+    /// Enabled subsystems that are not ready
+    pub fn problems(&self) -> impl Iterator<Item = &SubsystemReport> {
+        self.subsystems
+            .iter()
+            .filter(|report| matches!(report.status, ReadinessStatus::Degraded | ReadinessStatus::Failed))
+    }
+
+    /// Log each subsystem, warning about those that are not ready
+    pub fn log(&self) {
+        for report in &self.subsystems {
+            match report.status {
+                ReadinessStatus::Ready | ReadinessStatus::Disabled => {
+                    tracing::info!("{}: {:?}, {}", report.subsystem.label(), report.status, report.detail);
+                }
+                ReadinessStatus::Degraded | ReadinessStatus::Failed => {
+                    tracing::warn!(
+                        "{}: {:?}, {}{}{}",
+                        report.subsystem.label(),
+                        report.status,
+                        report.detail,
+                        report.last_error.as_deref().map(|error| format!(" ({})", error)).unwrap_or_default(),
+                        report.remediation.as_deref().map(|hint| format!("; {}", hint)).unwrap_or_default(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Checks whether one subsystem is usable
+#[async_trait]
+pub trait SubsystemProbe: Send + Sync {
+    fn subsystem(&self) -> Subsystem;
+
+    async fn probe(&self) -> SubsystemReport;
+}
+
+/// Runs the probes of enabled subsystems and aggregates their reports
+#[derive(Default)]
+pub struct SystemStatusChecker {
+    probes: Vec<Arc<dyn SubsystemProbe>>,
+}
+
+impl SystemStatusChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_probe(mut self, probe: Arc<dyn SubsystemProbe>) -> Self {
+        self.probes.push(probe);
+        self
+    }
+
+    /// One report per subsystem: disabled ones are not probed, and an
+    /// enabled one without a probe never started
+    pub async fn check(&self, capabilities: &MCPCapabilities) -> SystemStatus {
+        let probes = Subsystem::ALL.into_iter().map(|subsystem| async move {
+            if !subsystem.is_enabled(capabilities) {
+                return SubsystemReport::disabled(subsystem);
+            }
+            match self.probes.iter().find(|probe| probe.subsystem() == subsystem) {
+                Some(probe) => probe.probe().await,
+                None => SubsystemReport::failed(subsystem, format!("{} did not start", subsystem.label()))
+                    .with_remediation("Check the startup log for why it failed to initialize"),
+            }
+        });
+        SystemStatus::from_reports(futures_util::future::join_all(probes).await)
+    }
+}
+
+/// Judges a WebSocket connection from its latest status
+pub struct ConnectionProbe {
+    subsystem: Subsystem,
+    status: ConnectionStatus,
+    /// Variable pointing the connection elsewhere, for the remediation hint
+    url_variable: &'static str,
+    /// What the server speaks and offers, for servers with a handshake
+    protocol: Option<ServerProtocol>,
+}
+
+impl ConnectionProbe {
+    pub fn hot_reload(status: ConnectionStatus) -> Self {
+        Self { subsystem: Subsystem::HotReload, status, url_variable: "AXIOM_OBS_HOT_RELOAD_SERVER_URL", protocol: None }
+    }
+
+    pub fn intelligence(status: ConnectionStatus) -> Self {
+        Self { subsystem: Subsystem::Intelligence, status, url_variable: "AXIOM_OBS_INTELLIGENCE_SERVER_URL", protocol: None }
+    }
+
+    /// Judge a connected server by `protocol` too, degrading it when it
+    /// lacks capabilities
+    pub fn with_protocol(mut self, protocol: ServerProtocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    fn report(&self) -> SubsystemReport {
+        let status = &self.status;
+        let report = match status.state {
+            ConnectionState::Connected => return self.connected_report(),
+            ConnectionState::Connecting => SubsystemReport::degraded(self.subsystem, format!("Connecting to {}", status.url)),
+            ConnectionState::Reconnecting if status.reconnect_attempt >= FAILED_AFTER_RECONNECT_ATTEMPTS => {
+                SubsystemReport::failed(
+                    self.subsystem,
+                    format!("Cannot reach {} after {} attempts", status.url, status.reconnect_attempt),
+                )
+            }
+            ConnectionState::Reconnecting => SubsystemReport::degraded(
+                self.subsystem,
+                format!("Reconnecting to {} (attempt {}, {} messages buffered)", status.url, status.reconnect_attempt, status.buffered_messages),
+            ),
+        };
+        let report = report.with_remediation(format!(
+            "Start the {} server at {}, or set {} to where it runs",
+            self.subsystem.label(), status.url, self.url_variable
+        ));
+        match &status.last_error {
+            Some(error) => report.with_error(error.clone()),
+            None => report,
+        }
+    }
+
+    fn connected_report(&self) -> SubsystemReport {
+        let url = &self.status.url;
+        let Some(protocol) = self.protocol.as_ref().filter(|protocol| protocol.is_settled()) else {
+            return SubsystemReport::ready(self.subsystem, format!("Connected to {}", url));
+        };
+        let degraded: Vec<&str> = protocol.degraded().into_iter().map(|capability| capability.name()).collect();
+        match protocol.handshake {
+            HandshakeState::Unsupported => SubsystemReport::failed(
+                self.subsystem,
+                format!("Connected to {}, which speaks unsupported protocol version {}", url, protocol.version),
+            )
+            .with_degraded_capabilities(degraded)
+            .with_remediation(format!("Run a {} server speaking protocol version {}", self.subsystem.label(), PROTOCOL_VERSION)),
+            _ if degraded.is_empty() => {
+                SubsystemReport::ready(self.subsystem, format!("Connected to {} (protocol version {})", url, protocol.version))
+            }
+            _ => SubsystemReport::degraded(
+                self.subsystem,
+                format!("Connected to {} (protocol version {}) without {}", url, protocol.version, degraded.join(", ")),
+            )
+            .with_degraded_capabilities(degraded)
+            .with_remediation(format!("Upgrade the {} server to protocol version {}", self.subsystem.label(), PROTOCOL_VERSION)),
+        }
+    }
+}
+
+#[async_trait]
+impl SubsystemProbe for ConnectionProbe {
+    fn subsystem(&self) -> Subsystem {
+        self.subsystem
+    }
+
+    async fn probe(&self) -> SubsystemReport {
+        self.report()
+    }
+}
+
+/// Lists simulators to check simctl runs and a runtime is installed
+pub struct SimulatorProbe {
+    controller: Arc<SimulatorController>,
+}
+
+impl SimulatorProbe {
+    pub fn new(controller: Arc<SimulatorController>) -> Self {
+        Self { controller }
+    }
+}
+
+#[async_trait]
+impl SubsystemProbe for SimulatorProbe {
+    fn subsystem(&self) -> Subsystem {
+        Subsystem::Simulator
+    }
+
+    async fn probe(&self) -> SubsystemReport {
+        let devices = match self.controller.list_devices().await {
+            Ok(devices) => devices,
+            Err(error) => {
+                return SubsystemReport::failed(Subsystem::Simulator, "simctl is not available")
+                    .with_error(error.to_string())
+                    .with_remediation("Install Xcode and select it with `sudo xcode-select -s /Applications/Xcode.app`");
+            }
+        };
+
+        let (available, unavailable): (Vec<_>, Vec<_>) = devices.iter().partition(|device| device.is_available);
+        let unavailable_error = unavailable.iter().find_map(|device| device.availability_error.clone());
+        if available.is_empty() {
+            let report = SubsystemReport::failed(Subsystem::Simulator, "No simulator runtime is available")
+                .with_remediation("Install an iOS simulator runtime in Xcode > Settings > Platforms");
+            return match unavailable_error {
+                Some(error) => report.with_error(error),
+                None => report,
+            };
+        }
+
+        let runtimes: BTreeSet<&str> = available.iter().map(|device| device.runtime.as_str()).collect();
+        let detail = format!(
+            "{} devices on {}",
+            available.len(),
+            runtimes.into_iter().collect::<Vec<_>>().join(", ")
+        );
+        if unavailable.is_empty() {
+            return SubsystemReport::ready(Subsystem::Simulator, detail);
+        }
+        let report = SubsystemReport::degraded(Subsystem::Simulator, format!("{}; {} unavailable", detail, unavailable.len()))
+            .with_remediation("Remove devices whose runtime is gone with `xcrun simctl delete unavailable`");
+        match unavailable_error {
+            Some(error) => report.with_error(error),
+            None => report,
+        }
+    }
+}
+
+/// Checks a template is loaded for every kind of generated code
+pub struct TemplateProbe {
+    templates: Vec<TemplateInfo>,
+}
+
+impl TemplateProbe {
+    pub fn new(templates: Vec<TemplateInfo>) -> Self {
+        Self { templates }
+    }
+}
+
+#[async_trait]
+impl SubsystemProbe for TemplateProbe {
+    fn subsystem(&self) -> Subsystem {
+        Subsystem::CodeGeneration
+    }
+
+    async fn probe(&self) -> SubsystemReport {
+        let missing: Vec<&str> = TemplateKind::ALL
+            .iter()
+            .filter(|kind| !self.templates.iter().any(|template| template.kind == **kind))
+            .map(|kind| kind.name())
+            .collect();
+        if !missing.is_empty() {
+            return SubsystemReport::failed(Subsystem::CodeGeneration, format!("No {} template loaded", missing.join(", ")))
+                .with_remediation("Restore the missing templates or remove the project's template overrides");
+        }
+
+        let from_project = self
+            .templates
+            .iter()
+            .filter(|template| matches!(template.source, TemplateSource::Project { .. }))
+            .count();
+        SubsystemReport::ready(
+            Subsystem::CodeGeneration,
+            format!("{} templates loaded ({} from the project)", self.templates.len(), from_project),
+        )
+    }
+}
+
+/// Writes and removes a file in the screenshot directory
+pub struct StorageProbe {
+    dir: PathBuf,
+}
+
+impl StorageProbe {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl SubsystemProbe for StorageProbe {
+    fn subsystem(&self) -> Subsystem {
+        Subsystem::ScreenshotStorage
+    }
+
+    async fn probe(&self) -> SubsystemReport {
+        let check = self.dir.join(WRITE_CHECK_FILE_NAME);
+        let written = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&check, b"ok").await?;
+            tokio::fs::remove_file(&check).await
+        };
+        match written.await {
+            Ok(()) => SubsystemReport::ready(Subsystem::ScreenshotStorage, format!("{} is writable", self.dir.display())),
+            Err(error) => SubsystemReport::failed(Subsystem::ScreenshotStorage, format!("Cannot write to {}", self.dir.display()))
+                .with_error(error.to_string())
+                .with_remediation("Make the directory writable, or set AXIOM_OBS_SCREENSHOT_STORAGE__DIR to one that is"),
+        }
+    }
+}
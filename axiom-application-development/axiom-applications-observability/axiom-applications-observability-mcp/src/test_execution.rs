@@ -0,0 +1,379 @@
+use crate::artifact_store::{ArtifactKind, ArtifactSource, ArtifactStore};
+use crate::error::{AxiomMCPError, Result};
+use crate::project_build::{
+    materialize_project, parse_build_diagnostics, scheme_name, truncate_build_log, BuildCommand, BuildDiagnostic, DiagnosticSeverity,
+    ProcessBuildCommand, DEFAULT_LOG_CONTEXT_LINES,
+};
+use crate::simulator::{SimulatorController, SimulatorDevice};
+use crate::types::{Implementation, InfrastructureSpecification};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Where `xcodebuild test` writes its result bundle, relative to the project
+pub const RESULT_BUNDLE_PATH: &str = ".build/TestResults.xcresult";
+
+/// How a test case ended, as `xcresulttool` reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+    /// Failed inside `XCTExpectFailure`, which counts as passing
+    ExpectedFailure,
+}
+
+impl TestOutcome {
+    fn parse(result: &str) -> Self {
+        match result {
+            "Passed" => TestOutcome::Passed,
+            "Skipped" => TestOutcome::Skipped,
+            "Expected Failure" => TestOutcome::ExpectedFailure,
+            // Anything else, such as the `Mixed` of repeated runs, did not pass
+            _ => TestOutcome::Failed,
+        }
+    }
+
+    pub fn passed(self) -> bool {
+        matches!(self, TestOutcome::Passed | TestOutcome::ExpectedFailure)
+    }
+}
+
+impl std::fmt::Display for TestOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TestOutcome::Passed => "passed",
+            TestOutcome::Failed => "failed",
+            TestOutcome::Skipped => "skipped",
+            TestOutcome::ExpectedFailure => "expected failure",
+        })
+    }
+}
+
+/// One test case of a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestCaseResult {
+    /// `Suite/testName()`
+    pub identifier: String,
+    pub outcome: TestOutcome,
+    pub duration_ms: u64,
+    /// The failure messages, with the file and line they were raised at
+    pub failures: Vec<String>,
+}
+
+/// Line coverage of one target the tests exercised
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetCoverage {
+    pub name: String,
+    pub covered_lines: u64,
+    pub executable_lines: u64,
+}
+
+impl TargetCoverage {
+    /// In percent
+    pub fn line_coverage(&self) -> f64 {
+        match self.executable_lines {
+            0 => 0.0,
+            executable => self.covered_lines as f64 * 100.0 / executable as f64,
+        }
+    }
+}
+
+/// The outcome of running an implementation's generated tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestRun {
+    /// The tests built and none of them failed
+    pub succeeded: bool,
+    /// The command line that was run
+    pub command: String,
+    pub project_dir: PathBuf,
+    /// The simulator the tests ran on
+    pub device: SimulatorDevice,
+    pub tests: Vec<TestCaseResult>,
+    /// Line coverage in percent over the targets under test; `None` when
+    /// the result bundle has no coverage
+    pub coverage: Option<f64>,
+    pub targets: Vec<TargetCoverage>,
+    /// Why the tests did not build, when no test ran
+    pub build_errors: Vec<BuildDiagnostic>,
+    /// The errors with the lines around them, or the end of the log
+    pub log: String,
+    /// Id of `xcresulttool`'s reading of the result bundle in the artifact
+    /// store, when the runner has one and a test ran
+    #[serde(default)]
+    pub results_artifact: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl TestRun {
+    pub fn passed(&self) -> impl Iterator<Item = &TestCaseResult> {
+        self.tests.iter().filter(|test| test.outcome.passed())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &TestCaseResult> {
+        self.tests.iter().filter(|test| test.outcome == TestOutcome::Failed)
+    }
+
+    /// What a next iteration has to fix, one line per failure
+    pub fn failure_messages(&self) -> Vec<String> {
+        let build = self.build_errors.iter().map(|error| format!("Test build error: {}", error));
+        let tests = self.failed().flat_map(|test| match test.failures.as_slice() {
+            [] => vec![format!("Test failure: {}", test.identifier)],
+            failures => failures.iter().map(|failure| format!("Test failure: {}: {}", test.identifier, failure)).collect(),
+        });
+        build.chain(tests).collect()
+    }
+}
+
+/// Runs an implementation's generated XCTest suite on a simulator
+#[derive(Clone)]
+pub struct TestRunner {
+    command: Arc<dyn BuildCommand>,
+    simulator: Arc<SimulatorController>,
+    /// Name or UDID of the simulator, booted when it is not already
+    device: String,
+    /// Each implementation is materialized in a directory of its own here;
+    /// sharing the project builder's workspace tests the built project
+    workspace: PathBuf,
+    context_lines: usize,
+    artifacts: Option<Arc<ArtifactStore>>,
+}
+
+impl std::fmt::Debug for TestRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestRunner")
+            .field("device", &self.device)
+            .field("workspace", &self.workspace)
+            .field("context_lines", &self.context_lines)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TestRunner {
+    pub fn new(simulator: Arc<SimulatorController>, device: impl Into<String>, workspace: impl Into<PathBuf>) -> Self {
+        Self {
+            command: Arc::new(ProcessBuildCommand),
+            simulator,
+            device: device.into(),
+            workspace: workspace.into(),
+            context_lines: DEFAULT_LOG_CONTEXT_LINES,
+            artifacts: None,
+        }
+    }
+
+    pub fn with_command(mut self, command: Arc<dyn BuildCommand>) -> Self {
+        self.command = command;
+        self
+    }
+
+    pub fn with_context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    /// Keep each run's test results, as read from its result bundle, in `artifacts`
+    pub fn with_artifact_store(mut self, artifacts: Arc<ArtifactStore>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Materialize `implementation` with its tests, run them on the
+    /// simulator and read the result bundle
+    pub async fn run(&self, implementation: &Implementation) -> Result<TestRun> {
+        self.run_for(implementation, ArtifactSource::default()).await
+    }
+
+    /// `run`, keeping the test results as an artifact of `source`'s cycle and stage
+    pub async fn run_for(&self, implementation: &Implementation, source: ArtifactSource) -> Result<TestRun> {
+        let project_dir = self.workspace.join(&implementation.implementation_id);
+        materialize_project(implementation, &project_dir).await?;
+        let device = self.simulator.boot(&self.device).await?.device;
+        // xcodebuild will not write over the bundle of an earlier run
+        match tokio::fs::remove_dir_all(project_dir.join(RESULT_BUNDLE_PATH)).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let (program, args) = test_invocation(&implementation.infrastructure, &device.udid);
+        let command = std::iter::once(program.as_str()).chain(args.iter().map(String::as_str)).collect::<Vec<_>>().join(" ");
+        tracing::info!("Testing {} on {} with {}", project_dir.display(), device.label(), command);
+        let started = std::time::Instant::now();
+        let output = self.command.run(&project_dir, &program, &args).await?;
+
+        let results = self.command.run(&project_dir, "xcrun", &xcrun_args(&["xcresulttool", "get", "test-results", "tests", "--path"])).await?;
+        let tests = match (results.success, output.success) {
+            (true, _) => parse_test_results(&results.log)?,
+            // A suite that did not build leaves no bundle to read
+            (false, false) => Vec::new(),
+            (false, true) => {
+                return Err(AxiomMCPError::toolchain("test_execution", "read test results", format!(
+                    "xcresulttool could not read {}: {}", RESULT_BUNDLE_PATH, results.log.trim()
+                )));
+            }
+        };
+        let targets = match tests.is_empty() {
+            true => Vec::new(),
+            false => self.coverage(&project_dir).await,
+        };
+        let results_artifact = match (&self.artifacts, tests.is_empty()) {
+            (Some(artifacts), false) => {
+                let source = source.with_metadata("implementation_id", &implementation.implementation_id).with_metadata("device", &device.udid);
+                artifacts.keep(ArtifactKind::TestResults, results.log.as_bytes(), "json", source).await.map(|record| record.id)
+            }
+            _ => None,
+        };
+
+        let mut build_errors = Vec::new();
+        if !output.success && tests.is_empty() {
+            build_errors.extend(
+                parse_build_diagnostics(&output.log, &project_dir).into_iter().filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error),
+            );
+            if build_errors.is_empty() {
+                build_errors.push(BuildDiagnostic {
+                    severity: DiagnosticSeverity::Error,
+                    file: None,
+                    line: None,
+                    column: None,
+                    message: format!("{} test failed without running a test", program),
+                });
+            }
+        }
+        Ok(TestRun {
+            succeeded: output.success,
+            command,
+            project_dir,
+            device,
+            coverage: line_coverage(&targets),
+            tests,
+            targets,
+            build_errors,
+            log: truncate_build_log(&output.log, self.context_lines),
+            results_artifact,
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Coverage of the bundle's targets; a bundle without coverage is
+    /// only logged
+    async fn coverage(&self, project_dir: &std::path::Path) -> Vec<TargetCoverage> {
+        let report = self.command.run(project_dir, "xcrun", &xcrun_args(&["xccov", "view", "--report", "--json"])).await;
+        match report.and_then(|report| match report.success {
+            true => parse_coverage_report(&report.log),
+            false => Err(AxiomMCPError::toolchain("test_execution", "read coverage", report.log.trim().to_string())),
+        }) {
+            Ok(targets) => targets,
+            Err(e) => {
+                tracing::warn!("Recording the test run of {} without coverage: {}", project_dir.display(), e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// `args` followed by the result bundle
+fn xcrun_args(args: &[&str]) -> Vec<String> {
+    args.iter().chain(std::iter::once(&RESULT_BUNDLE_PATH)).map(|arg| arg.to_string()).collect()
+}
+
+/// The `xcodebuild test` that runs `infrastructure`'s tests on the
+/// simulator `udid`, with coverage, into `RESULT_BUNDLE_PATH`
+pub fn test_invocation(infrastructure: &InfrastructureSpecification, udid: &str) -> (String, Vec<String>) {
+    let destination = format!("platform=iOS Simulator,id={}", udid);
+    let args = [
+        "test",
+        "-scheme",
+        scheme_name(infrastructure),
+        "-destination",
+        destination.as_str(),
+        "-derivedDataPath",
+        ".build/DerivedData",
+        "-resultBundlePath",
+        RESULT_BUNDLE_PATH,
+        "-enableCodeCoverage",
+        "YES",
+    ];
+    ("xcodebuild".to_string(), args.iter().map(|arg| arg.to_string()).collect())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestResults {
+    test_nodes: Vec<TestNode>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestNode {
+    name: String,
+    node_type: String,
+    node_identifier: Option<String>,
+    result: Option<String>,
+    duration_in_seconds: Option<f64>,
+    /// `0.012s`, written by versions without `durationInSeconds`
+    duration: Option<String>,
+    #[serde(default)]
+    children: Vec<TestNode>,
+}
+
+/// The test cases in the output of `xcresulttool get test-results tests`,
+/// in the order they appear
+pub fn parse_test_results(json: &str) -> Result<Vec<TestCaseResult>> {
+    let results: TestResults = serde_json::from_str(json).map_err(|e| {
+        AxiomMCPError::toolchain("test_execution", "read test results", format!("Unexpected xcresulttool output: {}", e)).with_cause(e)
+    })?;
+    let mut tests = Vec::new();
+    collect_test_cases(&results.test_nodes, &mut tests);
+    Ok(tests)
+}
+
+fn collect_test_cases(nodes: &[TestNode], tests: &mut Vec<TestCaseResult>) {
+    for node in nodes {
+        if node.node_type != "Test Case" {
+            collect_test_cases(&node.children, tests);
+            continue;
+        }
+        let seconds = node.duration_in_seconds.or_else(|| node.duration.as_deref()?.strip_suffix('s')?.parse().ok()).unwrap_or(0.0);
+        tests.push(TestCaseResult {
+            identifier: node.node_identifier.clone().unwrap_or_else(|| node.name.clone()),
+            outcome: TestOutcome::parse(node.result.as_deref().unwrap_or_default()),
+            duration_ms: (seconds * 1000.0).round() as u64,
+            failures: node.children.iter().filter(|child| child.node_type == "Failure Message").map(|child| child.name.clone()).collect(),
+        });
+    }
+}
+
+#[derive(Deserialize)]
+struct CoverageReport {
+    targets: Vec<CoverageTarget>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CoverageTarget {
+    name: String,
+    covered_lines: u64,
+    executable_lines: u64,
+}
+
+/// The targets under test in the output of `xccov view --report --json`;
+/// the test bundles themselves are left out
+pub fn parse_coverage_report(json: &str) -> Result<Vec<TargetCoverage>> {
+    let report: CoverageReport = serde_json::from_str(json).map_err(|e| {
+        AxiomMCPError::toolchain("test_execution", "read coverage", format!("Unexpected xccov output: {}", e)).with_cause(e)
+    })?;
+    Ok(report
+        .targets
+        .into_iter()
+        .filter(|target| !target.name.ends_with(".xctest"))
+        .map(|target| TargetCoverage { name: target.name, covered_lines: target.covered_lines, executable_lines: target.executable_lines })
+        .collect())
+}
+
+/// Covered lines over executable lines of `targets`, in percent
+pub fn line_coverage(targets: &[TargetCoverage]) -> Option<f64> {
+    let executable: u64 = targets.iter().map(|target| target.executable_lines).sum();
+    let covered: u64 = targets.iter().map(|target| target.covered_lines).sum();
+    (executable > 0).then(|| covered as f64 * 100.0 / executable as f64)
+}
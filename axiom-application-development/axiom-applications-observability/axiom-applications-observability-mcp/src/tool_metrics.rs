@@ -0,0 +1,326 @@
+use crate::error::{AxiomMCPError, ErrorCategory, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bounds of the duration histogram's buckets; slower calls land in
+/// one more bucket past the last
+pub const DURATION_BUCKETS_MS: [u64; 11] = [10, 50, 100, 250, 500, 1000, 2500, 5000, 10000, 30000, 60000];
+
+tokio::task_local! {
+    static TIMING: TimingContext;
+}
+
+/// Where tool calls are kept beyond the process
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolMetricsConfig {
+    /// Every call is appended here as one JSON line when set
+    pub persist_path: Option<PathBuf>,
+}
+
+/// How many calls took how long
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DurationHistogram {
+    pub bounds_ms: Vec<u64>,
+    /// One count per bound, for calls no slower than it and slower than
+    /// the bound before, then the calls slower than every bound
+    pub counts: Vec<u64>,
+    pub sum_ms: u64,
+    pub max_ms: u64,
+}
+
+impl Default for DurationHistogram {
+    fn default() -> Self {
+        Self {
+            bounds_ms: DURATION_BUCKETS_MS.to_vec(),
+            counts: vec![0; DURATION_BUCKETS_MS.len() + 1],
+            sum_ms: 0,
+            max_ms: 0,
+        }
+    }
+}
+
+impl DurationHistogram {
+    pub fn record(&mut self, duration_ms: u64) {
+        let bucket = self.bounds_ms.partition_point(|bound| *bound < duration_ms);
+        self.counts[bucket] += 1;
+        self.sum_ms += duration_ms;
+        self.max_ms = self.max_ms.max(duration_ms);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        match self.count() {
+            0 => 0.0,
+            count => self.sum_ms as f64 / count as f64,
+        }
+    }
+
+    /// The bound of the bucket holding the `quantile` call, or the slowest
+    /// call when that is past every bound
+    pub fn quantile_ms(&self, quantile: f64) -> u64 {
+        let rank = (quantile.clamp(0.0, 1.0) * self.count() as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return self.bounds_ms.get(bucket).map_or(self.max_ms, |bound| (*bound).min(self.max_ms));
+            }
+        }
+        self.max_ms
+    }
+}
+
+/// Time spent in one stage of a tool call, such as one device's capture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub stage: String,
+    pub duration_ms: u64,
+}
+
+/// A stage's timings across calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StageStats {
+    pub count: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+}
+
+impl StageStats {
+    pub fn mean_ms(&self) -> f64 {
+        match self.count {
+            0 => 0.0,
+            count => self.total_ms as f64 / count as f64,
+        }
+    }
+}
+
+/// One finished tool call, as written to the JSONL file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallRecord {
+    pub tool: String,
+    pub at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub success: bool,
+    /// Size of the result serialized as JSON; nothing for failures
+    pub output_bytes: u64,
+    pub error_category: Option<ErrorCategory>,
+    #[serde(default)]
+    pub stages: Vec<StageTiming>,
+}
+
+/// Calls of one tool since the MCP started
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolStats {
+    pub tool: String,
+    pub calls: u64,
+    pub successes: u64,
+    pub failures: u64,
+    /// Failures by category, such as simulator or busy
+    pub failure_categories: BTreeMap<ErrorCategory, u64>,
+    pub output_bytes: u64,
+    /// Durations of successful and failed calls alike
+    pub durations: DurationHistogram,
+    /// Durations of the successful calls only, which failures returning
+    /// early would otherwise drag down
+    pub success_durations: DurationHistogram,
+    pub stages: BTreeMap<String, StageStats>,
+    pub last_call: DateTime<Utc>,
+}
+
+impl ToolStats {
+    fn new(tool: &str, at: DateTime<Utc>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            calls: 0,
+            successes: 0,
+            failures: 0,
+            failure_categories: BTreeMap::new(),
+            output_bytes: 0,
+            durations: DurationHistogram::default(),
+            success_durations: DurationHistogram::default(),
+            stages: BTreeMap::new(),
+            last_call: at,
+        }
+    }
+
+    fn record(&mut self, call: &ToolCallRecord) {
+        self.calls += 1;
+        self.durations.record(call.duration_ms);
+        if call.success {
+            self.successes += 1;
+            self.success_durations.record(call.duration_ms);
+        } else {
+            self.failures += 1;
+            if let Some(category) = call.error_category {
+                *self.failure_categories.entry(category).or_default() += 1;
+            }
+        }
+        self.output_bytes += call.output_bytes;
+        for timing in &call.stages {
+            let stage = self.stages.entry(timing.stage.clone()).or_default();
+            stage.count += 1;
+            stage.total_ms += timing.duration_ms;
+            stage.max_ms = stage.max_ms.max(timing.duration_ms);
+        }
+        self.last_call = call.at;
+    }
+
+    /// Share of calls that failed, from 0 to 1
+    pub fn failure_rate(&self) -> f64 {
+        match self.calls {
+            0 => 0.0,
+            calls => self.failures as f64 / calls as f64,
+        }
+    }
+}
+
+/// Every tool called since the MCP started, by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolMetricsReport {
+    pub since: DateTime<Utc>,
+    pub tools: Vec<ToolStats>,
+}
+
+impl ToolMetricsReport {
+    pub fn tool(&self, name: &str) -> Option<&ToolStats> {
+        self.tools.iter().find(|stats| stats.tool == name)
+    }
+
+    pub fn summary(&self) -> String {
+        let calls: u64 = self.tools.iter().map(|stats| stats.calls).sum();
+        let failures: u64 = self.tools.iter().map(|stats| stats.failures).sum();
+        let slowest = self.tools.iter().max_by(|a, b| a.durations.mean_ms().total_cmp(&b.durations.mean_ms()));
+        let mut summary = format!("{} calls of {} tools, {} failed", calls, self.tools.len(), failures);
+        if let Some(slowest) = slowest {
+            summary.push_str(&format!("; slowest on average: {} ({:.0}ms)", slowest.tool, slowest.durations.mean_ms()));
+        }
+        summary
+    }
+}
+
+/// Counts, durations, failures and output size of every tool call, kept
+/// in memory and appended to a JSONL file when one is configured
+#[derive(Debug)]
+pub struct ToolMetrics {
+    config: ToolMetricsConfig,
+    since: DateTime<Utc>,
+    tools: Mutex<BTreeMap<String, ToolStats>>,
+}
+
+impl Default for ToolMetrics {
+    fn default() -> Self {
+        Self::new(ToolMetricsConfig::default())
+    }
+}
+
+impl ToolMetrics {
+    pub fn new(config: ToolMetricsConfig) -> Self {
+        Self { config, since: Utc::now(), tools: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Run one call of `tool`, recording how it went along with the
+    /// stages it timed
+    pub async fn instrument<T: Serialize>(&self, tool: &str, call: impl Future<Output = Result<T>>) -> Result<T> {
+        let at = Utc::now();
+        let started = Instant::now();
+        let (result, stages) = TimingContext::scope(call).await;
+        let (output_bytes, error_category) = match &result {
+            Ok(output) => (serde_json::to_vec(output).map_or(0, |bytes| bytes.len() as u64), None),
+            Err(e) => (0, Some(e.category())),
+        };
+        self.record(ToolCallRecord {
+            tool: tool.to_string(),
+            at,
+            duration_ms: started.elapsed().as_millis() as u64,
+            success: result.is_ok(),
+            output_bytes,
+            error_category,
+            stages,
+        });
+        result
+    }
+
+    pub fn record(&self, call: ToolCallRecord) {
+        self.tools
+            .lock()
+            .unwrap()
+            .entry(call.tool.clone())
+            .or_insert_with(|| ToolStats::new(&call.tool, call.at))
+            .record(&call);
+        if let Err(e) = self.persist(&call) {
+            tracing::warn!("Could not persist the {} call's metrics: {}", call.tool, e);
+        }
+    }
+
+    pub fn report(&self) -> ToolMetricsReport {
+        ToolMetricsReport {
+            since: self.since,
+            tools: self.tools.lock().unwrap().values().cloned().collect(),
+        }
+    }
+
+    fn persist(&self, call: &ToolCallRecord) -> Result<()> {
+        let Some(path) = &self.config.persist_path else {
+            return Ok(());
+        };
+        let io_error = |e: std::io::Error| AxiomMCPError::internal("tool_metrics", "persist call", format!("{}: {}", path.display(), e));
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let mut line = serde_json::to_vec(call)?;
+        line.push(b'\n');
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(&line))
+            .map_err(io_error)
+    }
+}
+
+/// Collects the stage timings of the tool call running on this task, so
+/// the screenshot engine and the generator can report their stages without
+/// being handed anything
+#[derive(Debug, Default)]
+pub struct TimingContext {
+    stages: Mutex<Vec<StageTiming>>,
+}
+
+impl TimingContext {
+    /// Run `call` with a fresh context, returning what it produced and the
+    /// stages it timed
+    pub async fn scope<T>(call: impl Future<Output = T>) -> (T, Vec<StageTiming>) {
+        let context = TimingContext::default();
+        TIMING
+            .scope(context, async {
+                let output = call.await;
+                let stages = TIMING.with(|context| std::mem::take(&mut *context.stages.lock().unwrap()));
+                (output, stages)
+            })
+            .await
+    }
+}
+
+/// Record a stage of the current tool call; outside a tool call it is dropped
+pub fn record_stage(stage: impl Into<String>, duration: Duration) {
+    let timing = StageTiming { stage: stage.into(), duration_ms: duration.as_millis() as u64 };
+    let _ = TIMING.try_with(|context| context.stages.lock().unwrap().push(timing));
+}
+
+/// Run `stage` and record how long it took as a stage of the current tool call
+pub async fn time_stage<T>(stage: impl Into<String>, run: impl Future<Output = T>) -> T {
+    let started = Instant::now();
+    let output = run.await;
+    record_stage(stage, started.elapsed());
+    output
+}
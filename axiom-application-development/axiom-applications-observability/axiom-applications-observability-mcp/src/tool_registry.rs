@@ -0,0 +1,264 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::tools::{tool_spec, ToolResult, ToolSpec};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Future a tool handler returns, borrowing the context it runs against
+pub type ToolFuture<'a> = Pin<Box<dyn Future<Output = Result<ToolResult>> + Send + 'a>>;
+
+/// Handler taking validated JSON arguments
+type ErasedHandler<T> = Box<dyn for<'a> Fn(&'a T, Value) -> ToolFuture<'a> + Send + Sync>;
+
+/// Where arguments failed their tool's input schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON pointer into the arguments, empty for the arguments themselves
+    pub path: String,
+    pub message: String,
+}
+
+impl SchemaViolation {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { path: path.into(), message: message.into() }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// One tool: its spec, input schema and handler
+pub struct ToolDescriptor<T> {
+    spec: &'static ToolSpec,
+    input_schema: Value,
+    handler: ErasedHandler<T>,
+}
+
+impl<T> ToolDescriptor<T> {
+    pub fn name(&self) -> &'static str {
+        self.spec.name
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.spec.description
+    }
+
+    pub fn required_capabilities(&self) -> &'static [&'static str] {
+        self.spec.required_capabilities
+    }
+
+    pub fn input_schema(&self) -> &Value {
+        &self.input_schema
+    }
+}
+
+/// A tool as listed in the `tools/list` response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedTool {
+    pub name: String,
+    pub description: String,
+    pub input_schema: Value,
+}
+
+/// The `tools/list` response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolListing {
+    pub tools: Vec<ListedTool>,
+}
+
+/// Tools callable by name with JSON arguments, running against a `T`
+pub struct ToolRegistry<T> {
+    tools: Vec<ToolDescriptor<T>>,
+}
+
+impl<T: 'static> Default for ToolRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> ToolRegistry<T> {
+    pub fn new() -> Self {
+        Self { tools: Vec::new() }
+    }
+
+    /// Register the handler of the tool spec named `name`. Arguments are
+    /// checked against the spec's input schema, then deserialized into `A`.
+    ///
+    /// Panics when there is no spec by that name, which is a mistake in
+    /// the registry rather than in a call.
+    pub fn with_tool<A, F>(mut self, name: &'static str, handler: F) -> Self
+    where
+        A: DeserializeOwned + 'static,
+        F: for<'a> Fn(&'a T, A) -> ToolFuture<'a> + Send + Sync + 'static,
+    {
+        let spec = tool_spec(name).unwrap_or_else(|| panic!("No tool spec named {}", name));
+        let handler = erase(move |context: &T, arguments: Value| match serde_json::from_value::<A>(arguments) {
+            Ok(arguments) => handler(context, arguments),
+            Err(error) => Box::pin(std::future::ready(Err(AxiomMCPError::InvalidToolArguments {
+                tool: name.to_string(),
+                violations: vec![SchemaViolation::new("", error.to_string())],
+            }))),
+        });
+        self.tools.push(ToolDescriptor { spec, input_schema: spec.input_schema(), handler: Box::new(handler) });
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ToolDescriptor<T>> {
+        self.tools.iter().find(|tool| tool.name() == name)
+    }
+
+    /// Registered tools, in registration order
+    pub fn descriptors(&self) -> &[ToolDescriptor<T>] {
+        &self.tools
+    }
+
+    pub fn list(&self) -> ToolListing {
+        self.list_where(|_| true)
+    }
+
+    /// The registered tools `include` keeps, in registration order
+    pub fn list_where(&self, include: impl Fn(&ToolDescriptor<T>) -> bool) -> ToolListing {
+        ToolListing {
+            tools: self.tools.iter().filter(|tool| include(tool)).map(|tool| ListedTool {
+                name: tool.name().to_string(),
+                description: tool.description().to_string(),
+                input_schema: tool.input_schema.clone(),
+            }).collect(),
+        }
+    }
+
+    /// Run the named tool against `context` once `arguments` pass its
+    /// input schema
+    pub async fn call(&self, context: &T, name: &str, arguments: Value) -> Result<ToolResult> {
+        let tool = self.get(name).ok_or_else(|| AxiomMCPError::UnknownTool(name.to_string()))?;
+        let violations = validate_arguments(&tool.input_schema, &arguments);
+        if !violations.is_empty() {
+            return Err(AxiomMCPError::InvalidToolArguments { tool: name.to_string(), violations });
+        }
+        (tool.handler)(context, arguments).await
+    }
+}
+
+/// Pins a handler closure to the higher-ranked signature `ErasedHandler` needs
+fn erase<T, F>(handler: F) -> F
+where
+    F: for<'a> Fn(&'a T, Value) -> ToolFuture<'a>,
+{
+    handler
+}
+
+/// Check `value` against the subset of JSON Schema tool input schemas use:
+/// `type`, `enum`, `minimum`, `maximum`, `properties`, `required`,
+/// `additionalProperties` and `items`. Every violation is reported, not
+/// just the first.
+pub fn validate_arguments(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    check(schema, value, "", &mut violations);
+    violations
+}
+
+fn check(schema: &Value, value: &Value, path: &str, violations: &mut Vec<SchemaViolation>) {
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+            violations.push(SchemaViolation::new(path, format!("expected {}, found {}", types.join(" or "), describe(value))));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            violations.push(SchemaViolation::new(path, format!("expected one of {}, found {}", allowed.join(", "), value)));
+            return;
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+            if number < minimum {
+                violations.push(SchemaViolation::new(path, format!("must be at least {}, found {}", minimum, value)));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+            if number > maximum {
+                violations.push(SchemaViolation::new(path, format!("must be at most {}, found {}", maximum, value)));
+            }
+        }
+    }
+
+    if let Value::Object(object) = value {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    violations.push(SchemaViolation::new(path, format!("missing required property `{}`", name)));
+                }
+            }
+        }
+        for (name, property) in object {
+            let property_path = format!("{}/{}", path, name.replace('~', "~0").replace('/', "~1"));
+            match (properties.and_then(|properties| properties.get(name)), schema.get("additionalProperties")) {
+                (Some(property_schema), _) => check(property_schema, property, &property_path, violations),
+                (None, Some(Value::Bool(false))) => {
+                    let known: Vec<&str> = properties.map(|properties| properties.keys().map(String::as_str).collect()).unwrap_or_default();
+                    let message = if known.is_empty() {
+                        "unknown property; this tool takes no arguments".to_string()
+                    } else {
+                        format!("unknown property; expected one of {}", known.join(", "))
+                    };
+                    violations.push(SchemaViolation::new(property_path, message));
+                }
+                (None, Some(additional @ Value::Object(_))) => check(additional, property, &property_path, violations),
+                (None, _) => {}
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            check(item_schema, item, &format!("{}/{}", path, index), violations);
+        }
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
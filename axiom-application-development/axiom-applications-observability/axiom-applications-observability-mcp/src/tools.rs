@@ -1,7 +1,36 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::error::Result;
 use crate::types::*;
+use crate::connection::ConnectionStatus;
+use crate::hot_reload::{HotReloadFilters, HotReloadHandshake, HotReloadStats};
+use crate::code_generation::{PersistenceSpec, TemplateInfo, TemplateSource};
+use crate::generation_cache::GenerationCacheStats;
+use crate::feature_generation::{FeatureSpec, GeneratedFeature};
+use crate::axiom_framework_integration::FrameworkCompatibility;
+use crate::advanced_visual_intelligence::{AccessibilityAuditRequest, AccessibilityReport, ScreenTextAssertionRequest, ScreenTextReport};
+use crate::metrics_stream::{MetricsStreamHandle, MetricsStreamRequest, MetricsStreamSummary};
+use crate::performance_analysis_integration::{AppPerformanceMeasurement, CapturedMetrics, MeasureAppPerformanceRequest, MetricStatus, PerformanceMetric, PerformanceTrends, PerformanceVerdict, ReloadMemoryTrend, TrendOptions, VerdictStatus};
+use crate::visual_regression::RegressionCheckRequest;
+use crate::device_matrix::{DeviceMatrixPreset, ScreenshotMatrixRequest, WindowConfiguration};
+use crate::screenshot_store::PruneReport;
+use crate::artifact_store::{ArtifactKind, ArtifactPruneReport, ArtifactQuery, ArtifactRecord};
+use crate::approval_gates::{ApprovalDecision, RejectStageRequest, StageReview};
+use crate::requirement_clarification::{ClarificationRequest, RequirementAnswers};
+use crate::complete_development_loop::CycleSummary;
+use crate::cycle_report::{CycleReportRequest, ExportedCycleReport};
+use crate::development_workflow::{AbandonedCycle, CycleDiff};
+use crate::traceability::TraceabilityMatrix;
+use crate::system_status::SystemStatus;
+use crate::tool_metrics::ToolMetricsReport;
+use crate::resource_locks::ResourceNeed;
+use crate::simulator_capture::{Recording, RecordingHandle, StartRecordingRequest};
+use crate::ui_script::{RunUiScriptRequest, UiScriptRun};
+use crate::simulator::{InstallAppRequest, InstallAppResult, LaunchAppRequest, LaunchAppResult, ResetSimulatorStateRequest, SimulatorActionResult, SimulatorDevice, SimulatorStateReset};
 
-/// All available MCP tools for Axiom Applications Observability
+/// All available MCP tools for Axiom Applications Observability. Calls by
+/// name go through the tool registry; each variant stands for one call with
+/// its `arguments()`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AxiomMCPTool {
     /// Generate SwiftUI Presentation layer component
@@ -13,23 +42,68 @@ pub enum AxiomMCPTool {
     /// Generate mock Axiom Client layer component
     GenerateMockClient(ClientSpec),
     
+    /// Generate a SwiftData model, its container setup and repository
+    GeneratePersistence(PersistenceSpec),
+    
+    /// Generate the context, client, presentations and optional persistence
+    /// and navigation of a feature in one call, with consistent names
+    GenerateFeature(FeatureSpec),
+    
+    /// List the active code generation templates, their sources and variables
+    ListCodeGenerationTemplates,
+    
+    /// Report the Axiom framework versions code is generated for, and the
+    /// features downgraded for them
+    CheckFrameworkCompatibility,
+    
     /// Validate architecture compliance against Axiom patterns
     ValidateArchitecture,
     
     /// Analyze app structure and provide intelligence
     AnalyzeAppStructure,
     
-    /// Start streaming performance metrics
-    StreamPerformanceMetrics,
+    /// Push the running app's metrics to this session as `axiom/metrics`
+    /// notifications until the stream is stopped
+    StartMetricsStream(MetricsStreamRequest),
+    
+    /// Stop a metrics stream by id
+    StopMetricsStream(String),
+    
+    /// Grade measured app performance against the configured thresholds
+    EvaluatePerformance(CapturedMetrics),
+    
+    /// Measure an installed app's cold and warm launch, memory and frame rate over several runs
+    MeasureAppPerformance(MeasureAppPerformanceRequest),
+    
+    /// Time series of each metric across development cycles, flagging regressions
+    GetPerformanceTrends(TrendOptions),
     
-    /// Capture screenshot matrix across device configurations
-    CaptureScreenshotMatrix,
+    /// Capture screenshot matrix across a preset's or a custom list's device configurations
+    CaptureScreenshotMatrix(ScreenshotMatrixRequest),
     
     /// Compare visual states for differences
     CompareVisualStates,
     
-    /// Detect UI regressions automatically
-    DetectUIRegressions,
+    /// Record screenshots of a screen as baselines, or check them against the baselines
+    DetectUIRegressions(RegressionCheckRequest),
+    
+    /// Remove stored screenshots past the retention policy, keeping baselines
+    PruneScreenshots,
+    
+    /// List stored artifacts by kind, cycle and stage
+    ListArtifacts(ArtifactQuery),
+    
+    /// Look up a stored artifact by id
+    GetArtifact(String),
+    
+    /// Remove stored artifacts of every kind past the kind's retention policy
+    PruneArtifacts,
+    
+    /// Audit a screen for missing labels, small touch targets, low contrast and unlabeled images
+    AuditAccessibility(AccessibilityAuditRequest),
+    
+    /// Read a screenshot's text and check it shows the expected strings or patterns
+    AssertScreenContains(ScreenTextAssertionRequest),
     
     /// Start a new development session
     StartDevelopmentSession,
@@ -37,8 +111,93 @@ pub enum AxiomMCPTool {
     /// Process natural language requirement into implementation plan
     ProcessNaturalLanguageRequirement(String),
     
+    /// Continue a checkpointed development cycle from its last completed stage
+    ResumeDevelopmentCycle(String),
+    
+    /// List checkpointed development cycles and the stage each would resume at
+    ListDevelopmentCycles,
+    
+    /// Write a Markdown report of a cycle, and optionally a self-contained HTML one
+    ExportCycleReport(CycleReportRequest),
+    
+    /// Which generated elements of a cycle address which parts of its requirement
+    GetTraceabilityMatrix(String),
+    
+    /// Let a cycle waiting at an approval gate continue
+    ApproveStage(String),
+    
+    /// Send a waiting cycle's stage back to run again with feedback
+    RejectStage(RejectStageRequest),
+    
+    /// Answer the clarifying questions a cycle is waiting on
+    ProvideRequirementAnswers(RequirementAnswers),
+    
+    /// Everything a cycle committed to its branch, for review
+    GetCycleDiff(String),
+    
+    /// Discard a cycle's branch and go back to where it started
+    AbandonCycle(String),
+    
     /// Optimize identified performance bottlenecks
     OptimizePerformanceBottlenecks,
+    
+    /// Report the state of the hot reload and intelligence server connections
+    GetConnectionStatus,
+    
+    /// Report whether each enabled subsystem is ready, with what to do about
+    /// those that are not
+    GetSystemStatus,
+    
+    /// Replace the include/exclude globs deciding which file changes hot reload
+    SetHotReloadFilters(HotReloadFilters),
+    
+    /// Report filtered and forwarded hot reload file events
+    GetHotReloadStats,
+    
+    /// Ping the hot reload server and check that it answers in the
+    /// expected message schema
+    ValidateHotReloadConnection,
+    
+    /// Forget cached code generations and validations
+    ClearGenerationCache,
+    
+    /// Report the launched app's memory across its latest hot reloads and
+    /// whether it grows fast enough to be a leak
+    GetReloadMemoryTrend,
+    
+    /// Report each tool's calls, duration histogram, failure rate, output
+    /// size and stage timings since the MCP started
+    GetToolMetrics,
+    
+    /// List simulator devices with their runtime and availability
+    ListSimulators,
+    
+    /// Boot a simulator, selected by UDID or name and runtime, and wait until it is booted
+    BootSimulator(String),
+    
+    /// Shut down a simulator, selected by UDID or name and runtime
+    ShutdownSimulator(String),
+    
+    /// Erase a shut down simulator, selected by UDID or name and runtime
+    EraseSimulator(String),
+    
+    /// Install a built .app bundle on a simulator
+    InstallApp(InstallAppRequest),
+    
+    /// Launch an installed app on a simulator, optionally capturing its console
+    LaunchApp(LaunchAppRequest),
+    
+    /// Reset app data, permissions, status bar, keychain and appearance of a booted simulator
+    ResetSimulatorState(ResetSimulatorStateRequest),
+    
+    /// Start recording the screen of a booted simulator
+    StartRecording(StartRecordingRequest),
+    
+    /// Stop a recording by id, returning the video file
+    StopRecording(String),
+    
+    /// Tap, type, swipe and wait for elements on a booted simulator's app
+    RunUiScript(RunUiScriptRequest),
 }
 
 /// Results returned by MCP tool execution
@@ -47,6 +206,15 @@ pub enum ToolResult {
     /// Generated code with validation and performance metrics
     GeneratedCode(GeneratedCode),
     
+    /// Code of every artifact of a feature, and how they refer to one another
+    GeneratedFeature(GeneratedFeature),
+    
+    /// Active code generation templates
+    CodeGenerationTemplates(Vec<TemplateInfo>),
+    
+    /// Detected framework versions and the features downgraded for them
+    FrameworkCompatibility(FrameworkCompatibility),
+    
     /// App structure analysis with recommendations
     AppStructureAnalysis(AppStructureAnalysis),
     
@@ -59,6 +227,24 @@ pub enum ToolResult {
     /// UI regression detection report
     RegressionReport(RegressionReport),
     
+    /// Stored screenshots removed by retention
+    ScreenshotsPruned(PruneReport),
+    
+    /// Stored artifacts matching a query
+    Artifacts(Vec<ArtifactRecord>),
+    
+    /// One stored artifact
+    Artifact(ArtifactRecord),
+    
+    /// Stored artifacts removed by retention
+    ArtifactsPruned(ArtifactPruneReport),
+    
+    /// Accessibility audit findings
+    AccessibilityReport(AccessibilityReport),
+    
+    /// Expected texts found on and missing from a screenshot
+    ScreenText(ScreenTextReport),
+    
     /// Development session information
     DevelopmentSession(DevelopmentSession),
     
@@ -71,11 +257,89 @@ pub enum ToolResult {
     /// Architecture validation results
     ValidationResult(ValidationResult),
     
-    /// Performance metrics stream ID
-    PerformanceStream(String),
+    /// A metrics stream that started pushing notifications
+    MetricsStreamStarted(MetricsStreamHandle),
+    
+    /// What a stopped metrics stream sent
+    MetricsStreamStopped(MetricsStreamSummary),
+    
+    /// Pass, warn or fail per metric against the performance thresholds
+    PerformanceVerdict(PerformanceVerdict),
+    
+    /// Launch, memory and frame rate measured on a simulator
+    AppPerformance(AppPerformanceMeasurement),
+    
+    /// Metric history across development cycles
+    PerformanceTrends(PerformanceTrends),
     
     /// Complete development loop results
-    CompleteLoopResult(CompleteLoopResult),
+    CompleteLoopResult(Box<CompleteLoopResult>),
+    
+    /// Checkpointed development cycles
+    DevelopmentCycles(Vec<CycleSummary>),
+    
+    /// Where a cycle's report was written
+    CycleReport(ExportedCycleReport),
+    
+    /// A decision on a stage waiting for approval
+    StageReview(StageReview),
+    
+    /// Questions a cycle asked, with the answers it goes ahead with
+    RequirementAnswers(ClarificationRequest),
+    
+    /// The patch of a cycle's branch
+    CycleDiff(CycleDiff),
+    
+    /// Requirement fragments with the generated elements addressing them
+    TraceabilityMatrix(TraceabilityMatrix),
+    
+    /// A cycle branch that was deleted
+    AbandonedCycle(AbandonedCycle),
+    
+    /// State of each server connection
+    ConnectionStatus(Vec<ConnectionStatus>),
+    
+    /// Readiness of each subsystem
+    SystemStatus(SystemStatus),
+    
+    /// Hot reload filters and what they filtered
+    HotReloadStats(HotReloadStats),
+    
+    /// The hot reload server's answer to a schema ping
+    HotReloadHandshake(HotReloadHandshake),
+    
+    /// The generation cache's stats from just before it was cleared
+    GenerationCacheCleared(GenerationCacheStats),
+    
+    /// Memory of the launched app across hot reloads
+    ReloadMemoryTrend(ReloadMemoryTrend),
+    
+    /// Calls, durations and failures per tool
+    ToolMetrics(ToolMetricsReport),
+    
+    /// Simulator devices
+    Simulators(Vec<SimulatorDevice>),
+    
+    /// Outcome of a simulator boot, shutdown or erase
+    SimulatorAction(SimulatorActionResult),
+    
+    /// App bundle installed on a simulator
+    AppInstalled(InstallAppResult),
+    
+    /// App launched on a simulator
+    AppLaunched(LaunchAppResult),
+    
+    /// What a simulator state reset put back
+    SimulatorStateReset(SimulatorStateReset),
+    
+    /// A recording in progress
+    RecordingStarted(RecordingHandle),
+    
+    /// A finished recording
+    Recording(Recording),
+    
+    /// How far a UI script got, and what the screen showed if it failed
+    UiScript(UiScriptRun),
 }
 
 impl AxiomMCPTool {
@@ -85,57 +349,144 @@ impl AxiomMCPTool {
             AxiomMCPTool::GeneratePresentation(_) => "generate_presentation",
             AxiomMCPTool::GenerateContext(_) => "generate_context",
             AxiomMCPTool::GenerateMockClient(_) => "generate_mock_client",
+            AxiomMCPTool::GeneratePersistence(_) => "generate_persistence",
+            AxiomMCPTool::GenerateFeature(_) => "generate_feature",
+            AxiomMCPTool::ListCodeGenerationTemplates => "list_code_generation_templates",
+            AxiomMCPTool::CheckFrameworkCompatibility => "check_framework_compatibility",
             AxiomMCPTool::ValidateArchitecture => "validate_architecture",
             AxiomMCPTool::AnalyzeAppStructure => "analyze_app_structure",
-            AxiomMCPTool::StreamPerformanceMetrics => "stream_performance_metrics",
-            AxiomMCPTool::CaptureScreenshotMatrix => "capture_screenshot_matrix",
+            AxiomMCPTool::StartMetricsStream(_) => "start_metrics_stream",
+            AxiomMCPTool::StopMetricsStream(_) => "stop_metrics_stream",
+            AxiomMCPTool::EvaluatePerformance(_) => "evaluate_performance",
+            AxiomMCPTool::MeasureAppPerformance(_) => "measure_app_performance",
+            AxiomMCPTool::GetPerformanceTrends(_) => "get_performance_trends",
+            AxiomMCPTool::CaptureScreenshotMatrix(_) => "capture_screenshot_matrix",
             AxiomMCPTool::CompareVisualStates => "compare_visual_states",
-            AxiomMCPTool::DetectUIRegressions => "detect_ui_regressions",
+            AxiomMCPTool::DetectUIRegressions(_) => "detect_ui_regressions",
+            AxiomMCPTool::PruneScreenshots => "prune_screenshots",
+            AxiomMCPTool::ListArtifacts(_) => "list_artifacts",
+            AxiomMCPTool::GetArtifact(_) => "get_artifact",
+            AxiomMCPTool::PruneArtifacts => "prune_artifacts",
+            AxiomMCPTool::AuditAccessibility(_) => "audit_accessibility",
+            AxiomMCPTool::AssertScreenContains(_) => "assert_screen_contains",
             AxiomMCPTool::StartDevelopmentSession => "start_development_session",
             AxiomMCPTool::ProcessNaturalLanguageRequirement(_) => "process_natural_language_requirement",
+            AxiomMCPTool::ResumeDevelopmentCycle(_) => "resume_development_cycle",
+            AxiomMCPTool::ListDevelopmentCycles => "list_development_cycles",
+            AxiomMCPTool::ExportCycleReport(_) => "export_cycle_report",
+            AxiomMCPTool::GetTraceabilityMatrix(_) => "get_traceability_matrix",
+            AxiomMCPTool::ApproveStage(_) => "approve_stage",
+            AxiomMCPTool::RejectStage(_) => "reject_stage",
+            AxiomMCPTool::ProvideRequirementAnswers(_) => "provide_requirement_answers",
+            AxiomMCPTool::GetCycleDiff(_) => "get_cycle_diff",
+            AxiomMCPTool::AbandonCycle(_) => "abandon_cycle",
             AxiomMCPTool::OptimizePerformanceBottlenecks => "optimize_performance_bottlenecks",
+            AxiomMCPTool::GetConnectionStatus => "get_connection_status",
+            AxiomMCPTool::GetSystemStatus => "get_system_status",
+            AxiomMCPTool::SetHotReloadFilters(_) => "set_hot_reload_filters",
+            AxiomMCPTool::GetHotReloadStats => "get_hot_reload_stats",
+            AxiomMCPTool::ValidateHotReloadConnection => "validate_hot_reload_connection",
+            AxiomMCPTool::GetReloadMemoryTrend => "get_reload_memory_trend",
+            AxiomMCPTool::GetToolMetrics => "get_tool_metrics",
+            AxiomMCPTool::ClearGenerationCache => "clear_generation_cache",
+            AxiomMCPTool::ListSimulators => "list_simulators",
+            AxiomMCPTool::BootSimulator(_) => "boot_simulator",
+            AxiomMCPTool::ShutdownSimulator(_) => "shutdown_simulator",
+            AxiomMCPTool::EraseSimulator(_) => "erase_simulator",
+            AxiomMCPTool::InstallApp(_) => "install_app",
+            AxiomMCPTool::LaunchApp(_) => "launch_app",
+            AxiomMCPTool::ResetSimulatorState(_) => "reset_simulator_state",
+            AxiomMCPTool::StartRecording(_) => "start_recording",
+            AxiomMCPTool::StopRecording(_) => "stop_recording",
+            AxiomMCPTool::RunUiScript(_) => "run_ui_script",
         }
     }
     
     /// Get the description of the tool
     pub fn description(&self) -> &'static str {
-        match self {
-            AxiomMCPTool::GeneratePresentation(_) => "Generate SwiftUI Presentation layer component following Axiom patterns",
-            AxiomMCPTool::GenerateContext(_) => "Generate MainActor-bound Context layer component with @Published properties",
-            AxiomMCPTool::GenerateMockClient(_) => "Generate actor-based Client layer component conforming to AxiomClient protocol",
-            AxiomMCPTool::ValidateArchitecture => "Validate architecture compliance against Axiom Context-Presentation-Client patterns",
-            AxiomMCPTool::AnalyzeAppStructure => "Analyze app structure and provide intelligence recommendations",
-            AxiomMCPTool::StreamPerformanceMetrics => "Start real-time performance metrics streaming for monitoring",
-            AxiomMCPTool::CaptureScreenshotMatrix => "Capture screenshot matrix across multiple device configurations",
-            AxiomMCPTool::CompareVisualStates => "Compare visual states to detect differences and changes",
-            AxiomMCPTool::DetectUIRegressions => "Automatically detect UI regressions with low false positive rate",
-            AxiomMCPTool::StartDevelopmentSession => "Start complete development session with hot reload and monitoring",
-            AxiomMCPTool::ProcessNaturalLanguageRequirement(_) => "Process natural language requirements into implementation plans",
-            AxiomMCPTool::OptimizePerformanceBottlenecks => "Analyze and optimize identified performance bottlenecks",
-        }
+        self.spec().description
     }
     
     /// Check if the tool requires specific capabilities
     pub fn required_capabilities(&self) -> Vec<&'static str> {
-        match self {
-            AxiomMCPTool::GeneratePresentation(_) |
-            AxiomMCPTool::GenerateContext(_) |
-            AxiomMCPTool::GenerateMockClient(_) => vec!["code_generation"],
-            
-            AxiomMCPTool::ValidateArchitecture => vec!["intelligence_analysis"],
-            
+        self.spec().required_capabilities.to_vec()
+    }
+    
+    /// The tool's entry in `TOOL_SPECS`
+    pub fn spec(&self) -> &'static ToolSpec {
+        tool_spec(self.name()).expect("every tool has a spec")
+    }
+    
+    /// The `tools/call` arguments that run this tool
+    pub fn arguments(&self) -> Result<Value> {
+        let arguments = match self {
+            AxiomMCPTool::GeneratePresentation(spec) => serde_json::to_value(spec)?,
+            AxiomMCPTool::GenerateContext(spec) => serde_json::to_value(spec)?,
+            AxiomMCPTool::GenerateMockClient(spec) => serde_json::to_value(spec)?,
+            AxiomMCPTool::GeneratePersistence(spec) => serde_json::to_value(spec)?,
+            AxiomMCPTool::GenerateFeature(spec) => serde_json::to_value(spec)?,
+            AxiomMCPTool::EvaluatePerformance(metrics) => serde_json::to_value(metrics)?,
+            AxiomMCPTool::MeasureAppPerformance(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::GetPerformanceTrends(options) => serde_json::to_value(options)?,
+            AxiomMCPTool::StartMetricsStream(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::CaptureScreenshotMatrix(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::DetectUIRegressions(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::ListArtifacts(query) => serde_json::to_value(query)?,
+            AxiomMCPTool::AuditAccessibility(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::AssertScreenContains(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::ExportCycleReport(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::RejectStage(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::ProvideRequirementAnswers(answers) => serde_json::to_value(answers)?,
+            AxiomMCPTool::SetHotReloadFilters(filters) => serde_json::to_value(filters)?,
+            AxiomMCPTool::InstallApp(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::LaunchApp(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::ResetSimulatorState(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::StartRecording(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::RunUiScript(request) => serde_json::to_value(request)?,
+            AxiomMCPTool::ProcessNaturalLanguageRequirement(requirement) => {
+                serde_json::to_value(RequirementArguments { requirement: requirement.clone() })?
+            },
+            AxiomMCPTool::ResumeDevelopmentCycle(cycle_id) |
+            AxiomMCPTool::ApproveStage(cycle_id) |
+            AxiomMCPTool::GetCycleDiff(cycle_id) |
+            AxiomMCPTool::GetTraceabilityMatrix(cycle_id) |
+            AxiomMCPTool::AbandonCycle(cycle_id) => {
+                serde_json::to_value(CycleArguments { cycle_id: cycle_id.clone() })?
+            },
+            AxiomMCPTool::BootSimulator(device) |
+            AxiomMCPTool::ShutdownSimulator(device) |
+            AxiomMCPTool::EraseSimulator(device) => {
+                serde_json::to_value(DeviceArguments { device: device.clone() })?
+            },
+            AxiomMCPTool::StopRecording(recording_id) => {
+                serde_json::to_value(RecordingArguments { recording_id: recording_id.clone() })?
+            },
+            AxiomMCPTool::GetArtifact(artifact_id) => {
+                serde_json::to_value(ArtifactArguments { artifact_id: artifact_id.clone() })?
+            },
+            AxiomMCPTool::StopMetricsStream(stream_id) => {
+                serde_json::to_value(MetricsStreamArguments { stream_id: stream_id.clone() })?
+            },
+            AxiomMCPTool::ListCodeGenerationTemplates |
+            AxiomMCPTool::CheckFrameworkCompatibility |
+            AxiomMCPTool::ValidateArchitecture |
             AxiomMCPTool::AnalyzeAppStructure |
-            AxiomMCPTool::ProcessNaturalLanguageRequirement(_) => vec!["intelligence_analysis"],
-            
-            AxiomMCPTool::StreamPerformanceMetrics |
-            AxiomMCPTool::OptimizePerformanceBottlenecks => vec!["performance_monitoring"],
-            
-            AxiomMCPTool::CaptureScreenshotMatrix |
             AxiomMCPTool::CompareVisualStates |
-            AxiomMCPTool::DetectUIRegressions => vec!["visual_analysis"],
-            
-            AxiomMCPTool::StartDevelopmentSession => vec!["hot_reload_integration"],
-        }
+            AxiomMCPTool::PruneScreenshots |
+            AxiomMCPTool::PruneArtifacts |
+            AxiomMCPTool::StartDevelopmentSession |
+            AxiomMCPTool::ListDevelopmentCycles |
+            AxiomMCPTool::OptimizePerformanceBottlenecks |
+            AxiomMCPTool::GetConnectionStatus |
+            AxiomMCPTool::GetSystemStatus |
+            AxiomMCPTool::GetHotReloadStats |
+            AxiomMCPTool::ValidateHotReloadConnection |
+            AxiomMCPTool::GetReloadMemoryTrend |
+            AxiomMCPTool::GetToolMetrics |
+            AxiomMCPTool::ClearGenerationCache |
+            AxiomMCPTool::ListSimulators => serde_json::to_value(NoArguments {})?,
+        };
+        Ok(arguments)
     }
     
     /// Get estimated execution time in milliseconds
@@ -144,15 +495,56 @@ impl AxiomMCPTool {
             AxiomMCPTool::GeneratePresentation(_) => 800,
             AxiomMCPTool::GenerateContext(_) => 600,
             AxiomMCPTool::GenerateMockClient(_) => 700,
+            AxiomMCPTool::GeneratePersistence(_) => 700,
+            AxiomMCPTool::GenerateFeature(_) => 1500,
+            AxiomMCPTool::ListCodeGenerationTemplates => 10,
+            AxiomMCPTool::CheckFrameworkCompatibility => 10,
             AxiomMCPTool::ValidateArchitecture => 150,
             AxiomMCPTool::AnalyzeAppStructure => 200,
-            AxiomMCPTool::StreamPerformanceMetrics => 50,
-            AxiomMCPTool::CaptureScreenshotMatrix => 4000,
+            AxiomMCPTool::StartMetricsStream(_) => 10,
+            AxiomMCPTool::StopMetricsStream(_) => 10,
+            AxiomMCPTool::EvaluatePerformance(_) => 20,
+            AxiomMCPTool::MeasureAppPerformance(request) => u64::from(request.runs) * (2 * request.window_ms + 3000),
+            AxiomMCPTool::GetPerformanceTrends(_) => 50,
+            AxiomMCPTool::CaptureScreenshotMatrix(_) => 4000,
             AxiomMCPTool::CompareVisualStates => 300,
-            AxiomMCPTool::DetectUIRegressions => 500,
+            AxiomMCPTool::DetectUIRegressions(_) => 500,
+            AxiomMCPTool::PruneScreenshots => 1000,
+            AxiomMCPTool::ListArtifacts(_) => 20,
+            AxiomMCPTool::GetArtifact(_) => 5,
+            AxiomMCPTool::PruneArtifacts => 1000,
+            AxiomMCPTool::AuditAccessibility(_) => 400,
+            AxiomMCPTool::AssertScreenContains(_) => 3000,
             AxiomMCPTool::StartDevelopmentSession => 100,
             AxiomMCPTool::ProcessNaturalLanguageRequirement(_) => 250,
+            AxiomMCPTool::ResumeDevelopmentCycle(_) => 2000,
+            AxiomMCPTool::ListDevelopmentCycles => 20,
+            AxiomMCPTool::ExportCycleReport(_) => 500,
+            AxiomMCPTool::GetTraceabilityMatrix(_) => 20,
+            AxiomMCPTool::ApproveStage(_) => 5,
+            AxiomMCPTool::RejectStage(_) => 5,
+            AxiomMCPTool::ProvideRequirementAnswers(_) => 5,
+            AxiomMCPTool::GetCycleDiff(_) => 50,
+            AxiomMCPTool::AbandonCycle(_) => 100,
             AxiomMCPTool::OptimizePerformanceBottlenecks => 200,
+            AxiomMCPTool::GetConnectionStatus => 5,
+            AxiomMCPTool::GetSystemStatus => 1000,
+            AxiomMCPTool::SetHotReloadFilters(_) => 5,
+            AxiomMCPTool::GetHotReloadStats => 5,
+            AxiomMCPTool::ValidateHotReloadConnection => 100,
+            AxiomMCPTool::GetReloadMemoryTrend => 5,
+            AxiomMCPTool::GetToolMetrics => 5,
+            AxiomMCPTool::ClearGenerationCache => 5,
+            AxiomMCPTool::ListSimulators => 500,
+            AxiomMCPTool::BootSimulator(_) => 30000,
+            AxiomMCPTool::ShutdownSimulator(_) => 5000,
+            AxiomMCPTool::EraseSimulator(_) => 5000,
+            AxiomMCPTool::InstallApp(_) => 10000,
+            AxiomMCPTool::LaunchApp(request) => 3000 + request.console_seconds.unwrap_or(0) * 1000,
+            AxiomMCPTool::ResetSimulatorState(request) => if request.options.app_data.is_some() { 15000 } else { 3000 },
+            AxiomMCPTool::StartRecording(_) => 2000,
+            AxiomMCPTool::StopRecording(_) => 5000,
+            AxiomMCPTool::RunUiScript(_) => 5000,
         }
     }
 }
@@ -162,8 +554,10 @@ impl ToolResult {
     pub fn is_success(&self) -> bool {
         match self {
             ToolResult::GeneratedCode(code) => code.validation_passed,
+            ToolResult::GeneratedFeature(feature) => feature.passed(),
             ToolResult::ValidationResult(validation) => validation.passed,
             ToolResult::CompleteLoopResult(result) => result.success,
+            ToolResult::UiScript(run) => run.passed(),
             _ => true, // Most other results are informational and considered successful
         }
     }
@@ -177,6 +571,32 @@ impl ToolResult {
                     if code.validation_passed { "✅" } else { "❌" },
                     code.performance_score)
             },
+            ToolResult::GeneratedFeature(feature) => {
+                format!("Generated {} artifacts of {} ({} lines, {} unresolved references)",
+                    feature.artifacts().count(),
+                    feature.name,
+                    feature.artifacts().map(|artifact| artifact.code.generated_code.lines().count()).sum::<usize>(),
+                    feature.integration.issues.len())
+            },
+            ToolResult::CodeGenerationTemplates(templates) => {
+                let from_project = templates.iter()
+                    .filter(|template| matches!(template.source, TemplateSource::Project { .. }))
+                    .count();
+                format!("{} code generation templates ({} from the project, {} built in)",
+                    templates.len(), from_project, templates.len() - from_project)
+            },
+            ToolResult::FrameworkCompatibility(compatibility) => {
+                let versions = compatibility.frameworks.iter()
+                    .map(|framework| match framework.version {
+                        Some(version) => format!("{} {}", framework.package.name(), version),
+                        None => format!("{} unknown", framework.package.name()),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("Generating for {} ({} features downgraded)",
+                    if versions.is_empty() { "the latest frameworks".to_string() } else { versions },
+                    compatibility.downgrades.len())
+            },
             ToolResult::AppStructureAnalysis(analysis) => {
                 format!("Found {} contexts, {} presentations, {} clients (compliance: {:.1}%)",
                     analysis.contexts.len(),
@@ -194,19 +614,56 @@ impl ToolResult {
                     comparison.confidence_score,
                     comparison.similarity_score)
             },
-            ToolResult::RegressionReport(report) => {
-                format!("Regression analysis: {}/{} regressions detected (FP rate: {:.1}%)",
-                    report.regressions_detected,
-                    report.total_comparisons,
-                    report.false_positive_rate)
+            ToolResult::ScreenshotsPruned(report) => {
+                format!("Pruned {} screenshots ({} bytes); kept {}, {} of them baselines",
+                    report.removed.len(), report.freed_bytes, report.kept, report.baselines)
+            },
+            ToolResult::Artifacts(artifacts) => {
+                format!("{} artifacts ({} bytes)",
+                    artifacts.len(), artifacts.iter().map(|artifact| artifact.size_bytes).sum::<u64>())
+            },
+            ToolResult::Artifact(artifact) => {
+                format!("{} {} at {} ({} bytes)", artifact.kind, artifact.id, artifact.path.display(), artifact.size_bytes)
+            },
+            ToolResult::ArtifactsPruned(report) => {
+                format!("Pruned {} artifacts ({} bytes); kept {}, {} of them pinned",
+                    report.removed.len(), report.freed_bytes, report.kept, report.pinned)
+            },
+            ToolResult::RegressionReport(report) => match report.mode {
+                Some(crate::visual_regression::RegressionMode::Record) => {
+                    format!("Recorded {} baselines", report.total_comparisons)
+                },
+                _ => {
+                    format!("Regression analysis {}: {}/{} regressions detected (FP rate: {:.1}%)",
+                        if report.passed() { "passed" } else { "failed" },
+                        report.regressions_detected,
+                        report.total_comparisons,
+                        report.false_positive_rate)
+                },
+            },
+            ToolResult::AccessibilityReport(report) => {
+                format!("Accessibility audit: {} findings (compliance: {:.1}%)",
+                    report.findings.len(),
+                    report.compliance_score)
+            },
+            ToolResult::ScreenText(report) => {
+                format!("Screen text check {}: {} of {} expected texts found on screenshot {}",
+                    if report.passed() { "passed" } else { "failed" },
+                    report.matched.len(),
+                    report.matched.len() + report.missing.len(),
+                    report.screenshot_id)
             },
             ToolResult::DevelopmentSession(session) => {
                 format!("Development session {} started", session.session_id)
             },
             ToolResult::RequirementAnalysis(analysis) => {
-                format!("Requirement analyzed: {} complexity (confidence: {:.1}%)",
+                let unanswered = analysis.clarifying_questions.iter().filter(|question| question.answer.is_none()).count();
+                let questions = if unanswered > 0 { format!(", {} clarifying questions", unanswered) } else { String::new() };
+                format!("Requirement analyzed by the {}: {} complexity (confidence: {:.1}%){}",
+                    analysis.analyzer.label(),
                     analysis.complexity_estimate,
-                    analysis.confidence_score)
+                    analysis.confidence_score,
+                    questions)
             },
             ToolResult::PerformanceBottlenecks(bottlenecks) => {
                 format!("Found {} performance bottlenecks",
@@ -217,14 +674,1031 @@ impl ToolResult {
                     if validation.passed { "passed" } else { "failed" },
                     validation.overall_score)
             },
-            ToolResult::PerformanceStream(stream_id) => {
-                format!("Performance stream {} started", stream_id)
+            ToolResult::MetricsStreamStarted(handle) => {
+                format!("Streaming {} metrics every {}ms as {}", handle.metrics.len(), handle.interval_ms, handle.stream_id)
+            },
+            ToolResult::MetricsStreamStopped(summary) => {
+                format!("Stopped metrics stream {} after {} updates ({} samples coalesced, {} failed)",
+                    summary.stream_id, summary.updates_sent, summary.samples_coalesced, summary.failed_samples)
+            },
+            ToolResult::AppPerformance(measurement) => {
+                let launch = match measurement.summaries.get(&PerformanceMetric::ColdLaunchMs) {
+                    Some(cold) => format!("cold launch median {:.0}ms, p95 {:.0}ms", cold.median, cold.p95),
+                    None => "no launches".to_string(),
+                };
+                format!("Measured {} over {} runs: {}; performance {:?}",
+                    measurement.bundle_id, measurement.runs.len(), launch, measurement.verdict.status)
+            },
+            ToolResult::PerformanceTrends(trends) => trends.summary(),
+            ToolResult::PerformanceVerdict(verdict) => {
+                let count = |status: MetricStatus| verdict.metrics.iter().filter(|m| m.status == status).count();
+                format!("Performance {} ({} passed, {} warned, {} failed, {} missing)",
+                    match verdict.status {
+                        VerdictStatus::Pass => "passed",
+                        VerdictStatus::Warn => "passed with warnings",
+                        VerdictStatus::Fail => "failed",
+                    },
+                    count(MetricStatus::Pass),
+                    count(MetricStatus::Warn),
+                    count(MetricStatus::Fail),
+                    count(MetricStatus::Missing))
             },
             ToolResult::CompleteLoopResult(result) => {
-                format!("Development loop {} (score: {:.1}%)",
+                let termination = result.termination.as_ref()
+                    .map(|termination| format!(", {} after {} iterations", termination.reason, termination.iterations))
+                    .unwrap_or_default();
+                format!("Development loop {} (score: {:.1}%){}",
                     if result.success { "completed" } else { "failed" },
-                    result.validation_result.overall_score)
+                    result.validation_result.overall_score,
+                    termination)
+            },
+            ToolResult::DevelopmentCycles(cycles) => {
+                format!("{} development cycles, {} unfinished",
+                    cycles.len(),
+                    cycles.iter().filter(|cycle| cycle.next_stage.is_some()).count())
+            },
+            ToolResult::CycleReport(report) => {
+                format!("Wrote the report of cycle {} to {}{}",
+                    report.cycle_id,
+                    report.markdown_path.display(),
+                    report.html_path.as_ref().map(|path| format!(" and {}", path.display())).unwrap_or_default())
+            },
+            ToolResult::StageReview(review) => match &review.decision {
+                ApprovalDecision::Approve => {
+                    format!("Approved the {} of cycle {}", review.request.stage, review.request.cycle_id)
+                },
+                ApprovalDecision::Reject { .. } => {
+                    format!("Rejected the {} of cycle {}; it runs again with the feedback",
+                        review.request.stage, review.request.cycle_id)
+                },
+            },
+            ToolResult::RequirementAnswers(request) => {
+                let defaults = request.questions.iter()
+                    .filter(|question| question.answer.as_ref() == Some(&question.suggested_default))
+                    .count();
+                format!("Answered {} questions for cycle {}, {} with their suggested defaults",
+                    request.questions.len(), request.cycle_id, defaults)
+            },
+            ToolResult::CycleDiff(diff) => {
+                format!("{} commits on {} changing {} files", diff.commits, diff.branch, diff.files_changed.len())
+            },
+            ToolResult::TraceabilityMatrix(matrix) => {
+                format!("{:.1}% of {} requirement fragments implemented, {} gaps, {} elements without a requirement",
+                    matrix.coverage, matrix.rows.len(), matrix.gaps.len(), matrix.untraced.len())
+            },
+            ToolResult::AbandonedCycle(abandoned) => {
+                format!("Abandoned cycle {}: deleted {} with {} commits", abandoned.cycle_id, abandoned.branch, abandoned.discarded_commits)
+            },
+            ToolResult::ConnectionStatus(connections) => {
+                format!("{}/{} server connections up",
+                    connections.iter().filter(|c| c.state == crate::connection::ConnectionState::Connected).count(),
+                    connections.len())
+            },
+            ToolResult::SystemStatus(status) => {
+                let problems: Vec<String> = status.problems()
+                    .map(|report| format!("{:?} {:?}", report.subsystem, report.status))
+                    .collect();
+                let mut summary = if problems.is_empty() {
+                    format!("System {:?}", status.overall)
+                } else {
+                    format!("System {:?}: {}", status.overall, problems.join(", "))
+                };
+                if !status.locks.is_empty() {
+                    let busy: Vec<String> = status.locks.iter()
+                        .map(|holder| format!("{} held by {}", holder.resource, holder.tool))
+                        .collect();
+                    summary.push_str(&format!("; busy: {}", busy.join(", ")));
+                }
+                summary
+            },
+            ToolResult::ReloadMemoryTrend(trend) => trend.summary(),
+            ToolResult::ToolMetrics(report) => report.summary(),
+            ToolResult::GenerationCacheCleared(stats) => {
+                format!("Cleared {} cached generations ({} hits, {} misses, {:.0}% hit rate)",
+                    stats.entries, stats.hits, stats.misses, stats.hit_rate() * 100.0)
+            },
+            ToolResult::HotReloadStats(stats) => {
+                let filtered = format!("{} of {} file events filtered from hot reload",
+                    stats.events_filtered, stats.events_received);
+                match &stats.last_reload {
+                    Some(reload) => format!("{}; last reload {:?} because {}", filtered, reload.scope, reload.reason),
+                    None => filtered,
+                }
+            },
+            ToolResult::HotReloadHandshake(handshake) => {
+                format!("Hot reload server at {} speaks protocol {} ({} ms round trip, {} messages checked)",
+                    handshake.url, handshake.server_version, handshake.round_trip_ms, handshake.messages_checked)
+            },
+            ToolResult::Simulators(devices) => {
+                format!("{} simulators, {} booted",
+                    devices.len(),
+                    devices.iter().filter(|d| d.state == crate::simulator::DeviceState::Booted).count())
+            },
+            ToolResult::SimulatorAction(result) => {
+                if result.changed {
+                    format!("{} {}: {:?} -> {:?}", result.action, result.device.name, result.previous_state, result.device.state)
+                } else {
+                    format!("{} {}: already {:?}", result.action, result.device.name, result.device.state)
+                }
+            },
+            ToolResult::AppInstalled(result) => {
+                format!("Installed {} on {}", result.app_path.display(), result.device.name)
+            },
+            ToolResult::AppLaunched(result) => {
+                format!("Launched {} on {} (pid {})", result.bundle_id, result.device.name, result.pid)
+            },
+            ToolResult::SimulatorStateReset(result) => {
+                if result.reset.is_empty() {
+                    format!("Nothing to reset on {}", result.device.name)
+                } else {
+                    let reset: Vec<String> = result.reset.iter().map(ToString::to_string).collect();
+                    format!("Reset on {}: {}", result.device.name, reset.join(", "))
+                }
+            },
+            ToolResult::RecordingStarted(handle) => {
+                format!("Recording {} to {}", handle.device.name, handle.path.display())
+            },
+            ToolResult::Recording(recording) => {
+                format!("Recorded {:.1}s of {} to {} ({} bytes)",
+                    recording.duration_ms as f64 / 1000.0, recording.device.name, recording.path.display(), recording.size_bytes)
+            },
+            ToolResult::UiScript(run) => match &run.failure {
+                None => match run.secure_fields().as_slice() {
+                    [] => format!("UI script passed {} steps on {}", run.total_steps, run.udid),
+                    fields => format!("UI script passed {} steps on {}, leaving text out of the secure fields {}",
+                        run.total_steps, run.udid, fields.join(", ")),
+                },
+                Some(failure) => format!("UI script on {}: {}", run.udid, failure),
             },
         }
     }
-}
\ No newline at end of file
+}
+
+/// Arguments of tools that take none
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoArguments {}
+
+/// Arguments of `process_natural_language_requirement`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementArguments {
+    pub requirement: String,
+}
+
+/// Arguments of tools acting on one development cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleArguments {
+    pub cycle_id: String,
+}
+
+/// Arguments of tools acting on one simulator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceArguments {
+    /// UDID, or name and runtime
+    pub device: String,
+}
+
+/// Arguments of `stop_recording`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingArguments {
+    pub recording_id: String,
+}
+
+/// Arguments of `get_artifact`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactArguments {
+    pub artifact_id: String,
+}
+
+/// Arguments of `stop_metrics_stream`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsStreamArguments {
+    pub stream_id: String,
+}
+
+/// What `tools/list` says about a tool, apart from its handler
+#[derive(Debug)]
+pub struct ToolSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required_capabilities: &'static [&'static str],
+    /// Held for the whole call; calls needing a common one run one at a time
+    pub resources: &'static [ResourceNeed],
+    input_schema: fn() -> Value,
+}
+
+impl ToolSpec {
+    /// JSON Schema of the tool's `tools/call` arguments
+    pub fn input_schema(&self) -> Value {
+        (self.input_schema)()
+    }
+}
+
+pub fn tool_spec(name: &str) -> Option<&'static ToolSpec> {
+    TOOL_SPECS.iter().find(|spec| spec.name == name)
+}
+
+/// Every tool, in the order `tools/list` reports them
+pub const TOOL_SPECS: &[ToolSpec] = &[
+    ToolSpec {
+        name: "generate_presentation",
+        description: "Generate SwiftUI Presentation layer component following Axiom patterns",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: presentation_spec_schema,
+    },
+    ToolSpec {
+        name: "generate_context",
+        description: "Generate MainActor-bound Context layer component with @Published properties",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: context_spec_schema,
+    },
+    ToolSpec {
+        name: "generate_mock_client",
+        description: "Generate actor-based Client layer component conforming to AxiomClient protocol",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: client_spec_schema,
+    },
+    ToolSpec {
+        name: "generate_persistence",
+        description: "Generate a SwiftData @Model class with a ModelContainer helper and a repository actor for clients to load and save through",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: persistence_spec_schema,
+    },
+    ToolSpec {
+        name: "generate_feature",
+        description: "Generate a feature's context, mock client, a presentation per screen and optionally SwiftData persistence and navigation in one call, checking the references between them",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: feature_spec_schema,
+    },
+    ToolSpec {
+        name: "list_code_generation_templates",
+        description: "List the Context, Presentation and Client templates in use, whether each is built in or from the project, and the variables it can use",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "check_framework_compatibility",
+        description: "Report the AxiomCore and AxiomArchitecture versions detected from the configuration or the project's Package.resolved, and the generation features downgraded to older patterns for them",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "validate_architecture",
+        description: "Validate architecture compliance against Axiom Context-Presentation-Client patterns",
+        required_capabilities: &["intelligence_analysis"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "analyze_app_structure",
+        description: "Analyze app structure and provide intelligence recommendations",
+        required_capabilities: &["intelligence_analysis"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "start_metrics_stream",
+        description: "Sample the running app's memory, CPU, frame rate or hitches on an interval and push what changed as axiom/metrics notifications, merging updates while the session is slow to take them",
+        required_capabilities: &["performance_monitoring"],
+        resources: &[],
+        input_schema: metrics_stream_schema,
+    },
+    ToolSpec {
+        name: "stop_metrics_stream",
+        description: "Stop a metrics stream, reporting how many updates it sent and how many samples it coalesced",
+        required_capabilities: &["performance_monitoring"],
+        resources: &[],
+        input_schema: metrics_stream_id_schema,
+    },
+    ToolSpec {
+        name: "evaluate_performance",
+        description: "Grade launch time, memory, CPU, frame rate and hitch metrics against target, warn and fail thresholds",
+        required_capabilities: &["performance_monitoring"],
+        resources: &[],
+        input_schema: captured_metrics_schema,
+    },
+    ToolSpec {
+        name: "measure_app_performance",
+        description: "Terminate and relaunch an installed app to time cold and warm launches, sample its memory and count frames, reporting median and p95 across runs",
+        required_capabilities: &["simulator_management", "performance_monitoring"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: measure_app_performance_schema,
+    },
+    ToolSpec {
+        name: "get_performance_trends",
+        description: "Report each performance metric across development cycles and flag those whose latest value is worse than the rolling median",
+        required_capabilities: &["performance_monitoring"],
+        resources: &[],
+        input_schema: trend_options_schema,
+    },
+    ToolSpec {
+        name: "capture_screenshot_matrix",
+        description: "Capture screenshot matrix across the device configurations of a preset (mobile_only, tablet_only, ipad_inclusive, marketing, ipad_multitasking) or a custom device list",
+        required_capabilities: &["visual_analysis"],
+        resources: &[ResourceNeed::AllSimulators],
+        input_schema: screenshot_matrix_schema,
+    },
+    ToolSpec {
+        name: "compare_visual_states",
+        description: "Compare visual states to detect differences and changes",
+        required_capabilities: &["visual_analysis"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "detect_ui_regressions",
+        description: "Record screenshot baselines or diff screenshots against them, per screen configuration",
+        required_capabilities: &["visual_analysis"],
+        resources: &[],
+        input_schema: regression_check_schema,
+    },
+    ToolSpec {
+        name: "prune_screenshots",
+        description: "Remove stored screenshots older than the retention window or past its size limit, oldest first, keeping those recorded as baselines",
+        required_capabilities: &["visual_analysis"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "list_artifacts",
+        description: "List stored screenshots, recordings, build logs, test results, performance measurements and reports, filtered by kind, development cycle and stage, with their paths and the cycles that stored them",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: artifact_query_schema,
+    },
+    ToolSpec {
+        name: "get_artifact",
+        description: "Look up a stored artifact by its content hash: its kind, path, size and the cycles and stages that stored it",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: artifact_schema,
+    },
+    ToolSpec {
+        name: "prune_artifacts",
+        description: "Remove stored artifacts of every kind past the kind's retention window or size limit, oldest first, keeping screenshots recorded as baselines",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "audit_accessibility",
+        description: "Audit a screen's accessibility tree and screenshot for missing labels, touch targets under 44pt, WCAG AA contrast and unlabeled images",
+        required_capabilities: &["visual_analysis"],
+        resources: &[],
+        input_schema: accessibility_audit_schema,
+    },
+    ToolSpec {
+        name: "assert_screen_contains",
+        description: "Recognize the text on a screenshot and report which expected strings or regexes it shows, ignoring case, whitespace and smart quotes, with confidence and a crop of each",
+        required_capabilities: &["visual_analysis"],
+        resources: &[],
+        input_schema: screen_text_assertion_schema,
+    },
+    ToolSpec {
+        name: "start_development_session",
+        description: "Start complete development session with hot reload and monitoring",
+        required_capabilities: &["hot_reload_integration"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "process_natural_language_requirement",
+        description: "Process natural language requirements into implementation plans",
+        required_capabilities: &["intelligence_analysis"],
+        resources: &[],
+        input_schema: requirement_schema,
+    },
+    ToolSpec {
+        name: "resume_development_cycle",
+        description: "Resume a development cycle by id, reusing completed stages whose inputs are unchanged and re-running everything downstream of one that changed",
+        required_capabilities: &["code_generation"],
+        resources: &[ResourceNeed::Project],
+        input_schema: cycle_schema,
+    },
+    ToolSpec {
+        name: "list_development_cycles",
+        description: "List checkpointed development cycles with their completed stages, the next stage to run and why the last run stopped",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "export_cycle_report",
+        description: "Write a report of a development cycle with its analysis, generated files, validation issues, screenshots, performance against thresholds and why it stopped, as Markdown and optionally self-contained HTML",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: cycle_report_schema,
+    },
+    ToolSpec {
+        name: "get_traceability_matrix",
+        description: "Map each entity, screen and action of a development cycle's requirement to the generated context properties, actions and presentations addressing it, listing fragments nothing implements and elements no fragment asked for",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: cycle_schema,
+    },
+    ToolSpec {
+        name: "approve_stage",
+        description: "Approve the stage a development cycle is waiting on so it moves to the next one",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: cycle_schema,
+    },
+    ToolSpec {
+        name: "reject_stage",
+        description: "Reject the stage a development cycle is waiting on; it runs again with the feedback before asking for approval again",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: reject_stage_schema,
+    },
+    ToolSpec {
+        name: "provide_requirement_answers",
+        description: "Answer the questions a development cycle asked about its requirement; unanswered ones take their suggested defaults",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: requirement_answers_schema,
+    },
+    ToolSpec {
+        name: "get_cycle_diff",
+        description: "Get the patch of everything a development cycle committed to its axiom/cycle-<id> branch since it branched off",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: cycle_schema,
+    },
+    ToolSpec {
+        name: "abandon_cycle",
+        description: "Check out the branch a development cycle started from and delete the cycle's branch with all its commits",
+        required_capabilities: &["code_generation"],
+        resources: &[ResourceNeed::Project],
+        input_schema: cycle_schema,
+    },
+    ToolSpec {
+        name: "optimize_performance_bottlenecks",
+        description: "Analyze and optimize identified performance bottlenecks",
+        required_capabilities: &["performance_monitoring"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "get_connection_status",
+        description: "Report hot reload and intelligence connection state, reconnect attempts and buffered messages",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "get_system_status",
+        description: "Report readiness of hot reload, intelligence, simulators, code generation templates and screenshot storage, with the last error and how to fix each",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "set_hot_reload_filters",
+        description: "Set the include/exclude glob patterns that decide which file changes trigger a hot reload",
+        required_capabilities: &["hot_reload_integration"],
+        resources: &[ResourceNeed::HotReloadChannel],
+        input_schema: hot_reload_filters_schema,
+    },
+    ToolSpec {
+        name: "get_hot_reload_stats",
+        description: "Report hot reload file events received, forwarded and filtered, with the patterns that filtered them",
+        required_capabilities: &["hot_reload_integration"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "validate_hot_reload_connection",
+        description: "Ping the hot reload server and verify that its pong, and every message it sends meanwhile, matches the hot reload message schema; a mismatch names the message type and field",
+        required_capabilities: &["hot_reload_integration"],
+        resources: &[ResourceNeed::HotReloadChannel],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "clear_generation_cache",
+        description: "Forget cached code generations and validations, reporting the cache's hits, misses and entries before clearing",
+        required_capabilities: &["code_generation"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "get_reload_memory_trend",
+        description: "Report the launched app's memory before and after each recent hot reload, the fitted growth per reload, and a leak finding with a heap snapshot when growth exceeds the limit",
+        required_capabilities: &["performance_monitoring"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "get_tool_metrics",
+        description: "Report each tool's call count, duration histogram, failure rate by error category, bytes of output and internal stage timings since the MCP started",
+        required_capabilities: &[],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "list_simulators",
+        description: "List simulator devices parsed from simctl with their runtime, state and availability",
+        required_capabilities: &["simulator_management"],
+        resources: &[],
+        input_schema: no_arguments_schema,
+    },
+    ToolSpec {
+        name: "boot_simulator",
+        description: "Boot a simulator by UDID or name and runtime, waiting until it reaches the Booted state",
+        required_capabilities: &["simulator_management"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: device_schema,
+    },
+    ToolSpec {
+        name: "shutdown_simulator",
+        description: "Shut down a simulator by UDID or name and runtime",
+        required_capabilities: &["simulator_management"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: device_schema,
+    },
+    ToolSpec {
+        name: "erase_simulator",
+        description: "Erase all content and settings of a shut down simulator by UDID or name and runtime",
+        required_capabilities: &["simulator_management"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: device_schema,
+    },
+    ToolSpec {
+        name: "install_app",
+        description: "Install a simulator build of an .app bundle on a simulator",
+        required_capabilities: &["simulator_management"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: install_app_schema,
+    },
+    ToolSpec {
+        name: "launch_app",
+        description: "Launch an app by bundle id with arguments and environment, returning its pid and optionally its console output",
+        required_capabilities: &["simulator_management"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: launch_app_schema,
+    },
+    ToolSpec {
+        name: "reset_simulator_state",
+        description: "Reset selected state of a booted simulator: reinstall an app without its data, privacy permissions, status bar overrides, keychain and appearance",
+        required_capabilities: &["simulator_management"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: reset_simulator_state_schema,
+    },
+    ToolSpec {
+        name: "start_recording",
+        description: "Start recording the screen of a booted simulator to a video file, stopping by itself at the recording length cap",
+        required_capabilities: &["simulator_management"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: start_recording_schema,
+    },
+    ToolSpec {
+        name: "stop_recording",
+        description: "Stop a recording, returning the video file path, duration and size",
+        required_capabilities: &["simulator_management"],
+        resources: &[],
+        input_schema: recording_schema,
+    },
+    ToolSpec {
+        name: "run_ui_script",
+        description: "Run a UI script on a booted simulator's app through the intelligence server, stopping at the first failed step with a screenshot and the element tree",
+        required_capabilities: &["simulator_management", "intelligence_analysis"],
+        resources: &[ResourceNeed::Simulator("device")],
+        input_schema: run_ui_script_schema,
+    },
+];
+
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn string(description: &str) -> Value {
+    json!({ "type": "string", "description": description })
+}
+
+fn number(description: &str) -> Value {
+    json!({ "type": "number", "description": description })
+}
+
+fn count(description: &str) -> Value {
+    json!({ "type": "integer", "minimum": 0, "description": description })
+}
+
+fn boolean(description: &str) -> Value {
+    json!({ "type": "boolean", "description": description })
+}
+
+fn strings(description: &str) -> Value {
+    json!({ "type": "array", "items": { "type": "string" }, "description": description })
+}
+
+fn one_of(values: &[&str], description: &str) -> Value {
+    json!({ "type": "string", "enum": values, "description": description })
+}
+
+fn array_of(items: Value, description: &str) -> Value {
+    json!({ "type": "array", "items": items, "description": description })
+}
+
+fn string_map(description: &str) -> Value {
+    json!({ "type": "object", "additionalProperties": { "type": "string" }, "description": description })
+}
+
+/// An object whose fields are left to deserialization to check
+fn open_object(description: &str) -> Value {
+    json!({ "type": "object", "description": description })
+}
+
+/// `schema`, also accepting the `null` an unset `Option` serializes to
+fn nullable(mut schema: Value) -> Value {
+    if let Some(Value::String(name)) = schema.get("type").cloned() {
+        schema["type"] = json!([name, "null"]);
+    }
+    if let Some(Value::Array(values)) = schema.get_mut("enum") {
+        values.push(Value::Null);
+    }
+    schema
+}
+
+fn template_overrides() -> Value {
+    string_map("Template variable values replacing those derived from the spec, for this call only")
+}
+
+fn requirement_ids() -> Value {
+    strings("Ids of the requirement fragments this addresses, recorded for traceability only")
+}
+
+fn frame(description: &str) -> Value {
+    let mut schema = object(json!({
+        "x": number("Left edge"),
+        "y": number("Top edge"),
+        "width": number("Width"),
+        "height": number("Height"),
+    }), &["x", "y", "width", "height"]);
+    schema["description"] = json!(description);
+    schema
+}
+
+fn screenshot() -> Value {
+    object(json!({
+        "id": string("Screenshot id"),
+        "client_id": string("Client the screenshot was captured for"),
+        "configuration": open_object("Device type, screen size, orientation, scale, color scheme and capture mode"),
+        "image_data": array_of(json!({ "type": "integer", "minimum": 0, "maximum": 255 }), "PNG bytes; empty when the image is in the screenshot store"),
+        "image": nullable(open_object("Reference to the image in the screenshot store")),
+        "metadata": open_object("Capture time, device and app state"),
+    }), &["id", "client_id", "configuration", "metadata"])
+}
+
+fn device() -> Value {
+    string("Simulator UDID, or name and runtime")
+}
+
+fn no_arguments_schema() -> Value {
+    object(json!({}), &[])
+}
+
+fn presentation_spec_schema() -> Value {
+    object(json!({
+        "name": string("Name of the presentation"),
+        "context_binding": string("Context the presentation observes"),
+        "ui_components": strings("SwiftUI components the presentation is built from"),
+        "accessibility_requirements": strings("Accessibility requirements to meet"),
+        "performance_requirements": object(json!({
+            "max_render_time_ms": number("Render time budget in milliseconds"),
+            "max_memory_mb": number("Memory budget in megabytes"),
+        }), &["max_render_time_ms", "max_memory_mb"]),
+        "template_overrides": template_overrides(),
+        "requirement_ids": requirement_ids(),
+    }), &["name", "context_binding", "ui_components", "accessibility_requirements", "performance_requirements"])
+}
+
+fn context_spec_schema() -> Value {
+    let state_property = object(json!({
+        "name": string("Property name"),
+        "property_type": string("Swift type"),
+        "is_published": boolean("Marked @Published"),
+        "default_value": nullable(string("Swift expression the property starts as")),
+        "requirement_ids": requirement_ids(),
+    }), &["name", "property_type", "is_published"]);
+    object(json!({
+        "name": string("Name of the context"),
+        "state_properties": array_of(state_property, "State the context holds"),
+        "client_binding": string("Client the context talks to"),
+        "lifecycle_management": boolean("Generate appear and disappear handling"),
+        "template_overrides": template_overrides(),
+    }), &["name", "state_properties", "client_binding", "lifecycle_management"])
+}
+
+fn client_spec_schema() -> Value {
+    let action = object(json!({
+        "name": string("Action name"),
+        "parameters": strings("Swift parameter declarations"),
+        "return_type": string("Swift return type"),
+        "is_async": boolean("Declared async"),
+        "requirement_ids": requirement_ids(),
+    }), &["name", "parameters", "return_type", "is_async"]);
+    object(json!({
+        "name": string("Name of the client"),
+        "protocol_conformance": strings("Protocols the client conforms to"),
+        "actions": array_of(action, "Actions the client handles"),
+        "state_streaming": boolean("Stream state changes to observers"),
+        "mock_implementation": boolean("Generate a mock implementation"),
+        "template_overrides": template_overrides(),
+        "persistence": nullable(persistence_spec_schema()),
+    }), &["name", "protocol_conformance", "actions", "state_streaming", "mock_implementation"])
+}
+
+fn persistence_spec_schema() -> Value {
+    let field = object(json!({
+        "name": string("Field name"),
+        "field_type": string("Swift type, without the ? of an optional field"),
+        "optional": boolean("The field is optional"),
+        "default_value": nullable(string("Swift expression the initializer defaults the field to")),
+        "unique": boolean("Marked @Attribute(.unique)"),
+    }), &["name", "field_type"]);
+    let relationship = object(json!({
+        "name": string("Relationship name"),
+        "target_entity": string("Entity the relationship points at"),
+        "kind": one_of(&["to_one", "to_many"], "An optional reference to one target, or an array of targets"),
+        "inverse": nullable(string("Property of the target entity pointing back at this one")),
+        "delete_rule": one_of(&["nullify", "cascade", "deny"], "What deleting this entity does to its targets"),
+    }), &["name", "target_entity"]);
+    let migration_strategy = object(json!({
+        "type": one_of(&["automatic", "versioned"], "Lightweight migration, or a versioned schema with a migration plan"),
+        "version": count("Schema version of a versioned migration"),
+    }), &["type"]);
+    object(json!({
+        "entity_name": string("Name of the @Model class"),
+        "fields": array_of(field, "Stored fields"),
+        "relationships": array_of(relationship, "Relationships to other entities"),
+        "migration_strategy": migration_strategy,
+    }), &["entity_name", "fields"])
+}
+
+fn feature_spec_schema() -> Value {
+    let field = object(json!({
+        "name": string("Field name"),
+        "field_type": string("Swift type, without the ? of an optional field"),
+        "optional": boolean("The field is optional"),
+        "default_value": nullable(string("Swift expression the initializer defaults the field to")),
+        "unique": boolean("Marked @Attribute(.unique)"),
+    }), &["name", "field_type"]);
+    let entity = object(json!({
+        "name": string("Entity name, held by the context as an array"),
+        "fields": array_of(field, "Stored fields, with persistence"),
+    }), &["name"]);
+    let screen = object(json!({
+        "name": string("Screen name; TaskList gives TaskListView"),
+        "actions": strings("Client actions the screen triggers through the context"),
+    }), &["name"]);
+    object(json!({
+        "name": string("Feature name; TaskManager gives TaskManagerContext and TaskManagerClient"),
+        "entities": array_of(entity, "What the feature keeps track of"),
+        "screens": array_of(screen, "Screens of the feature, the first the navigation root"),
+        "persistence": boolean("Generate a SwiftData model per entity, the client loading and saving the first"),
+        "navigation": boolean("Push every other screen from the first"),
+        "template_overrides": object(json!({
+            "context": template_overrides(),
+            "presentation": template_overrides(),
+            "client": template_overrides(),
+        }), &[]),
+    }), &["name", "screens"])
+}
+
+fn captured_metrics_schema() -> Value {
+    object(json!({
+        "cold_launch_ms": number("Launch of a terminated app until its first frame"),
+        "warm_launch_ms": number("Launch of an app still in memory until its first frame"),
+        "memory_mb": number("Resident memory once the app settles"),
+        "cpu_percent": number("CPU usage"),
+        "frame_rate_fps": number("Frames per second"),
+        "hitch_time_ratio": number("Milliseconds spent hitching per second of scrolling or animation"),
+    }), &[])
+}
+
+fn metrics_stream_schema() -> Value {
+    object(json!({
+        "interval_ms": {
+            "type": "integer",
+            "minimum": crate::metrics_stream::MIN_METRICS_INTERVAL_MS,
+            "description": "Time between samples",
+        },
+        "metrics": array_of(
+            json!({ "type": "string", "enum": ["cold_launch_ms", "warm_launch_ms", "memory_mb", "cpu_percent", "frame_rate_fps", "hitch_time_ratio"] }),
+            "Metrics to stream; memory, CPU, frame rate and hitches when empty",
+        ),
+    }), &[])
+}
+
+fn metrics_stream_id_schema() -> Value {
+    object(json!({
+        "stream_id": string("Id start_metrics_stream returned"),
+    }), &["stream_id"])
+}
+
+fn measure_app_performance_schema() -> Value {
+    object(json!({
+        "device": device(),
+        "bundle_id": string("Bundle id of the installed app"),
+        "runs": {
+            "type": "integer",
+            "minimum": 1,
+            "maximum": crate::performance_analysis_integration::MAX_MEASUREMENT_RUNS,
+            "description": "Launches to take the median and p95 over",
+        },
+        "window_ms": count("How long to sample memory and count frames in each run"),
+        "memory_interval_ms": count("Time between memory samples"),
+    }), &["device", "bundle_id"])
+}
+
+fn trend_options_schema() -> Value {
+    object(json!({
+        "window": count("Earlier cycles the rolling median is taken over"),
+        "regression_threshold_percent": number("How much worse than the rolling median the latest value may be"),
+    }), &[])
+}
+
+fn screenshot_matrix_schema() -> Value {
+    let presets: Vec<&str> = DeviceMatrixPreset::ALL.iter().map(|preset| preset.name()).collect();
+    let windows: Vec<&str> = WindowConfiguration::ALL.iter().map(|window| window.name()).collect();
+    let custom_device = object(json!({
+        "model": string("Simulator device type name, such as iPhone 15 Pro"),
+        "os_version": nullable(string("OS version such as 17.2, or 17 for the newest 17.x; the newest installed when unset")),
+        "orientations": array_of(one_of(&["portrait", "landscape"], "Orientation"), "Orientations to capture in"),
+        "screen_size": nullable(object(json!({
+            "width": number("Width in points"),
+            "height": number("Height in points"),
+        }), &["width", "height"])),
+        "scale": nullable(number("Pixels per point; known models have theirs filled in")),
+    }), &["model"]);
+    object(json!({
+        "preset": one_of(&presets, "Device matrix preset; ipad_inclusive when neither a preset nor a custom list is given"),
+        "custom": object(json!({
+            "devices": array_of(custom_device, "Devices to capture on"),
+            "color_schemes": array_of(one_of(&["light", "dark"], "Color scheme"), "Color schemes to capture in"),
+            "window_configurations": array_of(one_of(&windows, "iPad window"), "Windows iPads capture the app in; other devices capture fullscreen only"),
+            "substitute_nearest_runtime": boolean("Capture on the nearest installed runtime, with a warning, when a requested one is missing"),
+        }), &["devices"]),
+        "pre_capture_script": string("UI script run on each simulator before it captures, one step per line: tap <id>, type \"<text>\" [into <id>], paste \"<text>\" [into <id>], swipe <direction> [on <id>], wait <id> [<timeout>], setLocale <locale> [keyboard <keyboard>]"),
+    }), &[])
+}
+
+fn regression_check_schema() -> Value {
+    object(json!({
+        "mode": one_of(&["record", "check"], "Record the screenshots as baselines, or check them against the baselines"),
+        "screen": string("Screen the screenshots show"),
+        "screenshots": array_of(screenshot(), "One screenshot per configuration"),
+    }), &["mode", "screen", "screenshots"])
+}
+
+fn artifact_query_schema() -> Value {
+    let kinds: Vec<&str> = ArtifactKind::ALL.iter().map(|kind| kind.name()).collect();
+    object(json!({
+        "kind": nullable(one_of(&kinds, "Only artifacts of this kind")),
+        "cycle_id": nullable(string("Only artifacts stored by this development cycle")),
+        "stage": nullable(string("Only artifacts stored by this stage, such as build or test execution")),
+    }), &[])
+}
+
+fn artifact_schema() -> Value {
+    object(json!({
+        "artifact_id": string("Artifact id, the SHA-256 of its content"),
+    }), &["artifact_id"])
+}
+
+fn accessibility_audit_schema() -> Value {
+    object(json!({
+        "screenshot": screenshot(),
+        "element_tree": nullable(open_object("Accessibility element tree with camelCase keys; requested from the intelligence server when not given")),
+    }), &["screenshot"])
+}
+
+fn screen_text_assertion_schema() -> Value {
+    let expectation = object(json!({
+        "text": string("Text to find"),
+        "regex": boolean("The text is a regular expression rather than literal text"),
+    }), &["text"]);
+    object(json!({
+        "screenshot": screenshot(),
+        "expected": array_of(expectation, "Text the screen should show"),
+        "region": nullable(frame("Only text centered inside this area counts, in points")),
+    }), &["screenshot", "expected"])
+}
+
+fn requirement_schema() -> Value {
+    object(json!({
+        "requirement": string("What to build, in plain language"),
+    }), &["requirement"])
+}
+
+fn cycle_schema() -> Value {
+    object(json!({
+        "cycle_id": string("Development cycle id"),
+    }), &["cycle_id"])
+}
+
+fn cycle_report_schema() -> Value {
+    object(json!({
+        "cycle_id": string("Development cycle id"),
+        "html": boolean("Also write an HTML report with the screenshots embedded"),
+    }), &["cycle_id"])
+}
+
+fn reject_stage_schema() -> Value {
+    object(json!({
+        "cycle_id": string("Development cycle id"),
+        "feedback": string("What to change when the stage runs again"),
+    }), &["cycle_id", "feedback"])
+}
+
+fn requirement_answers_schema() -> Value {
+    object(json!({
+        "cycle_id": string("Development cycle id"),
+        "answers": string_map("Answers by question id; unanswered questions take their suggested defaults"),
+    }), &["cycle_id"])
+}
+
+fn hot_reload_filters_schema() -> Value {
+    object(json!({
+        "include": strings("Globs a changed file must match to reload"),
+        "exclude": strings("Globs whose matches never reload"),
+    }), &[])
+}
+
+fn device_schema() -> Value {
+    object(json!({
+        "device": device(),
+    }), &["device"])
+}
+
+fn install_app_schema() -> Value {
+    object(json!({
+        "device": device(),
+        "app_path": string("Path of a simulator build of an .app bundle"),
+    }), &["device", "app_path"])
+}
+
+fn launch_app_schema() -> Value {
+    object(json!({
+        "device": device(),
+        "bundle_id": string("Bundle id of the installed app"),
+        "arguments": strings("Launch arguments"),
+        "environment": string_map("Environment variables"),
+        "console_seconds": nullable(count("Attach to the app's console and capture its stdout for this many seconds")),
+        "keep_running": boolean("Bring an already running instance to the foreground instead of restarting it"),
+    }), &["device", "bundle_id"])
+}
+
+fn reset_simulator_state_schema() -> Value {
+    let app_data = object(json!({
+        "bundle_id": string("App to reinstall without its data"),
+        "app_path": nullable(string("Installed again after uninstalling when set")),
+    }), &["bundle_id"]);
+    let services = [
+        "all", "calendar", "contacts", "location", "location_always", "photos", "photos_add",
+        "media_library", "microphone", "motion", "reminders", "siri",
+    ];
+    let privacy = object(json!({
+        "services": array_of(one_of(&services, "Privacy service"), "Every service when empty"),
+        "bundle_id": nullable(string("Only this app's permissions when set")),
+    }), &[]);
+    object(json!({
+        "device": device(),
+        "app_data": nullable(app_data),
+        "privacy": nullable(privacy),
+        "status_bar": boolean("Clear status bar overrides"),
+        "keychain": boolean("Reset the keychain"),
+        "appearance": nullable(one_of(&["light", "dark"], "Appearance to switch to")),
+    }), &["device"])
+}
+
+fn start_recording_schema() -> Value {
+    object(json!({
+        "device": device(),
+        "max_seconds": nullable(count("Stop sooner than the configured cap")),
+    }), &["device"])
+}
+
+fn run_ui_script_schema() -> Value {
+    object(json!({
+        "device": device(),
+        "script": string("One step per line, # for comments: tap <id>, type \"<text>\" [into <id>], paste \"<text>\" [into <id>], swipe up|down|left|right [on <id>], wait <id> [500ms|3s], setLocale <locale> [keyboard <keyboard>]"),
+        "locale": string("Locale to set the device to before the script runs, such as de_DE; text is typed as on a US keyboard when unset"),
+    }), &["device", "script"])
+}
+
+fn recording_schema() -> Value {
+    object(json!({
+        "recording_id": string("Id start_recording returned"),
+    }), &["recording_id"])
+}
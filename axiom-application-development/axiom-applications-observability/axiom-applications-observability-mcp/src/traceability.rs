@@ -0,0 +1,181 @@
+use crate::types::{ActionDefinition, Implementation, PresentationSpec, RequirementAnalysis, StateProperty};
+use serde::{Deserialize, Serialize};
+
+/// What part of a requirement a fragment is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FragmentKind {
+    /// Something the app keeps track of
+    Entity,
+    Screen,
+    /// Something users do
+    Action,
+}
+
+impl std::fmt::Display for FragmentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FragmentKind::Entity => "entity",
+            FragmentKind::Screen => "screen",
+            FragmentKind::Action => "action",
+        })
+    }
+}
+
+/// One thing a requirement asks for, under an id generated code is traced to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequirementFragment {
+    /// `R1`, `R2`, ... in the order of the analysis' entities, screens and actions
+    pub id: String,
+    pub kind: FragmentKind,
+    /// The entity, screen or action as the analysis names it, `NoteDetail`
+    pub text: String,
+}
+
+/// The entities, screens and actions of an analysis as fragments, numbered
+/// in that order so the same analysis always gives the same ids
+pub fn requirement_fragments(entities: &[String], screens: &[String], actions: &[String]) -> Vec<RequirementFragment> {
+    let kinds = entities.iter().map(|text| (FragmentKind::Entity, text))
+        .chain(screens.iter().map(|text| (FragmentKind::Screen, text)))
+        .chain(actions.iter().map(|text| (FragmentKind::Action, text)));
+    kinds
+        .enumerate()
+        .map(|(index, (kind, text))| RequirementFragment { id: format!("R{}", index + 1), kind, text: text.clone() })
+        .collect()
+}
+
+/// Fragments of `analysis`, numbered from its entities, screens and actions
+/// when it was checkpointed before it kept them
+pub fn analysis_fragments(analysis: &RequirementAnalysis) -> Vec<RequirementFragment> {
+    if analysis.fragments.is_empty() {
+        requirement_fragments(&analysis.entities, &analysis.screens, &analysis.actions)
+    } else {
+        analysis.fragments.clone()
+    }
+}
+
+/// Ids of the `kind` fragments whose text is `text`
+pub fn fragment_ids(fragments: &[RequirementFragment], kind: FragmentKind, text: &str) -> Vec<String> {
+    fragments.iter()
+        .filter(|fragment| fragment.kind == kind && fragment.text == text)
+        .map(|fragment| fragment.id.clone())
+        .collect()
+}
+
+/// What kind of generated element is traced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElementKind {
+    ContextProperty,
+    Action,
+    Presentation,
+}
+
+impl std::fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ElementKind::ContextProperty => "context property",
+            ElementKind::Action => "action",
+            ElementKind::Presentation => "presentation",
+        })
+    }
+}
+
+/// A generated context property, action or presentation, with the
+/// requirement fragments it addresses
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TracedElement {
+    pub kind: ElementKind,
+    pub name: String,
+    #[serde(default)]
+    pub requirement_ids: Vec<String>,
+}
+
+impl TracedElement {
+    pub fn new(kind: ElementKind, name: impl Into<String>, requirement_ids: Vec<String>) -> Self {
+        Self { kind, name: name.into(), requirement_ids }
+    }
+}
+
+impl std::fmt::Display for TracedElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kind, self.name)
+    }
+}
+
+impl From<&StateProperty> for TracedElement {
+    fn from(property: &StateProperty) -> Self {
+        Self::new(ElementKind::ContextProperty, &property.name, property.requirement_ids.clone())
+    }
+}
+
+impl From<&ActionDefinition> for TracedElement {
+    fn from(action: &ActionDefinition) -> Self {
+        Self::new(ElementKind::Action, &action.name, action.requirement_ids.clone())
+    }
+}
+
+impl From<&PresentationSpec> for TracedElement {
+    fn from(presentation: &PresentationSpec) -> Self {
+        Self::new(ElementKind::Presentation, &presentation.name, presentation.requirement_ids.clone())
+    }
+}
+
+/// A requirement fragment with the generated elements addressing it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceabilityRow {
+    pub fragment: RequirementFragment,
+    pub elements: Vec<TracedElement>,
+}
+
+/// Which generated elements satisfy which parts of a requirement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceabilityMatrix {
+    pub analysis_id: String,
+    pub implementation_id: String,
+    /// One per fragment, in the analysis' order
+    pub rows: Vec<TraceabilityRow>,
+    /// Fragments no generated element addresses
+    pub gaps: Vec<RequirementFragment>,
+    /// Generated elements addressing no fragment of the requirement, which
+    /// may have been generated for nothing it asks for
+    pub untraced: Vec<TracedElement>,
+    /// Percentage of fragments some element addresses; 100 for a
+    /// requirement the analysis found nothing in
+    pub coverage: f64,
+}
+
+impl TraceabilityMatrix {
+    pub fn build(analysis: &RequirementAnalysis, implementation: &Implementation) -> Self {
+        let fragments = analysis_fragments(analysis);
+        let rows: Vec<TraceabilityRow> = fragments.iter()
+            .map(|fragment| TraceabilityRow {
+                fragment: fragment.clone(),
+                elements: implementation.traceability.iter()
+                    .filter(|element| element.requirement_ids.contains(&fragment.id))
+                    .cloned()
+                    .collect(),
+            })
+            .collect();
+        let gaps: Vec<RequirementFragment> = rows.iter()
+            .filter(|row| row.elements.is_empty())
+            .map(|row| row.fragment.clone())
+            .collect();
+        let untraced = implementation.traceability.iter()
+            .filter(|element| !element.requirement_ids.iter().any(|id| fragments.iter().any(|fragment| fragment.id == *id)))
+            .cloned()
+            .collect();
+        let coverage = match rows.len() {
+            0 => 100.0,
+            total => (total - gaps.len()) as f64 * 100.0 / total as f64,
+        };
+        Self {
+            analysis_id: analysis.requirement_id.clone(),
+            implementation_id: implementation.implementation_id.clone(),
+            rows,
+            gaps,
+            untraced,
+            coverage,
+        }
+    }
+}
@@ -10,6 +10,14 @@ pub struct PresentationSpec {
     pub ui_components: Vec<String>,
     pub accessibility_requirements: Vec<String>,
     pub performance_requirements: PerformanceRequirements,
+    /// Values for template variables, replacing those derived from the
+    /// rest of the spec for this call only
+    #[serde(default)]
+    pub template_overrides: std::collections::HashMap<String, String>,
+    /// Requirement fragments the presentation addresses, which do not
+    /// reach the generated code
+    #[serde(default)]
+    pub requirement_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +32,9 @@ pub struct ContextSpec {
     pub state_properties: Vec<StateProperty>,
     pub client_binding: String,
     pub lifecycle_management: bool,
+    /// See `PresentationSpec::template_overrides`
+    #[serde(default)]
+    pub template_overrides: std::collections::HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +43,9 @@ pub struct StateProperty {
     pub property_type: String,
     pub is_published: bool,
     pub default_value: Option<String>,
+    /// See `PresentationSpec::requirement_ids`
+    #[serde(default)]
+    pub requirement_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +55,12 @@ pub struct ClientSpec {
     pub actions: Vec<ActionDefinition>,
     pub state_streaming: bool,
     pub mock_implementation: bool,
+    /// See `PresentationSpec::template_overrides`
+    #[serde(default)]
+    pub template_overrides: std::collections::HashMap<String, String>,
+    /// Entity the mock client loads and saves through its repository
+    #[serde(default)]
+    pub persistence: Option<crate::code_generation::PersistenceSpec>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +69,9 @@ pub struct ActionDefinition {
     pub parameters: Vec<String>,
     pub return_type: String,
     pub is_async: bool,
+    /// See `PresentationSpec::requirement_ids`
+    #[serde(default)]
+    pub requirement_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,10 +102,32 @@ pub struct Screenshot {
     pub id: String,
     pub client_id: String,
     pub configuration: ScreenshotConfiguration,
+    /// Empty when the image is in the screenshot store
+    #[serde(default)]
     pub image_data: Vec<u8>,
+    #[serde(default)]
+    pub image: Option<crate::screenshot_store::StoredImage>,
     pub metadata: ScreenshotMetadata,
 }
 
+impl Screenshot {
+    /// The PNG, from memory or from the screenshot store
+    pub fn png_data(&self) -> crate::error::Result<std::borrow::Cow<'_, [u8]>> {
+        match &self.image {
+            Some(image) if self.image_data.is_empty() => {
+                if image.encoding != crate::screenshot_store::ImageEncoding::Png {
+                    return Err(crate::error::AxiomMCPError::validation("screenshots", "read screenshot", format!(
+                        "Screenshot {} is stored as {:?}, which cannot be analyzed; store screenshots as PNG to compare them",
+                        self.id, image.encoding
+                    )));
+                }
+                Ok(std::borrow::Cow::Owned(std::fs::read(&image.path)?))
+            }
+            _ => Ok(std::borrow::Cow::Borrowed(&self.image_data)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenshotConfiguration {
     pub device_type: String,
@@ -91,9 +136,50 @@ pub struct ScreenshotConfiguration {
     pub scale: f64,
     pub color_scheme: String,
     pub capture_mode: String,
+    /// Fixed clock, battery and signal instead of the simulator's own
+    #[serde(default)]
+    pub status_bar: Option<crate::simulator::StatusBarOverride>,
+    /// Runtime to capture on, `iOS 17.2`; whichever the simulator of the
+    /// device type has when unset
+    #[serde(default)]
+    pub runtime: Option<String>,
+    /// The app's window on an iPad; `screen_size` is the window's size
+    #[serde(default)]
+    pub window: crate::device_matrix::WindowConfiguration,
+}
+
+impl ScreenshotConfiguration {
+    /// The device type and runtime of the simulator this configuration
+    /// is captured on
+    pub fn simulator(&self) -> (&str, Option<&str>) {
+        (&self.device_type, self.runtime.as_deref())
+    }
+    
+    /// What a simulator capture of this configuration overrides
+    pub fn overrides(&self) -> crate::simulator::CaptureOverrides {
+        crate::simulator::CaptureOverrides {
+            appearance: Some(self.color_scheme.clone()),
+            status_bar: self.status_bar.clone(),
+            window: Some(self.window).filter(|window| *window != crate::device_matrix::WindowConfiguration::Fullscreen),
+        }
+    }
+    
+    /// The horizontal size class the app lays out in
+    pub fn size_class(&self) -> crate::device_matrix::SizeClass {
+        crate::device_matrix::SizeClass::of(&self.device_type, self.screen_size.width)
+    }
+    
+    /// Device, orientation, color scheme and, when not fullscreen, window
+    pub fn label(&self) -> String {
+        let mut label = format!("{} {} {}", self.device_type, self.orientation, self.color_scheme);
+        if self.window != crate::device_matrix::WindowConfiguration::Fullscreen {
+            label.push_str(&format!(" {} ({})", self.window, self.size_class().name()));
+        }
+        label
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScreenSize {
     pub width: f64,
     pub height: f64,
@@ -104,6 +190,9 @@ pub struct ScreenshotMetadata {
     pub timestamp: DateTime<Utc>,
     pub device_info: DeviceInfo,
     pub app_state: AppState,
+    /// Overrides the device showed while it was captured
+    #[serde(default)]
+    pub overrides: crate::simulator::CaptureOverrides,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -111,19 +200,36 @@ pub struct DeviceInfo {
     pub model: String,
     pub screen_size: ScreenSize,
     pub orientation: String,
+    /// Simulator the screenshot was captured on
+    #[serde(default)]
+    pub udid: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppState {
     pub view_hierarchy: String,
     pub active_context: String,
+    /// Frames and text sizes of the views on screen, when the app reported them
+    #[serde(default)]
+    pub view_tree: Option<crate::advanced_visual_intelligence::ViewNode>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenshotAnalysis {
     pub total_screenshots: usize,
+    /// How alike each screen looks across devices, from 0 to 1
     pub consistency_score: f64,
     pub detected_issues: Vec<String>,
+    /// Truncated text and overlapping views behind `detected_issues`
+    #[serde(default)]
+    pub layout_issues: Vec<crate::advanced_visual_intelligence::LayoutIssue>,
+    /// The device variants `consistency_score` is the mean of
+    #[serde(default)]
+    pub consistency_pairs: Vec<crate::screenshot_consistency::PairConsistency>,
+    /// What the matrix left out or substituted, such as split windows on
+    /// an iPhone
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -139,6 +245,19 @@ pub struct RegressionReport {
     pub regressions_detected: usize,
     pub false_positive_rate: f64,
     pub confidence_score: f64,
+    /// Whether baselines were recorded or checked
+    #[serde(default)]
+    pub mode: Option<crate::visual_regression::RegressionMode>,
+    /// Outcome per screen configuration, in the order given
+    #[serde(default)]
+    pub configurations: Vec<crate::visual_regression::ConfigurationDiff>,
+}
+
+impl RegressionReport {
+    /// Whether every configuration had a baseline and stayed within its threshold
+    pub fn passed(&self) -> bool {
+        !self.configurations.iter().any(|diff| diff.status.is_failure())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -157,6 +276,35 @@ pub struct RequirementAnalysis {
     pub complexity_estimate: String,
     pub recommended_approach: String,
     pub estimated_components: Vec<String>,
+    /// Entity to persist, when the requirement asks for saving or offline use
+    #[serde(default)]
+    pub persistence: Option<crate::code_generation::PersistenceSpec>,
+    /// Routes between screens, when the requirement describes several
+    #[serde(default)]
+    pub navigation: Option<crate::code_generation::NavigationSpec>,
+    /// Reviewer feedback the analysis was redone with
+    #[serde(default)]
+    pub review_feedback: Vec<String>,
+    /// Things the app keeps track of, `Note`
+    #[serde(default)]
+    pub entities: Vec<String>,
+    /// Screens the requirement names, `NoteDetail`
+    #[serde(default)]
+    pub screens: Vec<String>,
+    /// What users do, `add`
+    #[serde(default)]
+    pub actions: Vec<String>,
+    /// What a low-confidence requirement leaves open, answered once the
+    /// cycle has asked
+    #[serde(default)]
+    pub clarifying_questions: Vec<crate::requirement_clarification::ClarifyingQuestion>,
+    /// Whether the intelligence server or the offline rules produced this
+    #[serde(default)]
+    pub analyzer: crate::requirement_analysis::AnalyzerKind,
+    /// The entities, screens and actions under the ids generated code is
+    /// traced to
+    #[serde(default)]
+    pub fragments: Vec<crate::traceability::RequirementFragment>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -182,6 +330,20 @@ pub struct Implementation {
     pub test_suite: TestSuite,
     pub documentation: ImplementationDocumentation,
     pub estimated_performance: PerformanceEstimate,
+    #[serde(default)]
+    pub persistence_layer: Option<GeneratedPersistence>,
+    #[serde(default)]
+    pub navigation_layer: Option<GeneratedNavigation>,
+    /// Reviewer feedback the implementation was regenerated with
+    #[serde(default)]
+    pub review_feedback: Vec<String>,
+    /// Text the app should show once launched, checked on its screen
+    #[serde(default)]
+    pub expected_screen_text: Vec<crate::advanced_visual_intelligence::TextExpectation>,
+    /// Generated context properties, actions and presentations with the
+    /// requirement fragments each addresses
+    #[serde(default)]
+    pub traceability: Vec<crate::traceability::TracedElement>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -195,6 +357,9 @@ pub struct GeneratedContext {
     pub name: String,
     pub responsibilities: Vec<String>,
     pub state_properties: Vec<String>,
+    /// What its presentations call on it
+    #[serde(default)]
+    pub actions: Vec<String>,
     pub client_binding: String,
     pub generated_code: String,
 }
@@ -227,6 +392,19 @@ pub struct GeneratedClient {
     pub generated_code: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedPersistence {
+    pub entity_name: String,
+    pub generated_code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedNavigation {
+    pub root: String,
+    pub routes: Vec<String>,
+    pub generated_code: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LayerIntegration {
     pub integration_points: Vec<String>,
@@ -256,9 +434,41 @@ pub struct BuildConfigurationSpec {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestSuite {
     pub total_tests: usize,
+    /// `Suite/testName()` identifiers
     pub test_cases: Vec<String>,
+    /// Line coverage in percent
     pub estimated_coverage: f64,
     pub test_categories: Vec<String>,
+    /// XCTest sources, written to a test target of the generated package
+    #[serde(default)]
+    pub test_files: Vec<GeneratedTestFile>,
+    /// Whether the tests and coverage above come from running the suite
+    /// rather than from the generator
+    #[serde(default)]
+    pub measured: bool,
+}
+
+impl TestSuite {
+    /// Replace the estimates with what running the suite found; a run
+    /// without coverage keeps the estimated coverage
+    pub fn record_run(&mut self, run: &crate::test_execution::TestRun) {
+        if run.tests.is_empty() {
+            return;
+        }
+        self.total_tests = run.tests.len();
+        self.test_cases = run.tests.iter().map(|test| test.identifier.clone()).collect();
+        if let Some(coverage) = run.coverage {
+            self.estimated_coverage = coverage;
+        }
+        self.measured = true;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedTestFile {
+    /// File name within the test target, such as `TaskManagerContextTests.swift`
+    pub name: String,
+    pub generated_code: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -286,6 +496,15 @@ pub struct ValidationResult {
     pub performance_score: f64,
     pub issues: Vec<String>,
     pub recommendations: Vec<String>,
+    /// Structural and compiler errors; any of these fail validation
+    #[serde(default)]
+    pub syntax_errors: Vec<crate::swift_validation::CodeFinding>,
+    /// Departures from Axiom patterns, which do not fail validation
+    #[serde(default)]
+    pub pattern_warnings: Vec<crate::swift_validation::CodeFinding>,
+    /// Whether `swiftc` parsed the code beyond the structural checks
+    #[serde(default)]
+    pub syntax_checked: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -295,6 +514,44 @@ pub struct CompleteLoopResult {
     pub implementation: Implementation,
     pub validation_result: ValidationResult,
     pub optimization_suggestions: Vec<PerformanceBottleneck>,
+    /// Install and launch on a simulator, when the loop is configured to run the app
+    #[serde(default)]
+    pub app_run: Option<crate::complete_development_loop::AppRunStage>,
+    /// Grades of the iteration's performance against the thresholds
+    #[serde(default)]
+    pub performance_verdict: Option<crate::performance_analysis_integration::PerformanceVerdict>,
+    /// How this cycle's metrics compare with earlier cycles, when history is kept
+    #[serde(default)]
+    pub performance_trends: Option<crate::performance_analysis_integration::PerformanceTrends>,
+    /// Checkpoint id the cycle can be resumed under
+    #[serde(default)]
+    pub cycle_id: String,
+    /// Stages taken from the checkpoint instead of run again
+    #[serde(default)]
+    pub reused_stages: Vec<crate::complete_development_loop::CycleStage>,
+    /// UI pattern and accessibility review of the build; `None` while
+    /// visual analysis is disabled
+    #[serde(default)]
+    pub visual_validation: Option<crate::complete_development_loop::VisualValidationStage>,
+    /// Checks left out because the capability each needs is disabled
+    #[serde(default)]
+    pub skipped_validations: Vec<crate::complete_development_loop::SkippedValidation>,
+    /// Each implementation generated, with its diff against the one before
+    #[serde(default)]
+    pub iterations: Vec<crate::complete_development_loop::IterationRecord>,
+    /// Why the loop stopped regenerating
+    #[serde(default)]
+    pub termination: Option<crate::complete_development_loop::LoopTermination>,
+    /// The build of the generated project, when the loop compiles them
+    #[serde(default)]
+    pub compilation: Option<crate::project_build::ProjectBuildResult>,
+    /// The generated tests as they ran on a simulator, when the loop runs them
+    #[serde(default)]
+    pub test_execution: Option<crate::test_execution::TestRun>,
+    /// Recording and logs of the app while a failed check of the last
+    /// iteration ran, when the loop captures them
+    #[serde(default)]
+    pub failure_evidence: Option<crate::simulator_capture::FailureEvidence>,
 }
 
 // Default implementations for testing
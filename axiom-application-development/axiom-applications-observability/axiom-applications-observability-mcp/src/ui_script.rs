@@ -0,0 +1,630 @@
+use crate::advanced_visual_intelligence::{AccessibilityElement, ElementType};
+use crate::error::{AxiomMCPError, Result};
+use crate::intelligence::IntelligenceClient;
+use crate::screenshot_store::{ScreenshotStore, StoredImage};
+use crate::simulator::{InputStrategy, SimulatorController, SimulatorLocale, TextEntry};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How long `wait` looks for its element unless the step says otherwise
+pub const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often `wait` reads the element tree again
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the app has to perform a tap, text entry or swipe
+const ACTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long the app has to send its element tree
+const ELEMENT_TREE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeDirection {
+    pub const ALL: [SwipeDirection; 4] = [SwipeDirection::Up, SwipeDirection::Down, SwipeDirection::Left, SwipeDirection::Right];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SwipeDirection::Up => "up",
+            SwipeDirection::Down => "down",
+            SwipeDirection::Left => "left",
+            SwipeDirection::Right => "right",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|direction| direction.name() == name)
+    }
+}
+
+/// Something the app does when told to, addressing elements by accessibility identifier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UiAction {
+    Tap { element: String },
+    /// Type into `element`, or into whichever field has focus
+    TypeText {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        element: Option<String>,
+    },
+    /// Swipe across `element`, or across the middle of the screen
+    Swipe {
+        direction: SwipeDirection,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        element: Option<String>,
+    },
+    /// Paste the pasteboard into `element`, or into whichever field has focus
+    Paste {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        element: Option<String>,
+    },
+}
+
+/// One line of a UI script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UiStep {
+    Perform(UiAction),
+    /// Wait until a visible element with the identifier is on screen
+    WaitFor { element: String, timeout: Duration },
+    /// Switch the device's locale and keyboard, which decide how text is
+    /// entered from then on
+    SetLocale(SimulatorLocale),
+    /// Enter text through the pasteboard whatever the keyboard
+    Paste {
+        text: String,
+        element: Option<String>,
+    },
+}
+
+impl std::fmt::Display for UiStep {
+    /// The step as a script would write it
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UiStep::Perform(UiAction::Tap { element }) => write!(f, "tap {}", quote(element)),
+            UiStep::Perform(UiAction::TypeText { text, element }) => {
+                write!(f, "type {}", quote_always(text))?;
+                match element {
+                    Some(element) => write!(f, " into {}", quote(element)),
+                    None => Ok(()),
+                }
+            }
+            UiStep::Perform(UiAction::Swipe { direction, element }) => {
+                write!(f, "swipe {}", direction.name())?;
+                match element {
+                    Some(element) => write!(f, " on {}", quote(element)),
+                    None => Ok(()),
+                }
+            }
+            // Not something scripts write; their `paste` names the text
+            UiStep::Perform(UiAction::Paste { element }) => match element {
+                Some(element) => write!(f, "paste the pasteboard into {}", quote(element)),
+                None => f.write_str("paste the pasteboard"),
+            },
+            UiStep::WaitFor { element, timeout } => write!(f, "wait {} {}ms", quote(element), timeout.as_millis()),
+            UiStep::Paste { text, element } => {
+                write!(f, "paste {}", quote_always(text))?;
+                match element {
+                    Some(element) => write!(f, " into {}", quote(element)),
+                    None => Ok(()),
+                }
+            }
+            UiStep::SetLocale(locale) => {
+                write!(f, "setLocale {}", locale.identifier)?;
+                match &locale.keyboard {
+                    Some(keyboard) => write!(f, " keyboard {}", quote(keyboard)),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// A step with the line of the script it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptStep {
+    pub line: usize,
+    pub step: UiStep,
+}
+
+/// A sequence of steps that walks the app through a flow
+///
+/// One step per line; blank lines and lines starting with `#` are skipped.
+/// Elements are accessibility identifiers, quoted when they contain spaces:
+///
+/// ```text
+/// tap addTaskButton
+/// type "Buy milk" into titleField
+/// swipe left on taskRow_0
+/// wait taskDetail 3s
+/// setLocale ja_JP keyboard "ja_JP-Kana@sw=Kana;hw=Automatic"
+/// paste "牛乳を買う 🥛" into titleField
+/// ```
+///
+/// `type` presses keys when the text is plain ASCII and the keyboard a US
+/// one, and otherwise goes through the pasteboard, as `paste` always does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UiScript {
+    pub steps: Vec<ScriptStep>,
+}
+
+impl UiScript {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut steps = Vec::new();
+        for (index, text) in source.lines().enumerate() {
+            let line = index + 1;
+            let text = text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let step = parse_step(text).map_err(|message| {
+                AxiomMCPError::validation("ui_script", "parse script", format!("Line {}: {}", line, message))
+            })?;
+            steps.push(ScriptStep { line, step });
+        }
+        if steps.is_empty() {
+            return Err(AxiomMCPError::validation("ui_script", "parse script", "The script has no steps"));
+        }
+        Ok(Self { steps })
+    }
+}
+
+fn parse_step(text: &str) -> std::result::Result<UiStep, String> {
+    let tokens = tokenize(text)?;
+    let mut tokens = tokens.iter().map(String::as_str);
+    let keyword = tokens.next().unwrap_or_default();
+    let step = match keyword {
+        "tap" => UiStep::Perform(UiAction::Tap { element: element(&mut tokens, "tap")? }),
+        "type" => {
+            let text = tokens.next().ok_or("type needs the text to enter")?.to_string();
+            UiStep::Perform(UiAction::TypeText { text, element: target(&mut tokens, "into")? })
+        }
+        "swipe" => {
+            let name = tokens.next().ok_or("swipe needs a direction: up, down, left or right")?;
+            let direction = SwipeDirection::parse(name)
+                .ok_or_else(|| format!("Unknown swipe direction {}; expected up, down, left or right", name))?;
+            UiStep::Perform(UiAction::Swipe { direction, element: target(&mut tokens, "on")? })
+        }
+        "paste" => {
+            let text = tokens.next().ok_or("paste needs the text to enter")?.to_string();
+            UiStep::Paste { text, element: target(&mut tokens, "into")? }
+        }
+        "setLocale" => {
+            let identifier = tokens.next().ok_or("setLocale needs a locale such as de_DE")?;
+            let locale = SimulatorLocale::parse(identifier)
+                .map_err(|_| format!("Invalid locale {}; write it like de_DE or ja_JP", identifier))?;
+            match tokens.next() {
+                None => UiStep::SetLocale(locale),
+                Some("keyboard") => match tokens.next() {
+                    Some(keyboard) if !keyboard.is_empty() => UiStep::SetLocale(locale.with_keyboard(keyboard)),
+                    _ => return Err("keyboard needs a keyboard identifier such as de_DE@sw=QWERTZ".to_string()),
+                },
+                Some(word) => return Err(format!("Expected keyboard after the locale, found {}", word)),
+            }
+        }
+        "wait" => {
+            let element = element(&mut tokens, "wait")?;
+            let timeout = match tokens.next() {
+                Some(timeout) => parse_timeout(timeout)?,
+                None => DEFAULT_WAIT_TIMEOUT,
+            };
+            UiStep::WaitFor { element, timeout }
+        }
+        other => return Err(format!("Unknown step {}; expected tap, type, paste, swipe, wait or setLocale", other)),
+    };
+    match tokens.next() {
+        Some(extra) => Err(format!("Unexpected {} after `{}`", extra, step)),
+        None => Ok(step),
+    }
+}
+
+fn element<'a>(tokens: &mut impl Iterator<Item = &'a str>, keyword: &str) -> std::result::Result<String, String> {
+    match tokens.next() {
+        Some(element) if !element.is_empty() => Ok(element.to_string()),
+        _ => Err(format!("{} needs an element's accessibility identifier", keyword)),
+    }
+}
+
+/// The element after `preposition`, when the step names one
+fn target<'a>(tokens: &mut impl Iterator<Item = &'a str>, preposition: &str) -> std::result::Result<Option<String>, String> {
+    match tokens.next() {
+        None => Ok(None),
+        Some(word) if word == preposition => element(tokens, preposition).map(Some),
+        Some(word) => Err(format!("Expected {} before the element, found {}", preposition, word)),
+    }
+}
+
+/// `500ms`, `3s` or `1.5s`
+fn parse_timeout(text: &str) -> std::result::Result<Duration, String> {
+    let invalid = || format!("Invalid timeout {}; write it like 500ms or 3s", text);
+    if let Some(millis) = text.strip_suffix("ms") {
+        return millis.parse().map(Duration::from_millis).map_err(|_| invalid());
+    }
+    let seconds: f64 = text.strip_suffix('s').ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Words separated by whitespace, or in double quotes with `\"` and `\\` escaped
+fn tokenize(text: &str) -> std::result::Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&next) = chars.peek() {
+        if next.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut token = String::new();
+        if next == '"' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('n') => token.push('\n'),
+                        Some(escaped @ ('"' | '\\')) => token.push(escaped),
+                        Some(other) => return Err(format!("Unknown escape \\{} in a quoted string", other)),
+                        None => return Err("Unterminated quoted string".to_string()),
+                    },
+                    Some(c) => token.push(c),
+                    None => return Err("Unterminated quoted string".to_string()),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                if c == '"' {
+                    return Err(format!("Unexpected quote in {}", token));
+                }
+                token.push(c);
+                chars.next();
+            }
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn quote(text: &str) -> String {
+    if !text.is_empty() && !text.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        text.to_string()
+    } else {
+        quote_always(text)
+    }
+}
+
+fn quote_always(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Drives the app on a simulator
+#[async_trait]
+pub trait UiDriver: Send + Sync {
+    async fn perform(&self, udid: &str, action: &UiAction) -> Result<()>;
+
+    /// The app's current accessibility element tree
+    async fn element_tree(&self, udid: &str) -> Result<AccessibilityElement>;
+
+    /// A PNG of the screen
+    async fn screenshot(&self, udid: &str) -> Result<Vec<u8>>;
+
+    async fn set_locale(&self, udid: &str, locale: &SimulatorLocale) -> Result<()>;
+
+    /// Put `text` on the device's pasteboard for a paste to enter
+    async fn set_pasteboard(&self, udid: &str, text: &str) -> Result<()>;
+}
+
+/// Performs actions through the app's intelligence connection, and takes
+/// screenshots with `simctl`
+pub struct IntelligenceUiDriver {
+    intelligence: Arc<IntelligenceClient>,
+    simulator: Arc<SimulatorController>,
+}
+
+impl IntelligenceUiDriver {
+    pub fn new(intelligence: Arc<IntelligenceClient>, simulator: Arc<SimulatorController>) -> Self {
+        Self { intelligence, simulator }
+    }
+}
+
+#[async_trait]
+impl UiDriver for IntelligenceUiDriver {
+    async fn perform(&self, _udid: &str, action: &UiAction) -> Result<()> {
+        self.intelligence.perform_ui_action(action, ACTION_TIMEOUT).await
+    }
+
+    async fn element_tree(&self, _udid: &str) -> Result<AccessibilityElement> {
+        self.intelligence.accessibility_tree(ELEMENT_TREE_TIMEOUT).await
+    }
+
+    async fn screenshot(&self, udid: &str) -> Result<Vec<u8>> {
+        self.simulator.screenshot(udid).await
+    }
+
+    async fn set_locale(&self, udid: &str, locale: &SimulatorLocale) -> Result<()> {
+        self.simulator.set_locale(udid, locale).await
+    }
+
+    async fn set_pasteboard(&self, udid: &str, text: &str) -> Result<()> {
+        self.simulator.set_pasteboard(udid, text).await
+    }
+}
+
+/// Arguments of `run_ui_script`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunUiScriptRequest {
+    /// Simulator name or UDID
+    pub device: String,
+    pub script: String,
+    /// Locale to set the device to before the script runs, such as `de_DE`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletedStep {
+    pub line: usize,
+    pub step: String,
+    pub duration_ms: u64,
+    /// How the text of a `type` or `paste` step was entered, or why it was not
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_entry: Option<TextEntry>,
+}
+
+/// The step a script stopped at, with what the screen looked like then
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiStepFailure {
+    /// Position of the step in the script, from 0
+    pub index: usize,
+    pub line: usize,
+    pub step: String,
+    pub error: String,
+    /// `None` when the screen could not be captured or there is no store to keep it
+    pub screenshot: Option<StoredImage>,
+    /// `None` when the app did not send it
+    pub element_tree: Option<AccessibilityElement>,
+}
+
+impl std::fmt::Display for UiStepFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "step {} (line {}, `{}`) failed: {}", self.index + 1, self.line, self.step, self.error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiScriptRun {
+    pub udid: String,
+    pub total_steps: usize,
+    /// The steps that succeeded, in order
+    pub completed: Vec<CompletedStep>,
+    pub failure: Option<UiStepFailure>,
+    pub duration_ms: u64,
+}
+
+impl UiScriptRun {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// Secure fields the script's text was left out of
+    pub fn secure_fields(&self) -> Vec<&str> {
+        self.completed
+            .iter()
+            .filter_map(|step| match &step.text_entry {
+                Some(TextEntry::SecureField { element }) => Some(element.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Runs UI scripts step by step, stopping at the first step that fails
+pub struct UiScriptRunner {
+    driver: Arc<dyn UiDriver>,
+    store: Option<Arc<ScreenshotStore>>,
+    poll_interval: Duration,
+    locale: Option<SimulatorLocale>,
+}
+
+impl UiScriptRunner {
+    pub fn new(driver: Arc<dyn UiDriver>) -> Self {
+        Self { driver, store: None, poll_interval: DEFAULT_POLL_INTERVAL, locale: None }
+    }
+
+    /// Keep the screenshot taken when a step fails in `store`
+    pub fn with_store(mut self, store: Arc<ScreenshotStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// The locale devices are set to when a run starts, which decides how
+    /// text is entered until a `setLocale` step changes it; a US keyboard
+    /// when unset
+    pub fn with_locale(mut self, locale: SimulatorLocale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    pub async fn run(&self, udid: &str, script: &UiScript) -> UiScriptRun {
+        let started = Instant::now();
+        let mut completed = Vec::new();
+        let mut failure = None;
+        let mut locale = self.locale.clone();
+        for (index, ScriptStep { line, step }) in script.steps.iter().enumerate() {
+            let step_started = Instant::now();
+            let text_entry = match self.run_step(udid, step, &mut locale).await {
+                Ok(text_entry) => text_entry,
+                Err(e) => {
+                    tracing::warn!("UI script step {} on {} failed: {}", step, udid, e);
+                    failure = Some(self.failure(udid, index, *line, step, e).await);
+                    break;
+                }
+            };
+            completed.push(CompletedStep {
+                line: *line,
+                step: step.to_string(),
+                duration_ms: step_started.elapsed().as_millis() as u64,
+                text_entry,
+            });
+        }
+        UiScriptRun {
+            udid: udid.to_string(),
+            total_steps: script.steps.len(),
+            completed,
+            failure,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+
+    async fn run_step(&self, udid: &str, step: &UiStep, locale: &mut Option<SimulatorLocale>) -> Result<Option<TextEntry>> {
+        match step {
+            UiStep::Perform(UiAction::TypeText { text, element }) => {
+                let strategy = InputStrategy::select(text, locale.as_ref());
+                self.enter_text(udid, text, element, strategy).await.map(Some)
+            }
+            UiStep::Perform(action) => self.driver.perform(udid, action).await.map(|_| None),
+            UiStep::Paste { text, element } => self.enter_text(udid, text, element, InputStrategy::Pasteboard).await.map(Some),
+            UiStep::WaitFor { element, timeout } => self.wait_for(udid, element, *timeout).await.map(|_| None),
+            UiStep::SetLocale(new) => {
+                self.driver.set_locale(udid, new).await?;
+                *locale = Some(new.clone());
+                Ok(None)
+            }
+        }
+    }
+
+    /// Type or paste `text`, pasting it after all when typing fails; text
+    /// for a secure field is left out and reported
+    async fn enter_text(&self, udid: &str, text: &str, element: &Option<String>, strategy: InputStrategy) -> Result<TextEntry> {
+        if let Some(element) = element {
+            if self.is_secure_field(udid, element).await {
+                tracing::warn!("Not entering text into the secure field {} on {}", element, udid);
+                return Ok(TextEntry::SecureField { element: element.clone() });
+            }
+        }
+        if strategy == InputStrategy::Pasteboard {
+            self.paste(udid, text, element).await?;
+            return Ok(TextEntry::Pasted { after_typing_failed: false });
+        }
+        let typing = match self.driver.perform(udid, &UiAction::TypeText { text: text.to_string(), element: element.clone() }).await {
+            Ok(()) => return Ok(TextEntry::Typed),
+            Err(e) => e,
+        };
+        tracing::debug!("Typing on {} failed, pasting instead: {}", udid, typing);
+        self.paste(udid, text, element).await.map_err(|e| {
+            AxiomMCPError::validation("ui_script", "enter text", format!("Typing failed ({}) and so did pasting: {}", typing, e))
+        })?;
+        Ok(TextEntry::Pasted { after_typing_failed: true })
+    }
+
+    async fn paste(&self, udid: &str, text: &str, element: &Option<String>) -> Result<()> {
+        self.driver.set_pasteboard(udid, text).await?;
+        self.driver.perform(udid, &UiAction::Paste { element: element.clone() }).await
+    }
+
+    /// Whether `element` is on screen as a secure text field; an unreadable
+    /// tree is left to the action itself to fail on
+    async fn is_secure_field(&self, udid: &str, element: &str) -> bool {
+        match self.driver.element_tree(udid).await {
+            Ok(tree) => find_element(&tree, element).is_some_and(|found| found.element_type == ElementType::SecureTextField),
+            Err(_) => false,
+        }
+    }
+
+    /// Read the element tree until it has a visible `element`; reading it
+    /// can fail while the app changes screens, so failures only count once
+    /// time is up
+    async fn wait_for(&self, udid: &str, element: &str, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let last_error = match self.driver.element_tree(udid).await {
+                Ok(tree) if find_element(&tree, element).is_some() => return Ok(()),
+                Ok(_) => None,
+                Err(e) => Some(e),
+            };
+            if Instant::now() >= deadline {
+                let reason = match last_error {
+                    Some(e) => format!("; the element tree could not be read: {}", e),
+                    None => String::new(),
+                };
+                return Err(AxiomMCPError::validation("ui_script", "wait for element", format!(
+                    "{} did not appear within {:?}{}", element, timeout, reason
+                )));
+            }
+            tokio::time::sleep(self.poll_interval.min(deadline.saturating_duration_since(Instant::now()))).await;
+        }
+    }
+
+    async fn failure(&self, udid: &str, index: usize, line: usize, step: &UiStep, error: AxiomMCPError) -> UiStepFailure {
+        let screenshot = match &self.store {
+            Some(store) => match self.keep_screenshot(udid, store).await {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    tracing::warn!("Could not keep the screen of the failed step: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let element_tree = match self.driver.element_tree(udid).await {
+            Ok(tree) => Some(tree),
+            Err(e) => {
+                tracing::warn!("Could not read the element tree of the failed step: {}", e);
+                None
+            }
+        };
+        UiStepFailure {
+            index,
+            line,
+            step: step.to_string(),
+            error: error.to_string(),
+            screenshot,
+            element_tree,
+        }
+    }
+
+    async fn keep_screenshot(&self, udid: &str, store: &ScreenshotStore) -> Result<StoredImage> {
+        let png = self.driver.screenshot(udid).await?;
+        store.put(&png).await
+    }
+}
+
+impl std::fmt::Debug for UiScriptRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UiScriptRunner")
+            .field("store", &self.store)
+            .field("poll_interval", &self.poll_interval)
+            .field("locale", &self.locale)
+            .finish()
+    }
+}
+
+/// The first element with `identifier` that is not hidden, searching depth first
+pub fn find_element<'a>(root: &'a AccessibilityElement, identifier: &str) -> Option<&'a AccessibilityElement> {
+    if root.hidden {
+        return None;
+    }
+    if root.identifier == identifier {
+        return Some(root);
+    }
+    root.children.iter().find_map(|child| find_element(child, identifier))
+}
@@ -0,0 +1,621 @@
+use crate::error::{AxiomMCPError, Result};
+use crate::screenshot_store::ScreenshotStore;
+use crate::types::{RegressionReport, Screenshot, ScreenshotConfiguration};
+use chrono::{DateTime, Utc};
+use image::{ImageFormat, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+const DIFFS_DIR: &str = "diffs";
+
+const MANIFEST_VERSION: u32 = 1;
+
+/// Changed pixels closer than this are reported as one region
+const REGION_MERGE_DISTANCE: u32 = 8;
+
+const CHANGED_COLOR: Rgba<u8> = Rgba([255, 0, 64, 255]);
+
+const IGNORED_COLOR: Rgba<u8> = Rgba([64, 96, 255, 255]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegressionMode {
+    /// Replace the baselines with the given screenshots
+    Record,
+    /// Compare the given screenshots against the baselines
+    Check,
+}
+
+/// Part of a screen left out of the comparison, in points from the top left
+///
+/// Scaled by each screenshot's scale factor, so one region covers the same
+/// content on every device, such as the status bar clock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IgnoreRegion {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Covers the status bar, so it is compared again in captures whose
+    /// status bar was overridden
+    #[serde(default)]
+    pub status_bar: bool,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl IgnoreRegion {
+    fn to_pixels(&self, scale: f64, width: u32, height: u32) -> Option<PixelRect> {
+        let left = (self.x * scale).floor().max(0.0) as u32;
+        let top = (self.y * scale).floor().max(0.0) as u32;
+        let right = (((self.x + self.width) * scale).ceil().max(0.0) as u32).min(width);
+        let bottom = (((self.y + self.height) * scale).ceil().max(0.0) as u32).min(height);
+        (right > left && bottom > top).then(|| PixelRect {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenRegressionSettings {
+    /// Overrides the default threshold for this screen
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    #[serde(default)]
+    pub ignore_regions: Vec<IgnoreRegion>,
+}
+
+/// Where baselines live and how much change a screen may show
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VisualRegressionConfig {
+    pub baseline_dir: PathBuf,
+    /// Fraction of compared pixels allowed to change before a screen regresses
+    pub threshold: f64,
+    /// Largest per-channel difference still counted as the same pixel,
+    /// which absorbs anti-aliasing and compression noise
+    pub pixel_tolerance: u8,
+    /// Settings by screen name
+    pub screens: BTreeMap<String, ScreenRegressionSettings>,
+}
+
+impl Default for VisualRegressionConfig {
+    fn default() -> Self {
+        Self {
+            baseline_dir: PathBuf::from(".axiom/baselines"),
+            threshold: 0.001,
+            pixel_tolerance: 8,
+            screens: BTreeMap::new(),
+        }
+    }
+}
+
+impl VisualRegressionConfig {
+    pub fn threshold_for(&self, screen: &str) -> f64 {
+        self.screens.get(screen).and_then(|settings| settings.threshold).unwrap_or(self.threshold)
+    }
+
+    pub fn ignore_regions_for(&self, screen: &str) -> &[IgnoreRegion] {
+        self.screens.get(screen).map(|settings| settings.ignore_regions.as_slice()).unwrap_or(&[])
+    }
+}
+
+/// Screenshots of one screen to record as baselines or check against them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionCheckRequest {
+    pub mode: RegressionMode,
+    pub screen: String,
+    pub screenshots: Vec<Screenshot>,
+}
+
+/// Identifies a baseline by screen and configuration axes
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineKey {
+    pub screen: String,
+    pub device_type: String,
+    pub orientation: String,
+    pub color_scheme: String,
+}
+
+impl BaselineKey {
+    pub fn new(screen: &str, configuration: &ScreenshotConfiguration) -> Self {
+        Self {
+            screen: screen.to_string(),
+            device_type: configuration.device_type.clone(),
+            orientation: configuration.orientation.clone(),
+            color_scheme: configuration.color_scheme.clone(),
+        }
+    }
+
+    /// `screen/device/orientation-color_scheme`, also the baseline's path
+    /// under the store without the extension
+    pub fn id(&self) -> String {
+        format!(
+            "{}/{}/{}-{}",
+            slug(&self.screen),
+            slug(&self.device_type),
+            slug(&self.orientation),
+            slug(&self.color_scheme)
+        )
+    }
+}
+
+/// Lowercase letters, digits and dashes, so any axis value makes a safe path segment
+pub(crate) fn slug(value: &str) -> String {
+    let mut slug = String::new();
+    for c in value.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "default".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub key: BaselineKey,
+    /// Relative to the store root
+    pub path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Index of every recorded baseline, by key id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineManifest {
+    pub version: u32,
+    pub baselines: BTreeMap<String, BaselineEntry>,
+}
+
+impl Default for BaselineManifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            baselines: BTreeMap::new(),
+        }
+    }
+}
+
+/// Baseline PNGs on disk under `screen/device/orientation-color_scheme.png`,
+/// with a `manifest.json` at the root and diff images under `diffs/`
+#[derive(Debug, Clone)]
+pub struct BaselineStore {
+    root: PathBuf,
+}
+
+impl BaselineStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The manifest, empty when nothing has been recorded yet
+    pub async fn manifest(&self) -> Result<BaselineManifest> {
+        match tokio::fs::read_to_string(self.root.join(MANIFEST_FILE)).await {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BaselineManifest::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn baseline_path(&self, key: &BaselineKey) -> PathBuf {
+        self.root.join(format!("{}.png", key.id()))
+    }
+
+    pub fn diff_path(&self, key: &BaselineKey) -> PathBuf {
+        self.root.join(DIFFS_DIR).join(format!("{}.diff.png", key.id()))
+    }
+
+    /// The recorded baseline image for `key`, if the manifest lists one
+    pub async fn baseline(&self, key: &BaselineKey) -> Result<Option<RgbaImage>> {
+        let manifest = self.manifest().await?;
+        let Some(entry) = manifest.baselines.get(&key.id()) else {
+            return Ok(None);
+        };
+        let png = tokio::fs::read(self.root.join(&entry.path)).await?;
+        decode_png(&png).map(Some)
+    }
+
+    /// Write `png` as the baseline for `key` and list it in the manifest
+    pub async fn record(&self, key: &BaselineKey, png: &[u8]) -> Result<BaselineEntry> {
+        let image = decode_png(png)?;
+        let path = self.baseline_path(key);
+        write_file(&path, png).await?;
+
+        let entry = BaselineEntry {
+            key: key.clone(),
+            path: PathBuf::from(format!("{}.png", key.id())),
+            width: image.width(),
+            height: image.height(),
+            recorded_at: Utc::now(),
+        };
+        let mut manifest = self.manifest().await?;
+        manifest.baselines.insert(key.id(), entry.clone());
+        write_file(&self.root.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?.as_bytes()).await?;
+        Ok(entry)
+    }
+
+    async fn write_diff(&self, key: &BaselineKey, image: &RgbaImage) -> Result<PathBuf> {
+        let path = self.diff_path(key);
+        write_file(&path, &encode_png(image)?).await?;
+        Ok(path)
+    }
+}
+
+async fn write_file(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, contents).await?;
+    Ok(())
+}
+
+pub fn decode_png(png: &[u8]) -> Result<RgbaImage> {
+    image::load_from_memory_with_format(png, ImageFormat::Png)
+        .map(|image| image.to_rgba8())
+        .map_err(|e| AxiomMCPError::validation("visual_regression", "decode PNG", format!("Screenshot is not a readable PNG: {}", e)))
+}
+
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| AxiomMCPError::internal("visual_regression", "encode PNG", format!("Could not encode PNG: {}", e)))?;
+    Ok(png)
+}
+
+/// Rectangle in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PixelRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PixelRect {
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Whether the two are within `distance` pixels of each other
+    fn near(&self, other: &PixelRect, distance: u32) -> bool {
+        self.x <= other.x + other.width + distance
+            && other.x <= self.x + self.width + distance
+            && self.y <= other.y + other.height + distance
+            && other.y <= self.y + self.height + distance
+    }
+
+    fn union(&self, other: &PixelRect) -> PixelRect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        PixelRect {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+}
+
+/// Pixel comparison of two same-sized images
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    /// Pixels outside the ignore regions
+    pub compared_pixels: u64,
+    pub changed_pixels: u64,
+    /// `changed_pixels / compared_pixels`
+    pub diff_ratio: f64,
+    /// Bounding boxes of changed areas, top to bottom
+    pub changed_regions: Vec<PixelRect>,
+    /// The current image faded, with changes and ignored areas painted over
+    pub diff_image: RgbaImage,
+}
+
+/// Compare `current` against `baseline`, which must be the same size
+///
+/// A pixel has changed when any channel differs by more than
+/// `pixel_tolerance`. Pixels inside `ignore` are neither compared nor
+/// counted.
+pub fn diff_images(baseline: &RgbaImage, current: &RgbaImage, pixel_tolerance: u8, ignore: &[PixelRect]) -> ImageDiff {
+    debug_assert_eq!(baseline.dimensions(), current.dimensions());
+    let (width, height) = current.dimensions();
+    let mut changed = vec![false; (width as usize) * (height as usize)];
+    let mut diff_image = RgbaImage::new(width, height);
+    let mut compared_pixels = 0;
+    let mut changed_pixels = 0;
+
+    for (x, y, pixel) in current.enumerate_pixels() {
+        if ignore.iter().any(|region| region.contains(x, y)) {
+            diff_image.put_pixel(x, y, IGNORED_COLOR);
+            continue;
+        }
+
+        compared_pixels += 1;
+        let before = baseline.get_pixel(x, y);
+        let differs = pixel.0.iter().zip(before.0.iter()).any(|(a, b)| a.abs_diff(*b) > pixel_tolerance);
+        if differs {
+            changed_pixels += 1;
+            changed[(y * width + x) as usize] = true;
+            diff_image.put_pixel(x, y, CHANGED_COLOR);
+        } else {
+            diff_image.put_pixel(x, y, faded(pixel));
+        }
+    }
+
+    ImageDiff {
+        compared_pixels,
+        changed_pixels,
+        diff_ratio: if compared_pixels == 0 { 0.0 } else { changed_pixels as f64 / compared_pixels as f64 },
+        changed_regions: changed_regions(&changed, width, height),
+        diff_image,
+    }
+}
+
+/// Light grey version of a pixel, so the changes stand out
+fn faded(pixel: &Rgba<u8>) -> Rgba<u8> {
+    let [r, g, b, _] = pixel.0;
+    let luma = (u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114) / 1000;
+    let value = (170 + luma / 3) as u8;
+    Rgba([value, value, value, 255])
+}
+
+/// Bounding boxes of connected changed pixels, with nearby boxes merged
+fn changed_regions(changed: &[bool], width: u32, height: u32) -> Vec<PixelRect> {
+    let mut visited = vec![false; changed.len()];
+    let mut regions = Vec::new();
+
+    for (start, &is_changed) in changed.iter().enumerate() {
+        if !is_changed || visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let (mut left, mut top) = ((start as u32) % width, (start as u32) / width);
+        let (mut right, mut bottom) = (left, top);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(index) = queue.pop_front() {
+            let (x, y) = ((index as u32) % width, (index as u32) / width);
+            left = left.min(x);
+            right = right.max(x);
+            top = top.min(y);
+            bottom = bottom.max(y);
+
+            for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                    let neighbour = (ny * width + nx) as usize;
+                    if changed[neighbour] && !visited[neighbour] {
+                        visited[neighbour] = true;
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        regions.push(PixelRect {
+            x: left,
+            y: top,
+            width: right - left + 1,
+            height: bottom - top + 1,
+        });
+    }
+
+    // Merging can bring a box near one it was not near before, so repeat until stable
+    loop {
+        let mut merged = false;
+        let mut i = 0;
+        while i < regions.len() {
+            let mut j = i + 1;
+            while j < regions.len() {
+                if regions[i].near(&regions[j], REGION_MERGE_DISTANCE) {
+                    let other = regions.remove(j);
+                    regions[i] = regions[i].union(&other);
+                    merged = true;
+                } else {
+                    j += 1;
+                }
+            }
+            i += 1;
+        }
+        if !merged {
+            break;
+        }
+    }
+
+    regions.sort_by_key(|region| (region.y, region.x));
+    regions
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    /// Stored as the new baseline
+    Recorded,
+    Unchanged,
+    /// Changed, but no more than the threshold allows
+    WithinThreshold,
+    Regressed,
+    /// The screenshot is not the size of its baseline
+    SizeChanged,
+    MissingBaseline,
+}
+
+impl DiffStatus {
+    /// Whether this configuration fails a check
+    pub fn is_failure(&self) -> bool {
+        matches!(self, DiffStatus::Regressed | DiffStatus::SizeChanged | DiffStatus::MissingBaseline)
+    }
+}
+
+/// Outcome for one configuration of the screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigurationDiff {
+    pub key: BaselineKey,
+    pub status: DiffStatus,
+    pub diff_ratio: f64,
+    pub threshold: f64,
+    pub changed_regions: Vec<PixelRect>,
+    pub baseline_path: PathBuf,
+    /// Written whenever any pixel changed
+    pub diff_image: Option<PathBuf>,
+    pub summary: String,
+}
+
+/// Records screenshots as baselines and checks new ones against them
+#[derive(Debug, Clone)]
+pub struct VisualRegressionChecker {
+    store: BaselineStore,
+    config: VisualRegressionConfig,
+    /// Where stored screenshots recorded as baselines are kept from pruning
+    screenshots: Option<Arc<ScreenshotStore>>,
+}
+
+impl VisualRegressionChecker {
+    pub fn new(config: VisualRegressionConfig) -> Self {
+        Self {
+            store: BaselineStore::new(config.baseline_dir.clone()),
+            config,
+            screenshots: None,
+        }
+    }
+
+    /// Keep the stored screenshots baselines are recorded from out of
+    /// `screenshots`' pruning
+    pub fn with_screenshot_store(mut self, screenshots: Arc<ScreenshotStore>) -> Self {
+        self.screenshots = Some(screenshots);
+        self
+    }
+
+    pub fn store(&self) -> &BaselineStore {
+        &self.store
+    }
+
+    pub async fn run(&self, request: &RegressionCheckRequest) -> Result<RegressionReport> {
+        if request.screen.trim().is_empty() {
+            return Err(AxiomMCPError::validation("visual_regression", "check regression", "Screen name cannot be empty"));
+        }
+        if request.screenshots.is_empty() {
+            return Err(AxiomMCPError::validation("visual_regression", "check regression", "No screenshots to compare"));
+        }
+
+        let mut configurations = Vec::new();
+        for screenshot in &request.screenshots {
+            let key = BaselineKey::new(&request.screen, &screenshot.configuration);
+            let diff = match request.mode {
+                RegressionMode::Record => self.record(key, screenshot).await?,
+                RegressionMode::Check => self.check(key, screenshot).await?,
+            };
+            configurations.push(diff);
+        }
+
+        let regressions_detected = configurations
+            .iter()
+            .filter(|diff| matches!(diff.status, DiffStatus::Regressed | DiffStatus::SizeChanged))
+            .count();
+        Ok(RegressionReport {
+            total_comparisons: configurations.len(),
+            regressions_detected,
+            // Pixel comparison against a recorded baseline is deterministic
+            false_positive_rate: 0.0,
+            confidence_score: 100.0,
+            mode: Some(request.mode),
+            configurations,
+        })
+    }
+
+    async fn record(&self, key: BaselineKey, screenshot: &Screenshot) -> Result<ConfigurationDiff> {
+        let entry = self.store.record(&key, &screenshot.png_data()?).await?;
+        if let (Some(screenshots), Some(image)) = (&self.screenshots, &screenshot.image) {
+            screenshots.pin_baseline(&key.id(), image).await?;
+        }
+        Ok(ConfigurationDiff {
+            summary: format!("{}: recorded {}x{} baseline", key.id(), entry.width, entry.height),
+            baseline_path: self.store.root().join(&entry.path),
+            threshold: self.config.threshold_for(&key.screen),
+            key,
+            status: DiffStatus::Recorded,
+            diff_ratio: 0.0,
+            changed_regions: Vec::new(),
+            diff_image: None,
+        })
+    }
+
+    async fn check(&self, key: BaselineKey, screenshot: &Screenshot) -> Result<ConfigurationDiff> {
+        let threshold = self.config.threshold_for(&key.screen);
+        let baseline_path = self.store.baseline_path(&key);
+        let mut result = ConfigurationDiff {
+            key: key.clone(),
+            status: DiffStatus::MissingBaseline,
+            diff_ratio: 0.0,
+            threshold,
+            changed_regions: Vec::new(),
+            baseline_path,
+            diff_image: None,
+            summary: String::new(),
+        };
+
+        let Some(baseline) = self.store.baseline(&key).await? else {
+            result.summary = format!("{}: no baseline; run in record mode first", key.id());
+            return Ok(result);
+        };
+        let current = decode_png(&screenshot.png_data()?)?;
+        if baseline.dimensions() != current.dimensions() {
+            result.status = DiffStatus::SizeChanged;
+            result.diff_ratio = 1.0;
+            result.summary = format!(
+                "{}: {}x{} screenshot against a {}x{} baseline",
+                key.id(), current.width(), current.height(), baseline.width(), baseline.height()
+            );
+            return Ok(result);
+        }
+
+        let (width, height) = current.dimensions();
+        let ignore: Vec<PixelRect> = self.config
+            .ignore_regions_for(&key.screen)
+            .iter()
+            .filter(|region| !(region.status_bar && screenshot.metadata.overrides.status_bar.is_some()))
+            .filter_map(|region| region.to_pixels(screenshot.configuration.scale, width, height))
+            .collect();
+        let diff = diff_images(&baseline, &current, self.config.pixel_tolerance, &ignore);
+
+        result.diff_ratio = diff.diff_ratio;
+        result.status = if diff.changed_pixels == 0 {
+            DiffStatus::Unchanged
+        } else if diff.diff_ratio > threshold {
+            DiffStatus::Regressed
+        } else {
+            DiffStatus::WithinThreshold
+        };
+        if diff.changed_pixels > 0 {
+            result.diff_image = Some(self.store.write_diff(&key, &diff.diff_image).await?);
+        }
+        result.summary = format!(
+            "{}: {:.3}% of pixels changed in {} region(s) (threshold {:.3}%)",
+            key.id(),
+            diff.diff_ratio * 100.0,
+            diff.changed_regions.len(),
+            threshold * 100.0
+        );
+        result.changed_regions = diff.changed_regions;
+        Ok(result)
+    }
+}
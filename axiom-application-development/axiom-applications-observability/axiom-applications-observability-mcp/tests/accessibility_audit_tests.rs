@@ -0,0 +1,293 @@
+use axiom_applications_observability::advanced_visual_intelligence::*;
+use axiom_applications_observability::types::*;
+use axiom_applications_observability::visual_regression::{encode_png, PixelRect};
+use image::{Rgba, RgbaImage};
+
+const WHITE: [u8; 3] = [255, 255, 255];
+const BLACK: [u8; 3] = [0, 0, 0];
+/// The lightest gray that fails AA body text on white
+const GRAY_77: [u8; 3] = [0x77, 0x77, 0x77];
+/// The lightest gray that passes AA body text on white
+const GRAY_76: [u8; 3] = [0x76, 0x76, 0x76];
+
+/// Element tree as the intelligence server sends it
+const TASK_LIST_TREE: &str = r#"{
+    "identifier": "TaskListView",
+    "frame": { "x": 0, "y": 0, "width": 100, "height": 200 },
+    "children": [
+        {
+            "identifier": "addTaskButton",
+            "elementType": "button",
+            "frame": { "x": 60, "y": 0, "width": 24, "height": 24 }
+        },
+        {
+            "identifier": "title",
+            "elementType": "staticText",
+            "label": "Tasks",
+            "frame": { "x": 0, "y": 40, "width": 100, "height": 20 }
+        },
+        {
+            "identifier": "emptyStateIllustration",
+            "elementType": "image",
+            "frame": { "x": 10, "y": 80, "width": 80, "height": 80 }
+        },
+        {
+            "identifier": "decoration",
+            "elementType": "image",
+            "hidden": true,
+            "frame": { "x": 0, "y": 180, "width": 20, "height": 20 },
+            "children": [
+                { "identifier": "hiddenButton", "elementType": "button", "frame": { "x": 0, "y": 180, "width": 10, "height": 10 } }
+            ]
+        },
+        {
+            "identifier": "doneToggle",
+            "elementType": "toggle",
+            "label": "Done",
+            "traits": ["button"],
+            "frame": { "x": 0, "y": 160, "width": 51, "height": 44 }
+        },
+        {
+            "identifier": "futureControl",
+            "elementType": "stepper",
+            "frame": { "x": 0, "y": 0, "width": 1, "height": 1 }
+        }
+    ]
+}"#;
+
+fn element(identifier: &str, element_type: ElementType, label: Option<&str>, frame: ElementFrame) -> AccessibilityElement {
+    AccessibilityElement {
+        identifier: identifier.to_string(),
+        element_type,
+        label: label.map(str::to_string),
+        traits: Vec::new(),
+        frame,
+        hidden: false,
+        font_size: None,
+        is_bold: false,
+        children: Vec::new(),
+    }
+}
+
+fn frame(x: f64, y: f64, width: f64, height: f64) -> ElementFrame {
+    ElementFrame { x, y, width, height }
+}
+
+/// White 100x100 image with a `text` colored bar covering a fifth of the
+/// top 20 rows, as a text element at 1x would
+fn text_on_white(text: [u8; 3]) -> RgbaImage {
+    let mut image = RgbaImage::from_pixel(100, 100, Rgba([255, 255, 255, 255]));
+    for y in 5..9 {
+        for x in 0..100 {
+            image.put_pixel(x, y, Rgba([text[0], text[1], text[2], 255]));
+        }
+    }
+    image
+}
+
+fn screenshot(image: &RgbaImage) -> Screenshot {
+    Screenshot {
+        id: "screenshot".to_string(),
+        client_id: "test-client".to_string(),
+        configuration: ScreenshotConfiguration {
+            device_type: "iPhone 15".to_string(),
+            screen_size: ScreenSize { width: 100.0, height: 100.0 },
+            orientation: "portrait".to_string(),
+            scale: 1.0,
+            color_scheme: "light".to_string(),
+            capture_mode: "standard".to_string(),
+            status_bar: None,
+            runtime: None,
+            window: Default::default(),
+        },
+        image_data: encode_png(image).unwrap(),
+        image: None,
+        metadata: ScreenshotMetadata {
+            timestamp: chrono::Utc::now(),
+            device_info: DeviceInfo {
+                model: "iPhone 15".to_string(),
+                screen_size: ScreenSize { width: 100.0, height: 100.0 },
+                orientation: "portrait".to_string(),
+                udid: None,
+            },
+            app_state: AppState {
+                view_hierarchy: "TaskListView".to_string(),
+                active_context: "TaskListContext".to_string(),
+                view_tree: None,
+            },
+            overrides: Default::default(),
+        },
+    }
+}
+
+fn rules(findings: &[AccessibilityFinding], element_id: &str) -> Vec<AccessibilityRule> {
+    findings.iter().filter(|f| f.element_id == element_id).map(|f| f.rule).collect()
+}
+
+#[test]
+fn test_relative_luminance_of_reference_colors() {
+    assert_eq!(relative_luminance(BLACK), 0.0);
+    assert!((relative_luminance(WHITE) - 1.0).abs() < 1e-9);
+    // Pure green carries most of the luminance
+    assert!((relative_luminance([0, 255, 0]) - 0.7152).abs() < 1e-9);
+}
+
+#[test]
+fn test_contrast_ratio_matches_wcag_reference_values() {
+    assert!((contrast_ratio(BLACK, WHITE) - 21.0).abs() < 1e-9);
+    assert_eq!(contrast_ratio(WHITE, WHITE), 1.0);
+    // Symmetric in its arguments
+    assert_eq!(contrast_ratio(GRAY_77, WHITE), contrast_ratio(WHITE, GRAY_77));
+    assert!((contrast_ratio(GRAY_77, WHITE) - 4.48).abs() < 0.01);
+    assert!((contrast_ratio(GRAY_76, WHITE) - 4.54).abs() < 0.01);
+}
+
+#[test]
+fn test_required_contrast_boundaries() {
+    assert_eq!(required_contrast(None, false), WCAG_AA_NORMAL_TEXT);
+    assert_eq!(required_contrast(Some(17.9), false), WCAG_AA_NORMAL_TEXT);
+    assert_eq!(required_contrast(Some(18.0), false), WCAG_AA_LARGE_TEXT);
+    assert_eq!(required_contrast(Some(13.9), true), WCAG_AA_NORMAL_TEXT);
+    assert_eq!(required_contrast(Some(14.0), true), WCAG_AA_LARGE_TEXT);
+}
+
+#[test]
+fn test_measure_contrast_finds_text_and_background_colors() {
+    let image = text_on_white(GRAY_77);
+    let sample = measure_contrast(&image, PixelRect { x: 0, y: 0, width: 100, height: 20 }).unwrap();
+
+    assert_eq!(sample.background, WHITE);
+    assert_eq!(sample.foreground, GRAY_77);
+    assert_eq!(sample.ratio, contrast_ratio(GRAY_77, WHITE));
+}
+
+#[test]
+fn test_measure_contrast_ignores_stray_pixels_and_solid_areas() {
+    let mut image = text_on_white(GRAY_77);
+    // A single black pixel is under 1% of the box
+    image.put_pixel(50, 15, Rgba([0, 0, 0, 255]));
+    let sample = measure_contrast(&image, PixelRect { x: 0, y: 0, width: 100, height: 20 }).unwrap();
+    assert_eq!(sample.foreground, GRAY_77);
+
+    assert!(measure_contrast(&image, PixelRect { x: 0, y: 40, width: 100, height: 20 }).is_none());
+}
+
+#[test]
+fn test_low_contrast_body_text_is_an_error() {
+    let image = text_on_white(GRAY_77);
+    let text = element("subtitle", ElementType::StaticText, Some("3 tasks"), frame(0.0, 0.0, 100.0, 20.0));
+
+    let (_, findings) = audit_accessibility(&text, Some(&image), 1.0);
+
+    assert_eq!(rules(&findings, "subtitle"), vec![AccessibilityRule::LowContrast]);
+    assert_eq!(findings[0].severity, FindingSeverity::Error);
+    assert!((findings[0].contrast_ratio.unwrap() - 4.48).abs() < 0.01);
+    assert!(findings[0].suggested_fix.contains("#777777"));
+}
+
+#[test]
+fn test_contrast_just_passing_aa_is_not_flagged() {
+    let image = text_on_white(GRAY_76);
+    let text = element("subtitle", ElementType::StaticText, Some("3 tasks"), frame(0.0, 0.0, 100.0, 20.0));
+
+    let (_, findings) = audit_accessibility(&text, Some(&image), 1.0);
+
+    assert!(findings.is_empty());
+}
+
+#[test]
+fn test_large_text_is_held_to_the_lower_ratio() {
+    let image = text_on_white(GRAY_77);
+    let mut title = element("title", ElementType::StaticText, Some("Tasks"), frame(0.0, 0.0, 100.0, 20.0));
+    title.font_size = Some(18.0);
+
+    let (_, findings) = audit_accessibility(&title, Some(&image), 1.0);
+
+    assert!(findings.is_empty());
+}
+
+#[test]
+fn test_contrast_uses_the_screenshot_scale() {
+    // At 2x, a 50x10pt frame covers the same 100x20 pixels
+    let image = text_on_white(GRAY_77);
+    let text = element("subtitle", ElementType::StaticText, Some("3 tasks"), frame(0.0, 0.0, 50.0, 10.0));
+
+    let (_, findings) = audit_accessibility(&text, Some(&image), 2.0);
+
+    assert_eq!(rules(&findings, "subtitle"), vec![AccessibilityRule::LowContrast]);
+}
+
+#[test]
+fn test_touch_target_boundary_is_44_points() {
+    let at_minimum = element("ok", ElementType::Button, Some("OK"), frame(0.0, 0.0, 44.0, 44.0));
+    let too_short = element("short", ElementType::Button, Some("Short"), frame(0.0, 0.0, 120.0, 43.9));
+    let too_narrow = element("narrow", ElementType::Link, Some("Narrow"), frame(0.0, 0.0, 43.0, 60.0));
+
+    assert!(audit_accessibility(&at_minimum, None, 3.0).1.is_empty());
+    assert_eq!(rules(&audit_accessibility(&too_short, None, 3.0).1, "short"), vec![AccessibilityRule::SmallTouchTarget]);
+    assert_eq!(rules(&audit_accessibility(&too_narrow, None, 3.0).1, "narrow"), vec![AccessibilityRule::SmallTouchTarget]);
+}
+
+#[test]
+fn test_static_text_is_not_a_touch_target() {
+    let caption = element("caption", ElementType::StaticText, Some("Due today"), frame(0.0, 0.0, 60.0, 12.0));
+
+    assert!(audit_accessibility(&caption, None, 3.0).1.is_empty());
+}
+
+#[test]
+fn test_fixture_tree_findings() {
+    let tree: AccessibilityElement = serde_json::from_str(TASK_LIST_TREE).unwrap();
+
+    let (audited, findings) = audit_accessibility(&tree, None, 3.0);
+
+    // The hidden image and the button inside it are skipped
+    assert_eq!(audited, 6);
+    assert_eq!(
+        rules(&findings, "addTaskButton"),
+        vec![AccessibilityRule::MissingLabel, AccessibilityRule::SmallTouchTarget]
+    );
+    assert_eq!(rules(&findings, "emptyStateIllustration"), vec![AccessibilityRule::UnlabeledImage]);
+    assert!(rules(&findings, "hiddenButton").is_empty());
+    assert!(rules(&findings, "doneToggle").is_empty());
+    // Unknown element types parse as other and are not tappable
+    assert!(rules(&findings, "futureControl").is_empty());
+    assert_eq!(tree.children[5].element_type, ElementType::Other);
+}
+
+#[test]
+fn test_button_trait_makes_an_element_tappable() {
+    let mut row = element("row", ElementType::Other, None, frame(0.0, 0.0, 300.0, 60.0));
+    row.traits = vec!["button".to_string()];
+
+    assert_eq!(rules(&audit_accessibility(&row, None, 3.0).1, "row"), vec![AccessibilityRule::MissingLabel]);
+}
+
+#[tokio::test]
+async fn test_audit_report_scores_and_orders_findings() {
+    let tree: AccessibilityElement = serde_json::from_str(TASK_LIST_TREE).unwrap();
+    let engine = VisualIntelligenceEngine::new().await.unwrap();
+
+    let report = engine.audit_accessibility(&screenshot(&RgbaImage::from_pixel(100, 200, Rgba([255, 255, 255, 255]))), &tree).await.unwrap();
+
+    assert_eq!(report.findings.len(), 3);
+    assert_eq!(report.findings[0].severity, FindingSeverity::Error);
+    assert_eq!(report.issues_found.len(), 3);
+    assert_eq!(report.recommendations.len(), 3);
+    // One of six audited elements has an error, two have any finding
+    assert!((report.compliance_score - 500.0 / 6.0).abs() < 1e-9);
+    assert!((report.overall_score - 400.0 / 6.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_audit_without_readable_screenshot_skips_contrast() {
+    let mut capture = screenshot(&text_on_white(GRAY_77));
+    capture.image_data = vec![1, 2, 3, 4];
+    let text = element("subtitle", ElementType::StaticText, Some("3 tasks"), frame(0.0, 0.0, 100.0, 20.0));
+    let engine = VisualIntelligenceEngine::new().await.unwrap();
+
+    let report = engine.audit_accessibility(&capture, &text).await.unwrap();
+
+    assert!(report.findings.is_empty());
+    assert_eq!(report.compliance_score, 100.0);
+}
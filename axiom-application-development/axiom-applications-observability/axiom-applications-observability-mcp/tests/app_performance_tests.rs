@@ -0,0 +1,323 @@
+use async_trait::async_trait;
+use axiom_applications_observability::performance_analysis_integration::*;
+use axiom_applications_observability::simulator::*;
+use axiom_applications_observability::{AxiomMCPError, PerformanceEstimate, Result};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const UDID: &str = "A1B2C3D4-0000-0000-0000-000000000002";
+const BUNDLE_ID: &str = "com.axiom.tasks";
+const COLD_LAUNCH: Duration = Duration::from_millis(30);
+
+/// Scripted simctl with one booted device; a launch that starts the app
+/// takes `COLD_LAUNCH` and gets a new pid, a launch that keeps the running
+/// app returns at once with its pid
+struct FakeSimctl {
+    running: Mutex<Option<u32>>,
+    next_pid: Mutex<u32>,
+    commands: Mutex<Vec<String>>,
+    installed: bool,
+}
+
+impl FakeSimctl {
+    fn new(installed: bool) -> Arc<Self> {
+        Arc::new(Self {
+            running: Mutex::new(None),
+            next_pid: Mutex::new(4000),
+            commands: Mutex::new(Vec::new()),
+            installed,
+        })
+    }
+
+    fn commands(&self) -> Vec<String> {
+        self.commands.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Simctl for FakeSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        self.commands.lock().unwrap().push(args.join(" "));
+        match args {
+            ["list", "devices", "--json"] => Ok(json!({
+                "devices": {
+                    "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [{
+                        "udid": UDID,
+                        "name": "iPhone 15 Pro",
+                        "state": "Booted",
+                        "isAvailable": true,
+                        "deviceTypeIdentifier": "com.apple.CoreSimulator.SimDeviceType.iPhone-15-Pro",
+                    }]
+                }
+            })
+            .to_string()),
+            ["terminate", _, _] => match self.running.lock().unwrap().take() {
+                Some(_) => Ok(String::new()),
+                None => Err(AxiomMCPError::simulator("simctl", "run", format!(
+                    "simctl {} failed: found nothing to terminate", args.join(" ")
+                ))),
+            },
+            ["launch", ..] if !self.installed => Err(AxiomMCPError::simulator("simctl", "run", format!(
+                "simctl {} failed: The request to open \"{}\" failed. found nothing to launch", args.join(" "), BUNDLE_ID
+            ))),
+            ["launch", ..] => {
+                let restart = args.contains(&"--terminate-running-process");
+                let running = if restart { None } else { *self.running.lock().unwrap() };
+                let pid = match running {
+                    Some(pid) => pid,
+                    None => {
+                        tokio::time::sleep(COLD_LAUNCH).await;
+                        let mut next_pid = self.next_pid.lock().unwrap();
+                        *next_pid += 1;
+                        *self.running.lock().unwrap() = Some(*next_pid);
+                        *next_pid
+                    }
+                };
+                Ok(format!("{}: {}\n", BUNDLE_ID, pid))
+            }
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], _env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], _input: &[u8]) -> Result<String> {
+        self.run(args).await
+    }
+}
+
+/// Hands out scripted resident memory readings in order
+struct FakeMemory {
+    readings: Mutex<VecDeque<f64>>,
+    pids: Mutex<Vec<u32>>,
+}
+
+impl FakeMemory {
+    fn new(readings: &[f64]) -> Arc<Self> {
+        Arc::new(Self {
+            readings: Mutex::new(readings.iter().copied().collect()),
+            pids: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[async_trait]
+impl ProcessMemory for FakeMemory {
+    async fn resident_mb(&self, pid: u32) -> Result<f64> {
+        self.pids.lock().unwrap().push(pid);
+        self.readings
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| AxiomMCPError::toolchain("performance", "sample memory", "no more readings"))
+    }
+}
+
+struct FakeFrameRate(Option<f64>);
+
+#[async_trait]
+impl FrameRateSource for FakeFrameRate {
+    async fn frame_rate(&self, _window: Duration) -> Result<f64> {
+        self.0.ok_or_else(|| AxiomMCPError::connectivity("performance", "count frames", "The app did not send its frame rate"))
+    }
+}
+
+fn meter(simctl: &Arc<FakeSimctl>, memory: &Arc<FakeMemory>) -> AppPerformanceMeter {
+    let controller = Arc::new(SimulatorController::with_simctl(simctl.clone()));
+    AppPerformanceMeter::new(controller).with_process_memory(memory.clone())
+}
+
+/// Three memory samples per run: at launch, 10ms and 20ms later
+fn request(runs: u32) -> MeasureAppPerformanceRequest {
+    MeasureAppPerformanceRequest {
+        runs,
+        window_ms: 20,
+        memory_interval_ms: 10,
+        ..MeasureAppPerformanceRequest::new(UDID, BUNDLE_ID)
+    }
+}
+
+#[test]
+fn test_summary_median_and_p95() {
+    let odd = MetricSummary::from_values(&[3.0, 1.0, 2.0]).unwrap();
+    assert_eq!((odd.median, odd.p95, odd.min, odd.max, odd.samples), (2.0, 3.0, 1.0, 3.0, 3));
+
+    let even = MetricSummary::from_values(&[4.0, 1.0, 3.0, 2.0]).unwrap();
+    assert_eq!(even.median, 2.5);
+    assert_eq!(even.p95, 4.0);
+
+    // The 95th percentile of 1..=20 by nearest rank is the 19th value
+    let twenty: Vec<f64> = (1..=20).map(f64::from).collect();
+    assert_eq!(MetricSummary::from_values(&twenty).unwrap().p95, 19.0);
+
+    let single = MetricSummary::from_values(&[7.0]).unwrap();
+    assert_eq!((single.median, single.p95), (7.0, 7.0));
+
+    assert!(MetricSummary::from_values(&[]).is_none());
+}
+
+#[test]
+fn test_parse_rss_converts_kilobytes_to_megabytes() {
+    assert_eq!(parse_rss_mb("  204800\n").unwrap(), 200.0);
+    assert!(parse_rss_mb("").is_err());
+    assert!(parse_rss_mb("RSS\n 1024").is_err());
+}
+
+#[test]
+fn test_measured_actuals_compare_with_the_estimate() {
+    let estimate = PerformanceEstimate {
+        estimated_memory_usage_kb: 102400,
+        estimated_startup_time_ms: 150.0,
+        estimated_cpu_usage_percent: 12.0,
+        performance_grade: "A".to_string(),
+    };
+    let actual = CapturedMetrics::new()
+        .with(PerformanceMetric::ColdLaunchMs, 400.0)
+        .with(PerformanceMetric::MemoryMb, 80.0)
+        .with(PerformanceMetric::WarmLaunchMs, 90.0);
+
+    let difference = actual.difference(&CapturedMetrics::from_estimate(&estimate));
+
+    assert_eq!(difference.get(PerformanceMetric::ColdLaunchMs), Some(250.0));
+    assert_eq!(difference.get(PerformanceMetric::MemoryMb), Some(-20.0));
+    assert_eq!(difference.get(PerformanceMetric::WarmLaunchMs), None, "Not estimated");
+    assert_eq!(difference.get(PerformanceMetric::CpuPercent), None, "Not measured");
+}
+
+#[tokio::test]
+async fn test_each_run_terminates_then_launches_cold_and_warm() {
+    let simctl = FakeSimctl::new(true);
+    let memory = FakeMemory::new(&[100.0; 6]);
+
+    let measurement = meter(&simctl, &memory).measure(&request(2), &PerformanceThresholds::default()).await.unwrap();
+
+    let commands: Vec<String> = simctl.commands().into_iter().filter(|c| !c.starts_with("list")).collect();
+    let terminate = format!("terminate {} {}", UDID, BUNDLE_ID);
+    let cold = format!("launch --terminate-running-process {} {}", UDID, BUNDLE_ID);
+    let warm = format!("launch {} {}", UDID, BUNDLE_ID);
+    assert_eq!(commands, [&terminate, &cold, &warm, &terminate, &cold, &warm].map(String::clone));
+
+    // Memory is sampled on the cold launch's process
+    assert_eq!(measurement.runs.iter().map(|run| run.pid).collect::<Vec<_>>(), vec![4001, 4002]);
+    assert_eq!(*memory.pids.lock().unwrap(), vec![4001, 4001, 4001, 4002, 4002, 4002]);
+    assert_eq!(measurement.device.udid, UDID);
+}
+
+#[tokio::test]
+async fn test_launch_times_are_timed_around_simctl() {
+    let simctl = FakeSimctl::new(true);
+    let memory = FakeMemory::new(&[100.0; 9]);
+
+    let measurement = meter(&simctl, &memory).measure(&request(3), &PerformanceThresholds::default()).await.unwrap();
+
+    for run in &measurement.runs {
+        assert!(run.metrics.get(PerformanceMetric::ColdLaunchMs).unwrap() >= COLD_LAUNCH.as_secs_f64() * 1000.0);
+        assert!(run.metrics.get(PerformanceMetric::WarmLaunchMs).is_some());
+    }
+    let cold = measurement.summaries[&PerformanceMetric::ColdLaunchMs];
+    assert_eq!(cold.samples, 3);
+    assert!(cold.median <= cold.p95);
+}
+
+#[tokio::test]
+async fn test_memory_is_the_median_of_each_window_aggregated_across_runs() {
+    let simctl = FakeSimctl::new(true);
+    let memory = FakeMemory::new(&[100.0, 120.0, 110.0, 90.0, 95.0, 100.0, 130.0, 150.0, 140.0]);
+
+    let measurement = meter(&simctl, &memory).measure(&request(3), &PerformanceThresholds::default()).await.unwrap();
+
+    let per_run: Vec<f64> = measurement.runs.iter().map(|run| run.metrics.get(PerformanceMetric::MemoryMb).unwrap()).collect();
+    assert_eq!(per_run, vec![110.0, 95.0, 140.0]);
+    assert_eq!(measurement.runs[0].memory_samples_mb, vec![100.0, 120.0, 110.0]);
+
+    let summary = measurement.summaries[&PerformanceMetric::MemoryMb];
+    assert_eq!((summary.median, summary.p95, summary.min, summary.max), (110.0, 140.0, 95.0, 140.0));
+    assert_eq!(measurement.metrics.get(PerformanceMetric::MemoryMb), Some(110.0));
+}
+
+#[tokio::test]
+async fn test_frame_rate_is_recorded_when_the_app_reports_it() {
+    let simctl = FakeSimctl::new(true);
+    let memory = FakeMemory::new(&[100.0; 6]);
+
+    let measurement = meter(&simctl, &memory)
+        .with_frame_rate_source(Arc::new(FakeFrameRate(Some(58.0))))
+        .measure(&request(2), &PerformanceThresholds::default())
+        .await
+        .unwrap();
+
+    assert_eq!(measurement.metrics.get(PerformanceMetric::FrameRateFps), Some(58.0));
+    assert_eq!(measurement.verdict.metric(PerformanceMetric::FrameRateFps).unwrap().status, MetricStatus::Pass);
+}
+
+#[tokio::test]
+async fn test_unreported_frame_rate_is_left_missing() {
+    let simctl = FakeSimctl::new(true);
+    let memory = FakeMemory::new(&[100.0; 3]);
+
+    let measurement = meter(&simctl, &memory)
+        .with_frame_rate_source(Arc::new(FakeFrameRate(None)))
+        .measure(&request(1), &PerformanceThresholds::default())
+        .await
+        .unwrap();
+
+    assert_eq!(measurement.metrics.get(PerformanceMetric::FrameRateFps), None);
+    assert!(!measurement.summaries.contains_key(&PerformanceMetric::FrameRateFps));
+    assert_eq!(measurement.verdict.metric(PerformanceMetric::FrameRateFps).unwrap().status, MetricStatus::Missing);
+}
+
+#[tokio::test]
+async fn test_verdict_grades_the_medians() {
+    let simctl = FakeSimctl::new(true);
+    let memory = FakeMemory::new(&[300.0; 3]);
+    let thresholds = PerformanceThresholds {
+        metrics: [(PerformanceMetric::MemoryMb, MetricThreshold::new(100.0, 200.0, 250.0))].into_iter().collect(),
+        fail_on_missing: false,
+    };
+
+    let measurement = meter(&simctl, &memory).measure(&request(1), &thresholds).await.unwrap();
+
+    assert_eq!(measurement.verdict.status, VerdictStatus::Fail);
+    assert_eq!(measurement.verdict.metric(PerformanceMetric::MemoryMb).unwrap().delta, Some(200.0));
+}
+
+#[tokio::test]
+async fn test_invalid_requests_are_rejected_before_simctl() {
+    let simctl = FakeSimctl::new(true);
+    let memory = FakeMemory::new(&[]);
+    let meter = meter(&simctl, &memory);
+    let thresholds = PerformanceThresholds::default();
+
+    let no_runs = MeasureAppPerformanceRequest { runs: 0, ..request(1) };
+    let too_many_runs = MeasureAppPerformanceRequest { runs: MAX_MEASUREMENT_RUNS + 1, ..request(1) };
+    let no_interval = MeasureAppPerformanceRequest { memory_interval_ms: 0, ..request(1) };
+    let no_bundle = MeasureAppPerformanceRequest { bundle_id: " ".to_string(), ..request(1) };
+
+    for request in [no_runs, too_many_runs, no_interval, no_bundle] {
+        let result = meter.measure(&request, &thresholds).await;
+        assert!(matches!(result, Err(AxiomMCPError::Validation(_))), "{:?}", request);
+    }
+    assert!(simctl.commands().is_empty());
+}
+
+#[tokio::test]
+async fn test_app_that_is_not_installed_fails_with_a_hint() {
+    let simctl = FakeSimctl::new(false);
+    let memory = FakeMemory::new(&[]);
+
+    let error = meter(&simctl, &memory).measure(&request(1), &PerformanceThresholds::default()).await.unwrap_err();
+
+    assert!(error.to_string().contains("install it first"), "{}", error);
+}
+
+#[test]
+fn test_request_defaults() {
+    let request: MeasureAppPerformanceRequest =
+        serde_json::from_value(json!({ "device": "iPhone 15 Pro", "bundle_id": BUNDLE_ID })).unwrap();
+
+    assert_eq!((request.runs, request.window_ms, request.memory_interval_ms), (3, 3000, 500));
+}
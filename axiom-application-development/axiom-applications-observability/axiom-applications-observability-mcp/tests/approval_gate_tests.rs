@@ -0,0 +1,235 @@
+mod common;
+
+use axiom_applications_observability::approval_gates::*;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+async fn development_loop(gates: Arc<ApprovalGates>) -> AxiomObservabilityLoop {
+    common::development_loop().await.with_approval_gates(gates)
+}
+
+fn interactive(gates: Vec<CycleStage>) -> Arc<ApprovalGates> {
+    Arc::new(ApprovalGates::new(ApprovalPolicy { gates, ..Default::default() }))
+}
+
+async fn next_request(requests: &mut broadcast::Receiver<ApprovalRequest>) -> ApprovalRequest {
+    tokio::time::timeout(Duration::from_secs(5), requests.recv()).await.unwrap().unwrap()
+}
+
+fn spawn_cycle(development_loop: AxiomObservabilityLoop, requirement: &str) -> tokio::task::JoinHandle<Result<CompleteLoopResult>> {
+    let requirement = requirement.to_string();
+    tokio::spawn(async move { development_loop.execute_complete_development_cycle(requirement).await })
+}
+
+#[tokio::test]
+async fn test_auto_approve_runs_through_every_gate() {
+    let gates = Arc::new(ApprovalGates::new(ApprovalPolicy { mode: ApprovalMode::AutoApprove, ..Default::default() }));
+    let mut requests = gates.subscribe();
+
+    let result = development_loop(gates.clone())
+        .await
+        .execute_complete_development_cycle("Task list".to_string())
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert!(requests.try_recv().is_err(), "nobody is asked");
+    assert!(gates.pending().is_empty());
+}
+
+#[tokio::test]
+async fn test_gates_pause_after_analysis_code_generation_and_visual_validation() {
+    let gates = Arc::new(ApprovalGates::new(ApprovalPolicy::default()));
+    let mut requests = gates.subscribe();
+    let cycle = spawn_cycle(development_loop(gates.clone()).await, "Task list");
+
+    let mut stages = Vec::new();
+    for _ in 0..3 {
+        let request = next_request(&mut requests).await;
+        assert_eq!(gates.pending().len(), 1);
+        assert!(!cycle.is_finished(), "the cycle waits at the {}", request.stage);
+        stages.push(request.stage);
+        gates.approve(&request.cycle_id).unwrap();
+    }
+    let result = cycle.await.unwrap().unwrap();
+
+    assert_eq!(stages, vec![CycleStage::Analysis, CycleStage::Implementation, CycleStage::VisualValidation]);
+    assert!(result.success);
+    assert!(gates.pending().is_empty());
+}
+
+#[tokio::test]
+async fn test_rejection_reruns_only_the_rejected_stage_with_its_feedback() {
+    let gates = Arc::new(ApprovalGates::new(ApprovalPolicy::default()));
+    let mut requests = gates.subscribe();
+    let cycle = spawn_cycle(development_loop(gates.clone()).await, "Task list");
+
+    let analysis = next_request(&mut requests).await;
+    gates.approve(&analysis.cycle_id).unwrap();
+    let first = next_request(&mut requests).await;
+    assert_eq!(first.stage, CycleStage::Implementation);
+    assert_eq!(first.attempt, 1);
+    assert!(first.summary.starts_with("1 contexts, 1 presentations and 1 clients"), "{}", first.summary);
+    let names: Vec<&str> = first.artifacts.iter().map(|artifact| artifact.name.as_str()).collect();
+    assert_eq!(names, vec!["TaskManagerContext.swift", "TaskListView.swift", "TaskManagerClient.swift"]);
+    let review = gates.reject(&first.cycle_id, "Show an empty state when there are no tasks".to_string()).unwrap();
+    assert_eq!(review.request.stage, CycleStage::Implementation);
+
+    // Back exactly one stage: code generation again, not analysis
+    let second = next_request(&mut requests).await;
+    assert_eq!(second.stage, CycleStage::Implementation);
+    assert_eq!(second.attempt, 2);
+    assert_eq!(second.output["analysis_id"], first.output["analysis_id"]);
+    assert_ne!(second.output["implementation_id"], first.output["implementation_id"]);
+    assert_eq!(second.output["review_feedback"][0], "Show an empty state when there are no tasks");
+    gates.approve(&second.cycle_id).unwrap();
+    let visual = next_request(&mut requests).await;
+    assert_eq!(visual.stage, CycleStage::VisualValidation);
+    gates.approve(&visual.cycle_id).unwrap();
+    let result = cycle.await.unwrap().unwrap();
+
+    assert_eq!(result.implementation.review_feedback, vec!["Show an empty state when there are no tasks"]);
+    assert!(result.implementation.documentation.overview.contains("Addresses review feedback: Show an empty state"));
+    assert!(result.requirement_analysis.review_feedback.is_empty());
+    assert_eq!(result.requirement_analysis.requirement_id, analysis.output["requirement_id"]);
+}
+
+#[tokio::test]
+async fn test_analysis_feedback_is_read_with_the_requirement() {
+    let gates = interactive(vec![CycleStage::Analysis]);
+    let mut requests = gates.subscribe();
+    let cycle = spawn_cycle(development_loop(gates.clone()).await, "Task list");
+
+    let first = next_request(&mut requests).await;
+    assert!(first.output["persistence"].is_null());
+    gates.reject(&first.cycle_id, "Users save their notes offline".to_string()).unwrap();
+    let second = next_request(&mut requests).await;
+    assert_eq!(second.stage, CycleStage::Analysis);
+    assert_eq!(second.output["persistence"]["entity_name"], "Note");
+    gates.approve(&second.cycle_id).unwrap();
+    let result = cycle.await.unwrap().unwrap();
+
+    assert_eq!(result.implementation.persistence_layer.unwrap().entity_name, "Note");
+    assert_eq!(result.requirement_analysis.review_feedback, vec!["Users save their notes offline"]);
+}
+
+#[tokio::test]
+async fn test_too_many_rejections_stop_the_cycle() {
+    let gates = Arc::new(ApprovalGates::new(ApprovalPolicy {
+        gates: vec![CycleStage::Analysis],
+        max_rejections: 1,
+        ..Default::default()
+    }));
+    let mut requests = gates.subscribe();
+    let cycle = spawn_cycle(development_loop(gates.clone()).await, "Task list");
+
+    for feedback in ["Add a settings screen", "Add a detail screen too"] {
+        let request = next_request(&mut requests).await;
+        gates.reject(&request.cycle_id, feedback.to_string()).unwrap();
+    }
+
+    match cycle.await.unwrap() {
+        Err(AxiomMCPError::Validation(context)) => assert!(context.message.ends_with("was rejected 2 times"), "{}", context),
+        other => panic!("expected the cycle to stop, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_unanswered_gate_times_out_and_resume_asks_again() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let impatient = Arc::new(ApprovalGates::new(ApprovalPolicy {
+        gates: vec![CycleStage::Implementation],
+        timeout_ms: 50,
+        ..Default::default()
+    }));
+
+    let error = development_loop(impatient.clone())
+        .await
+        .with_checkpoints(store.clone())
+        .execute_complete_development_cycle("Task list".to_string())
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("was not approved within 50ms"), "{}", error);
+    assert!(impatient.pending().is_empty());
+    let cycles = store.list().await.unwrap();
+    let checkpoint = store.load(&cycles[0].cycle_id).await.unwrap();
+    assert_eq!(checkpoint.completed_stages(), vec![CycleStage::Analysis, CycleStage::Implementation]);
+    assert!(checkpoint.approved.is_empty());
+    assert_eq!(checkpoint.failure.as_ref().unwrap().stage, CycleStage::Implementation);
+
+    let gates = interactive(vec![CycleStage::Implementation]);
+    let mut requests = gates.subscribe();
+    let resuming = development_loop(gates.clone()).await.with_checkpoints(store.clone());
+    let cycle_id = checkpoint.cycle_id.clone();
+    let resumed = tokio::spawn(async move { resuming.resume_development_cycle(&cycle_id).await });
+    let request = next_request(&mut requests).await;
+    assert_eq!(request.stage, CycleStage::Implementation);
+    gates.approve(&request.cycle_id).unwrap();
+    let result = resumed.await.unwrap().unwrap();
+
+    assert_eq!(result.reused_stages, CycleStage::ALL[..2].to_vec());
+    assert!(store.load(&checkpoint.cycle_id).await.unwrap().approved.contains(&CycleStage::Implementation));
+}
+
+#[test]
+fn test_decisions_need_a_waiting_cycle() {
+    let gates = ApprovalGates::new(ApprovalPolicy::default());
+
+    for (decision, problem) in [
+        (gates.approve("0b7e4a4e"), "Cycle 0b7e4a4e is not waiting for approval"),
+        (gates.reject("0b7e4a4e", "  ".to_string()), "Rejection feedback cannot be empty"),
+    ] {
+        match decision {
+            Err(AxiomMCPError::Validation(context)) => assert_eq!(context.message, problem),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_approve_and_reject_stage_tools() {
+    let gates = interactive(vec![CycleStage::Analysis]);
+    let config = MCPConfiguration {
+        hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
+        intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
+        simulator_management_enabled: false,
+        code_generation_enabled: false,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        ..Default::default()
+    };
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities)
+        .await
+        .unwrap()
+        .with_development_loop(development_loop(gates.clone()).await);
+    let mut requests = mcp.subscribe_approval_requests().unwrap();
+    let cycle = spawn_cycle(development_loop(gates).await, "Task list");
+
+    let request = next_request(&mut requests).await;
+    let rejected = mcp.execute_tool(AxiomMCPTool::RejectStage(RejectStageRequest {
+        cycle_id: request.cycle_id.clone(),
+        feedback: "Add a settings screen".to_string(),
+    })).await.unwrap();
+    assert_eq!(
+        rejected.summary(),
+        format!("Rejected the analysis of cycle {}; it runs again with the feedback", request.cycle_id)
+    );
+    next_request(&mut requests).await;
+    let approved = mcp.execute_tool(AxiomMCPTool::ApproveStage(request.cycle_id.clone())).await.unwrap();
+    assert_eq!(approved.summary(), format!("Approved the analysis of cycle {}", request.cycle_id));
+
+    assert!(cycle.await.unwrap().unwrap().success);
+}
@@ -0,0 +1,167 @@
+use axiom_applications_observability::artifact_store::*;
+use axiom_applications_observability::screenshot_store::{ScreenshotStorageConfig, ScreenshotStore};
+use axiom_applications_observability::visual_regression::encode_png;
+use image::{Rgba, RgbaImage};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn store(dir: &Path) -> ArtifactStore {
+    ArtifactStore::new(ArtifactStoreConfig { dir: dir.to_path_buf(), ..Default::default() })
+}
+
+fn age(record: &ArtifactRecord, by: Duration) {
+    let file = std::fs::File::options().write(true).open(&record.path).unwrap();
+    file.set_modified(SystemTime::now() - by).unwrap();
+}
+
+fn ids(records: &[ArtifactRecord]) -> Vec<&str> {
+    records.iter().map(|record| record.id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_artifacts_are_listed_by_kind_cycle_and_stage() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path());
+    let build_log = store.put(ArtifactKind::BuildLog, b"** BUILD SUCCEEDED **", "log", ArtifactSource::cycle("cycle-1", "build")).await.unwrap();
+    let results = store.put(ArtifactKind::TestResults, b"{}", "json", ArtifactSource::cycle("cycle-1", "test execution")).await.unwrap();
+    let other = store.put(ArtifactKind::BuildLog, b"** BUILD FAILED **", "log", ArtifactSource::cycle("cycle-2", "build")).await.unwrap();
+
+    let by_kind = store.list(&ArtifactQuery { kind: Some(ArtifactKind::BuildLog), ..Default::default() }).unwrap();
+    let mut expected = vec![build_log.id.as_str(), other.id.as_str()];
+    expected.sort();
+    let mut listed = ids(&by_kind);
+    listed.sort();
+    assert_eq!(listed, expected);
+
+    let by_cycle = store.list(&ArtifactQuery { cycle_id: Some("cycle-1".to_string()), ..Default::default() }).unwrap();
+    assert_eq!(by_cycle.len(), 2);
+    assert!(by_cycle.iter().all(|record| record.id != other.id));
+
+    let by_stage = store
+        .list(&ArtifactQuery { cycle_id: Some("cycle-1".to_string()), stage: Some("test execution".to_string()), ..Default::default() })
+        .unwrap();
+    assert_eq!(ids(&by_stage), vec![results.id.as_str()]);
+    assert_eq!(store.list(&ArtifactQuery::default()).unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_the_same_content_is_stored_once_with_every_source() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path());
+    let first = store.put(ArtifactKind::Report, b"# Cycle", "md", ArtifactSource::cycle("cycle-1", "report")).await.unwrap();
+    let second = store.put(ArtifactKind::Report, b"# Cycle", "md", ArtifactSource::cycle("cycle-2", "report")).await.unwrap();
+
+    assert_eq!(first.id, second.id);
+    assert_eq!(first.id, content_hash(b"# Cycle"));
+    assert_eq!(second.sources.len(), 2);
+    assert_eq!(std::fs::read_dir(dir.path().join("objects")).unwrap().count(), 1);
+
+    let query = ArtifactQuery { cycle_id: Some("cycle-2".to_string()), ..Default::default() };
+    assert_eq!(ids(&store.list(&query).unwrap()), vec![first.id.as_str()]);
+}
+
+#[tokio::test]
+async fn test_racing_puts_of_the_same_content_keep_both_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path());
+    let id = content_hash(b"# Cycle");
+
+    // Both puts missed the other in `renew` and move their copy in
+    for cycle in ["cycle-1", "cycle-2"] {
+        let temp = store.temp_path("md").await.unwrap();
+        std::fs::write(&temp, b"# Cycle").unwrap();
+        store.insert_file(&id, ArtifactKind::Report, &temp, "md", ArtifactSource::cycle(cycle, "report")).await.unwrap();
+    }
+
+    let record = store.get(&id).unwrap().unwrap();
+    let cycles: Vec<_> = record.sources.iter().map(|source| source.cycle_id.as_deref().unwrap()).collect();
+    assert_eq!(cycles, vec!["cycle-1", "cycle-2"]);
+    assert_eq!(std::fs::read_dir(dir.path().join("objects")).unwrap().count(), 1);
+}
+
+#[tokio::test]
+async fn test_artifacts_are_read_back_by_id_and_unknown_ids_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path());
+    let source = ArtifactSource::cycle("cycle-1", "build").with_metadata("implementation_id", "impl-1");
+    let record = store.put(ArtifactKind::BuildLog, b"log", "log", source.clone()).await.unwrap();
+
+    let found = store.require(&record.id).unwrap();
+    assert_eq!(found.kind, ArtifactKind::BuildLog);
+    assert_eq!(found.extension(), Some("log"));
+    assert_eq!(found.sources, vec![source]);
+    assert_eq!(store.read(&record.id).await.unwrap(), b"log");
+
+    assert!(store.get("missing").unwrap().is_none());
+    assert!(store.require("missing").is_err());
+}
+
+#[tokio::test]
+async fn test_artifacts_whose_files_are_gone_are_not_listed() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path());
+    let record = store.put(ArtifactKind::TestResults, b"{}", "json", ArtifactSource::default()).await.unwrap();
+    std::fs::remove_file(&record.path).unwrap();
+
+    assert!(store.get(&record.id).unwrap().is_none());
+    assert!(store.list(&ArtifactQuery::default()).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_each_kind_is_pruned_under_its_own_retention() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ArtifactStore::new(ArtifactStoreConfig {
+        dir: dir.path().to_path_buf(),
+        retention: BTreeMap::from([
+            (ArtifactKind::BuildLog, RetentionPolicy { max_age_secs: Some(DAY.as_secs()), max_total_bytes: None }),
+            (ArtifactKind::Report, RetentionPolicy::unlimited()),
+        ]),
+    });
+    let log = store.put(ArtifactKind::BuildLog, b"old log", "log", ArtifactSource::default()).await.unwrap();
+    let report = store.put(ArtifactKind::Report, b"old report", "md", ArtifactSource::default()).await.unwrap();
+    age(&log, 2 * DAY);
+    age(&report, 2 * DAY);
+
+    let pruned = store.prune(SystemTime::now(), &BTreeSet::new()).unwrap();
+
+    assert_eq!(pruned.removed.len(), 1);
+    assert_eq!(pruned.removed[0].id, log.id);
+    assert_eq!(pruned.removed[0].reason, PruneReason::Expired);
+    assert!(store.get(&log.id).unwrap().is_none());
+    assert!(store.get(&report.id).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_pinned_artifacts_survive_pruning() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path());
+    let log = store.put(ArtifactKind::BuildLog, b"baseline log", "log", ArtifactSource::default()).await.unwrap();
+    age(&log, 30 * DAY);
+
+    let pruned = store.prune(SystemTime::now(), &BTreeSet::from([log.id.clone()])).unwrap();
+
+    assert!(pruned.removed.is_empty());
+    assert_eq!(pruned.pinned, 1);
+    assert!(store.get(&log.id).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_screenshots_are_indexed_in_a_shared_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let artifacts = Arc::new(store(dir.path()));
+    let screenshots = ScreenshotStore::new(ScreenshotStorageConfig { dir: dir.path().to_path_buf(), ..Default::default() })
+        .with_artifact_store(Arc::clone(&artifacts));
+    let png = encode_png(&RgbaImage::from_pixel(10, 10, Rgba([40, 40, 40, 255]))).unwrap();
+
+    let image = screenshots.put_for(&png, ArtifactSource::cycle("cycle-1", "visual validation")).await.unwrap();
+
+    let query = ArtifactQuery { kind: Some(ArtifactKind::Screenshot), cycle_id: Some("cycle-1".to_string()), ..Default::default() };
+    let listed = artifacts.list(&query).unwrap();
+    assert_eq!(ids(&listed), vec![image.hash.as_str()]);
+    assert_eq!(listed[0].path, image.path);
+    assert!(artifacts.list(&ArtifactQuery { kind: Some(ArtifactKind::BuildLog), ..Default::default() }).unwrap().is_empty());
+}
@@ -9,6 +9,7 @@ async fn test_mcp_initialization() -> std::result::Result<(), Box<dyn std::error
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = MCPCapabilities {
@@ -40,6 +41,7 @@ async fn test_presentation_generation() -> std::result::Result<(), Box<dyn std::
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = MCPCapabilities {
@@ -62,6 +64,8 @@ async fn test_presentation_generation() -> std::result::Result<(), Box<dyn std::
             max_render_time_ms: 16.0,
             max_memory_mb: 10.0,
         },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
     };
     
     let result = mcp.execute_tool(AxiomMCPTool::GeneratePresentation(presentation_spec)).await?;
@@ -89,6 +93,7 @@ async fn test_context_generation() -> std::result::Result<(), Box<dyn std::error
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = MCPCapabilities {
@@ -110,10 +115,12 @@ async fn test_context_generation() -> std::result::Result<(), Box<dyn std::error
                 property_type: "String".to_string(),
                 is_published: true,
                 default_value: Some("\"Hello\"".to_string()),
+                requirement_ids: vec![],
             }
         ],
         client_binding: "TestClient".to_string(),
         lifecycle_management: true,
+        template_overrides: Default::default(),
     };
     
     let result = mcp.execute_tool(AxiomMCPTool::GenerateContext(context_spec)).await?;
@@ -142,6 +149,7 @@ async fn test_client_generation() -> std::result::Result<(), Box<dyn std::error:
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = MCPCapabilities {
@@ -164,10 +172,13 @@ async fn test_client_generation() -> std::result::Result<(), Box<dyn std::error:
                 parameters: vec![],
                 return_type: "String".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             }
         ],
         state_streaming: true,
         mock_implementation: true,
+        template_overrides: Default::default(),
+        persistence: None,
     };
     
     let result = mcp.execute_tool(AxiomMCPTool::GenerateMockClient(client_spec)).await?;
@@ -196,6 +207,7 @@ async fn test_architecture_validation() -> std::result::Result<(), Box<dyn std::
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = MCPCapabilities {
@@ -233,6 +245,7 @@ async fn test_performance_targets() -> std::result::Result<(), Box<dyn std::erro
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = MCPCapabilities {
@@ -255,6 +268,8 @@ async fn test_performance_targets() -> std::result::Result<(), Box<dyn std::erro
         ui_components: vec!["Text(\"Performance Test\")".to_string()],
         accessibility_requirements: vec![],
         performance_requirements: PerformanceRequirements::default(),
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
     };
     
     let context_spec = ContextSpec {
@@ -265,10 +280,12 @@ async fn test_performance_targets() -> std::result::Result<(), Box<dyn std::erro
                 property_type: "Int".to_string(),
                 is_published: true,
                 default_value: Some("0".to_string()),
+                requirement_ids: vec![],
             }
         ],
         client_binding: "PerformanceTestClient".to_string(),
         lifecycle_management: true,
+        template_overrides: Default::default(),
     };
     
     let client_spec = ClientSpec {
@@ -280,10 +297,13 @@ async fn test_performance_targets() -> std::result::Result<(), Box<dyn std::erro
                 parameters: vec![],
                 return_type: "Void".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             }
         ],
         state_streaming: true,
         mock_implementation: true,
+        template_overrides: Default::default(),
+        persistence: None,
     };
     
     // Generate trio
@@ -0,0 +1,133 @@
+mod common;
+
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::cycle_report::{CycleReportConfig, CycleReportExporter};
+use axiom_applications_observability::error::ErrorCategory;
+use axiom_applications_observability::tools::TOOL_SPECS;
+use axiom_applications_observability::*;
+use serde_json::json;
+
+fn capabilities(visual_analysis: bool) -> MCPCapabilities {
+    MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis,
+        simulator_management: false,
+        performance_monitoring: true,
+    }
+}
+
+async fn mcp(capabilities: MCPCapabilities) -> AxiomApplicationsObservabilityMCP {
+    let config = MCPConfiguration {
+        simulator_management_enabled: capabilities.simulator_management,
+        visual_analysis_enabled: capabilities.visual_analysis,
+        ..Default::default()
+    };
+    AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap()
+}
+
+async fn development_loop(store: CycleStore) -> AxiomObservabilityLoop {
+    common::development_loop().await.with_checkpoints(store)
+}
+
+#[tokio::test]
+async fn test_tools_of_disabled_capabilities_are_not_listed() {
+    let mcp = mcp(capabilities(false)).await;
+
+    let listed: Vec<String> = mcp.list_tools().tools.into_iter().map(|tool| tool.name).collect();
+
+    assert!(!listed.contains(&"analyze_ui_patterns".to_string()), "{:?}", listed);
+    assert!(!listed.contains(&"list_simulators".to_string()), "{:?}", listed);
+    assert!(listed.contains(&"generate_presentation".to_string()), "{:?}", listed);
+    assert!(listed.contains(&"get_connection_status".to_string()), "{:?}", listed);
+    let expected: Vec<&str> = TOOL_SPECS.iter()
+        .filter(|spec| spec.required_capabilities.iter().all(|capability| capabilities(false).is_enabled(capability)))
+        .map(|spec| spec.name)
+        .collect();
+    assert_eq!(listed, expected);
+}
+
+#[tokio::test]
+async fn test_calling_a_disabled_tool_fails_before_it_runs() {
+    let mcp = mcp(capabilities(false)).await;
+
+    let error = mcp.call_tool("boot_simulator", json!({ "device": "iPhone 15 Pro" })).await.unwrap_err();
+
+    match &error {
+        AxiomMCPError::CapabilityDisabled { capability, tool } => {
+            assert_eq!(capability, "simulator_management");
+            assert_eq!(tool.as_deref(), Some("boot_simulator"));
+        },
+        other => panic!("Expected a disabled capability, got {:?}", other),
+    }
+    assert_eq!(error.category(), ErrorCategory::Configuration);
+    assert_eq!(error.to_string(), "The simulator_management capability is not enabled; boot_simulator needs it");
+    assert_eq!(error.report().field.as_deref(), Some("simulator_management"));
+    assert!(!mcp.tool_metrics().report().tool("boot_simulator").unwrap().stages.contains_key("acquire resources"));
+}
+
+#[test]
+fn test_capability_names_match_the_tool_specs() {
+    let everything = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: true,
+        hot_reload_integration: true,
+        visual_analysis: true,
+        simulator_management: true,
+        performance_monitoring: true,
+    };
+
+    for spec in TOOL_SPECS {
+        assert_eq!(everything.first_disabled(spec.required_capabilities), None, "{}", spec.name);
+    }
+    assert!(!everything.is_enabled("telepathy"));
+    assert_eq!(capabilities(false).first_disabled(&["code_generation", "visual_analysis"]), Some("visual_analysis"));
+}
+
+#[tokio::test]
+async fn test_cycle_without_visual_analysis_completes_with_the_skip_recorded() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let development = development_loop(store.clone()).await.with_capabilities(capabilities(false));
+
+    let result = development.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    assert!(result.success);
+    assert!(result.visual_validation.is_none());
+    assert!(result.performance_verdict.is_some());
+    assert_eq!(result.skipped_validations, vec![SkippedValidation {
+        stage: CycleStage::VisualValidation,
+        check: "visual validation".to_string(),
+        capability: "visual_analysis".to_string(),
+    }]);
+    let checkpoint = store.load(&result.cycle_id).await.unwrap();
+    assert_eq!(checkpoint.completed_stages(), CycleStage::ALL.to_vec());
+    assert_eq!(checkpoint.skipped, result.skipped_validations);
+
+    let reports = tempfile::tempdir().unwrap();
+    let exporter = CycleReportExporter::new(CycleReportConfig { dir: reports.path().to_path_buf(), ..Default::default() });
+    let markdown = std::fs::read_to_string(exporter.export(&checkpoint, false).await.unwrap().markdown_path).unwrap();
+    assert!(markdown.contains("## Screenshots\n\nSkipped: visual validation (the visual_analysis capability is disabled).\n"), "{}", markdown);
+    assert!(!markdown.contains("UI patterns:"), "{}", markdown);
+}
+
+#[tokio::test]
+async fn test_reenabling_a_capability_reruns_the_skipped_stage() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let skipped = development_loop(store.clone()).await.with_capabilities(capabilities(false))
+        .execute_complete_development_cycle("Task list".to_string())
+        .await
+        .unwrap();
+
+    let resumed = development_loop(store.clone()).await.with_capabilities(capabilities(true))
+        .resume_development_cycle(&skipped.cycle_id)
+        .await
+        .unwrap();
+
+    assert!(resumed.visual_validation.is_some());
+    assert!(resumed.skipped_validations.is_empty());
+    assert_eq!(resumed.reused_stages, CycleStage::ALL[..4].to_vec());
+    assert!(store.load(&skipped.cycle_id).await.unwrap().skipped.is_empty());
+}
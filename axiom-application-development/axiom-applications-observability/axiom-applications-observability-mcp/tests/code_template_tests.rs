@@ -0,0 +1,199 @@
+use axiom_applications_observability::code_generation::*;
+use axiom_applications_observability::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+const SENTINEL: &str = "// project-template-sentinel";
+
+fn context_spec(overrides: &[(&str, &str)]) -> ContextSpec {
+    ContextSpec {
+        name: "ProfileContext".to_string(),
+        state_properties: vec![StateProperty {
+            name: "name".to_string(),
+            property_type: "String".to_string(),
+            is_published: true,
+            default_value: Some("\"\"".to_string()),
+            requirement_ids: vec![],
+        }],
+        client_binding: "ProfileClient".to_string(),
+        lifecycle_management: true,
+        template_overrides: overrides.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+    }
+}
+
+fn write_template(project: &Path, kind: TemplateKind, contents: &str) {
+    let dir = project.join(PROJECT_TEMPLATES_DIR);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(kind.file_name()), contents).unwrap();
+}
+
+/// The built-in context template with a sentinel comment on top
+fn project_context_template() -> String {
+    format!("{}\n{}", SENTINEL, TemplateKind::Context.built_in())
+}
+
+fn validation_message(error: AxiomMCPError) -> String {
+    match error {
+        AxiomMCPError::Validation(context) => context.message,
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+fn template_problem(error: AxiomMCPError) -> String {
+    match error {
+        AxiomMCPError::Generation(context) => context.message,
+        other => panic!("expected a generation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_project_template_takes_precedence_over_the_built_in() {
+    let project = tempfile::tempdir().unwrap();
+    write_template(project.path(), TemplateKind::Context, &project_context_template());
+    let generator = AxiomCodeGenerator::for_project(project.path()).await.unwrap();
+
+    let code = generator.generate_context(context_spec(&[])).await.unwrap().generated_code;
+
+    assert!(code.starts_with(SENTINEL), "{}", code);
+    assert!(code.contains("class ProfileContext: AxiomClientObservingContext {"));
+    assert!(code.contains("private let client: ProfileClient"));
+    let validation = generator.validate_generated_code(&code).await.unwrap();
+    assert!(validation.passed, "{:?}", validation.issues);
+}
+
+#[tokio::test]
+async fn test_per_call_overrides_take_precedence_over_the_project_template() {
+    let project = tempfile::tempdir().unwrap();
+    write_template(project.path(), TemplateKind::Context, &project_context_template());
+    let generator = AxiomCodeGenerator::for_project(project.path()).await.unwrap();
+
+    let code = generator
+        .generate_context(context_spec(&[
+            ("imports", "import SwiftUI\n// per-call-sentinel"),
+            ("base_class", "TrackedContext"),
+        ]))
+        .await
+        .unwrap()
+        .generated_code;
+
+    assert!(code.starts_with(&format!("{}\nimport SwiftUI\n// per-call-sentinel\n", SENTINEL)), "{}", code);
+    assert!(code.contains("class ProfileContext: TrackedContext {"));
+    assert!(!code.contains("AxiomClientObservingContext"));
+}
+
+#[tokio::test]
+async fn test_override_values_are_not_expanded() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let code = generator
+        .generate_context(context_spec(&[("imports", "import SwiftUI // {name}")]))
+        .await
+        .unwrap()
+        .generated_code;
+
+    assert!(code.starts_with("import SwiftUI // {name}\n"), "{}", code);
+}
+
+#[tokio::test]
+async fn test_unknown_per_call_variable_is_rejected() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let error = generator.generate_context(context_spec(&[("title", "Profile")])).await.unwrap_err();
+
+    assert_eq!(
+        validation_message(error),
+        "Unknown context template variable 'title'; expected one of name, imports, base_class, properties, client_binding"
+    );
+}
+
+#[tokio::test]
+async fn test_missing_project_templates_fall_back_to_built_ins() {
+    let project = tempfile::tempdir().unwrap();
+    write_template(project.path(), TemplateKind::Client, "import Foundation\n\nactor {name}: {protocols} {\n{actions}\n}\n");
+    let no_templates = tempfile::tempdir().unwrap();
+
+    let generator = AxiomCodeGenerator::for_project(project.path()).await.unwrap();
+    let built_in = AxiomCodeGenerator::for_project(no_templates.path()).await.unwrap();
+
+    let sources: HashMap<TemplateKind, TemplateSource> =
+        generator.templates().into_iter().map(|template| (template.kind, template.source)).collect();
+    assert_eq!(sources[&TemplateKind::Context], TemplateSource::BuiltIn);
+    assert_eq!(sources[&TemplateKind::Presentation], TemplateSource::BuiltIn);
+    assert_eq!(
+        sources[&TemplateKind::Client],
+        TemplateSource::Project { path: project.path().join(PROJECT_TEMPLATES_DIR).join("client.swift.template") }
+    );
+    assert!(built_in.templates().iter().all(|template| template.source == TemplateSource::BuiltIn));
+}
+
+#[tokio::test]
+async fn test_templates_report_their_variables() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let templates = generator.templates();
+
+    let kinds: Vec<TemplateKind> = templates.iter().map(|template| template.kind).collect();
+    assert_eq!(kinds, TemplateKind::ALL.to_vec());
+    let presentation = &templates[1];
+    assert_eq!(presentation.file_name, "presentation.swift.template");
+    let names: Vec<&str> = presentation.variables.iter().map(|variable| variable.name.as_str()).collect();
+    assert_eq!(names, vec!["name", "imports", "context_binding", "ui_components", "title"]);
+    assert!(presentation.variables.iter().all(|variable| !variable.description.is_empty()));
+}
+
+#[tokio::test]
+async fn test_invalid_project_templates_are_rejected_at_load() {
+    let cases = [
+        ("{imports}\n\nstruct {name}: View {\n    let title = \"{heading}\"\n}\n", "Unresolved template marker {heading}"),
+        ("{imports}\n\nstruct {name}: View {\n    var body: some View {\n        EmptyView()\n}\n", "is never closed"),
+        ("{imports}\n\nstruct ProfileView: View {}\n", "does not use {name}"),
+    ];
+
+    for (contents, problem) in cases {
+        let project = tempfile::tempdir().unwrap();
+        write_template(project.path(), TemplateKind::Presentation, contents);
+
+        let error = AxiomCodeGenerator::for_project(project.path()).await.unwrap_err();
+
+        let message = template_problem(error);
+        assert!(message.contains("presentation.swift.template"), "{}", message);
+        assert!(message.contains(problem), "{}", message);
+    }
+}
+
+#[test]
+fn test_built_in_templates_pass_their_own_validation() {
+    for kind in TemplateKind::ALL {
+        validate_template(kind, kind.built_in()).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_list_code_generation_templates_tool() {
+    let config = MCPConfiguration {
+        hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
+        intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
+        simulator_management_enabled: false,
+        code_generation_enabled: true,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        ..Default::default()
+    };
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap();
+
+    let result = mcp.execute_tool(AxiomMCPTool::ListCodeGenerationTemplates).await.unwrap();
+
+    match &result {
+        ToolResult::CodeGenerationTemplates(templates) => assert_eq!(templates.len(), 3),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(result.summary(), "3 code generation templates (0 from the project, 3 built in)");
+}
@@ -0,0 +1,88 @@
+//! Development loop fixtures shared by the integration tests
+
+#![allow(dead_code)]
+
+use axiom_applications_observability::*;
+use axiom_applications_observability::advanced_visual_intelligence::VisualIntelligenceEngine;
+use axiom_applications_observability::complete_development_loop::AxiomObservabilityLoop;
+use axiom_applications_observability::development_workflow::AxiomObservabilityWorkflow;
+use axiom_applications_observability::performance_analysis_integration::{PerformanceAnalysisIntegration, PerformanceThresholds};
+use std::sync::Arc;
+
+/// Builds a development loop over the local hot reload and intelligence
+/// servers, with the collaborators a test needs to control swapped in
+#[derive(Default)]
+pub struct DevelopmentLoopBuilder {
+    thresholds: Option<PerformanceThresholds>,
+    workflow: Option<AxiomObservabilityWorkflow>,
+    visual_intelligence: Option<VisualIntelligenceEngine>,
+}
+
+impl DevelopmentLoopBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Judge performance against `thresholds` instead of the defaults
+    pub fn with_thresholds(mut self, thresholds: PerformanceThresholds) -> Self {
+        self.thresholds = Some(thresholds);
+        self
+    }
+
+    /// Run cycles through `workflow` instead of one over the local servers
+    pub fn with_workflow(mut self, workflow: AxiomObservabilityWorkflow) -> Self {
+        self.workflow = Some(workflow);
+        self
+    }
+
+    /// Analyze screens with `engine` instead of a default one
+    pub fn with_visual_intelligence(mut self, engine: VisualIntelligenceEngine) -> Self {
+        self.visual_intelligence = Some(engine);
+        self
+    }
+
+    pub async fn build(self) -> AxiomObservabilityLoop {
+        let hot_reload_client = Arc::new(hot_reload::HotReloadClient::new("ws://localhost:8080/ws").await.unwrap());
+        let intelligence_client = Arc::new(intelligence::IntelligenceClient::new("ws://localhost:8080/intelligence").await.unwrap());
+        let simulator_controller = Arc::new(simulator::SimulatorController::new().await.unwrap());
+        let code_generator = Arc::new(code_generation::AxiomCodeGenerator::new().await.unwrap());
+        let visual_intelligence = Arc::new(match self.visual_intelligence {
+            Some(engine) => engine,
+            None => VisualIntelligenceEngine::new().await.unwrap(),
+        });
+        let screenshot_engine = Arc::new(screenshot_matrix_engine::ScreenshotMatrixEngine::new(simulator_controller.clone()).await.unwrap());
+        let performance_analysis = PerformanceAnalysisIntegration::new(intelligence_client.clone(), hot_reload_client.clone()).await.unwrap();
+        let performance_analysis = match self.thresholds {
+            Some(thresholds) => performance_analysis.with_thresholds(thresholds).unwrap(),
+            None => performance_analysis,
+        };
+        let workflow = match self.workflow {
+            Some(workflow) => workflow,
+            None => AxiomObservabilityWorkflow::new(
+                hot_reload_client,
+                simulator_controller,
+                intelligence_client.clone(),
+                screenshot_engine.clone(),
+                visual_intelligence.clone(),
+            )
+            .await
+            .unwrap(),
+        };
+
+        AxiomObservabilityLoop::new(
+            code_generator.clone(),
+            Arc::new(axiom_framework_integration::AxiomFrameworkIntegration::new(intelligence_client, code_generator).await.unwrap()),
+            Arc::new(performance_analysis),
+            Arc::new(workflow),
+            visual_intelligence,
+            screenshot_engine,
+        )
+        .await
+        .unwrap()
+    }
+}
+
+/// A development loop with nothing swapped in
+pub async fn development_loop() -> AxiomObservabilityLoop {
+    DevelopmentLoopBuilder::new().build().await
+}
@@ -0,0 +1,208 @@
+use axiom_applications_observability::config::*;
+use axiom_applications_observability::*;
+use std::path::{Path, PathBuf};
+
+fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(ToString::to_string).collect()
+}
+
+fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn field_error(result: Result<MCPConfiguration>) -> (String, String) {
+    match result {
+        Err(AxiomMCPError::Configuration { field, message }) => (field, message),
+        other => panic!("Expected a configuration error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_defaults_without_file_or_environment() {
+    let config = MCPConfiguration::load_from(args(&[]), env(&[("HOME", "/home/dev")])).unwrap();
+
+    let defaults = MCPConfiguration::default();
+    assert_eq!(config.hot_reload_server_url, defaults.hot_reload_server_url);
+    assert_eq!(config.intelligence_server_url, defaults.intelligence_server_url);
+    assert!(config.visual_analysis_enabled && config.simulator_management_enabled);
+    assert_eq!(config.visual_regression, defaults.visual_regression);
+}
+
+#[test]
+fn test_file_fields_override_defaults_and_keep_the_rest() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "axiom.toml", r#"
+hot_reload_server_url = "wss://reload.example.dev/ws"
+performance_monitoring_enabled = false
+
+[visual_regression]
+threshold = 0.01
+"#);
+
+    let config = MCPConfiguration::load_from(args(&["--config", path.to_str().unwrap()]), env(&[])).unwrap();
+
+    assert_eq!(config.hot_reload_server_url, "wss://reload.example.dev/ws");
+    assert!(!config.performance_monitoring_enabled);
+    assert_eq!(config.visual_regression.threshold, 0.01);
+    assert_eq!(config.visual_regression.baseline_dir, MCPConfiguration::default().visual_regression.baseline_dir);
+    assert_eq!(config.intelligence_server_url, MCPConfiguration::default().intelligence_server_url);
+}
+
+#[test]
+fn test_json_files_and_the_config_path_variable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "axiom.json", r#"{ "intelligence_server_url": "ws://10.0.0.5:9000/intelligence" }"#);
+
+    let config = MCPConfiguration::load_from(args(&[]), env(&[(CONFIG_PATH_ENV, path.to_str().unwrap())])).unwrap();
+
+    assert_eq!(config.intelligence_server_url, "ws://10.0.0.5:9000/intelligence");
+}
+
+#[test]
+fn test_config_argument_wins_over_the_config_path_variable() {
+    let dir = tempfile::tempdir().unwrap();
+    let from_argument = write(dir.path(), "argument.toml", r#"hot_reload_server_url = "ws://argument:8080/ws""#);
+    let from_variable = write(dir.path(), "variable.toml", r#"hot_reload_server_url = "ws://variable:8080/ws""#);
+
+    let config = MCPConfiguration::load_from(
+        args(&["serve", &format!("--config={}", from_argument.display())]),
+        env(&[(CONFIG_PATH_ENV, from_variable.to_str().unwrap())]),
+    ).unwrap();
+
+    assert_eq!(config.hot_reload_server_url, "ws://argument:8080/ws");
+}
+
+#[test]
+fn test_environment_overrides_file_overrides_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "axiom.toml", r#"
+hot_reload_server_url = "ws://file:8080/ws"
+intelligence_server_url = "ws://file:8080/intelligence"
+code_generation_enabled = false
+
+[screenshot_storage]
+dir = "/var/axiom/from-file"
+"#);
+
+    let config = MCPConfiguration::load_from(args(&["--config", path.to_str().unwrap()]), env(&[
+        ("AXIOM_OBS_HOT_RELOAD_SERVER_URL", "ws://env:8080/ws"),
+        ("AXIOM_OBS_CODE_GENERATION_ENABLED", "TRUE"),
+        ("AXIOM_OBS_SCREENSHOT_STORAGE__DIR", "/var/axiom/from-env"),
+        ("AXIOM_OBS_VISUAL_REGRESSION__PIXEL_TOLERANCE", "12"),
+        ("AXIOM_OBS_HOT_RELOAD_FILTERS__INCLUDE", r#"["Sources/**/*.swift"]"#),
+    ])).unwrap();
+
+    assert_eq!(config.hot_reload_server_url, "ws://env:8080/ws");
+    assert_eq!(config.intelligence_server_url, "ws://file:8080/intelligence");
+    assert!(config.code_generation_enabled);
+    assert_eq!(config.screenshot_storage.dir, PathBuf::from("/var/axiom/from-env"));
+    assert_eq!(config.visual_regression.pixel_tolerance, 12);
+    assert_eq!(config.hot_reload_filters.include, vec!["Sources/**/*.swift"]);
+}
+
+#[test]
+fn test_server_urls_must_be_websockets_with_a_host() {
+    let cases = [
+        ("AXIOM_OBS_HOT_RELOAD_SERVER_URL", "http://localhost:8080/ws", "hot_reload_server_url", "expected a ws:// or wss:// URL"),
+        ("AXIOM_OBS_INTELLIGENCE_SERVER_URL", "localhost:8080", "intelligence_server_url", "expected a ws:// or wss:// URL"),
+        ("AXIOM_OBS_INTELLIGENCE_SERVER_URL", "wss:///intelligence", "intelligence_server_url", "has no host"),
+        ("AXIOM_OBS_HOT_RELOAD_SERVER_URL", " ", "hot_reload_server_url", "cannot be empty"),
+    ];
+
+    for (variable, url, expected_field, expected_message) in cases {
+        let (field, message) = field_error(MCPConfiguration::load_from(args(&[]), env(&[(variable, url)])));
+        assert_eq!(field, expected_field, "{}", url);
+        assert!(message.contains(expected_message), "{}: {}", url, message);
+    }
+}
+
+#[test]
+fn test_visual_analysis_needs_simulator_management() {
+    let (field, message) = field_error(MCPConfiguration::load_from(
+        args(&[]),
+        env(&[("AXIOM_OBS_SIMULATOR_MANAGEMENT_ENABLED", "false")]),
+    ));
+
+    assert_eq!(field, "visual_analysis_enabled");
+    assert!(message.contains("simulator_management_enabled"), "{}", message);
+
+    let config = MCPConfiguration::load_from(args(&[]), env(&[
+        ("AXIOM_OBS_SIMULATOR_MANAGEMENT_ENABLED", "false"),
+        ("AXIOM_OBS_VISUAL_ANALYSIS_ENABLED", "0"),
+    ])).unwrap();
+    assert!(!config.visual_analysis_enabled);
+}
+
+#[test]
+fn test_malformed_environment_values_name_their_field() {
+    let cases = [
+        ("AXIOM_OBS_VISUAL_ANALYSIS_ENABLED", "yes", "visual_analysis_enabled", "must be true or false"),
+        ("AXIOM_OBS_VISUAL_REGRESSION__THRESHOLD", "a little", "visual_regression.threshold", "must be a number"),
+        ("AXIOM_OBS_HOT_RELOAD_FILTERS__EXCLUDE", "**/Pods/**", "hot_reload_filters.exclude", "must be JSON"),
+        ("AXIOM_OBS_HOT_RELOAD_URL", "ws://localhost/ws", "hot_reload_url", "does not name a configuration field"),
+    ];
+
+    for (variable, value, expected_field, expected_message) in cases {
+        let (field, message) = field_error(MCPConfiguration::load_from(args(&[]), env(&[(variable, value)])));
+        assert_eq!(field, expected_field, "{}", variable);
+        assert!(message.contains(expected_message), "{}: {}", variable, message);
+    }
+}
+
+#[test]
+fn test_file_problems_name_their_field() {
+    let dir = tempfile::tempdir().unwrap();
+    let misspelled = write(dir.path(), "misspelled.toml", "visual_analysis_enable = false\n");
+    let wrong_type = write(dir.path(), "wrong-type.toml", "[visual_regression]\npixel_tolerance = \"high\"\n");
+    let bad_glob = write(dir.path(), "bad-glob.json", r#"{ "hot_reload_filters": { "include": ["Sources/[*.swift"] } }"#);
+
+    let load = |path: &Path| MCPConfiguration::load_from(args(&["--config", path.to_str().unwrap()]), env(&[]));
+
+    assert_eq!(field_error(load(&misspelled)).0, "visual_analysis_enable");
+    assert_eq!(field_error(load(&wrong_type)).0, "visual_regression");
+    assert_eq!(field_error(load(&bad_glob)).0, "hot_reload_filters");
+}
+
+#[test]
+fn test_unreadable_config_files_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let yaml = write(dir.path(), "axiom.yaml", "hot_reload_server_url: ws://localhost/ws\n");
+    let broken = write(dir.path(), "broken.toml", "hot_reload_server_url = \n");
+
+    for (arguments, expected) in [
+        (args(&["--config", dir.path().join("missing.toml").to_str().unwrap()]), "Cannot read configuration file"),
+        (args(&["--config", yaml.to_str().unwrap()]), "expected a .toml or .json file"),
+        (args(&["--config", broken.to_str().unwrap()]), "Invalid configuration file"),
+        (args(&["--config"]), "--config needs a path"),
+    ] {
+        let error = MCPConfiguration::load_from(arguments, env(&[])).unwrap_err();
+        assert!(matches!(error, AxiomMCPError::Configuration { ref field, .. } if field == "--config"), "{:?}", error);
+        assert!(error.to_string().contains(expected), "{}", error);
+    }
+}
+
+#[tokio::test]
+async fn test_init_mcp_validates_an_explicit_configuration() {
+    let config = MCPConfiguration {
+        intelligence_server_url: "https://intelligence.example.dev".to_string(),
+        ..Default::default()
+    };
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+
+    let error = init_mcp(config, capabilities).await.unwrap_err();
+
+    assert!(matches!(error, AxiomMCPError::Configuration { ref field, .. } if field == "intelligence_server_url"), "{:?}", error);
+}
@@ -0,0 +1,151 @@
+use axiom_applications_observability::connection::*;
+use axiom_applications_observability::intelligence::IntelligenceClient;
+use futures_util::StreamExt;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+fn fast_policy() -> ReconnectPolicy {
+    ReconnectPolicy {
+        initial_delay: Duration::from_millis(20),
+        max_delay: Duration::from_millis(100),
+        jitter: 0.0,
+        ..ReconnectPolicy::default()
+    }
+}
+
+/// A port nothing listens on
+async fn closed_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+async fn next_text<S>(stream: &mut S) -> String
+where
+    S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("timed out waiting for a message")
+            .expect("connection closed")
+            .unwrap();
+        if let Message::Text(text) = message {
+            return text;
+        }
+    }
+}
+
+async fn wait_for(socket: &ReconnectingWebSocket, condition: impl Fn(&ConnectionStatus) -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !condition(&socket.status()) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for the connection state");
+}
+
+#[test]
+fn test_backoff_grows_and_is_capped() {
+    let policy = ReconnectPolicy {
+        initial_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+        multiplier: 2.0,
+        jitter: 0.0,
+        ..ReconnectPolicy::default()
+    };
+
+    assert_eq!(policy.delay(1), Duration::from_millis(100));
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(4), Duration::from_millis(800));
+    assert_eq!(policy.delay(5), Duration::from_secs(1));
+    assert_eq!(policy.delay(1000), Duration::from_secs(1));
+}
+
+#[test]
+fn test_backoff_jitter_stays_within_bounds() {
+    let policy = ReconnectPolicy {
+        initial_delay: Duration::from_secs(1),
+        jitter: 0.2,
+        ..ReconnectPolicy::default()
+    };
+
+    assert_eq!(policy.delay_with(1, 0.0), Duration::from_millis(800));
+    assert_eq!(policy.delay_with(1, 0.5), Duration::from_secs(1));
+    for _ in 0..100 {
+        let delay = policy.delay(1);
+        assert!(delay >= Duration::from_millis(800) && delay < Duration::from_millis(1200), "{:?}", delay);
+    }
+}
+
+#[tokio::test]
+async fn test_outage_buffer_drops_oldest_messages() {
+    let url = format!("ws://127.0.0.1:{}/ws", closed_port().await);
+    let socket = ReconnectingWebSocket::connect("test", &url, ReconnectPolicy {
+        buffer_capacity: 2,
+        ..fast_policy()
+    });
+
+    for message in ["one", "two", "three"] {
+        socket.send(message.to_string());
+    }
+    wait_for(&socket, |status| status.reconnect_attempt >= 2).await;
+
+    let status = socket.status();
+    assert_eq!(status.state, ConnectionState::Reconnecting);
+    assert_eq!(status.buffered_messages, 2);
+    assert_eq!(status.dropped_messages, 1);
+    assert!(status.last_error.is_some());
+}
+
+#[tokio::test]
+async fn test_reconnect_resubscribes_and_flushes_buffer() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}/intelligence", listener.local_addr().unwrap());
+    let socket = ReconnectingWebSocket::connect("intelligence", &url, fast_policy());
+    let mut events = socket.state_changes();
+
+    socket.subscribe("metrics", r#"{"type":"subscribe","stream":"metrics"}"#.to_string());
+    socket.send("one".to_string());
+
+    let (tcp, _) = listener.accept().await.unwrap();
+    let mut server = tokio_tungstenite::accept_async(tcp).await.unwrap();
+    assert_eq!(next_text(&mut server).await, r#"{"type":"subscribe","stream":"metrics"}"#);
+    assert_eq!(next_text(&mut server).await, "one");
+    assert_eq!(events.recv().await.unwrap().state, ConnectionState::Connected);
+
+    // Server restart
+    drop(server);
+    wait_for(&socket, |status| status.state != ConnectionState::Connected).await;
+    socket.send("two".to_string());
+
+    let (tcp, _) = listener.accept().await.unwrap();
+    let mut server = tokio_tungstenite::accept_async(tcp).await.unwrap();
+    assert_eq!(next_text(&mut server).await, r#"{"type":"subscribe","stream":"metrics"}"#);
+    assert_eq!(next_text(&mut server).await, "two");
+
+    wait_for(&socket, |status| status.state == ConnectionState::Connected).await;
+    let status = socket.status();
+    assert_eq!(status.reconnects, 1);
+    assert_eq!(status.reconnect_attempt, 0);
+    assert_eq!(status.active_subscriptions, 1);
+    assert_eq!(status.buffered_messages, 0);
+}
+
+#[tokio::test]
+async fn test_unsubscribed_streams_are_not_resubscribed() {
+    let url = format!("ws://127.0.0.1:{}/intelligence", closed_port().await);
+    let (events, _) = tokio::sync::broadcast::channel(8);
+    let client = IntelligenceClient::connect(&url, fast_policy(), events).await.unwrap();
+
+    client.subscribe_stream("metrics").unwrap();
+    client.subscribe_stream("metrics").unwrap();
+    client.subscribe_stream("errors").unwrap();
+    assert_eq!(client.connection_status().active_subscriptions, 2);
+
+    client.unsubscribe_stream("metrics").unwrap();
+    let status = client.connection_status();
+    assert_eq!(status.name, "intelligence");
+    assert_eq!(status.active_subscriptions, 1);
+}
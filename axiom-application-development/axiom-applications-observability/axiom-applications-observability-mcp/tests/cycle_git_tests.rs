@@ -0,0 +1,217 @@
+mod common;
+
+use axiom_applications_observability::approval_gates::*;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::development_workflow::*;
+use axiom_applications_observability::*;
+use git2::{BranchType, Repository, Signature};
+use std::path::Path;
+use std::sync::Arc;
+
+const CYCLE_ID: &str = "5b0e9c1a-7d2f-4c3e-9a61-0f8d2b7e4c15";
+
+/// A repository on `main` with one commit of a README
+fn project_repository() -> (tempfile::TempDir, Repository) {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    std::fs::write(dir.path().join("README.md"), "# Notes\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("README.md")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now("Dev", "dev@example.com").unwrap();
+    let initial = repo.commit(None, &signature, &signature, "Initial commit", &tree, &[]).unwrap();
+    repo.branch("main", &repo.find_commit(initial).unwrap(), false).unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    drop(tree);
+    (dir, repo)
+}
+
+fn head_branch(repo: &Repository) -> String {
+    repo.head().unwrap().shorthand().unwrap().to_string()
+}
+
+fn stage_commit(stage: CycleStage, files: &[(&str, &str)]) -> StageCommit {
+    StageCommit {
+        cycle_id: CYCLE_ID.to_string(),
+        stage,
+        attempt: 1,
+        summary: format!("{} output", stage),
+        files: files.iter().map(|(name, content)| ReviewArtifact { name: name.to_string(), content: content.to_string() }).collect(),
+    }
+}
+
+async fn development_loop(workflow: development_workflow::AxiomObservabilityWorkflow) -> AxiomObservabilityLoop {
+    common::DevelopmentLoopBuilder::new()
+        .with_workflow(workflow)
+        .build()
+        .await
+}
+
+async fn workflow() -> development_workflow::AxiomObservabilityWorkflow {
+    let hot_reload_client = Arc::new(hot_reload::HotReloadClient::new("ws://localhost:8080/ws").await.unwrap());
+    let intelligence_client = Arc::new(intelligence::IntelligenceClient::new("ws://localhost:8080/intelligence").await.unwrap());
+    let simulator_controller = Arc::new(simulator::SimulatorController::new().await.unwrap());
+    let visual_intelligence = Arc::new(advanced_visual_intelligence::VisualIntelligenceEngine::new().await.unwrap());
+    let screenshot_engine = Arc::new(screenshot_matrix_engine::ScreenshotMatrixEngine::new(simulator_controller.clone()).await.unwrap());
+
+    development_workflow::AxiomObservabilityWorkflow::new(
+        hot_reload_client,
+        simulator_controller,
+        intelligence_client,
+        screenshot_engine,
+        visual_intelligence,
+    ).await.unwrap()
+}
+
+#[test]
+fn test_cycle_gets_its_own_branch_off_head() {
+    let (dir, repo) = project_repository();
+    let base = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+    let branch = CycleRepository::new(dir.path()).start_cycle(CYCLE_ID).unwrap();
+
+    assert_eq!(branch.branch, format!("axiom/cycle-{}", CYCLE_ID));
+    assert_eq!(branch.base_commit, base.to_string());
+    assert_eq!(branch.origin_branch.as_deref(), Some("main"));
+    assert_eq!(head_branch(&repo), branch.branch);
+}
+
+#[test]
+fn test_resuming_a_cycle_checks_out_the_branch_it_already_has() {
+    let (dir, repo) = project_repository();
+    let cycles = CycleRepository::new(dir.path());
+    let first = cycles.start_cycle(CYCLE_ID).unwrap();
+    cycles.commit_stage(stage_commit(CycleStage::Analysis, &[])).unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+
+    let resumed = cycles.start_cycle(CYCLE_ID).unwrap();
+
+    assert_eq!(resumed, first);
+    assert_eq!(head_branch(&repo), first.branch);
+}
+
+#[test]
+fn test_approved_stages_are_committed_with_the_cycle_and_stage() {
+    let (dir, repo) = project_repository();
+    let cycles = CycleRepository::new(dir.path());
+    cycles.start_cycle(CYCLE_ID).unwrap();
+
+    let commit = cycles
+        .commit_stage(stage_commit(CycleStage::Implementation, &[("NoteContext.swift", "final class NoteContext {}\n")]))
+        .unwrap();
+
+    assert_eq!(commit.files, vec![Path::new("Generated/NoteContext.swift").to_path_buf()]);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id().to_string(), commit.commit);
+    let message = head.message().unwrap();
+    assert!(message.starts_with(&format!("Axiom: approve the implementation of cycle {}\n", CYCLE_ID)), "{}", message);
+    assert!(message.contains(&format!("Axiom-Cycle: {}\n", CYCLE_ID)), "{}", message);
+    assert!(message.contains("Axiom-Stage: implementation\n"), "{}", message);
+    assert!(head.tree().unwrap().get_path(Path::new("Generated/NoteContext.swift")).is_ok());
+}
+
+#[test]
+fn test_cycle_diff_covers_every_commit_since_the_branch_point() {
+    let (dir, _repo) = project_repository();
+    let cycles = CycleRepository::new(dir.path());
+    cycles.start_cycle(CYCLE_ID).unwrap();
+    cycles.commit_stage(stage_commit(CycleStage::Analysis, &[])).unwrap();
+    cycles
+        .commit_stage(stage_commit(CycleStage::Implementation, &[("NoteContext.swift", "final class NoteContext {}\n")]))
+        .unwrap();
+
+    let diff = cycles.cycle_diff(CYCLE_ID).unwrap();
+
+    assert_eq!(diff.commits, 2);
+    assert_eq!(diff.files_changed, vec!["Generated/NoteContext.swift"]);
+    assert!(diff.patch.contains("+++ b/Generated/NoteContext.swift"), "{}", diff.patch);
+    assert!(diff.patch.contains("+final class NoteContext {}"), "{}", diff.patch);
+    assert_eq!(ToolResult::CycleDiff(diff).summary(), format!("2 commits on axiom/cycle-{} changing 1 files", CYCLE_ID));
+}
+
+#[test]
+fn test_dirty_working_tree_is_refused_before_branching() {
+    let (dir, repo) = project_repository();
+    std::fs::write(dir.path().join("README.md"), "# Notes\n\nWork in progress\n").unwrap();
+
+    let error = CycleRepository::new(dir.path()).start_cycle(CYCLE_ID).unwrap_err();
+
+    assert!(error.to_string().contains("has uncommitted changes to README.md; commit or stash them first"), "{}", error);
+    assert_eq!(head_branch(&repo), "main");
+    assert!(repo.find_branch(&CycleRepository::branch_name(CYCLE_ID), BranchType::Local).is_err());
+}
+
+#[test]
+fn test_untracked_files_do_not_make_the_tree_dirty() {
+    let (dir, _repo) = project_repository();
+    std::fs::write(dir.path().join("notes.txt"), "scratch\n").unwrap();
+
+    assert!(CycleRepository::new(dir.path()).start_cycle(CYCLE_ID).is_ok());
+}
+
+#[test]
+fn test_branches_the_tool_did_not_create_are_left_alone() {
+    let (dir, repo) = project_repository();
+    let name = CycleRepository::branch_name(CYCLE_ID);
+    repo.branch(&name, &repo.head().unwrap().peel_to_commit().unwrap(), false).unwrap();
+    let cycles = CycleRepository::new(dir.path());
+
+    for error in [
+        cycles.start_cycle(CYCLE_ID).unwrap_err(),
+        cycles.cycle_diff(CYCLE_ID).unwrap_err(),
+        cycles.abandon_cycle(CYCLE_ID).unwrap_err(),
+    ] {
+        assert!(error.to_string().contains("was not created by Axiom; leaving it alone"), "{}", error);
+    }
+    assert!(repo.find_branch(&name, BranchType::Local).is_ok());
+    assert_eq!(head_branch(&repo), "main");
+}
+
+#[test]
+fn test_abandoning_a_cycle_restores_the_original_branch() {
+    let (dir, repo) = project_repository();
+    let cycles = CycleRepository::new(dir.path());
+    cycles.start_cycle(CYCLE_ID).unwrap();
+    cycles
+        .commit_stage(stage_commit(CycleStage::Implementation, &[("NoteContext.swift", "final class NoteContext {}\n")]))
+        .unwrap();
+
+    let abandoned = cycles.abandon_cycle(CYCLE_ID).unwrap();
+
+    assert_eq!(abandoned.discarded_commits, 1);
+    assert_eq!(abandoned.checked_out.as_deref(), Some("main"));
+    assert_eq!(head_branch(&repo), "main");
+    assert!(!dir.path().join("Generated/NoteContext.swift").exists());
+    assert!(repo.find_branch(&abandoned.branch, BranchType::Local).is_err());
+    assert!(repo.config().unwrap().get_string(&format!("branch.{}.axiomCycle", abandoned.branch)).is_err());
+    assert_eq!(std::fs::read_to_string(dir.path().join("README.md")).unwrap(), "# Notes\n");
+}
+
+#[tokio::test]
+async fn test_cycle_commits_each_approved_stage_on_its_branch() {
+    let (dir, repo) = project_repository();
+    let workflow = workflow().await.with_cycle_repository(CycleRepository::new(dir.path()));
+    let gates = Arc::new(ApprovalGates::new(ApprovalPolicy { mode: ApprovalMode::AutoApprove, ..Default::default() }));
+    let development_loop = development_loop(workflow).await.with_approval_gates(gates.clone());
+
+    let result = development_loop.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    let cycle_id = result.cycle_id.clone();
+    assert_eq!(head_branch(&repo), CycleRepository::branch_name(&cycle_id));
+    let diff = development_loop.workflow().get_cycle_diff(&cycle_id).await.unwrap();
+    assert_eq!(diff.commits, gates.policy().gates.len());
+    assert!(diff.files_changed.iter().all(|file| file.starts_with("Generated/")), "{:?}", diff.files_changed);
+    assert!(!diff.files_changed.is_empty());
+}
+
+#[tokio::test]
+async fn test_cycle_on_a_dirty_tree_stops_before_analysis() {
+    let (dir, _repo) = project_repository();
+    std::fs::write(dir.path().join("README.md"), "edited\n").unwrap();
+    let workflow = workflow().await.with_cycle_repository(CycleRepository::new(dir.path()));
+
+    let error = development_loop(workflow).await.execute_complete_development_cycle("Task list".to_string()).await.unwrap_err();
+
+    assert!(error.to_string().contains("has uncommitted changes"), "{}", error);
+}
@@ -0,0 +1,111 @@
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::cycle_report::*;
+use axiom_applications_observability::performance_analysis_integration::PerformanceThresholds;
+use axiom_applications_observability::protocol::{CallToolResult, ToolContent};
+use axiom_applications_observability::visual_regression::{decode_png, encode_png};
+use axiom_applications_observability::*;
+use base64::Engine;
+use image::{Rgba, RgbaImage};
+use serde_json::{json, Value};
+
+const CHECKPOINT: &str = include_str!("fixtures/cycle_checkpoint.json");
+const REPORT: &str = include_str!("fixtures/cycle_report.md");
+const CYCLE_ID: &str = "0b6f4f5e-7d1a-4c36-9a57-3c1c2d0e8a41";
+
+fn checkpoint_json() -> Value {
+    serde_json::from_str(CHECKPOINT).unwrap()
+}
+
+fn checkpoint(json: Value) -> CycleCheckpoint {
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn test_markdown_report_of_a_finished_cycle() {
+    let report = CycleReport::from_checkpoint(&checkpoint(checkpoint_json())).unwrap();
+
+    assert_eq!(report.to_markdown(&PerformanceThresholds::default()), REPORT);
+}
+
+#[test]
+fn test_report_of_a_cycle_that_stopped_after_its_analysis() {
+    let mut json = checkpoint_json();
+    let stages = json["stages"].as_object_mut().unwrap();
+    stages.retain(|stage, _| stage == "analysis");
+    json["iterations"] = json!([]);
+    json["termination"] = Value::Null;
+    json["failure"] = json!({
+        "stage": "implementation",
+        "error": "Code generation timed out",
+        "failed_at": "2026-03-02T09:01:00Z",
+    });
+
+    let markdown = CycleReport::from_checkpoint(&checkpoint(json)).unwrap().to_markdown(&PerformanceThresholds::default());
+
+    assert!(markdown.contains("| 2026-03-02 09:00:00 UTC | 2026-03-02 09:12:30 UTC | 0 | stopped at the implementation |"), "{}", markdown);
+    assert!(markdown.contains("## Generated files\n\nNot reached.\n"));
+    assert!(markdown.contains("## Performance\n\nNot reached.\n"));
+    assert!(markdown.contains("No iteration has completed."));
+    assert!(markdown.ends_with("Stopped at the implementation on 2026-03-02 09:01:00 UTC: Code generation timed out\n\n"));
+}
+
+#[test]
+fn test_unreadable_stage_output_is_a_validation_error() {
+    let mut json = checkpoint_json();
+    json["stages"]["build"]["output"] = json!({ "validation": "passed" });
+
+    let error = CycleReport::from_checkpoint(&checkpoint(json)).unwrap_err();
+
+    assert!(matches!(error, AxiomMCPError::Validation(_)), "{:?}", error);
+    assert!(error.to_string().contains("build"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_html_report_embeds_scaled_down_screenshots() {
+    let dir = tempfile::tempdir().unwrap();
+    let screenshot = dir.path().join("aa11.png");
+    let image = RgbaImage::from_pixel(480, 960, Rgba([20, 20, 40, 255]));
+    std::fs::write(&screenshot, encode_png(&image).unwrap()).unwrap();
+    let mut json = checkpoint_json();
+    json["stages"]["visual_validation"]["output"]["consistency"]["screenshots"][0]["image"]["path"] = json!(screenshot);
+
+    let exporter = CycleReportExporter::new(CycleReportConfig { dir: dir.path().join("reports"), thumbnail_width: 120 });
+    let exported = exporter.export(&checkpoint(json), true).await.unwrap();
+
+    assert_eq!(exported.cycle_id, CYCLE_ID);
+    assert_eq!(exported.termination.map(|termination| termination.reason), Some(TerminationReason::Converged));
+    assert!(exported.markdown_path.ends_with(format!("reports/{}.md", CYCLE_ID)));
+    let html = std::fs::read_to_string(exported.html_path.unwrap()).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<td>title needs 2 lines | has 1</td>"));
+
+    // Only the screenshot that exists is embedded; the others keep their names
+    let prefix = "<img src=\"data:image/png;base64,";
+    assert_eq!(html.matches(prefix).count(), 1);
+    assert!(html.contains("<span class=\"missing\">iphone-dark</span>"));
+    let start = html.find(prefix).unwrap() + prefix.len();
+    let encoded = &html[start..start + html[start..].find('"').unwrap()];
+    let thumbnail = decode_png(&base64::engine::general_purpose::STANDARD.decode(encoded).unwrap()).unwrap();
+    assert_eq!(thumbnail.dimensions(), (120, 240));
+}
+
+#[tokio::test]
+async fn test_exported_report_is_linked_from_the_tool_result() {
+    let dir = tempfile::tempdir().unwrap();
+    let exporter = CycleReportExporter::new(CycleReportConfig { dir: dir.path().to_path_buf(), ..Default::default() });
+    let exported = exporter.export(&checkpoint(checkpoint_json()), false).await.unwrap();
+    let markdown_path = exported.markdown_path.clone();
+
+    let result = CallToolResult::from_result(&ToolResult::CycleReport(exported));
+
+    assert_eq!(result.content.len(), 2);
+    assert_eq!(result.content[1], ToolContent::ResourceLink {
+        uri: format!("file://{}", markdown_path.display()),
+        name: format!("{}.md", CYCLE_ID),
+        mime_type: "text/markdown".to_string(),
+    });
+    let content = serde_json::to_value(&result.content[1]).unwrap();
+    assert_eq!(content["type"], "resource_link");
+    assert_eq!(content["mimeType"], "text/markdown");
+    assert_eq!(std::fs::read_to_string(markdown_path).unwrap(), REPORT);
+}
@@ -0,0 +1,257 @@
+mod common;
+
+use async_trait::async_trait;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::simulator::*;
+use axiom_applications_observability::*;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+const UDID: &str = "A1B2C3D4-0000-0000-0000-000000000003";
+const BUNDLE_ID: &str = "com.axiom.tasks";
+
+/// Simctl with one booted device; when `hang` is set, installing never
+/// finishes, like a cycle killed in the middle of its build stage
+struct FakeSimctl {
+    hang: bool,
+    installing: Notify,
+    commands: Mutex<Vec<String>>,
+}
+
+impl FakeSimctl {
+    fn new(hang: bool) -> Arc<Self> {
+        Arc::new(Self { hang, installing: Notify::new(), commands: Mutex::new(Vec::new()) })
+    }
+
+    fn commands(&self) -> Vec<String> {
+        self.commands.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Simctl for FakeSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        self.commands.lock().unwrap().push(args.join(" "));
+        match args {
+            ["list", "devices", "--json"] => Ok(json!({
+                "devices": {
+                    "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [{
+                        "udid": UDID,
+                        "name": "iPhone 15 Pro",
+                        "state": "Booted",
+                        "isAvailable": true,
+                        "deviceTypeIdentifier": "com.apple.CoreSimulator.SimDeviceType.iPhone-15-Pro",
+                    }]
+                }
+            })
+            .to_string()),
+            ["install", ..] if self.hang => {
+                self.installing.notify_one();
+                std::future::pending().await
+            }
+            ["install", ..] => Ok(String::new()),
+            ["launch", ..] => Ok(format!("{}: 4321\n", BUNDLE_ID)),
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], _env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], _input: &[u8]) -> Result<String> {
+        self.run(args).await
+    }
+}
+
+fn app_bundle() -> (tempfile::TempDir, PathBuf) {
+    let build = tempfile::tempdir().unwrap();
+    let app = build.path().join("Debug-iphonesimulator").join("Tasks.app");
+    std::fs::create_dir_all(&app).unwrap();
+    (build, app)
+}
+
+fn app_run_target(app: PathBuf) -> AppRunTarget {
+    AppRunTarget {
+        install: InstallAppRequest { device: UDID.to_string(), app_path: app },
+        launch: LaunchAppRequest {
+            device: UDID.to_string(),
+            bundle_id: BUNDLE_ID.to_string(),
+            ..Default::default()
+        },
+    }
+}
+
+async fn development_loop(store: CycleStore, simctl: Arc<FakeSimctl>, target: AppRunTarget) -> AxiomObservabilityLoop {
+    common::development_loop()
+        .await
+        .with_app_run(Arc::new(SimulatorController::with_simctl(simctl)), target)
+        .with_checkpoints(store)
+}
+
+/// Start a cycle and kill it once it is installing the app
+async fn killed_cycle(store: &CycleStore, target: AppRunTarget) -> String {
+    let simctl = FakeSimctl::new(true);
+    let blocked = development_loop(store.clone(), simctl.clone(), target).await;
+    let cycle = tokio::spawn(async move {
+        blocked.execute_complete_development_cycle("Task list".to_string()).await
+    });
+    tokio::time::timeout(Duration::from_secs(5), simctl.installing.notified()).await.unwrap();
+    cycle.abort();
+    assert!(cycle.await.unwrap_err().is_cancelled());
+
+    let cycles = store.list().await.unwrap();
+    assert_eq!(cycles.len(), 1);
+    cycles[0].cycle_id.clone()
+}
+
+#[tokio::test]
+async fn test_resume_skips_the_stages_a_killed_cycle_completed() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let (_build, app) = app_bundle();
+
+    let cycle_id = killed_cycle(&store, app_run_target(app.clone())).await;
+    let killed = store.load(&cycle_id).await.unwrap();
+    assert_eq!(killed.completed_stages(), vec![CycleStage::Analysis, CycleStage::Implementation]);
+    assert_eq!(killed.next_stage(), Some(CycleStage::Build));
+    let implementation: Implementation =
+        serde_json::from_value(killed.stages[&CycleStage::Implementation].output.clone()).unwrap();
+
+    let simctl = FakeSimctl::new(false);
+    let resumed = development_loop(store.clone(), simctl.clone(), app_run_target(app))
+        .await
+        .resume_development_cycle(&cycle_id)
+        .await
+        .unwrap();
+
+    assert_eq!(resumed.cycle_id, cycle_id);
+    assert_eq!(resumed.reused_stages, vec![CycleStage::Analysis, CycleStage::Implementation]);
+    assert_eq!(resumed.implementation.implementation_id, implementation.implementation_id);
+    assert!(resumed.success);
+    assert_eq!(resumed.app_run.as_ref().unwrap().launch.as_ref().unwrap().pid, 4321);
+    assert!(resumed.visual_validation.is_some());
+    assert!(simctl.commands().iter().any(|command| command.starts_with("install ")));
+
+    let finished = store.load(&cycle_id).await.unwrap();
+    assert_eq!(finished.completed_stages(), CycleStage::ALL.to_vec());
+    assert_eq!(finished.next_stage(), None);
+}
+
+#[tokio::test]
+async fn test_resuming_a_finished_cycle_reuses_every_stage() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let (_build, app) = app_bundle();
+    let simctl = FakeSimctl::new(false);
+    let development = development_loop(store, simctl.clone(), app_run_target(app)).await;
+
+    let first = development.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+    let commands = simctl.commands().len();
+    let resumed = development.resume_development_cycle(&first.cycle_id).await.unwrap();
+
+    assert!(first.reused_stages.is_empty());
+    assert_eq!(resumed.reused_stages, CycleStage::ALL.to_vec());
+    assert_eq!(resumed.implementation.implementation_id, first.implementation.implementation_id);
+    assert_eq!(simctl.commands().len(), commands, "nothing was run again");
+}
+
+#[tokio::test]
+async fn test_changed_input_reruns_its_stage_and_everything_downstream() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let (_build, app) = app_bundle();
+    let (_other_build, other_app) = app_bundle();
+    let development = development_loop(store.clone(), FakeSimctl::new(false), app_run_target(app)).await;
+    let first = development.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    // An edited analysis changes what the implementation is generated from
+    let mut checkpoint = store.load(&first.cycle_id).await.unwrap();
+    checkpoint.stages.get_mut(&CycleStage::Analysis).unwrap().output["confidence_score"] = json!(60.0);
+    store.save(&checkpoint).await.unwrap();
+    let regenerated = development.resume_development_cycle(&first.cycle_id).await.unwrap();
+
+    assert_eq!(regenerated.reused_stages, vec![CycleStage::Analysis]);
+    assert_eq!(regenerated.requirement_analysis.confidence_score, 60.0);
+    assert_ne!(regenerated.implementation.implementation_id, first.implementation.implementation_id);
+
+    // A different app to run only changes the build and what follows it
+    let rebuilt = development_loop(store, FakeSimctl::new(false), app_run_target(other_app.clone()))
+        .await
+        .resume_development_cycle(&first.cycle_id)
+        .await
+        .unwrap();
+
+    assert_eq!(rebuilt.reused_stages, vec![CycleStage::Analysis, CycleStage::Implementation]);
+    assert_eq!(rebuilt.app_run.unwrap().install.unwrap().app_path, other_app);
+}
+
+#[tokio::test]
+async fn test_unknown_cycles_are_reported() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let (_build, app) = app_bundle();
+    let development = development_loop(CycleStore::new(checkpoints.path()), FakeSimctl::new(false), app_run_target(app)).await;
+
+    for (cycle_id, problem) in [
+        ("0b7e4a4e-0000-0000-0000-000000000000", "No development cycle 0b7e4a4e-0000-0000-0000-000000000000"),
+        ("../secrets", "'../secrets' is not a development cycle id"),
+    ] {
+        match development.resume_development_cycle(cycle_id).await {
+            Err(AxiomMCPError::Validation(context)) => assert_eq!(context.message, problem),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_development_cycle_tools() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let (_build, app) = app_bundle();
+    let cycle_id = killed_cycle(&store, app_run_target(app.clone())).await;
+
+    let config = MCPConfiguration {
+        hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
+        intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
+        simulator_management_enabled: false,
+        code_generation_enabled: true,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        ..Default::default()
+    };
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities)
+        .await
+        .unwrap()
+        .with_development_loop(development_loop(store, FakeSimctl::new(false), app_run_target(app)).await);
+
+    let listed = mcp.execute_tool(AxiomMCPTool::ListDevelopmentCycles).await.unwrap();
+    match &listed {
+        ToolResult::DevelopmentCycles(cycles) => {
+            assert_eq!(cycles[0].cycle_id, cycle_id);
+            assert_eq!(cycles[0].requirement, "Task list");
+            assert_eq!(cycles[0].next_stage, Some(CycleStage::Build));
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(listed.summary(), "1 development cycles, 1 unfinished");
+
+    match mcp.execute_tool(AxiomMCPTool::ResumeDevelopmentCycle(cycle_id)).await.unwrap() {
+        ToolResult::CompleteLoopResult(result) => assert_eq!(result.reused_stages.len(), 2),
+        other => panic!("unexpected result {:?}", other),
+    }
+    match mcp.execute_tool(AxiomMCPTool::ListDevelopmentCycles).await.unwrap() {
+        ToolResult::DevelopmentCycles(cycles) => assert_eq!(cycles[0].next_stage, None),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
@@ -0,0 +1,374 @@
+use async_trait::async_trait;
+use axiom_applications_observability::device_matrix::*;
+use axiom_applications_observability::screenshot_matrix_engine::*;
+use axiom_applications_observability::simulator::{parse_device_list, SimulatorController, SimulatorDevice, StateReset, StateResetOptions, XcrunSimctl};
+use axiom_applications_observability::simulator_pool::*;
+use axiom_applications_observability::tool_registry::validate_arguments;
+use axiom_applications_observability::*;
+use std::sync::{Arc, Mutex};
+
+const SIMCTL_DEVICES: &str = include_str!("fixtures/simctl_devices.json");
+
+fn installed() -> Vec<SimulatorDevice> {
+    parse_device_list(SIMCTL_DEVICES).unwrap()
+}
+
+fn device(model: &str, os_version: Option<&str>) -> MatrixDevice {
+    MatrixDevice {
+        model: model.to_string(),
+        os_version: os_version.map(str::to_string),
+        orientations: vec!["portrait".to_string()],
+        screen_size: None,
+        scale: None,
+    }
+}
+
+fn custom(devices: Vec<MatrixDevice>, substitute_nearest_runtime: bool) -> DeviceMatrixConfig {
+    DeviceMatrixConfig {
+        devices,
+        color_schemes: vec!["light".to_string()],
+        window_configurations: vec![WindowConfiguration::Fullscreen],
+        substitute_nearest_runtime,
+    }
+}
+
+/// Backend whose simulators are those of `simctl list devices`
+struct InstalledBackend {
+    installed: Vec<SimulatorDevice>,
+    booted: Mutex<Vec<PooledDevice>>,
+}
+
+#[async_trait]
+impl SimulatorBackend for InstalledBackend {
+    async fn boot(&self, configuration: &ScreenshotConfiguration, exclude: &[String]) -> Result<PooledDevice> {
+        let (device_type, runtime) = configuration.simulator();
+        let simulator = self.installed
+            .iter()
+            .find(|device| device.name == device_type && Some(device.runtime.as_str()) == runtime && !exclude.contains(&device.udid))
+            .expect("resolved configurations name an installed runtime");
+        let device = PooledDevice {
+            udid: simulator.udid.clone(),
+            device_type: device_type.to_string(),
+            runtime: configuration.runtime.clone(),
+        };
+        self.booted.lock().unwrap().push(device.clone());
+        Ok(device)
+    }
+
+    async fn capture(&self, device: &PooledDevice, configuration: &ScreenshotConfiguration) -> Result<Vec<u8>> {
+        assert_eq!(device.simulator(), configuration.simulator());
+        Ok(vec![1, 2, 3, 4])
+    }
+
+    async fn shutdown(&self, _device: &PooledDevice) -> Result<()> {
+        Ok(())
+    }
+
+    async fn reset_state(&self, _device: &PooledDevice, _options: &StateResetOptions) -> Result<Vec<StateReset>> {
+        Ok(Vec::new())
+    }
+
+    async fn free_memory_mb(&self) -> Result<u64> {
+        Ok(64 * 1024)
+    }
+
+    async fn installed_devices(&self) -> Result<Option<Vec<SimulatorDevice>>> {
+        Ok(Some(self.installed.clone()))
+    }
+}
+
+fn spec(configuration_type: ConfigurationType) -> ScreenshotMatrixSpec {
+    ScreenshotMatrixSpec {
+        matrix_name: "tasks".to_string(),
+        configuration_type,
+        app_states: None,
+        capture_options: CaptureOptions { include_system_ui: false, capture_delay_ms: 0, quality: ImageQuality::High },
+        clean_state: false,
+        status_bar: None,
+        pre_capture_script: None,
+    }
+}
+
+#[test]
+fn test_devices_resolve_against_installed_runtimes() {
+    #[derive(Clone, Copy)]
+    enum Expected {
+        Runtime(&'static str),
+        Substituted(&'static str),
+        Error(&'static str),
+    }
+    use Expected::*;
+
+    let cases = [
+        ("iPhone 15 Pro", None, false, Runtime("iOS 18.0")),
+        ("iPhone 15 Pro", Some("17"), false, Runtime("iOS 17.2")),
+        ("iPhone 15 Pro", Some("17.2"), false, Runtime("iOS 17.2")),
+        ("iPhone 15 Pro", Some("18.0.0"), false, Runtime("iOS 18.0")),
+        ("iPad Pro 13-inch (M4)", None, false, Runtime("iOS 17.2")),
+        ("iPhone 15 Pro", Some("17.4"), true, Substituted("iOS 17.2")),
+        ("iPhone 15 Pro", Some("19"), true, Substituted("iOS 18.0")),
+        ("iPhone 15", Some("18.0"), true, Substituted("iOS 17.2")),
+        ("iPhone 15 Pro", Some("17.4"), false, Error("iPhone 15 Pro needs OS 17.4, which is not installed (installed: iOS 17.2, iOS 18.0)")),
+        ("iPhone 15 Pro", Some("latest"), true, Error("not a version number")),
+        // Its only simulator lost its runtime
+        ("iPhone SE (3rd generation)", None, true, Error("no iPhone SE (3rd generation) simulator is installed")),
+        ("iPhone 14", None, true, Error("no iPhone 14 simulator is installed")),
+    ];
+
+    for (model, os_version, substitute, expected) in cases {
+        let label = format!("{} {:?} substitute={}", model, os_version, substitute);
+        let mut device = device(model, os_version);
+        if model == "iPhone 14" {
+            device.screen_size = Some(ScreenSize { width: 390.0, height: 844.0 });
+            device.scale = Some(3.0);
+        }
+        let result = resolve_device_matrix(&custom(vec![device], substitute), &installed());
+
+        match (expected, result) {
+            (Runtime(runtime) | Substituted(runtime), Ok(resolved)) => {
+                let substituted = matches!(expected, Substituted(_));
+                assert_eq!(resolved.devices[0].runtime, runtime, "{}", label);
+                assert_eq!(resolved.devices[0].substituted, substituted, "{}", label);
+                assert_eq!(resolved.warnings.len(), usize::from(substituted), "{}", label);
+                assert!(resolved.configurations.iter().all(|c| c.runtime.as_deref() == Some(runtime)), "{}", label);
+            }
+            (Error(message), Err(e)) => {
+                assert!(matches!(e, AxiomMCPError::Simulator(_)), "{}: {:?}", label, e);
+                assert!(e.to_string().contains(message), "{}: {}", label, e);
+            }
+            (_, result) => panic!("{}: unexpected {:?}", label, result),
+        }
+    }
+}
+
+#[test]
+fn test_every_unresolvable_device_is_named_at_once() {
+    let matrix = custom(vec![
+        device("iPhone 15 Pro", Some("17.4")),
+        device("iPhone 15", None),
+        device("iPhone SE (3rd generation)", None),
+    ], false);
+
+    let error = resolve_device_matrix(&matrix, &installed()).unwrap_err().to_string();
+
+    assert!(error.contains("iPhone 15 Pro needs OS 17.4"), "{}", error);
+    assert!(error.contains("no iPhone SE (3rd generation) simulator"), "{}", error);
+    assert!(!error.contains("iPhone 15 needs"), "{}", error);
+}
+
+#[test]
+fn test_substituted_runtimes_are_warned_about() {
+    let matrix = custom(vec![device("iPhone 15 Pro", Some("17.4")), device("iPhone 16 Pro Max", Some("18"))], true);
+
+    let resolved = resolve_device_matrix(&matrix, &installed()).unwrap();
+
+    assert_eq!(resolved.warnings, vec!["iPhone 15 Pro: OS 17.4 is not installed; capturing on iOS 17.2 instead"]);
+    assert_eq!(resolved.devices[1], ResolvedDevice {
+        model: "iPhone 16 Pro Max".to_string(),
+        runtime: "iOS 18.0".to_string(),
+        requested_os_version: Some("18".to_string()),
+        substituted: false,
+    });
+    // Known models have their size filled in
+    assert_eq!(resolved.configurations[1].screen_size, ScreenSize { width: 440.0, height: 956.0 });
+    assert_eq!(resolved.configurations[1].scale, 3.0);
+}
+
+#[test]
+fn test_presets_cover_their_devices() {
+    let cases = [
+        (DeviceMatrixPreset::MobileOnly, 8),
+        (DeviceMatrixPreset::TabletOnly, 4),
+        (DeviceMatrixPreset::IpadInclusive, 12),
+        (DeviceMatrixPreset::Marketing, 3),
+        (DeviceMatrixPreset::IpadMultitasking, 8),
+    ];
+    for (preset, expected) in cases {
+        let configurations = preset.matrix().configurations().unwrap();
+        assert_eq!(configurations.len(), expected, "{}", preset.name());
+        assert_eq!(DeviceMatrixPreset::parse(preset.name()), Some(preset));
+        assert_eq!(serde_json::to_value(preset).unwrap(), serde_json::json!(preset.name()));
+    }
+
+    let marketing = DeviceMatrixPreset::Marketing.matrix().configurations().unwrap();
+    assert!(marketing.iter().all(|c| c.orientation == "portrait" && c.color_scheme == "light"));
+    // Earlier matrices named the iPad-inclusive preset all_devices
+    assert_eq!(DeviceMatrixPreset::parse("all_devices"), Some(DeviceMatrixPreset::IpadInclusive));
+    assert_eq!(serde_json::from_str::<DeviceMatrixPreset>("\"all_devices\"").unwrap(), DeviceMatrixPreset::IpadInclusive);
+
+    let error = matrix_configurations(&ConfigurationType::Preset("watch_only".to_string())).unwrap_err().to_string();
+    assert!(error.contains("mobile_only, tablet_only, ipad_inclusive, marketing, ipad_multitasking"), "{}", error);
+}
+
+#[test]
+fn test_ipad_windows_expand_with_their_size_and_size_class() {
+    let multitasking = DeviceMatrixPreset::IpadMultitasking.matrix();
+    assert_eq!(DeviceMatrixPreset::parse("ipad-multitasking"), Some(DeviceMatrixPreset::IpadMultitasking));
+    assert_eq!(serde_json::from_str::<DeviceMatrixPreset>("\"ipad-multitasking\"").unwrap(), DeviceMatrixPreset::IpadMultitasking);
+    assert!(multitasking.warnings().is_empty());
+
+    let configurations = multitasking.configurations().unwrap();
+
+    let windows: Vec<(&str, WindowConfiguration, f64, SizeClass)> = configurations
+        .iter()
+        .map(|c| (c.device_type.as_str(), c.window, c.screen_size.width, c.size_class()))
+        .collect();
+    assert_eq!(windows, vec![
+        ("iPad Pro 13-inch (M4)", WindowConfiguration::Fullscreen, 1376.0, SizeClass::Regular),
+        ("iPad Pro 13-inch (M4)", WindowConfiguration::SplitHalf, 683.0, SizeClass::Regular),
+        ("iPad Pro 13-inch (M4)", WindowConfiguration::SplitThird, 375.0, SizeClass::Compact),
+        ("iPad Pro 13-inch (M4)", WindowConfiguration::SlideOver, 375.0, SizeClass::Compact),
+        ("iPad Air 11-inch (M2)", WindowConfiguration::Fullscreen, 1180.0, SizeClass::Regular),
+        ("iPad Air 11-inch (M2)", WindowConfiguration::SplitHalf, 585.0, SizeClass::Compact),
+        ("iPad Air 11-inch (M2)", WindowConfiguration::SplitThird, 375.0, SizeClass::Compact),
+        ("iPad Air 11-inch (M2)", WindowConfiguration::SlideOver, 375.0, SizeClass::Compact),
+    ]);
+    assert!(configurations.iter().all(|c| c.screen_size.height == 1032.0 || c.screen_size.height == 820.0));
+    assert_eq!(configurations[0].overrides().window, None);
+    assert_eq!(configurations[2].overrides().window, Some(WindowConfiguration::SplitThird));
+    assert_eq!(configurations[5].label(), "iPad Air 11-inch (M2) landscape light split_half (compact)");
+    assert_eq!(configurations[4].label(), "iPad Air 11-inch (M2) landscape light");
+
+    // An iPad in portrait has a narrower third
+    let portrait = DeviceMatrixConfig {
+        window_configurations: vec![WindowConfiguration::SplitThird],
+        ..custom(vec![device("iPad Air 11-inch (M2)", None)], false)
+    };
+    assert_eq!(portrait.configurations().unwrap()[0].screen_size, ScreenSize { width: 320.0, height: 1180.0 });
+    let none = DeviceMatrixConfig { window_configurations: Vec::new(), ..portrait };
+    assert!(none.configurations().unwrap_err().to_string().contains("at least one window configuration"));
+}
+
+#[test]
+fn test_windows_on_other_devices_are_skipped_with_a_warning() {
+    let matrix = DeviceMatrixConfig {
+        window_configurations: vec![WindowConfiguration::Fullscreen, WindowConfiguration::SplitHalf, WindowConfiguration::SlideOver],
+        ..custom(vec![device("iPhone 15 Pro", Some("17.2")), device("iPad Pro 13-inch (M4)", None)], false)
+    };
+
+    let resolved = resolve_device_matrix(&matrix, &installed()).unwrap();
+
+    assert_eq!(resolved.warnings, vec!["iPhone 15 Pro is not an iPad, so it captures fullscreen only and skips split_half, slide_over"]);
+    let windows: Vec<(&str, WindowConfiguration)> = resolved.configurations.iter().map(|c| (c.device_type.as_str(), c.window)).collect();
+    assert_eq!(windows, vec![
+        ("iPhone 15 Pro", WindowConfiguration::Fullscreen),
+        ("iPad Pro 13-inch (M4)", WindowConfiguration::Fullscreen),
+        ("iPad Pro 13-inch (M4)", WindowConfiguration::SplitHalf),
+        ("iPad Pro 13-inch (M4)", WindowConfiguration::SlideOver),
+    ]);
+    assert_eq!(matrix.configurations().unwrap().len(), 4);
+    assert_eq!(matrix.warnings(), resolved.warnings);
+}
+
+#[test]
+fn test_custom_lists_are_validated() {
+    let unknown = custom(vec![device("iPhone 14", None)], false);
+    let mut sideways = device("iPhone 15", None);
+    sideways.orientations = vec!["upside_down".to_string()];
+    let sized = MatrixDevice {
+        screen_size: Some(ScreenSize { width: 390.0, height: 844.0 }),
+        scale: Some(3.0),
+        orientations: vec!["portrait".to_string(), "landscape".to_string()],
+        ..device("iPhone 14", None)
+    };
+    let sepia = DeviceMatrixConfig { color_schemes: vec!["sepia".to_string()], ..custom(vec![device("iPhone 15", None)], false) };
+
+    assert!(unknown.configurations().unwrap_err().to_string().contains("give its screen_size and scale"));
+    assert!(custom(vec![sideways], false).configurations().unwrap_err().to_string().contains("upside_down"));
+    assert!(custom(Vec::new(), false).configurations().is_err());
+    assert!(matches!(sepia.configurations(), Err(AxiomMCPError::Validation(_))));
+
+    let configurations = custom(vec![sized], false).configurations().unwrap();
+    assert_eq!(configurations[1].screen_size, ScreenSize { width: 844.0, height: 390.0 });
+    assert_eq!(configurations[1].runtime, None);
+
+    let both = ScreenshotMatrixRequest {
+        preset: Some(DeviceMatrixPreset::Marketing),
+        custom: Some(custom(vec![device("iPhone 15", None)], false)),
+        ..Default::default()
+    };
+    assert!(both.matrix().is_err());
+    assert_eq!(ScreenshotMatrixRequest::default().matrix().unwrap(), DeviceMatrixPreset::IpadInclusive.matrix());
+}
+
+#[tokio::test]
+async fn test_pooled_matrices_capture_on_the_resolved_runtimes() {
+    let backend = Arc::new(InstalledBackend { installed: installed(), booted: Mutex::new(Vec::new()) });
+    let pool = Arc::new(SimulatorPool::new(backend.clone(), SimulatorPoolConfig::default()));
+    let controller = Arc::new(SimulatorController::with_simctl(Arc::new(XcrunSimctl)));
+    let engine = ScreenshotMatrixEngine::new(controller).await.unwrap().with_pool(pool);
+    let matrix = custom(vec![device("iPhone 15 Pro", Some("17.2")), device("iPhone 15 Pro", Some("18.0"))], false);
+
+    let captured = engine.generate_screenshot_matrix(spec(ConfigurationType::Custom(matrix))).await.unwrap();
+
+    let runtimes: Vec<Option<&str>> = captured.screenshots.iter().map(|s| s.configuration.runtime.as_deref()).collect();
+    assert_eq!(runtimes, vec![Some("iOS 17.2"), Some("iOS 18.0")]);
+    // One simulator per runtime of the same model
+    let mut udids: Vec<String> = backend.booted.lock().unwrap().iter().map(|device| device.udid.clone()).collect();
+    udids.sort();
+    assert_eq!(udids, vec!["A1B2C3D4-0000-4000-8000-000000000172", "D4E5F6A7-0000-4000-8000-000000000180"]);
+
+    let missing = custom(vec![device("iPhone 15", Some("18"))], false);
+    let error = engine.generate_screenshot_matrix(spec(ConfigurationType::Custom(missing))).await.unwrap_err();
+    assert!(error.to_string().contains("iPhone 15 needs OS 18"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_capture_screenshot_matrix_takes_a_preset_or_a_custom_list() {
+    let config = MCPConfiguration::default();
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: true,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap();
+    let marketing = AxiomMCPTool::CaptureScreenshotMatrix(ScreenshotMatrixRequest { preset: Some(DeviceMatrixPreset::Marketing), ..Default::default() });
+    let listed = AxiomMCPTool::CaptureScreenshotMatrix(ScreenshotMatrixRequest {
+        preset: None,
+        custom: Some(custom(vec![device("iPhone 15 Pro", Some("17.2")), device("iPad Air 11-inch (M2)", None)], true)),
+        pre_capture_script: Some("tap open-settings\nwait settings-title".to_string()),
+    });
+
+    for tool in [&marketing, &listed] {
+        let violations = validate_arguments(&tool.spec().input_schema(), &tool.arguments().unwrap());
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+    let ToolResult::ScreenshotMatrix(marketing) = mcp.execute_tool(marketing).await.unwrap() else {
+        panic!("Expected a screenshot matrix");
+    };
+    let ToolResult::ScreenshotMatrix(listed) = mcp.execute_tool(listed).await.unwrap() else {
+        panic!("Expected a screenshot matrix");
+    };
+
+    let models: Vec<&str> = marketing.screenshots.iter().map(|s| s.configuration.device_type.as_str()).collect();
+    assert_eq!(models, vec!["iPhone 16 Pro Max", "iPhone 11 Pro Max", "iPad Pro 13-inch (M4)"]);
+    assert_eq!(listed.analysis.total_screenshots, 2);
+    assert_eq!(listed.screenshots[1].configuration.screen_size, ScreenSize { width: 820.0, height: 1180.0 });
+
+    let split = AxiomMCPTool::CaptureScreenshotMatrix(ScreenshotMatrixRequest {
+        custom: Some(DeviceMatrixConfig {
+            window_configurations: vec![WindowConfiguration::SplitHalf],
+            ..custom(vec![device("iPhone 15", None), device("iPad Air 11-inch (M2)", None)], false)
+        }),
+        ..Default::default()
+    });
+    let violations = validate_arguments(&split.spec().input_schema(), &split.arguments().unwrap());
+    assert!(violations.is_empty(), "{:?}", violations);
+    let ToolResult::ScreenshotMatrix(split) = mcp.execute_tool(split).await.unwrap() else {
+        panic!("Expected a screenshot matrix");
+    };
+    let windows: Vec<WindowConfiguration> = split.screenshots.iter().map(|s| s.configuration.window).collect();
+    assert_eq!(windows, vec![WindowConfiguration::Fullscreen, WindowConfiguration::SplitHalf]);
+    assert_eq!(split.screenshots[1].metadata.overrides.window, Some(WindowConfiguration::SplitHalf));
+    assert_eq!(split.analysis.warnings.len(), 1, "{:?}", split.analysis.warnings);
+
+    let misspelled = AxiomMCPTool::CaptureScreenshotMatrix(ScreenshotMatrixRequest {
+        pre_capture_script: Some("tap open-settings\nwiat settings-title".to_string()),
+        ..Default::default()
+    });
+    let error = mcp.execute_tool(misspelled).await.unwrap_err();
+    assert!(error.to_string().contains("Line 2: Unknown step wiat"), "{}", error);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use async_trait::async_trait;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::error::{ErrorCategory, ErrorReport};
+use axiom_applications_observability::protocol::CallToolResult;
+use axiom_applications_observability::simulator::{select_device, SimulatorDevice};
+use axiom_applications_observability::*;
+use serde_json::json;
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn io_error(kind: ErrorKind) -> AxiomMCPError {
+    std::io::Error::new(kind, "disk trouble").into()
+}
+
+fn git_error(code: git2::ErrorCode) -> AxiomMCPError {
+    git2::Error::new(code, git2::ErrorClass::Index, "index is busy").into()
+}
+
+fn no_devices() -> AxiomMCPError {
+    select_device(&Vec::<SimulatorDevice>::new(), "iPhone 15").unwrap_err()
+}
+
+#[test]
+fn test_failures_map_to_a_category_and_retryability() {
+    let cases: Vec<(&str, AxiomMCPError, ErrorCategory, bool)> = vec![
+        ("capability off", AxiomMCPError::capability_disabled("simulator_management"), ErrorCategory::Configuration, false),
+        ("bad field", AxiomMCPError::configuration("hot_reload_server_url", "not a URL"), ErrorCategory::Configuration, false),
+        ("server gone", AxiomMCPError::connectivity("intelligence", "analyze", "connection closed"), ErrorCategory::Connectivity, true),
+        ("swiftc missing", AxiomMCPError::toolchain("swift_validation", "run swiftc", "not found"), ErrorCategory::Toolchain, false),
+        ("git index locked", git_error(git2::ErrorCode::Locked), ErrorCategory::Toolchain, true),
+        ("git ref missing", git_error(git2::ErrorCode::NotFound), ErrorCategory::Toolchain, false),
+        ("broken template", AxiomMCPError::generation("code_generation", "validate template", "unbalanced"), ErrorCategory::Generation, false),
+        ("unknown simulator", no_devices(), ErrorCategory::Validation, false),
+        ("unknown tool", AxiomMCPError::UnknownTool("fly".to_string()), ErrorCategory::Validation, false),
+        ("wrong state", AxiomMCPError::simulator("simulator", "erase", "booted"), ErrorCategory::Simulator, false),
+        ("still booting", AxiomMCPError::simulator("simulator", "boot", "timed out").transient(), ErrorCategory::Simulator, true),
+        ("unreadable file", io_error(ErrorKind::PermissionDenied), ErrorCategory::Internal, false),
+        ("slow disk", io_error(ErrorKind::TimedOut), ErrorCategory::Internal, true),
+        ("bad JSON", serde_json::from_str::<u32>("{").unwrap_err().into(), ErrorCategory::Internal, false),
+    ];
+
+    for (failure, error, category, retryable) in cases {
+        assert_eq!(error.category(), category, "{}: {:?}", failure, error);
+        assert_eq!(error.is_retryable(), retryable, "{}: {:?}", failure, error);
+    }
+}
+
+#[test]
+fn test_context_keeps_the_operation_and_cause() {
+    let error = io_error(ErrorKind::NotFound);
+
+    let context = error.context().unwrap();
+    assert_eq!((context.subsystem, context.operation.as_str()), ("storage", "file I/O"));
+    assert_eq!(context.cause().unwrap().to_string(), "disk trouble");
+    assert!(std::error::Error::source(&error).is_some());
+    assert_eq!(error.to_string(), "disk trouble");
+}
+
+#[test]
+fn test_failed_tool_calls_report_their_category() {
+    let error = AxiomMCPError::toolchain("git", "commit stage", "index.lock exists")
+        .with_cause(std::io::Error::new(ErrorKind::AlreadyExists, "index.lock"))
+        .transient();
+
+    let result = CallToolResult::from_error(&error);
+
+    assert!(result.is_error);
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured, json!({
+        "category": "toolchain",
+        "subsystem": "git",
+        "operation": "commit stage",
+        "message": "index.lock exists",
+        "cause": "index.lock",
+        "retryable": true,
+    }));
+    let report: ErrorReport = serde_json::from_value(structured).unwrap();
+    assert_eq!(report, error.report());
+
+    let configuration = AxiomMCPError::capability_disabled("code_generation").report();
+    assert_eq!((configuration.field.as_deref(), configuration.subsystem), (Some("code_generation"), None));
+}
+
+/// Fails the first `failures` calls with `error`, then generates with the
+/// real generator
+struct UnreliableGenerator {
+    inner: code_generation::AxiomCodeGenerator,
+    failures: usize,
+    error: fn() -> AxiomMCPError,
+    calls: Mutex<usize>,
+}
+
+impl UnreliableGenerator {
+    async fn new(failures: usize, error: fn() -> AxiomMCPError) -> Arc<Self> {
+        Arc::new(Self {
+            inner: code_generation::AxiomCodeGenerator::new().await.unwrap(),
+            failures,
+            error,
+            calls: Mutex::new(0),
+        })
+    }
+
+    fn calls(&self) -> usize {
+        *self.calls.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl ImplementationGenerator for UnreliableGenerator {
+    async fn generate(&self, analysis: &RequirementAnalysis, feedback: Vec<String>) -> Result<Implementation> {
+        let call = {
+            let mut calls = self.calls.lock().unwrap();
+            *calls += 1;
+            *calls
+        };
+        if call <= self.failures {
+            return Err((self.error)());
+        }
+        self.inner.generate(analysis, feedback).await
+    }
+}
+
+fn dropped_connection() -> AxiomMCPError {
+    AxiomMCPError::connectivity("intelligence", "generate", "connection closed")
+}
+
+fn rejected_spec() -> AxiomMCPError {
+    AxiomMCPError::validation("code_generation", "generate", "Persistence spec has no fields")
+}
+
+async fn development_loop(generator: Arc<UnreliableGenerator>) -> AxiomObservabilityLoop {
+    common::development_loop()
+        .await
+        .with_implementation_generator(generator)
+        .with_retry_policy(StageRetryPolicy { max_attempts: 3, backoff: Duration::ZERO })
+}
+
+#[tokio::test]
+async fn test_loop_retries_a_stage_after_a_retryable_failure() {
+    let generator = UnreliableGenerator::new(2, dropped_connection).await;
+
+    let result = development_loop(generator.clone()).await.execute_complete_development_cycle("Task list".to_string()).await;
+
+    assert!(result.is_ok(), "{:?}", result);
+    assert_eq!(generator.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_loop_stops_when_retries_run_out() {
+    let generator = UnreliableGenerator::new(usize::MAX, dropped_connection).await;
+
+    let result = development_loop(generator.clone()).await.execute_complete_development_cycle("Task list".to_string()).await;
+
+    assert!(matches!(result, Err(AxiomMCPError::Connectivity(_))), "{:?}", result);
+    assert_eq!(generator.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_loop_does_not_retry_a_failure_that_would_repeat() {
+    let generator = UnreliableGenerator::new(1, rejected_spec).await;
+
+    let result = development_loop(generator.clone()).await.execute_complete_development_cycle("Task list".to_string()).await;
+
+    assert!(matches!(result, Err(AxiomMCPError::Validation(_))), "{:?}", result);
+    assert_eq!(generator.calls(), 1);
+}
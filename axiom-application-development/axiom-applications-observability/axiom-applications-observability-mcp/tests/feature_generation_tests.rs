@@ -0,0 +1,155 @@
+use axiom_applications_observability::code_generation::*;
+use axiom_applications_observability::feature_generation::*;
+use axiom_applications_observability::*;
+use std::collections::HashMap;
+
+fn screen(name: &str, actions: &[&str]) -> FeatureScreen {
+    FeatureScreen { name: name.to_string(), actions: actions.iter().map(ToString::to_string).collect() }
+}
+
+fn field(name: &str, field_type: &str) -> PersistedField {
+    PersistedField { name: name.to_string(), field_type: field_type.to_string(), optional: false, default_value: None, unique: false }
+}
+
+fn recipe_feature() -> FeatureSpec {
+    FeatureSpec {
+        name: "RecipeBook".to_string(),
+        entities: vec![FeatureEntity { name: "Recipe".to_string(), fields: vec![field("title", "String"), field("servings", "Int")] }],
+        screens: vec![
+            screen("RecipeList", &["refreshRecipes", "addRecipe"]),
+            screen("RecipeDetail", &["shareRecipe", "addRecipe"]),
+        ],
+        persistence: false,
+        navigation: false,
+        template_overrides: Default::default(),
+    }
+}
+
+fn overriding(kind: TemplateKind, variable: &str, value: &str) -> FeatureSpec {
+    FeatureSpec {
+        template_overrides: [(kind, HashMap::from([(variable.to_string(), value.to_string())]))].into(),
+        ..recipe_feature()
+    }
+}
+
+#[tokio::test]
+async fn test_feature_artifacts_are_named_consistently() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let feature = generator.generate_feature(recipe_feature()).await.unwrap();
+
+    assert!(feature.passed(), "{:?}", feature.integration.issues);
+    assert_eq!(feature.context.name, "RecipeBookContext");
+    assert_eq!(feature.client.name, "RecipeBookClient");
+    let presentations: Vec<&str> = feature.presentations.iter().map(|presentation| presentation.name.as_str()).collect();
+    assert_eq!(presentations, vec!["RecipeListView", "RecipeDetailView"]);
+
+    let context = &feature.context.code.generated_code;
+    assert!(context.contains("private let client: RecipeBookClient"), "{}", context);
+    assert!(context.contains("@Published var recipes: [Recipe] = []"), "{}", context);
+    assert!(context.contains("func shareRecipe() async {\n        await client.shareRecipe()"), "{}", context);
+    assert!(feature.client.code.generated_code.contains("actor RecipeBookClient"));
+    assert!(feature.presentations[1].code.generated_code.contains("@EnvironmentObject var context: RecipeBookContext"));
+    assert!(feature.presentations[1].code.generated_code.contains("Button(\"Share Recipe\") { Task { await context.shareRecipe() } }"));
+
+    // Each action once, with every screen calling it
+    let actions: Vec<&str> = feature.integration.actions.keys().map(String::as_str).collect();
+    assert_eq!(actions, vec!["addRecipe", "refreshRecipes", "shareRecipe"]);
+    assert_eq!(feature.integration.actions["addRecipe"], vec!["RecipeListView", "RecipeDetailView"]);
+    assert_eq!(feature.client.code.generated_code.matches("func addRecipe(").count(), 1);
+    assert!(feature.persistence.is_empty() && feature.navigation.is_none());
+}
+
+#[tokio::test]
+async fn test_mismatched_client_binding_is_reported() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let feature = generator
+        .generate_feature(overriding(TemplateKind::Context, "client_binding", "RecipeStoreClient"))
+        .await
+        .unwrap();
+
+    assert!(!feature.passed());
+    assert_eq!(feature.integration.issues.len(), 1, "{:?}", feature.integration.issues);
+    let issue = &feature.integration.issues[0];
+    assert_eq!(issue.kind, ReferenceKind::ClientBinding);
+    assert_eq!((issue.artifact.as_str(), issue.reference.as_str()), ("RecipeBookContext", "RecipeStoreClient"));
+    assert!(issue.message.contains("RecipeBookClient"), "{}", issue.message);
+}
+
+#[tokio::test]
+async fn test_missing_actions_and_context_binding_are_reported() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let without_actions = generator
+        .generate_feature(overriding(TemplateKind::Client, "actions", "    func refreshRecipes() async {}"))
+        .await
+        .unwrap();
+    let wrong_context = generator
+        .generate_feature(overriding(TemplateKind::Presentation, "context_binding", "RecipeContext"))
+        .await
+        .unwrap();
+
+    let missing: Vec<(ReferenceKind, &str)> = without_actions.integration.issues.iter()
+        .map(|issue| (issue.kind, issue.reference.as_str()))
+        .collect();
+    assert_eq!(missing, vec![(ReferenceKind::ClientAction, "addRecipe"), (ReferenceKind::ClientAction, "shareRecipe")]);
+    assert_eq!(wrong_context.integration.issues.len(), 2, "{:?}", wrong_context.integration.issues);
+    assert!(wrong_context.integration.issues.iter().all(|issue| issue.kind == ReferenceKind::ContextBinding && issue.reference == "RecipeContext"));
+}
+
+#[tokio::test]
+async fn test_feature_with_persistence_and_navigation() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let spec = FeatureSpec { persistence: true, navigation: true, ..recipe_feature() };
+
+    let specs = spec.derive_specs().unwrap();
+    let feature = generator.generate_feature(spec).await.unwrap();
+
+    assert!(feature.passed(), "{:?}", feature.integration.issues);
+    assert_eq!(specs.client.persistence.as_ref().map(|persistence| persistence.entity_name.as_str()), Some("Recipe"));
+    assert_eq!(feature.persistence.len(), 1);
+    assert!(feature.persistence[0].code.generated_code.contains("@Model"));
+    assert!(feature.client.code.generated_code.contains("func loadRecipes() async throws -> [Recipe]"));
+
+    let navigation = feature.navigation.as_ref().expect("navigation was asked for");
+    assert!(navigation.code.generated_code.contains("case recipeDetail"), "{}", navigation.code.generated_code);
+    assert_eq!(feature.integration.routes, vec!["recipeDetail"]);
+    assert_eq!(feature.artifacts().count(), 6);
+}
+
+#[tokio::test]
+async fn test_feature_specs_are_validated() {
+    let single_screen = FeatureSpec { navigation: true, screens: vec![screen("RecipeList", &[])], ..recipe_feature() };
+    let without_entities = FeatureSpec { persistence: true, entities: vec![], ..recipe_feature() };
+    let clashing = FeatureSpec { persistence: true, screens: vec![screen("RecipeList", &["saveRecipe"])], ..recipe_feature() };
+    let bad_action = FeatureSpec { screens: vec![screen("RecipeList", &["add recipe"])], ..recipe_feature() };
+    let duplicate = FeatureSpec { screens: vec![screen("RecipeList", &[]), screen("RecipeListView", &[])], ..recipe_feature() };
+
+    for spec in [single_screen, without_entities, clashing, bad_action, duplicate] {
+        let result = spec.derive_specs();
+        assert!(matches!(result, Err(AxiomMCPError::Validation(_))), "{:?}", result.map(|specs| specs.context.name));
+    }
+}
+
+#[tokio::test]
+async fn test_generate_feature_tool() {
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(MCPConfiguration::default(), capabilities).await.unwrap();
+
+    let result = mcp.execute_tool(AxiomMCPTool::GenerateFeature(recipe_feature())).await.unwrap();
+
+    let ToolResult::GeneratedFeature(feature) = &result else {
+        panic!("Expected a generated feature, got {:?}", result);
+    };
+    assert_eq!(feature.name, "RecipeBook");
+    assert!(result.is_success());
+    assert!(result.summary().contains("4 artifacts of RecipeBook"), "{}", result.summary());
+}
@@ -0,0 +1,189 @@
+use axiom_applications_observability::axiom_framework_integration::*;
+use axiom_applications_observability::code_generation::*;
+use axiom_applications_observability::*;
+use std::path::Path;
+
+const RESOLVED_V1: &str = include_str!("fixtures/package_resolved_v1.json");
+const RESOLVED_V2: &str = include_str!("fixtures/package_resolved_v2.json");
+
+fn version(text: &str) -> FrameworkVersion {
+    FrameworkVersion::parse(text).unwrap()
+}
+
+fn detected(package: FrameworkPackage, text: &str) -> DetectedFramework {
+    DetectedFramework { package, version: Some(version(text)), source: VersionSource::Configured }
+}
+
+fn write(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+fn context_spec() -> ContextSpec {
+    ContextSpec {
+        name: "ProfileContext".to_string(),
+        state_properties: vec![],
+        client_binding: "ProfileClient".to_string(),
+        lifecycle_management: true,
+        template_overrides: Default::default(),
+    }
+}
+
+#[test]
+fn test_versions_parse_and_order() {
+    assert_eq!(version("1.2.3"), FrameworkVersion::new(1, 2, 3));
+    assert_eq!(version("v2.0"), FrameworkVersion::new(2, 0, 0));
+    assert_eq!(version("2.0.0-beta.1"), FrameworkVersion::new(2, 0, 0));
+    assert!(version("1.10.0") > version("1.9.9"));
+    for invalid in ["", "main", "1.2.3.4", "1.x"] {
+        assert_eq!(FrameworkVersion::parse(invalid), None, "{}", invalid);
+    }
+    assert_eq!(serde_json::to_value(version("1.1")).unwrap(), serde_json::json!("1.1.0"));
+}
+
+#[test]
+fn test_package_resolved_v1_pins() {
+    let pins = parse_package_resolved(RESOLVED_V1).unwrap();
+
+    let identities: Vec<&str> = pins.iter().map(|pin| pin.identity.as_str()).collect();
+    assert_eq!(identities, vec!["axiomcore", "axiomarchitecture", "swift-collections"]);
+    assert_eq!(pins[0].version, Some(version("1.1.4")));
+    // Pinned to a branch, so only the branch and revision are known
+    assert_eq!(pins[1].version, None);
+    assert_eq!(pins[1].branch.as_deref(), Some("main"));
+    assert_eq!(pins[1].location, "https://github.com/tojkuv/AxiomArchitecture");
+}
+
+#[test]
+fn test_package_resolved_v2_pins() {
+    let pins = parse_package_resolved(RESOLVED_V2).unwrap();
+
+    assert_eq!(pins.len(), 2);
+    assert_eq!((pins[0].identity.as_str(), pins[0].version), ("axiom", Some(version("1.0.2"))));
+    assert_eq!(pins[0].location, "https://github.com/tojkuv/Axiom.git");
+    assert_eq!(pins[1].version, Some(version("600.0.1")));
+
+    let malformed = parse_package_resolved(r#"{ "version": 2, "pins": "none" }"#);
+    assert!(matches!(malformed, Err(AxiomMCPError::Validation(_))), "{:?}", malformed);
+}
+
+#[tokio::test]
+async fn test_versions_are_detected_from_the_project() {
+    let package = tempfile::tempdir().unwrap();
+    write(&package.path().join(PACKAGE_RESOLVED_FILE), RESOLVED_V1);
+    let app = tempfile::tempdir().unwrap();
+    let resolved = app.path().join("App.xcodeproj/project.xcworkspace/xcshareddata/swiftpm").join(PACKAGE_RESOLVED_FILE);
+    write(&resolved, RESOLVED_V2);
+    let empty = tempfile::tempdir().unwrap();
+
+    let from_package = detect_framework_versions(package.path(), &AxiomFrameworkConfig::default()).await.unwrap();
+    let from_app = detect_framework_versions(app.path(), &AxiomFrameworkConfig::default()).await.unwrap();
+    let undetected = detect_framework_versions(empty.path(), &AxiomFrameworkConfig::default()).await.unwrap();
+
+    assert_eq!(from_package[0].version, Some(version("1.1.4")));
+    assert_eq!(from_package[1].version, None);
+    assert!(matches!(&from_package[1].source, VersionSource::PackageResolved { identity, .. } if identity == "axiomarchitecture"));
+    // A single Axiom package provides both frameworks
+    assert!(from_app.iter().all(|framework| framework.version == Some(version("1.0.2"))));
+    assert!(matches!(&from_app[0].source, VersionSource::PackageResolved { path, .. } if *path == resolved));
+    assert!(undetected.iter().all(|framework| framework.version.is_none() && framework.source == VersionSource::Undetected));
+}
+
+#[tokio::test]
+async fn test_configured_versions_win_over_package_resolved() {
+    let project = tempfile::tempdir().unwrap();
+    write(&project.path().join(PACKAGE_RESOLVED_FILE), RESOLVED_V1);
+    let config = AxiomFrameworkConfig { core_version: None, architecture_version: Some(version("1.3.0")) };
+
+    let frameworks = detect_framework_versions(project.path(), &config).await.unwrap();
+
+    assert_eq!(frameworks, vec![
+        DetectedFramework {
+            package: FrameworkPackage::AxiomCore,
+            version: Some(version("1.1.4")),
+            source: VersionSource::PackageResolved { path: project.path().join(PACKAGE_RESOLVED_FILE), identity: "axiomcore".to_string() },
+        },
+        detected(FrameworkPackage::AxiomArchitecture, "1.3.0"),
+    ]);
+
+    // Nothing is read when every version is configured
+    write(&project.path().join(PACKAGE_RESOLVED_FILE), "not json");
+    let config = AxiomFrameworkConfig { core_version: Some(version("1.2.0")), ..config };
+    assert!(detect_framework_versions(project.path(), &config).await.is_ok());
+    assert!(detect_framework_versions(project.path(), &AxiomFrameworkConfig::default()).await.is_err());
+}
+
+#[test]
+fn test_features_below_their_floor_are_downgraded() {
+    let old = FrameworkCompatibility::for_frameworks(vec![
+        detected(FrameworkPackage::AxiomCore, "1.1.4"),
+        detected(FrameworkPackage::AxiomArchitecture, "1.0.9"),
+    ]);
+    let at_floor = FrameworkCompatibility::for_frameworks(vec![
+        detected(FrameworkPackage::AxiomCore, "1.2.0"),
+        detected(FrameworkPackage::AxiomArchitecture, "1.1.0"),
+    ]);
+    let unknown = FrameworkCompatibility::for_frameworks(vec![DetectedFramework {
+        package: FrameworkPackage::AxiomArchitecture,
+        version: None,
+        source: VersionSource::Undetected,
+    }]);
+
+    let context = old.downgrade(GenerationFeature::ClientObservingContext).expect("context downgraded");
+    assert_eq!((context.detected, context.minimum), (version("1.0.9"), version("1.1.0")));
+    assert!(context.fallback.is_some());
+    assert_eq!(old.context_base_class(), LEGACY_CONTEXT_BASE_CLASS);
+    // Persistence has no older pattern, so it is only warned about
+    let persistence = old.downgrade(GenerationFeature::SwiftDataPersistence).expect("persistence downgraded");
+    assert_eq!(persistence.fallback, None);
+    assert!(old.warnings.iter().any(|warning| warning.contains("AxiomCore 1.1.4 is below 1.2.0")), "{:?}", old.warnings);
+
+    assert!(at_floor.downgrades.is_empty() && at_floor.warnings.is_empty());
+    assert_eq!(at_floor.context_base_class(), CONTEXT_BASE_CLASS);
+    assert!(unknown.downgrades.is_empty());
+    assert_eq!(unknown.warnings, vec!["AxiomArchitecture version not detected; generating for the latest APIs"]);
+}
+
+#[tokio::test]
+async fn test_generated_contexts_follow_the_framework_version() {
+    let old = FrameworkCompatibility::for_frameworks(vec![detected(FrameworkPackage::AxiomArchitecture, "1.0.0")]);
+    let latest = AxiomCodeGenerator::new().await.unwrap();
+    let legacy = AxiomCodeGenerator::new().await.unwrap().with_framework_compatibility(old);
+
+    let modern = latest.generate_context(context_spec()).await.unwrap().generated_code;
+    let downgraded = legacy.generate_context(context_spec()).await.unwrap().generated_code;
+
+    assert!(modern.contains("class ProfileContext: AxiomClientObservingContext {"), "{}", modern);
+    assert!(downgraded.contains("class ProfileContext: AxiomContext {"), "{}", downgraded);
+}
+
+#[tokio::test]
+async fn test_check_framework_compatibility_tool() {
+    let config = MCPConfiguration {
+        axiom_framework: AxiomFrameworkConfig { core_version: Some(version("1.1.0")), architecture_version: Some(version("1.0.0")) },
+        ..MCPConfiguration::default()
+    };
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap();
+
+    let result = mcp.execute_tool(AxiomMCPTool::CheckFrameworkCompatibility).await.unwrap();
+
+    let ToolResult::FrameworkCompatibility(compatibility) = &result else {
+        panic!("Expected a compatibility report, got {:?}", result);
+    };
+    assert_eq!(compatibility.version(FrameworkPackage::AxiomCore), Some(version("1.1.0")));
+    assert_eq!(compatibility.downgrades.len(), 2);
+    assert_eq!(result.summary(), "Generating for AxiomCore 1.1.0, AxiomArchitecture 1.0.0 (2 features downgraded)");
+
+    let ToolResult::GeneratedCode(context) = mcp.execute_tool(AxiomMCPTool::GenerateContext(context_spec())).await.unwrap() else {
+        panic!("Expected generated code");
+    };
+    assert!(context.generated_code.contains(": AxiomContext {"), "{}", context.generated_code);
+}
@@ -0,0 +1,178 @@
+use axiom_applications_observability::code_generation::*;
+use axiom_applications_observability::generation_cache::*;
+use axiom_applications_observability::*;
+use std::sync::Arc;
+
+const SENTINEL: &str = "// project-presentation-sentinel";
+
+fn presentation_spec(name: &str) -> PresentationSpec {
+    PresentationSpec {
+        name: name.to_string(),
+        context_binding: "TaskListContext".to_string(),
+        ui_components: vec!["List".to_string(), "Button".to_string()],
+        accessibility_requirements: vec!["VoiceOver".to_string(), "Dynamic Type".to_string()],
+        performance_requirements: PerformanceRequirements { max_render_time_ms: 16.0, max_memory_mb: 10.0 },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
+    }
+}
+
+fn with_overrides(mut spec: PresentationSpec, overrides: &[(&str, &str)]) -> PresentationSpec {
+    spec.template_overrides = overrides.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+    spec
+}
+
+fn counts(generator: &AxiomCodeGenerator) -> (u64, u64) {
+    let stats = generator.cache_stats();
+    (stats.hits, stats.misses)
+}
+
+#[tokio::test]
+async fn test_identical_spec_is_generated_once() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let first = generator.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+    let second = generator.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+
+    assert_eq!(first.generated_code, second.generated_code);
+    assert_eq!(counts(&generator), (1, 1));
+    assert_eq!(generator.cache_stats().entries, 1);
+}
+
+#[tokio::test]
+async fn test_order_of_unordered_fields_does_not_miss() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let spec = with_overrides(presentation_spec("TaskListView"), &[("title", "Tasks"), ("imports", "import SwiftUI")]);
+    let mut reordered = with_overrides(presentation_spec("TaskListView"), &[("imports", "import SwiftUI"), ("title", "Tasks")]);
+    reordered.accessibility_requirements.reverse();
+
+    generator.generate_presentation(spec).await.unwrap();
+    generator.generate_presentation(reordered).await.unwrap();
+    assert_eq!(counts(&generator), (1, 1));
+
+    // Components are rendered in order, so their order is part of the key
+    let mut components_reordered = presentation_spec("TaskListView");
+    components_reordered.ui_components.reverse();
+    generator.generate_presentation(components_reordered).await.unwrap();
+    assert_eq!(counts(&generator), (1, 2));
+}
+
+#[tokio::test]
+async fn test_changed_override_misses() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let tasks = generator.generate_presentation(with_overrides(presentation_spec("TaskListView"), &[("title", "Tasks")])).await.unwrap();
+    let inbox = generator.generate_presentation(with_overrides(presentation_spec("TaskListView"), &[("title", "Inbox")])).await.unwrap();
+
+    assert_eq!(counts(&generator), (0, 2));
+    assert!(tasks.generated_code.contains("Tasks"));
+    assert!(inbox.generated_code.contains("Inbox"));
+}
+
+#[tokio::test]
+async fn test_project_template_invalidates_a_shared_cache() {
+    let cache = Arc::new(GenerationCache::default());
+    let built_in = AxiomCodeGenerator::new().await.unwrap().with_cache(cache.clone());
+    built_in.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+
+    let project = tempfile::tempdir().unwrap();
+    let dir = project.path().join(PROJECT_TEMPLATES_DIR);
+    std::fs::create_dir_all(&dir).unwrap();
+    let template = format!("{}\n{}", SENTINEL, TemplateKind::Presentation.built_in());
+    std::fs::write(dir.join(TemplateKind::Presentation.file_name()), template).unwrap();
+    let overridden = AxiomCodeGenerator::for_project(project.path()).await.unwrap().with_cache(cache.clone());
+
+    let code = overridden.generate_presentation(presentation_spec("TaskListView")).await.unwrap().generated_code;
+    assert!(code.starts_with(SENTINEL), "{}", code);
+    assert_eq!((cache.stats().hits, cache.stats().misses), (0, 2));
+
+    overridden.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+    built_in.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+    assert_eq!((cache.stats().hits, cache.stats().misses), (2, 2));
+}
+
+#[tokio::test]
+async fn test_least_recently_used_generation_is_evicted() {
+    let generator = AxiomCodeGenerator::new().await.unwrap().with_cache(Arc::new(GenerationCache::new(2)));
+
+    generator.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+    generator.generate_presentation(presentation_spec("TaskDetailView")).await.unwrap();
+    generator.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+    generator.generate_presentation(presentation_spec("SettingsView")).await.unwrap();
+
+    let stats = generator.cache_stats();
+    assert_eq!((stats.entries, stats.evictions), (2, 1));
+
+    generator.generate_presentation(presentation_spec("TaskListView")).await.unwrap();
+    assert_eq!(counts(&generator), (2, 3));
+    generator.generate_presentation(presentation_spec("TaskDetailView")).await.unwrap();
+    assert_eq!(counts(&generator), (2, 4));
+}
+
+#[tokio::test]
+async fn test_validation_is_cached_by_code() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let code = generator.generate_presentation(presentation_spec("TaskListView")).await.unwrap().generated_code;
+
+    let first = generator.validate_generated_code(&code).await.unwrap();
+    let second = generator.validate_generated_code(&code).await.unwrap();
+
+    assert_eq!(first.passed, second.passed);
+    assert_eq!(first.recommendations, second.recommendations);
+    assert_eq!(counts(&generator), (1, 2));
+    assert_eq!(generator.cache_stats().entries, 2);
+}
+
+#[tokio::test]
+async fn test_failed_generation_is_not_cached() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let spec = NavigationSpec { root: "TaskListView".to_string(), routes: Vec::new() };
+
+    assert!(generator.generate_navigation(spec.clone()).await.is_err());
+    assert!(generator.generate_navigation(spec).await.is_err());
+
+    assert_eq!(counts(&generator), (0, 2));
+    assert_eq!(generator.cache_stats().entries, 0);
+}
+
+#[test]
+fn test_spec_hash_is_stable_and_scoped_by_operation_and_version() {
+    let spec = presentation_spec("TaskListView");
+    let hash = spec_hash("presentation", &spec, "v1").unwrap();
+
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, spec_hash("presentation", &spec.clone(), "v1").unwrap());
+    assert_ne!(hash, spec_hash("context", &spec, "v1").unwrap());
+    assert_ne!(hash, spec_hash("presentation", &spec, "v2").unwrap());
+}
+
+#[tokio::test]
+async fn test_clear_generation_cache_tool() {
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let config = MCPConfiguration {
+        simulator_management_enabled: false,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        ..Default::default()
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap();
+    for _ in 0..2 {
+        mcp.execute_tool(AxiomMCPTool::GeneratePresentation(presentation_spec("TaskListView"))).await.unwrap();
+    }
+
+    let result = mcp.execute_tool(AxiomMCPTool::ClearGenerationCache).await.unwrap();
+    assert_eq!(result.summary(), "Cleared 1 cached generations (1 hits, 1 misses, 50% hit rate)");
+
+    mcp.execute_tool(AxiomMCPTool::GeneratePresentation(presentation_spec("TaskListView"))).await.unwrap();
+    match mcp.execute_tool(AxiomMCPTool::ClearGenerationCache).await.unwrap() {
+        ToolResult::GenerationCacheCleared(stats) => assert_eq!((stats.entries, stats.hits, stats.misses), (1, 1, 2)),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
@@ -0,0 +1,230 @@
+use axiom_applications_observability::connection::ReconnectPolicy;
+use axiom_applications_observability::error::ErrorCategory;
+use axiom_applications_observability::hot_reload::{HotReloadClient, ReloadMessage};
+use axiom_applications_observability::hot_reload_protocol::*;
+use axiom_applications_observability::*;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+/// One message of every type, as the client and server wrote them
+const FRAMES: &str = include_str!("fixtures/hot_reload_v1_frames.jsonl");
+
+fn frame(message_type: MessageType) -> Value {
+    FRAMES.lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .find(|frame| frame["type"] == message_type.name())
+        .unwrap_or_else(|| panic!("No {} fixture", message_type.name()))
+}
+
+fn violation(result: Result<ServerMessage>) -> (String, Vec<String>, AxiomMCPError) {
+    let error = result.unwrap_err();
+    let AxiomMCPError::ProtocolViolation { message_type, violations, .. } = &error else {
+        panic!("Expected a protocol violation, got {:?}", error);
+    };
+    let paths = violations.iter().map(|violation| violation.path.clone()).collect();
+    (message_type.clone(), paths, error)
+}
+
+#[test]
+fn test_fixtures_cover_every_message_type_in_both_directions() {
+    let checked: Vec<MessageType> = FRAMES.lines().map(|line| check_message(line).unwrap().0).collect();
+
+    assert_eq!(checked, MessageType::ALL.to_vec());
+    assert!(checked.iter().any(|message_type| message_type.direction() == Direction::ToServer));
+    assert!(checked.iter().any(|message_type| message_type.direction() == Direction::FromServer));
+}
+
+#[test]
+fn test_client_messages_round_trip_through_their_types() {
+    let reload: ReloadMessage = serde_json::from_value(frame(MessageType::FilesChanged)).unwrap();
+    let sent_ping = serde_json::to_value(ping("5d1f8a2c-3b6e-4c9d-8e7f-0a1b2c3d4e5f", "axiom-applications-observability 0.1.0")).unwrap();
+
+    assert_eq!(serde_json::to_value(&reload).unwrap(), frame(MessageType::FilesChanged));
+    assert_eq!(reload.payload.component_ids, vec!["TaskListView", "TaskRow"]);
+    assert_eq!(sent_ping, frame(MessageType::Ping));
+    assert!(check_message(&sent_ping.to_string()).is_ok());
+}
+
+#[test]
+fn test_server_messages_decode_into_their_types() {
+    let pong = decode_server_message(&frame(MessageType::Pong).to_string()).unwrap();
+    let state = decode_server_message(&frame(MessageType::StateSync).to_string()).unwrap();
+    let error = decode_server_message(&frame(MessageType::Error).to_string()).unwrap();
+
+    assert_eq!(pong.message_type(), MessageType::Pong);
+    assert_eq!(pong.version(), PROTOCOL_VERSION);
+    let ServerMessage::StateSync(state) = state else { panic!("Expected a state sync") };
+    assert_eq!(state.payload.state["selectedTab"], "tasks");
+    assert_eq!(serde_json::to_value(&state).unwrap(), frame(MessageType::StateSync));
+    let ServerMessage::Error(error) = error else { panic!("Expected an error") };
+    assert_eq!(error.payload.code, "compilation_failed");
+}
+
+#[test]
+fn test_unknown_field_names_the_message_type_and_field() {
+    let mut pong = frame(MessageType::Pong);
+    pong["payload"]["latencyMs"] = json!(12);
+
+    let (message_type, paths, error) = violation(decode_server_message(&pong.to_string()));
+
+    assert_eq!(message_type, "pong");
+    assert_eq!(paths, vec!["/payload/latencyMs"]);
+    assert_eq!(error.category(), ErrorCategory::Configuration);
+    assert!(!error.is_retryable());
+    assert_eq!(error.report().field.as_deref(), Some("/payload/latencyMs"));
+    assert!(error.to_string().starts_with("The pong message on the hot_reload connection does not match the protocol: /payload/latencyMs: unknown property"), "{}", error);
+}
+
+#[test]
+fn test_missing_and_mistyped_fields_are_named() {
+    let mut error = frame(MessageType::Error);
+    error["payload"].as_object_mut().unwrap().remove("code");
+    let mut state = frame(MessageType::StateSync);
+    state["payload"]["state"]["taskCount"] = json!(3);
+
+    let (message_type, paths, missing) = violation(decode_server_message(&error.to_string()));
+    let (_, mistyped, _) = violation(decode_server_message(&state.to_string()));
+
+    assert_eq!(message_type, "error");
+    assert_eq!(paths, vec!["/payload"]);
+    assert!(missing.to_string().contains("missing required property `code`"), "{}", missing);
+    assert_eq!(mistyped, vec!["/payload/state/taskCount"]);
+}
+
+#[test]
+fn test_other_protocol_versions_and_unknown_types_are_violations() {
+    let mut newer = frame(MessageType::Pong);
+    newer["version"] = json!("2.0.0");
+    let mut renamed = frame(MessageType::StateSync);
+    renamed["type"] = json!("state_snapshot");
+
+    let (_, version, _) = violation(decode_server_message(&newer.to_string()));
+    let (message_type, unknown, _) = violation(decode_server_message(&renamed.to_string()));
+    let (_, client_only, _) = violation(decode_server_message(&frame(MessageType::Ping).to_string()));
+    let (_, not_json, _) = violation(decode_server_message("{\"type\":"));
+
+    assert_eq!(version, vec!["/version"]);
+    assert_eq!((message_type.as_str(), unknown), ("state_snapshot", vec!["/type".to_string()]));
+    assert_eq!(client_only, vec!["/type"]);
+    assert_eq!(not_json, vec![""]);
+}
+
+/// A hot reload server answering each ping with `reply`, after
+/// sending `before` unprompted
+async fn server(before: Vec<Value>, reply: impl Fn(&Value) -> Option<Value> + Send + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}/ws", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+        while let Some(Ok(message)) = socket.next().await {
+            let Message::Text(text) = message else { continue };
+            let message: Value = serde_json::from_str(&text).unwrap();
+            if message["type"] != "ping" {
+                continue;
+            }
+            for frame in &before {
+                socket.send(Message::Text(frame.to_string())).await.unwrap();
+            }
+            if let Some(pong) = reply(&message) {
+                socket.send(Message::Text(pong.to_string())).await.unwrap();
+            }
+        }
+    });
+    url
+}
+
+fn echo(ping: &Value) -> Value {
+    let mut pong = frame(MessageType::Pong);
+    pong["payload"] = ping["payload"].clone();
+    pong
+}
+
+async fn client(url: &str) -> HotReloadClient {
+    let policy = ReconnectPolicy { initial_delay: Duration::from_millis(20), jitter: 0.0, ..ReconnectPolicy::default() };
+    HotReloadClient::connect(url, policy, tokio::sync::broadcast::channel(8).0).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_handshake_checks_every_message_up_to_the_pong() {
+    let url = server(vec![frame(MessageType::StateSync), frame(MessageType::Pong)], |ping| Some(echo(ping))).await;
+
+    let handshake = client(&url).await.validate_connection(Duration::from_secs(5)).await.unwrap();
+
+    assert_eq!(handshake.url, url);
+    assert_eq!(handshake.server_version, PROTOCOL_VERSION);
+    // The fixture pong answers another ping, so it is checked and passed over
+    assert_eq!(handshake.messages_checked, 3);
+}
+
+#[tokio::test]
+async fn test_handshake_fails_on_a_server_that_strays_from_the_schema() {
+    let url = server(Vec::new(), |ping| {
+        let mut pong = echo(ping);
+        pong["payload"]["serverTime"] = json!("2026-10-15T09:30:00Z");
+        Some(pong)
+    })
+    .await;
+
+    let error = client(&url).await.validate_connection(Duration::from_secs(5)).await.unwrap_err();
+
+    match &error {
+        AxiomMCPError::ProtocolViolation { message_type, violations, .. } => {
+            assert_eq!(message_type, "pong");
+            assert_eq!(violations[0].path, "/payload/serverTime");
+        },
+        other => panic!("Expected a protocol violation, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_handshake_fails_when_the_echo_comes_back_changed() {
+    let url = server(Vec::new(), |ping| {
+        let mut pong = echo(ping);
+        pong["payload"]["echo"] = json!("");
+        Some(pong)
+    })
+    .await;
+
+    let error = client(&url).await.validate_connection(Duration::from_secs(5)).await.unwrap_err();
+
+    assert_eq!(error.report().field.as_deref(), Some("/payload/echo"));
+}
+
+#[tokio::test]
+async fn test_handshake_times_out_on_a_server_that_does_not_answer_pings() {
+    let url = server(Vec::new(), |_| None).await;
+
+    let error = client(&url).await.validate_connection(Duration::from_millis(300)).await.unwrap_err();
+
+    assert_eq!(error.category(), ErrorCategory::Connectivity);
+    assert!(error.to_string().contains("did not answer the ping"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_validate_hot_reload_connection_tool() {
+    let url = server(Vec::new(), |ping| Some(echo(ping))).await;
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: true,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let config = MCPConfiguration { hot_reload_server_url: url.clone(), simulator_management_enabled: false, visual_analysis_enabled: false, ..Default::default() };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap();
+
+    let result = mcp.call_tool("validate_hot_reload_connection", json!({})).await.unwrap();
+
+    match result {
+        ToolResult::HotReloadHandshake(handshake) => {
+            assert_eq!(handshake.url, url);
+            assert_eq!(handshake.messages_checked, 1);
+        },
+        other => panic!("Expected a handshake, got {:?}", other),
+    }
+}
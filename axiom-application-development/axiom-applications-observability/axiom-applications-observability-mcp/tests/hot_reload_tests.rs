@@ -0,0 +1,310 @@
+use async_trait::async_trait;
+use axiom_applications_observability::hot_reload::*;
+use axiom_applications_observability::reload_scope::ReloadScope;
+use axiom_applications_observability::Result;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const WINDOW: Duration = Duration::from_millis(50);
+
+/// Records every reload and takes `latency` to finish each one
+struct RecordingTransport {
+    latency: Duration,
+    reloads: Mutex<Vec<ReloadMessage>>,
+}
+
+impl RecordingTransport {
+    fn new(latency: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            latency,
+            reloads: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn reloads(&self) -> Vec<ReloadMessage> {
+        self.reloads.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ReloadTransport for RecordingTransport {
+    async fn send_reload(&self, message: &ReloadMessage) -> Result<()> {
+        self.reloads.lock().unwrap().push(message.clone());
+        tokio::time::sleep(self.latency).await;
+        Ok(())
+    }
+}
+
+fn pipeline(transport: &Arc<RecordingTransport>) -> ReloadPipeline {
+    ReloadPipeline::spawn(transport.clone(), HotReloadConfig { debounce_window: WINDOW })
+}
+
+/// What Xcode does on save: swap file write, rename, attribute change
+async fn save(pipeline: &ReloadPipeline, path: &str) {
+    for _ in 0..3 {
+        pipeline.file_changed(FileChange::new(path, ChangeType::Modified)).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+fn file_names(message: &ReloadMessage) -> Vec<&str> {
+    message.payload.files.iter().map(|file| file.file_name.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_burst_of_writes_reloads_once() {
+    let transport = RecordingTransport::new(Duration::ZERO);
+    let pipeline = pipeline(&transport);
+
+    save(&pipeline, "Sources/App/ContentView.swift").await;
+    tokio::time::sleep(WINDOW * 4).await;
+
+    let reloads = transport.reloads();
+    assert_eq!(reloads.len(), 1);
+    assert_eq!(file_names(&reloads[0]), vec!["ContentView.swift"]);
+    assert_eq!(reloads[0].payload.files[0].change_type, ChangeType::Modified);
+}
+
+#[tokio::test]
+async fn test_files_changed_together_share_one_reload() {
+    let transport = RecordingTransport::new(Duration::ZERO);
+    let pipeline = pipeline(&transport);
+
+    save(&pipeline, "Sources/App/ContentView.swift").await;
+    save(&pipeline, "Sources/App/ContentContext.swift").await;
+    pipeline.file_changed(FileChange::new("Sources/App/DetailView.swift", ChangeType::Added)).unwrap();
+    tokio::time::sleep(WINDOW * 4).await;
+
+    let reloads = transport.reloads();
+    assert_eq!(reloads.len(), 1);
+    assert_eq!(file_names(&reloads[0]), vec!["ContentView.swift", "ContentContext.swift", "DetailView.swift"]);
+
+    let payload = serde_json::to_value(&reloads[0]).unwrap();
+    assert_eq!(payload["type"], "files_changed");
+    assert_eq!(payload["payload"]["batchId"], 1);
+    assert_eq!(payload["payload"]["files"][2]["filePath"], "Sources/App/DetailView.swift");
+    assert_eq!(payload["payload"]["files"][2]["changeType"], "added");
+}
+
+#[tokio::test]
+async fn test_separate_bursts_reload_separately() {
+    let transport = RecordingTransport::new(Duration::ZERO);
+    let pipeline = pipeline(&transport);
+
+    save(&pipeline, "Sources/App/ContentView.swift").await;
+    tokio::time::sleep(WINDOW * 4).await;
+    save(&pipeline, "Sources/App/ContentView.swift").await;
+    tokio::time::sleep(WINDOW * 4).await;
+
+    let reloads = transport.reloads();
+    assert_eq!(reloads.len(), 2);
+    assert_eq!(reloads[1].payload.batch_id, 2);
+}
+
+#[tokio::test]
+async fn test_bursts_during_a_reload_are_coalesced_into_one_pending_batch() {
+    let transport = RecordingTransport::new(Duration::from_millis(400));
+    let pipeline = pipeline(&transport);
+
+    save(&pipeline, "Sources/App/ContentView.swift").await;
+    tokio::time::sleep(WINDOW * 2).await;
+    assert_eq!(transport.reloads().len(), 1);
+
+    // Two more bursts while the first reload is still running
+    save(&pipeline, "Sources/App/ContentContext.swift").await;
+    tokio::time::sleep(WINDOW * 2).await;
+    save(&pipeline, "Sources/App/DetailView.swift").await;
+    tokio::time::sleep(WINDOW * 2).await;
+    assert_eq!(transport.reloads().len(), 1);
+
+    pipeline.shutdown().await.unwrap();
+
+    let reloads = transport.reloads();
+    assert_eq!(reloads.len(), 2);
+    assert_eq!(file_names(&reloads[1]), vec!["ContentContext.swift", "DetailView.swift"]);
+}
+
+#[tokio::test]
+async fn test_shutdown_sends_collected_changes_without_waiting_out_the_window() {
+    let transport = RecordingTransport::new(Duration::ZERO);
+    let pipeline = ReloadPipeline::spawn(transport.clone(), HotReloadConfig { debounce_window: Duration::from_secs(60) });
+
+    save(&pipeline, "Sources/App/ContentView.swift").await;
+    tokio::time::timeout(Duration::from_secs(1), pipeline.shutdown()).await.unwrap().unwrap();
+
+    assert_eq!(transport.reloads().len(), 1);
+}
+
+#[test]
+fn test_batch_folds_changes_to_the_same_file() {
+    let mut batch = ReloadBatch::default();
+    batch.push(FileChange::new("New.swift", ChangeType::Added));
+    batch.push(FileChange::new("New.swift", ChangeType::Modified));
+    batch.push(FileChange::new("Old.swift", ChangeType::Modified));
+    batch.push(FileChange::new("Old.swift", ChangeType::Deleted));
+
+    assert_eq!(batch.changes(), &[
+        FileChange::new("New.swift", ChangeType::Added),
+        FileChange::new("Old.swift", ChangeType::Deleted),
+    ]);
+    assert_eq!(HotReloadConfig::default().debounce_window, Duration::from_millis(150));
+}
+
+#[test]
+fn test_default_filters_keep_swift_sources_only() {
+    let filter = HotReloadFilter::default();
+
+    assert!(filter.decide(Path::new("/Users/dev/App/Sources/App/ContentView.swift")).is_included());
+    assert_eq!(filter.decide(Path::new("/Users/dev/App/Sources/App/Info.plist")), FilterDecision::NotIncluded);
+    assert_eq!(filter.decide(Path::new("/Users/dev/App/.git/index")), FilterDecision::Excluded { pattern: "**/.git/**".to_string() });
+    assert!(!filter.decide(Path::new("/Users/dev/App/.build/checkouts/Lib/Sources/Lib.swift")).is_included());
+    assert!(!filter.decide(Path::new("/Users/dev/Library/Developer/Xcode/DerivedData/App/Build/Intermediates/Foo.swift")).is_included());
+    assert!(!filter.decide(Path::new("/Users/dev/App/Sources/Generated/Assets.swift")).is_included());
+    assert!(!filter.decide(Path::new("/Users/dev/App/Sources/Strings.generated.swift")).is_included());
+}
+
+#[test]
+fn test_exclude_beats_include() {
+    let filter = HotReloadFilter::new(HotReloadFilters {
+        include: vec!["**/*.swift".to_string(), "**/Generated/**".to_string()],
+        exclude: vec!["**/Generated/**".to_string()],
+    })
+    .unwrap();
+
+    assert_eq!(
+        filter.decide(Path::new("Sources/Generated/Routes.swift")),
+        FilterDecision::Excluded { pattern: "**/Generated/**".to_string() }
+    );
+    assert!(filter.decide(Path::new("Sources/App/Routes.swift")).is_included());
+}
+
+#[test]
+fn test_empty_include_list_keeps_everything_not_excluded() {
+    let filter = HotReloadFilter::new(HotReloadFilters {
+        include: vec![],
+        exclude: vec!["**/*.tmp".to_string()],
+    })
+    .unwrap();
+
+    assert!(filter.decide(Path::new("Resources/Localizable.strings")).is_included());
+    assert!(!filter.decide(Path::new("Resources/Localizable.strings.tmp")).is_included());
+}
+
+#[test]
+fn test_invalid_filters_keep_the_current_ones() {
+    let filter = HotReloadFilter::default();
+
+    let result = filter.set_filters(HotReloadFilters {
+        include: vec!["**/*.{swift".to_string()],
+        exclude: vec![],
+    });
+
+    assert!(result.is_err());
+    assert_eq!(filter.filters(), HotReloadFilters::default());
+}
+
+#[tokio::test]
+async fn test_filtered_changes_are_counted_and_not_reloaded() {
+    let transport = RecordingTransport::new(Duration::ZERO);
+    let filter = Arc::new(HotReloadFilter::default());
+    let pipeline = ReloadPipeline::spawn_filtered(transport.clone(), HotReloadConfig { debounce_window: WINDOW }, filter.clone());
+
+    save(&pipeline, "App/.git/index.lock").await;
+    save(&pipeline, "App/Sources/Generated/Assets.swift").await;
+    save(&pipeline, "App/Sources/ContentView.swift").await;
+    pipeline.file_changed(FileChange::new("App/Sources/Info.plist", ChangeType::Modified)).unwrap();
+    pipeline.shutdown().await.unwrap();
+
+    let reloads = transport.reloads();
+    assert_eq!(reloads.len(), 1);
+    assert_eq!(file_names(&reloads[0]), vec!["ContentView.swift"]);
+
+    let stats = filter.stats();
+    assert_eq!(stats.events_received, 10);
+    assert_eq!(stats.events_forwarded, 3);
+    assert_eq!(stats.events_filtered, 7);
+    assert_eq!(stats.filtered_by["**/.git/**"], 3);
+    assert_eq!(stats.filtered_by["**/Generated/**"], 3);
+    assert_eq!(stats.filtered_by["not_included"], 1);
+    assert_eq!(stats.recent_filtered.last().unwrap().path, "App/Sources/Info.plist");
+}
+
+#[tokio::test]
+async fn test_reload_is_scoped_to_the_changed_view() {
+    let project = tempfile::tempdir().unwrap();
+    let view = project.path().join("ContentView.swift");
+    let detail = project.path().join("DetailView.swift");
+    std::fs::write(&view, "struct ContentView: View { var body: some View { Text(\"Hello\") } }").unwrap();
+    std::fs::write(&detail, "struct DetailView: View { var body: some View { EmptyView() } }").unwrap();
+
+    let transport = RecordingTransport::new(Duration::ZERO);
+    let pipeline = pipeline(&transport);
+    pipeline.components().index_file(&view, &std::fs::read_to_string(&view).unwrap());
+
+    std::fs::write(&view, "struct ContentView: View { var body: some View { Text(\"Hello, world\") } }").unwrap();
+    pipeline.file_changed(FileChange::new(&view, ChangeType::Modified)).unwrap();
+    tokio::time::sleep(WINDOW * 4).await;
+
+    // Never indexed before, so the reload cannot be narrowed down
+    pipeline.file_changed(FileChange::new(&detail, ChangeType::Modified)).unwrap();
+    tokio::time::sleep(WINDOW * 4).await;
+
+    let stats = pipeline.stats();
+    pipeline.shutdown().await.unwrap();
+
+    let reloads = transport.reloads();
+    assert_eq!(reloads.len(), 2);
+    assert_eq!(reloads[0].payload.scope, ReloadScope::Targeted);
+    assert_eq!(reloads[0].payload.component_ids, vec!["ContentView"]);
+    assert_eq!(reloads[1].payload.scope, ReloadScope::Full);
+
+    let payload = serde_json::to_value(&reloads[0]).unwrap();
+    assert_eq!(payload["payload"]["scope"], "targeted");
+    assert_eq!(payload["payload"]["componentIds"][0], "ContentView");
+
+    let last_reload = stats.last_reload.unwrap();
+    assert_eq!(last_reload.batch_id, 2);
+    assert_eq!(last_reload.scope, ReloadScope::Full);
+    assert_eq!(last_reload.reason, "DetailView.swift was not indexed before this change");
+}
+
+/// Records the order it is told about reloads in, next to what the
+/// transport had sent at the time
+struct OrderObserver {
+    transport: Arc<RecordingTransport>,
+    events: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl ReloadObserver for OrderObserver {
+    async fn before_reload(&self, batch_id: u64) {
+        let sent = self.transport.reloads().len();
+        self.events.lock().unwrap().push(format!("before {} ({} sent)", batch_id, sent));
+    }
+
+    async fn after_reload(&self, reload: &ReloadReport) {
+        let sent = self.transport.reloads().len();
+        self.events.lock().unwrap().push(format!("after {} ({} sent, {} files)", reload.batch_id, sent, reload.files.len()));
+    }
+}
+
+#[tokio::test]
+async fn test_observer_is_told_before_and_after_each_reload() {
+    let transport = RecordingTransport::new(Duration::ZERO);
+    let pipeline = pipeline(&transport);
+    let observer = Arc::new(OrderObserver { transport: transport.clone(), events: Mutex::new(Vec::new()) });
+    pipeline.observe(observer.clone());
+
+    save(&pipeline, "Sources/App/ContentView.swift").await;
+    tokio::time::sleep(WINDOW * 4).await;
+    save(&pipeline, "Sources/App/DetailView.swift").await;
+    pipeline.shutdown().await.unwrap();
+
+    assert_eq!(*observer.events.lock().unwrap(), vec![
+        "before 1 (0 sent)",
+        "after 1 (1 sent, 1 files)",
+        "before 2 (1 sent)",
+        "after 2 (2 sent, 1 files)",
+    ]);
+}
@@ -22,6 +22,7 @@ async fn test_complete_development_environment_setup() -> error::Result<()> {
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = mcp::MCPCapabilities {
@@ -81,7 +82,7 @@ async fn test_end_to_end_code_generation_workflow() -> error::Result<()> {
     // Setup complete development loop
     let observability_loop = setup_integration_test_loop().await?;
     
-    let test_requirements = vec![
+    let test_requirements = [
         "Create a simple counter app with increment and decrement buttons",
         "Build a shopping cart with add/remove items and total calculation", 
         "Design a user profile screen with photo, name, and settings",
@@ -99,7 +100,16 @@ async fn test_end_to_end_code_generation_workflow() -> error::Result<()> {
         
         // Validate results
         assert!(result.success, "Development cycle should succeed for requirement: {}", requirement);
-        assert!(result.requirement_analysis.confidence_score > 70.0, "Should have confident analysis");
+        match result.requirement_analysis.analyzer {
+            requirement_analysis::AnalyzerKind::Intelligence => {
+                assert!(result.requirement_analysis.confidence_score > 70.0, "Should have confident analysis");
+            }
+            // Offline rules score a requirement HEURISTIC_CONFIDENCE_PENALTY below the server
+            requirement_analysis::AnalyzerKind::Heuristic => assert!(
+                result.requirement_analysis.confidence_score > 70.0 - requirement_analysis::HEURISTIC_CONFIDENCE_PENALTY,
+                "Should have confident offline analysis"
+            ),
+        }
         assert!(!result.implementation.context_layer.contexts.is_empty(), "Should generate contexts");
         assert!(!result.implementation.presentation_layer.presentations.is_empty(), "Should generate presentations");
         assert!(!result.implementation.client_layer.clients.is_empty(), "Should generate clients");
@@ -158,6 +168,9 @@ async fn test_screenshot_matrix_integration() -> error::Result<()> {
                 capture_delay_ms: 200,
                 quality: screenshot_matrix_engine::ImageQuality::Medium,
             },
+            clean_state: false,
+            status_bar: None,
+            pre_capture_script: None,
         };
         
         let start_time = std::time::Instant::now();
@@ -173,13 +186,13 @@ async fn test_screenshot_matrix_integration() -> error::Result<()> {
         };
         
         assert!(matrix.screenshots.len() >= expected_screenshots / 2, "Should capture reasonable number of screenshots");
-        assert!(matrix.analysis.consistency_score > 75.0, "Should have good consistency");
+        assert!(matrix.analysis.consistency_score > 0.75, "Should have good consistency");
         
         if config_name == "all_devices" {
             assert!(duration.as_secs() < 10, "Full device matrix should complete within reasonable time");
         }
         
-        println!("    ✅ {} completed in {:?} ({} screenshots, {:.1}% consistency)", 
+        println!("    ✅ {} completed in {:?} ({} screenshots, {:.2} consistency)", 
                 config_name, duration, matrix.screenshots.len(), matrix.analysis.consistency_score);
     }
     
@@ -211,6 +224,7 @@ async fn test_performance_monitoring_integration() -> error::Result<()> {
             "Network".to_string(),
         ],
         detailed_analysis: true,
+        metrics: Default::default(),
     };
     
     let start_time = std::time::Instant::now();
@@ -433,6 +447,8 @@ fn create_test_presentation_spec() -> PresentationSpec {
             max_render_time_ms: 16.0,
             max_memory_mb: 10.0,
         },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
     }
 }
 
@@ -448,19 +464,26 @@ fn create_mock_screenshots_for_analysis() -> Vec<types::Screenshot> {
                 scale: 3.0,
                 color_scheme: "light".to_string(),
                 capture_mode: "full_screen".to_string(),
+                status_bar: None,
+                runtime: None,
+                window: Default::default(),
             },
             image_data: vec![1, 2, 3, 4], // Mock image data
+            image: None,
             metadata: types::ScreenshotMetadata {
                 timestamp: chrono::Utc::now(),
                 device_info: types::DeviceInfo {
                     model: "iPhone 15 Pro".to_string(),
                     screen_size: types::ScreenSize { width: 393.0, height: 852.0 },
                     orientation: "portrait".to_string(),
+                    udid: None,
                 },
                 app_state: types::AppState {
                     view_hierarchy: "MainView".to_string(),
                     active_context: "MainContext".to_string(),
+                    view_tree: None,
                 },
+                overrides: Default::default(),
             },
         },
     ]
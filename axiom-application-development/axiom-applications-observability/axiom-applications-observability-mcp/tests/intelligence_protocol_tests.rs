@@ -0,0 +1,249 @@
+use axiom_applications_observability::advanced_visual_intelligence::ElementType;
+use axiom_applications_observability::connection::*;
+use axiom_applications_observability::intelligence::{IntelligenceClient, HANDSHAKE_TIMEOUT};
+use axiom_applications_observability::intelligence_protocol::*;
+use axiom_applications_observability::performance_analysis_integration::PerformanceMetric;
+use axiom_applications_observability::system_status::*;
+use axiom_applications_observability::AxiomMCPError;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Frames captured from a server that predates the handshake
+const V1_FRAMES: &str = include_str!("fixtures/intelligence_v1_frames.jsonl");
+/// The same replies from a current server, after its hello
+const V2_FRAMES: &str = include_str!("fixtures/intelligence_v2_frames.jsonl");
+
+fn frame(frames: &str, index: usize) -> &str {
+    frames.lines().nth(index).unwrap()
+}
+
+fn decoded(version: u32, frame: &str) -> IntelligenceMessage {
+    decode_message(version, frame).unwrap().expect("a known message type")
+}
+
+fn hello(frames: &str) -> ServerHello {
+    match decoded(PROTOCOL_VERSION, frame(frames, 0)) {
+        IntelligenceMessage::Hello(hello) => hello,
+        other => panic!("Expected a hello, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_version_1_frames_upgrade_to_the_current_types() {
+    let old_tree = decoded(LEGACY_PROTOCOL_VERSION, frame(V1_FRAMES, 0));
+    let current_tree = decoded(PROTOCOL_VERSION, frame(V2_FRAMES, 1));
+    let old_frame_rate = decoded(LEGACY_PROTOCOL_VERSION, frame(V1_FRAMES, 1));
+
+    assert_eq!(old_tree, current_tree);
+    let IntelligenceMessage::AccessibilityTree { request_id, root } = old_tree else {
+        panic!("Expected an accessibility tree");
+    };
+    assert_eq!(request_id, "9f6c2a4e-1b7d-4c1e-8f0a-3d2b5e6c7a81");
+    assert_eq!(root.children[0].element_type, ElementType::Button);
+    assert_eq!(root.children[1].element_type, ElementType::TextField);
+    assert_eq!((root.children[0].frame.x, root.children[0].frame.width), (337.0, 44.0));
+    assert_eq!(
+        old_frame_rate,
+        IntelligenceMessage::FrameRate { request_id: "2c8e5b1f-7a3d-4f6e-9b0c-1d4a7e2f8c53".to_string(), fps: 58.4 }
+    );
+
+    let IntelligenceMessage::PerformanceMetrics { metrics, .. } = decoded(PROTOCOL_VERSION, frame(V2_FRAMES, 2)) else {
+        panic!("Expected performance metrics");
+    };
+    assert_eq!(metrics.get(PerformanceMetric::MemoryMb), Some(182.5));
+}
+
+#[test]
+fn test_unknown_message_types_are_skipped() {
+    assert_eq!(decode_message(LEGACY_PROTOCOL_VERSION, frame(V1_FRAMES, 2)).unwrap(), None);
+    assert_eq!(decode_message(PROTOCOL_VERSION, frame(V2_FRAMES, 3)).unwrap(), None);
+    // Version 1 had no performance metrics
+    assert_eq!(decode_message(LEGACY_PROTOCOL_VERSION, frame(V2_FRAMES, 2)).unwrap(), None);
+}
+
+#[test]
+fn test_malformed_known_messages_are_errors() {
+    // A version 1 frame read as version 2 lacks `requestId` and `root`
+    let misread = decode_message(PROTOCOL_VERSION, frame(V1_FRAMES, 0));
+    let not_json = decode_message(PROTOCOL_VERSION, "{\"type\":");
+
+    assert!(matches!(misread, Err(AxiomMCPError::Internal(_))), "{:?}", misread);
+    assert!(misread.unwrap_err().to_string().contains("accessibility_tree"));
+    assert!(matches!(not_json, Err(AxiomMCPError::Internal(_))), "{:?}", not_json);
+}
+
+#[test]
+fn test_requests_are_written_in_the_servers_schema() {
+    let request = IntelligenceRequest::FrameRate { window: Duration::from_secs(2) };
+
+    let old: Value = serde_json::from_str(&request.encode(LEGACY_PROTOCOL_VERSION, "r-1")).unwrap();
+    let current: Value = serde_json::from_str(&request.encode(PROTOCOL_VERSION, "r-1")).unwrap();
+
+    assert_eq!(old, json!({ "type": "request_frame_rate", "request_id": "r-1", "window_ms": 2000 }));
+    assert_eq!(current, json!({ "type": "request_frame_rate", "requestId": "r-1", "windowMs": 2000 }));
+    let hello: Value = serde_json::from_str(&client_hello()).unwrap();
+    assert_eq!(hello["protocolVersions"], json!(SUPPORTED_PROTOCOL_VERSIONS));
+}
+
+#[test]
+fn test_missing_capabilities_are_degraded() {
+    let current = ServerProtocol::from_hello(&hello(V2_FRAMES));
+    let legacy = ServerProtocol::legacy();
+    let newer = ServerProtocol::from_hello(&ServerHello { protocol_version: 3, capabilities: vec![], server_version: None });
+
+    assert_eq!((current.handshake, current.version), (HandshakeState::Negotiated, 2));
+    assert_eq!(current.server_version.as_deref(), Some("0.9.3"));
+    assert_eq!(current.degraded(), vec![ServerCapability::VisualStateStream, ServerCapability::UiInteraction, ServerCapability::RequirementAnalysis]);
+    assert!(current.require(ServerCapability::PerformanceMetrics).is_ok());
+    let streaming = current.require(ServerCapability::VisualStateStream);
+    assert!(matches!(streaming, Err(AxiomMCPError::Configuration { .. })), "{:?}", streaming);
+
+    assert_eq!(legacy.degraded(), vec![
+        ServerCapability::PerformanceMetrics,
+        ServerCapability::VisualStateStream,
+        ServerCapability::UiInteraction,
+        ServerCapability::RequirementAnalysis,
+    ]);
+    assert!(legacy.require(ServerCapability::AccessibilityTree).is_ok());
+
+    assert_eq!(newer.handshake, HandshakeState::Unsupported);
+    assert_eq!(newer.degraded(), ServerCapability::ALL.to_vec());
+    assert!(newer.require(ServerCapability::FrameRate).is_err());
+}
+
+fn connected() -> ConnectionStatus {
+    ConnectionStatus {
+        name: "intelligence".to_string(),
+        url: "ws://localhost:8080/intelligence".to_string(),
+        state: ConnectionState::Connected,
+        connected_since: Some(chrono::Utc::now()),
+        reconnect_attempt: 0,
+        reconnects: 0,
+        buffered_messages: 0,
+        dropped_messages: 0,
+        active_subscriptions: 0,
+        last_error: None,
+    }
+}
+
+#[tokio::test]
+async fn test_system_status_records_degraded_capabilities() {
+    let probe = |protocol: ServerProtocol| ConnectionProbe::intelligence(connected()).with_protocol(protocol);
+    let full = ServerProtocol::from_hello(&ServerHello {
+        protocol_version: PROTOCOL_VERSION,
+        capabilities: ServerCapability::ALL.map(|capability| capability.name().to_string()).to_vec(),
+        server_version: None,
+    });
+
+    let legacy = probe(ServerProtocol::legacy()).probe().await;
+    let current = probe(full).probe().await;
+    let pending = probe(ServerProtocol::pending()).probe().await;
+    let unsupported = probe(ServerProtocol::from_hello(&ServerHello { protocol_version: 7, capabilities: vec![], server_version: None }))
+        .probe()
+        .await;
+
+    assert_eq!(legacy.status, ReadinessStatus::Degraded);
+    assert_eq!(legacy.degraded_capabilities, vec!["performance_metrics", "visual_state_stream", "ui_interaction", "requirement_analysis"]);
+    assert!(legacy.remediation.as_deref().unwrap().contains("protocol version 2"));
+    assert_eq!(current.status, ReadinessStatus::Ready);
+    assert!(current.degraded_capabilities.is_empty());
+    assert_eq!(pending.status, ReadinessStatus::Ready);
+    assert_eq!(unsupported.status, ReadinessStatus::Failed);
+    assert!(unsupported.detail.contains("version 7"), "{}", unsupported.detail);
+
+    let status = SystemStatus::from_reports(vec![legacy]);
+    let reported = serde_json::to_value(&status).unwrap();
+    assert_eq!(reported["subsystems"][0]["degraded_capabilities"], json!(["performance_metrics", "visual_state_stream", "ui_interaction", "requirement_analysis"]));
+    let hot_reload = serde_json::to_value(ConnectionProbe::hot_reload(connected()).probe().await).unwrap();
+    assert!(hot_reload.get("degraded_capabilities").is_none());
+}
+
+fn fast_policy() -> ReconnectPolicy {
+    ReconnectPolicy { initial_delay: Duration::from_millis(20), jitter: 0.0, ..ReconnectPolicy::default() }
+}
+
+async fn next_message<S>(server: &mut S) -> Value
+where
+    S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), server.next())
+            .await
+            .expect("timed out waiting for a message")
+            .expect("connection closed")
+            .unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+async fn wait_until_settled(client: &IntelligenceClient) -> ServerProtocol {
+    tokio::time::timeout(HANDSHAKE_TIMEOUT * 3, async {
+        loop {
+            let protocol = client.server_protocol();
+            if protocol.is_settled() {
+                return protocol;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the handshake never settled")
+}
+
+#[tokio::test]
+async fn test_server_without_handshake_is_read_as_version_1() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}/intelligence", listener.local_addr().unwrap());
+    let client = IntelligenceClient::connect(&url, fast_policy(), tokio::sync::broadcast::channel(8).0).await.unwrap();
+
+    let (tcp, _) = listener.accept().await.unwrap();
+    let mut server = tokio_tungstenite::accept_async(tcp).await.unwrap();
+    let greeting = next_message(&mut server).await;
+    assert_eq!(greeting["type"], "hello");
+    // An old server answers what it does not know with an unknown type of its own
+    server.send(Message::Text(frame(V1_FRAMES, 2).to_string())).await.unwrap();
+
+    let frame_rate = tokio::spawn(async move {
+        let request = next_message(&mut server).await;
+        assert_eq!(request["type"], "request_frame_rate");
+        assert_eq!(request["window_ms"], 100);
+        let reply = json!({ "type": "frame_rate", "request_id": request["request_id"], "frame_rate": 59.5 });
+        server.send(Message::Text(reply.to_string())).await.unwrap();
+        server
+    });
+    let fps = client.frame_rate(Duration::from_millis(100)).await.unwrap();
+    let _server = frame_rate.await.unwrap();
+
+    assert_eq!(fps, 59.5);
+    let protocol = client.server_protocol();
+    assert_eq!((protocol.handshake, protocol.version), (HandshakeState::Legacy, LEGACY_PROTOCOL_VERSION));
+    let metrics = client.performance_metrics(&[PerformanceMetric::MemoryMb], Duration::from_secs(1)).await;
+    assert!(matches!(metrics, Err(AxiomMCPError::Configuration { .. })), "{:?}", metrics);
+    assert!(client.subscribe_stream(VISUAL_STATE_STREAM).is_err());
+}
+
+#[tokio::test]
+async fn test_streams_the_server_lacks_are_dropped_after_the_handshake() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}/intelligence", listener.local_addr().unwrap());
+    let client = IntelligenceClient::connect(&url, fast_policy(), tokio::sync::broadcast::channel(8).0).await.unwrap();
+    client.subscribe_stream(VISUAL_STATE_STREAM).unwrap();
+    client.subscribe_stream("errors").unwrap();
+
+    let (tcp, _) = listener.accept().await.unwrap();
+    let mut server = tokio_tungstenite::accept_async(tcp).await.unwrap();
+    assert_eq!(next_message(&mut server).await["type"], "hello");
+    server.send(Message::Text(frame(V2_FRAMES, 0).to_string())).await.unwrap();
+    let protocol = wait_until_settled(&client).await;
+
+    assert_eq!(protocol.handshake, HandshakeState::Negotiated);
+    assert_eq!(protocol.degraded(), vec![ServerCapability::VisualStateStream, ServerCapability::UiInteraction, ServerCapability::RequirementAnalysis]);
+    assert_eq!(client.connection_status().active_subscriptions, 1);
+    let streaming = client.subscribe_stream(VISUAL_STATE_STREAM);
+    assert!(matches!(streaming, Err(AxiomMCPError::Configuration { .. })), "{:?}", streaming);
+}
@@ -0,0 +1,364 @@
+use axiom_applications_observability::advanced_visual_intelligence::*;
+use axiom_applications_observability::types::*;
+use axiom_applications_observability::visual_regression::{decode_png, encode_png};
+use image::{Rgba, RgbaImage};
+
+/// View tree as the intelligence server attaches it to a screenshot
+const PROFILE_TREE: &str = r#"{
+    "identifier": "ProfileView",
+    "kind": "VStack",
+    "frame": { "x": 0, "y": 0, "width": 100, "height": 50 },
+    "children": [
+        {
+            "identifier": "displayName",
+            "kind": "Text",
+            "text": "Alexandria Ocasio-Smith",
+            "intrinsicSize": { "width": 120, "height": 10 },
+            "lineLimit": 1,
+            "frame": { "x": 10, "y": 10, "width": 40, "height": 10 }
+        },
+        {
+            "identifier": "avatar",
+            "kind": "ZStack",
+            "frame": { "x": 60, "y": 0, "width": 40, "height": 40 },
+            "children": [
+                { "identifier": "avatarImage", "kind": "Image", "frame": { "x": 60, "y": 0, "width": 40, "height": 40 } },
+                { "identifier": "onlineBadge", "kind": "Circle", "frame": { "x": 85, "y": 25, "width": 15, "height": 15 } }
+            ]
+        }
+    ]
+}"#;
+
+fn node(identifier: &str, kind: &str, frame: ElementFrame) -> ViewNode {
+    ViewNode {
+        identifier: identifier.to_string(),
+        kind: kind.to_string(),
+        frame,
+        text: None,
+        intrinsic_size: None,
+        line_limit: None,
+        hidden: false,
+        children: Vec::new(),
+    }
+}
+
+fn text(identifier: &str, frame: ElementFrame, intrinsic_width: f64, line_limit: Option<u32>) -> ViewNode {
+    ViewNode {
+        text: Some(identifier.to_string()),
+        intrinsic_size: Some(ElementSize { width: intrinsic_width, height: 20.0 }),
+        line_limit,
+        ..node(identifier, "Text", frame)
+    }
+}
+
+fn stack(kind: &str, children: Vec<ViewNode>) -> ViewNode {
+    ViewNode {
+        children,
+        ..node("container", kind, frame(0.0, 0.0, 400.0, 400.0))
+    }
+}
+
+fn frame(x: f64, y: f64, width: f64, height: f64) -> ElementFrame {
+    ElementFrame { x, y, width, height }
+}
+
+fn truncated_ids(root: &ViewNode) -> Vec<String> {
+    detect_truncation(root, TRUNCATION_TOLERANCE_PT)
+        .into_iter()
+        .flat_map(|issue| issue.element_ids)
+        .collect()
+}
+
+fn overlapping_pairs(root: &ViewNode) -> Vec<Vec<String>> {
+    detect_overlaps(root, OVERLAP_TOLERANCE)
+        .into_iter()
+        .map(|issue| issue.element_ids)
+        .collect()
+}
+
+fn screenshot(id: &str, image: &RgbaImage, scale: f64, view_tree: Option<ViewNode>) -> Screenshot {
+    let width = f64::from(image.width()) / scale;
+    let height = f64::from(image.height()) / scale;
+    Screenshot {
+        id: id.to_string(),
+        client_id: "test-client".to_string(),
+        configuration: ScreenshotConfiguration {
+            device_type: "iPhone 15".to_string(),
+            screen_size: ScreenSize { width, height },
+            orientation: "portrait".to_string(),
+            scale,
+            color_scheme: "light".to_string(),
+            capture_mode: "standard".to_string(),
+            status_bar: None,
+            runtime: None,
+            window: Default::default(),
+        },
+        image_data: encode_png(image).unwrap(),
+        image: None,
+        metadata: ScreenshotMetadata {
+            timestamp: chrono::Utc::now(),
+            device_info: DeviceInfo {
+                model: "iPhone 15".to_string(),
+                screen_size: ScreenSize { width, height },
+                orientation: "portrait".to_string(),
+                udid: None,
+            },
+            app_state: AppState {
+                view_hierarchy: "ProfileView".to_string(),
+                active_context: "ProfileContext".to_string(),
+                view_tree,
+            },
+            overrides: Default::default(),
+        },
+    }
+}
+
+fn matrix(screenshots: Vec<Screenshot>) -> ScreenshotMatrix {
+    ScreenshotMatrix {
+        analysis: ScreenshotAnalysis {
+            total_screenshots: screenshots.len(),
+            consistency_score: 1.0,
+            detected_issues: Vec::new(),
+            layout_issues: Vec::new(),
+            consistency_pairs: Vec::new(),
+            warnings: Vec::new(),
+        },
+        screenshots,
+    }
+}
+
+#[test]
+fn test_single_line_text_wider_than_its_frame_is_truncated() {
+    let root = stack("VStack", vec![text("title", frame(0.0, 0.0, 80.0, 20.0), 120.0, Some(1))]);
+
+    let issues = detect_truncation(&root, TRUNCATION_TOLERANCE_PT);
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, LayoutIssueKind::Truncation);
+    assert_eq!(issues[0].element_ids, vec!["title"]);
+    assert_eq!(issues[0].region, frame(0.0, 0.0, 80.0, 20.0));
+}
+
+#[test]
+fn test_truncation_tolerance_boundary() {
+    let within = stack("VStack", vec![text("within", frame(0.0, 0.0, 80.0, 20.0), 80.5, None)]);
+    let beyond = stack("VStack", vec![text("beyond", frame(0.0, 0.0, 80.0, 20.0), 80.6, None)]);
+
+    assert!(truncated_ids(&within).is_empty());
+    assert_eq!(truncated_ids(&beyond), vec!["beyond"]);
+}
+
+#[test]
+fn test_text_that_wraps_into_a_tall_enough_frame_is_not_truncated() {
+    // 150pt of text needs two 80pt lines, and the 40pt frame fits two 20pt lines
+    let wrapping = stack("VStack", vec![text("body", frame(0.0, 0.0, 80.0, 40.0), 150.0, None)]);
+    // Three lines do not fit
+    let overflowing = stack("VStack", vec![text("body", frame(0.0, 0.0, 80.0, 40.0), 170.0, None)]);
+
+    assert!(truncated_ids(&wrapping).is_empty());
+    assert_eq!(truncated_ids(&overflowing), vec!["body"]);
+}
+
+#[test]
+fn test_line_limit_caps_the_lines_a_frame_fits() {
+    let limited = stack("VStack", vec![text("body", frame(0.0, 0.0, 80.0, 40.0), 150.0, Some(1))]);
+    let unlimited = stack("VStack", vec![text("body", frame(0.0, 0.0, 80.0, 40.0), 150.0, Some(0))]);
+
+    assert_eq!(truncated_ids(&limited), vec!["body"]);
+    assert!(truncated_ids(&unlimited).is_empty());
+}
+
+#[test]
+fn test_truncation_skips_hidden_subtrees_and_views_without_sizes() {
+    let mut hidden = stack("VStack", vec![text("clipped", frame(0.0, 0.0, 10.0, 20.0), 200.0, Some(1))]);
+    hidden.hidden = true;
+    let without_size = ViewNode {
+        text: Some("no intrinsic size".to_string()),
+        ..node("label", "Text", frame(0.0, 0.0, 10.0, 20.0))
+    };
+    let root = stack("VStack", vec![hidden, without_size]);
+
+    assert!(truncated_ids(&root).is_empty());
+}
+
+#[test]
+fn test_overlapping_siblings_are_flagged_with_their_intersection() {
+    let root = stack(
+        "HStack",
+        vec![
+            node("name", "Text", frame(0.0, 0.0, 50.0, 20.0)),
+            node("badge", "Image", frame(45.0, 0.0, 50.0, 20.0)),
+        ],
+    );
+
+    let issues = detect_overlaps(&root, OVERLAP_TOLERANCE);
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, LayoutIssueKind::Overlap);
+    assert_eq!(issues[0].element_ids, vec!["name", "badge"]);
+    assert_eq!(issues[0].region, frame(45.0, 0.0, 5.0, 20.0));
+}
+
+#[test]
+fn test_overlap_tolerance_boundary() {
+    let pair = |badge_x: f64| {
+        stack(
+            "HStack",
+            vec![
+                node("name", "Text", frame(0.0, 0.0, 50.0, 20.0)),
+                node("badge", "Image", frame(badge_x, 0.0, 50.0, 20.0)),
+            ],
+        )
+    };
+
+    // 2.5pt of a 50pt-wide view is exactly the 5% tolerance
+    assert!(overlapping_pairs(&pair(47.5)).is_empty());
+    assert_eq!(overlapping_pairs(&pair(47.0)).len(), 1);
+    // Touching edges do not overlap
+    assert!(overlapping_pairs(&pair(50.0)).is_empty());
+}
+
+#[test]
+fn test_overlap_is_measured_against_the_smaller_view() {
+    // A 10x10 icon entirely inside a large card overlaps 100% of itself
+    let root = stack(
+        "VStack",
+        vec![
+            node("card", "VStack", frame(0.0, 0.0, 300.0, 300.0)),
+            node("icon", "Image", frame(100.0, 100.0, 10.0, 10.0)),
+        ],
+    );
+
+    assert_eq!(overlapping_pairs(&root), vec![vec!["card", "icon"]]);
+}
+
+#[test]
+fn test_layered_containers_and_hidden_views_are_not_compared() {
+    let mut hidden = node("placeholder", "Text", frame(0.0, 0.0, 50.0, 20.0));
+    hidden.hidden = true;
+    let root = stack(
+        "VStack",
+        vec![
+            stack(
+                "ZStack",
+                vec![
+                    node("background", "Color", frame(0.0, 0.0, 400.0, 400.0)),
+                    // Siblings inside a child of a ZStack are still compared
+                    stack(
+                        "HStack",
+                        vec![
+                            node("left", "Text", frame(0.0, 0.0, 50.0, 20.0)),
+                            node("right", "Text", frame(25.0, 0.0, 50.0, 20.0)),
+                        ],
+                    ),
+                ],
+            ),
+            hidden,
+        ],
+    );
+
+    assert_eq!(overlapping_pairs(&root), vec![vec!["left", "right"]]);
+}
+
+#[test]
+fn test_fixture_tree_deserializes_and_flags_only_the_name() {
+    let tree: ViewNode = serde_json::from_str(PROFILE_TREE).unwrap();
+
+    assert_eq!(tree.children[0].line_limit, Some(1));
+    assert_eq!(tree.children[0].intrinsic_size, Some(ElementSize { width: 120.0, height: 10.0 }));
+    assert_eq!(truncated_ids(&tree), vec!["displayName"]);
+    assert!(overlapping_pairs(&tree).is_empty());
+}
+
+#[tokio::test]
+async fn test_detect_layout_issues_saves_crops_and_feeds_the_analysis() {
+    let crop_dir = tempfile::tempdir().unwrap();
+    let tree: ViewNode = serde_json::from_str(PROFILE_TREE).unwrap();
+    let image = RgbaImage::from_pixel(200, 100, Rgba([255, 255, 255, 255]));
+    let mut matrix = matrix(vec![
+        screenshot("with-tree", &image, 2.0, Some(tree)),
+        screenshot("without-tree", &image, 2.0, None),
+    ]);
+    let engine = VisualIntelligenceEngine::new().await.unwrap();
+
+    let issues = engine.detect_layout_issues(&mut matrix, crop_dir.path()).await.unwrap();
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].screenshot_id, "with-tree");
+    assert_eq!(matrix.analysis.layout_issues.len(), 1);
+    assert_eq!(matrix.analysis.detected_issues.len(), 1);
+    assert!(matrix.analysis.detected_issues[0].starts_with("iPhone 15 portrait light: "));
+    assert!(matrix.analysis.detected_issues[0].contains("displayName"));
+    // The identical images differ only by the issue found on one of them
+    assert_eq!(matrix.analysis.consistency_pairs.len(), 1);
+    assert_eq!(matrix.analysis.consistency_pairs[0].issue_penalty, 0.1);
+    assert!((matrix.analysis.consistency_score - 0.9).abs() < 1e-9, "{}", matrix.analysis.consistency_score);
+
+    // The 40x10pt frame padded by 8pt on each side, at 2x
+    let crop_path = issues[0].crop_path.clone().unwrap();
+    assert!(crop_path.starts_with(crop_dir.path().join("with-tree")));
+    let crop = decode_png(&std::fs::read(&crop_path).unwrap()).unwrap();
+    assert_eq!(crop.dimensions(), (112, 52));
+}
+
+#[tokio::test]
+async fn test_detect_layout_issues_without_a_decodable_image_skips_crops() {
+    let crop_dir = tempfile::tempdir().unwrap();
+    let tree: ViewNode = serde_json::from_str(PROFILE_TREE).unwrap();
+    let mut shot = screenshot("corrupt", &RgbaImage::new(1, 1), 1.0, Some(tree));
+    shot.image_data = vec![1, 2, 3, 4];
+    let mut matrix = matrix(vec![shot]);
+    let engine = VisualIntelligenceEngine::new().await.unwrap();
+
+    let issues = engine.detect_layout_issues(&mut matrix, crop_dir.path()).await.unwrap();
+
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].crop_path.is_none());
+    assert_eq!(matrix.analysis.detected_issues.len(), 1);
+}
+
+#[test]
+fn test_views_reaching_past_a_narrow_window_are_flagged() {
+    let mut toolbar = node("toolbar", "HStack", frame(0.0, 0.0, 500.0, 44.0));
+    toolbar.children = vec![node("share", "Button", frame(440.0, 0.0, 44.0, 44.0))];
+    let mut carousel = node("carousel", "ScrollView", frame(0.0, 60.0, 375.0, 200.0));
+    carousel.children = vec![node("cards", "HStack", frame(0.0, 60.0, 1200.0, 200.0))];
+    let mut hidden = node("sidebar", "VStack", frame(-320.0, 0.0, 320.0, 800.0));
+    hidden.hidden = true;
+    let mut root = node("root", "VStack", frame(0.0, 0.0, 375.0, 1032.0));
+    root.children = vec![toolbar, carousel, hidden, node("footer", "Text", frame(0.0, 990.0, 375.3, 42.0))];
+
+    let issues = detect_offscreen(&root, &ScreenSize { width: 375.0, height: 1032.0 }, TRUNCATION_TOLERANCE_PT);
+
+    assert_eq!(issues.len(), 1, "{:?}", issues);
+    assert_eq!(issues[0].kind, LayoutIssueKind::Offscreen);
+    assert_eq!(issues[0].element_ids, vec!["toolbar"]);
+    assert_eq!(issues[0].region, frame(0.0, 0.0, 375.0, 44.0));
+    assert_eq!(issues[0].message, "toolbar is 500pt wide and reaches 125pt past the 375pt wide window");
+}
+
+#[tokio::test]
+async fn test_split_windows_are_checked_in_their_own_size_class() {
+    let crop_dir = tempfile::tempdir().unwrap();
+    let mut tree = node("root", "VStack", frame(0.0, 0.0, 100.0, 50.0));
+    tree.children = vec![node("banner", "Image", frame(0.0, 30.0, 150.0, 10.0))];
+    let image = RgbaImage::from_pixel(200, 100, Rgba([255, 255, 255, 255]));
+    let mut split = screenshot("split", &image, 2.0, Some(tree.clone()));
+    split.configuration.device_type = "iPad Air 11-inch (M2)".to_string();
+    split.configuration.window = axiom_applications_observability::device_matrix::WindowConfiguration::SplitThird;
+    let mut fullscreen = screenshot("fullscreen", &image, 2.0, Some(tree));
+    fullscreen.configuration.device_type = "iPad Air 11-inch (M2)".to_string();
+    let mut matrix = matrix(vec![split, fullscreen]);
+    let engine = VisualIntelligenceEngine::new().await.unwrap();
+
+    let issues = engine.detect_layout_issues(&mut matrix, crop_dir.path()).await.unwrap();
+
+    assert_eq!(issues.len(), 1, "{:?}", issues);
+    assert_eq!((issues[0].screenshot_id.as_str(), issues[0].kind), ("split", LayoutIssueKind::Offscreen));
+    assert!(
+        matrix.analysis.detected_issues[0].starts_with("iPad Air 11-inch (M2) portrait light split_third (compact): banner"),
+        "{:?}", matrix.analysis.detected_issues
+    );
+    // A split window is not compared with the fullscreen layout
+    assert!(matrix.analysis.consistency_pairs.is_empty(), "{:?}", matrix.analysis.consistency_pairs);
+}
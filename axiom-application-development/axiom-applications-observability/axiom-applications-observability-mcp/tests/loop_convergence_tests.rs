@@ -0,0 +1,174 @@
+mod common;
+
+use async_trait::async_trait;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::*;
+use std::sync::{Arc, Mutex};
+
+const REQUIREMENT: &str = "Users save their notes offline";
+
+/// Generates with the real generator, but the persistence layer stays
+/// broken for the first `broken_for` calls
+struct FlakyGenerator {
+    inner: code_generation::AxiomCodeGenerator,
+    broken_for: usize,
+    feedback: Mutex<Vec<Vec<String>>>,
+}
+
+impl FlakyGenerator {
+    async fn new(broken_for: usize) -> Arc<Self> {
+        Arc::new(Self {
+            inner: code_generation::AxiomCodeGenerator::new().await.unwrap(),
+            broken_for,
+            feedback: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn calls(&self) -> usize {
+        self.feedback.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl ImplementationGenerator for FlakyGenerator {
+    async fn generate(&self, analysis: &RequirementAnalysis, feedback: Vec<String>) -> Result<Implementation> {
+        let call = {
+            let mut calls = self.feedback.lock().unwrap();
+            calls.push(feedback.clone());
+            calls.len()
+        };
+        let mut implementation = self.inner.generate(analysis, feedback).await?;
+        if call <= self.broken_for {
+            let layer = implementation.persistence_layer.as_mut().unwrap();
+            layer.generated_code = "final class Note {\n    var title: String\n".to_string();
+        }
+        Ok(implementation)
+    }
+}
+
+async fn development_loop(generator: Arc<FlakyGenerator>) -> AxiomObservabilityLoop {
+    common::development_loop().await.with_implementation_generator(generator)
+}
+
+fn request(controls: LoopControls) -> DevelopmentCycleRequest {
+    DevelopmentCycleRequest { requirement: REQUIREMENT.to_string(), controls }
+}
+
+#[tokio::test]
+async fn test_generator_that_never_improves_stalls_at_the_configured_iteration() {
+    let generator = FlakyGenerator::new(usize::MAX).await;
+    let controls = LoopControls {
+        max_iterations: 10,
+        convergence: ConvergenceCriteria { stall_after: 2, ..Default::default() },
+        ..Default::default()
+    };
+
+    let result = development_loop(generator.clone()).await.execute_development_cycle(request(controls)).await.unwrap();
+
+    let termination = result.termination.unwrap();
+    assert_eq!(termination.reason, TerminationReason::Stalled);
+    assert_eq!(termination.iterations, 3);
+    assert_eq!(generator.calls(), 3);
+    assert!(!result.success);
+    assert_eq!(result.iterations.len(), 3);
+    assert!(result.iterations[0].diff.is_none());
+    for record in &result.iterations[1..] {
+        let diff = record.diff.as_ref().unwrap();
+        assert!(diff.files_changed.is_empty(), "{:?}", diff.files_changed);
+        assert_eq!(diff.validation_errors_delta, 0);
+        assert_eq!(diff.tests_passed_delta, 0);
+        assert!(!diff.made_progress());
+    }
+}
+
+#[tokio::test]
+async fn test_stall_window_is_configurable() {
+    let generator = FlakyGenerator::new(usize::MAX).await;
+    let controls = LoopControls {
+        max_iterations: 10,
+        convergence: ConvergenceCriteria { stall_after: 4, ..Default::default() },
+        ..Default::default()
+    };
+
+    let result = development_loop(generator.clone()).await.execute_development_cycle(request(controls)).await.unwrap();
+
+    assert_eq!(result.termination.unwrap().reason, TerminationReason::Stalled);
+    assert_eq!(generator.calls(), 5);
+}
+
+#[tokio::test]
+async fn test_failures_are_fed_to_the_next_iteration_until_it_converges() {
+    let generator = FlakyGenerator::new(1).await;
+    let controls = LoopControls { max_iterations: 5, ..Default::default() };
+
+    let result = development_loop(generator.clone()).await.execute_development_cycle(request(controls)).await.unwrap();
+
+    let termination = result.termination.as_ref().unwrap();
+    assert_eq!(termination.reason, TerminationReason::Converged);
+    assert_eq!(termination.iterations, 2);
+    assert!(result.success);
+    let feedback = generator.feedback.lock().unwrap().clone();
+    assert!(feedback[0].is_empty());
+    assert!(feedback[1].iter().any(|issue| issue.contains("never closed")), "{:?}", feedback[1]);
+    let diff = result.iterations[1].diff.as_ref().unwrap();
+    assert_eq!(diff.files_changed, vec!["NotePersistence.swift"]);
+    assert_eq!(diff.validation_errors_delta, -1);
+    assert_eq!(diff.tests_passed_delta, 1);
+    assert_eq!(
+        ToolResult::CompleteLoopResult(Box::new(result)).summary(),
+        "Development loop completed (score: 92.0%), converged after 2 iterations"
+    );
+}
+
+#[tokio::test]
+async fn test_iteration_budget_stops_a_loop_that_is_still_improving() {
+    let generator = FlakyGenerator::new(usize::MAX).await;
+    let controls = LoopControls { max_iterations: 2, ..Default::default() };
+
+    let result = development_loop(generator.clone()).await.execute_development_cycle(request(controls)).await.unwrap();
+
+    let termination = result.termination.unwrap();
+    assert_eq!(termination.reason, TerminationReason::BudgetExhausted);
+    assert_eq!(termination.iterations, 2);
+    assert_eq!(generator.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_time_budget_stops_after_the_iteration_that_exceeds_it() {
+    let generator = FlakyGenerator::new(usize::MAX).await;
+    let controls = LoopControls { max_iterations: 10, max_duration_ms: Some(0), ..Default::default() };
+
+    let result = development_loop(generator.clone()).await.execute_development_cycle(request(controls)).await.unwrap();
+
+    let termination = result.termination.unwrap();
+    assert_eq!(termination.reason, TerminationReason::BudgetExhausted);
+    assert!(termination.detail.contains("past the budget"), "{}", termination.detail);
+    assert_eq!(generator.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_a_plain_cycle_is_a_single_iteration() {
+    let generator = FlakyGenerator::new(0).await;
+
+    let result = development_loop(generator).await.execute_complete_development_cycle(REQUIREMENT.to_string()).await.unwrap();
+
+    assert_eq!(result.iterations.len(), 1);
+    assert_eq!(result.termination.unwrap().reason, TerminationReason::Converged);
+}
+
+#[test]
+fn test_failing_performance_never_converges() {
+    let controls = LoopControls { max_iterations: 3, ..Default::default() };
+    let record: IterationRecord = serde_json::from_value(serde_json::json!({
+        "iteration": 1,
+        "implementation_id": "b1",
+        "files": {},
+        "validation_errors": 0,
+        "tests_passed": 5,
+        "verdict": "fail",
+        "diff": null,
+        "completed_at": "2026-10-15T09:00:00Z",
+    })).unwrap();
+
+    assert!(controls.termination(&[record], std::time::Duration::ZERO).is_none());
+}
@@ -2,6 +2,8 @@ use axiom_applications_observability::mcp::*;
 use axiom_applications_observability::tools::*;
 use axiom_applications_observability::types::*;
 use axiom_applications_observability::error::*;
+use axiom_applications_observability::hot_reload::*;
+use axiom_applications_observability::visual_regression::*;
 
 use std::time::Instant;
 
@@ -18,6 +20,8 @@ async fn test_mcp_tool_execution_code_generation() -> Result<()> {
             max_render_time_ms: 16.0,
             max_memory_mb: 10.0,
         },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
     };
     
     let start_time = Instant::now();
@@ -54,16 +58,19 @@ async fn test_mcp_tool_execution_context_generation() -> Result<()> {
                 property_type: "[Task]".to_string(),
                 is_published: true,
                 default_value: Some("[]".to_string()),
+                requirement_ids: vec![],
             },
             StateProperty {
                 name: "isLoading".to_string(),
                 property_type: "Bool".to_string(),
                 is_published: true,
                 default_value: Some("false".to_string()),
+                requirement_ids: vec![],
             },
         ],
         client_binding: "TaskManagerClient".to_string(),
         lifecycle_management: true,
+        template_overrides: Default::default(),
     };
     
     let result = mcp.execute_tool(AxiomMCPTool::GenerateContext(context_spec)).await?;
@@ -97,16 +104,20 @@ async fn test_mcp_tool_execution_client_generation() -> Result<()> {
                 parameters: vec!["completion: @escaping ([Task]) -> Void".to_string()],
                 return_type: "Void".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             },
             ActionDefinition {
                 name: "createTask".to_string(),
                 parameters: vec!["task: Task".to_string(), "completion: @escaping (Result<Task, Error>) -> Void".to_string()],
                 return_type: "Void".to_string(),
                 is_async: true,
+                requirement_ids: vec![],
             },
         ],
         state_streaming: true,
         mock_implementation: true,
+        template_overrides: Default::default(),
+        persistence: None,
     };
     
     let result = mcp.execute_tool(AxiomMCPTool::GenerateMockClient(client_spec)).await?;
@@ -153,7 +164,7 @@ async fn test_mcp_screenshot_matrix_tool() -> Result<()> {
     let mcp = setup_test_mcp().await?;
     
     let start_time = Instant::now();
-    let result = mcp.execute_tool(AxiomMCPTool::CaptureScreenshotMatrix).await?;
+    let result = mcp.execute_tool(AxiomMCPTool::CaptureScreenshotMatrix(Default::default())).await?;
     let duration = start_time.elapsed();
     
     // Performance target from plan: < 5 seconds for full device matrix (6+ variations)
@@ -164,7 +175,7 @@ async fn test_mcp_screenshot_matrix_tool() -> Result<()> {
             assert!(!matrix.screenshots.is_empty(), "Should capture screenshots");
             assert!(matrix.screenshots.len() >= 4, "Should capture multiple device configurations");
             assert!(matrix.analysis.total_screenshots > 0, "Should analyze screenshots");
-            assert!(matrix.analysis.consistency_score > 80.0, "Should have good consistency");
+            assert!(matrix.analysis.consistency_score > 0.8, "Should have good consistency");
         },
         _ => panic!("Expected ScreenshotMatrix result"),
     }
@@ -188,22 +199,99 @@ async fn test_mcp_visual_analysis_tools() -> Result<()> {
         _ => panic!("Expected VisualComparison result"),
     }
     
-    // Test UI regression detection
-    let result = mcp.execute_tool(AxiomMCPTool::DetectUIRegressions).await?;
+    // Test UI regression detection against baselines recorded from the same screenshot
+    let baselines = tempfile::tempdir()?;
+    let mcp = setup_test_mcp_with_baselines(baselines.path()).await?;
+    let screenshots = vec![png_screenshot([40, 120, 200, 255])];
+    mcp.execute_tool(AxiomMCPTool::DetectUIRegressions(RegressionCheckRequest {
+        mode: RegressionMode::Record,
+        screen: "TaskList".to_string(),
+        screenshots: screenshots.clone(),
+    })).await?;
+    
+    let result = mcp.execute_tool(AxiomMCPTool::DetectUIRegressions(RegressionCheckRequest {
+        mode: RegressionMode::Check,
+        screen: "TaskList".to_string(),
+        screenshots,
+    })).await?;
     
     match result {
         ToolResult::RegressionReport(report) => {
             // Quality target from plan: < 1% false positive rate
             assert!(report.total_comparisons > 0, "Should perform comparisons");
-            // Note: In a real test with actual UI changes, we'd validate the false positive rate
+            assert_eq!(report.regressions_detected, 0, "An identical screenshot is not a regression");
+            assert!(report.passed());
         },
         _ => panic!("Expected RegressionReport result"),
     }
     
+    // A different screenshot of the same configuration fails the check
+    let result = mcp.execute_tool(AxiomMCPTool::DetectUIRegressions(RegressionCheckRequest {
+        mode: RegressionMode::Check,
+        screen: "TaskList".to_string(),
+        screenshots: vec![png_screenshot([220, 40, 40, 255])],
+    })).await?;
+    
+    match result {
+        ToolResult::RegressionReport(report) => {
+            assert_eq!(report.regressions_detected, 1);
+            assert!(!report.passed());
+            assert!(report.configurations[0].diff_image.as_ref().unwrap().exists());
+        },
+        _ => panic!("Expected RegressionReport result"),
+    }
+    
+    // Audit with the element tree given, so no intelligence round trip is needed
+    let tree: axiom_applications_observability::advanced_visual_intelligence::AccessibilityElement = serde_json::from_value(serde_json::json!({
+        "identifier": "addTaskButton",
+        "elementType": "button",
+        "frame": { "x": 0, "y": 0, "width": 20, "height": 20 }
+    }))?;
+    let result = mcp.execute_tool(AxiomMCPTool::AuditAccessibility(
+        axiom_applications_observability::advanced_visual_intelligence::AccessibilityAuditRequest {
+            screenshot: png_screenshot([255, 255, 255, 255]),
+            element_tree: Some(tree),
+        }
+    )).await?;
+    
+    match result {
+        ToolResult::AccessibilityReport(report) => {
+            assert_eq!(report.findings.len(), 2, "Unlabeled and too small");
+            assert_eq!(report.compliance_score, 0.0);
+        },
+        _ => panic!("Expected AccessibilityReport result"),
+    }
+    
     println!("✅ MCP Visual analysis tools test passed");
     Ok(())
 }
 
+#[tokio::test]
+async fn test_mcp_evaluate_performance_against_default_thresholds() -> Result<()> {
+    use axiom_applications_observability::performance_analysis_integration::*;
+
+    let mcp = setup_test_mcp().await?;
+
+    let metrics = CapturedMetrics::new()
+        .with(PerformanceMetric::ColdLaunchMs, 1200.0)
+        .with(PerformanceMetric::MemoryMb, 80.0);
+    let result = mcp.execute_tool(AxiomMCPTool::EvaluatePerformance(metrics)).await?;
+
+    match &result {
+        ToolResult::PerformanceVerdict(verdict) => {
+            assert_eq!(verdict.status, VerdictStatus::Warn, "A 1.2s cold launch is over the warn threshold");
+            assert!(verdict.meets_requirements());
+            assert_eq!(verdict.metric(PerformanceMetric::ColdLaunchMs).unwrap().status, MetricStatus::Warn);
+            assert_eq!(verdict.metric(PerformanceMetric::MemoryMb).unwrap().status, MetricStatus::Pass);
+            assert_eq!(verdict.metric(PerformanceMetric::FrameRateFps).unwrap().status, MetricStatus::Missing);
+        },
+        _ => panic!("Expected PerformanceVerdict result"),
+    }
+    assert_eq!(result.summary(), "Performance passed with warnings (1 passed, 1 warned, 0 failed, 4 missing)");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_mcp_development_session_tools() -> Result<()> {
     let mcp = setup_test_mcp().await?;
@@ -240,16 +328,18 @@ async fn test_mcp_tool_error_handling() -> Result<()> {
             max_render_time_ms: -1.0, // Invalid negative value
             max_memory_mb: -1.0, // Invalid negative value
         },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
     };
     
     let result = mcp.execute_tool(AxiomMCPTool::GeneratePresentation(invalid_presentation_spec)).await;
     
     match result {
-        Err(AxiomMCPError::ValidationError(_)) => {
+        Err(AxiomMCPError::Validation(_)) => {
             println!("✅ Properly rejected invalid presentation spec");
         },
-        Err(AxiomMCPError::ToolExecutionError(_)) => {
-            println!("✅ Properly handled tool execution error for invalid spec");
+        Err(AxiomMCPError::Generation(_)) => {
+            println!("✅ Properly handled generation error for invalid spec");
         },
         Ok(_) => {
             panic!("Should not succeed with invalid spec");
@@ -277,6 +367,8 @@ async fn test_mcp_performance_benchmarks() -> Result<()> {
             max_render_time_ms: 16.0,
             max_memory_mb: 20.0,
         },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
     };
     
     let mut durations = Vec::new();
@@ -325,6 +417,8 @@ async fn test_mcp_concurrent_tool_execution() -> Result<()> {
                         max_render_time_ms: 16.0,
                         max_memory_mb: 5.0,
                     },
+                    template_overrides: Default::default(),
+                    requirement_ids: vec![],
                 };
                 mcp.execute_tool(AxiomMCPTool::GeneratePresentation(spec)).await
             }
@@ -341,6 +435,8 @@ async fn test_mcp_concurrent_tool_execution() -> Result<()> {
                         max_render_time_ms: 16.0,
                         max_memory_mb: 5.0,
                     },
+                    template_overrides: Default::default(),
+                    requirement_ids: vec![],
                 };
                 mcp.execute_tool(AxiomMCPTool::GeneratePresentation(spec)).await
             }
@@ -365,6 +461,118 @@ async fn test_mcp_concurrent_tool_execution() -> Result<()> {
 
 // Helper functions for test setup
 
+#[tokio::test]
+async fn test_mcp_tool_execution_connection_status() -> Result<()> {
+    let mcp = setup_test_mcp().await?;
+    
+    let result = mcp.execute_tool(AxiomMCPTool::GetConnectionStatus).await?;
+    
+    match result {
+        ToolResult::ConnectionStatus(connections) => {
+            let names: Vec<&str> = connections.iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(names, vec!["hot_reload", "intelligence"]);
+            assert_eq!(connections[0].url, "ws://localhost:8080/ws");
+        },
+        _ => panic!("Expected ConnectionStatus result"),
+    }
+    
+    println!("✅ MCP Connection status test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mcp_tool_execution_hot_reload_filters() -> Result<()> {
+    let mcp = setup_test_mcp().await?;
+    
+    let filters = HotReloadFilters {
+        include: vec!["**/*.swift".to_string()],
+        exclude: vec!["**/Previews/**".to_string()],
+    };
+    mcp.execute_tool(AxiomMCPTool::SetHotReloadFilters(filters.clone())).await?;
+    
+    mcp.file_changed(FileChange::new("Sources/Previews/ContentView_Previews.swift", ChangeType::Modified))?;
+    mcp.file_changed(FileChange::new("Sources/ContentView.swift", ChangeType::Modified))?;
+    
+    match mcp.execute_tool(AxiomMCPTool::GetHotReloadStats).await? {
+        ToolResult::HotReloadStats(stats) => {
+            assert_eq!(stats.filters, filters);
+            assert_eq!(stats.events_received, 2);
+            assert_eq!(stats.events_filtered, 1);
+            assert_eq!(stats.filtered_by["**/Previews/**"], 1);
+        },
+        _ => panic!("Expected HotReloadStats result"),
+    }
+    
+    let invalid = HotReloadFilters { include: vec!["[".to_string()], exclude: vec![] };
+    assert!(mcp.execute_tool(AxiomMCPTool::SetHotReloadFilters(invalid)).await.is_err());
+    
+    println!("✅ MCP Hot reload filters test passed");
+    Ok(())
+}
+
+fn png_screenshot(color: [u8; 4]) -> Screenshot {
+    let image = image::RgbaImage::from_pixel(60, 120, image::Rgba(color));
+    Screenshot {
+        id: "screenshot".to_string(),
+        client_id: "test-client".to_string(),
+        configuration: ScreenshotConfiguration {
+            device_type: "iPhone 15".to_string(),
+            screen_size: ScreenSize { width: 20.0, height: 40.0 },
+            orientation: "portrait".to_string(),
+            scale: 3.0,
+            color_scheme: "light".to_string(),
+            capture_mode: "standard".to_string(),
+            status_bar: None,
+            runtime: None,
+            window: Default::default(),
+        },
+        image_data: encode_png(&image).unwrap(),
+        image: None,
+        metadata: ScreenshotMetadata {
+            timestamp: chrono::Utc::now(),
+            device_info: DeviceInfo {
+                model: "iPhone 15".to_string(),
+                screen_size: ScreenSize { width: 20.0, height: 40.0 },
+                orientation: "portrait".to_string(),
+                udid: None,
+            },
+            app_state: AppState {
+                view_hierarchy: "TaskListView".to_string(),
+                active_context: "TaskListContext".to_string(),
+                view_tree: None,
+            },
+            overrides: Default::default(),
+        },
+    }
+}
+
+async fn setup_test_mcp_with_baselines(baseline_dir: &std::path::Path) -> Result<AxiomApplicationsObservabilityMCP> {
+    let config = MCPConfiguration {
+        hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
+        intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
+        simulator_management_enabled: true,
+        code_generation_enabled: true,
+        visual_analysis_enabled: true,
+        performance_monitoring_enabled: true,
+        visual_regression: VisualRegressionConfig {
+            baseline_dir: baseline_dir.to_path_buf(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: true,
+        hot_reload_integration: true,
+        visual_analysis: true,
+        simulator_management: true,
+        performance_monitoring: true,
+    };
+    
+    AxiomApplicationsObservabilityMCP::new(config, capabilities).await
+}
+
 async fn setup_test_mcp() -> Result<AxiomApplicationsObservabilityMCP> {
     let config = MCPConfiguration {
         hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
@@ -373,6 +581,7 @@ async fn setup_test_mcp() -> Result<AxiomApplicationsObservabilityMCP> {
         code_generation_enabled: true,
         visual_analysis_enabled: true,
         performance_monitoring_enabled: true,
+        ..Default::default()
     };
     
     let capabilities = MCPCapabilities {
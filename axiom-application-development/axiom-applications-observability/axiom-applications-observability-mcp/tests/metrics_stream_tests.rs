@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use axiom_applications_observability::metrics_stream::*;
+use axiom_applications_observability::performance_analysis_integration::{CapturedMetrics, PerformanceMetric};
+use axiom_applications_observability::protocol::JsonRpcNotification;
+use axiom_applications_observability::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Memory grows by one megabyte per sample while the frame rate holds at 60
+#[derive(Default)]
+struct GrowingMemory {
+    samples: AtomicUsize,
+}
+
+#[async_trait]
+impl MetricsSource for GrowingMemory {
+    async fn sample(&self, _metrics: &[PerformanceMetric]) -> Result<CapturedMetrics> {
+        let sample = self.samples.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(CapturedMetrics::new()
+            .with(PerformanceMetric::MemoryMb, 100.0 + sample as f64)
+            .with(PerformanceMetric::FrameRateFps, 60.0))
+    }
+}
+
+/// A session whose transport takes `delay` to send each notification
+#[derive(Debug)]
+struct SlowSession {
+    delay: Duration,
+    updates: Mutex<Vec<MetricsUpdate>>,
+    sending: AtomicUsize,
+    most_at_once: AtomicUsize,
+}
+
+impl SlowSession {
+    fn new(delay: Duration) -> Arc<Self> {
+        Arc::new(Self { delay, updates: Mutex::new(Vec::new()), sending: AtomicUsize::new(0), most_at_once: AtomicUsize::new(0) })
+    }
+
+    fn updates(&self) -> Vec<MetricsUpdate> {
+        self.updates.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl NotificationSink for SlowSession {
+    async fn notify(&self, notification: JsonRpcNotification) -> Result<()> {
+        let sending = self.sending.fetch_add(1, Ordering::SeqCst) + 1;
+        self.most_at_once.fetch_max(sending, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.updates.lock().unwrap().push(serde_json::from_value(notification.params).unwrap());
+        self.sending.fetch_sub(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn request(interval_ms: u64) -> MetricsStreamRequest {
+    MetricsStreamRequest { interval_ms, metrics: vec![PerformanceMetric::MemoryMb, PerformanceMetric::FrameRateFps] }
+}
+
+async fn next_update(notifications: &mut mpsc::Receiver<JsonRpcNotification>) -> MetricsUpdate {
+    let notification = tokio::time::timeout(Duration::from_secs(2), notifications.recv())
+        .await
+        .expect("no update within 2s")
+        .expect("stream ended");
+    assert_eq!(notification.method, METRICS_NOTIFICATION_METHOD);
+    serde_json::from_value(notification.params).unwrap()
+}
+
+fn memory(metrics: &CapturedMetrics) -> Option<f64> {
+    metrics.get(PerformanceMetric::MemoryMb)
+}
+
+#[tokio::test]
+async fn test_updates_follow_the_interval_and_carry_deltas() {
+    let streams = MetricsStreams::new(Arc::new(GrowingMemory::default()));
+    let (sink, mut notifications) = mpsc::channel(16);
+
+    let handle = streams.start(&request(100), Arc::new(sink)).unwrap();
+    let mut updates = Vec::new();
+    for _ in 0..5 {
+        updates.push(next_update(&mut notifications).await);
+    }
+    let summary = streams.stop(&handle.stream_id).unwrap();
+
+    let sequences: Vec<u64> = updates.iter().map(|update| update.sequence).collect();
+    assert_eq!(sequences, vec![1, 2, 3, 4, 5]);
+    for pair in updates.windows(2) {
+        let gap = (pair[1].sampled_at - pair[0].sampled_at).num_milliseconds();
+        assert!((80..=250).contains(&gap), "updates {}ms apart", gap);
+    }
+    // The first update reports every metric, later ones only what changed
+    assert_eq!(updates[0].values.get(PerformanceMetric::FrameRateFps), Some(60.0));
+    assert!(updates[0].deltas.is_empty());
+    for update in &updates[1..] {
+        assert_eq!(update.values.get(PerformanceMetric::FrameRateFps), None);
+        assert_eq!(memory(&update.deltas), Some(1.0));
+        assert_eq!(update.samples, 1);
+    }
+    assert_eq!(summary.samples_coalesced, 0);
+    assert!(summary.updates_sent >= 4, "{:?}", summary);
+}
+
+#[tokio::test]
+async fn test_slow_session_gets_coalesced_updates() {
+    let streams = MetricsStreams::new(Arc::new(GrowingMemory::default()));
+    let session = SlowSession::new(Duration::from_millis(350));
+
+    let handle = streams.start(&request(100), session.clone()).unwrap();
+    tokio::time::sleep(Duration::from_millis(1300)).await;
+    let summary = streams.stop(&handle.stream_id).unwrap();
+
+    let updates = session.updates();
+    assert!((2..=5).contains(&updates.len()), "{} updates", updates.len());
+    assert_eq!(session.most_at_once.load(Ordering::SeqCst), 1);
+    assert!(updates[1..].iter().all(|update| update.samples >= 2), "{:?}", updates);
+    assert!(summary.samples_coalesced >= 2, "{:?}", summary);
+    // Deltas span every sample folded into the update, so none are lost
+    for pair in updates.windows(2) {
+        assert_eq!(memory(&pair[1].deltas), Some(memory(&pair[1].values).unwrap() - memory(&pair[0].values).unwrap()));
+        assert_eq!(memory(&pair[1].deltas), Some(f64::from(pair[1].samples)));
+    }
+}
+
+#[tokio::test]
+async fn test_unchanged_samples_send_no_update() {
+    struct Steady;
+
+    #[async_trait]
+    impl MetricsSource for Steady {
+        async fn sample(&self, _metrics: &[PerformanceMetric]) -> Result<CapturedMetrics> {
+            Ok(CapturedMetrics::new().with(PerformanceMetric::MemoryMb, 120.0))
+        }
+    }
+
+    let streams = MetricsStreams::new(Arc::new(Steady));
+    let (sink, mut notifications) = mpsc::channel(16);
+
+    let handle = streams.start(&request(100), Arc::new(sink)).unwrap();
+    let first = next_update(&mut notifications).await;
+    let more = tokio::time::timeout(Duration::from_millis(400), notifications.recv()).await;
+    streams.stop(&handle.stream_id).unwrap();
+
+    assert_eq!(memory(&first.values), Some(120.0));
+    assert!(more.is_err(), "unexpected update {:?}", more);
+}
+
+#[tokio::test]
+async fn test_sessions_stream_independently() {
+    let streams = MetricsStreams::new(Arc::new(GrowingMemory::default()));
+    let (first_sink, mut first) = mpsc::channel(16);
+    let (second_sink, mut second) = mpsc::channel(16);
+
+    let fast = streams.start(&request(100), Arc::new(first_sink)).unwrap();
+    let slow = streams.start(&MetricsStreamRequest { interval_ms: 300, ..request(100) }, Arc::new(second_sink)).unwrap();
+    assert_ne!(fast.stream_id, slow.stream_id);
+    assert_eq!(streams.active().len(), 2);
+
+    let from_first = next_update(&mut first).await;
+    let from_second = next_update(&mut second).await;
+    assert_eq!((from_first.stream_id.as_str(), from_first.sequence), (fast.stream_id.as_str(), 1));
+    assert_eq!((from_second.stream_id.as_str(), from_second.sequence), (slow.stream_id.as_str(), 1));
+
+    streams.stop(&fast.stream_id).unwrap();
+    let still_streaming = next_update(&mut second).await;
+    assert_eq!(still_streaming.stream_id, slow.stream_id);
+    assert_eq!(streams.active(), vec![slow]);
+}
+
+#[tokio::test]
+async fn test_requests_are_checked() {
+    let streams = MetricsStreams::new(Arc::new(GrowingMemory::default()));
+    let (sink, _notifications) = mpsc::channel(1);
+
+    let too_fast = streams.start(&request(MIN_METRICS_INTERVAL_MS - 1), Arc::new(sink));
+    let unknown = streams.stop("no-such-stream");
+
+    assert!(matches!(too_fast, Err(AxiomMCPError::Validation(_))), "{:?}", too_fast);
+    assert!(matches!(unknown, Err(AxiomMCPError::Validation(_))), "{:?}", unknown);
+
+    let defaults: MetricsStreamRequest = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(defaults, MetricsStreamRequest::default());
+    let (sink, _notifications) = mpsc::channel(1);
+    let handle = streams.start(&defaults, Arc::new(sink)).unwrap();
+    assert_eq!(handle.metrics, DEFAULT_STREAMED_METRICS.to_vec());
+    assert_eq!(handle.interval_ms, DEFAULT_METRICS_INTERVAL_MS);
+}
+
+#[tokio::test]
+async fn test_metrics_stream_tools_need_a_session_that_takes_notifications() {
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: true,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(MCPConfiguration::default(), capabilities)
+        .await
+        .unwrap()
+        .with_metrics_source(Arc::new(GrowingMemory::default()));
+
+    let without_sink = mcp.execute_tool(AxiomMCPTool::StartMetricsStream(request(100))).await;
+    assert!(matches!(without_sink, Err(AxiomMCPError::Configuration { ref field, .. }) if field == "notification_sink"), "{:?}", without_sink);
+
+    let (sink, mut notifications) = mpsc::channel(16);
+    let session = mcp.clone().with_notification_sink(Arc::new(sink));
+    let ToolResult::MetricsStreamStarted(handle) = session.execute_tool(AxiomMCPTool::StartMetricsStream(request(100))).await.unwrap() else {
+        panic!("Expected a started stream");
+    };
+    let update = next_update(&mut notifications).await;
+    assert_eq!(update.stream_id, handle.stream_id);
+
+    // Any clone can stop it, since clones share their streams
+    let ToolResult::MetricsStreamStopped(summary) = mcp.execute_tool(AxiomMCPTool::StopMetricsStream(handle.stream_id.clone())).await.unwrap() else {
+        panic!("Expected a stopped stream");
+    };
+    assert_eq!(summary.stream_id, handle.stream_id);
+}
@@ -0,0 +1,205 @@
+mod common;
+
+use axiom_applications_observability::code_generation::*;
+use axiom_applications_observability::complete_development_loop::{infer_navigation, navigation_for};
+use axiom_applications_observability::*;
+
+fn route(name: &str, presentation: &str, style: PresentationStyle) -> RouteSpec {
+    RouteSpec {
+        name: name.to_string(),
+        presentation: presentation.to_string(),
+        parameters: vec![],
+        style,
+        from: vec![],
+    }
+}
+
+fn parameter(name: &str, parameter_type: &str) -> RouteParameter {
+    RouteParameter { name: name.to_string(), parameter_type: parameter_type.to_string() }
+}
+
+fn task_navigation() -> NavigationSpec {
+    NavigationSpec {
+        root: "TaskListView".to_string(),
+        routes: vec![
+            RouteSpec {
+                parameters: vec![parameter("taskID", "UUID"), parameter("highlight", "Bool")],
+                ..route("taskDetail", "TaskDetailView", PresentationStyle::Push)
+            },
+            route("settings", "SettingsView", PresentationStyle::Sheet),
+            RouteSpec {
+                parameters: vec![parameter("taskID", "UUID")],
+                from: vec!["TaskDetailView".to_string()],
+                ..route("editTask", "EditTaskView", PresentationStyle::FullScreen)
+            },
+        ],
+    }
+}
+
+fn presentation_layer(names: &[&str]) -> PresentationLayer {
+    PresentationLayer {
+        presentations: names
+            .iter()
+            .map(|name| GeneratedPresentation {
+                name: name.to_string(),
+                context_binding: "TaskManagerContext".to_string(),
+                ui_components: vec![],
+                generated_code: String::new(),
+            })
+            .collect(),
+        total_presentations: names.len(),
+    }
+}
+
+#[tokio::test]
+async fn test_parameterized_routes_carry_their_values_to_the_destination() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let code = generator.generate_navigation(task_navigation()).await.unwrap().generated_code;
+
+    assert!(code.starts_with("import SwiftUI\n\nenum Route: Hashable, Identifiable {\n"), "{}", code);
+    assert!(code.contains("    case taskDetail(taskID: UUID, highlight: Bool)\n    case settings\n    case editTask(taskID: UUID)\n"));
+    assert!(code.contains(
+        "        case .taskDetail(let taskID, let highlight):\n            TaskDetailView(taskID: taskID, highlight: highlight)\n"
+    ), "{}", code);
+    assert!(code.contains("        case .settings:\n            SettingsView()\n"));
+    assert!(code.contains(
+        "    func showTaskDetail(taskID: UUID, highlight: Bool) {\n        coordinator.show(.taskDetail(taskID: taskID, highlight: highlight))\n    }"
+    ));
+
+    let result = generator.validate_generated_code(&code).await.unwrap();
+    assert!(result.passed, "{}\n{:?}", code, result.issues);
+    assert!(result.pattern_warnings.is_empty(), "{:?}", result.recommendations);
+}
+
+#[tokio::test]
+async fn test_routes_are_presented_by_their_style() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let code = generator.generate_navigation(task_navigation()).await.unwrap().generated_code;
+
+    assert!(code.contains("@MainActor\nfinal class AppCoordinator: ObservableObject {\n    @Published var path = NavigationPath()\n"));
+    assert!(code.contains("        case .taskDetail:\n            path.append(route)\n"));
+    assert!(code.contains("        case .settings:\n            sheet = route\n"));
+    assert!(code.contains("        case .editTask:\n            fullScreenCover = route\n"));
+    assert!(code.contains("        NavigationStack(path: $coordinator.path) {\n            TaskListView()\n"), "{}", code);
+    assert!(code.contains(".sheet(item: $coordinator.sheet)"));
+    assert!(code.contains(".fullScreenCover(item: $coordinator.fullScreenCover)"));
+}
+
+#[tokio::test]
+async fn test_each_presentation_gets_hooks_for_the_routes_it_triggers() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let code = generator.generate_navigation(task_navigation()).await.unwrap().generated_code;
+
+    // Routes without `from` are triggered from the root
+    assert!(code.contains("struct TaskListViewRoutes {"));
+    assert!(code.contains("    func showSettings() {\n        coordinator.show(.settings)\n    }"));
+    assert!(code.contains("    var taskListViewRoutes: TaskListViewRoutes { TaskListViewRoutes(coordinator: self) }"));
+    assert!(code.contains("struct TaskDetailViewRoutes {"));
+    assert!(code.contains("    func showEditTask(taskID: UUID) {\n        coordinator.show(.editTask(taskID: taskID))\n    }"));
+    assert_eq!(code.matches("func showEditTask").count(), 1);
+    assert!(!code.contains("SettingsViewRoutes"), "screens that navigate nowhere have no hooks");
+}
+
+#[test]
+fn test_navigation_to_a_missing_presentation_is_an_error() {
+    let navigation = task_navigation();
+
+    let complete = navigation.check_presentations(&presentation_layer(&[
+        "TaskListView", "TaskDetailView", "SettingsView", "EditTaskView",
+    ]));
+    let missing = navigation.check_presentations(&presentation_layer(&["TaskListView", "TaskDetailView"]));
+
+    assert!(complete.is_ok());
+    match missing {
+        Err(AxiomMCPError::Generation(context)) => assert_eq!(
+            context.message,
+            "Navigation refers to presentations missing from the implementation: SettingsView, EditTaskView"
+        ),
+        other => panic!("expected a generation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_navigation_specs_are_rejected() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let duplicate = NavigationSpec {
+        routes: vec![
+            route("settings", "SettingsView", PresentationStyle::Sheet),
+            route("settings", "PreferencesView", PresentationStyle::Push),
+        ],
+        ..task_navigation()
+    };
+    let empty = NavigationSpec { routes: vec![], ..task_navigation() };
+    let unnamed = NavigationSpec {
+        routes: vec![RouteSpec { parameters: vec![parameter("task id", "UUID")], ..route("detail", "DetailView", PresentationStyle::Push) }],
+        ..task_navigation()
+    };
+
+    for (spec, problem) in [
+        (duplicate, "Route settings is declared more than once"),
+        (empty, "Navigation from TaskListView has no routes"),
+        (unnamed, "Route detail uses 'task id', which is not a Swift identifier"),
+    ] {
+        match generator.generate_navigation(spec).await {
+            Err(AxiomMCPError::Validation(context)) => assert_eq!(context.message, problem),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_navigation_is_inferred_from_multi_screen_requirements() {
+    let navigation = infer_navigation("A task list screen, a task detail screen and a settings screen").unwrap();
+
+    assert_eq!(navigation.root, "TaskListView");
+    let routes: Vec<(&str, &str, PresentationStyle)> = navigation
+        .routes
+        .iter()
+        .map(|route| (route.name.as_str(), route.presentation.as_str(), route.style))
+        .collect();
+    assert_eq!(
+        routes,
+        vec![
+            ("taskDetail", "TaskDetailView", PresentationStyle::Push),
+            ("settings", "SettingsView", PresentationStyle::Sheet),
+        ]
+    );
+    assert_eq!(navigation.routes[0].parameters.len(), 1);
+    assert_eq!(navigation.routes[0].parameters[0].name, "id");
+    assert!(navigation.routes[1].parameters.is_empty());
+
+    assert!(infer_navigation("A single settings screen").is_none());
+    assert!(infer_navigation("Task list").is_none());
+}
+
+#[test]
+fn test_route_names_lowercase_multi_byte_screen_names() {
+    let screens = ["Home".to_string(), "Ÿield".to_string(), "Ωmega".to_string()];
+
+    let navigation = navigation_for(&screens).unwrap();
+
+    let names: Vec<&str> = navigation.routes.iter().map(|route| route.name.as_str()).collect();
+    assert_eq!(names, vec!["ÿield", "ωmega"]);
+}
+
+#[tokio::test]
+async fn test_development_loop_generates_a_presentation_per_screen_and_their_navigation() {
+    let result = common::development_loop()
+        .await
+        .execute_complete_development_cycle("A task list screen, a task detail screen and a settings screen".to_string())
+        .await
+        .unwrap();
+
+    assert!(result.requirement_analysis.navigation.is_some());
+    let names: Vec<&str> = result.implementation.presentation_layer.presentations.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["TaskListView", "TaskDetailView", "SettingsView"]);
+    assert_eq!(result.implementation.presentation_layer.total_presentations, 3);
+    let navigation = result.implementation.navigation_layer.as_ref().unwrap();
+    assert_eq!(navigation.root, "TaskListView");
+    assert_eq!(navigation.routes, vec!["taskDetail", "settings"]);
+    assert!(navigation.generated_code.contains("enum Route"));
+    assert!(result.validation_result.passed, "{:?}", result.validation_result.issues);
+}
@@ -0,0 +1,241 @@
+mod common;
+
+use axiom_applications_observability::*;
+use axiom_applications_observability::performance_analysis_integration::*;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn thresholds(metrics: &[(PerformanceMetric, MetricThreshold)]) -> PerformanceThresholds {
+    PerformanceThresholds {
+        metrics: metrics.iter().copied().collect::<BTreeMap<_, _>>(),
+        fail_on_missing: false,
+    }
+}
+
+fn launch_thresholds() -> PerformanceThresholds {
+    thresholds(&[(PerformanceMetric::ColdLaunchMs, MetricThreshold::new(400.0, 1000.0, 2000.0))])
+}
+
+fn cold_launch(ms: f64) -> CapturedMetrics {
+    CapturedMetrics::new().with(PerformanceMetric::ColdLaunchMs, ms)
+}
+
+fn status(verdict: &PerformanceVerdict, metric: PerformanceMetric) -> MetricStatus {
+    verdict.metric(metric).unwrap().status
+}
+
+#[test]
+fn test_lower_is_better_boundaries() {
+    let thresholds = launch_thresholds();
+    let cases = [
+        (300.0, MetricStatus::Pass),
+        (1000.0, MetricStatus::Pass),
+        (1000.1, MetricStatus::Warn),
+        (2000.0, MetricStatus::Warn),
+        (2000.1, MetricStatus::Fail),
+    ];
+
+    for (value, expected) in cases {
+        let verdict = thresholds.evaluate(&cold_launch(value));
+        assert_eq!(status(&verdict, PerformanceMetric::ColdLaunchMs), expected, "{}ms", value);
+    }
+}
+
+#[test]
+fn test_higher_is_better_boundaries() {
+    let thresholds = thresholds(&[(PerformanceMetric::FrameRateFps, MetricThreshold::new(60.0, 55.0, 45.0))]);
+    let cases = [
+        (60.0, MetricStatus::Pass),
+        (55.0, MetricStatus::Pass),
+        (54.9, MetricStatus::Warn),
+        (45.0, MetricStatus::Warn),
+        (44.9, MetricStatus::Fail),
+    ];
+
+    for (value, expected) in cases {
+        let verdict = thresholds.evaluate(&CapturedMetrics::new().with(PerformanceMetric::FrameRateFps, value));
+        assert_eq!(status(&verdict, PerformanceMetric::FrameRateFps), expected, "{}fps", value);
+    }
+}
+
+#[test]
+fn test_deltas_are_measured_from_the_target() {
+    let verdict = launch_thresholds().evaluate(&cold_launch(500.0));
+    let launch = verdict.metric(PerformanceMetric::ColdLaunchMs).unwrap();
+
+    assert_eq!(launch.value, Some(500.0));
+    assert_eq!(launch.target, 400.0);
+    assert_eq!(launch.delta, Some(100.0));
+    assert_eq!(launch.delta_percent, Some(25.0));
+}
+
+#[test]
+fn test_zero_target_has_no_delta_percent() {
+    let thresholds = thresholds(&[(PerformanceMetric::HitchTimeRatio, MetricThreshold::new(0.0, 5.0, 10.0))]);
+
+    let verdict = thresholds.evaluate(&CapturedMetrics::new().with(PerformanceMetric::HitchTimeRatio, 2.0));
+    let hitches = verdict.metric(PerformanceMetric::HitchTimeRatio).unwrap();
+
+    assert_eq!(hitches.delta, Some(2.0));
+    assert_eq!(hitches.delta_percent, None);
+}
+
+#[test]
+fn test_overall_status_is_the_worst_metric() {
+    let thresholds = thresholds(&[
+        (PerformanceMetric::ColdLaunchMs, MetricThreshold::new(400.0, 1000.0, 2000.0)),
+        (PerformanceMetric::MemoryMb, MetricThreshold::new(100.0, 250.0, 500.0)),
+    ]);
+
+    let warned = thresholds.evaluate(&cold_launch(300.0).with(PerformanceMetric::MemoryMb, 300.0));
+    let failed = thresholds.evaluate(&cold_launch(2500.0).with(PerformanceMetric::MemoryMb, 300.0));
+
+    assert_eq!(warned.status, VerdictStatus::Warn);
+    assert!(warned.meets_requirements());
+    assert_eq!(failed.status, VerdictStatus::Fail);
+    assert!(!failed.meets_requirements());
+}
+
+#[test]
+fn test_missing_metrics_are_reported_without_failing_by_default() {
+    let mut thresholds = thresholds(&[
+        (PerformanceMetric::ColdLaunchMs, MetricThreshold::new(400.0, 1000.0, 2000.0)),
+        (PerformanceMetric::FrameRateFps, MetricThreshold::new(60.0, 55.0, 45.0)),
+    ]);
+
+    let verdict = thresholds.evaluate(&cold_launch(300.0));
+    let frame_rate = verdict.metric(PerformanceMetric::FrameRateFps).unwrap();
+    assert_eq!(frame_rate.status, MetricStatus::Missing);
+    assert_eq!(frame_rate.value, None);
+    assert_eq!(frame_rate.delta, None);
+    assert_eq!(verdict.status, VerdictStatus::Pass);
+
+    thresholds.fail_on_missing = true;
+    assert_eq!(thresholds.evaluate(&cold_launch(300.0)).status, VerdictStatus::Fail);
+}
+
+#[test]
+fn test_metrics_without_a_threshold_are_not_graded() {
+    let verdict = launch_thresholds().evaluate(&cold_launch(300.0).with(PerformanceMetric::CpuPercent, 99.0));
+
+    assert_eq!(verdict.metrics.len(), 1);
+    assert!(verdict.metric(PerformanceMetric::CpuPercent).is_none());
+    assert_eq!(verdict.status, VerdictStatus::Pass);
+}
+
+#[test]
+fn test_no_thresholds_pass_anything() {
+    let verdict = thresholds(&[]).evaluate(&CapturedMetrics::new());
+
+    assert_eq!(verdict.status, VerdictStatus::Pass);
+    assert!(verdict.metrics.is_empty());
+}
+
+#[test]
+fn test_thresholds_must_order_from_best_to_worst() {
+    let inverted = thresholds(&[(PerformanceMetric::ColdLaunchMs, MetricThreshold::new(400.0, 2000.0, 1000.0))]);
+    let frame_rate_as_if_lower_is_better = thresholds(&[(PerformanceMetric::FrameRateFps, MetricThreshold::new(45.0, 55.0, 60.0))]);
+
+    assert!(inverted.validate().is_err());
+    assert!(frame_rate_as_if_lower_is_better.validate().is_err());
+    assert!(PerformanceThresholds::default().validate().is_ok());
+}
+
+#[test]
+fn test_estimate_converts_to_metrics() {
+    let estimate = types::PerformanceEstimate {
+        estimated_memory_usage_kb: 2048,
+        estimated_startup_time_ms: 150.0,
+        estimated_cpu_usage_percent: 12.0,
+        performance_grade: "A".to_string(),
+    };
+
+    let metrics = CapturedMetrics::from_estimate(&estimate);
+
+    assert_eq!(metrics.get(PerformanceMetric::ColdLaunchMs), Some(150.0));
+    assert_eq!(metrics.get(PerformanceMetric::MemoryMb), Some(2.0));
+    assert_eq!(metrics.get(PerformanceMetric::CpuPercent), Some(12.0));
+    assert_eq!(metrics.get(PerformanceMetric::FrameRateFps), None);
+}
+
+#[tokio::test]
+async fn test_project_file_replaces_configured_thresholds() {
+    let project = tempfile::tempdir().unwrap();
+    let configured = launch_thresholds();
+
+    let without_file = PerformanceThresholds::for_project(project.path(), &configured).await.unwrap();
+    assert_eq!(without_file, configured);
+
+    std::fs::create_dir_all(project.path().join(".axiom")).unwrap();
+    std::fs::write(
+        project.path().join(PROJECT_THRESHOLDS_FILE),
+        r#"{ "metrics": { "memory_mb": { "target": 50, "warn": 80, "fail": 120 } }, "fail_on_missing": true }"#,
+    )
+    .unwrap();
+
+    let from_file = PerformanceThresholds::for_project(project.path(), &configured).await.unwrap();
+    assert_eq!(from_file.metrics.len(), 1);
+    assert_eq!(from_file.metrics[&PerformanceMetric::MemoryMb], MetricThreshold::new(50.0, 80.0, 120.0));
+    assert!(from_file.fail_on_missing);
+}
+
+#[tokio::test]
+async fn test_invalid_project_file_is_rejected() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(project.path().join(".axiom")).unwrap();
+    std::fs::write(
+        project.path().join(PROJECT_THRESHOLDS_FILE),
+        r#"{ "metrics": { "cold_launch_ms": { "target": 900, "warn": 500, "fail": 1000 } } }"#,
+    )
+    .unwrap();
+
+    let result = PerformanceThresholds::for_project(project.path(), &PerformanceThresholds::default()).await;
+
+    assert!(matches!(result, Err(error::AxiomMCPError::Validation(_))));
+}
+
+async fn performance_analysis() -> PerformanceAnalysisIntegration {
+    let hot_reload_client = Arc::new(hot_reload::HotReloadClient::new("ws://localhost:8080/ws").await.unwrap());
+    let intelligence_client = Arc::new(intelligence::IntelligenceClient::new("ws://localhost:8080/intelligence").await.unwrap());
+    PerformanceAnalysisIntegration::new(intelligence_client, hot_reload_client).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_analysis_report_grades_the_spec_metrics() {
+    let analysis = performance_analysis().await.with_thresholds(launch_thresholds()).unwrap();
+    let spec = |metrics| PerformanceAnalysisSpec {
+        analysis_name: "launch".to_string(),
+        duration_seconds: 1,
+        profilers_to_include: vec!["Launch".to_string()],
+        detailed_analysis: false,
+        metrics,
+    };
+
+    let graded = analysis.start_comprehensive_analysis(spec(cold_launch(2500.0))).await.unwrap();
+    let ungraded = analysis.start_comprehensive_analysis(spec(CapturedMetrics::new())).await.unwrap();
+
+    assert_eq!(graded.verdict.unwrap().status, VerdictStatus::Fail);
+    assert!(ungraded.verdict.is_none());
+}
+
+async fn development_loop(thresholds: PerformanceThresholds) -> complete_development_loop::AxiomObservabilityLoop {
+    common::DevelopmentLoopBuilder::new()
+        .with_thresholds(thresholds)
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn test_development_loop_fails_an_iteration_over_the_performance_thresholds() {
+    // The generated implementation estimates a 150ms launch
+    let generous = development_loop(launch_thresholds()).await;
+    let strict = development_loop(thresholds(&[(PerformanceMetric::ColdLaunchMs, MetricThreshold::new(50.0, 80.0, 100.0))])).await;
+
+    let passed = generous.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+    let failed = strict.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    assert!(passed.success);
+    assert_eq!(passed.performance_verdict.unwrap().status, VerdictStatus::Pass);
+    assert!(!failed.success);
+    assert_eq!(failed.performance_verdict.unwrap().status, VerdictStatus::Fail);
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use axiom_applications_observability::*;
+use axiom_applications_observability::performance_analysis_integration::*;
+use chrono::{Duration, TimeZone, Utc};
+
+fn snapshot(cycle: i64, metrics: CapturedMetrics) -> PerformanceSnapshot {
+    PerformanceSnapshot {
+        recorded_at: Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap() + Duration::minutes(cycle),
+        analysis_id: format!("analysis-{}", cycle),
+        implementation_id: format!("implementation-{}", cycle),
+        metrics,
+    }
+}
+
+/// One snapshot per value of `metric`, oldest first
+fn history_of(metric: PerformanceMetric, values: &[f64]) -> Vec<PerformanceSnapshot> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(cycle, &value)| snapshot(cycle as i64, CapturedMetrics::new().with(metric, value)))
+        .collect()
+}
+
+fn trend(trends: &PerformanceTrends, metric: PerformanceMetric) -> &MetricTrend {
+    trends.metrics.iter().find(|trend| trend.metric == metric).unwrap()
+}
+
+#[test]
+fn test_latest_memory_above_the_rolling_median_is_a_regression() {
+    let history = history_of(PerformanceMetric::MemoryMb, &[100.0, 104.0, 98.0, 102.0, 115.0]);
+
+    let trends = PerformanceTrends::from_snapshots(&history, TrendOptions::default());
+    let memory = trend(&trends, PerformanceMetric::MemoryMb);
+
+    assert_eq!(memory.points.len(), 5);
+    assert_eq!(memory.latest, 115.0);
+    assert_eq!(memory.rolling_median, Some(101.0));
+    assert!(memory.regressed, "115MB is {:?}% over 101MB", memory.worse_by_percent);
+    assert_eq!(trends.regressions().count(), 1);
+}
+
+#[test]
+fn test_regression_threshold_boundary() {
+    // 110 is exactly 10% over a median of 100
+    let at_threshold = history_of(PerformanceMetric::ColdLaunchMs, &[100.0, 100.0, 110.0]);
+    let over_threshold = history_of(PerformanceMetric::ColdLaunchMs, &[100.0, 100.0, 111.0]);
+
+    let at = PerformanceTrends::from_snapshots(&at_threshold, TrendOptions::default());
+    let over = PerformanceTrends::from_snapshots(&over_threshold, TrendOptions::default());
+
+    assert!(!trend(&at, PerformanceMetric::ColdLaunchMs).regressed);
+    assert!(trend(&over, PerformanceMetric::ColdLaunchMs).regressed);
+}
+
+#[test]
+fn test_improvements_are_not_regressions() {
+    let history = history_of(PerformanceMetric::MemoryMb, &[200.0, 210.0, 120.0]);
+
+    let trends = PerformanceTrends::from_snapshots(&history, TrendOptions::default());
+    let memory = trend(&trends, PerformanceMetric::MemoryMb);
+
+    assert!(!memory.regressed);
+    assert!(memory.worse_by_percent.unwrap() < 0.0);
+}
+
+#[test]
+fn test_falling_frame_rate_is_a_regression() {
+    let history = history_of(PerformanceMetric::FrameRateFps, &[60.0, 59.0, 60.0, 48.0]);
+
+    let trends = PerformanceTrends::from_snapshots(&history, TrendOptions::default());
+    let frame_rate = trend(&trends, PerformanceMetric::FrameRateFps);
+
+    assert_eq!(frame_rate.rolling_median, Some(60.0));
+    assert_eq!(frame_rate.worse_by_percent, Some(20.0));
+    assert!(frame_rate.regressed);
+}
+
+#[test]
+fn test_rolling_median_only_covers_the_window() {
+    // An old spike outside the window does not mask the recent baseline
+    let history = history_of(PerformanceMetric::MemoryMb, &[300.0, 300.0, 300.0, 100.0, 100.0, 100.0, 125.0]);
+    let options = TrendOptions { window: 3, ..TrendOptions::default() };
+
+    let trends = PerformanceTrends::from_snapshots(&history, options);
+    let memory = trend(&trends, PerformanceMetric::MemoryMb);
+
+    assert_eq!(memory.rolling_median, Some(100.0));
+    assert!(memory.regressed);
+}
+
+#[test]
+fn test_single_cycle_has_no_baseline() {
+    let history = history_of(PerformanceMetric::MemoryMb, &[100.0]);
+
+    let trends = PerformanceTrends::from_snapshots(&history, TrendOptions::default());
+    let memory = trend(&trends, PerformanceMetric::MemoryMb);
+
+    assert_eq!(memory.rolling_median, None);
+    assert_eq!(memory.worse_by_percent, None);
+    assert!(!memory.regressed);
+    assert_eq!(trends.summary(), "No regressions across 1 cycles");
+}
+
+#[test]
+fn test_series_are_ordered_by_time_and_per_metric() {
+    // Recorded out of order, and launch time only measured in some cycles
+    let history = vec![
+        snapshot(2, CapturedMetrics::new().with(PerformanceMetric::MemoryMb, 150.0).with(PerformanceMetric::ColdLaunchMs, 300.0)),
+        snapshot(0, CapturedMetrics::new().with(PerformanceMetric::MemoryMb, 100.0)),
+        snapshot(1, CapturedMetrics::new().with(PerformanceMetric::MemoryMb, 100.0).with(PerformanceMetric::ColdLaunchMs, 310.0)),
+    ];
+
+    let trends = PerformanceTrends::from_snapshots(&history, TrendOptions::default());
+
+    let memory = trend(&trends, PerformanceMetric::MemoryMb);
+    let ids: Vec<&str> = memory.points.iter().map(|point| point.implementation_id.as_str()).collect();
+    assert_eq!(ids, vec!["implementation-0", "implementation-1", "implementation-2"]);
+    assert!(memory.regressed);
+
+    let launch = trend(&trends, PerformanceMetric::ColdLaunchMs);
+    assert_eq!(launch.points.len(), 2);
+    assert!(!launch.regressed);
+
+    assert_eq!(trends.summary(), "Regressed across 3 cycles: MemoryMb 50.0% worse");
+}
+
+#[tokio::test]
+async fn test_history_round_trips_and_skips_unreadable_lines() {
+    let project = tempfile::tempdir().unwrap();
+    let history = PerformanceHistory::for_project(project.path());
+    assert!(history.snapshots().await.unwrap().is_empty());
+
+    for snapshot in history_of(PerformanceMetric::MemoryMb, &[100.0, 120.0]) {
+        history.record(&snapshot).await.unwrap();
+    }
+    let mut contents = std::fs::read_to_string(history.path()).unwrap();
+    contents.push_str("{ not json\n\n");
+    std::fs::write(history.path(), contents).unwrap();
+    history.record(&snapshot(2, CapturedMetrics::new().with(PerformanceMetric::MemoryMb, 130.0))).await.unwrap();
+
+    let snapshots = history.snapshots().await.unwrap();
+
+    assert_eq!(history.path(), project.path().join(PROJECT_HISTORY_FILE));
+    assert_eq!(snapshots.len(), 3);
+    assert_eq!(snapshots[2].implementation_id, "implementation-2");
+    assert_eq!(snapshots[1].metrics.get(PerformanceMetric::MemoryMb), Some(120.0));
+}
+
+async fn development_loop(history: PerformanceHistory) -> complete_development_loop::AxiomObservabilityLoop {
+    common::development_loop().await.with_performance_history(history)
+}
+
+#[tokio::test]
+async fn test_development_loop_reports_the_trend_against_seeded_history() {
+    let project = tempfile::tempdir().unwrap();
+    let history = PerformanceHistory::for_project(project.path());
+    // Earlier cycles used far less memory than the 2MB the generator estimates
+    for snapshot in history_of(PerformanceMetric::MemoryMb, &[1.0, 1.0, 1.0]) {
+        history.record(&snapshot).await.unwrap();
+    }
+
+    let result = development_loop(history.clone()).await.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    let trends = result.performance_trends.unwrap();
+    assert_eq!(trends.snapshots, 4);
+    assert!(trend(&trends, PerformanceMetric::MemoryMb).regressed);
+    let recorded = history.snapshots().await.unwrap();
+    assert_eq!(recorded[3].implementation_id, result.implementation.implementation_id);
+    assert_eq!(recorded[3].analysis_id, result.requirement_analysis.requirement_id);
+}
+
+#[tokio::test]
+async fn test_unwritable_history_does_not_fail_the_cycle() {
+    let project = tempfile::tempdir().unwrap();
+    // A directory where the history file should be cannot be appended to
+    let history = PerformanceHistory::new(project.path());
+
+    let result = development_loop(history).await.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    assert!(result.success);
+    assert!(result.performance_trends.is_none());
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use axiom_applications_observability::code_generation::*;
+use axiom_applications_observability::complete_development_loop::infer_persistence;
+use axiom_applications_observability::*;
+
+fn field(name: &str, field_type: &str) -> PersistedField {
+    PersistedField {
+        name: name.to_string(),
+        field_type: field_type.to_string(),
+        optional: false,
+        default_value: None,
+        unique: false,
+    }
+}
+
+fn relationship(name: &str, target_entity: &str, kind: RelationshipKind) -> EntityRelationship {
+    EntityRelationship {
+        name: name.to_string(),
+        target_entity: target_entity.to_string(),
+        kind,
+        inverse: None,
+        delete_rule: DeleteRule::Nullify,
+    }
+}
+
+fn recipe_spec() -> PersistenceSpec {
+    PersistenceSpec {
+        entity_name: "Recipe".to_string(),
+        fields: vec![
+            PersistedField { unique: true, default_value: Some("UUID()".to_string()), ..field("id", "UUID") },
+            field("title", "String"),
+            PersistedField { optional: true, ..field("notes", "String") },
+            PersistedField { optional: true, default_value: Some("Date()".to_string()), ..field("cookedAt", "Date") },
+        ],
+        relationships: vec![
+            EntityRelationship {
+                inverse: Some("recipe".to_string()),
+                delete_rule: DeleteRule::Cascade,
+                ..relationship("ingredients", "Ingredient", RelationshipKind::ToMany)
+            },
+            relationship("category", "Category", RelationshipKind::ToOne),
+        ],
+        migration_strategy: MigrationStrategy::Automatic,
+    }
+}
+
+fn client_spec(persistence: Option<PersistenceSpec>) -> ClientSpec {
+    ClientSpec {
+        name: "RecipeClient".to_string(),
+        protocol_conformance: vec!["AxiomClient".to_string()],
+        actions: vec![
+            ActionDefinition {
+                name: "loadRecipes".to_string(),
+                parameters: vec![],
+                return_type: "[String]".to_string(),
+                is_async: true,
+                requirement_ids: vec![],
+            },
+            ActionDefinition {
+                name: "shareRecipe".to_string(),
+                parameters: vec!["id: UUID".to_string()],
+                return_type: "Void".to_string(),
+                is_async: true,
+                requirement_ids: vec![],
+            },
+        ],
+        state_streaming: false,
+        mock_implementation: true,
+        template_overrides: Default::default(),
+        persistence,
+    }
+}
+
+async fn assert_validates(generator: &AxiomCodeGenerator, code: &str) {
+    let result = generator.validate_generated_code(code).await.unwrap();
+    assert!(result.passed, "{}\n{:?}", code, result.issues);
+    assert!(result.pattern_warnings.is_empty(), "{}\n{:?}", code, result.recommendations);
+}
+
+#[tokio::test]
+async fn test_optional_fields_render_as_optionals_defaulting_to_nil() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let code = generator.generate_persistence(recipe_spec()).await.unwrap().generated_code;
+
+    assert!(code.starts_with("import Foundation\nimport SwiftData\n\n@Model\nfinal class Recipe {\n"), "{}", code);
+    assert!(code.contains("    @Attribute(.unique) var id: UUID\n"));
+    assert!(code.contains("    var title: String\n"));
+    assert!(code.contains("    var notes: String?\n"));
+    assert!(code.contains("    var cookedAt: Date?\n"));
+    assert!(
+        code.contains("    init(id: UUID = UUID(), title: String, notes: String? = nil, cookedAt: Date? = Date()) {\n"),
+        "{}",
+        code
+    );
+    assert!(code.contains("        self.notes = notes\n"));
+    assert_validates(&generator, &code).await;
+}
+
+#[tokio::test]
+async fn test_relationships_render_with_delete_rules_and_inverses() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let code = generator.generate_persistence(recipe_spec()).await.unwrap().generated_code;
+
+    assert!(code.contains("    @Relationship(deleteRule: .cascade, inverse: \\Ingredient.recipe) var ingredients: [Ingredient] = []\n"), "{}", code);
+    assert!(code.contains("    var category: Category?\n"), "{}", code);
+    assert!(!code.contains("self.ingredients"), "relationships are not initializer parameters");
+    assert!(code.contains("static let schema = Schema([Recipe.self, Ingredient.self, Category.self])"));
+    assert!(code.contains("return try ModelContainer(for: schema, configurations: [configuration])"));
+    assert!(code.contains("@ModelActor\nactor RecipeRepository {"));
+    assert!(code.contains("try modelContext.fetch(FetchDescriptor<Recipe>())"));
+    assert_validates(&generator, &code).await;
+}
+
+#[tokio::test]
+async fn test_versioned_migration_adds_a_schema_and_plan() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let spec = PersistenceSpec { migration_strategy: MigrationStrategy::Versioned { version: 2 }, ..recipe_spec() };
+
+    let code = generator.generate_persistence(spec).await.unwrap().generated_code;
+
+    assert!(code.contains("enum RecipeSchemaV2: VersionedSchema {"));
+    assert!(code.contains("static let versionIdentifier = Schema.Version(2, 0, 0)"));
+    assert!(code.contains("static var models: [any PersistentModel.Type] { [Recipe.self, Ingredient.self, Category.self] }"));
+    assert!(code.contains("enum RecipeMigrationPlan: SchemaMigrationPlan {"));
+    assert!(code.contains("static let schema = Schema(versionedSchema: RecipeSchemaV2.self)"));
+    assert!(code.contains("ModelContainer(for: schema, migrationPlan: RecipeMigrationPlan.self, configurations: [configuration])"));
+    assert_validates(&generator, &code).await;
+}
+
+#[tokio::test]
+async fn test_persistent_client_loads_and_saves_through_the_repository() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+
+    let code = generator.generate_mock_client(client_spec(Some(recipe_spec()))).await.unwrap().generated_code;
+
+    assert!(code.starts_with("import Foundation\nimport SwiftData\n"), "{}", code);
+    assert!(code.contains("    private let repository: RecipeRepository\n"));
+    assert!(code.contains("        repository = RecipeRepository(modelContainer: modelContainer)\n"));
+    assert!(code.contains("    func loadRecipes() async throws -> [Recipe] {\n        try await repository.loadAll()\n    }"), "{}", code);
+    assert!(code.contains("    func saveRecipe(_ recipe: Recipe) async throws {\n        try await repository.save(recipe)\n    }"));
+    assert!(code.contains("try await repository.delete(recipe)"));
+    // The mock loadRecipes is replaced, the other action kept
+    assert_eq!(code.matches("loadRecipes").count(), 1);
+    assert!(!code.contains("[String]"));
+    assert!(code.contains("shareRecipe"));
+    assert_validates(&generator, &code).await;
+
+    let mock = generator.generate_mock_client(client_spec(None)).await.unwrap().generated_code;
+    assert!(!mock.contains("SwiftData"));
+    assert!(!mock.contains("repository"));
+}
+
+#[tokio::test]
+async fn test_invalid_persistence_specs_are_rejected() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let duplicate = PersistenceSpec {
+        relationships: vec![relationship("title", "Tag", RelationshipKind::ToMany)],
+        ..recipe_spec()
+    };
+    let unnamed = PersistenceSpec { entity_name: "Saved Recipe".to_string(), ..recipe_spec() };
+    let empty = PersistenceSpec { fields: vec![], ..recipe_spec() };
+
+    for (spec, problem) in [
+        (duplicate, "Entity Recipe declares 'title' more than once"),
+        (unnamed, "Entity name 'Saved Recipe' is not a Swift type name"),
+        (empty, "Entity Recipe has no fields"),
+    ] {
+        match generator.generate_persistence(spec).await {
+            Err(AxiomMCPError::Validation(context)) => assert_eq!(context.message, problem),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_persistence_is_inferred_from_saving_and_offline_requirements() {
+    let recipes = infer_persistence("Users can save their recipes").unwrap();
+    assert_eq!(recipes.entity_name, "Recipe");
+    let names: Vec<&str> = recipes.fields.iter().map(|field| field.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "title", "createdAt"]);
+    assert!(recipes.fields[0].unique);
+
+    assert_eq!(infer_persistence("Saving categories for later").unwrap().entity_name, "Category");
+    assert_eq!(infer_persistence("Save all the boxes").unwrap().entity_name, "Box");
+    assert_eq!(infer_persistence("A task list that saves my tasks").unwrap().entity_name, "TaskItem");
+    assert_eq!(infer_persistence("Works offline on the train").unwrap().entity_name, "Item");
+    assert!(infer_persistence("Show a list of recipes").is_none());
+}
+
+#[tokio::test]
+async fn test_inferred_persistence_validates_and_plurals_read_naturally() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let spec = infer_persistence("Keep saved categories offline").unwrap();
+
+    let persistence = generator.generate_persistence(spec.clone()).await.unwrap().generated_code;
+    let client = generator.generate_mock_client(ClientSpec { actions: vec![], ..client_spec(Some(spec)) }).await.unwrap().generated_code;
+
+    assert_validates(&generator, &persistence).await;
+    assert!(client.contains("func loadCategories() async throws -> [Category]"), "{}", client);
+}
+
+#[tokio::test]
+async fn test_development_loop_generates_persistence_for_saving_requirements() {
+    let result = common::development_loop()
+        .await
+        .execute_complete_development_cycle("Save notes offline".to_string())
+        .await
+        .unwrap();
+
+    let spec = result.requirement_analysis.persistence.as_ref().unwrap();
+    assert_eq!(spec.entity_name, "Note");
+    assert!(result.requirement_analysis.estimated_components.contains(&"Persistence".to_string()));
+    let layer = result.implementation.persistence_layer.as_ref().unwrap();
+    assert_eq!(layer.entity_name, "Note");
+    assert!(layer.generated_code.contains("final class Note {"));
+    let client = &result.implementation.client_layer.clients[0].generated_code;
+    assert!(client.contains("func saveNote(_ note: Note) async throws"), "{}", client);
+    assert!(result.validation_result.passed, "{:?}", result.validation_result.issues);
+}
+
+#[tokio::test]
+async fn test_development_loop_skips_persistence_otherwise() {
+    let result = common::development_loop()
+        .await
+        .execute_complete_development_cycle("Task list".to_string())
+        .await
+        .unwrap();
+
+    assert!(result.requirement_analysis.persistence.is_none());
+    assert!(result.implementation.persistence_layer.is_none());
+    assert_eq!(result.implementation.client_layer.clients[0].generated_code, "actor TaskManagerClient: AxiomClient { }");
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use async_trait::async_trait;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::project_build::*;
+use axiom_applications_observability::*;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const XCODEBUILD_TYPE_ERRORS: &str = include_str!("fixtures/xcodebuild_type_errors.log");
+const SWIFT_BUILD_ERRORS: &str = include_str!("fixtures/swift_build_errors.log");
+
+/// Where the fixtures were captured
+const CAPTURED_PROJECT_DIR: &str = "/tmp/axiom-builds/3f2a";
+
+/// Replays captured build output as if it came from the project being
+/// built, then succeeds once it runs out
+struct ReplayBuild {
+    outputs: Mutex<VecDeque<CommandOutput>>,
+    runs: Mutex<Vec<(PathBuf, String, Vec<String>)>>,
+}
+
+impl ReplayBuild {
+    fn failing_with(logs: &[&str]) -> Arc<Self> {
+        let outputs = logs.iter().map(|log| CommandOutput { success: false, log: log.to_string() }).collect();
+        Arc::new(Self { outputs: Mutex::new(outputs), runs: Mutex::new(Vec::new()) })
+    }
+
+    fn runs(&self) -> Vec<(PathBuf, String, Vec<String>)> {
+        self.runs.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl BuildCommand for ReplayBuild {
+    async fn run(&self, dir: &Path, program: &str, args: &[String]) -> Result<CommandOutput> {
+        assert!(dir.join("Package.swift").exists(), "the project is written before it is built");
+        self.runs.lock().unwrap().push((dir.to_path_buf(), program.to_string(), args.to_vec()));
+        let output = self.outputs.lock().unwrap().pop_front();
+        Ok(match output {
+            Some(output) => CommandOutput { log: output.log.replace(CAPTURED_PROJECT_DIR, &dir.display().to_string()), ..output },
+            None => CommandOutput { success: true, log: "** BUILD SUCCEEDED **\n".to_string() },
+        })
+    }
+}
+
+async fn implementation(requirement: &str) -> Implementation {
+    let analysis = common::development_loop().await.analyze_requirement(requirement.to_string()).await.unwrap();
+    code_generation::AxiomCodeGenerator::new().await.unwrap().generate(&analysis, vec![]).await.unwrap()
+}
+
+fn diagnostic(severity: DiagnosticSeverity, file: &str, line: usize, column: usize, message: &str) -> BuildDiagnostic {
+    BuildDiagnostic {
+        severity,
+        file: Some(file.to_string()),
+        line: Some(line),
+        column: Some(column),
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn test_xcodebuild_diagnostics_are_parsed_once_each_relative_to_the_project() {
+    let diagnostics = parse_build_diagnostics(XCODEBUILD_TYPE_ERRORS, Path::new(CAPTURED_PROJECT_DIR));
+
+    assert_eq!(diagnostics, vec![
+        diagnostic(DiagnosticSeverity::Error, "Sources/App/NotePersistence.swift", 14, 23, "cannot find type 'NoteRecord' in scope"),
+        diagnostic(
+            DiagnosticSeverity::Warning,
+            "Sources/App/NotePersistence.swift",
+            21,
+            9,
+            "variable 'descriptor' was never mutated; consider changing to 'let' constant",
+        ),
+        diagnostic(DiagnosticSeverity::Error, "Sources/App/TaskManagerClient.swift", 8, 30, "value of type 'NoteRepository' has no member 'saveAll'"),
+    ]);
+    assert_eq!(diagnostics[0].to_string(), "Sources/App/NotePersistence.swift:14:23: cannot find type 'NoteRecord' in scope");
+}
+
+#[test]
+fn test_swift_build_diagnostics_keep_errors_without_a_location() {
+    let diagnostics = parse_build_diagnostics(SWIFT_BUILD_ERRORS, Path::new(CAPTURED_PROJECT_DIR));
+
+    assert_eq!(diagnostics.len(), 3, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error));
+    assert_eq!(diagnostics[0].file, None);
+    assert_eq!(diagnostics[0].message, "emit-module command failed with exit code 1 (use -v to see invocation)");
+    assert_eq!(
+        diagnostics[1],
+        diagnostic(DiagnosticSeverity::Error, "Sources/App/AppNavigation.swift", 32, 18, "type 'Route' does not conform to protocol 'Hashable'")
+    );
+}
+
+#[test]
+fn test_paths_outside_the_project_stay_absolute() {
+    let diagnostics = parse_build_diagnostics(XCODEBUILD_TYPE_ERRORS, Path::new("/tmp/elsewhere"));
+
+    assert_eq!(diagnostics[0].file.as_deref(), Some("/tmp/axiom-builds/3f2a/Sources/App/NotePersistence.swift"));
+}
+
+#[test]
+fn test_truncated_log_keeps_errors_with_their_context() {
+    let log = truncate_build_log(XCODEBUILD_TYPE_ERRORS, 1);
+    let lines: Vec<&str> = log.lines().collect();
+
+    assert!(lines[0].starts_with("... ") && lines[0].ends_with(" lines omitted"), "{}", lines[0]);
+    assert!(log.contains("NotePersistence.swift:14:23: error: cannot find type 'NoteRecord' in scope\n    func fetchAll() -> [NoteRecord] {"));
+    assert!(log.contains("** BUILD FAILED **"));
+    assert!(!log.contains("Resolve Package Graph"));
+    assert!(!log.contains("builtin-SwiftDriver"));
+    assert!(lines.len() < XCODEBUILD_TYPE_ERRORS.lines().count() / 2, "{}", log);
+}
+
+#[test]
+fn test_truncated_log_without_errors_keeps_its_end() {
+    assert_eq!(truncate_build_log("Compiling\nLinking\nSigning\n** BUILD SUCCEEDED **", 2), "... 2 lines omitted\nSigning\n** BUILD SUCCEEDED **");
+    assert_eq!(truncate_build_log("** BUILD SUCCEEDED **", 3), "** BUILD SUCCEEDED **");
+}
+
+#[tokio::test]
+async fn test_project_is_materialized_as_a_package() {
+    let project = tempfile::tempdir().unwrap();
+    let implementation = implementation("Users save their notes offline").await;
+
+    let written = materialize_project(&implementation, project.path()).await.unwrap();
+
+    assert_eq!(written[0], PathBuf::from("Package.swift"));
+    assert!(written.contains(&PathBuf::from("Sources/App/NotePersistence.swift")));
+    let manifest = std::fs::read_to_string(project.path().join("Package.swift")).unwrap();
+    assert!(manifest.starts_with("// swift-tools-version:5.9\n"), "{}", manifest);
+    assert!(manifest.contains(".target(name: \"App\", path: \"Sources/App\")"));
+    let persistence = std::fs::read_to_string(project.path().join("Sources/App/NotePersistence.swift")).unwrap();
+    assert_eq!(persistence, implementation.persistence_layer.unwrap().generated_code);
+}
+
+#[test]
+fn test_toolchains_build_the_first_target_for_a_simulator() {
+    let infrastructure = InfrastructureSpecification {
+        project_structure: ProjectStructureSpec { directories: vec!["Sources".to_string()], configuration_files: vec![] },
+        dependencies: vec![],
+        build_configuration: BuildConfigurationSpec {
+            targets: vec!["Notes".to_string()],
+            schemes: vec!["Debug".to_string()],
+            swift_version: "5.9".to_string(),
+        },
+    };
+
+    let (program, args) = BuildToolchain::default().invocation(&infrastructure);
+    assert_eq!(program, "xcodebuild");
+    assert_eq!(args, vec![
+        "build", "-scheme", "Notes", "-destination", "generic/platform=iOS Simulator", "-derivedDataPath", ".build/DerivedData",
+    ]);
+    assert_eq!(BuildToolchain::SwiftBuild.invocation(&infrastructure), ("swift".to_string(), vec!["build".to_string()]));
+}
+
+#[tokio::test]
+async fn test_build_errors_are_repaired_in_the_next_iteration() {
+    let workspace = tempfile::tempdir().unwrap();
+    let command = ReplayBuild::failing_with(&[XCODEBUILD_TYPE_ERRORS]);
+    let builder = ProjectBuilder::new(workspace.path()).with_command(command.clone()).with_context_lines(1);
+    let controls = LoopControls { max_iterations: 3, ..Default::default() };
+
+    let result = common::development_loop()
+        .await
+        .with_project_builder(builder)
+        .execute_development_cycle(DevelopmentCycleRequest { requirement: "Users save their notes offline".to_string(), controls })
+        .await
+        .unwrap();
+
+    let runs = command.runs();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].1, "xcodebuild");
+    assert_ne!(runs[0].0, runs[1].0, "each implementation is built in its own directory");
+    assert_eq!(result.iterations[0].validation_errors, 2);
+    assert_eq!(result.termination.unwrap().reason, TerminationReason::Converged);
+    let repair = "Build error: Sources/App/NotePersistence.swift:14:23: cannot find type 'NoteRecord' in scope";
+    assert!(result.implementation.review_feedback.iter().any(|feedback| feedback == repair), "{:?}", result.implementation.review_feedback);
+    assert!(result.compilation.unwrap().succeeded);
+    assert!(result.success);
+}
+
+#[tokio::test]
+async fn test_failed_build_without_diagnostics_still_says_so() {
+    let workspace = tempfile::tempdir().unwrap();
+    let command = ReplayBuild::failing_with(&["** BUILD FAILED **\n"]);
+    let builder = ProjectBuilder::new(workspace.path()).with_command(command);
+
+    let build = builder.build(&implementation("Task list").await).await.unwrap();
+
+    assert!(!build.succeeded);
+    assert_eq!(build.errors().map(ToString::to_string).collect::<Vec<_>>(), vec!["xcodebuild failed without reporting an error"]);
+    assert_eq!(build.log, "** BUILD FAILED **");
+}
@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use axiom_applications_observability::complete_development_loop::CycleCheckpoint;
+use axiom_applications_observability::cycle_report::CycleReport;
+use axiom_applications_observability::hot_reload::{ReloadObserver, ReloadReport};
+use axiom_applications_observability::performance_analysis_integration::*;
+use axiom_applications_observability::reload_scope::ReloadScope;
+use axiom_applications_observability::{AxiomMCPError, MCPConfiguration, Result};
+use chrono::TimeZone;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+const PID: u32 = 4242;
+const CHECKPOINT: &str = include_str!("fixtures/cycle_checkpoint.json");
+
+/// Hands out scripted readings in order
+struct FakeMemory {
+    readings: Mutex<VecDeque<f64>>,
+}
+
+impl FakeMemory {
+    fn new(readings: &[f64]) -> Arc<Self> {
+        Arc::new(Self { readings: Mutex::new(readings.iter().copied().collect()) })
+    }
+}
+
+#[async_trait]
+impl ProcessMemory for FakeMemory {
+    async fn resident_mb(&self, _pid: u32) -> Result<f64> {
+        self.readings
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| AxiomMCPError::toolchain("performance", "sample memory", "no more readings"))
+    }
+}
+
+/// Repeatable noise of about half a megabyte either way
+fn noise(i: usize) -> f64 {
+    0.5 * (i as f64 * 1.7).sin()
+}
+
+fn sample(batch_id: u64, after_mb: f64) -> ReloadMemorySample {
+    ReloadMemorySample {
+        batch_id,
+        at: chrono::Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, batch_id as u32).unwrap(),
+        files: vec!["Sources/App/ContentView.swift".to_string()],
+        before_mb: None,
+        after_mb,
+    }
+}
+
+fn samples(values: &[f64]) -> Vec<ReloadMemorySample> {
+    values.iter().enumerate().map(|(i, value)| sample(i as u64 + 1, *value)).collect()
+}
+
+fn reload(batch_id: u64) -> ReloadReport {
+    ReloadReport {
+        batch_id,
+        files: vec!["Sources/App/ContentView.swift".to_string()],
+        scope: ReloadScope::Full,
+        component_ids: Vec::new(),
+        reason: "ContentView.swift was not indexed before this change".to_string(),
+        at: chrono::Utc::now(),
+    }
+}
+
+fn config() -> ReloadMemoryConfig {
+    ReloadMemoryConfig { settle_ms: 0, ..Default::default() }
+}
+
+#[test]
+fn test_noisy_flat_memory_is_not_a_leak() {
+    let values: Vec<f64> = (0..10).map(|i| 120.0 + noise(i)).collect();
+
+    let trend = ReloadMemoryTrend::from_samples(Some(PID), &samples(&values), &config());
+
+    assert!(trend.fit.unwrap().slope.abs() < 0.3, "{:?}", trend.fit);
+    assert!(trend.finding.is_none());
+    assert!(trend.summary().starts_with("Memory of process 4242 changed"), "{}", trend.summary());
+}
+
+#[test]
+fn test_steady_growth_is_a_leak_despite_a_spike() {
+    let mut values: Vec<f64> = (0..10).map(|i| 100.0 + 2.0 * i as f64 + noise(i)).collect();
+    // A sample caught in the middle of an allocation burst
+    values[4] += 40.0;
+
+    let trend = ReloadMemoryTrend::from_samples(Some(PID), &samples(&values), &config());
+
+    let finding = trend.finding.unwrap();
+    assert!((finding.growth_mb_per_reload - 2.0).abs() < 0.3, "{}", finding.growth_mb_per_reload);
+    assert!((finding.total_growth_mb - 18.0).abs() < 3.0, "{}", finding.total_growth_mb);
+    assert_eq!(finding.pid, PID);
+    assert_eq!(finding.timeline.len(), 10);
+    assert!(finding.message().contains("above the 1.0 MB limit"), "{}", finding.message());
+}
+
+#[test]
+fn test_only_the_latest_window_of_reloads_is_fitted() {
+    // Grew early on, then levelled off
+    let mut values: Vec<f64> = (0..5).map(|i| 100.0 + 10.0 * i as f64).collect();
+    values.extend((0..10).map(|i| 150.0 + noise(i)));
+
+    let trend = ReloadMemoryTrend::from_samples(Some(PID), &samples(&values), &config());
+
+    assert_eq!(trend.samples.len(), 10);
+    assert_eq!(trend.samples[0].batch_id, 6);
+    assert!(trend.finding.is_none(), "{:?}", trend.fit);
+}
+
+#[test]
+fn test_too_few_reloads_are_not_judged() {
+    let trend = ReloadMemoryTrend::from_samples(Some(PID), &samples(&[100.0, 110.0, 120.0, 130.0]), &config());
+
+    assert!(trend.fit.unwrap().slope > 9.0);
+    assert!(trend.finding.is_none());
+}
+
+#[tokio::test]
+async fn test_tracker_samples_around_each_reload() {
+    // Before and after each of six reloads, growing 2 MB per reload
+    let readings: Vec<f64> = (0..6).flat_map(|i| [100.0 + 2.0 * i as f64, 101.0 + 2.0 * i as f64 + noise(i)]).collect();
+    let tracker = ReloadMemoryTracker::new(config()).with_process_memory(FakeMemory::new(&readings));
+
+    // Nothing is sampled before an app is launched
+    tracker.before_reload(0).await;
+    tracker.after_reload(&reload(0)).await;
+    assert!(tracker.trend().samples.is_empty());
+    assert_eq!(tracker.trend().summary(), "No launched app to follow across reloads");
+
+    tracker.track(PID);
+    for batch_id in 1..=6 {
+        tracker.before_reload(batch_id).await;
+        tracker.after_reload(&reload(batch_id)).await;
+        let leaking = tracker.trend().finding.is_some();
+        assert_eq!(leaking, batch_id >= 5, "after reload {}", batch_id);
+    }
+
+    let trend = tracker.trend();
+    assert_eq!(trend.samples.len(), 6);
+    assert_eq!(trend.samples[0].before_mb, Some(100.0));
+    assert_eq!(trend.samples[5].before_mb, Some(110.0));
+    assert!(trend.finding.unwrap().heap_snapshot.is_none());
+}
+
+#[tokio::test]
+async fn test_heap_snapshot_is_taken_once_when_growth_crosses_the_limit() {
+    let dir = tempfile::tempdir().unwrap();
+    let tracker = ReloadMemoryTracker::new(ReloadMemoryConfig {
+        heap_snapshot_command: Some(vec!["echo".to_string(), "heap of {pid}".to_string()]),
+        heap_snapshot_dir: dir.path().to_path_buf(),
+        ..config()
+    });
+    tracker.track(PID);
+
+    for batch_id in 1..=8 {
+        tracker.record(PID, sample(batch_id, 100.0 + 3.0 * batch_id as f64)).await;
+    }
+
+    let snapshot = tracker.trend().finding.unwrap().heap_snapshot.unwrap();
+    assert_eq!(snapshot.command, vec!["echo", "heap of 4242"]);
+    assert!(snapshot.error.is_none(), "{:?}", snapshot.error);
+    let path = snapshot.path.unwrap();
+    assert!(path.ends_with("heap-4242-reload-5.txt"), "{}", path.display());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "heap of 4242\n");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_heap_snapshot_is_reported_on_the_finding() {
+    let dir = tempfile::tempdir().unwrap();
+    let tracker = ReloadMemoryTracker::new(ReloadMemoryConfig {
+        heap_snapshot_command: Some(vec!["axiom-no-such-heap-tool".to_string(), "{pid}".to_string()]),
+        heap_snapshot_dir: dir.path().to_path_buf(),
+        ..config()
+    });
+    tracker.track(PID);
+
+    for batch_id in 1..=5 {
+        tracker.record(PID, sample(batch_id, 100.0 + 3.0 * batch_id as f64)).await;
+    }
+
+    let snapshot = tracker.trend().finding.unwrap().heap_snapshot.unwrap();
+    assert!(snapshot.path.is_none());
+    assert!(snapshot.error.unwrap().contains("axiom-no-such-heap-tool"));
+}
+
+#[tokio::test]
+async fn test_relaunch_starts_a_new_timeline() {
+    let tracker = ReloadMemoryTracker::new(config());
+    tracker.track(PID);
+    for batch_id in 1..=6 {
+        tracker.record(PID, sample(batch_id, 100.0 + 3.0 * batch_id as f64)).await;
+    }
+    assert!(tracker.trend().finding.is_some());
+
+    tracker.track(PID + 1);
+    // A late sample of the old process is dropped
+    tracker.record(PID, sample(7, 130.0)).await;
+
+    let trend = tracker.trend();
+    assert_eq!(trend.pid, Some(PID + 1));
+    assert!(trend.samples.is_empty());
+    assert!(trend.finding.is_none());
+}
+
+#[test]
+fn test_invalid_reload_memory_config() {
+    let invalid = [
+        ReloadMemoryConfig { min_reloads: 1, ..Default::default() },
+        ReloadMemoryConfig { window: 4, min_reloads: 5, ..Default::default() },
+        ReloadMemoryConfig { max_growth_mb_per_reload: -1.0, ..Default::default() },
+        ReloadMemoryConfig { max_growth_mb_per_reload: f64::NAN, ..Default::default() },
+        ReloadMemoryConfig { heap_snapshot_command: Some(Vec::new()), ..Default::default() },
+    ];
+    for config in invalid {
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, AxiomMCPError::Validation(_)), "{:?}", error);
+    }
+    assert!(ReloadMemoryConfig::default().validate().is_ok());
+
+    let config = MCPConfiguration {
+        reload_memory: ReloadMemoryConfig { min_reloads: 1, ..Default::default() },
+        ..Default::default()
+    };
+    let error = config.validate().unwrap_err();
+    assert!(matches!(error, AxiomMCPError::Configuration { .. }), "{:?}", error);
+    assert!(error.to_string().contains("min_reloads"), "{}", error);
+}
+
+#[test]
+fn test_cycle_report_shows_memory_across_reloads() {
+    let checkpoint: CycleCheckpoint = serde_json::from_str(CHECKPOINT).unwrap();
+    let mut report = CycleReport::from_checkpoint(&checkpoint).unwrap();
+    let values: Vec<f64> = (0..6).map(|i| 100.0 + 2.0 * i as f64).collect();
+    report.reload_memory = Some(ReloadMemoryTrend::from_samples(Some(PID), &samples(&values), &config()));
+
+    let markdown = report.to_markdown(&PerformanceThresholds::default());
+
+    assert!(markdown.contains("### Memory across hot reloads\n\nMemory of process 4242 grew 2.0 MB per reload"), "{}", markdown);
+    assert!(markdown.contains("| Reload | At | Files | Before | After |"), "{}", markdown);
+    assert!(markdown.contains("| 6 | 2026-03-02 09:00:06 UTC | 1 |  | 110.0 MB |"), "{}", markdown);
+}
@@ -0,0 +1,203 @@
+use axiom_applications_observability::hot_reload::{ChangeType, FileChange, ReloadBatch};
+use axiom_applications_observability::reload_scope::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const CONTENT_VIEW: &str = r#"
+import SwiftUI
+
+// struct CommentedOut: View {}
+struct ContentView: View {
+    @EnvironmentObject var context: ContentContext
+
+    struct Row: View {
+        var body: some View { Text("struct Fake {") }
+    }
+
+    var body: some View {
+        List { Row() }
+    }
+}
+
+/* class Hidden { /* nested */ } */
+private struct ContentRow<Item: Identifiable>: View where Item: Hashable {
+    let item: Item
+    var body: some View { Text("\(item.id)") }
+}
+"#;
+
+const CONTENT_CONTEXT: &str = r#"
+import Foundation
+
+@MainActor
+final class ContentContext: ObservableObject, AxiomContext {
+    @Published var items: [String] = []
+}
+
+actor ContentClient: AxiomClient {
+    let message = """
+    enum NotAType {}
+    """
+}
+"#;
+
+const CONTENT_VIEW_ACTIONS: &str = r#"
+extension ContentView {
+    func refresh() {}
+}
+
+extension ContentView: Equatable {
+    static func == (lhs: ContentView, rhs: ContentView) -> Bool { true }
+}
+"#;
+
+fn batch(changes: &[(&str, ChangeType)]) -> ReloadBatch {
+    let mut batch = ReloadBatch::default();
+    for (path, change_type) in changes {
+        batch.push(FileChange::new(*path, *change_type));
+    }
+    batch
+}
+
+fn sources(files: &[(&str, &str)]) -> HashMap<PathBuf, String> {
+    files.iter().map(|(path, source)| (PathBuf::from(path), source.to_string())).collect()
+}
+
+fn indexed_project() -> ComponentIndex {
+    let index = ComponentIndex::new();
+    index.index_file("ContentView.swift", CONTENT_VIEW);
+    index.index_file("ContentContext.swift", CONTENT_CONTEXT);
+    index.index_file("ContentView+Actions.swift", CONTENT_VIEW_ACTIONS);
+    index
+}
+
+#[test]
+fn test_top_level_declarations_skip_nested_types_comments_and_strings() {
+    let declarations = top_level_declarations(CONTENT_VIEW);
+
+    let names: Vec<&str> = declarations.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["ContentView", "ContentRow"]);
+    assert_eq!(declarations[0].kind, DeclarationKind::Struct);
+    assert_eq!(declarations[1].conformances, vec!["View"]);
+}
+
+#[test]
+fn test_multi_type_file_declarations_and_component_kinds() {
+    let declarations = top_level_declarations(CONTENT_CONTEXT);
+
+    assert_eq!(declarations.len(), 2);
+    assert_eq!(declarations[0].name, "ContentContext");
+    assert_eq!(declarations[0].conformances, vec!["ObservableObject", "AxiomContext"]);
+    assert_eq!(declarations[0].component_kind(), ComponentKind::Context);
+    assert_eq!(declarations[1].kind, DeclarationKind::Actor);
+    assert_eq!(declarations[1].component_kind(), ComponentKind::Client);
+    assert_eq!(top_level_declarations(CONTENT_VIEW)[0].component_kind(), ComponentKind::Presentation);
+}
+
+#[test]
+fn test_extensions_name_the_extended_type() {
+    let declarations = top_level_declarations(CONTENT_VIEW_ACTIONS);
+
+    assert_eq!(declarations.len(), 2);
+    assert!(declarations.iter().all(|d| d.kind == DeclarationKind::Extension && d.name == "ContentView"));
+    assert_eq!(declarations[1].conformances, vec!["Equatable"]);
+}
+
+#[test]
+fn test_modified_view_reloads_its_components() {
+    let index = indexed_project();
+
+    let decision = index.apply(
+        &batch(&[("ContentView.swift", ChangeType::Modified)]),
+        &sources(&[("ContentView.swift", CONTENT_VIEW)]),
+    );
+
+    assert_eq!(decision.scope, ReloadScope::Targeted);
+    assert_eq!(decision.component_ids, vec!["ContentRow", "ContentView"]);
+}
+
+#[test]
+fn test_modified_extension_reloads_the_extended_type() {
+    let index = indexed_project();
+
+    let decision = index.apply(
+        &batch(&[("ContentView+Actions.swift", ChangeType::Modified)]),
+        &sources(&[("ContentView+Actions.swift", CONTENT_VIEW_ACTIONS)]),
+    );
+
+    assert_eq!(decision.scope, ReloadScope::Targeted);
+    assert_eq!(decision.component_ids, vec!["ContentView"]);
+}
+
+#[test]
+fn test_extension_of_an_unindexed_type_falls_back_to_full() {
+    let index = indexed_project();
+    index.index_file("String+Trimming.swift", "extension String { var trimmed: String { self } }");
+
+    let decision = index.apply(
+        &batch(&[("String+Trimming.swift", ChangeType::Modified)]),
+        &sources(&[("String+Trimming.swift", "extension String { var trimmed: String { \"\" } }")]),
+    );
+
+    assert_eq!(decision.scope, ReloadScope::Full);
+    assert!(decision.reason.contains("extends String"), "{}", decision.reason);
+}
+
+#[test]
+fn test_changed_declarations_fall_back_to_full() {
+    let index = indexed_project();
+    let renamed = CONTENT_VIEW.replace("ContentRow", "ItemRow");
+
+    let decision = index.apply(
+        &batch(&[("ContentView.swift", ChangeType::Modified)]),
+        &sources(&[("ContentView.swift", &renamed)]),
+    );
+
+    assert_eq!(decision.scope, ReloadScope::Full);
+    assert!(decision.reason.contains("types declared in ContentView.swift changed"));
+
+    // The new content is indexed, so the next edit is targeted again
+    let decision = index.apply(
+        &batch(&[("ContentView.swift", ChangeType::Modified)]),
+        &sources(&[("ContentView.swift", &renamed)]),
+    );
+    assert_eq!(decision.component_ids, vec!["ContentView", "ItemRow"]);
+}
+
+#[test]
+fn test_ambiguous_or_unknown_files_fall_back_to_full() {
+    let index = indexed_project();
+    index.index_file("Legacy/ContentView.swift", "struct ContentView: View { var body: some View { EmptyView() } }");
+
+    let decision = index.apply(
+        &batch(&[("ContentView.swift", ChangeType::Modified)]),
+        &sources(&[("ContentView.swift", CONTENT_VIEW)]),
+    );
+    assert_eq!(decision.scope, ReloadScope::Full);
+    assert!(decision.reason.contains("ContentView is declared in more than one file"));
+
+    let decision = index.apply(
+        &batch(&[("DetailView.swift", ChangeType::Modified)]),
+        &sources(&[("DetailView.swift", "struct DetailView: View {}")]),
+    );
+    assert_eq!(decision.scope, ReloadScope::Full);
+    assert_eq!(decision.reason, "DetailView.swift was not indexed before this change");
+}
+
+#[test]
+fn test_added_or_deleted_files_reload_fully() {
+    let index = indexed_project();
+
+    let decision = index.apply(
+        &batch(&[
+            ("ContentView.swift", ChangeType::Modified),
+            ("ContentView+Actions.swift", ChangeType::Deleted),
+        ]),
+        &sources(&[("ContentView.swift", CONTENT_VIEW)]),
+    );
+
+    assert_eq!(decision.scope, ReloadScope::Full);
+    assert!(decision.component_ids.is_empty());
+    assert_eq!(decision.reason, "ContentView+Actions.swift was deleted");
+    assert!(index.declarations(&PathBuf::from("ContentView+Actions.swift")).is_none());
+}
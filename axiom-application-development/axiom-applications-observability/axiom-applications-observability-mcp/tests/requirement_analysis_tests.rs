@@ -0,0 +1,230 @@
+mod common;
+
+use async_trait::async_trait;
+use axiom_applications_observability::code_generation::PresentationStyle;
+use axiom_applications_observability::intelligence_protocol::*;
+use axiom_applications_observability::requirement_analysis::*;
+use axiom_applications_observability::*;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const REQUEST_ID: &str = "3c7e1f2a-8b4d-4e6f-9a1c-2d5b7e9f0a13";
+
+/// Stands in for the intelligence server: answers with `insights()`,
+/// or fails with `failure` when set
+#[derive(Debug)]
+struct FakeServer {
+    available: bool,
+    failure: Option<fn() -> AxiomMCPError>,
+    calls: AtomicUsize,
+}
+
+impl FakeServer {
+    fn new(available: bool, failure: Option<fn() -> AxiomMCPError>) -> Arc<Self> {
+        Arc::new(Self { available, failure, calls: AtomicUsize::new(0) })
+    }
+}
+
+#[async_trait]
+impl RequirementAnalyzer for FakeServer {
+    async fn analyze(&self, _requirement: &str) -> Result<RequirementAnalysis> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        match self.failure {
+            Some(failure) => Err(failure()),
+            None => Ok(analysis_from_insights(insights())),
+        }
+    }
+
+    fn available(&self) -> bool {
+        self.available
+    }
+}
+
+fn insights() -> RequirementInsights {
+    RequirementInsights {
+        confidence_score: 92.0,
+        complexity: "Medium".to_string(),
+        entities: vec!["Task".to_string()],
+        screens: vec!["TaskList".to_string(), "TaskDetail".to_string(), "Settings".to_string()],
+        actions: vec!["add".to_string(), "complete".to_string()],
+        persisted_entity: Some("Task".to_string()),
+    }
+}
+
+/// Requirement, entities, screens, actions and confidence
+type Case = (&'static str, &'static [&'static str], &'static [&'static str], &'static [&'static str], f64);
+
+#[tokio::test]
+async fn test_offline_rules_extract_entities_screens_and_actions() {
+    let cases: [Case; 6] = [
+        (
+            "Users add notes on a note list screen and a note detail screen",
+            &["Note"], &["NoteList", "NoteDetail"], &["add"], 75.0,
+        ),
+        (
+            "Create a simple counter app with increment and decrement buttons",
+            &["Counter"], &[], &["create", "increment", "decrement"], 60.0,
+        ),
+        ("Track categories on a category list page", &["Category"], &["CategoryList"], &["track"], 75.0),
+        ("Save all the boxes", &["Box"], &[], &["save"], 60.0),
+        // Saved tasks would shadow Swift's Task
+        ("A task list that saves my tasks", &["TaskItem"], &[], &["save"], 60.0),
+        ("Build me an app", &[], &[], &[], 30.0),
+    ];
+
+    for (requirement, entities, screens, actions, confidence) in cases {
+        let analysis = OfflineRequirementAnalyzer.analyze(requirement).await.unwrap();
+
+        assert_eq!(analysis.entities, entities, "{}", requirement);
+        assert_eq!(analysis.screens, screens, "{}", requirement);
+        assert_eq!(analysis.actions, actions, "{}", requirement);
+        assert_eq!(analysis.confidence_score, confidence, "{}", requirement);
+        assert_eq!(analysis.analyzer, AnalyzerKind::Heuristic);
+    }
+}
+
+#[tokio::test]
+async fn test_offline_analysis_outlines_persistence_and_navigation() {
+    let analysis = OfflineRequirementAnalyzer
+        .analyze("Users save recipes on a recipe list screen and open a recipe detail screen")
+        .await
+        .unwrap();
+
+    assert_eq!(analysis.persistence.unwrap().entity_name, "Recipe");
+    assert_eq!(analysis.navigation.unwrap().root, "RecipeListView");
+    assert_eq!(analysis.estimated_components, vec!["Context", "Presentation", "Client", "Persistence", "Navigation"]);
+    assert_eq!(analysis.complexity_estimate, "Medium");
+    assert!(analysis.clarifying_questions.is_empty());
+}
+
+#[tokio::test]
+async fn test_server_insights_become_the_analysis() {
+    let analysis = analysis_from_insights(insights());
+
+    assert_eq!(analysis.analyzer, AnalyzerKind::Intelligence);
+    assert_eq!(analysis.confidence_score, 92.0);
+    assert_eq!(analysis.persistence.unwrap().entity_name, "TaskItem");
+    let navigation = analysis.navigation.unwrap();
+    assert_eq!(navigation.root, "TaskListView");
+    assert_eq!(navigation.routes[0].parameters[0].name, "id");
+    assert_eq!(navigation.routes[1].style, PresentationStyle::Sheet);
+}
+
+#[tokio::test]
+async fn test_auto_analyzer_prefers_an_available_server() {
+    let server = FakeServer::new(true, None);
+
+    let analysis = AutoRequirementAnalyzer::new(server.clone()).analyze("Track tasks").await.unwrap();
+
+    assert_eq!(analysis.analyzer, AnalyzerKind::Intelligence);
+    assert_eq!(server.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_auto_analyzer_falls_back_to_offline_rules() {
+    let unreachable = FakeServer::new(false, None);
+    let analysis = AutoRequirementAnalyzer::new(unreachable.clone()).analyze("Save all the boxes").await.unwrap();
+    assert_eq!(analysis.analyzer, AnalyzerKind::Heuristic);
+    assert_eq!(analysis.entities, vec!["Box"]);
+    assert_eq!(unreachable.calls.load(Ordering::SeqCst), 0, "an unavailable server is not asked");
+
+    let failures: [fn() -> AxiomMCPError; 2] = [
+        || AxiomMCPError::connectivity("intelligence", "request_requirement_analysis", "Intelligence connection closed"),
+        || AxiomMCPError::configuration("intelligence_server", "The intelligence server does not offer requirement_analysis"),
+    ];
+    for failure in failures {
+        let server = FakeServer::new(true, Some(failure));
+        let analysis = AutoRequirementAnalyzer::new(server.clone()).analyze("Save all the boxes").await.unwrap();
+        assert_eq!(analysis.analyzer, AnalyzerKind::Heuristic, "{}", failure());
+        assert_eq!(server.calls.load(Ordering::SeqCst), 1);
+    }
+}
+
+#[tokio::test]
+async fn test_auto_analyzer_reports_other_server_failures() {
+    let server = FakeServer::new(true, Some(|| AxiomMCPError::internal("intelligence", "decode message", "Malformed requirement_analysis")));
+
+    let error = AutoRequirementAnalyzer::new(server).analyze("Save all the boxes").await.unwrap_err();
+
+    assert!(matches!(error, AxiomMCPError::Internal(_)), "{:?}", error);
+}
+
+#[test]
+fn test_requirement_analysis_messages() {
+    let request = IntelligenceRequest::RequirementAnalysis { requirement: "Track tasks" };
+    let encoded: serde_json::Value = serde_json::from_str(&request.encode(PROTOCOL_VERSION, REQUEST_ID)).unwrap();
+    assert_eq!(encoded, json!({ "type": "request_requirement_analysis", "requestId": REQUEST_ID, "requirement": "Track tasks" }));
+    assert_eq!(request.capability(), ServerCapability::RequirementAnalysis);
+
+    let reply = json!({
+        "type": "requirement_analysis",
+        "requestId": REQUEST_ID,
+        "confidenceScore": 92.0,
+        "complexity": "Medium",
+        "entities": ["Task"],
+        "screens": ["TaskList", "TaskDetail", "Settings"],
+        "actions": ["add", "complete"],
+        "persistedEntity": "Task",
+    });
+    let decoded = decode_message(PROTOCOL_VERSION, &reply.to_string()).unwrap().unwrap();
+    assert!(request.is_answered_by(&decoded));
+    assert_eq!(decoded, IntelligenceMessage::RequirementAnalysis { request_id: REQUEST_ID.to_string(), insights: insights() });
+    // Servers from before the handshake never analyzed requirements
+    assert_eq!(decode_message(LEGACY_PROTOCOL_VERSION, &reply.to_string()).unwrap(), None);
+}
+
+fn capabilities(intelligence_analysis: bool) -> MCPCapabilities {
+    MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    }
+}
+
+fn config(requirement_analyzer: RequirementAnalyzerMode) -> MCPConfiguration {
+    MCPConfiguration {
+        simulator_management_enabled: false,
+        code_generation_enabled: false,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        requirement_analyzer,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_configured_analyzer_mode() {
+    let error = requirement_analyzer(RequirementAnalyzerMode::Intelligence, None).unwrap_err();
+    assert!(matches!(error, AxiomMCPError::Configuration { .. }), "{:?}", error);
+    assert!(error.to_string().contains("requirement_analyzer"), "{}", error);
+    assert!(AxiomApplicationsObservabilityMCP::new(config(RequirementAnalyzerMode::Intelligence), capabilities(false)).await.is_err());
+
+    // Nothing listens on the intelligence server URL, so both analyze offline
+    for mode in [RequirementAnalyzerMode::Auto, RequirementAnalyzerMode::Offline] {
+        let mcp = AxiomApplicationsObservabilityMCP::new(config(mode), capabilities(true)).await.unwrap();
+        let result = mcp.call_tool("process_natural_language_requirement", json!({ "requirement": "Save all the boxes" })).await.unwrap();
+        match &result {
+            ToolResult::RequirementAnalysis(analysis) => assert_eq!(analysis.analyzer, AnalyzerKind::Heuristic, "{:?}", mode),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(result.summary().starts_with("Requirement analyzed by the offline rules (heuristic)"), "{}", result.summary());
+    }
+}
+
+#[tokio::test]
+async fn test_cycle_records_which_analyzer_ran() {
+    let requirement = "Users add notes on a note list screen and a note detail screen";
+
+    let offline = common::development_loop().await.execute_complete_development_cycle(requirement.to_string()).await.unwrap();
+    assert_eq!(offline.requirement_analysis.analyzer, AnalyzerKind::Heuristic);
+
+    let server = FakeServer::new(true, None);
+    let development = common::development_loop().await.with_requirement_analyzer(Arc::new(AutoRequirementAnalyzer::new(server)));
+    let result = development.execute_complete_development_cycle(requirement.to_string()).await.unwrap();
+    assert_eq!(result.requirement_analysis.analyzer, AnalyzerKind::Intelligence);
+    assert_eq!(result.requirement_analysis.screens, vec!["TaskList", "TaskDetail", "Settings"]);
+}
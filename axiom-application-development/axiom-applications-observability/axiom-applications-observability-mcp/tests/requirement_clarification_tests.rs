@@ -0,0 +1,226 @@
+mod common;
+
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::requirement_analysis::AnalyzerKind;
+use axiom_applications_observability::requirement_clarification::*;
+use axiom_applications_observability::*;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+const VAGUE_REQUIREMENT: &str = "Build me an app";
+
+async fn development_loop(clarifier: Arc<RequirementClarifier>) -> AxiomObservabilityLoop {
+    common::development_loop().await.with_requirement_clarifier(clarifier)
+}
+
+fn clarifier(mode: ClarificationMode) -> Arc<RequirementClarifier> {
+    Arc::new(RequirementClarifier::new(ClarificationPolicy { mode, ..Default::default() }))
+}
+
+async fn next_request(requests: &mut broadcast::Receiver<ClarificationRequest>) -> ClarificationRequest {
+    tokio::time::timeout(Duration::from_secs(5), requests.recv()).await.unwrap().unwrap()
+}
+
+fn question_ids(questions: &[ClarifyingQuestion]) -> Vec<&str> {
+    questions.iter().map(|question| question.id.as_str()).collect()
+}
+
+#[test]
+fn test_ambiguous_requirement_asks_how_many_screens() {
+    let questions = clarifying_questions(VAGUE_REQUIREMENT);
+
+    assert_eq!(requirement_confidence(VAGUE_REQUIREMENT), 45.0);
+    assert_eq!(question_ids(&questions), vec!["screen_count", "entities", "actions"]);
+    assert_eq!(questions[0].suggested_default, "One list screen showing every item");
+    assert!(questions.iter().all(|question| question.answer.is_none()));
+}
+
+#[test]
+fn test_specific_requirement_is_outlined_without_questions() {
+    let requirement = "Users add notes on a note list screen and a note detail screen";
+
+    assert_eq!(requirement_confidence(requirement), 90.0);
+    assert!(clarifying_questions(requirement).is_empty());
+    assert_eq!(infer_entities(requirement), vec!["Note"]);
+    assert_eq!(infer_screens(requirement), vec!["NoteList", "NoteDetail"]);
+    assert_eq!(infer_actions(requirement), vec!["add"]);
+}
+
+#[test]
+fn test_only_low_confidence_requirements_ask() {
+    // Names what users do and to what, but no screens
+    let requirement = "Create a simple counter app with increment and decrement buttons";
+
+    assert_eq!(infer_entities(requirement), vec!["Counter"]);
+    assert_eq!(infer_actions(requirement), vec!["create", "increment", "decrement"]);
+    assert_eq!(requirement_confidence(requirement), 75.0);
+    assert!(clarifying_questions(requirement).is_empty());
+    assert_eq!(question_ids(&clarifying_questions("Task list")), vec!["screen_count", "actions"]);
+}
+
+#[tokio::test]
+async fn test_cycle_waits_for_answers_and_analyzes_with_them() {
+    let clarifier = clarifier(ClarificationMode::Interactive);
+    let mut requests = clarifier.subscribe();
+    let development = development_loop(clarifier.clone()).await;
+    let cycle = tokio::spawn(async move { development.execute_complete_development_cycle(VAGUE_REQUIREMENT.to_string()).await });
+
+    let request = next_request(&mut requests).await;
+    assert_eq!(request.confidence_score, 45.0);
+    assert!(question_ids(&request.questions).contains(&"screen_count"));
+    assert!(!cycle.is_finished(), "the cycle waits for answers");
+    let answers = BTreeMap::from([("screen_count".to_string(), "A note list screen and a note detail screen".to_string())]);
+    let answered = clarifier.provide_answers(RequirementAnswers { cycle_id: request.cycle_id.clone(), answers }).unwrap();
+    assert!(answered.questions.iter().all(|question| question.answer.is_some()));
+    let result = cycle.await.unwrap().unwrap();
+
+    let analysis = result.requirement_analysis;
+    assert_eq!(analysis.screens, vec!["NoteList", "NoteDetail"]);
+    assert_eq!(analysis.navigation.unwrap().root, "NoteListView");
+    assert_eq!(analysis.actions, vec!["add", "edit", "delete"]);
+    assert_eq!(analysis.confidence_score, 75.0);
+    assert_eq!(analysis.analyzer, AnalyzerKind::Heuristic);
+    assert_eq!(analysis.clarifying_questions[0].answer.as_deref(), Some("A note list screen and a note detail screen"));
+    assert_eq!(analysis.clarifying_questions[2].answer.as_deref(), Some("Users add, edit and delete items"));
+    assert!(clarifier.pending().is_empty());
+}
+
+#[tokio::test]
+async fn test_non_interactive_cycles_go_ahead_with_the_defaults() {
+    let clarifier = clarifier(ClarificationMode::UseDefaults);
+    let mut requests = clarifier.subscribe();
+
+    let result = development_loop(clarifier)
+        .await
+        .execute_complete_development_cycle(VAGUE_REQUIREMENT.to_string())
+        .await
+        .unwrap();
+
+    assert!(requests.try_recv().is_err(), "nobody is asked");
+    let analysis = result.requirement_analysis;
+    assert!(analysis.clarifying_questions.iter().all(|question| question.answer.as_ref() == Some(&question.suggested_default)));
+    assert_eq!(analysis.screens, vec!["List"]);
+    assert_eq!(analysis.entities, vec!["Item"]);
+    assert!(result.success);
+}
+
+#[tokio::test]
+async fn test_resumed_cycle_keeps_its_answers() {
+    let checkpoints = tempfile::tempdir().unwrap();
+    let store = CycleStore::new(checkpoints.path());
+    let impatient = Arc::new(RequirementClarifier::new(ClarificationPolicy { timeout_ms: 50, ..Default::default() }));
+
+    let error = development_loop(impatient)
+        .await
+        .with_checkpoints(store.clone())
+        .execute_complete_development_cycle(VAGUE_REQUIREMENT.to_string())
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("got no answers within 50ms"), "{}", error);
+    let cycle_id = store.list().await.unwrap()[0].cycle_id.clone();
+    let checkpoint = store.load(&cycle_id).await.unwrap();
+    assert!(checkpoint.clarifications.is_empty());
+    assert_eq!(checkpoint.failure.unwrap().stage, CycleStage::Analysis);
+
+    // Unanswered questions are asked again
+    let asking = clarifier(ClarificationMode::Interactive);
+    let mut requests = asking.subscribe();
+    let resuming = development_loop(asking.clone()).await.with_checkpoints(store.clone());
+    let resumed_id = cycle_id.clone();
+    let resumed = tokio::spawn(async move { resuming.resume_development_cycle(&resumed_id).await });
+    let request = next_request(&mut requests).await;
+    assert_eq!(request.cycle_id, cycle_id);
+    let answers = BTreeMap::from([("entities".to_string(), "A recipe list".to_string())]);
+    asking.provide_answers(RequirementAnswers { cycle_id: cycle_id.clone(), answers }).unwrap();
+    let first = resumed.await.unwrap().unwrap();
+    assert_eq!(first.requirement_analysis.entities, vec!["Recipe", "Item"]);
+
+    // Answered ones are not
+    let silent = clarifier(ClarificationMode::Interactive);
+    let mut never_asked = silent.subscribe();
+    let second = development_loop(silent)
+        .await
+        .with_checkpoints(store.clone())
+        .resume_development_cycle(&cycle_id)
+        .await
+        .unwrap();
+
+    assert!(never_asked.try_recv().is_err());
+    assert_eq!(second.reused_stages, CycleStage::ALL.to_vec());
+    assert_eq!(second.requirement_analysis.clarifying_questions, first.requirement_analysis.clarifying_questions);
+    let checkpoint = store.load(&cycle_id).await.unwrap();
+    assert_eq!(checkpoint.clarifications[1].answer.as_deref(), Some("A recipe list"));
+}
+
+#[tokio::test]
+async fn test_answers_need_a_waiting_cycle_and_its_questions() {
+    let clarifier = clarifier(ClarificationMode::Interactive);
+    let mut requests = clarifier.subscribe();
+    let development = development_loop(clarifier.clone()).await;
+    let cycle = tokio::spawn(async move { development.execute_complete_development_cycle(VAGUE_REQUIREMENT.to_string()).await });
+    let request = next_request(&mut requests).await;
+
+    for (answers, problem) in [
+        (RequirementAnswers { cycle_id: "0b7e4a4e".to_string(), answers: BTreeMap::new() }, "Cycle 0b7e4a4e is not waiting for answers".to_string()),
+        (
+            RequirementAnswers { cycle_id: request.cycle_id.clone(), answers: BTreeMap::from([("colors".to_string(), "Blue".to_string())]) },
+            format!("Cycle {} did not ask 'colors'", request.cycle_id),
+        ),
+        (
+            RequirementAnswers { cycle_id: request.cycle_id.clone(), answers: BTreeMap::from([("actions".to_string(), " ".to_string())]) },
+            "The answer to 'actions' cannot be empty".to_string(),
+        ),
+    ] {
+        match clarifier.provide_answers(answers) {
+            Err(AxiomMCPError::Validation(context)) => assert_eq!(context.message, problem),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    assert_eq!(clarifier.pending().len(), 1, "bad answers leave the cycle waiting");
+    clarifier.provide_answers(RequirementAnswers { cycle_id: request.cycle_id, answers: BTreeMap::new() }).unwrap();
+    assert!(cycle.await.unwrap().unwrap().success);
+}
+
+#[tokio::test]
+async fn test_provide_requirement_answers_tool() {
+    let clarifier = clarifier(ClarificationMode::Interactive);
+    let config = MCPConfiguration {
+        hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
+        intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
+        simulator_management_enabled: false,
+        code_generation_enabled: false,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        ..Default::default()
+    };
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities)
+        .await
+        .unwrap()
+        .with_development_loop(development_loop(clarifier.clone()).await);
+    let mut requests = mcp.subscribe_clarification_requests().unwrap();
+    let development = development_loop(clarifier).await;
+    let cycle = tokio::spawn(async move { development.execute_complete_development_cycle(VAGUE_REQUIREMENT.to_string()).await });
+
+    let request = next_request(&mut requests).await;
+    let answered = mcp.execute_tool(AxiomMCPTool::ProvideRequirementAnswers(RequirementAnswers {
+        cycle_id: request.cycle_id.clone(),
+        answers: BTreeMap::from([("actions".to_string(), "Users add and archive notes".to_string())]),
+    })).await.unwrap();
+
+    assert_eq!(
+        answered.summary(),
+        format!("Answered 3 questions for cycle {}, 2 with their suggested defaults", request.cycle_id)
+    );
+    assert_eq!(cycle.await.unwrap().unwrap().requirement_analysis.actions, vec!["add", "archive"]);
+}
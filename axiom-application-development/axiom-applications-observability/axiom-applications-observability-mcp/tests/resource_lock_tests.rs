@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use axiom_applications_observability::error::ErrorCategory;
+use axiom_applications_observability::resource_locks::*;
+use axiom_applications_observability::simulator::*;
+use axiom_applications_observability::*;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+const IPHONE_15: &str = "B2C3D4E5-0000-0000-0000-000000000001";
+const IPHONE_15_PRO: &str = "B2C3D4E5-0000-0000-0000-000000000002";
+const ERASE_TIME: Duration = Duration::from_millis(200);
+
+/// Two shut down simulators whose erase takes a while, recording when
+/// each erase ran
+struct SlowEraseSimctl {
+    erases: Mutex<Vec<(String, Instant, Instant)>>,
+}
+
+impl SlowEraseSimctl {
+    fn new() -> Arc<Self> {
+        Arc::new(Self { erases: Mutex::new(Vec::new()) })
+    }
+
+    fn erases(&self) -> Vec<(String, Instant, Instant)> {
+        self.erases.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Simctl for SlowEraseSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["list", "devices", "--json"] => Ok(json!({
+                "devices": {
+                    "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [
+                        { "udid": IPHONE_15, "name": "iPhone 15", "state": "Shutdown", "isAvailable": true },
+                        { "udid": IPHONE_15_PRO, "name": "iPhone 15 Pro", "state": "Shutdown", "isAvailable": true },
+                    ]
+                }
+            })
+            .to_string()),
+            ["erase", udid] => {
+                let started = Instant::now();
+                tokio::time::sleep(ERASE_TIME).await;
+                self.erases.lock().unwrap().push((udid.to_string(), started, Instant::now()));
+                Ok(String::new())
+            }
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], _env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], _input: &[u8]) -> Result<String> {
+        self.run(args).await
+    }
+}
+
+async fn mcp(simctl: &Arc<SlowEraseSimctl>, lock_timeout: Duration) -> AxiomApplicationsObservabilityMCP {
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: true,
+        performance_monitoring: false,
+    };
+    let config = MCPConfiguration {
+        code_generation_enabled: false,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        ..Default::default()
+    };
+    AxiomApplicationsObservabilityMCP::new(config, capabilities)
+        .await
+        .unwrap()
+        .with_simulator_controller(SimulatorController::with_simctl(simctl.clone()))
+        .with_lock_timeout(lock_timeout)
+}
+
+fn erase(device: &str) -> AxiomMCPTool {
+    AxiomMCPTool::EraseSimulator(device.to_string())
+}
+
+#[tokio::test]
+async fn test_same_simulator_named_two_ways_is_erased_one_call_at_a_time() {
+    let simctl = SlowEraseSimctl::new();
+    let mcp = mcp(&simctl, Duration::from_secs(5)).await;
+
+    let (by_name, by_udid) = tokio::join!(mcp.execute_tool(erase("iPhone 15 Pro")), mcp.execute_tool(erase(IPHONE_15_PRO)));
+    by_name.unwrap();
+    by_udid.unwrap();
+
+    let mut erases = simctl.erases();
+    erases.sort_by_key(|(_, started, _)| *started);
+    assert_eq!(erases.len(), 2);
+    assert!(erases[1].1 >= erases[0].2, "erases of {} overlapped", IPHONE_15_PRO);
+}
+
+#[tokio::test]
+async fn test_different_simulators_are_erased_in_parallel() {
+    let simctl = SlowEraseSimctl::new();
+    let mcp = mcp(&simctl, Duration::from_secs(5)).await;
+
+    let started = Instant::now();
+    let (first, second) = tokio::join!(mcp.execute_tool(erase(IPHONE_15)), mcp.execute_tool(erase(IPHONE_15_PRO)));
+    first.unwrap();
+    second.unwrap();
+
+    assert!(started.elapsed() < ERASE_TIME * 2, "took {:?}", started.elapsed());
+    let erases = simctl.erases();
+    let (a, b) = (&erases[0], &erases[1]);
+    assert!(a.1 < b.2 && b.1 < a.2, "erases did not overlap");
+}
+
+#[tokio::test]
+async fn test_waiting_past_the_timeout_reports_the_holder() {
+    let simctl = SlowEraseSimctl::new();
+    let mcp = mcp(&simctl, Duration::from_millis(50)).await;
+
+    let (first, second) = tokio::join!(mcp.execute_tool(erase(IPHONE_15)), async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        mcp.execute_tool(erase(IPHONE_15)).await
+    });
+    first.unwrap();
+
+    let error = second.unwrap_err();
+    match &error {
+        AxiomMCPError::ResourceBusy { holder, waited_ms } => {
+            assert_eq!(holder.resource, Resource::Simulator(IPHONE_15.to_string()));
+            assert_eq!(holder.tool, "erase_simulator");
+            assert!(*waited_ms >= 50, "{}", waited_ms);
+        }
+        other => panic!("expected a busy error, got {:?}", other),
+    }
+    assert_eq!(error.category(), ErrorCategory::Busy);
+    assert!(error.is_retryable());
+    assert!(error.to_string().contains("erase_simulator"), "{}", error);
+    assert_eq!(simctl.erases().len(), 1);
+}
+
+#[tokio::test]
+async fn test_system_status_lists_held_resources() {
+    let simctl = SlowEraseSimctl::new();
+    let mcp = mcp(&simctl, Duration::from_secs(5)).await;
+
+    let (erased, status) = tokio::join!(mcp.execute_tool(erase(IPHONE_15)), async {
+        tokio::time::sleep(ERASE_TIME / 2).await;
+        mcp.execute_tool(AxiomMCPTool::GetSystemStatus).await
+    });
+    erased.unwrap();
+
+    let status = status.unwrap();
+    match &status {
+        ToolResult::SystemStatus(status) => {
+            assert_eq!(status.locks.len(), 1);
+            assert_eq!(status.locks[0].tool, "erase_simulator");
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(status.summary().ends_with(&format!("busy: simulator {} held by erase_simulator", IPHONE_15)), "{}", status.summary());
+
+    match mcp.execute_tool(AxiomMCPTool::GetSystemStatus).await.unwrap() {
+        ToolResult::SystemStatus(status) => assert!(status.locks.is_empty()),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_all_simulators_conflicts_with_each_simulator_only() {
+    let locks = ResourceLocks::new(Duration::from_millis(20));
+    let matrix = locks.acquire("capture_screenshot_matrix", vec![Resource::AllSimulators]).await.unwrap();
+
+    let error = locks.acquire("boot_simulator", vec![Resource::Simulator(IPHONE_15.to_string())]).await.unwrap_err();
+    assert!(matches!(error, AxiomMCPError::ResourceBusy { ref holder, .. } if holder.tool == "capture_screenshot_matrix"));
+    let project = locks.acquire("resume_development_cycle", vec![Resource::Project(PathBuf::from("/tmp/Tasks"))]).await.unwrap();
+    let channel = locks.acquire("set_hot_reload_filters", vec![Resource::HotReloadChannel]).await.unwrap();
+    assert_eq!(locks.holders().len(), 3);
+
+    drop(matrix);
+    locks.acquire("boot_simulator", vec![Resource::Simulator(IPHONE_15.to_string())]).await.unwrap();
+    drop((project, channel));
+    assert!(locks.holders().is_empty());
+}
+
+#[tokio::test]
+async fn test_waiter_takes_the_resource_once_released() {
+    let locks = Arc::new(ResourceLocks::new(Duration::from_secs(5)));
+    let held = locks.acquire("erase_simulator", vec![Resource::Simulator(IPHONE_15.to_string())]).await.unwrap();
+
+    let waiter = tokio::spawn({
+        let locks = locks.clone();
+        async move {
+            let started = Instant::now();
+            let _guard = locks.acquire("boot_simulator", vec![Resource::Simulator(IPHONE_15.to_string())]).await.unwrap();
+            started.elapsed()
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    drop(held);
+
+    let waited = waiter.await.unwrap();
+    assert!(waited >= Duration::from_millis(50) && waited < Duration::from_secs(1), "{:?}", waited);
+}
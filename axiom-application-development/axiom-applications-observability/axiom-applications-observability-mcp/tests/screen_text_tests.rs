@@ -0,0 +1,263 @@
+mod common;
+
+use async_trait::async_trait;
+use axiom_applications_observability::advanced_visual_intelligence::*;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::simulator::*;
+use axiom_applications_observability::*;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A 600x400 task list mockup at 2x, with bars where its lines of text
+/// sit, and the lines Vision and Tesseract report for it
+const SCREEN_PNG: &[u8] = include_bytes!("fixtures/task_list_screen.png");
+const VISION_LINES: &str = include_str!("fixtures/task_list_screen.vision.json");
+const TESSERACT_TSV: &str = include_str!("fixtures/task_list_screen.tsv");
+
+const UDID: &str = "A1B2C3D4-0000-0000-0000-000000000005";
+const BUNDLE_ID: &str = "com.axiom.tasks";
+
+fn recognized() -> Vec<RecognizedText> {
+    serde_json::from_str(VISION_LINES).unwrap()
+}
+
+/// Replays the lines recognized on the fixture, whatever image it is given
+struct ReplayRecognizer(Vec<RecognizedText>);
+
+#[async_trait]
+impl TextRecognizer for ReplayRecognizer {
+    async fn recognize(&self, _png: &[u8]) -> Result<Vec<RecognizedText>> {
+        Ok(self.0.clone())
+    }
+}
+
+async fn engine(lines: Vec<RecognizedText>) -> VisualIntelligenceEngine {
+    VisualIntelligenceEngine::new().await.unwrap().with_text_recognizer(Arc::new(ReplayRecognizer(lines)))
+}
+
+fn found(expected: TextExpectation) -> Option<String> {
+    find_text(&recognized(), &expected).unwrap().map(|line| line.text)
+}
+
+#[test]
+fn test_folding_replaces_typographic_punctuation_and_collapses_whitespace() {
+    assert_eq!(fold_text("  \u{201C}Call Mom\u{201D}\u{00A0}\u{2013} tomorrow\u{2026}\n"), "\"Call Mom\" - tomorrow...");
+    assert_eq!(fold_text("Don\u{2019}t\tforget"), "Don't forget");
+}
+
+#[test]
+fn test_literal_text_matches_regardless_of_case_whitespace_and_smart_quotes() {
+    assert_eq!(found(TextExpectation::literal("task list")).as_deref(), Some("Task List"));
+    assert_eq!(found(TextExpectation::literal("TaskList")).as_deref(), Some("Task List"));
+    assert_eq!(found(TextExpectation::literal("BUY MILK  &  EGGS")).as_deref(), Some("Buy milk & eggs"));
+    assert_eq!(found(TextExpectation::literal("\"Call Mom\" - tomorrow")).as_deref(), Some("\u{201C}Call Mom\u{201D} \u{2013} tomorrow"));
+    assert_eq!(found(TextExpectation::literal("3 tasks remaining")).as_deref(), Some("3 tasks  remaining"));
+    assert_eq!(found(TextExpectation::literal("Buy bread")), None);
+}
+
+#[test]
+fn test_text_wrapped_over_lines_is_found_across_them() {
+    let line = find_text(&recognized(), &TextExpectation::literal("Pick up dry cleaning")).unwrap().unwrap();
+
+    assert_eq!(line.text, "Pick up dry cleaning");
+    assert_eq!(line.confidence, 0.85);
+    assert_eq!(line.frame, ElementFrame { x: 40.0, y: 210.0, width: 180.0, height: 65.0 });
+}
+
+#[test]
+fn test_patterns_match_folded_text_ignoring_case() {
+    assert_eq!(found(TextExpectation::pattern(r"^\d+ TASKS? remaining$")).as_deref(), Some("3 tasks  remaining"));
+    assert_eq!(found(TextExpectation::pattern(r#"^"call mom" - "#)).as_deref(), Some("\u{201C}Call Mom\u{201D} \u{2013} tomorrow"));
+    assert_eq!(found(TextExpectation::pattern(r"^\d+ tasks done")), None);
+}
+
+#[test]
+fn test_unusable_expectations_are_rejected() {
+    for expected in [TextExpectation::literal("  "), TextExpectation::pattern("(unclosed")] {
+        match find_text(&recognized(), &expected) {
+            Err(AxiomMCPError::Validation(_)) => {}
+            other => panic!("expected a validation error for {:?}, got {:?}", expected, other),
+        }
+    }
+}
+
+#[test]
+fn test_tesseract_words_are_grouped_into_lines() {
+    let lines = parse_tesseract_tsv(TESSERACT_TSV);
+
+    assert_eq!(lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>(), vec!["Task List", "Buy milk eggs"]);
+    assert!((lines[0].confidence - 0.95).abs() < 1e-9);
+    assert!((lines[1].confidence - 0.89).abs() < 1e-9);
+    assert_eq!(lines[1].frame, ElementFrame { x: 40.0, y: 110.0, width: 260.0, height: 30.0 });
+}
+
+#[tokio::test]
+async fn test_screen_text_is_reported_in_points_with_evidence_crops() {
+    let crops = tempfile::tempdir().unwrap();
+    let engine = engine(recognized()).await;
+
+    let report = engine
+        .check_screen_text(
+            "task-list",
+            SCREEN_PNG,
+            2.0,
+            &[TextExpectation::literal("task list"), TextExpectation::literal("Buy bread")],
+            None,
+            crops.path(),
+        )
+        .await
+        .unwrap();
+
+    assert!(!report.passed());
+    assert_eq!(report.recognized.first().unwrap().text, "Task List", "reading order puts the title first");
+    let title = &report.matched[0];
+    assert_eq!(title.confidence, Some(0.98));
+    assert_eq!(title.frame, Some(ElementFrame { x: 20.0, y: 15.0, width: 100.0, height: 20.0 }));
+    // The title padded by 8pt, in pixels
+    let crop = image::open(title.crop_path.as_ref().unwrap()).unwrap().to_rgba8();
+    assert_eq!((crop.width(), crop.height()), (232, 72));
+    assert_eq!(report.missing[0].expected.text, "Buy bread");
+    assert_eq!(report.missing[0].crop_path, None, "without a region there is nothing narrower than the screenshot to show");
+}
+
+#[tokio::test]
+async fn test_a_region_limits_where_text_counts_and_frames_missing_text() {
+    let crops = tempfile::tempdir().unwrap();
+    let engine = engine(recognized()).await;
+    let list = ElementFrame { x: 0.0, y: 50.0, width: 300.0, height: 100.0 };
+
+    let report = engine
+        .check_screen_text(
+            "task-list",
+            SCREEN_PNG,
+            2.0,
+            &[TextExpectation::literal("Buy milk & eggs"), TextExpectation::literal("Task List")],
+            Some(list),
+            crops.path(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.matched.iter().map(|m| m.expected.text.as_str()).collect::<Vec<_>>(), vec!["Buy milk & eggs"]);
+    assert_eq!(report.missing[0].expected.text, "Task List");
+    assert!(report.recognized.iter().all(|line| line.text != "Task List" && line.text != "3 tasks  remaining"));
+    let crop = image::open(report.missing[0].crop_path.as_ref().unwrap()).unwrap().to_rgba8();
+    assert_eq!((crop.width(), crop.height()), (600, 232), "the region padded by 8pt, clipped to the screenshot");
+}
+
+#[tokio::test]
+async fn test_expectations_are_checked_before_the_screen_is_read() {
+    let engine = engine(recognized()).await;
+
+    let result = engine
+        .check_screen_text("task-list", SCREEN_PNG, 2.0, &[], None, std::env::temp_dir().as_path())
+        .await;
+
+    assert!(matches!(result, Err(AxiomMCPError::Validation(context)) if context.message == "No expected text given"));
+}
+
+/// Simctl with one booted device whose screen is the fixture
+struct FakeSimctl;
+
+#[async_trait]
+impl Simctl for FakeSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["list", "devices", "--json"] => Ok(json!({
+                "devices": {
+                    "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [{
+                        "udid": UDID,
+                        "name": "iPhone 15 Pro",
+                        "state": "Booted",
+                        "isAvailable": true,
+                        "deviceTypeIdentifier": "com.apple.CoreSimulator.SimDeviceType.iPhone-15-Pro",
+                    }]
+                }
+            })
+            .to_string()),
+            ["install", ..] => Ok(String::new()),
+            ["launch", ..] => Ok(format!("{}: 4321\n", BUNDLE_ID)),
+            ["io", UDID, "screenshot", path] => {
+                std::fs::write(path, SCREEN_PNG)?;
+                Ok(String::new())
+            }
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], _env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], _input: &[u8]) -> Result<String> {
+        self.run(args).await
+    }
+}
+
+async fn development_loop(lines: Vec<RecognizedText>, app: std::path::PathBuf) -> AxiomObservabilityLoop {
+    let target = AppRunTarget {
+        install: InstallAppRequest { device: UDID.to_string(), app_path: app },
+        launch: LaunchAppRequest {
+            device: UDID.to_string(),
+            bundle_id: BUNDLE_ID.to_string(),
+            ..Default::default()
+        },
+    };
+
+    common::DevelopmentLoopBuilder::new()
+        .with_visual_intelligence(engine(lines).await)
+        .build()
+        .await
+        .with_app_run(Arc::new(SimulatorController::with_simctl(Arc::new(FakeSimctl))), target)
+}
+
+fn app_bundle() -> (tempfile::TempDir, std::path::PathBuf) {
+    let build = tempfile::tempdir().unwrap();
+    let app = build.path().join("Debug-iphonesimulator").join("Tasks.app");
+    std::fs::create_dir_all(&app).unwrap();
+    (build, app)
+}
+
+const REQUIREMENT: &str = "A task list screen, a task detail screen and a settings screen";
+
+#[tokio::test]
+async fn test_the_loop_checks_the_launched_screen_shows_its_title() {
+    let (_build, app) = app_bundle();
+
+    let result = development_loop(recognized(), app).await.execute_complete_development_cycle(REQUIREMENT.to_string()).await.unwrap();
+
+    assert_eq!(result.implementation.expected_screen_text, vec![TextExpectation::literal("TaskList")]);
+    let screen_text = result.visual_validation.unwrap().screen_text.unwrap();
+    assert!(screen_text.passed());
+    assert_eq!(screen_text.matched[0].found.as_deref(), Some("Task List"));
+    assert!(result.success);
+}
+
+#[tokio::test]
+async fn test_a_launched_screen_missing_its_title_fails_the_cycle() {
+    let (_build, app) = app_bundle();
+    let loading = vec![RecognizedText {
+        text: "Loading\u{2026}".to_string(),
+        confidence: 0.9,
+        frame: ElementFrame { x: 250.0, y: 190.0, width: 100.0, height: 20.0 },
+    }];
+
+    let result = development_loop(loading, app).await.execute_complete_development_cycle(REQUIREMENT.to_string()).await.unwrap();
+
+    let screen_text = result.visual_validation.unwrap().screen_text.unwrap();
+    assert_eq!(screen_text.missing[0].expected.text, "TaskList");
+    assert!(result.validation_result.passed);
+    assert!(!result.success);
+}
+
+#[tokio::test]
+async fn test_implementations_without_expected_text_skip_the_check() {
+    let (_build, app) = app_bundle();
+
+    let result = development_loop(Vec::new(), app).await.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    assert!(result.implementation.expected_screen_text.is_empty());
+    assert!(result.visual_validation.unwrap().screen_text.is_none());
+    assert!(result.success);
+}
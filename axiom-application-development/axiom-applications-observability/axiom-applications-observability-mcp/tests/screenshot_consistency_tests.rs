@@ -0,0 +1,242 @@
+use axiom_applications_observability::advanced_visual_intelligence::*;
+use axiom_applications_observability::screenshot_consistency::*;
+use axiom_applications_observability::types::*;
+use axiom_applications_observability::visual_regression::encode_png;
+use axiom_applications_observability::*;
+use image::{Rgba, RgbaImage};
+use std::sync::Arc;
+
+const PHONE: (f64, f64) = (393.0, 852.0);
+const TABLET: (f64, f64) = (1032.0, 1376.0);
+
+/// x, y, width and height in units of the screen's size
+type Frame = (f64, f64, f64, f64);
+
+/// Views of the screen in units of its size
+const SIDEBAR: Frame = (0.0, 0.0, 0.3, 1.0);
+const TITLE: Frame = (0.35, 0.05, 0.6, 0.1);
+const SAVE: Frame = (0.35, 0.85, 0.3, 0.08);
+
+fn view(identifier: &str, (x, y, width, height): Frame, (screen_width, screen_height): (f64, f64)) -> ViewNode {
+    ViewNode {
+        identifier: identifier.to_string(),
+        kind: "View".to_string(),
+        frame: ElementFrame { x: x * screen_width, y: y * screen_height, width: width * screen_width, height: height * screen_height },
+        text: None,
+        intrinsic_size: None,
+        line_limit: None,
+        hidden: false,
+        children: Vec::new(),
+    }
+}
+
+fn tree(views: &[(&str, Frame)], size: (f64, f64)) -> ViewNode {
+    ViewNode {
+        children: views.iter().map(|&(identifier, frame)| view(identifier, frame, size)).collect(),
+        ..view("", (0.0, 0.0, 1.0, 1.0), size)
+    }
+}
+
+fn standard_tree(size: (f64, f64)) -> ViewNode {
+    tree(&[("sidebar", SIDEBAR), ("title", TITLE), ("save", SAVE)], size)
+}
+
+/// A white screen with a dark block over `frame`, at 1x
+fn render((x, y, width, height): Frame, (screen_width, screen_height): (f64, f64)) -> RgbaImage {
+    let (left, right) = ((x * screen_width).round() as u32, ((x + width) * screen_width).round() as u32);
+    let (top, bottom) = ((y * screen_height).round() as u32, ((y + height) * screen_height).round() as u32);
+    RgbaImage::from_fn(screen_width as u32, screen_height as u32, |px, py| {
+        if (left..right).contains(&px) && (top..bottom).contains(&py) {
+            Rgba([20, 20, 40, 255])
+        } else {
+            Rgba([255, 255, 255, 255])
+        }
+    })
+}
+
+fn screenshot(id: &str, device: &str, size: (f64, f64), image: Option<&RgbaImage>, view_tree: Option<ViewNode>) -> Screenshot {
+    let screen_size = ScreenSize { width: size.0, height: size.1 };
+    Screenshot {
+        id: id.to_string(),
+        client_id: "test-client".to_string(),
+        configuration: ScreenshotConfiguration {
+            device_type: device.to_string(),
+            screen_size: screen_size.clone(),
+            orientation: "portrait".to_string(),
+            scale: 1.0,
+            color_scheme: "light".to_string(),
+            capture_mode: "standard".to_string(),
+            status_bar: None,
+            runtime: None,
+            window: Default::default(),
+        },
+        image_data: image.map(|image| encode_png(image).unwrap()).unwrap_or_default(),
+        image: None,
+        metadata: ScreenshotMetadata {
+            timestamp: chrono::Utc::now(),
+            device_info: DeviceInfo { model: device.to_string(), screen_size, orientation: "portrait".to_string(), udid: None },
+            app_state: AppState {
+                view_hierarchy: "NotesView".to_string(),
+                active_context: "NotesContext".to_string(),
+                view_tree,
+            },
+            overrides: Default::default(),
+        },
+    }
+}
+
+fn phone() -> Screenshot {
+    screenshot("phone", "iPhone 15", PHONE, Some(&render(SIDEBAR, PHONE)), Some(standard_tree(PHONE)))
+}
+
+fn tablet() -> Screenshot {
+    screenshot("tablet", "iPad Pro 13-inch (M4)", TABLET, Some(&render(SIDEBAR, TABLET)), Some(standard_tree(TABLET)))
+}
+
+fn issue(screenshot_id: &str) -> LayoutIssue {
+    LayoutIssue {
+        kind: LayoutIssueKind::Truncation,
+        screenshot_id: screenshot_id.to_string(),
+        element_ids: vec!["title".to_string()],
+        region: ElementFrame::default(),
+        message: "title is truncated".to_string(),
+        crop_path: None,
+    }
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+}
+
+#[test]
+fn test_proportional_variants_are_consistent() {
+    let report = consistency_report(&[phone(), tablet()], &[]);
+
+    assert_eq!(report.pairs.len(), 1);
+    let pair = &report.pairs[0];
+    assert_eq!((pair.first.as_str(), pair.second.as_str()), ("phone", "tablet"));
+    assert_eq!(pair.screen, "NotesView portrait light");
+    assert_close(pair.layout_similarity.unwrap(), 1.0);
+    assert_eq!(pair.perceptual_similarity, Some(1.0));
+    assert_close(report.score, 1.0);
+}
+
+#[test]
+fn test_moved_and_missing_views_lower_layout_similarity() {
+    // The save button a half of its height lower, and the title left out
+    let moved = tree(&[("sidebar", SIDEBAR), ("title", TITLE), ("save", (0.35, 0.89, 0.3, 0.08))], TABLET);
+    let missing = tree(&[("sidebar", SIDEBAR), ("save", SAVE)], TABLET);
+    let reference = screenshot("phone", "iPhone 15", PHONE, None, Some(standard_tree(PHONE)));
+
+    let moved = consistency_report(&[reference.clone(), screenshot("moved", "iPad", TABLET, None, Some(moved))], &[]);
+    let missing = consistency_report(&[reference, screenshot("missing", "iPad", TABLET, None, Some(missing))], &[]);
+
+    // The moved button overlaps its place by a third
+    assert_close(moved.pairs[0].layout_similarity.unwrap(), (1.0 + 1.0 + 1.0 / 3.0) / 3.0);
+    assert_close(missing.pairs[0].layout_similarity.unwrap(), 2.0 / 3.0);
+    // Without images the layout is all there is to go by
+    assert_eq!(moved.pairs[0].perceptual_similarity, None);
+    assert_close(moved.score, moved.pairs[0].layout_similarity.unwrap());
+}
+
+#[test]
+fn test_a_mirrored_image_changes_its_perceptual_hash() {
+    let aspect = PHONE.0 / PHONE.1;
+    let mirrored_sidebar = (0.7, 0.0, 0.3, 1.0);
+
+    let phone_hash = difference_hash(&normalize_image(&render(SIDEBAR, PHONE), aspect));
+    let tablet_hash = difference_hash(&normalize_image(&render(SIDEBAR, TABLET), aspect));
+    let mirrored_hash = difference_hash(&normalize_image(&render(mirrored_sidebar, TABLET), aspect));
+
+    assert_eq!(phone_hash, tablet_hash);
+    // Every row of the mirrored screen darkens from left to right
+    assert!((phone_hash ^ mirrored_hash).count_ones() >= 8, "{:064b}\n{:064b}", phone_hash, mirrored_hash);
+
+    let mirrored = screenshot("mirrored", "iPad", TABLET, Some(&render(mirrored_sidebar, TABLET)), Some(standard_tree(TABLET)));
+    let report = consistency_report(&[phone(), mirrored], &[]);
+    let pair = &report.pairs[0];
+    assert!(pair.perceptual_similarity.unwrap() <= 0.875, "{:?}", pair);
+    assert_close(pair.score, 0.6 * pair.layout_similarity.unwrap() + 0.4 * pair.perceptual_similarity.unwrap());
+}
+
+#[test]
+fn test_layout_issues_penalize_the_pairs_of_their_variant() {
+    let pro = screenshot("pro", "iPhone 15 Pro", PHONE, Some(&render(SIDEBAR, PHONE)), Some(standard_tree(PHONE)));
+    let mut matrix = ScreenshotMatrix {
+        screenshots: vec![phone(), pro, tablet()],
+        analysis: ScreenshotAnalysis {
+            total_screenshots: 3,
+            consistency_score: 0.0,
+            detected_issues: Vec::new(),
+            layout_issues: vec![issue("tablet")],
+            consistency_pairs: Vec::new(),
+            warnings: Vec::new(),
+        },
+    };
+
+    matrix.score_consistency();
+
+    let penalties: Vec<(&str, &str, f64)> = matrix.analysis.consistency_pairs.iter()
+        .map(|pair| (pair.first.as_str(), pair.second.as_str(), pair.issue_penalty))
+        .collect();
+    assert_eq!(penalties, vec![("phone", "pro", 0.0), ("phone", "tablet", 0.1), ("pro", "tablet", 0.1)]);
+    assert_close(matrix.analysis.consistency_score, (1.0 + 0.9 + 0.9) / 3.0);
+    let report = ConsistencyReport { score: matrix.analysis.consistency_score, pairs: matrix.analysis.consistency_pairs };
+    assert_eq!(report.least_consistent().map(|pair| pair.second.as_str()), Some("tablet"));
+}
+
+#[test]
+fn test_only_variants_of_the_same_screen_are_compared() {
+    let mut dark = tablet();
+    dark.configuration.color_scheme = "dark".to_string();
+    let mut landscape = tablet();
+    landscape.configuration.orientation = "landscape".to_string();
+    let mut other_view = tablet();
+    other_view.metadata.app_state.view_hierarchy = "SettingsView".to_string();
+    // The mock engine's placeholder images cannot be compared
+    let placeholder = |id: &str| Screenshot { image_data: vec![1, 2, 3, 4], ..screenshot(id, "iPad", TABLET, None, None) };
+
+    let separate = consistency_report(&[phone(), dark, landscape, other_view], &[]);
+    let incomparable = consistency_report(&[placeholder("first"), placeholder("second")], &[]);
+
+    assert!(separate.pairs.is_empty(), "{:?}", separate.pairs);
+    assert!(incomparable.pairs.is_empty());
+    assert_eq!((separate.score, incomparable.score), (1.0, 1.0));
+}
+
+#[tokio::test]
+async fn test_development_loop_checks_consistency_against_its_minimum() {
+    let hot_reload_client = Arc::new(hot_reload::HotReloadClient::new("ws://localhost:8080/ws").await.unwrap());
+    let intelligence_client = Arc::new(intelligence::IntelligenceClient::new("ws://localhost:8080/intelligence").await.unwrap());
+    let simulator_controller = Arc::new(simulator::SimulatorController::new().await.unwrap());
+    let code_generator = Arc::new(code_generation::AxiomCodeGenerator::new().await.unwrap());
+    let visual_intelligence = Arc::new(VisualIntelligenceEngine::new().await.unwrap());
+    let screenshot_engine = Arc::new(screenshot_matrix_engine::ScreenshotMatrixEngine::new(simulator_controller.clone()).await.unwrap());
+    let performance_analysis = performance_analysis_integration::PerformanceAnalysisIntegration::new(
+        intelligence_client.clone(),
+        hot_reload_client.clone(),
+    ).await.unwrap();
+    let development_loop = complete_development_loop::AxiomObservabilityLoop::new(
+        code_generator.clone(),
+        Arc::new(axiom_framework_integration::AxiomFrameworkIntegration::new(intelligence_client.clone(), code_generator).await.unwrap()),
+        Arc::new(performance_analysis),
+        Arc::new(development_workflow::AxiomObservabilityWorkflow::new(
+            hot_reload_client,
+            simulator_controller,
+            intelligence_client,
+            screenshot_engine.clone(),
+            visual_intelligence.clone(),
+        ).await.unwrap()),
+        visual_intelligence,
+        screenshot_engine,
+    )
+    .await
+    .unwrap()
+    .with_min_consistency_score(0.9);
+
+    let result = development_loop.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    let check = result.visual_validation.unwrap().consistency.expect("a matrix was captured");
+    assert_eq!(check.min_score, 0.9);
+    assert!(check.passed(), "{:?}", check.report);
+}
@@ -0,0 +1,234 @@
+use async_trait::async_trait;
+use axiom_applications_observability::screenshot_store::*;
+use axiom_applications_observability::types::*;
+use axiom_applications_observability::visual_regression::*;
+use axiom_applications_observability::Result;
+use image::{Rgba, RgbaImage};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn store(dir: &Path, retention: RetentionPolicy) -> ScreenshotStore {
+    ScreenshotStore::new(ScreenshotStorageConfig { dir: dir.to_path_buf(), retention, ..Default::default() })
+}
+
+/// A 10x10 PNG of one color, so different shades are different images
+fn png(shade: u8) -> Vec<u8> {
+    encode_png(&RgbaImage::from_pixel(10, 10, Rgba([shade, shade, shade, 255]))).unwrap()
+}
+
+fn objects(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir.join("objects"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+fn age(image: &StoredImage, by: Duration) {
+    let file = std::fs::File::options().write(true).open(&image.path).unwrap();
+    file.set_modified(SystemTime::now() - by).unwrap();
+}
+
+fn screenshot(image: StoredImage) -> Screenshot {
+    Screenshot {
+        id: "task-list".to_string(),
+        client_id: "test-client".to_string(),
+        configuration: ScreenshotConfiguration {
+            device_type: "iPhone 15 Pro".to_string(),
+            screen_size: ScreenSize { width: 10.0, height: 10.0 },
+            orientation: "portrait".to_string(),
+            scale: 1.0,
+            color_scheme: "light".to_string(),
+            capture_mode: "standard".to_string(),
+            status_bar: None,
+            runtime: None,
+            window: Default::default(),
+        },
+        image_data: Vec::new(),
+        image: Some(image),
+        metadata: ScreenshotMetadata {
+            timestamp: chrono::Utc::now(),
+            device_info: DeviceInfo {
+                model: "iPhone 15 Pro".to_string(),
+                screen_size: ScreenSize { width: 10.0, height: 10.0 },
+                orientation: "portrait".to_string(),
+                udid: None,
+            },
+            app_state: AppState {
+                view_hierarchy: "TaskListView".to_string(),
+                active_context: "TaskListContext".to_string(),
+                view_tree: None,
+            },
+            overrides: Default::default(),
+        },
+    }
+}
+
+/// Writes the PNG it is given under the HEIC name
+struct CopyingEncoder;
+
+#[async_trait]
+impl HeicEncoder for CopyingEncoder {
+    async fn encode(&self, png: &Path, heic: &Path) -> Result<()> {
+        std::fs::copy(png, heic)?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_identical_captures_are_stored_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path(), RetentionPolicy::default());
+
+    let first = store.put(&png(10)).await.unwrap();
+    let again = store.put(&png(10)).await.unwrap();
+    let other = store.put(&png(20)).await.unwrap();
+
+    assert_eq!(first, again);
+    assert_ne!(first.hash, other.hash);
+    assert_eq!(first.hash, content_hash(&png(10)));
+    assert_eq!(objects(dir.path()), {
+        let mut names = vec![format!("{}.png", first.hash), format!("{}.png", other.hash)];
+        names.sort();
+        names
+    });
+    assert_eq!(store.read(&first).await.unwrap(), png(10));
+}
+
+#[tokio::test]
+async fn test_storing_a_capture_again_renews_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path(), RetentionPolicy { max_total_bytes: None, max_age_secs: Some(7 * DAY.as_secs()) });
+    let image = store.put(&png(10)).await.unwrap();
+    age(&image, 10 * DAY);
+
+    store.put(&png(10)).await.unwrap();
+    let report = store.prune(SystemTime::now()).unwrap();
+
+    assert!(report.removed.is_empty());
+    assert_eq!(report.kept, 1);
+}
+
+#[tokio::test]
+async fn test_screenshots_hold_a_reference_their_png_is_read_through() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = store(dir.path(), RetentionPolicy::default()).put(&png(10)).await.unwrap();
+
+    let screenshot = screenshot(image);
+
+    assert!(screenshot.image_data.is_empty());
+    assert_eq!(screenshot.png_data().unwrap().as_ref(), png(10).as_slice());
+}
+
+#[tokio::test]
+async fn test_heic_captures_are_stored_but_cannot_be_analyzed() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ScreenshotStore::new(ScreenshotStorageConfig {
+        dir: dir.path().to_path_buf(),
+        encoding: ImageEncoding::Heic,
+        ..Default::default()
+    })
+    .with_heic_encoder(Arc::new(CopyingEncoder));
+
+    let image = store.put(&png(10)).await.unwrap();
+
+    assert_eq!(image.encoding, ImageEncoding::Heic);
+    assert_eq!(objects(dir.path()), vec![format!("{}.heic", image.hash)]);
+    let error = screenshot(image).png_data().unwrap_err();
+    assert!(error.to_string().contains("store screenshots as PNG to compare them"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_pruning_removes_expired_then_oldest_until_under_the_size_limit() {
+    let dir = tempfile::tempdir().unwrap();
+    let sizes = [png(30).len() as u64, png(40).len() as u64];
+    let store = store(dir.path(), RetentionPolicy { max_total_bytes: Some(sizes[0] + sizes[1]), max_age_secs: Some(30 * DAY.as_secs()) });
+    let expired = store.put(&png(10)).await.unwrap();
+    let oldest = store.put(&png(20)).await.unwrap();
+    let older = store.put(&png(30)).await.unwrap();
+    let newest = store.put(&png(40)).await.unwrap();
+    age(&expired, 40 * DAY);
+    age(&oldest, 3 * DAY);
+    age(&older, 2 * DAY);
+    age(&newest, DAY);
+
+    let report = store.prune(SystemTime::now()).unwrap();
+
+    let removed: Vec<(&str, PruneReason)> = report.removed.iter().map(|pruned| (pruned.hash.as_str(), pruned.reason)).collect();
+    assert_eq!(removed, vec![(expired.hash.as_str(), PruneReason::Expired), (oldest.hash.as_str(), PruneReason::OverSize)]);
+    assert_eq!(report.freed_bytes, expired.size_bytes + oldest.size_bytes);
+    assert_eq!((report.kept, report.kept_bytes), (2, sizes[0] + sizes[1]));
+    assert!(older.path.exists() && newest.path.exists());
+    assert!(!expired.path.exists() && !oldest.path.exists());
+}
+
+#[tokio::test]
+async fn test_pruning_leaves_files_the_store_did_not_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path(), RetentionPolicy { max_total_bytes: Some(0), max_age_secs: None });
+    store.put(&png(10)).await.unwrap();
+    std::fs::write(dir.path().join("objects/notes.txt"), "keep").unwrap();
+
+    let report = store.prune(SystemTime::now()).unwrap();
+
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(objects(dir.path()), vec!["notes.txt"]);
+}
+
+#[tokio::test]
+async fn test_baselines_are_exempt_from_pruning() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path(), RetentionPolicy { max_total_bytes: Some(0), max_age_secs: Some(DAY.as_secs()) });
+    let baseline = store.put(&png(10)).await.unwrap();
+    let capture = store.put(&png(20)).await.unwrap();
+    age(&baseline, 90 * DAY);
+    store.pin_baseline("task-list/iphone-15-pro/portrait-light", &baseline).await.unwrap();
+
+    let report = store.prune(SystemTime::now()).unwrap();
+
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(report.removed[0].hash, capture.hash);
+    assert_eq!(report.baselines, 1);
+    assert!(baseline.path.exists());
+}
+
+#[tokio::test]
+async fn test_rerecording_a_baseline_releases_its_old_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = store(dir.path(), RetentionPolicy { max_total_bytes: Some(0), max_age_secs: None });
+    let first = store.put(&png(10)).await.unwrap();
+    let second = store.put(&png(20)).await.unwrap();
+    store.pin_baseline("task-list/iphone-15-pro/portrait-light", &first).await.unwrap();
+    store.pin_baseline("task-list/iphone-15-pro/portrait-light", &second).await.unwrap();
+
+    let report = store.prune(SystemTime::now()).unwrap();
+
+    assert_eq!(report.removed.iter().map(|pruned| pruned.hash.clone()).collect::<Vec<_>>(), vec![first.hash]);
+    assert!(second.path.exists());
+}
+
+#[tokio::test]
+async fn test_recording_a_stored_screenshot_as_baseline_pins_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(store(&dir.path().join("screenshots"), RetentionPolicy { max_total_bytes: Some(0), max_age_secs: None }));
+    let image = store.put(&png(10)).await.unwrap();
+    let checker = VisualRegressionChecker::new(VisualRegressionConfig {
+        baseline_dir: dir.path().join("baselines"),
+        ..Default::default()
+    })
+    .with_screenshot_store(store.clone());
+
+    checker.run(&RegressionCheckRequest {
+        mode: RegressionMode::Record,
+        screen: "Task List".to_string(),
+        screenshots: vec![screenshot(image.clone())],
+    }).await.unwrap();
+
+    assert_eq!(store.baselines().unwrap().into_values().collect::<Vec<_>>(), vec![image.hash.clone()]);
+    assert!(store.prune(SystemTime::now()).unwrap().removed.is_empty());
+    assert!(image.path.exists());
+}
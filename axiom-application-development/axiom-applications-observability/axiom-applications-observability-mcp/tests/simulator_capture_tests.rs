@@ -0,0 +1,342 @@
+use async_trait::async_trait;
+use axiom_applications_observability::simulator::*;
+use axiom_applications_observability::simulator_capture::*;
+use axiom_applications_observability::{AxiomMCPError, Result, ToolResult};
+use serde_json::json;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+const BOOTED: &str = "A1B2C3D4-0000-0000-0000-000000000002";
+const SHUTDOWN: &str = "A1B2C3D4-0000-0000-0000-000000000001";
+const SUBSYSTEM: &str = "com.axiom.tasks";
+
+/// Lists one booted and one shut down iPhone
+struct FakeSimctl;
+
+#[async_trait]
+impl Simctl for FakeSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["list", "devices", "--json"] => Ok(json!({
+                "devices": {
+                    "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [
+                        { "udid": SHUTDOWN, "name": "iPhone 15", "state": "Shutdown", "isAvailable": true },
+                        { "udid": BOOTED, "name": "iPhone 15 Pro", "state": "Booted", "isAvailable": true },
+                    ]
+                }
+            })
+            .to_string()),
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], _env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], _input: &[u8]) -> Result<String> {
+        self.run(args).await
+    }
+}
+
+/// Writes `video_bytes` to the path a recording is given, and answers log
+/// streams with `log_output`
+struct FakeCapture {
+    video_bytes: usize,
+    log_output: String,
+    started: Mutex<Vec<Vec<String>>>,
+}
+
+impl FakeCapture {
+    fn new(video_bytes: usize) -> Arc<Self> {
+        Arc::new(Self { video_bytes, log_output: log_output(), started: Mutex::new(Vec::new()) })
+    }
+}
+
+struct FakeProcess {
+    stdout: String,
+    max_output_bytes: usize,
+}
+
+#[async_trait]
+impl CaptureProcess for FakeProcess {
+    async fn stop(self: Box<Self>) -> Result<CapturedOutput> {
+        let truncated = self.stdout.len() > self.max_output_bytes;
+        let stdout = self.stdout.chars().take(self.max_output_bytes).collect();
+        Ok(CapturedOutput { stdout, truncated })
+    }
+}
+
+#[async_trait]
+impl CaptureCommand for FakeCapture {
+    async fn start(&self, args: &[String], limits: OutputLimits) -> Result<Box<dyn CaptureProcess>> {
+        self.started.lock().unwrap().push(args.to_vec());
+        let stdout = if args[0] == "io" {
+            std::fs::write(args.last().unwrap(), vec![0u8; self.video_bytes])?;
+            String::new()
+        } else {
+            self.log_output.clone()
+        };
+        Ok(Box::new(FakeProcess { stdout, max_output_bytes: limits.max_output_bytes }))
+    }
+}
+
+fn log_line(level: &str, category: &str, message: &str) -> String {
+    json!({
+        "timestamp": "2026-10-15 09:41:00.123456+0000",
+        "messageType": level,
+        "subsystem": SUBSYSTEM,
+        "category": category,
+        "processImagePath": "/Users/dev/Library/Developer/CoreSimulator/Devices/X/data/Containers/Bundle/Application/Y/Tasks.app/Tasks",
+        "eventMessage": message,
+    })
+    .to_string()
+}
+
+fn log_output() -> String {
+    [
+        "Filtering the log data using \"subsystem == \\\"com.axiom.tasks\\\"\"".to_string(),
+        log_line("Debug", "store", "Opened the task store"),
+        log_line("Info", "store", "Loaded 3 tasks"),
+        log_line("Error", "sync", "Sync failed: offline"),
+        log_line("Fault", "ui", "Missing row for task 4"),
+    ]
+    .join("\n")
+}
+
+fn capture(dir: &Path, command: &Arc<FakeCapture>) -> SimulatorCapture {
+    SimulatorCapture::new(dir).with_command(command.clone())
+}
+
+fn simulator() -> SimulatorController {
+    SimulatorController::with_simctl(Arc::new(FakeSimctl))
+}
+
+fn files_in(dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    files
+}
+
+fn age(path: &Path, by: Duration) {
+    let file = std::fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - by).unwrap();
+}
+
+#[test]
+fn test_predicate_matches_the_subsystem() {
+    assert_eq!(log_predicate(SUBSYSTEM, &[]).unwrap(), "subsystem == \"com.axiom.tasks\"");
+}
+
+#[test]
+fn test_predicate_narrows_to_any_of_the_categories() {
+    let categories = vec!["store".to_string(), "sync".to_string()];
+
+    assert_eq!(
+        log_predicate(SUBSYSTEM, &categories).unwrap(),
+        "subsystem == \"com.axiom.tasks\" AND (category == \"store\" OR category == \"sync\")"
+    );
+}
+
+#[test]
+fn test_predicate_escapes_quotes_and_backslashes() {
+    let categories = vec!["a\"b\\c".to_string()];
+
+    assert_eq!(
+        log_predicate("com.axiom\"x", &categories).unwrap(),
+        "subsystem == \"com.axiom\\\"x\" AND (category == \"a\\\"b\\\\c\")"
+    );
+}
+
+#[test]
+fn test_predicate_needs_a_subsystem() {
+    let error = log_predicate("  ", &[]).unwrap_err();
+
+    assert!(error.to_string().contains("subsystem cannot be empty"), "{}", error);
+}
+
+#[test]
+fn test_log_stream_runs_inside_the_simulator_as_ndjson() {
+    let args = log_stream_args(BOOTED, "subsystem == \"x\"", LogLevel::Error);
+
+    assert_eq!(
+        args,
+        vec!["spawn", BOOTED, "log", "stream", "--style", "ndjson", "--level", "default", "--predicate", "subsystem == \"x\""]
+    );
+    assert_eq!(log_stream_args(BOOTED, "p", LogLevel::Debug)[7], "debug");
+}
+
+#[test]
+fn test_record_video_overwrites_the_given_path() {
+    let args = record_video_args(BOOTED, Path::new("/tmp/captures/recording-1.mp4"));
+
+    assert_eq!(args, vec!["io", BOOTED, "recordVideo", "--codec=h264", "--force", "/tmp/captures/recording-1.mp4"]);
+}
+
+#[test]
+fn test_log_stream_is_parsed_into_leveled_entries() {
+    let entries = parse_log_stream(&log_output(), LogLevel::Info);
+
+    let levels: Vec<LogLevel> = entries.iter().map(|entry| entry.level).collect();
+    assert_eq!(levels, vec![LogLevel::Info, LogLevel::Error, LogLevel::Fault]);
+    assert_eq!(entries[1].category, "sync");
+    assert_eq!(entries[1].message, "Sync failed: offline");
+    assert_eq!(entries[1].process, "Tasks");
+    assert_eq!(parse_log_stream(&log_output(), LogLevel::Error).len(), 2);
+}
+
+#[test]
+fn test_pruning_removes_only_artifacts_past_retention() {
+    let dir = tempfile::tempdir().unwrap();
+    let old = dir.path().join("recording-old.mp4");
+    let recent = dir.path().join("logs-recent.json");
+    std::fs::write(&old, "old").unwrap();
+    std::fs::write(&recent, "[]").unwrap();
+    std::fs::create_dir(dir.path().join("keep")).unwrap();
+    age(&old, Duration::from_secs(8 * 24 * 60 * 60));
+    age(&recent, Duration::from_secs(60 * 60));
+
+    let pruned = prune_artifacts(dir.path(), Duration::from_secs(DEFAULT_RETENTION_SECS), SystemTime::now()).unwrap();
+
+    assert_eq!(pruned, vec![old]);
+    assert_eq!(files_in(dir.path()), vec!["keep", "logs-recent.json"]);
+}
+
+#[test]
+fn test_pruning_a_missing_directory_removes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let pruned = prune_artifacts(&dir.path().join("missing"), Duration::ZERO, SystemTime::now()).unwrap();
+
+    assert!(pruned.is_empty());
+}
+
+#[tokio::test]
+async fn test_starting_a_capture_prunes_expired_artifacts() {
+    let dir = tempfile::tempdir().unwrap();
+    let expired = dir.path().join("recording-expired.mp4");
+    std::fs::write(&expired, "old").unwrap();
+    age(&expired, Duration::from_secs(2 * 60 * 60));
+    let limits = CaptureLimits { retention_secs: 60 * 60, ..Default::default() };
+    let capture = capture(dir.path(), &FakeCapture::new(16)).with_limits(limits);
+
+    let handle = capture.start_recording(&simulator(), &StartRecordingRequest { device: BOOTED.to_string(), max_seconds: None }).await.unwrap();
+
+    assert!(!expired.exists());
+    assert!(handle.path.exists());
+}
+
+#[tokio::test]
+async fn test_recording_returns_its_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let command = FakeCapture::new(1024);
+    let capture = capture(dir.path(), &command);
+    let simulator = simulator();
+
+    let handle = capture.start_recording(&simulator, &StartRecordingRequest { device: BOOTED.to_string(), max_seconds: Some(30) }).await.unwrap();
+    let recording = capture.stop_recording(&handle.recording_id).await.unwrap();
+
+    assert_eq!(recording.path, handle.path);
+    assert_eq!(recording.size_bytes, 1024);
+    assert_eq!(recording.device.udid, BOOTED);
+    assert!(ToolResult::Recording(recording).summary().starts_with("Recorded "));
+    let error = capture.stop_recording(&handle.recording_id).await.unwrap_err();
+    assert!(error.to_string().contains("is running"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_recording_over_the_size_cap_is_deleted() {
+    let dir = tempfile::tempdir().unwrap();
+    let limits = CaptureLimits { max_recording_bytes: 512, ..Default::default() };
+    let capture = capture(dir.path(), &FakeCapture::new(1024)).with_limits(limits);
+
+    let handle = capture.start_recording(&simulator(), &StartRecordingRequest { device: BOOTED.to_string(), max_seconds: None }).await.unwrap();
+    let error = capture.stop_recording(&handle.recording_id).await.unwrap_err();
+
+    assert!(error.to_string().contains("over the 512 byte cap"), "{}", error);
+    assert!(!handle.path.exists());
+}
+
+#[tokio::test]
+async fn test_recording_needs_a_booted_device() {
+    let dir = tempfile::tempdir().unwrap();
+    let command = FakeCapture::new(16);
+
+    let error = capture(dir.path(), &command)
+        .start_recording(&simulator(), &StartRecordingRequest { device: SHUTDOWN.to_string(), max_seconds: None })
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("boot it before recording"), "{}", error);
+    assert!(command.started.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_log_capture_is_truncated_at_the_size_cap() {
+    let dir = tempfile::tempdir().unwrap();
+    let limits = CaptureLimits { max_log_bytes: log_output().len() - 10, ..Default::default() };
+    let capture = capture(dir.path(), &FakeCapture::new(16)).with_limits(limits);
+    let request = LogCaptureRequest {
+        device: BOOTED.to_string(),
+        subsystem: SUBSYSTEM.to_string(),
+        categories: Vec::new(),
+        min_level: LogLevel::Debug,
+    };
+
+    let handle = capture.start_log_capture(&simulator(), &request).await.unwrap();
+    let logs = capture.stop_log_capture(&handle.capture_id).await.unwrap();
+
+    assert!(logs.truncated);
+    // The cut off fault entry is not JSON any more
+    assert_eq!(logs.entries.len(), 3);
+    let written: Vec<LogEntry> = serde_json::from_slice(&std::fs::read(&logs.path).unwrap()).unwrap();
+    assert_eq!(written, logs.entries);
+}
+
+#[tokio::test]
+async fn test_session_without_failures_leaves_nothing_behind() {
+    let dir = tempfile::tempdir().unwrap();
+    let capture = capture(dir.path(), &FakeCapture::new(16));
+
+    let session = capture.start_session(&simulator(), BOOTED, SUBSYSTEM).await;
+    let evidence = capture.finish_session(session, Vec::new()).await;
+
+    assert!(evidence.is_none());
+    assert!(files_in(dir.path()).is_empty());
+}
+
+#[tokio::test]
+async fn test_session_with_failures_keeps_recording_and_logs() {
+    let dir = tempfile::tempdir().unwrap();
+    let capture = capture(dir.path(), &FakeCapture::new(16));
+    let failed = vec!["Performance: MemoryMb of 48 misses its threshold of 10".to_string()];
+
+    let session = capture.start_session(&simulator(), BOOTED, SUBSYSTEM).await;
+    let evidence = capture.finish_session(session, failed.clone()).await.unwrap();
+
+    assert_eq!(evidence.failed_checks, failed);
+    assert!(evidence.capture_errors.is_empty(), "{:?}", evidence.capture_errors);
+    assert!(evidence.recording.unwrap().path.exists());
+    let logs = evidence.logs.unwrap();
+    assert!(logs.path.exists());
+    assert_eq!(logs.predicate, "subsystem == \"com.axiom.tasks\"");
+    assert_eq!(logs.entries.len(), 3);
+}
+
+#[tokio::test]
+async fn test_session_on_a_shut_down_device_notes_why_nothing_was_captured() {
+    let dir = tempfile::tempdir().unwrap();
+    let capture = capture(dir.path(), &FakeCapture::new(16));
+
+    let session = capture.start_session(&simulator(), SHUTDOWN, SUBSYSTEM).await;
+    let evidence = capture.finish_session(session, vec!["App run: crashed".to_string()]).await.unwrap();
+
+    assert!(evidence.recording.is_none());
+    assert!(evidence.logs.is_none());
+    assert_eq!(evidence.capture_errors.len(), 2);
+    assert!(evidence.capture_errors[0].starts_with("Recording: "), "{:?}", evidence.capture_errors);
+}
@@ -0,0 +1,478 @@
+use async_trait::async_trait;
+use axiom_applications_observability::screenshot_matrix_engine::*;
+use axiom_applications_observability::screenshot_store::{ScreenshotStorageConfig, ScreenshotStore};
+use axiom_applications_observability::simulator::{SimulatorLocale, StateReset, StateResetOptions};
+use axiom_applications_observability::simulator_pool::*;
+use axiom_applications_observability::types::{ScreenSize, ScreenshotConfiguration};
+use axiom_applications_observability::advanced_visual_intelligence::{AccessibilityElement, ElementFrame};
+use axiom_applications_observability::ui_script::{UiAction, UiDriver, UiScript, UiScriptRunner};
+use axiom_applications_observability::{AxiomMCPError, Result};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Backend that tracks how many captures overlap and which devices are up
+struct MockBackend {
+    free_memory_mb: u64,
+    capture_delay: Duration,
+    booted: Mutex<Vec<PooledDevice>>,
+    boots: AtomicUsize,
+    shutdowns: Mutex<Vec<String>>,
+    /// Devices reset, in order
+    resets: Mutex<Vec<String>>,
+    /// Devices captured on, in order
+    captured_on: Mutex<Vec<String>>,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    /// Device type whose captures fail
+    failing: Option<String>,
+}
+
+impl MockBackend {
+    fn new(free_memory_mb: u64) -> Self {
+        Self {
+            free_memory_mb,
+            capture_delay: Duration::from_millis(20),
+            booted: Mutex::new(Vec::new()),
+            boots: AtomicUsize::new(0),
+            shutdowns: Mutex::new(Vec::new()),
+            resets: Mutex::new(Vec::new()),
+            captured_on: Mutex::new(Vec::new()),
+            in_flight: AtomicUsize::new(0),
+            max_in_flight: AtomicUsize::new(0),
+            failing: None,
+        }
+    }
+
+    fn booted(&self) -> Vec<PooledDevice> {
+        self.booted.lock().unwrap().clone()
+    }
+
+    fn resets(&self) -> Vec<String> {
+        self.resets.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl SimulatorBackend for MockBackend {
+    async fn boot(&self, configuration: &ScreenshotConfiguration, exclude: &[String]) -> Result<PooledDevice> {
+        let number = self.boots.fetch_add(1, Ordering::SeqCst);
+        let device = PooledDevice {
+            udid: format!("{}-{}", configuration.device_type, number),
+            device_type: configuration.device_type.clone(),
+            runtime: configuration.runtime.clone(),
+        };
+        assert!(!exclude.contains(&device.udid));
+        self.booted.lock().unwrap().push(device.clone());
+        Ok(device)
+    }
+
+    async fn capture(&self, device: &PooledDevice, configuration: &ScreenshotConfiguration) -> Result<Vec<u8>> {
+        assert_eq!(device.device_type, configuration.device_type);
+        assert!(self.booted().contains(device), "{} is not booted", device.udid);
+        if self.failing.as_deref() == Some(device.device_type.as_str()) {
+            return Err(AxiomMCPError::simulator("simulator_pool", "capture", format!("{} crashed", device.udid)));
+        }
+
+        self.captured_on.lock().unwrap().push(device.udid.clone());
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        tokio::time::sleep(self.capture_delay).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(format!("{}|{}|{}", configuration.device_type, configuration.orientation, configuration.color_scheme).into_bytes())
+    }
+
+    async fn shutdown(&self, device: &PooledDevice) -> Result<()> {
+        self.booted.lock().unwrap().retain(|booted| booted.udid != device.udid);
+        self.shutdowns.lock().unwrap().push(device.udid.clone());
+        Ok(())
+    }
+
+    async fn reset_state(&self, device: &PooledDevice, options: &StateResetOptions) -> Result<Vec<StateReset>> {
+        assert!(self.booted().contains(device), "{} is not booted", device.udid);
+        assert!(!self.captured_on.lock().unwrap().contains(&device.udid), "{} captured before its reset", device.udid);
+        self.resets.lock().unwrap().push(device.udid.clone());
+        Ok(if options.keychain { vec![StateReset::Keychain] } else { vec![] })
+    }
+
+    async fn free_memory_mb(&self) -> Result<u64> {
+        Ok(self.free_memory_mb)
+    }
+}
+
+fn configuration(device_type: &str, orientation: &str, color_scheme: &str) -> ScreenshotConfiguration {
+    ScreenshotConfiguration {
+        device_type: device_type.to_string(),
+        screen_size: ScreenSize { width: 393.0, height: 852.0 },
+        orientation: orientation.to_string(),
+        scale: 3.0,
+        color_scheme: color_scheme.to_string(),
+        capture_mode: "standard".to_string(),
+        status_bar: None,
+        runtime: None,
+        window: Default::default(),
+    }
+}
+
+fn matrix(device_types: &[&str]) -> Vec<ScreenshotConfiguration> {
+    let mut configurations = Vec::new();
+    for device_type in device_types {
+        for orientation in ["portrait", "landscape"] {
+            for color_scheme in ["light", "dark"] {
+                configurations.push(configuration(device_type, orientation, color_scheme));
+            }
+        }
+    }
+    configurations
+}
+
+fn pool(backend: &Arc<MockBackend>, config: SimulatorPoolConfig) -> SimulatorPool {
+    SimulatorPool::new(backend.clone(), config)
+}
+
+const PLENTY_MB: u64 = 32 * 1024;
+
+#[tokio::test]
+async fn test_captures_run_in_parallel_up_to_max_devices() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig::default());
+
+    let run = pool.capture_matrix(matrix(&["iPhone 15 Pro", "iPhone 15", "iPad Pro", "iPhone SE"])).await.unwrap();
+
+    assert_eq!(run.captures.len(), 16);
+    assert_eq!(run.stats.parallelism, DEFAULT_MAX_DEVICES);
+    assert!(backend.max_in_flight.load(Ordering::SeqCst) > 1);
+    assert!(backend.max_in_flight.load(Ordering::SeqCst) <= DEFAULT_MAX_DEVICES);
+    assert!(run.stats.guardrail.is_none());
+}
+
+#[tokio::test]
+async fn test_booted_devices_never_exceed_max_devices() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig { max_devices: 2, ..Default::default() });
+
+    pool.capture_matrix(matrix(&["iPhone 15 Pro", "iPhone 15", "iPad Pro", "iPhone SE"])).await.unwrap();
+
+    assert_eq!(backend.max_in_flight.load(Ordering::SeqCst), 2);
+    assert!(backend.booted().len() <= 2);
+    // Four device types through two slots means swapping devices out
+    assert_eq!(backend.boots.load(Ordering::SeqCst), 4);
+    assert_eq!(backend.shutdowns.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_results_keep_matrix_order_and_name_their_simulator() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig::default());
+    let configurations = matrix(&["iPhone 15 Pro", "iPhone 15", "iPad Pro"]);
+
+    let run = pool.capture_matrix(configurations.clone()).await.unwrap();
+
+    for (capture, expected) in run.captures.iter().zip(&configurations) {
+        assert_eq!(capture.configuration.device_type, expected.device_type);
+        assert_eq!(capture.configuration.orientation, expected.orientation);
+        assert_eq!(capture.configuration.color_scheme, expected.color_scheme);
+        assert!(capture.udid.starts_with(&expected.device_type), "{} captured {}", capture.udid, expected.device_type);
+        assert_eq!(
+            String::from_utf8(capture.image_data.clone()).unwrap(),
+            format!("{}|{}|{}", expected.device_type, expected.orientation, expected.color_scheme)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_pool_with_a_store_keeps_only_references() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(ScreenshotStore::new(ScreenshotStorageConfig { dir: dir.path().to_path_buf(), ..Default::default() }));
+    let pool = pool(&backend, SimulatorPoolConfig::default()).with_store(store.clone());
+
+    let run = pool.capture_matrix(matrix(&["iPhone 15"])).await.unwrap();
+
+    assert_eq!(run.captures.len(), 4);
+    for capture in &run.captures {
+        assert!(capture.image_data.is_empty());
+        let image = capture.image.as_ref().unwrap();
+        assert_eq!(
+            String::from_utf8(store.read(image).await.unwrap()).unwrap(),
+            format!("iPhone 15|{}|{}", capture.configuration.orientation, capture.configuration.color_scheme)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_each_device_type_is_booted_once_when_the_pool_has_room() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig::default());
+
+    let run = pool.capture_matrix(matrix(&["iPhone 15 Pro", "iPhone 15", "iPad Pro"])).await.unwrap();
+
+    assert_eq!(run.stats.booted_devices, 3);
+    assert_eq!(run.stats.reused_devices, 0);
+    let mut udids: Vec<&str> = run.captures.iter().map(|capture| capture.udid.as_str()).collect();
+    udids.sort();
+    udids.dedup();
+    assert_eq!(udids.len(), 3);
+}
+
+#[tokio::test]
+async fn test_keep_warm_reuses_devices_on_the_next_run() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig::default());
+    let configurations = matrix(&["iPhone 15 Pro", "iPhone 15"]);
+
+    let first = pool.capture_matrix(configurations.clone()).await.unwrap();
+    assert_eq!(first.stats.booted_devices, 2);
+    assert_eq!(pool.warm_devices().len(), 2);
+
+    let second = pool.capture_matrix(configurations).await.unwrap();
+    assert_eq!(second.stats.booted_devices, 0);
+    assert_eq!(second.stats.reused_devices, 2);
+    assert_eq!(backend.boots.load(Ordering::SeqCst), 2);
+    assert!(backend.shutdowns.lock().unwrap().is_empty());
+    assert_eq!(
+        first.captures.iter().map(|capture| &capture.udid).collect::<Vec<_>>(),
+        second.captures.iter().map(|capture| &capture.udid).collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn test_tear_down_shuts_every_device_down_after_the_run() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig { policy: PoolPolicy::TearDown, ..Default::default() });
+    let configurations = matrix(&["iPhone 15 Pro", "iPhone 15"]);
+
+    let first = pool.capture_matrix(configurations.clone()).await.unwrap();
+    assert_eq!(first.stats.shut_down_devices, 2);
+    assert!(backend.booted().is_empty());
+    assert!(pool.warm_devices().is_empty());
+
+    let second = pool.capture_matrix(configurations).await.unwrap();
+    assert_eq!(second.stats.booted_devices, 2);
+    assert_eq!(second.stats.reused_devices, 0);
+}
+
+#[tokio::test]
+async fn test_low_memory_falls_back_to_one_simulator() {
+    let backend = Arc::new(MockBackend::new(2048));
+    let pool = pool(&backend, SimulatorPoolConfig::default());
+
+    let run = pool.capture_matrix(matrix(&["iPhone 15 Pro", "iPhone 15", "iPad Pro"])).await.unwrap();
+
+    assert_eq!(run.stats.parallelism, 1);
+    assert_eq!(backend.max_in_flight.load(Ordering::SeqCst), 1);
+    assert!(run.stats.guardrail.unwrap().contains("2048 MB free"));
+    assert_eq!(run.captures.len(), 12);
+}
+
+#[tokio::test]
+async fn test_memory_budget_caps_parallelism() {
+    let backend = Arc::new(MockBackend::new(5000));
+    let pool = pool(&backend, SimulatorPoolConfig {
+        memory_per_device_mb: 2048,
+        ..Default::default()
+    });
+
+    let run = pool.capture_matrix(matrix(&["iPhone 15 Pro", "iPhone 15", "iPad Pro"])).await.unwrap();
+
+    assert_eq!(run.stats.parallelism, 2);
+    assert!(backend.max_in_flight.load(Ordering::SeqCst) <= 2);
+    assert!(run.stats.guardrail.is_some());
+}
+
+#[tokio::test]
+async fn test_parallelism_is_limited_by_matrix_size() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig::default());
+
+    let run = pool.capture_matrix(vec![configuration("iPhone 15", "portrait", "light")]).await.unwrap();
+
+    assert_eq!(run.stats.parallelism, 1);
+    assert_eq!(run.stats.booted_devices, 1);
+    assert!(run.stats.guardrail.is_none());
+}
+
+#[tokio::test]
+async fn test_capture_failure_fails_the_run_and_keeps_devices_tracked() {
+    let mut backend = MockBackend::new(PLENTY_MB);
+    backend.failing = Some("iPad Pro".to_string());
+    let backend = Arc::new(backend);
+    let pool = pool(&backend, SimulatorPoolConfig { policy: PoolPolicy::TearDown, ..Default::default() });
+
+    let error = pool.capture_matrix(matrix(&["iPhone 15", "iPad Pro"])).await.unwrap_err();
+
+    assert!(error.to_string().contains("crashed"));
+    // Torn down even though the run failed
+    assert!(backend.booted().is_empty());
+}
+
+#[tokio::test]
+async fn test_engine_captures_presets_on_the_pool() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = Arc::new(pool(&backend, SimulatorPoolConfig::default()));
+    let controller = Arc::new(
+        axiom_applications_observability::simulator::SimulatorController::with_simctl(Arc::new(
+            axiom_applications_observability::simulator::XcrunSimctl,
+        )),
+    );
+    let engine = ScreenshotMatrixEngine::new(controller).await.unwrap().with_pool(pool);
+
+    let matrix = engine.generate_screenshot_matrix(ScreenshotMatrixSpec {
+        matrix_name: "tasks".to_string(),
+        configuration_type: ConfigurationType::Preset("all_devices".to_string()),
+        app_states: None,
+        capture_options: CaptureOptions {
+            include_system_ui: false,
+            capture_delay_ms: 0,
+            quality: ImageQuality::High,
+        },
+        clean_state: false,
+        status_bar: None,
+        pre_capture_script: None,
+    }).await.unwrap();
+
+    assert_eq!(matrix.screenshots.len(), 12);
+    assert_eq!(matrix.analysis.total_screenshots, 12);
+    for screenshot in &matrix.screenshots {
+        let udid = screenshot.metadata.device_info.udid.as_deref().unwrap();
+        assert!(udid.starts_with(&screenshot.configuration.device_type));
+    }
+    assert_eq!(engine.last_run().unwrap().booted_devices, 3);
+    assert!(backend.resets().is_empty());
+}
+
+#[tokio::test]
+async fn test_clean_runs_reset_each_simulator_once_before_it_captures() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig { max_devices: 2, ..Default::default() });
+    let options = StateResetOptions::clean_slate();
+
+    let run = pool.capture_clean_matrix(matrix(&["iPhone 15 Pro", "iPhone 15"]), &options).await.unwrap();
+    assert_eq!(run.stats.reset_devices, 2);
+    let mut resets = backend.resets();
+    resets.sort();
+    let mut booted: Vec<String> = backend.booted().into_iter().map(|device| device.udid).collect();
+    booted.sort();
+    assert_eq!(resets, booted);
+
+    // Kept warm, and reset again by the next clean run
+    backend.captured_on.lock().unwrap().clear();
+    let run = pool.capture_clean_matrix(matrix(&["iPhone 15 Pro"]), &options).await.unwrap();
+    assert_eq!(run.stats.reused_devices, 1);
+    assert_eq!(run.stats.reset_devices, 1);
+    assert_eq!(backend.resets().len(), 3);
+}
+
+#[tokio::test]
+async fn test_engine_resets_simulators_when_asked_for_a_clean_state() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = Arc::new(pool(&backend, SimulatorPoolConfig::default()));
+    let controller = Arc::new(
+        axiom_applications_observability::simulator::SimulatorController::with_simctl(Arc::new(
+            axiom_applications_observability::simulator::XcrunSimctl,
+        )),
+    );
+    let engine = ScreenshotMatrixEngine::new(controller).await.unwrap().with_pool(pool);
+
+    engine.generate_screenshot_matrix(ScreenshotMatrixSpec {
+        matrix_name: "tasks".to_string(),
+        configuration_type: ConfigurationType::Preset("mobile_only".to_string()),
+        app_states: None,
+        capture_options: CaptureOptions {
+            include_system_ui: false,
+            capture_delay_ms: 0,
+            quality: ImageQuality::High,
+        },
+        clean_state: true,
+        status_bar: None,
+        pre_capture_script: None,
+    }).await.unwrap();
+
+    assert_eq!(backend.resets().len(), 2);
+    assert_eq!(engine.last_run().unwrap().reset_devices, 2);
+}
+
+/// Drives the app on the mock simulators, failing every tap of `failing`
+struct ScriptDriver {
+    backend: Arc<MockBackend>,
+    performed: Mutex<Vec<String>>,
+    failing: Option<String>,
+}
+
+#[async_trait]
+impl UiDriver for ScriptDriver {
+    async fn perform(&self, udid: &str, action: &UiAction) -> Result<()> {
+        assert!(!self.backend.captured_on.lock().unwrap().contains(&udid.to_string()), "{} captured before its script", udid);
+        if matches!(action, UiAction::Tap { element } if Some(element) == self.failing.as_ref()) {
+            return Err(AxiomMCPError::validation("ui_script", "tap", "No such element"));
+        }
+        self.performed.lock().unwrap().push(udid.to_string());
+        Ok(())
+    }
+
+    async fn element_tree(&self, _udid: &str) -> Result<AccessibilityElement> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "identifier": "root",
+            "frame": ElementFrame { x: 0.0, y: 0.0, width: 393.0, height: 852.0 },
+        }))?)
+    }
+
+    async fn screenshot(&self, _udid: &str) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    async fn set_locale(&self, _udid: &str, _locale: &SimulatorLocale) -> Result<()> {
+        Ok(())
+    }
+
+    async fn set_pasteboard(&self, _udid: &str, _text: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_scripted_runs_drive_each_simulator_once_after_its_reset() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig { max_devices: 2, ..Default::default() });
+    let driver = Arc::new(ScriptDriver { backend: backend.clone(), performed: Mutex::new(Vec::new()), failing: None });
+    let runner = UiScriptRunner::new(driver.clone());
+    let script = UiScript::parse("tap settingsTab\nswipe up").unwrap();
+
+    let run = pool
+        .capture_scripted_matrix(matrix(&["iPhone 15 Pro", "iPhone 15"]), Some(&StateResetOptions::clean_slate()), &runner, &script)
+        .await
+        .unwrap();
+
+    assert_eq!(run.captures.len(), 8);
+    assert_eq!((run.stats.reset_devices, run.stats.scripted_devices), (2, 2));
+    let mut performed = driver.performed.lock().unwrap().clone();
+    performed.sort();
+    let mut booted: Vec<String> = backend.booted().into_iter().flat_map(|device| [device.udid.clone(), device.udid]).collect();
+    booted.sort();
+    assert_eq!(performed, booted);
+}
+
+#[tokio::test]
+async fn test_failed_pre_capture_script_stops_the_run() {
+    let backend = Arc::new(MockBackend::new(PLENTY_MB));
+    let pool = pool(&backend, SimulatorPoolConfig::default());
+    let driver = Arc::new(ScriptDriver {
+        backend: backend.clone(),
+        performed: Mutex::new(Vec::new()),
+        failing: Some("settingsTab".to_string()),
+    });
+    let script = UiScript::parse("swipe up\ntap settingsTab").unwrap();
+
+    let error = pool.capture_scripted_matrix(matrix(&["iPhone 15"]), None, &UiScriptRunner::new(driver), &script).await.unwrap_err();
+
+    assert!(matches!(error, AxiomMCPError::Validation(_)), "{:?}", error);
+    assert!(error.to_string().contains("step 2 (line 2, `tap settingsTab`) failed: No such element"), "{}", error);
+    assert!(backend.captured_on.lock().unwrap().is_empty());
+    assert!(backend.resets().is_empty());
+}
+
+#[test]
+fn test_unknown_preset_is_rejected() {
+    let error = matrix_configurations(&ConfigurationType::Preset("watch_only".to_string())).unwrap_err();
+    assert!(error.to_string().contains("watch_only"));
+}
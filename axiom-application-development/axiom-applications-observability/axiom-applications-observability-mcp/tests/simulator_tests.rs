@@ -0,0 +1,655 @@
+use async_trait::async_trait;
+use axiom_applications_observability::device_matrix::WindowConfiguration;
+use axiom_applications_observability::simulator::*;
+use axiom_applications_observability::{AxiomMCPError, Result};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const IPHONE_15_17: &str = "A1B2C3D4-0000-0000-0000-000000000001";
+const IPHONE_15_PRO_17: &str = "A1B2C3D4-0000-0000-0000-000000000002";
+const IPHONE_15_18: &str = "A1B2C3D4-0000-0000-0000-000000000003";
+const IPAD_16: &str = "A1B2C3D4-0000-0000-0000-000000000004";
+const BUNDLE_ID: &str = "com.axiom.tasks";
+
+/// Scripted simctl: keeps device states, and a booting device reports
+/// `Booted` after `boot_polls` more listings
+struct FakeSimctl {
+    devices: Mutex<serde_json::Value>,
+    boot_polls: usize,
+    remaining_polls: Mutex<usize>,
+    commands: Mutex<Vec<String>>,
+    launch_env: Mutex<Vec<(String, String)>>,
+    /// stderr the next install, launch or screenshot fails with
+    failure: Mutex<Option<String>>,
+    /// stderr the next status bar override fails with
+    override_failure: Mutex<Option<String>>,
+    /// Bytes `pbcopy` was last given
+    pasteboard: Mutex<Vec<u8>>,
+    /// `pbcopy` replaces anything outside ASCII, as outside a UTF-8 locale
+    ascii_pasteboard: Mutex<bool>,
+}
+
+impl FakeSimctl {
+    fn new(boot_polls: usize) -> Arc<Self> {
+        Arc::new(Self {
+            devices: Mutex::new(json!({
+                "devices": {
+                    "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [
+                        device(IPHONE_15_17, "iPhone 15", "Shutdown", true),
+                        device(IPHONE_15_PRO_17, "iPhone 15 Pro", "Booted", true),
+                    ],
+                    "com.apple.CoreSimulator.SimRuntime.iOS-18-0": [
+                        device(IPHONE_15_18, "iPhone 15", "Shutdown", true),
+                    ],
+                    "com.apple.CoreSimulator.SimRuntime.iOS-16-4": [
+                        device(IPAD_16, "iPad Air (5th generation)", "Shutdown", false),
+                    ],
+                }
+            })),
+            boot_polls,
+            remaining_polls: Mutex::new(0),
+            commands: Mutex::new(Vec::new()),
+            launch_env: Mutex::new(Vec::new()),
+            failure: Mutex::new(None),
+            override_failure: Mutex::new(None),
+            pasteboard: Mutex::new(Vec::new()),
+            ascii_pasteboard: Mutex::new(false),
+        })
+    }
+
+    fn fail_with(&self, stderr: &str) {
+        *self.failure.lock().unwrap() = Some(stderr.to_string());
+    }
+
+    fn commands(&self) -> Vec<String> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    fn set_state(&self, udid: &str, state: &str) {
+        let mut devices = self.devices.lock().unwrap();
+        for runtime in devices["devices"].as_object_mut().unwrap().values_mut() {
+            for device in runtime.as_array_mut().unwrap() {
+                if device["udid"] == udid {
+                    device["state"] = json!(state);
+                }
+            }
+        }
+    }
+}
+
+fn device(udid: &str, name: &str, state: &str, available: bool) -> serde_json::Value {
+    let mut device = json!({
+        "udid": udid,
+        "name": name,
+        "state": state,
+        "isAvailable": available,
+        "deviceTypeIdentifier": "com.apple.CoreSimulator.SimDeviceType.iPhone-15",
+    });
+    if !available {
+        device["availabilityError"] = json!("runtime profile not found");
+    }
+    device
+}
+
+#[async_trait]
+impl Simctl for FakeSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        self.commands.lock().unwrap().push(args.join(" "));
+        match args {
+            ["list", "devices", "--json"] => {
+                let mut remaining = self.remaining_polls.lock().unwrap();
+                if *remaining > 0 {
+                    *remaining -= 1;
+                    if *remaining == 0 {
+                        let booting: Vec<String> = parse_device_list(&self.devices.lock().unwrap().to_string())?
+                            .into_iter()
+                            .filter(|device| device.state == DeviceState::Booting)
+                            .map(|device| device.udid)
+                            .collect();
+                        for udid in booting {
+                            self.set_state(&udid, "Booted");
+                        }
+                    }
+                }
+                Ok(self.devices.lock().unwrap().to_string())
+            }
+            ["boot", udid] => {
+                self.set_state(udid, "Booting");
+                *self.remaining_polls.lock().unwrap() = self.boot_polls;
+                Ok(String::new())
+            }
+            ["shutdown", udid] => {
+                self.set_state(udid, "Shutdown");
+                Ok(String::new())
+            }
+            ["erase", _] => Ok(String::new()),
+            ["ui", _, "appearance"] => Ok("light\n".to_string()),
+            ["status_bar", _, "override", ..] => match self.override_failure.lock().unwrap().take() {
+                Some(stderr) => Err(AxiomMCPError::simulator("simctl", "run", format!("simctl {} failed: {}", args.join(" "), stderr))),
+                None => Ok(String::new()),
+            },
+            ["uninstall", ..] | ["privacy", ..] | ["status_bar", ..] | ["keychain", ..] | ["ui", ..] | ["spawn", _, "launchctl", ..] | ["spawn", _, "defaults", ..] => Ok(String::new()),
+            ["install", ..] | ["launch", ..] => {
+                if let Some(stderr) = self.failure.lock().unwrap().take() {
+                    return Err(AxiomMCPError::simulator("simctl", "run", format!("simctl {} failed: {}", args.join(" "), stderr)));
+                }
+                match args {
+                    ["launch", ..] if args.contains(&"--console-pty") => {
+                        Ok(format!("{}: 4242\nApp started\nLoaded 3 tasks\n", BUNDLE_ID))
+                    }
+                    ["launch", ..] => Ok(format!("{}: 4242\n", BUNDLE_ID)),
+                    _ => Ok(String::new()),
+                }
+            }
+            ["io", _, "screenshot", path] => {
+                if let Some(stderr) = self.failure.lock().unwrap().take() {
+                    return Err(AxiomMCPError::simulator("simctl", "run", format!("simctl {} failed: {}", args.join(" "), stderr)));
+                }
+                std::fs::write(path, b"png")?;
+                Ok(String::new())
+            }
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.launch_env.lock().unwrap().extend(env.iter().cloned());
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], input: &[u8]) -> Result<String> {
+        self.commands.lock().unwrap().push(args.join(" "));
+        match args {
+            ["pbcopy", _] => {
+                let text = std::str::from_utf8(input).expect("pbcopy is given UTF-8");
+                *self.pasteboard.lock().unwrap() = if *self.ascii_pasteboard.lock().unwrap() {
+                    text.chars().map(|c| if c.is_ascii() { c } else { '?' }).collect::<String>().into_bytes()
+                } else {
+                    input.to_vec()
+                };
+                Ok(String::new())
+            }
+            ["pbpaste", _] => Ok(String::from_utf8_lossy(&self.pasteboard.lock().unwrap()).into_owned()),
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {} with input", args.join(" ")))),
+        }
+    }
+}
+
+fn controller(simctl: &Arc<FakeSimctl>) -> SimulatorController {
+    SimulatorController::with_simctl(simctl.clone()).with_poll_interval(Duration::from_millis(5))
+}
+
+#[tokio::test]
+async fn test_list_parses_runtime_and_availability() {
+    let simctl = FakeSimctl::new(0);
+    let devices = controller(&simctl).list_devices().await.unwrap();
+
+    assert_eq!(devices.len(), 4);
+    let ipad = devices.iter().find(|d| d.udid == IPAD_16).unwrap();
+    assert_eq!(ipad.runtime, "iOS 16.4");
+    assert_eq!(ipad.runtime_identifier, "com.apple.CoreSimulator.SimRuntime.iOS-16-4");
+    assert!(!ipad.is_available);
+    assert_eq!(ipad.availability_error.as_deref(), Some("runtime profile not found"));
+    let pro = devices.iter().find(|d| d.udid == IPHONE_15_PRO_17).unwrap();
+    assert_eq!(pro.state, DeviceState::Booted);
+}
+
+#[tokio::test]
+async fn test_selection_by_udid_and_fuzzy_name() {
+    let simctl = FakeSimctl::new(0);
+    let devices = controller(&simctl).list_devices().await.unwrap();
+
+    assert_eq!(select_device(&devices, &IPHONE_15_18.to_lowercase()).unwrap().udid, IPHONE_15_18);
+    assert_eq!(select_device(&devices, "iphone 15 pro").unwrap().udid, IPHONE_15_PRO_17);
+    assert_eq!(select_device(&devices, "iPhone 15 (iOS 18.0)").unwrap().udid, IPHONE_15_18);
+    assert_eq!(select_device(&devices, "iphone 15 17").unwrap().udid, IPHONE_15_17);
+}
+
+#[tokio::test]
+async fn test_ambiguous_and_unmatched_selectors_are_rejected() {
+    let simctl = FakeSimctl::new(0);
+    let devices = controller(&simctl).list_devices().await.unwrap();
+
+    // Same name on two runtimes
+    let error = select_device(&devices, "iPhone 15").unwrap_err().to_string();
+    assert!(error.contains("matches 2 simulators"), "{}", error);
+    assert!(error.contains(IPHONE_15_17) && error.contains(IPHONE_15_18));
+
+    // Unavailable devices are never picked by name
+    assert!(select_device(&devices, "iPad Air").is_err());
+    assert!(select_device(&devices, "Apple Watch").is_err());
+    assert!(select_device(&devices, "  ").is_err());
+}
+
+#[tokio::test]
+async fn test_boot_waits_until_booted() {
+    let simctl = FakeSimctl::new(3);
+    let result = controller(&simctl).boot("iPhone 15 iOS 17.2").await.unwrap();
+
+    assert!(result.changed);
+    assert_eq!(result.previous_state, DeviceState::Shutdown);
+    assert_eq!(result.device.state, DeviceState::Booted);
+    assert_eq!(simctl.commands().iter().filter(|c| c.starts_with("list")).count(), 4);
+    assert!(simctl.commands().contains(&format!("boot {}", IPHONE_15_17)));
+}
+
+#[tokio::test]
+async fn test_boot_times_out() {
+    let simctl = FakeSimctl::new(usize::MAX);
+    let result = controller(&simctl)
+        .with_boot_timeout(Duration::from_millis(50))
+        .boot(IPHONE_15_18)
+        .await;
+
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("did not finish booting"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_already_booted_device_is_left_alone() {
+    let simctl = FakeSimctl::new(0);
+    let result = controller(&simctl).boot(IPHONE_15_PRO_17).await.unwrap();
+
+    assert!(!result.changed);
+    assert!(!simctl.commands().iter().any(|c| c.starts_with("boot")));
+}
+
+#[tokio::test]
+async fn test_erase_requires_shutdown() {
+    let simctl = FakeSimctl::new(0);
+    let controller = controller(&simctl);
+
+    assert!(controller.erase("iPhone 15 Pro").await.is_err());
+    assert!(!simctl.commands().iter().any(|c| c.starts_with("erase")));
+
+    let shutdown = controller.shutdown("iPhone 15 Pro").await.unwrap();
+    assert_eq!(shutdown.previous_state, DeviceState::Booted);
+    assert_eq!(shutdown.device.state, DeviceState::Shutdown);
+
+    let erase = controller.erase("iPhone 15 Pro").await.unwrap();
+    assert!(erase.changed);
+    assert!(simctl.commands().contains(&format!("erase {}", IPHONE_15_PRO_17)));
+}
+
+fn app_bundle(directory: &str) -> (tempfile::TempDir, PathBuf) {
+    let build = tempfile::tempdir().unwrap();
+    let app = build.path().join(directory).join("Tasks.app");
+    std::fs::create_dir_all(&app).unwrap();
+    (build, app)
+}
+
+fn launch_request() -> LaunchAppRequest {
+    LaunchAppRequest {
+        device: "iPhone 15 Pro".to_string(),
+        bundle_id: BUNDLE_ID.to_string(),
+        arguments: vec!["-UITesting".to_string(), "YES".to_string()],
+        environment: BTreeMap::from([("API_URL".to_string(), "http://localhost:5000".to_string())]),
+        console_seconds: None,
+        keep_running: false,
+    }
+}
+
+#[test]
+fn test_launch_arguments() {
+    let mut request = launch_request();
+    assert_eq!(
+        launch_args(IPHONE_15_PRO_17, &request, false),
+        vec!["launch", "--terminate-running-process", IPHONE_15_PRO_17, BUNDLE_ID, "-UITesting", "YES"]
+    );
+
+    request.arguments.clear();
+    assert_eq!(
+        launch_args(IPHONE_15_PRO_17, &request, true),
+        vec!["launch", "--terminate-running-process", "--console-pty", IPHONE_15_PRO_17, BUNDLE_ID]
+    );
+
+    request.keep_running = true;
+    assert_eq!(
+        launch_args(IPHONE_15_PRO_17, &request, false),
+        vec!["launch", IPHONE_15_PRO_17, BUNDLE_ID]
+    );
+}
+
+#[tokio::test]
+async fn test_install_and_launch() {
+    let simctl = FakeSimctl::new(0);
+    let controller = controller(&simctl);
+    let (_build, app) = app_bundle("Debug-iphonesimulator");
+
+    let installed = controller.install_app(&InstallAppRequest {
+        device: IPHONE_15_PRO_17.to_string(),
+        app_path: app.clone(),
+    }).await.unwrap();
+    assert_eq!(installed.device.udid, IPHONE_15_PRO_17);
+    assert!(simctl.commands().contains(&format!("install {} {}", IPHONE_15_PRO_17, app.display())));
+
+    let launched = controller.launch_app(&launch_request()).await.unwrap();
+    assert_eq!(launched.pid, 4242);
+    assert_eq!(launched.output, None);
+    assert_eq!(
+        simctl.launch_env.lock().unwrap().clone(),
+        vec![("API_URL".to_string(), "http://localhost:5000".to_string())]
+    );
+
+    let captured = controller.launch_app(&LaunchAppRequest {
+        console_seconds: Some(2),
+        ..launch_request()
+    }).await.unwrap();
+    assert_eq!(captured.pid, 4242);
+    assert_eq!(captured.output.as_deref(), Some("App started\nLoaded 3 tasks"));
+}
+
+#[tokio::test]
+async fn test_bundles_that_cannot_run_on_a_simulator_are_rejected_before_simctl() {
+    let simctl = FakeSimctl::new(0);
+    let controller = controller(&simctl);
+    let (_build, device_build) = app_bundle("Debug-iphoneos");
+
+    let error = controller.install_app(&InstallAppRequest {
+        device: IPHONE_15_PRO_17.to_string(),
+        app_path: device_build,
+    }).await.unwrap_err();
+    assert!(error.to_string().contains("built for a device"), "{}", error);
+
+    let error = controller.install_app(&InstallAppRequest {
+        device: IPHONE_15_PRO_17.to_string(),
+        app_path: PathBuf::from("/nonexistent/Tasks.app"),
+    }).await.unwrap_err();
+    assert!(error.to_string().contains("build the app first"), "{}", error);
+
+    assert!(!simctl.commands().iter().any(|c| c.starts_with("install")));
+}
+
+#[tokio::test]
+async fn test_simctl_failures_map_to_actionable_errors() {
+    let simctl = FakeSimctl::new(0);
+    let controller = controller(&simctl);
+    let (_build, app) = app_bundle("Debug-iphonesimulator");
+
+    simctl.fail_with("An error was encountered processing the command (domain=IXErrorDomain, code=13): Failed to find matching arch for input file");
+    let error = controller.install_app(&InstallAppRequest {
+        device: IPHONE_15_PRO_17.to_string(),
+        app_path: app,
+    }).await.unwrap_err();
+    assert!(error.to_string().contains("not built for the simulator's architecture"), "{}", error);
+
+    simctl.fail_with("The request to open \"com.axiom.task\" failed. Application \"com.axiom.task\" is unknown to FrontBoard.");
+    let error = controller.launch_app(&LaunchAppRequest {
+        bundle_id: "com.axiom.task".to_string(),
+        ..launch_request()
+    }).await.unwrap_err();
+    assert!(error.to_string().contains("No app with bundle id com.axiom.task is installed"), "{}", error);
+
+    simctl.fail_with("Unable to lookup in current state: Shutdown");
+    let error = controller.launch_app(&launch_request()).await.unwrap_err();
+    assert!(error.to_string().contains("is shut down"), "{}", error);
+}
+
+#[test]
+fn test_reset_arguments_uninstall_first_and_reinstall_last() {
+    let options = StateResetOptions {
+        app_data: Some(AppDataReset { bundle_id: BUNDLE_ID.to_string(), app_path: Some(PathBuf::from("/build/Tasks.app")) }),
+        privacy: Some(PrivacyReset { services: vec![PrivacyService::Photos, PrivacyService::LocationAlways], bundle_id: Some(BUNDLE_ID.to_string()) }),
+        status_bar: true,
+        keychain: true,
+        appearance: Some("dark".to_string()),
+    };
+
+    let commands: Vec<String> = reset_state_args(IPHONE_15_PRO_17, &options)
+        .unwrap()
+        .into_iter()
+        .map(|(_, args)| args.join(" "))
+        .collect();
+
+    assert_eq!(commands, vec![
+        format!("uninstall {} {}", IPHONE_15_PRO_17, BUNDLE_ID),
+        format!("privacy {} reset photos {}", IPHONE_15_PRO_17, BUNDLE_ID),
+        format!("privacy {} reset location-always {}", IPHONE_15_PRO_17, BUNDLE_ID),
+        format!("status_bar {} clear", IPHONE_15_PRO_17),
+        format!("keychain {} reset", IPHONE_15_PRO_17),
+        format!("ui {} appearance dark", IPHONE_15_PRO_17),
+        format!("install {} /build/Tasks.app", IPHONE_15_PRO_17),
+    ]);
+}
+
+#[test]
+fn test_reset_arguments_cover_only_what_was_asked() {
+    assert!(reset_state_args(IPHONE_15_PRO_17, &StateResetOptions::default()).unwrap().is_empty());
+
+    let steps = reset_state_args(IPHONE_15_PRO_17, &StateResetOptions {
+        privacy: Some(PrivacyReset { services: vec![], bundle_id: None }),
+        ..Default::default()
+    }).unwrap();
+    assert_eq!(steps, vec![(
+        StateReset::Privacy { service: PrivacyService::All, bundle_id: None },
+        vec!["privacy".to_string(), IPHONE_15_PRO_17.to_string(), "reset".to_string(), "all".to_string()],
+    )]);
+
+    let error = reset_state_args(IPHONE_15_PRO_17, &StateResetOptions { appearance: Some("sepia".to_string()), ..Default::default() }).unwrap_err();
+    assert!(error.to_string().contains("Unknown color scheme sepia"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_reset_reports_what_was_reset() {
+    let simctl = FakeSimctl::new(0);
+    let controller = controller(&simctl);
+    let (_build, app) = app_bundle("Debug-iphonesimulator");
+
+    let result = controller.reset_state(&ResetSimulatorStateRequest {
+        device: "iPhone 15 Pro".to_string(),
+        options: StateResetOptions {
+            app_data: Some(AppDataReset { bundle_id: BUNDLE_ID.to_string(), app_path: Some(app.clone()) }),
+            keychain: true,
+            ..Default::default()
+        },
+    }).await.unwrap();
+
+    assert_eq!(result.device.udid, IPHONE_15_PRO_17);
+    assert_eq!(result.reset, vec![
+        StateReset::AppUninstalled { bundle_id: BUNDLE_ID.to_string() },
+        StateReset::Keychain,
+        StateReset::AppReinstalled { bundle_id: BUNDLE_ID.to_string(), app_path: app.clone() },
+    ]);
+    assert_eq!(
+        axiom_applications_observability::ToolResult::SimulatorStateReset(result).summary(),
+        format!("Reset on iPhone 15 Pro: uninstalled {}, keychain, reinstalled {}", BUNDLE_ID, BUNDLE_ID)
+    );
+    assert!(simctl.commands().contains(&format!("install {} {}", IPHONE_15_PRO_17, app.display())));
+}
+
+#[tokio::test]
+async fn test_reset_requires_a_booted_device() {
+    let simctl = FakeSimctl::new(0);
+    let controller = controller(&simctl);
+
+    let error = controller.reset_state(&ResetSimulatorStateRequest {
+        device: IPHONE_15_17.to_string(),
+        options: StateResetOptions::clean_slate(),
+    }).await.unwrap_err();
+
+    assert!(error.to_string().contains("boot it before resetting its state"), "{}", error);
+    assert_eq!(simctl.commands(), vec!["list devices --json"]);
+}
+
+#[test]
+fn test_status_bar_override_sets_the_marketing_status() {
+    let args = status_bar_override_args(IPHONE_15_PRO_17, &StatusBarOverride::marketing()).unwrap();
+
+    assert_eq!(args.join(" "), format!(
+        "status_bar {} override --time 9:41 --batteryLevel 100 --batteryState charged \
+         --cellularMode active --cellularBars 4 --wifiMode active --wifiBars 3",
+        IPHONE_15_PRO_17
+    ));
+}
+
+#[test]
+fn test_status_bar_override_rejects_values_simctl_cannot_show() {
+    let too_many_bars = StatusBarOverride { wifi_bars: Some(4), ..Default::default() };
+    let error = status_bar_override_args(IPHONE_15_PRO_17, &too_many_bars).unwrap_err();
+    assert!(error.to_string().contains("wifi bars must be between 0 and 3, not 4"), "{}", error);
+
+    let error = status_bar_override_args(IPHONE_15_PRO_17, &StatusBarOverride::default()).unwrap_err();
+    assert!(error.to_string().contains("sets nothing"), "{}", error);
+}
+
+fn marketing_overrides() -> CaptureOverrides {
+    CaptureOverrides { appearance: Some("dark".to_string()), status_bar: Some(StatusBarOverride::marketing()), window: None }
+}
+
+/// The commands run, without the device or the screenshot path
+fn capture_commands(simctl: &FakeSimctl) -> Vec<String> {
+    simctl
+        .commands()
+        .into_iter()
+        .map(|command| if command.contains(" screenshot ") { "io screenshot".to_string() } else { command })
+        .map(|command| command.replace(&format!(" {}", IPHONE_15_PRO_17), ""))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_overridden_screenshot_puts_the_device_back() {
+    let simctl = FakeSimctl::new(0);
+
+    let image = controller(&simctl).screenshot_with_overrides(IPHONE_15_PRO_17, &marketing_overrides()).await.unwrap();
+
+    assert_eq!(image, b"png");
+    let commands = capture_commands(&simctl);
+    assert_eq!(commands[..2], ["ui appearance", "ui appearance dark"]);
+    assert!(commands[2].starts_with("status_bar override --time 9:41"), "{:?}", commands);
+    assert_eq!(commands[3..], ["io screenshot", "status_bar clear", "ui appearance light"]);
+}
+
+#[tokio::test]
+async fn test_overrides_are_cleared_when_the_capture_fails() {
+    let simctl = FakeSimctl::new(0);
+    simctl.fail_with("Could not capture the main display");
+    let error = controller(&simctl).screenshot_with_overrides(IPHONE_15_PRO_17, &marketing_overrides()).await.unwrap_err();
+    assert!(error.to_string().contains("Could not capture the main display"), "{}", error);
+    assert_eq!(capture_commands(&simctl)[3..], ["io screenshot", "status_bar clear", "ui appearance light"]);
+
+    // An override that fails part way may still have changed the bar
+    let simctl = FakeSimctl::new(0);
+    *simctl.override_failure.lock().unwrap() = Some("Invalid battery state".to_string());
+    let error = controller(&simctl).screenshot_with_overrides(IPHONE_15_PRO_17, &marketing_overrides()).await.unwrap_err();
+    assert!(error.to_string().contains("Invalid battery state"), "{}", error);
+    let commands = capture_commands(&simctl);
+    assert!(!commands.contains(&"io screenshot".to_string()), "{:?}", commands);
+    assert_eq!(commands[3..], ["status_bar clear", "ui appearance light"]);
+}
+
+#[tokio::test]
+async fn test_ipad_window_is_set_for_the_capture_only() {
+    let simctl = FakeSimctl::new(0);
+    let overrides = CaptureOverrides { window: Some(WindowConfiguration::SplitThird), ..Default::default() };
+
+    controller(&simctl).screenshot_with_overrides(IPHONE_15_PRO_17, &overrides).await.unwrap();
+
+    assert_eq!(capture_commands(&simctl), [
+        "spawn launchctl setenv AXIOM_WINDOW_CONFIGURATION split_third",
+        "io screenshot",
+        "spawn launchctl unsetenv AXIOM_WINDOW_CONFIGURATION",
+    ]);
+
+    let simctl = FakeSimctl::new(0);
+    simctl.fail_with("Could not capture the main display");
+    controller(&simctl).screenshot_with_overrides(IPHONE_15_PRO_17, &overrides).await.unwrap_err();
+    assert_eq!(capture_commands(&simctl).last().unwrap(), "spawn launchctl unsetenv AXIOM_WINDOW_CONFIGURATION");
+}
+
+#[tokio::test]
+async fn test_appearance_already_in_place_is_not_restored() {
+    let simctl = FakeSimctl::new(0);
+    let overrides = CaptureOverrides { appearance: Some("light".to_string()), status_bar: None, window: None };
+
+    controller(&simctl).screenshot_with_overrides(IPHONE_15_PRO_17, &overrides).await.unwrap();
+
+    assert_eq!(capture_commands(&simctl), ["ui appearance", "ui appearance light", "io screenshot"]);
+}
+
+#[test]
+fn test_only_ascii_text_on_a_us_keyboard_is_typed_directly() {
+    let german = SimulatorLocale::parse("de_DE").unwrap();
+    let us = SimulatorLocale::parse("en_US").unwrap();
+    let us_layout = SimulatorLocale::parse("ja_JP").unwrap().with_keyboard("en_US@sw=QWERTY;hw=Automatic");
+
+    let cases = [
+        ("Buy milk", None, InputStrategy::Direct),
+        ("two\nlines, 50% off!", None, InputStrategy::Direct),
+        ("Buy milk", Some(&us), InputStrategy::Direct),
+        ("Buy milk", Some(&us_layout), InputStrategy::Direct),
+        // QWERTZ swaps y and z, so US key presses come out as other letters
+        ("Buy milk", Some(&german), InputStrategy::Pasteboard),
+        ("Crème brûlée", None, InputStrategy::Pasteboard),
+        ("Milch 🥛", Some(&us), InputStrategy::Pasteboard),
+        ("牛乳", None, InputStrategy::Pasteboard),
+        ("tab\there", None, InputStrategy::Pasteboard),
+    ];
+
+    for (text, locale, expected) in cases {
+        assert_eq!(InputStrategy::select(text, locale), expected, "{:?} on {:?}", text, locale);
+    }
+}
+
+#[test]
+fn test_locales_are_written_to_the_global_domain() {
+    let locale = SimulatorLocale::parse("zh_Hans_CN").unwrap();
+    assert_eq!(locale.language(), "zh-Hans");
+    assert_eq!(SimulatorLocale::parse("de_DE").unwrap().language(), "de");
+
+    let args: Vec<String> = locale_args(IPHONE_15_PRO_17, &locale.with_keyboard("zh_Hans-Pinyin@sw=Pinyin10;hw=Automatic"))
+        .into_iter()
+        .map(|args| args.join(" "))
+        .collect();
+    let write = format!("spawn {} defaults write Apple Global Domain", IPHONE_15_PRO_17);
+    assert_eq!(args, vec![
+        format!("{} AppleLanguages -array zh-Hans", write),
+        format!("{} AppleLocale -string zh_Hans_CN", write),
+        format!("{} AppleKeyboards -array zh_Hans-Pinyin@sw=Pinyin10;hw=Automatic", write),
+    ]);
+
+    for invalid in ["", "German", "de-DE", "de_", "deutsch_DE", "de_DE_x_y"] {
+        assert!(SimulatorLocale::parse(invalid).is_err(), "{:?}", invalid);
+    }
+}
+
+#[tokio::test]
+async fn test_set_locale_runs_each_write() {
+    let simctl = FakeSimctl::new(0);
+
+    controller(&simctl).set_locale(IPHONE_15_PRO_17, &SimulatorLocale::parse("de_DE").unwrap()).await.unwrap();
+
+    let commands = simctl.commands();
+    assert_eq!(commands.len(), 3);
+    assert!(commands[2].ends_with("AppleKeyboards -array de_DE"), "{:?}", commands);
+}
+
+#[tokio::test]
+async fn test_pasteboard_round_trips_unicode_text() {
+    let simctl = FakeSimctl::new(0);
+    let controller = controller(&simctl);
+    let text = "Crème brûlée 🍮\n牛乳を買う 👩‍👩‍👧";
+
+    controller.set_pasteboard(IPHONE_15_PRO_17, text).await.unwrap();
+
+    assert_eq!(*simctl.pasteboard.lock().unwrap(), text.as_bytes());
+    assert_eq!(controller.pasteboard(IPHONE_15_PRO_17).await.unwrap(), text);
+    assert_eq!(simctl.commands(), vec![
+        format!("pbcopy {}", IPHONE_15_PRO_17),
+        format!("pbpaste {}", IPHONE_15_PRO_17),
+        format!("pbpaste {}", IPHONE_15_PRO_17),
+    ]);
+}
+
+#[tokio::test]
+async fn test_text_the_pasteboard_mangled_is_an_error() {
+    let simctl = FakeSimctl::new(0);
+    *simctl.ascii_pasteboard.lock().unwrap() = true;
+
+    let error = controller(&simctl).set_pasteboard(IPHONE_15_PRO_17, "Milch 🥛").await.unwrap_err();
+
+    assert!(matches!(error, AxiomMCPError::Simulator(_)), "{:?}", error);
+    assert!(error.to_string().contains("holds \"Milch ?\" instead of \"Milch 🥛\""), "{}", error);
+}
@@ -0,0 +1,305 @@
+use async_trait::async_trait;
+use axiom_applications_observability::code_generation::AxiomCodeGenerator;
+use axiom_applications_observability::swift_validation::*;
+use axiom_applications_observability::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Stands in for `swiftc -parse`, recording the source of every unit it
+/// is handed
+struct FakeSwiftc {
+    errors: Option<Vec<CodeFinding>>,
+    checked: Mutex<Vec<(PathBuf, String)>>,
+}
+
+impl FakeSwiftc {
+    /// Reports `errors` for every unit, or fails to run when `None`
+    fn new(errors: Option<Vec<CodeFinding>>) -> Arc<Self> {
+        Arc::new(Self { errors, checked: Mutex::new(Vec::new()) })
+    }
+
+    fn checked(&self) -> Vec<(PathBuf, String)> {
+        self.checked.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl SwiftSyntaxChecker for FakeSwiftc {
+    async fn check(&self, source: &Path) -> Result<Vec<CodeFinding>> {
+        self.checked.lock().unwrap().push((source.to_path_buf(), std::fs::read_to_string(source)?));
+        self.errors
+            .clone()
+            .ok_or_else(|| AxiomMCPError::toolchain("swift_validation", "run swiftc", "swiftc: command not found"))
+    }
+}
+
+const VALID_VIEW: &str = r#"import SwiftUI
+
+struct ProfileView: View {
+    var body: some View {
+        Text("Hello")
+    }
+}"#;
+
+async fn validate(code: &str) -> ValidationResult {
+    AxiomCodeGenerator::new().await.unwrap().validate_generated_code(code).await.unwrap()
+}
+
+fn locations(findings: &[CodeFinding]) -> Vec<(usize, Option<usize>)> {
+    findings.iter().map(|finding| (finding.line, finding.column)).collect()
+}
+
+#[tokio::test]
+async fn test_missing_closing_brace_fails_validation() {
+    // Has an import, a struct and a View, which is all the old checks wanted
+    let code = VALID_VIEW.trim_end_matches('}');
+
+    let result = validate(code).await;
+
+    assert!(!result.passed);
+    assert_eq!(locations(&result.syntax_errors), vec![(3, Some(26))]);
+    assert_eq!(result.syntax_errors[0].tier, ValidationTier::Structure);
+    assert!(result.syntax_errors[0].message.contains("never closed"));
+    assert_eq!(result.issues, vec!["line 3:26: '{' is never closed"]);
+}
+
+#[tokio::test]
+async fn test_mismatched_delimiters_point_at_the_unclosed_opener() {
+    let code = r#"import SwiftUI
+
+struct ProfileView: View {
+    var body: some View {
+        VStack {
+            Text("Hello"
+        }
+    }
+}
+let total = 1)"#;
+
+    let result = validate(code).await;
+
+    assert!(!result.passed);
+    assert_eq!(locations(&result.syntax_errors), vec![(6, Some(17)), (10, Some(14))]);
+    assert_eq!(result.syntax_errors[0].message, "'(' is not closed before '}' on line 7");
+    assert_eq!(result.syntax_errors[1].message, "Unexpected ')' with nothing open");
+}
+
+#[tokio::test]
+async fn test_delimiters_in_strings_comments_and_interpolations_are_ignored() {
+    let code = r#"import Foundation
+
+// A stray } in a comment
+/* nested /* { */ still a comment ) */
+actor Formatter: AxiomClient {
+    func label(for count: Int) -> String {
+        let suffix = count == 1 ? "}" : "s)"
+        return "Showing \(items(count).map { "\($0)" }.joined()) item\(suffix) {"
+    }
+
+    let banner = """
+        Braces { and quotes " stay inside
+        """
+}"#;
+
+    let result = validate(code).await;
+
+    assert!(result.passed, "{:?}", result.issues);
+    assert!(result.pattern_warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_unterminated_literals_and_comments() {
+    let string = validate("import SwiftUI\n\nlet title = \"Hello\nlet other = 1\n").await;
+    let comment = validate("import SwiftUI\n\n/* never closed\nlet other = 1\n").await;
+
+    assert_eq!(locations(&string.syntax_errors), vec![(3, Some(13))]);
+    assert_eq!(string.syntax_errors[0].message, "Unterminated string literal");
+    assert_eq!(locations(&comment.syntax_errors), vec![(3, Some(1))]);
+    assert_eq!(comment.syntax_errors[0].message, "Unterminated block comment");
+}
+
+#[tokio::test]
+async fn test_unfilled_template_markers_fail_validation() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let data = HashMap::from([
+        ("name".to_string(), "ProfileContext".to_string()),
+        ("imports".to_string(), "import SwiftUI".to_string()),
+        ("base_class".to_string(), "AxiomClientObservingContext".to_string()),
+        ("properties".to_string(), "@Published var name: String = \"\"".to_string()),
+    ]);
+    let code = generator.process_template("context", &data).await.unwrap();
+
+    let result = generator.validate_generated_code(&code).await.unwrap();
+
+    assert!(!result.passed);
+    assert_eq!(locations(&result.syntax_errors), vec![(7, Some(25)), (9, Some(18))]);
+    assert!(result.syntax_errors.iter().all(|e| e.message == "Unresolved template marker {client_binding}"));
+}
+
+#[tokio::test]
+async fn test_other_placeholder_styles_are_markers() {
+    let result = validate("import SwiftUI\n\nlet x = <#value#>\nlet y = \"{{title}}\"\nlet z = items.map{$0}\n").await;
+
+    assert_eq!(locations(&result.syntax_errors), vec![(3, Some(9)), (4, Some(10))]);
+    assert_eq!(result.syntax_errors[0].message, "Unresolved template marker <#value#>");
+    assert_eq!(result.syntax_errors[1].message, "Unresolved template marker {{title}}");
+}
+
+#[tokio::test]
+async fn test_axiom_pattern_violations_are_warnings_with_lines() {
+    let code = r#"import SwiftUI
+
+final class ProfileContext: AxiomClientObservingContext {
+}
+
+class ProfileClient: AxiomClient {
+}
+
+@MainActor
+final class SettingsContext: AxiomClientObservingContext {
+    class func make() -> SettingsContext { SettingsContext() }
+}
+
+@MainActor class InlineContext: AxiomClientObservingContext {}
+actor SettingsClient: AxiomClient {}"#;
+
+    let result = validate(code).await;
+
+    assert!(result.passed);
+    assert!(result.syntax_errors.is_empty());
+    assert_eq!(locations(&result.pattern_warnings), vec![(3, None), (6, None)]);
+    assert!(result.pattern_warnings.iter().all(|w| w.tier == ValidationTier::Pattern));
+    assert_eq!(
+        result.recommendations,
+        vec![
+            "line 3: Context ProfileContext is not @MainActor; contexts publish state to views on the main actor",
+            "line 6: Client ProfileClient conforms to AxiomClient as a class; clients must be actors",
+        ]
+    );
+    assert_eq!(result.architecture_compliance, 80.0);
+    assert_eq!(result.overall_score, 90.0);
+}
+
+#[tokio::test]
+async fn test_missing_import_is_a_warning() {
+    let result = validate("struct Empty {}\n").await;
+
+    assert!(result.passed);
+    assert_eq!(result.pattern_warnings.len(), 1);
+    assert_eq!(result.pattern_warnings[0].message, "No import statement");
+}
+
+#[tokio::test]
+async fn test_generated_code_validates_cleanly() {
+    let generator = AxiomCodeGenerator::new().await.unwrap();
+    let context = generator.generate_context(ContextSpec {
+        name: "ProfileContext".to_string(),
+        state_properties: vec![StateProperty {
+            name: "name".to_string(),
+            property_type: "String".to_string(),
+            is_published: true,
+            default_value: Some("\"\"".to_string()),
+            requirement_ids: vec![],
+        }],
+        client_binding: "ProfileClient".to_string(),
+        lifecycle_management: true,
+        template_overrides: Default::default(),
+    }).await.unwrap();
+    let presentation = generator.generate_presentation(PresentationSpec {
+        name: "ProfileView".to_string(),
+        context_binding: "ProfileContext".to_string(),
+        ui_components: vec!["Text(context.name)".to_string()],
+        accessibility_requirements: vec![],
+        performance_requirements: PerformanceRequirements { max_render_time_ms: 16.0, max_memory_mb: 10.0 },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
+    }).await.unwrap();
+    let client = generator.generate_mock_client(ClientSpec {
+        name: "ProfileClient".to_string(),
+        protocol_conformance: vec!["AxiomClient".to_string()],
+        actions: vec![ActionDefinition {
+            name: "rename".to_string(),
+            parameters: vec!["to name: String".to_string()],
+            return_type: "Void".to_string(),
+            is_async: true,
+            requirement_ids: vec![],
+        }],
+        state_streaming: false,
+        mock_implementation: true,
+        template_overrides: Default::default(),
+        persistence: None,
+    }).await.unwrap();
+
+    for code in [context, presentation, client] {
+        let result = generator.validate_generated_code(&code.generated_code).await.unwrap();
+        assert!(result.passed, "{}\n{:?}", code.generated_code, result.issues);
+        assert!(result.pattern_warnings.is_empty(), "{}\n{:?}", code.generated_code, result.recommendations);
+        assert!(!result.syntax_checked);
+    }
+}
+
+#[tokio::test]
+async fn test_compiler_errors_fail_validation_and_are_cached() {
+    let errors = parse_swiftc_diagnostics("/tmp/unit.swift:5:22: error: expected ',' separator\n");
+    let swiftc = FakeSwiftc::new(Some(errors));
+    let generator = AxiomCodeGenerator::new().await.unwrap().with_syntax_checker(swiftc.clone());
+
+    let first = generator.validate_generated_code(VALID_VIEW).await.unwrap();
+    let again = generator.validate_generated_code(VALID_VIEW).await.unwrap();
+
+    assert!(!first.passed);
+    assert!(first.syntax_checked);
+    assert_eq!(locations(&first.syntax_errors), vec![(5, Some(22))]);
+    assert_eq!(first.syntax_errors[0].tier, ValidationTier::Syntax);
+    assert_eq!(again.syntax_errors, first.syntax_errors);
+
+    let checked = swiftc.checked();
+    assert_eq!(checked.len(), 1, "unchanged code is parsed once");
+    assert_eq!(checked[0].1, VALID_VIEW);
+    assert!(!checked[0].0.exists(), "the temp file is removed");
+
+    generator.validate_generated_code(&format!("{}\n", VALID_VIEW)).await.unwrap();
+    assert_eq!(swiftc.checked().len(), 2);
+}
+
+#[tokio::test]
+async fn test_compiler_is_skipped_after_structural_errors() {
+    let swiftc = FakeSwiftc::new(Some(Vec::new()));
+    let generator = AxiomCodeGenerator::new().await.unwrap().with_syntax_checker(swiftc.clone());
+
+    let result = generator.validate_generated_code(VALID_VIEW.trim_end_matches('}')).await.unwrap();
+
+    assert!(!result.passed);
+    assert!(!result.syntax_checked);
+    assert!(swiftc.checked().is_empty());
+}
+
+#[tokio::test]
+async fn test_unavailable_compiler_falls_back_to_structural_checks() {
+    let swiftc = FakeSwiftc::new(None);
+    let generator = AxiomCodeGenerator::new().await.unwrap().with_syntax_checker(swiftc.clone());
+
+    let result = generator.validate_generated_code(VALID_VIEW).await.unwrap();
+
+    assert!(result.passed);
+    assert!(!result.syntax_checked);
+    assert_eq!(swiftc.checked().len(), 1);
+}
+
+#[test]
+fn test_swiftc_diagnostics_keep_only_errors() {
+    let output = "\
+/tmp/axiom-validate-1-0.swift:3:8: warning: 'foo' is deprecated
+/tmp/axiom-validate-1-0.swift:4:5: error: expected declaration
+/tmp/axiom-validate-1-0.swift:2:1: note: in declaration of 'ProfileView'
+C:\\Temp\\unit.swift:12:1: error: expected '}' in struct
+1 error generated.
+";
+
+    let errors = parse_swiftc_diagnostics(output);
+
+    assert_eq!(locations(&errors), vec![(4, Some(5)), (12, Some(1))]);
+    assert_eq!(errors[0].message, "expected declaration");
+    assert_eq!(errors[1].message, "expected '}' in struct");
+}
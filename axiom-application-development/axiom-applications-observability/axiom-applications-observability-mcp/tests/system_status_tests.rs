@@ -0,0 +1,265 @@
+use async_trait::async_trait;
+use axiom_applications_observability::code_generation::AxiomCodeGenerator;
+use axiom_applications_observability::connection::{ConnectionState, ConnectionStatus};
+use axiom_applications_observability::screenshot_store::ScreenshotStorageConfig;
+use axiom_applications_observability::simulator::{Simctl, SimulatorController};
+use axiom_applications_observability::system_status::*;
+use axiom_applications_observability::*;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn capabilities(enabled: bool) -> MCPCapabilities {
+    MCPCapabilities {
+        code_generation: enabled,
+        intelligence_analysis: enabled,
+        hot_reload_integration: enabled,
+        visual_analysis: enabled,
+        simulator_management: enabled,
+        performance_monitoring: enabled,
+    }
+}
+
+/// A probe that reports what it was given
+struct FixedProbe(SubsystemReport);
+
+#[async_trait]
+impl SubsystemProbe for FixedProbe {
+    fn subsystem(&self) -> Subsystem {
+        self.0.subsystem
+    }
+
+    async fn probe(&self) -> SubsystemReport {
+        self.0.clone()
+    }
+}
+
+fn checker(reports: Vec<SubsystemReport>) -> SystemStatusChecker {
+    reports
+        .into_iter()
+        .fold(SystemStatusChecker::new(), |checker, report| checker.with_probe(Arc::new(FixedProbe(report))))
+}
+
+fn all_ready() -> Vec<SubsystemReport> {
+    Subsystem::ALL.into_iter().map(|subsystem| SubsystemReport::ready(subsystem, "fine")).collect()
+}
+
+fn connection(state: ConnectionState, reconnect_attempt: u32, last_error: Option<&str>) -> ConnectionStatus {
+    ConnectionStatus {
+        name: "hot_reload".to_string(),
+        url: "ws://localhost:8080/ws".to_string(),
+        state,
+        connected_since: None,
+        reconnect_attempt,
+        reconnects: 0,
+        buffered_messages: 2,
+        dropped_messages: 0,
+        active_subscriptions: 0,
+        last_error: last_error.map(ToString::to_string),
+    }
+}
+
+/// simctl answering `list devices` with `devices`, or failing like a
+/// machine without Xcode when `devices` is `None`
+struct FakeSimctl {
+    devices: Option<Value>,
+}
+
+#[async_trait]
+impl Simctl for FakeSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        match (&self.devices, args) {
+            (Some(devices), ["list", "devices", "--json"]) => Ok(devices.to_string()),
+            (None, _) => Err(AxiomMCPError::toolchain("simulator", "run simctl", "xcrun: error: unable to find utility \"simctl\"")),
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], _env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], _input: &[u8]) -> Result<String> {
+        self.run(args).await
+    }
+}
+
+fn simulator_probe(devices: Option<Value>) -> SimulatorProbe {
+    SimulatorProbe::new(Arc::new(SimulatorController::with_simctl(Arc::new(FakeSimctl { devices }))))
+}
+
+fn device(udid: &str, available: bool) -> Value {
+    let mut device = json!({
+        "udid": udid,
+        "name": "iPhone 15",
+        "state": "Shutdown",
+        "isAvailable": available,
+        "deviceTypeIdentifier": "com.apple.CoreSimulator.SimDeviceType.iPhone-15",
+    });
+    if !available {
+        device["availabilityError"] = json!("runtime profile not found");
+    }
+    device
+}
+
+#[tokio::test]
+async fn test_ready_when_every_enabled_subsystem_is_ready() {
+    let status = checker(all_ready()).check(&capabilities(true)).await;
+
+    assert_eq!(status.overall, ReadinessStatus::Ready);
+    assert_eq!(status.subsystems.len(), Subsystem::ALL.len());
+    assert_eq!(status.problems().count(), 0);
+}
+
+#[tokio::test]
+async fn test_overall_status_is_the_worst_subsystem() {
+    let mut reports = all_ready();
+    reports[1] = SubsystemReport::degraded(Subsystem::Intelligence, "Reconnecting");
+    let degraded = checker(reports.clone()).check(&capabilities(true)).await;
+
+    reports[4] = SubsystemReport::failed(Subsystem::ScreenshotStorage, "Cannot write")
+        .with_error("permission denied")
+        .with_remediation("Make it writable");
+    let failed = checker(reports).check(&capabilities(true)).await;
+
+    assert_eq!(degraded.overall, ReadinessStatus::Degraded);
+    assert_eq!(failed.overall, ReadinessStatus::Failed);
+    let problems: Vec<Subsystem> = failed.problems().map(|report| report.subsystem).collect();
+    assert_eq!(problems, vec![Subsystem::Intelligence, Subsystem::ScreenshotStorage]);
+    let storage = failed.subsystem(Subsystem::ScreenshotStorage).unwrap();
+    assert_eq!(storage.last_error.as_deref(), Some("permission denied"));
+    assert_eq!(storage.capability, "visual_analysis");
+}
+
+#[tokio::test]
+async fn test_disabled_subsystems_are_not_probed_and_do_not_count() {
+    let mut reports = all_ready();
+    reports[2] = SubsystemReport::failed(Subsystem::Simulator, "simctl is not available");
+    let without_simulators = MCPCapabilities { simulator_management: false, ..capabilities(true) };
+
+    let status = checker(reports).check(&without_simulators).await;
+
+    assert_eq!(status.overall, ReadinessStatus::Ready);
+    assert_eq!(status.subsystem(Subsystem::Simulator).unwrap().status, ReadinessStatus::Disabled);
+
+    let nothing_enabled = SystemStatusChecker::new().check(&capabilities(false)).await;
+    assert_eq!(nothing_enabled.overall, ReadinessStatus::Ready);
+    assert!(nothing_enabled.subsystems.iter().all(|report| report.status == ReadinessStatus::Disabled));
+}
+
+#[tokio::test]
+async fn test_enabled_subsystem_without_a_probe_failed_to_start() {
+    let status = SystemStatusChecker::new().check(&capabilities(true)).await;
+
+    assert_eq!(status.overall, ReadinessStatus::Failed);
+    let hot_reload = status.subsystem(Subsystem::HotReload).unwrap();
+    assert_eq!(hot_reload.status, ReadinessStatus::Failed);
+    assert!(hot_reload.remediation.is_some());
+}
+
+#[tokio::test]
+async fn test_connection_state_maps_to_readiness() {
+    let cases = [
+        (connection(ConnectionState::Connected, 0, None), ReadinessStatus::Ready),
+        (connection(ConnectionState::Connecting, 0, None), ReadinessStatus::Degraded),
+        (connection(ConnectionState::Reconnecting, 1, Some("connection refused")), ReadinessStatus::Degraded),
+        (
+            connection(ConnectionState::Reconnecting, FAILED_AFTER_RECONNECT_ATTEMPTS, Some("connection refused")),
+            ReadinessStatus::Failed,
+        ),
+    ];
+
+    for (status, expected) in cases {
+        let report = ConnectionProbe::hot_reload(status.clone()).probe().await;
+        assert_eq!(report.status, expected, "{:?}", status);
+        assert_eq!(report.last_error, status.last_error);
+        if expected != ReadinessStatus::Ready {
+            assert!(report.remediation.unwrap().contains("AXIOM_OBS_HOT_RELOAD_SERVER_URL"));
+        }
+    }
+
+    let intelligence = ConnectionProbe::intelligence(connection(ConnectionState::Connecting, 0, None)).probe().await;
+    assert_eq!(intelligence.subsystem, Subsystem::Intelligence);
+    assert!(intelligence.remediation.unwrap().contains("AXIOM_OBS_INTELLIGENCE_SERVER_URL"));
+}
+
+#[tokio::test]
+async fn test_simulators_need_simctl_and_an_available_runtime() {
+    let ready = simulator_probe(Some(json!({ "devices": {
+        "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [device("A", true)],
+    }}))).probe().await;
+    let some_unavailable = simulator_probe(Some(json!({ "devices": {
+        "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [device("A", true)],
+        "com.apple.CoreSimulator.SimRuntime.iOS-16-4": [device("B", false)],
+    }}))).probe().await;
+    let none_available = simulator_probe(Some(json!({ "devices": {
+        "com.apple.CoreSimulator.SimRuntime.iOS-16-4": [device("B", false)],
+    }}))).probe().await;
+    let no_simctl = simulator_probe(None).probe().await;
+
+    assert_eq!(ready.status, ReadinessStatus::Ready);
+    assert_eq!(some_unavailable.status, ReadinessStatus::Degraded);
+    assert_eq!(some_unavailable.last_error.as_deref(), Some("runtime profile not found"));
+    assert!(some_unavailable.remediation.unwrap().contains("simctl delete unavailable"));
+    assert_eq!(none_available.status, ReadinessStatus::Failed);
+    assert!(none_available.remediation.unwrap().contains("runtime"));
+    assert_eq!(no_simctl.status, ReadinessStatus::Failed);
+    assert!(no_simctl.last_error.unwrap().contains("simctl"));
+    assert!(no_simctl.remediation.unwrap().contains("xcode-select"));
+}
+
+#[tokio::test]
+async fn test_templates_need_every_kind() {
+    let templates = AxiomCodeGenerator::new().await.unwrap().templates();
+
+    let ready = TemplateProbe::new(templates.clone()).probe().await;
+    let missing = TemplateProbe::new(templates[1..].to_vec()).probe().await;
+
+    assert_eq!(ready.status, ReadinessStatus::Ready);
+    assert_eq!(missing.status, ReadinessStatus::Failed);
+    assert!(missing.detail.contains(templates[0].kind.name()), "{}", missing.detail);
+}
+
+#[tokio::test]
+async fn test_storage_must_be_writable() {
+    let dir = tempfile::tempdir().unwrap();
+    let blocker = dir.path().join("not-a-directory");
+    std::fs::write(&blocker, b"").unwrap();
+
+    let writable = StorageProbe::new(dir.path().join("screenshots")).probe().await;
+    let unwritable = StorageProbe::new(blocker.join("screenshots")).probe().await;
+
+    assert_eq!(writable.status, ReadinessStatus::Ready);
+    assert!(dir.path().join("screenshots").is_dir());
+    assert_eq!(std::fs::read_dir(dir.path().join("screenshots")).unwrap().count(), 0);
+    assert_eq!(unwritable.status, ReadinessStatus::Failed);
+    assert!(unwritable.last_error.is_some());
+    assert!(unwritable.remediation.unwrap().contains("AXIOM_OBS_SCREENSHOT_STORAGE__DIR"));
+}
+
+#[tokio::test]
+async fn test_get_system_status_tool_mirrors_capabilities() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = MCPConfiguration {
+        screenshot_storage: ScreenshotStorageConfig { dir: dir.path().join("screenshots"), ..Default::default() },
+        ..Default::default()
+    };
+    let capabilities = MCPCapabilities { code_generation: true, visual_analysis: true, ..capabilities(false) };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap();
+
+    let result = mcp.execute_tool(AxiomMCPTool::GetSystemStatus).await.unwrap();
+
+    let ToolResult::SystemStatus(status) = result else {
+        panic!("Expected a system status, got {:?}", result);
+    };
+    assert_eq!(status.overall, ReadinessStatus::Ready, "{:?}", status);
+    let statuses: Vec<(Subsystem, ReadinessStatus)> =
+        status.subsystems.iter().map(|report| (report.subsystem, report.status)).collect();
+    assert_eq!(statuses, vec![
+        (Subsystem::HotReload, ReadinessStatus::Disabled),
+        (Subsystem::Intelligence, ReadinessStatus::Disabled),
+        (Subsystem::Simulator, ReadinessStatus::Disabled),
+        (Subsystem::CodeGeneration, ReadinessStatus::Ready),
+        (Subsystem::ScreenshotStorage, ReadinessStatus::Ready),
+    ]);
+}
@@ -21,7 +21,6 @@ use std::env;
 /// - Setup Time: < 30 seconds from project open to development ready
 /// - Feedback Loop: Real-time visual and performance feedback
 /// - Code Quality: Generated code indistinguishable from hand-written
-
 pub struct TestConfig {
     pub mock_server_port: u16,
     pub test_timeout_seconds: u64,
@@ -373,6 +372,7 @@ let package = Package(
 }
 
 /// Test metrics collector for gathering performance data across test runs
+#[derive(Default)]
 pub struct TestMetricsCollector {
     pub code_generation_times: Vec<std::time::Duration>,
     pub screenshot_capture_times: Vec<std::time::Duration>,
@@ -381,18 +381,6 @@ pub struct TestMetricsCollector {
     pub quality_scores: Vec<f64>,
 }
 
-impl Default for TestMetricsCollector {
-    fn default() -> Self {
-        Self {
-            code_generation_times: Vec::new(),
-            screenshot_capture_times: Vec::new(),
-            validation_times: Vec::new(),
-            setup_times: Vec::new(),
-            quality_scores: Vec::new(),
-        }
-    }
-}
-
 impl TestMetricsCollector {
     pub fn record_code_generation_time(&mut self, duration: std::time::Duration) {
         self.code_generation_times.push(duration);
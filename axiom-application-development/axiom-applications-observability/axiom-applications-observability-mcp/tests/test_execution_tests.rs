@@ -0,0 +1,285 @@
+mod common;
+
+use async_trait::async_trait;
+use axiom_applications_observability::complete_development_loop::*;
+use axiom_applications_observability::project_build::*;
+use axiom_applications_observability::simulator::{Simctl, SimulatorController};
+use axiom_applications_observability::test_execution::*;
+use axiom_applications_observability::*;
+use serde_json::json;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const TEST_RESULTS: &str = include_str!("fixtures/xcresult_tests.json");
+const COVERAGE_REPORT: &str = include_str!("fixtures/xcresult_coverage.json");
+
+/// Where the fixtures were captured
+const CAPTURED_PROJECT_DIR: &str = "/tmp/axiom-builds/3f2a";
+
+const UDID: &str = "5A3E0B7C-1F2D-4E8A-9B6C-0D1E2F3A4B5C";
+
+const TEST_FAILED_LOG: &str = "Test Suite 'TaskManagerContextTests' started
+/tmp/axiom-builds/3f2a/Tests/AppTests/TaskManagerContextTests.swift:18: error: -[AppTests.TaskManagerContextTests testAddsTask] : XCTAssertEqual failed: (\"0\") is not equal to (\"1\")
+Test Suite 'TaskManagerContextTests' failed
+** TEST FAILED **
+";
+
+const TEST_BUILD_FAILED_LOG: &str = "CompileSwift normal arm64 (in target 'AppTests' from project 'App')
+/tmp/axiom-builds/3f2a/Tests/AppTests/TaskManagerContextTests.swift:7:23: error: cannot find 'TaskManagerContext' in scope
+** TEST BUILD FAILED **
+";
+
+fn output(success: bool, log: &str) -> CommandOutput {
+    CommandOutput { success, log: log.to_string() }
+}
+
+/// Replays captured output, in order, as if it came from the project
+/// being tested
+struct ReplayCommands {
+    outputs: Mutex<VecDeque<CommandOutput>>,
+    runs: Mutex<Vec<(PathBuf, String, Vec<String>)>>,
+}
+
+impl ReplayCommands {
+    fn new(outputs: Vec<CommandOutput>) -> Arc<Self> {
+        Arc::new(Self { outputs: Mutex::new(outputs.into()), runs: Mutex::new(Vec::new()) })
+    }
+
+    fn runs(&self) -> Vec<(PathBuf, String, Vec<String>)> {
+        self.runs.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl BuildCommand for ReplayCommands {
+    async fn run(&self, dir: &Path, program: &str, args: &[String]) -> Result<CommandOutput> {
+        assert!(dir.join("Package.swift").exists(), "the project is written before it is tested");
+        self.runs.lock().unwrap().push((dir.to_path_buf(), program.to_string(), args.to_vec()));
+        let output = self.outputs.lock().unwrap().pop_front().expect("a command nobody expected ran");
+        Ok(CommandOutput { log: output.log.replace(CAPTURED_PROJECT_DIR, &dir.display().to_string()), ..output })
+    }
+}
+
+/// Simctl with one booted iPhone
+struct FakeSimctl;
+
+#[async_trait]
+impl Simctl for FakeSimctl {
+    async fn run(&self, args: &[&str]) -> Result<String> {
+        match args {
+            ["list", "devices", "--json"] => Ok(json!({
+                "devices": {
+                    "com.apple.CoreSimulator.SimRuntime.iOS-17-2": [{
+                        "udid": UDID,
+                        "name": "iPhone 15",
+                        "state": "Booted",
+                        "isAvailable": true,
+                    }]
+                }
+            })
+            .to_string()),
+            _ => Err(AxiomMCPError::simulator("simctl", "run", format!("unexpected simctl {}", args.join(" ")))),
+        }
+    }
+
+    async fn run_with_env(&self, args: &[&str], _env: &[(String, String)], _capture: Option<Duration>) -> Result<String> {
+        self.run(args).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], _input: &[u8]) -> Result<String> {
+        self.run(args).await
+    }
+}
+
+fn runner(workspace: &Path, commands: Arc<ReplayCommands>) -> TestRunner {
+    TestRunner::new(Arc::new(SimulatorController::with_simctl(Arc::new(FakeSimctl))), "iPhone 15", workspace).with_command(commands)
+}
+
+/// Test results in which every test case of the fixture passed
+fn passing_results() -> String {
+    let mut results: serde_json::Value = serde_json::from_str(TEST_RESULTS).unwrap();
+    let suites = results["testNodes"][0]["children"][0]["children"].as_array_mut().unwrap();
+    for suite in suites {
+        for test in suite["children"].as_array_mut().unwrap() {
+            test["result"] = json!("Passed");
+            test["children"] = json!([]);
+        }
+    }
+    results.to_string()
+}
+
+async fn implementation() -> Implementation {
+    let analysis = common::development_loop().await.analyze_requirement("Task list".to_string()).await.unwrap();
+    code_generation::AxiomCodeGenerator::new().await.unwrap().generate(&analysis, vec![]).await.unwrap()
+}
+
+#[test]
+fn test_result_bundle_test_cases_are_parsed_with_their_failures() {
+    let tests = parse_test_results(TEST_RESULTS).unwrap();
+
+    let outcomes: Vec<(&str, TestOutcome, u64)> =
+        tests.iter().map(|test| (test.identifier.as_str(), test.outcome, test.duration_ms)).collect();
+    assert_eq!(outcomes, vec![
+        ("TaskManagerContextTests/testInitializes()", TestOutcome::Passed, 2),
+        ("TaskManagerContextTests/testAddsTask()", TestOutcome::Failed, 14),
+        ("TaskManagerContextTests/testSyncsWhenOnline()", TestOutcome::Skipped, 0),
+        ("TaskManagerClientTests/testLoadsSavedTasks()", TestOutcome::ExpectedFailure, 350),
+    ]);
+    assert_eq!(tests[1].failures, vec!["TaskManagerContextTests.swift:18: XCTAssertEqual failed: (\"0\") is not equal to (\"1\")"]);
+    // A skip reason is not a failure
+    assert!(tests[2].failures.is_empty());
+    assert!(tests[3].outcome.passed());
+}
+
+#[test]
+fn test_coverage_counts_only_the_targets_under_test() {
+    let targets = parse_coverage_report(COVERAGE_REPORT).unwrap();
+
+    assert_eq!(targets, vec![TargetCoverage { name: "App.o".to_string(), covered_lines: 36, executable_lines: 60 }]);
+    assert_eq!(line_coverage(&targets), Some(60.0));
+    assert_eq!(line_coverage(&[]), None);
+}
+
+#[test]
+fn test_unexpected_xcresulttool_output_is_a_toolchain_error() {
+    let error = parse_test_results("{\"issues\": []}").unwrap_err();
+
+    assert!(matches!(error, AxiomMCPError::Toolchain(_)), "{:?}", error);
+}
+
+#[tokio::test]
+async fn test_generated_tests_run_on_the_booted_simulator_into_a_result_bundle() {
+    let workspace = tempfile::tempdir().unwrap();
+    let commands = ReplayCommands::new(vec![
+        output(false, TEST_FAILED_LOG),
+        output(true, TEST_RESULTS),
+        output(true, COVERAGE_REPORT),
+    ]);
+    let implementation = implementation().await;
+
+    let run = runner(workspace.path(), commands.clone()).run(&implementation).await.unwrap();
+
+    let project_dir = workspace.path().join(&implementation.implementation_id);
+    assert_eq!(run.project_dir, project_dir);
+    let runs = commands.runs();
+    let (program, args) = test_invocation(&implementation.infrastructure, UDID);
+    assert_eq!((runs[0].1.as_str(), &runs[0].2), (program.as_str(), &args));
+    assert!(run.command.contains(&format!("-destination platform=iOS Simulator,id={}", UDID)), "{}", run.command);
+    assert!(run.command.contains("-resultBundlePath .build/TestResults.xcresult -enableCodeCoverage YES"), "{}", run.command);
+    assert_eq!(runs[1].2, ["xcresulttool", "get", "test-results", "tests", "--path", RESULT_BUNDLE_PATH]);
+    assert_eq!(runs[2].2, ["xccov", "view", "--report", "--json", RESULT_BUNDLE_PATH]);
+
+    let manifest = std::fs::read_to_string(project_dir.join("Package.swift")).unwrap();
+    assert!(manifest.contains(".testTarget(name: \"AppTests\", dependencies: [\"App\"], path: \"Tests/AppTests\")"), "{}", manifest);
+    let test_file = std::fs::read_to_string(project_dir.join("Tests/AppTests/TaskManagerContextTests.swift")).unwrap();
+    assert!(test_file.contains("@testable import App"), "{}", test_file);
+
+    assert!(!run.succeeded);
+    assert_eq!(run.device.udid, UDID);
+    assert_eq!((run.passed().count(), run.failed().count(), run.tests.len()), (2, 1, 4));
+    assert_eq!(run.coverage, Some(60.0));
+    assert!(run.build_errors.is_empty());
+    assert_eq!(run.failure_messages(), vec![
+        "Test failure: TaskManagerContextTests/testAddsTask(): TaskManagerContextTests.swift:18: XCTAssertEqual failed: (\"0\") is not equal to (\"1\")",
+    ]);
+}
+
+#[tokio::test]
+async fn test_tests_that_do_not_build_are_reported_as_build_errors() {
+    let workspace = tempfile::tempdir().unwrap();
+    let commands = ReplayCommands::new(vec![
+        output(false, TEST_BUILD_FAILED_LOG),
+        output(false, "Error: The result bundle could not be found\n"),
+    ]);
+
+    let run = runner(workspace.path(), commands.clone()).run(&implementation().await).await.unwrap();
+
+    assert_eq!(commands.runs().len(), 2, "coverage is not read without tests");
+    assert!(run.tests.is_empty());
+    assert_eq!(run.coverage, None);
+    assert_eq!(run.failure_messages(), vec![
+        "Test build error: Tests/AppTests/TaskManagerContextTests.swift:7:23: cannot find 'TaskManagerContext' in scope",
+    ]);
+    assert!(run.log.contains("** TEST BUILD FAILED **"), "{}", run.log);
+}
+
+#[tokio::test]
+async fn test_bundle_that_cannot_be_read_after_a_run_is_a_toolchain_error() {
+    let workspace = tempfile::tempdir().unwrap();
+    let commands = ReplayCommands::new(vec![
+        output(true, "** TEST SUCCEEDED **\n"),
+        output(false, "Error: This version of xcresulttool cannot read the result bundle\n"),
+    ]);
+
+    let error = runner(workspace.path(), commands).run(&implementation().await).await.unwrap_err();
+
+    assert!(matches!(error, AxiomMCPError::Toolchain(_)), "{:?}", error);
+    assert!(error.to_string().contains("cannot read the result bundle"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_loop_replaces_estimates_with_results_and_repairs_failing_tests() {
+    let workspace = tempfile::tempdir().unwrap();
+    let commands = ReplayCommands::new(vec![
+        output(false, TEST_FAILED_LOG),
+        output(true, TEST_RESULTS),
+        output(true, COVERAGE_REPORT),
+        output(true, "** TEST SUCCEEDED **\n"),
+        output(true, &passing_results()),
+        output(true, COVERAGE_REPORT),
+    ]);
+    let development = common::development_loop().await.with_test_runner(runner(workspace.path(), commands.clone()));
+    let request = DevelopmentCycleRequest {
+        requirement: "Task list".to_string(),
+        controls: LoopControls { max_iterations: 3, ..Default::default() },
+    };
+
+    let result = development.execute_development_cycle(request).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.termination.as_ref().unwrap().reason, TerminationReason::Converged);
+    assert_eq!(result.iterations.len(), 2);
+    assert_eq!((result.iterations[0].validation_errors, result.iterations[0].tests_passed), (1, 2));
+    assert_eq!((result.iterations[1].validation_errors, result.iterations[1].tests_passed), (0, 4));
+    assert!(result.implementation.review_feedback.iter().any(|feedback| {
+        feedback.starts_with("Test failure: TaskManagerContextTests/testAddsTask(): TaskManagerContextTests.swift:18")
+    }), "{:?}", result.implementation.review_feedback);
+
+    let suite = &result.implementation.test_suite;
+    assert!(suite.measured);
+    assert_eq!(suite.total_tests, 4);
+    assert_eq!(suite.test_cases[3], "TaskManagerClientTests/testLoadsSavedTasks()");
+    assert_eq!(suite.estimated_coverage, 60.0);
+    assert!(result.test_execution.unwrap().succeeded);
+    assert_eq!(commands.runs().len(), 6);
+}
+
+#[tokio::test]
+async fn test_tests_are_skipped_without_simulator_management() {
+    let workspace = tempfile::tempdir().unwrap();
+    let commands = ReplayCommands::new(Vec::new());
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: true,
+        simulator_management: false,
+        performance_monitoring: true,
+    };
+    let development = common::development_loop().await
+        .with_test_runner(runner(workspace.path(), commands.clone()))
+        .with_capabilities(capabilities);
+
+    let result = development.execute_complete_development_cycle("Task list".to_string()).await.unwrap();
+
+    assert!(result.test_execution.is_none());
+    assert!(!result.implementation.test_suite.measured);
+    assert_eq!(result.skipped_validations, vec![SkippedValidation {
+        stage: CycleStage::TestExecution,
+        check: "generated tests".to_string(),
+        capability: "simulator_management".to_string(),
+    }]);
+    assert!(commands.runs().is_empty());
+}
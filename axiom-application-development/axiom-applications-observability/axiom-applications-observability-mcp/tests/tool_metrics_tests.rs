@@ -0,0 +1,195 @@
+use axiom_applications_observability::cycle_report::{CycleReportConfig, CycleReportExporter};
+use axiom_applications_observability::error::ErrorCategory;
+use axiom_applications_observability::tool_metrics::*;
+use axiom_applications_observability::*;
+use serde_json::json;
+use std::time::Duration;
+
+fn call(tool: &str, duration_ms: u64, error_category: Option<ErrorCategory>) -> ToolCallRecord {
+    ToolCallRecord {
+        tool: tool.to_string(),
+        at: chrono::Utc::now(),
+        duration_ms,
+        success: error_category.is_none(),
+        output_bytes: if error_category.is_none() { 100 } else { 0 },
+        error_category,
+        stages: vec![],
+    }
+}
+
+#[test]
+fn test_durations_land_in_the_bucket_of_their_bound() {
+    let mut histogram = DurationHistogram::default();
+    for duration_ms in [0, 10, 11, 50, 999, 1000, 60000, 60001, 250000] {
+        histogram.record(duration_ms);
+    }
+
+    let mut expected = vec![0; DURATION_BUCKETS_MS.len() + 1];
+    expected[0] = 2; // 0 and 10
+    expected[1] = 2; // 11 and 50
+    expected[5] = 2; // 999 and 1000
+    expected[10] = 1; // 60000
+    expected[11] = 2; // past every bound
+    assert_eq!(histogram.counts, expected);
+    assert_eq!(histogram.count(), 9);
+    assert_eq!(histogram.max_ms, 250000);
+    assert_eq!(histogram.sum_ms, 372071);
+}
+
+#[test]
+fn test_quantiles_report_the_bucket_bound() {
+    let mut histogram = DurationHistogram::default();
+    assert_eq!(histogram.quantile_ms(0.95), 0);
+    assert_eq!(histogram.mean_ms(), 0.0);
+
+    for _ in 0..19 {
+        histogram.record(40);
+    }
+    histogram.record(4000);
+
+    assert_eq!(histogram.quantile_ms(0.5), 50);
+    assert_eq!(histogram.quantile_ms(0.95), 50);
+    assert_eq!(histogram.quantile_ms(1.0), 4000, "capped at the slowest call");
+    assert_eq!(histogram.mean_ms(), 238.0);
+
+    histogram.record(90000);
+    assert_eq!(histogram.quantile_ms(1.0), 90000);
+}
+
+#[test]
+fn test_failures_are_counted_apart_from_successes() {
+    let metrics = ToolMetrics::default();
+    metrics.record(call("boot_simulator", 20, None));
+    metrics.record(call("boot_simulator", 3000, None));
+    metrics.record(call("boot_simulator", 5, Some(ErrorCategory::Simulator)));
+    metrics.record(call("boot_simulator", 30000, Some(ErrorCategory::Busy)));
+    metrics.record(call("list_simulators", 400, None));
+
+    let report = metrics.report();
+    let boot = report.tool("boot_simulator").unwrap();
+    assert_eq!((boot.calls, boot.successes, boot.failures), (4, 2, 2));
+    assert_eq!(boot.failure_rate(), 0.5);
+    assert_eq!(boot.failure_categories[&ErrorCategory::Simulator], 1);
+    assert_eq!(boot.failure_categories[&ErrorCategory::Busy], 1);
+    assert_eq!(boot.durations.count(), 4);
+    assert_eq!(boot.success_durations.count(), 2);
+    assert_eq!(boot.success_durations.max_ms, 3000);
+    assert_eq!(boot.output_bytes, 200);
+
+    let list = report.tool("list_simulators").unwrap();
+    assert_eq!((list.calls, list.failures), (1, 0));
+    assert_eq!(report.tools.len(), 2);
+    assert!(report.summary().starts_with("5 calls of 2 tools, 2 failed"), "{}", report.summary());
+}
+
+#[tokio::test]
+async fn test_stages_are_timed_within_an_instrumented_call() {
+    let metrics = ToolMetrics::default();
+
+    let output = metrics.instrument("capture_screenshot_matrix", async {
+        for device in ["iPhone 15", "iPad Pro"] {
+            time_stage(format!("capture {}", device), tokio::time::sleep(Duration::from_millis(5))).await;
+        }
+        record_stage("capture iPhone 15", Duration::from_millis(30));
+        Ok::<_, AxiomMCPError>(json!({ "screenshots": 3 }))
+    }).await.unwrap();
+    assert_eq!(output["screenshots"], 3);
+    // Outside a call there is nothing to record the stage on
+    record_stage("capture iPhone 15", Duration::from_millis(1));
+
+    let report = metrics.report();
+    let matrix = report.tool("capture_screenshot_matrix").unwrap();
+    assert_eq!(matrix.output_bytes, json!({ "screenshots": 3 }).to_string().len() as u64);
+    assert_eq!(matrix.stages.keys().collect::<Vec<_>>(), ["capture iPad Pro", "capture iPhone 15"]);
+    let iphone = &matrix.stages["capture iPhone 15"];
+    assert_eq!(iphone.count, 2);
+    assert_eq!(iphone.max_ms, 30);
+    assert!(iphone.total_ms >= 35, "{:?}", iphone);
+}
+
+#[tokio::test]
+async fn test_calls_are_appended_as_json_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("metrics/tool_calls.jsonl");
+    let metrics = ToolMetrics::new(ToolMetricsConfig { persist_path: Some(path.clone()) });
+
+    metrics.instrument("list_simulators", async { Ok::<_, AxiomMCPError>(vec!["iPhone 15"]) }).await.unwrap();
+    let failure = metrics.instrument("boot_simulator", async {
+        Err::<(), _>(AxiomMCPError::simulator("simctl", "boot", "No such device"))
+    }).await;
+    assert!(failure.is_err());
+
+    let calls: Vec<ToolCallRecord> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(calls.len(), 2);
+    assert!(calls[0].success);
+    assert_eq!(calls[0].output_bytes, 13);
+    assert_eq!((calls[1].success, calls[1].error_category), (false, Some(ErrorCategory::Simulator)));
+}
+
+#[tokio::test]
+async fn test_get_tool_metrics_reports_calls_through_the_mcp() {
+    let capabilities = MCPCapabilities {
+        code_generation: false,
+        intelligence_analysis: false,
+        hot_reload_integration: false,
+        visual_analysis: false,
+        simulator_management: false,
+        performance_monitoring: false,
+    };
+    let config = MCPConfiguration {
+        simulator_management_enabled: false,
+        code_generation_enabled: false,
+        visual_analysis_enabled: false,
+        performance_monitoring_enabled: false,
+        ..Default::default()
+    };
+    let mcp = AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap();
+
+    mcp.call_tool("get_connection_status", json!({})).await.unwrap();
+    mcp.call_tool("list_simulators", json!({})).await.unwrap_err();
+    mcp.call_tool("no_such_tool", json!({})).await.unwrap_err();
+
+    let result = mcp.execute_tool(AxiomMCPTool::GetToolMetrics).await.unwrap();
+    let ToolResult::ToolMetrics(report) = &result else {
+        panic!("unexpected result {:?}", result);
+    };
+    assert_eq!(report.tools.iter().map(|stats| stats.tool.as_str()).collect::<Vec<_>>(), ["get_connection_status", "list_simulators"]);
+    let status = report.tool("get_connection_status").unwrap();
+    assert_eq!((status.successes, status.failures), (1, 0));
+    assert!(status.output_bytes > 0);
+    assert!(status.stages.contains_key("acquire resources"));
+    let simulators = report.tool("list_simulators").unwrap();
+    assert_eq!((simulators.successes, simulators.failures), (0, 1));
+    assert_eq!(simulators.failure_categories[&ErrorCategory::Configuration], 1);
+    assert!(result.summary().starts_with("2 calls of 2 tools, 1 failed"), "{}", result.summary());
+
+    // The metrics call itself is counted once it returns
+    assert_eq!(mcp.tool_metrics().report().tool("get_tool_metrics").unwrap().calls, 1);
+}
+
+#[tokio::test]
+async fn test_cycle_report_lists_tool_timings() {
+    let metrics = ToolMetrics::default();
+    let mut matrix = call("capture_screenshot_matrix", 4200, None);
+    matrix.stages = vec![
+        StageTiming { stage: "capture iPhone 15".to_string(), duration_ms: 1800 },
+        StageTiming { stage: "capture iPad Pro".to_string(), duration_ms: 2300 },
+    ];
+    metrics.record(matrix);
+    metrics.record(call("capture_screenshot_matrix", 30000, Some(ErrorCategory::Busy)));
+
+    let dir = tempfile::tempdir().unwrap();
+    let checkpoint = serde_json::from_str(include_str!("fixtures/cycle_checkpoint.json")).unwrap();
+    let exporter = CycleReportExporter::new(CycleReportConfig { dir: dir.path().to_path_buf(), ..Default::default() })
+        .with_tool_metrics(metrics.report());
+    let exported = exporter.export(&checkpoint, false).await.unwrap();
+
+    let markdown = std::fs::read_to_string(exported.markdown_path).unwrap();
+    assert!(markdown.contains("## Timing\n"), "{}", markdown);
+    assert!(markdown.contains("| capture_screenshot_matrix | 2 | 1 (50%) | 17100 ms | ≤ 30000 ms | 30000 ms | 100 B |"), "{}", markdown);
+    assert!(markdown.contains("| capture_screenshot_matrix | capture iPad Pro | 1 | 2300 ms | 2300 ms |"), "{}", markdown);
+}
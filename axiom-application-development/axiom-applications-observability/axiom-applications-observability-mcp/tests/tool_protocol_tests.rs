@@ -0,0 +1,222 @@
+use axiom_applications_observability::error::ErrorReport;
+use axiom_applications_observability::protocol::*;
+use axiom_applications_observability::simulator::{AppDataReset, PrivacyReset, PrivacyService, ResetSimulatorStateRequest, StateResetOptions};
+use axiom_applications_observability::tool_registry::*;
+use axiom_applications_observability::tools::{tool_spec, TOOL_SPECS};
+use axiom_applications_observability::*;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+async fn mcp() -> AxiomApplicationsObservabilityMCP {
+    let config = MCPConfiguration {
+        hot_reload_server_url: "ws://localhost:8080/ws".to_string(),
+        intelligence_server_url: "ws://localhost:8080/intelligence".to_string(),
+        simulator_management_enabled: true,
+        code_generation_enabled: true,
+        visual_analysis_enabled: true,
+        performance_monitoring_enabled: true,
+        ..Default::default()
+    };
+    let capabilities = MCPCapabilities {
+        code_generation: true,
+        intelligence_analysis: true,
+        hot_reload_integration: true,
+        visual_analysis: true,
+        simulator_management: true,
+        performance_monitoring: true,
+    };
+    AxiomApplicationsObservabilityMCP::new(config, capabilities).await.unwrap()
+}
+
+fn presentation_spec() -> PresentationSpec {
+    PresentationSpec {
+        name: "TaskList".to_string(),
+        context_binding: "TaskListContext".to_string(),
+        ui_components: vec!["List".to_string()],
+        accessibility_requirements: vec![],
+        performance_requirements: PerformanceRequirements { max_render_time_ms: 16.0, max_memory_mb: 5.0 },
+        template_overrides: Default::default(),
+        requirement_ids: vec![],
+    }
+}
+
+async fn call(mcp: &AxiomApplicationsObservabilityMCP, name: &str, arguments: Value) -> JsonRpcResponse {
+    let message = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments },
+    });
+    mcp.handle_message(&message.to_string()).await
+}
+
+fn violations(response: &JsonRpcResponse) -> Vec<SchemaViolation> {
+    let error = response.error.as_ref().expect("expected an error response");
+    assert_eq!(error.code, INVALID_PARAMS, "{:?}", error);
+    serde_json::from_value(error.data.as_ref().unwrap()["violations"].clone()).unwrap()
+}
+
+#[tokio::test]
+async fn test_tools_list_has_every_tool_with_an_object_schema() {
+    let mcp = mcp().await;
+
+    let response = mcp.handle_message(r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#).await;
+
+    assert!(response.error.is_none(), "{:?}", response.error);
+    let listing: ToolListing = serde_json::from_value(response.result.unwrap()).unwrap();
+    let names: Vec<&str> = listing.tools.iter().map(|tool| tool.name.as_str()).collect();
+    assert_eq!(names, TOOL_SPECS.iter().map(|spec| spec.name).collect::<Vec<_>>());
+    assert_eq!(names.iter().collect::<HashSet<_>>().len(), names.len());
+    for tool in &listing.tools {
+        assert_eq!(tool.input_schema["type"], "object", "{}", tool.name);
+        assert!(!tool.description.is_empty(), "{}", tool.name);
+    }
+    let listed = serde_json::to_value(&listing).unwrap();
+    assert!(listed["tools"][0].get("inputSchema").is_some());
+}
+
+#[test]
+fn test_enum_arguments_pass_their_tools_schema() {
+    let tools = [
+        AxiomMCPTool::GeneratePresentation(presentation_spec()),
+        AxiomMCPTool::ValidateArchitecture,
+        AxiomMCPTool::ApproveStage("cycle-1".to_string()),
+        AxiomMCPTool::BootSimulator("iPhone 15 Pro".to_string()),
+        AxiomMCPTool::ResetSimulatorState(ResetSimulatorStateRequest {
+            device: "iPhone 15 Pro".to_string(),
+            options: StateResetOptions {
+                app_data: Some(AppDataReset { bundle_id: "com.example.Tasks".to_string(), app_path: None }),
+                privacy: Some(PrivacyReset { services: vec![PrivacyService::PhotosAdd], bundle_id: None }),
+                status_bar: true,
+                keychain: false,
+                appearance: None,
+            },
+        }),
+    ];
+
+    for tool in tools {
+        let arguments = tool.arguments().unwrap();
+        let violations = validate_arguments(&tool.spec().input_schema(), &arguments);
+        assert!(violations.is_empty(), "{}: {:?}", tool.name(), violations);
+    }
+}
+
+#[test]
+fn test_every_violation_is_reported_with_its_path() {
+    let schema = AxiomMCPTool::GeneratePresentation(presentation_spec()).spec().input_schema();
+    let mut arguments = serde_json::to_value(presentation_spec()).unwrap();
+    arguments.as_object_mut().unwrap().remove("context_binding");
+    arguments["performance_requirements"]["max_memory_mb"] = json!("5MB");
+    arguments["ui_components"] = json!(["List", 3]);
+    arguments["colour"] = json!("blue");
+
+    let violations = validate_arguments(&schema, &arguments);
+
+    let described: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    assert!(described.contains(&"missing required property `context_binding`".to_string()), "{:?}", described);
+    assert!(described.contains(&"/performance_requirements/max_memory_mb: expected number, found string".to_string()), "{:?}", described);
+    assert!(described.contains(&"/ui_components/1: expected string, found integer".to_string()), "{:?}", described);
+    assert!(described.iter().any(|violation| violation.starts_with("/colour: unknown property; expected one of")), "{:?}", described);
+    assert_eq!(violations.len(), 4);
+}
+
+#[test]
+fn test_enums_and_bounds_are_checked() {
+    let schema = tool_spec("measure_app_performance").unwrap().input_schema();
+
+    let violations = validate_arguments(&schema, &json!({ "device": "iPhone 15 Pro", "bundle_id": "com.example.Tasks", "runs": 0 }));
+
+    assert_eq!(violations, vec![SchemaViolation::new("/runs", "must be at least 1, found 0")]);
+
+    let schema = AxiomMCPTool::ListSimulators.spec().input_schema();
+    assert_eq!(
+        validate_arguments(&schema, &json!({ "verbose": true })),
+        vec![SchemaViolation::new("/verbose", "unknown property; this tool takes no arguments")]
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_arguments_are_a_structured_protocol_error() {
+    let mcp = mcp().await;
+
+    let response = call(&mcp, "reset_simulator_state", json!({
+        "device": "iPhone 15 Pro",
+        "appearance": "sepia",
+        "privacy": { "services": ["photos", "camera"] },
+    })).await;
+
+    assert_eq!(response.id, json!(7));
+    assert!(response.result.is_none());
+    let error = response.error.as_ref().unwrap();
+    assert_eq!(error.data.as_ref().unwrap()["tool"], "reset_simulator_state");
+    assert!(error.message.starts_with("Invalid arguments for reset_simulator_state: "), "{}", error.message);
+    let paths: Vec<String> = violations(&response).into_iter().map(|violation| violation.path).collect();
+    assert_eq!(paths, vec!["/appearance", "/privacy/services/1"]);
+}
+
+#[tokio::test]
+async fn test_arguments_that_are_not_an_object_are_rejected() {
+    let mcp = mcp().await;
+
+    let response = call(&mcp, "boot_simulator", json!("iPhone 15 Pro")).await;
+
+    assert_eq!(violations(&response), vec![SchemaViolation::new("", "expected object, found string")]);
+}
+
+#[tokio::test]
+async fn test_call_tool_returns_the_schema_error() {
+    let mcp = mcp().await;
+
+    let result = mcp.call_tool("approve_stage", json!({ "cycle": "cycle-1" })).await;
+
+    match result {
+        Err(AxiomMCPError::InvalidToolArguments { tool, violations }) => {
+            assert_eq!(tool, "approve_stage");
+            assert_eq!(violations.len(), 2, "{:?}", violations);
+        },
+        other => panic!("Expected invalid arguments, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_tools_methods_and_malformed_messages() {
+    let mcp = mcp().await;
+
+    let unknown_tool = call(&mcp, "deploy_to_app_store", json!({})).await;
+    let unknown_method = mcp.handle_message(r#"{"jsonrpc":"2.0","id":2,"method":"resources/list"}"#).await;
+    let malformed = mcp.handle_message(r#"{"jsonrpc":"2.0","id":3,"method":"#).await;
+    let no_name = mcp.handle_message(r#"{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{}}"#).await;
+
+    let unknown_tool = unknown_tool.error.unwrap();
+    assert_eq!((unknown_tool.code, unknown_tool.message.as_str()), (INVALID_PARAMS, "Unknown tool: deploy_to_app_store"));
+    assert_eq!(unknown_method.error.unwrap().code, METHOD_NOT_FOUND);
+    assert_eq!((malformed.id, malformed.error.unwrap().code), (Value::Null, PARSE_ERROR));
+    assert_eq!((no_name.id, no_name.error.unwrap().code), (json!(4), INVALID_PARAMS));
+}
+
+#[tokio::test]
+async fn test_tool_results_and_failures_are_call_results() {
+    let mcp = mcp().await;
+
+    let listed = call(&mcp, "list_code_generation_templates", json!({})).await;
+    let failed = call(&mcp, "approve_stage", json!({ "cycle_id": "no-such-cycle" })).await;
+
+    let listed: CallToolResult = serde_json::from_value(listed.result.unwrap()).unwrap();
+    assert!(!listed.is_error);
+    assert!(listed.structured_content.unwrap().get("CodeGenerationTemplates").is_some());
+    let failed: CallToolResult = serde_json::from_value(failed.result.unwrap()).unwrap();
+    assert!(failed.is_error);
+    let report: ErrorReport = serde_json::from_value(failed.structured_content.unwrap()).unwrap();
+    assert!(!report.retryable);
+    assert_eq!(failed.content, vec![ToolContent::Text { text: report.message }]);
+}
+
+#[tokio::test]
+async fn test_execute_tool_goes_through_the_registry() {
+    let mcp = mcp().await;
+
+    let result = mcp.execute_tool(AxiomMCPTool::GeneratePresentation(presentation_spec())).await.unwrap();
+
+    assert!(matches!(result, ToolResult::GeneratedCode(_)));
+    assert_eq!(mcp.get_stats().await.total_operations, 1);
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use axiom_applications_observability::code_generation::AxiomCodeGenerator;
+use axiom_applications_observability::complete_development_loop::{CycleCheckpoint, ImplementationGenerator};
+use axiom_applications_observability::cycle_report::CycleReport;
+use axiom_applications_observability::requirement_analysis::{OfflineRequirementAnalyzer, RequirementAnalyzer};
+use axiom_applications_observability::traceability::*;
+use axiom_applications_observability::*;
+
+const REQUIREMENT: &str = "Users add and delete notes on a note list screen and a note detail screen";
+
+/// Generates with the real generator, then leaves out the delete action as
+/// if it had never been implemented
+struct WithoutDelete(AxiomCodeGenerator);
+
+#[async_trait]
+impl ImplementationGenerator for WithoutDelete {
+    async fn generate(&self, analysis: &RequirementAnalysis, feedback: Vec<String>) -> Result<Implementation> {
+        let mut implementation = self.0.generate(analysis, feedback).await?;
+        implementation.traceability.retain(|element| !(element.kind == ElementKind::Action && element.name == "delete"));
+        for context in &mut implementation.context_layer.contexts {
+            context.actions.retain(|action| action != "delete");
+        }
+        Ok(implementation)
+    }
+}
+
+fn fragment<'a>(matrix: &'a TraceabilityMatrix, kind: FragmentKind, text: &str) -> &'a TraceabilityRow {
+    matrix.rows.iter()
+        .find(|row| row.fragment.kind == kind && row.fragment.text == text)
+        .unwrap_or_else(|| panic!("no {} fragment {} in {:?}", kind, text, matrix.rows))
+}
+
+#[test]
+fn test_fragments_are_numbered_entities_then_screens_then_actions() {
+    let strings = |items: &[&str]| items.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    let fragments = requirement_fragments(&strings(&["Note"]), &strings(&["NoteList", "NoteDetail"]), &strings(&["add"]));
+
+    let ids: Vec<(&str, FragmentKind, &str)> = fragments.iter()
+        .map(|fragment| (fragment.id.as_str(), fragment.kind, fragment.text.as_str()))
+        .collect();
+    assert_eq!(ids, vec![
+        ("R1", FragmentKind::Entity, "Note"),
+        ("R2", FragmentKind::Screen, "NoteList"),
+        ("R3", FragmentKind::Screen, "NoteDetail"),
+        ("R4", FragmentKind::Action, "add"),
+    ]);
+}
+
+#[tokio::test]
+async fn test_analysis_assigns_ids_to_what_the_requirement_asks_for() {
+    let analysis = OfflineRequirementAnalyzer.analyze(REQUIREMENT).await.unwrap();
+
+    assert_eq!(analysis.fragments.len(), analysis.entities.len() + analysis.screens.len() + analysis.actions.len());
+    assert_eq!(analysis.fragments, requirement_fragments(&analysis.entities, &analysis.screens, &analysis.actions));
+    assert!(analysis.fragments.iter().any(|fragment| fragment.kind == FragmentKind::Action && fragment.text == "delete"));
+}
+
+#[tokio::test]
+async fn test_generated_elements_cover_every_fragment() {
+    let analysis = OfflineRequirementAnalyzer.analyze(REQUIREMENT).await.unwrap();
+    let implementation = AxiomCodeGenerator::new().await.unwrap().generate(&analysis, vec![]).await.unwrap();
+
+    let matrix = TraceabilityMatrix::build(&analysis, &implementation);
+
+    assert!(matrix.gaps.is_empty(), "{:?}", matrix.gaps);
+    assert_eq!(matrix.coverage, 100.0);
+    let delete = fragment(&matrix, FragmentKind::Action, "delete");
+    assert_eq!(delete.elements, vec![TracedElement::new(ElementKind::Action, "delete", vec![delete.fragment.id.clone()])]);
+    let detail = fragment(&matrix, FragmentKind::Screen, "NoteDetail");
+    assert_eq!(detail.elements.iter().map(|element| element.name.as_str()).collect::<Vec<_>>(), vec!["NoteDetailView"]);
+    let note = fragment(&matrix, FragmentKind::Entity, "Note");
+    assert_eq!(note.elements.iter().map(|element| element.to_string()).collect::<Vec<_>>(), vec!["context property notes"]);
+}
+
+#[tokio::test]
+async fn test_an_unimplemented_feature_is_reported_as_a_gap() {
+    let analysis = OfflineRequirementAnalyzer.analyze(REQUIREMENT).await.unwrap();
+    let generator = WithoutDelete(AxiomCodeGenerator::new().await.unwrap());
+    let implementation = generator.generate(&analysis, vec![]).await.unwrap();
+
+    let matrix = TraceabilityMatrix::build(&analysis, &implementation);
+
+    let delete = fragment(&matrix, FragmentKind::Action, "delete");
+    assert!(delete.elements.is_empty());
+    assert_eq!(matrix.gaps, vec![delete.fragment.clone()]);
+    let total = matrix.rows.len() as f64;
+    assert_eq!(matrix.coverage, (total - 1.0) * 100.0 / total);
+    assert!(!fragment(&matrix, FragmentKind::Action, "add").elements.is_empty());
+}
+
+#[tokio::test]
+async fn test_elements_without_a_requirement_are_possible_over_generation() {
+    let analysis = OfflineRequirementAnalyzer.analyze(REQUIREMENT).await.unwrap();
+    let mut implementation = AxiomCodeGenerator::new().await.unwrap().generate(&analysis, vec![]).await.unwrap();
+    implementation.traceability.push(TracedElement::new(ElementKind::Presentation, "SettingsView", vec![]));
+    implementation.traceability.push(TracedElement::new(ElementKind::Action, "archive", vec!["R99".to_string()]));
+
+    let matrix = TraceabilityMatrix::build(&analysis, &implementation);
+
+    let untraced: Vec<String> = matrix.untraced.iter().map(ToString::to_string).collect();
+    assert_eq!(untraced, vec!["context property isLoading", "presentation SettingsView", "action archive"]);
+    assert!(matrix.gaps.is_empty());
+}
+
+#[test]
+fn test_cycle_report_matrix_of_a_checkpointed_cycle() {
+    let checkpoint: CycleCheckpoint = serde_json::from_str(include_str!("fixtures/cycle_checkpoint.json")).unwrap();
+
+    let matrix = CycleReport::from_checkpoint(&checkpoint).unwrap().traceability().unwrap();
+
+    assert_eq!(matrix.implementation_id, "impl-2");
+    let gaps: Vec<&str> = matrix.gaps.iter().map(|fragment| fragment.text.as_str()).collect();
+    assert_eq!(gaps, vec!["NoteDetail", "delete"]);
+    assert_eq!(matrix.coverage, 60.0);
+}
@@ -0,0 +1,432 @@
+use async_trait::async_trait;
+use axiom_applications_observability::advanced_visual_intelligence::{AccessibilityElement, ElementFrame, ElementType};
+use axiom_applications_observability::intelligence_protocol::*;
+use axiom_applications_observability::screenshot_store::{ScreenshotStorageConfig, ScreenshotStore};
+use axiom_applications_observability::simulator::{SimulatorLocale, TextEntry};
+use axiom_applications_observability::ui_script::*;
+use axiom_applications_observability::visual_regression::encode_png;
+use axiom_applications_observability::{AxiomMCPError, Result, ToolResult};
+use image::{Rgba, RgbaImage};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const UDID: &str = "5A1C7F0E-2B3D-4E6F-8A9B-0C1D2E3F4A5B";
+
+fn element(identifier: &str, children: Vec<AccessibilityElement>) -> AccessibilityElement {
+    AccessibilityElement {
+        identifier: identifier.to_string(),
+        element_type: ElementType::Other,
+        label: None,
+        traits: Vec::new(),
+        frame: ElementFrame { x: 0.0, y: 0.0, width: 393.0, height: 852.0 },
+        hidden: false,
+        font_size: None,
+        is_bold: false,
+        children,
+    }
+}
+
+fn screen(identifiers: &[&str]) -> AccessibilityElement {
+    element("root", identifiers.iter().map(|identifier| element(identifier, Vec::new())).collect())
+}
+
+/// Records what it was told to do; element trees are served in order,
+/// the last one repeating
+struct FakeDriver {
+    performed: Mutex<Vec<UiAction>>,
+    trees: Mutex<VecDeque<Result<AccessibilityElement>>>,
+    tree_reads: Mutex<usize>,
+    /// Taps of this element fail
+    failing_tap: Option<String>,
+    /// Typing fails, as it does for keys the keyboard has no key code for
+    failing_typing: bool,
+    pasteboard: Mutex<Vec<String>>,
+    locales: Mutex<Vec<SimulatorLocale>>,
+}
+
+impl FakeDriver {
+    fn new(trees: Vec<Result<AccessibilityElement>>) -> Self {
+        Self {
+            performed: Mutex::new(Vec::new()),
+            trees: Mutex::new(trees.into()),
+            tree_reads: Mutex::new(0),
+            failing_tap: None,
+            failing_typing: false,
+            pasteboard: Mutex::new(Vec::new()),
+            locales: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn performed(&self) -> Vec<UiAction> {
+        self.performed.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl UiDriver for FakeDriver {
+    async fn perform(&self, udid: &str, action: &UiAction) -> Result<()> {
+        assert_eq!(udid, UDID);
+        if matches!(action, UiAction::Tap { element } if Some(element) == self.failing_tap.as_ref()) {
+            return Err(AxiomMCPError::validation("intelligence", "request_ui_action", "No hittable element saveButton"));
+        }
+        if self.failing_typing && matches!(action, UiAction::TypeText { .. }) {
+            return Err(AxiomMCPError::validation("intelligence", "request_ui_action", "Typed text did not reach the field"));
+        }
+        self.performed.lock().unwrap().push(action.clone());
+        Ok(())
+    }
+
+    async fn element_tree(&self, _udid: &str) -> Result<AccessibilityElement> {
+        *self.tree_reads.lock().unwrap() += 1;
+        let mut trees = self.trees.lock().unwrap();
+        if trees.len() > 1 {
+            return trees.pop_front().unwrap();
+        }
+        match trees.front().unwrap() {
+            Ok(tree) => Ok(tree.clone()),
+            Err(e) => Err(AxiomMCPError::connectivity("intelligence", "request_accessibility_tree", e.to_string())),
+        }
+    }
+
+    async fn screenshot(&self, _udid: &str) -> Result<Vec<u8>> {
+        encode_png(&RgbaImage::from_pixel(4, 8, Rgba([200, 40, 40, 255])))
+    }
+
+    async fn set_locale(&self, _udid: &str, locale: &SimulatorLocale) -> Result<()> {
+        self.locales.lock().unwrap().push(locale.clone());
+        Ok(())
+    }
+
+    async fn set_pasteboard(&self, _udid: &str, text: &str) -> Result<()> {
+        self.pasteboard.lock().unwrap().push(text.to_string());
+        Ok(())
+    }
+}
+
+fn text_entries(run: &UiScriptRun) -> Vec<Option<TextEntry>> {
+    run.completed.iter().map(|step| step.text_entry.clone()).collect()
+}
+
+fn steps(script: &UiScript) -> Vec<(usize, UiStep)> {
+    script.steps.iter().map(|step| (step.line, step.step.clone())).collect()
+}
+
+fn parse_error(source: &str) -> String {
+    match UiScript::parse(source) {
+        Err(error @ AxiomMCPError::Validation(_)) => error.to_string(),
+        other => panic!("Expected a validation error for {:?}, got {:?}", source, other),
+    }
+}
+
+#[test]
+fn test_script_parses_into_steps_with_their_lines() {
+    let script = UiScript::parse(
+        "# Add a task\n\
+         tap addTaskButton\n\
+         \n\
+         type \"Buy \\\"oat\\\" milk\" into titleField\n\
+         type \"two\\nlines\"\n\
+         swipe left on \"task row 0\"\n\
+         swipe down\n\
+         wait taskDetail 1.5s\n\
+         wait saveButton 300ms\n\
+         wait doneButton\n",
+    )
+    .unwrap();
+
+    assert_eq!(steps(&script), vec![
+        (2, UiStep::Perform(UiAction::Tap { element: "addTaskButton".to_string() })),
+        (4, UiStep::Perform(UiAction::TypeText { text: "Buy \"oat\" milk".to_string(), element: Some("titleField".to_string()) })),
+        (5, UiStep::Perform(UiAction::TypeText { text: "two\nlines".to_string(), element: None })),
+        (6, UiStep::Perform(UiAction::Swipe { direction: SwipeDirection::Left, element: Some("task row 0".to_string()) })),
+        (7, UiStep::Perform(UiAction::Swipe { direction: SwipeDirection::Down, element: None })),
+        (8, UiStep::WaitFor { element: "taskDetail".to_string(), timeout: Duration::from_millis(1500) }),
+        (9, UiStep::WaitFor { element: "saveButton".to_string(), timeout: Duration::from_millis(300) }),
+        (10, UiStep::WaitFor { element: "doneButton".to_string(), timeout: DEFAULT_WAIT_TIMEOUT }),
+    ]);
+}
+
+#[test]
+fn test_steps_print_as_scripts_that_parse_back() {
+    let script = UiScript::parse("tap \"Add task\"\ntype \"a \\\\ b\" into field\nswipe right on row\nwait detail 2s").unwrap();
+    let printed: Vec<String> = script.steps.iter().map(|step| step.step.to_string()).collect();
+
+    assert_eq!(printed, vec![
+        "tap \"Add task\"",
+        "type \"a \\\\ b\" into field",
+        "swipe right on row",
+        "wait detail 2000ms",
+    ]);
+    let reparsed = UiScript::parse(&printed.join("\n")).unwrap();
+    assert_eq!(reparsed.steps.iter().map(|step| &step.step).collect::<Vec<_>>(), script.steps.iter().map(|step| &step.step).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_malformed_scripts_name_the_line() {
+    let cases = [
+        ("", "The script has no steps"),
+        ("# only a comment\n\n", "The script has no steps"),
+        ("tap", "Line 1: tap needs an element's accessibility identifier"),
+        ("tap ok\npress ok", "Line 2: Unknown step press"),
+        ("tap ok\n\ntype", "Line 3: type needs the text to enter"),
+        ("type \"hello", "Line 1: Unterminated quoted string"),
+        ("type \"a\\tb\"", "Line 1: Unknown escape \\t"),
+        ("tap save\"Button", "Line 1: Unexpected quote in save"),
+        ("type hi onto field", "Line 1: Expected into before the element, found onto"),
+        ("type hi into", "Line 1: into needs an element's accessibility identifier"),
+        ("swipe sideways", "Line 1: Unknown swipe direction sideways"),
+        ("swipe", "Line 1: swipe needs a direction"),
+        ("wait detail soon", "Line 1: Invalid timeout soon"),
+        ("wait detail -1s", "Line 1: Invalid timeout -1s"),
+        ("tap ok now", "Line 1: Unexpected now after `tap ok`"),
+        ("paste", "Line 1: paste needs the text to enter"),
+        ("setLocale", "Line 1: setLocale needs a locale"),
+        ("setLocale German", "Line 1: Invalid locale German"),
+        ("setLocale de_DE layout QWERTZ", "Line 1: Expected keyboard after the locale, found layout"),
+        ("setLocale de_DE keyboard", "Line 1: keyboard needs a keyboard identifier"),
+    ];
+
+    for (source, expected) in cases {
+        let error = parse_error(source);
+        assert!(error.contains(expected), "{:?}: {}", source, error);
+    }
+}
+
+#[test]
+fn test_actions_are_sent_as_tagged_json() {
+    let swipe = UiAction::Swipe { direction: SwipeDirection::Up, element: None };
+    let typed = UiAction::TypeText { text: "Buy milk".to_string(), element: Some("titleField".to_string()) };
+
+    assert_eq!(serde_json::to_value(&swipe).unwrap(), json!({ "kind": "swipe", "direction": "up" }));
+    let encoded: Value = serde_json::from_str(&IntelligenceRequest::UiAction { action: &typed }.encode(PROTOCOL_VERSION, "r-7")).unwrap();
+    assert_eq!(encoded, json!({
+        "type": "request_ui_action",
+        "requestId": "r-7",
+        "action": { "kind": "type_text", "text": "Buy milk", "element": "titleField" },
+    }));
+
+    let done = decode_message(PROTOCOL_VERSION, r#"{"type":"ui_action_result","requestId":"r-7"}"#).unwrap();
+    let failed = decode_message(PROTOCOL_VERSION, r#"{"type":"ui_action_result","requestId":"r-8","error":"No element titleField"}"#).unwrap();
+    assert_eq!(done, Some(IntelligenceMessage::UiActionResult { request_id: "r-7".to_string(), error: None }));
+    assert_eq!(failed, Some(IntelligenceMessage::UiActionResult {
+        request_id: "r-8".to_string(),
+        error: Some("No element titleField".to_string()),
+    }));
+    assert_eq!(IntelligenceRequest::UiAction { action: &typed }.capability(), ServerCapability::UiInteraction);
+}
+
+#[tokio::test]
+async fn test_steps_run_in_order() {
+    let driver = Arc::new(FakeDriver::new(vec![Ok(screen(&["titleField", "saveButton"]))]));
+    let script = UiScript::parse("tap addTaskButton\nwait titleField\ntype \"Buy milk\" into titleField\ntap saveButton").unwrap();
+
+    let run = UiScriptRunner::new(driver.clone()).run(UDID, &script).await;
+
+    assert!(run.passed(), "{:?}", run.failure);
+    assert_eq!(run.total_steps, 4);
+    let completed: Vec<(usize, &str)> = run.completed.iter().map(|step| (step.line, step.step.as_str())).collect();
+    assert_eq!(completed, vec![
+        (1, "tap addTaskButton"),
+        (2, "wait titleField 5000ms"),
+        (3, "type \"Buy milk\" into titleField"),
+        (4, "tap saveButton"),
+    ]);
+    assert_eq!(driver.performed(), vec![
+        UiAction::Tap { element: "addTaskButton".to_string() },
+        UiAction::TypeText { text: "Buy milk".to_string(), element: Some("titleField".to_string()) },
+        UiAction::Tap { element: "saveButton".to_string() },
+    ]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_wait_polls_until_the_element_is_visible() {
+    let mut hidden = screen(&["taskDetail"]);
+    hidden.children[0].hidden = true;
+    let driver = Arc::new(FakeDriver::new(vec![
+        Ok(screen(&["taskList"])),
+        Err(AxiomMCPError::connectivity("intelligence", "request_accessibility_tree", "Changing screens")),
+        Ok(hidden),
+        Ok(screen(&["taskList", "taskDetail"])),
+    ]));
+    let runner = UiScriptRunner::new(driver.clone()).with_poll_interval(Duration::from_millis(100));
+
+    let run = runner.run(UDID, &UiScript::parse("wait taskDetail 1s").unwrap()).await;
+
+    assert!(run.passed(), "{:?}", run.failure);
+    assert_eq!(*driver.tree_reads.lock().unwrap(), 4);
+    assert_eq!(run.completed[0].duration_ms, 300);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_timed_out_wait_keeps_the_screen_and_element_tree() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(ScreenshotStore::new(ScreenshotStorageConfig { dir: dir.path().to_path_buf(), ..Default::default() }));
+    let driver = Arc::new(FakeDriver::new(vec![Ok(screen(&["taskList"]))]));
+    let runner = UiScriptRunner::new(driver.clone()).with_store(store).with_poll_interval(Duration::from_millis(200));
+    let script = UiScript::parse("tap addTaskButton\n# the sheet slides up\nwait taskSheet 500ms\ntap saveButton").unwrap();
+
+    let run = runner.run(UDID, &script).await;
+
+    assert!(!run.passed());
+    assert_eq!(run.completed.len(), 1);
+    let failure = run.failure.as_ref().unwrap();
+    assert_eq!((failure.index, failure.line, failure.step.as_str()), (1, 3, "wait taskSheet 500ms"));
+    assert_eq!(failure.error, "taskSheet did not appear within 500ms");
+    assert_eq!(failure.to_string(), "step 2 (line 3, `wait taskSheet 500ms`) failed: taskSheet did not appear within 500ms");
+    let screenshot = failure.screenshot.as_ref().unwrap();
+    assert!(screenshot.path.starts_with(dir.path()));
+    assert!(screenshot.path.exists());
+    assert_eq!(failure.element_tree, Some(screen(&["taskList"])));
+    // The step after the failed one never ran
+    assert_eq!(driver.performed().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_unreadable_tree_is_named_when_a_wait_times_out() {
+    let driver = Arc::new(FakeDriver::new(vec![Err(AxiomMCPError::connectivity(
+        "intelligence",
+        "request_accessibility_tree",
+        "Intelligence connection closed",
+    ))]));
+
+    let run = UiScriptRunner::new(driver).run(UDID, &UiScript::parse("wait taskSheet 1s").unwrap()).await;
+
+    let failure = run.failure.unwrap();
+    assert_eq!(failure.error, "taskSheet did not appear within 1s; the element tree could not be read: Intelligence connection closed");
+    assert_eq!(failure.element_tree, None);
+    assert_eq!(failure.screenshot, None);
+}
+
+#[tokio::test]
+async fn test_failed_action_stops_the_script() {
+    let mut driver = FakeDriver::new(vec![Ok(screen(&["titleField"]))]);
+    driver.failing_tap = Some("saveButton".to_string());
+    let driver = Arc::new(driver);
+    let script = UiScript::parse("type \"Buy milk\"\ntap saveButton\nswipe down").unwrap();
+
+    let run = UiScriptRunner::new(driver.clone()).run(UDID, &script).await;
+    let result = ToolResult::UiScript(run.clone());
+
+    let failure = run.failure.unwrap();
+    assert_eq!((failure.index, failure.line), (1, 2));
+    assert_eq!(failure.error, "No hittable element saveButton");
+    assert_eq!(driver.performed(), vec![UiAction::TypeText { text: "Buy milk".to_string(), element: None }]);
+    assert!(!result.is_success());
+    assert_eq!(
+        result.summary(),
+        format!("UI script on {}: step 2 (line 2, `tap saveButton`) failed: No hittable element saveButton", UDID)
+    );
+}
+
+#[test]
+fn test_find_element_skips_hidden_subtrees() {
+    let mut tree = element("root", vec![element("sheet", vec![element("saveButton", Vec::new())]), element("toolbar", vec![])]);
+    assert!(find_element(&tree, "saveButton").is_some());
+
+    tree.children[0].hidden = true;
+    assert!(find_element(&tree, "saveButton").is_none());
+    assert_eq!(find_element(&tree, "toolbar").map(|found| found.identifier.as_str()), Some("toolbar"));
+}
+
+#[test]
+fn test_locale_and_paste_steps_parse_and_print_back() {
+    let script = UiScript::parse(
+        "setLocale de_DE\n\
+         setLocale ja_JP keyboard \"ja_JP-Kana@sw=Kana;hw=Automatic\"\n\
+         paste \"Milch 🥛\" into noteField\n\
+         paste \"牛乳\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(steps(&script), vec![
+        (1, UiStep::SetLocale(SimulatorLocale::parse("de_DE").unwrap())),
+        (2, UiStep::SetLocale(SimulatorLocale::parse("ja_JP").unwrap().with_keyboard("ja_JP-Kana@sw=Kana;hw=Automatic"))),
+        (3, UiStep::Paste { text: "Milch 🥛".to_string(), element: Some("noteField".to_string()) }),
+        (4, UiStep::Paste { text: "牛乳".to_string(), element: None }),
+    ]);
+    let printed: Vec<String> = script.steps.iter().map(|step| step.step.to_string()).collect();
+    assert_eq!(printed, vec![
+        "setLocale de_DE",
+        "setLocale ja_JP keyboard ja_JP-Kana@sw=Kana;hw=Automatic",
+        "paste \"Milch 🥛\" into noteField",
+        "paste \"牛乳\"",
+    ]);
+    assert_eq!(UiScript::parse(&printed.join("\n")).unwrap(), script);
+}
+
+#[tokio::test]
+async fn test_text_is_pasted_when_the_keyboard_or_characters_rule_out_typing() {
+    let driver = Arc::new(FakeDriver::new(vec![Ok(screen(&["titleField", "noteField"]))]));
+    let script = UiScript::parse(
+        "type \"Buy milk\" into titleField\n\
+         type \"Milch 🥛\" into noteField\n\
+         setLocale de_DE\n\
+         type \"Buy milk\" into titleField\n\
+         paste \"plain\"",
+    )
+    .unwrap();
+
+    let run = UiScriptRunner::new(driver.clone()).run(UDID, &script).await;
+
+    assert!(run.passed(), "{:?}", run.failure);
+    let pasted = Some(TextEntry::Pasted { after_typing_failed: false });
+    assert_eq!(text_entries(&run), vec![Some(TextEntry::Typed), pasted.clone(), None, pasted.clone(), pasted]);
+    assert_eq!(*driver.locales.lock().unwrap(), vec![SimulatorLocale::parse("de_DE").unwrap()]);
+    assert_eq!(*driver.pasteboard.lock().unwrap(), vec!["Milch 🥛", "Buy milk", "plain"]);
+    assert_eq!(driver.performed(), vec![
+        UiAction::TypeText { text: "Buy milk".to_string(), element: Some("titleField".to_string()) },
+        UiAction::Paste { element: Some("noteField".to_string()) },
+        UiAction::Paste { element: Some("titleField".to_string()) },
+        UiAction::Paste { element: None },
+    ]);
+}
+
+#[tokio::test]
+async fn test_starting_locale_picks_the_strategy_until_a_step_changes_it() {
+    let driver = Arc::new(FakeDriver::new(vec![Ok(screen(&["titleField"]))]));
+    let script = UiScript::parse("type \"Buy milk\"\nsetLocale en_US\ntype \"Buy milk\"").unwrap();
+
+    let run = UiScriptRunner::new(driver.clone()).with_locale(SimulatorLocale::parse("fr_FR").unwrap()).run(UDID, &script).await;
+
+    assert_eq!(text_entries(&run), vec![Some(TextEntry::Pasted { after_typing_failed: false }), None, Some(TextEntry::Typed)]);
+}
+
+#[tokio::test]
+async fn test_text_is_pasted_when_typing_fails() {
+    let mut driver = FakeDriver::new(vec![Ok(screen(&["titleField"]))]);
+    driver.failing_typing = true;
+    let driver = Arc::new(driver);
+
+    let run = UiScriptRunner::new(driver.clone()).run(UDID, &UiScript::parse("type \"Buy milk\" into titleField").unwrap()).await;
+
+    assert!(run.passed(), "{:?}", run.failure);
+    assert_eq!(text_entries(&run), vec![Some(TextEntry::Pasted { after_typing_failed: true })]);
+    assert_eq!(*driver.pasteboard.lock().unwrap(), vec!["Buy milk"]);
+    assert_eq!(driver.performed(), vec![UiAction::Paste { element: Some("titleField".to_string()) }]);
+}
+
+#[tokio::test]
+async fn test_secure_fields_are_reported_instead_of_typed_into() {
+    let mut tree = screen(&["emailField", "passwordField"]);
+    tree.children[1].element_type = ElementType::SecureTextField;
+    let driver = Arc::new(FakeDriver::new(vec![Ok(tree)]));
+    let script = UiScript::parse("type \"me@example.com\" into emailField\ntype \"hunter2\" into passwordField\ntap signInButton").unwrap();
+
+    let run = UiScriptRunner::new(driver.clone()).run(UDID, &script).await;
+    let result = ToolResult::UiScript(run.clone());
+
+    assert!(run.passed(), "{:?}", run.failure);
+    assert_eq!(text_entries(&run)[1], Some(TextEntry::SecureField { element: "passwordField".to_string() }));
+    assert_eq!(run.secure_fields(), vec!["passwordField"]);
+    assert_eq!(driver.performed(), vec![
+        UiAction::TypeText { text: "me@example.com".to_string(), element: Some("emailField".to_string()) },
+        UiAction::Tap { element: "signInButton".to_string() },
+    ]);
+    assert!(driver.pasteboard.lock().unwrap().is_empty());
+    assert_eq!(
+        result.summary(),
+        format!("UI script passed 3 steps on {}, leaving text out of the secure fields passwordField", UDID)
+    );
+}
@@ -0,0 +1,326 @@
+use axiom_applications_observability::simulator::StatusBarOverride;
+use axiom_applications_observability::types::*;
+use axiom_applications_observability::visual_regression::*;
+use image::{Rgba, RgbaImage};
+use std::collections::BTreeMap;
+
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+/// 100x100 white image, so every changed pixel is 0.01% of it
+fn blank() -> RgbaImage {
+    RgbaImage::from_pixel(100, 100, WHITE)
+}
+
+fn paint(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y..y + height {
+        for px in x..x + width {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn screenshot(image: &RgbaImage, orientation: &str, scale: f64) -> Screenshot {
+    Screenshot {
+        id: "screenshot".to_string(),
+        client_id: "test-client".to_string(),
+        configuration: ScreenshotConfiguration {
+            device_type: "iPhone 15 Pro".to_string(),
+            screen_size: ScreenSize { width: 100.0 / scale, height: 100.0 / scale },
+            orientation: orientation.to_string(),
+            scale,
+            color_scheme: "dark".to_string(),
+            capture_mode: "standard".to_string(),
+            status_bar: None,
+            runtime: None,
+            window: Default::default(),
+        },
+        image_data: encode_png(image).unwrap(),
+        image: None,
+        metadata: ScreenshotMetadata {
+            timestamp: chrono::Utc::now(),
+            device_info: DeviceInfo {
+                model: "iPhone 15 Pro".to_string(),
+                screen_size: ScreenSize { width: 100.0 / scale, height: 100.0 / scale },
+                orientation: orientation.to_string(),
+                udid: None,
+            },
+            app_state: AppState {
+                view_hierarchy: "TaskListView".to_string(),
+                active_context: "TaskListContext".to_string(),
+                view_tree: None,
+            },
+            overrides: Default::default(),
+        },
+    }
+}
+
+fn config(dir: &std::path::Path, threshold: f64) -> VisualRegressionConfig {
+    VisualRegressionConfig {
+        baseline_dir: dir.to_path_buf(),
+        threshold,
+        ..Default::default()
+    }
+}
+
+fn request(mode: RegressionMode, screenshots: Vec<Screenshot>) -> RegressionCheckRequest {
+    RegressionCheckRequest {
+        mode,
+        screen: "Task List".to_string(),
+        screenshots,
+    }
+}
+
+#[test]
+fn test_identical_images_have_no_diff() {
+    let diff = diff_images(&blank(), &blank(), 0, &[]);
+
+    assert_eq!(diff.compared_pixels, 10_000);
+    assert_eq!(diff.changed_pixels, 0);
+    assert_eq!(diff.diff_ratio, 0.0);
+    assert!(diff.changed_regions.is_empty());
+}
+
+#[test]
+fn test_diff_ratio_and_bounding_box_of_a_changed_block() {
+    let mut current = blank();
+    paint(&mut current, 10, 20, 5, 4, BLACK);
+
+    let diff = diff_images(&blank(), &current, 0, &[]);
+
+    assert_eq!(diff.changed_pixels, 20);
+    assert_eq!(diff.diff_ratio, 0.002);
+    assert_eq!(diff.changed_regions, vec![PixelRect { x: 10, y: 20, width: 5, height: 4 }]);
+    assert_eq!(*diff.diff_image.get_pixel(12, 21), Rgba([255, 0, 64, 255]));
+}
+
+#[test]
+fn test_separate_changes_get_separate_regions_and_close_ones_merge() {
+    let mut current = blank();
+    paint(&mut current, 0, 0, 2, 2, BLACK);
+    // 5 pixels away from the first block, within the merge distance
+    paint(&mut current, 7, 0, 2, 2, BLACK);
+    paint(&mut current, 80, 80, 3, 3, BLACK);
+
+    let diff = diff_images(&blank(), &current, 0, &[]);
+
+    assert_eq!(diff.changed_regions, vec![
+        PixelRect { x: 0, y: 0, width: 9, height: 2 },
+        PixelRect { x: 80, y: 80, width: 3, height: 3 },
+    ]);
+}
+
+#[test]
+fn test_pixel_tolerance_absorbs_small_channel_differences() {
+    let mut current = blank();
+    paint(&mut current, 0, 0, 10, 10, Rgba([247, 255, 255, 255]));
+
+    assert_eq!(diff_images(&blank(), &current, 8, &[]).changed_pixels, 0);
+    assert_eq!(diff_images(&blank(), &current, 7, &[]).changed_pixels, 100);
+}
+
+#[test]
+fn test_ignored_pixels_are_neither_compared_nor_counted() {
+    let mut current = blank();
+    paint(&mut current, 0, 0, 100, 10, BLACK);
+    paint(&mut current, 50, 50, 1, 1, BLACK);
+
+    let status_bar = PixelRect { x: 0, y: 0, width: 100, height: 10 };
+    let diff = diff_images(&blank(), &current, 0, &[status_bar]);
+
+    assert_eq!(diff.compared_pixels, 9_000);
+    assert_eq!(diff.changed_pixels, 1);
+    assert_eq!(diff.changed_regions, vec![PixelRect { x: 50, y: 50, width: 1, height: 1 }]);
+}
+
+#[test]
+fn test_baseline_key_id_is_a_safe_relative_path() {
+    let key = BaselineKey {
+        screen: "Task List / Edit".to_string(),
+        device_type: "iPad Pro 13-inch (M4)".to_string(),
+        orientation: "landscape".to_string(),
+        color_scheme: "Dark".to_string(),
+    };
+
+    assert_eq!(key.id(), "task-list-edit/ipad-pro-13-inch-m4/landscape-dark");
+}
+
+#[tokio::test]
+async fn test_record_writes_baselines_and_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let checker = VisualRegressionChecker::new(config(dir.path(), 0.001));
+
+    let report = checker
+        .run(&request(RegressionMode::Record, vec![screenshot(&blank(), "portrait", 1.0), screenshot(&blank(), "landscape", 1.0)]))
+        .await
+        .unwrap();
+
+    assert_eq!(report.mode, Some(RegressionMode::Record));
+    assert!(report.configurations.iter().all(|diff| diff.status == DiffStatus::Recorded));
+    let manifest = checker.store().manifest().await.unwrap();
+    assert_eq!(
+        manifest.baselines.keys().collect::<Vec<_>>(),
+        vec!["task-list/iphone-15-pro/landscape-dark", "task-list/iphone-15-pro/portrait-dark"]
+    );
+    let entry = &manifest.baselines["task-list/iphone-15-pro/portrait-dark"];
+    assert_eq!((entry.width, entry.height), (100, 100));
+    assert!(dir.path().join("task-list/iphone-15-pro/portrait-dark.png").exists());
+}
+
+#[tokio::test]
+async fn test_check_without_baseline_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let checker = VisualRegressionChecker::new(config(dir.path(), 0.001));
+
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&blank(), "portrait", 1.0)])).await.unwrap();
+
+    assert_eq!(report.configurations[0].status, DiffStatus::MissingBaseline);
+    assert_eq!(report.regressions_detected, 0);
+    assert!(!report.passed());
+}
+
+#[tokio::test]
+async fn test_diff_exactly_at_threshold_passes_and_one_pixel_over_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    // 10 of 10,000 pixels
+    let checker = VisualRegressionChecker::new(config(dir.path(), 0.001));
+    checker.run(&request(RegressionMode::Record, vec![screenshot(&blank(), "portrait", 1.0)])).await.unwrap();
+
+    let mut at_threshold = blank();
+    paint(&mut at_threshold, 0, 50, 10, 1, BLACK);
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&at_threshold, "portrait", 1.0)])).await.unwrap();
+    let diff = &report.configurations[0];
+    assert_eq!(diff.status, DiffStatus::WithinThreshold);
+    assert_eq!(diff.diff_ratio, 0.001);
+    assert!(report.passed());
+    assert!(diff.diff_image.as_ref().unwrap().exists());
+
+    let mut over_threshold = at_threshold.clone();
+    over_threshold.put_pixel(99, 99, BLACK);
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&over_threshold, "portrait", 1.0)])).await.unwrap();
+    let diff = &report.configurations[0];
+    assert_eq!(diff.status, DiffStatus::Regressed);
+    assert_eq!(diff.changed_regions.len(), 2);
+    assert_eq!(report.regressions_detected, 1);
+    assert!(!report.passed());
+}
+
+#[tokio::test]
+async fn test_unchanged_screenshot_writes_no_diff_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let checker = VisualRegressionChecker::new(config(dir.path(), 0.0));
+    checker.run(&request(RegressionMode::Record, vec![screenshot(&blank(), "portrait", 1.0)])).await.unwrap();
+
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&blank(), "portrait", 1.0)])).await.unwrap();
+
+    assert_eq!(report.configurations[0].status, DiffStatus::Unchanged);
+    assert!(report.configurations[0].diff_image.is_none());
+    assert!(report.passed());
+}
+
+#[tokio::test]
+async fn test_per_screen_ignore_region_masks_the_status_bar_clock() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = config(dir.path(), 0.0);
+    config.screens = BTreeMap::from([(
+        "Task List".to_string(),
+        ScreenRegressionSettings {
+            threshold: None,
+            // In points; 3x scale makes it the top 30 pixel rows
+            ignore_regions: vec![IgnoreRegion {
+                name: Some("clock".to_string()),
+                status_bar: true,
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 10.0,
+            }],
+        },
+    )]);
+    let checker = VisualRegressionChecker::new(config);
+    checker.run(&request(RegressionMode::Record, vec![screenshot(&blank(), "portrait", 3.0)])).await.unwrap();
+
+    let mut clock_changed = blank();
+    paint(&mut clock_changed, 40, 5, 20, 20, BLACK);
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&clock_changed, "portrait", 3.0)])).await.unwrap();
+    assert_eq!(report.configurations[0].status, DiffStatus::Unchanged);
+
+    // Just below the masked rows still counts
+    let mut content_changed = clock_changed.clone();
+    content_changed.put_pixel(40, 30, BLACK);
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&content_changed, "portrait", 3.0)])).await.unwrap();
+    assert_eq!(report.configurations[0].status, DiffStatus::Regressed);
+    assert_eq!(report.configurations[0].changed_regions, vec![PixelRect { x: 40, y: 30, width: 1, height: 1 }]);
+}
+
+#[tokio::test]
+async fn test_status_bar_region_is_compared_when_the_status_bar_was_overridden() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = config(dir.path(), 0.0);
+    let status_bar = IgnoreRegion { name: Some("status bar".to_string()), status_bar: true, x: 0.0, y: 0.0, width: 100.0, height: 10.0 };
+    let banner = IgnoreRegion { name: Some("ad banner".to_string()), status_bar: false, x: 0.0, y: 80.0, width: 100.0, height: 20.0 };
+    config.screens.insert("Task List".to_string(), ScreenRegressionSettings {
+        threshold: None,
+        ignore_regions: vec![status_bar, banner],
+    });
+    let checker = VisualRegressionChecker::new(config);
+    let overridden = |image: &RgbaImage| {
+        let mut screenshot = screenshot(image, "portrait", 1.0);
+        screenshot.configuration.status_bar = Some(StatusBarOverride::marketing());
+        screenshot.metadata.overrides = screenshot.configuration.overrides();
+        screenshot
+    };
+    checker.run(&request(RegressionMode::Record, vec![overridden(&blank())])).await.unwrap();
+
+    let mut changed = blank();
+    paint(&mut changed, 40, 2, 5, 5, BLACK);
+    paint(&mut changed, 40, 90, 5, 5, BLACK);
+    let report = checker.run(&request(RegressionMode::Check, vec![overridden(&changed)])).await.unwrap();
+    assert_eq!(report.configurations[0].status, DiffStatus::Regressed);
+    assert_eq!(report.configurations[0].changed_regions, vec![PixelRect { x: 40, y: 2, width: 5, height: 5 }]);
+
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&changed, "portrait", 1.0)])).await.unwrap();
+    assert_eq!(report.configurations[0].status, DiffStatus::Unchanged);
+}
+
+#[tokio::test]
+async fn test_per_screen_threshold_overrides_the_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = config(dir.path(), 0.0);
+    config.screens.insert("Task List".to_string(), ScreenRegressionSettings {
+        threshold: Some(0.05),
+        ignore_regions: Vec::new(),
+    });
+    let checker = VisualRegressionChecker::new(config);
+    checker.run(&request(RegressionMode::Record, vec![screenshot(&blank(), "portrait", 1.0)])).await.unwrap();
+
+    let mut current = blank();
+    paint(&mut current, 0, 0, 10, 10, BLACK);
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&current, "portrait", 1.0)])).await.unwrap();
+
+    assert_eq!(report.configurations[0].threshold, 0.05);
+    assert_eq!(report.configurations[0].status, DiffStatus::WithinThreshold);
+}
+
+#[tokio::test]
+async fn test_size_change_is_a_regression() {
+    let dir = tempfile::tempdir().unwrap();
+    let checker = VisualRegressionChecker::new(config(dir.path(), 0.5));
+    checker.run(&request(RegressionMode::Record, vec![screenshot(&blank(), "portrait", 1.0)])).await.unwrap();
+
+    let taller = RgbaImage::from_pixel(100, 120, WHITE);
+    let report = checker.run(&request(RegressionMode::Check, vec![screenshot(&taller, "portrait", 1.0)])).await.unwrap();
+
+    assert_eq!(report.configurations[0].status, DiffStatus::SizeChanged);
+    assert_eq!(report.regressions_detected, 1);
+}
+
+#[tokio::test]
+async fn test_screenshot_that_is_not_a_png_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let checker = VisualRegressionChecker::new(config(dir.path(), 0.001));
+    let mut not_png = screenshot(&blank(), "portrait", 1.0);
+    not_png.image_data = vec![1, 2, 3, 4];
+
+    assert!(checker.run(&request(RegressionMode::Record, vec![not_png])).await.is_err());
+}
@@ -1,11 +1,28 @@
 use anyhow::Result;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::time::Duration;
+use std::sync::Arc;
 use tracing::{debug, warn, info};
 
+use crate::clients::auth::DashboardAuth;
+use crate::clients::http::HttpClient;
+
+/// The dashboard answered a request with an error status
+#[derive(Debug, thiserror::Error)]
+#[error("{context}, status: {status}")]
+pub struct DashboardStatusError {
+    pub context: String,
+    pub status: reqwest::StatusCode,
+}
+
+impl DashboardStatusError {
+    pub fn new(context: impl Into<String>, status: reqwest::StatusCode) -> Self {
+        Self { context: context.into(), status }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AspireResource {
     pub name: String,
@@ -41,31 +58,126 @@ pub struct AspireLogEntry {
     pub properties: Option<HashMap<String, Value>>,
 }
 
+/// A log record from the dashboard's OTLP export, kept structured
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceLogLine {
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    pub time_unix_nano: u64,
+    pub level: String,
+    /// OTLP severity number (1-24), derived from `level` when the record omits it
+    pub severity_number: u8,
+    pub service: String,
+    pub message: String,
+    pub attributes: HashMap<String, Value>,
+}
+
+/// One OTLP metric data point, flattened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricPoint {
+    pub name: String,
+    /// UCUM unit such as `s`, `ms` or `By`
+    pub unit: String,
+    pub time_unix_nano: u64,
+    pub start_time_unix_nano: u64,
+    /// Values accumulate since `start_time_unix_nano` rather than covering one interval
+    pub cumulative: bool,
+    pub attributes: HashMap<String, Value>,
+    pub value: MetricValue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricValue {
+    /// Gauge or sum
+    Number { value: f64 },
+    Histogram {
+        count: u64,
+        sum: Option<f64>,
+        /// One more entry than `explicit_bounds`; the last bucket is unbounded
+        bucket_counts: Vec<u64>,
+        explicit_bounds: Vec<f64>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanStatus {
+    Unset,
+    Ok,
+    Error,
+}
+
+/// One OTLP span, flattened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceSpan {
+    /// Lowercase hex
+    pub trace_id: String,
+    pub span_id: String,
+    /// `None` for root spans
+    pub parent_span_id: Option<String>,
+    pub name: String,
+    pub service: String,
+    /// `server`, `client`, `internal`, `producer`, `consumer` or `unspecified`
+    pub kind: String,
+    pub start_time_unix_nano: u64,
+    pub end_time_unix_nano: u64,
+    pub status: SpanStatus,
+    pub status_message: Option<String>,
+    pub attributes: HashMap<String, Value>,
+    pub events: Vec<SpanEvent>,
+}
+
+impl TraceSpan {
+    pub fn duration_nanos(&self) -> u64 {
+        self.end_time_unix_nano.saturating_sub(self.start_time_unix_nano)
+    }
+}
+
+/// Something that happened during a span, such as a recorded exception
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpanEvent {
+    pub name: String,
+    pub time_unix_nano: u64,
+    pub attributes: HashMap<String, Value>,
+}
+
+/// `AGGREGATION_TEMPORALITY_CUMULATIVE` in OTLP
+const OTLP_CUMULATIVE: u64 = 2;
+
+/// `STATUS_CODE_ERROR` in OTLP
+const OTLP_STATUS_ERROR: u64 = 2;
+
 pub struct AspireDashboardClient {
-    client: Client,
+    http: HttpClient,
     base_url: String,
 }
 
 impl AspireDashboardClient {
     pub fn new(dashboard_url: &str) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .danger_accept_invalid_certs(true) // For local development
-            .build()
-            .expect("Failed to create Aspire dashboard client");
-        
+        Self::with_http_client(dashboard_url, HttpClient::new())
+    }
+    
+    /// Use a client configured from `Settings::http`
+    pub fn with_http_client(dashboard_url: &str, http: HttpClient) -> Self {
         Self {
-            client,
+            http,
             base_url: dashboard_url.to_string(),
         }
     }
     
+    /// Authenticate every dashboard request with `auth`
+    pub fn with_auth(mut self, auth: DashboardAuth) -> Self {
+        self.http = self.http.with_auth(Arc::new(auth));
+        self
+    }
+    
     pub async fn get_resources(&self) -> Result<Vec<AspireResource>> {
         debug!("Getting resources from Aspire dashboard");
         
         let url = format!("{}/api/v1/resources", self.base_url);
         
-        match self.client.get(&url).send().await {
+        match self.http.send_authorized(|client| client.get(&url)).await {
             Ok(response) => {
                 if response.status().is_success() {
                     let resources: Vec<AspireResource> = response.json().await?;
@@ -78,7 +190,7 @@ impl AspireDashboardClient {
             }
             Err(e) => {
                 warn!("Failed to get resources: {}", e);
-                Err(e.into())
+                Err(e)
             }
         }
     }
@@ -95,20 +207,20 @@ impl AspireDashboardClient {
         
         let url = format!("{}/api/v1/resources/{}/restart", self.base_url, resource_name);
         
-        match self.client.post(&url).send().await {
+        match self.http.send_authorized(|client| client.post(&url)).await {
             Ok(response) => {
                 if response.status().is_success() {
                     debug!("Successfully restarted resource: {}", resource_name);
                     Ok(())
                 } else {
-                    let error_msg = format!("Failed to restart resource, status: {}", response.status());
-                    warn!("{}", error_msg);
-                    Err(anyhow::anyhow!(error_msg))
+                    let error = DashboardStatusError::new("Failed to restart resource", response.status());
+                    warn!("{}", error);
+                    Err(error.into())
                 }
             }
             Err(e) => {
                 warn!("Failed to send restart request: {}", e);
-                Err(e.into())
+                Err(e)
             }
         }
     }
@@ -122,7 +234,7 @@ impl AspireDashboardClient {
             url.push_str(&format!("?tail={}", tail_count));
         }
         
-        match self.client.get(&url).send().await {
+        match self.http.send_authorized(|client| client.get(&url)).await {
             Ok(response) => {
                 if response.status().is_success() {
                     // The actual log format may vary, this is a simplified implementation
@@ -141,12 +253,113 @@ impl AspireDashboardClient {
         }
     }
     
+    /// Fetch OTLP log records for a resource, oldest first
+    ///
+    /// With `after_unix_nano` only records newer than that timestamp are returned.
+    pub async fn get_otlp_logs(&self, resource_name: &str, after_unix_nano: Option<u64>) -> Result<Vec<ServiceLogLine>> {
+        debug!("Getting OTLP logs for resource: {}", resource_name);
+        
+        let url = format!("{}/api/v1/telemetry/logs", self.base_url);
+        let response = self.http.send_authorized(|client| {
+            let request = client.get(&url).query(&[("resource", resource_name)]);
+            match after_unix_nano {
+                Some(after) => request.query(&[("after", after.to_string())]),
+                None => request,
+            }
+        }).await?;
+        if !response.status().is_success() {
+            return Err(DashboardStatusError::new("Failed to get logs", response.status()).into());
+        }
+        
+        let payload: Value = response.json().await?;
+        let mut lines = parse_otlp_logs(&payload, resource_name);
+        
+        // Older dashboards ignore `after`, so the cursor is enforced here as well
+        if let Some(after) = after_unix_nano {
+            lines.retain(|line| line.time_unix_nano > after);
+        }
+        lines.sort_by_key(|line| line.time_unix_nano);
+        
+        debug!("Retrieved {} OTLP log records", lines.len());
+        Ok(lines)
+    }
+    
+    /// Fetch OTLP metric data points for a resource, oldest first
+    ///
+    /// With `after_unix_nano` only points newer than that timestamp are returned.
+    pub async fn get_otlp_metrics(&self, resource_name: &str, after_unix_nano: Option<u64>) -> Result<Vec<MetricPoint>> {
+        debug!("Getting OTLP metrics for resource: {}", resource_name);
+        
+        let url = format!("{}/api/v1/telemetry/metrics", self.base_url);
+        let response = self.http.send_authorized(|client| {
+            let request = client.get(&url).query(&[("resource", resource_name)]);
+            match after_unix_nano {
+                Some(after) => request.query(&[("after", after.to_string())]),
+                None => request,
+            }
+        }).await?;
+        if !response.status().is_success() {
+            return Err(DashboardStatusError::new("Failed to get metrics", response.status()).into());
+        }
+        
+        let payload: Value = response.json().await?;
+        let mut points = parse_otlp_metrics(&payload);
+        
+        if let Some(after) = after_unix_nano {
+            points.retain(|point| point.time_unix_nano > after);
+        }
+        points.sort_by_key(|point| point.time_unix_nano);
+        
+        debug!("Retrieved {} OTLP metric points", points.len());
+        Ok(points)
+    }
+    
+    /// Fetch the OTLP spans the dashboard holds for every resource, oldest first
+    pub async fn get_otlp_traces(&self) -> Result<Vec<TraceSpan>> {
+        debug!("Getting OTLP traces");
+        
+        let url = format!("{}/api/v1/telemetry/traces", self.base_url);
+        let response = self.http.send_authorized(|client| client.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(DashboardStatusError::new("Failed to get traces", response.status()).into());
+        }
+        
+        let payload: Value = response.json().await?;
+        let mut spans = parse_otlp_traces(&payload, "unknown");
+        spans.sort_by_key(|span| span.start_time_unix_nano);
+        
+        debug!("Retrieved {} OTLP spans", spans.len());
+        Ok(spans)
+    }
+    
+    /// Fetch the spans of one trace, oldest first; empty when the dashboard
+    /// does not know the trace
+    pub async fn get_otlp_trace(&self, trace_id: &str) -> Result<Vec<TraceSpan>> {
+        debug!("Getting OTLP trace: {}", trace_id);
+        
+        let url = format!("{}/api/v1/telemetry/traces/{}", self.base_url, trace_id);
+        let response = self.http.send_authorized(|client| client.get(&url)).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            return Err(DashboardStatusError::new("Failed to get trace", response.status()).into());
+        }
+        
+        let payload: Value = response.json().await?;
+        let trace_id = trace_id.to_ascii_lowercase();
+        let mut spans = parse_otlp_traces(&payload, "unknown");
+        spans.retain(|span| span.trace_id == trace_id);
+        spans.sort_by_key(|span| span.start_time_unix_nano);
+        Ok(spans)
+    }
+    
     pub async fn stream_logs(&self, resource_name: &str) -> Result<tokio::sync::mpsc::Receiver<AspireLogEntry>> {
         debug!("Starting log stream for resource: {}", resource_name);
         
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let url = format!("{}/api/v1/resources/{}/logs/stream", self.base_url, resource_name);
-        let client = self.client.clone();
+        let http = self.http.clone();
         
         tokio::spawn(async move {
             // This would implement SSE (Server-Sent Events) or WebSocket streaming
@@ -157,7 +370,7 @@ impl AspireDashboardClient {
                 interval.tick().await;
                 
                 // In a real implementation, this would be a persistent connection
-                match client.get(&url).send().await {
+                match http.send_authorized(|client| client.get(&url)).await {
                     Ok(response) => {
                         if response.status().is_success() {
                             if let Ok(logs) = response.json::<Vec<AspireLogEntry>>().await {
@@ -186,7 +399,7 @@ impl AspireDashboardClient {
         
         let url = format!("{}/api/v1/info", self.base_url);
         
-        match self.client.get(&url).send().await {
+        match self.http.send_authorized(|client| client.get(&url)).await {
             Ok(response) => {
                 if response.status().is_success() {
                     let info: Value = response.json().await?;
@@ -213,7 +426,7 @@ impl AspireDashboardClient {
         // Try the resources endpoint as health check
         let url = format!("{}/api/v1/resources", self.base_url);
         
-        match self.client.head(&url).send().await {
+        match self.http.send_authorized(|client| client.head(&url)).await {
             Ok(response) => Ok(response.status().is_success()),
             Err(_) => Ok(false),
         }
@@ -224,7 +437,7 @@ impl AspireDashboardClient {
         
         let url = format!("{}/api/v1/metrics", self.base_url);
         
-        match self.client.get(&url).send().await {
+        match self.http.send_authorized(|client| client.get(&url)).await {
             Ok(response) => {
                 if response.status().is_success() {
                     let metrics: Value = response.json().await?;
@@ -253,7 +466,7 @@ impl AspireDashboardClient {
             "command": command
         });
         
-        match self.client.post(&url).json(&payload).send().await {
+        match self.http.send_authorized(|client| client.post(&url).json(&payload)).await {
             Ok(response) => {
                 if response.status().is_success() {
                     let result: Value = response.json().await?;
@@ -267,4 +480,308 @@ impl AspireDashboardClient {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Flatten an OTLP/JSON `ExportLogsServiceRequest` into log lines
+///
+/// `default_service` is used for records whose resource has no `service.name`.
+pub fn parse_otlp_logs(payload: &Value, default_service: &str) -> Vec<ServiceLogLine> {
+    let mut lines = Vec::new();
+    
+    for resource_logs in otlp_array(payload, "resourceLogs") {
+        let resource_attributes = otlp_attributes(resource_logs.get("resource").unwrap_or(&Value::Null));
+        let service = resource_attributes
+            .get("service.name")
+            .and_then(|v| v.as_str())
+            .unwrap_or(default_service)
+            .to_string();
+        
+        for scope_logs in otlp_array(resource_logs, "scopeLogs") {
+            for record in otlp_array(scope_logs, "logRecords") {
+                let time_unix_nano = otlp_u64(record.get("timeUnixNano"))
+                    .filter(|t| *t > 0)
+                    .or_else(|| otlp_u64(record.get("observedTimeUnixNano")))
+                    .unwrap_or(0);
+                
+                let level = record.get("severityText")
+                    .and_then(|v| v.as_str())
+                    .filter(|text| !text.is_empty())
+                    .map(|text| text.to_string());
+                let severity_number = otlp_u64(record.get("severityNumber"))
+                    .map(|n| n.min(24) as u8)
+                    .filter(|n| *n > 0)
+                    .or_else(|| level.as_deref().and_then(severity_number_for_level))
+                    .unwrap_or(0);
+                let level = level.unwrap_or_else(|| severity_name(severity_number).to_string());
+                
+                let message = match record.get("body").map(otlp_value) {
+                    Some(Value::String(text)) => text,
+                    Some(Value::Null) | None => String::new(),
+                    Some(other) => other.to_string(),
+                };
+                
+                lines.push(ServiceLogLine {
+                    timestamp: format_unix_nano(time_unix_nano),
+                    time_unix_nano,
+                    level,
+                    severity_number,
+                    service: service.clone(),
+                    message,
+                    attributes: otlp_attributes(record),
+                });
+            }
+        }
+    }
+    
+    lines
+}
+
+/// Flatten an OTLP/JSON `ExportMetricsServiceRequest` into data points
+///
+/// Gauges, sums and explicit-bucket histograms are kept; other metric types are skipped.
+pub fn parse_otlp_metrics(payload: &Value) -> Vec<MetricPoint> {
+    let mut points = Vec::new();
+    
+    for resource_metrics in otlp_array(payload, "resourceMetrics") {
+        for scope_metrics in otlp_array(resource_metrics, "scopeMetrics") {
+            for metric in otlp_array(scope_metrics, "metrics") {
+                let Some(name) = metric.get("name").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let unit = metric.get("unit").and_then(|v| v.as_str()).unwrap_or_default();
+                
+                let (data, histogram) = if let Some(data) = metric.get("gauge") {
+                    (data, false)
+                } else if let Some(data) = metric.get("sum") {
+                    (data, false)
+                } else if let Some(data) = metric.get("histogram") {
+                    (data, true)
+                } else {
+                    continue;
+                };
+                let cumulative = otlp_temporality(data.get("aggregationTemporality")) == Some(OTLP_CUMULATIVE);
+                
+                for point in otlp_array(data, "dataPoints") {
+                    let value = if histogram {
+                        MetricValue::Histogram {
+                            count: otlp_u64(point.get("count")).unwrap_or(0),
+                            sum: point.get("sum").and_then(|v| v.as_f64()),
+                            bucket_counts: otlp_array(point, "bucketCounts").filter_map(|c| otlp_u64(Some(c))).collect(),
+                            explicit_bounds: otlp_array(point, "explicitBounds").filter_map(|b| b.as_f64()).collect(),
+                        }
+                    } else {
+                        let number = point.get("asDouble")
+                            .and_then(|v| v.as_f64())
+                            .or_else(|| match point.get("asInt")? {
+                                // 64-bit integers are encoded as strings
+                                Value::String(text) => text.parse::<i64>().ok().map(|v| v as f64),
+                                other => other.as_f64(),
+                            });
+                        match number {
+                            Some(value) => MetricValue::Number { value },
+                            None => continue,
+                        }
+                    };
+                    
+                    points.push(MetricPoint {
+                        name: name.to_string(),
+                        unit: unit.to_string(),
+                        time_unix_nano: otlp_u64(point.get("timeUnixNano")).unwrap_or(0),
+                        start_time_unix_nano: otlp_u64(point.get("startTimeUnixNano")).unwrap_or(0),
+                        cumulative,
+                        attributes: otlp_attributes(point),
+                        value,
+                    });
+                }
+            }
+        }
+    }
+    
+    points
+}
+
+/// Flatten an OTLP/JSON `ExportTraceServiceRequest` into spans
+///
+/// `default_service` is used for spans whose resource has no `service.name`.
+pub fn parse_otlp_traces(payload: &Value, default_service: &str) -> Vec<TraceSpan> {
+    let mut spans = Vec::new();
+    
+    for resource_spans in otlp_array(payload, "resourceSpans") {
+        let resource_attributes = otlp_attributes(resource_spans.get("resource").unwrap_or(&Value::Null));
+        let service = resource_attributes
+            .get("service.name")
+            .and_then(|v| v.as_str())
+            .unwrap_or(default_service)
+            .to_string();
+        
+        for scope_spans in otlp_array(resource_spans, "scopeSpans") {
+            for span in otlp_array(scope_spans, "spans") {
+                let (Some(trace_id), Some(span_id)) = (otlp_id(span.get("traceId")), otlp_id(span.get("spanId"))) else {
+                    continue;
+                };
+                let status = span.get("status").unwrap_or(&Value::Null);
+                let status_code = match status.get("code") {
+                    Some(Value::String(name)) if name == "STATUS_CODE_ERROR" => Some(OTLP_STATUS_ERROR),
+                    Some(Value::String(name)) if name == "STATUS_CODE_OK" => Some(1),
+                    other => otlp_u64(other),
+                };
+                
+                spans.push(TraceSpan {
+                    trace_id,
+                    span_id,
+                    parent_span_id: otlp_id(span.get("parentSpanId")),
+                    name: span.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                    service: service.clone(),
+                    kind: span_kind(span.get("kind")).to_string(),
+                    start_time_unix_nano: otlp_u64(span.get("startTimeUnixNano")).unwrap_or(0),
+                    end_time_unix_nano: otlp_u64(span.get("endTimeUnixNano")).unwrap_or(0),
+                    status: match status_code {
+                        Some(OTLP_STATUS_ERROR) => SpanStatus::Error,
+                        Some(1) => SpanStatus::Ok,
+                        _ => SpanStatus::Unset,
+                    },
+                    status_message: status.get("message")
+                        .and_then(|v| v.as_str())
+                        .filter(|message| !message.is_empty())
+                        .map(str::to_string),
+                    attributes: otlp_attributes(span),
+                    events: otlp_array(span, "events")
+                        .map(|event| SpanEvent {
+                            name: event.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                            time_unix_nano: otlp_u64(event.get("timeUnixNano")).unwrap_or(0),
+                            attributes: otlp_attributes(event),
+                        })
+                        .collect(),
+                });
+            }
+        }
+    }
+    
+    spans
+}
+
+/// Trace and span ids are hex in OTLP/JSON; an empty parent id means a root span
+fn otlp_id(value: Option<&Value>) -> Option<String> {
+    value?.as_str()
+        .filter(|id| !id.is_empty())
+        .map(str::to_ascii_lowercase)
+}
+
+/// Span kind as a number; OTLP/JSON may also spell out the enum name
+fn span_kind(value: Option<&Value>) -> &'static str {
+    let number = match value {
+        Some(Value::String(name)) if name.starts_with("SPAN_KIND_") => match name.as_str() {
+            "SPAN_KIND_INTERNAL" => 1,
+            "SPAN_KIND_SERVER" => 2,
+            "SPAN_KIND_CLIENT" => 3,
+            "SPAN_KIND_PRODUCER" => 4,
+            "SPAN_KIND_CONSUMER" => 5,
+            _ => 0,
+        },
+        other => otlp_u64(other).unwrap_or(0),
+    };
+    match number {
+        1 => "internal",
+        2 => "server",
+        3 => "client",
+        4 => "producer",
+        5 => "consumer",
+        _ => "unspecified",
+    }
+}
+
+/// Temporality as a number; OTLP/JSON may also spell out the enum name
+fn otlp_temporality(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::String(name) if name == "AGGREGATION_TEMPORALITY_CUMULATIVE" => Some(OTLP_CUMULATIVE),
+        Value::String(name) if name == "AGGREGATION_TEMPORALITY_DELTA" => Some(1),
+        other => otlp_u64(Some(other)),
+    }
+}
+
+/// Lowest OTLP severity number for a level name such as `warn` or `Information`
+pub fn severity_number_for_level(level: &str) -> Option<u8> {
+    match level.to_ascii_lowercase().as_str() {
+        "trace" => Some(1),
+        "debug" => Some(5),
+        "info" | "information" => Some(9),
+        "warn" | "warning" => Some(13),
+        "error" => Some(17),
+        "fatal" | "critical" => Some(21),
+        _ => None,
+    }
+}
+
+fn severity_name(severity_number: u8) -> &'static str {
+    match severity_number {
+        1..=4 => "Trace",
+        5..=8 => "Debug",
+        9..=12 => "Information",
+        13..=16 => "Warning",
+        17..=20 => "Error",
+        21..=24 => "Critical",
+        _ => "Unspecified",
+    }
+}
+
+fn format_unix_nano(time_unix_nano: u64) -> String {
+    let secs = (time_unix_nano / 1_000_000_000) as i64;
+    let nanos = (time_unix_nano % 1_000_000_000) as u32;
+    chrono::DateTime::from_timestamp(secs, nanos)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+fn otlp_array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value.get(key)
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+}
+
+/// OTLP/JSON encodes 64-bit integers as strings
+fn otlp_u64(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::String(text) => text.parse().ok(),
+        other => other.as_u64(),
+    }
+}
+
+fn otlp_attributes(value: &Value) -> HashMap<String, Value> {
+    otlp_key_values(otlp_array(value, "attributes"))
+}
+
+fn otlp_key_values<'a>(values: impl Iterator<Item = &'a Value>) -> HashMap<String, Value> {
+    values
+        .filter_map(|attribute| {
+            let key = attribute.get("key")?.as_str()?;
+            Some((key.to_string(), attribute.get("value").map(otlp_value).unwrap_or(Value::Null)))
+        })
+        .collect()
+}
+
+/// Unwrap an OTLP `AnyValue` into plain JSON
+fn otlp_value(value: &Value) -> Value {
+    if let Some(text) = value.get("stringValue") {
+        return text.clone();
+    }
+    if let Some(flag) = value.get("boolValue") {
+        return flag.clone();
+    }
+    if let Some(number) = value.get("intValue") {
+        return otlp_u64(Some(number))
+            .map(Value::from)
+            .or_else(|| number.as_str().and_then(|text| text.parse::<i64>().ok()).map(Value::from))
+            .unwrap_or_else(|| number.clone());
+    }
+    if let Some(number) = value.get("doubleValue") {
+        return number.clone();
+    }
+    if let Some(values) = value.get("arrayValue") {
+        return Value::Array(otlp_array(values, "values").map(otlp_value).collect());
+    }
+    if let Some(values) = value.get("kvlistValue") {
+        return Value::Object(otlp_key_values(otlp_array(values, "values")).into_iter().collect());
+    }
+    Value::Null
+}
@@ -0,0 +1,220 @@
+use anyhow::Result;
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::config::settings::DashboardAuthConfig;
+
+/// Replacement for credentials in logs and error messages
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Where the dashboard token is read from
+#[derive(Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    Static(String),
+    /// Re-read when the dashboard rejects the current token
+    File(PathBuf),
+    /// Re-read when the dashboard rejects the current token
+    Env(String),
+}
+
+impl TokenSource {
+    fn load(&self) -> Result<String> {
+        let token = match self {
+            TokenSource::Static(token) => token.clone(),
+            TokenSource::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read dashboard token file {}: {}", path.display(), e))?,
+            TokenSource::Env(var) => std::env::var(var)
+                .map_err(|_| anyhow::anyhow!("Environment variable {} is not set", var))?,
+        };
+
+        let token = token.trim().to_string();
+        if token.is_empty() {
+            return Err(anyhow::anyhow!("Dashboard token from {} is empty", self));
+        }
+        Ok(token)
+    }
+
+    fn remediation(&self) -> String {
+        match self {
+            TokenSource::Static(_) => "Update aspire.auth.token, or set aspire.auth.token_file so the token can be \
+                rotated without a restart".to_string(),
+            TokenSource::File(path) => format!(
+                "Check that {} contains the current dashboard browser token or API key; it was re-read before giving up",
+                path.display()
+            ),
+            TokenSource::Env(var) => format!(
+                "Check that {} holds the current dashboard browser token or API key; it was re-read before giving up",
+                var
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenSource::Static(_) => write!(f, "aspire.auth.token"),
+            TokenSource::File(path) => write!(f, "token file {}", path.display()),
+            TokenSource::Env(var) => write!(f, "environment variable {}", var),
+        }
+    }
+}
+
+impl fmt::Debug for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenSource::Static(_) => f.debug_tuple("Static").field(&REDACTED).finish(),
+            TokenSource::File(path) => f.debug_tuple("File").field(path).finish(),
+            TokenSource::Env(var) => f.debug_tuple("Env").field(var).finish(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Dashboard authentication failed (HTTP {status}){}: {hint}", .detail.as_ref().map(|d| format!(": {}", d)).unwrap_or_default())]
+pub struct DashboardAuthError {
+    pub status: u16,
+    /// Response body from the dashboard, with credentials redacted
+    pub detail: Option<String>,
+    pub hint: String,
+}
+
+/// Credentials for a secured Aspire dashboard
+pub struct DashboardAuth {
+    source: TokenSource,
+    header: HeaderName,
+    token: RwLock<Option<String>>,
+}
+
+impl DashboardAuth {
+    /// A missing token is not fatal here; it is retried on the first rejected request
+    pub fn new(source: TokenSource, header: &str) -> Result<Self> {
+        let header = HeaderName::from_bytes(header.as_bytes())
+            .map_err(|_| anyhow::anyhow!("Invalid dashboard auth header name: {}", header))?;
+
+        let token = match source.load() {
+            Ok(token) => Some(token),
+            Err(e) => {
+                warn!("No dashboard token loaded yet: {}", e);
+                None
+            }
+        };
+
+        Ok(Self {
+            source,
+            header,
+            token: RwLock::new(token),
+        })
+    }
+
+    /// `None` when no token source is configured
+    pub fn from_config(config: &DashboardAuthConfig) -> Result<Option<Self>> {
+        let source = if let Some(token) = &config.token {
+            TokenSource::Static(token.expose().to_string())
+        } else if let Some(path) = &config.token_file {
+            TokenSource::File(PathBuf::from(path))
+        } else if let Some(var) = &config.token_env {
+            TokenSource::Env(var.clone())
+        } else {
+            return Ok(None);
+        };
+
+        debug!("Dashboard authentication enabled using {}", source);
+        Self::new(source, &config.header).map(Some)
+    }
+
+    pub fn source(&self) -> &TokenSource {
+        &self.source
+    }
+
+    /// Header carrying the current token, marked sensitive so it is never printed
+    pub fn header(&self) -> Option<(HeaderName, HeaderValue)> {
+        let token = self.token.read().ok()?.clone()?;
+        let value = if self.header == AUTHORIZATION {
+            format!("Bearer {}", token)
+        } else {
+            token
+        };
+
+        let mut value = HeaderValue::from_str(&value).ok()?;
+        value.set_sensitive(true);
+        Some((self.header.clone(), value))
+    }
+
+    /// Re-read the token, returning whether it changed
+    pub fn refresh(&self) -> bool {
+        if matches!(self.source, TokenSource::Static(_)) {
+            return false;
+        }
+
+        let token = match self.source.load() {
+            Ok(token) => token,
+            Err(e) => {
+                warn!("Failed to refresh dashboard token: {}", e);
+                return false;
+            }
+        };
+
+        let Ok(mut current) = self.token.write() else {
+            return false;
+        };
+        if current.as_deref() == Some(token.as_str()) {
+            return false;
+        }
+        *current = Some(token);
+        true
+    }
+
+    /// Replace the current token in `text`
+    pub fn redact(&self, text: &str) -> String {
+        let mut redacted = text.to_string();
+        if let TokenSource::Static(token) = &self.source {
+            redacted = redact(&redacted, token);
+        }
+        if let Some(token) = self.token.read().ok().and_then(|token| token.clone()) {
+            redacted = redact(&redacted, &token);
+        }
+        redacted
+    }
+
+    /// Error for a request the dashboard still rejects after refreshing
+    pub fn error(auth: Option<&Self>, status: u16, body: &str) -> DashboardAuthError {
+        let detail = body.trim();
+        let detail = (!detail.is_empty()).then(|| {
+            let detail = auth.map_or_else(|| detail.to_string(), |auth| auth.redact(detail));
+            detail.chars().take(200).collect()
+        });
+
+        let mut hint = match auth {
+            Some(auth) => auth.source.remediation(),
+            None => "The dashboard requires a token; set aspire.auth.token_file, aspire.auth.token_env \
+                or aspire.auth.token (or AXIOM_ASPIRE__AUTH__TOKEN_FILE)".to_string(),
+        };
+        if status == 403 {
+            hint.push_str(". HTTP 403 means the token was recognised but lacks access to this endpoint");
+        }
+
+        DashboardAuthError { status, detail, hint }
+    }
+}
+
+impl fmt::Debug for DashboardAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DashboardAuth")
+            .field("source", &self.source)
+            .field("header", &self.header)
+            .field("token", &REDACTED)
+            .finish()
+    }
+}
+
+/// Replace every occurrence of `secret` in `text`
+pub fn redact(text: &str, secret: &str) -> String {
+    if secret.is_empty() {
+        return text.to_string();
+    }
+    text.replace(secret, REDACTED)
+}
@@ -0,0 +1,411 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::{Output, Stdio};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, warn};
+
+use crate::config::settings::DockerConfig;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DockerError {
+    #[error("Docker integration is disabled (docker.enabled = false)")]
+    Disabled,
+    #[error("Docker is not available: {reason}")]
+    Unavailable { reason: String },
+    #[error("No container found for resource '{resource}' (containers from Aspire: {})", .available.join(", "))]
+    ContainerNotFound { resource: String, available: Vec<String> },
+    #[error("Command '{command}' is not allowed; allowed commands: {}", .allowed.join(", "))]
+    CommandNotAllowed { command: String, allowed: Vec<String> },
+    #[error("docker {command} failed: {reason}")]
+    CommandFailed { command: String, reason: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DockerContainer {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub state: String,
+    #[serde(skip)]
+    pub labels: HashMap<String, String>,
+}
+
+impl DockerContainer {
+    fn is_running(&self) -> bool {
+        self.state == "running"
+    }
+}
+
+/// One line of `docker ps --format '{{json .}}'`
+#[derive(Debug, Deserialize)]
+struct PsLine {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "Names")]
+    names: String,
+    #[serde(rename = "Image", default)]
+    image: String,
+    #[serde(rename = "State", default)]
+    state: String,
+    #[serde(rename = "Labels", default)]
+    labels: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContainerLogs {
+    pub container: DockerContainer,
+    /// stdout and stderr interleaved by timestamp
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExecResult {
+    pub container: DockerContainer,
+    pub command: Vec<String>,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// One line of `docker network ls --format '{{json .}}'`
+#[derive(Debug, Deserialize)]
+struct NetworkLine {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "Name")]
+    name: String,
+}
+
+/// Outcome of removing one container or network
+#[derive(Debug, Clone, Serialize)]
+pub struct DockerRemoval {
+    pub id: String,
+    pub name: String,
+    /// Why it could not be removed; `None` once it is gone
+    pub error: Option<String>,
+}
+
+/// Container operations for Aspire's container resources through the Docker CLI
+///
+/// A container belongs to a resource when one of `docker.resource_labels`
+/// names it, or when Aspire created it (`docker.managed_label`) under the
+/// resource's name, optionally followed by the random suffix Aspire appends.
+pub struct DockerClient {
+    config: DockerConfig,
+}
+
+impl DockerClient {
+    pub fn new(config: DockerConfig) -> Self {
+        Self { config }
+    }
+
+    pub async fn list_containers(&self) -> Result<Vec<DockerContainer>> {
+        let output = self.run(&["ps", "--all", "--no-trunc", "--format", "{{json .}}"]).await?;
+        if !output.status.success() {
+            // The CLI exists, so this is almost always an unreachable daemon
+            return Err(DockerError::Unavailable { reason: stderr(&output) }.into());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str::<PsLine>(line) {
+                Ok(container) => Some(DockerContainer {
+                    id: container.id,
+                    name: container.names.split(',').next().unwrap_or_default().to_string(),
+                    image: container.image,
+                    state: container.state,
+                    labels: parse_labels(&container.labels),
+                }),
+                Err(e) => {
+                    warn!("Skipping unparseable docker ps line: {}", e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Containers of `resource`, running ones first
+    pub async fn find_containers(&self, resource: &str) -> Result<Vec<DockerContainer>> {
+        let containers = self.list_containers().await?;
+
+        let labelled: Vec<&DockerContainer> = containers
+            .iter()
+            .filter(|c| self.config.resource_labels.iter().any(|label| c.labels.get(label).map(String::as_str) == Some(resource)))
+            .collect();
+        let mut matches: Vec<DockerContainer> = if labelled.is_empty() {
+            containers
+                .iter()
+                .filter(|c| c.labels.contains_key(&self.config.managed_label) && name_matches(&c.name, resource))
+                .cloned()
+                .collect()
+        } else {
+            labelled.into_iter().cloned().collect()
+        };
+
+        if matches.is_empty() {
+            let mut available: Vec<String> = containers
+                .iter()
+                .filter(|c| c.labels.contains_key(&self.config.managed_label))
+                .map(|c| c.name.clone())
+                .collect();
+            available.sort();
+            return Err(DockerError::ContainerNotFound { resource: resource.to_string(), available }.into());
+        }
+
+        matches.sort_by_key(|c| !c.is_running());
+        Ok(matches)
+    }
+
+    /// Restart every container of `resource`, returning their new state
+    pub async fn restart_container(&self, resource: &str, stop_timeout: Option<Duration>) -> Result<Vec<DockerContainer>> {
+        let containers = self.find_containers(resource).await?;
+        let stop_timeout = stop_timeout.unwrap_or(Duration::from_secs(10)).as_secs().to_string();
+
+        for container in &containers {
+            info!("Restarting container {} ({}) for {}", container.name, short_id(&container.id), resource);
+            self.run_checked(&["restart", "--time", &stop_timeout, &container.id]).await?;
+        }
+
+        let ids: Vec<&str> = containers.iter().map(|c| c.id.as_str()).collect();
+        Ok(self.list_containers()
+            .await?
+            .into_iter()
+            .filter(|c| ids.contains(&c.id.as_str()))
+            .collect())
+    }
+
+    pub async fn container_logs(&self, resource: &str, tail: usize, since: Option<&str>) -> Result<ContainerLogs> {
+        let container = self.find_containers(resource).await?.remove(0);
+        let tail = tail.to_string();
+
+        let mut args = vec!["logs", "--timestamps", "--tail", tail.as_str()];
+        if let Some(since) = since {
+            args.extend(["--since", since]);
+        }
+        args.push(&container.id);
+        let output = self.run_checked(&args).await?;
+
+        // Timestamps are RFC 3339 with a fixed width, so they sort as text
+        let mut lines: Vec<String> = [&output.stdout, &output.stderr]
+            .iter()
+            .flat_map(|stream| String::from_utf8_lossy(stream).lines().map(str::to_string).collect::<Vec<_>>())
+            .collect();
+        lines.sort();
+
+        Ok(ContainerLogs { container, lines })
+    }
+
+    /// Force-remove every container Aspire created, running or not
+    ///
+    /// A container that cannot be removed is reported rather than failing
+    /// the others.
+    pub async fn remove_managed_containers(&self) -> Result<Vec<DockerRemoval>> {
+        let containers: Vec<DockerContainer> = self.list_containers()
+            .await?
+            .into_iter()
+            .filter(|c| c.labels.contains_key(&self.config.managed_label))
+            .collect();
+
+        let mut removals = Vec::new();
+        for container in containers {
+            info!("Removing container {} ({})", container.name, short_id(&container.id));
+            let error = self.run_checked(&["rm", "--force", &container.id]).await.err().map(|e| e.to_string());
+            removals.push(DockerRemoval { id: container.id, name: container.name, error });
+        }
+        Ok(removals)
+    }
+
+    /// Remove every network Aspire created
+    ///
+    /// Networks still used by a container fail to be removed, so this should
+    /// run after `remove_managed_containers`.
+    pub async fn remove_managed_networks(&self) -> Result<Vec<DockerRemoval>> {
+        let filter = format!("label={}", self.config.managed_label);
+        let output = self.run(&["network", "ls", "--no-trunc", "--filter", &filter, "--format", "{{json .}}"]).await?;
+        if !output.status.success() {
+            return Err(DockerError::Unavailable { reason: stderr(&output) }.into());
+        }
+
+        let networks: Vec<NetworkLine> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str::<NetworkLine>(line) {
+                Ok(network) => Some(network),
+                Err(e) => {
+                    warn!("Skipping unparseable docker network ls line: {}", e);
+                    None
+                }
+            })
+            .collect();
+
+        let mut removals = Vec::new();
+        for network in networks {
+            info!("Removing network {} ({})", network.name, short_id(&network.id));
+            let error = self.run_checked(&["network", "rm", &network.id]).await.err().map(|e| e.to_string());
+            removals.push(DockerRemoval { id: network.id, name: network.name, error });
+        }
+        Ok(removals)
+    }
+
+    /// Label that marks the containers and networks Aspire created
+    pub fn managed_label(&self) -> &str {
+        &self.config.managed_label
+    }
+
+    /// Run an allow-listed command in the resource's container
+    ///
+    /// Arguments are passed to the container as is, without a shell. A
+    /// non-zero exit code is part of the result rather than an error.
+    pub async fn exec(&self, resource: &str, command: &[String]) -> Result<ExecResult> {
+        if !self.is_allowed(command) {
+            return Err(DockerError::CommandNotAllowed {
+                command: command.join(" "),
+                allowed: self.config.exec_allowlist.clone(),
+            }.into());
+        }
+
+        let container = self.find_containers(resource).await?.remove(0);
+        let mut args = vec!["exec", container.id.as_str()];
+        args.extend(command.iter().map(String::as_str));
+
+        info!("Running '{}' in container {}", command.join(" "), container.name);
+        let output = self.run(&args).await?;
+
+        Ok(ExecResult {
+            container,
+            command: command.to_vec(),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+
+    /// Run a command the server builds itself, such as a database dump, in `container`
+    ///
+    /// The exec allow-list does not apply. `input` is piped to the command's
+    /// stdin, and a non-zero exit code is an error. Returns stdout.
+    pub async fn exec_trusted(
+        &self,
+        container: &DockerContainer,
+        command: &[String],
+        input: Option<Vec<u8>>,
+        timeout: Duration,
+    ) -> Result<Vec<u8>> {
+        let mut args = vec!["exec"];
+        if input.is_some() {
+            args.push("--interactive");
+        }
+        args.push(container.id.as_str());
+        args.extend(command.iter().map(String::as_str));
+
+        let output = self.run_with_input(&args, input, timeout).await?;
+        if !output.status.success() {
+            return Err(DockerError::CommandFailed {
+                command: "exec".to_string(),
+                reason: format!("{}: {}", command.first().map(String::as_str).unwrap_or_default(), stderr(&output)),
+            }.into());
+        }
+        Ok(output.stdout)
+    }
+
+    /// Whether `command` starts with the words of an allow-list entry
+    pub fn is_allowed(&self, command: &[String]) -> bool {
+        !command.is_empty() && self.config.exec_allowlist.iter().any(|entry| {
+            let words: Vec<&str> = entry.split_whitespace().collect();
+            !words.is_empty()
+                && words.len() <= command.len()
+                && words.iter().zip(command).all(|(word, arg)| *word == arg)
+        })
+    }
+
+    async fn run(&self, args: &[&str]) -> Result<Output> {
+        self.run_with_input(args, None, Duration::from_millis(self.config.command_timeout_ms)).await
+    }
+
+    async fn run_with_input(&self, args: &[&str], input: Option<Vec<u8>>, timeout: Duration) -> Result<Output> {
+        if !self.config.enabled {
+            return Err(DockerError::Disabled.into());
+        }
+        debug!("Running {} {}", self.config.binary, args.join(" "));
+
+        let mut command = tokio::process::Command::new(&self.config.binary);
+        command
+            .args(args)
+            .kill_on_drop(true)
+            .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let run = async {
+            let mut child = command.spawn()?;
+            if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+                // Written concurrently so a chatty command cannot fill its
+                // output pipe while we block on its input
+                tokio::spawn(async move {
+                    if let Err(e) = stdin.write_all(&input).await {
+                        debug!("Could not write docker stdin: {}", e);
+                    }
+                });
+            }
+            child.wait_with_output().await
+        };
+
+        match tokio::time::timeout(timeout, run).await {
+            Ok(Ok(output)) => Ok(output),
+            Ok(Err(e)) => Err(DockerError::Unavailable {
+                reason: format!("could not run '{}': {}", self.config.binary, e),
+            }.into()),
+            Err(_) => Err(DockerError::CommandFailed {
+                command: args.first().copied().unwrap_or_default().to_string(),
+                reason: format!("timed out after {}ms", timeout.as_millis()),
+            }.into()),
+        }
+    }
+
+    async fn run_checked(&self, args: &[&str]) -> Result<Output> {
+        let output = self.run(args).await?;
+        if !output.status.success() {
+            return Err(DockerError::CommandFailed {
+                command: args.first().copied().unwrap_or_default().to_string(),
+                reason: stderr(&output),
+            }.into());
+        }
+        Ok(output)
+    }
+}
+
+/// `docker ps` prints labels as `key=value,key=value`
+fn parse_labels(labels: &str) -> HashMap<String, String> {
+    labels
+        .split(',')
+        .filter_map(|label| label.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .collect()
+}
+
+/// `cache` matches `cache` and `cache-x7k2mq`, but not `cache-admin-x7k2mq`
+fn name_matches(container: &str, resource: &str) -> bool {
+    match container.strip_prefix(resource) {
+        Some("") => true,
+        Some(rest) => rest.strip_prefix('-').is_some_and(|suffix| {
+            !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_alphanumeric())
+        }),
+        None => false,
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(12)]
+}
+
+fn stderr(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        format!("exited with {}", output.status)
+    } else {
+        stderr
+    }
+}
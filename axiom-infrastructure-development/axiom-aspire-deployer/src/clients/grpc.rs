@@ -1,14 +1,48 @@
 use anyhow::Result;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{AlertDescription, CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::io::BufReader;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_health::pb::HealthCheckRequest;
 use tracing::{debug, warn, info};
 
+use crate::config::settings::{GrpcConfig, GrpcTlsConfig};
+
+/// Default time allowed for TCP connect plus TLS handshake
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, thiserror::Error)]
+pub enum GrpcConnectError {
+    #[error("Unknown gRPC service: {0}")]
+    UnknownService(String),
+    #[error("Invalid gRPC TLS configuration for '{service}': {message}")]
+    InvalidTlsConfig { service: String, message: String },
+    /// The server was reachable but the TLS handshake failed
+    #[error("TLS handshake with gRPC service '{service}' at {endpoint} failed: {reason}. {hint}")]
+    Handshake { service: String, endpoint: String, reason: String, hint: String },
+    #[error("Failed to connect to gRPC service '{service}' at {endpoint}: {reason}")]
+    Connect { service: String, endpoint: String, reason: String },
+}
+
 // Since we don't have specific gRPC service definitions yet,
-// this is a placeholder implementation that can be extended
+// method calls are a placeholder implementation that can be extended.
+// Channels and health checks are real.
 pub struct GrpcClient {
     // This would contain tonic clients once we have proto definitions
     endpoints: HashMap<String, String>,
+    /// TLS settings per service; services without an entry use TLS with the
+    /// system roots for `https` endpoints and plaintext otherwise
+    tls: HashMap<String, GrpcTlsConfig>,
+    connect_timeout: Duration,
 }
 
 #[derive(Debug)]
@@ -45,7 +79,120 @@ impl GrpcClient {
         // Default Aspire service gRPC endpoints
         endpoints.insert("notifications".to_string(), "https://localhost:7002".to_string());
         
-        Self { endpoints }
+        Self {
+            endpoints,
+            tls: HashMap::new(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+        }
+    }
+    
+    /// Client with the endpoints and TLS settings from `[grpc.services]`
+    pub fn from_config(config: &GrpcConfig) -> Self {
+        let mut client = Self::new();
+        for (service, target) in &config.services {
+            if let Some(endpoint) = &target.endpoint {
+                client.endpoints.insert(service.clone(), endpoint.clone());
+            }
+            if let Some(tls) = &target.tls {
+                client.tls.insert(service.clone(), tls.clone());
+            }
+        }
+        client
+    }
+    
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+    
+    pub fn endpoint(&self, service: &str) -> Option<&str> {
+        self.endpoints.get(service).map(String::as_str)
+    }
+    
+    /// Open a channel to `service`, over TLS when configured or for `https` endpoints
+    pub async fn connect(&self, service: &str) -> Result<Channel, GrpcConnectError> {
+        let endpoint = self.endpoints.get(service)
+            .ok_or_else(|| GrpcConnectError::UnknownService(service.to_string()))?;
+        let connect_error = |reason: String| GrpcConnectError::Connect {
+            service: service.to_string(),
+            endpoint: endpoint.clone(),
+            reason,
+        };
+        
+        let uri: Uri = endpoint.parse().map_err(|e| connect_error(format!("invalid endpoint: {}", e)))?;
+        let tls = self.tls.get(service);
+        if tls.is_none() && uri.scheme_str() != Some("https") {
+            return Endpoint::from(uri)
+                .connect_timeout(self.connect_timeout)
+                .connect()
+                .await
+                .map_err(|e| connect_error(error_chain(&e)));
+        }
+        
+        let default_tls = GrpcTlsConfig::default();
+        let tls = tls.unwrap_or(&default_tls);
+        let host = uri.host().ok_or_else(|| connect_error("endpoint has no host".to_string()))?.to_string();
+        let address = format!("{}:{}", host, uri.port_u16().unwrap_or(443));
+        
+        let name = tls.server_name.clone()
+            .unwrap_or_else(|| host.trim_start_matches('[').trim_end_matches(']').to_string());
+        let server_name = ServerName::try_from(name.clone()).map_err(|_| GrpcConnectError::InvalidTlsConfig {
+            service: service.to_string(),
+            message: format!("'{}' is not a valid server name", name),
+        })?;
+        let connector = TlsConnector::from(Arc::new(client_tls_config(service, tls)?));
+        
+        // tonic sees a plaintext URI; the connector below performs the handshake
+        // and records why it failed, which tonic's own error would hide
+        let handshake_failure: Arc<Mutex<Option<(String, String)>>> = Arc::new(Mutex::new(None));
+        let failure_slot = handshake_failure.clone();
+        let service_name = service.to_string();
+        let tcp_address = address.clone();
+        let tls_connector = tower::service_fn(move |_: Uri| {
+            let connector = connector.clone();
+            let server_name = server_name.clone();
+            let tcp_address = tcp_address.clone();
+            let failure_slot = failure_slot.clone();
+            let service_name = service_name.clone();
+            async move {
+                let tcp = TcpStream::connect(&tcp_address).await?;
+                connector.connect(server_name, tcp).await.inspect_err(|e| {
+                    if let Ok(mut slot) = failure_slot.lock() {
+                        *slot = Some((e.to_string(), handshake_hint(&service_name, e)));
+                    }
+                })
+            }
+        });
+        
+        let result = Endpoint::from_shared(format!("http://{}", address))
+            .map_err(|e| connect_error(format!("invalid endpoint: {}", e)))?
+            .connect_timeout(self.connect_timeout)
+            .connect_with_connector(tls_connector)
+            .await;
+        
+        result.map_err(|e| match handshake_failure.lock().ok().and_then(|mut slot| slot.take()) {
+            Some((reason, hint)) => GrpcConnectError::Handshake {
+                service: service.to_string(),
+                endpoint: endpoint.clone(),
+                reason,
+                hint,
+            },
+            None => connect_error(error_chain(&e)),
+        })
+    }
+    
+    /// Run a grpc.health.v1 check; `health_service` is the name sent in the
+    /// request, empty for the server as a whole
+    pub async fn check_health(&self, service: &str, health_service: &str) -> Result<bool> {
+        debug!("Checking gRPC health of {} (service '{}')", service, health_service);
+        
+        let channel = self.connect(service).await?;
+        let response = HealthClient::new(channel)
+            .check(HealthCheckRequest { service: health_service.to_string() })
+            .await
+            .map_err(|status| anyhow::anyhow!("gRPC health check failed: {}", status.message()))?;
+        
+        Ok(response.into_inner().status == ServingStatus::Serving as i32)
     }
     
     pub async fn call_method(&self, request: GrpcCallRequest) -> Result<GrpcCallResponse> {
@@ -100,23 +247,12 @@ impl GrpcClient {
     pub async fn check_service_health(&self, service: &str) -> Result<bool> {
         debug!("Checking gRPC service health: {}", service);
         
-        if let Some(endpoint) = self.endpoints.get(service) {
-            // Try to connect to the gRPC service
-            // For now, this is a simplified check
-            let health_request = GrpcCallRequest {
-                service: service.to_string(),
-                method: "Health.Check".to_string(),
-                message: json!({}),
-                metadata: None,
-                timeout: Some(Duration::from_secs(5)),
-            };
-            
-            match self.call_method(health_request).await {
-                Ok(response) => Ok(response.success),
-                Err(_) => Ok(false),
+        match self.check_health(service, "").await {
+            Ok(serving) => Ok(serving),
+            Err(e) => {
+                warn!("gRPC health check for {} failed: {}", service, e);
+                Ok(false)
             }
-        } else {
-            Ok(false)
         }
     }
     
@@ -222,6 +358,150 @@ impl GrpcClient {
     }
 }
 
+/// rustls client configuration for one service
+fn client_tls_config(service: &str, tls: &GrpcTlsConfig) -> Result<ClientConfig, GrpcConnectError> {
+    let invalid = |message: String| GrpcConnectError::InvalidTlsConfig {
+        service: service.to_string(),
+        message,
+    };
+    
+    let mut roots = RootCertStore::empty();
+    match &tls.ca_cert {
+        Some(path) => {
+            for cert in read_certs(path).map_err(invalid)? {
+                roots.add(cert).map_err(|e| invalid(format!("invalid CA certificate in {}: {}", path, e)))?;
+            }
+        }
+        None => {
+            let native = rustls_native_certs::load_native_certs()
+                .map_err(|e| invalid(format!("failed to load system root certificates: {}", e)))?;
+            roots.add_parsable_certificates(native);
+        }
+    }
+    
+    let builder = ClientConfig::builder().with_root_certificates(roots);
+    let mut config = match (&tls.client_cert, &tls.client_key) {
+        (Some(cert), Some(key)) => builder
+            .with_client_auth_cert(read_certs(cert).map_err(invalid)?, read_key(key).map_err(invalid)?)
+            .map_err(|e| invalid(format!("client certificate {} does not match key {}: {}", cert, key, e)))?,
+        (None, None) => builder.with_no_client_auth(),
+        _ => return Err(invalid("client_cert and client_key must be set together".to_string())),
+    };
+    
+    if tls.insecure_skip_verify {
+        warn!(
+            "!!! TLS certificate verification is DISABLED for gRPC service '{}' (insecure_skip_verify). \
+             Any server can impersonate it; never use this outside local development !!!",
+            service
+        );
+        config.dangerous().set_certificate_verifier(Arc::new(NoCertificateVerification));
+    }
+    
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    Ok(config)
+}
+
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("failed to open {}: {}", path, e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("failed to parse {}: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("no PEM certificates found in {}", path));
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &str) -> Result<PrivateKeyDer<'static>, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("failed to open {}: {}", path, e))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| format!("failed to parse {}: {}", path, e))?
+        .ok_or_else(|| format!("no PEM private key found in {}", path))
+}
+
+/// What to change in `[grpc.services.<service>.tls]` after a failed handshake
+fn handshake_hint(service: &str, error: &std::io::Error) -> String {
+    let section = format!("grpc.services.{}.tls", service);
+    let rustls_error = error.get_ref().and_then(|inner| inner.downcast_ref::<rustls::Error>());
+    
+    match rustls_error {
+        Some(rustls::Error::InvalidCertificate(CertificateError::UnknownIssuer | CertificateError::BadSignature)) => format!(
+            "The server certificate is not signed by a trusted CA; set {}.ca_cert to the CA bundle that issued it",
+            section
+        ),
+        Some(rustls::Error::InvalidCertificate(CertificateError::NotValidForName)) => format!(
+            "The server certificate does not cover the endpoint host; set {}.server_name to a name it was issued for",
+            section
+        ),
+        Some(rustls::Error::InvalidCertificate(CertificateError::Expired | CertificateError::NotValidYet)) => {
+            "The server certificate is expired or not yet valid; renew it or check the system clock".to_string()
+        }
+        Some(rustls::Error::AlertReceived(
+            AlertDescription::CertificateRequired | AlertDescription::BadCertificate | AlertDescription::UnknownCA,
+        )) => format!(
+            "The server rejected the client certificate; set {0}.client_cert and {0}.client_key to a certificate it trusts",
+            section
+        ),
+        _ => format!(
+            "Check that the endpoint serves TLS and that {} matches the server's certificate setup",
+            section
+        ),
+    }
+}
+
+/// Error message including its sources, since tonic's top-level message is generic
+fn error_chain(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Verifier used for `insecure_skip_verify`
+#[derive(Debug)]
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+    
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+    
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+    
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        rustls::crypto::ring::default_provider()
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
 impl Default for GrpcClient {
     fn default() -> Self {
         Self::new()
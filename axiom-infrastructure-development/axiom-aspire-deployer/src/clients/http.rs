@@ -1,26 +1,164 @@
 use anyhow::Result;
-use reqwest::{Client, Method, Response};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 
+use crate::clients::auth::DashboardAuth;
+use crate::clients::resilience::{self, CircuitBreakers, HttpResilienceError, RetryPolicy};
+use crate::config::settings::HttpConfig;
 use crate::mcp::protocol::{EndpointCallRequest, EndpointCallResponse};
 
+#[derive(Clone)]
 pub struct HttpClient {
     client: Client,
+    auth: Option<Arc<DashboardAuth>>,
+    /// Shared by clones, so `set_retry_policy` reaches every user of the client
+    retry: Arc<RwLock<RetryPolicy>>,
+    breakers: Arc<CircuitBreakers>,
 }
 
 impl HttpClient {
     pub fn new() -> Self {
+        Self::from_config(&HttpConfig::default())
+    }
+    
+    pub fn from_config(config: &HttpConfig) -> Self {
+        Self::with_redirect_policy(config, reqwest::redirect::Policy::limited(3))
+    }
+    
+    /// Like `from_config`, but redirects are returned instead of followed
+    pub fn without_redirects(config: &HttpConfig) -> Self {
+        Self::with_redirect_policy(config, reqwest::redirect::Policy::none())
+    }
+    
+    fn with_redirect_policy(config: &HttpConfig, policy: reqwest::redirect::Policy) -> Self {
         let client = Client::builder()
-            .timeout(Duration::from_secs(30))
+            .timeout(Duration::from_millis(config.request_timeout_ms))
             .danger_accept_invalid_certs(true) // For local development
-            .redirect(reqwest::redirect::Policy::limited(3))
+            .redirect(policy)
             .build()
             .expect("Failed to create HTTP client");
         
-        Self { client }
+        Self {
+            client,
+            auth: None,
+            retry: Arc::new(RwLock::new(RetryPolicy::from_config(config))),
+            breakers: Arc::new(CircuitBreakers::from_config(config)),
+        }
+    }
+    
+    /// Apply the retry settings of `config` to requests sent from now on
+    pub fn set_retry_policy(&self, config: &HttpConfig) {
+        *self.retry.write().expect("retry policy lock poisoned") = RetryPolicy::from_config(config);
+    }
+    
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retry.read().expect("retry policy lock poisoned").clone()
+    }
+    
+    /// Credentials attached by `send_authorized`; `call_endpoint` never sends them
+    pub fn with_auth(mut self, auth: Arc<DashboardAuth>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+    
+    /// Send a request with the configured credentials
+    ///
+    /// A 401/403 response re-reads the token and retries once if it changed,
+    /// then fails with a `DashboardAuthError`. `build` is called per attempt.
+    pub async fn send_authorized<F>(&self, build: F) -> Result<Response>
+    where
+        F: Fn(&Client) -> RequestBuilder,
+    {
+        let response = self.send(|client| self.authorize(build(client))).await?;
+        if !Self::is_auth_failure(response.status()) {
+            return Ok(response);
+        }
+        
+        let mut response = response;
+        if let Some(auth) = &self.auth {
+            if auth.refresh() {
+                debug!("Dashboard rejected the token; retrying with the token from {}", auth.source());
+                response = self.send(|client| self.authorize(build(client))).await?;
+                if !Self::is_auth_failure(response.status()) {
+                    return Ok(response);
+                }
+            }
+        }
+        
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        let error = DashboardAuth::error(self.auth.as_deref(), status, &body);
+        warn!("{}", error);
+        Err(error.into())
+    }
+    
+    /// Send with retries for idempotent requests and a per-host circuit breaker
+    ///
+    /// Connection errors, timeouts and 429/502/503/504 responses count as
+    /// failures. When retries run out on a retryable status, the last
+    /// response is returned; on connection errors an `HttpResilienceError`.
+    pub async fn send<F>(&self, build: F) -> Result<Response>
+    where
+        F: Fn(&Client) -> RequestBuilder,
+    {
+        let mut attempt = 0;
+        let retry = self.retry_policy();
+        
+        loop {
+            attempt += 1;
+            let request = build(&self.client).build()?;
+            let url = request.url().to_string();
+            let host = resilience::host_key(request.url());
+            let attempts = retry.attempts_for(request.method());
+            
+            self.breakers.try_acquire(&host, Instant::now())?;
+            
+            let last_error = match self.client.execute(request).await {
+                Ok(response) if resilience::is_retryable_status(response.status()) => {
+                    self.breakers.record_failure(&host, Instant::now());
+                    if attempt >= attempts {
+                        return Ok(response);
+                    }
+                    format!("HTTP {}", response.status().as_u16())
+                }
+                Ok(response) => {
+                    self.breakers.record_success(&host);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.breakers.record_failure(&host, Instant::now());
+                    if attempt >= attempts {
+                        return Err(HttpResilienceError::RetriesExhausted {
+                            url,
+                            state: self.breakers.state(&host, Instant::now()),
+                            host,
+                            attempts: attempt,
+                            last_error: e.to_string(),
+                        }.into());
+                    }
+                    e.to_string()
+                }
+            };
+            
+            let backoff = retry.backoff(attempt);
+            debug!("Attempt {}/{} for {} failed ({}); retrying in {}ms", attempt, attempts, url, last_error, backoff.as_millis());
+            tokio::time::sleep(backoff).await;
+        }
+    }
+    
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match self.auth.as_ref().and_then(|auth| auth.header()) {
+            Some((name, value)) => request.header(name, value),
+            None => request,
+        }
+    }
+    
+    fn is_auth_failure(status: StatusCode) -> bool {
+        status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
     }
     
     pub async fn call_endpoint(&self, request: EndpointCallRequest) -> Result<EndpointCallResponse> {
@@ -40,28 +178,32 @@ impl HttpClient {
             _ => return Err(anyhow::anyhow!("Unsupported HTTP method: {}", request.method)),
         };
         
-        // Build request
-        let mut req_builder = self.client.request(method, &request.endpoint);
-        
-        // Add headers
-        if let Some(headers) = &request.headers {
-            for (key, value) in headers {
-                req_builder = req_builder.header(key, value);
+        // Build request; rebuilt for every retry
+        let build = |client: &Client| {
+            let mut req_builder = client.request(method.clone(), &request.endpoint);
+            
+            // Add headers
+            if let Some(headers) = &request.headers {
+                for (key, value) in headers {
+                    req_builder = req_builder.header(key, value);
+                }
             }
-        }
-        
-        // Add body
-        if let Some(body) = &request.body {
-            req_builder = req_builder.json(body);
-        }
-        
-        // Set timeout
-        if let Some(timeout) = request.timeout {
-            req_builder = req_builder.timeout(Duration::from_secs(timeout));
-        }
+            
+            // Add body
+            if let Some(body) = &request.body {
+                req_builder = req_builder.json(body);
+            }
+            
+            // Per-request timeout overrides http.request_timeout_ms
+            if let Some(timeout) = request.timeout {
+                req_builder = req_builder.timeout(Duration::from_secs(timeout));
+            }
+            
+            req_builder
+        };
         
         // Send request
-        match req_builder.send().await {
+        match self.send(build).await {
             Ok(response) => {
                 let duration = start_time.elapsed().as_millis() as u64;
                 let status = response.status().as_u16();
@@ -87,13 +229,20 @@ impl HttpClient {
                 let duration = start_time.elapsed().as_millis() as u64;
                 warn!("HTTP request failed: {}", e);
                 
+                let (error_type, breaker_state) = match e.downcast_ref::<HttpResilienceError>() {
+                    Some(HttpResilienceError::CircuitOpen { state, .. }) => ("circuit_open", Some(*state)),
+                    Some(HttpResilienceError::RetriesExhausted { state, .. }) => ("network_error", Some(*state)),
+                    None => ("network_error", None),
+                };
+                
                 // Return error response
                 Ok(EndpointCallResponse {
                     status: 0,
                     headers: HashMap::new(),
                     body: serde_json::json!({
                         "error": e.to_string(),
-                        "type": "network_error"
+                        "type": error_type,
+                        "breaker_state": breaker_state
                     }),
                     duration,
                 })
@@ -1,7 +1,12 @@
 pub mod aspire;
+pub mod auth;
+pub mod docker;
 pub mod http;
 pub mod grpc;
+pub mod resilience;
 
 pub use aspire::AspireDashboardClient;
+pub use auth::DashboardAuth;
+pub use docker::DockerClient;
 pub use http::HttpClient;
-pub use grpc::GrpcClient;
\ No newline at end of file
+pub use grpc::{GrpcClient, GrpcConnectError};
\ No newline at end of file
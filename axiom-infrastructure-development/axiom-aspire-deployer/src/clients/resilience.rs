@@ -0,0 +1,187 @@
+use dashmap::DashMap;
+use reqwest::{Method, StatusCode, Url};
+use serde::Serialize;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
+use crate::config::settings::HttpConfig;
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per idempotent request, including the first
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &HttpConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.max(1),
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+        }
+    }
+
+    /// Attempts allowed for `method`; only idempotent requests are retried
+    pub fn attempts_for(&self, method: &Method) -> u32 {
+        if is_idempotent(method) {
+            self.max_attempts
+        } else {
+            1
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based): exponential, capped, with
+    /// half of it randomized so clients do not retry in lockstep
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        let half = exponential / 2;
+        half + half.mul_f64(jitter())
+    }
+}
+
+pub fn is_idempotent(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS)
+}
+
+/// Responses worth retrying: the server is restarting or overloaded
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Uniform value in [0, 1) without pulling in an RNG crate
+fn jitter() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos()));
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    /// Calls are short-circuited until the cool-down ends
+    Open,
+    /// Cool-down over; one trial call decides whether to close or reopen
+    HalfOpen,
+}
+
+impl fmt::Display for BreakerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakerState::Closed => write!(f, "closed"),
+            BreakerState::Open => write!(f, "open"),
+            BreakerState::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HttpResilienceError {
+    #[error("Circuit breaker for {host} is {state} after repeated failures; retry in {retry_after_ms}ms")]
+    CircuitOpen { host: String, state: BreakerState, retry_after_ms: u64 },
+    #[error("Request to {url} failed after {attempts} attempt(s), circuit breaker for {host} is {state}: {last_error}")]
+    RetriesExhausted { url: String, host: String, attempts: u32, state: BreakerState, last_error: String },
+}
+
+#[derive(Debug, Default)]
+struct HostBreaker {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// A half-open trial call is in flight
+    trial_in_flight: bool,
+}
+
+/// Per-host circuit breakers
+///
+/// After `failure_threshold` consecutive failures a host's breaker opens and
+/// calls fail immediately for `cooldown`. The first call after that is let
+/// through as a trial: success closes the breaker, failure reopens it.
+#[derive(Debug)]
+pub struct CircuitBreakers {
+    failure_threshold: u32,
+    cooldown: Duration,
+    hosts: DashMap<String, HostBreaker>,
+}
+
+impl CircuitBreakers {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            hosts: DashMap::new(),
+        }
+    }
+
+    pub fn from_config(config: &HttpConfig) -> Self {
+        Self::new(config.breaker_failure_threshold, Duration::from_millis(config.breaker_cooldown_ms))
+    }
+
+    pub fn state(&self, host: &str, now: Instant) -> BreakerState {
+        self.hosts
+            .get(host)
+            .map_or(BreakerState::Closed, |breaker| self.state_of(&breaker, now))
+    }
+
+    /// Reserve a call to `host`, or explain why it is short-circuited
+    pub fn try_acquire(&self, host: &str, now: Instant) -> Result<(), HttpResilienceError> {
+        let mut breaker = self.hosts.entry(host.to_string()).or_default();
+
+        match self.state_of(&breaker, now) {
+            BreakerState::Closed => Ok(()),
+            BreakerState::HalfOpen if !breaker.trial_in_flight => {
+                breaker.trial_in_flight = true;
+                Ok(())
+            }
+            state => {
+                let retry_after = breaker.opened_at
+                    .map_or(Duration::ZERO, |opened| (opened + self.cooldown).saturating_duration_since(now));
+                Err(HttpResilienceError::CircuitOpen {
+                    host: host.to_string(),
+                    state,
+                    retry_after_ms: retry_after.as_millis() as u64,
+                })
+            }
+        }
+    }
+
+    pub fn record_success(&self, host: &str) {
+        self.hosts.remove(host);
+    }
+
+    pub fn record_failure(&self, host: &str, now: Instant) {
+        let mut breaker = self.hosts.entry(host.to_string()).or_default();
+        breaker.consecutive_failures += 1;
+
+        if breaker.trial_in_flight || breaker.consecutive_failures >= self.failure_threshold {
+            breaker.opened_at = Some(now);
+            breaker.trial_in_flight = false;
+        }
+    }
+
+    fn state_of(&self, breaker: &HostBreaker, now: Instant) -> BreakerState {
+        match breaker.opened_at {
+            None => BreakerState::Closed,
+            Some(opened) if now.saturating_duration_since(opened) < self.cooldown => BreakerState::Open,
+            Some(_) => BreakerState::HalfOpen,
+        }
+    }
+}
+
+/// Breaker key for a URL: scheme, host and port
+pub fn host_key(url: &Url) -> String {
+    match url.port_or_known_default() {
+        Some(port) => format!("{}://{}:{}", url.scheme(), url.host_str().unwrap_or_default(), port),
+        None => format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()),
+    }
+}
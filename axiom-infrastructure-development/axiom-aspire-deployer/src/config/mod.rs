@@ -1,3 +1,5 @@
+pub mod reload;
 pub mod settings;
 
-pub use settings::Settings;
\ No newline at end of file
+pub use reload::ConfigReloader;
+pub use settings::Settings;
@@ -0,0 +1,279 @@
+use anyhow::Result;
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::clients::HttpClient;
+use crate::config::Settings;
+use crate::services::history::{self, HistoryEntry, HistoryOutcome};
+use crate::services::secrets::SecretMasker;
+use crate::services::{AspireOrchestrator, DeploymentHistory, HealthMonitor};
+
+/// Settings applied to the running server; any other change needs a restart
+const LIVE_SETTINGS: &[&str] = &[
+    "monitoring.health_check_interval_ms",
+    "monitoring.probes",
+    "secrets",
+    "http.max_attempts",
+    "http.initial_backoff_ms",
+    "http.max_backoff_ms",
+];
+
+/// Quiet period after a file event before reloading, since editors often
+/// write a file in several steps
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// History target of configuration reloads
+const CONFIG_TARGET: &str = "config";
+
+/// Actor of reloads triggered by the file watcher
+const WATCHER_ACTOR: &str = "config_watcher";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigReloadError {
+    #[error("The server was not started from a configuration file")]
+    NoSource,
+    #[error("Rejected configuration from {path}; the current settings stay active: {reason}")]
+    Rejected { path: String, reason: String },
+}
+
+/// File and profile the settings were loaded from
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    pub path: PathBuf,
+    pub profile: Option<String>,
+}
+
+impl ConfigSource {
+    pub fn new(path: impl Into<PathBuf>, profile: Option<String>) -> Self {
+        Self { path: path.into(), profile }
+    }
+
+    pub fn load(&self) -> Result<Settings> {
+        Settings::load(&self.path, self.profile.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigReload {
+    /// Every setting whose value differs from the active one
+    pub changed: Vec<String>,
+    /// Changes now in effect
+    pub applied: Vec<String>,
+    /// Changes that only take effect after a restart
+    pub deferred: Vec<String>,
+}
+
+/// Applies a changed configuration file to the running server
+///
+/// A new configuration is validated as a whole before anything is applied;
+/// an invalid one leaves the active settings untouched. Health check
+/// intervals and probes, secret masking and the HTTP retry policy change in
+/// place. Other changes, such as the bind port, are reported as deferred and
+/// do not become part of the active settings.
+pub struct ConfigReloader {
+    source: Option<ConfigSource>,
+    active: RwLock<Settings>,
+    health_monitor: Arc<HealthMonitor>,
+    orchestrator: Arc<AspireOrchestrator>,
+    http_clients: Vec<HttpClient>,
+    history: Option<Arc<DeploymentHistory>>,
+}
+
+impl ConfigReloader {
+    pub fn new(settings: Settings, health_monitor: Arc<HealthMonitor>, orchestrator: Arc<AspireOrchestrator>) -> Self {
+        Self {
+            source: None,
+            active: RwLock::new(settings),
+            health_monitor,
+            orchestrator,
+            http_clients: Vec::new(),
+            history: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: Option<ConfigSource>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Clients whose retry policy follows `http` settings
+    pub fn with_http_clients(mut self, http_clients: Vec<HttpClient>) -> Self {
+        self.http_clients = http_clients;
+        self
+    }
+
+    /// Record every reload, including rejected ones
+    pub fn with_history(mut self, history: Arc<DeploymentHistory>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    pub fn source(&self) -> Option<&ConfigSource> {
+        self.source.as_ref()
+    }
+
+    /// The settings currently in effect
+    pub fn settings(&self) -> Settings {
+        self.active.read().expect("active settings lock poisoned").clone()
+    }
+
+    /// Re-read the configuration file and apply what can be applied live
+    pub async fn reload(&self) -> Result<ConfigReload> {
+        let start = Instant::now();
+        let result = self.load_and_apply();
+
+        let (outcome, detail) = match &result {
+            Ok(reload) if reload.deferred.is_empty() => {
+                (HistoryOutcome::Success, format!("applied: [{}]", reload.applied.join(", ")))
+            }
+            Ok(reload) => (HistoryOutcome::Success, format!(
+                "applied: [{}], deferred until restart: [{}]",
+                reload.applied.join(", "),
+                reload.deferred.join(", ")
+            )),
+            Err(e) => (HistoryOutcome::Failure, e.to_string()),
+        };
+        if let Some(history) = &self.history {
+            let entry = HistoryEntry::new("reload_config", CONFIG_TARGET, outcome)
+                .with_duration(start.elapsed())
+                .with_detail(detail);
+            if let Err(e) = history.record(&entry).await {
+                warn!("Failed to record configuration reload: {}", e);
+            }
+        }
+
+        result
+    }
+
+    fn load_and_apply(&self) -> Result<ConfigReload> {
+        let source = self.source.as_ref().ok_or(ConfigReloadError::NoSource)?;
+        let settings = source.load().map_err(|e| ConfigReloadError::Rejected {
+            path: source.path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        self.apply(settings)
+    }
+
+    /// Apply already loaded settings
+    pub fn apply(&self, settings: Settings) -> Result<ConfigReload> {
+        settings.validate()?;
+        let secret_masker = SecretMasker::from_config(&settings.secrets)?;
+
+        let mut active = self.active.write().expect("active settings lock poisoned");
+        let changed = changed_settings(&active, &settings)?;
+        let (applied, deferred): (Vec<String>, Vec<String>) = changed.iter().cloned().partition(|path| is_live(path));
+
+        let mut updated = active.clone();
+        updated.monitoring.health_check_interval_ms = settings.monitoring.health_check_interval_ms;
+        updated.monitoring.probes = settings.monitoring.probes;
+        updated.secrets = settings.secrets;
+        updated.http.max_attempts = settings.http.max_attempts;
+        updated.http.initial_backoff_ms = settings.http.initial_backoff_ms;
+        updated.http.max_backoff_ms = settings.http.max_backoff_ms;
+
+        let touches = |section: &str| applied.iter().any(|path| path.starts_with(section));
+        if touches("monitoring.") {
+            self.health_monitor.update_schedule(
+                updated.monitoring.health_check_interval_ms,
+                updated.monitoring.probes.clone(),
+            );
+        }
+        if touches("secrets.") {
+            self.orchestrator.update_secret_masking(secret_masker, updated.secrets.allow_unmask);
+        }
+        if touches("http.") {
+            for client in &self.http_clients {
+                client.set_retry_policy(&updated.http);
+            }
+        }
+        if !applied.is_empty() {
+            info!("Applied configuration changes: {}", applied.join(", "));
+        }
+        if !deferred.is_empty() {
+            warn!("Configuration changes require a restart and were not applied: {}", deferred.join(", "));
+        }
+        *active = updated;
+
+        Ok(ConfigReload { changed, applied, deferred })
+    }
+
+    /// Reload whenever the configuration file changes
+    ///
+    /// Watches the file's directory, since editors often replace files
+    /// rather than write them in place.
+    pub fn watch(self: Arc<Self>) -> Result<()> {
+        let source = self.source.clone().ok_or(ConfigReloadError::NoSource)?;
+        let file_name = source.path.file_name().map(|name| name.to_os_string());
+        let directory = match source.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if event.kind.is_modify() || event.kind.is_create() => {
+                if event.paths.iter().any(|path| path.file_name() == file_name.as_deref()) {
+                    let _ = tx.send(());
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Configuration watch error: {}", e),
+        })?;
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+        info!("Watching {} for configuration changes", source.path.display());
+
+        tokio::spawn(async move {
+            // The watcher stops when dropped
+            let _watcher = watcher;
+            while rx.recv().await.is_some() {
+                tokio::time::sleep(WATCH_DEBOUNCE).await;
+                while rx.try_recv().is_ok() {}
+
+                debug!("{} changed, reloading", source.path.display());
+                match history::with_actor(WATCHER_ACTOR, self.reload()).await {
+                    Ok(reload) if reload.changed.is_empty() => debug!("Configuration unchanged"),
+                    Ok(_) => {}
+                    Err(e) => warn!("{}", e),
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Whether `path` is, or is inside, a setting applied live
+fn is_live(path: &str) -> bool {
+    LIVE_SETTINGS.iter().any(|live| {
+        path.strip_prefix(live).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+/// Dotted paths of the values that differ between two configurations
+pub fn changed_settings(old: &Settings, new: &Settings) -> Result<Vec<String>> {
+    let mut changed = Vec::new();
+    diff_values("", &serde_json::to_value(old)?, &serde_json::to_value(new)?, &mut changed);
+    changed.sort();
+    Ok(changed)
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, changed: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old.keys().chain(new.keys().filter(|key| !old.contains_key(*key))) {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                match (old.get(key), new.get(key)) {
+                    (Some(old), Some(new)) => diff_values(&child, old, new, changed),
+                    _ => changed.push(child),
+                }
+            }
+        }
+        (old, new) if old != new => changed.push(path.to_string()),
+        _ => {}
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +10,27 @@ pub struct Settings {
     pub monitoring: MonitoringConfig,
     pub network: NetworkConfig,
     pub logging: LoggingConfig,
+    /// Retry and circuit-breaker behaviour of outgoing HTTP calls
+    #[serde(default)]
+    pub http: HttpConfig,
+    /// Per-service gRPC endpoints and TLS
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    /// Masking of secrets in service configuration returned to clients
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    /// Container tools for Aspire's container resources
+    #[serde(default)]
+    pub docker: DockerConfig,
+    /// Persistent log of orchestrator actions and health transitions
+    #[serde(default)]
+    pub history: HistoryConfig,
+    /// Snapshot and restore of local database containers
+    #[serde(default)]
+    pub databases: DatabasesConfig,
+    /// `teardown_environment` and the idle timeout that triggers it
+    #[serde(default)]
+    pub teardown: TeardownConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +39,25 @@ pub struct ServerConfig {
     pub version: String,
     pub port: u16,
     pub host: String,
+    /// Bearer token HTTP transport clients must send; open when neither
+    /// this nor `auth_token_env` is set
+    #[serde(default)]
+    pub auth_token: Option<Secret>,
+    /// Name of an environment variable holding the HTTP transport token
+    #[serde(default)]
+    pub auth_token_env: Option<String>,
+    /// Browser origins, besides localhost, allowed to call the HTTP
+    /// transport, e.g. `https://tools.example.com`
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// How long an HTTP transport session lives without requests or an open
+    /// notification stream
+    #[serde(default = "default_session_idle_timeout_ms")]
+    pub session_idle_timeout_ms: u64,
+}
+
+fn default_session_idle_timeout_ms() -> u64 {
+    30 * 60_000
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +66,72 @@ pub struct AspireConfig {
     pub dashboard_url: String,
     pub polling_interval_ms: u64,
     pub api_timeout_ms: u64,
+    /// How long start/stop/restart of a single service waits for the target state
+    #[serde(default = "default_service_action_timeout_ms")]
+    pub service_action_timeout_ms: u64,
+    /// AppHost manifest used for the startup order; the dashboard's
+    /// resource relationships are used when unset
+    #[serde(default)]
+    pub manifest_path: Option<String>,
+    /// Credentials for a secured dashboard
+    #[serde(default)]
+    pub auth: DashboardAuthConfig,
+}
+
+fn default_service_action_timeout_ms() -> u64 {
+    60_000
+}
+
+/// Dashboard browser token or API key; set at most one source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardAuthConfig {
+    #[serde(default)]
+    pub token: Option<Secret>,
+    /// Re-read when the dashboard rejects the current token
+    #[serde(default)]
+    pub token_file: Option<String>,
+    /// Name of an environment variable holding the token
+    #[serde(default)]
+    pub token_env: Option<String>,
+    /// `Authorization` sends `Bearer <token>`; other headers carry the bare token
+    #[serde(default = "default_auth_header")]
+    pub header: String,
+}
+
+fn default_auth_header() -> String {
+    "x-api-key".to_string()
+}
+
+impl Default for DashboardAuthConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            token_file: None,
+            token_env: None,
+            header: default_auth_header(),
+        }
+    }
+}
+
+/// A credential whose `Debug` output is redacted
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+    
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +139,391 @@ pub struct MonitoringConfig {
     pub monitor_processes: bool,
     pub watch_config_files: bool,
     pub health_check_interval_ms: u64,
+    /// Per-service probe overrides, keyed by resource name
+    #[serde(default)]
+    pub probes: HashMap<String, ProbeConfig>,
+    /// How often resource metrics are pulled from the dashboard
+    #[serde(default = "default_metrics_interval_ms")]
+    pub metrics_interval_ms: u64,
+    /// How long collected metrics are kept per service
+    #[serde(default = "default_metrics_retention_minutes")]
+    pub metrics_retention_minutes: u64,
+    /// When repeated exits count as a crash loop
+    #[serde(default)]
+    pub crash_loop: CrashLoopConfig,
+    /// Per-service CPU and memory budgets
+    #[serde(default)]
+    pub budgets: BudgetsConfig,
+}
+
+fn default_metrics_interval_ms() -> u64 {
+    10_000
+}
+
+fn default_metrics_retention_minutes() -> u64 {
+    15
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrashLoopConfig {
+    /// Exits within `window_minutes` that make a crash loop
+    pub restart_threshold: u32,
+    pub window_minutes: u64,
+    /// Console log lines captured before each exit
+    pub log_lines: usize,
+}
+
+impl Default for CrashLoopConfig {
+    fn default() -> Self {
+        Self {
+            restart_threshold: 3,
+            window_minutes: 5,
+            log_lines: 50,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BudgetsConfig {
+    /// How long a service has to stay over budget before it is reported,
+    /// and back under the recovery level before it counts as recovered
+    pub sustained_seconds: u64,
+    /// Percent of the budget a service has to fall below to recover
+    pub recovery_percent: f64,
+    /// Keyed by resource name
+    pub services: HashMap<String, ResourceBudget>,
+}
+
+impl Default for BudgetsConfig {
+    fn default() -> Self {
+        Self {
+            sustained_seconds: 60,
+            recovery_percent: 90.0,
+            services: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceBudget {
+    #[serde(default)]
+    pub max_memory_mb: Option<u64>,
+    /// Percent of one core, so 200 is two full cores
+    #[serde(default)]
+    pub max_cpu_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeKind {
+    #[default]
+    Http,
+    Tcp,
+    /// grpc.health.v1 health-checking protocol
+    Grpc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeConfig {
+    #[serde(default)]
+    pub kind: ProbeKind,
+    /// Request path for HTTP probes
+    #[serde(default = "default_probe_path")]
+    pub path: String,
+    /// Falls back to `monitoring.health_check_interval_ms`
+    pub interval_ms: Option<u64>,
+    #[serde(default = "default_probe_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Consecutive failures before a service is marked unhealthy
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Failures are ignored this long after a service is first seen
+    #[serde(default)]
+    pub startup_grace_ms: u64,
+    /// Service name sent in gRPC health checks; empty checks the whole server
+    pub grpc_service: Option<String>,
+    /// Checked after the probe passes; the service stays starting until it does
+    pub readiness: Option<ReadinessCheck>,
+}
+
+/// Readiness gate for services that are reachable before they can serve,
+/// e.g. while database migrations run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ReadinessCheck {
+    /// Ready once `path` on the service answers with a 2xx status
+    Http { path: String },
+    /// Ready once `query` returns a row, run with psql or sqlcmd depending on
+    /// the connection string
+    Sql {
+        connection_string: String,
+        #[serde(default = "default_readiness_query")]
+        query: String,
+    },
+    /// Ready once the shell command exits with status 0
+    Command { command: String },
+}
+
+fn default_readiness_query() -> String {
+    "SELECT 1".to_string()
+}
+
+fn default_probe_path() -> String {
+    "/health".to_string()
+}
+
+fn default_probe_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            kind: ProbeKind::default(),
+            path: default_probe_path(),
+            interval_ms: None,
+            timeout_ms: default_probe_timeout_ms(),
+            failure_threshold: default_failure_threshold(),
+            startup_grace_ms: 0,
+            grpc_service: None,
+            readiness: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// Attempts per idempotent request (GET, HEAD, PUT, DELETE, OPTIONS), including the first
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubles per retry up to `max_backoff_ms`
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Timeout of each attempt
+    pub request_timeout_ms: u64,
+    /// Consecutive failures before calls to a host are short-circuited
+    pub breaker_failure_threshold: u32,
+    /// How long an open breaker short-circuits calls before a trial call
+    pub breaker_cooldown_ms: u64,
+    /// Response bodies returned by `invoke_service_endpoint` are cut off past this
+    pub max_response_body_bytes: usize,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 200,
+            max_backoff_ms: 5000,
+            request_timeout_ms: 30_000,
+            breaker_failure_threshold: 5,
+            breaker_cooldown_ms: 30_000,
+            max_response_body_bytes: 64 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretsConfig {
+    /// Case-insensitive regular expressions; environment variables and
+    /// connection-string keys matching any of them are masked
+    pub mask_patterns: Vec<String>,
+    /// Characters of a masked value shown at each end
+    pub visible_chars: usize,
+    /// Whether `get_service_config` may return unmasked values; keep this off
+    /// in shared environments
+    pub allow_unmask: bool,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            mask_patterns: ["password", "passwd", "pwd", "secret", "token", "key", "credential"]
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            visible_chars: 3,
+            allow_unmask: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DockerConfig {
+    /// Set to false to turn the container tools off entirely
+    pub enabled: bool,
+    /// Docker CLI, or a compatible one such as podman
+    pub binary: String,
+    /// Labels whose value is the Aspire resource name of a container
+    pub resource_labels: Vec<String>,
+    /// Label Aspire puts on the containers it creates
+    pub managed_label: String,
+    /// Commands `exec_in_container` may run; a command must start with the
+    /// words of one entry
+    pub exec_allowlist: Vec<String>,
+    pub command_timeout_ms: u64,
+}
+
+impl Default for DockerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            binary: "docker".to_string(),
+            resource_labels: Vec::new(),
+            managed_label: "com.microsoft.developer.usvc-dev.creatorProcessId".to_string(),
+            exec_allowlist: ["pg_isready", "redis-cli ping", "redis-cli info", "mysqladmin ping", "rabbitmq-diagnostics ping"]
+                .iter()
+                .map(|command| command.to_string())
+                .collect(),
+            command_timeout_ms: 60_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// JSONL file, relative to the working directory
+    pub path: String,
+    /// Size at which the file is rotated to `<path>.1`
+    pub max_file_bytes: u64,
+    /// Rotated files kept besides the current one
+    pub max_files: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            path: "deployment-history.jsonl".to_string(),
+            max_file_bytes: 10 * 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabasesConfig {
+    /// Snapshots are stored as `<snapshot_dir>/<name>/`, relative to the working directory
+    pub snapshot_dir: String,
+    /// How long a single dump or import may run
+    pub command_timeout_ms: u64,
+    /// Database resources by name; only those flagged `local_dev` can be
+    /// snapshotted or restored
+    pub resources: HashMap<String, DatabaseResourceConfig>,
+}
+
+impl Default for DatabasesConfig {
+    fn default() -> Self {
+        Self {
+            snapshot_dir: "db-snapshots".to_string(),
+            command_timeout_ms: 600_000,
+            resources: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatabaseResourceConfig {
+    /// Restoring drops the database, so only disposable development
+    /// databases should set this
+    #[serde(default)]
+    pub local_dev: bool,
+    /// Database used when a tool call names none
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Database user; `postgres` or `root` when unset
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Detected from the resource type or container image when unset
+    #[serde(default)]
+    pub dialect: Option<DatabaseDialect>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseDialect {
+    Postgres,
+    Mysql,
+}
+
+impl DatabaseDialect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DatabaseDialect::Postgres => "postgres",
+            DatabaseDialect::Mysql => "mysql",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TeardownConfig {
+    /// Tear the environment down after this many minutes without MCP
+    /// requests; never when unset
+    pub idle_timeout_minutes: Option<u64>,
+    /// How long before an idle teardown clients get a warning notification
+    pub warning_minutes: u64,
+    /// Also stop the AppHost once its services are stopped
+    pub stop_apphost: bool,
+    /// Remove the containers and networks Aspire created
+    pub remove_containers: bool,
+}
+
+impl Default for TeardownConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout_minutes: None,
+            warning_minutes: 5,
+            stop_apphost: true,
+            remove_containers: true,
+        }
+    }
+}
+
+/// Whether `name` is safe to use as a database name in dump and restore commands
+pub fn is_valid_database_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GrpcConfig {
+    /// Keyed by resource name
+    #[serde(default)]
+    pub services: HashMap<String, GrpcServiceConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GrpcServiceConfig {
+    /// Overrides the endpoint reported by the dashboard
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// TLS is used when this is set or the endpoint is `https`
+    #[serde(default)]
+    pub tls: Option<GrpcTlsConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrpcTlsConfig {
+    /// PEM bundle of trusted CAs; the system roots are used when unset
+    pub ca_cert: Option<String>,
+    /// PEM client certificate for mTLS, together with `client_key`
+    pub client_cert: Option<String>,
+    pub client_key: Option<String>,
+    /// Name verified against the server certificate and sent as SNI,
+    /// instead of the endpoint host
+    pub server_name: Option<String>,
+    /// Accept any server certificate. Local development only
+    pub insecure_skip_verify: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,17 +548,29 @@ impl Default for Settings {
                 version: "1.0.0".to_string(),
                 port: 3001,
                 host: "127.0.0.1".to_string(),
+                auth_token: None,
+                auth_token_env: None,
+                allowed_origins: Vec::new(),
+                session_idle_timeout_ms: default_session_idle_timeout_ms(),
             },
             aspire: AspireConfig {
                 auto_discovery: true,
                 dashboard_url: "https://localhost:15888".to_string(),
                 polling_interval_ms: 1000,
                 api_timeout_ms: 5000,
+                service_action_timeout_ms: default_service_action_timeout_ms(),
+                manifest_path: None,
+                auth: DashboardAuthConfig::default(),
             },
             monitoring: MonitoringConfig {
                 monitor_processes: true,
                 watch_config_files: true,
                 health_check_interval_ms: 5000,
+                probes: HashMap::new(),
+                metrics_interval_ms: default_metrics_interval_ms(),
+                metrics_retention_minutes: default_metrics_retention_minutes(),
+                crash_loop: CrashLoopConfig::default(),
+                budgets: BudgetsConfig::default(),
             },
             network: NetworkConfig {
                 local_interface: "0.0.0.0".to_string(),
@@ -78,20 +582,235 @@ impl Default for Settings {
                 file: Some("axiom-aspire-mcp.log".to_string()),
                 structured: true,
             },
+            http: HttpConfig::default(),
+            grpc: GrpcConfig::default(),
+            secrets: SecretsConfig::default(),
+            docker: DockerConfig::default(),
+            history: HistoryConfig::default(),
+            databases: DatabasesConfig::default(),
+            teardown: TeardownConfig::default(),
         }
     }
 }
 
+/// Prefix for environment overrides; `__` separates key path segments
+pub const ENV_PREFIX: &str = "AXIOM_";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SettingsError {
+    #[error("Unknown profile '{name}' (available: {})", .available.join(", "))]
+    UnknownProfile { name: String, available: Vec<String> },
+    #[error("Invalid configuration at '{path}': {message}")]
+    Invalid { path: String, message: String },
+}
+
 impl Settings {
+    /// Load the base configuration with environment overrides
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        if path.as_ref().exists() {
+        Self::load(path, None)
+    }
+    
+    /// Load a configuration file, layering sources in increasing precedence:
+    ///
+    /// 1. the base tables of the file (built-in defaults if the file is missing)
+    /// 2. `[profiles.<profile>]`, when a profile is selected
+    /// 3. `AXIOM_<SECTION>__<KEY>` environment variables,
+    ///    e.g. `AXIOM_ASPIRE__DASHBOARD_URL`
+    pub fn load<P: AsRef<Path>>(path: P, profile: Option<&str>) -> Result<Self> {
+        Self::load_with_env(path, profile, std::env::vars())
+    }
+    
+    pub fn load_with_env<P, I>(path: P, profile: Option<&str>, env: I) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut config = if path.as_ref().exists() {
             let content = std::fs::read_to_string(path)?;
-            let settings: Settings = toml::from_str(&content)?;
-            Ok(settings)
+            toml::from_str::<toml::Value>(&content)?
         } else {
             tracing::warn!("Config file not found, using defaults");
-            Ok(Settings::default())
+            toml::Value::try_from(Settings::default())?
+        };
+        
+        let mut profiles = match config.as_table_mut().and_then(|table| table.remove("profiles")) {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => return Err(SettingsError::Invalid {
+                path: "profiles".to_string(),
+                message: "expected a table of profiles".to_string(),
+            }.into()),
+            None => toml::map::Map::new(),
+        };
+        
+        if let Some(name) = profile {
+            let overlay = profiles.remove(name).ok_or_else(|| {
+                let mut available: Vec<String> = profiles.keys().cloned().collect();
+                available.sort();
+                SettingsError::UnknownProfile { name: name.to_string(), available }
+            })?;
+            tracing::info!("Using configuration profile: {}", name);
+            merge_values(&mut config, overlay);
+        }
+        
+        for (key, value) in env {
+            let Some(path) = key.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let segments: Vec<String> = path.split("__").map(|s| s.to_ascii_lowercase()).collect();
+            // Single-segment names like AXIOM_HOME are not settings
+            if segments.len() < 2 || segments.iter().any(|s| s.is_empty()) {
+                continue;
+            }
+            tracing::debug!("Overriding {} from {}", segments.join("."), key);
+            set_value(&mut config, &segments, parse_env_value(&value));
+        }
+        
+        let settings: Settings = serde_path_to_error::deserialize(config).map_err(|e| SettingsError::Invalid {
+            path: e.path().to_string(),
+            message: e.inner().to_string(),
+        })?;
+        settings.validate()?;
+        Ok(settings)
+    }
+    
+    /// Reject values that deserialize but cannot work
+    pub fn validate(&self) -> std::result::Result<(), SettingsError> {
+        let invalid = |path: &str, message: &str| SettingsError::Invalid {
+            path: path.to_string(),
+            message: message.to_string(),
+        };
+        
+        if self.server.host.trim().is_empty() {
+            return Err(invalid("server.host", "must not be empty"));
+        }
+        if self.server.auth_token.is_some() && self.server.auth_token_env.is_some() {
+            return Err(invalid("server", "set only one of auth_token or auth_token_env"));
+        }
+        if self.aspire.dashboard_url.trim().is_empty() {
+            return Err(invalid("aspire.dashboard_url", "must not be empty"));
+        }
+        let auth = &self.aspire.auth;
+        let token_sources = [auth.token.is_some(), auth.token_file.is_some(), auth.token_env.is_some()];
+        if token_sources.iter().filter(|set| **set).count() > 1 {
+            return Err(invalid("aspire.auth", "set only one of token, token_file or token_env"));
+        }
+        if self.http.max_attempts == 0 {
+            return Err(invalid("http.max_attempts", "must be at least 1"));
+        }
+        if self.http.request_timeout_ms == 0 {
+            return Err(invalid("http.request_timeout_ms", "must be greater than 0"));
+        }
+        if self.http.max_response_body_bytes == 0 {
+            return Err(invalid("http.max_response_body_bytes", "must be greater than 0"));
+        }
+        if self.aspire.polling_interval_ms == 0 {
+            return Err(invalid("aspire.polling_interval_ms", "must be greater than 0"));
+        }
+        if self.monitoring.health_check_interval_ms == 0 {
+            return Err(invalid("monitoring.health_check_interval_ms", "must be greater than 0"));
+        }
+        if self.monitoring.metrics_interval_ms == 0 {
+            return Err(invalid("monitoring.metrics_interval_ms", "must be greater than 0"));
+        }
+        if self.monitoring.metrics_retention_minutes == 0 {
+            return Err(invalid("monitoring.metrics_retention_minutes", "must be greater than 0"));
+        }
+        if self.monitoring.crash_loop.restart_threshold == 0 {
+            return Err(invalid("monitoring.crash_loop.restart_threshold", "must be at least 1"));
+        }
+        if self.monitoring.crash_loop.window_minutes == 0 {
+            return Err(invalid("monitoring.crash_loop.window_minutes", "must be greater than 0"));
+        }
+        let budgets = &self.monitoring.budgets;
+        if budgets.recovery_percent.is_nan() || budgets.recovery_percent <= 0.0 || budgets.recovery_percent > 100.0 {
+            return Err(invalid("monitoring.budgets.recovery_percent", "must be greater than 0 and at most 100"));
+        }
+        for (service, budget) in &budgets.services {
+            if budget.max_memory_mb == Some(0) {
+                return Err(invalid(&format!("monitoring.budgets.services.{}.max_memory_mb", service), "must be greater than 0"));
+            }
+            if budget.max_cpu_percent.is_some_and(|cpu| cpu.is_nan() || cpu <= 0.0) {
+                return Err(invalid(&format!("monitoring.budgets.services.{}.max_cpu_percent", service), "must be greater than 0"));
+            }
+        }
+        for (service, probe) in &self.monitoring.probes {
+            if probe.failure_threshold == 0 {
+                return Err(invalid(
+                    &format!("monitoring.probes.{}.failure_threshold", service),
+                    "must be greater than 0",
+                ));
+            }
+            let readiness_value = match &probe.readiness {
+                Some(ReadinessCheck::Http { path }) => Some(("path", path)),
+                Some(ReadinessCheck::Sql { connection_string, .. }) => Some(("connection_string", connection_string)),
+                Some(ReadinessCheck::Command { command }) => Some(("command", command)),
+                None => None,
+            };
+            if let Some((field, value)) = readiness_value {
+                if value.trim().is_empty() {
+                    return Err(invalid(
+                        &format!("monitoring.probes.{}.readiness.{}", service, field),
+                        "must not be empty",
+                    ));
+                }
+            }
+        }
+        for (service, target) in &self.grpc.services {
+            if let Some(tls) = &target.tls {
+                if tls.client_cert.is_some() != tls.client_key.is_some() {
+                    return Err(invalid(
+                        &format!("grpc.services.{}.tls", service),
+                        "client_cert and client_key must be set together",
+                    ));
+                }
+            }
+        }
+        if self.docker.enabled && self.docker.binary.trim().is_empty() {
+            return Err(invalid("docker.binary", "must not be empty"));
         }
+        if self.docker.command_timeout_ms == 0 {
+            return Err(invalid("docker.command_timeout_ms", "must be greater than 0"));
+        }
+        if self.history.path.trim().is_empty() {
+            return Err(invalid("history.path", "must not be empty"));
+        }
+        if self.history.max_file_bytes == 0 {
+            return Err(invalid("history.max_file_bytes", "must be greater than 0"));
+        }
+        if self.databases.snapshot_dir.trim().is_empty() {
+            return Err(invalid("databases.snapshot_dir", "must not be empty"));
+        }
+        if self.databases.command_timeout_ms == 0 {
+            return Err(invalid("databases.command_timeout_ms", "must be greater than 0"));
+        }
+        for (service, resource) in &self.databases.resources {
+            if resource.database.as_deref().is_some_and(|database| !is_valid_database_name(database)) {
+                return Err(invalid(
+                    &format!("databases.resources.{}.database", service),
+                    "may only contain letters, digits, '_' and '-'",
+                ));
+            }
+            if resource.user.as_deref().is_some_and(|user| !is_valid_database_name(user)) {
+                return Err(invalid(
+                    &format!("databases.resources.{}.user", service),
+                    "may only contain letters, digits, '_' and '-'",
+                ));
+            }
+        }
+        if self.teardown.idle_timeout_minutes == Some(0) {
+            return Err(invalid("teardown.idle_timeout_minutes", "must be greater than 0"));
+        }
+        if let Some(idle_timeout) = self.teardown.idle_timeout_minutes {
+            if self.teardown.warning_minutes >= idle_timeout {
+                return Err(invalid("teardown.warning_minutes", "must be less than teardown.idle_timeout_minutes"));
+            }
+        }
+        for (index, pattern) in self.secrets.mask_patterns.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(invalid(&format!("secrets.mask_patterns[{}]", index), &e.to_string()));
+            }
+        }
+        Ok(())
     }
     
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
@@ -99,4 +818,53 @@ impl Settings {
         std::fs::write(path, content)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Overlay `overlay` onto `base`, merging tables key by key
+fn merge_values(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn set_value(root: &mut toml::Value, path: &[String], value: toml::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    
+    let mut current = root;
+    for segment in parents {
+        if !current.is_table() {
+            *current = toml::Value::Table(toml::map::Map::new());
+        }
+        current = current
+            .as_table_mut()
+            .expect("ensured table above")
+            .entry(segment.clone())
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+    }
+    
+    if !current.is_table() {
+        *current = toml::Value::Table(toml::map::Map::new());
+    }
+    current.as_table_mut().expect("ensured table above").insert(last.clone(), value);
+}
+
+/// Environment values are TOML literals when they parse as one (`true`, `3001`), else strings
+fn parse_env_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .filter(|value| !value.is_table())
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
@@ -1,5 +1,74 @@
-// Library exports for testing
+//! Axiom Aspire deployer, as an MCP server (`main.rs`) or as a library
+//!
+//! Rust tooling that wants to drive a local Aspire environment without
+//! speaking MCP uses [`AspireDeployerApi`] in-process through
+//! [`LocalAspireDeployer`]:
+//!
+//! ```no_run
+//! use axiom_aspire_mcp::{AspireDeployerApi, LocalAspireDeployer};
+//! use axiom_aspire_mcp::config::Settings;
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let settings = Settings::load("config.toml", None)?;
+//! let deployer = LocalAspireDeployer::from_settings(&settings).await?;
+//!
+//! for result in deployer.get_health(false).await? {
+//!     println!("{:?}", result);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Anything that accepts an `Arc<dyn AspireDeployerApi>` can be handed a
+//! fake instead:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use axiom_aspire_mcp::AspireDeployerApi;
+//! use axiom_aspire_mcp::clients::aspire::ServiceLogLine;
+//! use axiom_aspire_mcp::services::health::HealthCheckResult;
+//! use axiom_aspire_mcp::services::logs::{LogQuery, LogWindow};
+//! use axiom_aspire_mcp::services::network::{EndpointResolution, ResolvedEndpoint};
+//! use axiom_aspire_mcp::services::orchestrator::{ServiceAction, ServiceActionResult};
+//! use futures::future::{BoxFuture, FutureExt};
+//! use futures::stream::{self, BoxStream, StreamExt};
+//!
+//! struct NothingRunning;
+//!
+//! impl AspireDeployerApi for NothingRunning {
+//!     fn change_service_state<'a>(&'a self, service: &'a str, _: ServiceAction, _: Option<Duration>) -> BoxFuture<'a, anyhow::Result<ServiceActionResult>> {
+//!         async move { Err(anyhow::anyhow!("{} is not running", service)) }.boxed()
+//!     }
+//!     fn get_health(&self, _: bool) -> BoxFuture<'_, anyhow::Result<Vec<HealthCheckResult>>> {
+//!         async { Ok(Vec::new()) }.boxed()
+//!     }
+//!     fn resolve_endpoint<'a>(&'a self, service: &'a str, _: Option<&'a str>, _: bool) -> BoxFuture<'a, anyhow::Result<EndpointResolution>> {
+//!         async move { Err(anyhow::anyhow!("{} has no endpoints", service)) }.boxed()
+//!     }
+//!     fn write_env_file<'a>(&'a self, _: &'a std::path::Path, _: bool) -> BoxFuture<'a, anyhow::Result<Vec<ResolvedEndpoint>>> {
+//!         async { Ok(Vec::new()) }.boxed()
+//!     }
+//!     fn read_logs<'a>(&'a self, query: &'a LogQuery) -> BoxFuture<'a, anyhow::Result<LogWindow>> {
+//!         async move {
+//!             Ok(LogWindow { service: query.service.clone(), lines: Vec::new(), cursor: None, followed_ms: 0 })
+//!         }.boxed()
+//!     }
+//!     fn stream_logs(&self, _: LogQuery) -> BoxStream<'static, anyhow::Result<ServiceLogLine>> {
+//!         stream::empty().boxed()
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let deployer: Arc<dyn AspireDeployerApi> = Arc::new(NothingRunning);
+//! assert!(deployer.start_service("api", None).await.is_err());
+//! assert!(deployer.get_health(true).await.unwrap().is_empty());
+//! # });
+//! ```
+
 pub mod mcp;
 pub mod services;
 pub mod clients;
-pub mod config;
\ No newline at end of file
+pub mod config;
+
+pub use services::{AspireDeployerApi, LocalAspireDeployer, UnsupportedOperation};
@@ -1,15 +1,14 @@
 use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
+use std::time::Duration;
 use tracing::{info, error};
 
-mod mcp;
-mod services;
-mod clients;
-mod config;
-
-use crate::config::Settings;
-use crate::mcp::server::AxiomAspireMcpServer;
+use axiom_aspire_mcp::config::Settings;
+use axiom_aspire_mcp::config::reload::ConfigSource;
+use axiom_aspire_mcp::mcp::server::{AxiomAspireMcpServer, Transport};
+use axiom_aspire_mcp::services::orchestrator::WaitForHealthyOptions;
+use axiom_aspire_mcp::services::topology::MapFormat;
 
 #[derive(Parser)]
 #[command(name = "axiom-aspire-mcp")]
@@ -18,9 +17,38 @@ struct Args {
     #[arg(short, long, default_value = "config.toml")]
     config: PathBuf,
     
+    /// Profile from `[profiles.<name>]` to merge over the base configuration
+    #[arg(long)]
+    profile: Option<String>,
+    
     #[arg(short, long, default_value = "3001")]
     port: u16,
     
+    /// tcp for newline-delimited JSON-RPC, http for MCP streamable HTTP with SSE
+    #[arg(long, default_value = "tcp")]
+    transport: String,
+    
+    /// Use the next free port if --port is already taken
+    #[arg(long)]
+    auto_port: bool,
+    
+    /// Wait up to this many seconds for services to become healthy, print a
+    /// summary and exit instead of serving; exits non-zero if any timed out
+    #[arg(long, value_name = "TIMEOUT_SECONDS")]
+    wait_for_healthy: Option<u64>,
+    
+    /// Services to wait for with --wait-for-healthy (default: all)
+    #[arg(long, value_delimiter = ',')]
+    wait_services: Vec<String>,
+    
+    /// Consecutive passing checks required by --wait-for-healthy
+    #[arg(long, default_value = "1")]
+    wait_consecutive: u32,
+    
+    /// Print the service topology as json, dot or mermaid and exit
+    #[arg(long, value_name = "FORMAT")]
+    service_map: Option<String>,
+    
     #[arg(short, long)]
     verbose: bool,
 }
@@ -43,15 +71,59 @@ async fn main() -> Result<()> {
     info!("Starting Axiom Aspire MCP Server");
     
     // Load configuration
-    let settings = Settings::from_file(&args.config)?;
+    let settings = Settings::load(&args.config, args.profile.as_deref())?;
+    let config_source = ConfigSource::new(&args.config, args.profile.clone());
     
     // Create and start MCP server
-    let server = AxiomAspireMcpServer::new(settings).await?;
+    let server = AxiomAspireMcpServer::with_config_source(settings, Some(config_source)).await?;
     
-    info!("Axiom Aspire MCP Server listening on port {}", args.port);
+    if let Some(timeout) = args.wait_for_healthy {
+        let options = WaitForHealthyOptions {
+            services: (!args.wait_services.is_empty()).then_some(args.wait_services),
+            timeout: Duration::from_secs(timeout),
+            required_consecutive: args.wait_consecutive,
+        };
+        let result = server.orchestrator().wait_for_healthy(&options, |status| {
+            info!(
+                "{}: {} ({} consecutive healthy checks)",
+                status.service,
+                if status.healthy { "healthy" } else { "waiting" },
+                status.consecutive_healthy
+            );
+        }).await?;
+        
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        if !result.all_healthy {
+            error!("{} service(s) did not become healthy", result.timed_out.len());
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    
+    if let Some(format) = args.service_map {
+        let format = MapFormat::parse(&format)
+            .ok_or_else(|| anyhow::anyhow!("Unknown --service-map format '{}'; expected json, dot or mermaid", format))?;
+        let mut map = server.orchestrator().get_service_map(None).await?;
+        
+        // Nothing has been monitored yet, so probe each service once
+        let health_monitor = server.health_monitor();
+        for node in &mut map.nodes {
+            if let Some(endpoint) = node.endpoints.iter().find(|e| e.url.starts_with("http")) {
+                node.health = Some(health_monitor.probe_service(&node.name, &endpoint.url).await);
+            }
+        }
+        
+        match map.render(format) {
+            Some(rendered) => print!("{}", rendered),
+            None => println!("{}", serde_json::to_string_pretty(&map)?),
+        }
+        return Ok(());
+    }
     
     // Run server
-    if let Err(e) = server.run(args.port).await {
+    let transport = Transport::parse(&args.transport)
+        .ok_or_else(|| anyhow::anyhow!("Unknown --transport '{}'; expected tcp or http", args.transport))?;
+    if let Err(e) = server.run(args.port, args.auto_port, transport).await {
         error!("Server error: {}", e);
         return Err(e);
     }
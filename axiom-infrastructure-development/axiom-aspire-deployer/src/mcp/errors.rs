@@ -0,0 +1,198 @@
+use reqwest::{StatusCode, Url};
+use std::error::Error as StdError;
+
+use crate::clients::aspire::DashboardStatusError;
+use crate::clients::auth::DashboardAuthError;
+use crate::clients::docker::DockerError;
+use crate::clients::resilience::{self, HttpResilienceError};
+use crate::config::reload::ConfigReloadError;
+use crate::mcp::protocol::{ErrorCode, McpError};
+use crate::services::manifest::ManifestError;
+use crate::services::network::EndpointError;
+use crate::services::orchestrator::{ScaleError, ServiceActionError, ServiceConfigError};
+use crate::services::ports::PortConflictError;
+use crate::services::api::UnsupportedOperation;
+use crate::services::proxy::ServiceProxyError;
+use crate::services::snapshots::DatabaseSnapshotError;
+use crate::services::startup::StartupPlanError;
+use crate::services::traces::TraceError;
+
+/// Turn a failed tool call into an MCP error with a code clients can act on
+///
+/// Connection failures count as `DashboardUnavailable` only when they are
+/// for `dashboard_url`; unreachable services are retryable internal errors.
+pub fn classify(error: &anyhow::Error, dashboard_url: &str) -> McpError {
+    let message = error.to_string();
+
+    if let Some(e) = find::<ServiceActionError>(error) {
+        return match e {
+            ServiceActionError::UnknownService { name, available } => McpError::with_code(ErrorCode::ServiceNotFound, message)
+                .service(name)
+                .detail("available_services", available),
+            ServiceActionError::Timeout { service, action, elapsed_ms } => McpError::with_code(ErrorCode::Timeout, message)
+                .service(service)
+                .detail("action", action)
+                .detail("elapsed_ms", elapsed_ms),
+        };
+    }
+    if let Some(UnsupportedOperation { operation }) = find::<UnsupportedOperation>(error) {
+        return McpError::with_code(ErrorCode::Unsupported, message).detail("operation", operation);
+    }
+    if let Some(e) = find::<ScaleError>(error) {
+        return match e {
+            ScaleError::Unsupported { service, resource_type } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .service(service)
+                .detail("resource_type", resource_type),
+            ScaleError::ConfirmationRequired { service } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .service(service)
+                .detail("parameter", "confirm_stop"),
+        };
+    }
+    if let Some(ServiceConfigError::UnmaskForbidden) = find::<ServiceConfigError>(error) {
+        return McpError::with_code(ErrorCode::InvalidArgument, message).detail("setting", "secrets.allow_unmask");
+    }
+    if find::<ManifestError>(error).is_some() {
+        return McpError::with_code(ErrorCode::InvalidArgument, message);
+    }
+    if let Some(StartupPlanError::Cycle { cycle }) = find::<StartupPlanError>(error) {
+        return McpError::with_code(ErrorCode::Conflict, message).detail("cycle", cycle);
+    }
+    if let Some(PortConflictError { conflicts }) = find::<PortConflictError>(error) {
+        return McpError::with_code(ErrorCode::Conflict, message).detail("conflicts", conflicts);
+    }
+    if let Some(e) = find::<DockerError>(error) {
+        return match e {
+            DockerError::Disabled => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .detail("capability", "docker")
+                .detail("reason", "disabled"),
+            // The daemon may just not be up yet
+            DockerError::Unavailable { .. } => McpError::with_code(ErrorCode::Internal, message)
+                .retryable(true)
+                .detail("capability", "docker")
+                .detail("reason", "unavailable"),
+            DockerError::ContainerNotFound { resource, available } => McpError::with_code(ErrorCode::ServiceNotFound, message)
+                .service(resource)
+                .detail("available_containers", available),
+            DockerError::CommandNotAllowed { allowed, .. } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .detail("allowed_commands", allowed),
+            DockerError::CommandFailed { .. } => McpError::with_code(ErrorCode::Internal, message),
+        };
+    }
+    if let Some(e) = find::<DatabaseSnapshotError>(error) {
+        return match e {
+            DatabaseSnapshotError::NotLocalDev { service } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .service(service)
+                .detail("setting", format!("databases.resources.{}.local_dev", service)),
+            DatabaseSnapshotError::UnknownDialect { service, resource_type, image } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .service(service)
+                .detail("resource_type", resource_type)
+                .detail("image", image),
+            DatabaseSnapshotError::MissingDatabase { service } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .service(service)
+                .detail("parameter", "database"),
+            DatabaseSnapshotError::InvalidName { value, .. } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .detail("value", value),
+            DatabaseSnapshotError::SnapshotExists { name } => McpError::with_code(ErrorCode::Conflict, message)
+                .detail("snapshot", name)
+                .detail("parameter", "overwrite"),
+            DatabaseSnapshotError::SnapshotNotFound { name, available } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .detail("snapshot", name)
+                .detail("available_snapshots", available),
+            DatabaseSnapshotError::DialectMismatch { service, snapshot, target, .. } => McpError::with_code(ErrorCode::Conflict, message)
+                .service(service)
+                .detail("snapshot_dialect", snapshot.as_str())
+                .detail("target_dialect", target.as_str()),
+        };
+    }
+    if let Some(e) = find::<ServiceProxyError>(error) {
+        return match e {
+            ServiceProxyError::UnsupportedMethod { .. } => McpError::with_code(ErrorCode::InvalidArgument, message),
+            ServiceProxyError::NoHttpEndpoint { service, endpoint } => {
+                let error = McpError::with_code(ErrorCode::InvalidArgument, message).service(service);
+                match endpoint {
+                    Some(endpoint) => error.endpoint(endpoint),
+                    None => error,
+                }
+            }
+            ServiceProxyError::OffEnvironment { url, allowed } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .endpoint(url)
+                .detail("allowed_origins", allowed),
+        };
+    }
+    if let Some(EndpointError::UnknownEndpoint { service, endpoint, available }) = find::<EndpointError>(error) {
+        return McpError::with_code(ErrorCode::InvalidArgument, message)
+            .service(service)
+            .endpoint(endpoint)
+            .detail("available_endpoints", available);
+    }
+    if let Some(TraceError::NotFound { trace_id }) = find::<TraceError>(error) {
+        return McpError::with_code(ErrorCode::InvalidArgument, message).detail("trace_id", trace_id);
+    }
+    if let Some(e) = find::<ConfigReloadError>(error) {
+        return match e {
+            ConfigReloadError::NoSource => McpError::with_code(ErrorCode::Conflict, message),
+            ConfigReloadError::Rejected { path, .. } => McpError::with_code(ErrorCode::InvalidArgument, message)
+                .detail("path", path),
+        };
+    }
+    if let Some(e) = find::<DashboardAuthError>(error) {
+        return McpError::with_code(ErrorCode::AuthFailed, message).detail("status", e.status);
+    }
+    if let Some(e) = find::<DashboardStatusError>(error) {
+        let code = match e.status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorCode::AuthFailed,
+            status if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS => ErrorCode::DashboardUnavailable,
+            _ => ErrorCode::Internal,
+        };
+        return McpError::with_code(code, message).detail("status", e.status.as_u16());
+    }
+    if let Some(e) = find::<HttpResilienceError>(error) {
+        let (host, url) = match e {
+            HttpResilienceError::CircuitOpen { host, .. } => (host, None),
+            HttpResilienceError::RetriesExhausted { host, url, .. } => (host, Some(url)),
+        };
+        let error = unreachable_host(host, dashboard_url, message);
+        let error = match url {
+            Some(url) => error.endpoint(url),
+            None => error,
+        };
+        return match e {
+            HttpResilienceError::CircuitOpen { retry_after_ms, .. } => error.detail("retry_after_ms", retry_after_ms),
+            HttpResilienceError::RetriesExhausted { attempts, .. } => error.detail("attempts", attempts),
+        };
+    }
+    if let Some(e) = find::<reqwest::Error>(error) {
+        if e.is_timeout() || e.is_connect() {
+            let host = e.url().map(resilience::host_key).unwrap_or_default();
+            let error = if e.is_timeout() && !is_dashboard(&host, dashboard_url) {
+                McpError::with_code(ErrorCode::Timeout, message)
+            } else {
+                unreachable_host(&host, dashboard_url, message)
+            };
+            return match e.url() {
+                Some(url) => error.endpoint(url.as_str()),
+                None => error,
+            };
+        }
+    }
+
+    McpError::with_code(ErrorCode::Internal, format!("Internal error: {}", message))
+}
+
+/// The first error of type `T` in the chain of causes
+fn find<T: StdError + Send + Sync + 'static>(error: &anyhow::Error) -> Option<&T> {
+    error.chain().find_map(|cause| cause.downcast_ref::<T>())
+}
+
+/// A host that did not answer: the dashboard, or one of the services
+fn unreachable_host(host: &str, dashboard_url: &str, message: String) -> McpError {
+    if is_dashboard(host, dashboard_url) {
+        McpError::with_code(ErrorCode::DashboardUnavailable, message)
+    } else {
+        McpError::with_code(ErrorCode::Internal, message).retryable(true)
+    }
+}
+
+fn is_dashboard(host: &str, dashboard_url: &str) -> bool {
+    Url::parse(dashboard_url).is_ok_and(|url| resilience::host_key(&url) == host)
+}
@@ -1,54 +1,234 @@
 use anyhow::Result;
-use dashmap::DashMap;
 use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
-use crate::mcp::protocol::{McpRequest, McpResponse, ServiceStatus, EndpointCallRequest, EndpointCallResponse};
-use crate::services::{AspireServiceDiscovery, AspireOrchestrator, HealthMonitor, NetworkManager};
+use crate::mcp::errors;
+use crate::mcp::notifications::ProgressReporter;
+use crate::mcp::protocol::{ErrorCode, McpRequest, McpResponse, McpError, EndpointCallRequest};
+use crate::services::AspireDeployerApi;
+use crate::services::history::{self, HistoryQuery};
+use crate::services::logs::LogQuery;
+use crate::services::orchestrator::{ServiceAction, ServiceWaitStatus, WaitForHealthyOptions};
+use crate::services::proxy::ServiceRequest;
+use crate::services::snapshots::SnapshotRequest;
+use crate::services::topology::MapFormat;
+use crate::services::traces::{TraceQuery, TraceStatus, DEFAULT_MAX_ATTRIBUTE_CHARS, DEFAULT_TRACE_LIMIT};
 
+/// Window summarized by `axiom_get_service_metrics` when none is given
+const DEFAULT_METRICS_WINDOW: Duration = Duration::from_secs(300);
+
+/// Container log lines returned by `axiom_get_container_logs` by default, and at most
+const DEFAULT_CONTAINER_LOG_TAIL: usize = 200;
+const MAX_CONTAINER_LOG_TAIL: usize = 5000;
+
+/// Entries returned by `axiom_get_deployment_history` when no limit is given
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Serves MCP tool calls
+///
+/// Every tool goes through the typed `AspireDeployerApi`; tools whose
+/// operations the API does not support fail with `ErrorCode::Unsupported`.
 pub struct RequestHandler {
-    service_discovery: Arc<AspireServiceDiscovery>,
-    orchestrator: Arc<AspireOrchestrator>,
-    network_manager: Arc<NetworkManager>,
-    health_monitor: Arc<HealthMonitor>,
-    service_state: Arc<DashMap<String, ServiceStatus>>,
+    api: Arc<dyn AspireDeployerApi>,
 }
 
 impl RequestHandler {
-    pub fn new(
-        service_discovery: Arc<AspireServiceDiscovery>,
-        orchestrator: Arc<AspireOrchestrator>,
-        network_manager: Arc<NetworkManager>,
-        health_monitor: Arc<HealthMonitor>,
-        service_state: Arc<DashMap<String, ServiceStatus>>,
-    ) -> Self {
-        Self {
-            service_discovery,
-            orchestrator,
-            network_manager,
-            health_monitor,
-            service_state,
-        }
+    pub fn new(api: Arc<dyn AspireDeployerApi>) -> Self {
+        Self { api }
     }
     
     pub async fn handle_request(&self, request: McpRequest) -> Result<McpResponse> {
+        self.handle_request_with_progress(request, None).await
+    }
+    
+    /// Handle a request, sending progress notifications through `progress`
+    /// for tools that report it
+    pub async fn handle_request_with_progress(&self, request: McpRequest, progress: Option<ProgressReporter>) -> Result<McpResponse> {
         debug!("Handling request: {}", request.method);
         
+        // Orchestrator actions taken for this request are recorded under its tool name
+        let tool = request.method.clone();
+        history::with_actor(&tool, self.dispatch(request, progress)).await
+    }
+    
+    async fn dispatch(&self, request: McpRequest, progress: Option<ProgressReporter>) -> Result<McpResponse> {
         match request.method.as_str() {
+            "axiom_aspire_health" => self.handle_aspire_health(request).await,
+            "axiom_start_service" => self.handle_service_action(request, ServiceAction::Start).await,
+            "axiom_stop_service" => self.handle_service_action(request, ServiceAction::Stop).await,
+            "axiom_restart_service" => self.handle_service_action(request, ServiceAction::Restart).await,
+            "axiom_stream_service_logs" => self.handle_stream_service_logs(request).await,
+            "axiom_resolve_endpoint" => self.handle_resolve_endpoint(request).await,
             "axiom_aspire_start" => self.handle_aspire_start(request).await,
             "axiom_aspire_stop" => self.handle_aspire_stop(request).await,
             "axiom_aspire_restart" => self.handle_aspire_restart(request).await,
             "axiom_aspire_status" => self.handle_aspire_status(request).await,
-            "axiom_aspire_health" => self.handle_aspire_health(request).await,
+            "axiom_get_health_history" => self.handle_get_health_history(request).await,
+            "axiom_diagnose_service" => self.handle_diagnose_service(request).await,
+            "axiom_get_deployment_history" => self.handle_get_deployment_history(request).await,
+            "axiom_reload_config" => self.handle_reload_config(request).await,
             "axiom_get_service_urls" => self.handle_get_service_urls(request).await,
             "axiom_call_endpoint" => self.handle_call_endpoint(request).await,
+            "axiom_invoke_service_endpoint" => self.handle_invoke_service_endpoint(request).await,
+            "axiom_scale_service" => self.handle_scale_service(request).await,
+            "axiom_get_startup_plan" => self.handle_get_startup_plan(request).await,
+            "axiom_start_services" => self.handle_start_services(request).await,
+            "axiom_teardown_environment" => self.handle_teardown_environment(request, progress).await,
+            "axiom_diff_environment" => self.handle_diff_environment(request).await,
+            "axiom_get_service_map" => self.handle_get_service_map(request).await,
+            "axiom_get_service_config" => self.handle_get_service_config(request).await,
+            "axiom_wait_for_healthy" => self.handle_wait_for_healthy(request, progress).await,
+            "axiom_restart_container" => self.handle_restart_container(request).await,
+            "axiom_get_container_logs" => self.handle_get_container_logs(request).await,
+            "axiom_exec_in_container" => self.handle_exec_in_container(request).await,
+            "axiom_snapshot_database" => self.handle_snapshot_database(request, progress).await,
+            "axiom_restore_database" => self.handle_restore_database(request, progress).await,
+            "axiom_list_database_snapshots" => self.handle_list_database_snapshots(request).await,
+            "axiom_get_service_metrics" => self.handle_get_service_metrics(request).await,
+            "axiom_get_budget_status" => self.handle_get_budget_status(request).await,
+            "axiom_search_traces" => self.handle_search_traces(request).await,
+            "axiom_get_trace" => self.handle_get_trace(request).await,
             "axiom_configure_local_network" => self.handle_configure_local_network(request).await,
             "axiom_get_network_urls" => self.handle_get_network_urls(request).await,
             _ => Ok(McpResponse::method_not_found(request.id)),
         }
     }
     
+    async fn handle_aspire_health(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let deep_check = params.get("deep_check")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        
+        debug!("Performing health check (deep: {})", deep_check);
+        
+        match self.api.get_health(deep_check).await {
+            Ok(health_results) => {
+                let result = json!({
+                    "health_results": health_results,
+                    "deep_check": deep_check,
+                    "timestamp": chrono::Utc::now().to_rfc3339()
+                });
+                Ok(McpResponse::success(request.id, result))
+            }
+            Err(e) => {
+                warn!("Health check failed: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_service_action(&self, request: McpRequest, action: ServiceAction) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let timeout = params.get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .map(Duration::from_millis);
+        
+        info!("Service {} requested for: {}", action.as_str(), service);
+        
+        match self.api.change_service_state(service, action, timeout).await {
+            Ok(result) => Ok(McpResponse::success(request.id, json!(result))),
+            Err(e) => {
+                warn!("Failed to {} service {}: {}", action.as_str(), service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_stream_service_logs(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        
+        let mut query = LogQuery::new(service);
+        if let Some(tail) = params.get("tail").and_then(|v| v.as_u64()) {
+            query.tail = tail as usize;
+        }
+        if let Some(follow) = params.get("follow_seconds").and_then(|v| v.as_u64()) {
+            query.follow = Duration::from_secs(follow);
+        }
+        query.cursor = params.get("cursor")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        
+        let query = match Self::apply_log_filters(query, &params) {
+            Ok(query) => query,
+            Err(e) => return Ok(McpResponse::invalid_params(request.id, e.to_string())),
+        };
+        
+        debug!("Reading logs for {} (tail: {}, follow: {:?})", service, query.tail, query.follow);
+        
+        match self.api.read_logs(&query).await {
+            Ok(window) => Ok(McpResponse::success(request.id, json!(window))),
+            Err(e) => {
+                warn!("Failed to read logs for {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    fn apply_log_filters(mut query: LogQuery, params: &Value) -> Result<LogQuery> {
+        if let Some(level) = params.get("min_level").and_then(|v| v.as_str()) {
+            query = query.with_min_level(level)?;
+        }
+        if let Some(pattern) = params.get("pattern").and_then(|v| v.as_str()) {
+            query = query.with_pattern(pattern)?;
+        }
+        Ok(query)
+    }
+    
+    async fn handle_resolve_endpoint(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let service = params.get("service").and_then(|v| v.as_str());
+        let endpoint = params.get("endpoint").and_then(|v| v.as_str());
+        let env_file = params.get("env_file")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from);
+        let refresh = params.get("refresh")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if service.is_none() && env_file.is_none() {
+            return Ok(McpResponse::invalid_params(request.id, "Pass service, env_file or both".to_string()));
+        }
+        
+        let mut result = json!({});
+        if let Some(service) = service {
+            debug!("Resolving endpoint {:?} of {}", endpoint, service);
+            match self.api.resolve_endpoint(service, endpoint, refresh).await {
+                Ok(resolution) => result = json!(resolution),
+                Err(e) => {
+                    warn!("Failed to resolve endpoint of {}: {}", service, e);
+                    return Ok(self.tool_error(request.id, &e));
+                }
+            }
+        }
+        if let Some(path) = env_file {
+            // A refresh was already done for the service, if asked for
+            match self.api.write_env_file(&path, refresh && service.is_none()).await {
+                Ok(endpoints) => {
+                    result["env_file"] = json!({
+                        "path": path.display().to_string(),
+                        "entries": endpoints.len()
+                    });
+                }
+                Err(e) => {
+                    warn!("Failed to write {}: {}", path.display(), e);
+                    return Ok(self.tool_error(request.id, &e));
+                }
+            }
+        }
+        
+        Ok(McpResponse::success(request.id, result))
+    }
+    
     async fn handle_aspire_start(&self, request: McpRequest) -> Result<McpResponse> {
         let params = request.params.unwrap_or(json!({}));
         let profile = params.get("profile")
@@ -60,7 +240,7 @@ impl RequestHandler {
         
         info!("Starting Aspire with profile: {}, watch: {}", profile, watch);
         
-        match self.orchestrator.start_aspire(profile, watch).await {
+        match self.api.start_aspire(profile, watch).await {
             Ok(_) => {
                 let result = json!({
                     "status": "started",
@@ -72,7 +252,7 @@ impl RequestHandler {
             }
             Err(e) => {
                 warn!("Failed to start Aspire: {}", e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
@@ -80,7 +260,7 @@ impl RequestHandler {
     async fn handle_aspire_stop(&self, request: McpRequest) -> Result<McpResponse> {
         info!("Stopping Aspire AppHost");
         
-        match self.orchestrator.stop_aspire().await {
+        match self.api.stop_aspire().await {
             Ok(_) => {
                 let result = json!({
                     "status": "stopped",
@@ -90,7 +270,7 @@ impl RequestHandler {
             }
             Err(e) => {
                 warn!("Failed to stop Aspire: {}", e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
@@ -103,7 +283,7 @@ impl RequestHandler {
         
         info!("Restarting service: {}", service);
         
-        match self.orchestrator.restart_service(service).await {
+        match self.api.restart_aspire(service).await {
             Ok(_) => {
                 let result = json!({
                     "status": "restarted",
@@ -114,7 +294,7 @@ impl RequestHandler {
             }
             Err(e) => {
                 warn!("Failed to restart service {}: {}", service, e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
@@ -122,10 +302,13 @@ impl RequestHandler {
     async fn handle_aspire_status(&self, request: McpRequest) -> Result<McpResponse> {
         debug!("Getting Aspire status");
         
-        let services: Vec<Value> = self.service_state
+        let services = match self.api.service_statuses() {
+            Ok(services) => services,
+            Err(e) => return Ok(self.tool_error(request.id, &e)),
+        };
+        let services: Vec<Value> = services
             .iter()
-            .map(|entry| {
-                let service = entry.value();
+            .map(|service| {
                 json!({
                     "name": service.name,
                     "status": service.status,
@@ -137,7 +320,7 @@ impl RequestHandler {
             })
             .collect();
         
-        let aspire_status = self.orchestrator.get_aspire_status().await
+        let aspire_status = self.api.get_aspire_status().await
             .unwrap_or_else(|_| json!({
                 "is_running": false,
                 "dashboard_accessible": false
@@ -152,26 +335,111 @@ impl RequestHandler {
         Ok(McpResponse::success(request.id, result))
     }
     
-    async fn handle_aspire_health(&self, request: McpRequest) -> Result<McpResponse> {
+    async fn handle_get_health_history(&self, request: McpRequest) -> Result<McpResponse> {
         let params = request.params.unwrap_or(json!({}));
-        let deep_check = params.get("deep_check")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let service = params.get("service").and_then(|v| v.as_str());
+        let limit = params.get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|limit| limit as usize);
         
-        debug!("Performing health check (deep: {})", deep_check);
+        debug!("Getting health history (service: {:?})", service);
         
-        match self.health_monitor.perform_health_check(deep_check).await {
-            Ok(health_results) => {
+        let history = match self.api.health_history(service, limit) {
+            Ok(history) => history,
+            Err(e) => return Ok(self.tool_error(request.id, &e)),
+        };
+        if let (Some(service), true) = (service, history.is_empty()) {
+            return Ok(McpResponse::error(
+                request.id,
+                McpError::with_code(ErrorCode::ServiceNotFound, format!("No health history for service '{}'", service))
+                    .service(service),
+            ));
+        }
+        
+        let result = json!({
+            "services": history,
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        });
+        Ok(McpResponse::success(request.id, result))
+    }
+    
+    async fn handle_diagnose_service(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        
+        debug!("Diagnosing {}", service);
+        
+        let diagnosis = match self.api.diagnose_service(service).await {
+            Ok(diagnosis) => diagnosis,
+            Err(e) => return Ok(self.tool_error(request.id, &e)),
+        };
+        let discovered = match self.api.service_statuses() {
+            Ok(services) => services.iter().any(|status| status.name == service),
+            Err(e) => return Ok(self.tool_error(request.id, &e)),
+        };
+        if diagnosis.state.is_none() && !discovered {
+            return Ok(McpResponse::error(
+                request.id,
+                McpError::with_code(ErrorCode::ServiceNotFound, format!("Service '{}' has not been discovered", service))
+                    .service(service),
+            ));
+        }
+        
+        Ok(McpResponse::success(request.id, json!(diagnosis)))
+    }
+    
+    async fn handle_get_deployment_history(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        
+        let [since, until] = match Self::time_bounds(&params) {
+            Ok(bounds) => bounds,
+            Err(message) => return Ok(McpResponse::invalid_params(request.id, message)),
+        };
+        let services: Option<HashSet<String>> = params.get("services")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect());
+        let limit = params.get("limit")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_HISTORY_LIMIT, |limit| limit as usize);
+        
+        debug!("Getting deployment history (since: {:?}, until: {:?}, services: {:?})", since, until, services);
+        
+        let query = HistoryQuery { since, until, services, limit: Some(limit) };
+        match self.api.deployment_history(&query).await {
+            Ok(entries) => {
                 let result = json!({
-                    "health_results": health_results,
-                    "deep_check": deep_check,
-                    "timestamp": chrono::Utc::now().to_rfc3339()
+                    "count": entries.len(),
+                    "entries": entries,
+                    "path": self.api.deployment_history_path().map(|path| path.display().to_string())
                 });
                 Ok(McpResponse::success(request.id, result))
             }
             Err(e) => {
-                warn!("Health check failed: {}", e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                warn!("Failed to read deployment history: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_reload_config(&self, request: McpRequest) -> Result<McpResponse> {
+        info!("Reloading configuration");
+        
+        match self.api.reload_config().await {
+            Ok(reload) => Ok(McpResponse::success(request.id, json!({
+                "changed": reload.changed,
+                "applied": reload.applied,
+                "deferred": reload.deferred,
+                "restart_required": !reload.deferred.is_empty(),
+                "path": self.api.config_path().map(|path| path.display().to_string())
+            }))),
+            Err(e) => {
+                warn!("Configuration reload failed: {}", e);
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
@@ -179,7 +447,7 @@ impl RequestHandler {
     async fn handle_get_service_urls(&self, request: McpRequest) -> Result<McpResponse> {
         debug!("Getting service URLs");
         
-        match self.service_discovery.get_service_urls().await {
+        match self.api.get_service_urls().await {
             Ok(urls) => {
                 let result = json!({
                     "api": urls.api,
@@ -191,22 +459,24 @@ impl RequestHandler {
             }
             Err(e) => {
                 warn!("Failed to get service URLs: {}", e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
     
     async fn handle_call_endpoint(&self, request: McpRequest) -> Result<McpResponse> {
-        let params = request.params.ok_or_else(|| {
-            anyhow::anyhow!("Missing parameters for endpoint call")
-        })?;
-        
-        let call_request: EndpointCallRequest = serde_json::from_value(params)?;
+        let Some(params) = request.params else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing parameters for endpoint call".to_string()));
+        };
+        let call_request: EndpointCallRequest = match serde_json::from_value(params) {
+            Ok(call_request) => call_request,
+            Err(e) => return Ok(McpResponse::invalid_params(request.id, e.to_string())),
+        };
         
         info!("Calling endpoint: {} {}/{}", 
             call_request.method, call_request.service, call_request.endpoint);
         
-        match self.orchestrator.call_service_endpoint(call_request).await {
+        match self.api.call_endpoint(call_request).await {
             Ok(response) => {
                 let result = json!({
                     "status": response.status,
@@ -218,11 +488,505 @@ impl RequestHandler {
             }
             Err(e) => {
                 warn!("Endpoint call failed: {}", e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_invoke_service_endpoint(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let service_request = ServiceRequest {
+            service: service.to_string(),
+            path: params.get("path").and_then(|v| v.as_str()).unwrap_or("/").to_string(),
+            method: params.get("method").and_then(|v| v.as_str()).unwrap_or("GET").to_string(),
+            endpoint: params.get("endpoint").and_then(|v| v.as_str()).map(|s| s.to_string()),
+            headers: params.get("headers")
+                .and_then(|v| v.as_object())
+                .map(|headers| headers.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect())
+                .unwrap_or_default(),
+            body: params.get("body").cloned(),
+        };
+        
+        info!("Invoking {} {} on {}", service_request.method, service_request.path, service);
+        
+        match self.api.invoke_service_endpoint(&service_request).await {
+            Ok(response) => Ok(McpResponse::success(request.id, json!(response))),
+            Err(e) => {
+                warn!("Failed to invoke {} on {}: {}", service_request.path, service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_scale_service(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let Some(replicas) = params.get("replicas").and_then(|v| v.as_u64()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: replicas".to_string()));
+        };
+        let confirm_stop = params.get("confirm_stop")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let timeout = params.get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .map(Duration::from_millis);
+        
+        info!("Scaling {} to {} replicas", service, replicas);
+        
+        match self.api.scale_service(service, replicas as usize, confirm_stop, timeout).await {
+            Ok(result) => Ok(McpResponse::success(request.id, json!(result))),
+            Err(e) => {
+                warn!("Failed to scale service {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_wait_for_healthy(&self, request: McpRequest, mut progress: Option<ProgressReporter>) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let defaults = WaitForHealthyOptions::default();
+        let options = WaitForHealthyOptions {
+            services: params.get("services")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .collect()),
+            timeout: params.get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.timeout),
+            required_consecutive: params.get("required_consecutive")
+                .and_then(|v| v.as_u64())
+                .map_or(defaults.required_consecutive, |required| required as u32),
+        };
+        
+        info!("Waiting for services to be healthy: {:?}", options.services);
+        
+        let required = options.required_consecutive.max(1);
+        let mut on_progress = |status: &ServiceWaitStatus| {
+            if let Some(progress) = progress.as_mut() {
+                progress.report(wait_progress_message(status, required));
+            }
+        };
+        
+        match self.api.wait_for_healthy(&options, &mut on_progress).await {
+            Ok(result) => Ok(McpResponse::success(request.id, json!(result))),
+            Err(e) => {
+                warn!("Waiting for healthy services failed: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_get_startup_plan(&self, request: McpRequest) -> Result<McpResponse> {
+        debug!("Computing startup plan");
+        
+        match self.api.get_startup_plan().await {
+            Ok(plan) => {
+                let result = json!({
+                    "order": plan.order(),
+                    "stages": plan.stages,
+                    "dependencies": plan.dependencies
+                });
+                Ok(McpResponse::success(request.id, result))
+            }
+            Err(e) => {
+                warn!("Failed to compute startup plan: {}", e);
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
     
+    async fn handle_start_services(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let timeout = params.get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .map(Duration::from_millis);
+        
+        info!("Starting services in dependency order");
+        
+        match self.api.start_services_in_order(timeout).await {
+            Ok(result) => Ok(McpResponse::success(request.id, json!(result))),
+            Err(e) => {
+                warn!("Ordered startup failed: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_teardown_environment(&self, request: McpRequest, mut progress: Option<ProgressReporter>) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let mut options = match self.api.default_teardown_options() {
+            Ok(options) => options,
+            Err(e) => return Ok(self.tool_error(request.id, &e)),
+        };
+        if let Some(stop_apphost) = params.get("stop_apphost").and_then(|v| v.as_bool()) {
+            options.stop_apphost = stop_apphost;
+        }
+        if let Some(remove_containers) = params.get("remove_containers").and_then(|v| v.as_bool()) {
+            options.remove_containers = remove_containers;
+        }
+        options.timeout = params.get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .map(Duration::from_millis);
+        
+        info!("Tearing down the environment");
+        
+        let mut on_progress = |message: String| {
+            if let Some(progress) = progress.as_mut() {
+                progress.report(message);
+            }
+        };
+        
+        // Leftovers are part of the report rather than an error
+        match self.api.teardown(&options, &mut on_progress).await {
+            Ok(report) => Ok(McpResponse::success(request.id, json!(report))),
+            Err(e) => {
+                warn!("Teardown failed: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_diff_environment(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let manifest_path = params.get("manifest_path")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from);
+        
+        debug!("Diffing the running environment against the AppHost manifest");
+        
+        match self.api.diff_environment(manifest_path.as_deref()).await {
+            Ok(diff) => Ok(McpResponse::success(request.id, json!(diff))),
+            Err(e) => {
+                warn!("Failed to diff environment: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_get_service_map(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let manifest_path = params.get("manifest_path")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from);
+        let format = match params.get("format").and_then(|v| v.as_str()) {
+            None => MapFormat::Json,
+            Some(format) => match MapFormat::parse(format) {
+                Some(format) => format,
+                None => return Ok(McpResponse::invalid_params(
+                    request.id,
+                    format!("Unknown format '{}'; expected json, dot or mermaid", format),
+                )),
+            },
+        };
+        
+        debug!("Building the service map");
+        
+        match self.api.get_service_map(manifest_path.as_deref()).await {
+            Ok(map) => {
+                let mut result = json!(map);
+                if let Some(rendered) = map.render(format) {
+                    result["format"] = json!(format.as_str());
+                    result["rendered"] = json!(rendered);
+                }
+                Ok(McpResponse::success(request.id, result))
+            }
+            Err(e) => {
+                warn!("Failed to build service map: {}", e);
+                // An unreadable manifest is the caller's to fix
+                if e.is::<std::io::Error>() || e.is::<serde_json::Error>() {
+                    Ok(McpResponse::invalid_params(request.id, format!("Failed to read manifest: {}", e)))
+                } else {
+                    Ok(self.tool_error(request.id, &e))
+                }
+            }
+        }
+    }
+    
+    async fn handle_get_service_config(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let unmask = params.get("unmask").and_then(|v| v.as_bool()).unwrap_or(false);
+        
+        debug!("Getting configuration of {} (unmask: {})", service, unmask);
+        
+        match self.api.get_service_config(service, unmask).await {
+            Ok(config) => Ok(McpResponse::success(request.id, json!(config))),
+            Err(e) => {
+                warn!("Failed to get configuration of {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_restart_container(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let stop_timeout = params.get("stop_timeout_seconds")
+            .and_then(|v| v.as_u64())
+            .map(Duration::from_secs);
+        
+        info!("Restarting container(s) of {}", service);
+        
+        match self.api.restart_container(service, stop_timeout).await {
+            Ok(containers) => Ok(McpResponse::success(request.id, json!({
+                "service": service,
+                "containers": containers
+            }))),
+            Err(e) => {
+                warn!("Failed to restart container of {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_get_container_logs(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let tail = params.get("tail")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_CONTAINER_LOG_TAIL, |tail| (tail as usize).min(MAX_CONTAINER_LOG_TAIL));
+        let since = params.get("since").and_then(|v| v.as_str());
+        
+        debug!("Getting container logs of {} (tail: {})", service, tail);
+        
+        match self.api.container_logs(service, tail, since).await {
+            Ok(logs) => Ok(McpResponse::success(request.id, json!(logs))),
+            Err(e) => {
+                warn!("Failed to get container logs of {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_exec_in_container(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let Some(command) = params.get("command").and_then(|v| v.as_array()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: command".to_string()));
+        };
+        let command: Vec<String> = command.iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect();
+        
+        info!("Executing '{}' in container of {}", command.join(" "), service);
+        
+        match self.api.exec_in_container(service, &command).await {
+            Ok(result) => Ok(McpResponse::success(request.id, json!(result))),
+            Err(e) => {
+                warn!("Failed to execute in container of {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_snapshot_database(&self, request: McpRequest, mut progress: Option<ProgressReporter>) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        
+        let mut snapshot = SnapshotRequest::new(service);
+        snapshot.database = params.get("database").and_then(|v| v.as_str()).map(|s| s.to_string());
+        snapshot.name = params.get("name").and_then(|v| v.as_str()).map(|s| s.to_string());
+        snapshot.overwrite = params.get("overwrite").and_then(|v| v.as_bool()).unwrap_or(false);
+        
+        info!("Snapshotting database of {}", service);
+        
+        let mut on_progress = |message: String| {
+            if let Some(progress) = progress.as_mut() {
+                progress.report(message);
+            }
+        };
+        
+        match self.api.snapshot_database(&snapshot, &mut on_progress).await {
+            Ok(snapshot) => Ok(McpResponse::success(request.id, json!(snapshot))),
+            Err(e) => {
+                warn!("Failed to snapshot database of {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_restore_database(&self, request: McpRequest, mut progress: Option<ProgressReporter>) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(name) = params.get("name").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: name".to_string()));
+        };
+        let service = params.get("service").and_then(|v| v.as_str());
+        let database = params.get("database").and_then(|v| v.as_str());
+        
+        info!("Restoring database snapshot {}", name);
+        
+        let mut on_progress = |message: String| {
+            if let Some(progress) = progress.as_mut() {
+                progress.report(message);
+            }
+        };
+        
+        match self.api.restore_database(name, service, database, &mut on_progress).await {
+            Ok(result) => Ok(McpResponse::success(request.id, json!(result))),
+            Err(e) => {
+                warn!("Failed to restore database snapshot {}: {}", name, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_list_database_snapshots(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let service = params.get("service").and_then(|v| v.as_str());
+        
+        match self.api.list_database_snapshots(service).await {
+            Ok(snapshots) => Ok(McpResponse::success(request.id, json!({
+                "count": snapshots.len(),
+                "snapshots": snapshots
+            }))),
+            Err(e) => {
+                warn!("Failed to list database snapshots: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_get_service_metrics(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(service) = params.get("service").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: service".to_string()));
+        };
+        let window = params.get("window_seconds")
+            .and_then(|v| v.as_u64())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_METRICS_WINDOW);
+        
+        debug!("Summarizing metrics for {} over {:?}", service, window);
+        
+        match self.api.get_service_metrics(service, window).await {
+            Ok(summary) => Ok(McpResponse::success(request.id, json!(summary))),
+            Err(e) => {
+                warn!("Failed to get metrics for {}: {}", service, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_get_budget_status(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let service = params.get("service").and_then(|v| v.as_str());
+        
+        let (config, mut services) = match (self.api.budgets_config(), self.api.budget_status()) {
+            (Ok(config), Ok(services)) => (config, services),
+            (Err(e), _) | (_, Err(e)) => return Ok(self.tool_error(request.id, &e)),
+        };
+        if let Some(service) = service {
+            services.retain(|status| status.service == service);
+        }
+        
+        Ok(McpResponse::success(request.id, json!({
+            "sustained_seconds": config.sustained_seconds,
+            "recovery_percent": config.recovery_percent,
+            "services": services
+        })))
+    }
+    
+    async fn handle_search_traces(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        
+        let [since, until] = match Self::time_bounds(&params) {
+            Ok(bounds) => bounds,
+            Err(message) => return Ok(McpResponse::invalid_params(request.id, message)),
+        };
+        let status = match params.get("status").and_then(|v| v.as_str()) {
+            None => None,
+            Some(status) => match TraceStatus::parse(status) {
+                Some(status) => Some(status),
+                None => return Ok(McpResponse::invalid_params(
+                    request.id,
+                    format!("Unknown status '{}'; expected ok or error", status),
+                )),
+            },
+        };
+        let query = TraceQuery {
+            service: params.get("service")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            min_duration: params.get("min_duration_ms")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis),
+            status,
+            since,
+            until,
+            limit: params.get("limit")
+                .and_then(|v| v.as_u64())
+                .map_or(DEFAULT_TRACE_LIMIT, |limit| limit as usize),
+        };
+        
+        debug!("Searching traces: {:?}", query);
+        
+        match self.api.search_traces(&query).await {
+            Ok(traces) => Ok(McpResponse::success(request.id, json!({
+                "count": traces.len(),
+                "traces": traces
+            }))),
+            Err(e) => {
+                warn!("Failed to search traces: {}", e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    async fn handle_get_trace(&self, request: McpRequest) -> Result<McpResponse> {
+        let params = request.params.unwrap_or(json!({}));
+        let Some(trace_id) = params.get("trace_id").and_then(|v| v.as_str()) else {
+            return Ok(McpResponse::invalid_params(request.id, "Missing required parameter: trace_id".to_string()));
+        };
+        let max_attribute_chars = params.get("max_attribute_chars")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_ATTRIBUTE_CHARS, |max| max as usize);
+        
+        debug!("Getting trace {}", trace_id);
+        
+        match self.api.get_trace(trace_id, max_attribute_chars).await {
+            Ok(tree) => Ok(McpResponse::success(request.id, json!(tree))),
+            Err(e) => {
+                warn!("Failed to get trace {}: {}", trace_id, e);
+                Ok(self.tool_error(request.id, &e))
+            }
+        }
+    }
+    
+    /// Optional RFC 3339 `since` and `until` parameters
+    fn time_bounds(params: &Value) -> std::result::Result<[Option<chrono::DateTime<chrono::Utc>>; 2], String> {
+        let mut bounds = [None, None];
+        for (bound, key) in bounds.iter_mut().zip(["since", "until"]) {
+            let Some(value) = params.get(key).and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let time = chrono::DateTime::parse_from_rfc3339(value)
+                .map_err(|e| format!("Invalid '{}' timestamp '{}': {}", key, value, e))?;
+            *bound = Some(time.with_timezone(&chrono::Utc));
+        }
+        Ok(bounds)
+    }
+    
     async fn handle_configure_local_network(&self, request: McpRequest) -> Result<McpResponse> {
         let params = request.params.unwrap_or(json!({}));
         let network_interface = params.get("network_interface")
@@ -242,7 +1006,7 @@ impl RequestHandler {
         info!("Configuring local network: interface={}, services={:?}, mode={}", 
             network_interface, expose_services, bind_mode);
         
-        match self.network_manager.configure_local_network(&expose_services, bind_mode).await {
+        match self.api.configure_local_network(&expose_services, bind_mode).await {
             Ok(config_result) => {
                 let result = json!({
                     "status": "configured",
@@ -255,7 +1019,7 @@ impl RequestHandler {
             }
             Err(e) => {
                 warn!("Network configuration failed: {}", e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
@@ -263,14 +1027,38 @@ impl RequestHandler {
     async fn handle_get_network_urls(&self, request: McpRequest) -> Result<McpResponse> {
         debug!("Getting network URLs");
         
-        match self.network_manager.get_network_urls().await {
+        match self.api.get_network_urls().await {
             Ok(urls) => {
                 Ok(McpResponse::success(request.id, json!(urls)))
             }
             Err(e) => {
                 warn!("Failed to get network URLs: {}", e);
-                Ok(McpResponse::internal_error(request.id, e.to_string()))
+                Ok(self.tool_error(request.id, &e))
             }
         }
     }
-}
\ No newline at end of file
+    
+    /// Respond with the classified error of a failed tool call
+    fn tool_error(&self, id: Value, e: &anyhow::Error) -> McpResponse {
+        // Without a dashboard of its own, the API's dashboard failures are
+        // reported like any other unreachable host
+        McpResponse::error(id, errors::classify(e, self.api.dashboard_url().unwrap_or("")))
+    }
+}
+
+fn wait_progress_message(status: &ServiceWaitStatus, required: u32) -> String {
+    if status.healthy {
+        format!("{} is healthy ({}/{} consecutive checks)", status.service, status.consecutive_healthy, required)
+    } else {
+        format!(
+            "{} is not healthy yet ({}/{} consecutive checks): {}",
+            status.service,
+            status.consecutive_healthy,
+            required,
+            match (&status.last_error, status.consecutive_healthy) {
+                (Some(error), 0) => error.as_str(),
+                _ => "waiting for another check",
+            }
+        )
+    }
+}
@@ -0,0 +1,459 @@
+use anyhow::Result;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use dashmap::DashMap;
+use futures::Stream;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::AbortHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::settings::ServerConfig;
+use crate::mcp::notifications::HealthWatch;
+use crate::mcp::protocol::{ErrorCode, McpError, McpNotification, McpRequest, McpResponse};
+use crate::mcp::server::AxiomAspireMcpServer;
+use crate::services::probes::HealthEvent;
+
+/// The single MCP endpoint
+pub const MCP_PATH: &str = "/mcp";
+
+/// Header carrying the session id handed out on `initialize`
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// JSON-RPC code for a body that is not JSON
+const PARSE_ERROR: i32 = -32700;
+
+/// State of one HTTP client, the counterpart of a TCP connection
+struct HttpSession {
+    id: String,
+    health_watch: Mutex<Option<HealthWatch>>,
+    /// Health event subscriptions for the session's notification task, one
+    /// per opt-in
+    subscriptions: mpsc::UnboundedSender<broadcast::Receiver<HealthEvent>>,
+    /// The client's open GET stream; notifications are dropped without one
+    stream: Mutex<Option<mpsc::UnboundedSender<String>>>,
+    /// The session's notification task
+    forwarder: Mutex<Option<AbortHandle>>,
+    last_request: Mutex<Instant>,
+}
+
+impl HttpSession {
+    fn watch(&self) -> MutexGuard<'_, Option<HealthWatch>> {
+        self.health_watch.lock().expect("health watch lock poisoned")
+    }
+
+    fn touch(&self) {
+        *self.last_request.lock().expect("session clock lock poisoned") = Instant::now();
+    }
+
+    /// No request within `timeout` and no client listening on the stream
+    fn is_idle(&self, timeout: Duration) -> bool {
+        let listening = self.stream
+            .lock()
+            .expect("session stream lock poisoned")
+            .as_ref()
+            .is_some_and(|sender| !sender.is_closed());
+        !listening && self.last_request.lock().expect("session clock lock poisoned").elapsed() >= timeout
+    }
+
+    /// End the stream and stop the notification task
+    fn close(&self) {
+        *self.stream.lock().expect("session stream lock poisoned") = None;
+        if let Some(forwarder) = self.forwarder.lock().expect("session task lock poisoned").take() {
+            forwarder.abort();
+        }
+    }
+
+    fn send<T: Serialize>(&self, frame: &T) {
+        let mut stream = self.stream.lock().expect("session stream lock poisoned");
+        let Some(sender) = stream.as_ref() else {
+            debug!("Session {} has no open stream, dropping notification", self.id);
+            return;
+        };
+        match serde_json::to_string(frame) {
+            Ok(frame) => {
+                if sender.send(frame).is_err() {
+                    *stream = None;
+                }
+            }
+            Err(e) => warn!("Failed to serialize notification for session {}: {}", self.id, e),
+        }
+    }
+
+    /// Deliver the session's health notifications, and those sent to every
+    /// client, until it is aborted on close
+    ///
+    /// Each session has its own task and stream, so notifications of
+    /// concurrent sessions never interleave.
+    async fn forward_notifications(
+        self: Arc<Self>,
+        mut subscriptions: mpsc::UnboundedReceiver<broadcast::Receiver<HealthEvent>>,
+        mut announcements: broadcast::Receiver<McpNotification>,
+    ) {
+        let mut health_events: Option<broadcast::Receiver<HealthEvent>> = None;
+
+        loop {
+            let (watching, flush_at) = {
+                let watch = self.watch();
+                (watch.is_some(), watch.as_ref().and_then(|watch| watch.next_deadline()))
+            };
+
+            tokio::select! {
+                Some(events) = subscriptions.recv() => health_events = Some(events),
+                announcement = announcements.recv() => {
+                    match announcement {
+                        Ok(notification) => self.send(&notification),
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Server notifications for session {} lagged, skipped {}", self.id, skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+                event = async { health_events.as_mut().expect("subscribed").recv().await }, if watching && health_events.is_some() => {
+                    match event {
+                        Ok(event) => {
+                            let notification = self.watch()
+                                .as_mut()
+                                .and_then(|watch| watch.on_event(event, Instant::now()));
+                            if let Some(notification) = notification {
+                                self.send(&notification);
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Health notifications for session {} lagged, skipped {} transitions", self.id, skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+                _ = tokio::time::sleep_until(flush_at.unwrap_or_else(Instant::now).into()), if flush_at.is_some() => {
+                    let notifications = self.watch()
+                        .as_mut()
+                        .map(|watch| watch.flush_due(Instant::now()))
+                        .unwrap_or_default();
+                    for notification in notifications {
+                        self.send(&notification);
+                    }
+                }
+            }
+        }
+
+        debug!("Session {} closed", self.id);
+    }
+}
+
+#[derive(Clone)]
+struct HttpState {
+    server: AxiomAspireMcpServer,
+    sessions: Arc<DashMap<String, Arc<HttpSession>>>,
+    token: Option<Arc<str>>,
+    allowed_origins: Arc<[String]>,
+}
+
+impl HttpState {
+    fn open_session(&self) -> Arc<HttpSession> {
+        let (subscriptions, subscription_rx) = mpsc::unbounded_channel();
+        let session = Arc::new(HttpSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            health_watch: Mutex::new(None),
+            subscriptions,
+            stream: Mutex::new(None),
+            forwarder: Mutex::new(None),
+            last_request: Mutex::new(Instant::now()),
+        });
+        let forwarder = tokio::spawn(session.clone().forward_notifications(subscription_rx, self.server.subscribe_announcements()));
+        *session.forwarder.lock().expect("session task lock poisoned") = Some(forwarder.abort_handle());
+        self.sessions.insert(session.id.clone(), session.clone());
+
+        info!("Opened MCP session {}", session.id);
+        session
+    }
+
+    /// The session named by the request's session header
+    fn session(&self, headers: &HeaderMap) -> Result<Arc<HttpSession>, Box<Response>> {
+        let id = headers
+            .get(SESSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| Box::new(rejection(StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header; call initialize first")))?;
+        let session = self.sessions
+            .get(id)
+            .map(|session| session.value().clone())
+            .ok_or_else(|| Box::new(rejection(StatusCode::NOT_FOUND, &format!("Unknown session '{}'; call initialize again", id))))?;
+        session.touch();
+        Ok(session)
+    }
+}
+
+/// Close sessions whose client went away without `DELETE`, until the server
+/// drops its session table
+fn expire_idle_sessions(sessions: Weak<DashMap<String, Arc<HttpSession>>>, idle_timeout: Duration) {
+    tokio::spawn(async move {
+        let mut sweeps = tokio::time::interval((idle_timeout / 4).max(Duration::from_millis(10)));
+        loop {
+            sweeps.tick().await;
+            let Some(sessions) = sessions.upgrade() else { break };
+            sessions.retain(|id, session| {
+                if !session.is_idle(idle_timeout) {
+                    return true;
+                }
+                session.close();
+                info!("Expired idle MCP session {}", id);
+                false
+            });
+        }
+    });
+}
+
+impl AxiomAspireMcpServer {
+    /// Serve MCP streamable HTTP on an already bound listener
+    ///
+    /// `POST /mcp` takes one JSON-RPC message and answers with JSON, or with
+    /// an SSE stream of progress notifications ending in the response when
+    /// the request carries a progress token. `GET /mcp` opens the session's
+    /// stream for health notifications and `DELETE /mcp` ends the session.
+    /// Sessions without requests or an open stream for
+    /// `server.session_idle_timeout_ms` are ended as well.
+    ///
+    /// Requests with an `Origin` other than localhost or one of
+    /// `server.allowed_origins` are refused, so a web page cannot reach the
+    /// server through DNS rebinding.
+    pub async fn serve_http(&self, listener: TcpListener) -> Result<()> {
+        let token = bearer_token(&self.settings().server)?;
+        if token.is_none() {
+            warn!("HTTP transport accepts unauthenticated clients; set server.auth_token or server.auth_token_env");
+        }
+
+        let state = HttpState {
+            server: self.clone(),
+            sessions: Arc::new(DashMap::new()),
+            token: token.map(Arc::from),
+            allowed_origins: self.settings().server.allowed_origins.iter().map(|origin| normalize_origin(origin)).collect(),
+        };
+        let idle_timeout = Duration::from_millis(self.settings().server.session_idle_timeout_ms);
+        expire_idle_sessions(Arc::downgrade(&state.sessions), idle_timeout);
+        let app = Router::new()
+            .route(MCP_PATH, post(handle_post).get(handle_get).delete(handle_delete))
+            .route_layer(middleware::from_fn_with_state(state.clone(), require_token))
+            .route_layer(middleware::from_fn_with_state(state.clone(), require_allowed_origin))
+            .with_state(state);
+
+        axum::serve(listener, app).await?;
+        Ok(())
+    }
+}
+
+/// `None` when the HTTP transport is open
+fn bearer_token(config: &ServerConfig) -> Result<Option<String>> {
+    let token = match (&config.auth_token, &config.auth_token_env) {
+        (Some(token), _) => token.expose().to_string(),
+        (None, Some(var)) => std::env::var(var)
+            .map_err(|_| anyhow::anyhow!("Environment variable {} is not set", var))?,
+        (None, None) => return Ok(None),
+    };
+
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        return Err(anyhow::anyhow!("The HTTP transport token is empty"));
+    }
+    Ok(Some(token))
+}
+
+async fn require_token(State(state): State<HttpState>, request: Request, next: Next) -> Response {
+    if let Some(token) = &state.token {
+        let presented = request.headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if presented != Some(&**token) {
+            let mut response = rejection(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token");
+            response.headers_mut().insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            return response;
+        }
+    }
+    next.run(request).await
+}
+
+/// Browsers send `Origin` on cross-site requests; clients that omit it are
+/// not browsers and are left to the bearer token
+async fn require_allowed_origin(State(state): State<HttpState>, request: Request, next: Next) -> Response {
+    if let Some(origin) = request.headers().get(header::ORIGIN) {
+        let allowed = origin.to_str().is_ok_and(|origin| {
+            is_localhost_origin(origin) || state.allowed_origins.contains(&normalize_origin(origin))
+        });
+        if !allowed {
+            warn!("Refused HTTP request from origin {:?}", origin);
+            return rejection(StatusCode::FORBIDDEN, "Origin is not allowed");
+        }
+    }
+    next.run(request).await
+}
+
+fn is_localhost_origin(origin: &str) -> bool {
+    reqwest::Url::parse(origin).is_ok_and(|url| {
+        matches!(url.scheme(), "http" | "https")
+            && matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"))
+    })
+}
+
+/// Origins compare without case or a trailing slash
+fn normalize_origin(origin: &str) -> String {
+    origin.trim().trim_end_matches('/').to_ascii_lowercase()
+}
+
+async fn handle_post(State(state): State<HttpState>, headers: HeaderMap, body: String) -> Response {
+    let message: Value = match serde_json::from_str(&body) {
+        Ok(message) => message,
+        Err(e) => {
+            let error = McpError::new(PARSE_ERROR, format!("Parse error: {}", e));
+            return (StatusCode::BAD_REQUEST, Json(McpResponse::error(json!(null), error))).into_response();
+        }
+    };
+    // Client notifications and responses need no answer
+    if message.get("method").is_none() || message.get("id").is_none() {
+        return StatusCode::ACCEPTED.into_response();
+    }
+    let request: McpRequest = match serde_json::from_value(message) {
+        Ok(request) => request,
+        Err(e) => return rejection(StatusCode::BAD_REQUEST, &format!("Invalid JSON-RPC request: {}", e)),
+    };
+
+    state.server.record_activity();
+    let session = if request.method == "initialize" {
+        state.open_session()
+    } else {
+        match state.session(&headers) {
+            Ok(session) => session,
+            Err(response) => return *response,
+        }
+    };
+
+    let mut response = if let Some(arguments) = AxiomAspireMcpServer::watch_arguments(&request) {
+        let mut watch = session.watch();
+        let was_watching = watch.is_some();
+        let response = AxiomAspireMcpServer::handle_watch_health(request.id, &arguments, &mut watch);
+        // Only report transitions that happen after opting in
+        if !was_watching && watch.is_some() {
+            let _ = session.subscriptions.send(state.server.health_monitor().subscribe());
+        }
+        Json(response).into_response()
+    } else if accepts_event_stream(&headers) && has_progress_token(&request) {
+        Sse::new(event_stream(dispatch_streaming(state.server.clone(), request))).into_response()
+    } else {
+        let id = request.id.clone();
+        // Progress is dropped when the client did not ask for a stream
+        let (progress_tx, _) = mpsc::unbounded_channel();
+        let response = state.server.dispatch(request, progress_tx).await.unwrap_or_else(|e| {
+            warn!("Request processing error: {}", e);
+            McpResponse::internal_error(id, e.to_string())
+        });
+        Json(response).into_response()
+    };
+
+    if let Ok(id) = HeaderValue::from_str(&session.id) {
+        response.headers_mut().insert(SESSION_HEADER, id);
+    }
+    response
+}
+
+async fn handle_get(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    let session = match state.session(&headers) {
+        Ok(session) => session,
+        Err(response) => return *response,
+    };
+
+    // A newer stream replaces the previous one, which then ends
+    let (sender, frames) = mpsc::unbounded_channel();
+    *session.stream.lock().expect("session stream lock poisoned") = Some(sender);
+    debug!("Opened notification stream for session {}", session.id);
+
+    Sse::new(event_stream(frames)).keep_alive(KeepAlive::default()).into_response()
+}
+
+async fn handle_delete(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    let session = match state.session(&headers) {
+        Ok(session) => session,
+        Err(response) => return *response,
+    };
+
+    state.sessions.remove(&session.id);
+    session.close();
+    info!("Ended MCP session {}", session.id);
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// Run a request, sending its progress notifications and then its response
+fn dispatch_streaming(server: AxiomAspireMcpServer, request: McpRequest) -> mpsc::UnboundedReceiver<String> {
+    let (frames_tx, frames) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        let id = request.id.clone();
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<McpNotification>();
+        let result = {
+            let request = server.dispatch(request, progress_tx);
+            tokio::pin!(request);
+            loop {
+                tokio::select! {
+                    result = &mut request => break result,
+                    Some(notification) = progress_rx.recv() => send_frame(&frames_tx, &notification),
+                }
+            }
+        };
+        while let Ok(notification) = progress_rx.try_recv() {
+            send_frame(&frames_tx, &notification);
+        }
+
+        let response = result.unwrap_or_else(|e| {
+            warn!("Request processing error: {}", e);
+            McpResponse::internal_error(id, e.to_string())
+        });
+        send_frame(&frames_tx, &response);
+    });
+
+    frames
+}
+
+fn send_frame<T: Serialize>(frames: &mpsc::UnboundedSender<String>, frame: &T) {
+    match serde_json::to_string(frame) {
+        // The client may have gone away; the request still completes
+        Ok(frame) => {
+            let _ = frames.send(frame);
+        }
+        Err(e) => warn!("Failed to serialize frame: {}", e),
+    }
+}
+
+fn event_stream(frames: mpsc::UnboundedReceiver<String>) -> impl Stream<Item = Result<Event, Infallible>> {
+    futures::stream::unfold(frames, |mut frames| async move {
+        let frame = frames.recv().await?;
+        Some((Ok(Event::default().event("message").data(frame)), frames))
+    })
+}
+
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.contains("text/event-stream"))
+}
+
+fn has_progress_token(request: &McpRequest) -> bool {
+    request.params
+        .as_ref()
+        .and_then(|params| params.pointer("/_meta/progressToken"))
+        .is_some()
+}
+
+fn rejection(status: StatusCode, message: &str) -> Response {
+    let error = McpError::with_code(ErrorCode::InvalidArgument, message);
+    (status, Json(McpResponse::error(json!(null), error))).into_response()
+}
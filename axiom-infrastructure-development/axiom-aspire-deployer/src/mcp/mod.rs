@@ -1,6 +1,9 @@
 pub mod server;
 pub mod handlers;
 pub mod protocol;
+pub mod errors;
+pub mod notifications;
+pub mod http;
 
-pub use server::AxiomAspireMcpServer;
-pub use protocol::{McpRequest, McpResponse, McpError};
\ No newline at end of file
+pub use server::{AxiomAspireMcpServer, Transport};
+pub use protocol::{ErrorCode, ErrorData, McpRequest, McpResponse, McpError, McpNotification};
\ No newline at end of file
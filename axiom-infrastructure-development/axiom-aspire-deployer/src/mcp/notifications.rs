@@ -0,0 +1,189 @@
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use crate::mcp::protocol::McpNotification;
+use crate::services::probes::{HealthEvent, HealthState};
+
+/// JSON-RPC method used for health-change notifications
+pub const HEALTH_CHANGED_METHOD: &str = "axiom/health_changed";
+
+/// Sent to every client when a service exceeds its CPU or memory budget
+/// for the sustained window, or recovers
+pub const BUDGET_ALERT_METHOD: &str = "axiom/budget_alert";
+
+/// Sent to every client before an idle environment is torn down
+pub const TEARDOWN_WARNING_METHOD: &str = "axiom/teardown_warning";
+
+/// Sent to every client with the report of an idle teardown
+pub const TEARDOWN_COMPLETED_METHOD: &str = "axiom/teardown_completed";
+
+/// MCP method for progress of a long-running request
+pub const PROGRESS_METHOD: &str = "notifications/progress";
+
+/// Default window for collapsing flapping transitions
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_secs(5);
+
+/// Per-connection opt-in to health notifications
+#[derive(Debug)]
+pub struct HealthWatch {
+    /// Only these services are reported; all when `None`
+    services: Option<HashSet<String>>,
+    debouncer: HealthDebouncer,
+}
+
+impl HealthWatch {
+    pub fn new(services: Option<HashSet<String>>, debounce: Duration) -> Self {
+        Self {
+            services,
+            debouncer: HealthDebouncer::new(debounce),
+        }
+    }
+
+    pub fn on_event(&mut self, event: HealthEvent, now: Instant) -> Option<McpNotification> {
+        let watched = self.services
+            .as_ref()
+            .is_none_or(|services| services.contains(&event.service));
+        if !watched {
+            return None;
+        }
+        self.debouncer.push(event, now)
+    }
+
+    pub fn flush_due(&mut self, now: Instant) -> Vec<McpNotification> {
+        self.debouncer.flush_due(now)
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.debouncer.next_deadline()
+    }
+}
+
+/// Reports progress for a request that passed `_meta.progressToken`
+#[derive(Debug, Clone)]
+pub struct ProgressReporter {
+    token: Value,
+    sender: mpsc::UnboundedSender<McpNotification>,
+    progress: u64,
+}
+
+impl ProgressReporter {
+    pub fn new(token: Value, sender: mpsc::UnboundedSender<McpNotification>) -> Self {
+        Self { token, sender, progress: 0 }
+    }
+
+    /// `None` when the caller did not ask for progress
+    pub fn from_params(params: Option<&Value>, sender: &mpsc::UnboundedSender<McpNotification>) -> Option<Self> {
+        let token = params?.get("_meta")?.get("progressToken")?;
+        Some(Self::new(token.clone(), sender.clone()))
+    }
+
+    /// Progress must increase with every notification, so it counts reports
+    pub fn report(&mut self, message: String) {
+        self.progress += 1;
+        let notification = McpNotification::new(PROGRESS_METHOD, json!({
+            "progressToken": self.token,
+            "progress": self.progress,
+            "message": message,
+        }));
+        // The request still completes if the connection has gone away
+        let _ = self.sender.send(notification);
+    }
+}
+
+/// Open debounce window for one service
+#[derive(Debug)]
+struct Window {
+    closes_at: Instant,
+    /// State reported by the last notification sent
+    reported: HealthState,
+    /// Transitions seen since then
+    pending: Vec<HealthEvent>,
+}
+
+/// Sends the first transition of a service immediately, then at most one
+/// notification per window; several transitions in one window are reported
+/// as a single `Degraded` notification marked as flapping.
+#[derive(Debug)]
+pub struct HealthDebouncer {
+    window: Duration,
+    windows: HashMap<String, Window>,
+}
+
+impl HealthDebouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            windows: HashMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, event: HealthEvent, now: Instant) -> Option<McpNotification> {
+        if let Some(window) = self.windows.get_mut(&event.service) {
+            if now < window.closes_at {
+                window.pending.push(event);
+                return None;
+            }
+        }
+
+        let notification = notification(&event, event.transition.from, event.transition.to, 1);
+        self.windows.insert(event.service.clone(), Window {
+            closes_at: now + self.window,
+            reported: event.transition.to,
+            pending: Vec::new(),
+        });
+        Some(notification)
+    }
+
+    /// Summarize windows that have closed
+    pub fn flush_due(&mut self, now: Instant) -> Vec<McpNotification> {
+        let mut notifications = Vec::new();
+        let window_length = self.window;
+
+        self.windows.retain(|_, window| {
+            if now < window.closes_at {
+                return true;
+            }
+            let Some(last) = window.pending.last() else {
+                return false;
+            };
+
+            let count = window.pending.len();
+            let new_state = if count > 1 { HealthState::Degraded } else { last.transition.to };
+            if new_state != window.reported || count > 1 {
+                notifications.push(notification(last, window.reported, new_state, count));
+            }
+
+            // Keep flapping services rate-limited
+            window.reported = new_state;
+            window.pending.clear();
+            window.closes_at = now + window_length;
+            true
+        });
+
+        notifications
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.windows
+            .values()
+            .filter(|window| !window.pending.is_empty())
+            .map(|window| window.closes_at)
+            .min()
+    }
+}
+
+fn notification(event: &HealthEvent, old_state: HealthState, new_state: HealthState, transitions: usize) -> McpNotification {
+    McpNotification::new(HEALTH_CHANGED_METHOD, json!({
+        "service": event.service,
+        "old_state": old_state,
+        "new_state": new_state,
+        "current_state": event.transition.to,
+        "at": event.transition.at.to_rfc3339(),
+        "consecutive_failures": event.transition.consecutive_failures,
+        "reason": event.transition.reason,
+        "flapping": transitions > 1,
+        "transitions": transitions,
+    }))
+}
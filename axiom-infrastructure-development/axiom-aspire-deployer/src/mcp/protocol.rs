@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,17 +14,102 @@ pub struct McpRequest {
 pub struct McpResponse {
     pub jsonrpc: String,
     pub id: Value,
+    /// Exactly one of `result` and `error` is sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<McpError>,
 }
 
+/// Server-initiated JSON-RPC notification (no `id`, no reply expected)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Option<Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpError {
     pub code: i32,
     pub message: String,
+    /// `ErrorData` for errors raised by tools
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub data: Option<Value>,
 }
 
+/// Why a tool failed, so clients can tell what is worth retrying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// No service, container or endpoint by the given name
+    ServiceNotFound,
+    /// The Aspire dashboard could not be reached or answered with a server error
+    DashboardUnavailable,
+    /// The dashboard rejected the configured credentials
+    AuthFailed,
+    /// A service did not reach the requested state in time
+    Timeout,
+    /// The request clashes with the current state, e.g. ports in use or a dependency cycle
+    Conflict,
+    /// The deployer serving the request does not provide the tool's operation
+    Unsupported,
+    /// Missing or malformed parameters, or a request the configuration forbids
+    InvalidArgument,
+    Internal,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 8] = [
+        ErrorCode::ServiceNotFound,
+        ErrorCode::DashboardUnavailable,
+        ErrorCode::AuthFailed,
+        ErrorCode::Timeout,
+        ErrorCode::Conflict,
+        ErrorCode::Unsupported,
+        ErrorCode::InvalidArgument,
+        ErrorCode::Internal,
+    ];
+    
+    /// JSON-RPC error code; codes without a standard equivalent use the
+    /// server error range -32000 to -32099
+    pub fn code(&self) -> i32 {
+        match self {
+            ErrorCode::ServiceNotFound => -32001,
+            ErrorCode::DashboardUnavailable => -32002,
+            ErrorCode::AuthFailed => -32003,
+            ErrorCode::Timeout => -32004,
+            ErrorCode::Conflict => -32005,
+            ErrorCode::Unsupported => -32006,
+            ErrorCode::InvalidArgument => -32602,
+            ErrorCode::Internal => -32603,
+        }
+    }
+    
+    pub fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|error_code| error_code.code() == code)
+    }
+    
+    /// Whether the same request may succeed later without changes
+    pub fn retryable(&self) -> bool {
+        matches!(self, ErrorCode::DashboardUnavailable | ErrorCode::Timeout)
+    }
+}
+
+/// `data` of a tool error
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorData {
+    pub kind: ErrorCode,
+    pub retryable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Fields specific to the error, such as `available_services`
+    #[serde(flatten)]
+    pub details: Map<String, Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolDefinition {
     pub name: String,
@@ -112,26 +197,28 @@ impl McpResponse {
     pub fn invalid_params(id: Value, message: String) -> Self {
         Self::error(
             id,
-            McpError {
-                code: -32602,
-                message: format!("Invalid params: {}", message),
-                data: None,
-            },
+            McpError::with_code(ErrorCode::InvalidArgument, format!("Invalid params: {}", message)),
         )
     }
     
     pub fn internal_error(id: Value, message: String) -> Self {
         Self::error(
             id,
-            McpError {
-                code: -32603,
-                message: format!("Internal error: {}", message),
-                data: None,
-            },
+            McpError::with_code(ErrorCode::Internal, format!("Internal error: {}", message)),
         )
     }
 }
 
+impl McpNotification {
+    pub fn new(method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: Some(params),
+        }
+    }
+}
+
 impl McpError {
     pub fn new(code: i32, message: String) -> Self {
         Self {
@@ -140,4 +227,43 @@ impl McpError {
             data: None,
         }
     }
+    
+    /// Error with `ErrorData` carrying the kind and its default retryable flag
+    pub fn with_code(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code: code.code(),
+            message: message.into(),
+            data: Some(json!({ "kind": code, "retryable": code.retryable() })),
+        }
+    }
+    
+    pub fn service(self, service: &str) -> Self {
+        self.detail("service", service)
+    }
+    
+    pub fn endpoint(self, endpoint: &str) -> Self {
+        self.detail("endpoint", endpoint)
+    }
+    
+    /// Override the retryable flag implied by the error code
+    pub fn retryable(self, retryable: bool) -> Self {
+        self.detail("retryable", retryable)
+    }
+    
+    pub fn detail(mut self, key: &str, value: impl Serialize) -> Self {
+        let data = self.data.get_or_insert_with(|| json!({}));
+        if let Some(fields) = data.as_object_mut() {
+            fields.insert(key.to_string(), json!(value));
+        }
+        self
+    }
+    
+    /// The structured data of a tool error; `None` for protocol errors
+    pub fn error_data(&self) -> Option<ErrorData> {
+        serde_json::from_value(self.data.clone()?).ok()
+    }
+    
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        self.error_data().map(|data| data.kind)
+    }
 }
\ No newline at end of file
@@ -1,14 +1,62 @@
 use anyhow::Result;
 use dashmap::DashMap;
 use serde_json::{json, Value};
-use std::sync::Arc;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 
 use crate::config::Settings;
-use crate::mcp::protocol::{McpRequest, McpResponse, ServiceStatus};
+use crate::config::reload::{ConfigReloader, ConfigSource};
+use crate::mcp::protocol::{McpNotification, McpRequest, McpResponse, ServiceStatus};
 use crate::mcp::handlers::RequestHandler;
-use crate::services::{AspireServiceDiscovery, AspireOrchestrator, HealthMonitor, NetworkManager};
+use crate::mcp::notifications::{HealthWatch, ProgressReporter, BUDGET_ALERT_METHOD, DEFAULT_DEBOUNCE, HEALTH_CHANGED_METHOD, TEARDOWN_COMPLETED_METHOD, TEARDOWN_WARNING_METHOD};
+use crate::services::budgets::BudgetEvent;
+use crate::services::history::{self, HistoryEntry};
+use crate::services::ports;
+use crate::services::teardown::{IdleAction, IdleTimer};
+use crate::services::{AspireServiceDiscovery, AspireOrchestrator, CrashLoopMonitor, DeploymentHistory, EnvironmentTeardown, HealthMonitor, LocalAspireDeployer, NetworkManager, ServiceMetricsCollector};
+
+/// Tool that toggles health notifications for the calling connection
+const WATCH_HEALTH_TOOL: &str = "axiom_watch_health";
+
+/// How often a disarmed idle timer is looked at again
+const IDLE_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// History actor of teardowns triggered by the idle timeout
+const IDLE_TEARDOWN_ACTOR: &str = "idle_timeout";
+
+/// Server-wide notifications buffered per client before it starts lagging
+const ANNOUNCEMENT_CHANNEL_CAPACITY: usize = 16;
+
+/// How MCP clients connect to the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// Newline-delimited JSON-RPC, one session per TCP connection
+    Tcp,
+    /// MCP streamable HTTP with SSE; see `mcp::http`
+    Http,
+}
+
+impl Transport {
+    pub fn parse(transport: &str) -> Option<Self> {
+        match transport.to_ascii_lowercase().as_str() {
+            "tcp" => Some(Transport::Tcp),
+            "http" | "sse" => Some(Transport::Http),
+            _ => None,
+        }
+    }
+    
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Transport::Tcp => "tcp",
+            Transport::Http => "http",
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct AxiomAspireMcpServer {
@@ -17,61 +65,71 @@ pub struct AxiomAspireMcpServer {
     orchestrator: Arc<AspireOrchestrator>,
     network_manager: Arc<NetworkManager>,
     health_monitor: Arc<HealthMonitor>,
+    metrics_collector: Arc<ServiceMetricsCollector>,
+    crash_monitor: Arc<CrashLoopMonitor>,
+    deployment_history: Arc<DeploymentHistory>,
+    config_reloader: Arc<ConfigReloader>,
     service_state: Arc<DashMap<String, ServiceStatus>>,
+    teardown: Arc<EnvironmentTeardown>,
+    /// `None` unless `teardown.idle_timeout_minutes` is set
+    idle_timer: Option<Arc<Mutex<IdleTimer>>>,
+    /// Notifications for every connected client, whatever it watches
+    announcements: broadcast::Sender<McpNotification>,
     handler: Arc<RequestHandler>,
 }
 
 impl AxiomAspireMcpServer {
     pub async fn new(settings: Settings) -> Result<Self> {
+        Self::with_config_source(settings, None).await
+    }
+    
+    /// Like `new`, with the file `settings` came from so they can be reloaded
+    pub async fn with_config_source(settings: Settings, config_source: Option<ConfigSource>) -> Result<Self> {
         info!("Initializing Axiom Aspire MCP Server");
         
-        // Create service components
-        let service_discovery = Arc::new(
-            AspireServiceDiscovery::new(&settings.aspire.dashboard_url)
-        );
+        let deployer = LocalAspireDeployer::with_config_source(&settings, config_source).await?;
+        let idle_timer = IdleTimer::from_config(&settings.teardown, Instant::now())
+            .map(|timer| Arc::new(Mutex::new(timer)));
         
-        let orchestrator = Arc::new(
-            AspireOrchestrator::new(&settings.aspire.dashboard_url).await?
-        );
-        
-        let network_manager = Arc::new(
-            NetworkManager::new(&settings.network)
-        );
-        
-        let health_monitor = Arc::new(
-            HealthMonitor::new(settings.monitoring.health_check_interval_ms)
-        );
-        
-        let service_state = Arc::new(DashMap::new());
-        
-        let handler = Arc::new(RequestHandler::new(
-            service_discovery.clone(),
-            orchestrator.clone(),
-            network_manager.clone(),
-            health_monitor.clone(),
-            service_state.clone(),
-        ));
+        let handler = Arc::new(RequestHandler::new(Arc::new(deployer.clone())));
         
         Ok(Self {
             settings,
-            service_discovery,
-            orchestrator,
-            network_manager,
-            health_monitor,
-            service_state,
+            service_discovery: deployer.service_discovery,
+            orchestrator: deployer.orchestrator,
+            network_manager: deployer.network_manager,
+            health_monitor: deployer.health_monitor,
+            metrics_collector: deployer.metrics_collector,
+            crash_monitor: deployer.crash_monitor,
+            deployment_history: deployer.deployment_history,
+            config_reloader: deployer.config_reloader,
+            service_state: deployer.service_state,
+            teardown: deployer.teardown,
+            idle_timer,
+            announcements: broadcast::channel(ANNOUNCEMENT_CHANNEL_CAPACITY).0,
             handler,
         })
     }
     
-    pub async fn run(&self, port: u16) -> Result<()> {
+    /// Bind and serve; with `auto_port` a taken port is replaced by the next free one
+    pub async fn run(&self, port: u16, auto_port: bool, transport: Transport) -> Result<()> {
+        let port = ports::resolve_server_port(&self.settings.server.host, port, auto_port).await?;
         let addr = format!("{}:{}", self.settings.server.host, port);
         let listener = TcpListener::bind(&addr).await?;
         
-        info!("MCP Server listening on {}", addr);
+        info!("MCP Server listening on {} ({})", addr, transport.as_str());
         
         // Start background tasks
         self.start_background_tasks().await?;
         
+        match transport {
+            Transport::Tcp => self.serve(listener).await,
+            Transport::Http => self.serve_http(listener).await,
+        }
+    }
+    
+    /// Accept MCP connections on an already bound listener
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
         loop {
             match listener.accept().await {
                 Ok((stream, addr)) => {
@@ -94,14 +152,49 @@ impl AxiomAspireMcpServer {
     async fn start_background_tasks(&self) -> Result<()> {
         info!("Starting background monitoring tasks");
         
+        // Subscribe before monitoring starts so no transition goes unrecorded
+        let history = self.deployment_history.clone();
+        let actions = self.orchestrator.subscribe();
+        let transitions = self.health_monitor.subscribe();
+        
+        tokio::spawn(async move {
+            history.record_events(actions, transitions).await;
+        });
+        
+        if self.settings.monitoring.watch_config_files && self.config_reloader.source().is_some() {
+            if let Err(e) = self.config_reloader.clone().watch() {
+                warn!("Configuration changes will not be picked up automatically: {}", e);
+            }
+        }
+        
         // Start service discovery polling
         if self.settings.aspire.auto_discovery {
             let discovery = self.service_discovery.clone();
+            let crash_monitor = self.crash_monitor.clone();
             let state = self.service_state.clone();
             let interval = self.settings.aspire.polling_interval_ms;
             
             tokio::spawn(async move {
-                Self::service_discovery_task(discovery, state, interval).await;
+                Self::service_discovery_task(discovery, crash_monitor, state, interval).await;
+            });
+        }
+        
+        // Budget alerts go to every client and into the history
+        let history = self.deployment_history.clone();
+        let budget_events = self.metrics_collector.subscribe_budgets();
+        let announcements = self.announcements.clone();
+        
+        tokio::spawn(async move {
+            Self::budget_alert_task(budget_events, history, announcements).await;
+        });
+        
+        // Tear the environment down once MCP clients have gone quiet
+        if let Some(idle_timer) = self.idle_timer.clone() {
+            let teardown = self.teardown.clone();
+            let announcements = self.announcements.clone();
+            
+            tokio::spawn(async move {
+                Self::idle_teardown_task(idle_timer, teardown, announcements).await;
             });
         }
         
@@ -113,11 +206,20 @@ impl AxiomAspireMcpServer {
             health_monitor.start_monitoring(state).await;
         });
         
+        // Start metrics collection
+        let metrics_collector = self.metrics_collector.clone();
+        let state = self.service_state.clone();
+        
+        tokio::spawn(async move {
+            metrics_collector.start_collecting(state).await;
+        });
+        
         Ok(())
     }
     
     async fn service_discovery_task(
         discovery: Arc<AspireServiceDiscovery>,
+        crash_monitor: Arc<CrashLoopMonitor>,
         state: Arc<DashMap<String, ServiceStatus>>,
         interval_ms: u64,
     ) {
@@ -130,6 +232,7 @@ impl AxiomAspireMcpServer {
             
             match discovery.discover_services().await {
                 Ok(services) => {
+                    crash_monitor.observe(&services).await;
                     for service in services {
                         state.insert(service.name.clone(), service);
                     }
@@ -141,45 +244,175 @@ impl AxiomAspireMcpServer {
         }
     }
     
+    async fn budget_alert_task(
+        mut budget_events: broadcast::Receiver<BudgetEvent>,
+        history: Arc<DeploymentHistory>,
+        announcements: broadcast::Sender<McpNotification>,
+    ) {
+        loop {
+            let event = match budget_events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Budget alerts lagged, skipped {}", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            
+            if let Err(e) = history.record(&HistoryEntry::from_budget_event(&event)).await {
+                warn!("Failed to record budget alert: {}", e);
+            }
+            let _ = announcements.send(McpNotification::new(BUDGET_ALERT_METHOD, json!(event)));
+        }
+    }
+    
+    async fn idle_teardown_task(
+        idle_timer: Arc<Mutex<IdleTimer>>,
+        teardown: Arc<EnvironmentTeardown>,
+        announcements: broadcast::Sender<McpNotification>,
+    ) {
+        loop {
+            let wakeup = idle_timer.lock().expect("idle timer lock poisoned").next_wakeup();
+            match wakeup {
+                Some(at) => tokio::time::sleep_until(at.into()).await,
+                // Disarmed until the next request
+                None => tokio::time::sleep(IDLE_RECHECK_INTERVAL).await,
+            }
+            
+            // Requests may have moved the deadline while we slept
+            let action = idle_timer.lock().expect("idle timer lock poisoned").poll(Instant::now());
+            match action {
+                Some(IdleAction::Warn { remaining }) => {
+                    warn!("No MCP activity, tearing the environment down in {}s", remaining.as_secs());
+                    // No receivers just means no client is connected
+                    let _ = announcements.send(McpNotification::new(TEARDOWN_WARNING_METHOD, json!({
+                        "reason": "idle_timeout",
+                        "remaining_seconds": remaining.as_secs(),
+                        "message": "Send any MCP request to keep the environment running",
+                    })));
+                }
+                Some(IdleAction::Teardown) => {
+                    info!("No MCP activity, tearing the environment down");
+                    let options = teardown.default_options();
+                    let report = history::with_actor(
+                        IDLE_TEARDOWN_ACTOR,
+                        teardown.teardown(&options, |message| debug!("Idle teardown: {}", message)),
+                    ).await;
+                    if !report.clean {
+                        warn!("Idle teardown left {} things behind", report.leftovers.len());
+                    }
+                    let _ = announcements.send(McpNotification::new(TEARDOWN_COMPLETED_METHOD, json!(report)));
+                }
+                None => {}
+            }
+        }
+    }
+    
+    /// Postpone the idle teardown
+    pub(crate) fn record_activity(&self) {
+        if let Some(idle_timer) = &self.idle_timer {
+            idle_timer.lock().expect("idle timer lock poisoned").touch(Instant::now());
+        }
+    }
+    
+    /// Receive the notifications sent to every client
+    pub(crate) fn subscribe_announcements(&self) -> broadcast::Receiver<McpNotification> {
+        self.announcements.subscribe()
+    }
+    
     async fn handle_connection(&self, mut stream: TcpStream) -> Result<()> {
-        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+        use tokio::io::{AsyncBufReadExt, BufReader};
         
         let (reader, mut writer) = stream.split();
-        let mut reader = BufReader::new(reader);
-        let mut line = String::new();
+        let mut lines = BufReader::new(reader).lines();
+        let mut health_events = self.health_monitor.subscribe();
+        let mut health_watch: Option<HealthWatch> = None;
+        let mut announcements = self.subscribe_announcements();
         
         loop {
-            line.clear();
-            match reader.read_line(&mut line).await {
-                Ok(0) => break, // Connection closed
-                Ok(_) => {
+            let flush_at = health_watch.as_ref().and_then(|watch| watch.next_deadline());
+            
+            tokio::select! {
+                line = lines.next_line() => {
+                    let line = match line {
+                        Ok(Some(line)) => line,
+                        Ok(None) => break, // Connection closed
+                        Err(e) => {
+                            error!("Connection read error: {}", e);
+                            break;
+                        }
+                    };
                     if line.trim().is_empty() {
                         continue;
                     }
                     
-                    match self.process_request(&line).await {
-                        Ok(response) => {
-                            let response_json = serde_json::to_string(&response)?;
-                            writer.write_all(response_json.as_bytes()).await?;
-                            writer.write_all(b"\n").await?;
-                            writer.flush().await?;
+                    let was_watching = health_watch.is_some();
+                    
+                    // Forward progress notifications while the request runs
+                    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<McpNotification>();
+                    let result = {
+                        let request = self.process_request(&line, &mut health_watch, progress_tx);
+                        tokio::pin!(request);
+                        loop {
+                            tokio::select! {
+                                result = &mut request => break result,
+                                Some(notification) = progress_rx.recv() => {
+                                    Self::write_frame(&mut writer, &notification).await?;
+                                }
+                            }
                         }
+                    };
+                    while let Ok(notification) = progress_rx.try_recv() {
+                        Self::write_frame(&mut writer, &notification).await?;
+                    }
+                    
+                    let response = match result {
+                        Ok(response) => response,
                         Err(e) => {
                             error!("Request processing error: {}", e);
-                            let error_response = McpResponse::internal_error(
-                                json!(null),
-                                e.to_string(),
-                            );
-                            let response_json = serde_json::to_string(&error_response)?;
-                            writer.write_all(response_json.as_bytes()).await?;
-                            writer.write_all(b"\n").await?;
-                            writer.flush().await?;
+                            McpResponse::internal_error(json!(null), e.to_string())
                         }
+                    };
+                    
+                    // Only report transitions that happen after opting in
+                    if !was_watching && health_watch.is_some() {
+                        health_events = self.health_monitor.subscribe();
                     }
+                    Self::write_frame(&mut writer, &response).await?;
                 }
-                Err(e) => {
-                    error!("Connection read error: {}", e);
-                    break;
+                event = health_events.recv(), if health_watch.is_some() => {
+                    match event {
+                        Ok(event) => {
+                            let notification = health_watch
+                                .as_mut()
+                                .and_then(|watch| watch.on_event(event, Instant::now()));
+                            if let Some(notification) = notification {
+                                Self::write_frame(&mut writer, &notification).await?;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Health notifications lagged, skipped {} transitions", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+                announcement = announcements.recv() => {
+                    match announcement {
+                        Ok(notification) => Self::write_frame(&mut writer, &notification).await?,
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Server notifications lagged, skipped {}", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+                _ = tokio::time::sleep_until(flush_at.unwrap_or_else(Instant::now).into()), if flush_at.is_some() => {
+                    let notifications = health_watch
+                        .as_mut()
+                        .map(|watch| watch.flush_due(Instant::now()))
+                        .unwrap_or_default();
+                    for notification in notifications {
+                        Self::write_frame(&mut writer, &notification).await?;
+                    }
                 }
             }
         }
@@ -187,23 +420,111 @@ impl AxiomAspireMcpServer {
         Ok(())
     }
     
-    async fn process_request(&self, request_line: &str) -> Result<McpResponse> {
+    async fn write_frame<W, T>(writer: &mut W, frame: &T) -> Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+        T: Serialize,
+    {
+        use tokio::io::AsyncWriteExt;
+        
+        let frame_json = serde_json::to_string(frame)?;
+        writer.write_all(frame_json.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        writer.flush().await?;
+        Ok(())
+    }
+    
+    async fn process_request(
+        &self,
+        request_line: &str,
+        health_watch: &mut Option<HealthWatch>,
+        progress_tx: mpsc::UnboundedSender<McpNotification>,
+    ) -> Result<McpResponse> {
         debug!("Processing request: {}", request_line);
+        self.record_activity();
         
         let request: McpRequest = serde_json::from_str(request_line.trim())?;
+        if let Some(arguments) = Self::watch_arguments(&request) {
+            return Ok(Self::handle_watch_health(request.id, &arguments, health_watch));
+        }
+        self.dispatch(request, progress_tx).await
+    }
+    
+    /// Handle any request but `axiom_watch_health`, which needs the
+    /// connection's state
+    pub(crate) async fn dispatch(
+        &self,
+        request: McpRequest,
+        progress_tx: mpsc::UnboundedSender<McpNotification>,
+    ) -> Result<McpResponse> {
+        let progress = ProgressReporter::from_params(request.params.as_ref(), &progress_tx);
         
         // Handle special MCP protocol methods
         match request.method.as_str() {
             "initialize" => self.handle_initialize(request).await,
             "tools/list" => self.handle_tools_list(request).await,
-            "tools/call" => self.handle_tools_call(request).await,
+            "tools/call" => self.handle_tools_call(request, progress).await,
             _ => {
                 // Delegate to request handler
-                self.handler.handle_request(request).await
+                self.handler.handle_request_with_progress(request, progress).await
             }
         }
     }
     
+    /// Arguments of an `axiom_watch_health` call, direct or through `tools/call`
+    pub(crate) fn watch_arguments(request: &McpRequest) -> Option<Value> {
+        match request.method.as_str() {
+            "tools/call" if Self::tool_name(request) == Some(WATCH_HEALTH_TOOL) => Some(
+                request.params
+                    .as_ref()
+                    .and_then(|params| params.get("arguments"))
+                    .cloned()
+                    .unwrap_or(json!({})),
+            ),
+            WATCH_HEALTH_TOOL => Some(request.params.clone().unwrap_or(json!({}))),
+            _ => None,
+        }
+    }
+    
+    fn tool_name(request: &McpRequest) -> Option<&str> {
+        request.params.as_ref()?.get("name")?.as_str()
+    }
+    
+    /// Health notifications are per connection, so this tool is handled here
+    /// rather than in `RequestHandler`
+    pub(crate) fn handle_watch_health(id: Value, arguments: &Value, health_watch: &mut Option<HealthWatch>) -> McpResponse {
+        let enabled = arguments.get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        
+        if !enabled {
+            *health_watch = None;
+            return McpResponse::success(id, json!({ "watching": false }));
+        }
+        
+        let services: Option<HashSet<String>> = arguments.get("services")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect());
+        let debounce = arguments.get("debounce_ms")
+            .and_then(|v| v.as_u64())
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_DEBOUNCE);
+        
+        info!("Health notifications enabled (services: {:?}, debounce: {:?})", services, debounce);
+        
+        let result = json!({
+            "watching": true,
+            "services": services,
+            "debounce_ms": debounce.as_millis() as u64,
+            "notification_method": HEALTH_CHANGED_METHOD
+        });
+        *health_watch = Some(HealthWatch::new(services, debounce));
+        McpResponse::success(id, result)
+    }
+    
     async fn handle_initialize(&self, request: McpRequest) -> Result<McpResponse> {
         let result = json!({
             "protocolVersion": "2024-11-05",
@@ -232,7 +553,7 @@ impl AxiomAspireMcpServer {
         Ok(McpResponse::success(request.id, result))
     }
     
-    async fn handle_tools_call(&self, request: McpRequest) -> Result<McpResponse> {
+    async fn handle_tools_call(&self, request: McpRequest, progress: Option<ProgressReporter>) -> Result<McpResponse> {
         if let Some(params) = request.params {
             if let Some(name) = params.get("name").and_then(|v| v.as_str()) {
                 let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
@@ -245,7 +566,7 @@ impl AxiomAspireMcpServer {
                     params: Some(arguments),
                 };
                 
-                return self.handler.handle_request(tool_request).await;
+                return self.handler.handle_request_with_progress(tool_request, progress).await;
             }
         }
         
@@ -255,6 +576,18 @@ impl AxiomAspireMcpServer {
         ))
     }
     
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+    
+    pub fn health_monitor(&self) -> Arc<HealthMonitor> {
+        self.health_monitor.clone()
+    }
+    
+    pub fn orchestrator(&self) -> Arc<AspireOrchestrator> {
+        self.orchestrator.clone()
+    }
+    
     fn get_available_tools(&self) -> Vec<Value> {
         vec![
             json!({
@@ -318,6 +651,99 @@ impl AxiomAspireMcpServer {
                     }
                 }
             }),
+            json!({
+                "name": "axiom_get_health_history",
+                "description": "Health state transitions (Starting, Healthy, Degraded, Unhealthy) recorded by the health monitor",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Limit to one service; all services when omitted"
+                        },
+                        "limit": {
+                            "type": "number",
+                            "description": "Most recent transitions to return per service"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_diagnose_service",
+                "description": "Explain a failing service: restart timeline from the dashboard's resource states, whether it is crash-looping, the stderr captured before its last exit and hints for common failures",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_reload_config",
+                "description": "Re-read the configuration file, apply health check, secret masking and HTTP retry changes live, and list changes that need a restart",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {}
+                }
+            }),
+            json!({
+                "name": "axiom_get_deployment_history",
+                "description": "Start/stop/restart actions and health transitions recorded in the persistent deployment history",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "since": {
+                            "type": "string",
+                            "description": "RFC 3339 timestamp; only entries at or after it"
+                        },
+                        "until": {
+                            "type": "string",
+                            "description": "RFC 3339 timestamp; only entries at or before it"
+                        },
+                        "services": {
+                            "type": "array",
+                            "items": {
+                                "type": "string"
+                            },
+                            "description": "Only entries targeting these services (`apphost` for the AppHost); all when omitted"
+                        },
+                        "limit": {
+                            "type": "number",
+                            "default": 100,
+                            "description": "Most recent entries to return"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": WATCH_HEALTH_TOOL,
+                "description": "Opt this session in or out of axiom/health_changed notifications",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "enabled": {
+                            "type": "boolean",
+                            "default": true
+                        },
+                        "services": {
+                            "type": "array",
+                            "items": {
+                                "type": "string"
+                            },
+                            "description": "Only notify for these services; all when omitted"
+                        },
+                        "debounce_ms": {
+                            "type": "number",
+                            "default": 5000,
+                            "description": "Transitions within this window are collapsed into one notification"
+                        }
+                    }
+                }
+            }),
             json!({
                 "name": "axiom_get_service_urls",
                 "description": "Get current service URLs from Aspire dashboard",
@@ -358,6 +784,509 @@ impl AxiomAspireMcpServer {
                     "required": ["service", "endpoint"]
                 }
             }),
+            json!({
+                "name": "axiom_invoke_service_endpoint",
+                "description": "Send a GET or POST to a service at the endpoint Aspire assigned it and return status, headers, timing and a size-limited body. Only hosts and ports of discovered services can be reached, including through redirects",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Resource or display name"
+                        },
+                        "path": {
+                            "type": "string",
+                            "default": "/",
+                            "description": "Path and query relative to the service's base URL, e.g. /health"
+                        },
+                        "method": {
+                            "type": "string",
+                            "enum": ["GET", "POST"],
+                            "default": "GET"
+                        },
+                        "endpoint": {
+                            "type": "string",
+                            "description": "Endpoint name such as https; the first HTTP endpoint when omitted"
+                        },
+                        "headers": {
+                            "type": "object"
+                        },
+                        "body": {
+                            "description": "Sent as is when a string, as JSON otherwise"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_start_service",
+                "description": "Start a single Aspire resource and wait until it is healthy",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name or display name"
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "How long to wait for the new state (defaults to aspire.service_action_timeout_ms)"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_stop_service",
+                "description": "Stop a single Aspire resource and wait until it is down",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name or display name"
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "How long to wait for the new state (defaults to aspire.service_action_timeout_ms)"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_restart_service",
+                "description": "Restart a single Aspire resource and wait until it is healthy again",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name or display name"
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "How long to wait for the new state (defaults to aspire.service_action_timeout_ms)"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_scale_service",
+                "description": "Run a number of replicas of an Aspire project resource and wait until all of them are healthy",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name or display name"
+                        },
+                        "replicas": {
+                            "type": "number",
+                            "description": "Instances to run; 0 stops the service and requires confirm_stop"
+                        },
+                        "confirm_stop": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Allow scaling to 0 replicas"
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "How long to wait for the replicas (defaults to aspire.service_action_timeout_ms)"
+                        }
+                    },
+                    "required": ["service", "replicas"]
+                }
+            }),
+            json!({
+                "name": "axiom_get_startup_plan",
+                "description": "Show the dependency-ordered startup stages computed from resource references",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {}
+                }
+            }),
+            json!({
+                "name": "axiom_start_services",
+                "description": "Start all services in dependency order, waiting for each stage to become healthy",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "Per-service readiness timeout (defaults to aspire.service_action_timeout_ms)"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_teardown_environment",
+                "description": "Stop all services in reverse dependency order, stop the AppHost, remove the containers and networks Aspire created and check that service ports are released. Anything left behind is reported with steps to clean it up by hand",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "stop_apphost": {
+                            "type": "boolean",
+                            "description": "Stop the AppHost after its services (defaults to teardown.stop_apphost)"
+                        },
+                        "remove_containers": {
+                            "type": "boolean",
+                            "description": "Remove Aspire's containers and networks (defaults to teardown.remove_containers)"
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "Per-service stop timeout (defaults to aspire.service_action_timeout_ms)"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_diff_environment",
+                "description": "Compare the AppHost manifest (resources, environment variables, endpoints, replicas) with the running environment and report added, removed and changed resources, flagging drift that needs a restart",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "manifest_path": {
+                            "type": "string",
+                            "description": "Manifest to compare; defaults to aspire.manifest_path"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_get_service_map",
+                "description": "Get the service topology as a graph: nodes with resource type, health, replicas and endpoints, and edges for manifest references (connection_string, endpoint, parameter) and dashboard relationships (wait_for, reference). Optionally rendered as Graphviz DOT or Mermaid with nodes colored by health",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "manifest_path": {
+                            "type": "string",
+                            "description": "Manifest to read references from; defaults to aspire.manifest_path. Without one, only dashboard relationships are mapped"
+                        },
+                        "format": {
+                            "type": "string",
+                            "enum": ["json", "dot", "mermaid"],
+                            "description": "Also render the graph for pasting into docs (default: json only)"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_get_service_config",
+                "description": "Get a service's resolved environment variables and Aspire-injected connection strings, with values whose keys look like secrets (password, key, token, connection-string credentials) masked to a short prefix and suffix",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Resource or display name"
+                        },
+                        "unmask": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Return values unmasked; rejected unless secrets.allow_unmask is enabled"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_wait_for_healthy",
+                "description": "Block until services pass their health checks, reporting per-service progress (pass _meta.progressToken) and returning which became healthy and which timed out with their last probe error",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "services": {
+                            "type": "array",
+                            "items": {
+                                "type": "string"
+                            },
+                            "description": "Resource or display names; all resources when omitted"
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "default": 120000
+                        },
+                        "required_consecutive": {
+                            "type": "number",
+                            "default": 1,
+                            "description": "Consecutive passing checks before a service counts as healthy"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_restart_container",
+                "description": "Hard-restart the Docker container(s) behind an Aspire container resource such as postgres or redis",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name"
+                        },
+                        "stop_timeout_seconds": {
+                            "type": "number",
+                            "default": 10,
+                            "description": "Grace period before the container is killed"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_get_container_logs",
+                "description": "Docker logs (stdout and stderr, timestamped) of an Aspire container resource",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name"
+                        },
+                        "tail": {
+                            "type": "number",
+                            "default": 200,
+                            "description": "Lines from the end of the log, at most 5000"
+                        },
+                        "since": {
+                            "type": "string",
+                            "description": "Timestamp or relative duration such as 10m"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_exec_in_container",
+                "description": "Run an allow-listed diagnostic command (docker.exec_allowlist, e.g. pg_isready) in an Aspire container resource",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name"
+                        },
+                        "command": {
+                            "type": "array",
+                            "items": {
+                                "type": "string"
+                            },
+                            "description": "Program and arguments, run without a shell"
+                        }
+                    },
+                    "required": ["service", "command"]
+                }
+            }),
+            json!({
+                "name": "axiom_snapshot_database",
+                "description": "Dump a local dev database container (Postgres with pg_dump, MySQL with mysqldump) into a named snapshot; only resources flagged databases.resources.<name>.local_dev are allowed",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name of the database container"
+                        },
+                        "database": {
+                            "type": "string",
+                            "description": "Database to dump; defaults to databases.resources.<service>.database"
+                        },
+                        "name": {
+                            "type": "string",
+                            "description": "Snapshot name (letters, digits, '_' and '-'); defaults to <service>-<timestamp>"
+                        },
+                        "overwrite": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Replace an existing snapshot of the same name"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_restore_database",
+                "description": "Drop and recreate a local dev database and import a snapshot into it",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Snapshot to restore"
+                        },
+                        "service": {
+                            "type": "string",
+                            "description": "Resource to restore into; defaults to the snapshot's source"
+                        },
+                        "database": {
+                            "type": "string",
+                            "description": "Database to replace; defaults to the snapshot's database"
+                        }
+                    },
+                    "required": ["name"]
+                }
+            }),
+            json!({
+                "name": "axiom_list_database_snapshots",
+                "description": "List database snapshots with source service, database, dialect, size and timestamp, newest first",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Only snapshots taken from this resource"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_stream_service_logs",
+                "description": "Tail structured logs for a service, optionally following for a bounded window",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name"
+                        },
+                        "tail": {
+                            "type": "number",
+                            "default": 100
+                        },
+                        "follow_seconds": {
+                            "type": "number",
+                            "default": 0,
+                            "description": "Keep collecting new lines for this long (max 60)"
+                        },
+                        "min_level": {
+                            "type": "string",
+                            "enum": ["trace", "debug", "information", "warning", "error", "critical"]
+                        },
+                        "pattern": {
+                            "type": "string",
+                            "description": "Regex matched against the log message"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "Cursor from a previous call; returns only newer lines"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_get_service_metrics",
+                "description": "Resource metrics for a service over a recent window: p50/p95 latency, request rate, memory and CPU, plus the latest value of every metric. Aggregates the service does not export are listed under `missing` instead of being reported as zero",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Aspire resource name"
+                        },
+                        "window_seconds": {
+                            "type": "number",
+                            "default": 300,
+                            "description": "How far back to aggregate (capped at monitoring.metrics_retention_minutes)"
+                        }
+                    },
+                    "required": ["service"]
+                }
+            }),
+            json!({
+                "name": "axiom_get_budget_status",
+                "description": "Latest memory and CPU usage of every service against its budget in monitoring.budgets, with each budget's state: within, over (not yet for the sustained window), exceeded or recovering",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Only this Aspire resource"
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_search_traces",
+                "description": "Search distributed traces in the dashboard's OTLP store, most recent first. Each result summarizes a trace: root span, services involved, start, duration, span and error counts",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Only traces with a span from this service"
+                        },
+                        "min_duration_ms": {
+                            "type": "number",
+                            "description": "Only traces at least this long"
+                        },
+                        "status": {
+                            "type": "string",
+                            "enum": ["ok", "error"],
+                            "description": "error: traces with at least one failed span; ok: traces without"
+                        },
+                        "since": {
+                            "type": "string",
+                            "description": "RFC 3339 timestamp; only traces starting at or after it"
+                        },
+                        "until": {
+                            "type": "string",
+                            "description": "RFC 3339 timestamp; only traces starting at or before it"
+                        },
+                        "limit": {
+                            "type": "number",
+                            "default": 20
+                        }
+                    }
+                }
+            }),
+            json!({
+                "name": "axiom_get_trace",
+                "description": "Get a trace as a span tree with timings relative to the trace start, status, attributes and events, plus an indented `text` rendering with one line per span. Long attribute values are cut to `max_attribute_chars`",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "trace_id": {
+                            "type": "string",
+                            "description": "Hex trace id, e.g. from axiom_search_traces or a traceparent header"
+                        },
+                        "max_attribute_chars": {
+                            "type": "number",
+                            "default": 200,
+                            "description": "Longest attribute value kept; 0 leaves attributes out"
+                        }
+                    },
+                    "required": ["trace_id"]
+                }
+            }),
+            json!({
+                "name": "axiom_resolve_endpoint",
+                "description": "Resolve where a service endpoint listens right now as scheme://host:port, from live discovery. A cached address that refuses connections is looked up again. Optionally writes every discovered endpoint to a .env file (services__<service>__<endpoint>__<replica>=<url>) for external processes",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "service": {
+                            "type": "string",
+                            "description": "Resource or display name"
+                        },
+                        "endpoint": {
+                            "type": "string",
+                            "description": "Endpoint name such as http, https or grpc; the service's first endpoint when omitted"
+                        },
+                        "env_file": {
+                            "type": "string",
+                            "description": "Write all discovered endpoints to this .env file"
+                        },
+                        "refresh": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Skip cached endpoints, e.g. after a connection to a resolved address was refused"
+                        }
+                    }
+                }
+            }),
             json!({
                 "name": "axiom_configure_local_network",
                 "description": "Configure services for local network access",
@@ -0,0 +1,687 @@
+use anyhow::Result;
+use dashmap::DashMap;
+use futures::future::{self, BoxFuture};
+use futures::stream::BoxStream;
+use futures::{FutureExt, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::clients::{AspireDashboardClient, DashboardAuth, DockerClient, HttpClient};
+use crate::clients::aspire::ServiceLogLine;
+use crate::clients::docker::{ContainerLogs, DockerContainer, ExecResult};
+use crate::config::Settings;
+use crate::config::reload::{ConfigReload, ConfigReloader, ConfigSource};
+use crate::config::settings::BudgetsConfig;
+use crate::mcp::protocol::{EndpointCallRequest, EndpointCallResponse, ServiceStatus, ServiceUrls};
+use crate::services::budgets::ServiceBudgetStatus;
+use crate::services::crashloop::ServiceDiagnosis;
+use crate::services::health::{HealthCheckResult, ServiceHealthHistory};
+use crate::services::history::{HistoryEntry, HistoryQuery};
+use crate::services::logs::{LogQuery, LogWindow};
+use crate::services::manifest::EnvironmentDiff;
+use crate::services::metrics::ServiceMetricsSummary;
+use crate::services::network::{EndpointResolution, ResolvedEndpoint};
+use crate::services::orchestrator::{
+    OrderedStartResult, ScaleResult, ServiceAction, ServiceActionResult, ServiceConfig, ServiceWaitStatus,
+    WaitForHealthyOptions, WaitForHealthyResult,
+};
+use crate::services::proxy::{ServiceRequest, ServiceResponse};
+use crate::services::secrets::SecretMasker;
+use crate::services::snapshots::{DatabaseSnapshot, RestoreResult, SnapshotRequest};
+use crate::services::startup::StartupPlan;
+use crate::services::teardown::{TeardownOptions, TeardownReport};
+use crate::services::topology::ServiceMap;
+use crate::services::traces::{TraceQuery, TraceSummary, TraceTree};
+use crate::services::{
+    AspireOrchestrator, AspireServiceDiscovery, CrashLoopMonitor, DatabaseSnapshots, DeploymentHistory, EndpointResolver,
+    EnvironmentTeardown, HealthMonitor, NetworkManager, ServiceLogStreamer, ServiceMetricsCollector, ServiceProxy, TraceExplorer,
+};
+
+/// Typed access to the deployer for Rust tooling that does not speak MCP
+///
+/// Every MCP tool is an adapter over this trait, so the same operations
+/// behave identically whether they come from an MCP client or from another
+/// crate in the monorepo. The trait is object safe; hold it as
+/// `Arc<dyn AspireDeployerApi>` to swap in a fake in tests.
+///
+/// Service actions, health checks, endpoint resolution and logs must be
+/// implemented. The other operations fail with `UnsupportedOperation` unless
+/// overridden, so a fake only implements what its test calls.
+///
+/// ```no_run
+/// use axiom_aspire_mcp::{AspireDeployerApi, LocalAspireDeployer};
+/// use axiom_aspire_mcp::config::Settings;
+/// use axiom_aspire_mcp::services::logs::LogQuery;
+/// use futures::StreamExt;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let deployer = LocalAspireDeployer::from_settings(&Settings::default()).await?;
+///
+/// let started = deployer.start_service("api", None).await?;
+/// println!("{} is {}", started.service, started.state);
+///
+/// let resolution = deployer.resolve_endpoint("api", Some("http"), false).await?;
+/// println!("api listens on {}", resolution.endpoint.url);
+///
+/// let mut logs = deployer.stream_logs(LogQuery::new("api").with_min_level("warning")?);
+/// while let Some(line) = logs.next().await {
+///     println!("{}", line?.message);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[allow(unused_variables)]
+pub trait AspireDeployerApi: Send + Sync {
+    /// Send `action` for one resource and wait until it reaches the target
+    /// state, or `timeout` (the configured default when `None`) passes
+    fn change_service_state<'a>(
+        &'a self,
+        service: &'a str,
+        action: ServiceAction,
+        timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<ServiceActionResult>>;
+
+    fn start_service<'a>(&'a self, service: &'a str, timeout: Option<Duration>) -> BoxFuture<'a, Result<ServiceActionResult>> {
+        self.change_service_state(service, ServiceAction::Start, timeout)
+    }
+
+    fn stop_service<'a>(&'a self, service: &'a str, timeout: Option<Duration>) -> BoxFuture<'a, Result<ServiceActionResult>> {
+        self.change_service_state(service, ServiceAction::Stop, timeout)
+    }
+
+    fn restart_service<'a>(&'a self, service: &'a str, timeout: Option<Duration>) -> BoxFuture<'a, Result<ServiceActionResult>> {
+        self.change_service_state(service, ServiceAction::Restart, timeout)
+    }
+
+    /// Check every known service once; `deep_check` also runs the
+    /// configured probes
+    fn get_health(&self, deep_check: bool) -> BoxFuture<'_, Result<Vec<HealthCheckResult>>>;
+
+    /// Where `endpoint` of `service` listens right now; its first endpoint
+    /// when `endpoint` is `None`
+    fn resolve_endpoint<'a>(
+        &'a self,
+        service: &'a str,
+        endpoint: Option<&'a str>,
+        refresh: bool,
+    ) -> BoxFuture<'a, Result<EndpointResolution>>;
+
+    /// Write every resolved endpoint to a dotenv file at `path`
+    fn write_env_file<'a>(&'a self, path: &'a Path, refresh: bool) -> BoxFuture<'a, Result<Vec<ResolvedEndpoint>>>;
+
+    /// A bounded window of logs; see `ServiceLogStreamer::read`
+    fn read_logs<'a>(&'a self, query: &'a LogQuery) -> BoxFuture<'a, Result<LogWindow>>;
+
+    /// Matching log lines as they arrive, until the stream is dropped; see
+    /// `ServiceLogStreamer::stream`
+    fn stream_logs(&self, query: LogQuery) -> BoxStream<'static, Result<ServiceLogLine>>;
+
+    /// Dashboard whose connection failures are reported as such; `None`
+    /// when the implementation has no dashboard of its own
+    fn dashboard_url(&self) -> Option<&str> {
+        None
+    }
+
+    /// Launch the AppHost with a launch profile
+    fn start_aspire<'a>(&'a self, profile: &'a str, watch: bool) -> BoxFuture<'a, Result<()>> {
+        unsupported("start_aspire")
+    }
+
+    fn stop_aspire(&self) -> BoxFuture<'_, Result<()>> {
+        unsupported("stop_aspire")
+    }
+
+    /// Restart `service` through the AppHost, or every resource for `all`
+    fn restart_aspire<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<()>> {
+        unsupported("restart_aspire")
+    }
+
+    /// Whether the AppHost runs and its dashboard answers
+    fn get_aspire_status(&self) -> BoxFuture<'_, Result<Value>> {
+        unsupported("get_aspire_status")
+    }
+
+    /// Services as of the last discovery poll
+    fn service_statuses(&self) -> Result<Vec<ServiceStatus>> {
+        Err(UnsupportedOperation { operation: "service_statuses" }.into())
+    }
+
+    /// Recorded health checks of `service`, or of every service
+    fn health_history(&self, service: Option<&str>, limit: Option<usize>) -> Result<Vec<ServiceHealthHistory>> {
+        Err(UnsupportedOperation { operation: "health_history" }.into())
+    }
+
+    fn diagnose_service<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<ServiceDiagnosis>> {
+        unsupported("diagnose_service")
+    }
+
+    fn deployment_history<'a>(&'a self, query: &'a HistoryQuery) -> BoxFuture<'a, Result<Vec<HistoryEntry>>> {
+        unsupported("deployment_history")
+    }
+
+    /// File the deployment history is kept in
+    fn deployment_history_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Apply the configuration file's current contents
+    fn reload_config(&self) -> BoxFuture<'_, Result<ConfigReload>> {
+        unsupported("reload_config")
+    }
+
+    /// File `reload_config` reads
+    fn config_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn get_service_urls(&self) -> BoxFuture<'_, Result<ServiceUrls>> {
+        unsupported("get_service_urls")
+    }
+
+    fn call_endpoint(&self, request: EndpointCallRequest) -> BoxFuture<'_, Result<EndpointCallResponse>> {
+        unsupported("call_endpoint")
+    }
+
+    /// Send an HTTP request to a discovered service; see `ServiceProxy::invoke`
+    fn invoke_service_endpoint<'a>(&'a self, request: &'a ServiceRequest) -> BoxFuture<'a, Result<ServiceResponse>> {
+        unsupported("invoke_service_endpoint")
+    }
+
+    fn scale_service<'a>(
+        &'a self,
+        service: &'a str,
+        replicas: usize,
+        confirm_stop: bool,
+        timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<ScaleResult>> {
+        unsupported("scale_service")
+    }
+
+    /// Poll until the services pass enough consecutive checks, calling
+    /// `on_progress` after every check
+    fn wait_for_healthy<'a>(
+        &'a self,
+        options: &'a WaitForHealthyOptions,
+        on_progress: &'a mut (dyn FnMut(&ServiceWaitStatus) + Send),
+    ) -> BoxFuture<'a, Result<WaitForHealthyResult>> {
+        unsupported("wait_for_healthy")
+    }
+
+    fn get_startup_plan(&self) -> BoxFuture<'_, Result<StartupPlan>> {
+        unsupported("get_startup_plan")
+    }
+
+    /// Start every service in dependency order, one stage at a time
+    fn start_services_in_order(&self, timeout: Option<Duration>) -> BoxFuture<'_, Result<OrderedStartResult>> {
+        unsupported("start_services_in_order")
+    }
+
+    /// Teardown options from the configuration
+    fn default_teardown_options(&self) -> Result<TeardownOptions> {
+        Err(UnsupportedOperation { operation: "default_teardown_options" }.into())
+    }
+
+    /// Stop the environment; leftovers are part of the report rather than
+    /// an error
+    fn teardown<'a>(
+        &'a self,
+        options: &'a TeardownOptions,
+        on_progress: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<TeardownReport>> {
+        unsupported("teardown")
+    }
+
+    /// Compare the running resources with the AppHost manifest
+    fn diff_environment<'a>(&'a self, manifest_path: Option<&'a Path>) -> BoxFuture<'a, Result<EnvironmentDiff>> {
+        unsupported("diff_environment")
+    }
+
+    fn get_service_map<'a>(&'a self, manifest_path: Option<&'a Path>) -> BoxFuture<'a, Result<ServiceMap>> {
+        unsupported("get_service_map")
+    }
+
+    /// Environment and arguments of `service`, with secrets masked unless
+    /// `unmask` is allowed
+    fn get_service_config<'a>(&'a self, service: &'a str, unmask: bool) -> BoxFuture<'a, Result<ServiceConfig>> {
+        unsupported("get_service_config")
+    }
+
+    fn restart_container<'a>(
+        &'a self,
+        service: &'a str,
+        stop_timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<Vec<DockerContainer>>> {
+        unsupported("restart_container")
+    }
+
+    fn container_logs<'a>(
+        &'a self,
+        service: &'a str,
+        tail: usize,
+        since: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<ContainerLogs>> {
+        unsupported("container_logs")
+    }
+
+    fn exec_in_container<'a>(&'a self, service: &'a str, command: &'a [String]) -> BoxFuture<'a, Result<ExecResult>> {
+        unsupported("exec_in_container")
+    }
+
+    fn snapshot_database<'a>(
+        &'a self,
+        request: &'a SnapshotRequest,
+        on_progress: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<DatabaseSnapshot>> {
+        unsupported("snapshot_database")
+    }
+
+    fn restore_database<'a>(
+        &'a self,
+        name: &'a str,
+        service: Option<&'a str>,
+        database: Option<&'a str>,
+        on_progress: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<RestoreResult>> {
+        unsupported("restore_database")
+    }
+
+    fn list_database_snapshots<'a>(&'a self, service: Option<&'a str>) -> BoxFuture<'a, Result<Vec<DatabaseSnapshot>>> {
+        unsupported("list_database_snapshots")
+    }
+
+    /// Metrics of `service` summarized over the last `window`
+    fn get_service_metrics<'a>(&'a self, service: &'a str, window: Duration) -> BoxFuture<'a, Result<ServiceMetricsSummary>> {
+        unsupported("get_service_metrics")
+    }
+
+    fn budgets_config(&self) -> Result<BudgetsConfig> {
+        Err(UnsupportedOperation { operation: "budgets_config" }.into())
+    }
+
+    fn budget_status(&self) -> Result<Vec<ServiceBudgetStatus>> {
+        Err(UnsupportedOperation { operation: "budget_status" }.into())
+    }
+
+    fn search_traces<'a>(&'a self, query: &'a TraceQuery) -> BoxFuture<'a, Result<Vec<TraceSummary>>> {
+        unsupported("search_traces")
+    }
+
+    fn get_trace<'a>(&'a self, trace_id: &'a str, max_attribute_chars: usize) -> BoxFuture<'a, Result<TraceTree>> {
+        unsupported("get_trace")
+    }
+
+    fn configure_local_network<'a>(&'a self, services: &'a [String], bind_mode: &'a str) -> BoxFuture<'a, Result<Value>> {
+        unsupported("configure_local_network")
+    }
+
+    fn get_network_urls(&self) -> BoxFuture<'_, Result<HashMap<String, String>>> {
+        unsupported("get_network_urls")
+    }
+}
+
+/// An `AspireDeployerApi` operation the implementation does not override
+#[derive(Debug, thiserror::Error)]
+#[error("The deployer does not support {operation}")]
+pub struct UnsupportedOperation {
+    pub operation: &'static str,
+}
+
+fn unsupported<'a, T: Send + 'a>(operation: &'static str) -> BoxFuture<'a, Result<T>> {
+    future::ready(Err(UnsupportedOperation { operation }.into())).boxed()
+}
+
+/// `AspireDeployerApi` running in the calling process, against the
+/// dashboard and AppHost the settings point at
+#[derive(Clone)]
+pub struct LocalAspireDeployer {
+    pub(crate) service_discovery: Arc<AspireServiceDiscovery>,
+    pub(crate) orchestrator: Arc<AspireOrchestrator>,
+    pub(crate) network_manager: Arc<NetworkManager>,
+    pub(crate) health_monitor: Arc<HealthMonitor>,
+    endpoint_resolver: Arc<EndpointResolver>,
+    log_streamer: Arc<ServiceLogStreamer>,
+    pub(crate) metrics_collector: Arc<ServiceMetricsCollector>,
+    pub(crate) crash_monitor: Arc<CrashLoopMonitor>,
+    trace_explorer: Arc<TraceExplorer>,
+    service_proxy: Arc<ServiceProxy>,
+    docker_client: Arc<DockerClient>,
+    database_snapshots: Arc<DatabaseSnapshots>,
+    pub(crate) teardown: Arc<EnvironmentTeardown>,
+    pub(crate) deployment_history: Arc<DeploymentHistory>,
+    pub(crate) config_reloader: Arc<ConfigReloader>,
+    /// Filled by the MCP server's discovery polling
+    pub(crate) service_state: Arc<DashMap<String, ServiceStatus>>,
+}
+
+impl LocalAspireDeployer {
+    /// Build the components from `settings`, without the MCP server or its
+    /// background tasks
+    pub async fn from_settings(settings: &Settings) -> Result<Self> {
+        Self::with_config_source(settings, None).await
+    }
+
+    /// Like `from_settings`, with the file `settings` came from so they can
+    /// be reloaded
+    pub async fn with_config_source(settings: &Settings, config_source: Option<ConfigSource>) -> Result<Self> {
+        let service_discovery = Arc::new(AspireServiceDiscovery::new(&settings.aspire.dashboard_url));
+
+        let health_monitor = Arc::new(
+            HealthMonitor::new(settings.monitoring.health_check_interval_ms)
+                .with_probes(settings.monitoring.probes.clone())
+                .with_grpc(settings.grpc.clone())
+        );
+
+        let orchestrator = Arc::new(
+            AspireOrchestrator::new(
+                &settings.aspire.dashboard_url,
+                service_discovery.clone(),
+                health_monitor.clone(),
+            )
+            .await?
+            .with_service_action_timeout(Duration::from_millis(settings.aspire.service_action_timeout_ms))
+            .with_manifest_path(settings.aspire.manifest_path.as_ref().map(PathBuf::from))
+            .with_secret_masking(SecretMasker::from_config(&settings.secrets)?, settings.secrets.allow_unmask)
+        );
+
+        let network_manager = Arc::new(NetworkManager::new(&settings.network));
+
+        let endpoint_resolver = Arc::new(EndpointResolver::new(service_discovery.clone()));
+
+        let dashboard_http = HttpClient::from_config(&settings.http);
+        let mut dashboard_client = AspireDashboardClient::with_http_client(
+            &settings.aspire.dashboard_url,
+            dashboard_http.clone(),
+        );
+        if let Some(auth) = DashboardAuth::from_config(&settings.aspire.auth)? {
+            dashboard_client = dashboard_client.with_auth(auth);
+        }
+
+        let dashboard_client = Arc::new(dashboard_client);
+        let log_streamer = Arc::new(ServiceLogStreamer::new(
+            dashboard_client.clone(),
+            settings.aspire.polling_interval_ms,
+        ));
+
+        let trace_explorer = Arc::new(TraceExplorer::new(dashboard_client.clone()));
+
+        let crash_monitor = Arc::new(CrashLoopMonitor::new(
+            dashboard_client.clone(),
+            settings.monitoring.crash_loop.clone(),
+        ));
+
+        let metrics_collector = Arc::new(ServiceMetricsCollector::new(
+            dashboard_client,
+            settings.monitoring.metrics_interval_ms,
+            Duration::from_secs(settings.monitoring.metrics_retention_minutes * 60),
+        ).with_budgets(settings.monitoring.budgets.clone()));
+
+        let proxy_http = HttpClient::without_redirects(&settings.http);
+        let service_proxy = Arc::new(ServiceProxy::new(
+            proxy_http.clone(),
+            service_discovery.clone(),
+            settings.http.max_response_body_bytes,
+        ));
+
+        let deployment_history = Arc::new(DeploymentHistory::from_config(&settings.history));
+
+        let docker_client = Arc::new(DockerClient::new(settings.docker.clone()));
+        let database_snapshots = Arc::new(DatabaseSnapshots::new(
+            docker_client.clone(),
+            service_discovery.clone(),
+            settings.databases.clone(),
+        ));
+
+        let teardown = Arc::new(EnvironmentTeardown::new(
+            orchestrator.clone(),
+            service_discovery.clone(),
+            docker_client.clone(),
+            settings.teardown.clone(),
+        ));
+
+        let config_reloader = Arc::new(
+            ConfigReloader::new(settings.clone(), health_monitor.clone(), orchestrator.clone())
+                .with_source(config_source)
+                .with_http_clients(vec![dashboard_http, proxy_http])
+                .with_history(deployment_history.clone())
+        );
+
+        Ok(Self {
+            service_discovery,
+            orchestrator,
+            network_manager,
+            health_monitor,
+            endpoint_resolver,
+            log_streamer,
+            metrics_collector,
+            crash_monitor,
+            trace_explorer,
+            service_proxy,
+            docker_client,
+            database_snapshots,
+            teardown,
+            deployment_history,
+            config_reloader,
+            service_state: Arc::new(DashMap::new()),
+        })
+    }
+}
+
+impl AspireDeployerApi for LocalAspireDeployer {
+    fn change_service_state<'a>(
+        &'a self,
+        service: &'a str,
+        action: ServiceAction,
+        timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<ServiceActionResult>> {
+        self.orchestrator.change_service_state(service, action, timeout).boxed()
+    }
+
+    fn get_health(&self, deep_check: bool) -> BoxFuture<'_, Result<Vec<HealthCheckResult>>> {
+        self.health_monitor.perform_health_check(deep_check).boxed()
+    }
+
+    fn resolve_endpoint<'a>(
+        &'a self,
+        service: &'a str,
+        endpoint: Option<&'a str>,
+        refresh: bool,
+    ) -> BoxFuture<'a, Result<EndpointResolution>> {
+        self.endpoint_resolver.resolve(service, endpoint, refresh).boxed()
+    }
+
+    fn write_env_file<'a>(&'a self, path: &'a Path, refresh: bool) -> BoxFuture<'a, Result<Vec<ResolvedEndpoint>>> {
+        self.endpoint_resolver.write_env_file(path, refresh).boxed()
+    }
+
+    fn read_logs<'a>(&'a self, query: &'a LogQuery) -> BoxFuture<'a, Result<LogWindow>> {
+        self.log_streamer.read(query).boxed()
+    }
+
+    fn stream_logs(&self, query: LogQuery) -> BoxStream<'static, Result<ServiceLogLine>> {
+        self.log_streamer.stream(query).boxed()
+    }
+
+    fn dashboard_url(&self) -> Option<&str> {
+        Some(self.service_discovery.dashboard_url())
+    }
+
+    fn start_aspire<'a>(&'a self, profile: &'a str, watch: bool) -> BoxFuture<'a, Result<()>> {
+        self.orchestrator.start_aspire(profile, watch).boxed()
+    }
+
+    fn stop_aspire(&self) -> BoxFuture<'_, Result<()>> {
+        self.orchestrator.stop_aspire().boxed()
+    }
+
+    fn restart_aspire<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<()>> {
+        self.orchestrator.restart_service(service).boxed()
+    }
+
+    fn get_aspire_status(&self) -> BoxFuture<'_, Result<Value>> {
+        self.orchestrator.get_aspire_status().boxed()
+    }
+
+    fn service_statuses(&self) -> Result<Vec<ServiceStatus>> {
+        Ok(self.service_state.iter().map(|entry| entry.value().clone()).collect())
+    }
+
+    fn health_history(&self, service: Option<&str>, limit: Option<usize>) -> Result<Vec<ServiceHealthHistory>> {
+        Ok(self.health_monitor.health_history(service, limit))
+    }
+
+    fn diagnose_service<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<ServiceDiagnosis>> {
+        self.crash_monitor.diagnose(service).map(Ok).boxed()
+    }
+
+    fn deployment_history<'a>(&'a self, query: &'a HistoryQuery) -> BoxFuture<'a, Result<Vec<HistoryEntry>>> {
+        self.deployment_history.query(query).boxed()
+    }
+
+    fn deployment_history_path(&self) -> Option<PathBuf> {
+        Some(self.deployment_history.path().to_path_buf())
+    }
+
+    fn reload_config(&self) -> BoxFuture<'_, Result<ConfigReload>> {
+        self.config_reloader.reload().boxed()
+    }
+
+    fn config_path(&self) -> Option<PathBuf> {
+        self.config_reloader.source().map(|source| source.path.clone())
+    }
+
+    fn get_service_urls(&self) -> BoxFuture<'_, Result<ServiceUrls>> {
+        self.service_discovery.get_service_urls().boxed()
+    }
+
+    fn call_endpoint(&self, request: EndpointCallRequest) -> BoxFuture<'_, Result<EndpointCallResponse>> {
+        self.orchestrator.call_service_endpoint(request).boxed()
+    }
+
+    fn invoke_service_endpoint<'a>(&'a self, request: &'a ServiceRequest) -> BoxFuture<'a, Result<ServiceResponse>> {
+        self.service_proxy.invoke(request).boxed()
+    }
+
+    fn scale_service<'a>(
+        &'a self,
+        service: &'a str,
+        replicas: usize,
+        confirm_stop: bool,
+        timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<ScaleResult>> {
+        self.orchestrator.scale_service(service, replicas, confirm_stop, timeout).boxed()
+    }
+
+    fn wait_for_healthy<'a>(
+        &'a self,
+        options: &'a WaitForHealthyOptions,
+        on_progress: &'a mut (dyn FnMut(&ServiceWaitStatus) + Send),
+    ) -> BoxFuture<'a, Result<WaitForHealthyResult>> {
+        self.orchestrator.wait_for_healthy(options, on_progress).boxed()
+    }
+
+    fn get_startup_plan(&self) -> BoxFuture<'_, Result<StartupPlan>> {
+        self.orchestrator.get_startup_plan().boxed()
+    }
+
+    fn start_services_in_order(&self, timeout: Option<Duration>) -> BoxFuture<'_, Result<OrderedStartResult>> {
+        self.orchestrator.start_services_in_order(timeout).boxed()
+    }
+
+    fn default_teardown_options(&self) -> Result<TeardownOptions> {
+        Ok(self.teardown.default_options())
+    }
+
+    fn teardown<'a>(
+        &'a self,
+        options: &'a TeardownOptions,
+        on_progress: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<TeardownReport>> {
+        self.teardown.teardown(options, on_progress).map(Ok).boxed()
+    }
+
+    fn diff_environment<'a>(&'a self, manifest_path: Option<&'a Path>) -> BoxFuture<'a, Result<EnvironmentDiff>> {
+        self.orchestrator.diff_environment(manifest_path).boxed()
+    }
+
+    fn get_service_map<'a>(&'a self, manifest_path: Option<&'a Path>) -> BoxFuture<'a, Result<ServiceMap>> {
+        self.orchestrator.get_service_map(manifest_path).boxed()
+    }
+
+    fn get_service_config<'a>(&'a self, service: &'a str, unmask: bool) -> BoxFuture<'a, Result<ServiceConfig>> {
+        self.orchestrator.get_service_config(service, unmask).boxed()
+    }
+
+    fn restart_container<'a>(
+        &'a self,
+        service: &'a str,
+        stop_timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<Vec<DockerContainer>>> {
+        self.docker_client.restart_container(service, stop_timeout).boxed()
+    }
+
+    fn container_logs<'a>(
+        &'a self,
+        service: &'a str,
+        tail: usize,
+        since: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<ContainerLogs>> {
+        self.docker_client.container_logs(service, tail, since).boxed()
+    }
+
+    fn exec_in_container<'a>(&'a self, service: &'a str, command: &'a [String]) -> BoxFuture<'a, Result<ExecResult>> {
+        self.docker_client.exec(service, command).boxed()
+    }
+
+    fn snapshot_database<'a>(
+        &'a self,
+        request: &'a SnapshotRequest,
+        on_progress: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<DatabaseSnapshot>> {
+        self.database_snapshots.snapshot(request, on_progress).boxed()
+    }
+
+    fn restore_database<'a>(
+        &'a self,
+        name: &'a str,
+        service: Option<&'a str>,
+        database: Option<&'a str>,
+        on_progress: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<RestoreResult>> {
+        self.database_snapshots.restore(name, service, database, on_progress).boxed()
+    }
+
+    fn list_database_snapshots<'a>(&'a self, service: Option<&'a str>) -> BoxFuture<'a, Result<Vec<DatabaseSnapshot>>> {
+        self.database_snapshots.list(service).boxed()
+    }
+
+    fn get_service_metrics<'a>(&'a self, service: &'a str, window: Duration) -> BoxFuture<'a, Result<ServiceMetricsSummary>> {
+        self.metrics_collector.summary(service, window).boxed()
+    }
+
+    fn budgets_config(&self) -> Result<BudgetsConfig> {
+        Ok(self.metrics_collector.budgets_config())
+    }
+
+    fn budget_status(&self) -> Result<Vec<ServiceBudgetStatus>> {
+        Ok(self.metrics_collector.budget_status())
+    }
+
+    fn search_traces<'a>(&'a self, query: &'a TraceQuery) -> BoxFuture<'a, Result<Vec<TraceSummary>>> {
+        self.trace_explorer.search(query).boxed()
+    }
+
+    fn get_trace<'a>(&'a self, trace_id: &'a str, max_attribute_chars: usize) -> BoxFuture<'a, Result<TraceTree>> {
+        self.trace_explorer.get_trace(trace_id, max_attribute_chars).boxed()
+    }
+
+    fn configure_local_network<'a>(&'a self, services: &'a [String], bind_mode: &'a str) -> BoxFuture<'a, Result<Value>> {
+        self.network_manager.configure_local_network(services, bind_mode).boxed()
+    }
+
+    fn get_network_urls(&self) -> BoxFuture<'_, Result<HashMap<String, String>>> {
+        self.network_manager.get_network_urls().boxed()
+    }
+}
@@ -0,0 +1,309 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::config::settings::{BudgetsConfig, ResourceBudget};
+use crate::services::metrics::ServiceMetricsSummary;
+
+/// Highest samples an alert carries
+const TOP_VALUES: usize = 5;
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetMetric {
+    Memory,
+    Cpu,
+}
+
+impl BudgetMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetMetric::Memory => "memory",
+            BudgetMetric::Cpu => "cpu",
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            BudgetMetric::Memory => "MB",
+            BudgetMetric::Cpu => "%",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetState {
+    Within,
+    /// Over budget, but not for `sustained_seconds` yet
+    Over,
+    /// Over budget for `sustained_seconds` and reported
+    Exceeded,
+    /// Reported and back under the recovery level, but not for long enough yet
+    Recovering,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAlertKind {
+    Exceeded,
+    Recovered,
+}
+
+/// Resource usage of one service at one sampling interval
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ResourceUsage {
+    pub memory_mb: Option<f64>,
+    /// Percent of one core
+    pub cpu_percent: Option<f64>,
+}
+
+impl ResourceUsage {
+    pub fn from_summary(summary: &ServiceMetricsSummary) -> Self {
+        Self {
+            memory_mb: summary.memory.as_ref().map(|memory| memory.current_bytes as f64 / BYTES_PER_MB),
+            cpu_percent: summary.cpu_percent,
+        }
+    }
+
+    fn get(&self, metric: BudgetMetric) -> Option<f64> {
+        match metric {
+            BudgetMetric::Memory => self.memory_mb,
+            BudgetMetric::Cpu => self.cpu_percent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetSample {
+    pub at: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// A budget exceeded for the sustained window, or recovered from
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetEvent {
+    pub service: String,
+    pub metric: BudgetMetric,
+    pub kind: BudgetAlertKind,
+    pub limit: f64,
+    pub unit: &'static str,
+    /// The sample that completed the window
+    pub value: f64,
+    /// How long the service was over budget, or back under the recovery level
+    pub sustained_ms: u64,
+    /// Highest samples while over budget, highest first
+    pub top_values: Vec<BudgetSample>,
+    pub at: DateTime<Utc>,
+}
+
+impl BudgetEvent {
+    pub fn summary(&self) -> String {
+        let top: Vec<String> = self.top_values.iter().map(|sample| format!("{:.1}", sample.value)).collect();
+        match self.kind {
+            BudgetAlertKind::Exceeded => format!(
+                "{} {:.1}{} over budget of {}{} for {}s (top: {})",
+                self.metric.as_str(), self.value, self.unit, self.limit, self.unit, self.sustained_ms / 1000, top.join(", "),
+            ),
+            BudgetAlertKind::Recovered => format!(
+                "{} back to {:.1}{} within budget of {}{} for {}s (peak: {})",
+                self.metric.as_str(), self.value, self.unit, self.limit, self.unit, self.sustained_ms / 1000,
+                top.first().map(String::as_str).unwrap_or("n/a"),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricBudgetStatus {
+    pub metric: BudgetMetric,
+    pub limit: f64,
+    pub unit: &'static str,
+    pub current: Option<f64>,
+    /// `current` as a percent of `limit`
+    pub utilization_percent: Option<f64>,
+    pub state: BudgetState,
+    /// Start of the pending `over` or `recovering` window
+    pub pending_since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceBudgetStatus {
+    pub service: String,
+    /// Latest sampled usage; empty before the first sample
+    pub usage: ResourceUsage,
+    /// Empty for services without a budget
+    pub budgets: Vec<MetricBudgetStatus>,
+}
+
+/// Hysteresis of one metric of one service
+#[derive(Debug, Default)]
+struct MetricTracker {
+    exceeded: bool,
+    /// First sample of the current run towards the other state
+    pending_since: Option<DateTime<Utc>>,
+    /// Highest samples of the over-budget episode
+    top: Vec<BudgetSample>,
+}
+
+impl MetricTracker {
+    fn state(&self) -> BudgetState {
+        match (self.exceeded, self.pending_since.is_some()) {
+            (false, false) => BudgetState::Within,
+            (false, true) => BudgetState::Over,
+            (true, false) => BudgetState::Exceeded,
+            (true, true) => BudgetState::Recovering,
+        }
+    }
+
+    /// The alert kind and how long the run lasted, when this sample
+    /// completes the sustained window
+    fn observe(&mut self, value: f64, limit: f64, recovery_level: f64, sustained: Duration, now: DateTime<Utc>) -> Option<(BudgetAlertKind, Duration)> {
+        if value > limit {
+            self.record_top(BudgetSample { at: now, value });
+        }
+
+        let moving = if self.exceeded { value < recovery_level } else { value > limit };
+        if !moving {
+            // A brief spike is forgotten, a brief dip does not end the episode
+            self.pending_since = None;
+            if !self.exceeded {
+                self.top.clear();
+            }
+            return None;
+        }
+
+        let since = *self.pending_since.get_or_insert(now);
+        let elapsed = now - since;
+        if elapsed < sustained {
+            return None;
+        }
+
+        self.exceeded = !self.exceeded;
+        self.pending_since = None;
+        let kind = if self.exceeded { BudgetAlertKind::Exceeded } else { BudgetAlertKind::Recovered };
+        Some((kind, elapsed))
+    }
+
+    fn record_top(&mut self, sample: BudgetSample) {
+        let index = self.top.partition_point(|top| top.value >= sample.value);
+        if index < TOP_VALUES {
+            self.top.insert(index, sample);
+            self.top.truncate(TOP_VALUES);
+        }
+    }
+}
+
+/// Checks sampled usage against `[monitoring.budgets]`
+///
+/// A budget is only reported once a service stays over it for
+/// `sustained_seconds`, and only recovers once the service stays under
+/// `recovery_percent` of it for as long, so a brief spike or a usage level
+/// hovering at the limit does not raise a stream of alerts.
+#[derive(Debug)]
+pub struct BudgetTracker {
+    config: BudgetsConfig,
+    trackers: BTreeMap<(String, BudgetMetric), MetricTracker>,
+    usage: BTreeMap<String, ResourceUsage>,
+}
+
+impl BudgetTracker {
+    pub fn new(config: BudgetsConfig) -> Self {
+        Self {
+            config,
+            trackers: BTreeMap::new(),
+            usage: BTreeMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &BudgetsConfig {
+        &self.config
+    }
+
+    /// Record one sampling interval of `service`, returning the alerts it raises
+    ///
+    /// Metrics the service did not report leave their windows untouched.
+    pub fn evaluate(&mut self, service: &str, usage: ResourceUsage, now: DateTime<Utc>) -> Vec<BudgetEvent> {
+        self.usage.insert(service.to_string(), usage);
+        let Some(budget) = self.config.services.get(service) else {
+            return Vec::new();
+        };
+
+        let sustained = Duration::seconds(self.config.sustained_seconds as i64);
+        let recovery_fraction = self.config.recovery_percent / 100.0;
+        let mut events = Vec::new();
+
+        for (metric, limit) in limits(budget) {
+            let Some(value) = usage.get(metric) else {
+                continue;
+            };
+            let tracker = self.trackers.entry((service.to_string(), metric)).or_default();
+            let Some((kind, elapsed)) = tracker.observe(value, limit, limit * recovery_fraction, sustained, now) else {
+                continue;
+            };
+
+            events.push(BudgetEvent {
+                service: service.to_string(),
+                metric,
+                kind,
+                limit,
+                unit: metric.unit(),
+                value,
+                sustained_ms: elapsed.num_milliseconds().max(0) as u64,
+                top_values: tracker.top.clone(),
+                at: now,
+            });
+            if kind == BudgetAlertKind::Recovered {
+                tracker.top.clear();
+            }
+        }
+
+        events
+    }
+
+    /// Latest usage against budget of every sampled or budgeted service
+    pub fn status(&self) -> Vec<ServiceBudgetStatus> {
+        let services: BTreeSet<&String> = self.usage.keys().chain(self.config.services.keys()).collect();
+
+        services
+            .into_iter()
+            .map(|service| {
+                let usage = self.usage.get(service).copied().unwrap_or_default();
+                let budgets = self.config.services
+                    .get(service)
+                    .map(limits)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(metric, limit)| {
+                        let tracker = self.trackers.get(&(service.clone(), metric));
+                        let current = usage.get(metric);
+                        MetricBudgetStatus {
+                            metric,
+                            limit,
+                            unit: metric.unit(),
+                            current,
+                            utilization_percent: current.map(|current| current / limit * 100.0),
+                            state: tracker.map_or(BudgetState::Within, MetricTracker::state),
+                            pending_since: tracker.and_then(|tracker| tracker.pending_since),
+                        }
+                    })
+                    .collect();
+
+                ServiceBudgetStatus { service: service.clone(), usage, budgets }
+            })
+            .collect()
+    }
+}
+
+fn limits(budget: &ResourceBudget) -> Vec<(BudgetMetric, f64)> {
+    let mut limits = Vec::new();
+    if let Some(memory) = budget.max_memory_mb {
+        limits.push((BudgetMetric::Memory, memory as f64));
+    }
+    if let Some(cpu) = budget.max_cpu_percent {
+        limits.push((BudgetMetric::Cpu, cpu));
+    }
+    limits
+}
@@ -0,0 +1,367 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::clients::aspire::{AspireDashboardClient, AspireLogEntry};
+use crate::config::settings::CrashLoopConfig;
+use crate::mcp::protocol::ServiceStatus;
+
+/// Dashboard resource states in which the process is no longer running
+pub const EXIT_STATES: &[&str] = &["Exited", "Finished", "FailedToStart", "Failed"];
+
+/// State transitions kept per service
+const MAX_TIMELINE: usize = 100;
+
+/// Exits, with their captured logs, kept per service
+const MAX_EXITS: usize = 20;
+
+/// A known failure and how to recognize it in a service's output
+///
+/// The first capture group that matched, if any, is reported as the hint's
+/// `detail`, e.g. the address or connection string name.
+pub struct HintPattern {
+    pub id: &'static str,
+    pub pattern: &'static str,
+    pub summary: &'static str,
+    pub remediation: &'static str,
+}
+
+/// Failure patterns checked against the output captured before exits, in order
+pub const HINT_PATTERNS: &[HintPattern] = &[
+    HintPattern {
+        id: "port_in_use",
+        pattern: r"(?i)failed to bind to address (\S+?):?(?:\s|$)|address already in use|EADDRINUSE|only one usage of each socket address",
+        summary: "The port the service listens on is already taken",
+        remediation: "Stop the process holding the port or change the endpoint's port in the AppHost",
+    },
+    HintPattern {
+        id: "missing_connection_string",
+        pattern: r"(?i)connection ?string '([^']+)' (?:is missing|was not found|could not be found)|ConnectionString property has not been initialized|no connection string named '([^']+)'",
+        summary: "A connection string the service needs is not configured",
+        remediation: "Add .WithReference(...) for the resource in the AppHost, or set ConnectionStrings__<name>",
+    },
+    HintPattern {
+        id: "missing_configuration",
+        pattern: r"(?i)environment variable '?(\w+)'? (?:is not set|is missing|not found)|configuration (?:value|section) '([^']+)' (?:is missing|was not found)",
+        summary: "A required setting or environment variable is missing",
+        remediation: "Set the value with .WithEnvironment(...) in the AppHost or in the service's appsettings",
+    },
+    HintPattern {
+        id: "dependency_unreachable",
+        pattern: r"(?i)connection refused|no such host is known|name or service not known|failed to connect to (\S+)",
+        summary: "A dependency was not reachable when the service started",
+        remediation: "Make the service .WaitFor(...) its dependencies in the AppHost, or retry the connection on startup",
+    },
+    HintPattern {
+        id: "file_not_found",
+        pattern: r"(?i)could not find (?:file|a part of the path) '([^']+)'|FileNotFoundException",
+        summary: "A file the service reads at startup does not exist",
+        remediation: "Check the working directory and that the file is copied to the build output",
+    },
+    HintPattern {
+        id: "out_of_memory",
+        pattern: r"OutOfMemoryException|(?i)out of memory|OOMKilled",
+        summary: "The service ran out of memory",
+        remediation: "Look for unbounded caches or loads at startup, or raise the container's memory limit",
+    },
+    // Last, so a more specific hint explains the exception first
+    HintPattern {
+        id: "unhandled_exception",
+        pattern: r"(?i)unhandled exception[.:]?\s+([\w.]+(?:Exception|Error))",
+        summary: "The service exited on an unhandled exception",
+        remediation: "Inspect the stack trace in the log excerpt; the exception type is in the hint's detail",
+    },
+];
+
+static COMPILED_PATTERNS: Lazy<Vec<(&'static HintPattern, Regex)>> = Lazy::new(|| {
+    HINT_PATTERNS
+        .iter()
+        .map(|hint| (hint, Regex::new(hint.pattern).expect("hint patterns are valid regexes")))
+        .collect()
+});
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StateTransition {
+    pub at: DateTime<Utc>,
+    pub from: Option<String>,
+    pub to: String,
+    /// Whether the process exited with this transition
+    pub exit: bool,
+}
+
+/// A console line captured when a service exited
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedLine {
+    pub timestamp: String,
+    pub message: String,
+    pub stderr: bool,
+}
+
+impl From<AspireLogEntry> for CapturedLine {
+    fn from(entry: AspireLogEntry) -> Self {
+        let stderr = entry.source.as_deref().is_some_and(|source| source.eq_ignore_ascii_case("stderr"))
+            || matches!(entry.level.to_ascii_lowercase().as_str(), "error" | "critical" | "fatal");
+        Self {
+            timestamp: entry.timestamp,
+            message: entry.message,
+            stderr,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceExit {
+    pub at: DateTime<Utc>,
+    pub state: String,
+    /// Console output right before the exit, oldest first
+    pub lines: Vec<CapturedLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiagnosisHint {
+    pub id: String,
+    pub summary: String,
+    pub remediation: String,
+    /// First capture of the pattern, such as the address or exception type
+    pub detail: Option<String>,
+    /// Line the pattern matched
+    pub evidence: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceDiagnosis {
+    pub service: String,
+    /// Last state seen on the dashboard
+    pub state: Option<String>,
+    pub crash_looping: bool,
+    /// Exits within the last `window_minutes`
+    pub recent_exits: usize,
+    pub restart_threshold: u32,
+    pub window_minutes: u64,
+    /// State transitions, oldest first
+    pub timeline: Vec<StateTransition>,
+    /// Exits with the log lines captured before each
+    pub exits: Vec<ServiceExit>,
+    /// stderr lines before the latest exit, or the current console tail when
+    /// no exit was captured
+    pub stderr_excerpt: Vec<String>,
+    pub hints: Vec<DiagnosisHint>,
+}
+
+#[derive(Default)]
+struct ServiceCrashHistory {
+    state: Option<String>,
+    timeline: VecDeque<StateTransition>,
+    exits: VecDeque<ServiceExit>,
+}
+
+/// Restart timelines assembled from the dashboard's resource states
+///
+/// The dashboard does not report restarts, so every poll's state is compared
+/// with the previous one; a transition into one of `EXIT_STATES` is an exit.
+pub struct CrashLoopDetector {
+    config: CrashLoopConfig,
+    services: DashMap<String, ServiceCrashHistory>,
+}
+
+impl CrashLoopDetector {
+    pub fn new(config: CrashLoopConfig) -> Self {
+        Self {
+            config,
+            services: DashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &CrashLoopConfig {
+        &self.config
+    }
+
+    /// Record a polled state, returning the transition when it changed
+    pub fn observe(&self, service: &str, state: &str, at: DateTime<Utc>) -> Option<StateTransition> {
+        let mut history = self.services.entry(service.to_string()).or_default();
+        if history.state.as_deref() == Some(state) {
+            return None;
+        }
+
+        let from = history.state.replace(state.to_string());
+        let exit = is_exit_state(state) && !from.as_deref().is_some_and(is_exit_state);
+        let transition = StateTransition { at, from, to: state.to_string(), exit };
+
+        if exit {
+            history.exits.push_back(ServiceExit { at, state: state.to_string(), lines: Vec::new() });
+            if history.exits.len() > MAX_EXITS {
+                history.exits.pop_front();
+            }
+        }
+        history.timeline.push_back(transition.clone());
+        if history.timeline.len() > MAX_TIMELINE {
+            history.timeline.pop_front();
+        }
+        Some(transition)
+    }
+
+    /// Attach the console output captured for the latest exit
+    pub fn capture_logs(&self, service: &str, lines: Vec<CapturedLine>) {
+        if let Some(mut history) = self.services.get_mut(service) {
+            if let Some(exit) = history.exits.back_mut() {
+                exit.lines = lines;
+            }
+        }
+    }
+
+    /// Exits of `service` within the crash-loop window ending at `now`
+    pub fn recent_exits(&self, service: &str, now: DateTime<Utc>) -> usize {
+        let since = now - self.window();
+        self.services
+            .get(service)
+            .map_or(0, |history| history.exits.iter().filter(|exit| exit.at >= since).count())
+    }
+
+    pub fn is_crash_looping(&self, service: &str, now: DateTime<Utc>) -> bool {
+        self.recent_exits(service, now) >= self.config.restart_threshold as usize
+    }
+
+    /// Timeline, captured output and hints for `service`
+    ///
+    /// `current_lines` stand in for the captured output when no exit has been
+    /// seen yet, e.g. for a service that is up but failing its probes.
+    pub fn diagnose(&self, service: &str, now: DateTime<Utc>, current_lines: Vec<CapturedLine>) -> ServiceDiagnosis {
+        let (state, timeline, exits) = self
+            .services
+            .get(service)
+            .map(|history| (
+                history.state.clone(),
+                history.timeline.iter().cloned().collect::<Vec<_>>(),
+                history.exits.iter().cloned().collect::<Vec<_>>(),
+            ))
+            .unwrap_or_default();
+
+        let latest = exits.iter().rev().find(|exit| !exit.lines.is_empty()).map(|exit| exit.lines.as_slice());
+        let excerpt_source = latest.unwrap_or(&current_lines);
+        let mut stderr_excerpt: Vec<String> = excerpt_source
+            .iter()
+            .filter(|line| line.stderr)
+            .map(|line| line.message.clone())
+            .collect();
+        // Services that log everything to stdout still get an excerpt
+        if stderr_excerpt.is_empty() {
+            stderr_excerpt = excerpt_source.iter().map(|line| line.message.clone()).collect();
+        }
+
+        // Latest exit first, so its evidence is reported for repeated hints
+        let lines = exits.iter().rev().flat_map(|exit| exit.lines.iter()).chain(current_lines.iter());
+        let hints = match_hints(lines.map(|line| line.message.as_str()));
+
+        let recent_exits = self.recent_exits(service, now);
+        ServiceDiagnosis {
+            service: service.to_string(),
+            state,
+            crash_looping: recent_exits >= self.config.restart_threshold as usize,
+            recent_exits,
+            restart_threshold: self.config.restart_threshold,
+            window_minutes: self.config.window_minutes,
+            timeline,
+            exits,
+            stderr_excerpt,
+            hints,
+        }
+    }
+
+    fn window(&self) -> ChronoDuration {
+        ChronoDuration::minutes(self.config.window_minutes as i64)
+    }
+}
+
+/// One hint per matching pattern, in `HINT_PATTERNS` order, with the first
+/// line that matched it
+pub fn match_hints<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<DiagnosisHint> {
+    let lines: Vec<&str> = lines.into_iter().collect();
+    COMPILED_PATTERNS
+        .iter()
+        .filter_map(|(hint, regex)| {
+            lines.iter().find_map(|line| regex.captures(line).map(|captures| (line, captures))).map(|(line, captures)| DiagnosisHint {
+                id: hint.id.to_string(),
+                summary: hint.summary.to_string(),
+                remediation: hint.remediation.to_string(),
+                detail: captures.iter().skip(1).flatten().next().map(|group| group.as_str().to_string()),
+                evidence: line.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn is_exit_state(state: &str) -> bool {
+    EXIT_STATES.iter().any(|exit| exit.eq_ignore_ascii_case(state))
+}
+
+/// Feeds polled resource states to a `CrashLoopDetector` and captures the
+/// console output of services as they exit
+pub struct CrashLoopMonitor {
+    client: Arc<AspireDashboardClient>,
+    detector: CrashLoopDetector,
+}
+
+impl CrashLoopMonitor {
+    pub fn new(client: Arc<AspireDashboardClient>, config: CrashLoopConfig) -> Self {
+        Self {
+            client,
+            detector: CrashLoopDetector::new(config),
+        }
+    }
+
+    pub fn detector(&self) -> &CrashLoopDetector {
+        &self.detector
+    }
+
+    /// Record the states of one discovery poll
+    pub async fn observe(&self, services: &[ServiceStatus]) {
+        let now = Utc::now();
+        for service in services {
+            let Some(transition) = self.detector.observe(&service.name, &service.status, now) else {
+                continue;
+            };
+            debug!("{} changed from {:?} to {}", service.name, transition.from, transition.to);
+            if !transition.exit {
+                continue;
+            }
+
+            let lines = self.console_tail(&service.name).await;
+            self.detector.capture_logs(&service.name, lines);
+            if self.detector.is_crash_looping(&service.name, now) {
+                warn!(
+                    "{} is crash-looping: {} exits in the last {} minutes",
+                    service.name,
+                    self.detector.recent_exits(&service.name, now),
+                    self.detector.config().window_minutes,
+                );
+            }
+        }
+    }
+
+    pub async fn diagnose(&self, service: &str) -> ServiceDiagnosis {
+        let has_captured_lines = self.detector.services.get(service).is_some_and(|history| {
+            history.exits.iter().any(|exit| !exit.lines.is_empty())
+        });
+        let current_lines = if has_captured_lines { Vec::new() } else { self.console_tail(service).await };
+        self.detector.diagnose(service, Utc::now(), current_lines)
+    }
+
+    async fn console_tail(&self, service: &str) -> Vec<CapturedLine> {
+        let tail = self.detector.config().log_lines;
+        match self.client.get_resource_logs(service, Some(tail)).await {
+            Ok(entries) => {
+                let skip = entries.len().saturating_sub(tail);
+                entries.into_iter().skip(skip).map(CapturedLine::from).collect()
+            }
+            Err(e) => {
+                warn!("Could not capture logs of {}: {}", service, e);
+                Vec::new()
+            }
+        }
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use std::time::Duration;
 use tracing::{debug, warn};
 
+use crate::clients::aspire::DashboardStatusError;
 use crate::mcp::protocol::{ServiceStatus, ServiceUrls};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,6 +21,23 @@ pub struct ResourceInfo {
     pub properties: Option<HashMap<String, serde_json::Value>>,
     pub environment: Option<HashMap<String, String>>,
     pub urls: Option<Vec<UrlInfo>>,
+    pub relationships: Option<Vec<ResourceRelationship>>,
+}
+
+impl ResourceInfo {
+    /// Whether `name` refers to this resource, by resource or display name
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.displayName == name
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResourceRelationship {
+    #[serde(rename = "resourceName")]
+    pub resource_name: String,
+    /// `Reference`, `WaitFor` or `Parent`
+    #[serde(rename = "type")]
+    pub relationship_type: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,7 +70,11 @@ impl AspireServiceDiscovery {
             dashboard_url: dashboard_url.to_string(),
         }
     }
-    
+
+    pub fn dashboard_url(&self) -> &str {
+        &self.dashboard_url
+    }
+
     pub async fn discover_services(&self) -> Result<Vec<ServiceStatus>> {
         debug!("Discovering services from Aspire dashboard");
         
@@ -77,6 +99,18 @@ impl AspireServiceDiscovery {
         }
     }
     
+    /// List the dashboard resources, failing when the dashboard is unreachable
+    pub async fn get_resources(&self) -> Result<Vec<ResourceInfo>> {
+        let resources_url = format!("{}/api/v1/resources", self.dashboard_url);
+        let response = self.client.get(&resources_url).send().await?;
+        
+        if !response.status().is_success() {
+            return Err(DashboardStatusError::new("Failed to list dashboard resources", response.status()).into());
+        }
+        
+        Ok(response.json().await?)
+    }
+    
     pub async fn get_service_urls(&self) -> Result<ServiceUrls> {
         debug!("Getting service URLs from Aspire dashboard");
         
@@ -2,15 +2,41 @@ use anyhow::Result;
 use dashmap::DashMap;
 use reqwest::Client;
 use serde_json::{json, Value};
-use std::sync::Arc;
-use std::time::Duration;
-use tracing::{debug, warn, error};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
 
+use crate::config::settings::{GrpcConfig, ProbeConfig};
 use crate::mcp::protocol::ServiceStatus;
+use crate::services::probes::{run_probe, HealthEvent, HealthState, HealthTransition, ProbeOutcome, ServiceHealthTracker};
+use crate::services::readiness::{run_readiness, ReadinessResult};
+
+/// Delay between probes while waiting for a service to change health
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Transitions buffered per subscriber before it starts lagging
+const EVENT_CHANNEL_CAPACITY: usize = 256;
 
 pub struct HealthMonitor {
     client: Client,
-    check_interval: Duration,
+    /// Both can change while monitoring; see `update_schedule`
+    check_interval: RwLock<Duration>,
+    probes: RwLock<HashMap<String, ProbeConfig>>,
+    grpc: GrpcConfig,
+    trackers: DashMap<String, ServiceHealthTracker>,
+    events: broadcast::Sender<HealthEvent>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ServiceHealthHistory {
+    pub service: String,
+    pub state: HealthState,
+    pub consecutive_failures: u32,
+    pub transitions: Vec<HealthTransition>,
+    /// Latest readiness gate result, for services with a gate configured
+    pub readiness: Option<ReadinessResult>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -33,47 +59,221 @@ impl HealthMonitor {
         
         Self {
             client,
-            check_interval: Duration::from_millis(check_interval_ms),
+            check_interval: RwLock::new(Duration::from_millis(check_interval_ms)),
+            probes: RwLock::new(HashMap::new()),
+            grpc: GrpcConfig::default(),
+            trackers: DashMap::new(),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        }
+    }
+    
+    /// Receive every state transition recorded from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<HealthEvent> {
+        self.events.subscribe()
+    }
+    
+    /// Per-service probe settings; other services get `ProbeConfig::default()`
+    pub fn with_probes(mut self, probes: HashMap<String, ProbeConfig>) -> Self {
+        *self.probes.get_mut().expect("probe settings lock poisoned") = probes;
+        self
+    }
+    
+    /// Apply new check intervals and probe settings to a running monitor
+    ///
+    /// Services keep their health state and history; the next check uses the
+    /// new settings.
+    pub fn update_schedule(&self, check_interval_ms: u64, probes: HashMap<String, ProbeConfig>) {
+        for mut tracker in self.trackers.iter_mut() {
+            let config = probes.get(tracker.key()).cloned().unwrap_or_default();
+            tracker.set_config(config);
         }
+        *self.check_interval.write().expect("check interval lock poisoned") = Duration::from_millis(check_interval_ms);
+        *self.probes.write().expect("probe settings lock poisoned") = probes;
+        info!("Health checks rescheduled every {}ms", check_interval_ms);
+    }
+    
+    pub fn check_interval(&self) -> Duration {
+        *self.check_interval.read().expect("check interval lock poisoned")
+    }
+    
+    /// Often enough for the default interval and the shortest probe interval
+    fn tick_interval(&self) -> Duration {
+        self.probes
+            .read()
+            .expect("probe settings lock poisoned")
+            .values()
+            .filter_map(|probe| probe.interval_ms)
+            .map(Duration::from_millis)
+            .fold(self.check_interval(), Duration::min)
+    }
+    
+    /// Endpoints and TLS used by gRPC probes
+    pub fn with_grpc(mut self, grpc: GrpcConfig) -> Self {
+        self.grpc = grpc;
+        self
     }
     
     pub async fn start_monitoring(&self, service_state: Arc<DashMap<String, ServiceStatus>>) {
-        debug!("Starting health monitoring with interval: {:?}", self.check_interval);
+        let mut tick = self.tick_interval();
+        debug!("Starting health monitoring with interval: {:?}", tick);
         
-        let mut interval = tokio::time::interval(self.check_interval);
+        let mut interval = tokio::time::interval(tick);
         
         loop {
             interval.tick().await;
             
+            let current = self.tick_interval();
+            if current != tick {
+                debug!("Health monitoring interval changed to {:?}", current);
+                tick = current;
+                interval = tokio::time::interval_at(tokio::time::Instant::now() + tick, tick);
+            }
+            let check_interval = self.check_interval();
+            
             let services: Vec<(String, ServiceStatus)> = service_state
                 .iter()
                 .map(|entry| (entry.key().clone(), entry.value().clone()))
                 .collect();
             
             for (service_name, mut service_status) in services {
-                if let Some(url) = &service_status.url {
-                    match self.check_service_health(url, &service_name).await {
-                        Ok(health_result) => {
-                            service_status.health = if health_result.is_healthy {
-                                "healthy".to_string()
-                            } else {
-                                "unhealthy".to_string()
-                            };
-                            service_status.last_check = chrono::Utc::now();
-                            service_state.insert(service_name, service_status);
-                        }
-                        Err(e) => {
-                            warn!("Health check failed for {}: {}", service_name, e);
-                            service_status.health = "unknown".to_string();
-                            service_status.last_check = chrono::Utc::now();
-                            service_state.insert(service_name, service_status);
-                        }
-                    }
+                let Some(url) = service_status.url.clone() else {
+                    continue;
+                };
+                
+                let due = self.trackers
+                    .get(&service_name)
+                    .is_none_or(|tracker| tracker.is_due(chrono::Utc::now(), check_interval));
+                if !due {
+                    continue;
                 }
+                
+                let state = self.probe_service(&service_name, &url).await;
+                service_status.health = state.as_str().to_string();
+                service_status.last_check = chrono::Utc::now();
+                service_state.insert(service_name, service_status);
             }
         }
     }
     
+    /// Run the configured probe for a service and advance its health state
+    pub async fn probe_service(&self, service_name: &str, service_url: &str) -> HealthState {
+        self.check_service(service_name, service_url).await.0
+    }
+    
+    /// Like `probe_service`, also returning the probe result itself
+    ///
+    /// A passing probe is followed by the service's readiness gate until the
+    /// gate passes; the service stays `Starting` while it fails.
+    pub async fn check_service(&self, service_name: &str, service_url: &str) -> (HealthState, ProbeOutcome) {
+        let (config, readiness_pending) = {
+            let tracker = self.trackers
+                .entry(service_name.to_string())
+                .or_insert_with(|| ServiceHealthTracker::new(self.probe_config(service_name), chrono::Utc::now()));
+            (tracker.config().clone(), tracker.readiness_pending())
+        };
+        
+        let mut outcome = run_probe(&self.client, &self.grpc, service_name, service_url, &config).await;
+        if let Some(check) = config.readiness.as_ref().filter(|_| outcome.success && readiness_pending) {
+            let timeout = Duration::from_millis(config.timeout_ms);
+            let readiness = run_readiness(&self.client, check, service_name, service_url, timeout).await;
+            if !readiness.ready {
+                debug!("{} is not ready yet: {}", service_name, readiness.output);
+            }
+            outcome = outcome.with_readiness(readiness);
+        }
+        
+        let mut tracker = self.trackers
+            .entry(service_name.to_string())
+            .or_insert_with(|| ServiceHealthTracker::new(config, chrono::Utc::now()));
+        let transition = tracker.record(&outcome, chrono::Utc::now());
+        let state = tracker.state();
+        drop(tracker);
+        
+        if let Some(transition) = transition {
+            match transition.to {
+                HealthState::Unhealthy | HealthState::Degraded => warn!(
+                    "{} is {:?} after {} consecutive failures: {}",
+                    service_name, transition.to, transition.consecutive_failures,
+                    transition.reason.as_deref().unwrap_or("probe failed")
+                ),
+                _ => info!("{} is {:?}", service_name, transition.to),
+            }
+            
+            // No subscribers is fine; nobody is watching yet
+            let _ = self.events.send(HealthEvent {
+                service: service_name.to_string(),
+                transition,
+            });
+        }
+        (state, outcome)
+    }
+    
+    fn probe_config(&self, service_name: &str) -> ProbeConfig {
+        self.probes
+            .read()
+            .expect("probe settings lock poisoned")
+            .get(service_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+    
+    /// Whether `service_name` has a readiness gate configured
+    pub fn has_readiness_gate(&self, service_name: &str) -> bool {
+        self.probe_config(service_name).readiness.is_some()
+    }
+    
+    /// Run the readiness gate again on the next passing probe
+    pub fn reset_readiness(&self, service_name: &str) {
+        if let Some(mut tracker) = self.trackers.get_mut(service_name) {
+            tracker.reset_readiness();
+        }
+    }
+    
+    /// Check a service until it passes its probe and readiness gate
+    ///
+    /// Returns the passing check, or `None` if `timeout` elapses first.
+    pub async fn wait_until_ready(&self, service_name: &str, service_url: &str, timeout: Duration) -> Option<ProbeOutcome> {
+        debug!("Waiting up to {:?} for {} to be ready", timeout, service_name);
+        
+        let deadline = Instant::now() + timeout;
+        
+        loop {
+            let (state, outcome) = self.check_service(service_name, service_url).await;
+            if state == HealthState::Healthy {
+                return Some(outcome);
+            }
+            
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+    
+    /// Recorded state transitions, for one service or all of them
+    pub fn health_history(&self, service_name: Option<&str>, limit: Option<usize>) -> Vec<ServiceHealthHistory> {
+        let mut histories: Vec<ServiceHealthHistory> = self.trackers
+            .iter()
+            .filter(|entry| service_name.is_none_or(|name| entry.key() == name))
+            .map(|entry| {
+                let tracker = entry.value();
+                let transitions: Vec<HealthTransition> = tracker.history().cloned().collect();
+                let skip = limit.map_or(0, |limit| transitions.len().saturating_sub(limit));
+                ServiceHealthHistory {
+                    service: entry.key().clone(),
+                    state: tracker.state(),
+                    consecutive_failures: tracker.consecutive_failures(),
+                    transitions: transitions.into_iter().skip(skip).collect(),
+                    readiness: tracker.readiness().cloned(),
+                }
+            })
+            .collect();
+        
+        histories.sort_by(|a, b| a.service.cmp(&b.service));
+        histories
+    }
+    
     pub async fn perform_health_check(&self, deep_check: bool) -> Result<Vec<HealthCheckResult>> {
         debug!("Performing health check (deep: {})", deep_check);
         
@@ -318,4 +518,33 @@ impl HealthMonitor {
     pub async fn check_specific_service(&self, service_url: &str, service_name: &str) -> Result<HealthCheckResult> {
         self.check_service_health(service_url, service_name).await
     }
-}
\ No newline at end of file
+    
+    /// Probe `service_url` until its health matches `expect_healthy`
+    ///
+    /// Returns the matching check, or `None` if `timeout` elapses first.
+    pub async fn wait_for_health(
+        &self,
+        service_url: &str,
+        service_name: &str,
+        expect_healthy: bool,
+        timeout: Duration,
+    ) -> Result<Option<HealthCheckResult>> {
+        debug!("Waiting up to {:?} for {} to be {}", timeout, service_name,
+            if expect_healthy { "healthy" } else { "down" });
+        
+        let deadline = Instant::now() + timeout;
+        
+        loop {
+            let result = self.check_service_health(service_url, service_name).await?;
+            if result.is_healthy == expect_healthy {
+                return Ok(Some(result));
+            }
+            
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, Mutex};
+use tracing::{debug, warn};
+
+use crate::config::settings::HistoryConfig;
+use crate::services::budgets::{BudgetAlertKind, BudgetEvent};
+use crate::services::probes::HealthEvent;
+
+/// Actor of entries recorded outside a tool call
+pub const DEFAULT_ACTOR: &str = "axiom-aspire-mcp";
+
+/// Actor of health transitions
+pub const HEALTH_MONITOR_ACTOR: &str = "health_monitor";
+
+/// Actor of budget alerts
+pub const METRICS_COLLECTOR_ACTOR: &str = "metrics_collector";
+
+tokio::task_local! {
+    static ACTOR: String;
+}
+
+/// Run `future` with `actor` as the actor of the history entries it records
+pub async fn with_actor<F: Future>(actor: &str, future: F) -> F::Output {
+    ACTOR.scope(actor.to_string(), future).await
+}
+
+/// Tool being handled by the current task, or `DEFAULT_ACTOR`
+pub fn current_actor() -> String {
+    ACTOR.try_with(Clone::clone).unwrap_or_else(|_| DEFAULT_ACTOR.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryOutcome {
+    Success,
+    Failure,
+    /// Recorded rather than performed, e.g. a health transition
+    Observed,
+}
+
+/// One line of the history file
+///
+/// Every field is always written, so consumers can rely on the schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Tool that caused the entry, or the component that observed it
+    pub actor: String,
+    /// `start`, `stop`, `restart`, `start_apphost`, `health_changed`, …
+    pub action: String,
+    /// Service name, or `apphost` for the AppHost as a whole
+    pub target: String,
+    pub outcome: HistoryOutcome,
+    pub duration_ms: Option<u64>,
+    /// Error message, profile or health states, depending on the action
+    pub detail: Option<String>,
+}
+
+impl HistoryEntry {
+    /// An entry stamped with the current time and actor
+    pub fn new(action: &str, target: &str, outcome: HistoryOutcome) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            actor: current_actor(),
+            action: action.to_string(),
+            target: target.to_string(),
+            outcome,
+            duration_ms: None,
+            detail: None,
+        }
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration_ms = Some(duration.as_millis() as u64);
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn from_health_event(event: &HealthEvent) -> Self {
+        let transition = &event.transition;
+        let mut detail = format!("{:?} -> {:?}", transition.from, transition.to);
+        if let Some(reason) = &transition.reason {
+            detail.push_str(&format!(": {}", reason));
+        }
+
+        Self {
+            timestamp: transition.at,
+            actor: HEALTH_MONITOR_ACTOR.to_string(),
+            action: "health_changed".to_string(),
+            target: event.service.clone(),
+            outcome: HistoryOutcome::Observed,
+            duration_ms: None,
+            detail: Some(detail),
+        }
+    }
+
+    pub fn from_budget_event(event: &BudgetEvent) -> Self {
+        let action = match event.kind {
+            BudgetAlertKind::Exceeded => "budget_exceeded",
+            BudgetAlertKind::Recovered => "budget_recovered",
+        };
+
+        Self {
+            timestamp: event.at,
+            actor: METRICS_COLLECTOR_ACTOR.to_string(),
+            action: action.to_string(),
+            target: event.service.clone(),
+            outcome: HistoryOutcome::Observed,
+            duration_ms: Some(event.sustained_ms),
+            detail: Some(event.summary()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Only entries targeting these services; all when `None`
+    pub services: Option<HashSet<String>>,
+    /// Most recent entries to return
+    pub limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+            && self.services.as_ref().is_none_or(|services| services.contains(&entry.target))
+    }
+}
+
+/// Append-only JSONL log of orchestrator actions and health transitions
+///
+/// When a line would grow the file past `max_file_bytes`, the file is
+/// rotated to `<path>.1`, shifting older files up to `<path>.<max_files>`
+/// and dropping the oldest.
+pub struct DeploymentHistory {
+    path: PathBuf,
+    max_file_bytes: u64,
+    max_files: usize,
+    /// Serializes appends and rotation
+    write_lock: Mutex<()>,
+}
+
+impl DeploymentHistory {
+    pub fn new(path: impl Into<PathBuf>, max_file_bytes: u64, max_files: usize) -> Self {
+        Self {
+            path: path.into(),
+            max_file_bytes,
+            max_files,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn from_config(config: &HistoryConfig) -> Self {
+        Self::new(&config.path, config.max_file_bytes, config.max_files)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn record(&self, entry: &HistoryEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let size = match tokio::fs::metadata(&self.path).await {
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        };
+        if size > 0 && size + line.len() as u64 > self.max_file_bytes {
+            self.rotate().await?;
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to open deployment history {}: {}", self.path.display(), e))?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Matching entries from the current and rotated files, oldest first
+    pub async fn query(&self, query: &HistoryQuery) -> Result<Vec<HistoryEntry>> {
+        let mut entries = Vec::new();
+        for path in self.files().iter().rev() {
+            let text = match tokio::fs::read_to_string(path).await {
+                Ok(text) => text,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(anyhow::anyhow!("Failed to read deployment history {}: {}", path.display(), e)),
+            };
+
+            for line in text.lines().filter(|line| !line.trim().is_empty()) {
+                match serde_json::from_str::<HistoryEntry>(line) {
+                    Ok(entry) if query.matches(&entry) => entries.push(entry),
+                    Ok(_) => {}
+                    Err(e) => warn!("Skipping unreadable history line in {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        // Files are in order, but concurrent actions can finish out of order
+        entries.sort_by_key(|entry| entry.timestamp);
+        if let Some(limit) = query.limit {
+            entries.drain(..entries.len().saturating_sub(limit));
+        }
+        Ok(entries)
+    }
+
+    /// Record entries from `orchestrator` and transitions from `health`
+    /// until both channels close
+    pub async fn record_events(
+        &self,
+        mut orchestrator: broadcast::Receiver<HistoryEntry>,
+        mut health: broadcast::Receiver<HealthEvent>,
+    ) {
+        let mut orchestrator_open = true;
+        let mut health_open = true;
+
+        while orchestrator_open || health_open {
+            let entry = tokio::select! {
+                event = orchestrator.recv(), if orchestrator_open => match event {
+                    Ok(entry) => entry,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Deployment history lagged, skipped {} actions", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        orchestrator_open = false;
+                        continue;
+                    }
+                },
+                event = health.recv(), if health_open => match event {
+                    Ok(event) => HistoryEntry::from_health_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Deployment history lagged, skipped {} health transitions", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        health_open = false;
+                        continue;
+                    }
+                },
+            };
+
+            debug!("Recording {} of {} by {}", entry.action, entry.target, entry.actor);
+            if let Err(e) = self.record(&entry).await {
+                warn!("Failed to record deployment history: {}", e);
+            }
+        }
+    }
+
+    /// The current file followed by rotated files, newest first
+    fn files(&self) -> Vec<PathBuf> {
+        std::iter::once(self.path.clone())
+            .chain((1..=self.max_files).map(|index| self.rotated(index)))
+            .collect()
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+
+    async fn rotate(&self) -> Result<()> {
+        debug!("Rotating deployment history {}", self.path.display());
+
+        let files = self.files();
+        if let Some(oldest) = files.last() {
+            remove_if_exists(oldest).await?;
+        }
+        for index in (0..files.len() - 1).rev() {
+            if tokio::fs::try_exists(&files[index]).await? {
+                tokio::fs::rename(&files[index], &files[index + 1]).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn remove_if_exists(path: &Path) -> Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use futures::Stream;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::clients::aspire::{severity_number_for_level, AspireDashboardClient, ServiceLogLine};
+
+/// Longest follow window a single tool call may block for
+pub const MAX_FOLLOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct LogQuery {
+    pub service: String,
+    /// Most recent lines to return when no cursor is given
+    pub tail: usize,
+    /// How long to keep polling for new lines before returning
+    pub follow: Duration,
+    /// Minimum OTLP severity number
+    pub min_severity: Option<u8>,
+    pub pattern: Option<Regex>,
+    /// Cursor returned by a previous call; only newer lines are returned
+    pub cursor: Option<String>,
+}
+
+impl LogQuery {
+    pub fn new(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            tail: 100,
+            follow: Duration::ZERO,
+            min_severity: None,
+            pattern: None,
+            cursor: None,
+        }
+    }
+
+    pub fn with_min_level(mut self, level: &str) -> Result<Self> {
+        let severity = severity_number_for_level(level)
+            .ok_or_else(|| anyhow::anyhow!("Unknown log level: {}", level))?;
+        self.min_severity = Some(severity);
+        Ok(self)
+    }
+
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self> {
+        self.pattern = Some(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    fn matches(&self, line: &ServiceLogLine) -> bool {
+        self.min_severity.is_none_or(|min| line.severity_number >= min)
+            && self.pattern.as_ref().is_none_or(|pattern| pattern.is_match(&line.message))
+    }
+}
+
+/// A bounded slice of a service's logs
+#[derive(Debug, Serialize)]
+pub struct LogWindow {
+    pub service: String,
+    pub lines: Vec<ServiceLogLine>,
+    /// Pass back as `cursor` to continue after the last line seen
+    pub cursor: Option<String>,
+    pub followed_ms: u64,
+}
+
+pub struct ServiceLogStreamer {
+    client: Arc<AspireDashboardClient>,
+    poll_interval: Duration,
+}
+
+impl ServiceLogStreamer {
+    pub fn new(client: Arc<AspireDashboardClient>, poll_interval_ms: u64) -> Self {
+        Self {
+            client,
+            poll_interval: Duration::from_millis(poll_interval_ms),
+        }
+    }
+
+    /// Read the tail (or everything after the cursor), then follow for `query.follow`
+    ///
+    /// MCP tool calls return a single response, so follow mode returns what
+    /// arrived during the window together with a cursor for the next call.
+    pub async fn read(&self, query: &LogQuery) -> Result<LogWindow> {
+        let start = Instant::now();
+        let deadline = start + query.follow.min(MAX_FOLLOW);
+
+        let mut after = parse_cursor(query)?;
+        let mut lines = Vec::new();
+
+        loop {
+            lines.extend(next_batch(&self.client, query, &mut after).await?);
+
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            tokio::time::sleep(self.poll_interval.min(deadline - now)).await;
+        }
+
+        debug!("Returning {} log lines for {}", lines.len(), query.service);
+
+        Ok(LogWindow {
+            service: query.service.clone(),
+            lines,
+            cursor: after.map(|nanos| nanos.to_string()),
+            followed_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Stream the tail (or everything after the cursor), then every matching
+    /// line as it arrives, until the stream is dropped
+    ///
+    /// `query.follow` is ignored. The stream ends after the first error;
+    /// the `time_unix_nano` of the last line received is the cursor to
+    /// resume from.
+    pub fn stream(&self, query: LogQuery) -> impl Stream<Item = Result<ServiceLogLine>> + Send + 'static {
+        let state = LogStream {
+            client: self.client.clone(),
+            poll_interval: self.poll_interval,
+            after: None,
+            polled: false,
+            failed: false,
+            pending: VecDeque::new(),
+            query,
+        };
+
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(line) = state.pending.pop_front() {
+                    return Some((Ok(line), state));
+                }
+                if state.failed {
+                    return None;
+                }
+
+                let batch = if state.polled {
+                    tokio::time::sleep(state.poll_interval).await;
+                    next_batch(&state.client, &state.query, &mut state.after).await
+                } else {
+                    state.polled = true;
+                    match parse_cursor(&state.query) {
+                        Ok(after) => {
+                            state.after = after;
+                            next_batch(&state.client, &state.query, &mut state.after).await
+                        }
+                        Err(e) => Err(e),
+                    }
+                };
+                match batch {
+                    Ok(lines) => state.pending.extend(lines),
+                    Err(e) => {
+                        state.failed = true;
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct LogStream {
+    client: Arc<AspireDashboardClient>,
+    poll_interval: Duration,
+    query: LogQuery,
+    after: Option<u64>,
+    polled: bool,
+    failed: bool,
+    pending: VecDeque<ServiceLogLine>,
+}
+
+fn parse_cursor(query: &LogQuery) -> Result<Option<u64>> {
+    match &query.cursor {
+        Some(cursor) => cursor.parse::<u64>()
+            .map(Some)
+            .map_err(|_| anyhow::anyhow!("Invalid log cursor: {}", cursor)),
+        None => Ok(None),
+    }
+}
+
+/// Matching lines after `after`, advancing it past everything fetched
+///
+/// The tail only limits the backlog, so it applies while `after` is unset.
+async fn next_batch(client: &AspireDashboardClient, query: &LogQuery, after: &mut Option<u64>) -> Result<Vec<ServiceLogLine>> {
+    let backlog = after.is_none();
+    let batch = client.get_otlp_logs(&query.service, *after).await?;
+
+    // Advance past filtered-out lines too so they are not fetched again
+    if let Some(last) = batch.last() {
+        *after = Some(last.time_unix_nano);
+    }
+    let mut matching: Vec<ServiceLogLine> = batch.into_iter().filter(|line| query.matches(line)).collect();
+
+    if backlog && matching.len() > query.tail {
+        matching.drain(..matching.len() - query.tail);
+    }
+    Ok(matching)
+}
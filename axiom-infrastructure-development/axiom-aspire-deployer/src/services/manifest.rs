@@ -0,0 +1,259 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Url;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::services::discovery::ResourceInfo;
+
+/// Manifest resource types that run locally and appear in the dashboard
+pub(crate) const LOCAL_RESOURCE_TYPES: &[&str] = &["project", "container", "dockerfile", "executable"];
+
+/// Expressions such as `{cache.connectionString}` that Aspire resolves at run time
+static MANIFEST_EXPRESSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{[^{}]+\}").expect("valid manifest expression pattern")
+});
+
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+    #[error("No AppHost manifest configured; set aspire.manifest_path or pass manifest_path")]
+    NotConfigured,
+    #[error("AppHost manifest {path} has no `resources` object")]
+    MissingResources { path: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ManifestBinding {
+    pub scheme: Option<String>,
+    /// Host port; Aspire picks one when unset
+    pub port: Option<u16>,
+    pub target_port: Option<u16>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ManifestResource {
+    /// `project`, `container`, … without the version suffix
+    pub resource_type: String,
+    pub env: BTreeMap<String, String>,
+    pub bindings: BTreeMap<String, ManifestBinding>,
+    pub replicas: u32,
+}
+
+/// Resources declared by an AppHost manifest (`--publisher manifest`)
+#[derive(Debug, Clone, Default)]
+pub struct AppHostManifest {
+    pub resources: BTreeMap<String, ManifestResource>,
+    /// Parameters, values and cloud resources, which never run locally
+    pub external: BTreeSet<String>,
+}
+
+impl AppHostManifest {
+    pub async fn load(path: &Path) -> Result<Self> {
+        let text = tokio::fs::read_to_string(path).await
+            .map_err(|e| anyhow::anyhow!("Failed to read AppHost manifest {}: {}", path.display(), e))?;
+        let manifest: Value = serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("Invalid AppHost manifest {}: {}", path.display(), e))?;
+
+        Self::parse(&manifest).ok_or_else(|| ManifestError::MissingResources {
+            path: path.display().to_string(),
+        }.into())
+    }
+
+    /// `None` when the document has no `resources` object
+    pub fn parse(manifest: &Value) -> Option<Self> {
+        let mut parsed = Self::default();
+
+        for (name, resource) in manifest.get("resources")?.as_object()? {
+            let resource_type = resource.get("type")
+                .and_then(|v| v.as_str())
+                .and_then(|t| t.split('.').next())
+                .unwrap_or_default();
+            if !LOCAL_RESOURCE_TYPES.contains(&resource_type) {
+                parsed.external.insert(name.clone());
+                continue;
+            }
+
+            let env = resource.get("env")
+                .and_then(|v| v.as_object())
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| (key.clone(), value.as_str().map_or_else(|| value.to_string(), str::to_string)))
+                .collect();
+            let bindings = resource.get("bindings")
+                .and_then(|v| v.as_object())
+                .into_iter()
+                .flatten()
+                .map(|(binding, value)| (binding.clone(), ManifestBinding {
+                    scheme: value.get("scheme").and_then(|v| v.as_str()).map(str::to_string),
+                    port: value.get("port").and_then(|v| v.as_u64()).and_then(|p| u16::try_from(p).ok()),
+                    target_port: value.get("targetPort").and_then(|v| v.as_u64()).and_then(|p| u16::try_from(p).ok()),
+                }))
+                .collect();
+            let replicas = resource.get("replicas")
+                .and_then(|v| v.as_u64())
+                .map_or(1, |replicas| replicas as u32);
+
+            parsed.resources.insert(name.clone(), ManifestResource {
+                resource_type: resource_type.to_string(),
+                env,
+                bindings,
+                replicas,
+            });
+        }
+
+        Some(parsed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    /// `env.<NAME>`, `endpoints.<binding>[.port|.scheme]` or `replicas`
+    pub field: String,
+    pub declared: Option<Value>,
+    pub live: Option<Value>,
+    /// The running environment only picks the change up after a restart
+    pub restart_required: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceDrift {
+    pub resource: String,
+    pub changes: Vec<FieldChange>,
+    pub restart_required: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EnvironmentDiff {
+    /// Declared in the manifest but not running
+    pub added: Vec<String>,
+    /// Running but no longer declared
+    pub removed: Vec<String>,
+    pub changed: Vec<ResourceDrift>,
+    pub unchanged: Vec<String>,
+    /// Some drift only goes away by restarting the AppHost or a service
+    pub restart_required: bool,
+}
+
+/// Compare the declared resources with what the dashboard reports
+///
+/// Only environment variables the manifest declares are compared, since
+/// Aspire injects many of its own. Declared values containing expressions
+/// match any live value in their place. Replicas are the live resources
+/// sharing the manifest name as their display name.
+pub fn diff_environment(manifest: &AppHostManifest, live: &[ResourceInfo]) -> EnvironmentDiff {
+    let mut diff = EnvironmentDiff::default();
+
+    for (name, declared) in &manifest.resources {
+        let replicas: Vec<&ResourceInfo> = live.iter().filter(|r| r.matches(name)).collect();
+        let Some(resource) = replicas.first() else {
+            diff.added.push(name.clone());
+            continue;
+        };
+
+        let changes = resource_changes(declared, resource, replicas.len());
+        if changes.is_empty() {
+            diff.unchanged.push(name.clone());
+        } else {
+            diff.changed.push(ResourceDrift {
+                resource: name.clone(),
+                restart_required: changes.iter().any(|c| c.restart_required),
+                changes,
+            });
+        }
+    }
+
+    let removed: BTreeSet<&str> = live
+        .iter()
+        .filter(|r| !r.resource_type.eq_ignore_ascii_case("parameter"))
+        .filter(|r| !manifest.resources.keys().chain(&manifest.external).any(|name| r.matches(name)))
+        .map(|r| r.displayName.as_str())
+        .collect();
+    diff.removed = removed.into_iter().map(str::to_string).collect();
+
+    diff.restart_required = !diff.added.is_empty()
+        || !diff.removed.is_empty()
+        || diff.changed.iter().any(|drift| drift.restart_required);
+    diff
+}
+
+fn resource_changes(declared: &ManifestResource, live: &ResourceInfo, replicas: usize) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let change = |field: String, declared: Option<Value>, live: Option<Value>, restart_required: bool| FieldChange {
+        field,
+        declared,
+        live,
+        restart_required,
+    };
+
+    for (key, value) in &declared.env {
+        let live_value = live.environment.as_ref().and_then(|env| env.get(key));
+        if !live_value.is_some_and(|live_value| value_matches(value, live_value)) {
+            changes.push(change(format!("env.{}", key), Some(json!(value)), live_value.map(|v| json!(v)), true));
+        }
+    }
+
+    let urls: BTreeMap<&str, Option<Url>> = live.urls
+        .iter()
+        .flatten()
+        .map(|u| (u.name.as_str(), Url::parse(&u.url).ok()))
+        .collect();
+    for (binding_name, binding) in &declared.bindings {
+        let Some(url) = urls.get(binding_name.as_str()) else {
+            changes.push(change(format!("endpoints.{}", binding_name), Some(json!(binding)), None, true));
+            continue;
+        };
+        let Some(url) = url else {
+            continue;
+        };
+
+        if let Some(port) = binding.port {
+            if url.port_or_known_default() != Some(port) {
+                changes.push(change(
+                    format!("endpoints.{}.port", binding_name),
+                    Some(json!(port)),
+                    url.port_or_known_default().map(|p| json!(p)),
+                    true,
+                ));
+            }
+        }
+        if let Some(scheme) = &binding.scheme {
+            if url.scheme() != scheme {
+                changes.push(change(
+                    format!("endpoints.{}.scheme", binding_name),
+                    Some(json!(scheme)),
+                    Some(json!(url.scheme())),
+                    true,
+                ));
+            }
+        }
+    }
+    // Endpoints added at run time (e.g. from a launch profile) are informational
+    for name in urls.keys().filter(|name| !declared.bindings.contains_key(**name)) {
+        let url = live.urls.iter().flatten().find(|u| u.name == *name).map(|u| json!(u.url));
+        changes.push(change(format!("endpoints.{}", name), None, url, false));
+    }
+
+    if declared.replicas as usize != replicas {
+        changes.push(change("replicas".to_string(), Some(json!(declared.replicas)), Some(json!(replicas)), true));
+    }
+
+    changes
+}
+
+/// Whether a live value satisfies a declared one, treating each manifest
+/// expression as a wildcard
+pub fn value_matches(declared: &str, live: &str) -> bool {
+    if !MANIFEST_EXPRESSION.is_match(declared) {
+        return declared == live;
+    }
+
+    let pattern = MANIFEST_EXPRESSION
+        .split(declared)
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("(?s:.*)");
+    Regex::new(&format!("^{}$", pattern)).is_ok_and(|re| re.is_match(live))
+}
@@ -0,0 +1,503 @@
+use anyhow::Result;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::clients::aspire::{AspireDashboardClient, MetricPoint, MetricValue};
+use crate::config::settings::BudgetsConfig;
+use crate::mcp::protocol::ServiceStatus;
+use crate::services::budgets::{BudgetAlertKind, BudgetEvent, BudgetTracker, ResourceUsage, ServiceBudgetStatus};
+
+/// Request duration histograms, current semantic conventions first
+pub const LATENCY_METRICS: &[&str] = &["http.server.request.duration", "http.server.duration"];
+
+/// Process memory in bytes
+pub const MEMORY_METRICS: &[&str] = &["process.memory.usage", "dotnet.process.memory.working_set"];
+
+/// CPU utilization gauges (0-1)
+pub const CPU_UTILIZATION_METRICS: &[&str] = &["process.cpu.utilization"];
+
+/// Cumulative CPU time in seconds, used when no utilization gauge is exported
+pub const CPU_TIME_METRICS: &[&str] = &["process.cpu.time", "dotnet.process.cpu.time"];
+
+const NANOS_PER_SEC: f64 = 1_000_000_000.0;
+
+/// Shortest window budgets are evaluated over; CPU time needs two samples in it
+const BUDGET_SAMPLE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Budget alerts buffered per subscriber before it starts lagging
+const BUDGET_CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySummary {
+    pub metric: String,
+    /// Estimated from histogram buckets
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub request_count: u64,
+    pub requests_per_second: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemorySummary {
+    pub metric: String,
+    pub current_bytes: u64,
+    pub max_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CurrentValue {
+    pub value: f64,
+    pub unit: String,
+    pub time_unix_nano: u64,
+}
+
+/// An aggregate that could not be computed
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingMetric {
+    pub metric: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceMetricsSummary {
+    pub service: String,
+    pub window_ms: u64,
+    /// Data points in the window
+    pub samples: usize,
+    pub latency: Option<LatencySummary>,
+    pub memory: Option<MemorySummary>,
+    /// Percent of one core
+    pub cpu_percent: Option<f64>,
+    /// Latest value of every gauge and sum, summed across attribute sets
+    pub current: BTreeMap<String, CurrentValue>,
+    /// Aggregates left out above because the service does not export them
+    pub missing: Vec<MissingMetric>,
+}
+
+/// Rolling per-service window of metric points
+pub struct MetricsStore {
+    retention: Duration,
+    services: DashMap<String, VecDeque<MetricPoint>>,
+}
+
+impl MetricsStore {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            services: DashMap::new(),
+        }
+    }
+
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Timestamp of the newest stored point, used as the fetch cursor
+    pub fn latest(&self, service: &str) -> Option<u64> {
+        self.services.get(service)?.back().map(|point| point.time_unix_nano)
+    }
+
+    /// Append points newer than the ones stored and drop those past the
+    /// retention period, returning how many were added
+    pub fn record(&self, service: &str, mut points: Vec<MetricPoint>, now_unix_nano: u64) -> usize {
+        let latest = self.latest(service);
+        points.retain(|point| latest.is_none_or(|latest| point.time_unix_nano > latest));
+        points.sort_by_key(|point| point.time_unix_nano);
+        let added = points.len();
+
+        let cutoff = now_unix_nano.saturating_sub(self.retention.as_nanos() as u64);
+        let mut stored = self.services.entry(service.to_string()).or_default();
+        stored.extend(points);
+        while stored.front().is_some_and(|point| point.time_unix_nano < cutoff) {
+            stored.pop_front();
+        }
+
+        added
+    }
+
+    /// Points from the last `window`, capped at the retention period
+    pub fn window(&self, service: &str, window: Duration, now_unix_nano: u64) -> Vec<MetricPoint> {
+        let cutoff = now_unix_nano.saturating_sub(window.min(self.retention).as_nanos() as u64);
+        self.services
+            .get(service)
+            .map(|points| points.iter().filter(|point| point.time_unix_nano >= cutoff).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn summary(&self, service: &str, window: Duration, now_unix_nano: u64) -> ServiceMetricsSummary {
+        let window = window.min(self.retention);
+        summarize(service, &self.window(service, window, now_unix_nano), window)
+    }
+}
+
+/// Pulls metrics from the dashboard into a `MetricsStore`
+pub struct ServiceMetricsCollector {
+    client: Arc<AspireDashboardClient>,
+    store: MetricsStore,
+    interval: Duration,
+    budgets: Mutex<BudgetTracker>,
+    budget_events: broadcast::Sender<BudgetEvent>,
+}
+
+impl ServiceMetricsCollector {
+    pub fn new(client: Arc<AspireDashboardClient>, interval_ms: u64, retention: Duration) -> Self {
+        Self {
+            client,
+            store: MetricsStore::new(retention),
+            interval: Duration::from_millis(interval_ms),
+            budgets: Mutex::new(BudgetTracker::new(BudgetsConfig::default())),
+            budget_events: broadcast::channel(BUDGET_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Check every collection against these budgets
+    pub fn with_budgets(self, config: BudgetsConfig) -> Self {
+        *self.tracker() = BudgetTracker::new(config);
+        self
+    }
+
+    pub fn store(&self) -> &MetricsStore {
+        &self.store
+    }
+
+    /// Receive every budget alert raised from now on
+    pub fn subscribe_budgets(&self) -> broadcast::Receiver<BudgetEvent> {
+        self.budget_events.subscribe()
+    }
+
+    /// Latest usage against budget of every collected or budgeted service
+    pub fn budget_status(&self) -> Vec<ServiceBudgetStatus> {
+        self.tracker().status()
+    }
+
+    /// Seconds over budget before an alert, and the recovery level
+    pub fn budgets_config(&self) -> BudgetsConfig {
+        self.tracker().config().clone()
+    }
+
+    fn tracker(&self) -> MutexGuard<'_, BudgetTracker> {
+        self.budgets.lock().expect("budget tracker lock poisoned")
+    }
+
+    /// Check the latest collection of `service` against its budget
+    pub fn evaluate_budgets(&self, service: &str) -> Vec<BudgetEvent> {
+        let window = BUDGET_SAMPLE_WINDOW.max(self.interval * 2);
+        let usage = ResourceUsage::from_summary(&self.store.summary(service, window, now_unix_nano()));
+        let events = self.tracker().evaluate(service, usage, chrono::Utc::now());
+
+        for event in &events {
+            match event.kind {
+                BudgetAlertKind::Exceeded => warn!("{} exceeded its budget: {}", service, event.summary()),
+                BudgetAlertKind::Recovered => info!("{} is within budget again: {}", service, event.summary()),
+            }
+            // No subscribers just means nobody is listening
+            let _ = self.budget_events.send(event.clone());
+        }
+        events
+    }
+
+    /// Fetch points published since the last collection
+    pub async fn collect(&self, service: &str) -> Result<usize> {
+        let points = self.client.get_otlp_metrics(service, self.store.latest(service)).await?;
+        Ok(self.store.record(service, points, now_unix_nano()))
+    }
+
+    pub async fn start_collecting(&self, service_state: Arc<DashMap<String, ServiceStatus>>) {
+        let mut interval = tokio::time::interval(self.interval);
+
+        loop {
+            interval.tick().await;
+
+            let services: Vec<String> = service_state.iter().map(|entry| entry.key().clone()).collect();
+            for service in services {
+                match self.collect(&service).await {
+                    Ok(_) => {
+                        self.evaluate_budgets(&service);
+                    }
+                    Err(e) => debug!("Failed to collect metrics for {}: {}", service, e),
+                }
+            }
+        }
+    }
+
+    /// Collect the latest points, then summarize the last `window`
+    ///
+    /// A failed fetch is only an error when nothing was collected before.
+    pub async fn summary(&self, service: &str, window: Duration) -> Result<ServiceMetricsSummary> {
+        if let Err(e) = self.collect(service).await {
+            if self.store.latest(service).is_none() {
+                return Err(e);
+            }
+            warn!("Failed to refresh metrics for {}, using stored points: {}", service, e);
+        }
+        Ok(self.store.summary(service, window, now_unix_nano()))
+    }
+}
+
+fn now_unix_nano() -> u64 {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64
+}
+
+/// Aggregate the points of one service
+pub fn summarize(service: &str, points: &[MetricPoint], window: Duration) -> ServiceMetricsSummary {
+    let latency = latency_summary(points);
+    let memory = memory_summary(points);
+    let cpu_percent = cpu_percent(points);
+
+    let mut missing = Vec::new();
+    if latency.is_none() {
+        missing.push(missing_metric("latency", points, LATENCY_METRICS));
+    }
+    if memory.is_none() {
+        missing.push(missing_metric("memory", points, MEMORY_METRICS));
+    }
+    if cpu_percent.is_none() {
+        missing.push(match first_metric(points, CPU_TIME_METRICS) {
+            Some((name, _)) => MissingMetric {
+                metric: "cpu".to_string(),
+                reason: format!("{} needs at least two samples in the window", name),
+            },
+            None => {
+                let names: Vec<&str> = CPU_UTILIZATION_METRICS.iter().chain(CPU_TIME_METRICS).copied().collect();
+                missing_metric("cpu", points, &names)
+            }
+        });
+    }
+
+    ServiceMetricsSummary {
+        service: service.to_string(),
+        window_ms: window.as_millis() as u64,
+        samples: points.len(),
+        latency,
+        memory,
+        cpu_percent,
+        current: current_values(points),
+        missing,
+    }
+}
+
+fn missing_metric(metric: &str, points: &[MetricPoint], names: &[&str]) -> MissingMetric {
+    let reason = if points.is_empty() {
+        "no metrics received for this service in the window".to_string()
+    } else {
+        format!("none of {} exported in the window", names.join(", "))
+    };
+    MissingMetric { metric: metric.to_string(), reason }
+}
+
+/// Points of the first metric in `names` that has any
+fn first_metric<'a>(points: &'a [MetricPoint], names: &[&str]) -> Option<(String, Vec<&'a MetricPoint>)> {
+    names.iter().find_map(|name| {
+        let matching: Vec<&MetricPoint> = points.iter().filter(|point| point.name == *name).collect();
+        (!matching.is_empty()).then(|| (name.to_string(), matching))
+    })
+}
+
+/// Attribute set identifying one time series of a metric
+fn series_key(point: &MetricPoint) -> String {
+    let mut attributes: Vec<String> = point.attributes.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+    attributes.sort();
+    attributes.join(",")
+}
+
+/// Sum of numeric values per timestamp, across attribute sets
+fn totals_by_time(points: &[&MetricPoint]) -> BTreeMap<u64, f64> {
+    let mut totals = BTreeMap::new();
+    for point in points {
+        if let MetricValue::Number { value } = point.value {
+            *totals.entry(point.time_unix_nano).or_insert(0.0) += value;
+        }
+    }
+    totals
+}
+
+fn memory_summary(points: &[MetricPoint]) -> Option<MemorySummary> {
+    let (metric, points) = first_metric(points, MEMORY_METRICS)?;
+    let totals = totals_by_time(&points);
+    let (_, current) = totals.iter().next_back()?;
+    let max = totals.values().copied().fold(f64::MIN, f64::max);
+
+    Some(MemorySummary {
+        metric,
+        current_bytes: *current as u64,
+        max_bytes: max as u64,
+    })
+}
+
+fn cpu_percent(points: &[MetricPoint]) -> Option<f64> {
+    if let Some((_, points)) = first_metric(points, CPU_UTILIZATION_METRICS) {
+        let totals = totals_by_time(&points);
+        return totals.values().next_back().map(|utilization| utilization * 100.0);
+    }
+
+    // CPU time only yields a rate between two samples
+    let (_, points) = first_metric(points, CPU_TIME_METRICS)?;
+    let totals = totals_by_time(&points);
+    let mut latest = totals.iter().rev();
+    let (last_time, last_value) = latest.next()?;
+    let (previous_time, previous_value) = latest.next()?;
+    let elapsed = (last_time - previous_time) as f64 / NANOS_PER_SEC;
+    (elapsed > 0.0).then(|| ((last_value - previous_value) / elapsed * 100.0).max(0.0))
+}
+
+fn current_values(points: &[MetricPoint]) -> BTreeMap<String, CurrentValue> {
+    let mut by_name: HashMap<&str, Vec<&MetricPoint>> = HashMap::new();
+    for point in points {
+        by_name.entry(point.name.as_str()).or_default().push(point);
+    }
+
+    by_name
+        .into_iter()
+        .filter_map(|(name, points)| {
+            let (time_unix_nano, value) = totals_by_time(&points).into_iter().next_back()?;
+            Some((name.to_string(), CurrentValue {
+                value,
+                unit: points[0].unit.clone(),
+                time_unix_nano,
+            }))
+        })
+        .collect()
+}
+
+/// Bucket counts observed during the window
+#[derive(Debug, Clone, PartialEq)]
+struct Buckets {
+    bounds: Vec<f64>,
+    counts: Vec<u64>,
+    /// Nanoseconds the counts cover
+    span: u64,
+}
+
+fn latency_summary(points: &[MetricPoint]) -> Option<LatencySummary> {
+    let (metric, points) = first_metric(points, LATENCY_METRICS)?;
+    let scale = millis_per_unit(&points[0].unit);
+
+    let mut series: HashMap<String, Vec<&MetricPoint>> = HashMap::new();
+    for point in points {
+        series.entry(series_key(point)).or_default().push(point);
+    }
+
+    let mut merged: Option<Buckets> = None;
+    for points in series.values() {
+        let Some(buckets) = window_buckets(points) else {
+            continue;
+        };
+        match &mut merged {
+            None => merged = Some(buckets),
+            Some(merged) if merged.bounds == buckets.bounds => {
+                for (total, count) in merged.counts.iter_mut().zip(&buckets.counts) {
+                    *total += count;
+                }
+                merged.span = merged.span.max(buckets.span);
+            }
+            Some(_) => debug!("Skipping {} series with different bucket bounds", metric),
+        }
+    }
+
+    let buckets = merged?;
+    let request_count: u64 = buckets.counts.iter().sum();
+    let p50 = histogram_percentile(&buckets.bounds, &buckets.counts, 0.5)?;
+    let p95 = histogram_percentile(&buckets.bounds, &buckets.counts, 0.95)?;
+    let span = buckets.span as f64 / NANOS_PER_SEC;
+
+    Some(LatencySummary {
+        metric,
+        p50_ms: p50 * scale,
+        p95_ms: p95 * scale,
+        request_count,
+        requests_per_second: (span > 0.0).then(|| request_count as f64 / span),
+    })
+}
+
+/// Counts one histogram series accumulated during the window
+///
+/// Cumulative series subtract the oldest point in the window from the
+/// newest; delta series add up every point.
+fn window_buckets(points: &[&MetricPoint]) -> Option<Buckets> {
+    let histogram = |point: &MetricPoint| match &point.value {
+        MetricValue::Histogram { bucket_counts, explicit_bounds, .. } if bucket_counts.len() == explicit_bounds.len() + 1 => {
+            Some((explicit_bounds.clone(), bucket_counts.clone()))
+        }
+        _ => None,
+    };
+
+    let first = *points.first()?;
+    let last = *points.last()?;
+    let (bounds, mut counts) = histogram(last)?;
+
+    if last.cumulative {
+        let baseline = (points.len() > 1 && first.start_time_unix_nano == last.start_time_unix_nano)
+            .then(|| histogram(first))
+            .flatten()
+            .filter(|(first_bounds, _)| *first_bounds == bounds);
+        let span = match baseline {
+            Some((_, baseline_counts)) => {
+                for (count, baseline) in counts.iter_mut().zip(baseline_counts) {
+                    *count = count.saturating_sub(baseline);
+                }
+                last.time_unix_nano.saturating_sub(first.time_unix_nano)
+            }
+            // Counter restarted or a single point: everything since its start
+            None => last.time_unix_nano.saturating_sub(last.start_time_unix_nano),
+        };
+        return Some(Buckets { bounds, counts, span });
+    }
+
+    for point in &points[..points.len() - 1] {
+        if let Some((point_bounds, point_counts)) = histogram(point) {
+            if point_bounds == bounds {
+                for (count, extra) in counts.iter_mut().zip(point_counts) {
+                    *count += extra;
+                }
+            }
+        }
+    }
+    let start = if first.start_time_unix_nano > 0 { first.start_time_unix_nano } else { first.time_unix_nano };
+    let span = last.time_unix_nano.saturating_sub(start);
+    Some(Buckets { bounds, counts, span })
+}
+
+/// Estimate the `quantile` (0-1) of an explicit-bucket histogram by linear
+/// interpolation within the bucket that contains it
+///
+/// Values in the unbounded last bucket are reported as the highest bound.
+pub fn histogram_percentile(bounds: &[f64], counts: &[u64], quantile: f64) -> Option<f64> {
+    let total: u64 = counts.iter().sum();
+    if total == 0 || bounds.is_empty() {
+        return None;
+    }
+
+    let rank = quantile.clamp(0.0, 1.0) * total as f64;
+    let mut seen = 0u64;
+    for (index, &count) in counts.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        if (seen + count) as f64 >= rank {
+            let lower = if index == 0 { bounds[0].min(0.0) } else { bounds[index - 1] };
+            let Some(&upper) = bounds.get(index) else {
+                return Some(lower);
+            };
+            let fraction = (rank - seen as f64) / count as f64;
+            return Some(lower + (upper - lower) * fraction);
+        }
+        seen += count;
+    }
+
+    bounds.last().copied()
+}
+
+/// Conversion of a duration unit to milliseconds
+fn millis_per_unit(unit: &str) -> f64 {
+    match unit {
+        "s" => 1000.0,
+        "us" | "µs" => 0.001,
+        "ns" => 0.000_001,
+        _ => 1.0,
+    }
+}
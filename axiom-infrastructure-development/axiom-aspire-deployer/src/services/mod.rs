@@ -1,9 +1,35 @@
+pub mod api;
+pub mod budgets;
+pub mod crashloop;
 pub mod discovery;
 pub mod orchestrator;
 pub mod health;
+pub mod history;
+pub mod probes;
+pub mod readiness;
 pub mod network;
+pub mod ports;
+pub mod proxy;
+pub mod logs;
+pub mod manifest;
+pub mod metrics;
+pub mod secrets;
+pub mod snapshots;
+pub mod startup;
+pub mod teardown;
+pub mod topology;
+pub mod traces;
 
+pub use api::{AspireDeployerApi, LocalAspireDeployer, UnsupportedOperation};
+pub use crashloop::CrashLoopMonitor;
 pub use discovery::AspireServiceDiscovery;
 pub use orchestrator::AspireOrchestrator;
 pub use health::HealthMonitor;
-pub use network::NetworkManager;
\ No newline at end of file
+pub use history::DeploymentHistory;
+pub use network::{EndpointResolver, NetworkManager};
+pub use logs::ServiceLogStreamer;
+pub use metrics::ServiceMetricsCollector;
+pub use proxy::ServiceProxy;
+pub use snapshots::DatabaseSnapshots;
+pub use teardown::EnvironmentTeardown;
+pub use traces::TraceExplorer;
@@ -1,12 +1,232 @@
 use anyhow::Result;
 use local_ip_address::local_ip;
+use reqwest::Url;
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tokio::process::Command;
 use tracing::{debug, info, warn};
 
 use crate::config::settings::NetworkConfig;
+use crate::services::discovery::{AspireServiceDiscovery, ResourceInfo};
+use crate::services::orchestrator::ServiceActionError;
+
+/// How long discovered endpoints are reused before asking the dashboard again
+const ENDPOINT_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Time allowed for the reachability check of a resolved endpoint
+const ENDPOINT_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, thiserror::Error)]
+pub enum EndpointError {
+    #[error("Service '{service}' has no endpoint named '{endpoint}' (endpoints: {})", .available.join(", "))]
+    UnknownEndpoint { service: String, endpoint: String, available: Vec<String> },
+}
+
+/// Where a service endpoint listens right now
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedEndpoint {
+    /// Display name, shared by all replicas
+    pub service: String,
+    /// Resource name of the replica the endpoint belongs to
+    pub resource: String,
+    /// Endpoint name such as `http`, `https` or `grpc`
+    pub endpoint: String,
+    /// `scheme://host:port`, without a path
+    pub url: String,
+    pub scheme: String,
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointResolution {
+    #[serde(flatten)]
+    pub endpoint: ResolvedEndpoint,
+    /// The cached address was refused and the endpoint was looked up again
+    pub refreshed: bool,
+    /// Whether a TCP connection to the address succeeded
+    pub reachable: bool,
+}
+
+/// Every endpoint of every running resource, in dashboard order
+pub fn endpoints(resources: &[ResourceInfo]) -> Vec<ResolvedEndpoint> {
+    resources
+        .iter()
+        .flat_map(|resource| resource.urls.iter().flatten().map(move |u| (resource, u)))
+        .filter_map(|(resource, u)| {
+            let url = Url::parse(&u.url).ok()?;
+            let host = url.host_str()?.to_string();
+            let port = url.port_or_known_default()?;
+            Some(ResolvedEndpoint {
+                service: resource.displayName.clone(),
+                resource: resource.name.clone(),
+                endpoint: u.name.clone(),
+                url: format!("{}://{}:{}", url.scheme(), host, port),
+                scheme: url.scheme().to_string(),
+                host,
+                port,
+            })
+        })
+        .collect()
+}
+
+/// Find `service`'s endpoint by name, or its first endpoint when `endpoint` is `None`
+///
+/// `service` may be a resource or display name; for a replicated service the
+/// first replica's endpoint is returned.
+pub fn resolve_endpoint(endpoints: &[ResolvedEndpoint], service: &str, endpoint: Option<&str>) -> Result<ResolvedEndpoint> {
+    let candidates: Vec<&ResolvedEndpoint> = endpoints
+        .iter()
+        .filter(|e| e.service == service || e.resource == service)
+        .collect();
+    if candidates.is_empty() {
+        let mut available: Vec<String> = endpoints.iter().map(|e| e.service.clone()).collect();
+        available.sort();
+        available.dedup();
+        return Err(ServiceActionError::UnknownService { name: service.to_string(), available }.into());
+    }
+
+    match endpoint {
+        Some(name) => candidates
+            .iter()
+            .copied()
+            .find(|e| e.endpoint == name)
+            .cloned()
+            .ok_or_else(|| {
+                let mut available: Vec<String> = candidates.iter().map(|e| e.endpoint.clone()).collect();
+                available.sort();
+                available.dedup();
+                EndpointError::UnknownEndpoint {
+                    service: service.to_string(),
+                    endpoint: name.to_string(),
+                    available,
+                }.into()
+            }),
+        None => Ok(candidates[0].clone()),
+    }
+}
+
+/// `.env` lines in the form Aspire injects into referencing services,
+/// `services__<service>__<endpoint>__<replica>=<url>`
+pub fn render_env_file(endpoints: &[ResolvedEndpoint]) -> String {
+    let mut replicas: BTreeMap<(&str, &str), Vec<&str>> = BTreeMap::new();
+    for endpoint in endpoints {
+        replicas.entry((endpoint.service.as_str(), endpoint.endpoint.as_str())).or_default().push(&endpoint.url);
+    }
+
+    let mut env = String::from("# Service endpoints from the Aspire dashboard; regenerate after restarts\n");
+    for ((service, endpoint), urls) in replicas {
+        for (index, url) in urls.iter().enumerate() {
+            env.push_str(&format!("services__{}__{}__{}={}\n", service, endpoint, index, url));
+        }
+    }
+    env
+}
+
+/// Resolves service endpoints from live discovery for external processes
+///
+/// Discovered endpoints are cached briefly. When the cached address of an
+/// endpoint refuses connections, the service has likely restarted on a new
+/// port, so the endpoint is looked up again before it is returned.
+pub struct EndpointResolver {
+    service_discovery: Arc<AspireServiceDiscovery>,
+    cache: RwLock<Option<(Instant, Vec<ResolvedEndpoint>)>>,
+}
+
+impl EndpointResolver {
+    pub fn new(service_discovery: Arc<AspireServiceDiscovery>) -> Self {
+        Self {
+            service_discovery,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Resolve an endpoint, looking it up again if the cached address is refused
+    ///
+    /// With `refresh` the cache is skipped, e.g. because the caller was
+    /// refused at the address it was given earlier.
+    pub async fn resolve(&self, service: &str, endpoint: Option<&str>, refresh: bool) -> Result<EndpointResolution> {
+        let (mut endpoints, mut cached) = self.endpoints(refresh).await?;
+        // The service may have started since the cache was filled
+        if cached && resolve_endpoint(&endpoints, service, endpoint).is_err() {
+            endpoints = self.endpoints(true).await?.0;
+            cached = false;
+        }
+        let resolved = resolve_endpoint(&endpoints, service, endpoint)?;
+
+        match connect(&resolved).await {
+            Reachability::Refused if cached => {
+                debug!("{} refused connections, resolving {} again", resolved.url, service);
+                let (endpoints, _) = self.endpoints(true).await?;
+                let resolved = resolve_endpoint(&endpoints, service, endpoint)?;
+                let reachable = connect(&resolved).await == Reachability::Reachable;
+                Ok(EndpointResolution { endpoint: resolved, refreshed: true, reachable })
+            }
+            reachability => Ok(EndpointResolution {
+                endpoint: resolved,
+                refreshed: false,
+                reachable: reachability == Reachability::Reachable,
+            }),
+        }
+    }
+
+    /// Write every discovered endpoint to `path` as a `.env` file
+    pub async fn write_env_file(&self, path: &Path, refresh: bool) -> Result<Vec<ResolvedEndpoint>> {
+        let (endpoints, _) = self.endpoints(refresh).await?;
+
+        // Write next to the target and rename, so readers never see a partial file
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        tokio::fs::write(&temporary, render_env_file(&endpoints)).await?;
+        tokio::fs::rename(&temporary, path).await?;
+
+        info!("Wrote {} endpoints to {}", endpoints.len(), path.display());
+        Ok(endpoints)
+    }
+
+    /// Forget discovered endpoints
+    pub fn invalidate(&self) {
+        *self.cache.write().expect("endpoint cache lock poisoned") = None;
+    }
+
+    /// Discovered endpoints, and whether they came from the cache
+    async fn endpoints(&self, refresh: bool) -> Result<(Vec<ResolvedEndpoint>, bool)> {
+        if !refresh {
+            let cache = self.cache.read().expect("endpoint cache lock poisoned");
+            if let Some((fetched, endpoints)) = cache.as_ref() {
+                if fetched.elapsed() < ENDPOINT_CACHE_TTL {
+                    return Ok((endpoints.clone(), true));
+                }
+            }
+        }
+
+        let endpoints = endpoints(&self.service_discovery.get_resources().await?);
+        *self.cache.write().expect("endpoint cache lock poisoned") = Some((Instant::now(), endpoints.clone()));
+        Ok((endpoints, false))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Reachability {
+    Reachable,
+    Refused,
+    /// Timed out or failed otherwise; says nothing about a stale address
+    Unknown,
+}
+
+async fn connect(endpoint: &ResolvedEndpoint) -> Reachability {
+    let address = format!("{}:{}", endpoint.host, endpoint.port);
+    match tokio::time::timeout(ENDPOINT_CONNECT_TIMEOUT, tokio::net::TcpStream::connect(&address)).await {
+        Ok(Ok(_)) => Reachability::Reachable,
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::ConnectionRefused => Reachability::Refused,
+        _ => Reachability::Unknown,
+    }
+}
 
 pub struct NetworkManager {
     config: NetworkConfig,
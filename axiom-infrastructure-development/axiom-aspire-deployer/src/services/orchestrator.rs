@@ -1,12 +1,52 @@
 use anyhow::Result;
 use reqwest::Client;
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use futures::future::join_all;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 use sysinfo::{System, Pid};
+use tokio::sync::broadcast;
 use tracing::{debug, info, warn, error};
 
+use crate::clients::aspire::DashboardStatusError;
 use crate::mcp::protocol::{EndpointCallRequest, EndpointCallResponse};
+use crate::services::discovery::{AspireServiceDiscovery, ResourceInfo};
+use crate::services::health::HealthMonitor;
+use crate::services::history::{HistoryEntry, HistoryOutcome};
+use crate::services::manifest::{self, AppHostManifest, EnvironmentDiff, ManifestError};
+use crate::services::ports::{self, PortConflictError, PortRequirement};
+use crate::services::probes::HealthState;
+use crate::services::secrets::SecretMasker;
+use crate::services::startup::{DependencyGraph, StartupPlan};
+use crate::services::topology::ServiceMap;
+
+/// Dashboard states that count as stopped for `stop_service`
+const STOPPED_STATES: &[&str] = &["Exited", "Finished", "Stopped", "NotStarted"];
+
+/// Environment variable prefix Aspire uses to inject connection strings
+const CONNECTION_STRING_PREFIX: &str = "ConnectionStrings__";
+
+/// Resource properties holding a resource's own connection string
+const CONNECTION_STRING_PROPERTIES: &[&str] = &["connectionString", "resource.connectionString"];
+
+/// Delay between dashboard polls for services without an HTTP endpoint
+const STATE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Resource types Aspire can run several replicas of
+const REPLICABLE_TYPES: &[&str] = &["project"];
+
+/// History target of actions on the AppHost as a whole
+pub const APPHOST_TARGET: &str = "apphost";
+
+/// Actions buffered per subscriber before it starts lagging
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Endpoint hosts whose ports are bound on this machine
+const LOCAL_HOSTS: &[&str] = &["localhost", "127.0.0.1", "0.0.0.0", "::1"];
 
 #[derive(Debug)]
 pub struct AspireProcess {
@@ -23,14 +63,184 @@ pub struct AspireStatus {
     pub processes: Vec<AspireProcess>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServiceAction {
+    /// Resource command name in the dashboard API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+        }
+    }
+    
+    fn expects_running(&self) -> bool {
+        !matches!(self, ServiceAction::Stop)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ServiceActionError {
+    #[error("Unknown service '{name}'. Available services: {}", .available.join(", "))]
+    UnknownService { name: String, available: Vec<String> },
+    #[error("Timed out after {elapsed_ms}ms waiting for '{service}' to {action}")]
+    Timeout { service: String, action: &'static str, elapsed_ms: u64 },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceEndpoint {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceActionResult {
+    pub service: String,
+    pub action: &'static str,
+    pub state: String,
+    /// Health probe result, `None` for services without an HTTP endpoint
+    pub healthy: Option<bool>,
+    pub endpoints: Vec<ServiceEndpoint>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScaleError {
+    #[error("'{service}' is a {resource_type} resource; only project resources can be scaled")]
+    Unsupported { service: String, resource_type: String },
+    #[error("Scaling '{service}' to 0 replicas stops it; set confirm_stop to do so")]
+    ConfirmationRequired { service: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplicaStatus {
+    pub name: String,
+    pub state: String,
+    /// Health probe result, `None` for replicas without an HTTP endpoint
+    pub healthy: Option<bool>,
+    pub endpoints: Vec<ServiceEndpoint>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScaleResult {
+    pub service: String,
+    pub previous_replicas: usize,
+    pub replicas: usize,
+    /// Running replicas, or the stopped ones after scaling to zero
+    pub instances: Vec<ReplicaStatus>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceActionFailure {
+    pub service: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderedStartResult {
+    pub plan: StartupPlan,
+    pub started: Vec<ServiceActionResult>,
+    pub failed: Vec<ServiceActionFailure>,
+    /// Services not attempted because an earlier stage failed
+    pub skipped: Vec<String>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderedStopResult {
+    /// Stop order: dependents before the services they depend on
+    pub stages: Vec<Vec<String>>,
+    pub stopped: Vec<ServiceActionResult>,
+    pub failed: Vec<ServiceActionFailure>,
+    /// Services that were already stopped or are not known to the dashboard
+    pub not_running: Vec<String>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ServiceConfigError {
+    #[error("Unmasked configuration is disabled; set secrets.allow_unmask to allow it")]
+    UnmaskForbidden,
+}
+
+/// Resolved configuration of one resource
+#[derive(Debug, Serialize)]
+pub struct ServiceConfig {
+    pub service: String,
+    pub environment: BTreeMap<String, String>,
+    /// Aspire-injected `ConnectionStrings__<name>` variables, plus the
+    /// resource's own connection string under its name
+    pub connection_strings: BTreeMap<String, String>,
+    /// Keys of `environment` whose values were masked
+    pub masked: Vec<String>,
+    pub unmasked: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct WaitForHealthyOptions {
+    /// Resource or display names; every resource when `None`
+    pub services: Option<Vec<String>>,
+    pub timeout: Duration,
+    /// Consecutive passing checks before a service counts as healthy
+    pub required_consecutive: u32,
+}
+
+impl Default for WaitForHealthyOptions {
+    fn default() -> Self {
+        Self {
+            services: None,
+            timeout: Duration::from_secs(120),
+            required_consecutive: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceWaitStatus {
+    pub service: String,
+    pub healthy: bool,
+    pub consecutive_healthy: u32,
+    pub checks: u32,
+    /// Error from the most recent failed check
+    pub last_error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WaitForHealthyResult {
+    /// Every service passed the required consecutive checks in time
+    pub all_healthy: bool,
+    pub healthy: Vec<ServiceWaitStatus>,
+    pub timed_out: Vec<ServiceWaitStatus>,
+    pub elapsed_ms: u64,
+}
+
 pub struct AspireOrchestrator {
     client: Client,
     dashboard_url: String,
     system: System,
+    service_discovery: Arc<AspireServiceDiscovery>,
+    health_monitor: Arc<HealthMonitor>,
+    service_action_timeout: Duration,
+    manifest_path: Option<PathBuf>,
+    secret_masker: RwLock<SecretMasker>,
+    allow_unmask: AtomicBool,
+    events: broadcast::Sender<HistoryEntry>,
 }
 
 impl AspireOrchestrator {
-    pub async fn new(dashboard_url: &str) -> Result<Self> {
+    pub async fn new(
+        dashboard_url: &str,
+        service_discovery: Arc<AspireServiceDiscovery>,
+        health_monitor: Arc<HealthMonitor>,
+    ) -> Result<Self> {
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
             .danger_accept_invalid_certs(true)
@@ -42,10 +252,639 @@ impl AspireOrchestrator {
             client,
             dashboard_url: dashboard_url.to_string(),
             system,
+            service_discovery,
+            health_monitor,
+            service_action_timeout: Duration::from_secs(60),
+            manifest_path: None,
+            secret_masker: RwLock::new(SecretMasker::default()),
+            allow_unmask: AtomicBool::new(false),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        })
+    }
+    
+    /// Receive an entry for every action performed from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<HistoryEntry> {
+        self.events.subscribe()
+    }
+    
+    /// Publish the outcome of an action that started at `start`
+    fn emit<T>(&self, action: &str, target: &str, start: Instant, result: &Result<T>, detail: Option<String>) {
+        let outcome = if result.is_ok() { HistoryOutcome::Success } else { HistoryOutcome::Failure };
+        let mut entry = HistoryEntry::new(action, target, outcome).with_duration(start.elapsed());
+        entry.detail = match result {
+            Err(e) => Some(e.to_string()),
+            Ok(_) => detail,
+        };
+        // No subscribers just means nothing is recording
+        let _ = self.events.send(entry);
+    }
+    
+    /// Read the startup order from an AppHost manifest instead of the dashboard
+    pub fn with_manifest_path(mut self, manifest_path: Option<PathBuf>) -> Self {
+        self.manifest_path = manifest_path;
+        self
+    }
+    
+    /// Masking applied by `get_service_config`, and whether callers may opt out of it
+    pub fn with_secret_masking(self, secret_masker: SecretMasker, allow_unmask: bool) -> Self {
+        self.update_secret_masking(secret_masker, allow_unmask);
+        self
+    }
+    
+    /// Replace the masking of a running orchestrator
+    pub fn update_secret_masking(&self, secret_masker: SecretMasker, allow_unmask: bool) {
+        *self.secret_masker.write().expect("secret masker lock poisoned") = secret_masker;
+        self.allow_unmask.store(allow_unmask, Ordering::Relaxed);
+    }
+    
+    /// Default wait for `start_service`/`stop_service`/`restart_service`
+    pub fn with_service_action_timeout(mut self, timeout: Duration) -> Self {
+        self.service_action_timeout = timeout;
+        self
+    }
+    
+    pub async fn start_service(&self, service: &str, timeout: Option<Duration>) -> Result<ServiceActionResult> {
+        self.change_service_state(service, ServiceAction::Start, timeout).await
+    }
+    
+    pub async fn stop_service(&self, service: &str, timeout: Option<Duration>) -> Result<ServiceActionResult> {
+        self.change_service_state(service, ServiceAction::Stop, timeout).await
+    }
+    
+    pub async fn restart_single_service(&self, service: &str, timeout: Option<Duration>) -> Result<ServiceActionResult> {
+        self.change_service_state(service, ServiceAction::Restart, timeout).await
+    }
+    
+    /// Compute the dependency-ordered startup stages
+    pub async fn get_startup_plan(&self) -> Result<StartupPlan> {
+        let graph = match &self.manifest_path {
+            Some(path) => {
+                debug!("Reading startup dependencies from {}", path.display());
+                let manifest: Value = serde_json::from_str(&tokio::fs::read_to_string(path).await?)?;
+                DependencyGraph::from_manifest(&manifest)
+            }
+            None => DependencyGraph::from_resources(&self.service_discovery.get_resources().await?),
+        };
+        
+        Ok(graph.startup_plan()?)
+    }
+    
+    /// Environment variables and connection strings the dashboard reports for
+    /// a resource, with secrets masked unless `unmask` is set and allowed
+    pub async fn get_service_config(&self, service: &str, unmask: bool) -> Result<ServiceConfig> {
+        if unmask && !self.allow_unmask.load(Ordering::Relaxed) {
+            return Err(ServiceConfigError::UnmaskForbidden.into());
+        }
+        
+        let secret_masker = self.secret_masker.read().expect("secret masker lock poisoned").clone();
+        let resource = self.resolve_service(service).await?;
+        if unmask {
+            warn!("Returning unmasked configuration of {}", resource.name);
+        }
+        let mask = |key: &str, value: &str| {
+            if unmask {
+                (value.to_string(), false)
+            } else {
+                secret_masker.mask_entry(key, value)
+            }
+        };
+        
+        let mut config = ServiceConfig {
+            service: resource.name.clone(),
+            environment: BTreeMap::new(),
+            connection_strings: BTreeMap::new(),
+            masked: Vec::new(),
+            unmasked: unmask,
+        };
+        
+        for (key, value) in resource.environment.iter().flatten() {
+            let (value, masked) = mask(key, value);
+            if masked {
+                config.masked.push(key.clone());
+            }
+            if let Some(name) = key.strip_prefix(CONNECTION_STRING_PREFIX) {
+                config.connection_strings.insert(name.to_string(), value.clone());
+            }
+            config.environment.insert(key.clone(), value);
+        }
+        config.masked.sort();
+        
+        let own_connection_string = CONNECTION_STRING_PROPERTIES
+            .iter()
+            .find_map(|property| resource.properties.as_ref()?.get(*property)?.as_str());
+        if let Some(connection_string) = own_connection_string {
+            let (value, _) = mask(CONNECTION_STRING_PREFIX, connection_string);
+            config.connection_strings.insert(resource.name.clone(), value);
+        }
+        
+        Ok(config)
+    }
+    
+    /// Compare the AppHost manifest with the running resources
+    ///
+    /// `manifest_path` overrides the configured `aspire.manifest_path`.
+    pub async fn diff_environment(&self, manifest_path: Option<&Path>) -> Result<EnvironmentDiff> {
+        let path = manifest_path
+            .or(self.manifest_path.as_deref())
+            .ok_or(ManifestError::NotConfigured)?;
+        debug!("Comparing {} with the running environment", path.display());
+        
+        let manifest = AppHostManifest::load(path).await?;
+        let resources = self.service_discovery.get_resources().await?;
+        Ok(manifest::diff_environment(&manifest, &resources))
+    }
+    
+    /// Resources, their health and the references between them
+    ///
+    /// Uses the manifest at `manifest_path`, or the configured one, for
+    /// resource types and reference kinds; without one, only the dashboard's
+    /// relationships are mapped. With a manifest, the map is built even when
+    /// the dashboard is unreachable.
+    pub async fn get_service_map(&self, manifest_path: Option<&Path>) -> Result<ServiceMap> {
+        let manifest: Option<Value> = match manifest_path.or(self.manifest_path.as_deref()) {
+            Some(path) => {
+                debug!("Reading references from {}", path.display());
+                Some(serde_json::from_str(&tokio::fs::read_to_string(path).await?)?)
+            }
+            None => None,
+        };
+        
+        let resources = match self.service_discovery.get_resources().await {
+            Ok(resources) => resources,
+            Err(e) if manifest.is_some() => {
+                warn!("Mapping services from the manifest only: {}", e);
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+        let health: HashMap<String, HealthState> = self.health_monitor
+            .health_history(None, Some(0))
+            .into_iter()
+            .map(|history| (history.service, history.state))
+            .collect();
+        
+        Ok(ServiceMap::build(manifest.as_ref(), &resources, &health))
+    }
+    
+    /// Start every service stage by stage, in dependency order
+    ///
+    /// Services in a stage start in parallel and each must pass its readiness
+    /// check before the next stage begins. A failure stops later stages.
+    pub async fn start_services_in_order(&self, timeout: Option<Duration>) -> Result<OrderedStartResult> {
+        let start = Instant::now();
+        let plan = self.get_startup_plan().await?;
+        
+        let resources = self.service_discovery.get_resources().await?;
+        let planned: Vec<&ResourceInfo> = resources
+            .iter()
+            .filter(|r| plan.dependencies.contains_key(&r.name))
+            .collect();
+        self.check_service_ports(&planned).await?;
+        
+        info!("Starting services in order: {:?}", plan.stages);
+        
+        let mut started = Vec::new();
+        let mut failed = Vec::new();
+        let mut skipped = Vec::new();
+        
+        for stage in &plan.stages {
+            if !failed.is_empty() {
+                skipped.extend(stage.iter().cloned());
+                continue;
+            }
+            
+            let results = join_all(stage.iter().map(|service| self.start_service(service, timeout))).await;
+            for (service, result) in stage.iter().zip(results) {
+                match result {
+                    Ok(result) => started.push(result),
+                    Err(e) => {
+                        warn!("Service {} did not become ready: {}", service, e);
+                        failed.push(ServiceActionFailure {
+                            service: service.clone(),
+                            error: e.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        
+        Ok(OrderedStartResult {
+            plan,
+            started,
+            failed,
+            skipped,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+    
+    /// Stop every running service stage by stage, in reverse dependency order
+    ///
+    /// Uses the startup plan backwards, so a service stops before the services
+    /// it depends on. Unlike startup, a failure does not hold back later
+    /// stages: everything that can be stopped is.
+    pub async fn stop_services_in_order(&self, timeout: Option<Duration>) -> Result<OrderedStopResult> {
+        let start = Instant::now();
+        let stages = self.get_startup_plan().await?.teardown_stages();
+        let resources = self.service_discovery.get_resources().await?;
+        let is_running = |service: &str| resources.iter().any(|r| r.name == service && !Self::is_stopped(r));
+        
+        info!("Stopping services in order: {:?}", stages);
+        
+        let mut stopped = Vec::new();
+        let mut failed = Vec::new();
+        let mut not_running = Vec::new();
+        
+        for stage in &stages {
+            let (running, idle): (Vec<&String>, Vec<&String>) = stage.iter().partition(|service| is_running(service));
+            not_running.extend(idle.into_iter().cloned());
+            
+            let results = join_all(running.iter().map(|service| self.stop_service(service, timeout))).await;
+            for (service, result) in running.into_iter().zip(results) {
+                match result {
+                    Ok(result) => stopped.push(result),
+                    Err(e) => {
+                        warn!("Service {} did not stop: {}", service, e);
+                        failed.push(ServiceActionFailure {
+                            service: service.clone(),
+                            error: e.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        
+        Ok(OrderedStopResult {
+            stages,
+            stopped,
+            failed,
+            not_running,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+    
+    /// Check services until each passes `required_consecutive` checks in a row
+    /// or the timeout elapses
+    ///
+    /// Services with an HTTP endpoint are checked with their health probe, the
+    /// rest by the dashboard reporting them as running. `on_progress` is called
+    /// after every check.
+    pub async fn wait_for_healthy<F>(&self, options: &WaitForHealthyOptions, mut on_progress: F) -> Result<WaitForHealthyResult>
+    where
+        F: FnMut(&ServiceWaitStatus),
+    {
+        let start = Instant::now();
+        let deadline = start + options.timeout;
+        let required = options.required_consecutive.max(1);
+        
+        let resources = self.service_discovery.get_resources().await?;
+        let available = || resources.iter().map(|r| r.name.clone()).collect::<Vec<_>>();
+        let targets: Vec<&ResourceInfo> = match &options.services {
+            Some(services) => services
+                .iter()
+                .map(|service| {
+                    resources.iter().find(|r| r.matches(service)).ok_or_else(|| ServiceActionError::UnknownService {
+                        name: service.clone(),
+                        available: available(),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            None => resources.iter().collect(),
+        };
+        
+        info!("Waiting up to {:?} for {} service(s) to be healthy", options.timeout, targets.len());
+        
+        let mut statuses: Vec<ServiceWaitStatus> = targets
+            .iter()
+            .map(|resource| ServiceWaitStatus {
+                service: resource.name.clone(),
+                healthy: false,
+                consecutive_healthy: 0,
+                checks: 0,
+                last_error: None,
+                elapsed_ms: 0,
+            })
+            .collect();
+        
+        loop {
+            let pending: Vec<usize> = (0..statuses.len()).filter(|i| !statuses[*i].healthy).collect();
+            if pending.is_empty() {
+                break;
+            }
+            
+            let checks = join_all(pending.iter().map(|i| self.check_ready(targets[*i]))).await;
+            for (i, check) in pending.into_iter().zip(checks) {
+                let status = &mut statuses[i];
+                status.checks += 1;
+                status.elapsed_ms = start.elapsed().as_millis() as u64;
+                match check {
+                    Ok(()) => {
+                        status.consecutive_healthy += 1;
+                        status.healthy = status.consecutive_healthy >= required;
+                    }
+                    Err(error) => {
+                        status.consecutive_healthy = 0;
+                        status.last_error = Some(error);
+                    }
+                }
+                on_progress(status);
+            }
+            
+            let now = Instant::now();
+            if now >= deadline || statuses.iter().all(|status| status.healthy) {
+                break;
+            }
+            tokio::time::sleep(STATE_POLL_INTERVAL.min(deadline - now)).await;
+        }
+        
+        let (healthy, timed_out): (Vec<_>, Vec<_>) = statuses.into_iter().partition(|status| status.healthy);
+        for status in &timed_out {
+            warn!("{} did not become healthy: {}", status.service,
+                status.last_error.as_deref().unwrap_or("not enough consecutive healthy checks"));
+        }
+        
+        Ok(WaitForHealthyResult {
+            all_healthy: timed_out.is_empty(),
+            healthy,
+            timed_out,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+    
+    /// One readiness check, with the reason when it fails
+    async fn check_ready(&self, resource: &ResourceInfo) -> Result<(), String> {
+        if let Some(url) = Self::primary_url(resource) {
+            let (_, outcome) = self.health_monitor.check_service(&resource.name, url).await;
+            return match outcome.reason() {
+                None if outcome.is_ready() => Ok(()),
+                reason => Err(reason.unwrap_or_else(|| "probe failed".to_string())),
+            };
+        }
+        
+        let state = self.find_resource(&resource.name).await
+            .map_err(|e| e.to_string())?
+            .and_then(|resource| resource.state)
+            .unwrap_or_else(|| "unknown".to_string());
+        if state == "Running" {
+            Ok(())
+        } else {
+            Err(format!("Dashboard reports state {}", state))
+        }
+    }
+    
+    /// Send `action` for one resource and wait until it reaches the target state
+    ///
+    /// Services with an HTTP endpoint are confirmed through the health monitor,
+    /// the rest through the state the dashboard reports. A started service with
+    /// a readiness gate is only done once the gate passes.
+    pub async fn change_service_state(
+        &self,
+        service: &str,
+        action: ServiceAction,
+        timeout: Option<Duration>,
+    ) -> Result<ServiceActionResult> {
+        let start = Instant::now();
+        let result = self.apply_service_action(service, action, timeout).await;
+        let target = result.as_ref().map_or(service, |r| r.service.as_str());
+        self.emit(action.as_str(), target, start, &result, None);
+        result
+    }
+    
+    async fn apply_service_action(
+        &self,
+        service: &str,
+        action: ServiceAction,
+        timeout: Option<Duration>,
+    ) -> Result<ServiceActionResult> {
+        let timeout = timeout.unwrap_or(self.service_action_timeout);
+        let start = Instant::now();
+        
+        let resource = self.resolve_service(service).await?;
+        let name = resource.name.clone();
+        if action.expects_running() {
+            self.check_service_ports(&[&resource]).await?;
+        }
+        info!("Requesting {} for service: {}", action.as_str(), name);
+        
+        let action_url = format!("{}/api/v1/resources/{}/{}", self.dashboard_url, name, action.as_str());
+        let response = self.client.post(&action_url).send().await?;
+        if !response.status().is_success() {
+            return Err(DashboardStatusError::new(
+                format!("Dashboard rejected the {} request on '{}'", action.as_str(), name),
+                response.status(),
+            ).into());
+        }
+        let gated = action.expects_running() && self.health_monitor.has_readiness_gate(&name);
+        if gated {
+            self.health_monitor.reset_readiness(&name);
+        }
+        
+        let timed_out = || ServiceActionError::Timeout {
+            service: name.clone(),
+            action: action.as_str(),
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        };
+        
+        let healthy = match Self::primary_url(&resource) {
+            Some(url) => {
+                let remaining = timeout.saturating_sub(start.elapsed());
+                let check = self.health_monitor
+                    .wait_for_health(url, &name, action.expects_running(), remaining)
+                    .await?
+                    .ok_or_else(timed_out)?;
+                if gated {
+                    let remaining = timeout.saturating_sub(start.elapsed());
+                    self.health_monitor
+                        .wait_until_ready(&name, url, remaining)
+                        .await
+                        .ok_or_else(timed_out)?;
+                }
+                Some(check.is_healthy)
+            }
+            None => {
+                self.wait_for_state(&name, action, start + timeout).await?
+                    .ok_or_else(timed_out)?;
+                None
+            }
+        };
+        
+        let resource = self.find_resource(&name).await?.unwrap_or(resource);
+        
+        Ok(ServiceActionResult {
+            service: name,
+            action: action.as_str(),
+            state: resource.state.clone().unwrap_or_else(|| "unknown".to_string()),
+            healthy,
+            endpoints: resource.urls
+                .unwrap_or_default()
+                .into_iter()
+                .map(|url| ServiceEndpoint { name: url.name, url: url.url })
+                .collect(),
+            elapsed_ms: start.elapsed().as_millis() as u64,
         })
     }
     
+    /// Run `replicas` instances of a project resource and wait until all of
+    /// them are running and healthy
+    ///
+    /// Zero replicas stops every instance, which needs `confirm_stop`.
+    pub async fn scale_service(
+        &self,
+        service: &str,
+        replicas: usize,
+        confirm_stop: bool,
+        timeout: Option<Duration>,
+    ) -> Result<ScaleResult> {
+        let start = Instant::now();
+        let result = self.apply_scale(service, replicas, confirm_stop, timeout).await;
+        let target = result.as_ref().map_or(service, |r| r.service.as_str());
+        self.emit("scale", target, start, &result, Some(format!("replicas: {}", replicas)));
+        result
+    }
+    
+    async fn apply_scale(
+        &self,
+        service: &str,
+        replicas: usize,
+        confirm_stop: bool,
+        timeout: Option<Duration>,
+    ) -> Result<ScaleResult> {
+        let timeout = timeout.unwrap_or(self.service_action_timeout);
+        let start = Instant::now();
+        
+        let resources = self.service_discovery.get_resources().await?;
+        let resource = resources
+            .iter()
+            .find(|r| r.matches(service))
+            .ok_or_else(|| ServiceActionError::UnknownService {
+                name: service.to_string(),
+                available: resources.iter().map(|r| r.name.clone()).collect(),
+            })?;
+        // Replicas are separate resources sharing the display name
+        let group = resource.displayName.clone();
+        if !REPLICABLE_TYPES.iter().any(|t| resource.resource_type.eq_ignore_ascii_case(t)) {
+            return Err(ScaleError::Unsupported {
+                service: group,
+                resource_type: resource.resource_type.clone(),
+            }.into());
+        }
+        let current: Vec<&ResourceInfo> = resources
+            .iter()
+            .filter(|r| r.displayName == group && !Self::is_stopped(r))
+            .collect();
+        let previous_replicas = current.len();
+        
+        if replicas == 0 {
+            if !confirm_stop {
+                return Err(ScaleError::ConfirmationRequired { service: group }.into());
+            }
+            info!("Scaling {} to 0 replicas by stopping {} instance(s)", group, current.len());
+            
+            let mut instances = Vec::new();
+            for replica in current {
+                let remaining = timeout.saturating_sub(start.elapsed());
+                let stopped = self.change_service_state(&replica.name, ServiceAction::Stop, Some(remaining)).await?;
+                instances.push(ReplicaStatus {
+                    name: stopped.service,
+                    state: stopped.state,
+                    healthy: stopped.healthy,
+                    endpoints: stopped.endpoints,
+                });
+            }
+            return Ok(ScaleResult {
+                service: group,
+                previous_replicas,
+                replicas: 0,
+                instances,
+                elapsed_ms: start.elapsed().as_millis() as u64,
+            });
+        }
+        
+        info!("Scaling {} from {} to {} replicas", group, previous_replicas, replicas);
+        let scale_url = format!("{}/api/v1/resources/{}/scale", self.dashboard_url, group);
+        let response = self.client.post(&scale_url).json(&json!({ "replicas": replicas })).send().await?;
+        if !response.status().is_success() {
+            return Err(DashboardStatusError::new(
+                format!("Dashboard rejected the scale request on '{}'", group),
+                response.status(),
+            ).into());
+        }
+        
+        let timed_out = || ServiceActionError::Timeout {
+            service: group.clone(),
+            action: "scale",
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        };
+        
+        // Wait for the dashboard to report exactly `replicas` running instances
+        let deadline = start + timeout;
+        let running = loop {
+            let running: Vec<ResourceInfo> = self.service_discovery
+                .get_resources()
+                .await?
+                .into_iter()
+                .filter(|r| r.displayName == group && !Self::is_stopped(r))
+                .collect();
+            if running.len() == replicas && running.iter().all(|r| r.state.as_deref() == Some("Running")) {
+                break running;
+            }
+            
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(timed_out().into());
+            }
+            tokio::time::sleep(STATE_POLL_INTERVAL.min(deadline - now)).await;
+        };
+        
+        let checks = join_all(running.iter().map(|replica| async move {
+            match Self::primary_url(replica) {
+                Some(url) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    self.health_monitor
+                        .wait_for_health(url, &replica.name, true, remaining)
+                        .await
+                        .map(|check| check.map(|check| Some(check.is_healthy)))
+                }
+                None => Ok(Some(None)),
+            }
+        })).await;
+        
+        let mut instances = Vec::new();
+        for (replica, check) in running.into_iter().zip(checks) {
+            let healthy = check?.ok_or_else(timed_out)?;
+            instances.push(ReplicaStatus {
+                name: replica.name,
+                state: replica.state.unwrap_or_else(|| "unknown".to_string()),
+                healthy,
+                endpoints: replica.urls
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|url| ServiceEndpoint { name: url.name, url: url.url })
+                    .collect(),
+            });
+        }
+        
+        Ok(ScaleResult {
+            service: group,
+            previous_replicas,
+            replicas,
+            instances,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+    
+    fn is_stopped(resource: &ResourceInfo) -> bool {
+        STOPPED_STATES.contains(&resource.state.as_deref().unwrap_or_default())
+    }
+    
     pub async fn start_aspire(&self, profile: &str, watch: bool) -> Result<()> {
+        let start = Instant::now();
+        let result = self.launch_aspire(profile, watch).await;
+        let detail = format!("profile: {}, watch: {}", profile, watch);
+        self.emit("start_apphost", APPHOST_TARGET, start, &result, Some(detail));
+        result
+    }
+    
+    async fn launch_aspire(&self, profile: &str, watch: bool) -> Result<()> {
         info!("Starting Aspire with profile: {}, watch: {}", profile, watch);
         
         // Check if Aspire is already running
@@ -74,6 +913,13 @@ impl AspireOrchestrator {
     }
     
     pub async fn stop_aspire(&self) -> Result<()> {
+        let start = Instant::now();
+        let result = self.shut_down_aspire().await;
+        self.emit("stop_apphost", APPHOST_TARGET, start, &result, None);
+        result
+    }
+    
+    async fn shut_down_aspire(&self) -> Result<()> {
         info!("Stopping Aspire AppHost");
         
         // Try to stop via dashboard API first
@@ -90,6 +936,13 @@ impl AspireOrchestrator {
     }
     
     pub async fn restart_service(&self, service: &str) -> Result<()> {
+        let start = Instant::now();
+        let result = self.request_restart(service).await;
+        self.emit(ServiceAction::Restart.as_str(), service, start, &result, None);
+        result
+    }
+    
+    async fn request_restart(&self, service: &str) -> Result<()> {
         info!("Restarting service: {}", service);
         
         if service == "all" {
@@ -325,4 +1178,95 @@ impl AspireOrchestrator {
             Err(e) => Err(e.into()),
         }
     }
-}
\ No newline at end of file
+    
+    /// Look up a resource by name or display name
+    async fn resolve_service(&self, service: &str) -> Result<ResourceInfo> {
+        let resources = self.service_discovery.get_resources().await?;
+        let available: Vec<String> = resources.iter().map(|r| r.name.clone()).collect();
+        
+        resources
+            .into_iter()
+            .find(|r| r.matches(service))
+            .ok_or_else(|| ServiceActionError::UnknownService {
+                name: service.to_string(),
+                available,
+            }.into())
+    }
+    
+    async fn find_resource(&self, name: &str) -> Result<Option<ResourceInfo>> {
+        let resources = self.service_discovery.get_resources().await?;
+        Ok(resources.into_iter().find(|r| r.name == name))
+    }
+    
+    /// Poll the dashboard until `name` reports the state `action` targets
+    async fn wait_for_state(&self, name: &str, action: ServiceAction, deadline: Instant) -> Result<Option<ResourceInfo>> {
+        loop {
+            if let Some(resource) = self.find_resource(name).await? {
+                let state = resource.state.as_deref().unwrap_or_default();
+                let reached = if action.expects_running() {
+                    state == "Running"
+                } else {
+                    STOPPED_STATES.contains(&state)
+                };
+                if reached {
+                    return Ok(Some(resource));
+                }
+            }
+            
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(STATE_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+    
+    /// Fail with the owning processes if a stopped service's local ports are taken
+    ///
+    /// Aspire's DCP proxies keep endpoint ports bound while a resource is
+    /// stopped, so ports held by DCP are not conflicts.
+    async fn check_service_ports(&self, resources: &[&ResourceInfo]) -> Result<()> {
+        let requirements: Vec<PortRequirement> = resources
+            .iter()
+            .filter(|r| !matches!(r.state.as_deref(), Some("Running") | Some("Starting")))
+            .flat_map(|r| Self::local_ports(r))
+            .collect();
+        
+        let mut conflicts = ports::find_conflicts(&requirements).await;
+        conflicts.retain(|conflict| {
+            !conflict.owner
+                .as_ref()
+                .and_then(|owner| owner.process_name.as_deref())
+                .is_some_and(|name| name.to_lowercase().starts_with("dcp"))
+        });
+        
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(PortConflictError { conflicts }.into())
+        }
+    }
+    
+    /// Ports of `resource`'s endpoints that are bound on this machine
+    pub(crate) fn local_ports(resource: &ResourceInfo) -> Vec<PortRequirement> {
+        resource.urls
+            .iter()
+            .flatten()
+            .filter_map(|url| {
+                let parsed = reqwest::Url::parse(&url.url).ok()?;
+                let host = parsed.host_str()?.trim_start_matches('[').trim_end_matches(']');
+                if !LOCAL_HOSTS.contains(&host) {
+                    return None;
+                }
+                Some(PortRequirement::new(&resource.name, host, parsed.port_or_known_default()?))
+            })
+            .collect()
+    }
+    
+    fn primary_url(resource: &ResourceInfo) -> Option<&str> {
+        resource.urls
+            .as_ref()
+            .and_then(|urls| urls.first())
+            .map(|url| url.url.as_str())
+    }
+}
@@ -0,0 +1,262 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+use sysinfo::{Pid, System};
+use tracing::{debug, info};
+
+/// How far past a taken port to look for a free alternative
+const SUGGESTION_RANGE: u16 = 100;
+
+/// A port some component needs to bind
+#[derive(Debug, Clone)]
+pub struct PortRequirement {
+    pub service: String,
+    pub host: String,
+    pub port: u16,
+}
+
+impl PortRequirement {
+    pub fn new(service: &str, host: &str, port: u16) -> Self {
+        Self {
+            service: service.to_string(),
+            host: host.to_string(),
+            port,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortOwner {
+    pub pid: u32,
+    pub process_name: Option<String>,
+}
+
+impl fmt::Display for PortOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.process_name {
+            Some(name) => write!(f, "{} (PID {})", name, self.pid),
+            None => write!(f, "PID {}", self.pid),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortConflict {
+    pub service: String,
+    pub host: String,
+    pub port: u16,
+    /// Process listening on the port, when the platform lets us find it
+    pub owner: Option<PortOwner>,
+    /// Next free port after `port`
+    pub suggested_port: Option<u16>,
+}
+
+impl fmt::Display for PortConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "port {} on {} for '{}' is in use", self.port, self.host, self.service)?;
+        if let Some(owner) = &self.owner {
+            write!(f, " by {}", owner)?;
+        }
+        if let Some(port) = self.suggested_port {
+            write!(f, " (port {} is free)", port)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Port conflict: {}", .conflicts.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("; "))]
+pub struct PortConflictError {
+    pub conflicts: Vec<PortConflict>,
+}
+
+/// Whether `port` can be bound on every address `host` resolves to
+pub fn is_port_free(host: &str, port: u16) -> bool {
+    let addrs: Vec<SocketAddr> = match (host, port).to_socket_addrs() {
+        Ok(addrs) => addrs.collect(),
+        Err(_) => return false,
+    };
+    !addrs.is_empty() && addrs.iter().all(|addr| TcpListener::bind(addr).is_ok())
+}
+
+/// First free port after `port`, skipping `exclude`
+pub fn find_free_port(host: &str, port: u16, exclude: &HashSet<u16>) -> Option<u16> {
+    (port.saturating_add(1)..=port.saturating_add(SUGGESTION_RANGE))
+        .filter(|candidate| !exclude.contains(candidate))
+        .find(|candidate| is_port_free(host, *candidate))
+}
+
+/// Check every requirement and describe the ones whose port is taken
+pub async fn find_conflicts(requirements: &[PortRequirement]) -> Vec<PortConflict> {
+    // Never suggest a port another requirement (or suggestion) already claims
+    let mut claimed: HashSet<u16> = requirements.iter().map(|r| r.port).collect();
+    let mut conflicts = Vec::new();
+
+    for requirement in requirements {
+        if requirement.port == 0 || is_port_free(&requirement.host, requirement.port) {
+            continue;
+        }
+
+        let owner = find_port_owner(requirement.port).await;
+        let suggested_port = find_free_port(&requirement.host, requirement.port, &claimed);
+        if let Some(port) = suggested_port {
+            claimed.insert(port);
+        }
+
+        debug!("Port {} for {} is in use (owner: {:?})", requirement.port, requirement.service, owner);
+        conflicts.push(PortConflict {
+            service: requirement.service.clone(),
+            host: requirement.host.clone(),
+            port: requirement.port,
+            owner,
+            suggested_port,
+        });
+    }
+
+    conflicts
+}
+
+pub async fn ensure_ports_free(requirements: &[PortRequirement]) -> Result<(), PortConflictError> {
+    let conflicts = find_conflicts(requirements).await;
+    if conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(PortConflictError { conflicts })
+    }
+}
+
+/// Port the MCP server should bind; with `auto_port` a taken port is
+/// replaced by the next free one instead of failing
+pub async fn resolve_server_port(host: &str, port: u16, auto_port: bool) -> Result<u16, PortConflictError> {
+    let conflicts = find_conflicts(&[PortRequirement::new("mcp-server", host, port)]).await;
+    let Some(conflict) = conflicts.first() else {
+        return Ok(port);
+    };
+
+    match conflict.suggested_port {
+        Some(free_port) if auto_port => {
+            info!("{}; using port {} instead", conflict, free_port);
+            Ok(free_port)
+        }
+        _ => Err(PortConflictError { conflicts }),
+    }
+}
+
+/// Find the process listening on a TCP port
+pub async fn find_port_owner(port: u16) -> Option<PortOwner> {
+    let pid = listening_pid(port).await?;
+
+    let mut system = System::new();
+    let process_name = system
+        .refresh_process(Pid::from_u32(pid))
+        .then(|| system.process(Pid::from_u32(pid)).map(|p| p.name().to_string()))
+        .flatten();
+
+    Some(PortOwner { pid, process_name })
+}
+
+#[cfg(target_os = "linux")]
+async fn listening_pid(port: u16) -> Option<u32> {
+    // Match the socket inode from /proc/net/tcp* against each process's fds
+    let inodes = linux::listening_inodes(port);
+    if inodes.is_empty() {
+        return None;
+    }
+    linux::pid_for_inodes(&inodes)
+}
+
+#[cfg(target_os = "macos")]
+async fn listening_pid(port: u16) -> Option<u32> {
+    let output = tokio::process::Command::new("lsof")
+        .arg("-nP")
+        .arg(format!("-iTCP:{}", port))
+        .args(["-sTCP:LISTEN", "-Fp"])
+        .output()
+        .await
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix('p').and_then(|pid| pid.parse().ok()))
+}
+
+#[cfg(target_os = "windows")]
+async fn listening_pid(port: u16) -> Option<u32> {
+    let output = tokio::process::Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .output()
+        .await
+        .ok()?;
+
+    // "  TCP    0.0.0.0:3001    0.0.0.0:0    LISTENING    1234"
+    String::from_utf8_lossy(&output.stdout).lines().find_map(|line| {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        match columns.as_slice() {
+            ["TCP", local, _, "LISTENING", pid]
+                if local.rsplit(':').next().and_then(|p| p.parse::<u16>().ok()) == Some(port) => pid.parse().ok(),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+async fn listening_pid(_port: u16) -> Option<u32> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::HashSet;
+
+    /// TCP state code for LISTEN in /proc/net/tcp
+    const TCP_LISTEN: &str = "0A";
+
+    pub fn listening_inodes(port: u16) -> HashSet<u64> {
+        let port_hex = format!("{:04X}", port);
+        let mut inodes = HashSet::new();
+
+        for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            let Ok(content) = std::fs::read_to_string(table) else {
+                continue;
+            };
+            // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+            for line in content.lines().skip(1) {
+                let columns: Vec<&str> = line.split_whitespace().collect();
+                if columns.len() < 10 || columns[3] != TCP_LISTEN {
+                    continue;
+                }
+                if columns[1].rsplit(':').next() == Some(port_hex.as_str()) {
+                    if let Ok(inode) = columns[9].parse::<u64>() {
+                        inodes.insert(inode);
+                    }
+                }
+            }
+        }
+
+        inodes
+    }
+
+    pub fn pid_for_inodes(inodes: &HashSet<u64>) -> Option<u32> {
+        let targets: HashSet<String> = inodes.iter().map(|inode| format!("socket:[{}]", inode)).collect();
+
+        for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+                continue;
+            };
+            // Other users' processes are unreadable without privileges
+            let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                if let Ok(link) = std::fs::read_link(fd.path()) {
+                    if targets.contains(link.to_string_lossy().as_ref()) {
+                        return Some(pid);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,283 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+use crate::clients::GrpcClient;
+use crate::config::settings::{GrpcConfig, ProbeConfig, ProbeKind};
+use crate::services::readiness::ReadinessResult;
+
+/// Transitions kept per service for `get_health_history`
+const MAX_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum HealthState {
+    /// Not yet healthy; failures are ignored during the startup grace period,
+    /// and a service whose readiness gate fails stays here
+    Starting,
+    Healthy,
+    /// Failing, but fewer consecutive failures than the threshold
+    Degraded,
+    Unhealthy,
+}
+
+impl HealthState {
+    /// Value used for `ServiceStatus::health`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthState::Starting => "starting",
+            HealthState::Healthy => "healthy",
+            HealthState::Degraded => "degraded",
+            HealthState::Unhealthy => "unhealthy",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthTransition {
+    pub from: HealthState,
+    pub to: HealthState,
+    pub at: DateTime<Utc>,
+    pub consecutive_failures: u32,
+    /// Probe error that caused the transition, if any
+    pub reason: Option<String>,
+}
+
+/// A transition published by the health monitor
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthEvent {
+    pub service: String,
+    pub transition: HealthTransition,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProbeOutcome {
+    pub success: bool,
+    pub error: Option<String>,
+    /// Readiness gate result, when the gate ran after a passing probe
+    pub readiness: Option<ReadinessResult>,
+}
+
+impl ProbeOutcome {
+    pub fn success() -> Self {
+        Self { success: true, error: None, readiness: None }
+    }
+
+    pub fn failure(error: impl Into<String>) -> Self {
+        Self { success: false, error: Some(error.into()), readiness: None }
+    }
+
+    pub fn with_readiness(mut self, readiness: ReadinessResult) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    /// The probe passed and so did the readiness gate, if it ran
+    pub fn is_ready(&self) -> bool {
+        self.success && self.readiness.as_ref().is_none_or(|readiness| readiness.ready)
+    }
+
+    /// The probe error, or the output of a failed readiness gate
+    pub fn reason(&self) -> Option<String> {
+        self.error.clone().or_else(|| {
+            self.readiness.as_ref().filter(|readiness| !readiness.ready).map(|readiness| readiness.output.clone())
+        })
+    }
+}
+
+/// Health state machine for one service
+#[derive(Debug, Clone)]
+pub struct ServiceHealthTracker {
+    config: ProbeConfig,
+    state: HealthState,
+    consecutive_failures: u32,
+    first_seen: DateTime<Utc>,
+    last_probe: Option<DateTime<Utc>>,
+    history: VecDeque<HealthTransition>,
+    /// Whether the readiness gate passed since the service last started
+    readiness_passed: bool,
+    last_readiness: Option<ReadinessResult>,
+}
+
+impl ServiceHealthTracker {
+    pub fn new(config: ProbeConfig, first_seen: DateTime<Utc>) -> Self {
+        Self {
+            config,
+            state: HealthState::Starting,
+            consecutive_failures: 0,
+            first_seen,
+            last_probe: None,
+            history: VecDeque::new(),
+            readiness_passed: false,
+            last_readiness: None,
+        }
+    }
+
+    pub fn config(&self) -> &ProbeConfig {
+        &self.config
+    }
+
+    /// Replace the probe settings, keeping the state and history
+    pub fn set_config(&mut self, config: ProbeConfig) {
+        self.config = config;
+    }
+
+    pub fn state(&self) -> HealthState {
+        self.state
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    pub fn history(&self) -> impl Iterator<Item = &HealthTransition> {
+        self.history.iter()
+    }
+
+    /// Latest readiness gate result
+    pub fn readiness(&self) -> Option<&ReadinessResult> {
+        self.last_readiness.as_ref()
+    }
+
+    /// Whether the next passing probe must be followed by the readiness gate
+    pub fn readiness_pending(&self) -> bool {
+        self.config.readiness.is_some() && !self.readiness_passed
+    }
+
+    /// Run the readiness gate again, e.g. after the service restarts
+    pub fn reset_readiness(&mut self) {
+        self.readiness_passed = false;
+    }
+
+    /// Whether the probe interval has passed since the last probe
+    pub fn is_due(&self, now: DateTime<Utc>, default_interval: Duration) -> bool {
+        let interval = self.config.interval_ms.map(Duration::from_millis).unwrap_or(default_interval);
+        match self.last_probe {
+            Some(last) => (now - last).to_std().map_or(true, |elapsed| elapsed >= interval),
+            None => true,
+        }
+    }
+
+    /// Apply a probe result, returning the transition it caused
+    pub fn record(&mut self, outcome: &ProbeOutcome, now: DateTime<Utc>) -> Option<HealthTransition> {
+        self.last_probe = Some(now);
+        if let Some(readiness) = &outcome.readiness {
+            self.readiness_passed = readiness.ready;
+            self.last_readiness = Some(readiness.clone());
+        }
+
+        let next = if outcome.success && !outcome.is_ready() {
+            // Reachable, but not ready to serve yet
+            self.consecutive_failures = 0;
+            HealthState::Starting
+        } else if outcome.success {
+            self.consecutive_failures = 0;
+            HealthState::Healthy
+        } else if self.state == HealthState::Starting && self.in_grace_period(now) {
+            HealthState::Starting
+        } else {
+            self.consecutive_failures += 1;
+            if self.consecutive_failures >= self.config.failure_threshold.max(1) {
+                HealthState::Unhealthy
+            } else if self.state == HealthState::Healthy {
+                HealthState::Degraded
+            } else {
+                self.state
+            }
+        };
+
+        if next == HealthState::Unhealthy {
+            self.readiness_passed = false;
+        }
+        if next == self.state {
+            return None;
+        }
+
+        let transition = HealthTransition {
+            from: self.state,
+            to: next,
+            at: now,
+            consecutive_failures: self.consecutive_failures,
+            reason: outcome.reason(),
+        };
+        self.state = next;
+        if self.history.len() == MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(transition.clone());
+        Some(transition)
+    }
+
+    fn in_grace_period(&self, now: DateTime<Utc>) -> bool {
+        (now - self.first_seen).to_std().map_or(true, |elapsed| elapsed < Duration::from_millis(self.config.startup_grace_ms))
+    }
+}
+
+/// Run the configured probe against a service's base URL
+pub async fn run_probe(
+    client: &Client,
+    grpc: &GrpcConfig,
+    service_name: &str,
+    service_url: &str,
+    config: &ProbeConfig,
+) -> ProbeOutcome {
+    let timeout = Duration::from_millis(config.timeout_ms);
+
+    let result = match config.kind {
+        ProbeKind::Http => probe_http(client, service_url, &config.path, timeout).await,
+        ProbeKind::Tcp => probe_tcp(service_url, timeout).await,
+        ProbeKind::Grpc => probe_grpc(grpc, service_name, service_url, config.grpc_service.as_deref(), timeout).await,
+    };
+
+    match result {
+        Ok(outcome) => outcome,
+        Err(e) => ProbeOutcome::failure(e.to_string()),
+    }
+}
+
+async fn probe_http(client: &Client, service_url: &str, path: &str, timeout: Duration) -> Result<ProbeOutcome> {
+    let url = format!("{}/{}", service_url.trim_end_matches('/'), path.trim_start_matches('/'));
+    let response = client.get(&url).timeout(timeout).send().await?;
+
+    if response.status().is_success() {
+        Ok(ProbeOutcome::success())
+    } else {
+        Ok(ProbeOutcome::failure(format!("HTTP {}", response.status().as_u16())))
+    }
+}
+
+async fn probe_tcp(service_url: &str, timeout: Duration) -> Result<ProbeOutcome> {
+    let url = reqwest::Url::parse(service_url)?;
+    let host = url.host_str().ok_or_else(|| anyhow::anyhow!("No host in {}", service_url))?;
+    let port = url.port_or_known_default().ok_or_else(|| anyhow::anyhow!("No port in {}", service_url))?;
+
+    match tokio::time::timeout(timeout, TcpStream::connect((host, port))).await {
+        Ok(Ok(_)) => Ok(ProbeOutcome::success()),
+        Ok(Err(e)) => Ok(ProbeOutcome::failure(format!("Connection failed: {}", e))),
+        Err(_) => Ok(ProbeOutcome::failure(format!("Timed out after {}ms", timeout.as_millis()))),
+    }
+}
+
+async fn probe_grpc(
+    grpc: &GrpcConfig,
+    service_name: &str,
+    service_url: &str,
+    grpc_service: Option<&str>,
+    timeout: Duration,
+) -> Result<ProbeOutcome> {
+    // Endpoint and TLS come from [grpc.services.<name>] when configured
+    let mut grpc_client = GrpcClient::from_config(grpc).with_connect_timeout(timeout);
+    if grpc_client.endpoint(service_name).is_none() {
+        grpc_client.add_service_endpoint(service_name.to_string(), service_url.to_string());
+    }
+
+    match tokio::time::timeout(timeout, grpc_client.check_health(service_name, grpc_service.unwrap_or(""))).await {
+        Ok(Ok(true)) => Ok(ProbeOutcome::success()),
+        Ok(Ok(false)) => Ok(ProbeOutcome::failure("gRPC health check reported NOT_SERVING")),
+        Ok(Err(e)) => Ok(ProbeOutcome::failure(e.to_string())),
+        Err(_) => Ok(ProbeOutcome::failure(format!("Timed out after {}ms", timeout.as_millis()))),
+    }
+}
@@ -0,0 +1,236 @@
+use anyhow::Result;
+use reqwest::header::LOCATION;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, Url};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, warn};
+
+use crate::clients::HttpClient;
+use crate::services::discovery::{AspireServiceDiscovery, ResourceInfo};
+use crate::services::orchestrator::ServiceActionError;
+
+/// Redirects followed within the environment before the response is returned as is
+const MAX_REDIRECTS: usize = 3;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ServiceProxyError {
+    #[error("Unsupported method {method}; only GET and POST can be proxied")]
+    UnsupportedMethod { method: String },
+    #[error("Service '{service}' has no HTTP endpoint{}", .endpoint.as_ref().map(|e| format!(" named '{}'", e)).unwrap_or_default())]
+    NoHttpEndpoint { service: String, endpoint: Option<String> },
+    #[error("Refusing to call {url}: it is not an endpoint of a discovered service")]
+    OffEnvironment { url: String, allowed: Vec<String> },
+}
+
+/// A request to one of the environment's services
+#[derive(Debug, Clone, Default)]
+pub struct ServiceRequest {
+    pub service: String,
+    /// Path and query, relative to the service's base URL
+    pub path: String,
+    pub method: String,
+    /// Endpoint name such as `https`; the first HTTP endpoint when unset
+    pub endpoint: Option<String>,
+    pub headers: HashMap<String, String>,
+    /// Strings are sent as is, anything else as JSON
+    pub body: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceResponse {
+    pub service: String,
+    /// URL of the response, after redirects
+    pub url: String,
+    pub method: String,
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+    /// Bytes in `body`; the service sent more when `truncated`
+    pub body_bytes: usize,
+    pub truncated: bool,
+    pub content_length: Option<u64>,
+    pub duration_ms: u64,
+    /// Redirects followed, in order
+    pub redirects: Vec<String>,
+    /// Redirect target outside the environment, which was not followed
+    pub blocked_redirect: Option<String>,
+}
+
+/// Sends requests to services at the endpoints the dashboard reports
+///
+/// Only hosts and ports of discovered endpoints can be reached, including
+/// through redirects, and response bodies are capped.
+pub struct ServiceProxy {
+    http: HttpClient,
+    service_discovery: Arc<AspireServiceDiscovery>,
+    max_body_bytes: usize,
+}
+
+impl ServiceProxy {
+    /// `http` should not follow redirects itself; see `HttpClient::without_redirects`
+    pub fn new(http: HttpClient, service_discovery: Arc<AspireServiceDiscovery>, max_body_bytes: usize) -> Self {
+        Self {
+            http,
+            service_discovery,
+            max_body_bytes,
+        }
+    }
+
+    pub async fn invoke(&self, request: &ServiceRequest) -> Result<ServiceResponse> {
+        let mut method = match request.method.to_uppercase().as_str() {
+            "GET" => Method::GET,
+            "POST" => Method::POST,
+            _ => return Err(ServiceProxyError::UnsupportedMethod { method: request.method.clone() }.into()),
+        };
+
+        let resources = self.service_discovery.get_resources().await?;
+        let resource = resources
+            .iter()
+            .find(|r| r.matches(&request.service))
+            .ok_or_else(|| ServiceActionError::UnknownService {
+                name: request.service.clone(),
+                available: resources.iter().map(|r| r.name.clone()).collect(),
+            })?;
+        let base = base_url(resource, request.endpoint.as_deref()).ok_or_else(|| ServiceProxyError::NoHttpEndpoint {
+            service: resource.name.clone(),
+            endpoint: request.endpoint.clone(),
+        })?;
+
+        let allowed = allowed_origins(&resources);
+        let mut url = base.join(&request.path)
+            .map_err(|e| anyhow::anyhow!("Invalid path '{}': {}", request.path, e))?;
+        if !allowed.contains(&origin(&url)) {
+            return Err(ServiceProxyError::OffEnvironment {
+                url: url.to_string(),
+                allowed: allowed.into_iter().collect(),
+            }.into());
+        }
+
+        debug!("Proxying {} {} for {}", method, url, resource.name);
+
+        let start = Instant::now();
+        let mut body = request.body.as_ref();
+        let mut redirects = Vec::new();
+        let mut blocked_redirect = None;
+
+        let response = loop {
+            let response = self.http
+                .send(|client| build_request(client, &method, &url, &request.headers, body))
+                .await?;
+
+            let Some(location) = redirect_target(&response, &url) else {
+                break response;
+            };
+            if !allowed.contains(&origin(&location)) {
+                warn!("Not following redirect from {} to {} outside the environment", url, location);
+                blocked_redirect = Some(location.to_string());
+                break response;
+            }
+            if redirects.len() >= MAX_REDIRECTS {
+                break response;
+            }
+
+            // Only 307 and 308 repeat the original method and body
+            if !matches!(response.status(), StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT) {
+                method = Method::GET;
+                body = None;
+            }
+            redirects.push(location.to_string());
+            url = location;
+        };
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+        let content_length = response.content_length();
+        let (bytes, truncated) = read_limited(response, self.max_body_bytes).await?;
+
+        Ok(ServiceResponse {
+            service: resource.name.clone(),
+            url: url.to_string(),
+            method: method.to_string(),
+            status,
+            headers,
+            body: String::from_utf8_lossy(&bytes).into_owned(),
+            body_bytes: bytes.len(),
+            truncated,
+            content_length,
+            duration_ms: start.elapsed().as_millis() as u64,
+            redirects,
+            blocked_redirect,
+        })
+    }
+}
+
+fn build_request(
+    client: &Client,
+    method: &Method,
+    url: &Url,
+    headers: &HashMap<String, String>,
+    body: Option<&Value>,
+) -> RequestBuilder {
+    let mut builder = client.request(method.clone(), url.clone());
+    for (key, value) in headers {
+        builder = builder.header(key, value);
+    }
+    match body {
+        Some(Value::String(text)) => builder.body(text.clone()),
+        Some(body) => builder.json(body),
+        None => builder,
+    }
+}
+
+fn base_url(resource: &ResourceInfo, endpoint: Option<&str>) -> Option<Url> {
+    resource.urls
+        .iter()
+        .flatten()
+        .filter(|u| endpoint.is_none_or(|name| u.name == name))
+        .filter_map(|u| Url::parse(&u.url).ok())
+        .find(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// `host:port` of every endpoint the dashboard reports
+fn allowed_origins(resources: &[ResourceInfo]) -> BTreeSet<String> {
+    resources
+        .iter()
+        .flat_map(|r| r.urls.iter().flatten())
+        .filter_map(|u| Url::parse(&u.url).ok())
+        .map(|url| origin(&url))
+        .collect()
+}
+
+fn origin(url: &Url) -> String {
+    format!(
+        "{}:{}",
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default().unwrap_or_default()
+    )
+}
+
+fn redirect_target(response: &Response, current: &Url) -> Option<Url> {
+    if !response.status().is_redirection() {
+        return None;
+    }
+    let location = response.headers().get(LOCATION)?.to_str().ok()?;
+    current.join(location).ok()
+}
+
+/// Read at most `limit` bytes, reporting whether the body was longer
+async fn read_limited(mut response: Response, limit: usize) -> Result<(Vec<u8>, bool)> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let remaining = limit - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
+}
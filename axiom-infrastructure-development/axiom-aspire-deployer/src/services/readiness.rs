@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::process::Output;
+use std::time::Duration;
+
+use crate::config::settings::ReadinessCheck;
+
+/// Characters of check output kept on the health record
+const MAX_OUTPUT_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessResult {
+    pub ready: bool,
+    pub checked_at: DateTime<Utc>,
+    /// Response body or command output, trimmed to the last 2000 characters
+    pub output: String,
+}
+
+impl ReadinessResult {
+    fn new(ready: bool, output: impl AsRef<str>) -> Self {
+        Self {
+            ready,
+            checked_at: Utc::now(),
+            output: tail(output.as_ref().trim(), MAX_OUTPUT_CHARS),
+        }
+    }
+}
+
+/// Run a readiness gate against a service that already passed its probe
+pub async fn run_readiness(
+    client: &Client,
+    check: &ReadinessCheck,
+    service_name: &str,
+    service_url: &str,
+    timeout: Duration,
+) -> ReadinessResult {
+    match check {
+        ReadinessCheck::Http { path } => check_http(client, service_url, path, timeout).await,
+        ReadinessCheck::Sql { connection_string, query } => check_sql(connection_string, query, timeout).await,
+        ReadinessCheck::Command { command } => {
+            let mut process = shell(command);
+            process.env("AXIOM_SERVICE_NAME", service_name).env("AXIOM_SERVICE_URL", service_url);
+            match run(process, "shell", timeout).await {
+                Ok(output) => ReadinessResult::new(output.status.success(), describe(&output)),
+                Err(error) => ReadinessResult::new(false, error),
+            }
+        }
+    }
+}
+
+async fn check_http(client: &Client, service_url: &str, path: &str, timeout: Duration) -> ReadinessResult {
+    let url = format!("{}/{}", service_url.trim_end_matches('/'), path.trim_start_matches('/'));
+    match client.get(&url).timeout(timeout).send().await {
+        Ok(response) => {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            ReadinessResult::new(status.is_success(), format!("HTTP {} {}", status.as_u16(), body))
+        }
+        Err(e) => ReadinessResult::new(false, format!("GET {} failed: {}", url, e)),
+    }
+}
+
+/// psql for PostgreSQL connection strings, sqlcmd for SQL Server ones
+async fn check_sql(connection_string: &str, query: &str, timeout: Duration) -> ReadinessResult {
+    let keys = parse_connection_string(connection_string);
+    let sql_server = keys.contains_key("server") || keys.contains_key("data source");
+
+    let (process, program) = if sql_server {
+        (sqlcmd(&keys, query), "sqlcmd")
+    } else {
+        (psql(connection_string, &keys, query), "psql")
+    };
+
+    match run(process, program, timeout).await {
+        // No rows means the schema is not there yet
+        Ok(output) if output.status.success() && String::from_utf8_lossy(&output.stdout).trim().is_empty() => {
+            ReadinessResult::new(false, format!("Query returned no rows: {}", query))
+        }
+        Ok(output) => ReadinessResult::new(output.status.success(), describe(&output)),
+        Err(error) => ReadinessResult::new(false, error),
+    }
+}
+
+fn psql(connection_string: &str, keys: &HashMap<String, String>, query: &str) -> tokio::process::Command {
+    let mut process = tokio::process::Command::new("psql");
+
+    let target = if connection_string.starts_with("postgres://") || connection_string.starts_with("postgresql://") {
+        connection_string.to_string()
+    } else {
+        // Npgsql keywords to libpq ones; the password goes through the environment
+        [("host", "host"), ("server", "host"), ("port", "port"), ("database", "dbname"),
+         ("username", "user"), ("user id", "user"), ("userid", "user")]
+            .iter()
+            .filter_map(|(from, to)| keys.get(*from).map(|value| format!("{}='{}'", to, value.replace('\'', "\\'"))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    if let Some(password) = keys.get("password") {
+        process.env("PGPASSWORD", password);
+    }
+
+    process.args(["--no-psqlrc", "--tuples-only", "--no-align", "--set", "ON_ERROR_STOP=1", "--dbname", &target, "--command", query]);
+    process
+}
+
+fn sqlcmd(keys: &HashMap<String, String>, query: &str) -> tokio::process::Command {
+    let mut process = tokio::process::Command::new("sqlcmd");
+
+    if let Some(server) = keys.get("server").or_else(|| keys.get("data source")) {
+        process.args(["-S", server]);
+    }
+    if let Some(database) = keys.get("database").or_else(|| keys.get("initial catalog")) {
+        process.args(["-d", database]);
+    }
+    match keys.get("user id").or_else(|| keys.get("uid")) {
+        Some(user) => {
+            process.args(["-U", user]);
+            if let Some(password) = keys.get("password").or_else(|| keys.get("pwd")) {
+                process.env("SQLCMDPASSWORD", password);
+            }
+        }
+        None => {
+            process.arg("-E");
+        }
+    }
+    if keys.get("trustservercertificate").is_some_and(|value| value.eq_ignore_ascii_case("true")) {
+        process.arg("-C");
+    }
+
+    // No headers and no row counts, so an empty result prints nothing
+    process.args(["-b", "-h", "-1", "-Q", &format!("SET NOCOUNT ON; {}", query)]);
+    process
+}
+
+/// `Key=Value;` pairs with lowercase keys
+fn parse_connection_string(connection_string: &str) -> HashMap<String, String> {
+    connection_string
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect()
+}
+
+fn shell(command: &str) -> tokio::process::Command {
+    if cfg!(windows) {
+        let mut process = tokio::process::Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    } else {
+        let mut process = tokio::process::Command::new("sh");
+        process.args(["-c", command]);
+        process
+    }
+}
+
+async fn run(mut process: tokio::process::Command, program: &str, timeout: Duration) -> Result<Output, String> {
+    process.kill_on_drop(true);
+    match tokio::time::timeout(timeout, process.output()).await {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(e)) => Err(format!("Could not run {}: {}", program, e)),
+        Err(_) => Err(format!("{} timed out after {}ms", program, timeout.as_millis())),
+    }
+}
+
+/// Exit status followed by stdout and stderr
+fn describe(output: &Output) -> String {
+    format!(
+        "{}\n{}{}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr),
+    )
+}
+
+fn tail(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let skip = count - max_chars;
+    format!("...{}", text.chars().skip(skip).collect::<String>())
+}
@@ -0,0 +1,373 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::clients::docker::{DockerClient, DockerContainer};
+use crate::config::settings::{is_valid_database_name, DatabaseDialect, DatabasesConfig};
+use crate::services::AspireServiceDiscovery;
+
+/// Plain SQL dump inside a snapshot directory
+pub const DUMP_FILE: &str = "dump.sql";
+
+/// Snapshot metadata inside a snapshot directory
+pub const METADATA_FILE: &str = "snapshot.json";
+
+#[derive(Debug, thiserror::Error)]
+pub enum DatabaseSnapshotError {
+    #[error("'{service}' is not flagged as a local dev database; set databases.resources.{service}.local_dev = true to allow snapshot and restore")]
+    NotLocalDev { service: String },
+    #[error("Cannot tell the database dialect of '{service}' (resource type '{resource_type}', image '{image}'); set databases.resources.{service}.dialect")]
+    UnknownDialect { service: String, resource_type: String, image: String },
+    #[error("No database given for '{service}'; pass database or set databases.resources.{service}.database")]
+    MissingDatabase { service: String },
+    #[error("Invalid {kind} '{value}': only letters, digits, '_' and '-' are allowed")]
+    InvalidName { kind: &'static str, value: String },
+    #[error("Snapshot '{name}' already exists; pass overwrite: true to replace it")]
+    SnapshotExists { name: String },
+    #[error("No snapshot named '{name}' (available: {})", .available.join(", "))]
+    SnapshotNotFound { name: String, available: Vec<String> },
+    #[error("Snapshot '{name}' is a {} dump and cannot be restored into '{service}' ({})", .snapshot.as_str(), .target.as_str())]
+    DialectMismatch { name: String, service: String, snapshot: DatabaseDialect, target: DatabaseDialect },
+}
+
+/// Metadata of a stored snapshot, as written to `snapshot.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseSnapshot {
+    pub name: String,
+    /// Resource the dump was taken from
+    pub service: String,
+    pub database: String,
+    pub dialect: DatabaseDialect,
+    /// Size of the dump file
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+    /// Name of the container the dump was taken from
+    pub container: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotRequest {
+    pub service: String,
+    /// Falls back to `databases.resources.<service>.database`
+    pub database: Option<String>,
+    /// `<service>-<UTC timestamp>` when unset
+    pub name: Option<String>,
+    pub overwrite: bool,
+}
+
+impl SnapshotRequest {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            database: None,
+            name: None,
+            overwrite: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreResult {
+    pub snapshot: DatabaseSnapshot,
+    /// Resource the snapshot was restored into
+    pub service: String,
+    pub database: String,
+    pub container: String,
+    pub duration_ms: u64,
+}
+
+/// Database the dump and restore commands run against
+struct DatabaseTarget {
+    service: String,
+    database: String,
+    user: String,
+    dialect: DatabaseDialect,
+    container: DockerContainer,
+}
+
+/// Snapshots of Aspire's local database containers
+///
+/// Dumps are taken with the database's own tools inside its container, so
+/// nothing but Docker has to be installed next to the server. Only resources
+/// flagged `local_dev` in `[databases.resources]` are touched.
+pub struct DatabaseSnapshots {
+    docker: Arc<DockerClient>,
+    service_discovery: Arc<AspireServiceDiscovery>,
+    config: DatabasesConfig,
+}
+
+impl DatabaseSnapshots {
+    pub fn new(docker: Arc<DockerClient>, service_discovery: Arc<AspireServiceDiscovery>, config: DatabasesConfig) -> Self {
+        Self { docker, service_discovery, config }
+    }
+
+    /// Dump a database into a new snapshot directory
+    pub async fn snapshot<F>(&self, request: &SnapshotRequest, mut on_progress: F) -> Result<DatabaseSnapshot>
+    where
+        F: FnMut(String),
+    {
+        let started = Instant::now();
+        let name = match &request.name {
+            Some(name) => name.clone(),
+            None => format!("{}-{}", request.service, Utc::now().format("%Y%m%d-%H%M%S")),
+        };
+        validate_name("snapshot name", &name)?;
+
+        let target = self.resolve_target(&request.service, request.database.as_deref()).await?;
+        let dir = self.snapshot_path(&name);
+        if tokio::fs::try_exists(&dir).await? && !request.overwrite {
+            return Err(DatabaseSnapshotError::SnapshotExists { name }.into());
+        }
+
+        let command = dump_command(target.dialect, &target.user, &target.database);
+        on_progress(format!("Dumping {} from {} with {}", target.database, target.container.name, dump_program(target.dialect)));
+        info!("Snapshotting {} of {} into {}", target.database, target.service, dir.display());
+        let dump = self.docker.exec_trusted(&target.container, &command, None, self.timeout()).await?;
+
+        on_progress(format!("Writing {} bytes to {}", dump.len(), dir.display()));
+        if tokio::fs::try_exists(&dir).await? {
+            tokio::fs::remove_dir_all(&dir).await?;
+        }
+        tokio::fs::create_dir_all(&dir).await?;
+        tokio::fs::write(dir.join(DUMP_FILE), &dump).await?;
+
+        let snapshot = DatabaseSnapshot {
+            name,
+            service: target.service,
+            database: target.database,
+            dialect: target.dialect,
+            size_bytes: dump.len() as u64,
+            created_at: Utc::now(),
+            container: target.container.name,
+            duration_ms: started.elapsed().as_millis() as u64,
+        };
+        tokio::fs::write(dir.join(METADATA_FILE), serde_json::to_vec_pretty(&snapshot)?).await?;
+        Ok(snapshot)
+    }
+
+    /// Drop and recreate the database, then import the snapshot's dump
+    ///
+    /// Restores into the snapshot's own resource and database unless others
+    /// are given; the target must have the snapshot's dialect.
+    pub async fn restore<F>(&self, name: &str, service: Option<&str>, database: Option<&str>, mut on_progress: F) -> Result<RestoreResult>
+    where
+        F: FnMut(String),
+    {
+        let started = Instant::now();
+        validate_name("snapshot name", name)?;
+        let snapshot = self.load(name).await?;
+
+        let service = service.unwrap_or(&snapshot.service);
+        let target = self.resolve_target(service, Some(database.unwrap_or(&snapshot.database))).await?;
+        if target.dialect != snapshot.dialect {
+            return Err(DatabaseSnapshotError::DialectMismatch {
+                name: snapshot.name,
+                service: target.service,
+                snapshot: snapshot.dialect,
+                target: target.dialect,
+            }.into());
+        }
+        let dump = tokio::fs::read(self.snapshot_path(name).join(DUMP_FILE)).await?;
+
+        on_progress(format!("Dropping and recreating {} in {}", target.database, target.container.name));
+        info!("Restoring snapshot {} into {} of {}", name, target.database, target.service);
+        for command in recreate_commands(target.dialect, &target.user, &target.database) {
+            self.docker.exec_trusted(&target.container, &command, None, self.timeout()).await?;
+        }
+
+        on_progress(format!("Importing {} bytes from snapshot {}", dump.len(), name));
+        let command = import_command(target.dialect, &target.user, &target.database);
+        self.docker.exec_trusted(&target.container, &command, Some(dump), self.timeout()).await?;
+
+        Ok(RestoreResult {
+            snapshot,
+            service: target.service,
+            database: target.database,
+            container: target.container.name,
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Stored snapshots, newest first, optionally only those of `service`
+    pub async fn list(&self, service: Option<&str>) -> Result<Vec<DatabaseSnapshot>> {
+        let mut entries = match tokio::fs::read_dir(&self.config.snapshot_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut snapshots = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path().join(METADATA_FILE);
+            // Directories without metadata are not snapshots
+            let Ok(content) = tokio::fs::read(&path).await else {
+                continue;
+            };
+            match serde_json::from_slice::<DatabaseSnapshot>(&content) {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => warn!("Skipping unreadable snapshot metadata {}: {}", path.display(), e),
+            }
+        }
+
+        snapshots.retain(|snapshot| service.is_none_or(|service| snapshot.service == service));
+        snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created_at));
+        Ok(snapshots)
+    }
+
+    async fn load(&self, name: &str) -> Result<DatabaseSnapshot> {
+        match tokio::fs::read(self.snapshot_path(name).join(METADATA_FILE)).await {
+            Ok(content) => Ok(serde_json::from_slice(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let available = self.list(None).await?.into_iter().map(|snapshot| snapshot.name).collect();
+                Err(DatabaseSnapshotError::SnapshotNotFound { name: name.to_string(), available }.into())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Check the guardrail first, so unflagged resources never reach Docker
+    async fn resolve_target(&self, service: &str, database: Option<&str>) -> Result<DatabaseTarget> {
+        let resource = self.config.resources.get(service).filter(|resource| resource.local_dev).ok_or_else(|| {
+            DatabaseSnapshotError::NotLocalDev { service: service.to_string() }
+        })?;
+
+        let database = database
+            .or(resource.database.as_deref())
+            .ok_or_else(|| DatabaseSnapshotError::MissingDatabase { service: service.to_string() })?
+            .to_string();
+        validate_name("database", &database)?;
+
+        let container = self.docker.find_containers(service).await?.remove(0);
+        let dialect = match resource.dialect {
+            Some(dialect) => dialect,
+            None => {
+                let resource_type = self.resource_type(service).await;
+                detect_dialect(&resource_type, &container.image).ok_or_else(|| DatabaseSnapshotError::UnknownDialect {
+                    service: service.to_string(),
+                    resource_type,
+                    image: container.image.clone(),
+                })?
+            }
+        };
+        let user = resource.user.clone().unwrap_or_else(|| default_user(dialect).to_string());
+
+        Ok(DatabaseTarget {
+            service: service.to_string(),
+            database,
+            user,
+            dialect,
+            container,
+        })
+    }
+
+    /// The dashboard's resource type, or an empty string when it cannot be asked;
+    /// the container image still identifies the dialect then
+    async fn resource_type(&self, service: &str) -> String {
+        match self.service_discovery.get_resource_details(service).await {
+            Ok(resource) => resource.map(|resource| resource.resource_type).unwrap_or_default(),
+            Err(e) => {
+                warn!("Could not look up the resource type of {}: {}", service, e);
+                String::new()
+            }
+        }
+    }
+
+    fn snapshot_path(&self, name: &str) -> PathBuf {
+        PathBuf::from(&self.config.snapshot_dir).join(name)
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.config.command_timeout_ms)
+    }
+}
+
+/// Dialect named by a resource type (`PostgresServerResource`) or image (`docker.io/library/mysql:8.4`)
+pub fn detect_dialect(resource_type: &str, image: &str) -> Option<DatabaseDialect> {
+    [resource_type, image].iter().find_map(|text| {
+        let text = text.to_ascii_lowercase();
+        if text.contains("postgres") {
+            Some(DatabaseDialect::Postgres)
+        } else if text.contains("mysql") {
+            Some(DatabaseDialect::Mysql)
+        } else {
+            None
+        }
+    })
+}
+
+/// Command writing a plain SQL dump of `database` to stdout
+pub fn dump_command(dialect: DatabaseDialect, user: &str, database: &str) -> Vec<String> {
+    match dialect {
+        DatabaseDialect::Postgres => args(&["pg_dump", "--username", user, "--no-owner", "--no-privileges", "--dbname", database]),
+        DatabaseDialect::Mysql => mysql_shell(user, &format!(
+            "mysqldump --user='{}' --single-transaction --routines --triggers '{}'",
+            user, database,
+        )),
+    }
+}
+
+/// Commands, run in order, that leave `database` empty
+pub fn recreate_commands(dialect: DatabaseDialect, user: &str, database: &str) -> Vec<Vec<String>> {
+    match dialect {
+        // Connected to template1, so any other database can be dropped; created
+        // from template0, which nobody can be connected to
+        DatabaseDialect::Postgres => [
+            format!("DROP DATABASE IF EXISTS \"{}\" WITH (FORCE)", database),
+            format!("CREATE DATABASE \"{}\" TEMPLATE template0", database),
+        ]
+        .iter()
+        .map(|sql| args(&["psql", "--no-psqlrc", "--username", user, "--dbname", "template1", "--set", "ON_ERROR_STOP=1", "--command", sql.as_str()]))
+        .collect(),
+        DatabaseDialect::Mysql => vec![mysql_shell(user, &format!(
+            "mysql --user='{}' --execute='DROP DATABASE IF EXISTS `{}`; CREATE DATABASE `{}`'",
+            user, database, database,
+        ))],
+    }
+}
+
+/// Command importing a dump from stdin into `database`
+pub fn import_command(dialect: DatabaseDialect, user: &str, database: &str) -> Vec<String> {
+    match dialect {
+        DatabaseDialect::Postgres => args(&["psql", "--no-psqlrc", "--quiet", "--username", user, "--dbname", database, "--set", "ON_ERROR_STOP=1"]),
+        DatabaseDialect::Mysql => mysql_shell(user, &format!("mysql --user='{}' '{}'", user, database)),
+    }
+}
+
+/// MySQL clients need the password from the container's environment, so they run
+/// through a shell; `user` and the database are validated names and safe to quote
+fn mysql_shell(user: &str, command: &str) -> Vec<String> {
+    let password = if user == "root" { "$MYSQL_ROOT_PASSWORD" } else { "$MYSQL_PASSWORD" };
+    let script = format!("MYSQL_PWD=\"{}\" exec {}", password, command);
+    args(&["sh", "-c", script.as_str()])
+}
+
+fn dump_program(dialect: DatabaseDialect) -> &'static str {
+    match dialect {
+        DatabaseDialect::Postgres => "pg_dump",
+        DatabaseDialect::Mysql => "mysqldump",
+    }
+}
+
+fn default_user(dialect: DatabaseDialect) -> &'static str {
+    match dialect {
+        DatabaseDialect::Postgres => "postgres",
+        DatabaseDialect::Mysql => "root",
+    }
+}
+
+fn validate_name(kind: &'static str, value: &str) -> Result<(), DatabaseSnapshotError> {
+    if is_valid_database_name(value) {
+        Ok(())
+    } else {
+        Err(DatabaseSnapshotError::InvalidName { kind, value: value.to_string() })
+    }
+}
+
+fn args(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
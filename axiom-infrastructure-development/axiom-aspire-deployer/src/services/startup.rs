@@ -0,0 +1,196 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::services::discovery::ResourceInfo;
+
+/// Relationship types in the dashboard API that mean "start after"
+const DEPENDENCY_RELATIONSHIPS: &[&str] = &["Reference", "WaitFor"];
+
+/// Manifest expressions such as `{postgres.connectionString}` or `{api.bindings.http.url}`
+static MANIFEST_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{([A-Za-z0-9_-]+)\.").expect("valid manifest reference pattern")
+});
+
+#[derive(Debug, thiserror::Error)]
+pub enum StartupPlanError {
+    #[error("Dependency cycle between services: {}", .cycle.join(" -> "))]
+    Cycle { cycle: Vec<String> },
+}
+
+/// Services and the services each one has to wait for
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Services grouped into stages; a stage only depends on earlier stages
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupPlan {
+    pub stages: Vec<Vec<String>>,
+    pub dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl StartupPlan {
+    /// Flattened start order
+    pub fn order(&self) -> Vec<&str> {
+        self.stages.iter().flatten().map(|s| s.as_str()).collect()
+    }
+
+    /// Stages in stop order: a service stops before the services it depends on
+    pub fn teardown_stages(&self) -> Vec<Vec<String>> {
+        self.stages.iter().rev().cloned().collect()
+    }
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_service(&mut self, service: &str) {
+        self.dependencies.entry(service.to_string()).or_default();
+    }
+
+    /// Record that `service` must start after `depends_on`
+    pub fn add_dependency(&mut self, service: &str, depends_on: &str) {
+        self.add_service(depends_on);
+        self.dependencies
+            .entry(service.to_string())
+            .or_default()
+            .insert(depends_on.to_string());
+    }
+
+    pub fn dependencies_of(&self, service: &str) -> impl Iterator<Item = &str> {
+        self.dependencies
+            .get(service)
+            .into_iter()
+            .flatten()
+            .map(|s| s.as_str())
+    }
+
+    /// Build the graph from the relationships reported by the dashboard
+    pub fn from_resources(resources: &[ResourceInfo]) -> Self {
+        let mut graph = Self::new();
+        let known: BTreeSet<&str> = resources.iter().map(|r| r.name.as_str()).collect();
+
+        for resource in resources {
+            graph.add_service(&resource.name);
+            for relationship in resource.relationships.iter().flatten() {
+                if DEPENDENCY_RELATIONSHIPS.contains(&relationship.relationship_type.as_str())
+                    && relationship.resource_name != resource.name
+                    && known.contains(relationship.resource_name.as_str())
+                {
+                    graph.add_dependency(&resource.name, &relationship.resource_name);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Build the graph from an AppHost manifest (`--publisher manifest`)
+    ///
+    /// A resource depends on every other resource its properties reference
+    /// through `{name.…}` expressions.
+    pub fn from_manifest(manifest: &Value) -> Self {
+        let mut graph = Self::new();
+        let Some(resources) = manifest.get("resources").and_then(|v| v.as_object()) else {
+            return graph;
+        };
+
+        for (name, resource) in resources {
+            graph.add_service(name);
+
+            let mut strings = Vec::new();
+            collect_strings(resource, &mut strings);
+            for text in strings {
+                for capture in MANIFEST_REFERENCE.captures_iter(text) {
+                    let target = &capture[1];
+                    if target != name.as_str() && resources.contains_key(target) {
+                        graph.add_dependency(name, target);
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Group services into stages in topological order (Kahn's algorithm)
+    ///
+    /// Services within a stage are independent and can start in parallel.
+    pub fn startup_plan(&self) -> Result<StartupPlan, StartupPlanError> {
+        let mut remaining: BTreeMap<&str, BTreeSet<&str>> = self.dependencies
+            .iter()
+            .map(|(service, deps)| (service.as_str(), deps.iter().map(|d| d.as_str()).collect()))
+            .collect();
+        let mut stages = Vec::new();
+
+        while !remaining.is_empty() {
+            let ready: Vec<&str> = remaining
+                .iter()
+                .filter(|(_, deps)| deps.is_empty())
+                .map(|(service, _)| *service)
+                .collect();
+
+            if ready.is_empty() {
+                return Err(StartupPlanError::Cycle { cycle: find_cycle(&remaining) });
+            }
+
+            for service in &ready {
+                remaining.remove(service);
+            }
+            for deps in remaining.values_mut() {
+                for service in &ready {
+                    deps.remove(service);
+                }
+            }
+
+            stages.push(ready.into_iter().map(|s| s.to_string()).collect());
+        }
+
+        Ok(StartupPlan {
+            stages,
+            dependencies: self.dependencies
+                .iter()
+                .map(|(service, deps)| (service.clone(), deps.iter().cloned().collect()))
+                .collect(),
+        })
+    }
+}
+
+/// Follow unresolved dependencies until a service repeats
+///
+/// Every service left over by Kahn's algorithm still has an unresolved
+/// dependency, so the walk always closes a loop.
+fn find_cycle(remaining: &BTreeMap<&str, BTreeSet<&str>>) -> Vec<String> {
+    let Some(mut current) = remaining.keys().next().copied() else {
+        return Vec::new();
+    };
+    let mut path: Vec<&str> = Vec::new();
+
+    loop {
+        if let Some(start) = path.iter().position(|s| *s == current) {
+            let mut cycle: Vec<String> = path[start..].iter().map(|s| s.to_string()).collect();
+            cycle.push(current.to_string());
+            return cycle;
+        }
+        path.push(current);
+        match remaining.get(&current).and_then(|deps| deps.iter().next()) {
+            Some(next) => current = *next,
+            None => return path.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+fn collect_strings<'a>(value: &'a Value, strings: &mut Vec<&'a str>) {
+    match value {
+        Value::String(text) => strings.push(text),
+        Value::Array(items) => items.iter().for_each(|item| collect_strings(item, strings)),
+        Value::Object(map) => map.values().for_each(|item| collect_strings(item, strings)),
+        _ => {}
+    }
+}
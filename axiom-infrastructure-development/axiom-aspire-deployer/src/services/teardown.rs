@@ -0,0 +1,338 @@
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::clients::docker::{DockerClient, DockerError, DockerRemoval};
+use crate::config::settings::TeardownConfig;
+use crate::services::ports::{self, PortConflict, PortRequirement};
+use crate::services::{AspireOrchestrator, AspireServiceDiscovery};
+
+/// How long ports get to be released once everything is stopped
+pub const DEFAULT_PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Delay between port checks while waiting for them to be released
+const PORT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone)]
+pub struct TeardownOptions {
+    /// Stop the AppHost once its services are stopped
+    pub stop_apphost: bool,
+    /// Remove the containers and networks Aspire created
+    pub remove_containers: bool,
+    /// Wait per service; the orchestrator's default when unset
+    pub timeout: Option<Duration>,
+    pub port_release_timeout: Duration,
+}
+
+impl TeardownOptions {
+    pub fn from_config(config: &TeardownConfig) -> Self {
+        Self {
+            stop_apphost: config.stop_apphost,
+            remove_containers: config.remove_containers,
+            timeout: None,
+            port_release_timeout: DEFAULT_PORT_RELEASE_TIMEOUT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeftoverKind {
+    Service,
+    Apphost,
+    Container,
+    Network,
+    Port,
+}
+
+/// Something teardown could not clean up, and how to do it by hand
+#[derive(Debug, Clone, Serialize)]
+pub struct TeardownLeftover {
+    pub kind: LeftoverKind,
+    pub target: String,
+    pub error: String,
+    pub remediation: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TeardownReport {
+    /// Stop order: dependents before the services they depend on
+    pub stages: Vec<Vec<String>>,
+    pub stopped: Vec<String>,
+    pub apphost_stopped: bool,
+    pub removed_containers: Vec<String>,
+    pub removed_networks: Vec<String>,
+    pub leftovers: Vec<TeardownLeftover>,
+    /// Whether nothing was left behind
+    pub clean: bool,
+    pub elapsed_ms: u64,
+}
+
+/// Tears the local environment down and checks that nothing is left behind
+///
+/// Every step runs even when an earlier one fails, so a single stuck service
+/// does not leave the containers and ports of all the others behind.
+/// Failures end up in the report's leftovers instead.
+pub struct EnvironmentTeardown {
+    orchestrator: Arc<AspireOrchestrator>,
+    service_discovery: Arc<AspireServiceDiscovery>,
+    docker: Arc<DockerClient>,
+    config: TeardownConfig,
+}
+
+impl EnvironmentTeardown {
+    pub fn new(
+        orchestrator: Arc<AspireOrchestrator>,
+        service_discovery: Arc<AspireServiceDiscovery>,
+        docker: Arc<DockerClient>,
+        config: TeardownConfig,
+    ) -> Self {
+        Self { orchestrator, service_discovery, docker, config }
+    }
+
+    /// Options from `[teardown]`
+    pub fn default_options(&self) -> TeardownOptions {
+        TeardownOptions::from_config(&self.config)
+    }
+
+    pub async fn teardown<F>(&self, options: &TeardownOptions, mut on_progress: F) -> TeardownReport
+    where
+        F: FnMut(String),
+    {
+        let start = Instant::now();
+        let mut report = TeardownReport::default();
+
+        // Read before the dashboard goes away with the AppHost
+        let ports: Vec<PortRequirement> = match self.service_discovery.get_resources().await {
+            Ok(resources) => resources.iter().flat_map(AspireOrchestrator::local_ports).collect(),
+            Err(e) => {
+                warn!("Cannot list service ports, skipping the port check: {}", e);
+                Vec::new()
+            }
+        };
+
+        on_progress("Stopping services in reverse dependency order".to_string());
+        match self.orchestrator.stop_services_in_order(options.timeout).await {
+            Ok(result) => {
+                report.stages = result.stages;
+                report.stopped = result.stopped.into_iter().map(|r| r.service).collect();
+                for failure in result.failed {
+                    report.leftovers.push(TeardownLeftover {
+                        kind: LeftoverKind::Service,
+                        remediation: format!("Stop '{}' from the Aspire dashboard, or stop the AppHost", failure.service),
+                        target: failure.service,
+                        error: failure.error,
+                    });
+                }
+            }
+            Err(e) => report.leftovers.push(TeardownLeftover {
+                kind: LeftoverKind::Service,
+                target: "all".to_string(),
+                error: e.to_string(),
+                remediation: "Stop the services from the Aspire dashboard, or stop the AppHost".to_string(),
+            }),
+        }
+
+        if options.stop_apphost {
+            on_progress("Stopping the AppHost".to_string());
+            match self.orchestrator.stop_aspire().await {
+                Ok(()) => report.apphost_stopped = true,
+                Err(e) => report.leftovers.push(TeardownLeftover {
+                    kind: LeftoverKind::Apphost,
+                    target: "apphost".to_string(),
+                    error: e.to_string(),
+                    remediation: "Stop the AppHost with Ctrl+C in its terminal, or end its dotnet process".to_string(),
+                }),
+            }
+        }
+
+        if options.remove_containers {
+            on_progress("Removing containers created by Aspire".to_string());
+            let removals = self.docker.remove_managed_containers().await;
+            report.removed_containers = self.record_removals(LeftoverKind::Container, removals, &mut report.leftovers);
+
+            on_progress("Removing networks created by Aspire".to_string());
+            let removals = self.docker.remove_managed_networks().await;
+            report.removed_networks = self.record_removals(LeftoverKind::Network, removals, &mut report.leftovers);
+        }
+
+        on_progress(format!("Checking that {} ports are released", ports.len()));
+        for conflict in wait_for_ports(&ports, options.port_release_timeout).await {
+            report.leftovers.push(TeardownLeftover {
+                kind: LeftoverKind::Port,
+                target: format!("{}:{}", conflict.host, conflict.port),
+                error: conflict.to_string(),
+                remediation: port_remediation(&conflict),
+            });
+        }
+
+        report.clean = report.leftovers.is_empty();
+        report.elapsed_ms = start.elapsed().as_millis() as u64;
+        info!(
+            "Teardown finished in {}ms: {} services stopped, {} containers and {} networks removed, {} leftovers",
+            report.elapsed_ms,
+            report.stopped.len(),
+            report.removed_containers.len(),
+            report.removed_networks.len(),
+            report.leftovers.len(),
+        );
+        report
+    }
+
+    /// Names of what was removed; everything else becomes a leftover
+    fn record_removals(
+        &self,
+        kind: LeftoverKind,
+        removals: anyhow::Result<Vec<DockerRemoval>>,
+        leftovers: &mut Vec<TeardownLeftover>,
+    ) -> Vec<String> {
+        let noun = if kind == LeftoverKind::Network { "network" } else { "container" };
+        let removals = match removals {
+            Ok(removals) => removals,
+            Err(e) if matches!(e.downcast_ref::<DockerError>(), Some(DockerError::Disabled)) => {
+                debug!("Docker integration is disabled, leaving {}s alone", noun);
+                return Vec::new();
+            }
+            Err(e) => {
+                let label = self.docker.managed_label();
+                let remediation = match kind {
+                    LeftoverKind::Network => format!("Once Docker is reachable, run: docker network ls --quiet --filter label={} | xargs docker network rm", label),
+                    _ => format!("Once Docker is reachable, run: docker ps --all --quiet --filter label={} | xargs docker rm --force", label),
+                };
+                leftovers.push(TeardownLeftover {
+                    kind,
+                    target: format!("{}s labelled {}", noun, label),
+                    error: e.to_string(),
+                    remediation,
+                });
+                return Vec::new();
+            }
+        };
+
+        let mut removed = Vec::new();
+        for removal in removals {
+            match removal.error {
+                None => removed.push(removal.name),
+                Some(error) => {
+                    let remediation = match kind {
+                        LeftoverKind::Network => format!(
+                            "Disconnect the containers still attached (docker network inspect {}), then run: docker network rm {}",
+                            removal.name, removal.name,
+                        ),
+                        _ => format!("Run: docker rm --force {}", removal.name),
+                    };
+                    leftovers.push(TeardownLeftover { kind, target: removal.name, error, remediation });
+                }
+            }
+        }
+        removed
+    }
+}
+
+/// Conflicts still present once `timeout` has passed; processes can take a
+/// moment to exit and release their ports
+async fn wait_for_ports(requirements: &[PortRequirement], timeout: Duration) -> Vec<PortConflict> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let conflicts = ports::find_conflicts(requirements).await;
+        let now = Instant::now();
+        if conflicts.is_empty() || now >= deadline {
+            return conflicts;
+        }
+        tokio::time::sleep(PORT_POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
+
+fn port_remediation(conflict: &PortConflict) -> String {
+    match &conflict.owner {
+        Some(owner) if owner.process_name.as_deref().is_some_and(|name| name.to_lowercase().starts_with("dcp")) => {
+            "Aspire's DCP still holds the port; stop the AppHost (stop_apphost: true)".to_string()
+        }
+        Some(owner) if cfg!(windows) => format!("Stop {}: taskkill /PID {} /F", owner, owner.pid),
+        Some(owner) => format!("Stop {}: kill {}", owner, owner.pid),
+        None if cfg!(windows) => format!("Find the process listening on port {} with: netstat -ano | findstr :{}", conflict.port, conflict.port),
+        None => format!("Find the process listening on port {} with: lsof -i :{}", conflict.port, conflict.port),
+    }
+}
+
+/// What the idle timer asks for when polled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleAction {
+    /// Warn clients that the environment is torn down in `remaining`
+    Warn { remaining: Duration },
+    Teardown,
+}
+
+/// Counts down `[teardown].idle_timeout_minutes` from the last MCP request
+///
+/// Activity arms the timer and cancels a pending teardown. Once it fires the
+/// timer stays disarmed until the next activity, so an idle environment is
+/// torn down once rather than over and over.
+#[derive(Debug)]
+pub struct IdleTimer {
+    timeout: Duration,
+    warning: Duration,
+    last_activity: Instant,
+    armed: bool,
+    warned: bool,
+}
+
+impl IdleTimer {
+    /// Armed from `now`; `warning` before the teardown clients are warned
+    pub fn new(timeout: Duration, warning: Duration, now: Instant) -> Self {
+        Self {
+            timeout,
+            warning: warning.min(timeout),
+            last_activity: now,
+            armed: true,
+            warned: false,
+        }
+    }
+
+    /// `None` when no idle timeout is configured
+    pub fn from_config(config: &TeardownConfig, now: Instant) -> Option<Self> {
+        let timeout = Duration::from_secs(config.idle_timeout_minutes? * 60);
+        Some(Self::new(timeout, Duration::from_secs(config.warning_minutes * 60), now))
+    }
+
+    /// Record MCP activity, re-arming the timer
+    pub fn touch(&mut self, now: Instant) {
+        self.last_activity = now;
+        self.armed = true;
+        self.warned = false;
+    }
+
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    /// When the environment is torn down unless there is activity first
+    pub fn deadline(&self) -> Option<Instant> {
+        self.armed.then(|| self.last_activity + self.timeout)
+    }
+
+    /// When `poll` next has something to do
+    pub fn next_wakeup(&self) -> Option<Instant> {
+        let deadline = self.deadline()?;
+        if self.warned || self.warning.is_zero() {
+            Some(deadline)
+        } else {
+            Some(deadline - self.warning)
+        }
+    }
+
+    pub fn poll(&mut self, now: Instant) -> Option<IdleAction> {
+        let deadline = self.deadline()?;
+        if now >= deadline {
+            self.armed = false;
+            return Some(IdleAction::Teardown);
+        }
+        let remaining = deadline - now;
+        if !self.warned && !self.warning.is_zero() && remaining <= self.warning {
+            self.warned = true;
+            return Some(IdleAction::Warn { remaining });
+        }
+        None
+    }
+}
@@ -0,0 +1,308 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+use crate::services::discovery::ResourceInfo;
+use crate::services::manifest::LOCAL_RESOURCE_TYPES;
+use crate::services::orchestrator::ServiceEndpoint;
+use crate::services::probes::HealthState;
+
+/// Manifest expressions with the property they read, e.g. `{postgres.connectionString}`
+static MANIFEST_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{([A-Za-z0-9_-]+)\.([A-Za-z]+)").expect("valid manifest reference pattern")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapFormat {
+    Json,
+    Dot,
+    Mermaid,
+}
+
+impl MapFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "json" => Some(MapFormat::Json),
+            "dot" | "graphviz" => Some(MapFormat::Dot),
+            "mermaid" => Some(MapFormat::Mermaid),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MapFormat::Json => "json",
+            MapFormat::Dot => "dot",
+            MapFormat::Mermaid => "mermaid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TopologyNode {
+    /// Manifest name, or the display name of resources only the dashboard knows
+    pub name: String,
+    /// `project`, `container`, `parameter`, `azure.bicep`, … without the version suffix
+    pub resource_type: String,
+    /// Declared but never run locally (parameters, cloud resources, connection strings)
+    pub external: bool,
+    /// Dashboard state of the first replica; `None` when not running
+    pub state: Option<String>,
+    #[serde(serialize_with = "serialize_health")]
+    pub health: Option<HealthState>,
+    pub replicas: usize,
+    pub endpoints: Vec<ServiceEndpoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct TopologyEdge {
+    pub from: String,
+    pub to: String,
+    /// `connection_string`, `endpoint`, `parameter` or `reference` from the
+    /// manifest; `wait_for`, `reference`, … from the dashboard
+    pub kind: String,
+}
+
+/// What talks to what: resources and the references between them
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ServiceMap {
+    pub nodes: Vec<TopologyNode>,
+    pub edges: Vec<TopologyEdge>,
+}
+
+impl ServiceMap {
+    /// Combine the manifest's reference graph with live resources
+    ///
+    /// Without a manifest, nodes and edges come from the dashboard alone.
+    /// `health_states` is keyed by resource or display name.
+    pub fn build(manifest: Option<&Value>, live: &[ResourceInfo], health_states: &HashMap<String, HealthState>) -> Self {
+        let declared = manifest
+            .and_then(|m| m.get("resources"))
+            .and_then(|v| v.as_object());
+
+        let mut types: BTreeMap<String, String> = BTreeMap::new();
+        for (name, resource) in declared.into_iter().flatten() {
+            let resource_type = resource.get("type")
+                .and_then(|v| v.as_str())
+                .map(strip_version)
+                .unwrap_or_default();
+            types.insert(name.clone(), resource_type.to_string());
+        }
+        for resource in live {
+            if !types.keys().any(|name| resource.matches(name)) {
+                types.insert(resource.displayName.clone(), resource.resource_type.to_ascii_lowercase());
+            }
+        }
+
+        let nodes: Vec<TopologyNode> = types
+            .into_iter()
+            .map(|(name, resource_type)| {
+                let replicas: Vec<&ResourceInfo> = live.iter().filter(|r| r.matches(&name)).collect();
+                let health = std::iter::once(name.as_str())
+                    .chain(replicas.iter().map(|r| r.name.as_str()))
+                    .find_map(|key| health_states.get(key).copied());
+                TopologyNode {
+                    external: replicas.is_empty() && !LOCAL_RESOURCE_TYPES.contains(&resource_type.as_str()),
+                    state: replicas.first().and_then(|r| r.state.clone()),
+                    health,
+                    replicas: replicas.len(),
+                    endpoints: replicas
+                        .iter()
+                        .flat_map(|r| r.urls.iter().flatten())
+                        .map(|u| ServiceEndpoint { name: u.name.clone(), url: u.url.clone() })
+                        .collect(),
+                    name,
+                    resource_type,
+                }
+            })
+            .collect();
+
+        let mut edges = BTreeSet::new();
+        for (name, resource) in declared.into_iter().flatten() {
+            for (target, property) in references(resource) {
+                if target != *name && declared.is_some_and(|d| d.contains_key(&target)) {
+                    edges.insert(TopologyEdge {
+                        from: name.clone(),
+                        to: target,
+                        kind: reference_kind(&property).to_string(),
+                    });
+                }
+            }
+        }
+        let node_of = |resource: &str| {
+            nodes.iter()
+                .find(|node| node.name == resource || live.iter().any(|r| r.name == resource && r.matches(&node.name)))
+                .map(|node| node.name.clone())
+        };
+        for resource in live {
+            let Some(from) = node_of(&resource.name) else {
+                continue;
+            };
+            for relationship in resource.relationships.iter().flatten() {
+                let Some(to) = node_of(&relationship.resource_name).filter(|to| *to != from) else {
+                    continue;
+                };
+                let kind = snake_case(&relationship.relationship_type);
+                // The manifest already says how a reference is used
+                if kind == "reference" && edges.iter().any(|e: &TopologyEdge| e.from == from && e.to == to) {
+                    continue;
+                }
+                edges.insert(TopologyEdge { from: from.clone(), to, kind });
+            }
+        }
+
+        Self { nodes, edges: edges.into_iter().collect() }
+    }
+
+    pub fn render(&self, format: MapFormat) -> Option<String> {
+        match format {
+            MapFormat::Json => None,
+            MapFormat::Dot => Some(self.to_dot()),
+            MapFormat::Mermaid => Some(self.to_mermaid()),
+        }
+    }
+
+    /// Graphviz digraph with nodes filled by health; external nodes are dashed
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph services {\n  rankdir=LR;\n  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+        for node in &self.nodes {
+            let (fill, stroke) = health_colors(node.health);
+            let style = if node.external { "rounded,filled,dashed" } else { "rounded,filled" };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" [label=\"{}\\n{}\", fillcolor=\"{}\", color=\"{}\", style=\"{}\"];",
+                escape_dot(&node.name), escape_dot(&node.name), escape_dot(&node_caption(node)), fill, stroke, style
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape_dot(&edge.from), escape_dot(&edge.to), edge.kind
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Mermaid flowchart with a class per health state
+    pub fn to_mermaid(&self) -> String {
+        let ids: HashMap<&str, String> = self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.name.as_str(), format!("n{}", index)))
+            .collect();
+
+        let mut mermaid = String::from("flowchart LR\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                mermaid,
+                "  {}[\"{}<br/>{}\"]:::{}",
+                ids[node.name.as_str()], escape_mermaid(&node.name), escape_mermaid(&node_caption(node)), health_class(node)
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(mermaid, "  {} -->|{}| {}", ids[edge.from.as_str()], edge.kind, ids[edge.to.as_str()]);
+        }
+        for health in [None, Some(HealthState::Starting), Some(HealthState::Healthy), Some(HealthState::Degraded), Some(HealthState::Unhealthy)] {
+            let (fill, stroke) = health_colors(health);
+            let class = health.map_or("unknown", |h| h.as_str());
+            let _ = writeln!(mermaid, "  classDef {} fill:{},stroke:{}", class, fill, stroke);
+        }
+        let (fill, stroke) = health_colors(None);
+        let _ = writeln!(mermaid, "  classDef external fill:{},stroke:{},stroke-dasharray:5 5", fill, stroke);
+        mermaid
+    }
+}
+
+/// Targets referenced by a manifest resource, with the property read from each
+fn references(resource: &Value) -> BTreeSet<(String, String)> {
+    let mut strings = Vec::new();
+    collect_strings(resource, &mut strings);
+    strings
+        .into_iter()
+        .flat_map(|text| MANIFEST_REFERENCE.captures_iter(text).map(|c| (c[1].to_string(), c[2].to_string())).collect::<Vec<_>>())
+        .collect()
+}
+
+fn reference_kind(property: &str) -> &'static str {
+    match property {
+        "connectionString" => "connection_string",
+        "bindings" => "endpoint",
+        "value" | "inputs" => "parameter",
+        _ => "reference",
+    }
+}
+
+fn strip_version(resource_type: &str) -> &str {
+    resource_type
+        .rsplit_once('.')
+        .filter(|(_, version)| version.len() > 1 && version.starts_with('v') && version[1..].chars().all(|c| c.is_ascii_digit()))
+        .map_or(resource_type, |(base, _)| base)
+}
+
+fn snake_case(text: &str) -> String {
+    let mut snake = String::new();
+    for (index, c) in text.chars().enumerate() {
+        if c.is_ascii_uppercase() && index > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+fn node_caption(node: &TopologyNode) -> String {
+    let mut caption = node.resource_type.clone();
+    if node.replicas > 1 {
+        caption.push_str(&format!(" ×{}", node.replicas));
+    }
+    if let Some(health) = node.health {
+        caption.push_str(&format!(" ({})", health.as_str()));
+    }
+    caption
+}
+
+fn health_class(node: &TopologyNode) -> &'static str {
+    match node.health {
+        Some(health) => health.as_str(),
+        None if node.external => "external",
+        None => "unknown",
+    }
+}
+
+/// Fill and border colors
+fn health_colors(health: Option<HealthState>) -> (&'static str, &'static str) {
+    match health {
+        Some(HealthState::Healthy) => ("#c8e6c9", "#2e7d32"),
+        Some(HealthState::Starting) => ("#bbdefb", "#1565c0"),
+        Some(HealthState::Degraded) => ("#ffe0b2", "#ef6c00"),
+        Some(HealthState::Unhealthy) => ("#ffcdd2", "#c62828"),
+        None => ("#eeeeee", "#757575"),
+    }
+}
+
+/// Same lowercase values as `ServiceStatus::health`
+fn serialize_health<S: serde::Serializer>(health: &Option<HealthState>, serializer: S) -> Result<S::Ok, S::Error> {
+    health.map(|h| h.as_str()).serialize(serializer)
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+fn collect_strings<'a>(value: &'a Value, strings: &mut Vec<&'a str>) {
+    match value {
+        Value::String(text) => strings.push(text),
+        Value::Array(items) => items.iter().for_each(|item| collect_strings(item, strings)),
+        Value::Object(map) => map.values().for_each(|item| collect_strings(item, strings)),
+        _ => {}
+    }
+}
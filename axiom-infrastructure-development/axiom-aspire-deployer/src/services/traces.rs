@@ -0,0 +1,338 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::clients::aspire::{AspireDashboardClient, SpanStatus, TraceSpan};
+
+/// Traces returned by a search unless a limit is given
+pub const DEFAULT_TRACE_LIMIT: usize = 20;
+
+/// Attribute values longer than this are cut unless the caller asks otherwise
+pub const DEFAULT_MAX_ATTRIBUTE_CHARS: usize = 200;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TraceError {
+    #[error("Trace '{trace_id}' not found; the dashboard may have evicted it")]
+    NotFound { trace_id: String },
+}
+
+/// Whether any span of a trace failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStatus {
+    Ok,
+    Error,
+}
+
+impl TraceStatus {
+    pub fn parse(status: &str) -> Option<Self> {
+        match status.to_ascii_lowercase().as_str() {
+            "ok" => Some(TraceStatus::Ok),
+            "error" => Some(TraceStatus::Error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceQuery {
+    /// Only traces with a span from this service
+    pub service: Option<String>,
+    /// Only traces at least this long
+    pub min_duration: Option<Duration>,
+    pub status: Option<TraceStatus>,
+    /// Bounds on the start of the trace
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Most recent traces to return
+    pub limit: usize,
+}
+
+impl Default for TraceQuery {
+    fn default() -> Self {
+        Self {
+            service: None,
+            min_duration: None,
+            status: None,
+            since: None,
+            until: None,
+            limit: DEFAULT_TRACE_LIMIT,
+        }
+    }
+}
+
+impl TraceQuery {
+    fn matches(&self, summary: &TraceSummary) -> bool {
+        self.service.as_ref().is_none_or(|service| summary.services.contains(service))
+            && self.min_duration.is_none_or(|min| summary.duration_ms >= min.as_secs_f64() * 1000.0)
+            && self.status.is_none_or(|status| summary.status == status)
+            && self.since.is_none_or(|since| summary.start >= since)
+            && self.until.is_none_or(|until| summary.start <= until)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceSummary {
+    pub trace_id: String,
+    /// Name of the root span, or of the earliest span when the root is missing
+    pub root: String,
+    pub root_service: String,
+    pub services: BTreeSet<String>,
+    pub start: DateTime<Utc>,
+    pub duration_ms: f64,
+    pub span_count: usize,
+    pub error_count: usize,
+    pub status: TraceStatus,
+}
+
+/// A span with its children, timed relative to the start of the trace
+#[derive(Debug, Clone, Serialize)]
+pub struct SpanNode {
+    pub span_id: String,
+    pub name: String,
+    pub service: String,
+    pub kind: String,
+    pub offset_ms: f64,
+    pub duration_ms: f64,
+    pub status: SpanStatus,
+    pub status_message: Option<String>,
+    /// Values longer than the attribute limit are cut and marked
+    pub attributes: BTreeMap<String, Value>,
+    pub events: Vec<SpanEventNode>,
+    pub children: Vec<SpanNode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpanEventNode {
+    pub name: String,
+    pub offset_ms: f64,
+    pub attributes: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceTree {
+    #[serde(flatten)]
+    pub summary: TraceSummary,
+    /// Root spans; spans whose parent was not exported are roots too
+    pub spans: Vec<SpanNode>,
+    /// The same tree as indented text, one line per span
+    pub text: String,
+}
+
+/// Searches and expands the traces held by the dashboard's OTLP store
+pub struct TraceExplorer {
+    client: Arc<AspireDashboardClient>,
+}
+
+impl TraceExplorer {
+    pub fn new(client: Arc<AspireDashboardClient>) -> Self {
+        Self { client }
+    }
+
+    /// Matching traces, most recent first
+    pub async fn search(&self, query: &TraceQuery) -> Result<Vec<TraceSummary>> {
+        let mut traces: HashMap<String, Vec<TraceSpan>> = HashMap::new();
+        for span in self.client.get_otlp_traces().await? {
+            traces.entry(span.trace_id.clone()).or_default().push(span);
+        }
+
+        let mut summaries: Vec<TraceSummary> = traces
+            .values()
+            .map(|spans| summarize(spans))
+            .filter(|summary| query.matches(summary))
+            .collect();
+        summaries.sort_by(|a, b| b.start.cmp(&a.start).then_with(|| a.trace_id.cmp(&b.trace_id)));
+        summaries.truncate(query.limit);
+
+        debug!("Found {} matching traces", summaries.len());
+        Ok(summaries)
+    }
+
+    /// The span tree of a trace; `max_attribute_chars` of 0 leaves attributes out
+    pub async fn get_trace(&self, trace_id: &str, max_attribute_chars: usize) -> Result<TraceTree> {
+        let spans = self.client.get_otlp_trace(trace_id).await?;
+        if spans.is_empty() {
+            return Err(TraceError::NotFound { trace_id: trace_id.to_string() }.into());
+        }
+        Ok(build_tree(&spans, max_attribute_chars))
+    }
+}
+
+/// Summarize the spans of one trace
+pub fn summarize(spans: &[TraceSpan]) -> TraceSummary {
+    let start = spans.iter().map(|s| s.start_time_unix_nano).min().unwrap_or(0);
+    let end = spans.iter().map(|s| s.end_time_unix_nano).max().unwrap_or(start);
+    let ids: BTreeSet<&str> = spans.iter().map(|s| s.span_id.as_str()).collect();
+    let root = spans
+        .iter()
+        .filter(|s| s.parent_span_id.as_deref().is_none_or(|parent| !ids.contains(parent)))
+        .min_by_key(|s| s.start_time_unix_nano);
+    let error_count = spans.iter().filter(|s| s.status == SpanStatus::Error).count();
+
+    TraceSummary {
+        trace_id: spans.first().map(|s| s.trace_id.clone()).unwrap_or_default(),
+        root: root.map(|s| s.name.clone()).unwrap_or_default(),
+        root_service: root.map(|s| s.service.clone()).unwrap_or_default(),
+        services: spans.iter().map(|s| s.service.clone()).collect(),
+        start: timestamp(start),
+        duration_ms: millis(end.saturating_sub(start)),
+        span_count: spans.len(),
+        error_count,
+        status: if error_count > 0 { TraceStatus::Error } else { TraceStatus::Ok },
+    }
+}
+
+/// Arrange the spans of one trace into a tree and render it as text
+pub fn build_tree(spans: &[TraceSpan], max_attribute_chars: usize) -> TraceTree {
+    let summary = summarize(spans);
+    let start = spans.iter().map(|s| s.start_time_unix_nano).min().unwrap_or(0);
+    let ids: BTreeSet<&str> = spans.iter().map(|s| s.span_id.as_str()).collect();
+
+    let mut children: HashMap<&str, Vec<&TraceSpan>> = HashMap::new();
+    let mut roots = Vec::new();
+    for span in spans {
+        match span.parent_span_id.as_deref().filter(|parent| ids.contains(parent)) {
+            Some(parent) => children.entry(parent).or_default().push(span),
+            None => roots.push(span),
+        }
+    }
+
+    let mut nodes: Vec<SpanNode> = roots
+        .into_iter()
+        .map(|span| span_node(span, &children, start, max_attribute_chars))
+        .collect();
+    nodes.sort_by(|a, b| a.offset_ms.total_cmp(&b.offset_ms));
+
+    let mut text = format!(
+        "trace {} {} {:.1}ms, {} spans, {} error{}, services: {}\n",
+        summary.trace_id,
+        summary.start.to_rfc3339(),
+        summary.duration_ms,
+        summary.span_count,
+        summary.error_count,
+        if summary.error_count == 1 { "" } else { "s" },
+        summary.services.iter().cloned().collect::<Vec<_>>().join(", ")
+    );
+    for node in &nodes {
+        render(node, 0, &mut text);
+    }
+
+    TraceTree { summary, spans: nodes, text }
+}
+
+fn span_node(
+    span: &TraceSpan,
+    children: &HashMap<&str, Vec<&TraceSpan>>,
+    trace_start: u64,
+    max_attribute_chars: usize,
+) -> SpanNode {
+    let mut nested: Vec<SpanNode> = children
+        .get(span.span_id.as_str())
+        .into_iter()
+        .flatten()
+        .map(|child| span_node(child, children, trace_start, max_attribute_chars))
+        .collect();
+    nested.sort_by(|a, b| a.offset_ms.total_cmp(&b.offset_ms));
+
+    SpanNode {
+        span_id: span.span_id.clone(),
+        name: span.name.clone(),
+        service: span.service.clone(),
+        kind: span.kind.clone(),
+        offset_ms: millis(span.start_time_unix_nano.saturating_sub(trace_start)),
+        duration_ms: millis(span.duration_nanos()),
+        status: span.status,
+        status_message: span.status_message.clone(),
+        attributes: truncate_attributes(&span.attributes, max_attribute_chars),
+        events: span.events
+            .iter()
+            .map(|event| SpanEventNode {
+                name: event.name.clone(),
+                offset_ms: millis(event.time_unix_nano.saturating_sub(trace_start)),
+                attributes: truncate_attributes(&event.attributes, max_attribute_chars),
+            })
+            .collect(),
+        children: nested,
+    }
+}
+
+/// One line per span, its attributes and events on indented lines below it
+fn render(node: &SpanNode, depth: usize, text: &mut String) {
+    let indent = "  ".repeat(depth);
+    let _ = write!(
+        text,
+        "{}{} [{} {}] +{:.1}ms {:.1}ms",
+        indent, node.name, node.service, node.kind, node.offset_ms, node.duration_ms
+    );
+    if node.status == SpanStatus::Error {
+        text.push_str(" ERROR");
+        if let Some(message) = &node.status_message {
+            let _ = write!(text, ": {}", message);
+        }
+    }
+    text.push('\n');
+
+    if !node.attributes.is_empty() {
+        let _ = writeln!(text, "{}  | {}", indent, render_attributes(&node.attributes));
+    }
+    for event in &node.events {
+        let _ = write!(text, "{}  * {} +{:.1}ms", indent, event.name, event.offset_ms);
+        if !event.attributes.is_empty() {
+            let _ = write!(text, " {}", render_attributes(&event.attributes));
+        }
+        text.push('\n');
+    }
+    for child in &node.children {
+        render(child, depth + 1, text);
+    }
+}
+
+fn render_attributes(attributes: &BTreeMap<String, Value>) -> String {
+    attributes
+        .iter()
+        .map(|(key, value)| match value {
+            Value::String(text) => format!("{}={}", key, text),
+            other => format!("{}={}", key, other),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cut values longer than `max_chars`, noting how much was left out
+fn truncate_attributes(attributes: &HashMap<String, Value>, max_chars: usize) -> BTreeMap<String, Value> {
+    if max_chars == 0 {
+        return BTreeMap::new();
+    }
+
+    attributes
+        .iter()
+        .map(|(key, value)| {
+            let text = match value {
+                Value::String(text) => text.clone(),
+                Value::Array(_) | Value::Object(_) => value.to_string(),
+                _ => return (key.clone(), value.clone()),
+            };
+            let length = text.chars().count();
+            if length <= max_chars {
+                return (key.clone(), value.clone());
+            }
+            let kept: String = text.chars().take(max_chars).collect();
+            (key.clone(), Value::String(format!("{}… [+{} chars]", kept, length - max_chars)))
+        })
+        .collect()
+}
+
+fn millis(nanos: u64) -> f64 {
+    (nanos as f64 / 1_000.0).round() / 1_000.0
+}
+
+fn timestamp(unix_nano: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp((unix_nano / 1_000_000_000) as i64, (unix_nano % 1_000_000_000) as u32)
+        .unwrap_or_default()
+}
@@ -0,0 +1,74 @@
+//! Sequential vs. concurrent writing of a large synthetic generation
+//!
+//! Mirrors the output of a 60-service schema: per service a client actor,
+//! action enum, state struct, test file and contract file.
+
+use axiom_universal_client_generator::utils::file_manager::{
+    FileManager, PendingWrite, DEFAULT_WRITE_CONCURRENCY,
+};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::path::Path;
+use tempfile::TempDir;
+
+const SERVICES: usize = 60;
+const FILES_PER_SERVICE: [&str; 5] = ["Client", "Action", "State", "ClientTests", "Contracts"];
+
+fn synthetic_writes(output_dir: &Path) -> Vec<PendingWrite> {
+    let body = "    public let value: String\n".repeat(400);
+
+    (0..SERVICES)
+        .flat_map(|i| {
+            FILES_PER_SERVICE.iter().map(move |suffix| (i, *suffix))
+        })
+        .map(|(i, suffix)| {
+            PendingWrite::new(
+                output_dir.join(format!("Service{}{}.swift", i, suffix)),
+                format!("// Service{}{}\nimport Foundation\n\n{}", i, suffix, body),
+            )
+        })
+        .collect()
+}
+
+fn bench_file_writes(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("write_large_synthetic_generation");
+
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || {
+                let dir = TempDir::new().unwrap();
+                let writes = synthetic_writes(dir.path());
+                (dir, writes)
+            },
+            |(_dir, writes)| {
+                runtime.block_on(async {
+                    for write in &writes {
+                        FileManager::write_file(&write.path, &write.content, true).await.unwrap();
+                    }
+                })
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function("concurrent", |b| {
+        b.iter_batched(
+            || {
+                let dir = TempDir::new().unwrap();
+                let writes = synthetic_writes(dir.path());
+                (dir, writes)
+            },
+            |(_dir, writes)| {
+                runtime.block_on(async {
+                    FileManager::write_files(writes, true, DEFAULT_WRITE_CONCURRENCY).await.unwrap();
+                })
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_file_writes);
+criterion_main!(benches);
@@ -0,0 +1,319 @@
+//! Typed builder for [`GenerateRequest`]
+//!
+//! Embedding tools should prefer the builder over filling in the request's
+//! nested `Option` configuration by hand: defaults match the CLI, and
+//! conflicting settings are rejected at [`GenerateRequestBuilder::build`].
+//!
+//! ```
+//! use axiom_universal_client_generator::GenerateRequest;
+//!
+//! let request = GenerateRequest::builder("proto/task_service.proto", "generated")
+//!     .language("swift")
+//!     .swift_package_name("TaskModule")
+//!     .include_documentation(false)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(request.target_languages, vec!["swift"]);
+//! ```
+
+use crate::error::{Error, Result};
+use crate::utils::naming::is_valid_swift_package_name;
+use crate::validation::swift::{is_warning_category, WARNING_CATEGORIES};
+use crate::{FrameworkConfig, GenerateRequest, GenerationOptions, SwiftConfig};
+
+/// Builder for [`GenerateRequest`] with validation at build time
+#[derive(Debug, Clone, Default)]
+pub struct GenerateRequestBuilder {
+    proto_path: String,
+    output_path: String,
+    languages: Option<Vec<String>>,
+    services: Option<Vec<String>>,
+    swift_axiom_version: Option<String>,
+    swift_client_suffix: Option<String>,
+    swift_package_name: Option<String>,
+    split_contracts_module: Option<bool>,
+    generate_contracts: Option<bool>,
+    generate_clients: Option<bool>,
+    generate_tests: Option<bool>,
+    force_overwrite: Option<bool>,
+    include_documentation: Option<bool>,
+    style_guide: Option<String>,
+    file_header: Option<String>,
+    generate_previews: Option<bool>,
+    strict: Option<bool>,
+    strict_categories: Option<Vec<String>>,
+    max_memory_warn_mb: Option<u64>,
+}
+
+impl GenerateRequestBuilder {
+    /// Start a request for the given proto input and output directory
+    pub fn new(proto_path: impl Into<String>, output_path: impl Into<String>) -> Self {
+        Self {
+            proto_path: proto_path.into(),
+            output_path: output_path.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a target language (defaults to Swift when none are set)
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.languages.get_or_insert_with(Vec::new).push(language.into());
+        self
+    }
+
+    /// Replace the target languages
+    pub fn languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.languages = Some(languages.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Restrict generation to a single additional service
+    pub fn service(mut self, service: impl Into<String>) -> Self {
+        self.services.get_or_insert_with(Vec::new).push(service.into());
+        self
+    }
+
+    /// Restrict generation to the given services (all services when unset)
+    pub fn services<I, S>(mut self, services: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.services = Some(services.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Target Axiom Swift framework version
+    pub fn swift_axiom_version(mut self, version: impl Into<String>) -> Self {
+        self.swift_axiom_version = Some(version.into());
+        self
+    }
+
+    /// Suffix for generated Swift client types
+    pub fn swift_client_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.swift_client_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Swift module holding the generated contracts; client files import it
+    pub fn swift_package_name(mut self, package_name: impl Into<String>) -> Self {
+        self.swift_package_name = Some(package_name.into());
+        self
+    }
+
+    /// Generate a Package.swift with the contracts and clients as separate
+    /// products (requires [`swift_package_name`](Self::swift_package_name))
+    pub fn split_contracts_module(mut self, enabled: bool) -> Self {
+        self.split_contracts_module = Some(enabled);
+        self
+    }
+
+    /// Generate contract/model files (default: true)
+    pub fn generate_contracts(mut self, enabled: bool) -> Self {
+        self.generate_contracts = Some(enabled);
+        self
+    }
+
+    /// Generate framework client files (default: true)
+    pub fn generate_clients(mut self, enabled: bool) -> Self {
+        self.generate_clients = Some(enabled);
+        self
+    }
+
+    /// Generate test files (default: true)
+    pub fn generate_tests(mut self, enabled: bool) -> Self {
+        self.generate_tests = Some(enabled);
+        self
+    }
+
+    /// Overwrite existing files without confirmation (default: false)
+    pub fn force_overwrite(mut self, enabled: bool) -> Self {
+        self.force_overwrite = Some(enabled);
+        self
+    }
+
+    /// Include documentation comments and files (default: true)
+    pub fn include_documentation(mut self, enabled: bool) -> Self {
+        self.include_documentation = Some(enabled);
+        self
+    }
+
+    /// Code style guide to follow (default: "axiom")
+    pub fn style_guide(mut self, style_guide: impl Into<String>) -> Self {
+        self.style_guide = Some(style_guide.into());
+        self
+    }
+
+    /// License header for generated files: inline text or `@path/to/header.txt`
+    pub fn file_header(mut self, file_header: impl Into<String>) -> Self {
+        self.file_header = Some(file_header.into());
+        self
+    }
+
+    /// Generate SwiftUI preview states for each service's state (default: false)
+    pub fn generate_previews(mut self, enabled: bool) -> Self {
+        self.generate_previews = Some(enabled);
+        self
+    }
+
+    /// Treat validation warnings as errors (default: false)
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = Some(enabled);
+        self
+    }
+
+    /// Limit strict mode to these warning categories, e.g. "access_control"
+    pub fn strict_categories<I, S>(mut self, categories: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.strict_categories = Some(categories.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Log a warning when the parsed schema is estimated above this many megabytes
+    pub fn max_memory_warn_mb(mut self, megabytes: u64) -> Self {
+        self.max_memory_warn_mb = Some(megabytes);
+        self
+    }
+
+    /// Validate the configuration and produce the request
+    ///
+    /// ```
+    /// use axiom_universal_client_generator::GenerateRequest;
+    ///
+    /// // Tests are generated against clients, so disabling clients conflicts
+    /// let result = GenerateRequest::builder("api.proto", "out")
+    ///     .generate_clients(false)
+    ///     .generate_tests(true)
+    ///     .build();
+    ///
+    /// assert!(result.is_err());
+    /// ```
+    pub fn build(self) -> Result<GenerateRequest> {
+        if self.proto_path.trim().is_empty() {
+            return Err(Error::ConfigError("proto_path must not be empty".to_string()));
+        }
+        if self.output_path.trim().is_empty() {
+            return Err(Error::ConfigError("output_path must not be empty".to_string()));
+        }
+
+        let mut languages = Vec::new();
+        for language in self.languages.unwrap_or_else(|| vec!["swift".to_string()]) {
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+        if languages.is_empty() {
+            return Err(Error::ConfigError("At least one target language is required".to_string()));
+        }
+        if languages.iter().any(|l| l.trim().is_empty()) {
+            return Err(Error::ConfigError("Target language names must not be empty".to_string()));
+        }
+
+        if let Some(services) = &self.services {
+            if services.iter().any(|s| s.trim().is_empty()) {
+                return Err(Error::ConfigError("Service names must not be empty".to_string()));
+            }
+        }
+
+        if self.generate_contracts == Some(false) && self.generate_clients == Some(false) {
+            return Err(Error::ConfigError(
+                "generate_contracts and generate_clients are both disabled; nothing would be generated".to_string(),
+            ));
+        }
+        if self.generate_previews == Some(true) && self.generate_clients == Some(false) {
+            return Err(Error::ConfigError(
+                "generate_previews requires generate_clients; previews extend the generated state".to_string(),
+            ));
+        }
+        if let Some(categories) = &self.strict_categories {
+            if self.strict == Some(false) {
+                return Err(Error::ConfigError(
+                    "strict_categories requires strict mode".to_string(),
+                ));
+            }
+            if let Some(unknown) = categories.iter().find(|c| !is_warning_category(c)) {
+                return Err(Error::ConfigError(format!(
+                    "Unknown strict category '{}'; expected one of: {}",
+                    unknown,
+                    WARNING_CATEGORIES.join(", ")
+                )));
+            }
+        }
+        if self.max_memory_warn_mb == Some(0) {
+            return Err(Error::ConfigError(
+                "max_memory_warn_mb must be greater than zero".to_string(),
+            ));
+        }
+        if self.generate_tests == Some(true) && self.generate_clients == Some(false) {
+            return Err(Error::ConfigError(
+                "generate_tests requires generate_clients; tests are generated against client actors".to_string(),
+            ));
+        }
+
+        if let Some(package_name) = &self.swift_package_name {
+            if !is_valid_swift_package_name(package_name) {
+                return Err(Error::ConfigError(format!(
+                    "'{}' is not a valid Swift module name",
+                    package_name
+                )));
+            }
+        }
+        if self.split_contracts_module == Some(true) && self.swift_package_name.is_none() {
+            return Err(Error::ConfigError(
+                "split_contracts_module requires swift_package_name; it names the contracts module".to_string(),
+            ));
+        }
+
+        let has_swift_config = self.swift_axiom_version.is_some()
+            || self.swift_client_suffix.is_some()
+            || self.swift_package_name.is_some()
+            || self.split_contracts_module.is_some();
+        let framework_config = has_swift_config.then(|| FrameworkConfig {
+            swift: Some(SwiftConfig {
+                axiom_version: self.swift_axiom_version,
+                client_suffix: self.swift_client_suffix.or_else(|| Some("Client".to_string())),
+                generate_tests: self.generate_tests,
+                package_name: self.swift_package_name,
+                split_contracts_module: self.split_contracts_module,
+            }),
+            kotlin: None,
+        });
+
+        let defaults = GenerationOptions::default();
+        let generation_options = GenerationOptions {
+            generate_contracts: self.generate_contracts.or(defaults.generate_contracts),
+            generate_clients: self.generate_clients.or(defaults.generate_clients),
+            generate_tests: self.generate_tests.or(defaults.generate_tests),
+            force_overwrite: self.force_overwrite.or(defaults.force_overwrite),
+            include_documentation: self.include_documentation.or(defaults.include_documentation),
+            style_guide: self.style_guide.or(defaults.style_guide),
+            file_header: self.file_header,
+            generate_previews: self.generate_previews.or(defaults.generate_previews),
+            // Naming categories implies strict mode
+            strict: if self.strict_categories.is_some() {
+                Some(true)
+            } else {
+                self.strict.or(defaults.strict)
+            },
+            strict_categories: self.strict_categories,
+            max_memory_warn_mb: self.max_memory_warn_mb,
+        };
+
+        Ok(GenerateRequest {
+            proto_path: self.proto_path,
+            output_path: self.output_path,
+            target_languages: languages,
+            services: self.services,
+            framework_config,
+            generation_options: Some(generation_options),
+        })
+    }
+}
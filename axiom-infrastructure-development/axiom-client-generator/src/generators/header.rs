@@ -0,0 +1,107 @@
+//! License/copyright header injection for generated source files
+//!
+//! Headers come from `GenerationOptions::file_header`, either as inline text
+//! or as `@path/to/header.txt`, and support the `{year}`, `{file_name}` and
+//! `{generator_version}` placeholders.
+
+use crate::error::{Error, Result};
+use chrono::Datelike;
+use std::path::Path;
+
+/// Header prepended to every generated source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHeader {
+    template: String,
+}
+
+impl FileHeader {
+    /// Create a header from already-resolved template text
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    /// Resolve a header spec: inline text, or `@path` to read from a file
+    pub fn resolve(spec: &str) -> Result<Self> {
+        let template = match spec.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path).map_err(|e| {
+                Error::ConfigError(format!("Failed to read file header from {}: {}", path, e))
+            })?,
+            None => spec.to_string(),
+        };
+
+        Ok(Self::new(template.trim_end()))
+    }
+
+    /// Render the header for a file, as Swift/Kotlin line comments
+    pub fn render(&self, file_name: &str) -> String {
+        let text = self
+            .template
+            .replace("{year}", &chrono::Utc::now().year().to_string())
+            .replace("{file_name}", file_name)
+            .replace("{generator_version}", env!("CARGO_PKG_VERSION"));
+
+        // Headers that are already comments are kept verbatim
+        let trimmed = text.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with("/*") {
+            return format!("{}\n", text);
+        }
+
+        text.lines()
+            .map(|line| {
+                if line.trim().is_empty() {
+                    "//\n".to_string()
+                } else {
+                    format!("// {}\n", line)
+                }
+            })
+            .collect()
+    }
+
+    /// Insert the header at the top of `content`, before any imports.
+    ///
+    /// `Package.swift` must keep its `swift-tools-version` line first, so the
+    /// header goes directly below it there. Content that already starts with
+    /// this header is returned unchanged, so regenerating never duplicates it.
+    pub fn apply(&self, file_path: &Path, content: &str) -> String {
+        let file_name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let header = self.render(file_name);
+
+        let (preamble, body) = match content.split_once('\n') {
+            Some((first, rest)) if first.starts_with("// swift-tools-version") => {
+                (format!("{}\n", first), rest)
+            }
+            _ => (String::new(), content),
+        };
+
+        if body.starts_with(&header) {
+            return content.to_string();
+        }
+
+        format!("{}{}\n{}", preamble, header, body)
+    }
+}
+
+/// Number of leading lines that are comments or blank (the file header region)
+pub fn leading_comment_lines(content: &str) -> usize {
+    let mut in_block = false;
+    content
+        .lines()
+        .take_while(|line| {
+            let line = line.trim();
+            if in_block {
+                in_block = !line.contains("*/");
+                return true;
+            }
+            if line.starts_with("/*") {
+                in_block = !line.contains("*/");
+                return true;
+            }
+            line.is_empty() || line.starts_with("//")
+        })
+        .count()
+}
@@ -0,0 +1,109 @@
+//! Platform-neutral identifier resolution
+//!
+//! Collection and entity names are inferred the same way for every target
+//! language; only the final casing is platform specific. Generators and the
+//! naming audit share these helpers so they cannot drift apart.
+
+use crate::proto::types::{Method, Service};
+
+/// Resolve the state collection a method operates on
+///
+/// Order of precedence: the explicit `collection_name` method option, then
+/// inference from the method and response names, then `collection_property_name`
+/// applied to the response entity.
+pub fn resolve_collection_name(
+    method: &Method,
+    service: &Service,
+    collection_property_name: impl Fn(&str) -> String,
+) -> String {
+    method.options.axiom_method
+        .as_ref()
+        .and_then(|opts| opts.collection_name.clone())
+        .or_else(|| infer_collection_name_from_method(method, service))
+        .unwrap_or_else(|| collection_property_name(&extract_entity_name(&method.output_type)))
+}
+
+/// Infer collection name from method patterns
+pub fn infer_collection_name_from_method(method: &Method, service: &Service) -> Option<String> {
+    let method_lower = method.name.to_lowercase();
+    
+    // Look for patterns like "getTasks", "listUsers", etc.
+    if method_lower.starts_with("get") || method_lower.starts_with("list") || method_lower.starts_with("find") {
+        if let Some(entity) = extract_collection_from_method_name(&method.name) {
+            return Some(entity);
+        }
+    }
+
+    // Look for patterns in the output type
+    if method.output_type.contains("Response") {
+        // For types like GetTasksResponse, extract "tasks"
+        if let Some(entity) = extract_collection_from_response_type(&method.output_type) {
+            return Some(entity);
+        }
+    }
+
+    // Default to service-based collection name
+    let service_entity = service.name.replace("Service", "");
+    Some(format!("{}s", service_entity.to_lowercase()))
+}
+
+/// Extract collection name from method name like "getTasks" -> "tasks"
+fn extract_collection_from_method_name(method_name: &str) -> Option<String> {
+    let prefixes = ["get", "list", "find", "fetch", "load"];
+    
+    for prefix in &prefixes {
+        if method_name.to_lowercase().starts_with(prefix) {
+            let remaining = &method_name[prefix.len()..];
+            if !remaining.is_empty() {
+                return Some(remaining.to_lowercase());
+            }
+        }
+    }
+    
+    None
+}
+
+/// Extract collection name from response type like "GetTasksResponse" -> "tasks"
+fn extract_collection_from_response_type(type_name: &str) -> Option<String> {
+    let clean_name = clean_type_name(type_name);
+    
+    if clean_name.starts_with("Get") && clean_name.ends_with("Response") {
+        let middle = &clean_name[3..clean_name.len() - 8]; // Remove "Get" and "Response"
+        if !middle.is_empty() {
+            return Some(middle.to_lowercase());
+        }
+    }
+    
+    None
+}
+
+/// Extract entity name from type name
+pub fn extract_entity_name(type_name: &str) -> String {
+    let clean_name = clean_type_name(type_name);
+    
+    // Remove common suffixes
+    if clean_name.ends_with("Response") {
+        let without_response = &clean_name[..clean_name.len() - 8];
+        if !without_response.is_empty() {
+            return without_response.to_string();
+        }
+    }
+    
+    if clean_name.ends_with("Request") {
+        let without_request = &clean_name[..clean_name.len() - 7];
+        if !without_request.is_empty() {
+            return without_request.to_string();
+        }
+    }
+    
+    clean_name
+}
+
+/// Clean type name by removing package prefixes
+pub fn clean_type_name(type_name: &str) -> String {
+    if let Some(last_part) = type_name.rsplit('.').next() {
+        last_part.to_string()
+    } else {
+        type_name.to_string()
+    }
+}
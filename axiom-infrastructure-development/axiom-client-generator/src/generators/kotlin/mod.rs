@@ -0,0 +1,9 @@
+//! Kotlin naming conventions
+//!
+//! Kotlin code generation is not implemented yet; the naming rules live here
+//! so cross-platform tooling such as the naming audit can predict the
+//! identifiers a Kotlin client will expose.
+
+pub mod naming;
+
+pub use naming::KotlinNaming;
@@ -0,0 +1,112 @@
+use crate::utils::naming::NamingUtils;
+use heck::{ToLowerCamelCase, ToPascalCase, ToShoutySnakeCase};
+
+/// Kotlin naming convention handler
+pub struct KotlinNaming;
+
+impl KotlinNaming {
+    /// Create a new Kotlin naming handler
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Convert to Kotlin class name (PascalCase)
+    pub fn type_name(&self, name: &str) -> String {
+        name.to_pascal_case()
+    }
+
+    /// Convert to Kotlin property name (camelCase)
+    pub fn property_name(&self, name: &str) -> String {
+        self.escape_reserved_word(&name.to_lower_camel_case())
+    }
+
+    /// Convert a proto field to a Kotlin property name
+    ///
+    /// Boolean fields drop a leading `is_`: Jackson and other bean-based
+    /// serializers expose a Kotlin `isCompleted` property as `completed`,
+    /// so the Kotlin clients name the property that way from the start.
+    pub fn field_property_name(&self, field_name: &str, field_type: &str) -> String {
+        match field_name.strip_prefix("is_") {
+            Some(rest) if field_type == "bool" && !rest.is_empty() => self.property_name(rest),
+            _ => self.property_name(field_name),
+        }
+    }
+
+    /// Convert to Kotlin function name (camelCase)
+    pub fn method_name(&self, name: &str) -> String {
+        self.escape_reserved_word(&name.to_lower_camel_case())
+    }
+
+    /// Convert an RPC to its action subtype name (PascalCase sealed class member)
+    pub fn action_case_name(&self, method_name: &str) -> String {
+        method_name.to_pascal_case()
+    }
+
+    /// Convert to Kotlin enum constant (SCREAMING_SNAKE_CASE)
+    ///
+    /// Only the enum's own name is stripped as a prefix, e.g. `PRIORITY_HIGH`
+    /// in `Priority` becomes `HIGH` while `IN_PROGRESS` is kept whole.
+    pub fn enum_case_name(&self, enum_name: &str, value_name: &str) -> String {
+        let prefix = format!("{}_", enum_name.to_shouty_snake_case());
+        let cleaned = match value_name.strip_prefix(&prefix) {
+            Some(rest) if !rest.is_empty() => rest,
+            _ => value_name,
+        };
+        cleaned.to_shouty_snake_case()
+    }
+
+    /// Convert to Kotlin file name
+    pub fn file_name(&self, name: &str) -> String {
+        format!("{}.kt", name.to_pascal_case())
+    }
+
+    /// Generate Kotlin client name
+    pub fn client_name(&self, service_name: &str) -> String {
+        format!("{}Client", Self::service_base(service_name).to_pascal_case())
+    }
+
+    /// Generate Kotlin state name
+    pub fn state_name(&self, service_name: &str) -> String {
+        format!("{}State", Self::service_base(service_name).to_pascal_case())
+    }
+
+    /// Generate Kotlin action name
+    pub fn action_name(&self, service_name: &str) -> String {
+        format!("{}Action", Self::service_base(service_name).to_pascal_case())
+    }
+
+    /// Generate collection property name for state
+    pub fn collection_property_name(&self, entity_name: &str) -> String {
+        let singular = NamingUtils::singularize(entity_name);
+        NamingUtils::pluralize(&singular.to_lower_camel_case())
+    }
+
+    /// Check whether a word is a Kotlin hard keyword
+    pub fn is_reserved_word(&self, word: &str) -> bool {
+        matches!(word,
+            "as" | "break" | "class" | "continue" | "do" | "else" | "false" | "for" | "fun" |
+            "if" | "in" | "interface" | "is" | "null" | "object" | "package" | "return" |
+            "super" | "this" | "throw" | "true" | "try" | "typealias" | "typeof" | "val" |
+            "var" | "when" | "while"
+        )
+    }
+
+    /// Escape Kotlin hard keywords with backticks
+    fn escape_reserved_word(&self, word: &str) -> String {
+        if self.is_reserved_word(word) {
+            format!("`{}`", word)
+        } else {
+            word.to_string()
+        }
+    }
+
+    fn service_base(service_name: &str) -> &str {
+        service_name.strip_suffix("Service").unwrap_or(service_name)
+    }
+}
+
+impl Default for KotlinNaming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,7 +3,13 @@
 //! This module provides the core code generation functionality for
 //! generating Axiom-compatible Swift clients from proto definitions.
 
+pub mod header;
+pub mod identifiers;
+pub mod kotlin;
+pub mod naming_audit;
 pub mod registry;
 pub mod swift;
 
+pub use header::FileHeader;
+pub use naming_audit::{NamingAudit, NamingAuditReport};
 pub use registry::GeneratorRegistry;
\ No newline at end of file
@@ -0,0 +1,471 @@
+//! Cross-platform naming audit
+//!
+//! Resolves the user-facing identifiers each platform derives from a schema,
+//! without rendering templates, and reports where Swift and Kotlin disagree.
+//! Casing differences that follow platform convention (`high` vs `HIGH`) are
+//! not divergences; different words are (`isCompleted` vs `completed`).
+
+use crate::generators::identifiers::resolve_collection_name;
+use crate::generators::kotlin::naming::KotlinNaming;
+use crate::generators::swift::naming::SwiftNaming;
+use crate::proto::types::{Enum, Field, Message, ProtoSchema, Service};
+use heck::{ToShoutySnakeCase, ToSnakeCase};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Naming rules a platform applies to proto identifiers
+pub trait PlatformNaming {
+    /// Platform name used in reports, e.g. `swift`
+    fn platform(&self) -> &'static str;
+    /// Client type name for a service
+    fn client_name(&self, service_name: &str) -> String;
+    /// State type name for a service
+    fn state_name(&self, service_name: &str) -> String;
+    /// Action type name for a service
+    fn action_name(&self, service_name: &str) -> String;
+    /// Action case name for an RPC
+    fn action_case_name(&self, method_name: &str) -> String;
+    /// Collection property name for an entity, used when nothing else applies
+    fn collection_property_name(&self, entity_name: &str) -> String;
+    /// Property name for a message field
+    fn field_property_name(&self, field: &Field) -> String;
+    /// Case name for an enum value
+    fn enum_case_name(&self, enum_name: &str, value_name: &str) -> String;
+}
+
+impl PlatformNaming for SwiftNaming {
+    fn platform(&self) -> &'static str {
+        "swift"
+    }
+
+    fn client_name(&self, service_name: &str) -> String {
+        SwiftNaming::client_name(self, service_name)
+    }
+
+    fn state_name(&self, service_name: &str) -> String {
+        SwiftNaming::state_name(self, service_name)
+    }
+
+    fn action_name(&self, service_name: &str) -> String {
+        SwiftNaming::action_name(self, service_name)
+    }
+
+    fn action_case_name(&self, method_name: &str) -> String {
+        self.method_name(method_name)
+    }
+
+    fn collection_property_name(&self, entity_name: &str) -> String {
+        SwiftNaming::collection_property_name(self, entity_name)
+    }
+
+    fn field_property_name(&self, field: &Field) -> String {
+        self.property_name(&field.name)
+    }
+
+    fn enum_case_name(&self, _enum_name: &str, value_name: &str) -> String {
+        SwiftNaming::enum_case_name(self, value_name)
+    }
+}
+
+impl PlatformNaming for KotlinNaming {
+    fn platform(&self) -> &'static str {
+        "kotlin"
+    }
+
+    fn client_name(&self, service_name: &str) -> String {
+        KotlinNaming::client_name(self, service_name)
+    }
+
+    fn state_name(&self, service_name: &str) -> String {
+        KotlinNaming::state_name(self, service_name)
+    }
+
+    fn action_name(&self, service_name: &str) -> String {
+        KotlinNaming::action_name(self, service_name)
+    }
+
+    fn action_case_name(&self, method_name: &str) -> String {
+        KotlinNaming::action_case_name(self, method_name)
+    }
+
+    fn collection_property_name(&self, entity_name: &str) -> String {
+        KotlinNaming::collection_property_name(self, entity_name)
+    }
+
+    fn field_property_name(&self, field: &Field) -> String {
+        KotlinNaming::field_property_name(self, &field.name, &field.field_type)
+    }
+
+    fn enum_case_name(&self, enum_name: &str, value_name: &str) -> String {
+        KotlinNaming::enum_case_name(self, enum_name, value_name)
+    }
+}
+
+/// Kind of user-facing identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierKind {
+    ClientType,
+    StateType,
+    ActionType,
+    ActionCase,
+    Collection,
+    Property,
+    EnumCase,
+}
+
+impl IdentifierKind {
+    /// Short label used in table output
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ClientType => "client",
+            Self::StateType => "state",
+            Self::ActionType => "action",
+            Self::ActionCase => "action case",
+            Self::Collection => "collection",
+            Self::Property => "property",
+            Self::EnumCase => "enum case",
+        }
+    }
+}
+
+/// An identifier as one platform names it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedIdentifier {
+    pub kind: IdentifierKind,
+    /// Dotted proto location, e.g. `TaskService.CreateTask` or `Task.is_completed`
+    pub location: String,
+    /// The proto name the identifier was derived from
+    pub proto_name: String,
+    /// The platform identifier, including any keyword escaping
+    pub name: String,
+}
+
+impl ResolvedIdentifier {
+    /// Whether the platform escaped the identifier as a reserved word
+    pub fn is_reserved_rename(&self) -> bool {
+        self.name.starts_with('`')
+    }
+}
+
+/// Resolve every user-facing identifier one platform derives from a schema
+///
+/// The traversal order is deterministic, so results for two platforms line
+/// up index by index.
+pub fn resolve_identifiers(schema: &ProtoSchema, naming: &dyn PlatformNaming) -> Vec<ResolvedIdentifier> {
+    let mut identifiers = Vec::new();
+    for service in &schema.services {
+        resolve_service(service, naming, &mut identifiers);
+    }
+    for message in &schema.messages {
+        resolve_message(message, None, naming, &mut identifiers);
+    }
+    for enum_type in &schema.enums {
+        resolve_enum(enum_type, None, naming, &mut identifiers);
+    }
+    identifiers
+}
+
+fn resolve_service(service: &Service, naming: &dyn PlatformNaming, out: &mut Vec<ResolvedIdentifier>) {
+    let options = service.options.axiom_service.as_ref();
+    let mut push = |kind, location: String, proto_name: &str, name: String| {
+        out.push(ResolvedIdentifier {
+            kind,
+            location,
+            proto_name: proto_name.to_string(),
+            name,
+        });
+    };
+
+    push(
+        IdentifierKind::ClientType,
+        service.name.clone(),
+        &service.name,
+        options.and_then(|o| o.client_name.clone()).unwrap_or_else(|| naming.client_name(&service.name)),
+    );
+    push(
+        IdentifierKind::StateType,
+        service.name.clone(),
+        &service.name,
+        options.and_then(|o| o.state_name.clone()).unwrap_or_else(|| naming.state_name(&service.name)),
+    );
+    push(
+        IdentifierKind::ActionType,
+        service.name.clone(),
+        &service.name,
+        options.and_then(|o| o.action_name.clone()).unwrap_or_else(|| naming.action_name(&service.name)),
+    );
+
+    // Collections are recorded per method, not deduplicated, so both
+    // platforms always produce the same number of identifiers
+    for method in &service.methods {
+        let location = format!("{}.{}", service.name, method.name);
+        push(
+            IdentifierKind::ActionCase,
+            location.clone(),
+            &method.name,
+            naming.action_case_name(&method.name),
+        );
+
+        let collection = resolve_collection_name(method, service, |entity| {
+            naming.collection_property_name(entity)
+        });
+        push(IdentifierKind::Collection, location, &method.name, collection);
+    }
+}
+
+fn resolve_message(
+    message: &Message,
+    parent: Option<&str>,
+    naming: &dyn PlatformNaming,
+    out: &mut Vec<ResolvedIdentifier>,
+) {
+    let qualified = match parent {
+        Some(parent) => format!("{}.{}", parent, message.name),
+        None => message.name.clone(),
+    };
+
+    for field in &message.fields {
+        out.push(ResolvedIdentifier {
+            kind: IdentifierKind::Property,
+            location: format!("{}.{}", qualified, field.name),
+            proto_name: field.name.clone(),
+            name: naming.field_property_name(field),
+        });
+    }
+    for nested in &message.nested_messages {
+        resolve_message(nested, Some(&qualified), naming, out);
+    }
+    for nested_enum in &message.nested_enums {
+        resolve_enum(nested_enum, Some(&qualified), naming, out);
+    }
+}
+
+fn resolve_enum(
+    enum_type: &Enum,
+    parent: Option<&str>,
+    naming: &dyn PlatformNaming,
+    out: &mut Vec<ResolvedIdentifier>,
+) {
+    let qualified = match parent {
+        Some(parent) => format!("{}.{}", parent, enum_type.name),
+        None => enum_type.name.clone(),
+    };
+
+    for value in &enum_type.values {
+        out.push(ResolvedIdentifier {
+            kind: IdentifierKind::EnumCase,
+            location: format!("{}.{}", qualified, value.name),
+            proto_name: value.name.clone(),
+            name: naming.enum_case_name(&enum_type.name, &value.name),
+        });
+    }
+}
+
+/// Reduce an identifier to its words so platform casing is ignored
+fn normalize(name: &str) -> String {
+    name.trim_matches('`').to_snake_case()
+}
+
+/// An identifier Swift and Kotlin name differently
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamingDivergence {
+    pub kind: IdentifierKind,
+    pub location: String,
+    pub proto_name: String,
+    pub swift: String,
+    pub kotlin: String,
+    /// How to force both platforms to the same identifier, when possible
+    pub suggestion: Option<String>,
+}
+
+/// An identifier a platform had to escape because it is a reserved word
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservedRename {
+    pub platform: String,
+    pub kind: IdentifierKind,
+    pub location: String,
+    pub proto_name: String,
+    pub identifier: String,
+}
+
+/// Result of auditing a schema's identifiers across platforms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamingAuditReport {
+    /// Number of identifiers compared
+    pub identifiers_checked: usize,
+    pub divergences: Vec<NamingDivergence>,
+    pub reserved_renames: Vec<ReservedRename>,
+}
+
+impl NamingAuditReport {
+    /// Whether every identifier matches across platforms
+    pub fn is_aligned(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Format the report as aligned text tables
+    pub fn format_table(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{} identifiers checked, {} divergence(s), {} reserved-word rename(s)",
+            self.identifiers_checked,
+            self.divergences.len(),
+            self.reserved_renames.len()
+        );
+
+        if !self.divergences.is_empty() {
+            let rows: Vec<[&str; 4]> = self.divergences.iter()
+                .map(|d| [d.kind.label(), d.location.as_str(), d.swift.as_str(), d.kotlin.as_str()])
+                .collect();
+            let _ = writeln!(out, "\nDivergences:");
+            write_table(&mut out, ["KIND", "LOCATION", "SWIFT", "KOTLIN"], &rows);
+
+            let suggestions: Vec<&NamingDivergence> = self.divergences.iter()
+                .filter(|d| d.suggestion.is_some())
+                .collect();
+            if !suggestions.is_empty() {
+                let _ = writeln!(out, "\nSuggested overrides:");
+                for divergence in suggestions {
+                    let _ = writeln!(
+                        out,
+                        "  {}: {}",
+                        divergence.location,
+                        divergence.suggestion.as_deref().unwrap_or_default()
+                    );
+                }
+            }
+        }
+
+        if !self.reserved_renames.is_empty() {
+            let rows: Vec<[&str; 4]> = self.reserved_renames.iter()
+                .map(|r| [r.platform.as_str(), r.kind.label(), r.location.as_str(), r.identifier.as_str()])
+                .collect();
+            let _ = writeln!(out, "\nReserved-word renames:");
+            write_table(&mut out, ["PLATFORM", "KIND", "LOCATION", "IDENTIFIER"], &rows);
+        }
+
+        out
+    }
+}
+
+fn write_table(out: &mut String, headers: [&str; 4], rows: &[[&str; 4]]) {
+    let mut widths = headers.map(str::len);
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut write_row = |cells: [&str; 4]| {
+        let line = cells.iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let _ = writeln!(out, "  {}", line.trim_end());
+    };
+    write_row(headers);
+    for row in rows {
+        write_row(*row);
+    }
+}
+
+/// Audits Swift and Kotlin identifiers derived from the same schema
+pub struct NamingAudit {
+    swift: SwiftNaming,
+    kotlin: KotlinNaming,
+}
+
+impl NamingAudit {
+    /// Create an audit with the default naming rules of each platform
+    pub fn new() -> Self {
+        Self {
+            swift: SwiftNaming::new(),
+            kotlin: KotlinNaming::new(),
+        }
+    }
+
+    /// Compare the identifiers both platforms derive from `schema`
+    pub fn run(&self, schema: &ProtoSchema) -> NamingAuditReport {
+        let swift = resolve_identifiers(schema, &self.swift);
+        let kotlin = resolve_identifiers(schema, &self.kotlin);
+
+        let mut divergences = Vec::new();
+        let mut reserved_renames = Vec::new();
+
+        for (platform, identifiers) in [(self.swift.platform(), &swift), (self.kotlin.platform(), &kotlin)] {
+            for identifier in identifiers.iter().filter(|i| i.is_reserved_rename()) {
+                reserved_renames.push(ReservedRename {
+                    platform: platform.to_string(),
+                    kind: identifier.kind,
+                    location: identifier.location.clone(),
+                    proto_name: identifier.proto_name.clone(),
+                    identifier: identifier.name.clone(),
+                });
+            }
+        }
+
+        for (swift_id, kotlin_id) in swift.iter().zip(&kotlin) {
+            if normalize(&swift_id.name) != normalize(&kotlin_id.name) {
+                divergences.push(NamingDivergence {
+                    kind: swift_id.kind,
+                    location: swift_id.location.clone(),
+                    proto_name: swift_id.proto_name.clone(),
+                    swift: swift_id.name.clone(),
+                    kotlin: kotlin_id.name.clone(),
+                    suggestion: suggest_override(swift_id, kotlin_id),
+                });
+            }
+        }
+
+        NamingAuditReport {
+            identifiers_checked: swift.len(),
+            divergences,
+            reserved_renames,
+        }
+    }
+}
+
+impl Default for NamingAudit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Suggest the axiom option or proto change that aligns both platforms
+fn suggest_override(swift: &ResolvedIdentifier, kotlin: &ResolvedIdentifier) -> Option<String> {
+    let swift_name = swift.name.trim_matches('`');
+    match swift.kind {
+        IdentifierKind::ClientType => Some(format!(
+            "option (axiom_service).client_name = \"{}\";", swift_name
+        )),
+        IdentifierKind::StateType => Some(format!(
+            "option (axiom_service).state_name = \"{}\";", swift_name
+        )),
+        IdentifierKind::ActionType => Some(format!(
+            "option (axiom_service).action_name = \"{}\";", swift_name
+        )),
+        IdentifierKind::Collection => Some(format!(
+            "option (axiom_method).collection_name = \"{}\";", swift_name
+        )),
+        IdentifierKind::Property => {
+            // Rename the proto field to the shorter form; both platforms keep it as-is
+            let kotlin_words = normalize(&kotlin.name);
+            (kotlin_words != swift.proto_name).then(|| {
+                format!("rename field {} to {}", swift.proto_name, kotlin_words)
+            })
+        }
+        IdentifierKind::EnumCase => {
+            let (enum_name, _) = swift.location.rsplit_once('.')?;
+            let enum_name = enum_name.rsplit('.').next().unwrap_or(enum_name);
+            Some(format!(
+                "prefix the value with the enum name ({}_{}) so both platforms strip the same prefix",
+                enum_name.to_shouty_snake_case(),
+                swift.proto_name
+            ))
+        }
+        IdentifierKind::ActionCase => None,
+    }
+}
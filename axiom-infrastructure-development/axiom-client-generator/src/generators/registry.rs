@@ -1,9 +1,11 @@
 use crate::error::{Error, Result};
+use crate::generators::header::FileHeader;
 use crate::generators::swift;
 use crate::proto::types::ProtoSchema;
 use crate::GenerateRequest;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Registry for managing language-specific code generators
 pub struct GeneratorRegistry {
@@ -18,9 +20,11 @@ pub trait LanguageGenerator: Send + Sync {
     fn language(&self) -> &str;
 
     /// Generate code for the given schema and request
+    ///
+    /// The schema is shared with every generation context rather than copied.
     async fn generate(
         &self,
-        schema: &ProtoSchema,
+        schema: &Arc<ProtoSchema>,
         request: &GenerateRequest,
     ) -> Result<Vec<String>>;
 
@@ -45,11 +49,28 @@ impl GeneratorRegistry {
         Ok(Self { generators })
     }
 
+    /// Create a registry with no generators, for embedders that register their own
+    pub fn empty() -> Self {
+        Self {
+            generators: HashMap::new(),
+        }
+    }
+
+    /// Register a generator under its `language()` name, replacing any existing one
+    pub fn register(&mut self, generator: Box<dyn LanguageGenerator>) {
+        let language = generator.language().to_string();
+        if self.generators.insert(language.clone(), generator).is_some() {
+            tracing::info!("Replaced {} generator in registry", language);
+        } else {
+            tracing::info!("Registered {} generator", language);
+        }
+    }
+
     /// Generate code for a specific language
     pub async fn generate(
         &self,
         language: &str,
-        schema: &ProtoSchema,
+        schema: &Arc<ProtoSchema>,
         request: &GenerateRequest,
     ) -> Result<Vec<String>> {
         let generator = self.generators.get(language).ok_or_else(|| {
@@ -115,7 +136,7 @@ impl GeneratorRegistry {
     pub async fn generate_parallel(
         &self,
         languages: &[String],
-        schema: &ProtoSchema,
+        schema: &Arc<ProtoSchema>,
         request: &GenerateRequest,
     ) -> Result<HashMap<String, Vec<String>>> {
         // Validate all languages first
@@ -163,14 +184,22 @@ pub struct GenerationConfig {
     pub style_guide: String,
     /// Custom template variables
     pub template_vars: HashMap<String, String>,
+    /// License header injected into generated source files
+    pub file_header: Option<FileHeader>,
+    /// Whether to generate SwiftUI preview states
+    pub generate_previews: bool,
 }
 
 impl GenerationConfig {
     /// Create a new generation config from a request
-    pub fn from_request(request: &GenerateRequest) -> Self {
+    pub fn from_request(request: &GenerateRequest) -> Result<Self> {
         let options = request.generation_options.as_ref();
+        let file_header = options
+            .and_then(|o| o.file_header.as_deref())
+            .map(FileHeader::resolve)
+            .transpose()?;
         
-        Self {
+        Ok(Self {
             output_dir: PathBuf::from(&request.output_path),
             force_overwrite: options
                 .and_then(|o| o.force_overwrite)
@@ -182,6 +211,18 @@ impl GenerationConfig {
                 .and_then(|o| o.style_guide.clone())
                 .unwrap_or_else(|| "axiom".to_string()),
             template_vars: HashMap::new(),
+            file_header,
+            generate_previews: options
+                .and_then(|o| o.generate_previews)
+                .unwrap_or(false),
+        })
+    }
+
+    /// Prepend the configured license header (if any) to generated source
+    pub fn apply_file_header(&self, file_path: &Path, content: &str) -> String {
+        match &self.file_header {
+            Some(header) => header.apply(file_path, content),
+            None => content.to_string(),
         }
     }
 }
@@ -194,6 +235,8 @@ impl Default for GenerationConfig {
             include_documentation: true,
             style_guide: "axiom".to_string(),
             template_vars: HashMap::new(),
+            file_header: None,
+            generate_previews: false,
         }
     }
 }
@@ -203,8 +246,8 @@ impl Default for GenerationConfig {
 pub struct GenerationContext {
     /// Generation configuration
     pub config: GenerationConfig,
-    /// Proto schema being generated
-    pub schema: ProtoSchema,
+    /// Proto schema being generated, shared across contexts
+    pub schema: Arc<ProtoSchema>,
     /// Language-specific configuration
     pub language_config: HashMap<String, serde_json::Value>,
     /// Template variables
@@ -213,7 +256,7 @@ pub struct GenerationContext {
 
 impl GenerationContext {
     /// Create a new generation context
-    pub fn new(config: GenerationConfig, schema: ProtoSchema) -> Self {
+    pub fn new(config: GenerationConfig, schema: Arc<ProtoSchema>) -> Self {
         Self {
             config,
             schema,
@@ -241,6 +284,6 @@ impl GenerationContext {
 
 impl Default for GenerationContext {
     fn default() -> Self {
-        Self::new(GenerationConfig::default(), ProtoSchema::default())
+        Self::new(GenerationConfig::default(), Arc::default())
     }
 }
\ No newline at end of file
@@ -1,57 +1,87 @@
 use crate::error::{Error, Result};
+use crate::generators::identifiers::{
+    clean_type_name, extract_entity_name, infer_collection_name_from_method, resolve_collection_name,
+};
 use crate::generators::registry::GenerationContext;
-use crate::generators::swift::{naming::SwiftNaming, templates::SwiftTemplateEngine};
+use crate::generators::swift::defaults::proto3_default;
+use crate::generators::swift::{clients_module, contracts_module, naming::SwiftNaming, templates::SwiftTemplateEngine};
 use crate::proto::{metadata::MetadataExtractor, types::*};
-use crate::utils::file_manager::FileManager;
+use crate::utils::file_manager::{FileManager, PendingWrite, DEFAULT_WRITE_CONCURRENCY};
+use heck::{ToShoutySnakeCase, ToSnakeCase, ToTitleCase};
 use std::collections::{HashMap, HashSet};
 use tera::Context;
 
 /// Generate Swift client files (actors, actions, state)
+///
+/// All files are rendered before any is written, then written concurrently;
+/// the returned list keeps the deterministic render order.
 pub async fn generate_clients(
     context: &GenerationContext,
     template_engine: &SwiftTemplateEngine,
     naming: &SwiftNaming,
 ) -> Result<Vec<String>> {
-    let mut generated_files = Vec::new();
-    
     // Create clients directory
     let clients_dir = context.config.output_dir.join("swift/Clients");
     std::fs::create_dir_all(&clients_dir)?;
 
-    // Generate shared error types file
-    let error_file = generate_error_types(template_engine, context, &clients_dir).await?;
-    generated_files.push(error_file);
+    // Shared error types plus typed errors for services that declare them
+    let mut pending = render_error_types(template_engine, naming, context, &clients_dir)?;
 
-    // Generate client files for each service
+    // Client files for each service
     for service in &context.schema.services {
-        // Generate main client actor
-        let client_file = generate_client_actor(service, template_engine, naming, context, &clients_dir).await?;
-        generated_files.push(client_file);
-
-        // Generate action enum
-        let action_file = generate_action_enum(service, template_engine, naming, context, &clients_dir).await?;
-        generated_files.push(action_file);
+        pending.push(render_client_actor(service, template_engine, naming, context, &clients_dir)?);
+        pending.push(render_action_enum(service, template_engine, naming, context, &clients_dir)?);
+        pending.push(render_state_struct(service, template_engine, naming, context, &clients_dir)?);
 
-        // Generate state struct
-        let state_file = generate_state_struct(service, template_engine, naming, context, &clients_dir).await?;
-        generated_files.push(state_file);
+        if context.config.generate_previews {
+            pending.push(render_state_previews(service, template_engine, naming, context, &clients_dir)?);
+        }
 
-        // Generate tests if enabled
         if should_generate_tests(context) {
-            let test_file = generate_test_file(service, template_engine, naming, context, &clients_dir).await?;
-            generated_files.push(test_file);
+            pending.push(render_test_file(service, template_engine, naming, context, &clients_dir)?);
         }
     }
 
-    Ok(generated_files)
+    FileManager::write_files(pending, context.config.force_overwrite, DEFAULT_WRITE_CONCURRENCY).await
+}
+
+/// Render the shared error types file and one typed error file per service
+/// that declares an error enum
+fn render_error_types(
+    template_engine: &SwiftTemplateEngine,
+    naming: &SwiftNaming,
+    context: &GenerationContext,
+    output_dir: &std::path::Path,
+) -> Result<Vec<PendingWrite>> {
+    let mut pending = vec![render_shared_error_types(template_engine, context, output_dir)?];
+
+    for service in &context.schema.services {
+        let Some(service_error) = service_error_for_template(service, context, naming)? else {
+            continue;
+        };
+
+        let swift_name = service_error["swift_name"].as_str().unwrap_or_default();
+        let file_path = output_dir.join(naming.file_name(swift_name));
+
+        let mut template_context = Context::new();
+        template_context.insert("service_name", &service.name);
+        template_context.insert("error", &service_error);
+        add_swift_config_to_context(&mut template_context, context);
+
+        let content = template_engine.render_client("service_errors", &template_context)?;
+        let content = context.config.apply_file_header(&file_path, &content);
+        pending.push(PendingWrite::new(file_path, content));
+    }
+
+    Ok(pending)
 }
 
-/// Generate shared error types file
-async fn generate_error_types(
+/// Render shared error types file
+fn render_shared_error_types(
     template_engine: &SwiftTemplateEngine,
     context: &GenerationContext,
     output_dir: &std::path::Path,
-) -> Result<String> {
+) -> Result<PendingWrite> {
     let file_path = output_dir.join("AxiomErrors.swift");
 
     let mut template_context = Context::new();
@@ -66,19 +96,19 @@ async fn generate_error_types(
     add_swift_config_to_context(&mut template_context, context);
 
     let content = template_engine.render_client("error_types", &template_context)?;
-    FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
+    let content = context.config.apply_file_header(&file_path, &content);
 
-    Ok(file_path.to_string_lossy().to_string())
+    Ok(PendingWrite::new(file_path, content))
 }
 
-/// Generate the main client actor file
-async fn generate_client_actor(
+/// Render the main client actor file
+fn render_client_actor(
     service: &Service,
     template_engine: &SwiftTemplateEngine,
     naming: &SwiftNaming,
     context: &GenerationContext,
     output_dir: &std::path::Path,
-) -> Result<String> {
+) -> Result<PendingWrite> {
     let client_name = get_client_name(service, naming);
     let file_path = output_dir.join(naming.file_name(&client_name));
 
@@ -95,27 +125,39 @@ async fn generate_client_actor(
     let template_methods = process_methods_for_template(service, naming)?;
     template_context.insert("methods", &template_methods);
 
+    // Only emit the timeout helper when some method declares a timeout
+    let has_loading_timeouts = template_methods
+        .iter()
+        .any(|method| method["loading_timeout_ms"].as_u64().is_some());
+    template_context.insert("has_loading_timeouts", &has_loading_timeouts);
+
+    // Typed errors are mapped from gRPC status before being stored in state
+    if let Some(service_error) = service_error_for_template(service, context, naming)? {
+        template_context.insert("service_error", &service_error);
+    }
+
     // Add configuration
     add_swift_config_to_context(&mut template_context, context);
+    add_contracts_import_to_context(&mut template_context, service, context);
 
     // Add imports
     let imports = get_client_imports(service, context);
     template_context.insert("imports", &imports);
 
     let content = template_engine.render_client("client_actor", &template_context)?;
-    FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
+    let content = context.config.apply_file_header(&file_path, &content);
 
-    Ok(file_path.to_string_lossy().to_string())
+    Ok(PendingWrite::new(file_path, content))
 }
 
-/// Generate the action enum file
-async fn generate_action_enum(
+/// Render the action enum file
+fn render_action_enum(
     service: &Service,
     template_engine: &SwiftTemplateEngine,
     naming: &SwiftNaming,
     context: &GenerationContext,
     output_dir: &std::path::Path,
-) -> Result<String> {
+) -> Result<PendingWrite> {
     let action_name = get_action_name(service, naming);
     let file_path = output_dir.join(naming.file_name(&action_name));
 
@@ -124,7 +166,6 @@ async fn generate_action_enum(
     template_context.insert("service", service);
     template_context.insert("service_name", &service.name);
     template_context.insert("action_name", &action_name);
-    template_context.insert("package_name", &service.package);
 
     // Process methods for action cases
     let template_methods = process_methods_for_template(service, naming)?;
@@ -132,21 +173,22 @@ async fn generate_action_enum(
 
     // Add configuration
     add_swift_config_to_context(&mut template_context, context);
+    add_contracts_import_to_context(&mut template_context, service, context);
 
     let content = template_engine.render_client("action_enum", &template_context)?;
-    FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
+    let content = context.config.apply_file_header(&file_path, &content);
 
-    Ok(file_path.to_string_lossy().to_string())
+    Ok(PendingWrite::new(file_path, content))
 }
 
-/// Generate the state struct file
-async fn generate_state_struct(
+/// Render the state struct file
+fn render_state_struct(
     service: &Service,
     template_engine: &SwiftTemplateEngine,
     naming: &SwiftNaming,
     context: &GenerationContext,
     output_dir: &std::path::Path,
-) -> Result<String> {
+) -> Result<PendingWrite> {
     let state_name = get_state_name(service, naming);
     let file_path = output_dir.join(naming.file_name(&state_name));
 
@@ -155,7 +197,6 @@ async fn generate_state_struct(
     template_context.insert("service", service);
     template_context.insert("service_name", &service.name);
     template_context.insert("state_name", &state_name);
-    template_context.insert("package_name", &service.package);
 
     // Analyze service to determine state collections
     let collections = analyze_state_collections(service, naming, context)?;
@@ -181,21 +222,172 @@ async fn generate_state_struct(
 
     // Add configuration
     add_swift_config_to_context(&mut template_context, context);
+    add_contracts_import_to_context(&mut template_context, service, context);
 
     let content = template_engine.render_client("state_struct", &template_context)?;
-    FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
+    let content = context.config.apply_file_header(&file_path, &content);
+
+    Ok(PendingWrite::new(file_path, content))
+}
+
+/// Number of fixture items in the populated preview state
+const PREVIEW_ITEM_COUNT: usize = 3;
+
+/// Render `<State>+Previews.swift` with the canonical preview states
+fn render_state_previews(
+    service: &Service,
+    template_engine: &SwiftTemplateEngine,
+    naming: &SwiftNaming,
+    context: &GenerationContext,
+    output_dir: &std::path::Path,
+) -> Result<PendingWrite> {
+    let state_name = get_state_name(service, naming);
+    let file_path = output_dir.join(format!("{}+Previews.swift", state_name));
+
+    // Populate every collection whose item type is a message in the schema
+    let collections = analyze_state_collections(service, naming, context)?;
+    let populated_collections: Vec<serde_json::Value> = collections.iter()
+        .filter_map(|collection| {
+            let name = collection["name"].as_str()?;
+            let item_type = collection["type"].as_str()?;
+            let message = find_message(&context.schema, item_type)?;
+            let items: Vec<String> = (1..=PREVIEW_ITEM_COUNT)
+                .map(|index| preview_message_literal(message, index, &context.schema, naming, 0))
+                .collect();
+            Some(serde_json::json!({ "name": name, "items": items }))
+        })
+        .collect();
+
+    let mut template_context = Context::new();
+    template_context.insert("service_name", &service.name);
+    template_context.insert("state_name", &state_name);
+    template_context.insert("populated_collections", &populated_collections);
+    add_swift_config_to_context(&mut template_context, context);
+    add_contracts_import_to_context(&mut template_context, service, context);
+
+    let content = template_engine.render_client("state_previews", &template_context)?;
+    let content = context.config.apply_file_header(&file_path, &content);
+
+    Ok(PendingWrite::new(file_path, content))
+}
+
+/// Find a message (top-level or nested) by its unqualified name
+fn find_message<'a>(schema: &'a ProtoSchema, type_name: &str) -> Option<&'a Message> {
+    fn search<'a>(messages: &'a [Message], name: &str) -> Option<&'a Message> {
+        messages.iter().find_map(|message| {
+            if message.name == name {
+                Some(message)
+            } else {
+                search(&message.nested_messages, name)
+            }
+        })
+    }
+    search(&schema.messages, &clean_type_name(type_name))
+}
+
+/// Find an enum (top-level or nested in a message) by its unqualified name
+pub(crate) fn find_enum<'a>(schema: &'a ProtoSchema, type_name: &str) -> Option<&'a Enum> {
+    fn search<'a>(messages: &'a [Message], name: &str) -> Option<&'a Enum> {
+        messages.iter().find_map(|message| {
+            message.nested_enums.iter()
+                .find(|e| e.name == name)
+                .or_else(|| search(&message.nested_messages, name))
+        })
+    }
+    let name = clean_type_name(type_name);
+    schema.enums.iter()
+        .find(|e| e.name == name)
+        .or_else(|| search(&schema.messages, &name))
+}
+
+/// Swift initializer literal for a fixture instance of `message`
+///
+/// Values come from field defaults where present, otherwise from the field
+/// name and `index`, so each fixture item is distinct and deterministic.
+fn preview_message_literal(
+    message: &Message,
+    index: usize,
+    schema: &ProtoSchema,
+    naming: &SwiftNaming,
+    depth: usize,
+) -> String {
+    let arguments: Vec<String> = message.fields.iter()
+        .map(|field| {
+            let value = preview_field_literal(message, field, index, schema, naming, depth);
+            format!("{}: {}", naming.property_name(&field.name), value)
+        })
+        .collect();
+    format!("{}({})", naming.type_name(&message.name), arguments.join(", "))
+}
+
+/// Swift literal for one field of a fixture instance
+fn preview_field_literal(
+    message: &Message,
+    field: &Field,
+    index: usize,
+    schema: &ProtoSchema,
+    naming: &SwiftNaming,
+    depth: usize,
+) -> String {
+    if field.label == FieldLabel::Repeated {
+        return "[]".to_string();
+    }
+
+    // Implicit-presence message fields get a nested fixture; `optional` ones stay nil
+    let is_optional = field.proto3_optional;
+    let default_value = field.default_value.as_deref();
 
-    Ok(file_path.to_string_lossy().to_string())
+    match field.field_type.as_str() {
+        "string" if field.name == "id" || field.name.ends_with("_id") => {
+            format!("\"{}-{}\"", message.name.to_snake_case().replace('_', "-"), index)
+        }
+        "string" => match default_value {
+            Some(value) => format!("{:?}", value.trim_matches('"')),
+            None => format!("\"{} {}\"", field.name.to_title_case(), index),
+        },
+        "bool" => default_value
+            .map(str::to_string)
+            .unwrap_or_else(|| index.is_multiple_of(2).to_string()),
+        "int32" | "int64" | "uint32" | "uint64" | "sint32" | "sint64" |
+        "fixed32" | "fixed64" | "sfixed32" | "sfixed64" => default_value
+            .map(str::to_string)
+            .unwrap_or_else(|| index.to_string()),
+        "float" | "double" => default_value
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}.0", index)),
+        "bytes" => "Data()".to_string(),
+        other if other.contains("Timestamp") => {
+            // Fixed dates keep previews and snapshots stable
+            format!("Date(timeIntervalSince1970: {})", 1_700_000_000 + index * 86_400)
+        }
+        other => {
+            if let Some(enum_type) = find_enum(schema, other) {
+                // Prefer the first meaningful value over the zero/UNSPECIFIED one
+                let value = enum_type.values.iter()
+                    .find(|v| v.number != 0)
+                    .or_else(|| enum_type.values.first());
+                if let Some(value) = value {
+                    return format!(".{}", naming.enum_case_name(&value.name).trim_matches('`'));
+                }
+            }
+            match find_message(schema, other) {
+                Some(nested) if !is_optional && depth < 2 => {
+                    preview_message_literal(nested, index, schema, naming, depth + 1)
+                }
+                _ => "nil".to_string(),
+            }
+        }
+    }
 }
 
-/// Generate test file if enabled
-async fn generate_test_file(
+/// Render test file if enabled
+fn render_test_file(
     service: &Service,
     template_engine: &SwiftTemplateEngine,
     naming: &SwiftNaming,
     context: &GenerationContext,
     output_dir: &std::path::Path,
-) -> Result<String> {
+) -> Result<PendingWrite> {
     let client_name = get_client_name(service, naming);
     let test_file_name = format!("{}Tests", client_name);
     let file_path = output_dir.join(naming.file_name(&test_file_name));
@@ -210,13 +402,21 @@ async fn generate_test_file(
     let template_methods = process_methods_for_template(service, naming)?;
     template_context.insert("methods", &template_methods);
 
+    if let Some(service_error) = service_error_for_template(service, context, naming)? {
+        template_context.insert("service_error", &service_error);
+    }
+
     // Add configuration
     add_swift_config_to_context(&mut template_context, context);
+    add_contracts_import_to_context(&mut template_context, service, context);
+    if let Some(module) = clients_module(context) {
+        template_context.insert("test_module", &module);
+    }
 
     let content = template_engine.render_client("test_file", &template_context)?;
-    FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
+    let content = context.config.apply_file_header(&file_path, &content);
 
-    Ok(file_path.to_string_lossy().to_string())
+    Ok(PendingWrite::new(file_path, content))
 }
 
 /// Process service methods for template rendering with enhanced Axiom metadata
@@ -309,6 +509,7 @@ fn process_methods_for_template(
                 "validation_checks": validation_checks,
                 "cache_strategy": cache_strategy_str,
                 "supports_offline": axiom_options.supports_offline.unwrap_or(false),
+                "loading_timeout_ms": axiom_options.loading_timeout_ms,
                 "client_streaming": method.client_streaming,
                 "server_streaming": method.server_streaming
             })
@@ -332,6 +533,7 @@ fn process_methods_for_template(
                     "validation_checks": Vec::<String>::new(),
                     "cache_strategy": "memory",
                     "supports_offline": false,
+                    "loading_timeout_ms": null,
                     "client_streaming": method.client_streaming,
                     "server_streaming": method.server_streaming
                 })
@@ -376,11 +578,9 @@ fn analyze_state_collections(
     // Then, infer collections from methods for any missing ones
     for method in &service.methods {
         // Get collection name from Axiom options or infer it
-        let collection_name = method.options.axiom_method
-            .as_ref()
-            .and_then(|opts| opts.collection_name.clone())
-            .or_else(|| infer_collection_name_from_method(method, service))
-            .unwrap_or_else(|| naming.collection_property_name(&extract_entity_name(&method.output_type)));
+        let collection_name = resolve_collection_name(method, service, |entity| {
+            naming.collection_property_name(entity)
+        });
 
         if !seen_collections.contains(&collection_name) {
             seen_collections.insert(collection_name.clone());
@@ -440,91 +640,6 @@ fn extract_field_name(rule: &str) -> String {
     "field".to_string()
 }
 
-/// Infer collection name from method patterns
-fn infer_collection_name_from_method(method: &Method, service: &Service) -> Option<String> {
-    let method_lower = method.name.to_lowercase();
-    
-    // Look for patterns like "getTasks", "listUsers", etc.
-    if method_lower.starts_with("get") || method_lower.starts_with("list") || method_lower.starts_with("find") {
-        if let Some(entity) = extract_collection_from_method_name(&method.name) {
-            return Some(entity);
-        }
-    }
-
-    // Look for patterns in the output type
-    if method.output_type.contains("Response") {
-        // For types like GetTasksResponse, extract "tasks"
-        if let Some(entity) = extract_collection_from_response_type(&method.output_type) {
-            return Some(entity);
-        }
-    }
-
-    // Default to service-based collection name
-    let service_entity = service.name.replace("Service", "");
-    Some(format!("{}s", service_entity.to_lowercase()))
-}
-
-/// Extract collection name from method name like "getTasks" -> "tasks"
-fn extract_collection_from_method_name(method_name: &str) -> Option<String> {
-    let prefixes = ["get", "list", "find", "fetch", "load"];
-    
-    for prefix in &prefixes {
-        if method_name.to_lowercase().starts_with(prefix) {
-            let remaining = &method_name[prefix.len()..];
-            if !remaining.is_empty() {
-                return Some(remaining.to_lowercase());
-            }
-        }
-    }
-    
-    None
-}
-
-/// Extract collection name from response type like "GetTasksResponse" -> "tasks"
-fn extract_collection_from_response_type(type_name: &str) -> Option<String> {
-    let clean_name = clean_type_name(type_name);
-    
-    if clean_name.starts_with("Get") && clean_name.ends_with("Response") {
-        let middle = &clean_name[3..clean_name.len() - 8]; // Remove "Get" and "Response"
-        if !middle.is_empty() {
-            return Some(middle.to_lowercase());
-        }
-    }
-    
-    None
-}
-
-/// Extract entity name from type name
-fn extract_entity_name(type_name: &str) -> String {
-    let clean_name = clean_type_name(type_name);
-    
-    // Remove common suffixes
-    if clean_name.ends_with("Response") {
-        let without_response = &clean_name[..clean_name.len() - 8];
-        if !without_response.is_empty() {
-            return without_response.to_string();
-        }
-    }
-    
-    if clean_name.ends_with("Request") {
-        let without_request = &clean_name[..clean_name.len() - 7];
-        if !without_request.is_empty() {
-            return without_request.to_string();
-        }
-    }
-    
-    clean_name
-}
-
-/// Clean type name by removing package prefixes
-fn clean_type_name(type_name: &str) -> String {
-    if let Some(last_part) = type_name.split('.').last() {
-        last_part.to_string()
-    } else {
-        type_name.to_string()
-    }
-}
-
 /// Get custom state properties using Axiom service options
 fn get_custom_state_properties(service: &Service) -> Vec<serde_json::Value> {
     let mut properties = Vec::new();
@@ -559,7 +674,7 @@ fn get_custom_state_properties(service: &Service) -> Vec<serde_json::Value> {
 }
 
 /// Get client name from service, considering Axiom options
-fn get_client_name(service: &Service, naming: &SwiftNaming) -> String {
+pub(crate) fn get_client_name(service: &Service, naming: &SwiftNaming) -> String {
     service.options.axiom_service
         .as_ref()
         .and_then(|opts| opts.client_name.clone())
@@ -582,43 +697,154 @@ fn get_action_name(service: &Service, naming: &SwiftNaming) -> String {
         .unwrap_or_else(|| naming.action_name(&service.name))
 }
 
+/// gRPC status codes by canonical name, used to map error enum values
+const GRPC_STATUS_CODES: &[(&str, i32)] = &[
+    ("CANCELLED", 1),
+    ("UNKNOWN", 2),
+    ("INVALID_ARGUMENT", 3),
+    ("DEADLINE_EXCEEDED", 4),
+    ("NOT_FOUND", 5),
+    ("ALREADY_EXISTS", 6),
+    ("PERMISSION_DENIED", 7),
+    ("RESOURCE_EXHAUSTED", 8),
+    ("FAILED_PRECONDITION", 9),
+    ("ABORTED", 10),
+    ("OUT_OF_RANGE", 11),
+    ("UNIMPLEMENTED", 12),
+    ("INTERNAL", 13),
+    ("UNAVAILABLE", 14),
+    ("DATA_LOSS", 15),
+    ("UNAUTHENTICATED", 16),
+];
+
+/// Find the proto enum describing a service's errors: the `error_type` option,
+/// or by convention `<Service>Error` / `<Name>Error` for `<Name>Service`
+fn find_service_error_enum<'a>(service: &Service, schema: &'a ProtoSchema) -> Result<Option<&'a Enum>> {
+    let find = |name: &str| {
+        let short_name = name.rsplit('.').next().unwrap_or(name);
+        schema.enums.iter().find(|e| e.name == short_name)
+    };
+
+    let declared = service.options.axiom_service
+        .as_ref()
+        .and_then(|opts| opts.error_type.as_deref());
+    if let Some(error_type) = declared {
+        return find(error_type).map(Some).ok_or_else(|| {
+            Error::ValidationError(format!(
+                "Service {} declares error_type '{}' but no such enum exists",
+                service.name, error_type
+            ))
+        });
+    }
+
+    let stem = service.name.strip_suffix("Service").unwrap_or(&service.name);
+    Ok(find(&format!("{}Error", service.name)).or_else(|| find(&format!("{}Error", stem))))
+}
+
+/// Build the typed error description for a service, if it declares an error enum
+fn service_error_for_template(
+    service: &Service,
+    context: &GenerationContext,
+    naming: &SwiftNaming,
+) -> Result<Option<serde_json::Value>> {
+    let Some(error_enum) = find_service_error_enum(service, &context.schema)? else {
+        return Ok(None);
+    };
+
+    // Values are conventionally prefixed with the enum name (TASK_ERROR_NOT_FOUND)
+    let prefix = format!("{}_", error_enum.name.to_shouty_snake_case());
+    let cases: Vec<serde_json::Value> = error_enum.values
+        .iter()
+        .filter(|value| value.number != 0 && !value.name.ends_with("UNSPECIFIED"))
+        .map(|value| {
+            let short_name = value.name.strip_prefix(&prefix).unwrap_or(&value.name);
+            let status_code = GRPC_STATUS_CODES
+                .iter()
+                .find(|(name, _)| *name == short_name)
+                .map(|(_, code)| *code);
+
+            serde_json::json!({
+                "case_name": naming.property_name(short_name),
+                "proto_value": value.name,
+                "short_name": short_name,
+                "display_name": short_name.to_title_case(),
+                "status_code": status_code,
+                "documentation": value.documentation,
+            })
+        })
+        .collect();
+
+    if cases.is_empty() {
+        tracing::warn!("Error enum {} for {} has no error values", error_enum.name, service.name);
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::json!({
+        "swift_name": format!("{}Error", get_client_name(service, naming)),
+        "proto_enum": error_enum.name,
+        "cases": cases,
+    })))
+}
+
 /// Add Swift configuration to template context
 fn add_swift_config_to_context(template_context: &mut Context, context: &GenerationContext) {
+    template_context.insert("include_documentation", &context.config.include_documentation);
+
     if let Some(swift_config) = context.language_config.get("swift") {
-        template_context.insert("package_name", &swift_config.get("package_name"));
         template_context.insert("swift_config", swift_config);
     }
 }
 
-/// Get required imports for client files using enhanced Axiom options
-fn get_client_imports(service: &Service, _context: &GenerationContext) -> Vec<String> {
-    let mut imports = vec![
-        "Foundation".to_string(),
-    ];
+/// Module the client files import to reach the generated contracts
+///
+/// The Swift config's `package_name` wins over the service's
+/// `swift_package_name` option; with neither set, contracts share the
+/// client's module and no import is emitted.
+fn contracts_import(service: &Service, context: &GenerationContext) -> Option<String> {
+    contracts_module(context)
+        .map(str::to_string)
+        .or_else(|| {
+            service.options.axiom_service
+                .as_ref()
+                .and_then(|opts| opts.swift_package_name.clone())
+                .filter(|name| !name.trim().is_empty())
+        })
+}
 
-    // Add imports from Axiom service options
-    if let Some(axiom_options) = &service.options.axiom_service {
-        imports.extend(axiom_options.import_modules.clone());
-        
-        // Add Swift package import if specified
-        if let Some(package_name) = &axiom_options.swift_package_name {
-            imports.push(package_name.clone());
-        }
-    } else {
-        // Default imports if no Axiom options
-        imports.extend(vec![
-            "AxiomCore".to_string(),
-            "AxiomArchitecture".to_string(),
-        ]);
+/// Insert `package_name` only when a contracts module is configured, so the
+/// templates never render an empty or `null` import
+fn add_contracts_import_to_context(template_context: &mut Context, service: &Service, context: &GenerationContext) {
+    if let Some(package_name) = contracts_import(service, context) {
+        template_context.insert("package_name", &package_name);
     }
+}
+
+/// Get the additional imports for client files using enhanced Axiom options
+///
+/// Foundation, the Axiom frameworks and the contracts module are emitted by
+/// the template itself, so they are filtered out here to keep each import
+/// line unique.
+fn get_client_imports(service: &Service, context: &GenerationContext) -> Vec<String> {
+    let contracts = contracts_import(service, context);
+    let mut imports: Vec<String> = service.options.axiom_service
+        .as_ref()
+        .map(|opts| opts.import_modules.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|module| !BASE_CLIENT_IMPORTS.contains(&module.as_str()))
+        .filter(|module| contracts.as_deref() != Some(module.as_str()))
+        .collect();
 
     imports.sort();
     imports.dedup();
     imports
 }
 
+/// Imports every client actor declares unconditionally
+const BASE_CLIENT_IMPORTS: &[&str] = &["Foundation", "AxiomCore", "AxiomArchitecture"];
+
 /// Check if tests should be generated using Axiom service options
-fn should_generate_tests(context: &GenerationContext) -> bool {
+pub(crate) fn should_generate_tests(context: &GenerationContext) -> bool {
     // First check if any service has explicit test generation setting
     let service_wants_tests = context.schema.services.iter().any(|service| {
         service.options.axiom_service
@@ -660,7 +886,9 @@ impl SwiftClientGenerator {
         template_context.insert("client_name", &client_name);
         template_context.insert("state_name", &get_state_name(service, &naming));
         template_context.insert("action_name", &get_action_name(service, &naming));
-        template_context.insert("package_name", &service.package);
+        if let Some(package_name) = service.options.axiom_service.as_ref().and_then(|opts| opts.swift_package_name.as_ref()) {
+            template_context.insert("package_name", package_name);
+        }
 
         // Process methods with simple fallback for robustness  
         let template_methods = service.methods.iter().map(|method| {
@@ -680,6 +908,7 @@ impl SwiftClientGenerator {
                 "validation_checks": Vec::<String>::new(),
                 "cache_strategy": "memory",
                 "supports_offline": false,
+                "loading_timeout_ms": null,
                 "client_streaming": method.client_streaming,
                 "server_streaming": method.server_streaming
             })
@@ -718,31 +947,14 @@ impl SwiftStateGenerator {
         for field in &message.fields {
             let swift_field_name = naming.property_name(&field.name);
             
-            // Determine if field should be optional based on Swift conventions
-            let should_be_optional = field.name == "description" || 
-                (field.field_type == "string" && field.name.to_lowercase().contains("optional"));
+            // Every parameter defaults to the proto3 value of an absent field
+            let field_default = proto3_default(field, &naming.swift_type(&field.field_type), None, &naming);
             
-            let swift_type = if field.label == crate::proto::types::FieldLabel::Repeated {
-                match field.field_type.as_str() {
-                    "string" => "[String]".to_string(),
-                    _ => format!("[{}]", field.field_type),
-                }
-            } else {
-                match field.field_type.as_str() {
-                    "string" => if should_be_optional { "String?".to_string() } else { "String".to_string() },
-                    "int32" => "Int32".to_string(), 
-                    "bool" => "Bool".to_string(),
-                    _ => field.field_type.clone(),
-                }
-            };
-            
-            properties.push(format!("    public let {}: {}", swift_field_name, swift_type));
-            
-            if should_be_optional && field.field_type == "string" {
-                init_params.push(format!("{}: {} = nil", swift_field_name, swift_type));
-            } else {
-                init_params.push(format!("{}: {}", swift_field_name, swift_type));
-            }
+            properties.push(format!("    public let {}: {}", swift_field_name, field_default.swift_type));
+            init_params.push(format!(
+                "{}: {} = {}",
+                swift_field_name, field_default.swift_type, field_default.default_value
+            ));
             
             init_assignments.push(format!("        self.{} = {}", swift_field_name, swift_field_name));
         }
@@ -760,23 +972,7 @@ impl SwiftStateGenerator {
                 (naming.type_name(&field.name), swift_field_name.clone())
             };
             
-            // Determine if field should be optional for the method parameter
-            let should_be_optional = field.name == "description" || 
-                (field.field_type == "string" && field.name.to_lowercase().contains("optional"));
-            
-            let swift_type = if field.label == crate::proto::types::FieldLabel::Repeated {
-                match field.field_type.as_str() {
-                    "string" => "[String]".to_string(),
-                    _ => format!("[{}]", field.field_type),
-                }
-            } else {
-                match field.field_type.as_str() {
-                    "string" => if should_be_optional { "String?".to_string() } else { "String".to_string() },
-                    "int32" => "Int32".to_string(),
-                    "bool" => "Bool".to_string(), 
-                    _ => field.field_type.clone(),
-                }
-            };
+            let swift_type = proto3_default(field, &naming.swift_type(&field.field_type), None, &naming).swift_type;
             
             // Create parameter assignments for with method
             let mut param_assignments = Vec::new();
@@ -883,7 +1079,9 @@ impl SwiftActionGenerator {
         template_context.insert("service", service);
         template_context.insert("service_name", &service.name);
         template_context.insert("action_name", &action_name);
-        template_context.insert("package_name", &service.package);
+        if let Some(package_name) = service.options.axiom_service.as_ref().and_then(|opts| opts.swift_package_name.as_ref()) {
+            template_context.insert("package_name", package_name);
+        }
 
         // Process methods for action cases
         let template_methods = process_methods_for_template(service, &naming)?;
@@ -915,18 +1113,7 @@ impl SwiftContractGenerator {
             "equatable": true,
             "sendable": true,
             "fields": message.fields.iter().map(|field| {
-                let swift_type = if field.label == crate::proto::types::FieldLabel::Repeated {
-                    match field.field_type.as_str() {
-                        "string" => "[String]".to_string(),
-                        _ => format!("[{}]", naming.swift_type(&field.field_type)),
-                    }
-                } else {
-                    naming.swift_type(&field.field_type)
-                };
-                
-                // Determine if field should be optional
-                let should_be_optional = field.name == "description" || 
-                    (field.field_type == "string" && field.name.to_lowercase().contains("optional"));
+                let field_default = proto3_default(field, &naming.swift_type(&field.field_type), None, &naming);
                 
                 // Check if field name needs CodingKey mapping (snake_case to camelCase)
                 let coding_key_needed = field.name.contains("_");
@@ -934,9 +1121,10 @@ impl SwiftContractGenerator {
                 serde_json::json!({
                     "name": field.name,
                     "swift_name": naming.property_name(&field.name),
-                    "type": swift_type,
+                    "type": field_default.swift_type.trim_end_matches('?'),
                     "documentation": field.documentation.as_deref().unwrap_or(""),
-                    "optional": should_be_optional,
+                    "optional": field_default.is_optional,
+                    "default_value": field_default.default_value,
                     "repeated": field.label == crate::proto::types::FieldLabel::Repeated,
                     "coding_key_needed": coding_key_needed
                 })
@@ -966,7 +1154,9 @@ impl SwiftTestGenerator {
         template_context.insert("service", service);
         template_context.insert("service_name", &service.name);
         template_context.insert("client_name", &client_name);
-        template_context.insert("package_name", &service.package);
+        if let Some(package_name) = service.options.axiom_service.as_ref().and_then(|opts| opts.swift_package_name.as_ref()) {
+            template_context.insert("package_name", package_name);
+        }
 
         // Process methods for testing
         let template_methods = process_methods_for_template(service, &naming)?;
@@ -1,18 +1,23 @@
 use crate::error::{Error, Result};
 use crate::generators::registry::GenerationContext;
+use crate::generators::swift::clients::find_enum;
+use crate::generators::swift::defaults::proto3_default;
 use crate::generators::swift::{naming::SwiftNaming, templates::SwiftTemplateEngine};
 use crate::proto::types::*;
-use crate::utils::file_manager::FileManager;
+use crate::utils::file_manager::{FileManager, PendingWrite, DEFAULT_WRITE_CONCURRENCY};
 use std::path::PathBuf;
 use tera::Context;
 
 /// Generate Swift contract files (models, types, enums)
+///
+/// All files are rendered before any is written, then written concurrently;
+/// the returned list keeps the deterministic render order.
 pub async fn generate_contracts(
     context: &GenerationContext,
     template_engine: &SwiftTemplateEngine,
     naming: &SwiftNaming,
 ) -> Result<Vec<String>> {
-    let mut generated_files = Vec::new();
+    let mut pending = Vec::new();
     
     // Create contracts directory
     let contracts_dir = context.config.output_dir.join("swift/Contracts");
@@ -25,8 +30,8 @@ pub async fn generate_contracts(
         let file_path = contracts_dir.join(naming.file_name(&service_name));
         let content = generate_service_contracts(&types, template_engine, naming, context).await?;
         
-        FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
-        generated_files.push(file_path.to_string_lossy().to_string());
+        let content = context.config.apply_file_header(&file_path, &content);
+        pending.push(PendingWrite::new(file_path, content));
     }
 
     // Generate standalone enums if any
@@ -35,12 +40,12 @@ pub async fn generate_contracts(
             let file_path = contracts_dir.join(naming.file_name(&enum_type.name));
             let content = generate_enum_file(enum_type, template_engine, naming, context).await?;
             
-            FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
-            generated_files.push(file_path.to_string_lossy().to_string());
+            let content = context.config.apply_file_header(&file_path, &content);
+            pending.push(PendingWrite::new(file_path, content));
         }
     }
 
-    Ok(generated_files)
+    FileManager::write_files(pending, context.config.force_overwrite, DEFAULT_WRITE_CONCURRENCY).await
 }
 
 /// Group messages and enums by their associated service
@@ -223,10 +228,9 @@ async fn generate_service_contracts(
     }
     template_context.insert("enums", &template_enums);
 
-    // Add configuration
-    if let Some(swift_config) = context.language_config.get("swift") {
-        template_context.insert("package_name", &swift_config.get("package_name"));
-    }
+    // Add configuration (contracts are the configured package itself, so no
+    // package import is added here)
+    template_context.insert("include_documentation", &context.config.include_documentation);
 
     // Add imports
     let imports = get_required_imports(&types.messages, &types.enums);
@@ -249,9 +253,7 @@ async fn generate_enum_file(
     template_context.insert("enum", &template_enum);
 
     // Add configuration
-    if let Some(swift_config) = context.language_config.get("swift") {
-        template_context.insert("package_name", &swift_config.get("package_name"));
-    }
+    template_context.insert("include_documentation", &context.config.include_documentation);
 
     template_engine.render_contract("enum", &template_context)
 }
@@ -260,7 +262,7 @@ async fn generate_enum_file(
 fn process_message_for_template(
     message: &Message,
     naming: &SwiftNaming,
-    _context: &GenerationContext,
+    context: &GenerationContext,
 ) -> Result<serde_json::Value> {
     let mut template_fields = Vec::new();
     
@@ -268,12 +270,11 @@ fn process_message_for_template(
         let swift_type = naming.swift_type(&field.field_type);
         let swift_name = naming.property_name(&field.name);
         
-        // Determine field characteristics
+        // Determine field characteristics from proto3 presence rules
+        let enum_type = find_enum(&context.schema, &field.field_type);
+        let default = proto3_default(field, &swift_type, enum_type, naming);
         let is_array = matches!(field.label, FieldLabel::Repeated);
-        let is_optional = !is_array && (
-            matches!(field.label, FieldLabel::Optional) ||
-            naming.is_optional_field(&field.field_type, &field.name)
-        );
+        let is_optional = default.is_optional;
         
         // Handle JSON field name mapping if different from Swift name
         let json_name = if field.name != swift_name {
@@ -287,6 +288,7 @@ fn process_message_for_template(
             "swift_type": swift_type,
             "is_array": is_array,
             "is_optional": is_optional,
+            "default_value": default.default_value,
             "json_name": json_name,
             "documentation": field.documentation,
             "original_type": field.field_type,
@@ -0,0 +1,96 @@
+//! Proto3 default-value semantics for generated Swift initializers
+//!
+//! Every init parameter defaults to what a proto3 decoder produces for an
+//! absent field, so initial state only needs the values that matter:
+//!
+//! | Proto field             | Swift type    | Default        |
+//! |-------------------------|---------------|----------------|
+//! | numeric scalar          | `Int32`, ...  | `0`            |
+//! | `bool`                  | `Bool`        | `false`        |
+//! | `string`                | `String`      | `""`           |
+//! | `bytes`                 | `Data`        | `Data()`       |
+//! | enum                    | the enum      | its zero value |
+//! | `repeated` / `map`      | `[T]`         | `[]`           |
+//! | message                 | `T?`          | `nil`          |
+//! | proto3 `optional`       | `T?`          | `nil`          |
+
+use crate::generators::swift::naming::SwiftNaming;
+use crate::proto::types::{Enum, Field, FieldLabel};
+
+/// How a field is declared in a generated Swift initializer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwiftFieldDefault {
+    /// Full Swift type, including `[...]` or `?`
+    pub swift_type: String,
+    /// Whether the property is a Swift optional
+    pub is_optional: bool,
+    /// Default expression for the init parameter
+    pub default_value: String,
+}
+
+/// Default literal for a proto scalar type, `None` for messages and enums
+pub fn scalar_default(proto_type: &str) -> Option<&'static str> {
+    match proto_type {
+        "int32" | "int64" | "uint32" | "uint64" | "sint32" | "sint64" |
+        "fixed32" | "fixed64" | "sfixed32" | "sfixed64" => Some("0"),
+        "float" | "double" => Some("0.0"),
+        "bool" => Some("false"),
+        "string" => Some("\"\""),
+        "bytes" => Some("Data()"),
+        _ => None,
+    }
+}
+
+/// Swift case for the zero value of a proto3 enum (its first declared value)
+pub fn enum_default_case(enum_type: &Enum, naming: &SwiftNaming) -> Option<String> {
+    enum_type.values.iter()
+        .find(|value| value.number == 0)
+        .or_else(|| enum_type.values.first())
+        .map(|value| format!(".{}", naming.enum_case_name(&value.name).trim_matches('`')))
+}
+
+/// Swift declaration for `field` given its mapped element type
+///
+/// `enum_type` is the field's enum when the caller could resolve it; without
+/// it a non-scalar field is treated as a message.
+pub fn proto3_default(field: &Field, element_type: &str, enum_type: Option<&Enum>, naming: &SwiftNaming) -> SwiftFieldDefault {
+    if field.label == FieldLabel::Repeated {
+        return SwiftFieldDefault {
+            swift_type: format!("[{}]", element_type),
+            is_optional: false,
+            default_value: "[]".to_string(),
+        };
+    }
+
+    let implicit_default = if field.proto3_optional {
+        None
+    } else if let Some(literal) = scalar_default(&field.field_type) {
+        Some(explicit_default(field).unwrap_or_else(|| literal.to_string()))
+    } else {
+        enum_type.and_then(|e| enum_default_case(e, naming))
+    };
+
+    match implicit_default {
+        Some(default_value) => SwiftFieldDefault {
+            swift_type: element_type.to_string(),
+            is_optional: false,
+            default_value,
+        },
+        None => SwiftFieldDefault {
+            swift_type: format!("{}?", element_type),
+            is_optional: true,
+            default_value: "nil".to_string(),
+        },
+    }
+}
+
+/// Proto2 `[default = ...]` value as a Swift literal
+fn explicit_default(field: &Field) -> Option<String> {
+    let value = field.default_value.as_deref()?;
+    match field.field_type.as_str() {
+        "string" => Some(format!("{:?}", value)),
+        "bool" => matches!(value, "true" | "false").then(|| value.to_string()),
+        // Bytes defaults are C-escaped, and inf/nan have no Swift literal
+        _ => value.parse::<f64>().ok().filter(|v| v.is_finite()).map(|_| value.to_string()),
+    }
+}
@@ -1,10 +1,20 @@
 use crate::error::{Error, Result};
 use crate::generators::registry::GenerationContext;
 use crate::generators::swift::naming::SwiftNaming;
+use crate::generators::swift::{clients_module, contracts_module};
 use crate::proto::types::*;
 use crate::utils::file_manager::FileManager;
 use std::collections::HashMap;
 
+/// Modules an app imports to use the generated code, in dependency order
+fn generated_modules(context: &GenerationContext) -> Vec<String> {
+    contracts_module(context)
+        .map(str::to_string)
+        .into_iter()
+        .chain(clients_module(context))
+        .collect()
+}
+
 /// Generate comprehensive documentation for Swift client code
 pub struct SwiftDocumentationGenerator {
     naming: SwiftNaming,
@@ -24,6 +34,11 @@ impl SwiftDocumentationGenerator {
         _generated_files: &[String],
     ) -> Result<Vec<String>> {
         let mut doc_files = Vec::new();
+
+        // Documentation is opt-out; don't leave an empty directory behind
+        if !context.config.include_documentation {
+            return Ok(doc_files);
+        }
         
         // Create documentation directory
         let docs_dir = context.config.output_dir.join("swift/Documentation");
@@ -90,10 +105,14 @@ impl SwiftDocumentationGenerator {
             content.push_str(&format!("- `{}.swift` - Main client actor\n", client_name));
             content.push_str(&format!("- `{}.swift` - Immutable state container\n", state_name));
             content.push_str(&format!("- `{}.swift` - Action enum with validation\n", action_name));
+            if context.config.generate_previews {
+                content.push_str(&format!("- `{}+Previews.swift` - Empty, loading, populated and error preview states (DEBUG only)\n", state_name));
+            }
         }
         
         content.push_str("\n### Support Files\n");
         content.push_str("- `AxiomErrors.swift` - Error types and handling\n");
+        content.push_str("- `<Client>Error.swift` - Typed errors for services with an error enum (`<Service>Error` or the `error_type` option)\n");
         content.push_str("- Contract files for proto message types\n\n");
 
         // Quick start section
@@ -103,6 +122,9 @@ impl SwiftDocumentationGenerator {
         content.push_str("   ```swift\n");
         content.push_str("   import AxiomCore\n");
         content.push_str("   import AxiomArchitecture\n");
+        for module in generated_modules(context) {
+            content.push_str(&format!("   import {}\n", module));
+        }
         content.push_str("   ```\n");
         content.push_str("3. Initialize and use the client:\n");
         
@@ -255,9 +277,24 @@ impl SwiftDocumentationGenerator {
         content.push_str("```\n\n");
 
         content.push_str("### 2. Add Generated Files\n\n");
-        content.push_str("1. Copy all generated `.swift` files to your project\n");
-        content.push_str("2. Add them to your target in Xcode\n");
-        content.push_str("3. Ensure proper module organization\n\n");
+        match (contracts_module(context), clients_module(context)) {
+            (Some(contracts), Some(clients)) => {
+                content.push_str("The generated `Package.swift` declares two products:\n\n");
+                content.push_str(&format!("- `{}` - Contract types from `Contracts/`\n", contracts));
+                content.push_str(&format!("- `{}` - Client actors, state and actions from `Clients/`, depending on `{}`\n\n", clients, contracts));
+                content.push_str("Add the package as a local dependency and link both products to your target.\n\n");
+            }
+            (Some(contracts), None) => {
+                content.push_str(&format!("1. Add the files in `Contracts/` to a `{}` module\n", contracts));
+                content.push_str(&format!("2. Add the files in `Clients/` to a target that depends on `{}`\n", contracts));
+                content.push_str("3. Link both to your app target in Xcode\n\n");
+            }
+            _ => {
+                content.push_str("1. Copy all generated `.swift` files to your project\n");
+                content.push_str("2. Add them to your target in Xcode\n");
+                content.push_str("3. Ensure proper module organization\n\n");
+            }
+        }
 
         // Basic Usage
         content.push_str("## Basic Usage\n\n");
@@ -270,7 +307,14 @@ impl SwiftDocumentationGenerator {
             content.push_str("```swift\n");
             content.push_str("import AxiomCore\n");
             content.push_str("import AxiomArchitecture\n");
-            content.push_str("import YourGeneratedModule\n\n");
+            let modules = generated_modules(context);
+            if modules.is_empty() {
+                content.push_str("import YourGeneratedModule\n");
+            }
+            for module in modules {
+                content.push_str(&format!("import {}\n", module));
+            }
+            content.push('\n');
             content.push_str("class MyViewController: UIViewController {\n");
             content.push_str(&format!("    private let client = {}()\n", client_name));
             content.push_str("    private var stateObserver: Task<Void, Never>?\n\n");
@@ -526,6 +570,22 @@ impl SwiftDocumentationGenerator {
         content.push_str("try await client.process(action)\n");
         content.push_str("```\n\n");
 
+        content.push_str("### Loading State Never Clears\n\n");
+        content.push_str("**Issue**: `isLoading` stays `true` when the server hangs on a long-running call\n\n");
+        content.push_str("**Solution**:\n");
+        content.push_str("1. Declare a timeout on the method in your proto:\n");
+        content.push_str("   ```protobuf\n");
+        content.push_str("   option (axiom.method_options) = { loading_timeout_ms: 30000 };\n");
+        content.push_str("   ```\n");
+        content.push_str("2. Regenerate the clients. Calls that exceed the timeout throw `AxiomError.clientError(.timedOut)` and the state's `error` is set while loaded data is kept:\n");
+        content.push_str("   ```swift\n");
+        content.push_str("   do {\n");
+        content.push_str("       try await client.process(action)\n");
+        content.push_str("   } catch let error as AxiomError where error.isTimeout {\n");
+        content.push_str("       // Offer a retry; previously loaded items are still in state\n");
+        content.push_str("   }\n");
+        content.push_str("   ```\n\n");
+
         // Memory Issues
         content.push_str("## Memory Issues\n\n");
         content.push_str("### Memory Leaks in State Observation\n\n");
@@ -5,9 +5,11 @@
 
 pub mod clients;
 pub mod contracts;
+pub mod defaults;
 pub mod naming;
 pub mod templates;
 pub mod documentation;
+pub mod package;
 pub mod types;
 
 use crate::error::{Error, Result};
@@ -15,6 +17,7 @@ use crate::generators::registry::{LanguageGenerator, GenerationConfig, Generatio
 use crate::proto::types::ProtoSchema;
 use crate::GenerateRequest;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Swift code generator
 pub struct SwiftGenerator {
@@ -37,6 +40,8 @@ impl SwiftGenerator {
     }
 
     /// Generate all Swift files for the given schema
+    ///
+    /// Copies the borrowed schema once; the registry path shares it instead.
     pub async fn generate_all(
         &self,
         schema: &ProtoSchema,
@@ -52,7 +57,38 @@ impl SwiftGenerator {
             generation_options: options,
         };
 
-        self.generate(schema, &request).await
+        self.generate(&Arc::new(schema.clone()), &request).await
+    }
+}
+
+/// Configured Swift module holding the generated contracts
+///
+/// Client files import this module; contract files are part of it and never do.
+pub(crate) fn contracts_module(context: &GenerationContext) -> Option<&str> {
+    context.language_config
+        .get("swift")
+        .and_then(|config| config.get("package_name"))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
+
+/// Whether contracts and clients are emitted as separate SwiftPM products
+pub(crate) fn split_contracts_module(context: &GenerationContext) -> bool {
+    contracts_module(context).is_some()
+        && context.language_config
+            .get("swift")
+            .and_then(|config| config.get("split_contracts_module"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+}
+
+/// Module name of the clients product when contracts are split out
+pub(crate) fn clients_module(context: &GenerationContext) -> Option<String> {
+    if split_contracts_module(context) {
+        contracts_module(context).map(|name| format!("{}Clients", name))
+    } else {
+        None
     }
 }
 
@@ -64,11 +100,14 @@ impl LanguageGenerator for SwiftGenerator {
 
     async fn generate(
         &self,
-        schema: &ProtoSchema,
+        schema: &Arc<ProtoSchema>,
         request: &GenerateRequest,
     ) -> Result<Vec<String>> {
-        let config = GenerationConfig::from_request(request);
-        let context = GenerationContext::new(config, schema.clone());
+        let config = GenerationConfig::from_request(request)?;
+        let mut context = GenerationContext::new(config, Arc::clone(schema));
+        if let Some(swift_config) = request.framework_config.as_ref().and_then(|c| c.swift.as_ref()) {
+            context = context.with_language_config("swift", serde_json::to_value(swift_config)?);
+        }
 
         let mut generated_files = Vec::new();
 
@@ -92,12 +131,13 @@ impl LanguageGenerator for SwiftGenerator {
             generated_files.extend(client_files);
         }
 
+        // Separate contracts and clients products for SwiftPM
+        if split_contracts_module(&context) {
+            generated_files.push(package::generate_package_manifest(&context).await?);
+        }
+
         // Generate documentation
-        if request.generation_options
-            .as_ref()
-            .and_then(|o| o.include_documentation)
-            .unwrap_or(true)
-        {
+        if context.config.include_documentation {
             let doc_generator = documentation::SwiftDocumentationGenerator::new();
             let doc_files = doc_generator.generate_documentation(&context, &generated_files).await?;
             generated_files.extend(doc_files);
@@ -110,16 +110,6 @@ impl SwiftNaming {
         }
     }
 
-    /// Check if field should be optional in Swift
-    pub fn is_optional_field(&self, proto_type: &str, field_name: &str) -> bool {
-        // Proto3 fields are optional by default except for primitives with zero values
-        match proto_type {
-            "bool" | "int32" | "int64" | "uint32" | "uint64" | "float" | "double" => false,
-            "string" => field_name.to_lowercase().contains("optional") || field_name.ends_with("?"),
-            _ => true, // Messages, enums, and other types are optional
-        }
-    }
-
     /// Escape Swift reserved words
     fn escape_reserved_word(&self, word: &str) -> String {
         match word {
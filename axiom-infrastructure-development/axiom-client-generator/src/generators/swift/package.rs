@@ -0,0 +1,98 @@
+//! Package.swift generation for split contracts/clients modules
+//!
+//! When `split_contracts_module` is enabled the contracts are published as the
+//! configured package product and the clients as a second `<package>Clients`
+//! product that depends on it, matching the imports emitted by the templates.
+
+use crate::error::{Error, Result};
+use crate::generators::registry::GenerationContext;
+use crate::generators::swift::clients::{get_client_name, should_generate_tests};
+use crate::generators::swift::naming::SwiftNaming;
+use crate::generators::swift::{clients_module, contracts_module};
+use crate::utils::file_manager::FileManager;
+
+/// Axiom framework version used when the Swift config does not pin one
+const DEFAULT_AXIOM_VERSION: &str = "1.0.0";
+
+/// Write `swift/Package.swift` declaring the contracts and clients products
+pub async fn generate_package_manifest(context: &GenerationContext) -> Result<String> {
+    let file_path = context.config.output_dir.join("swift/Package.swift");
+    let content = render_package_manifest(context)?;
+    let content = context.config.apply_file_header(&file_path, &content);
+
+    FileManager::write_file(&file_path, &content, context.config.force_overwrite).await?;
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// Render the manifest for the configured contracts module
+pub fn render_package_manifest(context: &GenerationContext) -> Result<String> {
+    let (Some(contracts), Some(clients)) = (contracts_module(context), clients_module(context)) else {
+        return Err(Error::ConfigError(
+            "split_contracts_module requires a Swift package_name".to_string(),
+        ));
+    };
+
+    let axiom_version = context.language_config
+        .get("swift")
+        .and_then(|config| config.get("axiom_version"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_AXIOM_VERSION);
+
+    // Generated XCTest files live next to the clients but are not library sources
+    let naming = SwiftNaming::new();
+    let excluded: Vec<String> = if should_generate_tests(context) {
+        context.schema.services
+            .iter()
+            .map(|service| format!("\"{}\"", naming.file_name(&format!("{}Tests", get_client_name(service, &naming)))))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let exclude = if excluded.is_empty() {
+        String::new()
+    } else {
+        format!(",\n            exclude: [{}]", excluded.join(", "))
+    };
+
+    Ok(format!(
+        r#"// swift-tools-version: 5.9
+
+import PackageDescription
+
+let package = Package(
+    name: "{contracts}",
+    platforms: [.iOS(.v15), .macOS(.v12)],
+    products: [
+        .library(name: "{contracts}", targets: ["{contracts}"]),
+        .library(name: "{clients}", targets: ["{clients}"])
+    ],
+    dependencies: [
+        .package(url: "https://github.com/axiom/axiom-core", from: "{version}"),
+        .package(url: "https://github.com/axiom/axiom-architecture", from: "{version}")
+    ],
+    targets: [
+        .target(
+            name: "{contracts}",
+            dependencies: [
+                .product(name: "AxiomCore", package: "axiom-core")
+            ],
+            path: "Contracts"
+        ),
+        .target(
+            name: "{clients}",
+            dependencies: [
+                "{contracts}",
+                .product(name: "AxiomCore", package: "axiom-core"),
+                .product(name: "AxiomArchitecture", package: "axiom-architecture")
+            ],
+            path: "Clients"{exclude}
+        )
+    ]
+)
+"#,
+        contracts = contracts,
+        clients = clients,
+        version = axiom_version,
+        exclude = exclude,
+    ))
+}
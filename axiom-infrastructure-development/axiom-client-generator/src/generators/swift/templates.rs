@@ -1,16 +1,64 @@
 use crate::error::{Error, Result};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tera::{Context, Tera};
 
+/// Environment variable naming an on-disk template directory to load instead
+/// of the embedded templates; honoured in debug builds only
+pub const TEMPLATE_DIR_ENV: &str = "AXIOM_TEMPLATE_DIR";
+
+/// Template directory enabled explicitly via `--templates-dev`
+static DEV_TEMPLATE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Load templates from `template_dir` and re-read them on every render
+///
+/// This is an explicit opt-in that works in release builds too, unlike
+/// [`TEMPLATE_DIR_ENV`]. Only the first call takes effect.
+pub fn enable_dev_templates(template_dir: impl Into<PathBuf>) {
+    let _ = DEV_TEMPLATE_DIR.set(template_dir.into());
+}
+
+/// The template directory for dev mode, if enabled
+///
+/// Release binaries ignore [`TEMPLATE_DIR_ENV`] so a stray environment
+/// variable cannot change production output.
+pub fn dev_template_dir() -> Option<PathBuf> {
+    if let Some(dir) = DEV_TEMPLATE_DIR.get() {
+        return Some(dir.clone());
+    }
+
+    let dir = std::env::var_os(TEMPLATE_DIR_ENV).filter(|dir| !dir.is_empty())?;
+    if cfg!(debug_assertions) {
+        Some(PathBuf::from(dir))
+    } else {
+        tracing::warn!(
+            "{} is ignored in release builds; pass --templates-dev to load templates from disk",
+            TEMPLATE_DIR_ENV
+        );
+        None
+    }
+}
+
 /// Swift template engine using Tera
 pub struct SwiftTemplateEngine {
     /// Tera template engine
     tera: Tera,
+    /// On-disk template directory re-read on every render (dev mode)
+    dev_template_dir: Option<PathBuf>,
 }
 
 impl SwiftTemplateEngine {
     /// Create a new Swift template engine
+    ///
+    /// Uses the embedded templates unless dev mode is enabled, see
+    /// [`dev_template_dir`].
     pub async fn new() -> Result<Self> {
+        if let Some(template_dir) = dev_template_dir() {
+            tracing::info!("Loading templates from {} (dev mode)", template_dir.display());
+            return Self::with_dev_template_dir(template_dir);
+        }
+
         let mut tera = Tera::new("src/templates/swift/**/*.tera")
             .map_err(|e| Error::TemplateError(format!("Failed to initialize Tera: {}", e)))?;
 
@@ -21,7 +69,7 @@ impl SwiftTemplateEngine {
         // Add built-in templates if external templates not found
         Self::add_builtin_templates(&mut tera)?;
 
-        Ok(Self { tera })
+        Ok(Self { tera, dev_template_dir: None })
     }
 
     /// Create a new Swift template engine with custom template directory
@@ -34,7 +82,71 @@ impl SwiftTemplateEngine {
         Self::register_swift_filters(&mut tera)?;
         Self::register_swift_functions(&mut tera)?;
 
-        Ok(Self { tera })
+        Ok(Self { tera, dev_template_dir: None })
+    }
+
+    /// Create an engine that re-reads templates from `template_dir` on every render
+    ///
+    /// The directory mirrors `src/templates/swift` (`clients/`, `contracts/`);
+    /// templates missing on disk fall back to the embedded ones.
+    pub fn with_dev_template_dir(template_dir: impl Into<PathBuf>) -> Result<Self> {
+        let template_dir = template_dir.into();
+        if !template_dir.is_dir() {
+            return Err(Error::ConfigError(format!(
+                "Template directory does not exist: {}",
+                template_dir.display()
+            )));
+        }
+
+        // Load once up front so broken templates fail fast
+        let tera = Self::load_dev_templates(&template_dir)?;
+        Ok(Self {
+            tera,
+            dev_template_dir: Some(template_dir),
+        })
+    }
+
+    /// Whether templates are loaded from disk on every render
+    pub fn is_dev_mode(&self) -> bool {
+        self.dev_template_dir.is_some()
+    }
+
+    /// Build a Tera instance from the embedded templates overlaid with the
+    /// templates currently on disk
+    fn load_dev_templates(template_dir: &Path) -> Result<Tera> {
+        let mut tera = Tera::default();
+        Self::register_swift_filters(&mut tera)?;
+        Self::register_swift_functions(&mut tera)?;
+        Self::add_builtin_templates(&mut tera)?;
+
+        let mut paths: Vec<PathBuf> = walkdir::WalkDir::new(template_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "tera"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let name = path
+                .strip_prefix(template_dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                Error::TemplateError(format!("Failed to read template {}: {}", path.display(), e))
+            })?;
+            tera.add_raw_template(&name, &content).map_err(|e| {
+                Error::TemplateError(format!(
+                    "Failed to parse template {}: {}",
+                    path.display(),
+                    tera_error_chain(&e)
+                ))
+            })?;
+        }
+
+        Ok(tera)
     }
 
     /// Initialize templates (currently a no-op since templates are initialized in new())
@@ -80,25 +192,33 @@ impl SwiftTemplateEngine {
 
     /// Render a Swift contract template
     pub fn render_contract(&self, template_name: &str, context: &Context) -> Result<String> {
-        let template_path = format!("contracts/{}.swift.tera", template_name);
-        self.tera
-            .render(&template_path, context)
-            .map_err(|e| Error::TemplateError(format!("Failed to render {}: {}", template_path, e)))
+        self.render(&format!("contracts/{}.swift.tera", template_name), context)
     }
 
     /// Render a Swift client template
     pub fn render_client(&self, template_name: &str, context: &Context) -> Result<String> {
-        let template_path = format!("clients/{}.swift.tera", template_name);
-        self.tera
-            .render(&template_path, context)
-            .map_err(|e| Error::TemplateError(format!("Failed to render {}: {}", template_path, e)))
+        self.render(&format!("clients/{}.swift.tera", template_name), context)
     }
 
     /// Render an arbitrary template
+    ///
+    /// In dev mode the templates are re-read from disk first, so edits take
+    /// effect without a rebuild.
     pub fn render(&self, template_name: &str, context: &Context) -> Result<String> {
-        self.tera
-            .render(template_name, context)
-            .map_err(|e| Error::TemplateError(format!("Failed to render {}: {}", template_name, e)))
+        let Some(template_dir) = &self.dev_template_dir else {
+            return self.tera
+                .render(template_name, context)
+                .map_err(|e| Error::TemplateError(format!("Failed to render {}: {}", template_name, e)));
+        };
+
+        let tera = Self::load_dev_templates(template_dir)?;
+        tera.render(template_name, context).map_err(|e| {
+            Error::TemplateError(format!(
+                "Failed to render {}: {}",
+                template_dir.join(template_name).display(),
+                tera_error_chain(&e)
+            ))
+        })
     }
 
     /// Register Swift-specific filters
@@ -311,12 +431,30 @@ impl SwiftTemplateEngine {
             include_str!("../../templates/swift/clients/state_struct.swift.tera")
         ).ok();
 
+        // State previews template
+        tera.add_raw_template(
+            "clients/state_previews.swift.tera",
+            include_str!("../../templates/swift/clients/state_previews.swift.tera")
+        ).ok();
+
         // Test file template
         tera.add_raw_template(
             "clients/test_file.swift.tera",
             include_str!("../../templates/swift/clients/test_file.swift.tera")
         ).ok();
 
+        // Shared error types template
+        tera.add_raw_template(
+            "clients/error_types.swift.tera",
+            include_str!("../../templates/swift/clients/error_types.swift.tera")
+        ).ok();
+
+        // Per-service typed error template
+        tera.add_raw_template(
+            "clients/service_errors.swift.tera",
+            include_str!("../../templates/swift/clients/service_errors.swift.tera")
+        ).ok();
+
         // Fallback templates with minimal content
         if !tera.get_template_names().any(|name| name == "contracts/service.swift.tera") {
             tera.add_raw_template(
@@ -403,4 +541,17 @@ public actor {{ client_name }}: AxiomObservableClient<{{ state_name }}, {{ actio
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Flatten a Tera error and its sources; parse errors carry line and column
+/// information only in the source chain
+fn tera_error_chain(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
@@ -32,8 +32,14 @@ impl SwiftTypeMapper {
     }
 
     /// Check if a type should be optional
+    ///
+    /// Follows proto3 presence: `optional` fields and singular non-scalar
+    /// fields are optional. Enums cannot be told apart from messages here; use
+    /// [`super::defaults::proto3_default`] when the schema is at hand.
     pub fn is_optional_type(field: &Field) -> bool {
-        matches!(field.label, FieldLabel::Optional)
+        field.proto3_optional
+            || (field.label != FieldLabel::Repeated
+                && super::defaults::scalar_default(&field.field_type).is_none())
     }
 
     /// Check if a type is a collection type
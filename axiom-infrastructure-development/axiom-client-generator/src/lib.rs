@@ -3,6 +3,7 @@
 //! A Rust-based MCP tool that generates Axiom framework-compatible Swift client code
 //! directly from gRPC proto definitions.
 
+pub mod builder;
 pub mod error;
 pub mod generators;
 pub mod mcp;
@@ -11,14 +12,19 @@ pub mod utils;
 pub mod validation;
 pub mod testing;
 
+pub use builder::GenerateRequestBuilder;
 pub use error::{Error, Result};
+pub use generators::registry::{GeneratorRegistry, LanguageGenerator};
 
-use generators::registry::GeneratorRegistry;
+use generators::naming_audit::{NamingAudit, NamingAuditReport};
+use proto::analyzer::{CompatibilityReport, ProtoAnalyzer, SchemaFingerprint, Suggestion};
 use proto::parser::ProtoParser;
 use testing::TestRunner;
+use utils::manifest::GenerationManifest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Main configuration for client generation
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -37,6 +43,16 @@ pub struct GenerateRequest {
     pub generation_options: Option<GenerationOptions>,
 }
 
+impl GenerateRequest {
+    /// Start building a request; see [`GenerateRequestBuilder`]
+    pub fn builder(
+        proto_path: impl Into<String>,
+        output_path: impl Into<String>,
+    ) -> GenerateRequestBuilder {
+        GenerateRequestBuilder::new(proto_path, output_path)
+    }
+}
+
 /// Framework-specific configuration options
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FrameworkConfig {
@@ -66,8 +82,10 @@ pub struct SwiftConfig {
     pub client_suffix: Option<String>,
     /// Generate XCTest files
     pub generate_tests: Option<bool>,
-    /// Swift package name for imports
+    /// Swift module holding the generated contracts; client files import it
     pub package_name: Option<String>,
+    /// Emit a Package.swift with separate contracts and clients products
+    pub split_contracts_module: Option<bool>,
 }
 
 
@@ -87,6 +105,17 @@ pub struct GenerationOptions {
     pub include_documentation: Option<bool>,
     /// Code style guide to follow
     pub style_guide: Option<String>,
+    /// License header for generated sources: inline text or `@path/to/header.txt`.
+    /// Supports `{year}`, `{file_name}` and `{generator_version}` placeholders.
+    pub file_header: Option<String>,
+    /// Generate `<State>+Previews.swift` with SwiftUI preview states
+    pub generate_previews: Option<bool>,
+    /// Treat validation warnings as errors
+    pub strict: Option<bool>,
+    /// Restrict strict mode to these warning categories (e.g. "access_control")
+    pub strict_categories: Option<Vec<String>>,
+    /// Warn when the parsed schema is estimated above this many megabytes
+    pub max_memory_warn_mb: Option<u64>,
 }
 
 impl Default for GenerationOptions {
@@ -98,6 +127,11 @@ impl Default for GenerationOptions {
             force_overwrite: Some(false),
             include_documentation: Some(true),
             style_guide: Some("axiom".to_string()),
+            file_header: None,
+            generate_previews: Some(false),
+            strict: Some(false),
+            strict_categories: None,
+            max_memory_warn_mb: None,
         }
     }
 }
@@ -147,6 +181,8 @@ pub struct ValidationSummary {
     pub total_warnings: usize,
     /// Compilation success rate (if compilation was attempted)
     pub compilation_success_rate: Option<f64>,
+    /// Warnings promoted to errors by strict mode
+    pub promoted_warnings: Vec<String>,
 }
 
 /// Main Swift client generator
@@ -159,10 +195,37 @@ pub struct AxiomSwiftClientGenerator {
 impl AxiomSwiftClientGenerator {
     /// Create a new client generator instance
     pub async fn new() -> Result<Self> {
-        let parser = ProtoParser::new().await?;
         let registry = GeneratorRegistry::new().await?;
+        Self::with_registry(registry).await
+    }
+
+    /// Create a generator that dispatches to a caller-supplied registry
+    ///
+    /// Use this to add custom [`LanguageGenerator`]s alongside (or instead of)
+    /// the built-in Swift generator.
+    ///
+    /// ```no_run
+    /// use axiom_universal_client_generator::{
+    ///     AxiomSwiftClientGenerator, GenerateRequest, GeneratorRegistry,
+    /// };
+    ///
+    /// # async fn run() -> axiom_universal_client_generator::Result<()> {
+    /// let registry = GeneratorRegistry::new().await?;
+    /// // registry.register(Box::new(MyKotlinGenerator::new()));
+    /// let generator = AxiomSwiftClientGenerator::with_registry(registry).await?;
+    ///
+    /// let request = GenerateRequest::builder("proto/", "generated/")
+    ///     .language("swift")
+    ///     .build()?;
+    /// let response = generator.generate(request).await?;
+    /// assert!(response.success);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_registry(registry: GeneratorRegistry) -> Result<Self> {
+        let parser = ProtoParser::new().await?;
         let test_runner = TestRunner::new();
-        
+
         Ok(Self {
             parser,
             registry,
@@ -170,18 +233,74 @@ impl AxiomSwiftClientGenerator {
         })
     }
 
+    /// Languages this generator can produce code for
+    pub fn supported_languages(&self) -> Vec<String> {
+        self.registry.supported_languages()
+    }
+
+    /// Compare a proto schema against the one recorded by the last generation
+    /// into `output_path`; `None` when there is no previous manifest
+    pub async fn check_compatibility(
+        &self,
+        proto_path: &str,
+        output_path: &str,
+    ) -> Result<Option<CompatibilityReport>> {
+        let Some(previous) = GenerationManifest::load(Path::new(output_path)).await? else {
+            return Ok(None);
+        };
+
+        let schema = self.parser.parse(proto_path).await?;
+        let analyzer = ProtoAnalyzer::new(schema);
+        Ok(Some(analyzer.check_compatibility(&previous.schema)))
+    }
+
+    /// Compare the Swift and Kotlin identifiers derived from a proto schema
+    /// without generating any files
+    pub async fn audit_naming(&self, proto_path: &str) -> Result<NamingAuditReport> {
+        let schema = self.parser.parse(proto_path).await?;
+        Ok(NamingAudit::new().run(&schema))
+    }
+
+    /// Suggest axiom options for services and methods the proto leaves
+    /// un-annotated
+    pub async fn suggest_options(&self, proto_path: &str) -> Result<Vec<Suggestion>> {
+        let schema = self.parser.parse(proto_path).await?;
+        Ok(ProtoAnalyzer::new(schema).suggest_options())
+    }
+
     /// Generate clients from the given request
     pub async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
         let start_time = std::time::Instant::now();
         
         tracing::info!("Starting client generation for: {}", request.proto_path);
         
-        // Parse proto files
-        let schema = self.parser.parse(&request.proto_path).await?;
+        // Parse proto files; every generator shares this one copy
+        let schema = Arc::new(self.parser.parse(&request.proto_path).await?);
+        let output_dir = PathBuf::from(&request.output_path);
+        let fingerprint = SchemaFingerprint::from_schema(&schema);
         
         // Generate for each target language
         let mut generated_files = Vec::new();
         let mut warnings = Vec::new();
+
+        if let Some(limit_mb) = request.generation_options.as_ref().and_then(|o| o.max_memory_warn_mb) {
+            if let Some(warning) = schema.size_warning(limit_mb) {
+                tracing::warn!("{}", warning);
+                warnings.push(warning);
+            }
+        }
+
+        // Surface breaking changes against the previous generation
+        match GenerationManifest::load(&output_dir).await {
+            Ok(Some(previous)) => {
+                let report = CompatibilityReport::between(&previous.schema, &fingerprint);
+                for change in &report.breaking {
+                    warnings.push(format!("Breaking schema change: {}", change.description));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warnings.push(format!("Ignoring previous manifest: {}", e)),
+        }
         
         for language in &request.target_languages {
             match self.registry.generate(language, &schema, &request).await {
@@ -208,7 +327,16 @@ impl AxiomSwiftClientGenerator {
         
         // Run validation on generated files
         let validation_summary = match self.test_runner.run_tests(&generated_files).await {
-            Ok(test_results) => {
+            Ok(mut test_results) => {
+                let options = request.generation_options.as_ref();
+                let promoted_warnings = if options.and_then(|o| o.strict).unwrap_or(false) {
+                    test_results.promote_warnings(
+                        options.and_then(|o| o.strict_categories.as_deref()),
+                    )
+                } else {
+                    Vec::new()
+                };
+
                 tracing::info!("Validation completed: {} files tested", test_results.total_files_tested());
                 
                 let compilation_success_rate = if test_results.results_by_language.values()
@@ -237,6 +365,7 @@ impl AxiomSwiftClientGenerator {
                     total_errors: test_results.total_errors(),
                     total_warnings: test_results.total_warnings(),
                     compilation_success_rate,
+                    promoted_warnings,
                 })
             }
             Err(e) => {
@@ -246,6 +375,12 @@ impl AxiomSwiftClientGenerator {
             }
         };
         
+        let manifest = GenerationManifest::new(fingerprint, &output_dir, &generated_files);
+        if let Err(e) = manifest.save(&output_dir).await {
+            tracing::warn!("Failed to write generation manifest: {}", e);
+            warnings.push(format!("Failed to write generation manifest: {}", e));
+        }
+
         let generation_time = start_time.elapsed().as_millis() as u64;
         
         tracing::info!(
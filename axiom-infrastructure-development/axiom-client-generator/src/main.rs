@@ -1,6 +1,8 @@
 use anyhow::Result;
 use axiom_universal_client_generator::{GenerateRequest, UniversalClientGenerator};
+use axiom_universal_client_generator::proto::analyzer::format_suggestions;
 use axiom_universal_client_generator::validation::SwiftValidator;
+use axiom_universal_client_generator::validation::swift::{is_warning_category, WARNING_CATEGORIES};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -14,6 +16,16 @@ struct Cli {
     #[arg(short, long, value_enum, default_value_t = LogLevel::Info)]
     log_level: LogLevel,
     
+    /// Load templates from disk and re-read them on every render (template development)
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = "src/templates/swift"
+    )]
+    templates_dev: Option<PathBuf>,
+    
     #[command(subcommand)]
     command: Commands,
 }
@@ -77,6 +89,14 @@ enum Commands {
         #[arg(long)]
         swift_framework_version: Option<String>,
         
+        /// Swift module holding the generated contracts; client files import it
+        #[arg(long)]
+        swift_package_name: Option<String>,
+        
+        /// Emit a Package.swift with separate contracts and clients products
+        #[arg(long, requires = "swift_package_name")]
+        split_contracts_module: bool,
+        
         /// Kotlin framework version
         #[arg(long)]
         kotlin_framework_version: Option<String>,
@@ -85,6 +105,10 @@ enum Commands {
         #[arg(long)]
         generate_tests: bool,
         
+        /// Generate SwiftUI preview states for each service's state
+        #[arg(long)]
+        generate_previews: bool,
+        
         /// Force overwrite existing files
         #[arg(long)]
         force_overwrite: bool,
@@ -97,6 +121,22 @@ enum Commands {
         #[arg(long)]
         skip_compilation: bool,
         
+        /// License header for generated files (inline text or @path/to/header.txt)
+        #[arg(long)]
+        file_header: Option<String>,
+        
+        /// Treat validation warnings as errors and exit non-zero on any
+        #[arg(long)]
+        strict: bool,
+        
+        /// Only promote warnings in these categories (comma-separated, implies --strict)
+        #[arg(long, value_delimiter = ',')]
+        strict_categories: Option<Vec<String>>,
+        
+        /// Warn when the parsed schema is estimated above this many megabytes
+        #[arg(long, value_name = "MB")]
+        max_memory_warn: Option<u64>,
+        
         /// Show detailed progress information
         #[arg(long)]
         verbose: bool,
@@ -118,6 +158,50 @@ enum Commands {
         /// Categorize issues by type
         #[arg(long)]
         categorize: bool,
+        
+        /// Treat validation warnings as errors and exit non-zero on any
+        #[arg(long)]
+        strict: bool,
+        
+        /// Only promote warnings in these categories (comma-separated, implies --strict)
+        #[arg(long, value_delimiter = ',')]
+        strict_categories: Option<Vec<String>>,
+        
+        /// Also suggest axiom options for un-annotated services in these protos
+        #[arg(long, value_name = "PROTO_PATH")]
+        suggest: Option<PathBuf>,
+    },
+    /// Compare proto files against the schema recorded by the last generation
+    CheckCompat {
+        /// Path to proto file or directory
+        #[arg(short, long)]
+        proto_path: PathBuf,
+        
+        /// Output directory of the previous generation (holds the manifest)
+        #[arg(short, long)]
+        output_path: PathBuf,
+        
+        /// Report breaking changes without failing
+        #[arg(long)]
+        allow_breaking: bool,
+        
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare Swift and Kotlin identifiers derived from proto files
+    AuditNaming {
+        /// Path to proto file or directory
+        #[arg(short, long)]
+        proto_path: PathBuf,
+        
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+        
+        /// Exit with an error when any identifier diverges
+        #[arg(long)]
+        fail_on_divergence: bool,
     },
     /// Check system setup and dependencies
     Doctor,
@@ -152,6 +236,15 @@ async fn main() -> Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    if let Some(template_dir) = cli.templates_dev {
+        if !template_dir.is_dir() {
+            eprintln!("❌ Template directory does not exist: {}", template_dir.display());
+            std::process::exit(1);
+        }
+        tracing::info!("Template dev mode: re-reading templates from {}", template_dir.display());
+        axiom_universal_client_generator::generators::swift::templates::enable_dev_templates(template_dir);
+    }
+
     match cli.command {
         Commands::McpServer { progress, debug, validate } => {
             tracing::info!("Starting Enhanced Axiom Universal Client Generator MCP Server");
@@ -163,11 +256,18 @@ async fn main() -> Result<()> {
             languages,
             services,
             swift_framework_version,
+            swift_package_name,
+            split_contracts_module,
             kotlin_framework_version,
             generate_tests,
+            generate_previews,
             force_overwrite,
             validate,
             skip_compilation,
+            file_header,
+            strict,
+            strict_categories,
+            max_memory_warn,
             verbose,
         } => {
             tracing::info!("Running CLI generation");
@@ -177,11 +277,18 @@ async fn main() -> Result<()> {
                 languages,
                 services,
                 swift_framework_version,
+                swift_package_name,
+                split_contracts_module,
                 kotlin_framework_version,
                 generate_tests,
+                generate_previews,
                 force_overwrite,
                 validate,
                 skip_compilation,
+                file_header,
+                strict,
+                strict_categories,
+                max_memory_warn,
                 verbose,
             ).await
         }
@@ -190,9 +297,29 @@ async fn main() -> Result<()> {
             detailed,
             compile_check,
             categorize,
+            strict,
+            strict_categories,
+            suggest,
         } => {
             tracing::info!("Running validation");
-            run_validation(path, detailed, compile_check, categorize).await
+            run_validation(path, detailed, compile_check, categorize, strict, strict_categories, suggest).await
+        }
+        Commands::CheckCompat {
+            proto_path,
+            output_path,
+            allow_breaking,
+            json,
+        } => {
+            tracing::info!("Running schema compatibility check");
+            run_check_compat(proto_path, output_path, allow_breaking, json).await
+        }
+        Commands::AuditNaming {
+            proto_path,
+            json,
+            fail_on_divergence,
+        } => {
+            tracing::info!("Running naming audit");
+            run_audit_naming(proto_path, json, fail_on_divergence).await
         }
         Commands::Doctor => {
             tracing::info!("Running system check");
@@ -221,13 +348,14 @@ async fn run_mcp_server(progress: bool, debug: bool, validate: bool) -> Result<(
         }
     }
     
+    let mut progress_handle = None;
     let server = if progress {
         // Create progress channel for real-time updates
         let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<ProgressUpdate>();
         
         // Spawn progress monitoring task
         if debug {
-            tokio::spawn(async move {
+            progress_handle = Some(tokio::spawn(async move {
                 while let Some(update) = progress_rx.recv().await {
                     tracing::info!(
                         "Progress [{}]: {} - {:.1}% - {}",
@@ -241,7 +369,7 @@ async fn run_mcp_server(progress: bool, debug: bool, validate: bool) -> Result<(
                         tracing::debug!("Progress details: {}", serde_json::to_string_pretty(&details).unwrap_or_default());
                     }
                 }
-            });
+            }));
         }
         
         AxiomMcpServer::new_with_progress(progress_tx).await?
@@ -250,10 +378,19 @@ async fn run_mcp_server(progress: bool, debug: bool, validate: bool) -> Result<(
     };
     
     tracing::info!("MCP Server ready for Claude Code integration");
-    tracing::info!("Protocol version: 2024-11-05");
-    tracing::info!("Available tools: generate_axiom_clients, validate_proto, doctor, get_examples");
+    tracing::info!(
+        "Supported protocol versions: {}",
+        axiom_universal_client_generator::mcp::protocol::ProtocolVersion::supported_strings().join(", ")
+    );
+    tracing::info!("Available tools: generate_axiom_clients, validate_proto, suggest_axiom_options, check_proto_compatibility, doctor, get_examples");
     
     server.run().await?;
+
+    // Dropping the server closes the progress channel so pending updates drain
+    drop(server);
+    if let Some(handle) = progress_handle {
+        let _ = handle.await;
+    }
     
     Ok(())
 }
@@ -264,11 +401,18 @@ async fn run_cli_generation(
     languages: Vec<String>,
     services: Option<Vec<String>>,
     swift_framework_version: Option<String>,
+    swift_package_name: Option<String>,
+    split_contracts_module: bool,
     kotlin_framework_version: Option<String>,
     generate_tests: bool,
+    generate_previews: bool,
     force_overwrite: bool,
     validate: bool,
     skip_compilation: bool,
+    file_header: Option<String>,
+    strict: bool,
+    strict_categories: Option<Vec<String>>,
+    max_memory_warn: Option<u64>,
     verbose: bool,
 ) -> Result<()> {
     let start_time = Instant::now();
@@ -305,31 +449,41 @@ async fn run_cli_generation(
     
     let generator = UniversalClientGenerator::new().await?;
     
-    let request = GenerateRequest {
-        proto_path: proto_path.to_string_lossy().to_string(),
-        output_path: output_path.to_string_lossy().to_string(),
-        target_languages: languages.clone(),
-        services: services.clone(),
-        framework_config: Some(axiom_universal_client_generator::FrameworkConfig {
-            swift: swift_framework_version.map(|version| {
-                axiom_universal_client_generator::SwiftConfig {
-                    axiom_version: Some(version),
-                    client_suffix: Some("Client".to_string()),
-                    generate_tests: Some(generate_tests),
-                    package_name: None,
-                }
-            }),
-            kotlin: None,
-        }),
-        generation_options: Some(axiom_universal_client_generator::GenerationOptions {
-            generate_tests: Some(false),
-            generate_contracts: Some(true),
-            generate_clients: Some(true),
-            force_overwrite: Some(force_overwrite),
-            include_documentation: Some(true),
-            style_guide: Some("axiom".to_string()),
-        }),
-    };
+    let mut builder = GenerateRequest::builder(
+        proto_path.to_string_lossy(),
+        output_path.to_string_lossy(),
+    )
+    .languages(languages.iter().cloned())
+    .generate_tests(generate_tests)
+    .generate_previews(generate_previews)
+    .force_overwrite(force_overwrite);
+
+    if let Some(services) = services.clone() {
+        builder = builder.services(services);
+    }
+    if let Some(version) = swift_framework_version {
+        builder = builder.swift_axiom_version(version);
+    }
+    if let Some(package_name) = swift_package_name {
+        builder = builder.swift_package_name(package_name);
+    }
+    if split_contracts_module {
+        builder = builder.split_contracts_module(true);
+    }
+    if let Some(file_header) = file_header {
+        builder = builder.file_header(file_header);
+    }
+    if strict {
+        builder = builder.strict(true);
+    }
+    if let Some(categories) = strict_categories.clone() {
+        builder = builder.strict_categories(categories);
+    }
+    if let Some(megabytes) = max_memory_warn {
+        builder = builder.max_memory_warn_mb(megabytes);
+    }
+
+    let request = builder.build()?;
 
     if verbose {
         println!("🚀 Starting generation...");
@@ -376,7 +530,10 @@ async fn run_cli_generation(
                 
             if !swift_files.is_empty() {
                 let validator = SwiftValidator::new();
-                let validation_result = validator.validate_files(&swift_files).await?;
+                let mut validation_result = validator.validate_files(&swift_files).await?;
+                if strict || strict_categories.is_some() {
+                    validation_result.promote_warnings(strict_categories.as_deref());
+                }
                 
                 println!("{}", validation_result.summary());
                 
@@ -418,6 +575,17 @@ async fn run_cli_generation(
             }
         }
         
+        if let Some(summary) = response.validation.as_ref() {
+            if strict || strict_categories.is_some() {
+                print_promoted_warnings(&summary.promoted_warnings);
+                if !summary.all_valid {
+                    eprintln!("\n❌ Strict validation failed: {} errors ({} promoted from warnings)",
+                        summary.total_errors, summary.promoted_warnings.len());
+                    std::process::exit(1);
+                }
+            }
+        }
+        
         println!("\n🎉 Generation completed successfully!");
         if !validate {
             println!("💡 Run with --validate to check generated code quality");
@@ -443,14 +611,35 @@ async fn run_validation(
     detailed: bool,
     compile_check: bool,
     categorize: bool,
+    strict: bool,
+    strict_categories: Option<Vec<String>>,
+    suggest: Option<PathBuf>,
 ) -> Result<()> {
     println!("🔍 Running validation on: {}", path.display());
     
+    let strict = strict || strict_categories.is_some();
+    if let Some(unknown) = strict_categories.iter().flatten().find(|c| !is_warning_category(c)) {
+        eprintln!("❌ Unknown strict category: {}", unknown);
+        eprintln!("💡 Expected one of: {}", WARNING_CATEGORIES.join(", "));
+        std::process::exit(1);
+    }
+    
     if !path.exists() {
         eprintln!("❌ Path does not exist: {}", path.display());
         std::process::exit(1);
     }
     
+    if let Some(proto_path) = suggest {
+        if !proto_path.exists() {
+            eprintln!("❌ Proto path does not exist: {}", proto_path.display());
+            std::process::exit(1);
+        }
+        let generator = UniversalClientGenerator::new().await?;
+        let suggestions = generator.suggest_options(&proto_path.to_string_lossy()).await?;
+        println!("💡 Suggested axiom options for {}:\n", proto_path.display());
+        print!("{}", format_suggestions(&suggestions));
+    }
+    
     // Find all Swift files recursively
     let mut swift_files = Vec::new();
     if path.is_file() && path.extension().map_or(false, |ext| ext == "swift") {
@@ -474,7 +663,12 @@ async fn run_validation(
     println!("📁 Found {} Swift files", swift_files.len());
     
     let validator = SwiftValidator::new();
-    let validation_result = validator.validate_files(&swift_files).await?;
+    let mut validation_result = validator.validate_files(&swift_files).await?;
+    let promoted = if strict {
+        validation_result.promote_warnings(strict_categories.as_deref())
+    } else {
+        Vec::new()
+    };
     
     if detailed {
         println!("\n{}", validation_result.detailed_report());
@@ -529,6 +723,98 @@ async fn run_validation(
         }
     }
     
+    if strict {
+        print_promoted_warnings(&promoted);
+        if !validation_result.is_valid() {
+            eprintln!("\n❌ Strict validation failed: {} errors ({} promoted from warnings)",
+                validation_result.errors.len(), promoted.len());
+            std::process::exit(1);
+        }
+    }
+    
+    Ok(())
+}
+
+fn print_promoted_warnings(promoted: &[String]) {
+    if promoted.is_empty() {
+        return;
+    }
+    println!("\n🔒 Warnings promoted to errors by strict mode ({}):", promoted.len());
+    for (i, warning) in promoted.iter().enumerate() {
+        println!("{}. {}", i + 1, warning.lines().next().unwrap_or(warning));
+    }
+}
+
+async fn run_check_compat(
+    proto_path: PathBuf,
+    output_path: PathBuf,
+    allow_breaking: bool,
+    json: bool,
+) -> Result<()> {
+    if !proto_path.exists() {
+        eprintln!("❌ Proto path does not exist: {}", proto_path.display());
+        std::process::exit(1);
+    }
+    
+    let generator = UniversalClientGenerator::new().await?;
+    let report = generator
+        .check_compatibility(&proto_path.to_string_lossy(), &output_path.to_string_lossy())
+        .await?;
+    
+    let Some(report) = report else {
+        println!("ℹ️  No generation manifest found in {}", output_path.display());
+        println!("💡 Run `generate` once to record a baseline schema");
+        return Ok(());
+    };
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("🔍 Schema compatibility: {}", proto_path.display());
+        println!("   Previous: {}", &report.previous_hash[..12.min(report.previous_hash.len())]);
+        println!("   Current:  {}", &report.current_hash[..12.min(report.current_hash.len())]);
+        println!();
+        print!("{}", report.format_report());
+    }
+    
+    if !report.is_compatible() {
+        if allow_breaking {
+            println!("\n⚠️  {} breaking change(s) allowed by --allow-breaking", report.breaking.len());
+        } else {
+            eprintln!("\n❌ {} breaking change(s) found", report.breaking.len());
+            eprintln!("💡 Pass --allow-breaking to accept them");
+            std::process::exit(1);
+        }
+    }
+    
+    Ok(())
+}
+
+async fn run_audit_naming(proto_path: PathBuf, json: bool, fail_on_divergence: bool) -> Result<()> {
+    if !proto_path.exists() {
+        eprintln!("❌ Proto path does not exist: {}", proto_path.display());
+        std::process::exit(1);
+    }
+    
+    let generator = UniversalClientGenerator::new().await?;
+    let report = generator.audit_naming(&proto_path.to_string_lossy()).await?;
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("🔤 Naming audit (Swift vs Kotlin): {}", proto_path.display());
+        println!();
+        print!("{}", report.format_table());
+        if report.is_aligned() {
+            println!("\n✅ All identifiers match across platforms");
+        }
+    }
+    
+    if fail_on_divergence && !report.is_aligned() {
+        eprintln!("\n❌ {} identifier(s) diverge between Swift and Kotlin", report.divergences.len());
+        std::process::exit(1);
+    }
+    
     Ok(())
 }
 
@@ -1,6 +1,7 @@
 use crate::error::{Error, Result};
 use crate::mcp::protocol::*;
 use crate::mcp::server::ProgressUpdate;
+use crate::proto::analyzer::{format_suggestions, SuggestionConfidence};
 use crate::{GenerateRequest, AxiomSwiftClientGenerator};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -17,8 +18,12 @@ impl McpHandlers {
         Self
     }
 
-    /// Handle initialize request
-    pub async fn handle_initialize(&self, params: Option<Value>) -> Result<InitializeResult> {
+    /// Handle initialize request, replying with the already negotiated version
+    pub async fn handle_initialize(
+        &self,
+        params: Option<Value>,
+        protocol_version: ProtocolVersion,
+    ) -> Result<InitializeResult> {
         tracing::info!("Handling initialize request");
 
         // Parse client info if provided
@@ -32,7 +37,7 @@ impl McpHandlers {
         }
 
         Ok(InitializeResult {
-            protocol_version: "2024-11-05".to_string(),
+            protocol_version: protocol_version.as_str().to_string(),
             capabilities: ServerCapabilities {
                 tools: Some(ToolsCapability {
                     list_changed: Some(false),
@@ -70,11 +75,59 @@ impl McpHandlers {
                             "type": "boolean",
                             "description": "Provide detailed validation results",
                             "default": false
+                        },
+                        "suggest": {
+                            "type": "boolean",
+                            "description": "Append axiom option suggestions for un-annotated services and methods",
+                            "default": false
                         }
                     },
                     "required": ["proto_path"]
                 })
             },
+            Tool {
+                name: "suggest_axiom_options".to_string(),
+                description: "Suggests axiom service and method options (as proto snippets with confidence levels) for un-annotated protos".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "proto_path": {
+                            "type": "string",
+                            "description": "Path to proto file or directory to inspect"
+                        },
+                        "min_confidence": {
+                            "type": "string",
+                            "enum": ["low", "medium", "high"],
+                            "description": "Only return suggestions at or above this confidence",
+                            "default": "low"
+                        }
+                    },
+                    "required": ["proto_path"]
+                })
+            },
+            Tool {
+                name: "check_proto_compatibility".to_string(),
+                description: "Diffs proto files against the schema recorded by the last generation and reports breaking changes".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "proto_path": {
+                            "type": "string",
+                            "description": "Path to proto file or directory to check"
+                        },
+                        "output_path": {
+                            "type": "string",
+                            "description": "Output directory of the previous generation (contains .axiom-manifest.json)"
+                        },
+                        "allow_breaking": {
+                            "type": "boolean",
+                            "description": "Report breaking changes without marking the result as an error",
+                            "default": false
+                        }
+                    },
+                    "required": ["proto_path", "output_path"]
+                })
+            },
             Tool {
                 name: "doctor".to_string(),
                 description: "Diagnoses system setup and provides recommendations".to_string(),
@@ -173,7 +226,12 @@ impl McpHandlers {
                                 },
                                 "package_name": {
                                     "type": "string",
-                                    "description": "Swift package name for imports"
+                                    "description": "Swift module holding the generated contracts; client files import it"
+                                },
+                                "split_contracts_module": {
+                                    "type": "boolean",
+                                    "description": "Generate a Package.swift with separate contracts and <package_name>Clients products (requires package_name)",
+                                    "default": false
                                 },
                                 "enable_validation": {
                                     "type": "boolean",
@@ -213,6 +271,30 @@ impl McpHandlers {
                             "description": "Code style guide to follow",
                             "default": "axiom"
                         },
+                        "file_header": {
+                            "type": "string",
+                            "description": "License header for generated files: inline text or @path/to/header.txt. Supports {year}, {file_name} and {generator_version}"
+                        },
+                        "generate_previews": {
+                            "type": "boolean",
+                            "description": "Generate <State>+Previews.swift with empty, loading, populated and error preview states",
+                            "default": false
+                        },
+                        "strict": {
+                            "type": "boolean",
+                            "description": "Treat validation warnings as errors; the result is an error if any remain",
+                            "default": false
+                        },
+                        "strict_categories": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Only promote warnings in these categories (naming_conventions, actor_patterns, imports, access_control, best_practices); implies strict"
+                        },
+                        "max_memory_warn_mb": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Add a warning when the parsed schema is estimated above this many megabytes"
+                        },
                         "real_time_validation": {
                             "type": "boolean",
                             "description": "Provide real-time validation feedback during generation",
@@ -290,7 +372,12 @@ impl McpHandlers {
                                         },
                                         "package_name": {
                                             "type": "string",
-                                            "description": "Swift package name for imports"
+                                            "description": "Swift module holding the generated contracts; client files import it"
+                                        },
+                                        "split_contracts_module": {
+                                            "type": "boolean",
+                                            "description": "Generate a Package.swift with separate contracts and <package_name>Clients products (requires package_name)",
+                                            "default": false
                                         }
                                     }
                                 },
@@ -344,6 +431,30 @@ impl McpHandlers {
                                     "enum": ["axiom", "language-standard", "custom"],
                                     "description": "Code style guide to follow",
                                     "default": "axiom"
+                                },
+                                "file_header": {
+                                    "type": "string",
+                                    "description": "License header for generated files: inline text or @path/to/header.txt"
+                                },
+                                "generate_previews": {
+                                    "type": "boolean",
+                                    "description": "Generate SwiftUI preview states for each service's state",
+                                    "default": false
+                                },
+                                "strict": {
+                                    "type": "boolean",
+                                    "description": "Treat validation warnings as errors",
+                                    "default": false
+                                },
+                                "strict_categories": {
+                                    "type": "array",
+                                    "items": {"type": "string"},
+                                    "description": "Only promote warnings in these categories; implies strict"
+                                },
+                                "max_memory_warn_mb": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "description": "Warn when the parsed schema is estimated above this many megabytes"
                                 }
                             }
                         }
@@ -368,6 +479,12 @@ impl McpHandlers {
             "validate_proto" => {
                 self.handle_validate_proto(generator, params.arguments, progress_sender).await
             }
+            "check_proto_compatibility" => {
+                self.handle_check_compatibility(generator, params.arguments).await
+            }
+            "suggest_axiom_options" => {
+                self.handle_suggest_options(generator, params.arguments).await
+            }
             "doctor" => {
                 self.handle_doctor(generator, params.arguments, progress_sender).await
             }
@@ -412,6 +529,11 @@ impl McpHandlers {
             });
         }
 
+        // Strict mode turns a failed validation into a failed tool call
+        let strict = request.generation_options.as_ref()
+            .and_then(|o| o.strict)
+            .unwrap_or(false);
+
         // Generate the clients
         let response = generator.generate(request).await?;
         
@@ -428,9 +550,15 @@ impl McpHandlers {
             });
         }
 
+        let strict_failed = strict && response.validation.as_ref().is_some_and(|v| !v.all_valid);
+
         let content = if response.success {
             let mut output = String::new();
-            output.push_str(&format!("✅ Successfully generated {} files:\n\n", response.generated_files.len()));
+            if strict_failed {
+                output.push_str(&format!("❌ Strict validation failed for {} generated files:\n\n", response.generated_files.len()));
+            } else {
+                output.push_str(&format!("✅ Successfully generated {} files:\n\n", response.generated_files.len()));
+            }
             
             for file in &response.generated_files {
                 output.push_str(&format!("📄 {}\n", file));
@@ -443,6 +571,13 @@ impl McpHandlers {
                 }
             }
 
+            if let Some(validation) = response.validation.as_ref().filter(|v| !v.promoted_warnings.is_empty()) {
+                output.push_str("\n🔒 Promoted to errors (strict):\n");
+                for warning in &validation.promoted_warnings {
+                    output.push_str(&format!("• {}\n", warning.lines().next().unwrap_or(warning)));
+                }
+            }
+
             output.push_str(&format!("\n📊 Generation Stats:\n"));
             output.push_str(&format!("• Time: {}ms\n", response.stats.generation_time_ms));
             output.push_str(&format!("• Proto files: {}\n", response.stats.proto_files_processed));
@@ -462,7 +597,7 @@ impl McpHandlers {
 
         Ok(CallToolResult {
             content: vec![ToolContent::text(content)],
-            is_error: Some(!response.success),
+            is_error: Some(!response.success || strict_failed),
         })
     }
 
@@ -478,68 +613,85 @@ impl McpHandlers {
             .ok_or_else(|| Error::McpError("Missing required parameter: output_path".to_string()))?
             .to_string();
 
-        let target_languages = args.get("target_languages")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect()
-            })
-            .unwrap_or_else(|| vec!["swift".to_string()]);
-
-        let services = args.get("services")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect()
-            });
+        let mut builder = GenerateRequest::builder(proto_path, output_path);
+
+        if let Some(languages) = args.get("target_languages").and_then(|v| v.as_array()) {
+            builder = builder.languages(languages.iter().filter_map(|v| v.as_str()));
+        }
+
+        if let Some(services) = args.get("services").and_then(|v| v.as_array()) {
+            builder = builder.services(services.iter().filter_map(|v| v.as_str()));
+        }
+
+        let str_arg = |v: &Value, key: &str| v.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+        let bool_arg = |v: &Value, key: &str| v.get(key).and_then(|v| v.as_bool());
 
         // Parse framework config
-        let framework_config = args.get("framework_config")
-            .and_then(|v| {
-                let swift_config = v.get("swift").map(|sc| {
-                    crate::SwiftConfig {
-                        axiom_version: sc.get("axiom_version").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                        client_suffix: sc.get("client_suffix").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                        generate_tests: sc.get("generate_tests").and_then(|v| v.as_bool()),
-                        package_name: sc.get("package_name").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    }
-                });
+        let swift_config = args.get("framework_config").and_then(|v| v.get("swift"));
+        if let Some(sc) = swift_config {
+            if let Some(version) = str_arg(sc, "axiom_version") {
+                builder = builder.swift_axiom_version(version);
+            }
+            if let Some(suffix) = str_arg(sc, "client_suffix") {
+                builder = builder.swift_client_suffix(suffix);
+            }
+            if let Some(package_name) = str_arg(sc, "package_name") {
+                builder = builder.swift_package_name(package_name);
+            }
+            if let Some(enabled) = bool_arg(sc, "split_contracts_module") {
+                builder = builder.split_contracts_module(enabled);
+            }
+        }
 
-                Some(crate::FrameworkConfig {
-                    swift: swift_config,
-                    kotlin: None,
-                })
-            });
+        // Parse generation options; a generation_options flag wins over the Swift one
+        let options = args.get("generation_options");
+        let generate_tests = options
+            .and_then(|v| bool_arg(v, "generate_tests"))
+            .or_else(|| swift_config.and_then(|sc| bool_arg(sc, "generate_tests")));
+        if let Some(enabled) = generate_tests {
+            builder = builder.generate_tests(enabled);
+        }
 
-        // Parse generation options
-        let generation_options = args.get("generation_options")
-            .map(|v| {
-                crate::GenerationOptions {
-                    generate_contracts: v.get("generate_contracts").and_then(|v| v.as_bool()),
-                    generate_clients: v.get("generate_clients").and_then(|v| v.as_bool()),
-                    generate_tests: v.get("generate_tests").and_then(|v| v.as_bool()),
-                    force_overwrite: v.get("force_overwrite").and_then(|v| v.as_bool()),
-                    include_documentation: v.get("include_documentation").and_then(|v| v.as_bool()),
-                    style_guide: v.get("style_guide").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                }
-            });
+        if let Some(v) = options {
+            if let Some(enabled) = bool_arg(v, "generate_contracts") {
+                builder = builder.generate_contracts(enabled);
+            }
+            if let Some(enabled) = bool_arg(v, "generate_clients") {
+                builder = builder.generate_clients(enabled);
+            }
+            if let Some(enabled) = bool_arg(v, "force_overwrite") {
+                builder = builder.force_overwrite(enabled);
+            }
+            if let Some(enabled) = bool_arg(v, "include_documentation") {
+                builder = builder.include_documentation(enabled);
+            }
+            if let Some(style_guide) = str_arg(v, "style_guide") {
+                builder = builder.style_guide(style_guide);
+            }
+            if let Some(file_header) = str_arg(v, "file_header") {
+                builder = builder.file_header(file_header);
+            }
+            if let Some(enabled) = bool_arg(v, "generate_previews") {
+                builder = builder.generate_previews(enabled);
+            }
+            if let Some(enabled) = bool_arg(v, "strict") {
+                builder = builder.strict(enabled);
+            }
+            if let Some(categories) = v.get("strict_categories").and_then(|v| v.as_array()) {
+                builder = builder.strict_categories(categories.iter().filter_map(|c| c.as_str()));
+            }
+            if let Some(megabytes) = v.get("max_memory_warn_mb").and_then(|v| v.as_u64()) {
+                builder = builder.max_memory_warn_mb(megabytes);
+            }
+        }
 
-        Ok(GenerateRequest {
-            proto_path,
-            output_path,
-            target_languages,
-            services,
-            framework_config,
-            generation_options,
-        })
+        builder.build()
     }
     
     /// Handle validate_proto tool call
     async fn handle_validate_proto(
         &self,
-        _generator: &Arc<AxiomSwiftClientGenerator>,
+        generator: &Arc<AxiomSwiftClientGenerator>,
         arguments: Option<HashMap<String, Value>>,
         progress_sender: Option<mpsc::UnboundedSender<ProgressUpdate>>,
     ) -> Result<CallToolResult> {
@@ -556,6 +708,10 @@ impl McpHandlers {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
+        let suggest = args.get("suggest")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         if let Some(ref sender) = progress_sender {
             let _ = sender.send(ProgressUpdate {
                 operation_id: "validate".to_string(),
@@ -574,12 +730,110 @@ impl McpHandlers {
             format!("✅ Proto file validation passed: {}", proto_path)
         };
 
+        let content = if suggest {
+            let suggestions = generator.suggest_options(&proto_path).await?;
+            format!("{}\n\n💡 Suggested axiom options:\n\n{}", content, format_suggestions(&suggestions))
+        } else {
+            content
+        };
+
         Ok(CallToolResult {
             content: vec![ToolContent::text(content)],
             is_error: Some(false),
         })
     }
     
+    /// Handle suggest_axiom_options tool call
+    async fn handle_suggest_options(
+        &self,
+        generator: &Arc<AxiomSwiftClientGenerator>,
+        arguments: Option<HashMap<String, Value>>,
+    ) -> Result<CallToolResult> {
+        let args = arguments.ok_or_else(|| {
+            Error::McpError("Missing arguments for suggest_axiom_options".to_string())
+        })?;
+
+        let proto_path = args.get("proto_path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::McpError("Missing required parameter: proto_path".to_string()))?;
+
+        let min_confidence = match args.get("min_confidence").and_then(|v| v.as_str()) {
+            None | Some("low") => SuggestionConfidence::Low,
+            Some("medium") => SuggestionConfidence::Medium,
+            Some("high") => SuggestionConfidence::High,
+            Some(other) => {
+                return Err(Error::McpError(format!(
+                    "Invalid min_confidence '{}': expected low, medium or high",
+                    other
+                )))
+            }
+        };
+
+        let suggestions: Vec<_> = generator.suggest_options(proto_path).await?
+            .into_iter()
+            .filter(|s| s.confidence >= min_confidence)
+            .collect();
+        let structured = serde_json::to_string_pretty(&suggestions)
+            .map_err(|e| Error::McpError(format!("Failed to serialize suggestions: {}", e)))?;
+
+        Ok(CallToolResult {
+            content: vec![
+                ToolContent::text(format_suggestions(&suggestions)),
+                ToolContent::text(structured),
+            ],
+            is_error: Some(false),
+        })
+    }
+
+    /// Handle check_proto_compatibility tool call
+    async fn handle_check_compatibility(
+        &self,
+        generator: &Arc<AxiomSwiftClientGenerator>,
+        arguments: Option<HashMap<String, Value>>,
+    ) -> Result<CallToolResult> {
+        let args = arguments.ok_or_else(|| {
+            Error::McpError("Missing arguments for check_proto_compatibility".to_string())
+        })?;
+
+        let proto_path = args.get("proto_path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::McpError("Missing required parameter: proto_path".to_string()))?;
+
+        let output_path = args.get("output_path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::McpError("Missing required parameter: output_path".to_string()))?;
+
+        let allow_breaking = args.get("allow_breaking")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let Some(report) = generator.check_compatibility(proto_path, output_path).await? else {
+            return Ok(CallToolResult {
+                content: vec![ToolContent::text(format!(
+                    "ℹ️ No generation manifest found in {}. Generate once to record a baseline schema.",
+                    output_path
+                ))],
+                is_error: Some(false),
+            });
+        };
+
+        let summary = if report.is_compatible() {
+            format!("✅ No breaking changes ({} additive)\n\n", report.additive.len())
+        } else {
+            format!("❌ {} breaking change(s) found\n\n", report.breaking.len())
+        };
+        let structured = serde_json::to_string_pretty(&report)
+            .map_err(|e| Error::McpError(format!("Failed to serialize report: {}", e)))?;
+
+        Ok(CallToolResult {
+            content: vec![
+                ToolContent::text(format!("{}{}", summary, report.format_report())),
+                ToolContent::text(structured),
+            ],
+            is_error: Some(!report.is_compatible() && !allow_breaking),
+        })
+    }
+
     /// Handle doctor tool call
     async fn handle_doctor(
         &self,
@@ -11,4 +11,4 @@ pub mod realtime_validator;
 pub mod server;
 
 pub use realtime_validator::{RealtimeValidator, ValidationIssue, IssueSeverity};
-pub use server::{AxiomMcpServer, ProgressUpdate};
\ No newline at end of file
+pub use server::{AxiomMcpServer, Lifecycle, ProgressUpdate};
\ No newline at end of file
@@ -122,6 +122,95 @@ pub struct InitializeResult {
     pub server_info: ServerInfo,
 }
 
+/// MCP protocol revisions understood by this server, oldest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ProtocolVersion {
+    #[serde(rename = "2024-11-05")]
+    V2024_11_05,
+    #[serde(rename = "2025-03-26")]
+    V2025_03_26,
+    #[serde(rename = "2025-06-18")]
+    V2025_06_18,
+}
+
+impl ProtocolVersion {
+    /// All supported versions, oldest first
+    pub const SUPPORTED: &'static [ProtocolVersion] = &[
+        ProtocolVersion::V2024_11_05,
+        ProtocolVersion::V2025_03_26,
+        ProtocolVersion::V2025_06_18,
+    ];
+
+    /// Wire representation of the version
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtocolVersion::V2024_11_05 => "2024-11-05",
+            ProtocolVersion::V2025_03_26 => "2025-03-26",
+            ProtocolVersion::V2025_06_18 => "2025-06-18",
+        }
+    }
+
+    /// Parse an exact protocol version string
+    pub fn parse(version: &str) -> Option<Self> {
+        Self::SUPPORTED.iter().copied().find(|v| v.as_str() == version)
+    }
+
+    /// Newest version this server speaks
+    pub fn latest() -> Self {
+        *Self::SUPPORTED.last().unwrap()
+    }
+
+    /// Pick the highest version supported by both sides.
+    ///
+    /// MCP versions are ISO dates, so a client requesting a revision we don't
+    /// know is assumed to also speak every earlier one. Returns `None` when the
+    /// request predates (or isn't) any version we support.
+    pub fn negotiate(requested: &str) -> Option<Self> {
+        if !is_protocol_date(requested) {
+            return None;
+        }
+
+        Self::SUPPORTED
+            .iter()
+            .rev()
+            .copied()
+            .find(|v| v.as_str() <= requested)
+    }
+
+    /// Tool results may carry a `structuredContent` payload (2025-06-18+)
+    pub fn supports_structured_content(&self) -> bool {
+        *self >= ProtocolVersion::V2025_06_18
+    }
+
+    /// Wire strings of all supported versions
+    pub fn supported_strings() -> Vec<&'static str> {
+        Self::SUPPORTED.iter().map(|v| v.as_str()).collect()
+    }
+}
+
+impl Default for ProtocolVersion {
+    /// Behavior assumed before a session has negotiated a version
+    fn default() -> Self {
+        ProtocolVersion::V2024_11_05
+    }
+}
+
+impl std::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Check for a `YYYY-MM-DD` version string
+fn is_protocol_date(version: &str) -> bool {
+    let bytes = version.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
 /// Tool definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tool {
@@ -146,6 +235,42 @@ pub struct CallToolResult {
     pub is_error: Option<bool>,
 }
 
+impl CallToolResult {
+    /// Serialize the result in the shape expected by the negotiated protocol.
+    ///
+    /// Only `text` content exists on the wire; JSON payloads are flattened to
+    /// text and, where supported, mirrored into `structuredContent`.
+    pub fn to_versioned_value(&self, version: ProtocolVersion) -> serde_json::Value {
+        let mut structured = None;
+        let content: Vec<serde_json::Value> = self
+            .content
+            .iter()
+            .map(|item| {
+                if structured.is_none() && item.content_type == "json" {
+                    structured = item.data.clone();
+                }
+
+                // Text content has no `mimeType` field in any revision
+                serde_json::json!({
+                    "type": "text",
+                    "text": item.text,
+                })
+            })
+            .collect();
+
+        let mut result = serde_json::json!({
+            "content": content,
+            "isError": self.is_error.unwrap_or(false),
+        });
+        if version.supports_structured_content() {
+            if let Some(structured) = structured {
+                result["structuredContent"] = structured;
+            }
+        }
+        result
+    }
+}
+
 /// Tool content with enhanced formatting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolContent {
@@ -261,6 +386,18 @@ impl McpError {
         }
     }
     
+    /// Reject an initialize request whose protocol version we can't serve
+    pub fn unsupported_protocol_version(requested: &str) -> Self {
+        Self {
+            code: error_codes::INVALID_PARAMS,
+            message: "Unsupported protocol version".to_string(),
+            data: Some(serde_json::json!({
+                "supported": ProtocolVersion::supported_strings(),
+                "requested": requested
+            })),
+        }
+    }
+    
     /// Create error with contextual information
     pub fn contextual_error(code: i32, message: &str, context: serde_json::Value) -> Self {
         Self {
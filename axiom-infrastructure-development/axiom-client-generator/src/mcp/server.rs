@@ -1,18 +1,40 @@
 use crate::error::{Error, Result};
 use crate::mcp::{handlers::McpHandlers, protocol::*};
+use crate::utils::file_manager::TempFileTracker;
 use crate::AxiomSwiftClientGenerator;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::{mpsc, RwLock};
+use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::task::JoinSet;
 use tokio::time::{interval, Duration};
 use tracing::{debug, error, info, warn};
 
+/// Server lifecycle as driven by `shutdown` / `exit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    /// Accepting requests
+    Running,
+    /// `shutdown` received; only `exit` is expected from here on
+    ShuttingDown,
+    /// `exit` received or input closed
+    Exited,
+}
+
+/// Tool call currently executing on its own task
+#[derive(Debug, Clone)]
+struct InFlightCall {
+    id: Value,
+    temp_files: TempFileTracker,
+}
+
 /// Session state for tracking operations
 #[derive(Debug, Clone)]
 struct SessionState {
     client_info: Option<ClientInfo>,
+    protocol_version: Option<ProtocolVersion>,
+    lifecycle: Lifecycle,
     last_generation_time: Option<std::time::Instant>,
     generation_cache: HashMap<String, Value>,
 }
@@ -42,6 +64,8 @@ impl AxiomMcpServer {
         let handlers = McpHandlers::new();
         let session_state = Arc::new(RwLock::new(SessionState {
             client_info: None,
+            protocol_version: None,
+            lifecycle: Lifecycle::Running,
             last_generation_time: None,
             generation_cache: HashMap::new(),
         }));
@@ -61,57 +85,40 @@ impl AxiomMcpServer {
         Ok(server)
     }
 
-    /// Run the MCP server with enhanced performance and batching
+    /// Run the MCP server over stdio
     pub async fn run(&self) -> Result<()> {
         info!("Starting Axiom Universal Client Generator MCP Server v{}", env!("CARGO_PKG_VERSION"));
         info!("Enhanced features: progress reporting, caching, real-time validation");
 
-        let stdin = io::stdin();
-        let mut stdout = io::stdout();
-        let mut reader = BufReader::new(stdin);
-        
-        // Message batching for improved performance
-        let (message_tx, mut message_rx) = mpsc::unbounded_channel::<String>();
+        self.run_with_io(BufReader::new(io::stdin()), io::stdout()).await
+    }
+
+    /// Run the MCP server over an arbitrary transport.
+    ///
+    /// Returns once the client sends `exit`, the input reaches EOF, or reading
+    /// fails. Tool calls still running at that point (or when `shutdown`
+    /// arrives) are cancelled, temp files from their interrupted writes are
+    /// removed, and queued responses are flushed before returning.
+    pub async fn run_with_io<R, W>(&self, reader: R, mut writer: W) -> Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
         let (response_tx, mut response_rx) = mpsc::unbounded_channel::<String>();
         
-        // Spawn message processor
-        let server_clone = self.clone();
-        let processor_handle = tokio::spawn(async move {
-            while let Some(message) = message_rx.recv().await {
-                match server_clone.handle_message(&message).await {
-                    Ok(Some(response)) => {
-                        if let Ok(response_json) = serde_json::to_string(&response) {
-                            let _ = response_tx.send(response_json);
-                        }
-                    }
-                    Ok(None) => {}
-                    Err(e) => {
-                        error!("Error processing message: {}", e);
-                        let error_response = McpResponse::error(
-                            Value::Null,
-                            McpError::internal_error(&e.to_string()),
-                        );
-                        if let Ok(error_json) = serde_json::to_string(&error_response) {
-                            let _ = response_tx.send(error_json);
-                        }
-                    }
-                }
-            }
-        });
-        
         // Spawn response writer
         let writer_handle = tokio::spawn(async move {
             while let Some(response) = response_rx.recv().await {
                 debug!("Sending MCP response: {}", response);
-                if let Err(e) = stdout.write_all(response.as_bytes()).await {
+                if let Err(e) = writer.write_all(response.as_bytes()).await {
                     error!("Failed to write response: {}", e);
                     break;
                 }
-                if let Err(e) = stdout.write_all(b"\n").await {
+                if let Err(e) = writer.write_all(b"\n").await {
                     error!("Failed to write newline: {}", e);
                     break;
                 }
-                if let Err(e) = stdout.flush().await {
+                if let Err(e) = writer.flush().await {
                     error!("Failed to flush output: {}", e);
                     break;
                 }
@@ -136,45 +143,153 @@ impl AxiomMcpServer {
             }
         });
 
+        // Tool calls run on their own tasks so shutdown can interrupt them
+        let mut tool_calls = JoinSet::new();
+        let in_flight: Arc<Mutex<HashMap<String, InFlightCall>>> = Arc::new(Mutex::new(HashMap::new()));
+
         // Main message reading loop
+        let mut lines = reader.lines();
         loop {
-            let mut line = String::new();
-            match reader.read_line(&mut line).await {
-                Ok(0) => {
-                    info!("EOF reached, shutting down MCP server");
-                    break;
-                }
-                Ok(_) => {
-                    let line = line.trim();
-                    if line.is_empty() {
-                        continue;
-                    }
+            tokio::select! {
+                line = lines.next_line() => {
+                    match line {
+                        Ok(Some(line)) => {
+                            let line = line.trim();
+                            if line.is_empty() {
+                                continue;
+                            }
+
+                            debug!("Received MCP message: {}", line);
+
+                            if let Some(request) = self.take_tool_call(line).await {
+                                self.spawn_tool_call(request, &mut tool_calls, &in_flight, &response_tx).await;
+                                continue;
+                            }
 
-                    debug!("Received MCP message: {}", line);
-                    
-                    // Send to processor
-                    if let Err(e) = message_tx.send(line.to_string()) {
-                        error!("Failed to queue message: {}", e);
-                        break;
+                            match self.handle_message(line).await {
+                                Ok(Some(response)) => send_response(&response_tx, &response),
+                                Ok(None) => {}
+                                Err(e) => {
+                                    error!("Error processing message: {}", e);
+                                    send_response(
+                                        &response_tx,
+                                        &McpResponse::error(Value::Null, McpError::internal_error(&e.to_string())),
+                                    );
+                                }
+                            }
+
+                            match self.lifecycle().await {
+                                Lifecycle::Running => {}
+                                Lifecycle::ShuttingDown => {
+                                    self.cancel_tool_calls(&mut tool_calls, &in_flight, &response_tx).await;
+                                }
+                                Lifecycle::Exited => {
+                                    info!("Exit notification received, stopping MCP server");
+                                    break;
+                                }
+                            }
+                        }
+                        Ok(None) => {
+                            info!("EOF reached, shutting down MCP server");
+                            break;
+                        }
+                        Err(e) => {
+                            error!("Error reading from input: {}", e);
+                            break;
+                        }
                     }
                 }
-                Err(e) => {
-                    error!("Error reading from stdin: {}", e);
-                    break;
-                }
+                // Reap finished tool calls so the set doesn't grow unbounded
+                Some(_) = tool_calls.join_next(), if !tool_calls.is_empty() => {}
             }
         }
         
-        // Cleanup
-        processor_handle.abort();
-        writer_handle.abort();
+        // Cleanup: EOF and read errors count as shutdown too
+        self.session_state.write().await.lifecycle = Lifecycle::Exited;
+        self.cancel_tool_calls(&mut tool_calls, &in_flight, &response_tx).await;
         cleanup_handle.abort();
 
+        // Let the writer drain whatever is still queued
+        drop(response_tx);
+        if let Err(e) = writer_handle.await {
+            warn!("Response writer terminated abnormally: {}", e);
+        }
+
         Ok(())
     }
 
-    /// Handle an incoming MCP message
-    async fn handle_message(&self, message: &str) -> Result<Option<McpResponse>> {
+    /// Current lifecycle state of the session
+    pub async fn lifecycle(&self) -> Lifecycle {
+        self.session_state.read().await.lifecycle
+    }
+
+    /// Parse a `tools/call` request that should run on its own task
+    async fn take_tool_call(&self, message: &str) -> Option<McpRequest> {
+        let request = serde_json::from_str::<McpRequest>(message).ok()?;
+        if request.method != "tools/call" || self.lifecycle().await != Lifecycle::Running {
+            return None;
+        }
+        Some(request)
+    }
+
+    /// Run a tool call in the background, tracking it until it responds
+    async fn spawn_tool_call(
+        &self,
+        request: McpRequest,
+        tool_calls: &mut JoinSet<()>,
+        in_flight: &Arc<Mutex<HashMap<String, InFlightCall>>>,
+        response_tx: &mpsc::UnboundedSender<String>,
+    ) {
+        let call_key = request.id.to_string();
+        let temp_files = TempFileTracker::new();
+
+        in_flight.lock().await.insert(call_key.clone(), InFlightCall {
+            id: request.id.clone(),
+            temp_files: temp_files.clone(),
+        });
+
+        let server = self.clone();
+        let in_flight = in_flight.clone();
+        let response_tx = response_tx.clone();
+        tool_calls.spawn(async move {
+            let response = temp_files.scope(server.handle_request(request)).await;
+            in_flight.lock().await.remove(&call_key);
+            send_response(&response_tx, &response);
+        });
+    }
+
+    /// Abort running tool calls and remove temp files from their interrupted writes
+    async fn cancel_tool_calls(
+        &self,
+        tool_calls: &mut JoinSet<()>,
+        in_flight: &Mutex<HashMap<String, InFlightCall>>,
+        response_tx: &mpsc::UnboundedSender<String>,
+    ) {
+        if tool_calls.is_empty() {
+            return;
+        }
+
+        warn!("Cancelling {} in-flight tool call(s)", tool_calls.len());
+        tool_calls.abort_all();
+        while tool_calls.join_next().await.is_some() {}
+
+        let interrupted: Vec<InFlightCall> = in_flight.lock().await.drain().map(|(_, call)| call).collect();
+        for call in interrupted {
+            match call.temp_files.remove_all().await {
+                Ok(0) => {}
+                Ok(removed) => info!("Removed {} temporary file(s) left by call {}", removed, call.id),
+                Err(e) => warn!("Failed to clean up temporary files of call {}: {}", call.id, e),
+            }
+
+            send_response(
+                response_tx,
+                &McpResponse::error(call.id, McpError::internal_error("Request cancelled: server is shutting down")),
+            );
+        }
+    }
+
+    /// Handle an incoming MCP message, returning the response to send (if any)
+    pub async fn handle_message(&self, message: &str) -> Result<Option<McpResponse>> {
         // Try to parse as request first
         if let Ok(request) = serde_json::from_str::<McpRequest>(message) {
             return Ok(Some(self.handle_request(request).await));
@@ -191,8 +306,16 @@ impl AxiomMcpServer {
 
     /// Handle MCP request
     async fn handle_request(&self, request: McpRequest) -> McpResponse {
+        if self.lifecycle().await != Lifecycle::Running && request.method != "shutdown" {
+            return McpResponse::error(
+                request.id,
+                McpError::invalid_request("Server is shutting down"),
+            );
+        }
+
         match request.method.as_str() {
             "initialize" => self.handle_initialize(request).await,
+            "shutdown" => self.handle_shutdown(request).await,
             "tools/list" => self.handle_tools_list(request).await,
             "tools/call" => self.handle_tools_call(request).await,
             _ => McpResponse::error(
@@ -211,6 +334,10 @@ impl AxiomMcpServer {
             "notifications/cancelled" => {
                 tracing::info!("Operation cancelled");
             }
+            "exit" => {
+                tracing::info!("Client requested exit");
+                self.session_state.write().await.lifecycle = Lifecycle::Exited;
+            }
             _ => {
                 tracing::warn!("Unknown notification method: {}", notification.method);
             }
@@ -218,21 +345,60 @@ impl AxiomMcpServer {
         Ok(())
     }
 
-    /// Handle initialize request with enhanced session tracking
+    /// Handle initialize request with protocol version negotiation
     async fn handle_initialize(&self, request: McpRequest) -> McpResponse {
-        match self.handlers.handle_initialize(request.params.clone()).await {
+        let init_params = match request
+            .params
+            .clone()
+            .map(serde_json::from_value::<InitializeParams>)
+        {
+            Some(Ok(params)) => params,
+            Some(Err(e)) => {
+                return McpResponse::error(
+                    request.id,
+                    McpError::invalid_params(&format!("Invalid initialize parameters: {}", e)),
+                );
+            }
+            None => {
+                return McpResponse::error(
+                    request.id,
+                    McpError::invalid_params("Missing initialize parameters"),
+                );
+            }
+        };
+
+        let protocol_version = match ProtocolVersion::negotiate(&init_params.protocol_version) {
+            Some(version) => version,
+            None => {
+                warn!(
+                    "Rejecting client {} with unsupported protocol version {}",
+                    init_params.client_info.name, init_params.protocol_version
+                );
+                return McpResponse::error(
+                    request.id,
+                    McpError::unsupported_protocol_version(&init_params.protocol_version),
+                );
+            }
+        };
+
+        if protocol_version.as_str() != init_params.protocol_version {
+            info!(
+                "Client requested protocol {}, negotiated down to {}",
+                init_params.protocol_version, protocol_version
+            );
+        }
+
+        match self.handlers.handle_initialize(request.params, protocol_version).await {
             Ok(result) => {
-                // Store client info in session state
-                if let Some(params) = request.params {
-                    if let Ok(init_params) = serde_json::from_value::<InitializeParams>(params) {
-                        let mut state = self.session_state.write().await;
-                        state.client_info = Some(init_params.client_info.clone());
-                        info!("Client connected: {} v{}", 
-                            init_params.client_info.name, 
-                            init_params.client_info.version
-                        );
-                    }
-                }
+                // Store client info and negotiated version in session state
+                let mut state = self.session_state.write().await;
+                state.client_info = Some(init_params.client_info.clone());
+                state.protocol_version = Some(protocol_version);
+                info!("Client connected: {} v{} (protocol {})", 
+                    init_params.client_info.name, 
+                    init_params.client_info.version,
+                    protocol_version
+                );
                 
                 McpResponse::success(request.id, serde_json::to_value(result).unwrap())
             }
@@ -240,6 +406,17 @@ impl AxiomMcpServer {
         }
     }
 
+    /// Handle shutdown request; the server stops once `exit` or EOF follows
+    async fn handle_shutdown(&self, request: McpRequest) -> McpResponse {
+        let mut state = self.session_state.write().await;
+        if state.lifecycle == Lifecycle::Running {
+            info!("Shutdown requested");
+            state.lifecycle = Lifecycle::ShuttingDown;
+        }
+
+        McpResponse::success(request.id, Value::Null)
+    }
+
     /// Handle tools/list request
     async fn handle_tools_list(&self, request: McpRequest) -> McpResponse {
         match self.handlers.handle_tools_list().await {
@@ -300,9 +477,11 @@ impl AxiomMcpServer {
 
         let start_time = std::time::Instant::now();
         
+        let protocol_version = self.protocol_version().await;
+
         match self.handlers.handle_tool_call(&self.generator, call_params, self.progress_sender.clone()).await {
             Ok(result) => {
-                let result_value = serde_json::to_value(&result).unwrap();
+                let result_value = result.to_versioned_value(protocol_version);
                 
                 // Cache successful results
                 {
@@ -344,7 +523,7 @@ impl AxiomMcpServer {
                     content: vec![ToolContent::text(format!("Error: {}", e))],
                     is_error: Some(true),
                 };
-                McpResponse::success(request.id, serde_json::to_value(error_result).unwrap())
+                McpResponse::success(request.id, error_result.to_versioned_value(protocol_version))
             }
         }
     }
@@ -360,11 +539,17 @@ impl AxiomMcpServer {
         Ok(serde_json::to_value(result)?)
     }
     
+    /// Protocol version negotiated during initialize, or the legacy default
+    pub async fn protocol_version(&self) -> ProtocolVersion {
+        self.session_state.read().await.protocol_version.unwrap_or_default()
+    }
+    
     /// Get session statistics
     pub async fn get_session_stats(&self) -> Value {
         let state = self.session_state.read().await;
         serde_json::json!({
             "client_info": state.client_info,
+            "protocol_version": state.protocol_version.map(|v| v.as_str()),
             "cache_entries": state.generation_cache.len(),
             "last_generation": state.last_generation_time.map(|t| t.elapsed().as_secs()),
             "server_version": env!("CARGO_PKG_VERSION")
@@ -372,6 +557,13 @@ impl AxiomMcpServer {
     }
 }
 
+/// Queue a response for the writer task
+fn send_response(response_tx: &mpsc::UnboundedSender<String>, response: &McpResponse) {
+    if let Ok(response_json) = serde_json::to_string(response) {
+        let _ = response_tx.send(response_json);
+    }
+}
+
 // Make server cloneable for async processing
 impl Clone for AxiomMcpServer {
     fn clone(&self) -> Self {
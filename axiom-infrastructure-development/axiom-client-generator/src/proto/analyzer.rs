@@ -1,6 +1,9 @@
 use crate::error::{Error, Result};
+use crate::proto::metadata::MetadataExtractor;
 use crate::proto::types::*;
-use std::collections::{HashMap, HashSet};
+use heck::ToSnakeCase;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 
 /// Proto schema analyzer for validation and optimization
 pub struct ProtoAnalyzer {
@@ -242,6 +245,183 @@ impl ProtoAnalyzer {
         Ok(())
     }
 
+    /// Diff the analyzed schema against a previously generated one
+    pub fn check_compatibility(&self, previous: &SchemaFingerprint) -> CompatibilityReport {
+        CompatibilityReport::between(previous, &SchemaFingerprint::from_schema(&self.schema))
+    }
+
+    /// Suggest axiom options for services and methods that don't declare them
+    ///
+    /// Inspects method names, request/response shapes and id fields. Methods
+    /// with an explicit `collection_name` and services that declare
+    /// `collections` count as annotated and are skipped; streaming methods
+    /// and methods whose shape isn't recognized produce no suggestion.
+    pub fn suggest_options(&self) -> Vec<Suggestion> {
+        let mut suggestions = Vec::new();
+
+        for service in &self.schema.services {
+            let mut collections: Vec<SuggestedCollection> = Vec::new();
+            let mut paginated = false;
+            let mut confidence = SuggestionConfidence::Medium;
+            let mut method_suggestions = Vec::new();
+
+            for method in &service.methods {
+                if method.client_streaming || method.server_streaming {
+                    continue;
+                }
+                let Some(shape) = self.method_shape(method) else {
+                    continue;
+                };
+
+                paginated |= shape.paginated;
+                if let (Some(name), Some(item_type)) = (&shape.collection, &shape.entity) {
+                    if !collections.iter().any(|c| &c.name == name) {
+                        collections.push(SuggestedCollection {
+                            name: name.clone(),
+                            item_type: item_type.clone(),
+                            primary_key: shape.primary_key.clone(),
+                        });
+                    }
+                    confidence = confidence.max(shape.confidence);
+                }
+
+                let annotated = method.options.axiom_method.as_ref()
+                    .is_some_and(|o| o.collection_name.is_some());
+                if !annotated {
+                    method_suggestions.push(Suggestion {
+                        service: service.name.clone(),
+                        method: Some(method.name.clone()),
+                        snippet: shape.method_snippet(),
+                        confidence: shape.confidence,
+                        reason: shape.reason,
+                    });
+                }
+            }
+
+            let service_annotated = service.options.axiom_service.as_ref()
+                .is_some_and(|o| !o.collections.is_empty());
+            if !service_annotated && !collections.is_empty() {
+                let names: Vec<&str> = collections.iter().map(|c| c.name.as_str()).collect();
+                suggestions.push(Suggestion {
+                    service: service.name.clone(),
+                    method: None,
+                    snippet: service_snippet(&collections, paginated),
+                    confidence,
+                    reason: format!("Methods operate on collections: {}", names.join(", ")),
+                });
+            }
+
+            suggestions.extend(method_suggestions);
+        }
+
+        suggestions
+    }
+
+    /// Classify a method by its name and request/response messages
+    fn method_shape(&self, method: &Method) -> Option<MethodShape> {
+        let method_name = method.name.to_snake_case();
+        let verb = method_name.split('_').next().unwrap_or_default();
+        let input = self.schema.find_message(short_type_name(&method.input_type));
+        let output = self.schema.find_message(short_type_name(&method.output_type));
+
+        let paginated = input.is_some_and(|m| {
+            m.fields.iter().any(|f| PAGINATION_FIELDS.contains(&f.name.as_str()))
+        });
+
+        // A response carrying `repeated <Entity>` is a collection read
+        let repeated = output.and_then(|m| {
+            m.fields.iter().find(|f| {
+                f.label == FieldLabel::Repeated
+                    && self.schema.find_message(short_type_name(&f.field_type)).is_some()
+            })
+        });
+
+        let mut shape = MethodShape {
+            strategy: StateUpdateStrategy::Custom,
+            confidence: SuggestionConfidence::Low,
+            reason: String::new(),
+            collection: None,
+            entity: None,
+            primary_key: None,
+            paginated,
+        };
+
+        if let Some(field) = repeated {
+            if !READ_VERBS.contains(&verb) {
+                return None;
+            }
+            let entity = short_type_name(&field.field_type);
+            shape.strategy = StateUpdateStrategy::ReplaceAll;
+            shape.confidence = SuggestionConfidence::High;
+            shape.reason = format!("Returns repeated {} in `{}`; replaces the collection", entity, field.name);
+            shape.collection = Some(field.name.clone());
+            shape.set_entity(self.schema.find_message(entity));
+            return Some(shape);
+        }
+
+        let entity = output.and_then(|m| self.entity_of(m));
+        let target = entity.or_else(|| {
+            let name = method.name.get(verb.len()..).unwrap_or_default();
+            self.schema.find_message(name).filter(|m| id_field(m).is_some())
+        });
+        let input_has_id = input.is_some_and(|m| {
+            id_field(m).is_some()
+                || target.is_some_and(|t| m.fields.iter().any(|f| short_type_name(&f.field_type) == t.name))
+        });
+
+        match verb {
+            v if CREATE_VERBS.contains(&v) => {
+                shape.strategy = StateUpdateStrategy::Append;
+                shape.confidence = if entity.is_some() { SuggestionConfidence::High } else { SuggestionConfidence::Medium };
+                shape.reason = match entity {
+                    Some(e) => format!("Creates and returns a {}; appends it to the collection", e.name),
+                    None => "Create method; appends the result to the collection".to_string(),
+                };
+            }
+            v if UPDATE_VERBS.contains(&v) => {
+                shape.strategy = StateUpdateStrategy::UpdateById;
+                shape.confidence = if entity.is_some() && input_has_id { SuggestionConfidence::High } else { SuggestionConfidence::Medium };
+                shape.reason = "Update method keyed by id; replaces the matching item".to_string();
+            }
+            v if DELETE_VERBS.contains(&v) => {
+                shape.strategy = StateUpdateStrategy::RemoveById;
+                shape.confidence = if input_has_id { SuggestionConfidence::High } else { SuggestionConfidence::Medium };
+                shape.reason = "Delete method keyed by id; removes the matching item".to_string();
+            }
+            v if READ_VERBS.contains(&v) && entity.is_some() => {
+                shape.strategy = StateUpdateStrategy::UpdateById;
+                shape.confidence = SuggestionConfidence::Medium;
+                shape.reason = format!(
+                    "Returns a single {}; refreshes the cached item by id",
+                    entity.map(|e| e.name.as_str()).unwrap_or_default()
+                );
+            }
+            _ if short_type_name(&method.output_type) == "Empty" => {
+                shape.strategy = StateUpdateStrategy::NoChange;
+                shape.confidence = SuggestionConfidence::Low;
+                shape.reason = "Returns Empty and matches no CRUD pattern; state is likely unchanged".to_string();
+                return Some(shape);
+            }
+            _ => return None,
+        }
+
+        shape.set_entity(target);
+        Some(shape)
+    }
+
+    /// The entity a message carries: itself when it has an id field, or the
+    /// sole message field of a wrapper like `CreateTaskResponse { Task task = 1; }`
+    fn entity_of<'a>(&'a self, message: &'a Message) -> Option<&'a Message> {
+        if id_field(message).is_some() {
+            return Some(message);
+        }
+        match message.fields.as_slice() {
+            [field] => self.schema.find_message(short_type_name(&field.field_type))
+                .filter(|m| id_field(m).is_some()),
+            _ => None,
+        }
+    }
+
     /// Check if schema uses timestamp types
     fn has_timestamp_usage(&self) -> bool {
         self.schema.messages.iter().any(|m| {
@@ -346,4 +526,567 @@ impl AnalysisResult {
 
         output
     }
-}
\ No newline at end of file
+}
+/// Compact, order-independent summary of a schema's wire-relevant surface
+///
+/// Persisted in the generation manifest so later generations can detect
+/// breaking backend changes without keeping the original proto files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaFingerprint {
+    /// SHA-256 over the canonical form of the fields below
+    pub hash: String,
+    /// Methods per service
+    pub services: BTreeMap<String, BTreeMap<String, MethodSignature>>,
+    /// Fields per message (nested messages use `Outer.Inner`), keyed by field number
+    pub messages: BTreeMap<String, BTreeMap<i32, FieldSignature>>,
+    /// Values per enum, keyed by value number
+    pub enums: BTreeMap<String, BTreeMap<i32, String>>,
+}
+
+/// Wire-relevant shape of an RPC method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodSignature {
+    pub input_type: String,
+    pub output_type: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
+
+/// Wire-relevant shape of a message field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSignature {
+    pub name: String,
+    pub field_type: String,
+    pub repeated: bool,
+}
+
+impl SchemaFingerprint {
+    /// Fingerprint a parsed schema
+    pub fn from_schema(schema: &ProtoSchema) -> Self {
+        let services = schema.services.iter()
+            .map(|service| {
+                let methods = service.methods.iter()
+                    .map(|method| {
+                        (method.name.clone(), MethodSignature {
+                            input_type: normalize_type(&method.input_type),
+                            output_type: normalize_type(&method.output_type),
+                            client_streaming: method.client_streaming,
+                            server_streaming: method.server_streaming,
+                        })
+                    })
+                    .collect();
+                (service.name.clone(), methods)
+            })
+            .collect();
+
+        let mut messages = BTreeMap::new();
+        let mut enums = BTreeMap::new();
+        for message in &schema.messages {
+            collect_message_signatures(message, None, &mut messages, &mut enums);
+        }
+        for enum_type in &schema.enums {
+            enums.insert(enum_type.name.clone(), enum_signature(enum_type));
+        }
+
+        let mut fingerprint = Self {
+            hash: String::new(),
+            services,
+            messages,
+            enums,
+        };
+        fingerprint.hash = fingerprint.compute_hash();
+        fingerprint
+    }
+
+    fn compute_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        // BTreeMaps serialize in key order, so the canonical form is stable
+        let canonical = serde_json::to_vec(&(&self.services, &self.messages, &self.enums))
+            .unwrap_or_default();
+        format!("{:x}", Sha256::digest(&canonical))
+    }
+}
+
+fn normalize_type(type_name: &str) -> String {
+    type_name.trim_start_matches('.').to_string()
+}
+
+fn enum_signature(enum_type: &Enum) -> BTreeMap<i32, String> {
+    enum_type.values.iter()
+        .map(|value| (value.number, value.name.clone()))
+        .collect()
+}
+
+fn collect_message_signatures(
+    message: &Message,
+    parent: Option<&str>,
+    messages: &mut BTreeMap<String, BTreeMap<i32, FieldSignature>>,
+    enums: &mut BTreeMap<String, BTreeMap<i32, String>>,
+) {
+    let name = match parent {
+        Some(parent) => format!("{}.{}", parent, message.name),
+        None => message.name.clone(),
+    };
+
+    let fields = message.fields.iter()
+        .map(|field| {
+            (field.number, FieldSignature {
+                name: field.name.clone(),
+                field_type: normalize_type(&field.field_type),
+                repeated: field.label == FieldLabel::Repeated,
+            })
+        })
+        .collect();
+    messages.insert(name.clone(), fields);
+
+    for nested_enum in &message.nested_enums {
+        enums.insert(format!("{}.{}", name, nested_enum.name), enum_signature(nested_enum));
+    }
+    for nested in &message.nested_messages {
+        collect_message_signatures(nested, Some(&name), messages, enums);
+    }
+}
+
+/// Category of a schema change between two generations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaChangeKind {
+    // Breaking
+    ServiceRemoved,
+    MethodRemoved,
+    MethodSignatureChanged,
+    MessageRemoved,
+    FieldRemoved,
+    FieldTypeChanged,
+    FieldNumberReused,
+    EnumRemoved,
+    EnumValueRemoved,
+    EnumValueNumberReused,
+    // Additive
+    ServiceAdded,
+    MethodAdded,
+    MessageAdded,
+    FieldAdded,
+    EnumAdded,
+    EnumValueAdded,
+}
+
+impl SchemaChangeKind {
+    /// Whether existing clients break when this change ships
+    pub fn is_breaking(&self) -> bool {
+        !matches!(
+            self,
+            Self::ServiceAdded
+                | Self::MethodAdded
+                | Self::MessageAdded
+                | Self::FieldAdded
+                | Self::EnumAdded
+                | Self::EnumValueAdded
+        )
+    }
+}
+
+/// A single difference between the previous and current schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    /// Change category
+    pub kind: SchemaChangeKind,
+    /// Dotted location, e.g. `TaskService.GetTask` or `Task.title`
+    pub location: String,
+    /// Human-readable description
+    pub description: String,
+}
+
+/// Result of diffing two schema fingerprints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    /// Fingerprint hash of the previously generated schema
+    pub previous_hash: String,
+    /// Fingerprint hash of the current schema
+    pub current_hash: String,
+    /// Changes that break existing clients
+    pub breaking: Vec<SchemaChange>,
+    /// Changes that existing clients tolerate
+    pub additive: Vec<SchemaChange>,
+}
+
+impl CompatibilityReport {
+    /// Diff two fingerprints
+    pub fn between(previous: &SchemaFingerprint, current: &SchemaFingerprint) -> Self {
+        let mut changes = Vec::new();
+        diff_services(previous, current, &mut changes);
+        diff_messages(previous, current, &mut changes);
+        diff_enums(previous, current, &mut changes);
+
+        let (breaking, additive) = changes.into_iter().partition(|c| c.kind.is_breaking());
+
+        Self {
+            previous_hash: previous.hash.clone(),
+            current_hash: current.hash.clone(),
+            breaking,
+            additive,
+        }
+    }
+
+    /// True when no breaking changes were found
+    pub fn is_compatible(&self) -> bool {
+        self.breaking.is_empty()
+    }
+
+    /// Get the report as a formatted string
+    pub fn format_report(&self) -> String {
+        let mut output = String::new();
+
+        if self.breaking.is_empty() && self.additive.is_empty() {
+            output.push_str("✅ Schema unchanged since last generation\n");
+            return output;
+        }
+
+        if !self.breaking.is_empty() {
+            output.push_str(&format!("BREAKING CHANGES ({}):\n", self.breaking.len()));
+            for change in &self.breaking {
+                output.push_str(&format!("  ❌ {}\n", change.description));
+            }
+            output.push('\n');
+        }
+
+        if !self.additive.is_empty() {
+            output.push_str(&format!("ADDITIVE CHANGES ({}):\n", self.additive.len()));
+            for change in &self.additive {
+                output.push_str(&format!("  ➕ {}\n", change.description));
+            }
+        }
+
+        output
+    }
+}
+
+fn change(kind: SchemaChangeKind, location: String, description: String) -> SchemaChange {
+    SchemaChange { kind, location, description }
+}
+
+fn diff_services(previous: &SchemaFingerprint, current: &SchemaFingerprint, changes: &mut Vec<SchemaChange>) {
+    for (service, old_methods) in &previous.services {
+        let Some(new_methods) = current.services.get(service) else {
+            changes.push(change(
+                SchemaChangeKind::ServiceRemoved,
+                service.clone(),
+                format!("Service {} was removed", service),
+            ));
+            continue;
+        };
+
+        for (method, old_sig) in old_methods {
+            let location = format!("{}.{}", service, method);
+            match new_methods.get(method) {
+                None => changes.push(change(
+                    SchemaChangeKind::MethodRemoved,
+                    location.clone(),
+                    format!("Method {} was removed or renamed", location),
+                )),
+                Some(new_sig) if new_sig != old_sig => changes.push(change(
+                    SchemaChangeKind::MethodSignatureChanged,
+                    location.clone(),
+                    format!(
+                        "Method {} changed from ({}) -> {} to ({}) -> {}",
+                        location, old_sig.input_type, old_sig.output_type,
+                        new_sig.input_type, new_sig.output_type
+                    ),
+                )),
+                Some(_) => {}
+            }
+        }
+
+        for method in new_methods.keys().filter(|m| !old_methods.contains_key(*m)) {
+            let location = format!("{}.{}", service, method);
+            changes.push(change(
+                SchemaChangeKind::MethodAdded,
+                location.clone(),
+                format!("Method {} was added", location),
+            ));
+        }
+    }
+
+    for service in current.services.keys().filter(|s| !previous.services.contains_key(*s)) {
+        changes.push(change(
+            SchemaChangeKind::ServiceAdded,
+            service.clone(),
+            format!("Service {} was added", service),
+        ));
+    }
+}
+
+fn diff_messages(previous: &SchemaFingerprint, current: &SchemaFingerprint, changes: &mut Vec<SchemaChange>) {
+    for (message, old_fields) in &previous.messages {
+        let Some(new_fields) = current.messages.get(message) else {
+            changes.push(change(
+                SchemaChangeKind::MessageRemoved,
+                message.clone(),
+                format!("Message {} was removed", message),
+            ));
+            continue;
+        };
+
+        for (number, old_field) in old_fields {
+            let location = format!("{}.{}", message, old_field.name);
+            match new_fields.get(number) {
+                None => changes.push(change(
+                    SchemaChangeKind::FieldRemoved,
+                    location.clone(),
+                    format!("Field {} (#{}) was removed", location, number),
+                )),
+                Some(new_field) if new_field.name != old_field.name => changes.push(change(
+                    SchemaChangeKind::FieldNumberReused,
+                    location.clone(),
+                    format!(
+                        "Field number #{} in {} was reused: {} is now {}",
+                        number, message, old_field.name, new_field.name
+                    ),
+                )),
+                Some(new_field) if new_field.field_type != old_field.field_type || new_field.repeated != old_field.repeated => {
+                    changes.push(change(
+                        SchemaChangeKind::FieldTypeChanged,
+                        location.clone(),
+                        format!(
+                            "Field {} changed type from {} to {}",
+                            location,
+                            describe_field_type(old_field),
+                            describe_field_type(new_field)
+                        ),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (number, new_field) in new_fields.iter().filter(|(n, _)| !old_fields.contains_key(*n)) {
+            let location = format!("{}.{}", message, new_field.name);
+            changes.push(change(
+                SchemaChangeKind::FieldAdded,
+                location.clone(),
+                format!("Field {} (#{}) was added", location, number),
+            ));
+        }
+    }
+
+    for message in current.messages.keys().filter(|m| !previous.messages.contains_key(*m)) {
+        changes.push(change(
+            SchemaChangeKind::MessageAdded,
+            message.clone(),
+            format!("Message {} was added", message),
+        ));
+    }
+}
+
+fn describe_field_type(field: &FieldSignature) -> String {
+    if field.repeated {
+        format!("repeated {}", field.field_type)
+    } else {
+        field.field_type.clone()
+    }
+}
+
+fn diff_enums(previous: &SchemaFingerprint, current: &SchemaFingerprint, changes: &mut Vec<SchemaChange>) {
+    for (enum_name, old_values) in &previous.enums {
+        let Some(new_values) = current.enums.get(enum_name) else {
+            changes.push(change(
+                SchemaChangeKind::EnumRemoved,
+                enum_name.clone(),
+                format!("Enum {} was removed", enum_name),
+            ));
+            continue;
+        };
+
+        for (number, old_name) in old_values {
+            let location = format!("{}.{}", enum_name, old_name);
+            match new_values.get(number) {
+                None => changes.push(change(
+                    SchemaChangeKind::EnumValueRemoved,
+                    location.clone(),
+                    format!("Enum value {} (= {}) was removed", location, number),
+                )),
+                Some(new_name) if new_name != old_name => changes.push(change(
+                    SchemaChangeKind::EnumValueNumberReused,
+                    location.clone(),
+                    format!(
+                        "Enum value number {} in {} was reused: {} is now {}",
+                        number, enum_name, old_name, new_name
+                    ),
+                )),
+                Some(_) => {}
+            }
+        }
+
+        for (number, new_name) in new_values.iter().filter(|(n, _)| !old_values.contains_key(*n)) {
+            let location = format!("{}.{}", enum_name, new_name);
+            changes.push(change(
+                SchemaChangeKind::EnumValueAdded,
+                location.clone(),
+                format!("Enum value {} (= {}) was added", location, number),
+            ));
+        }
+    }
+
+    for enum_name in current.enums.keys().filter(|e| !previous.enums.contains_key(*e)) {
+        changes.push(change(
+            SchemaChangeKind::EnumAdded,
+            enum_name.clone(),
+            format!("Enum {} was added", enum_name),
+        ));
+    }
+}
+
+/// Request fields that indicate a paginated list
+const PAGINATION_FIELDS: &[&str] = &["page_size", "page_token", "cursor", "offset"];
+const READ_VERBS: &[&str] = &["get", "list", "search", "query", "find", "fetch", "load"];
+const CREATE_VERBS: &[&str] = &["create", "add", "insert"];
+const UPDATE_VERBS: &[&str] = &["update", "edit", "modify", "patch", "set"];
+const DELETE_VERBS: &[&str] = &["delete", "remove", "archive"];
+
+/// How sure the analyzer is that a suggestion matches the method's intent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionConfidence {
+    /// Only the method name matched a pattern
+    Low,
+    /// The name matched and the messages are consistent with it
+    Medium,
+    /// Name and message shapes (collections, id fields) agree
+    High,
+}
+
+impl SuggestionConfidence {
+    /// Lowercase label used in reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+/// A proto option snippet to add to an un-annotated service or method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// Service the option belongs to
+    pub service: String,
+    /// Method the option belongs to; `None` for service-level options
+    pub method: Option<String>,
+    /// Option declaration to paste inside the service or rpc body
+    pub snippet: String,
+    /// How likely the snippet is right as-is
+    pub confidence: SuggestionConfidence,
+    /// What in the schema led to the suggestion
+    pub reason: String,
+}
+
+impl Suggestion {
+    /// Dotted location, e.g. `TaskService` or `TaskService.GetTask`
+    pub fn location(&self) -> String {
+        match &self.method {
+            Some(method) => format!("{}.{}", self.service, method),
+            None => self.service.clone(),
+        }
+    }
+}
+
+/// Format suggestions for terminal and MCP output
+pub fn format_suggestions(suggestions: &[Suggestion]) -> String {
+    if suggestions.is_empty() {
+        return "✅ No option suggestions; services are already annotated\n".to_string();
+    }
+
+    let mut output = String::new();
+    for suggestion in suggestions {
+        output.push_str(&format!("💡 {} [{}]\n", suggestion.location(), suggestion.confidence.label()));
+        output.push_str(&format!("   {}\n", suggestion.reason));
+        for line in suggestion.snippet.lines() {
+            output.push_str(&format!("   {}\n", line));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// What a method appears to do to client state
+struct MethodShape {
+    strategy: StateUpdateStrategy,
+    confidence: SuggestionConfidence,
+    reason: String,
+    collection: Option<String>,
+    entity: Option<String>,
+    primary_key: Option<String>,
+    paginated: bool,
+}
+
+impl MethodShape {
+    /// Record the entity the method works on, deriving the collection name
+    /// when the response didn't name one
+    fn set_entity(&mut self, entity: Option<&Message>) {
+        let Some(entity) = entity else {
+            return;
+        };
+        self.entity = Some(entity.name.clone());
+        self.primary_key = id_field(entity).map(|f| f.name.clone());
+        if self.collection.is_none() {
+            self.collection = Some(MetadataExtractor::infer_collection_name(&entity.name.to_snake_case()));
+        }
+    }
+
+    fn method_snippet(&self) -> String {
+        let mut lines = vec![format!("  state_update_strategy: {}", self.strategy.proto_name())];
+        if self.strategy == StateUpdateStrategy::NoChange {
+            lines.push("  modifies_state: false".to_string());
+        } else if let Some(collection) = &self.collection {
+            lines.push(format!("  collection_name: \"{}\"", collection));
+        }
+        if matches!(self.strategy, StateUpdateStrategy::UpdateById | StateUpdateStrategy::RemoveById) {
+            if let Some(key) = self.primary_key.as_deref().filter(|k| *k != "id") {
+                lines.push(format!("  id_field_name: \"{}\"", key));
+            }
+        }
+
+        format!("option (axiom.options.v1.axiom_method) = {{\n{}\n}};", lines.join("\n"))
+    }
+}
+
+struct SuggestedCollection {
+    name: String,
+    item_type: String,
+    primary_key: Option<String>,
+}
+
+fn service_snippet(collections: &[SuggestedCollection], paginated: bool) -> String {
+    let mut lines = Vec::new();
+    if paginated {
+        lines.push("  supports_pagination: true".to_string());
+    }
+    lines.push("  collections: [".to_string());
+    for collection in collections {
+        let primary_key = collection.primary_key.as_ref()
+            .map(|key| format!(" primary_key: \"{}\"", key))
+            .unwrap_or_default();
+        lines.push(format!(
+            "    {{ name: \"{}\" item_type: \"{}\"{} }}",
+            collection.name, collection.item_type, primary_key
+        ));
+    }
+    lines.push("  ]".to_string());
+
+    format!("option (axiom.options.v1.axiom_service) = {{\n{}\n}};", lines.join("\n"))
+}
+
+/// `.pkg.v1.Task` -> `Task`
+fn short_type_name(type_name: &str) -> &str {
+    type_name.rsplit('.').next().unwrap_or(type_name)
+}
+
+/// The field identifying an entity: `id`, then `<entity>_id`
+fn id_field(message: &Message) -> Option<&Field> {
+    let entity_id = format!("{}_id", message.name.to_snake_case());
+    message.fields.iter()
+        .find(|f| f.name == "id")
+        .or_else(|| message.fields.iter().find(|f| f.name == entity_id))
+}
@@ -21,6 +21,7 @@ impl MetadataExtractor {
             swift_package_name: None,
             collections: Vec::new(),
             supports_pagination: Some(false),
+            error_type: None,
         };
 
         if let Some(opts) = options {
@@ -67,6 +68,7 @@ impl MetadataExtractor {
             id_field_name: None,
             supports_offline: Some(false),
             cache_strategy: CacheStrategy::Memory,
+            loading_timeout_ms: None,
         };
 
         if let Some(opts) = options {
@@ -335,6 +337,11 @@ impl MetadataExtractor {
                     options.supports_pagination = Some(val);
                 }
             }
+            if value.contains("error_type:") {
+                if let Some(name) = Self::extract_string_value(value, "error_type") {
+                    options.error_type = Some(name);
+                }
+            }
             tracing::debug!("Parsed service options from aggregate value");
         }
         Ok(())
@@ -376,6 +383,11 @@ impl MetadataExtractor {
                     options.id_field_name = Some(name);
                 }
             }
+            if value.contains("loading_timeout_ms:") {
+                if let Some(val) = Self::extract_u64_value(value, "loading_timeout_ms") {
+                    options.loading_timeout_ms = Some(val);
+                }
+            }
             tracing::debug!("Parsed method options from aggregate value");
         }
         Ok(())
@@ -438,6 +450,17 @@ impl MetadataExtractor {
         None
     }
 
+    /// Extract unsigned integer value from aggregate option text
+    fn extract_u64_value(text: &str, key: &str) -> Option<u64> {
+        let pattern = format!("{}: ", key);
+        if let Some(start) = text.find(&pattern) {
+            let start_pos = start + pattern.len();
+            let digits: String = text[start_pos..].chars().take_while(|c| c.is_ascii_digit()).collect();
+            return digits.parse().ok();
+        }
+        None
+    }
+
     /// Extract enum value from aggregate option text
     fn extract_enum_value(text: &str, key: &str) -> Option<String> {
         let pattern = format!("{}: ", key);
@@ -2,8 +2,8 @@ use crate::error::{Error, Result};
 use crate::proto::types::*;
 use crate::proto::metadata::MetadataExtractor;
 use prost_types::{
-    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorProto,
-    MethodDescriptorProto, ServiceDescriptorProto,
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto,
 };
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -35,30 +35,36 @@ impl ProtoParser {
             return Err(Error::ProtoFileNotFound(path.to_string_lossy().to_string()));
         }
 
-        let proto_files = if path.is_file() {
-            vec![path.to_path_buf()]
+        let mut schema = ProtoSchema::new();
+        let mut files_parsed = 0;
+
+        // Files are parsed and merged one at a time so that only the
+        // aggregated schema stays resident, not every file's source
+        if path.is_file() {
+            let file_schema = self.parse_file(path).await?;
+            self.merge_schema(&mut schema, file_schema)?;
+            files_parsed += 1;
         } else {
-            self.find_proto_files(path)?
-        };
+            for proto_file in self.proto_files(path) {
+                let file_schema = self.parse_file(&proto_file).await?;
+                self.merge_schema(&mut schema, file_schema)?;
+                files_parsed += 1;
+            }
+        }
 
-        if proto_files.is_empty() {
+        if files_parsed == 0 {
             return Err(Error::ProtoParsingError {
                 file_path: std::path::PathBuf::from(path),
                 message: "No proto files found".to_string(),
             });
         }
 
-        tracing::info!("Found {} proto files to parse", proto_files.len());
-
-        let mut schema = ProtoSchema::new();
-        
-        for proto_file in proto_files {
-            let file_schema = self.parse_file(&proto_file).await?;
-            self.merge_schema(&mut schema, file_schema)?;
-        }
+        schema.dependencies.sort();
+        schema.dependencies.dedup();
 
         tracing::info!(
-            "Parsed schema: {} services, {} messages, {} enums",
+            "Parsed {} proto files: {} services, {} messages, {} enums",
+            files_parsed,
             schema.services.len(),
             schema.messages.len(),
             schema.enums.len()
@@ -67,23 +73,18 @@ impl ProtoParser {
         Ok(schema)
     }
 
-    /// Find all .proto files in a directory
-    fn find_proto_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
-        let mut proto_files = Vec::new();
-
-        for entry in WalkDir::new(dir)
+    /// Walk a directory for .proto files in a stable, name-sorted order
+    fn proto_files(&self, dir: &Path) -> impl Iterator<Item = PathBuf> {
+        WalkDir::new(dir)
             .follow_links(true)
+            .sort_by_file_name()
             .into_iter()
             .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.is_file() && path.extension().map_or(false, |ext| ext == "proto") {
-                proto_files.push(path.to_path_buf());
-            }
-        }
-
-        proto_files.sort();
-        Ok(proto_files)
+            .filter(|entry| {
+                entry.file_type().is_file()
+                    && entry.path().extension().is_some_and(|ext| ext == "proto")
+            })
+            .map(|entry| entry.into_path())
     }
 
     /// Parse a single proto file
@@ -100,12 +101,15 @@ impl ProtoParser {
         // Parse using protoc/prost
         let file_descriptor = self.compile_proto_file(file_path, &content).await?;
 
+        // The descriptor owns everything needed from here on
+        drop(content);
+
         // Convert to our internal representation
         self.convert_file_descriptor(file_descriptor, file_path)
     }
 
     /// Compile proto file using protoc
-    async fn compile_proto_file(&self, file_path: &Path, _content: &str) -> Result<FileDescriptorProto> {
+    async fn compile_proto_file(&self, file_path: &Path, content: &str) -> Result<FileDescriptorProto> {
         // Use tonic-build to compile the proto file
         let mut config = tonic_build::configure();
         
@@ -117,25 +121,18 @@ impl ProtoParser {
         // This is a simplified version - in reality we'd need to properly configure
         // protoc compilation and handle the file descriptor set
         // For now, we'll create a basic descriptor for development
-        self.create_mock_descriptor(file_path)
+        self.create_mock_descriptor(file_path, content)
     }
 
     /// Create a mock descriptor for development
-    fn create_mock_descriptor(&self, file_path: &Path) -> Result<FileDescriptorProto> {
-        // Read the proto file content to extract package name and other info
-        let content = std::fs::read_to_string(file_path)
-            .map_err(|e| Error::ProtoParsingError {
-                file_path: file_path.to_path_buf(),
-                message: format!("Failed to read {}: {}", file_path.display(), e),
-            })?;
-
+    fn create_mock_descriptor(&self, file_path: &Path, content: &str) -> Result<FileDescriptorProto> {
         // Extract package name from content
-        let package = self.extract_package_from_content(&content);
+        let package = self.extract_package_from_content(content);
         
         // Extract services, messages, and enums from content
-        let services = self.extract_services_from_content(&content);
-        let messages = self.extract_messages_from_content(&content);
-        let enums = self.extract_enums_from_content(&content);
+        let services = self.extract_services_from_content(content);
+        let messages = self.extract_messages_from_content(content);
+        let enums = self.extract_enums_from_content(content);
 
         Ok(FileDescriptorProto {
             name: Some(file_path.to_string_lossy().to_string()),
@@ -339,6 +336,7 @@ impl ProtoParser {
             field_type,
             number: descriptor.number.unwrap_or(0),
             label,
+            proto3_optional: descriptor.proto3_optional.unwrap_or(false),
             default_value: descriptor.default_value,
             options: field_options,
             documentation: None,
@@ -393,10 +391,6 @@ impl ProtoParser {
         main_schema.enums.extend(file_schema.enums);
         main_schema.dependencies.extend(file_schema.dependencies);
 
-        // Remove duplicates
-        main_schema.dependencies.sort();
-        main_schema.dependencies.dedup();
-
         Ok(())
     }
 
@@ -583,11 +577,11 @@ impl ProtoParser {
             return None;
         }
         
-        // Handle repeated fields
-        let (label, type_start_idx) = if left_parts[0] == "repeated" {
-            (3, 1) // LABEL_REPEATED = 3
-        } else {
-            (1, 0) // LABEL_OPTIONAL = 1
+        // Handle repeated and proto3 `optional` fields
+        let (label, type_start_idx, proto3_optional) = match left_parts[0] {
+            "repeated" => (3, 1, None), // LABEL_REPEATED = 3
+            "optional" => (1, 1, Some(true)),
+            _ => (1, 0, None), // LABEL_OPTIONAL = 1
         };
         
         if left_parts.len() <= type_start_idx + 1 {
@@ -612,7 +606,7 @@ impl ProtoParser {
             oneof_index: None,
             json_name: None,
             options: None,
-            proto3_optional: None,
+            proto3_optional,
         })
     }
 
@@ -635,9 +629,70 @@ impl ProtoParser {
         matches!(field_type, "string" | "int32" | "int64" | "bool" | "float" | "double" | "bytes")
     }
 
-    /// Extract enums from proto content
-    fn extract_enums_from_content(&self, _content: &str) -> Vec<EnumDescriptorProto> {
-        // For MVP, we'll implement basic enum parsing later if needed
-        vec![]
+    /// Extract top-level enums from proto content
+    fn extract_enums_from_content(&self, content: &str) -> Vec<EnumDescriptorProto> {
+        let mut enums = Vec::new();
+        let mut current_enum: Option<EnumDescriptorProto> = None;
+        let mut brace_count = 0;
+
+        for line in content.lines() {
+            let line = line.split("//").next().unwrap_or("").trim();
+
+            if brace_count == 0 && line.starts_with("enum ") {
+                let enum_name = line
+                    .strip_prefix("enum ")
+                    .unwrap_or("")
+                    .split(|c: char| c.is_whitespace() || c == '{')
+                    .next()
+                    .unwrap_or("")
+                    .to_string();
+                current_enum = Some(EnumDescriptorProto {
+                    name: Some(enum_name),
+                    ..Default::default()
+                });
+            } else if brace_count == 1 {
+                if let (Some(enum_desc), Some(value)) = (current_enum.as_mut(), self.parse_enum_value(line)) {
+                    enum_desc.value.push(value);
+                }
+            }
+
+            brace_count += line.matches('{').count();
+            if line.contains('}') {
+                brace_count = brace_count.saturating_sub(line.matches('}').count());
+
+                // End of enum definition
+                if brace_count == 0 {
+                    if let Some(enum_desc) = current_enum.take() {
+                        enums.push(enum_desc);
+                    }
+                }
+            }
+        }
+
+        enums
+    }
+
+    /// Parse an enum value from a line like "TASK_PRIORITY_HIGH = 2;"
+    fn parse_enum_value(&self, line: &str) -> Option<EnumValueDescriptorProto> {
+        let (name, number) = line.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            // `option allow_alias = true;` and similar statements
+            return None;
+        }
+
+        let number = number
+            .trim()
+            .trim_end_matches(';')
+            .split(|c: char| c.is_whitespace() || c == '[')
+            .next()?
+            .parse()
+            .ok()?;
+
+        Some(EnumValueDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            options: None,
+        })
     }
 }
\ No newline at end of file
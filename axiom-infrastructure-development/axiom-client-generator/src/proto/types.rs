@@ -102,7 +102,13 @@ pub struct Field {
     /// Field number
     pub number: i32,
     /// Field label (optional, required, repeated)
+    ///
+    /// Proto3 singular fields are `Optional` here whether or not they were
+    /// declared with the `optional` keyword; see `proto3_optional`.
     pub label: FieldLabel,
+    /// Declared with the proto3 `optional` keyword (explicit presence)
+    #[serde(default)]
+    pub proto3_optional: bool,
     /// Default value if any
     pub default_value: Option<String>,
     /// Field-level options
@@ -218,6 +224,9 @@ pub struct AxiomServiceOptions {
     pub collections: Vec<AxiomCollection>,
     /// Whether this service supports pagination
     pub supports_pagination: Option<bool>,
+    /// Proto enum describing this service's domain errors
+    /// (defaults to `<Service>Error` when such an enum exists)
+    pub error_type: Option<String>,
 }
 
 /// Axiom-specific method options
@@ -243,6 +252,8 @@ pub struct AxiomMethodOptions {
     pub supports_offline: Option<bool>,
     /// Cache strategy for this method
     pub cache_strategy: CacheStrategy,
+    /// Milliseconds before an in-flight call is abandoned with a timeout error
+    pub loading_timeout_ms: Option<u64>,
 }
 
 /// Axiom-specific message options
@@ -327,6 +338,21 @@ impl Default for StateUpdateStrategy {
     }
 }
 
+impl StateUpdateStrategy {
+    /// Enum value name as declared in `axiom_options.proto`
+    pub fn proto_name(&self) -> &'static str {
+        match self {
+            StateUpdateStrategy::Unspecified => "STATE_UPDATE_STRATEGY_UNSPECIFIED",
+            StateUpdateStrategy::Append => "STATE_UPDATE_STRATEGY_APPEND",
+            StateUpdateStrategy::ReplaceAll => "STATE_UPDATE_STRATEGY_REPLACE_ALL",
+            StateUpdateStrategy::UpdateById => "STATE_UPDATE_STRATEGY_UPDATE_BY_ID",
+            StateUpdateStrategy::RemoveById => "STATE_UPDATE_STRATEGY_REMOVE_BY_ID",
+            StateUpdateStrategy::Custom => "STATE_UPDATE_STRATEGY_CUSTOM",
+            StateUpdateStrategy::NoChange => "STATE_UPDATE_STRATEGY_NO_CHANGE",
+        }
+    }
+}
+
 /// Cache strategies for methods
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum CacheStrategy {
@@ -385,6 +411,92 @@ impl ProtoSchema {
     pub fn services_in_package(&self, package: &str) -> Vec<&Service> {
         self.services.iter().filter(|s| s.package == package).collect()
     }
+
+    /// Approximate heap footprint of the schema in bytes
+    ///
+    /// Counts the structs and their names, paths and type strings; option
+    /// payloads are not walked, so this is a lower bound for diagnostics.
+    pub fn estimated_size_bytes(&self) -> usize {
+        let files: usize = self.files.iter().map(|file| {
+            std::mem::size_of::<ProtoFile>()
+                + file.path.len()
+                + file.package.len()
+                + file.syntax.len()
+                + strings_size(&file.imports)
+                + strings_size(&file.services)
+                + strings_size(&file.messages)
+                + strings_size(&file.enums)
+        }).sum();
+
+        let services: usize = self.services.iter().map(|service| {
+            std::mem::size_of::<Service>()
+                + service.name.len()
+                + service.package.len()
+                + service.file_path.len()
+                + option_size(&service.documentation)
+                + service.methods.iter().map(|method| {
+                    std::mem::size_of::<Method>()
+                        + method.name.len()
+                        + method.input_type.len()
+                        + method.output_type.len()
+                        + option_size(&method.documentation)
+                }).sum::<usize>()
+        }).sum();
+
+        files
+            + services
+            + self.messages.iter().map(message_size).sum::<usize>()
+            + self.enums.iter().map(enum_size).sum::<usize>()
+            + strings_size(&self.dependencies)
+    }
+
+    /// Diagnostic for when the estimated schema size exceeds `limit_mb`
+    pub fn size_warning(&self, limit_mb: u64) -> Option<String> {
+        let size = self.estimated_size_bytes() as u64;
+        (size > limit_mb.saturating_mul(1024 * 1024)).then(|| format!(
+            "Parsed schema is ~{:.1} MB ({} files, {} messages), above the {} MB warning threshold",
+            size as f64 / (1024.0 * 1024.0),
+            self.files.len(),
+            self.messages.len(),
+            limit_mb
+        ))
+    }
+}
+
+fn strings_size(values: &[String]) -> usize {
+    values.iter().map(|v| std::mem::size_of::<String>() + v.len()).sum()
+}
+
+fn option_size(value: &Option<String>) -> usize {
+    value.as_ref().map_or(0, String::len)
+}
+
+fn message_size(message: &Message) -> usize {
+    std::mem::size_of::<Message>()
+        + message.name.len()
+        + message.package.len()
+        + message.file_path.len()
+        + option_size(&message.documentation)
+        + message.fields.iter().map(|field| {
+            std::mem::size_of::<Field>()
+                + field.name.len()
+                + field.field_type.len()
+                + option_size(&field.default_value)
+                + option_size(&field.documentation)
+        }).sum::<usize>()
+        + message.nested_messages.iter().map(message_size).sum::<usize>()
+        + message.nested_enums.iter().map(enum_size).sum::<usize>()
+}
+
+fn enum_size(enum_type: &Enum) -> usize {
+    std::mem::size_of::<Enum>()
+        + enum_type.name.len()
+        + enum_type.package.len()
+        + enum_type.file_path.len()
+        + option_size(&enum_type.documentation)
+        + enum_type.values.iter().map(|value| {
+            std::mem::size_of::<EnumValue>() + value.name.len() + option_size(&value.documentation)
+        }).sum::<usize>()
 }
 
 /// Type aliases for backward compatibility with tests
@@ -80,7 +80,10 @@ pub struct TestResults {
 
 impl TestResults {
     pub fn new() -> Self {
-        Self::default()
+        Self {
+            results_by_language: HashMap::new(),
+            overall_success: true,
+        }
     }
 
     pub fn add_language_result(&mut self, language: String, result: LanguageTestResult) {
@@ -94,6 +97,23 @@ impl TestResults {
         self.results_by_language.insert(language, result);
     }
 
+    /// Promote validation warnings to errors (strict mode) and recompute
+    /// `overall_success`. Returns the promoted warnings.
+    pub fn promote_warnings(&mut self, categories: Option<&[String]>) -> Vec<String> {
+        let mut promoted = Vec::new();
+
+        for result in self.results_by_language.values_mut() {
+            promoted.extend(result.validation_result.promote_warnings(categories));
+        }
+
+        self.overall_success = self.results_by_language.values().all(|r| {
+            r.validation_result.is_valid()
+                && r.compilation_result.as_ref().is_none_or(|c| c.is_successful())
+        });
+
+        promoted
+    }
+
     pub fn total_files_tested(&self) -> usize {
         self.results_by_language.values()
             .map(|r| r.validation_result.files_validated)
@@ -1,7 +1,9 @@
 use crate::error::{Error, Result};
 use crate::validation::swift::SwiftValidator;
+use futures::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs;
@@ -9,9 +11,88 @@ use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+/// Default number of files written concurrently by [`FileManager::write_files`]
+pub const DEFAULT_WRITE_CONCURRENCY: usize = 16;
+
 /// Multi-language file management utilities
 pub struct FileManager;
 
+tokio::task_local! {
+    static TEMP_FILES: TempFileTracker;
+}
+
+/// Temporary files of atomic writes that have not been renamed into place.
+///
+/// Writes made inside [`TempFileTracker::scope`] record their temp path until
+/// the rename completes, so an interrupted caller can remove exactly the files
+/// it left behind instead of sweeping a whole directory.
+#[derive(Debug, Clone, Default)]
+pub struct TempFileTracker {
+    paths: Arc<std::sync::Mutex<HashSet<PathBuf>>>,
+}
+
+impl TempFileTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `future` with every [`FileManager::write_file`] it makes recorded here
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        TEMP_FILES.scope(self.clone(), future).await
+    }
+
+    /// Temp paths of writes that started but never completed
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.paths.lock().unwrap().iter().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// Delete the recorded temp files, returning how many were removed
+    pub async fn remove_all(&self) -> Result<usize> {
+        let paths: Vec<PathBuf> = self.paths.lock().unwrap().drain().collect();
+
+        let mut removed = 0;
+        for path in paths {
+            match fs::remove_file(&path).await {
+                Ok(()) => {
+                    tracing::debug!("Removed interrupted temporary file: {}", path.display());
+                    removed += 1;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(removed)
+    }
+
+    fn record(path: &Path) {
+        let _ = TEMP_FILES.try_with(|tracker| tracker.paths.lock().unwrap().insert(path.to_path_buf()));
+    }
+
+    fn forget(path: &Path) {
+        let _ = TEMP_FILES.try_with(|tracker| tracker.paths.lock().unwrap().remove(path));
+    }
+}
+
+/// Rendered file content waiting to be written
+#[derive(Debug, Clone)]
+pub struct PendingWrite {
+    /// Destination path
+    pub path: PathBuf,
+    /// Fully rendered content
+    pub content: String,
+}
+
+impl PendingWrite {
+    /// Create a pending write for `path`
+    pub fn new(path: PathBuf, content: String) -> Self {
+        Self { path, content }
+    }
+}
+
 impl FileManager {
     /// Write content to a file with atomic operations
     pub async fn write_file(
@@ -33,10 +114,8 @@ impl FileManager {
         }
 
         // Write to temporary file first for atomic operation
-        let temp_path = file_path.with_extension(format!(
-            "{}.tmp",
-            file_path.extension().and_then(|s| s.to_str()).unwrap_or("tmp")
-        ));
+        let temp_path = Self::temp_path_for(file_path);
+        TempFileTracker::record(&temp_path);
 
         // Write content to temporary file
         let mut temp_file = fs::File::create(&temp_path).await?;
@@ -46,11 +125,97 @@ impl FileManager {
 
         // Atomically rename temporary file to final destination
         fs::rename(&temp_path, file_path).await?;
+        TempFileTracker::forget(&temp_path);
 
         tracing::debug!("Successfully wrote file: {}", file_path.display());
         Ok(())
     }
 
+    /// Write a batch of rendered files with bounded concurrency.
+    ///
+    /// The returned paths follow the order of `writes`, not completion order.
+    /// If any write fails the remaining writes are cancelled and every file in
+    /// the batch is restored to its previous state (or removed if it is new).
+    pub async fn write_files(
+        writes: Vec<PendingWrite>,
+        force_overwrite: bool,
+        concurrency: usize,
+    ) -> Result<Vec<String>> {
+        // Snapshot existing files so a failed batch can be undone
+        let mut originals = Vec::with_capacity(writes.len());
+        for write in &writes {
+            match fs::read(&write.path).await {
+                Ok(bytes) => originals.push(Some(bytes)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => originals.push(None),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        // Collected up front: a lazy `map` closure in the stream type makes the
+        // future fail the `Send` bound of async-trait callers.
+        // Dropping the stream on the first error cancels in-flight writes
+        let pending: Vec<_> = writes
+            .iter()
+            .map(|write| Self::write_file(&write.path, &write.content, force_overwrite))
+            .collect();
+        let result = futures::stream::iter(pending)
+            .buffer_unordered(concurrency.max(1))
+            .try_collect::<Vec<()>>()
+            .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Batch write failed, rolling back {} files: {}", writes.len(), e);
+            Self::rollback_writes(&writes, originals).await;
+            return Err(e);
+        }
+
+        Ok(writes.iter().map(|write| write.path.to_string_lossy().to_string()).collect())
+    }
+
+    /// Restore files touched by a failed batch to their previous contents
+    async fn rollback_writes(writes: &[PendingWrite], originals: Vec<Option<Vec<u8>>>) {
+        for (write, original) in writes.iter().zip(originals) {
+            let _ = fs::remove_file(Self::temp_path_for(&write.path)).await;
+
+            let restored = match original {
+                Some(bytes) => fs::write(&write.path, bytes).await,
+                None if write.path.exists() => fs::remove_file(&write.path).await,
+                None => Ok(()),
+            };
+
+            if let Err(e) = restored {
+                tracing::error!("Failed to roll back {}: {}", write.path.display(), e);
+            }
+        }
+    }
+
+    /// Temporary path used while atomically writing `file_path`
+    fn temp_path_for(file_path: &Path) -> PathBuf {
+        file_path.with_extension(format!(
+            "{}.tmp",
+            file_path.extension().and_then(|s| s.to_str()).unwrap_or("tmp")
+        ))
+    }
+
+    /// Remove temporary files left behind by interrupted atomic writes
+    pub async fn cleanup_temp_files(dir: &Path) -> Result<usize> {
+        if !dir.exists() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+        for entry in walkdir::WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if entry.file_type().is_file() && path.extension().and_then(|s| s.to_str()) == Some("tmp") {
+                fs::remove_file(path).await?;
+                tracing::debug!("Removed stale temporary file: {}", path.display());
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
     /// Read file content
     pub async fn read_file(file_path: &Path) -> Result<String> {
         let content = fs::read_to_string(file_path).await?;
@@ -0,0 +1,70 @@
+use crate::error::{Error, Result};
+use crate::proto::analyzer::SchemaFingerprint;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest written at the root of the output directory
+pub const MANIFEST_FILE_NAME: &str = ".axiom-manifest.json";
+
+/// Record of a completed generation, used to compare against later runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationManifest {
+    /// Version of the generator that produced the output
+    pub generator_version: String,
+    /// RFC 3339 timestamp of the generation
+    pub generated_at: String,
+    /// Fingerprint of the schema the output was generated from
+    pub schema: SchemaFingerprint,
+    /// Generated files, relative to the output directory
+    pub files: Vec<String>,
+}
+
+impl GenerationManifest {
+    /// Create a manifest for files generated under `output_dir`
+    pub fn new(schema: SchemaFingerprint, output_dir: &Path, files: &[String]) -> Self {
+        let files = files
+            .iter()
+            .map(|file| {
+                Path::new(file)
+                    .strip_prefix(output_dir)
+                    .map(|relative| relative.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| file.clone())
+            })
+            .collect();
+
+        Self {
+            generator_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            schema,
+            files,
+        }
+    }
+
+    /// Manifest location for an output directory
+    pub fn path(output_dir: &Path) -> PathBuf {
+        output_dir.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Load the manifest from a previous generation, if there is one
+    pub async fn load(output_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(output_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = tokio::fs::read_to_string(&path).await?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            Error::ConfigError(format!("Invalid generation manifest {}: {}", path.display(), e))
+        })
+    }
+
+    /// Write the manifest into `output_dir`
+    pub async fn save(&self, output_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            Error::ConfigError(format!("Failed to serialize generation manifest: {}", e))
+        })?;
+        tokio::fs::create_dir_all(output_dir).await?;
+        tokio::fs::write(Self::path(output_dir), content).await?;
+        Ok(())
+    }
+}
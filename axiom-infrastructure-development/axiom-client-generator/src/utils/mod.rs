@@ -4,7 +4,9 @@
 
 pub mod config;
 pub mod file_manager;
+pub mod manifest;
 pub mod naming;
 pub mod validation;
 
-pub use file_manager::FileManager;
\ No newline at end of file
+pub use file_manager::FileManager;
+pub use manifest::GenerationManifest;
\ No newline at end of file
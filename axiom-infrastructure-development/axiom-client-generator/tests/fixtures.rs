@@ -39,6 +39,11 @@ impl TestFixtures {
         self.fixtures_dir.join("proto").join(filename)
     }
     
+    /// Get the path to a Swift validation fixture
+    pub fn validation_path(&self, filename: &str) -> PathBuf {
+        self.fixtures_dir.join("validation").join(filename)
+    }
+    
     /// Get the path to the proto fixtures directory
     pub fn proto_dir(&self) -> PathBuf {
         self.fixtures_dir.join("proto")
@@ -105,6 +110,11 @@ pub mod fixtures {
     pub const TASK_ACTION_SWIFT: &str = "TaskAction.swift";
     
     pub const TEST_CONFIG_JSON: &str = "test_config.json";
+    
+    /// Validates without errors or warnings
+    pub const WARNING_CLEAN_SWIFT: &str = "TaskSummary.swift";
+    /// Validates without errors but has an Access Control warning
+    pub const ACCESS_WARNING_SWIFT: &str = "TaskBadge.swift";
 }
 
 /// Fixture data for proto files
@@ -0,0 +1,168 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: BasicAction.swift
+
+// Generated action enum for BasicService
+import Foundation
+
+// MARK: - BasicAction
+
+/// Actions that can be performed on BasicService
+public enum BasicAction: Sendable, Equatable {
+    
+    
+    case getMessage(GetMessageRequest)
+    
+    
+    case listMessages(ListMessagesRequest)
+    
+}
+
+// MARK: - Action Validation
+
+extension BasicAction {
+    /// Validates the action and its associated request
+    public var isValid: Bool {
+        switch self {
+        
+        case .getMessage(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .listMessages(let request):
+            
+            return true // No specific validation rules
+            
+        
+        }
+    }
+    
+    /// Returns validation errors for the action
+    public var validationErrors: [String] {
+        switch self {
+        
+        case .getMessage(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .listMessages(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        }
+    }
+}
+
+// MARK: - Action Metadata
+
+extension BasicAction {
+    /// Whether this action requires network access
+    public var requiresNetworkAccess: Bool {
+        switch self {
+        
+        case .getMessage:
+            return true
+        
+        case .listMessages:
+            return true
+        
+        }
+    }
+    
+    /// Whether this action modifies state
+    public var modifiesState: Bool {
+        switch self {
+        
+        case .getMessage:
+            return true
+        
+        case .listMessages:
+            return true
+        
+        }
+    }
+    
+    /// The name of this action for logging and analytics
+    public var actionName: String {
+        switch self {
+        
+        case .getMessage:
+            return "getMessage"
+        
+        case .listMessages:
+            return "listMessages"
+        
+        }
+    }
+    
+    /// Whether this action should show loading state
+    public var showsLoadingState: Bool {
+        switch self {
+        
+        case .getMessage:
+            return true
+        
+        case .listMessages:
+            return true
+        
+        }
+    }
+    
+    /// The state update strategy for this action
+    public var stateUpdateStrategy: StateUpdateStrategy {
+        switch self {
+        
+        case .getMessage:
+            return .replace_all
+        
+        case .listMessages:
+            return .replace_all
+        
+        }
+    }
+}
+
+// MARK: - State Update Strategy
+
+public enum StateUpdateStrategy: String, Sendable {
+    case append = "append"
+    case replaceAll = "replace_all"
+    case updateById = "update_by_id"
+    case removeById = "remove_by_id"
+    case custom = "custom"
+}
+
+// MARK: - Action Request Access
+
+extension BasicAction {
+    /// The request associated with this action
+    public var request: Any {
+        switch self {
+        
+        case .getMessage(let request):
+            return request
+        
+        case .listMessages(let request):
+            return request
+        
+        }
+    }
+}
+
+// MARK: - CustomStringConvertible
+
+extension BasicAction: CustomStringConvertible {
+    public var description: String {
+        switch self {
+        
+        case .getMessage(let request):
+            return "BasicAction.getMessage(\(request))"
+        
+        case .listMessages(let request):
+            return "BasicAction.listMessages(\(request))"
+        
+        }
+    }
+}
\ No newline at end of file
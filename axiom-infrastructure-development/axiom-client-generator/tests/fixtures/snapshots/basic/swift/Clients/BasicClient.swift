@@ -0,0 +1,182 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: BasicClient.swift
+
+// Generated Swift client for BasicService
+import Foundation
+import AxiomCore
+import AxiomArchitecture
+
+
+
+@globalActor
+public actor BasicClient: AxiomObservableClient<BasicState, BasicAction> {
+    public typealias StateType = BasicState
+    public typealias ActionType = BasicAction
+    
+    // MARK: - Private Properties
+    private var _state: BasicState
+    private let apiClient: BasicServiceProtocol
+    private var streamContinuations: [UUID: AsyncStream<BasicState>.Continuation] = [:]
+    
+    // MARK: - Initialization
+    
+    /// Initialize the client with an API client implementation
+    /// - Parameters:
+    ///   - apiClient: The API client that implements BasicServiceProtocol
+    ///   - initialState: The initial state (defaults to empty state)
+    public init(apiClient: BasicServiceProtocol, initialState: BasicState = BasicState()) {
+        self._state = initialState
+        self.apiClient = apiClient
+    }
+    
+    // MARK: - AxiomClient Protocol Implementation
+    
+    public var stateStream: AsyncStream<BasicState> {
+        AsyncStream { [weak self] continuation in
+            let id = UUID()
+            Task { [weak self] in
+                await self?.addContinuation(continuation, id: id)
+                
+                // Emit current state immediately
+                if let currentState = await self?._state {
+                    continuation.yield(currentState)
+                }
+                
+                // Handle stream termination
+                continuation.onTermination = { [weak self, id] _ in
+                    Task { await self?.removeContinuation(id: id) }
+                }
+            }
+        }
+    }
+    
+    private func addContinuation(_ continuation: AsyncStream<BasicState>.Continuation, id: UUID) {
+        streamContinuations[id] = continuation
+    }
+    
+    private func removeContinuation(id: UUID) {
+        streamContinuations.removeValue(forKey: id)
+    }
+    
+    public func getCurrentState() async -> BasicState {
+        return _state
+    }
+    
+    public func rollbackToState(_ state: BasicState) async {
+        let oldState = _state
+        _state = state
+        
+        // Notify observers
+        for (_, continuation) in streamContinuations {
+            continuation.yield(state)
+        }
+        
+        await stateDidUpdate(from: oldState, to: state)
+    }
+    
+    /// Process an action and update the state accordingly
+    /// - Parameter action: The action to process
+    /// - Throws: Any error that occurs during processing
+    public func process(_ action: BasicAction) async throws {
+        // Validate action
+        guard action.isValid else {
+            let errors = action.validationErrors
+            throw AxiomError.clientError(.invalidAction("Action validation failed: \(errors.joined(separator: ", "))"))
+        }
+        
+        // Get old state for lifecycle hooks
+        let oldState = _state
+        
+        // Process the action
+        let newState = try await processAction(action, currentState: _state)
+        
+        // Only update if state actually changed
+        guard newState != oldState else { return }
+        
+        // Call lifecycle hooks
+        await stateWillUpdate(from: oldState, to: newState)
+        
+        // Update state
+        _state = newState
+        
+        // Notify observers
+        for (_, continuation) in streamContinuations {
+            continuation.yield(newState)
+        }
+        
+        // Call lifecycle hooks
+        await stateDidUpdate(from: oldState, to: newState)
+    }
+    
+    // MARK: - Action Processing
+    
+    private func processAction(_ action: BasicAction, currentState: BasicState) async throws -> BasicState {
+        switch action {
+        
+        case .getMessage(let request):
+            
+            
+            let result = try await apiClient.getMessage(request)
+            
+            
+            
+            
+            return currentState.withMessage(result.message)
+            
+            
+        
+        
+        case .listMessages(let request):
+            
+            
+            let result = try await apiClient.listMessages(request)
+            
+            
+            
+            
+            return currentState.withMessages(result.messages)
+            
+            
+        
+        
+        }
+    }
+    
+    // MARK: - Lifecycle Hooks
+    
+    public func stateWillUpdate(from old: BasicState, to new: BasicState) async {
+        // Override in subclasses if needed
+    }
+    
+    public func stateDidUpdate(from old: BasicState, to new: BasicState) async {
+        // Override in subclasses if needed
+    }
+    
+    // MARK: - Public Query Methods
+    
+    /// Get current statistics
+    public func getStatistics() async -> BasicState.Statistics {
+        return _state.statistics
+    }
+    
+    /// Get performance metrics
+    public func getPerformanceMetrics() async -> BasicClientMetrics {
+        BasicClientMetrics(
+            lastActionTime: Date(),
+            stateStreamSubscribers: streamContinuations.count
+        )
+    }
+}
+
+// MARK: - Performance Metrics
+
+/// Performance metrics for the BasicClient
+public struct BasicClientMetrics: Sendable, Equatable {
+    public let lastActionTime: Date
+    public let stateStreamSubscribers: Int
+    
+    public init(lastActionTime: Date, stateStreamSubscribers: Int) {
+        self.lastActionTime = lastActionTime
+        self.stateStreamSubscribers = stateStreamSubscribers
+    }
+}
\ No newline at end of file
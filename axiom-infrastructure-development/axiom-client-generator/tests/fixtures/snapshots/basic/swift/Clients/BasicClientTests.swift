@@ -0,0 +1,110 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: BasicClientTests.swift
+
+// Generated tests for BasicClient
+import XCTest
+@testable import YourPackage
+
+final class BasicClientTests: XCTestCase {
+    var client: BasicClient!
+    var mockApiClient: MockBasicServiceClient!
+    
+    override func setUp() {
+        super.setUp()
+        mockApiClient = MockBasicServiceClient()
+        client = BasicClient(apiClient: mockApiClient)
+    }
+    
+    override func tearDown() {
+        client = nil
+        mockApiClient = nil
+        super.tearDown()
+    }
+    
+    
+    func testGetMessage() async throws {
+        // Given
+        let request = GetMessageRequest(/* initialize with test data */)
+        
+        let expectedResult = Message(/* initialize with test data */)
+        mockApiClient.getMessageResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.getMessage(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertEqual(state.message, [expectedResult])
+        
+    }
+    
+    
+    func testListMessages() async throws {
+        // Given
+        let request = ListMessagesRequest(/* initialize with test data */)
+        
+        let expectedResult = ListMessagesResponse(/* initialize with test data */)
+        mockApiClient.listMessagesResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.listMessages(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertEqual(state.messages, [expectedResult])
+        
+    }
+    
+    
+}
+
+// Mock API client for testing
+class MockBasicServiceClient {
+    
+    
+    var getMessageResult: Message!
+    
+    var getMessageError: Error?
+    
+    
+    
+    var listMessagesResult: ListMessagesResponse!
+    
+    var listMessagesError: Error?
+    
+    
+    
+    
+    func getMessage(_ request: GetMessageRequest) async throws -> Message {
+        if let error = getMessageError {
+            throw error
+        }
+        
+        return getMessageResult
+        
+    }
+    
+    
+    func listMessages(_ request: ListMessagesRequest) async throws -> ListMessagesResponse {
+        if let error = listMessagesError {
+            throw error
+        }
+        
+        return listMessagesResult
+        
+    }
+    
+    
+}
\ No newline at end of file
@@ -0,0 +1,479 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: BasicState.swift
+
+// Generated state for BasicService
+import Foundation
+import AxiomCore
+
+// MARK: - BasicState
+
+/// State container for BasicService operations
+public struct BasicState: AxiomState {
+    
+    // MARK: - Collections
+    
+    /// Collection of Message entities
+    public let message: [Message]
+    
+    /// Collection of ListMessages entities
+    public let messages: [ListMessages]
+    
+    
+    // MARK: - Loading State
+    /// Indicates if an operation is currently in progress
+    public let isLoading: Bool
+    
+    /// The last error that occurred, if any
+    public let error: Error?
+    
+    /// Last time the state was updated
+    public let lastUpdated: Date
+    
+    // MARK: - Pagination
+    
+    
+    // MARK: - Additional Properties
+    
+    
+    // MARK: - Initialization
+    
+    public init(
+        
+        message: [Message] = [],
+        
+        messages: [ListMessages] = [],
+        
+        isLoading: Bool = false,
+        error: Error? = nil,
+        lastUpdated: Date = Date()
+    ) {
+        
+        self.message = message
+        
+        self.messages = messages
+        
+        self.isLoading = isLoading
+        self.error = error
+        self.lastUpdated = lastUpdated
+        
+        
+    }
+    
+    // MARK: - Immutable Update Methods
+    
+    
+    /// Add a message to the collection
+    public func addingMessage(_ item: Message) -> BasicState {
+        BasicState(
+            
+            message: message + [item],
+            
+            messages: messages,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire message collection
+    public func withMessages(_ newMessages: [Message]) -> BasicState {
+        BasicState(
+            
+            message: newMessages,
+            
+            messages: messages,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a message in the collection
+    public func updatingMessage(_ updatedMessage: Message) -> BasicState {
+        let updatedMessages = message.map { item in
+            item.id == updatedMessage.id ? updatedMessage : item
+        }
+        return BasicState(
+            
+            message: updatedMessages,
+            
+            messages: messages,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a message from the collection by ID
+    public func removingMessage(withId id: String) -> BasicState {
+        BasicState(
+            
+            message: message.filter { $0.id != id },
+            
+            messages: messages,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    /// Add a listmessages to the collection
+    public func addingListMessages(_ item: ListMessages) -> BasicState {
+        BasicState(
+            
+            message: message,
+            
+            messages: messages + [item],
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire messages collection
+    public func withListMessagess(_ newListMessagess: [ListMessages]) -> BasicState {
+        BasicState(
+            
+            message: message,
+            
+            messages: newListMessagess,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a listmessages in the collection
+    public func updatingListMessages(_ updatedListMessages: ListMessages) -> BasicState {
+        let updatedListMessagess = messages.map { item in
+            item.id == updatedListMessages.id ? updatedListMessages : item
+        }
+        return BasicState(
+            
+            message: message,
+            
+            messages: updatedListMessagess,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a listmessages from the collection by ID
+    public func removingListMessages(withId id: String) -> BasicState {
+        BasicState(
+            
+            message: message,
+            
+            messages: messages.filter { $0.id != id },
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    
+    /// Update the loading state
+    public func withLoading(_ loading: Bool) -> BasicState {
+        BasicState(
+            
+            message: message,
+            
+            messages: messages,
+            
+            isLoading: loading,
+            error: error,
+            lastUpdated: lastUpdated
+        )
+    }
+    
+    /// Update the error state
+    public func withError(_ error: Error?) -> BasicState {
+        BasicState(
+            
+            message: message,
+            
+            messages: messages,
+            
+            isLoading: false,
+            error: error,
+            lastUpdated: Date()
+        )
+    }
+    
+    // MARK: - Computed Properties
+    
+    
+    /// Number of message in the collection
+    public var messageCount: Int {
+        message.count
+    }
+    
+    /// Returns true if message collection is empty
+    public var isMessageEmpty: Bool {
+        message.isEmpty
+    }
+    
+    /// Find a Message by ID
+    public func message(withId id: String) -> Message? {
+        message.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Number of messages in the collection
+    public var messagesCount: Int {
+        messages.count
+    }
+    
+    /// Returns true if messages collection is empty
+    public var isListMessagesEmpty: Bool {
+        messages.isEmpty
+    }
+    
+    /// Find a ListMessages by ID
+    public func listmessages(withId id: String) -> ListMessages? {
+        messages.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Returns true if any operation is currently loading
+    public var hasActiveOperation: Bool {
+        isLoading
+    }
+    
+    /// Returns true if there was an error in the last operation
+    public var hasError: Bool {
+        error != nil
+    }
+    
+    /// Statistics for this state
+    public var statistics: Statistics {
+        Statistics(
+            
+            totalMessages: message.count,
+            
+            messageCacheUtilization: Double(message.count) / Double(1000),
+            
+            
+            totalListMessagess: messages.count,
+            
+            messagesCacheUtilization: Double(messages.count) / Double(1000),
+            
+            
+            lastUpdated: lastUpdated,
+            hasActiveOperations: isLoading,
+            hasErrors: hasError
+        )
+    }
+    
+    /// Performance metrics for large state objects
+    public var performanceMetrics: PerformanceMetrics {
+        let totalItems = message.count + messages.count
+        let memoryFootprint = totalItems * MemoryLayout<AnyObject>.stride
+        
+        return PerformanceMetrics(
+            totalItems: totalItems,
+            estimatedMemoryFootprint: memoryFootprint,
+            collectionsCount: 2,
+            isOptimizedForLargeDatasets: totalItems > 1000
+        )
+    }
+    
+    /// State validation based on Axiom field constraints
+    public var validationResults: StateValidationResults {
+        var issues: [ValidationIssue] = []
+        
+        
+        // Validate message collection
+        
+        if message.count > 1000 {
+            issues.append(.collectionSizeExceeded("message", current: message.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in message
+        for (index, item) in message.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("message[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        // Validate messages collection
+        
+        if messages.count > 1000 {
+            issues.append(.collectionSizeExceeded("messages", current: messages.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in messages
+        for (index, item) in messages.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("messages[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        
+        return StateValidationResults(
+            isValid: issues.isEmpty,
+            issues: issues,
+            validatedAt: Date()
+        )
+    }
+}
+
+// MARK: - BasicState + Equatable & Hashable
+
+extension BasicState {
+    public static func == (lhs: BasicState, rhs: BasicState) -> Bool {
+        return lhs.message == rhs.message &&
+               lhs.messages == rhs.messages &&
+               lhs.isLoading == rhs.isLoading &&
+               lhs.hasError == rhs.hasError
+    }
+    
+    public func hash(into hasher: inout Hasher) {
+        
+        hasher.combine(message)
+        
+        hasher.combine(messages)
+        
+        hasher.combine(isLoading)
+        hasher.combine(hasError)
+        
+        
+    }
+}
+
+// MARK: - Supporting Types
+
+extension BasicState {
+    /// Enhanced statistics for state monitoring
+    public struct Statistics: Sendable, Equatable {
+        
+        public let totalMessages: Int
+        
+        public let messageCacheUtilization: Double
+        
+        
+        public let totalListMessagess: Int
+        
+        public let messagesCacheUtilization: Double
+        
+        
+        public let lastUpdated: Date
+        public let hasActiveOperations: Bool
+        public let hasErrors: Bool
+        
+        
+        public init(
+            
+            totalMessages: Int,
+            
+            messageCacheUtilization: Double,
+            
+            
+            totalListMessagess: Int,
+            
+            messagesCacheUtilization: Double,
+            
+            
+            lastUpdated: Date,
+            hasActiveOperations: Bool,
+            hasErrors: Bool
+        ) {
+            
+            self.totalMessages = totalMessages
+            
+            self.messageCacheUtilization = messageCacheUtilization
+            
+            
+            self.totalListMessagess = totalListMessagess
+            
+            self.messagesCacheUtilization = messagesCacheUtilization
+            
+            
+            self.lastUpdated = lastUpdated
+            self.hasActiveOperations = hasActiveOperations
+            self.hasErrors = hasErrors
+            
+        }
+    }
+    
+    
+    
+    /// Performance metrics for optimization
+    public struct PerformanceMetrics: Sendable, Equatable {
+        public let totalItems: Int
+        public let estimatedMemoryFootprint: Int
+        public let collectionsCount: Int
+        public let isOptimizedForLargeDatasets: Bool
+        
+        public init(
+            totalItems: Int,
+            estimatedMemoryFootprint: Int,
+            collectionsCount: Int,
+            isOptimizedForLargeDatasets: Bool
+        ) {
+            self.totalItems = totalItems
+            self.estimatedMemoryFootprint = estimatedMemoryFootprint
+            self.collectionsCount = collectionsCount
+            self.isOptimizedForLargeDatasets = isOptimizedForLargeDatasets
+        }
+    }
+    
+    /// State validation results based on Axiom constraints
+    public struct StateValidationResults: Sendable, Equatable {
+        public let isValid: Bool
+        public let issues: [ValidationIssue]
+        public let validatedAt: Date
+        
+        public init(isValid: Bool, issues: [ValidationIssue], validatedAt: Date) {
+            self.isValid = isValid
+            self.issues = issues
+            self.validatedAt = validatedAt
+        }
+    }
+    
+    /// Individual validation issues
+    public enum ValidationIssue: Sendable, Equatable {
+        case collectionSizeExceeded(String, current: Int, max: Int)
+        case invalidFieldValue(String, reason: String)
+        case constraintViolation(String, constraint: String)
+        
+        public var description: String {
+            switch self {
+            case .collectionSizeExceeded(let collection, let current, let max):
+                return "Collection '\(collection)' size \(current) exceeds maximum \(max)"
+            case .invalidFieldValue(let field, let reason):
+                return "Field '\(field)': \(reason)"
+            case .constraintViolation(let field, let constraint):
+                return "Field '\(field)' violates constraint: \(constraint)"
+            }
+        }
+    }
+}
\ No newline at end of file
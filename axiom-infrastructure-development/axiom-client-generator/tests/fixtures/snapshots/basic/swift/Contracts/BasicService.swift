@@ -0,0 +1,155 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: BasicService.swift
+
+// Generated Swift service contracts
+import Foundation
+
+import AxiomCore
+
+
+
+
+// MARK: - GetMessageRequest
+
+public struct GetMessageRequest: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public init(id: String = "") {
+        
+        self.id = id
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+    }
+    
+    
+    
+}
+
+
+// MARK: - ListMessagesRequest
+
+public struct ListMessagesRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let limit: Int32
+    
+    
+    
+    
+    public init(limit: Int32 = 0) {
+        
+        self.limit = limit
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.limit = try container.decodeIfPresent(Int32.self, forKey: .limit) ?? 0
+        
+    }
+    
+    
+    
+}
+
+
+// MARK: - ListMessagesResponse
+
+public struct ListMessagesResponse: Codable, Equatable, Hashable {
+    
+    
+    
+    public let messages: [Message]
+    
+    
+    
+    
+    public init(messages: [Message] = []) {
+        
+        self.messages = messages
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.messages = try container.decodeIfPresent([Message].self, forKey: .messages) ?? []
+        
+    }
+    
+    
+    
+}
+
+
+// MARK: - Message
+
+public struct Message: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let content: String
+    
+    
+    
+    
+    public init(id: String = "", content: String = "") {
+        
+        self.id = id
+        
+        self.content = content
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.content = try container.decodeIfPresent(String.self, forKey: .content) ?? ""
+        
+    }
+    
+    
+    
+}
+
+
+
+
+
+
+// MARK: - BasicService Protocol
+
+public protocol BasicServiceProtocol {
+    
+    
+    func getMessage(_ request: GetMessageRequest) async throws -> Message
+    
+    
+    func listMessages(_ request: ListMessagesRequest) async throws -> ListMessagesResponse
+    
+}
@@ -0,0 +1,168 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskAction.swift
+
+// Generated action enum for TaskService
+import Foundation
+
+// MARK: - TaskAction
+
+/// Actions that can be performed on TaskService
+public enum TaskAction: Sendable, Equatable {
+    
+    
+    case getTask(GetTaskRequest)
+    
+    
+    case deleteTask(DeleteTaskRequest)
+    
+}
+
+// MARK: - Action Validation
+
+extension TaskAction {
+    /// Validates the action and its associated request
+    public var isValid: Bool {
+        switch self {
+        
+        case .getTask(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .deleteTask(let request):
+            
+            return true // No specific validation rules
+            
+        
+        }
+    }
+    
+    /// Returns validation errors for the action
+    public var validationErrors: [String] {
+        switch self {
+        
+        case .getTask(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .deleteTask(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        }
+    }
+}
+
+// MARK: - Action Metadata
+
+extension TaskAction {
+    /// Whether this action requires network access
+    public var requiresNetworkAccess: Bool {
+        switch self {
+        
+        case .getTask:
+            return true
+        
+        case .deleteTask:
+            return true
+        
+        }
+    }
+    
+    /// Whether this action modifies state
+    public var modifiesState: Bool {
+        switch self {
+        
+        case .getTask:
+            return true
+        
+        case .deleteTask:
+            return true
+        
+        }
+    }
+    
+    /// The name of this action for logging and analytics
+    public var actionName: String {
+        switch self {
+        
+        case .getTask:
+            return "getTask"
+        
+        case .deleteTask:
+            return "deleteTask"
+        
+        }
+    }
+    
+    /// Whether this action should show loading state
+    public var showsLoadingState: Bool {
+        switch self {
+        
+        case .getTask:
+            return true
+        
+        case .deleteTask:
+            return true
+        
+        }
+    }
+    
+    /// The state update strategy for this action
+    public var stateUpdateStrategy: StateUpdateStrategy {
+        switch self {
+        
+        case .getTask:
+            return .replace_all
+        
+        case .deleteTask:
+            return .remove_by_id
+        
+        }
+    }
+}
+
+// MARK: - State Update Strategy
+
+public enum StateUpdateStrategy: String, Sendable {
+    case append = "append"
+    case replaceAll = "replace_all"
+    case updateById = "update_by_id"
+    case removeById = "remove_by_id"
+    case custom = "custom"
+}
+
+// MARK: - Action Request Access
+
+extension TaskAction {
+    /// The request associated with this action
+    public var request: Any {
+        switch self {
+        
+        case .getTask(let request):
+            return request
+        
+        case .deleteTask(let request):
+            return request
+        
+        }
+    }
+}
+
+// MARK: - CustomStringConvertible
+
+extension TaskAction: CustomStringConvertible {
+    public var description: String {
+        switch self {
+        
+        case .getTask(let request):
+            return "TaskAction.getTask(\(request))"
+        
+        case .deleteTask(let request):
+            return "TaskAction.deleteTask(\(request))"
+        
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,203 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskClient.swift
+
+// Generated Swift client for TaskService
+import Foundation
+import AxiomCore
+import AxiomArchitecture
+
+
+
+@globalActor
+public actor TaskClient: AxiomObservableClient<TaskState, TaskAction> {
+    public typealias StateType = TaskState
+    public typealias ActionType = TaskAction
+    
+    // MARK: - Private Properties
+    private var _state: TaskState
+    private let apiClient: TaskServiceProtocol
+    private var streamContinuations: [UUID: AsyncStream<TaskState>.Continuation] = [:]
+    
+    // MARK: - Initialization
+    
+    /// Initialize the client with an API client implementation
+    /// - Parameters:
+    ///   - apiClient: The API client that implements TaskServiceProtocol
+    ///   - initialState: The initial state (defaults to empty state)
+    public init(apiClient: TaskServiceProtocol, initialState: TaskState = TaskState()) {
+        self._state = initialState
+        self.apiClient = apiClient
+    }
+    
+    // MARK: - AxiomClient Protocol Implementation
+    
+    public var stateStream: AsyncStream<TaskState> {
+        AsyncStream { [weak self] continuation in
+            let id = UUID()
+            Task { [weak self] in
+                await self?.addContinuation(continuation, id: id)
+                
+                // Emit current state immediately
+                if let currentState = await self?._state {
+                    continuation.yield(currentState)
+                }
+                
+                // Handle stream termination
+                continuation.onTermination = { [weak self, id] _ in
+                    Task { await self?.removeContinuation(id: id) }
+                }
+            }
+        }
+    }
+    
+    private func addContinuation(_ continuation: AsyncStream<TaskState>.Continuation, id: UUID) {
+        streamContinuations[id] = continuation
+    }
+    
+    private func removeContinuation(id: UUID) {
+        streamContinuations.removeValue(forKey: id)
+    }
+    
+    public func getCurrentState() async -> TaskState {
+        return _state
+    }
+    
+    public func rollbackToState(_ state: TaskState) async {
+        let oldState = _state
+        _state = state
+        
+        // Notify observers
+        for (_, continuation) in streamContinuations {
+            continuation.yield(state)
+        }
+        
+        await stateDidUpdate(from: oldState, to: state)
+    }
+    
+    /// Process an action and update the state accordingly
+    /// - Parameter action: The action to process
+    /// - Throws: Any error that occurs during processing
+    public func process(_ action: TaskAction) async throws {
+        // Validate action
+        guard action.isValid else {
+            let errors = action.validationErrors
+            throw AxiomError.clientError(.invalidAction("Action validation failed: \(errors.joined(separator: ", "))"))
+        }
+        
+        // Get old state for lifecycle hooks
+        let oldState = _state
+        
+        // Process the action
+        let newState: TaskState
+        do {
+            newState = try await processAction(action, currentState: _state)
+        } catch let status as AxiomStatusError {
+            // Map the gRPC status onto the service's typed error
+            let error = TaskClientError(status: status)
+            await storeError(error, from: oldState)
+            throw error
+        }
+        
+        // Only update if state actually changed
+        guard newState != oldState else { return }
+        
+        // Call lifecycle hooks
+        await stateWillUpdate(from: oldState, to: newState)
+        
+        // Update state
+        _state = newState
+        
+        // Notify observers
+        for (_, continuation) in streamContinuations {
+            continuation.yield(newState)
+        }
+        
+        // Call lifecycle hooks
+        await stateDidUpdate(from: oldState, to: newState)
+    }
+    
+    // MARK: - Action Processing
+    
+    private func processAction(_ action: TaskAction, currentState: TaskState) async throws -> TaskState {
+        switch action {
+        
+        case .getTask(let request):
+            
+            
+            let result = try await apiClient.getTask(request)
+            
+            
+            
+            
+            return currentState.withTask(result.task)
+            
+            
+        
+        
+        case .deleteTask(let request):
+            
+            
+            let result = try await apiClient.deleteTask(request)
+            
+            
+            
+            
+            return currentState.removingTask(withId: request.id)
+            
+            
+        
+        
+        }
+    }
+    
+    // MARK: - Error State
+    
+    /// Store a failed action's error in state while keeping loaded data intact
+    private func storeError(_ error: Error, from oldState: TaskState) async {
+        let errorState = _state.withError(error)
+        await stateWillUpdate(from: oldState, to: errorState)
+        _state = errorState
+        for (_, continuation) in streamContinuations {
+            continuation.yield(errorState)
+        }
+        await stateDidUpdate(from: oldState, to: errorState)
+    }
+    
+    // MARK: - Lifecycle Hooks
+    
+    public func stateWillUpdate(from old: TaskState, to new: TaskState) async {
+        // Override in subclasses if needed
+    }
+    
+    public func stateDidUpdate(from old: TaskState, to new: TaskState) async {
+        // Override in subclasses if needed
+    }
+    
+    // MARK: - Public Query Methods
+    
+    /// Get current statistics
+    public func getStatistics() async -> TaskState.Statistics {
+        return _state.statistics
+    }
+    
+    /// Get performance metrics
+    public func getPerformanceMetrics() async -> TaskClientMetrics {
+        TaskClientMetrics(
+            lastActionTime: Date(),
+            stateStreamSubscribers: streamContinuations.count
+        )
+    }
+}
+
+// MARK: - Performance Metrics
+
+/// Performance metrics for the TaskClient
+public struct TaskClientMetrics: Sendable, Equatable {
+    public let lastActionTime: Date
+    public let stateStreamSubscribers: Int
+    
+    public init(lastActionTime: Date, stateStreamSubscribers: Int) {
+        self.lastActionTime = lastActionTime
+        self.stateStreamSubscribers = stateStreamSubscribers
+    }
+}
\ No newline at end of file
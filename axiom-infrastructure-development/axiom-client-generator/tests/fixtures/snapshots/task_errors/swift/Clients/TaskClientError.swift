@@ -0,0 +1,100 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskClientError.swift
+
+// Generated typed errors for TaskService
+import Foundation
+
+// MARK: - TaskClientError
+
+/// Errors returned by TaskService, mapped from the `TaskError` proto enum
+public enum TaskClientError: Error, Equatable, Sendable {
+    case notFound(message: String, details: [String: String])
+    
+    case permissionDenied(message: String, details: [String: String])
+    
+    case taskLocked(message: String, details: [String: String])
+    
+    /// A status that does not correspond to any `TaskError` value
+    case unknown(statusCode: Int, message: String, details: [String: String])
+}
+
+// MARK: - Status Mapping
+
+extension TaskClientError {
+    /// Map a gRPC status and its error details onto a typed error
+    ///
+    /// A `reason` detail naming a `TaskError` value takes precedence;
+    /// otherwise the gRPC status code selects the matching case.
+    public init(statusCode: Int, message: String, details: [String: String] = [:]) {
+        switch details["reason"] {
+        case "TASK_ERROR_NOT_FOUND"?, "NOT_FOUND"?:
+            self = .notFound(message: message, details: details)
+            return
+        case "TASK_ERROR_PERMISSION_DENIED"?, "PERMISSION_DENIED"?:
+            self = .permissionDenied(message: message, details: details)
+            return
+        case "TASK_ERROR_TASK_LOCKED"?, "TASK_LOCKED"?:
+            self = .taskLocked(message: message, details: details)
+            return
+        default:
+            break
+        }
+        
+        switch statusCode {
+        case 5:
+            self = .notFound(message: message, details: details)
+        case 7:
+            self = .permissionDenied(message: message, details: details)
+        default:
+            self = .unknown(statusCode: statusCode, message: message, details: details)
+        }
+    }
+    
+    /// Map a status error thrown by the API client
+    public init(status: AxiomStatusError) {
+        self.init(statusCode: status.statusCode, message: status.statusMessage, details: status.errorDetails)
+    }
+    
+    /// Message sent by the server
+    public var message: String {
+        switch self {
+        case .notFound(let message, _):
+            return message
+        case .permissionDenied(let message, _):
+            return message
+        case .taskLocked(let message, _):
+            return message
+        case .unknown(_, let message, _):
+            return message
+        }
+    }
+    
+    /// Error details sent by the server
+    public var details: [String: String] {
+        switch self {
+        case .notFound(_, let details):
+            return details
+        case .permissionDenied(_, let details):
+            return details
+        case .taskLocked(_, let details):
+            return details
+        case .unknown(_, _, let details):
+            return details
+        }
+    }
+}
+
+extension TaskClientError: LocalizedError {
+    public var errorDescription: String? {
+        switch self {
+        case .notFound(let message, _):
+            return "Not Found: \(message)"
+        case .permissionDenied(let message, _):
+            return "Permission Denied: \(message)"
+        case .taskLocked(let message, _):
+            return "Task Locked: \(message)"
+        case .unknown(let statusCode, let message, _):
+            return "Unknown Error (status \(statusCode)): \(message)"
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,138 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskClientTests.swift
+
+// Generated tests for TaskClient
+import XCTest
+@testable import YourPackage
+
+final class TaskClientTests: XCTestCase {
+    var client: TaskClient!
+    var mockApiClient: MockTaskServiceClient!
+    
+    override func setUp() {
+        super.setUp()
+        mockApiClient = MockTaskServiceClient()
+        client = TaskClient(apiClient: mockApiClient)
+    }
+    
+    override func tearDown() {
+        client = nil
+        mockApiClient = nil
+        super.tearDown()
+    }
+    
+    
+    func testGetTask() async throws {
+        // Given
+        let request = GetTaskRequest(/* initialize with test data */)
+        
+        let expectedResult = Task(/* initialize with test data */)
+        mockApiClient.getTaskResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.getTask(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertEqual(state.task, [expectedResult])
+        
+    }
+    
+    
+    func testDeleteTask() async throws {
+        // Given
+        let request = DeleteTaskRequest(/* initialize with test data */)
+        
+        let expectedResult = DeleteTaskResponse(/* initialize with test data */)
+        mockApiClient.deleteTaskResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.deleteTask(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testTaskClientErrorMapsReasonNotFound() {
+        let error = TaskClientError(statusCode: 2, message: "test", details: ["reason": "TASK_ERROR_NOT_FOUND"])
+        XCTAssertEqual(error, .notFound(message: "test", details: ["reason": "TASK_ERROR_NOT_FOUND"]))
+    }
+    
+    func testTaskClientErrorMapsStatusNotFound() {
+        let error = TaskClientError(statusCode: 5, message: "test")
+        XCTAssertEqual(error, .notFound(message: "test", details: [:]))
+    }
+    
+    func testTaskClientErrorMapsReasonPermissionDenied() {
+        let error = TaskClientError(statusCode: 2, message: "test", details: ["reason": "TASK_ERROR_PERMISSION_DENIED"])
+        XCTAssertEqual(error, .permissionDenied(message: "test", details: ["reason": "TASK_ERROR_PERMISSION_DENIED"]))
+    }
+    
+    func testTaskClientErrorMapsStatusPermissionDenied() {
+        let error = TaskClientError(statusCode: 7, message: "test")
+        XCTAssertEqual(error, .permissionDenied(message: "test", details: [:]))
+    }
+    
+    func testTaskClientErrorMapsReasonTaskLocked() {
+        let error = TaskClientError(statusCode: 2, message: "test", details: ["reason": "TASK_ERROR_TASK_LOCKED"])
+        XCTAssertEqual(error, .taskLocked(message: "test", details: ["reason": "TASK_ERROR_TASK_LOCKED"]))
+    }
+    
+    func testTaskClientErrorFallsBackToUnknown() {
+        let error = TaskClientError(statusCode: 999, message: "test")
+        XCTAssertEqual(error, .unknown(statusCode: 999, message: "test", details: [:]))
+    }
+    
+}
+
+// Mock API client for testing
+class MockTaskServiceClient {
+    
+    
+    var getTaskResult: Task!
+    
+    var getTaskError: Error?
+    
+    
+    
+    var deleteTaskResult: DeleteTaskResponse!
+    
+    var deleteTaskError: Error?
+    
+    
+    
+    
+    func getTask(_ request: GetTaskRequest) async throws -> Task {
+        if let error = getTaskError {
+            throw error
+        }
+        
+        return getTaskResult
+        
+    }
+    
+    
+    func deleteTask(_ request: DeleteTaskRequest) async throws -> DeleteTaskResponse {
+        if let error = deleteTaskError {
+            throw error
+        }
+        
+        return deleteTaskResult
+        
+    }
+    
+    
+}
\ No newline at end of file
@@ -0,0 +1,479 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskState.swift
+
+// Generated state for TaskService
+import Foundation
+import AxiomCore
+
+// MARK: - TaskState
+
+/// State container for TaskService operations
+public struct TaskState: AxiomState {
+    
+    // MARK: - Collections
+    
+    /// Collection of Task entities
+    public let task: [Task]
+    
+    /// Collection of DeleteTask entities
+    public let tasks: [DeleteTask]
+    
+    
+    // MARK: - Loading State
+    /// Indicates if an operation is currently in progress
+    public let isLoading: Bool
+    
+    /// The last error that occurred, if any
+    public let error: Error?
+    
+    /// Last time the state was updated
+    public let lastUpdated: Date
+    
+    // MARK: - Pagination
+    
+    
+    // MARK: - Additional Properties
+    
+    
+    // MARK: - Initialization
+    
+    public init(
+        
+        task: [Task] = [],
+        
+        tasks: [DeleteTask] = [],
+        
+        isLoading: Bool = false,
+        error: Error? = nil,
+        lastUpdated: Date = Date()
+    ) {
+        
+        self.task = task
+        
+        self.tasks = tasks
+        
+        self.isLoading = isLoading
+        self.error = error
+        self.lastUpdated = lastUpdated
+        
+        
+    }
+    
+    // MARK: - Immutable Update Methods
+    
+    
+    /// Add a task to the collection
+    public func addingTask(_ item: Task) -> TaskState {
+        TaskState(
+            
+            task: task + [item],
+            
+            tasks: tasks,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire task collection
+    public func withTasks(_ newTasks: [Task]) -> TaskState {
+        TaskState(
+            
+            task: newTasks,
+            
+            tasks: tasks,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a task in the collection
+    public func updatingTask(_ updatedTask: Task) -> TaskState {
+        let updatedTasks = task.map { item in
+            item.id == updatedTask.id ? updatedTask : item
+        }
+        return TaskState(
+            
+            task: updatedTasks,
+            
+            tasks: tasks,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a task from the collection by ID
+    public func removingTask(withId id: String) -> TaskState {
+        TaskState(
+            
+            task: task.filter { $0.id != id },
+            
+            tasks: tasks,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    /// Add a deletetask to the collection
+    public func addingDeleteTask(_ item: DeleteTask) -> TaskState {
+        TaskState(
+            
+            task: task,
+            
+            tasks: tasks + [item],
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire tasks collection
+    public func withDeleteTasks(_ newDeleteTasks: [DeleteTask]) -> TaskState {
+        TaskState(
+            
+            task: task,
+            
+            tasks: newDeleteTasks,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a deletetask in the collection
+    public func updatingDeleteTask(_ updatedDeleteTask: DeleteTask) -> TaskState {
+        let updatedDeleteTasks = tasks.map { item in
+            item.id == updatedDeleteTask.id ? updatedDeleteTask : item
+        }
+        return TaskState(
+            
+            task: task,
+            
+            tasks: updatedDeleteTasks,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a deletetask from the collection by ID
+    public func removingDeleteTask(withId id: String) -> TaskState {
+        TaskState(
+            
+            task: task,
+            
+            tasks: tasks.filter { $0.id != id },
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    
+    /// Update the loading state
+    public func withLoading(_ loading: Bool) -> TaskState {
+        TaskState(
+            
+            task: task,
+            
+            tasks: tasks,
+            
+            isLoading: loading,
+            error: error,
+            lastUpdated: lastUpdated
+        )
+    }
+    
+    /// Update the error state
+    public func withError(_ error: Error?) -> TaskState {
+        TaskState(
+            
+            task: task,
+            
+            tasks: tasks,
+            
+            isLoading: false,
+            error: error,
+            lastUpdated: Date()
+        )
+    }
+    
+    // MARK: - Computed Properties
+    
+    
+    /// Number of task in the collection
+    public var taskCount: Int {
+        task.count
+    }
+    
+    /// Returns true if task collection is empty
+    public var isTaskEmpty: Bool {
+        task.isEmpty
+    }
+    
+    /// Find a Task by ID
+    public func task(withId id: String) -> Task? {
+        task.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Number of tasks in the collection
+    public var tasksCount: Int {
+        tasks.count
+    }
+    
+    /// Returns true if tasks collection is empty
+    public var isDeleteTaskEmpty: Bool {
+        tasks.isEmpty
+    }
+    
+    /// Find a DeleteTask by ID
+    public func deletetask(withId id: String) -> DeleteTask? {
+        tasks.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Returns true if any operation is currently loading
+    public var hasActiveOperation: Bool {
+        isLoading
+    }
+    
+    /// Returns true if there was an error in the last operation
+    public var hasError: Bool {
+        error != nil
+    }
+    
+    /// Statistics for this state
+    public var statistics: Statistics {
+        Statistics(
+            
+            totalTasks: task.count,
+            
+            taskCacheUtilization: Double(task.count) / Double(1000),
+            
+            
+            totalDeleteTasks: tasks.count,
+            
+            tasksCacheUtilization: Double(tasks.count) / Double(1000),
+            
+            
+            lastUpdated: lastUpdated,
+            hasActiveOperations: isLoading,
+            hasErrors: hasError
+        )
+    }
+    
+    /// Performance metrics for large state objects
+    public var performanceMetrics: PerformanceMetrics {
+        let totalItems = task.count + tasks.count
+        let memoryFootprint = totalItems * MemoryLayout<AnyObject>.stride
+        
+        return PerformanceMetrics(
+            totalItems: totalItems,
+            estimatedMemoryFootprint: memoryFootprint,
+            collectionsCount: 2,
+            isOptimizedForLargeDatasets: totalItems > 1000
+        )
+    }
+    
+    /// State validation based on Axiom field constraints
+    public var validationResults: StateValidationResults {
+        var issues: [ValidationIssue] = []
+        
+        
+        // Validate task collection
+        
+        if task.count > 1000 {
+            issues.append(.collectionSizeExceeded("task", current: task.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in task
+        for (index, item) in task.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("task[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        // Validate tasks collection
+        
+        if tasks.count > 1000 {
+            issues.append(.collectionSizeExceeded("tasks", current: tasks.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in tasks
+        for (index, item) in tasks.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("tasks[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        
+        return StateValidationResults(
+            isValid: issues.isEmpty,
+            issues: issues,
+            validatedAt: Date()
+        )
+    }
+}
+
+// MARK: - TaskState + Equatable & Hashable
+
+extension TaskState {
+    public static func == (lhs: TaskState, rhs: TaskState) -> Bool {
+        return lhs.task == rhs.task &&
+               lhs.tasks == rhs.tasks &&
+               lhs.isLoading == rhs.isLoading &&
+               lhs.hasError == rhs.hasError
+    }
+    
+    public func hash(into hasher: inout Hasher) {
+        
+        hasher.combine(task)
+        
+        hasher.combine(tasks)
+        
+        hasher.combine(isLoading)
+        hasher.combine(hasError)
+        
+        
+    }
+}
+
+// MARK: - Supporting Types
+
+extension TaskState {
+    /// Enhanced statistics for state monitoring
+    public struct Statistics: Sendable, Equatable {
+        
+        public let totalTasks: Int
+        
+        public let taskCacheUtilization: Double
+        
+        
+        public let totalDeleteTasks: Int
+        
+        public let tasksCacheUtilization: Double
+        
+        
+        public let lastUpdated: Date
+        public let hasActiveOperations: Bool
+        public let hasErrors: Bool
+        
+        
+        public init(
+            
+            totalTasks: Int,
+            
+            taskCacheUtilization: Double,
+            
+            
+            totalDeleteTasks: Int,
+            
+            tasksCacheUtilization: Double,
+            
+            
+            lastUpdated: Date,
+            hasActiveOperations: Bool,
+            hasErrors: Bool
+        ) {
+            
+            self.totalTasks = totalTasks
+            
+            self.taskCacheUtilization = taskCacheUtilization
+            
+            
+            self.totalDeleteTasks = totalDeleteTasks
+            
+            self.tasksCacheUtilization = tasksCacheUtilization
+            
+            
+            self.lastUpdated = lastUpdated
+            self.hasActiveOperations = hasActiveOperations
+            self.hasErrors = hasErrors
+            
+        }
+    }
+    
+    
+    
+    /// Performance metrics for optimization
+    public struct PerformanceMetrics: Sendable, Equatable {
+        public let totalItems: Int
+        public let estimatedMemoryFootprint: Int
+        public let collectionsCount: Int
+        public let isOptimizedForLargeDatasets: Bool
+        
+        public init(
+            totalItems: Int,
+            estimatedMemoryFootprint: Int,
+            collectionsCount: Int,
+            isOptimizedForLargeDatasets: Bool
+        ) {
+            self.totalItems = totalItems
+            self.estimatedMemoryFootprint = estimatedMemoryFootprint
+            self.collectionsCount = collectionsCount
+            self.isOptimizedForLargeDatasets = isOptimizedForLargeDatasets
+        }
+    }
+    
+    /// State validation results based on Axiom constraints
+    public struct StateValidationResults: Sendable, Equatable {
+        public let isValid: Bool
+        public let issues: [ValidationIssue]
+        public let validatedAt: Date
+        
+        public init(isValid: Bool, issues: [ValidationIssue], validatedAt: Date) {
+            self.isValid = isValid
+            self.issues = issues
+            self.validatedAt = validatedAt
+        }
+    }
+    
+    /// Individual validation issues
+    public enum ValidationIssue: Sendable, Equatable {
+        case collectionSizeExceeded(String, current: Int, max: Int)
+        case invalidFieldValue(String, reason: String)
+        case constraintViolation(String, constraint: String)
+        
+        public var description: String {
+            switch self {
+            case .collectionSizeExceeded(let collection, let current, let max):
+                return "Collection '\(collection)' size \(current) exceeds maximum \(max)"
+            case .invalidFieldValue(let field, let reason):
+                return "Field '\(field)': \(reason)"
+            case .constraintViolation(let field, let constraint):
+                return "Field '\(field)' violates constraint: \(constraint)"
+            }
+        }
+    }
+}
\ No newline at end of file
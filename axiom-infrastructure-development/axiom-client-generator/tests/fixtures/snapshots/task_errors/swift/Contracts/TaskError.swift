@@ -0,0 +1,20 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskError.swift
+
+// MARK: - TaskError
+
+public enum TaskError: String, Codable, CaseIterable, Sendable {
+    
+    
+    case errorUnspecified = "TASK_ERROR_UNSPECIFIED"
+    
+    
+    case errorNotFound = "TASK_ERROR_NOT_FOUND"
+    
+    
+    case errorPermissionDenied = "TASK_ERROR_PERMISSION_DENIED"
+    
+    
+    case errorTaskLocked = "TASK_ERROR_TASK_LOCKED"
+    
+}
\ No newline at end of file
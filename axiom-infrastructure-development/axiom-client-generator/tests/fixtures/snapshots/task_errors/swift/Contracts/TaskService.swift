@@ -0,0 +1,155 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskService.swift
+
+// Generated Swift service contracts
+import Foundation
+
+import AxiomCore
+
+
+
+
+// MARK: - GetTaskRequest
+
+public struct GetTaskRequest: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public init(id: String = "") {
+        
+        self.id = id
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+    }
+    
+    
+    
+}
+
+
+// MARK: - DeleteTaskRequest
+
+public struct DeleteTaskRequest: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public init(id: String = "") {
+        
+        self.id = id
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+    }
+    
+    
+    
+}
+
+
+// MARK: - DeleteTaskResponse
+
+public struct DeleteTaskResponse: Codable, Equatable, Hashable {
+    
+    
+    
+    public let deleted: Bool
+    
+    
+    
+    
+    public init(deleted: Bool = false) {
+        
+        self.deleted = deleted
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.deleted = try container.decodeIfPresent(Bool.self, forKey: .deleted) ?? false
+        
+    }
+    
+    
+    
+}
+
+
+// MARK: - Task
+
+public struct Task: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let title: String
+    
+    
+    
+    
+    public init(id: String = "", title: String = "") {
+        
+        self.id = id
+        
+        self.title = title
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.title = try container.decodeIfPresent(String.self, forKey: .title) ?? ""
+        
+    }
+    
+    
+    
+}
+
+
+
+
+
+
+// MARK: - TaskService Protocol
+
+public protocol TaskServiceProtocol {
+    
+    
+    func getTask(_ request: GetTaskRequest) async throws -> Task
+    
+    
+    func deleteTask(_ request: DeleteTaskRequest) async throws -> DeleteTaskResponse
+    
+}
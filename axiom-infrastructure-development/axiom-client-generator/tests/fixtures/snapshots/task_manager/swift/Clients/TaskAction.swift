@@ -0,0 +1,372 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskAction.swift
+
+// Generated action enum for TaskService
+import Foundation
+
+// MARK: - TaskAction
+
+/// Actions that can be performed on TaskService
+public enum TaskAction: Sendable, Equatable {
+    
+    
+    case createTask(CreateTaskRequest)
+    
+    
+    case getTasks(GetTasksRequest)
+    
+    
+    case updateTask(UpdateTaskRequest)
+    
+    
+    case deleteTask(DeleteTaskRequest)
+    
+    
+    case toggleTaskCompletion(ToggleTaskCompletionRequest)
+    
+    
+    case getCategories(GetCategoriesRequest)
+    
+    
+    case createCategory(CreateCategoryRequest)
+    
+    
+    case searchTasks(SearchTasksRequest)
+    
+}
+
+// MARK: - Action Validation
+
+extension TaskAction {
+    /// Validates the action and its associated request
+    public var isValid: Bool {
+        switch self {
+        
+        case .createTask(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .getTasks(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .updateTask(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .deleteTask(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .toggleTaskCompletion(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .getCategories(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .createCategory(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .searchTasks(let request):
+            
+            return true // No specific validation rules
+            
+        
+        }
+    }
+    
+    /// Returns validation errors for the action
+    public var validationErrors: [String] {
+        switch self {
+        
+        case .createTask(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .getTasks(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .updateTask(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .deleteTask(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .toggleTaskCompletion(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .getCategories(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .createCategory(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .searchTasks(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        }
+    }
+}
+
+// MARK: - Action Metadata
+
+extension TaskAction {
+    /// Whether this action requires network access
+    public var requiresNetworkAccess: Bool {
+        switch self {
+        
+        case .createTask:
+            return true
+        
+        case .getTasks:
+            return true
+        
+        case .updateTask:
+            return true
+        
+        case .deleteTask:
+            return true
+        
+        case .toggleTaskCompletion:
+            return true
+        
+        case .getCategories:
+            return true
+        
+        case .createCategory:
+            return true
+        
+        case .searchTasks:
+            return true
+        
+        }
+    }
+    
+    /// Whether this action modifies state
+    public var modifiesState: Bool {
+        switch self {
+        
+        case .createTask:
+            return true
+        
+        case .getTasks:
+            return true
+        
+        case .updateTask:
+            return true
+        
+        case .deleteTask:
+            return true
+        
+        case .toggleTaskCompletion:
+            return true
+        
+        case .getCategories:
+            return true
+        
+        case .createCategory:
+            return true
+        
+        case .searchTasks:
+            return true
+        
+        }
+    }
+    
+    /// The name of this action for logging and analytics
+    public var actionName: String {
+        switch self {
+        
+        case .createTask:
+            return "createTask"
+        
+        case .getTasks:
+            return "getTasks"
+        
+        case .updateTask:
+            return "updateTask"
+        
+        case .deleteTask:
+            return "deleteTask"
+        
+        case .toggleTaskCompletion:
+            return "toggleTaskCompletion"
+        
+        case .getCategories:
+            return "getCategories"
+        
+        case .createCategory:
+            return "createCategory"
+        
+        case .searchTasks:
+            return "searchTasks"
+        
+        }
+    }
+    
+    /// Whether this action should show loading state
+    public var showsLoadingState: Bool {
+        switch self {
+        
+        case .createTask:
+            return true
+        
+        case .getTasks:
+            return true
+        
+        case .updateTask:
+            return true
+        
+        case .deleteTask:
+            return true
+        
+        case .toggleTaskCompletion:
+            return true
+        
+        case .getCategories:
+            return true
+        
+        case .createCategory:
+            return true
+        
+        case .searchTasks:
+            return true
+        
+        }
+    }
+    
+    /// The state update strategy for this action
+    public var stateUpdateStrategy: StateUpdateStrategy {
+        switch self {
+        
+        case .createTask:
+            return .append
+        
+        case .getTasks:
+            return .replace_all
+        
+        case .updateTask:
+            return .update_by_id
+        
+        case .deleteTask:
+            return .remove_by_id
+        
+        case .toggleTaskCompletion:
+            return .custom
+        
+        case .getCategories:
+            return .replace_all
+        
+        case .createCategory:
+            return .append
+        
+        case .searchTasks:
+            return .custom
+        
+        }
+    }
+}
+
+// MARK: - State Update Strategy
+
+public enum StateUpdateStrategy: String, Sendable {
+    case append = "append"
+    case replaceAll = "replace_all"
+    case updateById = "update_by_id"
+    case removeById = "remove_by_id"
+    case custom = "custom"
+}
+
+// MARK: - Action Request Access
+
+extension TaskAction {
+    /// The request associated with this action
+    public var request: Any {
+        switch self {
+        
+        case .createTask(let request):
+            return request
+        
+        case .getTasks(let request):
+            return request
+        
+        case .updateTask(let request):
+            return request
+        
+        case .deleteTask(let request):
+            return request
+        
+        case .toggleTaskCompletion(let request):
+            return request
+        
+        case .getCategories(let request):
+            return request
+        
+        case .createCategory(let request):
+            return request
+        
+        case .searchTasks(let request):
+            return request
+        
+        }
+    }
+}
+
+// MARK: - CustomStringConvertible
+
+extension TaskAction: CustomStringConvertible {
+    public var description: String {
+        switch self {
+        
+        case .createTask(let request):
+            return "TaskAction.createTask(\(request))"
+        
+        case .getTasks(let request):
+            return "TaskAction.getTasks(\(request))"
+        
+        case .updateTask(let request):
+            return "TaskAction.updateTask(\(request))"
+        
+        case .deleteTask(let request):
+            return "TaskAction.deleteTask(\(request))"
+        
+        case .toggleTaskCompletion(let request):
+            return "TaskAction.toggleTaskCompletion(\(request))"
+        
+        case .getCategories(let request):
+            return "TaskAction.getCategories(\(request))"
+        
+        case .createCategory(let request):
+            return "TaskAction.createCategory(\(request))"
+        
+        case .searchTasks(let request):
+            return "TaskAction.searchTasks(\(request))"
+        
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,329 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskClientTests.swift
+
+// Generated tests for TaskClient
+import XCTest
+@testable import YourPackage
+
+final class TaskClientTests: XCTestCase {
+    var client: TaskClient!
+    var mockApiClient: MockTaskServiceClient!
+    
+    override func setUp() {
+        super.setUp()
+        mockApiClient = MockTaskServiceClient()
+        client = TaskClient(apiClient: mockApiClient)
+    }
+    
+    override func tearDown() {
+        client = nil
+        mockApiClient = nil
+        super.tearDown()
+    }
+    
+    
+    func testCreateTask() async throws {
+        // Given
+        let request = CreateTaskRequest(/* initialize with test data */)
+        
+        let expectedResult = Task(/* initialize with test data */)
+        mockApiClient.createTaskResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.createTask(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertTrue(state.tasks.contains(expectedResult))
+        
+    }
+    
+    
+    func testGetTasks() async throws {
+        // Given
+        let request = GetTasksRequest(/* initialize with test data */)
+        
+        let expectedResult = GetTasksResponse(/* initialize with test data */)
+        mockApiClient.getTasksResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.getTasks(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertEqual(state.tasks, [expectedResult])
+        
+    }
+    
+    
+    func testUpdateTask() async throws {
+        // Given
+        let request = UpdateTaskRequest(/* initialize with test data */)
+        
+        let expectedResult = Task(/* initialize with test data */)
+        mockApiClient.updateTaskResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.updateTask(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testDeleteTask() async throws {
+        // Given
+        let request = DeleteTaskRequest(/* initialize with test data */)
+        
+        
+        // When
+        
+        try await client.process(.deleteTask(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testToggleTaskCompletion() async throws {
+        // Given
+        let request = ToggleTaskCompletionRequest(/* initialize with test data */)
+        
+        let expectedResult = Task(/* initialize with test data */)
+        mockApiClient.toggleTaskCompletionResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.toggleTaskCompletion(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testGetCategories() async throws {
+        // Given
+        let request = GetCategoriesRequest(/* initialize with test data */)
+        
+        let expectedResult = GetCategoriesResponse(/* initialize with test data */)
+        mockApiClient.getCategoriesResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.getCategories(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertEqual(state.categories, [expectedResult])
+        
+    }
+    
+    
+    func testCreateCategory() async throws {
+        // Given
+        let request = CreateCategoryRequest(/* initialize with test data */)
+        
+        let expectedResult = Category(/* initialize with test data */)
+        mockApiClient.createCategoryResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.createCategory(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertTrue(state.tasks.contains(expectedResult))
+        
+    }
+    
+    
+    func testSearchTasks() async throws {
+        // Given
+        let request = SearchTasksRequest(/* initialize with test data */)
+        
+        let expectedResult = SearchTasksResponse(/* initialize with test data */)
+        mockApiClient.searchTasksResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.searchTasks(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+}
+
+// Mock API client for testing
+class MockTaskServiceClient {
+    
+    
+    var createTaskResult: Task!
+    
+    var createTaskError: Error?
+    
+    
+    
+    var getTasksResult: GetTasksResponse!
+    
+    var getTasksError: Error?
+    
+    
+    
+    var updateTaskResult: Task!
+    
+    var updateTaskError: Error?
+    
+    
+    
+    var deleteTaskError: Error?
+    
+    
+    
+    var toggleTaskCompletionResult: Task!
+    
+    var toggleTaskCompletionError: Error?
+    
+    
+    
+    var getCategoriesResult: GetCategoriesResponse!
+    
+    var getCategoriesError: Error?
+    
+    
+    
+    var createCategoryResult: Category!
+    
+    var createCategoryError: Error?
+    
+    
+    
+    var searchTasksResult: SearchTasksResponse!
+    
+    var searchTasksError: Error?
+    
+    
+    
+    
+    func createTask(_ request: CreateTaskRequest) async throws -> Task {
+        if let error = createTaskError {
+            throw error
+        }
+        
+        return createTaskResult
+        
+    }
+    
+    
+    func getTasks(_ request: GetTasksRequest) async throws -> GetTasksResponse {
+        if let error = getTasksError {
+            throw error
+        }
+        
+        return getTasksResult
+        
+    }
+    
+    
+    func updateTask(_ request: UpdateTaskRequest) async throws -> Task {
+        if let error = updateTaskError {
+            throw error
+        }
+        
+        return updateTaskResult
+        
+    }
+    
+    
+    func deleteTask(_ request: DeleteTaskRequest) async throws {
+        if let error = deleteTaskError {
+            throw error
+        }
+        
+    }
+    
+    
+    func toggleTaskCompletion(_ request: ToggleTaskCompletionRequest) async throws -> Task {
+        if let error = toggleTaskCompletionError {
+            throw error
+        }
+        
+        return toggleTaskCompletionResult
+        
+    }
+    
+    
+    func getCategories(_ request: GetCategoriesRequest) async throws -> GetCategoriesResponse {
+        if let error = getCategoriesError {
+            throw error
+        }
+        
+        return getCategoriesResult
+        
+    }
+    
+    
+    func createCategory(_ request: CreateCategoryRequest) async throws -> Category {
+        if let error = createCategoryError {
+            throw error
+        }
+        
+        return createCategoryResult
+        
+    }
+    
+    
+    func searchTasks(_ request: SearchTasksRequest) async throws -> SearchTasksResponse {
+        if let error = searchTasksError {
+            throw error
+        }
+        
+        return searchTasksResult
+        
+    }
+    
+    
+}
\ No newline at end of file
@@ -0,0 +1,479 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskState.swift
+
+// Generated state for TaskService
+import Foundation
+import AxiomCore
+
+// MARK: - TaskState
+
+/// State container for TaskService operations
+public struct TaskState: AxiomState {
+    
+    // MARK: - Collections
+    
+    /// Collection of Task entities
+    public let tasks: [Task]
+    
+    /// Collection of GetCategories entities
+    public let categories: [GetCategories]
+    
+    
+    // MARK: - Loading State
+    /// Indicates if an operation is currently in progress
+    public let isLoading: Bool
+    
+    /// The last error that occurred, if any
+    public let error: Error?
+    
+    /// Last time the state was updated
+    public let lastUpdated: Date
+    
+    // MARK: - Pagination
+    
+    
+    // MARK: - Additional Properties
+    
+    
+    // MARK: - Initialization
+    
+    public init(
+        
+        tasks: [Task] = [],
+        
+        categories: [GetCategories] = [],
+        
+        isLoading: Bool = false,
+        error: Error? = nil,
+        lastUpdated: Date = Date()
+    ) {
+        
+        self.tasks = tasks
+        
+        self.categories = categories
+        
+        self.isLoading = isLoading
+        self.error = error
+        self.lastUpdated = lastUpdated
+        
+        
+    }
+    
+    // MARK: - Immutable Update Methods
+    
+    
+    /// Add a task to the collection
+    public func addingTask(_ item: Task) -> TaskState {
+        TaskState(
+            
+            tasks: tasks + [item],
+            
+            categories: categories,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire tasks collection
+    public func withTasks(_ newTasks: [Task]) -> TaskState {
+        TaskState(
+            
+            tasks: newTasks,
+            
+            categories: categories,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a task in the collection
+    public func updatingTask(_ updatedTask: Task) -> TaskState {
+        let updatedTasks = tasks.map { item in
+            item.id == updatedTask.id ? updatedTask : item
+        }
+        return TaskState(
+            
+            tasks: updatedTasks,
+            
+            categories: categories,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a task from the collection by ID
+    public func removingTask(withId id: String) -> TaskState {
+        TaskState(
+            
+            tasks: tasks.filter { $0.id != id },
+            
+            categories: categories,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    /// Add a getcategories to the collection
+    public func addingGetCategories(_ item: GetCategories) -> TaskState {
+        TaskState(
+            
+            tasks: tasks,
+            
+            categories: categories + [item],
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire categories collection
+    public func withGetCategoriess(_ newGetCategoriess: [GetCategories]) -> TaskState {
+        TaskState(
+            
+            tasks: tasks,
+            
+            categories: newGetCategoriess,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a getcategories in the collection
+    public func updatingGetCategories(_ updatedGetCategories: GetCategories) -> TaskState {
+        let updatedGetCategoriess = categories.map { item in
+            item.id == updatedGetCategories.id ? updatedGetCategories : item
+        }
+        return TaskState(
+            
+            tasks: tasks,
+            
+            categories: updatedGetCategoriess,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a getcategories from the collection by ID
+    public func removingGetCategories(withId id: String) -> TaskState {
+        TaskState(
+            
+            tasks: tasks,
+            
+            categories: categories.filter { $0.id != id },
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    
+    /// Update the loading state
+    public func withLoading(_ loading: Bool) -> TaskState {
+        TaskState(
+            
+            tasks: tasks,
+            
+            categories: categories,
+            
+            isLoading: loading,
+            error: error,
+            lastUpdated: lastUpdated
+        )
+    }
+    
+    /// Update the error state
+    public func withError(_ error: Error?) -> TaskState {
+        TaskState(
+            
+            tasks: tasks,
+            
+            categories: categories,
+            
+            isLoading: false,
+            error: error,
+            lastUpdated: Date()
+        )
+    }
+    
+    // MARK: - Computed Properties
+    
+    
+    /// Number of tasks in the collection
+    public var tasksCount: Int {
+        tasks.count
+    }
+    
+    /// Returns true if tasks collection is empty
+    public var isTaskEmpty: Bool {
+        tasks.isEmpty
+    }
+    
+    /// Find a Task by ID
+    public func task(withId id: String) -> Task? {
+        tasks.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Number of categories in the collection
+    public var categoriesCount: Int {
+        categories.count
+    }
+    
+    /// Returns true if categories collection is empty
+    public var isGetCategoriesEmpty: Bool {
+        categories.isEmpty
+    }
+    
+    /// Find a GetCategories by ID
+    public func getcategories(withId id: String) -> GetCategories? {
+        categories.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Returns true if any operation is currently loading
+    public var hasActiveOperation: Bool {
+        isLoading
+    }
+    
+    /// Returns true if there was an error in the last operation
+    public var hasError: Bool {
+        error != nil
+    }
+    
+    /// Statistics for this state
+    public var statistics: Statistics {
+        Statistics(
+            
+            totalTasks: tasks.count,
+            
+            tasksCacheUtilization: Double(tasks.count) / Double(1000),
+            
+            
+            totalGetCategoriess: categories.count,
+            
+            categoriesCacheUtilization: Double(categories.count) / Double(1000),
+            
+            
+            lastUpdated: lastUpdated,
+            hasActiveOperations: isLoading,
+            hasErrors: hasError
+        )
+    }
+    
+    /// Performance metrics for large state objects
+    public var performanceMetrics: PerformanceMetrics {
+        let totalItems = tasks.count + categories.count
+        let memoryFootprint = totalItems * MemoryLayout<AnyObject>.stride
+        
+        return PerformanceMetrics(
+            totalItems: totalItems,
+            estimatedMemoryFootprint: memoryFootprint,
+            collectionsCount: 2,
+            isOptimizedForLargeDatasets: totalItems > 1000
+        )
+    }
+    
+    /// State validation based on Axiom field constraints
+    public var validationResults: StateValidationResults {
+        var issues: [ValidationIssue] = []
+        
+        
+        // Validate tasks collection
+        
+        if tasks.count > 1000 {
+            issues.append(.collectionSizeExceeded("tasks", current: tasks.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in tasks
+        for (index, item) in tasks.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("tasks[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        // Validate categories collection
+        
+        if categories.count > 1000 {
+            issues.append(.collectionSizeExceeded("categories", current: categories.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in categories
+        for (index, item) in categories.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("categories[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        
+        return StateValidationResults(
+            isValid: issues.isEmpty,
+            issues: issues,
+            validatedAt: Date()
+        )
+    }
+}
+
+// MARK: - TaskState + Equatable & Hashable
+
+extension TaskState {
+    public static func == (lhs: TaskState, rhs: TaskState) -> Bool {
+        return lhs.tasks == rhs.tasks &&
+               lhs.categories == rhs.categories &&
+               lhs.isLoading == rhs.isLoading &&
+               lhs.hasError == rhs.hasError
+    }
+    
+    public func hash(into hasher: inout Hasher) {
+        
+        hasher.combine(tasks)
+        
+        hasher.combine(categories)
+        
+        hasher.combine(isLoading)
+        hasher.combine(hasError)
+        
+        
+    }
+}
+
+// MARK: - Supporting Types
+
+extension TaskState {
+    /// Enhanced statistics for state monitoring
+    public struct Statistics: Sendable, Equatable {
+        
+        public let totalTasks: Int
+        
+        public let tasksCacheUtilization: Double
+        
+        
+        public let totalGetCategoriess: Int
+        
+        public let categoriesCacheUtilization: Double
+        
+        
+        public let lastUpdated: Date
+        public let hasActiveOperations: Bool
+        public let hasErrors: Bool
+        
+        
+        public init(
+            
+            totalTasks: Int,
+            
+            tasksCacheUtilization: Double,
+            
+            
+            totalGetCategoriess: Int,
+            
+            categoriesCacheUtilization: Double,
+            
+            
+            lastUpdated: Date,
+            hasActiveOperations: Bool,
+            hasErrors: Bool
+        ) {
+            
+            self.totalTasks = totalTasks
+            
+            self.tasksCacheUtilization = tasksCacheUtilization
+            
+            
+            self.totalGetCategoriess = totalGetCategoriess
+            
+            self.categoriesCacheUtilization = categoriesCacheUtilization
+            
+            
+            self.lastUpdated = lastUpdated
+            self.hasActiveOperations = hasActiveOperations
+            self.hasErrors = hasErrors
+            
+        }
+    }
+    
+    
+    
+    /// Performance metrics for optimization
+    public struct PerformanceMetrics: Sendable, Equatable {
+        public let totalItems: Int
+        public let estimatedMemoryFootprint: Int
+        public let collectionsCount: Int
+        public let isOptimizedForLargeDatasets: Bool
+        
+        public init(
+            totalItems: Int,
+            estimatedMemoryFootprint: Int,
+            collectionsCount: Int,
+            isOptimizedForLargeDatasets: Bool
+        ) {
+            self.totalItems = totalItems
+            self.estimatedMemoryFootprint = estimatedMemoryFootprint
+            self.collectionsCount = collectionsCount
+            self.isOptimizedForLargeDatasets = isOptimizedForLargeDatasets
+        }
+    }
+    
+    /// State validation results based on Axiom constraints
+    public struct StateValidationResults: Sendable, Equatable {
+        public let isValid: Bool
+        public let issues: [ValidationIssue]
+        public let validatedAt: Date
+        
+        public init(isValid: Bool, issues: [ValidationIssue], validatedAt: Date) {
+            self.isValid = isValid
+            self.issues = issues
+            self.validatedAt = validatedAt
+        }
+    }
+    
+    /// Individual validation issues
+    public enum ValidationIssue: Sendable, Equatable {
+        case collectionSizeExceeded(String, current: Int, max: Int)
+        case invalidFieldValue(String, reason: String)
+        case constraintViolation(String, constraint: String)
+        
+        public var description: String {
+            switch self {
+            case .collectionSizeExceeded(let collection, let current, let max):
+                return "Collection '\(collection)' size \(current) exceeds maximum \(max)"
+            case .invalidFieldValue(let field, let reason):
+                return "Field '\(field)': \(reason)"
+            case .constraintViolation(let field, let constraint):
+                return "Field '\(field)' violates constraint: \(constraint)"
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,1812 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: TaskService.swift
+
+// Generated Swift service contracts
+import Foundation
+
+import AxiomCore
+
+
+
+
+// MARK: - Task
+
+public struct Task: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let title: String
+    
+    
+    
+    
+    public let description: String
+    
+    
+    
+    
+    public let isCompleted: Bool
+    
+    
+    
+    
+    public let createdAt: Date?
+    
+    
+    
+    
+    public let updatedAt: Date?
+    
+    
+    
+    
+    public let dueDate: Date?
+    
+    
+    
+    
+    public let priority: TaskPriority
+    
+    
+    
+    
+    public let status: TaskStatus
+    
+    
+    
+    
+    public let tags: [String]
+    
+    
+    
+    
+    public let categoryId: String
+    
+    
+    
+    
+    public let assignedTo: String
+    
+    
+    
+    
+    public let estimatedHours: Int32
+    
+    
+    
+    
+    public let actualHours: Int32
+    
+    
+    
+    
+    public let comments: [TaskComment]
+    
+    
+    
+    
+    public let attachments: [TaskAttachment]
+    
+    
+    
+    
+    public init(id: String = "", title: String = "", description: String = "", isCompleted: Bool = false, createdAt: Date? = nil, updatedAt: Date? = nil, dueDate: Date? = nil, priority: TaskPriority = .unspecified, status: TaskStatus = .statusUnspecified, tags: [String] = [], categoryId: String = "", assignedTo: String = "", estimatedHours: Int32 = 0, actualHours: Int32 = 0, comments: [TaskComment] = [], attachments: [TaskAttachment] = []) {
+        
+        self.id = id
+        
+        self.title = title
+        
+        self.description = description
+        
+        self.isCompleted = isCompleted
+        
+        self.createdAt = createdAt
+        
+        self.updatedAt = updatedAt
+        
+        self.dueDate = dueDate
+        
+        self.priority = priority
+        
+        self.status = status
+        
+        self.tags = tags
+        
+        self.categoryId = categoryId
+        
+        self.assignedTo = assignedTo
+        
+        self.estimatedHours = estimatedHours
+        
+        self.actualHours = actualHours
+        
+        self.comments = comments
+        
+        self.attachments = attachments
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.title = try container.decodeIfPresent(String.self, forKey: .title) ?? ""
+        
+        self.description = try container.decodeIfPresent(String.self, forKey: .description) ?? ""
+        
+        self.isCompleted = try container.decodeIfPresent(Bool.self, forKey: .isCompleted) ?? false
+        
+        self.createdAt = try container.decodeIfPresent(Date.self, forKey: .createdAt)
+        
+        self.updatedAt = try container.decodeIfPresent(Date.self, forKey: .updatedAt)
+        
+        self.dueDate = try container.decodeIfPresent(Date.self, forKey: .dueDate)
+        
+        self.priority = try container.decodeIfPresent(TaskPriority.self, forKey: .priority) ?? .unspecified
+        
+        self.status = try container.decodeIfPresent(TaskStatus.self, forKey: .status) ?? .statusUnspecified
+        
+        self.tags = try container.decodeIfPresent([String].self, forKey: .tags) ?? []
+        
+        self.categoryId = try container.decodeIfPresent(String.self, forKey: .categoryId) ?? ""
+        
+        self.assignedTo = try container.decodeIfPresent(String.self, forKey: .assignedTo) ?? ""
+        
+        self.estimatedHours = try container.decodeIfPresent(Int32.self, forKey: .estimatedHours) ?? 0
+        
+        self.actualHours = try container.decodeIfPresent(Int32.self, forKey: .actualHours) ?? 0
+        
+        self.comments = try container.decodeIfPresent([TaskComment].self, forKey: .comments) ?? []
+        
+        self.attachments = try container.decodeIfPresent([TaskAttachment].self, forKey: .attachments) ?? []
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case id
+        
+        case title
+        
+        case description
+        
+        case isCompleted = "is_completed"
+        
+        case createdAt = "created_at"
+        
+        case updatedAt = "updated_at"
+        
+        case dueDate = "due_date"
+        
+        case priority
+        
+        case status
+        
+        case tags
+        
+        case categoryId = "category_id"
+        
+        case assignedTo = "assigned_to"
+        
+        case estimatedHours = "estimated_hours"
+        
+        case actualHours = "actual_hours"
+        
+        case comments
+        
+        case attachments
+        
+    }
+    
+}
+
+
+// MARK: - Category
+
+public struct Category: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let name: String
+    
+    
+    
+    
+    public let description: String
+    
+    
+    
+    
+    public let color: String
+    
+    
+    
+    
+    public let icon: String
+    
+    
+    
+    
+    public let taskCount: Int32
+    
+    
+    
+    
+    public let isDefault: Bool
+    
+    
+    
+    
+    public let createdAt: Date?
+    
+    
+    
+    
+    public init(id: String = "", name: String = "", description: String = "", color: String = "", icon: String = "", taskCount: Int32 = 0, isDefault: Bool = false, createdAt: Date? = nil) {
+        
+        self.id = id
+        
+        self.name = name
+        
+        self.description = description
+        
+        self.color = color
+        
+        self.icon = icon
+        
+        self.taskCount = taskCount
+        
+        self.isDefault = isDefault
+        
+        self.createdAt = createdAt
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.name = try container.decodeIfPresent(String.self, forKey: .name) ?? ""
+        
+        self.description = try container.decodeIfPresent(String.self, forKey: .description) ?? ""
+        
+        self.color = try container.decodeIfPresent(String.self, forKey: .color) ?? ""
+        
+        self.icon = try container.decodeIfPresent(String.self, forKey: .icon) ?? ""
+        
+        self.taskCount = try container.decodeIfPresent(Int32.self, forKey: .taskCount) ?? 0
+        
+        self.isDefault = try container.decodeIfPresent(Bool.self, forKey: .isDefault) ?? false
+        
+        self.createdAt = try container.decodeIfPresent(Date.self, forKey: .createdAt)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case id
+        
+        case name
+        
+        case description
+        
+        case color
+        
+        case icon
+        
+        case taskCount = "task_count"
+        
+        case isDefault = "is_default"
+        
+        case createdAt = "created_at"
+        
+    }
+    
+}
+
+
+// MARK: - TaskComment
+
+public struct TaskComment: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let taskId: String
+    
+    
+    
+    
+    public let author: String
+    
+    
+    
+    
+    public let content: String
+    
+    
+    
+    
+    public let createdAt: Date?
+    
+    
+    
+    
+    public let isSystemComment: Bool
+    
+    
+    
+    
+    public init(id: String = "", taskId: String = "", author: String = "", content: String = "", createdAt: Date? = nil, isSystemComment: Bool = false) {
+        
+        self.id = id
+        
+        self.taskId = taskId
+        
+        self.author = author
+        
+        self.content = content
+        
+        self.createdAt = createdAt
+        
+        self.isSystemComment = isSystemComment
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.taskId = try container.decodeIfPresent(String.self, forKey: .taskId) ?? ""
+        
+        self.author = try container.decodeIfPresent(String.self, forKey: .author) ?? ""
+        
+        self.content = try container.decodeIfPresent(String.self, forKey: .content) ?? ""
+        
+        self.createdAt = try container.decodeIfPresent(Date.self, forKey: .createdAt)
+        
+        self.isSystemComment = try container.decodeIfPresent(Bool.self, forKey: .isSystemComment) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case id
+        
+        case taskId = "task_id"
+        
+        case author
+        
+        case content
+        
+        case createdAt = "created_at"
+        
+        case isSystemComment = "is_system_comment"
+        
+    }
+    
+}
+
+
+// MARK: - TaskAttachment
+
+public struct TaskAttachment: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let taskId: String
+    
+    
+    
+    
+    public let filename: String
+    
+    
+    
+    
+    public let url: String
+    
+    
+    
+    
+    public let sizeBytes: Int64
+    
+    
+    
+    
+    public let mimeType: String
+    
+    
+    
+    
+    public let uploadedAt: Date?
+    
+    
+    
+    
+    public init(id: String = "", taskId: String = "", filename: String = "", url: String = "", sizeBytes: Int64 = 0, mimeType: String = "", uploadedAt: Date? = nil) {
+        
+        self.id = id
+        
+        self.taskId = taskId
+        
+        self.filename = filename
+        
+        self.url = url
+        
+        self.sizeBytes = sizeBytes
+        
+        self.mimeType = mimeType
+        
+        self.uploadedAt = uploadedAt
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.taskId = try container.decodeIfPresent(String.self, forKey: .taskId) ?? ""
+        
+        self.filename = try container.decodeIfPresent(String.self, forKey: .filename) ?? ""
+        
+        self.url = try container.decodeIfPresent(String.self, forKey: .url) ?? ""
+        
+        self.sizeBytes = try container.decodeIfPresent(Int64.self, forKey: .sizeBytes) ?? 0
+        
+        self.mimeType = try container.decodeIfPresent(String.self, forKey: .mimeType) ?? ""
+        
+        self.uploadedAt = try container.decodeIfPresent(Date.self, forKey: .uploadedAt)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case id
+        
+        case taskId = "task_id"
+        
+        case filename
+        
+        case url
+        
+        case sizeBytes = "size_bytes"
+        
+        case mimeType = "mime_type"
+        
+        case uploadedAt = "uploaded_at"
+        
+    }
+    
+}
+
+
+// MARK: - CreateTaskRequest
+
+public struct CreateTaskRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let title: String
+    
+    
+    
+    
+    public let description: String
+    
+    
+    
+    
+    public let priority: TaskPriority
+    
+    
+    
+    
+    public let dueDate: Date?
+    
+    
+    
+    
+    public let tags: [String]
+    
+    
+    
+    
+    public let categoryId: String
+    
+    
+    
+    
+    public let assignedTo: String
+    
+    
+    
+    
+    public let estimatedHours: Int32
+    
+    
+    
+    
+    public init(title: String = "", description: String = "", priority: TaskPriority = .unspecified, dueDate: Date? = nil, tags: [String] = [], categoryId: String = "", assignedTo: String = "", estimatedHours: Int32 = 0) {
+        
+        self.title = title
+        
+        self.description = description
+        
+        self.priority = priority
+        
+        self.dueDate = dueDate
+        
+        self.tags = tags
+        
+        self.categoryId = categoryId
+        
+        self.assignedTo = assignedTo
+        
+        self.estimatedHours = estimatedHours
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.title = try container.decodeIfPresent(String.self, forKey: .title) ?? ""
+        
+        self.description = try container.decodeIfPresent(String.self, forKey: .description) ?? ""
+        
+        self.priority = try container.decodeIfPresent(TaskPriority.self, forKey: .priority) ?? .unspecified
+        
+        self.dueDate = try container.decodeIfPresent(Date.self, forKey: .dueDate)
+        
+        self.tags = try container.decodeIfPresent([String].self, forKey: .tags) ?? []
+        
+        self.categoryId = try container.decodeIfPresent(String.self, forKey: .categoryId) ?? ""
+        
+        self.assignedTo = try container.decodeIfPresent(String.self, forKey: .assignedTo) ?? ""
+        
+        self.estimatedHours = try container.decodeIfPresent(Int32.self, forKey: .estimatedHours) ?? 0
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case title
+        
+        case description
+        
+        case priority
+        
+        case dueDate = "due_date"
+        
+        case tags
+        
+        case categoryId = "category_id"
+        
+        case assignedTo = "assigned_to"
+        
+        case estimatedHours = "estimated_hours"
+        
+    }
+    
+}
+
+
+// MARK: - GetTasksRequest
+
+public struct GetTasksRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let completed: Bool?
+    
+    
+    
+    
+    public let priority: TaskPriority?
+    
+    
+    
+    
+    public let categoryId: String?
+    
+    
+    
+    
+    public let assignedTo: String?
+    
+    
+    
+    
+    public let status: TaskStatus?
+    
+    
+    
+    
+    public let limit: Int32
+    
+    
+    
+    
+    public let cursor: String
+    
+    
+    
+    
+    public let searchQuery: String
+    
+    
+    
+    
+    public let sortField: String
+    
+    
+    
+    
+    public let sortAscending: Bool
+    
+    
+    
+    
+    public let createdAfter: Date?
+    
+    
+    
+    
+    public let createdBefore: Date?
+    
+    
+    
+    
+    public let dueAfter: Date?
+    
+    
+    
+    
+    public let dueBefore: Date?
+    
+    
+    
+    
+    public init(completed: Bool? = nil, priority: TaskPriority? = nil, categoryId: String? = nil, assignedTo: String? = nil, status: TaskStatus? = nil, limit: Int32 = 0, cursor: String = "", searchQuery: String = "", sortField: String = "", sortAscending: Bool = false, createdAfter: Date? = nil, createdBefore: Date? = nil, dueAfter: Date? = nil, dueBefore: Date? = nil) {
+        
+        self.completed = completed
+        
+        self.priority = priority
+        
+        self.categoryId = categoryId
+        
+        self.assignedTo = assignedTo
+        
+        self.status = status
+        
+        self.limit = limit
+        
+        self.cursor = cursor
+        
+        self.searchQuery = searchQuery
+        
+        self.sortField = sortField
+        
+        self.sortAscending = sortAscending
+        
+        self.createdAfter = createdAfter
+        
+        self.createdBefore = createdBefore
+        
+        self.dueAfter = dueAfter
+        
+        self.dueBefore = dueBefore
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.completed = try container.decodeIfPresent(Bool.self, forKey: .completed)
+        
+        self.priority = try container.decodeIfPresent(TaskPriority.self, forKey: .priority)
+        
+        self.categoryId = try container.decodeIfPresent(String.self, forKey: .categoryId)
+        
+        self.assignedTo = try container.decodeIfPresent(String.self, forKey: .assignedTo)
+        
+        self.status = try container.decodeIfPresent(TaskStatus.self, forKey: .status)
+        
+        self.limit = try container.decodeIfPresent(Int32.self, forKey: .limit) ?? 0
+        
+        self.cursor = try container.decodeIfPresent(String.self, forKey: .cursor) ?? ""
+        
+        self.searchQuery = try container.decodeIfPresent(String.self, forKey: .searchQuery) ?? ""
+        
+        self.sortField = try container.decodeIfPresent(String.self, forKey: .sortField) ?? ""
+        
+        self.sortAscending = try container.decodeIfPresent(Bool.self, forKey: .sortAscending) ?? false
+        
+        self.createdAfter = try container.decodeIfPresent(Date.self, forKey: .createdAfter)
+        
+        self.createdBefore = try container.decodeIfPresent(Date.self, forKey: .createdBefore)
+        
+        self.dueAfter = try container.decodeIfPresent(Date.self, forKey: .dueAfter)
+        
+        self.dueBefore = try container.decodeIfPresent(Date.self, forKey: .dueBefore)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case completed
+        
+        case priority
+        
+        case categoryId = "category_id"
+        
+        case assignedTo = "assigned_to"
+        
+        case status
+        
+        case limit
+        
+        case cursor
+        
+        case searchQuery = "search_query"
+        
+        case sortField = "sort_field"
+        
+        case sortAscending = "sort_ascending"
+        
+        case createdAfter = "created_after"
+        
+        case createdBefore = "created_before"
+        
+        case dueAfter = "due_after"
+        
+        case dueBefore = "due_before"
+        
+    }
+    
+}
+
+
+// MARK: - GetTasksResponse
+
+public struct GetTasksResponse: Codable, Equatable, Hashable {
+    
+    
+    
+    public let tasks: [Task]
+    
+    
+    
+    
+    public let nextCursor: String
+    
+    
+    
+    
+    public let totalCount: Int32
+    
+    
+    
+    
+    public let hasMore: Bool
+    
+    
+    
+    
+    public let statistics: TaskStatistics?
+    
+    
+    
+    
+    public init(tasks: [Task] = [], nextCursor: String = "", totalCount: Int32 = 0, hasMore: Bool = false, statistics: TaskStatistics? = nil) {
+        
+        self.tasks = tasks
+        
+        self.nextCursor = nextCursor
+        
+        self.totalCount = totalCount
+        
+        self.hasMore = hasMore
+        
+        self.statistics = statistics
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.tasks = try container.decodeIfPresent([Task].self, forKey: .tasks) ?? []
+        
+        self.nextCursor = try container.decodeIfPresent(String.self, forKey: .nextCursor) ?? ""
+        
+        self.totalCount = try container.decodeIfPresent(Int32.self, forKey: .totalCount) ?? 0
+        
+        self.hasMore = try container.decodeIfPresent(Bool.self, forKey: .hasMore) ?? false
+        
+        self.statistics = try container.decodeIfPresent(TaskStatistics.self, forKey: .statistics)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case tasks
+        
+        case nextCursor = "next_cursor"
+        
+        case totalCount = "total_count"
+        
+        case hasMore = "has_more"
+        
+        case statistics
+        
+    }
+    
+}
+
+
+// MARK: - UpdateTaskRequest
+
+public struct UpdateTaskRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let taskId: String
+    
+    
+    
+    
+    public let title: String?
+    
+    
+    
+    
+    public let description: String?
+    
+    
+    
+    
+    public let isCompleted: Bool?
+    
+    
+    
+    
+    public let priority: TaskPriority?
+    
+    
+    
+    
+    public let status: TaskStatus?
+    
+    
+    
+    
+    public let dueDate: Date?
+    
+    
+    
+    
+    public let tags: [String]
+    
+    
+    
+    
+    public let categoryId: String?
+    
+    
+    
+    
+    public let assignedTo: String?
+    
+    
+    
+    
+    public let estimatedHours: Int32?
+    
+    
+    
+    
+    public let actualHours: Int32?
+    
+    
+    
+    
+    public init(taskId: String = "", title: String? = nil, description: String? = nil, isCompleted: Bool? = nil, priority: TaskPriority? = nil, status: TaskStatus? = nil, dueDate: Date? = nil, tags: [String] = [], categoryId: String? = nil, assignedTo: String? = nil, estimatedHours: Int32? = nil, actualHours: Int32? = nil) {
+        
+        self.taskId = taskId
+        
+        self.title = title
+        
+        self.description = description
+        
+        self.isCompleted = isCompleted
+        
+        self.priority = priority
+        
+        self.status = status
+        
+        self.dueDate = dueDate
+        
+        self.tags = tags
+        
+        self.categoryId = categoryId
+        
+        self.assignedTo = assignedTo
+        
+        self.estimatedHours = estimatedHours
+        
+        self.actualHours = actualHours
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.taskId = try container.decodeIfPresent(String.self, forKey: .taskId) ?? ""
+        
+        self.title = try container.decodeIfPresent(String.self, forKey: .title)
+        
+        self.description = try container.decodeIfPresent(String.self, forKey: .description)
+        
+        self.isCompleted = try container.decodeIfPresent(Bool.self, forKey: .isCompleted)
+        
+        self.priority = try container.decodeIfPresent(TaskPriority.self, forKey: .priority)
+        
+        self.status = try container.decodeIfPresent(TaskStatus.self, forKey: .status)
+        
+        self.dueDate = try container.decodeIfPresent(Date.self, forKey: .dueDate)
+        
+        self.tags = try container.decodeIfPresent([String].self, forKey: .tags) ?? []
+        
+        self.categoryId = try container.decodeIfPresent(String.self, forKey: .categoryId)
+        
+        self.assignedTo = try container.decodeIfPresent(String.self, forKey: .assignedTo)
+        
+        self.estimatedHours = try container.decodeIfPresent(Int32.self, forKey: .estimatedHours)
+        
+        self.actualHours = try container.decodeIfPresent(Int32.self, forKey: .actualHours)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case taskId = "task_id"
+        
+        case title
+        
+        case description
+        
+        case isCompleted = "is_completed"
+        
+        case priority
+        
+        case status
+        
+        case dueDate = "due_date"
+        
+        case tags
+        
+        case categoryId = "category_id"
+        
+        case assignedTo = "assigned_to"
+        
+        case estimatedHours = "estimated_hours"
+        
+        case actualHours = "actual_hours"
+        
+    }
+    
+}
+
+
+// MARK: - DeleteTaskRequest
+
+public struct DeleteTaskRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let taskId: String
+    
+    
+    
+    
+    public let softDelete: Bool
+    
+    
+    
+    
+    public init(taskId: String = "", softDelete: Bool = false) {
+        
+        self.taskId = taskId
+        
+        self.softDelete = softDelete
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.taskId = try container.decodeIfPresent(String.self, forKey: .taskId) ?? ""
+        
+        self.softDelete = try container.decodeIfPresent(Bool.self, forKey: .softDelete) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case taskId = "task_id"
+        
+        case softDelete = "soft_delete"
+        
+    }
+    
+}
+
+
+// MARK: - ToggleTaskCompletionRequest
+
+public struct ToggleTaskCompletionRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let taskId: String
+    
+    
+    
+    
+    public init(taskId: String = "") {
+        
+        self.taskId = taskId
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.taskId = try container.decodeIfPresent(String.self, forKey: .taskId) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case taskId = "task_id"
+        
+    }
+    
+}
+
+
+// MARK: - GetCategoriesRequest
+
+public struct GetCategoriesRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let searchQuery: String?
+    
+    
+    
+    
+    public let sortField: String
+    
+    
+    
+    
+    public let sortAscending: Bool
+    
+    
+    
+    
+    public let includeTaskCounts: Bool
+    
+    
+    
+    
+    public init(searchQuery: String? = nil, sortField: String = "", sortAscending: Bool = false, includeTaskCounts: Bool = false) {
+        
+        self.searchQuery = searchQuery
+        
+        self.sortField = sortField
+        
+        self.sortAscending = sortAscending
+        
+        self.includeTaskCounts = includeTaskCounts
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.searchQuery = try container.decodeIfPresent(String.self, forKey: .searchQuery)
+        
+        self.sortField = try container.decodeIfPresent(String.self, forKey: .sortField) ?? ""
+        
+        self.sortAscending = try container.decodeIfPresent(Bool.self, forKey: .sortAscending) ?? false
+        
+        self.includeTaskCounts = try container.decodeIfPresent(Bool.self, forKey: .includeTaskCounts) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case searchQuery = "search_query"
+        
+        case sortField = "sort_field"
+        
+        case sortAscending = "sort_ascending"
+        
+        case includeTaskCounts = "include_task_counts"
+        
+    }
+    
+}
+
+
+// MARK: - GetCategoriesResponse
+
+public struct GetCategoriesResponse: Codable, Equatable, Hashable {
+    
+    
+    
+    public let categories: [Category]
+    
+    
+    
+    
+    public let totalCount: Int32
+    
+    
+    
+    
+    public init(categories: [Category] = [], totalCount: Int32 = 0) {
+        
+        self.categories = categories
+        
+        self.totalCount = totalCount
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.categories = try container.decodeIfPresent([Category].self, forKey: .categories) ?? []
+        
+        self.totalCount = try container.decodeIfPresent(Int32.self, forKey: .totalCount) ?? 0
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case categories
+        
+        case totalCount = "total_count"
+        
+    }
+    
+}
+
+
+// MARK: - CreateCategoryRequest
+
+public struct CreateCategoryRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let name: String
+    
+    
+    
+    
+    public let description: String
+    
+    
+    
+    
+    public let color: String
+    
+    
+    
+    
+    public let icon: String
+    
+    
+    
+    
+    public let isDefault: Bool
+    
+    
+    
+    
+    public init(name: String = "", description: String = "", color: String = "", icon: String = "", isDefault: Bool = false) {
+        
+        self.name = name
+        
+        self.description = description
+        
+        self.color = color
+        
+        self.icon = icon
+        
+        self.isDefault = isDefault
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.name = try container.decodeIfPresent(String.self, forKey: .name) ?? ""
+        
+        self.description = try container.decodeIfPresent(String.self, forKey: .description) ?? ""
+        
+        self.color = try container.decodeIfPresent(String.self, forKey: .color) ?? ""
+        
+        self.icon = try container.decodeIfPresent(String.self, forKey: .icon) ?? ""
+        
+        self.isDefault = try container.decodeIfPresent(Bool.self, forKey: .isDefault) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case name
+        
+        case description
+        
+        case color
+        
+        case icon
+        
+        case isDefault = "is_default"
+        
+    }
+    
+}
+
+
+// MARK: - SearchTasksRequest
+
+public struct SearchTasksRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let query: String
+    
+    
+    
+    
+    public let limit: Int32
+    
+    
+    
+    
+    public let scope: SearchScope
+    
+    
+    
+    
+    public let priorityFilter: [TaskPriority]
+    
+    
+    
+    
+    public let categoryFilter: [String]
+    
+    
+    
+    
+    public init(query: String = "", limit: Int32 = 0, scope: SearchScope = .scopeUnspecified, priorityFilter: [TaskPriority] = [], categoryFilter: [String] = []) {
+        
+        self.query = query
+        
+        self.limit = limit
+        
+        self.scope = scope
+        
+        self.priorityFilter = priorityFilter
+        
+        self.categoryFilter = categoryFilter
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.query = try container.decodeIfPresent(String.self, forKey: .query) ?? ""
+        
+        self.limit = try container.decodeIfPresent(Int32.self, forKey: .limit) ?? 0
+        
+        self.scope = try container.decodeIfPresent(SearchScope.self, forKey: .scope) ?? .scopeUnspecified
+        
+        self.priorityFilter = try container.decodeIfPresent([TaskPriority].self, forKey: .priorityFilter) ?? []
+        
+        self.categoryFilter = try container.decodeIfPresent([String].self, forKey: .categoryFilter) ?? []
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case query
+        
+        case limit
+        
+        case scope
+        
+        case priorityFilter = "priority_filter"
+        
+        case categoryFilter = "category_filter"
+        
+    }
+    
+}
+
+
+// MARK: - SearchTasksResponse
+
+public struct SearchTasksResponse: Codable, Equatable, Hashable {
+    
+    
+    
+    public let tasks: [Task]
+    
+    
+    
+    
+    public let totalMatches: Int32
+    
+    
+    
+    
+    public let highlights: [SearchHighlight]
+    
+    
+    
+    
+    public let statistics: SearchStatistics?
+    
+    
+    
+    
+    public init(tasks: [Task] = [], totalMatches: Int32 = 0, highlights: [SearchHighlight] = [], statistics: SearchStatistics? = nil) {
+        
+        self.tasks = tasks
+        
+        self.totalMatches = totalMatches
+        
+        self.highlights = highlights
+        
+        self.statistics = statistics
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.tasks = try container.decodeIfPresent([Task].self, forKey: .tasks) ?? []
+        
+        self.totalMatches = try container.decodeIfPresent(Int32.self, forKey: .totalMatches) ?? 0
+        
+        self.highlights = try container.decodeIfPresent([SearchHighlight].self, forKey: .highlights) ?? []
+        
+        self.statistics = try container.decodeIfPresent(SearchStatistics.self, forKey: .statistics)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case tasks
+        
+        case totalMatches = "total_matches"
+        
+        case highlights
+        
+        case statistics
+        
+    }
+    
+}
+
+
+// MARK: - TaskStatistics
+
+public struct TaskStatistics: Codable, Equatable, Hashable {
+    
+    
+    
+    public let totalTasks: Int32
+    
+    
+    
+    
+    public let completedTasks: Int32
+    
+    
+    
+    
+    public let pendingTasks: Int32
+    
+    
+    
+    
+    public let overdueTasks: Int32
+    
+    
+    
+    
+    public let completionRate: Double
+    
+    
+    
+    
+    public let totalEstimatedHours: Int32
+    
+    
+    
+    
+    public let totalActualHours: Int32
+    
+    
+    
+    
+    public init(totalTasks: Int32 = 0, completedTasks: Int32 = 0, pendingTasks: Int32 = 0, overdueTasks: Int32 = 0, completionRate: Double = 0.0, totalEstimatedHours: Int32 = 0, totalActualHours: Int32 = 0) {
+        
+        self.totalTasks = totalTasks
+        
+        self.completedTasks = completedTasks
+        
+        self.pendingTasks = pendingTasks
+        
+        self.overdueTasks = overdueTasks
+        
+        self.completionRate = completionRate
+        
+        self.totalEstimatedHours = totalEstimatedHours
+        
+        self.totalActualHours = totalActualHours
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.totalTasks = try container.decodeIfPresent(Int32.self, forKey: .totalTasks) ?? 0
+        
+        self.completedTasks = try container.decodeIfPresent(Int32.self, forKey: .completedTasks) ?? 0
+        
+        self.pendingTasks = try container.decodeIfPresent(Int32.self, forKey: .pendingTasks) ?? 0
+        
+        self.overdueTasks = try container.decodeIfPresent(Int32.self, forKey: .overdueTasks) ?? 0
+        
+        self.completionRate = try container.decodeIfPresent(Double.self, forKey: .completionRate) ?? 0.0
+        
+        self.totalEstimatedHours = try container.decodeIfPresent(Int32.self, forKey: .totalEstimatedHours) ?? 0
+        
+        self.totalActualHours = try container.decodeIfPresent(Int32.self, forKey: .totalActualHours) ?? 0
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case totalTasks = "total_tasks"
+        
+        case completedTasks = "completed_tasks"
+        
+        case pendingTasks = "pending_tasks"
+        
+        case overdueTasks = "overdue_tasks"
+        
+        case completionRate = "completion_rate"
+        
+        case totalEstimatedHours = "total_estimated_hours"
+        
+        case totalActualHours = "total_actual_hours"
+        
+    }
+    
+}
+
+
+// MARK: - SearchHighlight
+
+public struct SearchHighlight: Codable, Equatable, Hashable {
+    
+    
+    
+    public let field: String
+    
+    
+    
+    
+    public let highlightedText: String
+    
+    
+    
+    
+    public let startOffset: Int32
+    
+    
+    
+    
+    public let endOffset: Int32
+    
+    
+    
+    
+    public init(field: String = "", highlightedText: String = "", startOffset: Int32 = 0, endOffset: Int32 = 0) {
+        
+        self.field = field
+        
+        self.highlightedText = highlightedText
+        
+        self.startOffset = startOffset
+        
+        self.endOffset = endOffset
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.field = try container.decodeIfPresent(String.self, forKey: .field) ?? ""
+        
+        self.highlightedText = try container.decodeIfPresent(String.self, forKey: .highlightedText) ?? ""
+        
+        self.startOffset = try container.decodeIfPresent(Int32.self, forKey: .startOffset) ?? 0
+        
+        self.endOffset = try container.decodeIfPresent(Int32.self, forKey: .endOffset) ?? 0
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case field
+        
+        case highlightedText = "highlighted_text"
+        
+        case startOffset = "start_offset"
+        
+        case endOffset = "end_offset"
+        
+    }
+    
+}
+
+
+// MARK: - SearchStatistics
+
+public struct SearchStatistics: Codable, Equatable, Hashable {
+    
+    
+    
+    public let totalSearched: Int32
+    
+    
+    
+    
+    public let matchesFound: Int32
+    
+    
+    
+    
+    public let searchTimeMs: Double
+    
+    
+    
+    
+    public init(totalSearched: Int32 = 0, matchesFound: Int32 = 0, searchTimeMs: Double = 0.0) {
+        
+        self.totalSearched = totalSearched
+        
+        self.matchesFound = matchesFound
+        
+        self.searchTimeMs = searchTimeMs
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.totalSearched = try container.decodeIfPresent(Int32.self, forKey: .totalSearched) ?? 0
+        
+        self.matchesFound = try container.decodeIfPresent(Int32.self, forKey: .matchesFound) ?? 0
+        
+        self.searchTimeMs = try container.decodeIfPresent(Double.self, forKey: .searchTimeMs) ?? 0.0
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case totalSearched = "total_searched"
+        
+        case matchesFound = "matches_found"
+        
+        case searchTimeMs = "search_time_ms"
+        
+    }
+    
+}
+
+
+
+
+// MARK: - TaskPriority
+
+public enum TaskPriority: String, Codable, CaseIterable, Sendable {
+    
+    
+    case unspecified = "TASK_PRIORITY_UNSPECIFIED"
+    
+    
+    case low = "TASK_PRIORITY_LOW"
+    
+    
+    case medium = "TASK_PRIORITY_MEDIUM"
+    
+    
+    case high = "TASK_PRIORITY_HIGH"
+    
+    
+    case urgent = "TASK_PRIORITY_URGENT"
+    
+}
+
+
+// MARK: - TaskStatus
+
+public enum TaskStatus: String, Codable, CaseIterable, Sendable {
+    
+    
+    case statusUnspecified = "TASK_STATUS_UNSPECIFIED"
+    
+    
+    case statusDraft = "TASK_STATUS_DRAFT"
+    
+    
+    case statusOpen = "TASK_STATUS_OPEN"
+    
+    
+    case statusInProgress = "TASK_STATUS_IN_PROGRESS"
+    
+    
+    case statusReview = "TASK_STATUS_REVIEW"
+    
+    
+    case statusCompleted = "TASK_STATUS_COMPLETED"
+    
+    
+    case statusCancelled = "TASK_STATUS_CANCELLED"
+    
+    
+    case statusArchived = "TASK_STATUS_ARCHIVED"
+    
+}
+
+
+// MARK: - SearchScope
+
+public enum SearchScope: String, Codable, CaseIterable, Sendable {
+    
+    
+    case scopeUnspecified = "SEARCH_SCOPE_UNSPECIFIED"
+    
+    
+    case scopeTitle = "SEARCH_SCOPE_TITLE"
+    
+    
+    case scopeDescription = "SEARCH_SCOPE_DESCRIPTION"
+    
+    
+    case scopeTags = "SEARCH_SCOPE_TAGS"
+    
+    
+    case scopeAll = "SEARCH_SCOPE_ALL"
+    
+}
+
+
+
+
+// MARK: - TaskService Protocol
+
+public protocol TaskServiceProtocol {
+    
+    
+    func createTask(_ request: CreateTaskRequest) async throws -> Task
+    
+    
+    func getTasks(_ request: GetTasksRequest) async throws -> GetTasksResponse
+    
+    
+    func updateTask(_ request: UpdateTaskRequest) async throws -> Task
+    
+    
+    func deleteTask(_ request: DeleteTaskRequest) async throws
+    
+    
+    func toggleTaskCompletion(_ request: ToggleTaskCompletionRequest) async throws -> Task
+    
+    
+    func getCategories(_ request: GetCategoriesRequest) async throws -> GetCategoriesResponse
+    
+    
+    func createCategory(_ request: CreateCategoryRequest) async throws -> Category
+    
+    
+    func searchTasks(_ request: SearchTasksRequest) async throws -> SearchTasksResponse
+    
+}
@@ -0,0 +1,542 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: UserAction.swift
+
+// Generated action enum for UserService
+import Foundation
+
+// MARK: - UserAction
+
+/// Actions that can be performed on UserService
+public enum UserAction: Sendable, Equatable {
+    
+    
+    case register(RegisterRequest)
+    
+    
+    case login(LoginRequest)
+    
+    
+    case logout(LogoutRequest)
+    
+    
+    case refreshToken(RefreshTokenRequest)
+    
+    
+    case getProfile(GetProfileRequest)
+    
+    
+    case updateProfile(UpdateProfileRequest)
+    
+    
+    case changePassword(ChangePasswordRequest)
+    
+    
+    case getUsers(GetUsersRequest)
+    
+    
+    case deleteUser(DeleteUserRequest)
+    
+    
+    case requestPasswordReset(PasswordResetRequest)
+    
+    
+    case resetPassword(ResetPasswordRequest)
+    
+    
+    case verifyEmail(VerifyEmailRequest)
+    
+    
+    case resendVerification(ResendVerificationRequest)
+    
+}
+
+// MARK: - Action Validation
+
+extension UserAction {
+    /// Validates the action and its associated request
+    public var isValid: Bool {
+        switch self {
+        
+        case .register(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .login(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .logout(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .refreshToken(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .getProfile(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .updateProfile(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .changePassword(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .getUsers(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .deleteUser(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .requestPasswordReset(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .resetPassword(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .verifyEmail(let request):
+            
+            return true // No specific validation rules
+            
+        
+        case .resendVerification(let request):
+            
+            return true // No specific validation rules
+            
+        
+        }
+    }
+    
+    /// Returns validation errors for the action
+    public var validationErrors: [String] {
+        switch self {
+        
+        case .register(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .login(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .logout(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .refreshToken(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .getProfile(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .updateProfile(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .changePassword(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .getUsers(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .deleteUser(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .requestPasswordReset(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .resetPassword(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .verifyEmail(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        case .resendVerification(let request):
+            var errors: [String] = []
+            
+            return errors
+        
+        }
+    }
+}
+
+// MARK: - Action Metadata
+
+extension UserAction {
+    /// Whether this action requires network access
+    public var requiresNetworkAccess: Bool {
+        switch self {
+        
+        case .register:
+            return true
+        
+        case .login:
+            return true
+        
+        case .logout:
+            return true
+        
+        case .refreshToken:
+            return true
+        
+        case .getProfile:
+            return true
+        
+        case .updateProfile:
+            return true
+        
+        case .changePassword:
+            return true
+        
+        case .getUsers:
+            return true
+        
+        case .deleteUser:
+            return true
+        
+        case .requestPasswordReset:
+            return true
+        
+        case .resetPassword:
+            return true
+        
+        case .verifyEmail:
+            return true
+        
+        case .resendVerification:
+            return true
+        
+        }
+    }
+    
+    /// Whether this action modifies state
+    public var modifiesState: Bool {
+        switch self {
+        
+        case .register:
+            return true
+        
+        case .login:
+            return true
+        
+        case .logout:
+            return true
+        
+        case .refreshToken:
+            return true
+        
+        case .getProfile:
+            return true
+        
+        case .updateProfile:
+            return true
+        
+        case .changePassword:
+            return true
+        
+        case .getUsers:
+            return true
+        
+        case .deleteUser:
+            return true
+        
+        case .requestPasswordReset:
+            return true
+        
+        case .resetPassword:
+            return true
+        
+        case .verifyEmail:
+            return true
+        
+        case .resendVerification:
+            return true
+        
+        }
+    }
+    
+    /// The name of this action for logging and analytics
+    public var actionName: String {
+        switch self {
+        
+        case .register:
+            return "register"
+        
+        case .login:
+            return "login"
+        
+        case .logout:
+            return "logout"
+        
+        case .refreshToken:
+            return "refreshToken"
+        
+        case .getProfile:
+            return "getProfile"
+        
+        case .updateProfile:
+            return "updateProfile"
+        
+        case .changePassword:
+            return "changePassword"
+        
+        case .getUsers:
+            return "getUsers"
+        
+        case .deleteUser:
+            return "deleteUser"
+        
+        case .requestPasswordReset:
+            return "requestPasswordReset"
+        
+        case .resetPassword:
+            return "resetPassword"
+        
+        case .verifyEmail:
+            return "verifyEmail"
+        
+        case .resendVerification:
+            return "resendVerification"
+        
+        }
+    }
+    
+    /// Whether this action should show loading state
+    public var showsLoadingState: Bool {
+        switch self {
+        
+        case .register:
+            return true
+        
+        case .login:
+            return true
+        
+        case .logout:
+            return true
+        
+        case .refreshToken:
+            return true
+        
+        case .getProfile:
+            return true
+        
+        case .updateProfile:
+            return true
+        
+        case .changePassword:
+            return true
+        
+        case .getUsers:
+            return true
+        
+        case .deleteUser:
+            return true
+        
+        case .requestPasswordReset:
+            return true
+        
+        case .resetPassword:
+            return true
+        
+        case .verifyEmail:
+            return true
+        
+        case .resendVerification:
+            return true
+        
+        }
+    }
+    
+    /// The state update strategy for this action
+    public var stateUpdateStrategy: StateUpdateStrategy {
+        switch self {
+        
+        case .register:
+            return .custom
+        
+        case .login:
+            return .custom
+        
+        case .logout:
+            return .custom
+        
+        case .refreshToken:
+            return .custom
+        
+        case .getProfile:
+            return .replace_all
+        
+        case .updateProfile:
+            return .update_by_id
+        
+        case .changePassword:
+            return .custom
+        
+        case .getUsers:
+            return .replace_all
+        
+        case .deleteUser:
+            return .remove_by_id
+        
+        case .requestPasswordReset:
+            return .custom
+        
+        case .resetPassword:
+            return .custom
+        
+        case .verifyEmail:
+            return .custom
+        
+        case .resendVerification:
+            return .custom
+        
+        }
+    }
+}
+
+// MARK: - State Update Strategy
+
+public enum StateUpdateStrategy: String, Sendable {
+    case append = "append"
+    case replaceAll = "replace_all"
+    case updateById = "update_by_id"
+    case removeById = "remove_by_id"
+    case custom = "custom"
+}
+
+// MARK: - Action Request Access
+
+extension UserAction {
+    /// The request associated with this action
+    public var request: Any {
+        switch self {
+        
+        case .register(let request):
+            return request
+        
+        case .login(let request):
+            return request
+        
+        case .logout(let request):
+            return request
+        
+        case .refreshToken(let request):
+            return request
+        
+        case .getProfile(let request):
+            return request
+        
+        case .updateProfile(let request):
+            return request
+        
+        case .changePassword(let request):
+            return request
+        
+        case .getUsers(let request):
+            return request
+        
+        case .deleteUser(let request):
+            return request
+        
+        case .requestPasswordReset(let request):
+            return request
+        
+        case .resetPassword(let request):
+            return request
+        
+        case .verifyEmail(let request):
+            return request
+        
+        case .resendVerification(let request):
+            return request
+        
+        }
+    }
+}
+
+// MARK: - CustomStringConvertible
+
+extension UserAction: CustomStringConvertible {
+    public var description: String {
+        switch self {
+        
+        case .register(let request):
+            return "UserAction.register(\(request))"
+        
+        case .login(let request):
+            return "UserAction.login(\(request))"
+        
+        case .logout(let request):
+            return "UserAction.logout(\(request))"
+        
+        case .refreshToken(let request):
+            return "UserAction.refreshToken(\(request))"
+        
+        case .getProfile(let request):
+            return "UserAction.getProfile(\(request))"
+        
+        case .updateProfile(let request):
+            return "UserAction.updateProfile(\(request))"
+        
+        case .changePassword(let request):
+            return "UserAction.changePassword(\(request))"
+        
+        case .getUsers(let request):
+            return "UserAction.getUsers(\(request))"
+        
+        case .deleteUser(let request):
+            return "UserAction.deleteUser(\(request))"
+        
+        case .requestPasswordReset(let request):
+            return "UserAction.requestPasswordReset(\(request))"
+        
+        case .resetPassword(let request):
+            return "UserAction.resetPassword(\(request))"
+        
+        case .verifyEmail(let request):
+            return "UserAction.verifyEmail(\(request))"
+        
+        case .resendVerification(let request):
+            return "UserAction.resendVerification(\(request))"
+        
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,307 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: UserClient.swift
+
+// Generated Swift client for UserService
+import Foundation
+import AxiomCore
+import AxiomArchitecture
+
+
+
+@globalActor
+public actor UserClient: AxiomObservableClient<UserState, UserAction> {
+    public typealias StateType = UserState
+    public typealias ActionType = UserAction
+    
+    // MARK: - Private Properties
+    private var _state: UserState
+    private let apiClient: UserServiceProtocol
+    private var streamContinuations: [UUID: AsyncStream<UserState>.Continuation] = [:]
+    
+    // MARK: - Initialization
+    
+    /// Initialize the client with an API client implementation
+    /// - Parameters:
+    ///   - apiClient: The API client that implements UserServiceProtocol
+    ///   - initialState: The initial state (defaults to empty state)
+    public init(apiClient: UserServiceProtocol, initialState: UserState = UserState()) {
+        self._state = initialState
+        self.apiClient = apiClient
+    }
+    
+    // MARK: - AxiomClient Protocol Implementation
+    
+    public var stateStream: AsyncStream<UserState> {
+        AsyncStream { [weak self] continuation in
+            let id = UUID()
+            Task { [weak self] in
+                await self?.addContinuation(continuation, id: id)
+                
+                // Emit current state immediately
+                if let currentState = await self?._state {
+                    continuation.yield(currentState)
+                }
+                
+                // Handle stream termination
+                continuation.onTermination = { [weak self, id] _ in
+                    Task { await self?.removeContinuation(id: id) }
+                }
+            }
+        }
+    }
+    
+    private func addContinuation(_ continuation: AsyncStream<UserState>.Continuation, id: UUID) {
+        streamContinuations[id] = continuation
+    }
+    
+    private func removeContinuation(id: UUID) {
+        streamContinuations.removeValue(forKey: id)
+    }
+    
+    public func getCurrentState() async -> UserState {
+        return _state
+    }
+    
+    public func rollbackToState(_ state: UserState) async {
+        let oldState = _state
+        _state = state
+        
+        // Notify observers
+        for (_, continuation) in streamContinuations {
+            continuation.yield(state)
+        }
+        
+        await stateDidUpdate(from: oldState, to: state)
+    }
+    
+    /// Process an action and update the state accordingly
+    /// - Parameter action: The action to process
+    /// - Throws: Any error that occurs during processing
+    public func process(_ action: UserAction) async throws {
+        // Validate action
+        guard action.isValid else {
+            let errors = action.validationErrors
+            throw AxiomError.clientError(.invalidAction("Action validation failed: \(errors.joined(separator: ", "))"))
+        }
+        
+        // Get old state for lifecycle hooks
+        let oldState = _state
+        
+        // Process the action
+        let newState = try await processAction(action, currentState: _state)
+        
+        // Only update if state actually changed
+        guard newState != oldState else { return }
+        
+        // Call lifecycle hooks
+        await stateWillUpdate(from: oldState, to: newState)
+        
+        // Update state
+        _state = newState
+        
+        // Notify observers
+        for (_, continuation) in streamContinuations {
+            continuation.yield(newState)
+        }
+        
+        // Call lifecycle hooks
+        await stateDidUpdate(from: oldState, to: newState)
+    }
+    
+    // MARK: - Action Processing
+    
+    private func processAction(_ action: UserAction, currentState: UserState) async throws -> UserState {
+        switch action {
+        
+        case .register(let request):
+            
+            
+            let result = try await apiClient.register(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .login(let request):
+            
+            
+            let result = try await apiClient.login(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .logout(let request):
+            
+            
+            let result = try await apiClient.logout(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .refreshToken(let request):
+            
+            
+            let result = try await apiClient.refreshToken(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .getProfile(let request):
+            
+            
+            let result = try await apiClient.getProfile(request)
+            
+            
+            
+            
+            return currentState.withProfile(result.profile)
+            
+            
+        
+        
+        case .updateProfile(let request):
+            
+            
+            let result = try await apiClient.updateProfile(request)
+            
+            
+            
+            
+            return currentState.updatingUser(result)
+            
+            
+        
+        
+        case .changePassword(let request):
+            
+            
+            let result = try await apiClient.changePassword(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .getUsers(let request):
+            
+            
+            let result = try await apiClient.getUsers(request)
+            
+            
+            
+            
+            return currentState.withUsers(result.users)
+            
+            
+        
+        
+        case .deleteUser(let request):
+            
+            
+            let result = try await apiClient.deleteUser(request)
+            
+            
+            
+            
+            return currentState.removingUser(withId: request.id)
+            
+            
+        
+        
+        case .requestPasswordReset(let request):
+            
+            
+            let result = try await apiClient.requestPasswordReset(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .resetPassword(let request):
+            
+            
+            let result = try await apiClient.resetPassword(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .verifyEmail(let request):
+            
+            
+            let result = try await apiClient.verifyEmail(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        case .resendVerification(let request):
+            
+            
+            let result = try await apiClient.resendVerification(request)
+            
+            
+            
+            return currentState
+            
+        
+        
+        }
+    }
+    
+    // MARK: - Lifecycle Hooks
+    
+    public func stateWillUpdate(from old: UserState, to new: UserState) async {
+        // Override in subclasses if needed
+    }
+    
+    public func stateDidUpdate(from old: UserState, to new: UserState) async {
+        // Override in subclasses if needed
+    }
+    
+    // MARK: - Public Query Methods
+    
+    /// Get current statistics
+    public func getStatistics() async -> UserState.Statistics {
+        return _state.statistics
+    }
+    
+    /// Get performance metrics
+    public func getPerformanceMetrics() async -> UserClientMetrics {
+        UserClientMetrics(
+            lastActionTime: Date(),
+            stateStreamSubscribers: streamContinuations.count
+        )
+    }
+}
+
+// MARK: - Performance Metrics
+
+/// Performance metrics for the UserClient
+public struct UserClientMetrics: Sendable, Equatable {
+    public let lastActionTime: Date
+    public let stateStreamSubscribers: Int
+    
+    public init(lastActionTime: Date, stateStreamSubscribers: Int) {
+        self.lastActionTime = lastActionTime
+        self.stateStreamSubscribers = stateStreamSubscribers
+    }
+}
\ No newline at end of file
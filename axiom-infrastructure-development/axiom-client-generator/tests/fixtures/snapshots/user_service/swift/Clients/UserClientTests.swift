@@ -0,0 +1,475 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: UserClientTests.swift
+
+// Generated tests for UserClient
+import XCTest
+@testable import YourPackage
+
+final class UserClientTests: XCTestCase {
+    var client: UserClient!
+    var mockApiClient: MockUserServiceClient!
+    
+    override func setUp() {
+        super.setUp()
+        mockApiClient = MockUserServiceClient()
+        client = UserClient(apiClient: mockApiClient)
+    }
+    
+    override func tearDown() {
+        client = nil
+        mockApiClient = nil
+        super.tearDown()
+    }
+    
+    
+    func testRegister() async throws {
+        // Given
+        let request = RegisterRequest(/* initialize with test data */)
+        
+        let expectedResult = AuthResponse(/* initialize with test data */)
+        mockApiClient.registerResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.register(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testLogin() async throws {
+        // Given
+        let request = LoginRequest(/* initialize with test data */)
+        
+        let expectedResult = AuthResponse(/* initialize with test data */)
+        mockApiClient.loginResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.login(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testLogout() async throws {
+        // Given
+        let request = LogoutRequest(/* initialize with test data */)
+        
+        
+        // When
+        
+        try await client.process(.logout(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testRefreshToken() async throws {
+        // Given
+        let request = RefreshTokenRequest(/* initialize with test data */)
+        
+        let expectedResult = AuthResponse(/* initialize with test data */)
+        mockApiClient.refreshTokenResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.refreshToken(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testGetProfile() async throws {
+        // Given
+        let request = GetProfileRequest(/* initialize with test data */)
+        
+        let expectedResult = User(/* initialize with test data */)
+        mockApiClient.getProfileResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.getProfile(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertEqual(state.profile, [expectedResult])
+        
+    }
+    
+    
+    func testUpdateProfile() async throws {
+        // Given
+        let request = UpdateProfileRequest(/* initialize with test data */)
+        
+        let expectedResult = User(/* initialize with test data */)
+        mockApiClient.updateProfileResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.updateProfile(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testChangePassword() async throws {
+        // Given
+        let request = ChangePasswordRequest(/* initialize with test data */)
+        
+        
+        // When
+        
+        try await client.process(.changePassword(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testGetUsers() async throws {
+        // Given
+        let request = GetUsersRequest(/* initialize with test data */)
+        
+        let expectedResult = GetUsersResponse(/* initialize with test data */)
+        mockApiClient.getUsersResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.getUsers(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+        XCTAssertEqual(state.users, [expectedResult])
+        
+    }
+    
+    
+    func testDeleteUser() async throws {
+        // Given
+        let request = DeleteUserRequest(/* initialize with test data */)
+        
+        
+        // When
+        
+        try await client.process(.deleteUser(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testRequestPasswordReset() async throws {
+        // Given
+        let request = PasswordResetRequest(/* initialize with test data */)
+        
+        
+        // When
+        
+        try await client.process(.requestPasswordReset(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testResetPassword() async throws {
+        // Given
+        let request = ResetPasswordRequest(/* initialize with test data */)
+        
+        
+        // When
+        
+        try await client.process(.resetPassword(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testVerifyEmail() async throws {
+        // Given
+        let request = VerifyEmailRequest(/* initialize with test data */)
+        
+        let expectedResult = User(/* initialize with test data */)
+        mockApiClient.verifyEmailResult = expectedResult
+        
+        
+        // When
+        
+        try await client.process(.verifyEmail(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+    func testResendVerification() async throws {
+        // Given
+        let request = ResendVerificationRequest(/* initialize with test data */)
+        
+        
+        // When
+        
+        try await client.process(.resendVerification(request))
+        
+        
+        // Then
+        let state = await client.state
+        XCTAssertFalse(state.isLoading)
+        XCTAssertNil(state.lastError)
+        
+    }
+    
+    
+}
+
+// Mock API client for testing
+class MockUserServiceClient {
+    
+    
+    var registerResult: AuthResponse!
+    
+    var registerError: Error?
+    
+    
+    
+    var loginResult: AuthResponse!
+    
+    var loginError: Error?
+    
+    
+    
+    var logoutError: Error?
+    
+    
+    
+    var refreshTokenResult: AuthResponse!
+    
+    var refreshTokenError: Error?
+    
+    
+    
+    var getProfileResult: User!
+    
+    var getProfileError: Error?
+    
+    
+    
+    var updateProfileResult: User!
+    
+    var updateProfileError: Error?
+    
+    
+    
+    var changePasswordError: Error?
+    
+    
+    
+    var getUsersResult: GetUsersResponse!
+    
+    var getUsersError: Error?
+    
+    
+    
+    var deleteUserError: Error?
+    
+    
+    
+    var requestPasswordResetError: Error?
+    
+    
+    
+    var resetPasswordError: Error?
+    
+    
+    
+    var verifyEmailResult: User!
+    
+    var verifyEmailError: Error?
+    
+    
+    
+    var resendVerificationError: Error?
+    
+    
+    
+    
+    func register(_ request: RegisterRequest) async throws -> AuthResponse {
+        if let error = registerError {
+            throw error
+        }
+        
+        return registerResult
+        
+    }
+    
+    
+    func login(_ request: LoginRequest) async throws -> AuthResponse {
+        if let error = loginError {
+            throw error
+        }
+        
+        return loginResult
+        
+    }
+    
+    
+    func logout(_ request: LogoutRequest) async throws {
+        if let error = logoutError {
+            throw error
+        }
+        
+    }
+    
+    
+    func refreshToken(_ request: RefreshTokenRequest) async throws -> AuthResponse {
+        if let error = refreshTokenError {
+            throw error
+        }
+        
+        return refreshTokenResult
+        
+    }
+    
+    
+    func getProfile(_ request: GetProfileRequest) async throws -> User {
+        if let error = getProfileError {
+            throw error
+        }
+        
+        return getProfileResult
+        
+    }
+    
+    
+    func updateProfile(_ request: UpdateProfileRequest) async throws -> User {
+        if let error = updateProfileError {
+            throw error
+        }
+        
+        return updateProfileResult
+        
+    }
+    
+    
+    func changePassword(_ request: ChangePasswordRequest) async throws {
+        if let error = changePasswordError {
+            throw error
+        }
+        
+    }
+    
+    
+    func getUsers(_ request: GetUsersRequest) async throws -> GetUsersResponse {
+        if let error = getUsersError {
+            throw error
+        }
+        
+        return getUsersResult
+        
+    }
+    
+    
+    func deleteUser(_ request: DeleteUserRequest) async throws {
+        if let error = deleteUserError {
+            throw error
+        }
+        
+    }
+    
+    
+    func requestPasswordReset(_ request: PasswordResetRequest) async throws {
+        if let error = requestPasswordResetError {
+            throw error
+        }
+        
+    }
+    
+    
+    func resetPassword(_ request: ResetPasswordRequest) async throws {
+        if let error = resetPasswordError {
+            throw error
+        }
+        
+    }
+    
+    
+    func verifyEmail(_ request: VerifyEmailRequest) async throws -> User {
+        if let error = verifyEmailError {
+            throw error
+        }
+        
+        return verifyEmailResult
+        
+    }
+    
+    
+    func resendVerification(_ request: ResendVerificationRequest) async throws {
+        if let error = resendVerificationError {
+            throw error
+        }
+        
+    }
+    
+    
+}
\ No newline at end of file
@@ -0,0 +1,479 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: UserState.swift
+
+// Generated state for UserService
+import Foundation
+import AxiomCore
+
+// MARK: - UserState
+
+/// State container for UserService operations
+public struct UserState: AxiomState {
+    
+    // MARK: - Collections
+    
+    /// Collection of Auth entities
+    public let users: [Auth]
+    
+    /// Collection of User entities
+    public let profile: [User]
+    
+    
+    // MARK: - Loading State
+    /// Indicates if an operation is currently in progress
+    public let isLoading: Bool
+    
+    /// The last error that occurred, if any
+    public let error: Error?
+    
+    /// Last time the state was updated
+    public let lastUpdated: Date
+    
+    // MARK: - Pagination
+    
+    
+    // MARK: - Additional Properties
+    
+    
+    // MARK: - Initialization
+    
+    public init(
+        
+        users: [Auth] = [],
+        
+        profile: [User] = [],
+        
+        isLoading: Bool = false,
+        error: Error? = nil,
+        lastUpdated: Date = Date()
+    ) {
+        
+        self.users = users
+        
+        self.profile = profile
+        
+        self.isLoading = isLoading
+        self.error = error
+        self.lastUpdated = lastUpdated
+        
+        
+    }
+    
+    // MARK: - Immutable Update Methods
+    
+    
+    /// Add a auth to the collection
+    public func addingAuth(_ item: Auth) -> UserState {
+        UserState(
+            
+            users: users + [item],
+            
+            profile: profile,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire users collection
+    public func withAuths(_ newAuths: [Auth]) -> UserState {
+        UserState(
+            
+            users: newAuths,
+            
+            profile: profile,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a auth in the collection
+    public func updatingAuth(_ updatedAuth: Auth) -> UserState {
+        let updatedAuths = users.map { item in
+            item.id == updatedAuth.id ? updatedAuth : item
+        }
+        return UserState(
+            
+            users: updatedAuths,
+            
+            profile: profile,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a auth from the collection by ID
+    public func removingAuth(withId id: String) -> UserState {
+        UserState(
+            
+            users: users.filter { $0.id != id },
+            
+            profile: profile,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    /// Add a user to the collection
+    public func addingUser(_ item: User) -> UserState {
+        UserState(
+            
+            users: users,
+            
+            profile: profile + [item],
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Replace the entire profile collection
+    public func withUsers(_ newUsers: [User]) -> UserState {
+        UserState(
+            
+            users: users,
+            
+            profile: newUsers,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Update a user in the collection
+    public func updatingUser(_ updatedUser: User) -> UserState {
+        let updatedUsers = profile.map { item in
+            item.id == updatedUser.id ? updatedUser : item
+        }
+        return UserState(
+            
+            users: users,
+            
+            profile: updatedUsers,
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    /// Remove a user from the collection by ID
+    public func removingUser(withId id: String) -> UserState {
+        UserState(
+            
+            users: users,
+            
+            profile: profile.filter { $0.id != id },
+            
+            isLoading: false,
+            error: nil,
+            lastUpdated: Date()
+        )
+    }
+    
+    
+    
+    /// Update the loading state
+    public func withLoading(_ loading: Bool) -> UserState {
+        UserState(
+            
+            users: users,
+            
+            profile: profile,
+            
+            isLoading: loading,
+            error: error,
+            lastUpdated: lastUpdated
+        )
+    }
+    
+    /// Update the error state
+    public func withError(_ error: Error?) -> UserState {
+        UserState(
+            
+            users: users,
+            
+            profile: profile,
+            
+            isLoading: false,
+            error: error,
+            lastUpdated: Date()
+        )
+    }
+    
+    // MARK: - Computed Properties
+    
+    
+    /// Number of users in the collection
+    public var usersCount: Int {
+        users.count
+    }
+    
+    /// Returns true if users collection is empty
+    public var isAuthEmpty: Bool {
+        users.isEmpty
+    }
+    
+    /// Find a Auth by ID
+    public func auth(withId id: String) -> Auth? {
+        users.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Number of profile in the collection
+    public var profileCount: Int {
+        profile.count
+    }
+    
+    /// Returns true if profile collection is empty
+    public var isUserEmpty: Bool {
+        profile.isEmpty
+    }
+    
+    /// Find a User by ID
+    public func user(withId id: String) -> User? {
+        profile.first { $0.id == id }
+    }
+    
+    
+    
+    
+    
+    
+    
+    
+    
+    /// Returns true if any operation is currently loading
+    public var hasActiveOperation: Bool {
+        isLoading
+    }
+    
+    /// Returns true if there was an error in the last operation
+    public var hasError: Bool {
+        error != nil
+    }
+    
+    /// Statistics for this state
+    public var statistics: Statistics {
+        Statistics(
+            
+            totalAuths: users.count,
+            
+            usersCacheUtilization: Double(users.count) / Double(1000),
+            
+            
+            totalUsers: profile.count,
+            
+            profileCacheUtilization: Double(profile.count) / Double(1000),
+            
+            
+            lastUpdated: lastUpdated,
+            hasActiveOperations: isLoading,
+            hasErrors: hasError
+        )
+    }
+    
+    /// Performance metrics for large state objects
+    public var performanceMetrics: PerformanceMetrics {
+        let totalItems = users.count + profile.count
+        let memoryFootprint = totalItems * MemoryLayout<AnyObject>.stride
+        
+        return PerformanceMetrics(
+            totalItems: totalItems,
+            estimatedMemoryFootprint: memoryFootprint,
+            collectionsCount: 2,
+            isOptimizedForLargeDatasets: totalItems > 1000
+        )
+    }
+    
+    /// State validation based on Axiom field constraints
+    public var validationResults: StateValidationResults {
+        var issues: [ValidationIssue] = []
+        
+        
+        // Validate users collection
+        
+        if users.count > 1000 {
+            issues.append(.collectionSizeExceeded("users", current: users.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in users
+        for (index, item) in users.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("users[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        // Validate profile collection
+        
+        if profile.count > 1000 {
+            issues.append(.collectionSizeExceeded("profile", current: profile.count, max: 1000))
+        }
+        
+        
+        // Validate individual items in profile
+        for (index, item) in profile.enumerated() {
+            // Add validation based on field constraints
+            if item.id.isEmpty {
+                issues.append(.invalidFieldValue("profile[\(index)].id", reason: "ID cannot be empty"))
+            }
+        }
+        
+        
+        return StateValidationResults(
+            isValid: issues.isEmpty,
+            issues: issues,
+            validatedAt: Date()
+        )
+    }
+}
+
+// MARK: - UserState + Equatable & Hashable
+
+extension UserState {
+    public static func == (lhs: UserState, rhs: UserState) -> Bool {
+        return lhs.users == rhs.users &&
+               lhs.profile == rhs.profile &&
+               lhs.isLoading == rhs.isLoading &&
+               lhs.hasError == rhs.hasError
+    }
+    
+    public func hash(into hasher: inout Hasher) {
+        
+        hasher.combine(users)
+        
+        hasher.combine(profile)
+        
+        hasher.combine(isLoading)
+        hasher.combine(hasError)
+        
+        
+    }
+}
+
+// MARK: - Supporting Types
+
+extension UserState {
+    /// Enhanced statistics for state monitoring
+    public struct Statistics: Sendable, Equatable {
+        
+        public let totalAuths: Int
+        
+        public let usersCacheUtilization: Double
+        
+        
+        public let totalUsers: Int
+        
+        public let profileCacheUtilization: Double
+        
+        
+        public let lastUpdated: Date
+        public let hasActiveOperations: Bool
+        public let hasErrors: Bool
+        
+        
+        public init(
+            
+            totalAuths: Int,
+            
+            usersCacheUtilization: Double,
+            
+            
+            totalUsers: Int,
+            
+            profileCacheUtilization: Double,
+            
+            
+            lastUpdated: Date,
+            hasActiveOperations: Bool,
+            hasErrors: Bool
+        ) {
+            
+            self.totalAuths = totalAuths
+            
+            self.usersCacheUtilization = usersCacheUtilization
+            
+            
+            self.totalUsers = totalUsers
+            
+            self.profileCacheUtilization = profileCacheUtilization
+            
+            
+            self.lastUpdated = lastUpdated
+            self.hasActiveOperations = hasActiveOperations
+            self.hasErrors = hasErrors
+            
+        }
+    }
+    
+    
+    
+    /// Performance metrics for optimization
+    public struct PerformanceMetrics: Sendable, Equatable {
+        public let totalItems: Int
+        public let estimatedMemoryFootprint: Int
+        public let collectionsCount: Int
+        public let isOptimizedForLargeDatasets: Bool
+        
+        public init(
+            totalItems: Int,
+            estimatedMemoryFootprint: Int,
+            collectionsCount: Int,
+            isOptimizedForLargeDatasets: Bool
+        ) {
+            self.totalItems = totalItems
+            self.estimatedMemoryFootprint = estimatedMemoryFootprint
+            self.collectionsCount = collectionsCount
+            self.isOptimizedForLargeDatasets = isOptimizedForLargeDatasets
+        }
+    }
+    
+    /// State validation results based on Axiom constraints
+    public struct StateValidationResults: Sendable, Equatable {
+        public let isValid: Bool
+        public let issues: [ValidationIssue]
+        public let validatedAt: Date
+        
+        public init(isValid: Bool, issues: [ValidationIssue], validatedAt: Date) {
+            self.isValid = isValid
+            self.issues = issues
+            self.validatedAt = validatedAt
+        }
+    }
+    
+    /// Individual validation issues
+    public enum ValidationIssue: Sendable, Equatable {
+        case collectionSizeExceeded(String, current: Int, max: Int)
+        case invalidFieldValue(String, reason: String)
+        case constraintViolation(String, constraint: String)
+        
+        public var description: String {
+            switch self {
+            case .collectionSizeExceeded(let collection, let current, let max):
+                return "Collection '\(collection)' size \(current) exceeds maximum \(max)"
+            case .invalidFieldValue(let field, let reason):
+                return "Field '\(field)': \(reason)"
+            case .constraintViolation(let field, let constraint):
+                return "Field '\(field)' violates constraint: \(constraint)"
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,2009 @@
+// Copyright (c) Axiom Team. Licensed under the MIT License.
+// Source: UserService.swift
+
+// Generated Swift service contracts
+import Foundation
+
+import AxiomCore
+
+
+
+
+// MARK: - User
+
+public struct User: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let email: String
+    
+    
+    
+    
+    public let username: String
+    
+    
+    
+    
+    public let firstName: String
+    
+    
+    
+    
+    public let lastName: String
+    
+    
+    
+    
+    public let displayName: String
+    
+    
+    
+    
+    public let avatarUrl: String
+    
+    
+    
+    
+    public let role: UserRole
+    
+    
+    
+    
+    public let status: UserStatus
+    
+    
+    
+    
+    public let emailVerified: Bool
+    
+    
+    
+    
+    public let createdAt: Date?
+    
+    
+    
+    
+    public let updatedAt: Date?
+    
+    
+    
+    
+    public let lastLoginAt: Date?
+    
+    
+    
+    
+    public let preferences: UserPreferences?
+    
+    
+    
+    
+    public let profile: UserProfile?
+    
+    
+    
+    
+    public let permissions: [String]
+    
+    
+    
+    
+    public let devices: [UserDevice]
+    
+    
+    
+    
+    public init(id: String = "", email: String = "", username: String = "", firstName: String = "", lastName: String = "", displayName: String = "", avatarUrl: String = "", role: UserRole = .roleUnspecified, status: UserStatus = .unspecified, emailVerified: Bool = false, createdAt: Date? = nil, updatedAt: Date? = nil, lastLoginAt: Date? = nil, preferences: UserPreferences? = nil, profile: UserProfile? = nil, permissions: [String] = [], devices: [UserDevice] = []) {
+        
+        self.id = id
+        
+        self.email = email
+        
+        self.username = username
+        
+        self.firstName = firstName
+        
+        self.lastName = lastName
+        
+        self.displayName = displayName
+        
+        self.avatarUrl = avatarUrl
+        
+        self.role = role
+        
+        self.status = status
+        
+        self.emailVerified = emailVerified
+        
+        self.createdAt = createdAt
+        
+        self.updatedAt = updatedAt
+        
+        self.lastLoginAt = lastLoginAt
+        
+        self.preferences = preferences
+        
+        self.profile = profile
+        
+        self.permissions = permissions
+        
+        self.devices = devices
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.email = try container.decodeIfPresent(String.self, forKey: .email) ?? ""
+        
+        self.username = try container.decodeIfPresent(String.self, forKey: .username) ?? ""
+        
+        self.firstName = try container.decodeIfPresent(String.self, forKey: .firstName) ?? ""
+        
+        self.lastName = try container.decodeIfPresent(String.self, forKey: .lastName) ?? ""
+        
+        self.displayName = try container.decodeIfPresent(String.self, forKey: .displayName) ?? ""
+        
+        self.avatarUrl = try container.decodeIfPresent(String.self, forKey: .avatarUrl) ?? ""
+        
+        self.role = try container.decodeIfPresent(UserRole.self, forKey: .role) ?? .roleUnspecified
+        
+        self.status = try container.decodeIfPresent(UserStatus.self, forKey: .status) ?? .unspecified
+        
+        self.emailVerified = try container.decodeIfPresent(Bool.self, forKey: .emailVerified) ?? false
+        
+        self.createdAt = try container.decodeIfPresent(Date.self, forKey: .createdAt)
+        
+        self.updatedAt = try container.decodeIfPresent(Date.self, forKey: .updatedAt)
+        
+        self.lastLoginAt = try container.decodeIfPresent(Date.self, forKey: .lastLoginAt)
+        
+        self.preferences = try container.decodeIfPresent(UserPreferences.self, forKey: .preferences)
+        
+        self.profile = try container.decodeIfPresent(UserProfile.self, forKey: .profile)
+        
+        self.permissions = try container.decodeIfPresent([String].self, forKey: .permissions) ?? []
+        
+        self.devices = try container.decodeIfPresent([UserDevice].self, forKey: .devices) ?? []
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case id
+        
+        case email
+        
+        case username
+        
+        case firstName = "first_name"
+        
+        case lastName = "last_name"
+        
+        case displayName = "display_name"
+        
+        case avatarUrl = "avatar_url"
+        
+        case role
+        
+        case status
+        
+        case emailVerified = "email_verified"
+        
+        case createdAt = "created_at"
+        
+        case updatedAt = "updated_at"
+        
+        case lastLoginAt = "last_login_at"
+        
+        case preferences
+        
+        case profile
+        
+        case permissions
+        
+        case devices
+        
+    }
+    
+}
+
+
+// MARK: - UserSession
+
+public struct UserSession: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let userId: String
+    
+    
+    
+    
+    public let accessToken: String
+    
+    
+    
+    
+    public let refreshToken: String
+    
+    
+    
+    
+    public let expiresAt: Date?
+    
+    
+    
+    
+    public let createdAt: Date?
+    
+    
+    
+    
+    public let deviceInfo: String
+    
+    
+    
+    
+    public let ipAddress: String
+    
+    
+    
+    
+    public let userAgent: String
+    
+    
+    
+    
+    public let isActive: Bool
+    
+    
+    
+    
+    public init(id: String = "", userId: String = "", accessToken: String = "", refreshToken: String = "", expiresAt: Date? = nil, createdAt: Date? = nil, deviceInfo: String = "", ipAddress: String = "", userAgent: String = "", isActive: Bool = false) {
+        
+        self.id = id
+        
+        self.userId = userId
+        
+        self.accessToken = accessToken
+        
+        self.refreshToken = refreshToken
+        
+        self.expiresAt = expiresAt
+        
+        self.createdAt = createdAt
+        
+        self.deviceInfo = deviceInfo
+        
+        self.ipAddress = ipAddress
+        
+        self.userAgent = userAgent
+        
+        self.isActive = isActive
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.userId = try container.decodeIfPresent(String.self, forKey: .userId) ?? ""
+        
+        self.accessToken = try container.decodeIfPresent(String.self, forKey: .accessToken) ?? ""
+        
+        self.refreshToken = try container.decodeIfPresent(String.self, forKey: .refreshToken) ?? ""
+        
+        self.expiresAt = try container.decodeIfPresent(Date.self, forKey: .expiresAt)
+        
+        self.createdAt = try container.decodeIfPresent(Date.self, forKey: .createdAt)
+        
+        self.deviceInfo = try container.decodeIfPresent(String.self, forKey: .deviceInfo) ?? ""
+        
+        self.ipAddress = try container.decodeIfPresent(String.self, forKey: .ipAddress) ?? ""
+        
+        self.userAgent = try container.decodeIfPresent(String.self, forKey: .userAgent) ?? ""
+        
+        self.isActive = try container.decodeIfPresent(Bool.self, forKey: .isActive) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case id
+        
+        case userId = "user_id"
+        
+        case accessToken = "access_token"
+        
+        case refreshToken = "refresh_token"
+        
+        case expiresAt = "expires_at"
+        
+        case createdAt = "created_at"
+        
+        case deviceInfo = "device_info"
+        
+        case ipAddress = "ip_address"
+        
+        case userAgent = "user_agent"
+        
+        case isActive = "is_active"
+        
+    }
+    
+}
+
+
+// MARK: - UserPreferences
+
+public struct UserPreferences: Codable, Equatable, Hashable {
+    
+    
+    
+    public let language: String
+    
+    
+    
+    
+    public let timezone: String
+    
+    
+    
+    
+    public let notificationsEnabled: Bool
+    
+    
+    
+    
+    public let emailNotifications: Bool
+    
+    
+    
+    
+    public let pushNotifications: Bool
+    
+    
+    
+    
+    public let theme: String
+    
+    
+    
+    
+    public let disabledFeatures: [String]
+    
+    
+    
+    
+    public init(language: String = "", timezone: String = "", notificationsEnabled: Bool = false, emailNotifications: Bool = false, pushNotifications: Bool = false, theme: String = "", disabledFeatures: [String] = []) {
+        
+        self.language = language
+        
+        self.timezone = timezone
+        
+        self.notificationsEnabled = notificationsEnabled
+        
+        self.emailNotifications = emailNotifications
+        
+        self.pushNotifications = pushNotifications
+        
+        self.theme = theme
+        
+        self.disabledFeatures = disabledFeatures
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.language = try container.decodeIfPresent(String.self, forKey: .language) ?? ""
+        
+        self.timezone = try container.decodeIfPresent(String.self, forKey: .timezone) ?? ""
+        
+        self.notificationsEnabled = try container.decodeIfPresent(Bool.self, forKey: .notificationsEnabled) ?? false
+        
+        self.emailNotifications = try container.decodeIfPresent(Bool.self, forKey: .emailNotifications) ?? false
+        
+        self.pushNotifications = try container.decodeIfPresent(Bool.self, forKey: .pushNotifications) ?? false
+        
+        self.theme = try container.decodeIfPresent(String.self, forKey: .theme) ?? ""
+        
+        self.disabledFeatures = try container.decodeIfPresent([String].self, forKey: .disabledFeatures) ?? []
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case language
+        
+        case timezone
+        
+        case notificationsEnabled = "notifications_enabled"
+        
+        case emailNotifications = "email_notifications"
+        
+        case pushNotifications = "push_notifications"
+        
+        case theme
+        
+        case disabledFeatures = "disabled_features"
+        
+    }
+    
+}
+
+
+// MARK: - UserProfile
+
+public struct UserProfile: Codable, Equatable, Hashable {
+    
+    
+    
+    public let bio: String
+    
+    
+    
+    
+    public let phone: String
+    
+    
+    
+    
+    public let location: String
+    
+    
+    
+    
+    public let website: String
+    
+    
+    
+    
+    public let dateOfBirth: Date?
+    
+    
+    
+    
+    public let company: String
+    
+    
+    
+    
+    public let jobTitle: String
+    
+    
+    
+    
+    public let interests: [String]
+    
+    
+    
+    
+    public let socialLinks: [SocialLink]
+    
+    
+    
+    
+    public init(bio: String = "", phone: String = "", location: String = "", website: String = "", dateOfBirth: Date? = nil, company: String = "", jobTitle: String = "", interests: [String] = [], socialLinks: [SocialLink] = []) {
+        
+        self.bio = bio
+        
+        self.phone = phone
+        
+        self.location = location
+        
+        self.website = website
+        
+        self.dateOfBirth = dateOfBirth
+        
+        self.company = company
+        
+        self.jobTitle = jobTitle
+        
+        self.interests = interests
+        
+        self.socialLinks = socialLinks
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.bio = try container.decodeIfPresent(String.self, forKey: .bio) ?? ""
+        
+        self.phone = try container.decodeIfPresent(String.self, forKey: .phone) ?? ""
+        
+        self.location = try container.decodeIfPresent(String.self, forKey: .location) ?? ""
+        
+        self.website = try container.decodeIfPresent(String.self, forKey: .website) ?? ""
+        
+        self.dateOfBirth = try container.decodeIfPresent(Date.self, forKey: .dateOfBirth)
+        
+        self.company = try container.decodeIfPresent(String.self, forKey: .company) ?? ""
+        
+        self.jobTitle = try container.decodeIfPresent(String.self, forKey: .jobTitle) ?? ""
+        
+        self.interests = try container.decodeIfPresent([String].self, forKey: .interests) ?? []
+        
+        self.socialLinks = try container.decodeIfPresent([SocialLink].self, forKey: .socialLinks) ?? []
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case bio
+        
+        case phone
+        
+        case location
+        
+        case website
+        
+        case dateOfBirth = "date_of_birth"
+        
+        case company
+        
+        case jobTitle = "job_title"
+        
+        case interests
+        
+        case socialLinks = "social_links"
+        
+    }
+    
+}
+
+
+// MARK: - SocialLink
+
+public struct SocialLink: Codable, Equatable, Hashable {
+    
+    
+    
+    public let platform: String
+    
+    
+    
+    
+    public let url: String
+    
+    
+    
+    
+    public let isPublic: Bool
+    
+    
+    
+    
+    public init(platform: String = "", url: String = "", isPublic: Bool = false) {
+        
+        self.platform = platform
+        
+        self.url = url
+        
+        self.isPublic = isPublic
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.platform = try container.decodeIfPresent(String.self, forKey: .platform) ?? ""
+        
+        self.url = try container.decodeIfPresent(String.self, forKey: .url) ?? ""
+        
+        self.isPublic = try container.decodeIfPresent(Bool.self, forKey: .isPublic) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case platform
+        
+        case url
+        
+        case isPublic = "is_public"
+        
+    }
+    
+}
+
+
+// MARK: - UserDevice
+
+public struct UserDevice: Codable, Identifiable, Equatable, Hashable {
+    
+    
+    
+    public let id: String
+    
+    
+    
+    
+    public let name: String
+    
+    
+    
+    
+    public let type: String
+    
+    
+    
+    
+    public let os: String
+    
+    
+    
+    
+    public let browser: String
+    
+    
+    
+    
+    public let lastSeen: Date?
+    
+    
+    
+    
+    public let isTrusted: Bool
+    
+    
+    
+    
+    public let pushToken: String
+    
+    
+    
+    
+    public init(id: String = "", name: String = "", type: String = "", os: String = "", browser: String = "", lastSeen: Date? = nil, isTrusted: Bool = false, pushToken: String = "") {
+        
+        self.id = id
+        
+        self.name = name
+        
+        self.type = type
+        
+        self.os = os
+        
+        self.browser = browser
+        
+        self.lastSeen = lastSeen
+        
+        self.isTrusted = isTrusted
+        
+        self.pushToken = pushToken
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.id = try container.decodeIfPresent(String.self, forKey: .id) ?? ""
+        
+        self.name = try container.decodeIfPresent(String.self, forKey: .name) ?? ""
+        
+        self.type = try container.decodeIfPresent(String.self, forKey: .type) ?? ""
+        
+        self.os = try container.decodeIfPresent(String.self, forKey: .os) ?? ""
+        
+        self.browser = try container.decodeIfPresent(String.self, forKey: .browser) ?? ""
+        
+        self.lastSeen = try container.decodeIfPresent(Date.self, forKey: .lastSeen)
+        
+        self.isTrusted = try container.decodeIfPresent(Bool.self, forKey: .isTrusted) ?? false
+        
+        self.pushToken = try container.decodeIfPresent(String.self, forKey: .pushToken) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case id
+        
+        case name
+        
+        case type
+        
+        case os
+        
+        case browser
+        
+        case lastSeen = "last_seen"
+        
+        case isTrusted = "is_trusted"
+        
+        case pushToken = "push_token"
+        
+    }
+    
+}
+
+
+// MARK: - RegisterRequest
+
+public struct RegisterRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let email: String
+    
+    
+    
+    
+    public let password: String
+    
+    
+    
+    
+    public let username: String
+    
+    
+    
+    
+    public let firstName: String
+    
+    
+    
+    
+    public let lastName: String
+    
+    
+    
+    
+    public let acceptTerms: Bool
+    
+    
+    
+    
+    public let subscribeNewsletter: Bool
+    
+    
+    
+    
+    public let invitationCode: String
+    
+    
+    
+    
+    public init(email: String = "", password: String = "", username: String = "", firstName: String = "", lastName: String = "", acceptTerms: Bool = false, subscribeNewsletter: Bool = false, invitationCode: String = "") {
+        
+        self.email = email
+        
+        self.password = password
+        
+        self.username = username
+        
+        self.firstName = firstName
+        
+        self.lastName = lastName
+        
+        self.acceptTerms = acceptTerms
+        
+        self.subscribeNewsletter = subscribeNewsletter
+        
+        self.invitationCode = invitationCode
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.email = try container.decodeIfPresent(String.self, forKey: .email) ?? ""
+        
+        self.password = try container.decodeIfPresent(String.self, forKey: .password) ?? ""
+        
+        self.username = try container.decodeIfPresent(String.self, forKey: .username) ?? ""
+        
+        self.firstName = try container.decodeIfPresent(String.self, forKey: .firstName) ?? ""
+        
+        self.lastName = try container.decodeIfPresent(String.self, forKey: .lastName) ?? ""
+        
+        self.acceptTerms = try container.decodeIfPresent(Bool.self, forKey: .acceptTerms) ?? false
+        
+        self.subscribeNewsletter = try container.decodeIfPresent(Bool.self, forKey: .subscribeNewsletter) ?? false
+        
+        self.invitationCode = try container.decodeIfPresent(String.self, forKey: .invitationCode) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case email
+        
+        case password
+        
+        case username
+        
+        case firstName = "first_name"
+        
+        case lastName = "last_name"
+        
+        case acceptTerms = "accept_terms"
+        
+        case subscribeNewsletter = "subscribe_newsletter"
+        
+        case invitationCode = "invitation_code"
+        
+    }
+    
+}
+
+
+// MARK: - LoginRequest
+
+public struct LoginRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let email: String
+    
+    
+    
+    
+    public let password: String
+    
+    
+    
+    
+    public let rememberMe: Bool
+    
+    
+    
+    
+    public let deviceInfo: String
+    
+    
+    
+    
+    public let captchaToken: String
+    
+    
+    
+    
+    public init(email: String = "", password: String = "", rememberMe: Bool = false, deviceInfo: String = "", captchaToken: String = "") {
+        
+        self.email = email
+        
+        self.password = password
+        
+        self.rememberMe = rememberMe
+        
+        self.deviceInfo = deviceInfo
+        
+        self.captchaToken = captchaToken
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.email = try container.decodeIfPresent(String.self, forKey: .email) ?? ""
+        
+        self.password = try container.decodeIfPresent(String.self, forKey: .password) ?? ""
+        
+        self.rememberMe = try container.decodeIfPresent(Bool.self, forKey: .rememberMe) ?? false
+        
+        self.deviceInfo = try container.decodeIfPresent(String.self, forKey: .deviceInfo) ?? ""
+        
+        self.captchaToken = try container.decodeIfPresent(String.self, forKey: .captchaToken) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case email
+        
+        case password
+        
+        case rememberMe = "remember_me"
+        
+        case deviceInfo = "device_info"
+        
+        case captchaToken = "captcha_token"
+        
+    }
+    
+}
+
+
+// MARK: - LogoutRequest
+
+public struct LogoutRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let sessionToken: String
+    
+    
+    
+    
+    public let logoutAllDevices: Bool
+    
+    
+    
+    
+    public init(sessionToken: String = "", logoutAllDevices: Bool = false) {
+        
+        self.sessionToken = sessionToken
+        
+        self.logoutAllDevices = logoutAllDevices
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.sessionToken = try container.decodeIfPresent(String.self, forKey: .sessionToken) ?? ""
+        
+        self.logoutAllDevices = try container.decodeIfPresent(Bool.self, forKey: .logoutAllDevices) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case sessionToken = "session_token"
+        
+        case logoutAllDevices = "logout_all_devices"
+        
+    }
+    
+}
+
+
+// MARK: - RefreshTokenRequest
+
+public struct RefreshTokenRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let refreshToken: String
+    
+    
+    
+    
+    public let deviceInfo: String
+    
+    
+    
+    
+    public init(refreshToken: String = "", deviceInfo: String = "") {
+        
+        self.refreshToken = refreshToken
+        
+        self.deviceInfo = deviceInfo
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.refreshToken = try container.decodeIfPresent(String.self, forKey: .refreshToken) ?? ""
+        
+        self.deviceInfo = try container.decodeIfPresent(String.self, forKey: .deviceInfo) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case refreshToken = "refresh_token"
+        
+        case deviceInfo = "device_info"
+        
+    }
+    
+}
+
+
+// MARK: - AuthResponse
+
+public struct AuthResponse: Codable, Equatable, Hashable {
+    
+    
+    
+    public let user: User?
+    
+    
+    
+    
+    public let session: UserSession?
+    
+    
+    
+    
+    public let requiresVerification: Bool
+    
+    
+    
+    
+    public let requires2fa: Bool
+    
+    
+    
+    
+    public let requiredActions: [String]
+    
+    
+    
+    
+    public init(user: User? = nil, session: UserSession? = nil, requiresVerification: Bool = false, requires2fa: Bool = false, requiredActions: [String] = []) {
+        
+        self.user = user
+        
+        self.session = session
+        
+        self.requiresVerification = requiresVerification
+        
+        self.requires2fa = requires2fa
+        
+        self.requiredActions = requiredActions
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.user = try container.decodeIfPresent(User.self, forKey: .user)
+        
+        self.session = try container.decodeIfPresent(UserSession.self, forKey: .session)
+        
+        self.requiresVerification = try container.decodeIfPresent(Bool.self, forKey: .requiresVerification) ?? false
+        
+        self.requires2fa = try container.decodeIfPresent(Bool.self, forKey: .requires2fa) ?? false
+        
+        self.requiredActions = try container.decodeIfPresent([String].self, forKey: .requiredActions) ?? []
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case user
+        
+        case session
+        
+        case requiresVerification = "requires_verification"
+        
+        case requires2fa = "requires_2fa"
+        
+        case requiredActions = "required_actions"
+        
+    }
+    
+}
+
+
+// MARK: - GetProfileRequest
+
+public struct GetProfileRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let userId: String
+    
+    
+    
+    
+    public let includePreferences: Bool
+    
+    
+    
+    
+    public let includeDevices: Bool
+    
+    
+    
+    
+    public init(userId: String = "", includePreferences: Bool = false, includeDevices: Bool = false) {
+        
+        self.userId = userId
+        
+        self.includePreferences = includePreferences
+        
+        self.includeDevices = includeDevices
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.userId = try container.decodeIfPresent(String.self, forKey: .userId) ?? ""
+        
+        self.includePreferences = try container.decodeIfPresent(Bool.self, forKey: .includePreferences) ?? false
+        
+        self.includeDevices = try container.decodeIfPresent(Bool.self, forKey: .includeDevices) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case userId = "user_id"
+        
+        case includePreferences = "include_preferences"
+        
+        case includeDevices = "include_devices"
+        
+    }
+    
+}
+
+
+// MARK: - UpdateProfileRequest
+
+public struct UpdateProfileRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let userId: String
+    
+    
+    
+    
+    public let email: String?
+    
+    
+    
+    
+    public let username: String?
+    
+    
+    
+    
+    public let firstName: String?
+    
+    
+    
+    
+    public let lastName: String?
+    
+    
+    
+    
+    public let displayName: String?
+    
+    
+    
+    
+    public let avatarUrl: String?
+    
+    
+    
+    
+    public let preferences: UserPreferences?
+    
+    
+    
+    
+    public let profile: UserProfile?
+    
+    
+    
+    
+    public init(userId: String = "", email: String? = nil, username: String? = nil, firstName: String? = nil, lastName: String? = nil, displayName: String? = nil, avatarUrl: String? = nil, preferences: UserPreferences? = nil, profile: UserProfile? = nil) {
+        
+        self.userId = userId
+        
+        self.email = email
+        
+        self.username = username
+        
+        self.firstName = firstName
+        
+        self.lastName = lastName
+        
+        self.displayName = displayName
+        
+        self.avatarUrl = avatarUrl
+        
+        self.preferences = preferences
+        
+        self.profile = profile
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.userId = try container.decodeIfPresent(String.self, forKey: .userId) ?? ""
+        
+        self.email = try container.decodeIfPresent(String.self, forKey: .email)
+        
+        self.username = try container.decodeIfPresent(String.self, forKey: .username)
+        
+        self.firstName = try container.decodeIfPresent(String.self, forKey: .firstName)
+        
+        self.lastName = try container.decodeIfPresent(String.self, forKey: .lastName)
+        
+        self.displayName = try container.decodeIfPresent(String.self, forKey: .displayName)
+        
+        self.avatarUrl = try container.decodeIfPresent(String.self, forKey: .avatarUrl)
+        
+        self.preferences = try container.decodeIfPresent(UserPreferences.self, forKey: .preferences)
+        
+        self.profile = try container.decodeIfPresent(UserProfile.self, forKey: .profile)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case userId = "user_id"
+        
+        case email
+        
+        case username
+        
+        case firstName = "first_name"
+        
+        case lastName = "last_name"
+        
+        case displayName = "display_name"
+        
+        case avatarUrl = "avatar_url"
+        
+        case preferences
+        
+        case profile
+        
+    }
+    
+}
+
+
+// MARK: - ChangePasswordRequest
+
+public struct ChangePasswordRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let currentPassword: String
+    
+    
+    
+    
+    public let newPassword: String
+    
+    
+    
+    
+    public let logoutOtherSessions: Bool
+    
+    
+    
+    
+    public init(currentPassword: String = "", newPassword: String = "", logoutOtherSessions: Bool = false) {
+        
+        self.currentPassword = currentPassword
+        
+        self.newPassword = newPassword
+        
+        self.logoutOtherSessions = logoutOtherSessions
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.currentPassword = try container.decodeIfPresent(String.self, forKey: .currentPassword) ?? ""
+        
+        self.newPassword = try container.decodeIfPresent(String.self, forKey: .newPassword) ?? ""
+        
+        self.logoutOtherSessions = try container.decodeIfPresent(Bool.self, forKey: .logoutOtherSessions) ?? false
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case currentPassword = "current_password"
+        
+        case newPassword = "new_password"
+        
+        case logoutOtherSessions = "logout_other_sessions"
+        
+    }
+    
+}
+
+
+// MARK: - GetUsersRequest
+
+public struct GetUsersRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let limit: Int32
+    
+    
+    
+    
+    public let cursor: String
+    
+    
+    
+    
+    public let searchQuery: String
+    
+    
+    
+    
+    public let roleFilter: UserRole
+    
+    
+    
+    
+    public let statusFilter: UserStatus
+    
+    
+    
+    
+    public let emailVerifiedFilter: Bool
+    
+    
+    
+    
+    public let sortField: String
+    
+    
+    
+    
+    public let sortAscending: Bool
+    
+    
+    
+    
+    public let createdAfter: Date?
+    
+    
+    
+    
+    public let createdBefore: Date?
+    
+    
+    
+    
+    public init(limit: Int32 = 0, cursor: String = "", searchQuery: String = "", roleFilter: UserRole = .roleUnspecified, statusFilter: UserStatus = .unspecified, emailVerifiedFilter: Bool = false, sortField: String = "", sortAscending: Bool = false, createdAfter: Date? = nil, createdBefore: Date? = nil) {
+        
+        self.limit = limit
+        
+        self.cursor = cursor
+        
+        self.searchQuery = searchQuery
+        
+        self.roleFilter = roleFilter
+        
+        self.statusFilter = statusFilter
+        
+        self.emailVerifiedFilter = emailVerifiedFilter
+        
+        self.sortField = sortField
+        
+        self.sortAscending = sortAscending
+        
+        self.createdAfter = createdAfter
+        
+        self.createdBefore = createdBefore
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.limit = try container.decodeIfPresent(Int32.self, forKey: .limit) ?? 0
+        
+        self.cursor = try container.decodeIfPresent(String.self, forKey: .cursor) ?? ""
+        
+        self.searchQuery = try container.decodeIfPresent(String.self, forKey: .searchQuery) ?? ""
+        
+        self.roleFilter = try container.decodeIfPresent(UserRole.self, forKey: .roleFilter) ?? .roleUnspecified
+        
+        self.statusFilter = try container.decodeIfPresent(UserStatus.self, forKey: .statusFilter) ?? .unspecified
+        
+        self.emailVerifiedFilter = try container.decodeIfPresent(Bool.self, forKey: .emailVerifiedFilter) ?? false
+        
+        self.sortField = try container.decodeIfPresent(String.self, forKey: .sortField) ?? ""
+        
+        self.sortAscending = try container.decodeIfPresent(Bool.self, forKey: .sortAscending) ?? false
+        
+        self.createdAfter = try container.decodeIfPresent(Date.self, forKey: .createdAfter)
+        
+        self.createdBefore = try container.decodeIfPresent(Date.self, forKey: .createdBefore)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case limit
+        
+        case cursor
+        
+        case searchQuery = "search_query"
+        
+        case roleFilter = "role_filter"
+        
+        case statusFilter = "status_filter"
+        
+        case emailVerifiedFilter = "email_verified_filter"
+        
+        case sortField = "sort_field"
+        
+        case sortAscending = "sort_ascending"
+        
+        case createdAfter = "created_after"
+        
+        case createdBefore = "created_before"
+        
+    }
+    
+}
+
+
+// MARK: - GetUsersResponse
+
+public struct GetUsersResponse: Codable, Equatable, Hashable {
+    
+    
+    
+    public let users: [User]
+    
+    
+    
+    
+    public let nextCursor: String
+    
+    
+    
+    
+    public let totalCount: Int32
+    
+    
+    
+    
+    public let hasMore: Bool
+    
+    
+    
+    
+    public let statistics: UserStatistics?
+    
+    
+    
+    
+    public init(users: [User] = [], nextCursor: String = "", totalCount: Int32 = 0, hasMore: Bool = false, statistics: UserStatistics? = nil) {
+        
+        self.users = users
+        
+        self.nextCursor = nextCursor
+        
+        self.totalCount = totalCount
+        
+        self.hasMore = hasMore
+        
+        self.statistics = statistics
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.users = try container.decodeIfPresent([User].self, forKey: .users) ?? []
+        
+        self.nextCursor = try container.decodeIfPresent(String.self, forKey: .nextCursor) ?? ""
+        
+        self.totalCount = try container.decodeIfPresent(Int32.self, forKey: .totalCount) ?? 0
+        
+        self.hasMore = try container.decodeIfPresent(Bool.self, forKey: .hasMore) ?? false
+        
+        self.statistics = try container.decodeIfPresent(UserStatistics.self, forKey: .statistics)
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case users
+        
+        case nextCursor = "next_cursor"
+        
+        case totalCount = "total_count"
+        
+        case hasMore = "has_more"
+        
+        case statistics
+        
+    }
+    
+}
+
+
+// MARK: - DeleteUserRequest
+
+public struct DeleteUserRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let userId: String
+    
+    
+    
+    
+    public let hardDelete: Bool
+    
+    
+    
+    
+    public let reason: String
+    
+    
+    
+    
+    public init(userId: String = "", hardDelete: Bool = false, reason: String = "") {
+        
+        self.userId = userId
+        
+        self.hardDelete = hardDelete
+        
+        self.reason = reason
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.userId = try container.decodeIfPresent(String.self, forKey: .userId) ?? ""
+        
+        self.hardDelete = try container.decodeIfPresent(Bool.self, forKey: .hardDelete) ?? false
+        
+        self.reason = try container.decodeIfPresent(String.self, forKey: .reason) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case userId = "user_id"
+        
+        case hardDelete = "hard_delete"
+        
+        case reason
+        
+    }
+    
+}
+
+
+// MARK: - PasswordResetRequest
+
+public struct PasswordResetRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let email: String
+    
+    
+    
+    
+    public let captchaToken: String
+    
+    
+    
+    
+    public init(email: String = "", captchaToken: String = "") {
+        
+        self.email = email
+        
+        self.captchaToken = captchaToken
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.email = try container.decodeIfPresent(String.self, forKey: .email) ?? ""
+        
+        self.captchaToken = try container.decodeIfPresent(String.self, forKey: .captchaToken) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case email
+        
+        case captchaToken = "captcha_token"
+        
+    }
+    
+}
+
+
+// MARK: - ResetPasswordRequest
+
+public struct ResetPasswordRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let resetToken: String
+    
+    
+    
+    
+    public let newPassword: String
+    
+    
+    
+    
+    public init(resetToken: String = "", newPassword: String = "") {
+        
+        self.resetToken = resetToken
+        
+        self.newPassword = newPassword
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.resetToken = try container.decodeIfPresent(String.self, forKey: .resetToken) ?? ""
+        
+        self.newPassword = try container.decodeIfPresent(String.self, forKey: .newPassword) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case resetToken = "reset_token"
+        
+        case newPassword = "new_password"
+        
+    }
+    
+}
+
+
+// MARK: - VerifyEmailRequest
+
+public struct VerifyEmailRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let verificationToken: String
+    
+    
+    
+    
+    public init(verificationToken: String = "") {
+        
+        self.verificationToken = verificationToken
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.verificationToken = try container.decodeIfPresent(String.self, forKey: .verificationToken) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case verificationToken = "verification_token"
+        
+    }
+    
+}
+
+
+// MARK: - ResendVerificationRequest
+
+public struct ResendVerificationRequest: Codable, Equatable, Hashable {
+    
+    
+    
+    public let email: String
+    
+    
+    
+    
+    public let captchaToken: String
+    
+    
+    
+    
+    public init(email: String = "", captchaToken: String = "") {
+        
+        self.email = email
+        
+        self.captchaToken = captchaToken
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.email = try container.decodeIfPresent(String.self, forKey: .email) ?? ""
+        
+        self.captchaToken = try container.decodeIfPresent(String.self, forKey: .captchaToken) ?? ""
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case email
+        
+        case captchaToken = "captcha_token"
+        
+    }
+    
+}
+
+
+// MARK: - UserStatistics
+
+public struct UserStatistics: Codable, Equatable, Hashable {
+    
+    
+    
+    public let totalUsers: Int32
+    
+    
+    
+    
+    public let activeUsers: Int32
+    
+    
+    
+    
+    public let verifiedUsers: Int32
+    
+    
+    
+    
+    public let newUsersToday: Int32
+    
+    
+    
+    
+    public let newUsersThisWeek: Int32
+    
+    
+    
+    
+    public let newUsersThisMonth: Int32
+    
+    
+    
+    
+    public let verificationRate: Double
+    
+    
+    
+    
+    public init(totalUsers: Int32 = 0, activeUsers: Int32 = 0, verifiedUsers: Int32 = 0, newUsersToday: Int32 = 0, newUsersThisWeek: Int32 = 0, newUsersThisMonth: Int32 = 0, verificationRate: Double = 0.0) {
+        
+        self.totalUsers = totalUsers
+        
+        self.activeUsers = activeUsers
+        
+        self.verifiedUsers = verifiedUsers
+        
+        self.newUsersToday = newUsersToday
+        
+        self.newUsersThisWeek = newUsersThisWeek
+        
+        self.newUsersThisMonth = newUsersThisMonth
+        
+        self.verificationRate = verificationRate
+        
+    }
+    
+    /// Fields omitted from the payload decode to their proto3 defaults
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        
+        self.totalUsers = try container.decodeIfPresent(Int32.self, forKey: .totalUsers) ?? 0
+        
+        self.activeUsers = try container.decodeIfPresent(Int32.self, forKey: .activeUsers) ?? 0
+        
+        self.verifiedUsers = try container.decodeIfPresent(Int32.self, forKey: .verifiedUsers) ?? 0
+        
+        self.newUsersToday = try container.decodeIfPresent(Int32.self, forKey: .newUsersToday) ?? 0
+        
+        self.newUsersThisWeek = try container.decodeIfPresent(Int32.self, forKey: .newUsersThisWeek) ?? 0
+        
+        self.newUsersThisMonth = try container.decodeIfPresent(Int32.self, forKey: .newUsersThisMonth) ?? 0
+        
+        self.verificationRate = try container.decodeIfPresent(Double.self, forKey: .verificationRate) ?? 0.0
+        
+    }
+    
+    
+    
+    private enum CodingKeys: String, CodingKey {
+        
+        case totalUsers = "total_users"
+        
+        case activeUsers = "active_users"
+        
+        case verifiedUsers = "verified_users"
+        
+        case newUsersToday = "new_users_today"
+        
+        case newUsersThisWeek = "new_users_this_week"
+        
+        case newUsersThisMonth = "new_users_this_month"
+        
+        case verificationRate = "verification_rate"
+        
+    }
+    
+}
+
+
+
+
+// MARK: - UserRole
+
+public enum UserRole: String, Codable, CaseIterable, Sendable {
+    
+    
+    case roleUnspecified = "USER_ROLE_UNSPECIFIED"
+    
+    
+    case roleUser = "USER_ROLE_USER"
+    
+    
+    case roleModerator = "USER_ROLE_MODERATOR"
+    
+    
+    case roleAdmin = "USER_ROLE_ADMIN"
+    
+    
+    case roleSuperAdmin = "USER_ROLE_SUPER_ADMIN"
+    
+}
+
+
+// MARK: - UserStatus
+
+public enum UserStatus: String, Codable, CaseIterable, Sendable {
+    
+    
+    case unspecified = "USER_STATUS_UNSPECIFIED"
+    
+    
+    case active = "USER_STATUS_ACTIVE"
+    
+    
+    case inactive = "USER_STATUS_INACTIVE"
+    
+    
+    case suspended = "USER_STATUS_SUSPENDED"
+    
+    
+    case banned = "USER_STATUS_BANNED"
+    
+    
+    case pendingVerification = "USER_STATUS_PENDING_VERIFICATION"
+    
+}
+
+
+
+
+// MARK: - UserService Protocol
+
+public protocol UserServiceProtocol {
+    
+    
+    func register(_ request: RegisterRequest) async throws -> AuthResponse
+    
+    
+    func login(_ request: LoginRequest) async throws -> AuthResponse
+    
+    
+    func logout(_ request: LogoutRequest) async throws
+    
+    
+    func refreshToken(_ request: RefreshTokenRequest) async throws -> AuthResponse
+    
+    
+    func getProfile(_ request: GetProfileRequest) async throws -> User
+    
+    
+    func updateProfile(_ request: UpdateProfileRequest) async throws -> User
+    
+    
+    func changePassword(_ request: ChangePasswordRequest) async throws
+    
+    
+    func getUsers(_ request: GetUsersRequest) async throws -> GetUsersResponse
+    
+    
+    func deleteUser(_ request: DeleteUserRequest) async throws
+    
+    
+    func requestPasswordReset(_ request: PasswordResetRequest) async throws
+    
+    
+    func resetPassword(_ request: ResetPasswordRequest) async throws
+    
+    
+    func verifyEmail(_ request: VerifyEmailRequest) async throws -> User
+    
+    
+    func resendVerification(_ request: ResendVerificationRequest) async throws
+    
+}
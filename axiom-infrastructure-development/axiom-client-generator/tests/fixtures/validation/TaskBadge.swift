@@ -0,0 +1,11 @@
+import Foundation
+
+struct TaskBadge: Equatable {
+    let title: String
+    let count: Int
+
+    init(title: String, count: Int) {
+        self.title = title
+        self.count = count
+    }
+}
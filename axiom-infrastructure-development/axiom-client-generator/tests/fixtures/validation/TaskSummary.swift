@@ -0,0 +1,13 @@
+import Foundation
+
+public struct TaskSummary: Equatable {
+    public let id: String
+    public let title: String
+    public let isCompleted: Bool
+
+    public init(id: String, title: String, isCompleted: Bool) {
+        self.id = id
+        self.title = title
+        self.isCompleted = isCompleted
+    }
+}
@@ -95,6 +95,7 @@ pub fn create_task_service() -> Service {
                 swift_package_name: Some("TaskManager".to_string()),
                 collections: vec![],
                 supports_pagination: Some(true),
+                error_type: None,
             }),
             standard_options: HashMap::new(),
         },
@@ -114,6 +115,7 @@ pub fn create_task_message() -> Message {
                 field_type: "string".to_string(),
                 number: 1,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -123,6 +125,7 @@ pub fn create_task_message() -> Message {
                 field_type: "string".to_string(),
                 number: 2,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: create_field_options(true, 1, None),
                 documentation: None,
@@ -132,6 +135,7 @@ pub fn create_task_message() -> Message {
                 field_type: "string".to_string(),
                 number: 3,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -141,6 +145,7 @@ pub fn create_task_message() -> Message {
                 field_type: "Priority".to_string(),
                 number: 4,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -150,6 +155,7 @@ pub fn create_task_message() -> Message {
                 field_type: "bool".to_string(),
                 number: 5,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -159,6 +165,7 @@ pub fn create_task_message() -> Message {
                 field_type: "string".to_string(),
                 number: 6,
                 label: FieldLabel::Repeated,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -168,6 +175,7 @@ pub fn create_task_message() -> Message {
                 field_type: "google.protobuf.Timestamp".to_string(),
                 number: 7,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -224,6 +232,7 @@ pub fn create_user_service() -> Service {
                 swift_package_name: Some("UserManager".to_string()),
                 collections: vec![],
                 supports_pagination: Some(false),
+                error_type: None,
             }),
             standard_options: HashMap::new(),
         },
@@ -243,6 +252,7 @@ pub fn create_user_message() -> Message {
                 field_type: "string".to_string(),
                 number: 1,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -252,6 +262,7 @@ pub fn create_user_message() -> Message {
                 field_type: "string".to_string(),
                 number: 2,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: create_field_options(true, 1, Some("email".to_string())),
                 documentation: None,
@@ -261,6 +272,7 @@ pub fn create_user_message() -> Message {
                 field_type: "string".to_string(),
                 number: 3,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: create_field_options(true, 1, None),
                 documentation: None,
@@ -270,6 +282,7 @@ pub fn create_user_message() -> Message {
                 field_type: "string".to_string(),
                 number: 4,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: create_field_options(true, 1, None),
                 documentation: None,
@@ -305,6 +318,7 @@ pub fn create_complete_proto_schema() -> ProtoSchema {
                 field_type: "string".to_string(),
                 number: 1,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: create_field_options(true, 1, None),
                 documentation: None,
@@ -314,6 +328,7 @@ pub fn create_complete_proto_schema() -> ProtoSchema {
                 field_type: "string".to_string(),
                 number: 2,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -335,6 +350,7 @@ pub fn create_complete_proto_schema() -> ProtoSchema {
                 field_type: "Task".to_string(),
                 number: 1,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -419,6 +435,7 @@ fn create_method_options(swift_action_name: &str, state_update_strategy: StateUp
             id_field_name: Some("id".to_string()),
             supports_offline: Some(false),
             cache_strategy: CacheStrategy::Memory,
+            loading_timeout_ms: None,
         }),
         standard_options: HashMap::new(),
     }
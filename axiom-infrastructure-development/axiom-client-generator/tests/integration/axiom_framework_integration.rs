@@ -247,6 +247,7 @@ enum TaskStatus {
                     client_suffix: Some("Client".to_string()),
                     generate_tests: Some(true),
                     package_name: Some("ComprehensiveModule".to_string()),
+                    split_contracts_module: None,
                 }),
                 kotlin: None,
             }),
@@ -257,6 +258,11 @@ enum TaskStatus {
                 force_overwrite: Some(true),
                 include_documentation: Some(true),
                 style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
             }),
         };
 
@@ -376,6 +382,7 @@ enum TaskStatus {
                     client_suffix: Some("Client".to_string()),
                     generate_tests: Some(true),
                     package_name: Some("ComprehensiveModule".to_string()),
+                    split_contracts_module: None,
                 }),
                 kotlin: None,
             }),
@@ -386,6 +393,11 @@ enum TaskStatus {
                 force_overwrite: Some(true),
                 include_documentation: Some(true),
                 style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
             }),
         };
 
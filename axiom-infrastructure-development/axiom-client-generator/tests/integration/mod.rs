@@ -1,4 +1,5 @@
 // Integration tests module
 
 pub mod swift_generation;
-pub mod phase2_complete;
\ No newline at end of file
+pub mod phase2_complete;
+pub mod snapshots;
\ No newline at end of file
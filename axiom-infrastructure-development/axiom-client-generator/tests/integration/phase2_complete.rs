@@ -178,6 +178,7 @@ enum UserRole {
                     client_suffix: Some("Client".to_string()),
                     generate_tests: Some(true),
                     package_name: Some("ComprehensiveModule".to_string()),
+                    split_contracts_module: None,
                 }),
                 kotlin: None,
             }),
@@ -188,6 +189,11 @@ enum UserRole {
                 force_overwrite: Some(true),
                 include_documentation: Some(true),
                 style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
             }),
         };
 
@@ -306,6 +312,11 @@ enum UserRole {
                 force_overwrite: Some(true),
                 include_documentation: Some(false), // Test without docs
                 style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
             }),
         };
 
@@ -0,0 +1,265 @@
+//! End-to-end golden-file snapshot tests
+//!
+//! Generates each fixture into a temp directory and compares every produced
+//! file against the checked-in golden copy under `tests/fixtures/snapshots`.
+//! A missing golden file fails the test; run with `UPDATE_SNAPSHOTS=1` to
+//! record or regenerate the golden files after an intentional output change.
+
+#[cfg(test)]
+mod snapshot_tests {
+    use axiom_universal_client_generator::proto::analyzer::format_suggestions;
+    use axiom_universal_client_generator::{AxiomSwiftClientGenerator, GenerateRequest, GenerationOptions};
+    use pretty_assertions::assert_eq;
+    use regex::Regex;
+    use std::collections::BTreeSet;
+    use std::path::{Path, PathBuf};
+    use walkdir::WalkDir;
+
+    /// Placeholder substituted for any timestamp in generated output
+    const TIMESTAMP_PLACEHOLDER: &str = "<TIMESTAMP>";
+    /// Placeholder substituted for the temporary output directory
+    const OUTPUT_DIR_PLACEHOLDER: &str = "<OUTPUT_DIR>";
+    /// Deterministic license header so snapshots pin header injection too
+    const SNAPSHOT_FILE_HEADER: &str = "Copyright (c) Axiom Team. Licensed under the MIT License.\nSource: {file_name}";
+
+    /// A proto fixture paired with the directory holding its golden output
+    struct SnapshotFixture {
+        name: &'static str,
+        proto: &'static str,
+    }
+
+    const FIXTURES: &[SnapshotFixture] = &[
+        SnapshotFixture { name: "basic", proto: "tests/fixtures/proto/basic_service.proto" },
+        SnapshotFixture { name: "task_manager", proto: "examples/task_manager/proto/task_service.proto" },
+        SnapshotFixture { name: "user_service", proto: "examples/user_service/proto/user_service.proto" },
+        SnapshotFixture { name: "task_errors", proto: "tests/fixtures/proto/task_errors.proto" },
+    ];
+
+    fn manifest_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    fn snapshot_dir(fixture: &SnapshotFixture) -> PathBuf {
+        manifest_dir().join("tests/fixtures/snapshots").join(fixture.name)
+    }
+
+    fn update_mode() -> bool {
+        std::env::var("UPDATE_SNAPSHOTS").map(|v| v == "1").unwrap_or(false)
+    }
+
+    /// Strip content that legitimately changes between runs
+    fn normalize(content: &str, output_dir: &Path) -> String {
+        let timestamp = Regex::new(
+            r"\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}:\d{2}(\.\d+)?( ?UTC|Z|[+-]\d{2}:?\d{2})?",
+        )
+        .unwrap();
+
+        let content = content.replace(&output_dir.to_string_lossy().to_string(), OUTPUT_DIR_PLACEHOLDER);
+        let content = timestamp.replace_all(&content, TIMESTAMP_PLACEHOLDER);
+
+        // Normalize line endings so snapshots compare equal across platforms
+        content.replace("\r\n", "\n")
+    }
+
+    /// Collect every regular file under `root` as a sorted set of relative paths
+    fn collect_files(root: &Path) -> BTreeSet<PathBuf> {
+        if !root.exists() {
+            return BTreeSet::new();
+        }
+
+        WalkDir::new(root)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.path().strip_prefix(root).unwrap().to_path_buf())
+            .collect()
+    }
+
+    async fn generate_fixture(fixture: &SnapshotFixture, output_dir: &Path) {
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest {
+            proto_path: manifest_dir().join(fixture.proto).to_string_lossy().to_string(),
+            output_path: output_dir.to_string_lossy().to_string(),
+            target_languages: vec!["swift".to_string()],
+            services: None,
+            framework_config: None,
+            generation_options: Some(GenerationOptions {
+                generate_contracts: Some(true),
+                generate_clients: Some(true),
+                generate_tests: Some(true),
+                force_overwrite: Some(true),
+                include_documentation: Some(true),
+                style_guide: Some("axiom".to_string()),
+                file_header: Some(SNAPSHOT_FILE_HEADER.to_string()),
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
+            }),
+        };
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(
+            response.success,
+            "Generation failed for fixture '{}': {:?}",
+            fixture.name,
+            response.error
+        );
+    }
+
+    /// Generate a fixture and compare (or record) every produced file
+    async fn assert_fixture_snapshot(fixture: &SnapshotFixture) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir = temp_dir.path().join("generated");
+
+        generate_fixture(fixture, &output_dir).await;
+        assert_matches_snapshot(fixture, &output_dir);
+    }
+
+    /// Compare (or record) every file generated into `output_dir`
+    fn assert_matches_snapshot(fixture: &SnapshotFixture, output_dir: &Path) {
+        let golden_dir = snapshot_dir(fixture);
+        let generated = collect_files(output_dir);
+        assert!(!generated.is_empty(), "No files generated for fixture '{}'", fixture.name);
+
+        if update_mode() && golden_dir.exists() {
+            // Drop stale snapshots so removed outputs don't linger
+            for relative in collect_files(&golden_dir) {
+                std::fs::remove_file(golden_dir.join(relative)).unwrap();
+            }
+        }
+
+        let mut recorded = Vec::new();
+        for relative in &generated {
+            let actual = normalize(&std::fs::read_to_string(output_dir.join(relative)).unwrap(), output_dir);
+            let golden_path = golden_dir.join(relative);
+
+            if update_mode() {
+                std::fs::create_dir_all(golden_path.parent().unwrap()).unwrap();
+                std::fs::write(&golden_path, &actual).unwrap();
+                recorded.push(relative.display().to_string());
+                continue;
+            }
+
+            let expected = std::fs::read_to_string(&golden_path).unwrap_or_else(|_| {
+                panic!(
+                    "Missing snapshot {} for fixture '{}'. Run with UPDATE_SNAPSHOTS=1 to record it.",
+                    golden_path.display(),
+                    fixture.name
+                )
+            });
+
+            assert_eq!(
+                expected,
+                actual,
+                "Snapshot mismatch for {}/{} (run with UPDATE_SNAPSHOTS=1 to accept)",
+                fixture.name,
+                relative.display()
+            );
+        }
+
+        // Golden files with no generated counterpart mean output was dropped
+        let stale: Vec<String> = collect_files(&golden_dir)
+            .difference(&generated)
+            .map(|path| path.display().to_string())
+            .collect();
+        assert!(
+            stale.is_empty(),
+            "Fixture '{}' no longer generates snapshotted files: {:?} (run with UPDATE_SNAPSHOTS=1 to accept)",
+            fixture.name,
+            stale
+        );
+
+        if !recorded.is_empty() {
+            println!("📸 Recorded {} snapshot(s) for '{}': {:?}", recorded.len(), fixture.name, recorded);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_basic_snapshot() {
+        assert_fixture_snapshot(&FIXTURES[0]).await;
+    }
+
+    #[tokio::test]
+    async fn test_task_manager_snapshot() {
+        assert_fixture_snapshot(&FIXTURES[1]).await;
+    }
+
+    #[tokio::test]
+    async fn test_user_service_snapshot() {
+        assert_fixture_snapshot(&FIXTURES[2]).await;
+    }
+
+    #[tokio::test]
+    async fn test_task_errors_snapshot() {
+        assert_fixture_snapshot(&FIXTURES[3]).await;
+    }
+
+    #[tokio::test]
+    async fn test_regeneration_keeps_a_single_file_header() {
+        let fixture = &FIXTURES[0];
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_dir = temp_dir.path().join("generated");
+
+        // The second run overwrites files that already carry the header
+        generate_fixture(fixture, &output_dir).await;
+        generate_fixture(fixture, &output_dir).await;
+
+        for relative in collect_files(&output_dir) {
+            if relative.extension().and_then(|e| e.to_str()) != Some("swift") {
+                continue;
+            }
+            let content = std::fs::read_to_string(output_dir.join(&relative)).unwrap();
+            assert_eq!(
+                content.matches("// Copyright (c) Axiom Team.").count(),
+                1,
+                "{} should carry the license header exactly once",
+                relative.display()
+            );
+        }
+        assert_matches_snapshot(fixture, &output_dir);
+    }
+
+    #[tokio::test]
+    async fn test_basic_option_suggestions_snapshot() {
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+        let proto = manifest_dir().join(FIXTURES[0].proto);
+
+        let suggestions = generator.suggest_options(&proto.to_string_lossy()).await.unwrap();
+        let actual = format_suggestions(&suggestions);
+        let golden_path = manifest_dir().join("tests/fixtures/snapshots/suggestions/basic.txt");
+
+        if update_mode() {
+            std::fs::create_dir_all(golden_path.parent().unwrap()).unwrap();
+            std::fs::write(&golden_path, &actual).unwrap();
+            println!("📸 Recorded option suggestions snapshot: {}", golden_path.display());
+            return;
+        }
+
+        let expected = std::fs::read_to_string(&golden_path).unwrap_or_else(|_| {
+            panic!(
+                "Missing snapshot {}. Run with UPDATE_SNAPSHOTS=1 to record it.",
+                golden_path.display()
+            )
+        });
+        assert_eq!(
+            expected,
+            actual,
+            "Option suggestions changed for the basic fixture (run with UPDATE_SNAPSHOTS=1 to accept)"
+        );
+    }
+
+    #[test]
+    fn test_normalize_strips_volatile_content() {
+        let output_dir = Path::new("/tmp/axiom-output");
+        let content = "Generation time: 2024-03-01 12:34:56 UTC\r\nPath: /tmp/axiom-output/swift\r\nAt 2024-03-01T12:34:56.123Z\n";
+
+        let normalized = normalize(content, output_dir);
+
+        assert_eq!(
+            normalized,
+            "Generation time: <TIMESTAMP>\nPath: <OUTPUT_DIR>/swift\nAt <TIMESTAMP>\n"
+        );
+    }
+}
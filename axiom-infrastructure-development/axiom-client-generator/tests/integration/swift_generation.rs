@@ -90,6 +90,7 @@ enum TaskPriority {
                     client_suffix: Some("Client".to_string()),
                     generate_tests: Some(true),
                     package_name: Some("TaskModule".to_string()),
+                    split_contracts_module: None,
                 }),
                 kotlin: None,
             }),
@@ -100,6 +101,11 @@ enum TaskPriority {
                 force_overwrite: Some(true),
                 include_documentation: Some(true),
                 style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
             }),
         };
 
@@ -188,6 +194,11 @@ enum TaskPriority {
                 force_overwrite: Some(true),
                 include_documentation: Some(true),
                 style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
             }),
         };
 
@@ -237,6 +248,7 @@ enum TaskPriority {
                     client_suffix: Some("Client".to_string()),
                     generate_tests: Some(true),
                     package_name: Some("TaskModule".to_string()),
+                    split_contracts_module: None,
                 }),
                 kotlin: None,
             }),
@@ -247,6 +259,11 @@ enum TaskPriority {
                 force_overwrite: Some(true),
                 include_documentation: Some(true),
                 style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
             }),
         };
 
@@ -300,4 +317,415 @@ enum TaskPriority {
 
         println!("✅ Comprehensive Swift validation test passed");
     }
+
+    #[tokio::test]
+    async fn test_swift_generation_without_documentation() {
+        let (_temp_dir, proto_path, output_path) = setup_test_env().await;
+
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest {
+            proto_path: proto_path.to_string_lossy().to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            target_languages: vec!["swift".to_string()],
+            services: None,
+            framework_config: None,
+            generation_options: Some(axiom_universal_client_generator::GenerationOptions {
+                generate_contracts: Some(true),
+                generate_clients: Some(true),
+                generate_tests: Some(true),
+                force_overwrite: Some(true),
+                include_documentation: Some(false),
+                style_guide: Some("axiom".to_string()),
+                file_header: None,
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
+            }),
+        };
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        // No markdown documentation should be produced
+        assert!(
+            !output_path.join("swift/Documentation").exists(),
+            "Documentation directory generated despite include_documentation=false"
+        );
+
+        // Generated Swift sources should carry no doc comments or MARK sections
+        for file_path in &response.generated_files {
+            assert!(!file_path.ends_with(".md"), "Unexpected documentation file: {}", file_path);
+
+            if file_path.ends_with(".swift") {
+                let content = std::fs::read_to_string(file_path).unwrap();
+                assert!(!content.contains("///"), "Doc comment found in {}", file_path);
+                assert!(!content.contains("// MARK:"), "MARK comment found in {}", file_path);
+            }
+        }
+
+        println!("✅ Swift generation without documentation test passed");
+    }
+
+    #[tokio::test]
+    async fn test_file_header_injected_once_on_regeneration() {
+        let (_temp_dir, proto_path, output_path) = setup_test_env().await;
+
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest {
+            proto_path: proto_path.to_string_lossy().to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            target_languages: vec!["swift".to_string()],
+            services: None,
+            framework_config: None,
+            generation_options: Some(axiom_universal_client_generator::GenerationOptions {
+                force_overwrite: Some(true),
+                file_header: Some("Copyright (c) {year} Axiom Team (all rights reserved\nFile: {file_name}".to_string()),
+                generate_previews: None,
+                strict: None,
+                strict_categories: None,
+                max_memory_warn_mb: None,
+                ..Default::default()
+            }),
+        };
+
+        // Generate twice over the same output directory
+        generator.generate(request.clone()).await.unwrap();
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        let swift_files: Vec<&String> = response.generated_files.iter()
+            .filter(|f| f.ends_with(".swift"))
+            .collect();
+        assert!(!swift_files.is_empty(), "No Swift files generated");
+
+        for file_path in &swift_files {
+            let content = std::fs::read_to_string(file_path).unwrap();
+            let file_name = PathBuf::from(file_path).file_name().unwrap().to_string_lossy().to_string();
+
+            assert!(content.starts_with("// Copyright (c) "), "Header missing in {}", file_path);
+            assert!(content.contains(&format!("// File: {}", file_name)), "file_name not substituted in {}", file_path);
+            assert_eq!(content.matches("Axiom Team (all rights reserved").count(), 1, "Header duplicated in {}", file_path);
+
+            // Header precedes every import
+            let header_pos = content.find("// Copyright").unwrap();
+            if let Some(import_pos) = content.find("import ") {
+                assert!(header_pos < import_pos, "Header placed after imports in {}", file_path);
+            }
+        }
+
+        // The unbalanced parenthesis in the header must not trip the validator
+        let validation = SwiftValidator::new()
+            .validate_files(&response.generated_files)
+            .await
+            .unwrap();
+        assert!(
+            !validation.errors.iter().any(|e| e.contains("Unbalanced parentheses")),
+            "Header lines flagged by validator: {:?}",
+            validation.errors
+        );
+
+        println!("✅ File header injection test passed");
+    }
+
+    #[tokio::test]
+    async fn test_typed_service_errors_from_error_enum() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let proto_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/proto/task_errors.proto");
+        let output_path = temp_dir.path().join("generated");
+
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest {
+            proto_path: proto_path.to_string_lossy().to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            target_languages: vec!["swift".to_string()],
+            services: None,
+            framework_config: None,
+            generation_options: Some(axiom_universal_client_generator::GenerationOptions {
+                force_overwrite: Some(true),
+                ..Default::default()
+            }),
+        };
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        // TaskService + TaskError enum produce a typed TaskClientError
+        let error_file = response.generated_files.iter()
+            .find(|f| f.ends_with("TaskClientError.swift"))
+            .expect("TaskClientError.swift not generated");
+        let errors = std::fs::read_to_string(error_file).unwrap();
+
+        assert!(errors.contains("public enum TaskClientError: Error, Equatable, Sendable"));
+        assert!(errors.contains("case notFound(message: String, details: [String: String])"));
+        assert!(errors.contains("case permissionDenied(message: String, details: [String: String])"));
+        assert!(errors.contains("case taskLocked(message: String, details: [String: String])"));
+        assert!(!errors.contains("unspecified"), "UNSPECIFIED value must not become a case");
+
+        // Reason details take precedence, then canonical gRPC status codes
+        assert!(errors.contains(r#"case "TASK_ERROR_NOT_FOUND"?, "NOT_FOUND"?:"#));
+        assert!(errors.contains("case 5:\n            self = .notFound"));
+        assert!(errors.contains("case 7:\n            self = .permissionDenied"));
+        assert!(errors.contains("self = .unknown(statusCode: statusCode, message: message, details: details)"));
+
+        // The shared file still exists and declares the status protocol
+        let shared = response.generated_files.iter()
+            .find(|f| f.ends_with("AxiomErrors.swift"))
+            .expect("AxiomErrors.swift not generated");
+        assert!(std::fs::read_to_string(shared).unwrap().contains("public protocol AxiomStatusError: Error"));
+
+        // The client maps thrown statuses before storing them in state
+        let client_file = response.generated_files.iter()
+            .find(|f| f.ends_with("TaskClient.swift"))
+            .expect("TaskClient.swift not generated");
+        let client = std::fs::read_to_string(client_file).unwrap();
+        assert!(client.contains("catch let status as AxiomStatusError"));
+        assert!(client.contains("let error = TaskClientError(status: status)"));
+        assert!(client.contains("await storeError(error, from: oldState)"));
+
+        // Generated tests cover the mapping
+        let test_file = response.generated_files.iter()
+            .find(|f| f.ends_with("TaskClientTests.swift"))
+            .expect("TaskClientTests.swift not generated");
+        let tests = std::fs::read_to_string(test_file).unwrap();
+        assert!(tests.contains("func testTaskClientErrorMapsReasonNotFound()"));
+        assert!(tests.contains("func testTaskClientErrorMapsStatusNotFound()"));
+        assert!(tests.contains("func testTaskClientErrorFallsBackToUnknown()"));
+
+        println!("✅ Typed service error generation test passed");
+    }
+
+    #[tokio::test]
+    async fn test_state_previews_generation() {
+        let (_temp_dir, proto_path, output_path) = setup_test_env().await;
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest::builder(
+            proto_path.to_string_lossy(),
+            output_path.to_string_lossy(),
+        )
+        .force_overwrite(true)
+        .generate_previews(true)
+        .build()
+        .unwrap();
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        let previews_file = response.generated_files.iter()
+            .find(|f| f.ends_with("TaskState+Previews.swift"))
+            .expect("TaskState+Previews.swift not generated");
+        let previews = std::fs::read_to_string(previews_file).unwrap();
+
+        // Debug-only, with the four canonical states
+        assert!(previews.contains("#if DEBUG"));
+        for state in ["previewEmpty", "previewLoading", "previewPopulated", "previewError"] {
+            assert!(
+                previews.contains(&format!("public static var {}: TaskState", state)),
+                "missing {} in previews",
+                state
+            );
+        }
+        assert!(previews.contains("TaskState(isLoading: true)"));
+        assert!(previews.contains("TaskState(error: TaskStatePreviewError())"));
+
+        // Populated uses three distinct Task fixtures built from the message fields
+        for index in 1..=3 {
+            assert!(previews.contains(&format!(r#"Task(id: "task-{}""#, index)));
+        }
+        assert!(!previews.contains(r#"Task(id: "task-4""#));
+        assert!(previews.contains("isCompleted: "));
+        assert!(previews.contains("createdAt: Date(timeIntervalSince1970: "));
+
+        let validator = SwiftValidator::new();
+        let validation = validator.validate_files(std::slice::from_ref(previews_file)).await.unwrap();
+        assert!(validation.is_valid(), "Validation failed: {:?}", validation.errors);
+
+        let compilation = validator.compile_check(&response.generated_files).await.unwrap();
+        if compilation.successful_compilations > 0 || compilation.compilation_errors.is_empty() {
+            assert!(compilation.is_successful(), "Compilation failed: {:?}", compilation.compilation_errors);
+        }
+
+        // Previews are opt-in
+        let default_output = output_path.with_file_name("generated_default");
+        let request = GenerateRequest::builder(
+            proto_path.to_string_lossy(),
+            default_output.to_string_lossy(),
+        )
+        .force_overwrite(true)
+        .build()
+        .unwrap();
+        let response = generator.generate(request).await.unwrap();
+        assert!(!response.generated_files.iter().any(|f| f.ends_with("+Previews.swift")));
+    }
+
+    fn generated_file<'a>(files: &'a [String], suffix: &str) -> &'a String {
+        files.iter()
+            .find(|f| f.ends_with(suffix))
+            .unwrap_or_else(|| panic!("{} not generated", suffix))
+    }
+
+    fn import_count(content: &str, module: &str) -> usize {
+        let line = format!("import {}", module);
+        content.lines().filter(|l| l.trim() == line).count()
+    }
+
+    #[tokio::test]
+    async fn test_package_name_imported_once_by_clients_only() {
+        let (_temp_dir, proto_path, output_path) = setup_test_env().await;
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest::builder(
+            proto_path.to_string_lossy(),
+            output_path.to_string_lossy(),
+        )
+        .swift_package_name("TaskContracts")
+        .force_overwrite(true)
+        .generate_previews(true)
+        .build()
+        .unwrap();
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        for suffix in ["Clients/TaskClient.swift", "Clients/TaskAction.swift", "Clients/TaskState.swift", "Clients/TaskState+Previews.swift"] {
+            let content = std::fs::read_to_string(generated_file(&response.generated_files, suffix)).unwrap();
+            assert_eq!(import_count(&content, "TaskContracts"), 1, "{} should import TaskContracts once", suffix);
+            assert!(!content.contains("import null"));
+        }
+
+        let client = std::fs::read_to_string(generated_file(&response.generated_files, "Clients/TaskClient.swift")).unwrap();
+        for module in ["Foundation", "AxiomCore", "AxiomArchitecture"] {
+            assert_eq!(import_count(&client, module), 1, "{} imported more than once", module);
+        }
+
+        // Contracts are the package itself
+        for file in response.generated_files.iter().filter(|f| f.contains("/Contracts/")) {
+            let content = std::fs::read_to_string(file).unwrap();
+            assert_eq!(import_count(&content, "TaskContracts"), 0, "{} must not import its own module", file);
+        }
+
+        let guide = std::fs::read_to_string(generated_file(&response.generated_files, "IntegrationGuide.md")).unwrap();
+        assert!(guide.contains("import TaskContracts"));
+        assert!(!guide.contains("YourGeneratedModule"));
+
+        // Without split_contracts_module no manifest is written
+        assert!(!response.generated_files.iter().any(|f| f.ends_with("Package.swift")));
+    }
+
+    #[tokio::test]
+    async fn test_no_package_import_without_package_name() {
+        let (_temp_dir, proto_path, output_path) = setup_test_env().await;
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest::builder(
+            proto_path.to_string_lossy(),
+            output_path.to_string_lossy(),
+        )
+        .force_overwrite(true)
+        .build()
+        .unwrap();
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        for file in response.generated_files.iter().filter(|f| f.ends_with(".swift")) {
+            let content = std::fs::read_to_string(file).unwrap();
+            let imports: Vec<&str> = content.lines()
+                .map(str::trim)
+                .filter(|l| l.starts_with("import "))
+                .collect();
+            for import in &imports {
+                assert!(
+                    ["import Foundation", "import AxiomCore", "import AxiomArchitecture", "import SwiftUI", "import XCTest"].contains(import),
+                    "unexpected {} in {}",
+                    import,
+                    file
+                );
+            }
+            let mut unique = imports.clone();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), imports.len(), "duplicate imports in {}", file);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_split_contracts_module_package_manifest() {
+        let (_temp_dir, proto_path, output_path) = setup_test_env().await;
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest::builder(
+            proto_path.to_string_lossy(),
+            output_path.to_string_lossy(),
+        )
+        .swift_package_name("TaskContracts")
+        .split_contracts_module(true)
+        .generate_tests(true)
+        .force_overwrite(true)
+        .build()
+        .unwrap();
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        let manifest = std::fs::read_to_string(generated_file(&response.generated_files, "swift/Package.swift")).unwrap();
+        assert!(manifest.starts_with("// swift-tools-version:"));
+        assert!(manifest.contains(r#".library(name: "TaskContracts", targets: ["TaskContracts"])"#));
+        assert!(manifest.contains(r#".library(name: "TaskContractsClients", targets: ["TaskContractsClients"])"#));
+        assert!(manifest.contains(r#"path: "Contracts""#));
+        assert!(manifest.contains(r#"path: "Clients""#));
+        assert!(manifest.contains(r#"exclude: ["TaskClientTests.swift"]"#));
+
+        let tests = std::fs::read_to_string(generated_file(&response.generated_files, "TaskClientTests.swift")).unwrap();
+        assert!(tests.contains("@testable import TaskContractsClients"));
+        assert_eq!(import_count(&tests, "TaskContracts"), 1);
+
+        // The manifest requires a contracts module to name
+        let result = GenerateRequest::builder("task.proto", "out")
+            .split_contracts_module(true)
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_contract_initializers_use_proto3_defaults() {
+        let (_temp_dir, proto_path, output_path) = setup_test_env().await;
+        let generator = AxiomSwiftClientGenerator::new().await.unwrap();
+
+        let request = GenerateRequest::builder(
+            proto_path.to_string_lossy(),
+            output_path.to_string_lossy(),
+        )
+        .force_overwrite(true)
+        .build()
+        .unwrap();
+
+        let response = generator.generate(request).await.unwrap();
+        assert!(response.success, "Generation failed: {:?}", response.error);
+
+        let contract = response.generated_files.iter()
+            .find(|f| f.contains("/Contracts/"))
+            .expect("no contract file generated");
+        let content = std::fs::read_to_string(contract).unwrap();
+
+        // Implicit scalars and repeated fields have zero values
+        assert!(content.contains(r#"title: String = """#));
+        assert!(content.contains(r#"description: String = """#));
+        assert!(content.contains("isCompleted: Bool = false"));
+        assert!(content.contains("tags: [String] = []"));
+        assert!(content.contains("limit: Int32 = 0"));
+        // `optional` fields are Swift optionals
+        assert!(content.contains("public let completed: Bool?"));
+        assert!(content.contains("completed: Bool? = nil"));
+
+        // Absent keys decode to the same defaults
+        assert!(content.contains("public init(from decoder: Decoder) throws"));
+        assert!(content.contains(r#"try container.decodeIfPresent(String.self, forKey: .title) ?? """#));
+        assert!(content.contains("try container.decodeIfPresent(Bool.self, forKey: .completed)\n"));
+    }
 }
\ No newline at end of file
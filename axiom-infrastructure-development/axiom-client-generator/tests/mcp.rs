@@ -1,6 +1,7 @@
 //! MCP protocol integration tests
 
 use axiom_universal_client_generator::mcp::protocol::*;
+use axiom_universal_client_generator::mcp::{AxiomMcpServer, Lifecycle};
 use serde_json::json;
 
 #[test]
@@ -137,4 +138,205 @@ fn test_tool_content_types() {
     assert!(text_serialized.contains("Simple text content"));
     assert!(json_serialized.contains("key"));
     assert!(json_serialized.contains("value"));
-}
\ No newline at end of file
+}
+#[test]
+fn test_protocol_version_negotiation_exact_match() {
+    for version in ProtocolVersion::SUPPORTED {
+        assert_eq!(ProtocolVersion::negotiate(version.as_str()), Some(*version));
+    }
+}
+
+#[test]
+fn test_protocol_version_negotiation_downgrade() {
+    // A newer client is served our latest revision
+    assert_eq!(ProtocolVersion::negotiate("2099-01-01"), Some(ProtocolVersion::latest()));
+
+    // An unknown revision between two supported ones falls back to the older one
+    assert_eq!(ProtocolVersion::negotiate("2025-01-15"), Some(ProtocolVersion::V2024_11_05));
+}
+
+#[test]
+fn test_protocol_version_negotiation_rejection() {
+    assert_eq!(ProtocolVersion::negotiate("2024-10-07"), None);
+    assert_eq!(ProtocolVersion::negotiate("latest"), None);
+    assert_eq!(ProtocolVersion::negotiate(""), None);
+}
+
+#[test]
+fn test_tool_result_shape_per_protocol_version() {
+    let result = CallToolResult {
+        content: vec![ToolContent::json(json!({"files": 3}))],
+        is_error: Some(false),
+    };
+
+    let legacy = result.to_versioned_value(ProtocolVersion::V2024_11_05);
+    assert_eq!(legacy["content"][0]["type"], "text");
+    assert!(legacy["content"][0].get("mimeType").is_none());
+    assert!(legacy.get("structuredContent").is_none());
+
+    let latest = result.to_versioned_value(ProtocolVersion::V2025_06_18);
+    assert_eq!(latest["content"][0]["type"], "text");
+    assert!(latest["content"][0].get("mimeType").is_none());
+    assert_eq!(latest["structuredContent"], json!({"files": 3}));
+}
+
+fn initialize_message(protocol_version: &str) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": protocol_version,
+            "capabilities": {},
+            "clientInfo": { "name": "protocol-test", "version": "1.0.0" }
+        }
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_initialize_negotiates_requested_version() {
+    let server = AxiomMcpServer::new().await.unwrap();
+
+    let response = server
+        .handle_message(&initialize_message("2025-03-26"))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(response.error.is_none());
+    assert_eq!(response.result.unwrap()["protocolVersion"], "2025-03-26");
+    assert_eq!(server.protocol_version().await, ProtocolVersion::V2025_03_26);
+}
+
+#[tokio::test]
+async fn test_initialize_downgrades_newer_client() {
+    let server = AxiomMcpServer::new().await.unwrap();
+
+    let response = server
+        .handle_message(&initialize_message("2099-01-01"))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(response.result.unwrap()["protocolVersion"], ProtocolVersion::latest().as_str());
+    assert_eq!(server.protocol_version().await, ProtocolVersion::latest());
+}
+
+#[tokio::test]
+async fn test_initialize_rejects_unsupported_version() {
+    let server = AxiomMcpServer::new().await.unwrap();
+
+    let response = server
+        .handle_message(&initialize_message("2023-01-01"))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(response.result.is_none());
+    let error = response.error.unwrap();
+    assert_eq!(error.code, error_codes::INVALID_PARAMS);
+    assert_eq!(error.data.unwrap()["requested"], "2023-01-01");
+
+    // Nothing was negotiated, so the session keeps legacy behavior
+    assert_eq!(server.protocol_version().await, ProtocolVersion::default());
+}
+
+/// Drive the server over in-memory pipes and collect every response line
+async fn run_session(server: &AxiomMcpServer, messages: Vec<String>) -> Vec<serde_json::Value> {
+    let input = messages.join("\n") + "\n";
+    let (server_out, client_in) = tokio::io::duplex(64 * 1024);
+
+    let collector = tokio::spawn(async move {
+        use tokio::io::AsyncBufReadExt;
+        let mut lines = tokio::io::BufReader::new(client_in).lines();
+        let mut responses = Vec::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            responses.push(serde_json::from_str::<serde_json::Value>(&line).unwrap());
+        }
+        responses
+    });
+
+    tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        server.run_with_io(tokio::io::BufReader::new(input.as_bytes()), server_out),
+    )
+    .await
+    .expect("server did not terminate")
+    .unwrap();
+
+    collector.await.unwrap()
+}
+
+fn find_response(responses: &[serde_json::Value], id: i64) -> Vec<&serde_json::Value> {
+    responses.iter().filter(|r| r["id"] == id).collect()
+}
+
+#[tokio::test]
+async fn test_lifecycle_shutdown_during_tool_call() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let output_path = temp_dir.path().join("generated");
+    let proto_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/proto/task_service.proto");
+
+    let server = AxiomMcpServer::new().await.unwrap();
+    let messages = vec![
+        initialize_message(ProtocolVersion::latest().as_str()),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}).to_string(),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "generate_axiom_clients",
+                "arguments": {
+                    "proto_path": proto_path.to_string_lossy(),
+                    "output_path": output_path.to_string_lossy(),
+                    "generation_options": { "force_overwrite": true }
+                }
+            }
+        })
+        .to_string(),
+        json!({"jsonrpc": "2.0", "id": 3, "method": "shutdown"}).to_string(),
+        json!({"jsonrpc": "2.0", "id": 4, "method": "tools/list"}).to_string(),
+        json!({"jsonrpc": "2.0", "method": "exit"}).to_string(),
+    ];
+
+    let responses = run_session(&server, messages).await;
+
+    // Every request is answered exactly once
+    assert_eq!(find_response(&responses, 1).len(), 1);
+    assert_eq!(find_response(&responses, 2).len(), 1, "tool call must complete or be cancelled");
+    assert_eq!(find_response(&responses, 3).len(), 1);
+    assert_eq!(find_response(&responses, 4).len(), 1);
+
+    let shutdown = find_response(&responses, 3)[0];
+    assert!(shutdown.get("error").is_none());
+    assert!(shutdown["result"].is_null());
+
+    // Requests after shutdown are refused
+    assert_eq!(find_response(&responses, 4)[0]["error"]["code"], error_codes::INVALID_REQUEST);
+
+    assert_eq!(server.lifecycle().await, Lifecycle::Exited);
+
+    // No interrupted atomic writes may be left behind
+    if output_path.exists() {
+        let stray: Vec<_> = walkdir::WalkDir::new(&output_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("tmp"))
+            .map(|e| e.path().display().to_string())
+            .collect();
+        assert!(stray.is_empty(), "Stray temp files: {:?}", stray);
+    }
+}
+
+#[tokio::test]
+async fn test_lifecycle_eof_terminates_server() {
+    let server = AxiomMcpServer::new().await.unwrap();
+
+    let responses = run_session(&server, vec![initialize_message("2024-11-05")]).await;
+
+    assert_eq!(find_response(&responses, 1).len(), 1);
+    assert_eq!(server.lifecycle().await, Lifecycle::Exited);
+}
@@ -0,0 +1,132 @@
+//! Memory tests for parsing large proto trees
+//!
+//! These install a counting global allocator, so they live in their own test
+//! binary and only run with `--features memory-tests`.
+
+#![cfg(feature = "memory-tests")]
+
+use axiom_universal_client_generator::proto::parser::ProtoParser;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::TempDir;
+
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const FILE_COUNT: usize = 1_000;
+/// Comment padding per file so raw sources dwarf the parsed schema
+const PADDING_BYTES: usize = 16 * 1024;
+
+/// Write `FILE_COUNT` small services spread over nested packages
+fn write_fixture(root: &std::path::Path) -> usize {
+    let padding = format!("// {}\n", "x".repeat(77)).repeat(PADDING_BYTES / 81);
+    let mut total_bytes = 0;
+
+    for i in 0..FILE_COUNT {
+        let dir = root.join(format!("pkg{:02}", i % 20));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let content = format!(
+            r#"syntax = "proto3";
+
+package memory.pkg{pkg}.v1;
+
+{padding}
+service Service{i} {{
+    rpc GetItem{i}(GetItem{i}Request) returns (Item{i});
+}}
+
+message GetItem{i}Request {{
+    string id = 1;
+}}
+
+message Item{i} {{
+    string id = 1;
+    string name = 2;
+    int64 created_at = 3;
+}}
+"#,
+            pkg = i % 20,
+            padding = padding,
+            i = i,
+        );
+        total_bytes += content.len();
+        std::fs::write(dir.join(format!("service_{:04}.proto", i)), content).unwrap();
+    }
+
+    total_bytes
+}
+
+#[tokio::test]
+async fn test_directory_parse_does_not_retain_sources() {
+    let temp_dir = TempDir::new().unwrap();
+    let source_bytes = write_fixture(temp_dir.path());
+    let parser = ProtoParser::new().await.unwrap();
+
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+
+    let schema = parser.parse_proto_directory(temp_dir.path()).await.unwrap();
+
+    let peak = PEAK.load(Ordering::SeqCst) - baseline;
+    let retained = CURRENT.load(Ordering::SeqCst).saturating_sub(baseline);
+
+    assert_eq!(schema.files.len(), FILE_COUNT);
+    assert_eq!(schema.services.len(), FILE_COUNT);
+    assert_eq!(schema.messages.len(), FILE_COUNT * 2);
+
+    // Sources are read and released one file at a time, so the peak is the
+    // retained schema plus roughly a single file, not the whole tree
+    let file_bytes = source_bytes / FILE_COUNT;
+    assert!(
+        peak < retained + 8 * file_bytes,
+        "peak {} bytes, retained {} bytes, {} bytes per source file",
+        peak,
+        retained,
+        file_bytes
+    );
+    assert!(peak < source_bytes / 2, "peak {} bytes for {} bytes of sources", peak, source_bytes);
+    assert!(
+        retained <= schema.estimated_size_bytes() * 3,
+        "retained {} bytes for a schema estimated at {} bytes",
+        retained,
+        schema.estimated_size_bytes()
+    );
+}
+
+#[tokio::test]
+async fn test_schema_size_warning_threshold() {
+    let temp_dir = TempDir::new().unwrap();
+    write_fixture(temp_dir.path());
+    let parser = ProtoParser::new().await.unwrap();
+    let schema = parser.parse_proto_directory(temp_dir.path()).await.unwrap();
+
+    let size_mb = schema.estimated_size_bytes() as u64 / (1024 * 1024);
+    assert!(size_mb > 0, "fixture schema should exceed 1 MB");
+    assert!(schema.size_warning(size_mb + 1).is_none());
+
+    let warning = schema.size_warning(size_mb).unwrap();
+    assert!(warning.contains("1000 files"), "{}", warning);
+    assert!(warning.contains(&format!("{} MB warning threshold", size_mb)), "{}", warning);
+}
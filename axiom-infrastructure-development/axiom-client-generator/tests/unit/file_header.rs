@@ -0,0 +1,79 @@
+//! Unit tests for license header injection
+
+use axiom_universal_client_generator::generators::header::{leading_comment_lines, FileHeader};
+use std::path::Path;
+use tempfile::TempDir;
+
+#[test]
+fn test_header_renders_placeholders_as_line_comments() {
+    let header = FileHeader::new("Copyright (c) {year} Axiom\n\nFile: {file_name} ({generator_version})");
+
+    let rendered = header.render("TaskClient.swift");
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("// Copyright (c) 20"));
+    assert_eq!(lines[1], "//");
+    assert_eq!(
+        lines[2],
+        format!("// File: TaskClient.swift ({})", env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[test]
+fn test_header_already_commented_is_kept_verbatim() {
+    let header = FileHeader::new("/* SPDX-License-Identifier: MIT */");
+
+    assert_eq!(header.render("Task.swift"), "/* SPDX-License-Identifier: MIT */\n");
+}
+
+#[test]
+fn test_header_inserted_before_imports() {
+    let header = FileHeader::new("Licensed under MIT");
+    let content = "import Foundation\n\npublic struct Task {}\n";
+
+    let output = header.apply(Path::new("Contracts/Task.swift"), content);
+
+    assert!(output.starts_with("// Licensed under MIT\n\nimport Foundation"));
+}
+
+#[test]
+fn test_header_follows_swift_tools_version_in_package_manifest() {
+    let header = FileHeader::new("Licensed under MIT");
+    let content = "// swift-tools-version: 5.9\nimport PackageDescription\n";
+
+    let output = header.apply(Path::new("Package.swift"), content);
+
+    assert!(output.starts_with("// swift-tools-version: 5.9\n// Licensed under MIT\n\nimport PackageDescription"));
+}
+
+#[test]
+fn test_header_is_not_duplicated() {
+    let header = FileHeader::new("Copyright (c) {year} Axiom");
+    let path = Path::new("TaskClient.swift");
+
+    let once = header.apply(path, "import Foundation\n");
+    let twice = header.apply(path, &once);
+
+    assert_eq!(once, twice);
+}
+
+#[test]
+fn test_header_resolved_from_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let header_path = temp_dir.path().join("header.txt");
+    std::fs::write(&header_path, "Licensed under MIT\n").unwrap();
+
+    let header = FileHeader::resolve(&format!("@{}", header_path.display())).unwrap();
+
+    assert_eq!(header, FileHeader::new("Licensed under MIT"));
+    assert!(FileHeader::resolve("@/nonexistent/header.txt").is_err());
+}
+
+#[test]
+fn test_leading_comment_lines() {
+    let content = "// Copyright (c) 2025\n/* multi\n   line) */\n\nimport Foundation\n// trailing\n";
+
+    assert_eq!(leading_comment_lines(content), 4);
+    assert_eq!(leading_comment_lines("import Foundation\n"), 0);
+}
@@ -419,4 +419,82 @@ async fn test_file_watcher_integration() {
     
     let file_changes = changes.unwrap();
     assert!(!file_changes.is_empty());
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_write_files_preserves_input_order() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let writes: Vec<PendingWrite> = (0..40)
+        .map(|i| {
+            // Vary sizes so completion order differs from input order
+            let content = "x".repeat((40 - i) * 1024);
+            PendingWrite::new(temp_dir.path().join(format!("File{:02}.swift", i)), content)
+        })
+        .collect();
+    let expected: Vec<String> = writes.iter()
+        .map(|w| w.path.to_string_lossy().to_string())
+        .collect();
+
+    let written = FileManager::write_files(writes, false, 4).await.unwrap();
+
+    assert_eq!(written, expected);
+    for path in &written {
+        assert!(PathBuf::from(path).exists());
+    }
+}
+
+#[tokio::test]
+async fn test_write_files_rolls_back_on_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let existing = temp_dir.path().join("Existing.swift");
+    std::fs::write(&existing, "original").unwrap();
+
+    // A regular file where a directory is expected makes this write fail
+    let blocker = temp_dir.path().join("blocker");
+    std::fs::write(&blocker, "").unwrap();
+
+    let writes = vec![
+        PendingWrite::new(existing.clone(), "regenerated".to_string()),
+        PendingWrite::new(temp_dir.path().join("New.swift"), "new".to_string()),
+        PendingWrite::new(blocker.join("Broken.swift"), "broken".to_string()),
+    ];
+
+    let result = FileManager::write_files(writes, true, DEFAULT_WRITE_CONCURRENCY).await;
+
+    assert!(result.is_err());
+    assert_eq!(std::fs::read_to_string(&existing).unwrap(), "original");
+    assert!(!temp_dir.path().join("New.swift").exists());
+    assert_eq!(FileManager::cleanup_temp_files(temp_dir.path()).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_temp_file_tracker_removes_only_its_own_writes() {
+    let temp_dir = TempDir::new().unwrap();
+
+    // Another writer's temp file in the same directory must survive
+    let unrelated = temp_dir.path().join("Unrelated.swift.tmp");
+    std::fs::write(&unrelated, "partial").unwrap();
+
+    // A directory at the destination makes the final rename fail
+    let blocked = temp_dir.path().join("Blocked.swift");
+    std::fs::create_dir(&blocked).unwrap();
+
+    let tracker = TempFileTracker::new();
+    let (done, failed) = tracker
+        .scope(async {
+            let done = FileManager::write_file(&temp_dir.path().join("Done.swift"), "done", true).await;
+            let failed = FileManager::write_file(&blocked, "blocked", true).await;
+            (done, failed)
+        })
+        .await;
+
+    assert!(done.is_ok());
+    assert!(failed.is_err());
+    assert_eq!(tracker.paths(), vec![temp_dir.path().join("Blocked.swift.tmp")]);
+
+    assert_eq!(tracker.remove_all().await.unwrap(), 1);
+    assert!(tracker.paths().is_empty());
+    assert!(!temp_dir.path().join("Blocked.swift.tmp").exists());
+    assert!(unrelated.exists());
+    assert!(temp_dir.path().join("Done.swift").exists());
+}
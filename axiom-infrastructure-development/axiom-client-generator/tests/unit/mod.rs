@@ -15,4 +15,10 @@ pub mod naming_conventions;
 pub mod config_management;
 pub mod error_handling;
 pub mod validation;
-pub mod file_management;
\ No newline at end of file
+pub mod file_management;
+pub mod file_header;
+pub mod request_builder;
+pub mod schema_compatibility;
+pub mod naming_audit;
+pub mod option_suggestions;
+pub mod proto3_defaults;
\ No newline at end of file
@@ -0,0 +1,146 @@
+//! Unit tests for the Swift/Kotlin naming audit
+
+use crate::helpers::create_complete_proto_schema;
+use axiom_universal_client_generator::generators::kotlin::KotlinNaming;
+use axiom_universal_client_generator::generators::naming_audit::*;
+use axiom_universal_client_generator::generators::swift::naming::SwiftNaming;
+use axiom_universal_client_generator::proto::types::*;
+
+fn field(name: &str, field_type: &str, number: i32) -> Field {
+    Field {
+        name: name.to_string(),
+        field_type: field_type.to_string(),
+        number,
+        label: FieldLabel::Optional,
+        proto3_optional: false,
+        default_value: None,
+        options: FieldOptions::default(),
+        documentation: None,
+    }
+}
+
+fn enum_value(name: &str, number: i32) -> EnumValue {
+    EnumValue {
+        name: name.to_string(),
+        number,
+        options: EnumValueOptions::default(),
+        documentation: None,
+    }
+}
+
+#[test]
+fn test_kotlin_naming_conventions() {
+    let naming = KotlinNaming::new();
+
+    assert_eq!(naming.client_name("TaskService"), "TaskClient");
+    assert_eq!(naming.action_case_name("CreateTask"), "CreateTask");
+    assert_eq!(naming.field_property_name("is_completed", "bool"), "completed");
+    assert_eq!(naming.field_property_name("is_completed", "string"), "isCompleted");
+    assert_eq!(naming.property_name("object"), "`object`");
+    assert_eq!(naming.enum_case_name("Priority", "PRIORITY_HIGH"), "HIGH");
+    assert_eq!(naming.enum_case_name("Status", "IN_PROGRESS"), "IN_PROGRESS");
+    assert_eq!(naming.file_name("task_client"), "TaskClient.kt");
+}
+
+#[test]
+fn test_resolve_identifiers_lines_up_across_platforms() {
+    let schema = create_complete_proto_schema();
+
+    let swift = resolve_identifiers(&schema, &SwiftNaming::new());
+    let kotlin = resolve_identifiers(&schema, &KotlinNaming::new());
+
+    assert_eq!(swift.len(), kotlin.len());
+    for (s, k) in swift.iter().zip(&kotlin) {
+        assert_eq!(s.location, k.location);
+        assert_eq!(s.kind, k.kind);
+    }
+
+    // The declared client_name option wins over the derived TaskClient
+    let client = swift.iter().find(|i| i.kind == IdentifierKind::ClientType).unwrap();
+    assert_eq!(client.name, "TaskManager");
+}
+
+#[test]
+fn test_audit_reports_boolean_prefix_divergence() {
+    let schema = create_complete_proto_schema();
+
+    let report = NamingAudit::new().run(&schema);
+
+    let divergence = report.divergences.iter()
+        .find(|d| d.location == "Task.is_completed")
+        .expect("is_completed should diverge");
+    assert_eq!(divergence.kind, IdentifierKind::Property);
+    assert_eq!(divergence.swift, "isCompleted");
+    assert_eq!(divergence.kotlin, "completed");
+    assert_eq!(divergence.suggestion.as_deref(), Some("rename field is_completed to completed"));
+    assert!(!report.is_aligned());
+}
+
+#[test]
+fn test_audit_ignores_convention_only_casing() {
+    let schema = create_complete_proto_schema();
+
+    let report = NamingAudit::new().run(&schema);
+
+    // Priority cases differ only in casing (`high` vs `HIGH`) and actions
+    // only in leading case (`createTask` vs `CreateTask`)
+    assert!(report.divergences.iter().all(|d| d.kind != IdentifierKind::EnumCase));
+    assert!(report.divergences.iter().all(|d| d.kind != IdentifierKind::ActionCase));
+}
+
+#[test]
+fn test_audit_reports_enum_prefix_divergence() {
+    let mut schema = create_complete_proto_schema();
+    let mut status = schema.enums[0].clone();
+    status.name = "Status".to_string();
+    status.values = vec![enum_value("STATUS_UNSPECIFIED", 0), enum_value("IN_PROGRESS", 1)];
+    schema.enums.push(status);
+
+    let report = NamingAudit::new().run(&schema);
+
+    let divergence = report.divergences.iter()
+        .find(|d| d.location == "Status.IN_PROGRESS")
+        .expect("IN_PROGRESS should diverge");
+    assert_eq!(divergence.swift, "progress");
+    assert_eq!(divergence.kotlin, "IN_PROGRESS");
+    assert!(divergence.suggestion.as_deref().unwrap().contains("STATUS_IN_PROGRESS"));
+}
+
+#[test]
+fn test_audit_reports_reserved_word_renames_per_platform() {
+    let mut schema = create_complete_proto_schema();
+    let task = schema.messages.iter_mut().find(|m| m.name == "Task").unwrap();
+    task.fields.push(field("default", "string", 20));
+    task.fields.push(field("object", "string", 21));
+
+    let report = NamingAudit::new().run(&schema);
+
+    let swift_rename = report.reserved_renames.iter()
+        .find(|r| r.location == "Task.default")
+        .unwrap();
+    assert_eq!(swift_rename.platform, "swift");
+    assert_eq!(swift_rename.identifier, "`default`");
+
+    let kotlin_rename = report.reserved_renames.iter()
+        .find(|r| r.location == "Task.object")
+        .unwrap();
+    assert_eq!(kotlin_rename.platform, "kotlin");
+
+    // Escaping alone is not a divergence
+    assert!(report.divergences.iter().all(|d| d.location != "Task.default"));
+}
+
+#[test]
+fn test_audit_table_and_json_output() {
+    let schema = create_complete_proto_schema();
+    let report = NamingAudit::new().run(&schema);
+
+    let table = report.format_table();
+    assert!(table.contains("Divergences:"));
+    assert!(table.contains("Task.is_completed"));
+    assert!(table.contains("Suggested overrides:"));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["divergences"][0]["kind"], "property");
+    assert!(json["identifiers_checked"].as_u64().unwrap() > 0);
+}
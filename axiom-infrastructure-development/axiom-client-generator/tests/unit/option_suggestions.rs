@@ -0,0 +1,114 @@
+//! Unit tests for axiom option suggestions on un-annotated protos
+
+use crate::helpers::create_complete_proto_schema;
+use axiom_universal_client_generator::proto::analyzer::*;
+use axiom_universal_client_generator::proto::types::*;
+
+fn suggest(schema: ProtoSchema) -> Vec<Suggestion> {
+    ProtoAnalyzer::new(schema).suggest_options()
+}
+
+fn find<'a>(suggestions: &'a [Suggestion], location: &str) -> &'a Suggestion {
+    suggestions.iter()
+        .find(|s| s.location() == location)
+        .unwrap_or_else(|| panic!("no suggestion for {}", location))
+}
+
+#[test]
+fn test_suggests_strategies_from_method_shapes() {
+    let suggestions = suggest(create_complete_proto_schema());
+
+    let locations: Vec<String> = suggestions.iter().map(|s| s.location()).collect();
+    assert_eq!(
+        locations,
+        vec![
+            "TaskService",
+            "TaskService.CreateTask",
+            "TaskService.GetTask",
+            "TaskService.UpdateTask",
+            "TaskService.DeleteTask",
+        ]
+    );
+
+    let service = find(&suggestions, "TaskService");
+    assert!(service.snippet.starts_with("option (axiom.options.v1.axiom_service) = {"));
+    assert!(service.snippet.contains(r#"{ name: "tasks" item_type: "Task" primary_key: "id" }"#));
+
+    // CreateTaskResponse wraps a Task, which has an id
+    let create = find(&suggestions, "TaskService.CreateTask");
+    assert_eq!(create.confidence, SuggestionConfidence::High);
+    assert!(create.snippet.contains("state_update_strategy: STATE_UPDATE_STRATEGY_APPEND"));
+    assert!(create.snippet.contains(r#"collection_name: "tasks""#));
+
+    let get = find(&suggestions, "TaskService.GetTask");
+    assert_eq!(get.confidence, SuggestionConfidence::Medium);
+    assert!(get.snippet.contains("STATE_UPDATE_STRATEGY_UPDATE_BY_ID"));
+
+    let delete = find(&suggestions, "TaskService.DeleteTask");
+    assert!(delete.snippet.contains("STATE_UPDATE_STRATEGY_REMOVE_BY_ID"));
+    assert!(delete.snippet.contains(r#"collection_name: "tasks""#));
+}
+
+#[test]
+fn test_annotated_services_and_methods_are_skipped() {
+    let mut schema = create_complete_proto_schema();
+    let service = &mut schema.services[0];
+    service.methods[0].options.axiom_method.as_mut().unwrap().collection_name = Some("tasks".to_string());
+    service.options.axiom_service.as_mut().unwrap().collections.push(AxiomCollection {
+        name: "tasks".to_string(),
+        item_type: "Task".to_string(),
+        primary_key: Some("id".to_string()),
+        paginated: None,
+        default_sort_field: None,
+        searchable: None,
+        max_cached_items: None,
+    });
+
+    let suggestions = suggest(schema);
+
+    assert!(suggestions.iter().all(|s| s.method.is_some()));
+    assert!(suggestions.iter().all(|s| s.location() != "TaskService.CreateTask"));
+}
+
+#[test]
+fn test_streaming_skipped_and_empty_response_suggests_no_change() {
+    let mut schema = create_complete_proto_schema();
+    schema.services[0].methods[1].server_streaming = true;
+
+    let mut ping = schema.services[0].methods[0].clone();
+    ping.name = "Ping".to_string();
+    ping.input_type = ".task.v1.PingRequest".to_string();
+    ping.output_type = ".google.protobuf.Empty".to_string();
+    schema.services[0].methods.push(ping);
+
+    let suggestions = suggest(schema);
+
+    assert!(suggestions.iter().all(|s| s.location() != "TaskService.GetTask"));
+    let ping = find(&suggestions, "TaskService.Ping");
+    assert_eq!(ping.confidence, SuggestionConfidence::Low);
+    assert!(ping.snippet.contains("STATE_UPDATE_STRATEGY_NO_CHANGE"));
+    assert!(ping.snippet.contains("modifies_state: false"));
+    assert!(!ping.snippet.contains("collection_name"));
+}
+
+#[test]
+fn test_entity_specific_id_field_is_suggested() {
+    let mut schema = create_complete_proto_schema();
+    let task = schema.messages.iter_mut().find(|m| m.name == "Task").unwrap();
+    task.fields[0].name = "task_id".to_string();
+
+    let suggestions = suggest(schema);
+
+    let delete = find(&suggestions, "TaskService.DeleteTask");
+    assert!(delete.snippet.contains(r#"id_field_name: "task_id""#));
+    let service = find(&suggestions, "TaskService");
+    assert!(service.snippet.contains(r#"primary_key: "task_id""#));
+
+    let rendered = format_suggestions(&suggestions);
+    assert!(rendered.contains("💡 TaskService.DeleteTask [medium]"));
+}
+
+#[test]
+fn test_no_suggestions_message() {
+    assert!(format_suggestions(&[]).contains("No option suggestions"));
+}
@@ -0,0 +1,194 @@
+//! Unit tests for proto3 default values in generated Swift initializers
+
+use axiom_universal_client_generator::generators::swift::clients::SwiftStateGenerator;
+use axiom_universal_client_generator::generators::swift::defaults::*;
+use axiom_universal_client_generator::generators::swift::naming::SwiftNaming;
+use axiom_universal_client_generator::proto::types::*;
+
+fn field(name: &str, field_type: &str, label: FieldLabel, proto3_optional: bool) -> Field {
+    Field {
+        name: name.to_string(),
+        field_type: field_type.to_string(),
+        number: 1,
+        label,
+        proto3_optional,
+        default_value: None,
+        options: FieldOptions::default(),
+        documentation: None,
+    }
+}
+
+fn priority_enum() -> Enum {
+    let value = |name: &str, number| EnumValue {
+        name: name.to_string(),
+        number,
+        options: EnumValueOptions::default(),
+        documentation: None,
+    };
+    Enum {
+        name: "TaskPriority".to_string(),
+        package: "task.v1".to_string(),
+        file_path: "task.proto".to_string(),
+        values: vec![value("TASK_PRIORITY_UNSPECIFIED", 0), value("TASK_PRIORITY_HIGH", 1)],
+        options: EnumOptions::default(),
+        documentation: None,
+    }
+}
+
+fn default_for(field: &Field, enum_type: Option<&Enum>) -> SwiftFieldDefault {
+    let naming = SwiftNaming::new();
+    proto3_default(field, &naming.swift_type(&field.field_type), enum_type, &naming)
+}
+
+#[test]
+fn test_implicit_scalars_default_to_zero_values() {
+    let cases = [
+        ("int32", "Int32", "0"),
+        ("int64", "Int64", "0"),
+        ("uint32", "UInt32", "0"),
+        ("uint64", "UInt64", "0"),
+        ("sint32", "Int32", "0"),
+        ("sint64", "Int64", "0"),
+        ("fixed32", "UInt32", "0"),
+        ("fixed64", "UInt64", "0"),
+        ("sfixed32", "Int32", "0"),
+        ("sfixed64", "Int64", "0"),
+        ("float", "Float", "0.0"),
+        ("double", "Double", "0.0"),
+        ("bool", "Bool", "false"),
+        ("string", "String", "\"\""),
+        ("bytes", "Data", "Data()"),
+    ];
+
+    for (proto_type, swift_type, default_value) in cases {
+        let result = default_for(&field("value", proto_type, FieldLabel::Optional, false), None);
+        assert_eq!(
+            result,
+            SwiftFieldDefault {
+                swift_type: swift_type.to_string(),
+                is_optional: false,
+                default_value: default_value.to_string(),
+            },
+            "{}",
+            proto_type
+        );
+    }
+}
+
+#[test]
+fn test_field_names_do_not_affect_optionality() {
+    // `description` and `optional_*` used to be special-cased by name
+    for name in ["description", "optional_note", "note?"] {
+        let result = default_for(&field(name, "string", FieldLabel::Optional, false), None);
+        assert!(!result.is_optional, "{}", name);
+        assert_eq!(result.default_value, "\"\"");
+    }
+}
+
+#[test]
+fn test_proto3_optional_fields_are_swift_optionals() {
+    for proto_type in ["string", "int32", "bool", "bytes", ".task.v1.TaskPriority", ".task.v1.Task"] {
+        let priority = priority_enum();
+        let enum_type = proto_type.ends_with("TaskPriority").then_some(&priority);
+        let result = default_for(&field("value", proto_type, FieldLabel::Optional, true), enum_type);
+        assert!(result.is_optional, "{}", proto_type);
+        assert!(result.swift_type.ends_with('?'), "{}", proto_type);
+        assert_eq!(result.default_value, "nil");
+    }
+}
+
+#[test]
+fn test_message_fields_default_to_nil() {
+    let result = default_for(&field("task", ".task.v1.Task", FieldLabel::Optional, false), None);
+    assert_eq!(result.swift_type, "Task?");
+    assert!(result.is_optional);
+    assert_eq!(result.default_value, "nil");
+
+    let result = default_for(&field("created_at", ".google.protobuf.Timestamp", FieldLabel::Optional, false), None);
+    assert!(result.is_optional);
+    assert_eq!(result.default_value, "nil");
+}
+
+#[test]
+fn test_enum_fields_default_to_zero_value() {
+    let priority = priority_enum();
+    let result = default_for(&field("priority", ".task.v1.TaskPriority", FieldLabel::Optional, false), Some(&priority));
+    assert_eq!(
+        result,
+        SwiftFieldDefault {
+            swift_type: "TaskPriority".to_string(),
+            is_optional: false,
+            default_value: ".unspecified".to_string(),
+        }
+    );
+
+    let naming = SwiftNaming::new();
+    assert_eq!(enum_default_case(&priority, &naming).as_deref(), Some(".unspecified"));
+}
+
+#[test]
+fn test_repeated_and_map_fields_default_to_empty() {
+    let priority = priority_enum();
+    let cases: [(&str, Option<&Enum>, &str); 4] = [
+        ("string", None, "[String]"),
+        (".task.v1.Task", None, "[Task]"),
+        (".task.v1.TaskPriority", Some(&priority), "[TaskPriority]"),
+        // Maps arrive from protoc as repeated `<Field>Entry` messages
+        (".task.v1.Task.LabelsEntry", None, "[LabelsEntry]"),
+    ];
+
+    for (proto_type, enum_type, swift_type) in cases {
+        let result = default_for(&field("values", proto_type, FieldLabel::Repeated, false), enum_type);
+        assert_eq!(result.swift_type, swift_type);
+        assert!(!result.is_optional);
+        assert_eq!(result.default_value, "[]");
+    }
+}
+
+#[test]
+fn test_required_fields_use_scalar_defaults() {
+    let result = default_for(&field("count", "int32", FieldLabel::Required, false), None);
+    assert_eq!(result.swift_type, "Int32");
+    assert_eq!(result.default_value, "0");
+}
+
+#[test]
+fn test_explicit_proto2_defaults_are_honored() {
+    let mut with_default = field("retries", "int32", FieldLabel::Optional, false);
+    with_default.default_value = Some("3".to_string());
+    assert_eq!(default_for(&with_default, None).default_value, "3");
+
+    let mut with_default = field("label", "string", FieldLabel::Optional, false);
+    with_default.default_value = Some("todo".to_string());
+    assert_eq!(default_for(&with_default, None).default_value, "\"todo\"");
+
+    // No Swift literal for inf; the zero value is used instead
+    let mut with_default = field("ratio", "double", FieldLabel::Optional, false);
+    with_default.default_value = Some("inf".to_string());
+    assert_eq!(default_for(&with_default, None).default_value, "0.0");
+}
+
+#[tokio::test]
+async fn test_state_initializer_has_defaults_for_every_parameter() {
+    let message = Message {
+        name: "Task".to_string(),
+        package: "task.v1".to_string(),
+        file_path: "task.proto".to_string(),
+        fields: vec![
+            field("id", "string", FieldLabel::Optional, false),
+            field("is_completed", "bool", FieldLabel::Optional, false),
+            field("note", "string", FieldLabel::Optional, true),
+            field("tags", "string", FieldLabel::Repeated, false),
+        ],
+        nested_messages: vec![],
+        nested_enums: vec![],
+        options: MessageOptions::default(),
+        documentation: None,
+    };
+
+    let swift_code = SwiftStateGenerator::new().generate_state_struct(&message).await.unwrap();
+
+    assert!(swift_code.contains("public init(id: String = \"\", isCompleted: Bool = false, note: String? = nil, tags: [String] = [])"));
+    assert!(swift_code.contains("public let note: String?"));
+    assert!(swift_code.contains("func withNote(_ note: String?) -> TaskState"));
+}
@@ -220,6 +220,52 @@ service InvalidService {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_parse_top_level_enums() {
+    let proto_content = r#"
+syntax = "proto3";
+package tasks.v1;
+
+enum TaskError {
+  TASK_ERROR_UNSPECIFIED = 0;
+  // The requested task does not exist
+  TASK_ERROR_NOT_FOUND = 1;
+  TASK_ERROR_LOCKED = 2 [deprecated = true];
+}
+
+message Task {
+  enum Kind {
+    KIND_UNSPECIFIED = 0;
+  }
+  string id = 1;
+  TaskError last_error = 2;
+}
+"#;
+
+    let temp_dir = TempDir::new().unwrap();
+    let proto_file = temp_dir.path().join("tasks.proto");
+    std::fs::write(&proto_file, proto_content).unwrap();
+
+    let parser = ProtoParser::new().await.unwrap();
+    let schema = parser.parse_proto_file(&proto_file).await.unwrap();
+
+    // Nested enums are not lifted to the top level
+    assert_eq!(schema.enums.len(), 1);
+    let task_error = &schema.enums[0];
+    assert_eq!(task_error.name, "TaskError");
+    assert_eq!(task_error.package, "tasks.v1");
+
+    let values: Vec<(&str, i32)> = task_error.values.iter()
+        .map(|v| (v.name.as_str(), v.number))
+        .collect();
+    assert_eq!(values, vec![
+        ("TASK_ERROR_UNSPECIFIED", 0),
+        ("TASK_ERROR_NOT_FOUND", 1),
+        ("TASK_ERROR_LOCKED", 2),
+    ]);
+    assert_eq!(schema.files[0].enums, vec!["TaskError".to_string()]);
+}
+
 #[tokio::test]
 async fn test_parse_directory_with_multiple_protos() {
     let proto1 = r#"
@@ -0,0 +1,123 @@
+//! Unit tests for the library-level request builder and registry injection
+
+use async_trait::async_trait;
+use axiom_universal_client_generator::proto::types::ProtoSchema;
+use std::sync::Arc;
+use axiom_universal_client_generator::{
+    AxiomSwiftClientGenerator, Error, GenerateRequest, GeneratorRegistry, LanguageGenerator,
+};
+
+#[test]
+fn test_builder_defaults_match_cli() {
+    let request = GenerateRequest::builder("api.proto", "out").build().unwrap();
+
+    assert_eq!(request.target_languages, vec!["swift"]);
+    assert!(request.services.is_none());
+    assert!(request.framework_config.is_none());
+
+    let options = request.generation_options.unwrap();
+    assert_eq!(options.generate_contracts, Some(true));
+    assert_eq!(options.generate_clients, Some(true));
+    assert_eq!(options.include_documentation, Some(true));
+    assert_eq!(options.force_overwrite, Some(false));
+    assert_eq!(options.style_guide.as_deref(), Some("axiom"));
+    assert!(options.file_header.is_none());
+    assert_eq!(options.generate_previews, Some(false));
+    assert_eq!(options.strict, Some(false));
+    assert!(options.strict_categories.is_none());
+}
+
+#[test]
+fn test_builder_typed_setters() {
+    let request = GenerateRequest::builder("api.proto", "out")
+        .language("swift")
+        .language("swift")
+        .service("TaskService")
+        .swift_package_name("TaskModule")
+        .include_documentation(false)
+        .file_header("Copyright (c) {year}")
+        .build()
+        .unwrap();
+
+    assert_eq!(request.target_languages, vec!["swift"]);
+    assert_eq!(request.services, Some(vec!["TaskService".to_string()]));
+
+    let swift = request.framework_config.unwrap().swift.unwrap();
+    assert_eq!(swift.package_name.as_deref(), Some("TaskModule"));
+    assert_eq!(swift.client_suffix.as_deref(), Some("Client"));
+
+    let options = request.generation_options.unwrap();
+    assert_eq!(options.include_documentation, Some(false));
+    assert_eq!(options.file_header.as_deref(), Some("Copyright (c) {year}"));
+}
+
+#[test]
+fn test_builder_rejects_invalid_configuration() {
+    let cases = vec![
+        GenerateRequest::builder("", "out"),
+        GenerateRequest::builder("api.proto", " "),
+        GenerateRequest::builder("api.proto", "out").languages(Vec::<String>::new()),
+        GenerateRequest::builder("api.proto", "out").service(""),
+        GenerateRequest::builder("api.proto", "out")
+            .generate_contracts(false)
+            .generate_clients(false),
+        GenerateRequest::builder("api.proto", "out")
+            .generate_clients(false)
+            .generate_tests(true),
+        GenerateRequest::builder("api.proto", "out")
+            .generate_clients(false)
+            .generate_tests(false)
+            .generate_previews(true),
+        GenerateRequest::builder("api.proto", "out")
+            .strict(false)
+            .strict_categories(["access_control"]),
+        GenerateRequest::builder("api.proto", "out").strict_categories(["spelling"]),
+        GenerateRequest::builder("api.proto", "out").max_memory_warn_mb(0),
+    ];
+
+    for builder in cases {
+        let result = builder.clone().build();
+        assert!(
+            matches!(result, Err(Error::ConfigError(_))),
+            "expected ConfigError for {:?}",
+            builder
+        );
+    }
+}
+
+struct EchoGenerator;
+
+#[async_trait]
+impl LanguageGenerator for EchoGenerator {
+    fn language(&self) -> &str {
+        "echo"
+    }
+
+    async fn generate(
+        &self,
+        _schema: &Arc<ProtoSchema>,
+        request: &GenerateRequest,
+    ) -> axiom_universal_client_generator::Result<Vec<String>> {
+        Ok(vec![format!("{}/echo.txt", request.output_path)])
+    }
+
+    fn validate_schema(&self, _schema: &ProtoSchema) -> axiom_universal_client_generator::Result<()> {
+        Ok(())
+    }
+
+    fn file_extensions(&self) -> Vec<&str> {
+        vec!["txt"]
+    }
+}
+
+#[tokio::test]
+async fn test_with_registry_uses_custom_generators() {
+    let mut registry = GeneratorRegistry::empty();
+    registry.register(Box::new(EchoGenerator));
+
+    assert!(registry.supports_language("echo"));
+    assert!(!registry.supports_language("swift"));
+
+    let generator = AxiomSwiftClientGenerator::with_registry(registry).await.unwrap();
+    assert_eq!(generator.supported_languages(), vec!["echo"]);
+}
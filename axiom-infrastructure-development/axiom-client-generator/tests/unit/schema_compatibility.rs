@@ -0,0 +1,184 @@
+//! Unit tests for schema fingerprinting and compatibility diffing
+
+use crate::helpers::create_complete_proto_schema;
+use axiom_universal_client_generator::proto::analyzer::*;
+use axiom_universal_client_generator::proto::types::*;
+use axiom_universal_client_generator::utils::manifest::GenerationManifest;
+use tempfile::TempDir;
+
+fn message_mut<'a>(schema: &'a mut ProtoSchema, name: &str) -> &'a mut Message {
+    schema.messages.iter_mut().find(|m| m.name == name).unwrap()
+}
+
+fn diff(previous: &ProtoSchema, current: &ProtoSchema) -> CompatibilityReport {
+    CompatibilityReport::between(
+        &SchemaFingerprint::from_schema(previous),
+        &SchemaFingerprint::from_schema(current),
+    )
+}
+
+fn breaking_kinds(report: &CompatibilityReport) -> Vec<SchemaChangeKind> {
+    report.breaking.iter().map(|c| c.kind).collect()
+}
+
+#[test]
+fn test_fingerprint_is_stable_and_order_independent() {
+    let schema = create_complete_proto_schema();
+    let mut reordered = schema.clone();
+    reordered.messages.reverse();
+    reordered.services[0].methods.reverse();
+
+    assert_eq!(
+        SchemaFingerprint::from_schema(&schema).hash,
+        SchemaFingerprint::from_schema(&reordered).hash
+    );
+
+    let report = diff(&schema, &reordered);
+    assert!(report.is_compatible());
+    assert!(report.additive.is_empty());
+}
+
+#[test]
+fn test_field_type_change_is_breaking() {
+    let previous = create_complete_proto_schema();
+    let mut current = previous.clone();
+    message_mut(&mut current, "Task").fields[1].field_type = "int32".to_string();
+
+    let report = diff(&previous, &current);
+
+    assert_eq!(breaking_kinds(&report), vec![SchemaChangeKind::FieldTypeChanged]);
+    assert_eq!(report.breaking[0].location, "Task.title");
+}
+
+#[test]
+fn test_repeated_label_change_is_breaking() {
+    let previous = create_complete_proto_schema();
+    let mut current = previous.clone();
+    message_mut(&mut current, "Task").fields[1].label = FieldLabel::Repeated;
+
+    let report = diff(&previous, &current);
+
+    assert_eq!(breaking_kinds(&report), vec![SchemaChangeKind::FieldTypeChanged]);
+}
+
+#[test]
+fn test_field_removal_is_breaking() {
+    let previous = create_complete_proto_schema();
+    let mut current = previous.clone();
+    message_mut(&mut current, "Task").fields.retain(|f| f.name != "title");
+
+    let report = diff(&previous, &current);
+
+    assert_eq!(breaking_kinds(&report), vec![SchemaChangeKind::FieldRemoved]);
+}
+
+#[test]
+fn test_field_number_reuse_is_breaking() {
+    let previous = create_complete_proto_schema();
+    let mut current = previous.clone();
+    let task = message_mut(&mut current, "Task");
+    task.fields[1].name = "headline".to_string();
+
+    let report = diff(&previous, &current);
+
+    assert_eq!(breaking_kinds(&report), vec![SchemaChangeKind::FieldNumberReused]);
+    assert!(report.breaking[0].description.contains("title is now headline"));
+}
+
+#[test]
+fn test_method_removal_and_rename_are_breaking() {
+    let previous = create_complete_proto_schema();
+
+    let mut removed = previous.clone();
+    removed.services[0].methods.retain(|m| m.name != "DeleteTask");
+    assert_eq!(breaking_kinds(&diff(&previous, &removed)), vec![SchemaChangeKind::MethodRemoved]);
+
+    let mut renamed = previous.clone();
+    renamed.services[0].methods.iter_mut()
+        .find(|m| m.name == "GetTask")
+        .unwrap()
+        .name = "FetchTask".to_string();
+    let report = diff(&previous, &renamed);
+    assert_eq!(breaking_kinds(&report), vec![SchemaChangeKind::MethodRemoved]);
+    assert_eq!(report.additive[0].kind, SchemaChangeKind::MethodAdded);
+}
+
+#[test]
+fn test_method_signature_change_is_breaking() {
+    let previous = create_complete_proto_schema();
+    let mut current = previous.clone();
+    current.services[0].methods[0].output_type = "Task".to_string();
+
+    let report = diff(&previous, &current);
+
+    assert_eq!(breaking_kinds(&report), vec![SchemaChangeKind::MethodSignatureChanged]);
+}
+
+#[test]
+fn test_service_and_message_removal_are_breaking() {
+    let previous = create_complete_proto_schema();
+    let mut current = previous.clone();
+    current.services.clear();
+    current.messages.retain(|m| m.name != "CreateTaskResponse");
+
+    let kinds = breaking_kinds(&diff(&previous, &current));
+
+    assert!(kinds.contains(&SchemaChangeKind::ServiceRemoved));
+    assert!(kinds.contains(&SchemaChangeKind::MessageRemoved));
+}
+
+#[test]
+fn test_enum_value_removal_and_reuse_are_breaking() {
+    let previous = create_complete_proto_schema();
+
+    let mut removed = previous.clone();
+    removed.enums[0].values.retain(|v| v.number != 3);
+    assert_eq!(breaking_kinds(&diff(&previous, &removed)), vec![SchemaChangeKind::EnumValueRemoved]);
+
+    let mut reused = previous.clone();
+    reused.enums[0].values[3].name = "PRIORITY_URGENT".to_string();
+    assert_eq!(breaking_kinds(&diff(&previous, &reused)), vec![SchemaChangeKind::EnumValueNumberReused]);
+}
+
+#[test]
+fn test_additions_are_not_breaking() {
+    let previous = create_complete_proto_schema();
+    let mut current = previous.clone();
+    let mut new_field = message_mut(&mut current, "Task").fields[0].clone();
+    new_field.name = "archived".to_string();
+    new_field.field_type = "bool".to_string();
+    new_field.number = 99;
+    message_mut(&mut current, "Task").fields.push(new_field);
+    current.enums[0].values.push(EnumValue {
+        name: "PRIORITY_CRITICAL".to_string(),
+        number: 4,
+        options: EnumValueOptions::default(),
+        documentation: None,
+    });
+
+    let report = diff(&previous, &current);
+
+    assert!(report.is_compatible());
+    let kinds: Vec<SchemaChangeKind> = report.additive.iter().map(|c| c.kind).collect();
+    assert!(kinds.contains(&SchemaChangeKind::FieldAdded));
+    assert!(kinds.contains(&SchemaChangeKind::EnumValueAdded));
+}
+
+#[tokio::test]
+async fn test_manifest_round_trips_fingerprint() {
+    let temp_dir = TempDir::new().unwrap();
+    let schema = create_complete_proto_schema();
+    let fingerprint = SchemaFingerprint::from_schema(&schema);
+    let generated = vec![temp_dir.path().join("swift/Clients/TaskClient.swift").to_string_lossy().to_string()];
+
+    assert!(GenerationManifest::load(temp_dir.path()).await.unwrap().is_none());
+
+    GenerationManifest::new(fingerprint.clone(), temp_dir.path(), &generated)
+        .save(temp_dir.path())
+        .await
+        .unwrap();
+    let loaded = GenerationManifest::load(temp_dir.path()).await.unwrap().unwrap();
+
+    assert_eq!(loaded.schema, fingerprint);
+    assert_eq!(loaded.files, vec!["swift/Clients/TaskClient.swift"]);
+}
@@ -59,6 +59,7 @@ fn create_test_message() -> ProtoMessage {
                 field_type: "string".to_string(),
                 number: 1,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -68,6 +69,7 @@ fn create_test_message() -> ProtoMessage {
                 field_type: "string".to_string(),
                 number: 2,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -77,6 +79,7 @@ fn create_test_message() -> ProtoMessage {
                 field_type: "string".to_string(),
                 number: 3,
                 label: FieldLabel::Optional,
+                proto3_optional: true,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -86,6 +89,7 @@ fn create_test_message() -> ProtoMessage {
                 field_type: "int32".to_string(),
                 number: 4,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -95,6 +99,7 @@ fn create_test_message() -> ProtoMessage {
                 field_type: "bool".to_string(),
                 number: 5,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -104,6 +109,7 @@ fn create_test_message() -> ProtoMessage {
                 field_type: "string".to_string(),
                 number: 6,
                 label: FieldLabel::Repeated,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
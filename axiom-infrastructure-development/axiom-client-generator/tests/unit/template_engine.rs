@@ -127,6 +127,133 @@ async fn test_client_actor_template() {
     assert!(output.contains("public func process(_ action: TaskAction) async throws"));
 }
 
+/// Client actor context with one method declaring a loading timeout
+fn loading_timeout_context(with_timeout: bool) -> Context {
+    let mut context = Context::new();
+    context.insert("service_name", "ReportService");
+    context.insert("client_name", "ReportClient");
+    context.insert("state_name", "ReportState");
+    context.insert("action_name", "ReportAction");
+    context.insert("imports", &Vec::<String>::new());
+
+    let timeout = if with_timeout { serde_json::json!(30000) } else { serde_json::Value::Null };
+    let methods = vec![
+        serde_json::json!({
+            "swift_name": "generateReport",
+            "input_type": "GenerateReportRequest",
+            "output_type": "Report",
+            "state_update": "append",
+            "collection_name": "reports",
+            "loading_timeout_ms": timeout,
+        }),
+        serde_json::json!({
+            "swift_name": "getReports",
+            "input_type": "GetReportsRequest",
+            "output_type": "GetReportsResponse",
+            "state_update": "replace_all",
+            "collection_name": "reports",
+            "loading_timeout_ms": null,
+        }),
+    ];
+    context.insert("methods", &methods);
+    context.insert("has_loading_timeouts", &with_timeout);
+    context
+}
+
+#[tokio::test]
+async fn test_client_actor_wraps_only_timed_methods() {
+    let mut template_engine = SwiftTemplateEngine::new().await.unwrap();
+    template_engine.initialize_templates().await.unwrap();
+
+    let output = template_engine
+        .render_client_actor(&loading_timeout_context(true))
+        .await
+        .unwrap();
+
+    // The method declaring a timeout is raced against it
+    assert!(output.contains("private func withLoadingTimeout<T: Sendable>("));
+    assert!(output.contains("withLoadingTimeout(milliseconds: 30000, operation: \"generateReport\")"));
+    assert_eq!(output.matches("withLoadingTimeout(milliseconds:").count(), 1);
+
+    // Methods without a timeout call the API directly
+    assert!(output.contains("let result = try await apiClient.getReports(request)"));
+
+    // Timeouts land in state rather than leaving isLoading stuck
+    assert!(output.contains("catch let error as AxiomError where error.isTimeout"));
+    assert!(output.contains("_state.withError(error)"));
+}
+
+#[tokio::test]
+async fn test_client_actor_without_timeouts_has_no_wrapper() {
+    let mut template_engine = SwiftTemplateEngine::new().await.unwrap();
+    template_engine.initialize_templates().await.unwrap();
+
+    let output = template_engine
+        .render_client_actor(&loading_timeout_context(false))
+        .await
+        .unwrap();
+
+    assert!(!output.contains("withLoadingTimeout"));
+    assert!(!output.contains("isTimeout"));
+    assert!(output.contains("let result = try await apiClient.generateReport(request)"));
+    assert!(output.contains("let newState = try await processAction(action, currentState: _state)"));
+}
+
+#[tokio::test]
+async fn test_error_types_include_timeout_case() {
+    let mut template_engine = SwiftTemplateEngine::new().await.unwrap();
+    template_engine.initialize_templates().await.unwrap();
+
+    let mut context = Context::new();
+    context.insert("service_name", "ReportService");
+
+    let output = template_engine.render_client("error_types", &context).unwrap();
+
+    assert!(output.contains("case timedOut(operation: String, milliseconds: Int)"));
+    assert!(output.contains("public var isTimeout: Bool"));
+    assert!(output.contains("public static func timedOut(_ operation: String, milliseconds: Int) -> AxiomError"));
+}
+
+#[tokio::test]
+async fn test_service_errors_map_reason_and_status() {
+    let mut template_engine = SwiftTemplateEngine::new().await.unwrap();
+    template_engine.initialize_templates().await.unwrap();
+
+    let mut context = Context::new();
+    context.insert("service_name", "TaskService");
+    context.insert("error", &serde_json::json!({
+        "swift_name": "TaskClientError",
+        "proto_enum": "TaskError",
+        "cases": [
+            {
+                "case_name": "notFound",
+                "proto_value": "TASK_ERROR_NOT_FOUND",
+                "short_name": "NOT_FOUND",
+                "display_name": "Not Found",
+                "status_code": 5,
+                "documentation": null,
+            },
+            {
+                "case_name": "taskLocked",
+                "proto_value": "TASK_ERROR_TASK_LOCKED",
+                "short_name": "TASK_LOCKED",
+                "display_name": "Task Locked",
+                "status_code": null,
+                "documentation": null,
+            },
+        ],
+    }));
+
+    let output = template_engine.render_client("service_errors", &context).unwrap();
+
+    assert!(output.contains("public enum TaskClientError: Error, Equatable, Sendable"));
+    assert!(output.contains(r#"case "TASK_ERROR_TASK_LOCKED"?, "TASK_LOCKED"?:"#));
+    assert!(output.contains("case 5:"));
+    // Values without a canonical gRPC status are only reachable through `reason`
+    assert_eq!(output.matches("self = .taskLocked(").count(), 1);
+    assert!(output.contains("public init(status: AxiomStatusError)"));
+}
+
 #[tokio::test]
 async fn test_state_struct_template() {
     let mut template_engine = SwiftTemplateEngine::new().await.unwrap();
@@ -450,6 +577,7 @@ async fn test_template_context_builder() {
                 field_type: "string".to_string(),
                 number: 1,
                 label: FieldLabel::Optional,
+                proto3_optional: false,
                 default_value: None,
                 options: FieldOptions::default(),
                 documentation: None,
@@ -534,4 +662,50 @@ public struct {{ struct_name }} {
     
     let tera = template_engine.get_tera();
     assert!(tera.get_template_names().any(|name| name.contains("test_struct")));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_dev_templates_reload_without_rebuild() {
+    let temp_dir = TempDir::new().unwrap();
+    let clients_dir = temp_dir.path().join("clients");
+    std::fs::create_dir_all(&clients_dir).unwrap();
+    let template_path = clients_dir.join("client_actor.swift.tera");
+    std::fs::write(&template_path, "// v1 {{ client_name }}").unwrap();
+
+    let engine = SwiftTemplateEngine::with_dev_template_dir(temp_dir.path()).unwrap();
+    assert!(engine.is_dev_mode());
+
+    let mut context = Context::new();
+    context.insert("client_name", "TaskClient");
+    assert_eq!(engine.render_client("client_actor", &context).unwrap(), "// v1 TaskClient");
+
+    std::fs::write(&template_path, "// v2 {{ client_name }}").unwrap();
+    assert_eq!(engine.render_client("client_actor", &context).unwrap(), "// v2 TaskClient");
+
+    // Templates not present on disk fall back to the embedded ones
+    assert!(engine.get_tera().get_template_names().any(|name| name == "clients/state_struct.swift.tera"));
+}
+
+#[test]
+fn test_dev_template_errors_include_path_and_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let clients_dir = temp_dir.path().join("clients");
+    std::fs::create_dir_all(&clients_dir).unwrap();
+    let template_path = clients_dir.join("client_actor.swift.tera");
+    std::fs::write(&template_path, "// ok").unwrap();
+
+    let engine = SwiftTemplateEngine::with_dev_template_dir(temp_dir.path()).unwrap();
+    std::fs::write(&template_path, "// line one\n{{ client_name ").unwrap();
+
+    let error = engine.render_client("client_actor", &Context::new()).unwrap_err().to_string();
+    assert!(error.contains(&template_path.display().to_string()), "missing path: {}", error);
+    assert!(error.contains("2:"), "missing line info: {}", error);
+}
+
+#[test]
+fn test_dev_template_dir_must_exist() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing");
+
+    assert!(SwiftTemplateEngine::with_dev_template_dir(&missing).is_err());
+}
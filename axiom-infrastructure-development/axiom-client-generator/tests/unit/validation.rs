@@ -61,6 +61,68 @@ public struct Task {
     assert!(!validation_result.errors.is_empty());
 }
 
+fn validation_fixture(filename: &str) -> String {
+    crate::fixtures::TestFixtures::new()
+        .validation_path(filename)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_strict_mode_keeps_warning_clean_fixture_valid() {
+    let validator = SwiftValidator::new();
+    let file = validation_fixture(crate::fixtures::fixtures::WARNING_CLEAN_SWIFT);
+
+    let mut result = validator.validate_files(&[file]).await.unwrap();
+    assert!(result.warnings.is_empty(), "unexpected warnings: {:?}", result.warnings);
+
+    let promoted = result.promote_warnings(None);
+    assert!(promoted.is_empty());
+    assert!(result.is_valid());
+}
+
+#[tokio::test]
+async fn test_strict_mode_promotes_warnings_to_errors() {
+    let validator = SwiftValidator::new();
+    let file = validation_fixture(crate::fixtures::fixtures::ACCESS_WARNING_SWIFT);
+
+    let mut result = validator.validate_files(&[file]).await.unwrap();
+    assert!(result.is_valid(), "fixture should only fail in strict mode: {:?}", result.errors);
+    assert_eq!(result.warnings.len(), 1);
+    assert_eq!(warning_category(&result.warnings[0]), "Access Control");
+
+    let promoted = result.promote_warnings(None);
+    assert_eq!(promoted.len(), 1);
+    assert!(!result.is_valid());
+    assert!(result.warnings.is_empty());
+    assert!(result.errors[0].starts_with("[strict] "));
+}
+
+#[tokio::test]
+async fn test_strict_categories_filter_promoted_warnings() {
+    let validator = SwiftValidator::new();
+    let file = validation_fixture(crate::fixtures::fixtures::ACCESS_WARNING_SWIFT);
+
+    let mut result = validator.validate_files(std::slice::from_ref(&file)).await.unwrap();
+    let promoted = result.promote_warnings(Some(&["naming_conventions".to_string()]));
+    assert!(promoted.is_empty());
+    assert!(result.is_valid());
+    assert_eq!(result.warnings.len(), 1);
+
+    let mut result = validator.validate_files(&[file]).await.unwrap();
+    let promoted = result.promote_warnings(Some(&["access-control".to_string()]));
+    assert_eq!(promoted.len(), 1);
+    assert!(!result.is_valid());
+}
+
+#[test]
+fn test_warning_category_names() {
+    assert!(is_warning_category("Access Control"));
+    assert!(is_warning_category("access_control"));
+    assert!(is_warning_category("BEST-PRACTICES"));
+    assert!(!is_warning_category("spelling"));
+}
+
 // Note: SwiftCompilationValidator not implemented yet
 // #[tokio::test]
 // async fn test_swift_compilation_validation() {